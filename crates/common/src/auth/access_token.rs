@@ -8,8 +8,8 @@ use super::AccessToken;
 use crate::{
     Server,
     auth::{
-        AccessScope, AccessTo, AccessTokenInner, AccountTenantIds, RECOVERY_ADMIN_ID, Permissions,
-        permissions::{BuildPermissions, PermissionsListBuilder},
+        AccessScope, AccessTo, AccessTokenInner, AccountTenantIds, Permissions, RECOVERY_ADMIN_ID,
+        permissions::{BuildPermissions, PermissionsListBuilder, ReadOnlyPermissions},
     },
     network::limiter::{ConcurrencyLimiter, LimiterResult},
 };
@@ -203,6 +203,7 @@ impl Server {
                     revision_account,
                     account_id,
                     tenant_id,
+                    impersonator_id: None,
                     member_of,
                     access_to: access_to.into_boxed_slice(),
                     scopes: []
@@ -245,6 +246,7 @@ impl Server {
                     revision_account,
                     account_id,
                     tenant_id,
+                    impersonator_id: None,
                     member_of: Default::default(),
                     access_to: Default::default(),
                     scopes: Box::new([AccessScope::new(permissions.finalize(), u32::MAX)]),
@@ -419,6 +421,32 @@ impl AccessToken {
         }
     }
 
+    pub fn impersonate(
+        inner: Arc<AccessTokenInner>,
+        impersonator_id: u32,
+        read_only: bool,
+        remote_ip: IpAddr,
+    ) -> trc::Result<Self> {
+        let mut inner = inner.as_ref().clone();
+        inner.impersonator_id = Some(impersonator_id);
+        if read_only {
+            inner.scopes = inner
+                .scopes
+                .iter()
+                .map(|scope| AccessScope {
+                    permissions: scope.permissions.read_only(),
+                    ..scope.clone()
+                })
+                .collect();
+        }
+
+        AccessToken {
+            scope_idx: 0,
+            inner: Arc::new(inner),
+        }
+        .assert_is_valid(remote_ip)
+    }
+
     pub fn state(&self) -> u32 {
         // Hash state
         let mut s = AHasher::default();
@@ -437,6 +465,11 @@ impl AccessToken {
         self.inner.tenant_id
     }
 
+    #[inline(always)]
+    pub fn impersonator_id(&self) -> Option<u32> {
+        self.inner.impersonator_id
+    }
+
     pub fn secondary_ids(&self) -> impl Iterator<Item = &u32> {
         self.inner
             .member_of
@@ -545,6 +578,7 @@ impl AccessToken {
                     scopes: scopes.into_boxed_slice(),
                     account_id: old_inner.account_id,
                     tenant_id: old_inner.tenant_id,
+                    impersonator_id: old_inner.impersonator_id,
                     member_of: old_inner.member_of.clone(),
                     access_to: old_inner.access_to.clone(),
                     concurrent_http_requests: old_inner.concurrent_http_requests.clone(),
@@ -736,6 +770,7 @@ impl AccessToken {
             inner: Arc::new(AccessTokenInner {
                 account_id,
                 tenant_id: Default::default(),
+                impersonator_id: Default::default(),
                 member_of: Default::default(),
                 access_to: Default::default(),
                 scopes: Box::new([AccessScope::new(permissions, u32::MAX)]),
@@ -780,6 +815,7 @@ impl AccessTokenInner {
         AccessTokenInner {
             account_id: RECOVERY_ADMIN_ID,
             tenant_id: Default::default(),
+            impersonator_id: Default::default(),
             member_of: Default::default(),
             access_to: Default::default(),
             scopes: Box::new([AccessScope::new(Permissions::all(), u32::MAX)]),
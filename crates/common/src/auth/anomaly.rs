@@ -0,0 +1,434 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    Server,
+    auth::{AccessToken, AuthRequest, RECOVERY_ADMIN_ID},
+    config::server::ServerProtocol,
+};
+use registry::{
+    schema::{
+        enums::{LoginAnomalySensitivity, LoginAnomalyType, NetworkListenerProtocol},
+        prelude::Task,
+        structs::{TaskLoginAnomaly, TaskStatus},
+    },
+    types::{EnumImpl, datetime::UTCDateTime},
+};
+use std::{net::IpAddr, time::Duration};
+use store::{
+    ValueKey,
+    rand::{self, Rng},
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder, now},
+};
+use trc::AddContext;
+use types::{collection::Collection, field::PrincipalField};
+
+const MAX_LOGIN_HISTORY: usize = 32;
+const LAST_LOGIN_UPDATE_INTERVAL: u64 = 15 * 60;
+const MAX_UPDATE_RETRIES: u32 = 5;
+
+// Countries, networks (ASNs) and protocols an account has logged in from,
+// most recently seen last. The last login is kept separately to detect
+// logins from two countries in a short period of time.
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Clone, Default, PartialEq, Eq,
+)]
+pub struct LoginHistory {
+    pub countries: Vec<String>,
+    pub networks: Vec<u32>,
+    pub clients: Vec<String>,
+    pub last_country: Option<String>,
+    pub last_login: u64,
+}
+
+impl Server {
+    // The history is updated in the background so that GeoIP lookups and
+    // store writes do not delay the login. Failures are logged rather than
+    // returned, a successful login is never rejected because its history
+    // could not be updated.
+    pub fn check_login_anomaly(&self, req: &AuthRequest, token: &AccessToken) {
+        let account_id = token.account_id();
+        if account_id == RECOVERY_ADMIN_ID || token.impersonator_id().is_some() {
+            return;
+        }
+
+        let server = self.clone();
+        let tenant_id = token.tenant_id();
+        let remote_ip = req.remote_ip;
+        let session_id = req.session_id;
+        let protocol = req.protocol;
+        tokio::spawn(async move {
+            if let Err(err) = server
+                .update_login_history(account_id, tenant_id, remote_ip, session_id, protocol)
+                .await
+            {
+                trc::error!(
+                    err.account_id(account_id)
+                        .span_id(session_id)
+                        .caused_by(trc::location!())
+                );
+            }
+        });
+    }
+
+    pub async fn update_login_history(
+        &self,
+        account_id: u32,
+        tenant_id: Option<u32>,
+        remote_ip: IpAddr,
+        session_id: u64,
+        protocol: ServerProtocol,
+    ) -> trc::Result<()> {
+        let sensitivity = self.login_anomaly_sensitivity(tenant_id).await?;
+        if sensitivity == LoginAnomalySensitivity::Disabled {
+            return Ok(());
+        }
+
+        let asn_geo = self.lookup_asn_country(remote_ip).await;
+        let country = asn_geo.country.as_ref().map(|c| c.to_uppercase());
+        let network = asn_geo.asn.as_ref().map(|asn| asn.id);
+        let client = protocol.as_str();
+        let login_at = now();
+        let mut try_count = 0;
+
+        // Concurrent logins of the same account race to update its history,
+        // the write is asserted against the version that was read and
+        // retried when another login got there first.
+        loop {
+            let current = self
+                .store()
+                .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                    account_id,
+                    Collection::Principal,
+                    0,
+                    PrincipalField::LoginHistory,
+                ))
+                .await
+                .caused_by(trc::location!())?;
+            let mut history = current
+                .as_ref()
+                .map(|history| history.deserialize::<LoginHistory>())
+                .transpose()
+                .caused_by(trc::location!())?;
+
+            // Logins are only compared against history once there is some,
+            // the very first login of an account just records where it came from.
+            let anomaly = history.as_ref().and_then(|history| {
+                history.detect_anomaly(
+                    country.as_deref(),
+                    network,
+                    client,
+                    login_at,
+                    sensitivity,
+                    self.core.network.security.login_travel_window,
+                )
+            });
+            let history = history.get_or_insert_default();
+            let previous_country = history.last_country.clone();
+            if !history.update(country.as_deref(), network, client, login_at) {
+                return Ok(());
+            }
+
+            let mut batch = BatchBuilder::new();
+            batch
+                .with_account_id(account_id)
+                .with_collection(Collection::Principal)
+                .with_document(0);
+            if let Some(current) = &current {
+                batch.assert_value(PrincipalField::LoginHistory, current);
+            } else {
+                batch.assert_value(PrincipalField::LoginHistory, ());
+            }
+            batch.set(
+                PrincipalField::LoginHistory,
+                Archiver::new(std::mem::take(history))
+                    .serialize()
+                    .caused_by(trc::location!())?,
+            );
+
+            if let Some(anomaly_type) = anomaly {
+                batch.schedule_task(Task::LoginAnomaly(TaskLoginAnomaly {
+                    account_id: account_id.into(),
+                    anomaly_type,
+                    remote_ip,
+                    country: country.clone(),
+                    previous_country: previous_country.clone(),
+                    protocol: match protocol {
+                        ServerProtocol::Smtp => NetworkListenerProtocol::Smtp,
+                        ServerProtocol::Lmtp => NetworkListenerProtocol::Lmtp,
+                        ServerProtocol::Imap => NetworkListenerProtocol::Imap,
+                        ServerProtocol::Pop3 => NetworkListenerProtocol::Pop3,
+                        ServerProtocol::Http => NetworkListenerProtocol::Http,
+                        ServerProtocol::ManageSieve => NetworkListenerProtocol::ManageSieve,
+                        ServerProtocol::AgentCheck => NetworkListenerProtocol::AgentCheck,
+                    },
+                    login_at: UTCDateTime::from_timestamp(login_at as i64),
+                    status: TaskStatus::now(),
+                }));
+            }
+
+            match self.store().write(batch.build_all()).await {
+                Ok(_) => {
+                    if let Some(anomaly_type) = anomaly {
+                        trc::event!(
+                            Security(trc::SecurityEvent::LoginAnomaly),
+                            AccountId = account_id,
+                            RemoteIp = remote_ip,
+                            Type = anomaly_type.as_str(),
+                            Details = country,
+                            Reason = previous_country,
+                            SpanId = session_id,
+                        );
+
+                        self.notify_task_queue();
+                    }
+
+                    return Ok(());
+                }
+                Err(err) if err.is_assertion_failure() && try_count < MAX_UPDATE_RETRIES => {
+                    let backoff = rand::rng().random_range(10..=100);
+                    tokio::time::sleep(Duration::from_millis(backoff)).await;
+                    try_count += 1;
+                }
+                Err(err) => return Err(err.caused_by(trc::location!())),
+            }
+        }
+    }
+
+    pub async fn login_history(&self, account_id: u32) -> trc::Result<Option<LoginHistory>> {
+        self.store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Principal,
+                0,
+                PrincipalField::LoginHistory,
+            ))
+            .await
+            .caused_by(trc::location!())?
+            .map(|history| history.deserialize::<LoginHistory>())
+            .transpose()
+            .caused_by(trc::location!())
+    }
+
+    pub async fn login_anomaly_sensitivity(
+        &self,
+        tenant_id: Option<u32>,
+    ) -> trc::Result<LoginAnomalySensitivity> {
+        if let Some(tenant_id) = tenant_id
+            && let Some(sensitivity) = self
+                .tenant(tenant_id)
+                .await
+                .caused_by(trc::location!())?
+                .login_anomaly_sensitivity
+        {
+            Ok(sensitivity)
+        } else {
+            Ok(self.core.network.security.login_anomaly_sensitivity)
+        }
+    }
+}
+
+impl LoginHistory {
+    pub fn detect_anomaly(
+        &self,
+        country: Option<&str>,
+        network: Option<u32>,
+        client: &str,
+        login_at: u64,
+        sensitivity: LoginAnomalySensitivity,
+        travel_window: u64,
+    ) -> Option<LoginAnomalyType> {
+        if let Some(country) = country {
+            if self
+                .last_country
+                .as_deref()
+                .is_some_and(|last_country| last_country != country)
+                && login_at.saturating_sub(self.last_login) < travel_window
+            {
+                return Some(LoginAnomalyType::ImpossibleTravel);
+            } else if matches!(
+                sensitivity,
+                LoginAnomalySensitivity::Medium | LoginAnomalySensitivity::High
+            ) && !self.countries.iter().any(|c| c == country)
+            {
+                return Some(LoginAnomalyType::NewCountry);
+            }
+        }
+
+        if sensitivity == LoginAnomalySensitivity::High {
+            if network.is_some_and(|network| !self.networks.contains(&network)) {
+                return Some(LoginAnomalyType::NewNetwork);
+            } else if !self.clients.iter().any(|c| c == client) {
+                return Some(LoginAnomalyType::NewClient);
+            }
+        }
+
+        None
+    }
+
+    // Records a login, returning whether the history needs to be written.
+    // The write is skipped when the login came from a known country, network
+    // and client shortly after the previous one.
+    pub fn update(
+        &mut self,
+        country: Option<&str>,
+        network: Option<u32>,
+        client: &str,
+        login_at: u64,
+    ) -> bool {
+        let mut has_changes = country
+            .is_some_and(|country| self.last_country.as_deref() != Some(country))
+            || login_at.saturating_sub(self.last_login) >= LAST_LOGIN_UPDATE_INTERVAL;
+        if let Some(country) = country {
+            has_changes |= touch(&mut self.countries, country.to_string());
+        }
+        if let Some(network) = network {
+            has_changes |= touch(&mut self.networks, network);
+        }
+        has_changes |= touch(&mut self.clients, client.to_string());
+
+        if has_changes {
+            if let Some(country) = country {
+                self.last_country = Some(country.to_string());
+            }
+            self.last_login = login_at;
+        }
+
+        has_changes
+    }
+}
+
+// Moves the value to the end of the list, evicting the least recently seen
+// entry when full. Returns whether the value was not in the list.
+fn touch<T: PartialEq>(list: &mut Vec<T>, value: T) -> bool {
+    if let Some(pos) = list.iter().position(|v| *v == value) {
+        let value = list.remove(pos);
+        list.push(value);
+        false
+    } else {
+        if list.len() >= MAX_LOGIN_HISTORY {
+            list.remove(0);
+        }
+        list.push(value);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LoginHistory, MAX_LOGIN_HISTORY, touch};
+    use registry::schema::enums::{LoginAnomalySensitivity, LoginAnomalyType};
+
+    const WINDOW: u64 = 3600;
+
+    #[test]
+    fn detect_anomaly() {
+        let mut history = LoginHistory::default();
+        assert!(history.update(Some("ES"), Some(1), "imap", 1000));
+
+        for (country, network, client, login_at, sensitivity, expected) in [
+            (
+                Some("ES"),
+                Some(1),
+                "imap",
+                1500,
+                LoginAnomalySensitivity::High,
+                None,
+            ),
+            (
+                Some("FR"),
+                Some(1),
+                "imap",
+                1500,
+                LoginAnomalySensitivity::Low,
+                Some(LoginAnomalyType::ImpossibleTravel),
+            ),
+            (
+                Some("FR"),
+                Some(1),
+                "imap",
+                1000 + WINDOW,
+                LoginAnomalySensitivity::Low,
+                None,
+            ),
+            (
+                Some("FR"),
+                Some(1),
+                "imap",
+                1000 + WINDOW,
+                LoginAnomalySensitivity::Medium,
+                Some(LoginAnomalyType::NewCountry),
+            ),
+            (
+                Some("ES"),
+                Some(2),
+                "imap",
+                1500,
+                LoginAnomalySensitivity::Medium,
+                None,
+            ),
+            (
+                Some("ES"),
+                Some(2),
+                "imap",
+                1500,
+                LoginAnomalySensitivity::High,
+                Some(LoginAnomalyType::NewNetwork),
+            ),
+            (
+                None,
+                None,
+                "pop3",
+                1500,
+                LoginAnomalySensitivity::High,
+                Some(LoginAnomalyType::NewClient),
+            ),
+        ] {
+            assert_eq!(
+                history.detect_anomaly(country, network, client, login_at, sensitivity, WINDOW),
+                expected,
+                "{country:?} {network:?} {client} {login_at} {sensitivity:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn update_history() {
+        let mut history = LoginHistory::default();
+        assert!(history.update(Some("ES"), Some(1), "imap", 1000));
+        assert_eq!(history.last_country.as_deref(), Some("ES"));
+        assert_eq!(history.last_login, 1000);
+
+        // Known logins shortly after the previous one are not written
+        assert!(!history.update(Some("ES"), Some(1), "imap", 1010));
+        assert!(!history.update(None, None, "imap", 1020));
+        assert_eq!(history.last_login, 1000);
+
+        // New clients and countries are recorded, unknown countries keep the last one
+        assert!(history.update(None, None, "pop3", 1030));
+        assert_eq!(history.last_country.as_deref(), Some("ES"));
+        assert!(history.update(Some("FR"), Some(2), "imap", 1040));
+        assert_eq!(history.countries, ["ES", "FR"]);
+        assert_eq!(history.networks, [1, 2]);
+        assert_eq!(history.clients, ["pop3", "imap"]);
+        assert_eq!(history.last_country.as_deref(), Some("FR"));
+
+        // Periodic updates refresh the last login
+        assert!(history.update(Some("FR"), Some(2), "imap", 1040 + 15 * 60));
+        assert_eq!(history.last_login, 1040 + 15 * 60);
+    }
+
+    #[test]
+    fn touch_evicts_least_recent() {
+        let mut list = (0..MAX_LOGIN_HISTORY).collect::<Vec<_>>();
+        assert!(!touch(&mut list, 0));
+        assert_eq!(list.last(), Some(&0));
+        assert!(touch(&mut list, MAX_LOGIN_HISTORY));
+        assert_eq!(list.len(), MAX_LOGIN_HISTORY);
+        assert_eq!(list.first(), Some(&2));
+        assert_eq!(list.last(), Some(&MAX_LOGIN_HISTORY));
+    }
+}
@@ -11,6 +11,8 @@ use crate::{
         credential::{ApiKey, AppPassword},
         oauth::GrantType,
     },
+    config::server::ServerProtocol,
+    telemetry::metrics::breakdown::BreakdownMetric,
 };
 use directory::{
     Credentials, Directory,
@@ -42,7 +44,10 @@ impl Server {
             .await
             .and_then(|token| token.assert_has_permission(Permission::Authenticate))
         {
-            Ok(token) => Ok(token),
+            Ok(token) => {
+                self.check_login_anomaly(req, &token);
+                Ok(token)
+            }
             Err(err) => {
                 // Random delay to mitigate user enumeration attacks
                 #[cfg(not(feature = "test_mode"))]
@@ -53,6 +58,15 @@ impl Server {
                     tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
                 }
 
+                if matches!(err.as_ref(), trc::EventType::Auth(trc::AuthEvent::Failed))
+                    && let Some((_, domain)) = req
+                        .username()
+                        .and_then(|username| username.rsplit_once('@'))
+                {
+                    self.record_metrics_breakdown(BreakdownMetric::AuthFailures, domain)
+                        .await;
+                }
+
                 if matches!(
                     err.as_ref(),
                     trc::EventType::Auth(trc::AuthEvent::Failed)
@@ -324,12 +338,20 @@ impl Server {
                 }
 
                 // Internal OAuth
-                let token_info = self
-                    .validate_access_token(GrantType::AccessToken.into(), token)
-                    .await?;
-                self.access_token(token_info.account_id)
-                    .await
-                    .and_then(|token| AccessToken::new(token, req.remote_ip))
+                let token_info = self.validate_access_token(None, token).await?;
+                match token_info.grant_type {
+                    GrantType::AccessToken => self
+                        .access_token(token_info.account_id)
+                        .await
+                        .and_then(|token| AccessToken::new(token, req.remote_ip)),
+                    GrantType::Impersonation => {
+                        self.impersonation_access_token(&token_info, req.remote_ip)
+                            .await
+                    }
+                    _ => Err(trc::AuthEvent::Error
+                        .into_err()
+                        .details("Invalid grant type")),
+                }
             }
         }
     }
@@ -435,7 +457,7 @@ impl Server {
         }
     }
 
-    fn add_missing_domain(&self, address: &mut Username) {
+    pub(crate) fn add_missing_domain(&self, address: &mut Username) {
         if address.domain().is_none() {
             trc::event!(
                 Auth(trc::AuthEvent::Warning),
@@ -559,11 +581,17 @@ impl Username {
 }
 
 impl AuthRequest {
-    pub fn from_credentials(credentials: Credentials, session_id: u64, remote_ip: IpAddr) -> Self {
+    pub fn from_credentials(
+        credentials: Credentials,
+        session_id: u64,
+        remote_ip: IpAddr,
+        protocol: ServerProtocol,
+    ) -> Self {
         Self {
             credentials,
             session_id,
             remote_ip,
+            protocol,
         }
     }
 
@@ -572,6 +600,7 @@ impl AuthRequest {
         pass: impl Into<String>,
         session_id: u64,
         remote_ip: IpAddr,
+        protocol: ServerProtocol,
     ) -> Self {
         Self::from_credentials(
             Credentials::Basic {
@@ -581,6 +610,7 @@ impl AuthRequest {
             },
             session_id,
             remote_ip,
+            protocol,
         )
     }
 
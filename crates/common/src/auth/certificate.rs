@@ -0,0 +1,105 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    Server,
+    auth::{AccessToken, authentication::Username},
+};
+use registry::schema::enums::{Permission, TlsClientCertField};
+use std::net::IpAddr;
+use x509_parser::{
+    parse_x509_certificate,
+    prelude::{GeneralName, ParsedExtension, X509Certificate},
+};
+
+impl Server {
+    // Maps a verified client certificate to an account, the configured
+    // certificate fields are tried in order until an account is found.
+    pub async fn authenticate_certificate(
+        &self,
+        certificate: &[u8],
+        principal: &[TlsClientCertField],
+        session_id: u64,
+        remote_ip: IpAddr,
+    ) -> trc::Result<AccessToken> {
+        let (_, cert) = parse_x509_certificate(certificate).map_err(|err| {
+            trc::AuthEvent::Error
+                .into_err()
+                .ctx(trc::Key::SpanId, session_id)
+                .ctx(trc::Key::RemoteIp, remote_ip)
+                .reason(err)
+                .details("Failed to parse client certificate")
+        })?;
+
+        for field in principal {
+            for name in certificate_names(&cert, *field) {
+                let mut username = Username {
+                    domain_start: name.rfind('@').map_or(usize::MAX, |pos| pos + 1),
+                    name,
+                };
+                self.add_missing_domain(&mut username);
+
+                if let Some(account_id) = self
+                    .account_id_from_email(username.address(), false)
+                    .await?
+                {
+                    let token = self
+                        .access_token(account_id)
+                        .await
+                        .and_then(|token| AccessToken::new(token, remote_ip))
+                        .and_then(|token| token.assert_has_permission(Permission::Authenticate))?;
+
+                    trc::event!(
+                        Auth(trc::AuthEvent::Success),
+                        AccountName = username.address().to_string(),
+                        AccountId = account_id,
+                        SpanId = session_id,
+                        Details = "Authenticated with client certificate",
+                    );
+
+                    return Ok(token);
+                }
+            }
+        }
+
+        Err(trc::AuthEvent::Failed
+            .into_err()
+            .ctx(trc::Key::SpanId, session_id)
+            .ctx(trc::Key::RemoteIp, remote_ip)
+            .details(cert.subject().to_string())
+            .reason("No account found for client certificate"))
+    }
+}
+
+fn certificate_names(cert: &X509Certificate<'_>, field: TlsClientCertField) -> Vec<String> {
+    match field {
+        TlsClientCertField::SanEmail => cert
+            .extensions()
+            .iter()
+            .filter_map(|ext| match ext.parsed_extension() {
+                ParsedExtension::SubjectAlternativeName(san) => Some(san),
+                _ => None,
+            })
+            .flat_map(|san| san.general_names.iter())
+            .filter_map(|name| match name {
+                GeneralName::RFC822Name(email) => Some(email.to_lowercase()),
+                _ => None,
+            })
+            .collect(),
+        TlsClientCertField::SubjectEmail => cert
+            .subject()
+            .iter_email()
+            .filter_map(|attr| attr.as_str().ok())
+            .map(|email| email.to_lowercase())
+            .collect(),
+        TlsClientCertField::SubjectCommonName => cert
+            .subject()
+            .iter_common_name()
+            .filter_map(|attr| attr.as_str().ok())
+            .map(|name| name.to_lowercase())
+            .collect(),
+    }
+}
@@ -0,0 +1,112 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    Server,
+    auth::{
+        AccessToken, AccessTokenInner,
+        oauth::{GrantType, token::TokenInfo},
+        permissions::ReadOnlyPermissions,
+    },
+};
+use registry::schema::enums::Permission;
+use std::net::IpAddr;
+
+pub const IMPERSONATION_MAX_EXPIRY: u64 = 3600;
+
+impl Server {
+    pub async fn encode_impersonation_token(
+        &self,
+        impersonator: &AccessToken,
+        account_id: u32,
+        read_only: bool,
+        expiry_in: u64,
+    ) -> trc::Result<String> {
+        impersonator.assert_can_impersonate(&*self.access_token(account_id).await?, read_only)?;
+
+        // The impersonator and the access mode are carried in the client id,
+        // which is part of the token's encryption context
+        let client_id = if read_only {
+            format!("{}:ro", impersonator.account_id())
+        } else {
+            impersonator.account_id().to_string()
+        };
+
+        self.encode_access_token(
+            GrantType::Impersonation,
+            account_id,
+            &client_id,
+            expiry_in.min(IMPERSONATION_MAX_EXPIRY),
+        )
+        .await
+    }
+
+    pub async fn impersonation_access_token(
+        &self,
+        token_info: &TokenInfo,
+        remote_ip: IpAddr,
+    ) -> trc::Result<AccessToken> {
+        let (impersonator_id, read_only) = match token_info.client_id.strip_suffix(":ro") {
+            Some(impersonator_id) => (impersonator_id, true),
+            None => (token_info.client_id.as_str(), false),
+        };
+        let impersonator_id = impersonator_id.parse::<u32>().map_err(|_| {
+            trc::AuthEvent::Error
+                .into_err()
+                .details("Invalid impersonation token")
+        })?;
+
+        // Outstanding tokens stop working as soon as the impersonator loses the permission
+        let impersonator = self
+            .access_token(impersonator_id)
+            .await
+            .and_then(|token| AccessToken::new(token, remote_ip))?
+            .assert_has_permission(Permission::Impersonate)?;
+        let account = self.access_token(token_info.account_id).await?;
+        if impersonator
+            .tenant_id()
+            .is_some_and(|tenant_id| account.tenant_id != Some(tenant_id))
+        {
+            return Err(trc::SecurityEvent::Unauthorized
+                .into_err()
+                .account_id(impersonator_id)
+                .details("Account belongs to a different tenant"));
+        }
+
+        impersonator.assert_can_impersonate(&account, read_only)?;
+
+        AccessToken::impersonate(account, impersonator_id, read_only, remote_ip)
+    }
+}
+
+impl AccessToken {
+    pub fn assert_can_impersonate(
+        &self,
+        account: &AccessTokenInner,
+        read_only: bool,
+    ) -> trc::Result<()> {
+        // Impersonation cannot grant permissions the impersonator does not hold
+        let permissions = self.access_scope().map(|scope| &scope.permissions);
+        for scope in account.scopes.iter() {
+            let mut missing = if read_only {
+                scope.permissions.read_only()
+            } else {
+                scope.permissions.clone()
+            };
+            if let Some(permissions) = permissions {
+                missing.difference(permissions);
+            }
+            if !missing.is_empty() {
+                return Err(trc::SecurityEvent::Unauthorized
+                    .into_err()
+                    .account_id(self.account_id())
+                    .details("Account has permissions the impersonator lacks"));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -5,20 +5,25 @@
  */
 
 use crate::{
+    config::server::ServerProtocol,
     expr::if_block::IfBlock,
-    network::limiter::ConcurrencyLimiter,
+    network::{disclaimer::DomainDisclaimer, limiter::ConcurrencyLimiter},
     storage::{ObjectQuota, TenantQuota},
 };
 use directory::Credentials;
 use quick_cache::Equivalent;
 use registry::{
-    schema::enums::{Locale, Permission},
+    schema::{
+        enums::{EncryptionPolicy, Locale, LoginAnomalySensitivity, Permission},
+        structs::RetentionPolicy,
+    },
     types::{EnumImpl, ipmask::IpAddrOrMask},
 };
 use std::{
     hash::{Hash, Hasher},
     net::IpAddr,
     sync::Arc,
+    time::Duration,
 };
 use tinyvec::TinyVec;
 use trc::ipc::bitset::Bitset;
@@ -26,8 +31,11 @@ use types::collection::Collection;
 use utils::{cache::CacheItemWeight, map::bitmap::Bitmap};
 
 pub mod access_token;
+pub mod anomaly;
 pub mod authentication;
+pub mod certificate;
 pub mod credential;
+pub mod impersonation;
 pub mod oauth;
 pub mod permissions;
 pub mod rate_limit;
@@ -62,11 +70,29 @@ pub struct DomainCache {
     pub id_tenant: Option<u32>,
     pub catch_all: Option<Box<str>>,
     pub sub_addressing_custom: Option<Box<IfBlock>>,
+    pub disclaimer: Option<Box<DomainDisclaimer>>,
+    pub relay_verify: Option<Arc<RelayVerify>>,
     pub flags: u8,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RelayVerify {
+    Smtp {
+        host: Option<Box<str>>,
+        cache_ttl: Duration,
+    },
+    Http {
+        url: Box<str>,
+        cache_ttl: Duration,
+    },
+}
+
 pub const DOMAIN_FLAG_RELAY: u8 = 1;
 pub const DOMAIN_FLAG_SUB_ADDRESSING: u8 = 1 << 1;
+pub const DOMAIN_FLAG_SUB_ADDRESSING_FOLDERS: u8 = 1 << 2;
+pub const DOMAIN_FLAG_SUB_ADDRESSING_CREATE: u8 = 1 << 3;
+pub const DOMAIN_FLAG_ENCRYPT_REQUIRED: u8 = 1 << 4;
+pub const DOMAIN_FLAG_ENCRYPT_DISABLED: u8 = 1 << 5;
 
 #[derive(Debug, Clone, Default)]
 pub struct AccountCache {
@@ -81,6 +107,7 @@ pub struct AccountCache {
     pub encryption_key: Option<EncryptionKeys>,
     pub locale: Locale,
     pub flags: u64,
+    pub disabled_jmap_capabilities: u32,
 }
 
 pub type EncryptionKeys = Box<[Box<[u8]>]>;
@@ -92,6 +119,14 @@ pub const ACCOUNT_FLAG_ENCRYPT_METHOD_PGP: u64 = 1 << 3;
 pub const ACCOUNT_FLAG_ENCRYPT_ALGO_AES256: u64 = 1 << 4;
 pub const ACCOUNT_FLAG_ENCRYPT_ALGO_AES128: u64 = 1 << 5;
 pub const ACCOUNT_FLAG_ENCRYPT_APPEND: u64 = 1 << 6;
+pub const ACCOUNT_FLAG_RESOURCE_ROOM: u64 = 1 << 7;
+pub const ACCOUNT_FLAG_RESOURCE_EQUIPMENT: u64 = 1 << 8;
+pub const ACCOUNT_FLAG_RESOURCE_ACCEPT_ALWAYS: u64 = 1 << 9;
+pub const ACCOUNT_FLAG_RESOURCE_DECLINE_ALWAYS: u64 = 1 << 10;
+pub const ACCOUNT_FLAG_RESOURCE_MANUAL: u64 = 1 << 11;
+pub const ACCOUNT_FLAG_CALENDAR_AUTO_IMPORT: u64 = 1 << 12;
+pub const ACCOUNT_FLAG_LEGAL_HOLD: u64 = 1 << 13;
+pub const ACCOUNT_FLAG_PUBLIC_FOLDERS: u64 = 1 << 14;
 
 #[derive(Debug, Clone)]
 pub struct RoleCache {
@@ -102,6 +137,7 @@ pub struct RoleCache {
 #[derive(Debug, Clone)]
 pub struct MailingListCache {
     pub recipients: Arc<[Box<str>]>,
+    pub allow_unsubscribe: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -110,6 +146,11 @@ pub struct TenantCache {
     pub quota_disk: u64,
     pub quota_objects: Option<Box<TenantQuota>>,
     pub permissions: Option<Box<PermissionsGroup>>,
+    pub index_attachment_contents: bool,
+    pub retention_policies: Arc<[RetentionPolicy]>,
+    pub disabled_jmap_capabilities: u32,
+    pub encryption_policy: EncryptionPolicy,
+    pub login_anomaly_sensitivity: Option<LoginAnomalySensitivity>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -129,6 +170,7 @@ pub struct AccessToken {
 pub struct AccessTokenInner {
     pub(crate) account_id: u32,
     pub(crate) tenant_id: Option<u32>,
+    pub(crate) impersonator_id: Option<u32>,
     pub(crate) member_of: TinyVec<[u32; 3]>,
     pub(crate) access_to: Box<[AccessTo]>,
     pub(crate) scopes: Box<[AccessScope]>,
@@ -171,6 +213,7 @@ pub struct AuthRequest {
     pub credentials: Credentials,
     pub session_id: u64,
     pub remote_ip: IpAddr,
+    pub protocol: ServerProtocol,
 }
 
 impl CacheItemWeight for AccessTokenInner {
@@ -200,6 +243,26 @@ impl CacheItemWeight for DomainCache {
                 .sub_addressing_custom
                 .as_ref()
                 .map_or(0, |s| s.weight())
+            + self.disclaimer.as_ref().map_or(0, |d| d.weight())
+            + self.relay_verify.as_ref().map_or(0, |v| v.weight())
+    }
+}
+
+impl CacheItemWeight for RelayVerify {
+    fn weight(&self) -> u64 {
+        std::mem::size_of::<RelayVerify>() as u64
+            + match self {
+                RelayVerify::Smtp { host, .. } => host.as_ref().map_or(0, |h| h.len() as u64),
+                RelayVerify::Http { url, .. } => url.len() as u64,
+            }
+    }
+}
+
+impl RelayVerify {
+    pub fn cache_ttl(&self) -> Duration {
+        match self {
+            RelayVerify::Smtp { cache_ttl, .. } | RelayVerify::Http { cache_ttl, .. } => *cache_ttl,
+        }
     }
 }
 
@@ -253,6 +316,7 @@ impl CacheItemWeight for TenantCache {
     fn weight(&self) -> u64 {
         std::mem::size_of::<TenantCache>() as u64
             + self.permissions.as_ref().map_or(0, |p| p.weight())
+            + (self.retention_policies.len() * std::mem::size_of::<RetentionPolicy>()) as u64
     }
 }
 
@@ -26,6 +26,10 @@ pub enum GrantType {
     LiveMetrics,
     LiveDelivery,
     Rsvp,
+    ShareLink,
+    ForwardConfirm,
+    ListUnsubscribe,
+    Impersonation,
 }
 
 impl GrantType {
@@ -37,6 +41,10 @@ impl GrantType {
             GrantType::LiveMetrics => "live_metrics",
             GrantType::LiveDelivery => "live_delivery",
             GrantType::Rsvp => "rsvp",
+            GrantType::ShareLink => "share_link",
+            GrantType::ForwardConfirm => "forward_confirm",
+            GrantType::ListUnsubscribe => "list_unsubscribe",
+            GrantType::Impersonation => "impersonation",
         }
     }
 
@@ -48,6 +56,10 @@ impl GrantType {
             GrantType::LiveMetrics => 3,
             GrantType::LiveDelivery => 4,
             GrantType::Rsvp => 5,
+            GrantType::ShareLink => 6,
+            GrantType::ForwardConfirm => 8,
+            GrantType::ListUnsubscribe => 9,
+            GrantType::Impersonation => 7,
         }
     }
 
@@ -59,6 +71,10 @@ impl GrantType {
             3 => Some(GrantType::LiveMetrics),
             4 => Some(GrantType::LiveDelivery),
             5 => Some(GrantType::Rsvp),
+            6 => Some(GrantType::ShareLink),
+            7 => Some(GrantType::Impersonation),
+            8 => Some(GrantType::ForwardConfirm),
+            9 => Some(GrantType::ListUnsubscribe),
             _ => None,
         }
     }
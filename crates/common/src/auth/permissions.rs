@@ -276,6 +276,7 @@ impl Default for DefaultPermissions {
                         || name.starts_with("sysArchivedItem")
                         || name.starts_with("sysAccountSettings")
                         || name.starts_with("sysPublicKey")
+                        || name.starts_with("sysShareLink")
                         || (name.starts_with("sysSpamTrainingSample") && !name.contains("Create"))
                     {
                         default.user.push(permission);
@@ -297,6 +298,7 @@ impl Default for DefaultPermissions {
                         || name.starts_with("sysExternalReport")
                         || name.starts_with("sysDnsServer")
                         || name.starts_with("sysQueuedMessage")
+                        || name.starts_with("sysSuppressedAddress")
                     {
                         default.tenant.push(permission);
                         default.superuser.push(permission);
@@ -354,3 +356,84 @@ impl BuildPermissions for Permissions {
         permission
     }
 }
+
+pub trait ReadOnlyPermissions {
+    fn read_only(&self) -> Permissions;
+}
+
+impl ReadOnlyPermissions for Permissions {
+    fn read_only(&self) -> Permissions {
+        let mut permissions = Permissions::default();
+        for permission in self.build_permissions_list() {
+            if is_read_only(permission) {
+                permissions.set(permission as usize);
+            }
+        }
+        permissions
+    }
+}
+
+fn is_read_only(permission: Permission) -> bool {
+    match permission {
+        Permission::Authenticate
+        | Permission::AuthenticateWithAlias
+        | Permission::ImapAuthenticate
+        | Permission::ImapAclGet
+        | Permission::ImapMyRights
+        | Permission::ImapListRights
+        | Permission::ImapCapability
+        | Permission::ImapId
+        | Permission::ImapEnable
+        | Permission::ImapExamine
+        | Permission::ImapIdle
+        | Permission::ImapList
+        | Permission::ImapLsub
+        | Permission::ImapNamespace
+        | Permission::ImapSearch
+        | Permission::ImapSort
+        | Permission::ImapStatus
+        | Permission::ImapThread
+        | Permission::Pop3Authenticate
+        | Permission::Pop3List
+        | Permission::Pop3Uidl
+        | Permission::Pop3Stat
+        | Permission::Pop3Retr
+        | Permission::SieveAuthenticate
+        | Permission::SieveListScripts
+        | Permission::SieveGetScript
+        | Permission::SieveCheckScript
+        | Permission::SieveHaveSpace
+        | Permission::DavSyncCollection
+        | Permission::DavExpandProperty
+        | Permission::DavPrincipalAcl
+        | Permission::DavPrincipalList
+        | Permission::DavPrincipalMatch
+        | Permission::DavPrincipalSearch
+        | Permission::DavPrincipalSearchPropSet
+        | Permission::DavFilePropFind
+        | Permission::DavFileGet
+        | Permission::DavCardPropFind
+        | Permission::DavCardGet
+        | Permission::DavCardQuery
+        | Permission::DavCardMultiGet
+        | Permission::DavCalPropFind
+        | Permission::DavCalGet
+        | Permission::DavCalQuery
+        | Permission::DavCalMultiGet
+        | Permission::DavCalFreeBusyQuery
+        | Permission::JmapSearchSnippetGet
+        | Permission::JmapEmailParse
+        | Permission::JmapContactCardParse
+        | Permission::JmapCalendarEventParse
+        | Permission::JmapSieveScriptValidate
+        | Permission::JmapPrincipalGetAvailability
+        | Permission::JmapBlobLookup
+        | Permission::JmapCoreEcho => true,
+        permission => {
+            let name = permission.as_str();
+            (name.starts_with("jmap") || name.starts_with("sys"))
+                && (name.ends_with("Get") || name.ends_with("Query") || name.ends_with("Changes"))
+                && !name.starts_with("sysAccountPassword")
+        }
+    }
+}
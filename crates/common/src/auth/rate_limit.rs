@@ -7,9 +7,11 @@
 use crate::auth::AccessToken;
 use crate::network::ip_to_bytes;
 use crate::network::limiter::{InFlight, LimiterResult};
+use crate::network::scheduler::{SchedulerLimits, SchedulerPermit, SchedulerResult};
 use crate::{KV_RATE_LIMIT_HTTP_ANONYMOUS, KV_RATE_LIMIT_HTTP_AUTHENTICATED, Server};
-use registry::schema::enums::Permission;
-use std::net::IpAddr;
+use registry::schema::{enums::Permission, structs::Rate};
+use std::{net::IpAddr, time::Duration};
+use store::write::now;
 use trc::AddContext;
 
 impl Server {
@@ -21,17 +23,12 @@ impl Server {
         let is_rate_allowed = if let Some(rate) = &self.core.network.http.rate_authenticated {
             self.is_ip_allowed(addr)
                 || self
-                    .core
-                    .storage
-                    .memory
                     .is_rate_allowed(
                         KV_RATE_LIMIT_HTTP_AUTHENTICATED,
                         &access_token.account_id().to_be_bytes(),
                         rate,
-                        false,
                     )
-                    .await
-                    .caused_by(trc::location!())?
+                    .await?
                     .is_none()
         } else {
             true
@@ -60,17 +57,8 @@ impl Server {
         if let Some(rate) = &self.core.network.http.rate_anonymous
             && !self.is_ip_allowed(addr)
             && self
-                .core
-                .storage
-                .memory
-                .is_rate_allowed(
-                    KV_RATE_LIMIT_HTTP_ANONYMOUS,
-                    &ip_to_bytes(&addr),
-                    rate,
-                    false,
-                )
-                .await
-                .caused_by(trc::location!())?
+                .is_rate_allowed(KV_RATE_LIMIT_HTTP_ANONYMOUS, &ip_to_bytes(&addr), rate)
+                .await?
                 .is_some()
         {
             return Err(trc::LimitEvent::TooManyRequests.into_err());
@@ -78,6 +66,73 @@ impl Server {
         Ok(())
     }
 
+    // Counters live in the shared in-memory store so that limits apply across
+    // the cluster, keys over their limit are cached locally until the window ends.
+    pub async fn is_rate_allowed(
+        &self,
+        prefix: u8,
+        key: &[u8],
+        rate: &Rate,
+    ) -> trc::Result<Option<u64>> {
+        let mut cache_key = Vec::with_capacity(key.len() + 1);
+        cache_key.push(prefix);
+        cache_key.extend_from_slice(key);
+
+        if let Some(window_end) = self.inner.cache.rate_limited.get(cache_key.as_slice()) {
+            return Ok(Some(window_end.saturating_sub(now())));
+        }
+
+        let result = self
+            .core
+            .storage
+            .memory
+            .is_rate_allowed(prefix, key, rate, false)
+            .await
+            .caused_by(trc::location!())?;
+
+        if let Some(expires_in) = result.filter(|expires_in| *expires_in > 0) {
+            self.inner.cache.rate_limited.insert(
+                cache_key.into_boxed_slice(),
+                now() + expires_in,
+                Duration::from_secs(expires_in),
+            );
+        }
+
+        Ok(result)
+    }
+
+    pub async fn is_jmap_call_allowed(
+        &self,
+        access_token: &AccessToken,
+    ) -> trc::Result<Option<SchedulerPermit>> {
+        let Some(max_in_flight) = self.core.jmap.call_max_concurrent else {
+            return Ok(None);
+        };
+        if access_token.has_permission(Permission::UnlimitedRequests) {
+            return Ok(None);
+        }
+
+        match self
+            .inner
+            .data
+            .request_scheduler
+            .acquire(
+                access_token.account_id(),
+                SchedulerLimits {
+                    max_in_flight: max_in_flight as usize,
+                    max_queued: self.core.jmap.call_max_queued,
+                    max_wait: self.core.jmap.call_max_wait,
+                },
+            )
+            .await
+        {
+            SchedulerResult::Allowed(permit) => Ok(Some(permit)),
+            SchedulerResult::Rejected { retry_after } => Err(trc::LimitEvent::TooManyRequests
+                .into_err()
+                .ctx(trc::Key::Expires, retry_after.as_secs())),
+        }
+    }
+
     pub fn is_upload_allowed(&self, access_token: &AccessToken) -> trc::Result<Option<InFlight>> {
         match access_token.is_upload_allowed() {
             LimiterResult::Allowed(in_flight) => Ok(Some(in_flight)),
@@ -74,6 +74,14 @@ impl Server {
                     updated_account.description = account.description;
                     has_changes = true;
                 }
+                if account.proxy_backend != updated_account.proxy_backend {
+                    updated_account.proxy_backend = account.proxy_backend;
+                    has_changes = true;
+                }
+                if account.login_referral != updated_account.login_referral {
+                    updated_account.login_referral = account.login_referral;
+                    has_changes = true;
+                }
                 for alias in account.email_aliases {
                     if let Some((local, alias_domain)) = self.validate_alias(&alias).await?
                         && alias_domain.id_tenant == domain.id_tenant
@@ -181,6 +189,8 @@ impl Server {
                     aliases: aliases.into(),
                     created_at: UTCDateTime::now(),
                     description: account.description,
+                    proxy_backend: account.proxy_backend,
+                    login_referral: account.login_referral,
                     member_group_ids: member_group_ids.into(),
                     member_tenant_id: domain.id_tenant.map(Id::from),
                     roles: UserRoles::User,
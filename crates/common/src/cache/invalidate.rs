@@ -17,7 +17,7 @@ use registry::{
     },
     types::id::ObjectId,
 };
-use store::{registry::RegistryQuery, roaring::RoaringBitmap};
+use store::{dispatch::cache::ReadCacheEviction, registry::RegistryQuery, roaring::RoaringBitmap};
 use types::id::Id;
 
 #[derive(Debug, Default)]
@@ -111,6 +111,17 @@ impl CacheInvalidationBuilder {
                     || (current.sub_addressing != new.sub_addressing)
                     || (current.allow_relaying != new.allow_relaying)
                     || (current.is_enabled != new.is_enabled)
+                    || (current.disclaimer_text != new.disclaimer_text)
+                    || (current.disclaimer_html != new.disclaimer_html)
+                    || (current.disclaimer_inbound != new.disclaimer_inbound)
+                    || (current.disclaimer_wrap_signed != new.disclaimer_wrap_signed)
+                    || (current.sub_addressing_folders != new.sub_addressing_folders)
+                    || (current.sub_addressing_create_folders != new.sub_addressing_create_folders)
+                    || (current.relay_verify != new.relay_verify)
+                    || (current.relay_verify_host != new.relay_verify_host)
+                    || (current.relay_verify_url != new.relay_verify_url)
+                    || (current.relay_verify_cache_ttl != new.relay_verify_cache_ttl)
+                    || (current.encryption_policy != new.encryption_policy)
                 {
                     self.invalidate(CacheInvalidation::Domain(id));
                 }
@@ -133,6 +144,8 @@ impl CacheInvalidationBuilder {
                 if (current.permissions != new.permissions)
                     || (current.roles != new.roles)
                     || (current.quotas != new.quotas)
+                    || (current.encryption_policy != new.encryption_policy)
+                    || (current.login_anomaly_sensitivity != new.login_anomaly_sensitivity)
                 {
                     self.invalidate(CacheInvalidation::Tenant(id));
                 }
@@ -155,7 +168,8 @@ impl CacheInvalidationBuilder {
                 if (current.aliases != new.aliases)
                     || (current.name != new.name)
                     || (current.recipients != new.recipients)
-                    || (current.domain_id != new.domain_id) =>
+                    || (current.domain_id != new.domain_id)
+                    || (current.allow_unsubscribe != new.allow_unsubscribe) =>
             {
                 self.invalidate(CacheInvalidation::List(id));
             }
@@ -291,6 +305,9 @@ impl Server {
         self.inner.cache.roles.clear();
         self.inner.cache.lists.clear();
         self.inner.data.logos.lock().clear();
+        if let Some(read_cache) = self.store().read_cache() {
+            read_cache.evict_local(&ReadCacheEviction::All);
+        }
     }
 
     pub fn invalidate_all_local_negative_caches(&self) {
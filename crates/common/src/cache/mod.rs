@@ -4,7 +4,14 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use crate::{DavResources, HttpAuthCache, MailboxCache, MessageStoreCache, UpdateLock};
+use crate::{
+    DavResources, DirectoryCards, DirectoryScope, EmailQueryCache, EmailQueryKey, HttpAuthCache,
+    MailboxCache, MessageStoreCache, UpdateLock,
+};
+use jmap_proto::{
+    method::query::{Comparator, Filter},
+    object::email::{EmailComparator, EmailFilter},
+};
 use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::{Semaphore, SemaphorePermit};
 use utils::cache::CacheItemWeight;
@@ -81,6 +88,24 @@ impl CacheItemWeight for MessageStoreCache {
     }
 }
 
+impl CacheItemWeight for EmailQueryKey {
+    fn weight(&self) -> u64 {
+        (std::mem::size_of::<EmailQueryKey>()
+            + self.shared_mailboxes.len() * std::mem::size_of::<u32>()
+            + self.filter.len() * std::mem::size_of::<Filter<EmailFilter>>()
+            + self.sort.as_ref().map_or(0, |sort| {
+                sort.len() * std::mem::size_of::<Comparator<EmailComparator>>()
+            })) as u64
+    }
+}
+
+impl CacheItemWeight for EmailQueryCache {
+    fn weight(&self) -> u64 {
+        (std::mem::size_of::<EmailQueryCache>()
+            + self.ids.len() * std::mem::size_of::<(u32, u32)>()) as u64
+    }
+}
+
 impl CacheItemWeight for HttpAuthCache {
     fn weight(&self) -> u64 {
         std::mem::size_of::<HttpAuthCache>() as u64
@@ -92,3 +117,15 @@ impl CacheItemWeight for DavResources {
         self.size
     }
 }
+
+impl CacheItemWeight for DirectoryScope {
+    fn weight(&self) -> u64 {
+        std::mem::size_of::<DirectoryScope>() as u64
+    }
+}
+
+impl CacheItemWeight for DirectoryCards {
+    fn weight(&self) -> u64 {
+        self.size
+    }
+}
@@ -7,16 +7,22 @@
 use crate::{
     Server,
     auth::{
-        ACCOUNT_FLAG_ENCRYPT_ALGO_AES128, ACCOUNT_FLAG_ENCRYPT_ALGO_AES256,
-        ACCOUNT_FLAG_ENCRYPT_APPEND, ACCOUNT_FLAG_ENCRYPT_METHOD_PGP,
-        ACCOUNT_FLAG_ENCRYPT_METHOD_SMIME, ACCOUNT_FLAG_ENCRYPT_TRAIN_SPAM_FILTER, ACCOUNT_IS_USER,
-        AccountCache, AccountInfo, AccountTenantIds, DOMAIN_FLAG_RELAY, DOMAIN_FLAG_SUB_ADDRESSING,
-        DomainCache, EmailAddress, EmailAddressRef, EmailCache, MailingListCache, PermissionsGroup,
-        RECOVERY_ADMIN_ID, RoleCache, TenantCache, permissions::BuildPermissions,
+        ACCOUNT_FLAG_CALENDAR_AUTO_IMPORT, ACCOUNT_FLAG_ENCRYPT_ALGO_AES128,
+        ACCOUNT_FLAG_ENCRYPT_ALGO_AES256, ACCOUNT_FLAG_ENCRYPT_APPEND,
+        ACCOUNT_FLAG_ENCRYPT_METHOD_PGP, ACCOUNT_FLAG_ENCRYPT_METHOD_SMIME,
+        ACCOUNT_FLAG_ENCRYPT_TRAIN_SPAM_FILTER, ACCOUNT_FLAG_LEGAL_HOLD,
+        ACCOUNT_FLAG_PUBLIC_FOLDERS, ACCOUNT_FLAG_RESOURCE_ACCEPT_ALWAYS,
+        ACCOUNT_FLAG_RESOURCE_DECLINE_ALWAYS, ACCOUNT_FLAG_RESOURCE_EQUIPMENT,
+        ACCOUNT_FLAG_RESOURCE_MANUAL, ACCOUNT_FLAG_RESOURCE_ROOM, ACCOUNT_IS_USER, AccountCache,
+        AccountInfo, AccountTenantIds, DOMAIN_FLAG_ENCRYPT_DISABLED, DOMAIN_FLAG_ENCRYPT_REQUIRED,
+        DOMAIN_FLAG_RELAY, DOMAIN_FLAG_SUB_ADDRESSING, DOMAIN_FLAG_SUB_ADDRESSING_CREATE,
+        DOMAIN_FLAG_SUB_ADDRESSING_FOLDERS, DomainCache, EmailAddress, EmailAddressRef, EmailCache,
+        MailingListCache, PermissionsGroup, RECOVERY_ADMIN_ID, RelayVerify, RoleCache, TenantCache,
+        permissions::BuildPermissions,
     },
     config::smtp::auth::DkimSigner,
     expr::if_block::BootstrapExprExt,
-    network::mta::AddressResolver,
+    network::{disclaimer::DomainDisclaimer, mta::AddressResolver},
     storage::{
         ObjectQuota, TenantQuota,
         encryption::{EncryptionMethod, parse_public_key},
@@ -24,14 +30,17 @@ use crate::{
 };
 use registry::{
     schema::{
-        enums::{DkimRotationStage, Locale, StorageQuota, TenantStorageQuota},
+        enums::{
+            DkimRotationStage, EncryptionPolicy, JmapCapability, Locale, RelayVerification,
+            SchedulingResourcePolicy, SchedulingResourceType, StorageQuota, TenantStorageQuota,
+        },
         prelude::{ObjectType, Property},
         structs::{
             Account, DkimSignature, Domain, EncryptionAtRest, MailingList, MaskedEmail,
             Permissions, PublicKey, Role, SubAddressing, Tenant,
         },
     },
-    types::id::ObjectId,
+    types::{EnumImpl, id::ObjectId, map::Map},
 };
 use std::{borrow::Cow, sync::Arc};
 use store::{
@@ -140,7 +149,7 @@ impl Server {
                 if domain.allow_relaying {
                     flags |= DOMAIN_FLAG_RELAY;
                 }
-                let sub_addressing_custom = match domain.sub_addressing {
+                let sub_addressing_custom = match &domain.sub_addressing {
                     SubAddressing::Enabled => {
                         flags |= DOMAIN_FLAG_SUB_ADDRESSING;
                         None
@@ -161,6 +170,54 @@ impl Server {
                     }
                     SubAddressing::Disabled => None,
                 };
+                if domain.sub_addressing_folders {
+                    flags |= DOMAIN_FLAG_SUB_ADDRESSING_FOLDERS;
+                    if domain.sub_addressing_create_folders {
+                        flags |= DOMAIN_FLAG_SUB_ADDRESSING_CREATE;
+                    }
+                }
+                match domain.encryption_policy {
+                    EncryptionPolicy::Optional => {}
+                    EncryptionPolicy::Required => flags |= DOMAIN_FLAG_ENCRYPT_REQUIRED,
+                    EncryptionPolicy::Disabled => flags |= DOMAIN_FLAG_ENCRYPT_DISABLED,
+                }
+                let relay_verify = match domain.relay_verify {
+                    RelayVerification::Smtp if domain.allow_relaying => {
+                        Some(Arc::new(RelayVerify::Smtp {
+                            host: domain.relay_verify_host.as_deref().map(Box::from),
+                            cache_ttl: domain.relay_verify_cache_ttl.into_inner(),
+                        }))
+                    }
+                    RelayVerification::Http if domain.allow_relaying => {
+                        if let Some(url) = domain.relay_verify_url.as_deref() {
+                            Some(Arc::new(RelayVerify::Http {
+                                url: url.into(),
+                                cache_ttl: domain.relay_verify_cache_ttl.into_inner(),
+                            }))
+                        } else {
+                            let mut bp = Bootstrap::new_uninitialized(self.registry().clone());
+                            bp.build_error(
+                                ObjectId::new(ObjectType::Domain, domain_id.into()),
+                                "Recipient verification lookup URL is not set",
+                            );
+                            bp.log_errors();
+                            None
+                        }
+                    }
+                    _ => None,
+                };
+                let disclaimer = match DomainDisclaimer::parse(&domain) {
+                    Ok(disclaimer) => disclaimer.map(Box::new),
+                    Err(err) => {
+                        let mut bp = Bootstrap::new_uninitialized(self.registry().clone());
+                        bp.build_error(
+                            ObjectId::new(ObjectType::Domain, domain_id.into()),
+                            format!("Invalid disclaimer template: {err}"),
+                        );
+                        bp.log_errors();
+                        None
+                    }
+                };
 
                 let cache = Arc::new(DomainCache {
                     names: [domain.name.into_boxed_str()]
@@ -177,6 +234,8 @@ impl Server {
                     id_tenant: domain.member_tenant_id.map(|id| id.document_id()),
                     catch_all: domain.catch_all_address.map(|s| s.into_boxed_str()),
                     sub_addressing_custom,
+                    disclaimer,
+                    relay_verify,
                     flags,
                 });
 
@@ -299,6 +358,7 @@ impl Server {
                 encryption_key: Default::default(),
                 locale: Default::default(),
                 flags: Default::default(),
+                disabled_jmap_capabilities: Default::default(),
             }))
         } else {
             Err(trc::AuthEvent::Error
@@ -414,6 +474,33 @@ impl Server {
                             None
                         };
 
+                        // Scheduling resource settings
+                        match account.scheduling_resource {
+                            SchedulingResourceType::None => {}
+                            SchedulingResourceType::Room => flags |= ACCOUNT_FLAG_RESOURCE_ROOM,
+                            SchedulingResourceType::Equipment => {
+                                flags |= ACCOUNT_FLAG_RESOURCE_EQUIPMENT
+                            }
+                        }
+                        match account.scheduling_policy {
+                            SchedulingResourcePolicy::AcceptIfFree => {}
+                            SchedulingResourcePolicy::AcceptAlways => {
+                                flags |= ACCOUNT_FLAG_RESOURCE_ACCEPT_ALWAYS
+                            }
+                            SchedulingResourcePolicy::DeclineAlways => {
+                                flags |= ACCOUNT_FLAG_RESOURCE_DECLINE_ALWAYS
+                            }
+                            SchedulingResourcePolicy::Manual => {
+                                flags |= ACCOUNT_FLAG_RESOURCE_MANUAL
+                            }
+                        }
+                        if account.calendar_auto_import {
+                            flags |= ACCOUNT_FLAG_CALENDAR_AUTO_IMPORT;
+                        }
+                        if account.legal_hold {
+                            flags |= ACCOUNT_FLAG_LEGAL_HOLD;
+                        }
+
                         AccountCache {
                             id: account_id,
                             name: name.into_boxed_str(),
@@ -439,6 +526,9 @@ impl Server {
                             locale: account.locale,
                             encryption_key,
                             flags,
+                            disabled_jmap_capabilities: capability_mask(
+                                &account.disabled_jmap_capabilities,
+                            ),
                         }
                     }
                     Account::Group(account) => {
@@ -471,6 +561,14 @@ impl Server {
                             }
                         }
 
+                        let mut flags = 0;
+                        if account.legal_hold {
+                            flags |= ACCOUNT_FLAG_LEGAL_HOLD;
+                        }
+                        if account.public_folders {
+                            flags |= ACCOUNT_FLAG_PUBLIC_FOLDERS;
+                        }
+
                         AccountCache {
                             id: account_id,
                             name: name.into_boxed_str(),
@@ -491,7 +589,8 @@ impl Server {
                             description: account.description.map(Into::into),
                             encryption_key: None,
                             locale: account.locale,
-                            flags: 0,
+                            flags,
+                            disabled_jmap_capabilities: 0,
                         }
                     }
                 });
@@ -742,6 +841,16 @@ impl Server {
                     quota_disk,
                     quota_objects: quota_objects.map(Box::new),
                     permissions,
+                    index_attachment_contents: tenant.index_attachment_contents,
+                    retention_policies: tenant
+                        .retention_policies
+                        .values()
+                        .filter(|policy| policy.enabled)
+                        .cloned()
+                        .collect(),
+                    disabled_jmap_capabilities: capability_mask(&tenant.disabled_jmap_capabilities),
+                    encryption_policy: tenant.encryption_policy,
+                    login_anomaly_sensitivity: tenant.login_anomaly_sensitivity,
                 });
 
                 let _ = guard.insert(cache.clone());
@@ -750,6 +859,38 @@ impl Server {
         }
     }
 
+    pub async fn disabled_jmap_capabilities(&self, account_id: u32) -> trc::Result<u32> {
+        let account = self.account(account_id).await?;
+        let mut disabled = account.disabled_jmap_capabilities;
+        if let Some(tenant_id) = account.id_tenant {
+            disabled |= self.tenant(tenant_id).await?.disabled_jmap_capabilities;
+        }
+        Ok(disabled)
+    }
+
+    // A policy disabling encryption takes precedence over one requiring it,
+    // whether it is set on the account's domain or on its tenant
+    pub async fn encryption_policy(&self, account: &AccountCache) -> trc::Result<EncryptionPolicy> {
+        let mut policy = EncryptionPolicy::Optional;
+        if let Some(address) = account.addresses.first()
+            && let Some(domain) = self.domain_by_id(address.domain_id).await?
+        {
+            if domain.flags & DOMAIN_FLAG_ENCRYPT_DISABLED != 0 {
+                return Ok(EncryptionPolicy::Disabled);
+            } else if domain.flags & DOMAIN_FLAG_ENCRYPT_REQUIRED != 0 {
+                policy = EncryptionPolicy::Required;
+            }
+        }
+        if let Some(tenant_id) = account.id_tenant {
+            match self.tenant(tenant_id).await?.encryption_policy {
+                EncryptionPolicy::Optional => {}
+                EncryptionPolicy::Required => policy = EncryptionPolicy::Required,
+                EncryptionPolicy::Disabled => return Ok(EncryptionPolicy::Disabled),
+            }
+        }
+        Ok(policy)
+    }
+
     pub async fn try_list(&self, id: u32) -> trc::Result<Option<Arc<MailingListCache>>> {
         let cache = &self.inner.cache.lists;
         match cache.get_value_or_guard_async(&id).await {
@@ -766,6 +907,7 @@ impl Server {
                 };
                 let cache = Arc::new(MailingListCache {
                     recipients: list.recipients.into_iter().map(Into::into).collect(),
+                    allow_unsubscribe: list.allow_unsubscribe,
                 });
                 let _ = guard.insert(cache.clone());
                 Ok(Some(cache))
@@ -819,6 +961,9 @@ impl Server {
                 }
 
                 if !signatures.is_empty() {
+                    // RSA signatures go first when dual signing, verifiers that
+                    // predate RFC 8463 often only evaluate the topmost signature
+                    signatures.sort_by_key(|signer| !matches!(signer, DkimSigner::RsaSha256(_)));
                     let signatures: Arc<[DkimSigner]> = signatures.into();
                     let _ = guard.insert(signatures.clone());
                     Ok(Some(signatures))
@@ -909,6 +1054,37 @@ impl AccountCache {
         self.flags & ACCOUNT_IS_USER != 0
     }
 
+    pub fn scheduling_resource_policy(&self) -> Option<SchedulingResourcePolicy> {
+        if self.flags & (ACCOUNT_FLAG_RESOURCE_ROOM | ACCOUNT_FLAG_RESOURCE_EQUIPMENT) != 0 {
+            Some(if self.flags & ACCOUNT_FLAG_RESOURCE_ACCEPT_ALWAYS != 0 {
+                SchedulingResourcePolicy::AcceptAlways
+            } else if self.flags & ACCOUNT_FLAG_RESOURCE_DECLINE_ALWAYS != 0 {
+                SchedulingResourcePolicy::DeclineAlways
+            } else if self.flags & ACCOUNT_FLAG_RESOURCE_MANUAL != 0 {
+                SchedulingResourcePolicy::Manual
+            } else {
+                SchedulingResourcePolicy::AcceptIfFree
+            })
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    pub fn calendar_auto_import(&self) -> bool {
+        self.flags & ACCOUNT_FLAG_CALENDAR_AUTO_IMPORT != 0
+    }
+
+    #[inline(always)]
+    pub fn is_legal_hold(&self) -> bool {
+        self.flags & ACCOUNT_FLAG_LEGAL_HOLD != 0
+    }
+
+    #[inline(always)]
+    pub fn has_public_folders(&self) -> bool {
+        self.flags & ACCOUNT_FLAG_PUBLIC_FOLDERS != 0
+    }
+
     #[inline(always)]
     pub fn disk_quota(&self) -> u64 {
         self.quota_disk
@@ -927,3 +1103,9 @@ impl AccountCache {
         }
     }
 }
+
+fn capability_mask(capabilities: &Map<JmapCapability>) -> u32 {
+    capabilities
+        .iter()
+        .fold(0, |mask, capability| mask | (1 << capability.to_id()))
+}
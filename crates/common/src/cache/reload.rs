@@ -7,28 +7,71 @@
 use crate::{
     Core, Server,
     config::{
+        mailstore::{
+            scripts::SieveScripts,
+            spamfilter::{SpamFilterLists, SpamFilterRules},
+        },
         server::{Listeners, tls::parse_certificates},
         storage::Storage,
         telemetry::Telemetry,
     },
-    ipc::{QueueEvent, RegistryChange},
-    network::security::{BlockedIps, IpWithTtl},
+    ipc::{QueueEvent, RegistryChange, ReloadSubsystem},
+    network::security::{AllowedIps, BlockedIps, IpWithTtl},
 };
 use ahash::AHashMap;
 use directory::Directories;
 use registry::{
     schema::{prelude::ObjectType, structs::BlockedIp},
-    types::error::{Error, Warning},
+    types::{
+        error::{Error, Warning},
+        id::ObjectId,
+    },
 };
 use std::sync::Arc;
-use store::{LookupStores, registry::bootstrap::Bootstrap, write::now};
+use store::{
+    LookupStores,
+    registry::{RegistryOverlay, bootstrap::Bootstrap},
+    write::now,
+};
 
 pub struct ReloadResult {
     pub errors: Vec<Error>,
     pub warnings: Vec<Warning>,
+    pub changes: Vec<SettingChange>,
     pub replaced_core: bool,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub struct SettingChange {
+    pub object_id: ObjectId,
+    pub change: SettingChangeType,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingChangeType {
+    Added,
+    Modified,
+    Removed,
+}
+
+// Returns the object type whose reload path covers the given object type,
+// objects outside of the dedicated paths are reloaded by rebuilding the core.
+pub fn reload_scope(object: ObjectType) -> ObjectType {
+    match object {
+        ObjectType::MemoryLookupKeyValue | ObjectType::HttpLookup | ObjectType::StoreLookup => {
+            ObjectType::MemoryLookupKey
+        }
+        ObjectType::SieveUserScript => ObjectType::SieveSystemScript,
+        ObjectType::Certificate
+        | ObjectType::MemoryLookupKey
+        | ObjectType::BlockedIp
+        | ObjectType::AllowedIp
+        | ObjectType::SieveSystemScript
+        | ObjectType::Application => object,
+        _ => ObjectType::DataStore,
+    }
+}
+
 impl Server {
     pub async fn reload_registry(&self, change: RegistryChange) -> trc::Result<ReloadResult> {
         let mut bootstrap = Bootstrap::new(self.registry().clone()).await;
@@ -52,6 +95,7 @@ impl Server {
                                     .push(IpWithTtl::new(ip.address, expires_at));
                             }
                         }
+                        self.notify_firewall_sync();
                     }
                     return Ok(bootstrap.into());
                 } else {
@@ -60,22 +104,16 @@ impl Server {
             }
             RegistryChange::Delete(id) => id.object(),
             RegistryChange::Reload(object) => object,
+            RegistryChange::ReloadSubsystem(subsystem) => {
+                return Ok(self.reload_subsystem(subsystem).await);
+            }
         };
 
-        match object {
+        match reload_scope(object) {
             ObjectType::Certificate => {
-                let mut certificates = AHashMap::new();
-                parse_certificates(&mut bootstrap, &mut certificates, &mut Default::default())
-                    .await;
-                self.inner
-                    .data
-                    .tls_certificates
-                    .store(Arc::new(certificates));
+                self.reload_certificates(&mut bootstrap).await;
             }
-            ObjectType::MemoryLookupKey
-            | ObjectType::MemoryLookupKeyValue
-            | ObjectType::HttpLookup
-            | ObjectType::StoreLookup => {
+            ObjectType::MemoryLookupKey => {
                 let lookup = LookupStores::build(&mut bootstrap).await;
 
                 if bootstrap.errors.is_empty() {
@@ -87,8 +125,19 @@ impl Server {
                 let blocked_ips = BlockedIps::parse(&mut bootstrap).await;
                 if bootstrap.errors.is_empty() {
                     *self.inner.data.blocked_ips.write() = blocked_ips;
+                    self.notify_firewall_sync();
+                }
+            }
+            ObjectType::AllowedIp => {
+                let allowed_ips = AllowedIps::parse(&mut bootstrap).await;
+                if bootstrap.errors.is_empty() {
+                    *self.inner.data.allowed_ips.write() = allowed_ips;
+                    self.notify_firewall_sync();
                 }
             }
+            ObjectType::SieveSystemScript => {
+                self.reload_sieve_scripts(&mut bootstrap).await;
+            }
             ObjectType::Application => {
                 self.inner.data.applications.reload(&mut bootstrap).await;
                 if bootstrap.errors.is_empty() {
@@ -97,20 +146,7 @@ impl Server {
             }
             _ => {
                 // Load stores
-                let directory = Directories::build(&mut bootstrap).await;
-                let storage = &self.core.storage;
-                let storage = Storage {
-                    registry: storage.registry.clone(),
-                    data: storage.data.clone(),
-                    blob: storage.blob.clone(),
-                    search: storage.search.clone(),
-                    metrics: storage.metrics.clone(),
-                    tracing: storage.tracing.clone(),
-                    memory: storage.memory.clone(),
-                    coordinator: storage.coordinator.clone(),
-                    directory: directory.default_directory,
-                    directories: directory.directories,
-                };
+                let storage = self.reload_storage(&mut bootstrap).await;
 
                 // Parse tracers
                 let tracers = Telemetry::parse(&mut bootstrap, &storage).await;
@@ -124,9 +160,19 @@ impl Server {
                             .parse_tcp_acceptors(&mut bootstrap, self.inner.clone())
                             .await;
 
+                        // Parse the sections kept outside the core
+                        let spam_rules = SpamFilterRules::parse(&mut bootstrap).await;
+                        let spam_lists = SpamFilterLists::parse(&mut bootstrap).await;
+                        let sieve_scripts = SieveScripts::parse(&mut bootstrap, &core.sieve).await;
+                        let allowed_ips = AllowedIps::parse(&mut bootstrap).await;
+
                         if bootstrap.errors.is_empty() {
                             // Update core
                             self.inner.shared_core.store(core.into());
+                            self.inner.data.spam_rules.store(Arc::new(spam_rules));
+                            self.inner.data.spam_lists.store(Arc::new(spam_lists));
+                            self.inner.data.sieve_scripts.store(Arc::new(sieve_scripts));
+                            *self.inner.data.allowed_ips.write() = allowed_ips;
 
                             // Update tracers
 
@@ -147,7 +193,11 @@ impl Server {
                                 .await
                                 .ok();
 
+                            // Reconcile the external firewall with the new settings
+                            self.notify_firewall_sync();
+
                             return Ok(ReloadResult {
+                                changes: self.track_changes(&bootstrap),
                                 errors: bootstrap.errors,
                                 warnings: bootstrap.warnings,
                                 replaced_core: true,
@@ -155,10 +205,166 @@ impl Server {
                         }
                     }
                 }
+
+                return Ok(bootstrap.into());
+            }
+        }
+
+        Ok(ReloadResult {
+            changes: self.track_changes(&bootstrap),
+            ..bootstrap.into()
+        })
+    }
+
+    // Reloads a single subsystem on top of the running core, reporting which
+    // settings changed since they were last loaded. Only the objects of the
+    // subsystem are read and the core itself is left untouched.
+    pub async fn reload_subsystem(&self, subsystem: ReloadSubsystem) -> ReloadResult {
+        let mut bootstrap = Bootstrap::new_uninitialized(self.registry().clone());
+
+        match subsystem {
+            ReloadSubsystem::Certificates => {
+                self.reload_certificates(&mut bootstrap).await;
+            }
+            ReloadSubsystem::SpamFilter => {
+                let spam_rules = SpamFilterRules::parse(&mut bootstrap).await;
+                let spam_lists = SpamFilterLists::parse(&mut bootstrap).await;
+                if bootstrap.errors.is_empty() {
+                    self.inner.data.spam_rules.store(Arc::new(spam_rules));
+                    self.inner.data.spam_lists.store(Arc::new(spam_lists));
+                }
+            }
+            ReloadSubsystem::Sieve => {
+                self.reload_sieve_scripts(&mut bootstrap).await;
+            }
+            ReloadSubsystem::Network => {
+                let allowed_ips = AllowedIps::parse(&mut bootstrap).await;
+                let blocked_ips = BlockedIps::parse(&mut bootstrap).await;
+                if bootstrap.errors.is_empty() {
+                    *self.inner.data.allowed_ips.write() = allowed_ips;
+                    *self.inner.data.blocked_ips.write() = blocked_ips;
+                    self.notify_firewall_sync();
+                }
+            }
+        }
+
+        ReloadResult {
+            changes: self.track_changes(&bootstrap),
+            ..bootstrap.into()
+        }
+    }
+
+    async fn reload_sieve_scripts(&self, bootstrap: &mut Bootstrap) {
+        // Scripts are compiled with the interpreter limits of the running core
+        let core = self.inner.shared_core.load_full();
+        let sieve_scripts = SieveScripts::parse(bootstrap, &core.sieve).await;
+        if bootstrap.errors.is_empty() {
+            self.inner.data.sieve_scripts.store(Arc::new(sieve_scripts));
+        }
+    }
+
+    async fn reload_certificates(&self, bootstrap: &mut Bootstrap) {
+        let mut certificates = AHashMap::new();
+        parse_certificates(bootstrap, &mut certificates, &mut Default::default()).await;
+        self.inner
+            .data
+            .tls_certificates
+            .store(Arc::new(certificates));
+    }
+
+    // Compares the revisions of the objects read during a reload with the ones
+    // last applied. Nothing is recorded when the reload failed.
+    fn track_changes(&self, bootstrap: &Bootstrap) -> Vec<SettingChange> {
+        let mut changes = Vec::new();
+        if !bootstrap.errors.is_empty() {
+            return changes;
+        }
+
+        let mut applied = self.inner.data.registry_revisions.lock();
+        for (object_id, revision) in &bootstrap.revisions {
+            let change = match applied.insert(*object_id, *revision) {
+                None => SettingChangeType::Added,
+                Some(old_revision) if old_revision != *revision => SettingChangeType::Modified,
+                _ => continue,
+            };
+            changes.push(SettingChange {
+                object_id: *object_id,
+                change,
+            });
+        }
+
+        // Objects of fully listed types that were not read anymore have been removed
+        applied.retain(|object_id, _| {
+            if bootstrap.listed.contains(&object_id.object())
+                && !bootstrap.revisions.contains_key(object_id)
+            {
+                changes.push(SettingChange {
+                    object_id: *object_id,
+                    change: SettingChangeType::Removed,
+                });
+                false
+            } else {
+                true
+            }
+        });
+
+        changes.sort_unstable_by_key(|change| {
+            (
+                change.object_id.object().to_id(),
+                change.object_id.id().id(),
+            )
+        });
+        changes
+    }
+
+    // Parses the full configuration with the given uncommitted changes applied,
+    // without replacing the running core.
+    pub async fn validate_registry(&self, overlay: RegistryOverlay) -> ReloadResult {
+        let mut bootstrap = Bootstrap::new(self.registry().with_overlay(overlay)).await;
+
+        parse_certificates(
+            &mut bootstrap,
+            &mut AHashMap::new(),
+            &mut Default::default(),
+        )
+        .await;
+        LookupStores::build(&mut bootstrap).await;
+        AllowedIps::parse(&mut bootstrap).await;
+        BlockedIps::parse(&mut bootstrap).await;
+        SpamFilterRules::parse(&mut bootstrap).await;
+        SpamFilterLists::parse(&mut bootstrap).await;
+
+        let storage = self.reload_storage(&mut bootstrap).await;
+        Telemetry::parse(&mut bootstrap, &storage).await;
+        if bootstrap.errors.is_empty() {
+            let core = Box::pin(Core::parse(&mut bootstrap, storage)).await;
+
+            if bootstrap.errors.is_empty() {
+                SieveScripts::parse(&mut bootstrap, &core.sieve).await;
+                Listeners::parse(&mut bootstrap).await;
             }
         }
 
-        Ok(bootstrap.into())
+        bootstrap.into()
+    }
+
+    async fn reload_storage(&self, bootstrap: &mut Bootstrap) -> Storage {
+        let directory = Directories::build(bootstrap).await;
+        let storage = &self.core.storage;
+        Storage {
+            registry: storage.registry.clone(),
+            data: storage.data.clone(),
+            blob: storage.blob.clone(),
+            queue: storage.queue.clone(),
+            queue_blob: storage.queue_blob.clone(),
+            search: storage.search.clone(),
+            metrics: storage.metrics.clone(),
+            tracing: storage.tracing.clone(),
+            memory: storage.memory.clone(),
+            coordinator: storage.coordinator.clone(),
+            directory: directory.default_directory,
+            directories: directory.directories,
+        }
     }
 }
 
@@ -182,6 +388,7 @@ impl From<Bootstrap> for ReloadResult {
         Self {
             errors: bootstrap.errors,
             warnings: bootstrap.warnings,
+            changes: Vec::new(),
             replaced_core: false,
         }
     }
@@ -0,0 +1,24 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use registry::schema::structs::DataRetention;
+use std::time::Duration;
+use store::registry::bootstrap::Bootstrap;
+
+#[derive(Debug, Clone, Default)]
+pub struct AuditLogConfig {
+    pub retention: Option<Duration>,
+}
+
+impl AuditLogConfig {
+    pub async fn parse(bp: &mut Bootstrap) -> Self {
+        let dr = bp.setting_infallible::<DataRetention>().await;
+
+        AuditLogConfig {
+            retention: dr.hold_audit_log_for.map(|v| v.into_inner()),
+        }
+    }
+}
@@ -4,13 +4,16 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use registry::schema::structs::{
-    AddressBook, Calendar, CalendarAlarm, CalendarScheduling, DataRetention, FileStorage, Sharing,
-    SystemSettings, WebDav,
+use registry::schema::{
+    enums::{AddressBookDirectoryField, AddressBookDirectoryScope, FreeBusyAccess},
+    structs::{
+        AddressBook, Calendar, CalendarAlarm, CalendarScheduling, DataRetention, FileStorage,
+        Sharing, SystemSettings, WebDav,
+    },
 };
-use std::str::FromStr;
+use std::{str::FromStr, time::Duration};
 use store::registry::bootstrap::Bootstrap;
-use utils::template::Template;
+use utils::{map::vec_map::VecMap, template::Template};
 
 #[derive(Debug, Clone, Default)]
 pub struct GroupwareConfig {
@@ -43,11 +46,22 @@ pub struct GroupwareConfig {
     pub itip_http_rsvp_expiration: u64,
     pub itip_inbox_auto_expunge: Option<u64>,
     pub itip_template: Template<CalendarTemplateVariable>,
+    pub freebusy_http_access: Option<FreeBusyAccess>,
+    pub freebusy_max_range: i64,
+    pub subscription_refresh_interval: u64,
+    pub subscription_max_backoff: u64,
+    pub subscription_max_size: usize,
+    pub subscription_timeout: Duration,
 
     // Addressbook settings
     pub max_vcard_size: usize,
     pub default_addressbook_name: Option<String>,
     pub default_addressbook_display_name: Option<String>,
+    pub directory_addressbook_name: Option<String>,
+    pub directory_addressbook_display_name: String,
+    pub directory_addressbook_scope: AddressBookDirectoryScope,
+    pub directory_addressbook_refresh: u64,
+    pub directory_addressbook_fields: VecMap<AddressBookDirectoryField, String>,
 
     // File storage settings
     pub max_file_size: usize,
@@ -55,6 +69,9 @@ pub struct GroupwareConfig {
     // Sharing settings
     pub max_shares_per_item: usize,
     pub allow_directory_query: bool,
+    pub share_link_url: Option<String>,
+    pub share_link_max_expiry: u64,
+    pub max_share_links: usize,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Hash)]
@@ -108,6 +125,22 @@ impl GroupwareConfig {
             max_ical_instances: calendar.max_recurrence_expansions as usize,
             max_ical_attendees_per_instance: calendar.max_attendees as usize,
             max_vcard_size: book.max_v_card_size as usize,
+            directory_addressbook_name: book.directory_enable.then_some(book.directory_href_name),
+            directory_addressbook_display_name: book.directory_display_name,
+            directory_addressbook_scope: book.directory_scope,
+            directory_addressbook_refresh: book.directory_refresh_interval.into_inner().as_secs(),
+            directory_addressbook_fields: book
+                .directory_fields
+                .into_iter()
+                .filter_map(|(field, property)| {
+                    let property = property.trim().to_ascii_uppercase();
+                    (!property.is_empty()
+                        && property
+                            .bytes()
+                            .all(|ch| ch.is_ascii_alphanumeric() || ch == b'-'))
+                    .then_some((field, property))
+                })
+                .collect(),
             max_file_size: file.max_size as usize,
             alarms_enabled: alarm.enable,
             alarms_minimum_interval: alarm.min_trigger_interval.into_inner().as_secs() as i64,
@@ -142,7 +175,23 @@ impl GroupwareConfig {
             },
             max_shares_per_item: share.max_shares as usize,
             allow_directory_query: share.allow_directory_queries,
+            share_link_url: share
+                .share_link_enable
+                .then(|| format!("https://{}/share", system.default_hostname)),
+            share_link_max_expiry: share.share_link_max_expiry.into_inner().as_secs(),
+            max_share_links: share.max_share_links as usize,
             itip_http_rsvp_expiration: sched.http_rsvp_link_expiry.into_inner().as_secs(),
+            freebusy_http_access: sched
+                .http_free_busy_enable
+                .then_some(sched.http_free_busy_access),
+            freebusy_max_range: sched.http_free_busy_max_range.into_inner().as_secs() as i64,
+            subscription_refresh_interval: calendar
+                .subscription_refresh_interval
+                .into_inner()
+                .as_secs(),
+            subscription_max_backoff: calendar.subscription_max_backoff.into_inner().as_secs(),
+            subscription_max_size: calendar.subscription_max_size as usize,
+            subscription_timeout: calendar.subscription_timeout.into_inner(),
             itip_template: Template::parse(include_str!(concat!(
                 env!("CARGO_MANIFEST_DIR"),
                 "/../../resources/html-templates/calendar-invite.html.min"
@@ -6,11 +6,14 @@
 
 use super::server::tls::build_self_signed_cert;
 use crate::{
-    Caches, Data, DavResource, DavResources, MailboxCache, MessageStoreCache, MessageUidCache,
-    TlsConnectors,
+    Caches, Core, Data, DavResource, DavResources, DirectoryCards, EmailQueryCache, EmailQueryKey,
+    MailboxCache, MessageStoreCache, MessageUidCache, TlsConnectors,
     auth::{AccessTokenInner, AccountCache, DomainCache, MailingListCache, RoleCache, TenantCache},
     config::{
-        mailstore::spamfilter::SpamClassifier,
+        mailstore::{
+            scripts::SieveScripts,
+            spamfilter::{SpamClassifier, SpamFilterLists, SpamFilterRules},
+        },
         server::tls::parse_certificates,
         smtp::{
             auth::DkimSigner,
@@ -18,12 +21,12 @@ use crate::{
         },
     },
     manager::application::WebApplications,
-    network::security::BlockedIps,
+    network::security::{AllowedIps, BlockedIps},
 };
 use ahash::{AHashMap, AHashSet};
 use arc_swap::ArcSwap;
 use mail_auth::{MX, Parameters, Txt};
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use registry::schema::{prelude::ObjectType, structs};
 use std::{
     net::{IpAddr, Ipv4Addr, Ipv6Addr},
@@ -38,7 +41,7 @@ use utils::{
 };
 
 impl Data {
-    pub async fn parse(bp: &mut Bootstrap) -> Self {
+    pub async fn parse(bp: &mut Bootstrap, core: &Core) -> Self {
         // Parse certificates
         let mut certificates = AHashMap::new();
         let mut subject_names = AHashSet::new();
@@ -59,11 +62,20 @@ impl Data {
         let applications = WebApplications::new();
         applications.reload(bp).await;
 
+        let allowed_ips = AllowedIps::parse(bp).await;
         let blocked_ips = BlockedIps::parse(bp).await;
         let lookup_stores = LookupStores::build(bp).await;
 
+        // Parse hot-reloadable spam filter and Sieve sections
+        let spam_rules = SpamFilterRules::parse(bp).await;
+        let spam_lists = SpamFilterLists::parse(bp).await;
+        let sieve_scripts = SieveScripts::parse(bp, &core.sieve).await;
+
         Data {
             spam_classifier: ArcSwap::from_pointee(SpamClassifier::default()),
+            spam_rules: ArcSwap::from_pointee(spam_rules),
+            spam_lists: ArcSwap::from_pointee(spam_lists),
+            sieve_scripts: ArcSwap::from_pointee(sieve_scripts),
             tls_certificates: ArcSwap::from_pointee(certificates),
             tls_self_signed_cert: build_self_signed_cert(
                 subject_names
@@ -81,16 +93,30 @@ impl Data {
             .ok()
             .map(Arc::new),
             lookup_stores: ArcSwap::from_pointee(lookup_stores.stores),
+            allowed_ips: RwLock::new(allowed_ips),
             blocked_ips: RwLock::new(blocked_ips),
             jmap_id_gen: id_generator.clone(),
             queue_id_gen: id_generator.clone(),
             registry_id_gen: id_generator.clone(),
             span_id_gen: id_generator,
             queue_status: true.into(),
+            draining: false.into(),
+            drain_resume_queue: false.into(),
             applications,
             logos: Default::default(),
             smtp_connectors: TlsConnectors::try_new().failed("Failed to build TLS connectors"),
             asn_geo_data: Default::default(),
+            registry_revisions: Mutex::new(bp.revisions.clone()),
+            active_sessions: Default::default(),
+            active_deliveries: Default::default(),
+            audit_head: Default::default(),
+            metrics_breakdown: Default::default(),
+            cluster_metrics: Default::default(),
+            delivery_latency: Default::default(),
+            mta_sts_fetches: Default::default(),
+            ingest_extensions: Default::default(),
+            bandwidth_usage: Default::default(),
+            request_scheduler: Default::default(),
         }
     }
 }
@@ -112,6 +138,12 @@ impl Caches {
                     + (1024 * std::mem::size_of::<MessageUidCache>())
                     + (15 * (std::mem::size_of::<MailboxCache>() + 60))) as u64,
             ),
+            email_queries: CacheWithTtl::new(
+                cache.email_queries,
+                (std::mem::size_of::<EmailQueryKey>()
+                    + std::mem::size_of::<EmailQueryCache>()
+                    + (256 * std::mem::size_of::<(u32, u32)>())) as u64,
+            ),
             files: Cache::new(
                 cache.files,
                 (std::mem::size_of::<DavResources>() + (500 * std::mem::size_of::<DavResource>()))
@@ -132,6 +164,10 @@ impl Caches {
                 (std::mem::size_of::<DavResources>() + (500 * std::mem::size_of::<DavResource>()))
                     as u64,
             ),
+            directory_cards: CacheWithTtl::new(
+                cache.contacts,
+                (std::mem::size_of::<DirectoryCards>() + (500 * 512)) as u64,
+            ),
             emails: Cache::new(cache.email_addresses, 255u64),
             emails_negative: CacheWithTtl::new(
                 cache.email_addresses_negative,
@@ -186,6 +222,10 @@ impl Caches {
                 cache.dns_rbl,
                 ((std::mem::size_of::<Ipv4Addr>() + 255) * 2) as u64,
             ),
+            rate_limited: CacheWithTtl::new(
+                cache.rate_limited,
+                (std::mem::size_of::<u64>() * 4) as u64,
+            ),
             negative_cache_ttl: cache.negative_ttl.into_inner(),
         }
     }
@@ -219,19 +259,36 @@ impl Default for Data {
     fn default() -> Self {
         Self {
             spam_classifier: Default::default(),
+            spam_rules: Default::default(),
+            spam_lists: Default::default(),
+            sieve_scripts: Default::default(),
             tls_certificates: Default::default(),
             tls_self_signed_cert: Default::default(),
+            allowed_ips: Default::default(),
             blocked_ips: Default::default(),
             jmap_id_gen: Default::default(),
             queue_id_gen: Default::default(),
             span_id_gen: Default::default(),
             registry_id_gen: Default::default(),
             queue_status: true.into(),
+            draining: false.into(),
+            drain_resume_queue: false.into(),
             applications: WebApplications::new(),
             logos: Default::default(),
             smtp_connectors: TlsConnectors::try_new().unwrap(),
             asn_geo_data: Default::default(),
             lookup_stores: Default::default(),
+            registry_revisions: Default::default(),
+            active_sessions: Default::default(),
+            active_deliveries: Default::default(),
+            audit_head: Default::default(),
+            metrics_breakdown: Default::default(),
+            cluster_metrics: Default::default(),
+            delivery_latency: Default::default(),
+            mta_sts_fetches: Default::default(),
+            ingest_extensions: Default::default(),
+            bandwidth_usage: Default::default(),
+            request_scheduler: Default::default(),
         }
     }
 }
@@ -29,7 +29,7 @@ use store::{
 use types::special_use::SpecialUse;
 use utils::cron::SimpleCron;
 
-use crate::storage::ObjectQuota;
+use crate::{MailboxCache, auth::AccountCache, storage::ObjectQuota};
 
 #[derive(Clone)]
 pub struct EmailConfig {
@@ -46,18 +46,23 @@ pub struct EmailConfig {
     pub email_submission_autoexpunge_after: Option<u64>,
 
     pub changes_max_history: Option<usize>,
+    pub email_change_max_history: Option<usize>,
     pub share_notification_max_history: Option<Duration>,
 
     pub sieve_max_script_name: usize,
 
     pub default_folders: Vec<DefaultFolder>,
+    pub folder_policies: Vec<FolderPolicy>,
     pub shared_folder: String,
+    pub public_folder: String,
 
     pub encrypt: bool,
     pub encrypt_append: bool,
 
     pub index_batch_size: usize,
+    pub reindex_rate: u64,
     pub index_fields: AHashMap<SearchIndex, AHashSet<SearchField>>,
+    pub index_attachment_contents: bool,
 
     pub max_objects: ObjectQuota,
     pub compression: CompressionAlgo,
@@ -74,6 +79,15 @@ pub struct DefaultFolder {
     pub special_use: SpecialUse,
     pub subscribe: bool,
     pub create: bool,
+    pub protect: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct FolderPolicy {
+    pub path: String,
+    pub subscribe: bool,
+    pub create: bool,
+    pub protect: bool,
 }
 
 impl EmailConfig {
@@ -174,6 +188,7 @@ impl EmailConfig {
                         special_use,
                         SpecialUse::Inbox | SpecialUse::Trash | SpecialUse::Junk
                     ),
+                protect: folder.protect,
             });
         }
         for (special_use, name) in [
@@ -190,10 +205,37 @@ impl EmailConfig {
                     special_use,
                     subscribe: true,
                     create: true,
+                    protect: false,
                 });
             }
         }
 
+        // Parse folder policies, skipping paths that clash with a default folder.
+        // Sorting by path ensures parents are provisioned before their children.
+        let mut folder_policies = email
+            .folder_policies
+            .into_iter()
+            .filter_map(|(path, policy)| {
+                let path = path
+                    .split('/')
+                    .map(|name| name.trim())
+                    .filter(|name| !name.is_empty())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                (!path.is_empty()
+                    && !default_folders
+                        .iter()
+                        .any(|folder| folder.name.eq_ignore_ascii_case(&path)))
+                .then_some(FolderPolicy {
+                    path,
+                    subscribe: policy.subscribe,
+                    create: policy.create,
+                    protect: policy.protect,
+                })
+            })
+            .collect::<Vec<_>>();
+        folder_policies.sort_unstable_by_key(|policy| policy.path.to_lowercase());
+
         // Search Index settings
         let mut index_fields = AHashMap::new();
         if search.index_email {
@@ -278,15 +320,23 @@ impl EmailConfig {
                 .expunge_submissions_after
                 .map(|d| d.into_inner().as_secs()),
             changes_max_history: dr.max_changes_history.map(|v| v as usize),
+            email_change_max_history: dr
+                .max_email_change_history
+                .filter(|v| *v > 0)
+                .map(|v| v as usize),
             share_notification_max_history: dr.expunge_share_notify_after.map(|v| v.into_inner()),
             sieve_max_script_name: sieve.max_script_name_length as usize,
             encrypt: email.encrypt_at_rest,
             encrypt_append: email.encrypt_on_append,
             index_batch_size: search.index_batch_size as usize,
+            reindex_rate: search.reindex_rate,
             index_fields,
+            index_attachment_contents: search.index_attachment_contents,
             max_objects,
             default_folders,
+            folder_policies,
             shared_folder,
+            public_folder: email.public_folder_name,
             account_purge_frequency: dr.expunge_schedule.into(),
             data_purge_frequency: dr.data_cleanup_schedule.into(),
             blob_purge_frequency: dr.blob_cleanup_schedule.into(),
@@ -295,4 +345,44 @@ impl EmailConfig {
             default_domain_name,
         }
     }
+
+    /// Returns whether users are prevented from deleting or renaming a folder,
+    /// either because it is protected by policy or contains a protected folder.
+    pub fn is_protected_folder(&self, mailbox: &MailboxCache) -> bool {
+        if mailbox.role != SpecialUse::None
+            && self
+                .default_folders
+                .iter()
+                .any(|folder| folder.protect && folder.special_use == mailbox.role)
+        {
+            return true;
+        }
+
+        self.folder_policies.iter().any(|policy| {
+            policy.protect
+                && policy.path.len() >= mailbox.path.len()
+                && policy.path.as_bytes()[..mailbox.path.len()]
+                    .eq_ignore_ascii_case(mailbox.path.as_bytes())
+                && (policy.path.len() == mailbox.path.len()
+                    || policy.path.as_bytes()[mailbox.path.len()] == b'/')
+        })
+    }
+
+    /// Returns the path under which the mailboxes of another account are listed,
+    /// which is the public folders root for groups that publish their mailboxes.
+    pub fn shared_account_prefix(&self, account: &AccountCache) -> String {
+        format!(
+            "{}/{}",
+            if account.has_public_folders() {
+                &self.public_folder
+            } else {
+                &self.shared_folder
+            },
+            account.name()
+        )
+    }
+
+    pub fn is_shared_root(&self, name: &str) -> bool {
+        name == self.shared_folder || name == self.public_folder
+    }
 }
@@ -20,6 +20,11 @@ pub struct ImapConfig {
 
     pub rate_requests: Option<Rate>,
     pub rate_concurrent: Option<u64>,
+
+    pub proxy_imap_port: u16,
+    pub proxy_pop3_port: u16,
+    pub proxy_implicit_tls: bool,
+    pub proxy_allow_invalid_certs: bool,
 }
 
 impl ImapConfig {
@@ -35,6 +40,10 @@ impl ImapConfig {
             rate_requests: imap.max_request_rate,
             rate_concurrent: imap.max_concurrent,
             allow_plain_auth: imap.allow_plain_text_auth,
+            proxy_imap_port: imap.proxy_imap_port as u16,
+            proxy_pop3_port: imap.proxy_pop3_port as u16,
+            proxy_implicit_tls: imap.proxy_implicit_tls,
+            proxy_allow_invalid_certs: imap.proxy_allow_invalid_certs,
         }
     }
 }
@@ -19,6 +19,10 @@ pub struct JmapConfig {
     pub request_max_calls: usize,
     pub request_max_concurrent: Option<u64>,
 
+    pub call_max_concurrent: Option<u64>,
+    pub call_max_queued: usize,
+    pub call_max_wait: Duration,
+
     pub get_max_objects: usize,
     pub set_max_objects: usize,
 
@@ -60,6 +64,9 @@ impl JmapConfig {
             request_max_size: jmap.max_request_size as usize,
             request_max_calls: jmap.max_method_calls as usize,
             request_max_concurrent: jmap.max_concurrent_requests,
+            call_max_concurrent: jmap.max_concurrent_calls,
+            call_max_queued: jmap.max_queued_calls as usize,
+            call_max_wait: jmap.max_queue_wait.into_inner(),
             get_max_objects: jmap.get_max_results as usize,
             set_max_objects: jmap.set_max_objects as usize,
             upload_max_size: jmap.max_upload_size as usize,
@@ -29,14 +29,19 @@ use store::registry::bootstrap::Bootstrap;
 
 pub struct Scripting {
     pub untrusted_compiler: Compiler,
+    pub trusted_compiler: Compiler,
     pub untrusted_runtime: Runtime,
     pub trusted_runtime: Runtime,
     pub from_addr: IfBlock,
     pub from_name: IfBlock,
     pub return_path: IfBlock,
     pub sign: IfBlock,
-    pub trusted_scripts: AHashMap<String, Arc<Sieve>>,
-    pub untrusted_scripts: AHashMap<String, Arc<Sieve>>,
+}
+
+#[derive(Default)]
+pub struct SieveScripts {
+    pub trusted: AHashMap<String, Arc<Sieve>>,
+    pub untrusted: AHashMap<String, Arc<Sieve>>,
 }
 
 impl Scripting {
@@ -132,16 +137,46 @@ impl Scripting {
             .with_default_duplicate_expiry(trusted.duplicate_expiry.into_inner().as_secs());
         trusted_runtime.set_local_hostname(system.default_hostname.clone());
 
+        Scripting {
+            untrusted_compiler,
+            trusted_compiler,
+            untrusted_runtime,
+            trusted_runtime,
+            from_addr: bp.compile_expr(
+                ObjectType::SieveSystemScript.singleton(),
+                &trusted.ctx_default_from_address(),
+            ),
+            from_name: bp.compile_expr(
+                ObjectType::SieveSystemScript.singleton(),
+                &trusted.ctx_default_from_name(),
+            ),
+            return_path: bp.compile_expr(
+                ObjectType::SieveSystemScript.singleton(),
+                &trusted.ctx_default_return_path(),
+            ),
+            sign: bp.compile_expr(
+                ObjectType::SieveSystemScript.singleton(),
+                &trusted.ctx_dkim_sign_domain(),
+            ),
+        }
+    }
+}
+
+impl SieveScripts {
+    pub async fn parse(bp: &mut Bootstrap, scripting: &Scripting) -> Self {
         // Parse trusted scripts
-        let mut trusted_scripts = AHashMap::new();
+        let mut trusted = AHashMap::new();
         for script in bp.list_infallible::<SieveSystemScript>().await {
             if !script.object.is_active {
                 continue;
             }
 
-            match trusted_compiler.compile(script.object.contents.as_bytes()) {
+            match scripting
+                .trusted_compiler
+                .compile(script.object.contents.as_bytes())
+            {
                 Ok(compiled) => {
-                    trusted_scripts.insert(script.object.name, compiled.into());
+                    trusted.insert(script.object.name, compiled.into());
                 }
                 Err(err) => {
                     bp.build_error(
@@ -153,15 +188,18 @@ impl Scripting {
         }
 
         // Parse untrusted scripts
-        let mut untrusted_scripts = AHashMap::new();
+        let mut untrusted = AHashMap::new();
         for script in bp.list_infallible::<SieveUserScript>().await {
             if !script.object.is_active {
                 continue;
             }
 
-            match untrusted_compiler.compile(script.object.contents.as_bytes()) {
+            match scripting
+                .untrusted_compiler
+                .compile(script.object.contents.as_bytes())
+            {
                 Ok(compiled) => {
-                    untrusted_scripts.insert(script.object.name, compiled.into());
+                    untrusted.insert(script.object.name, compiled.into());
                 }
                 Err(err) => {
                     bp.build_error(
@@ -172,29 +210,7 @@ impl Scripting {
             }
         }
 
-        Scripting {
-            untrusted_compiler,
-            untrusted_runtime,
-            trusted_runtime,
-            untrusted_scripts,
-            trusted_scripts,
-            from_addr: bp.compile_expr(
-                ObjectType::SieveSystemScript.singleton(),
-                &trusted.ctx_default_from_address(),
-            ),
-            from_name: bp.compile_expr(
-                ObjectType::SieveSystemScript.singleton(),
-                &trusted.ctx_default_from_name(),
-            ),
-            return_path: bp.compile_expr(
-                ObjectType::SieveSystemScript.singleton(),
-                &trusted.ctx_default_return_path(),
-            ),
-            sign: bp.compile_expr(
-                ObjectType::SieveSystemScript.singleton(),
-                &trusted.ctx_dkim_sign_domain(),
-            ),
-        }
+        SieveScripts { trusted, untrusted }
     }
 }
 
@@ -202,14 +218,13 @@ impl Clone for Scripting {
     fn clone(&self) -> Self {
         Self {
             untrusted_compiler: self.untrusted_compiler.clone(),
+            trusted_compiler: self.trusted_compiler.clone(),
             untrusted_runtime: self.untrusted_runtime.clone(),
             trusted_runtime: self.trusted_runtime.clone(),
             from_addr: self.from_addr.clone(),
             from_name: self.from_name.clone(),
             return_path: self.return_path.clone(),
             sign: self.sign.clone(),
-            trusted_scripts: self.trusted_scripts.clone(),
-            untrusted_scripts: self.untrusted_scripts.clone(),
         }
     }
 }
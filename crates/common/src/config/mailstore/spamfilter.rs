@@ -16,17 +16,18 @@ use registry::schema::{
     enums::{ExpressionVariable, ModelSize},
     prelude::ObjectType,
     structs::{
-        self, SpamDnsblServer, SpamDnsblSettings, SpamFileExtension, SpamPyzor, SpamRule,
-        SpamSettings, SpamTag,
+        self, Rate, SpamDnsblServer, SpamDnsblSettings, SpamFileExtension, SpamPyzor, SpamRspamd,
+        SpamRule, SpamSettings, SpamTag,
     },
 };
 use std::{
     net::{IpAddr, SocketAddr},
+    sync::{Arc, atomic::AtomicU64},
     time::Duration,
 };
 use store::registry::{RegistryObject, bootstrap::Bootstrap};
 use tokio::net::lookup_host;
-use utils::{cache::CacheItemWeight, glob::GlobMap};
+use utils::{Client, cache::CacheItemWeight, glob::GlobMap};
 
 #[derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default)]
 pub enum SpamClassifier {
@@ -50,14 +51,20 @@ pub struct SpamFilterConfig {
     pub grey_list_expiry: Option<u64>,
 
     pub dnsbl: DnsBlConfig,
-    pub rules: SpamFilterRules,
-    pub lists: SpamFilterLists,
     pub pyzor: Option<PyzorConfig>,
+    pub rspamd: Option<RspamdConfig>,
     pub classifier: Option<ClassifierConfig>,
     pub scores: SpamFilterScoreConfig,
+    pub traps: SpamTrapConfig,
     pub spam_rules_url: Option<String>,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct SpamTrapConfig {
+    pub block_ip: bool,
+    pub penalty_for: Option<u64>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SpamFilterScoreConfig {
     pub reject_threshold: f32,
@@ -71,6 +78,9 @@ pub struct DnsBlConfig {
     pub max_domain_checks: usize,
     pub max_email_checks: usize,
     pub max_url_checks: usize,
+    pub max_failures: u64,
+    pub disable_for: Duration,
+    pub reject_threshold: Option<f32>,
     pub servers: Vec<DnsBlServer>,
 }
 
@@ -101,6 +111,7 @@ pub struct ClassifierConfig {
     pub auto_learn_spam_rbl_count: u32,
     pub hold_samples_for: u64,
     pub train_frequency: Option<u64>,
+    pub train_rate: Option<Rate>,
     pub log_scale: bool,
     pub l2_normalize: bool,
 }
@@ -123,6 +134,13 @@ pub struct PyzorConfig {
     pub ratio: f64,
 }
 
+#[derive(Debug, Clone)]
+pub struct RspamdConfig {
+    pub url: String,
+    pub client: Client,
+    pub replace_score: bool,
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct SpamFilterRules {
     pub url: Vec<IfBlock>,
@@ -181,6 +199,18 @@ pub struct DnsBlServer {
     pub zone: IfBlock,
     pub scope: Element,
     pub tags: IfBlock,
+    pub weight: f32,
+    pub timeout: Duration,
+    pub health: Arc<DnsBlHealth>,
+}
+
+// Counters are kept in memory and reset when the configuration is reloaded
+#[derive(Debug, Default)]
+pub struct DnsBlHealth {
+    pub queries: AtomicU64,
+    pub hits: AtomicU64,
+    pub failures: AtomicU64,
+    pub disabled_until: AtomicU64,
 }
 
 impl SpamFilterConfig {
@@ -192,9 +222,8 @@ impl SpamFilterConfig {
             card_is_ham: spam.trust_contacts,
             trusted_reply: spam.trust_replies,
             dnsbl: DnsBlConfig::parse(bp).await,
-            rules: SpamFilterRules::parse(bp).await,
-            lists: SpamFilterLists::parse(bp).await,
             pyzor: PyzorConfig::parse(bp).await,
+            rspamd: RspamdConfig::parse(bp).await,
             classifier: ClassifierConfig::parse(bp).await,
             scores: SpamFilterScoreConfig {
                 reject_threshold: spam.score_reject.into_inner() as f32,
@@ -202,6 +231,10 @@ impl SpamFilterConfig {
                 spam_threshold: spam.score_spam.into_inner() as f32,
             },
             grey_list_expiry: spam.greylist_for.map(|d| d.into_inner().as_secs()),
+            traps: SpamTrapConfig {
+                block_ip: spam.trap_block_ip,
+                penalty_for: spam.trap_penalty_for.map(|d| d.into_inner().as_secs()),
+            },
             spam_rules_url: spam.spam_filter_rules_url,
         }
     }
@@ -306,6 +339,9 @@ impl DnsBlConfig {
             max_domain_checks: dnsbl.domain_limit as usize,
             max_email_checks: dnsbl.email_limit as usize,
             max_url_checks: dnsbl.url_limit as usize,
+            max_failures: dnsbl.max_failures,
+            disable_for: dnsbl.disable_for.into_inner(),
+            reject_threshold: dnsbl.score_reject.map(|score| score.into_inner() as f32),
             servers,
         }
     }
@@ -319,6 +355,9 @@ impl DnsBlServer {
                 tags: bp.compile_expr(obj.id, &server.ctx_tag()),
                 scope: Element::Any,
                 id: server.name,
+                weight: server.weight.into_inner() as f32,
+                timeout: server.timeout.into_inner(),
+                health: Default::default(),
             }
             .into(),
             SpamDnsblServer::Url(server) if server.enable => DnsBlServer {
@@ -326,6 +365,9 @@ impl DnsBlServer {
                 tags: bp.compile_expr(obj.id, &server.ctx_tag()),
                 scope: Element::Url,
                 id: server.name,
+                weight: server.weight.into_inner() as f32,
+                timeout: server.timeout.into_inner(),
+                health: Default::default(),
             }
             .into(),
             SpamDnsblServer::Domain(server) if server.enable => DnsBlServer {
@@ -333,6 +375,9 @@ impl DnsBlServer {
                 tags: bp.compile_expr(obj.id, &server.ctx_tag()),
                 scope: Element::Domain,
                 id: server.name,
+                weight: server.weight.into_inner() as f32,
+                timeout: server.timeout.into_inner(),
+                health: Default::default(),
             }
             .into(),
             SpamDnsblServer::Email(server) if server.enable => DnsBlServer {
@@ -340,6 +385,9 @@ impl DnsBlServer {
                 tags: bp.compile_expr(obj.id, &server.ctx_tag()),
                 scope: Element::Email,
                 id: server.name,
+                weight: server.weight.into_inner() as f32,
+                timeout: server.timeout.into_inner(),
+                health: Default::default(),
             }
             .into(),
             SpamDnsblServer::Ip(server) if server.enable => DnsBlServer {
@@ -347,6 +395,9 @@ impl DnsBlServer {
                 tags: bp.compile_expr(obj.id, &server.ctx_tag()),
                 scope: Element::Ip,
                 id: server.name,
+                weight: server.weight.into_inner() as f32,
+                timeout: server.timeout.into_inner(),
+                health: Default::default(),
             }
             .into(),
             SpamDnsblServer::Header(server) if server.enable => DnsBlServer {
@@ -354,6 +405,9 @@ impl DnsBlServer {
                 tags: bp.compile_expr(obj.id, &server.ctx_tag()),
                 scope: Element::Header,
                 id: server.name,
+                weight: server.weight.into_inner() as f32,
+                timeout: server.timeout.into_inner(),
+                health: Default::default(),
             }
             .into(),
             SpamDnsblServer::Body(server) if server.enable => DnsBlServer {
@@ -361,6 +415,9 @@ impl DnsBlServer {
                 tags: bp.compile_expr(obj.id, &server.ctx_tag()),
                 scope: Element::Body,
                 id: server.name,
+                weight: server.weight.into_inner() as f32,
+                timeout: server.timeout.into_inner(),
+                health: Default::default(),
             }
             .into(),
             _ => None,
@@ -403,6 +460,15 @@ impl SpamFilterLists {
             );
         }
 
+        // Senders that recently hit a spam trap are scored unless a tag overrides it
+        if lists.scores.get("TRAP_SENDER").is_none() {
+            let spam = bp.setting_infallible::<SpamSettings>().await;
+            lists.scores.insert_entry(
+                "TRAP_SENDER".to_string(),
+                SpamFilterAction::Allow(spam.trap_penalty_score.into_inner() as f32),
+            );
+        }
+
         lists
     }
 }
@@ -449,6 +515,41 @@ impl PyzorConfig {
     }
 }
 
+impl RspamdConfig {
+    pub async fn parse(bp: &mut Bootstrap) -> Option<Self> {
+        let rspamd = bp.setting_infallible::<SpamRspamd>().await;
+
+        if !rspamd.enable {
+            return None;
+        }
+
+        match rspamd
+            .http_auth
+            .build_http_client(
+                rspamd.http_headers,
+                None,
+                rspamd.timeout,
+                rspamd.allow_invalid_certs,
+            )
+            .await
+        {
+            Ok(client) => RspamdConfig {
+                url: format!("{}/checkv2", rspamd.url.trim_end_matches('/')),
+                client,
+                replace_score: rspamd.replace_score,
+            }
+            .into(),
+            Err(err) => {
+                bp.build_error(
+                    ObjectType::SpamRspamd.singleton(),
+                    format!("Unable to build HTTP client: {err}"),
+                );
+                None
+            }
+        }
+    }
+}
+
 impl ClassifierConfig {
     pub async fn parse(bp: &mut Bootstrap) -> Option<Self> {
         let classifier = bp.setting_infallible::<structs::SpamClassifier>().await;
@@ -485,6 +586,7 @@ impl ClassifierConfig {
             min_ham_samples: classifier.min_ham_samples,
             min_spam_samples: classifier.min_spam_samples,
             train_frequency: classifier.train_frequency.map(|d| d.into_inner().as_secs()),
+            train_rate: classifier.train_rate_limit,
             log_scale,
             l2_normalize,
         }
@@ -15,12 +15,14 @@ use crate::{
     },
 };
 use arc_swap::ArcSwap;
+use audit::AuditLogConfig;
 use groupware::GroupwareConfig;
 use hyper::HeaderMap;
 use ring::signature::{EcdsaKeyPair, RsaKeyPair};
 use store::registry::bootstrap::Bootstrap;
 use telemetry::Metrics;
 
+pub mod audit;
 pub mod groupware;
 pub mod inner;
 pub mod mailstore;
@@ -88,6 +90,7 @@ impl Core {
             spam: SpamFilterConfig::parse(bp).await,
             email: EmailConfig::parse(bp).await,
             groupware: GroupwareConfig::parse(bp).await,
+            audit: AuditLogConfig::parse(bp).await,
             storage,
         }
     }
@@ -15,16 +15,21 @@ use crate::{
         security::Security,
     },
 };
+use ahash::AHashMap;
 use registry::schema::{
-    enums::{AcmeChallengeType, ClusterTaskType, ProviderInfo, ServiceProtocol},
-    prelude::ObjectType,
+    enums::{
+        AcmeChallengeType, CaptchaProvider, ClusterTaskType, ExpressionVariable, ProviderInfo,
+        ServiceProtocol,
+    },
+    prelude::{ObjectType, Property},
     structs::{
         self, AcmeProvider, Asn, ClusterTaskGroup, HttpForm, MailExchanger, Rate, Service,
         SystemSettings, TaskManager,
     },
 };
 use std::{str::FromStr, time::Duration};
-use utils::map::vec_map::VecMap;
+use types::id::Id;
+use utils::{map::vec_map::VecMap, template::Template};
 
 #[derive(Clone)]
 pub struct Network {
@@ -38,6 +43,7 @@ pub struct Network {
     pub task_manager: TaskManager,
     pub has_acme_tls_challenge: bool,
     pub has_acme_http_challenge: bool,
+    pub default_acme_provider_id: Option<Id>,
     pub info: NetworkInfo,
 }
 
@@ -46,6 +52,7 @@ pub struct NetworkInfo {
     pub pacc: Pacc,
     pub mxs: Vec<MailExchanger>,
     pub services: VecMap<ServiceProtocol, Service>,
+    pub provider_info: VecMap<ProviderInfo, String>,
 }
 
 #[derive(Clone)]
@@ -61,20 +68,67 @@ pub struct Http {
     pub url_https: String,
     pub url_http: String,
     pub allowed_endpoint: IfBlock,
+    pub asn_geo_lookup: bool,
     pub response_headers: Vec<(hyper::header::HeaderName, hyper::header::HeaderValue)>,
     pub use_forwarded: bool,
+    pub http2: bool,
+    pub h2c: bool,
+    pub http2_max_concurrent_streams: u32,
+    pub compression: Option<HttpCompression>,
+    pub cors: Cors,
+    pub health: HealthCheck,
+}
+
+#[derive(Clone)]
+pub struct Cors {
+    pub allowed_origins: IfBlock,
+    pub allowed_methods: IfBlock,
+    pub allowed_headers: IfBlock,
+    pub allow_credentials: IfBlock,
+    pub allow_any_origin: bool,
+    pub max_age: u64,
+}
+
+#[derive(Clone)]
+pub struct HealthCheck {
+    pub store_timeout: Duration,
+    pub queue_threshold: Option<u64>,
+    pub degraded_weight: u64,
+}
+
+#[derive(Clone)]
+pub struct HttpCompression {
+    pub min_size: usize,
+    pub content_types: Vec<String>,
 }
 
 #[derive(Clone)]
 pub struct ContactForm {
-    pub rcpt_to: Vec<String>,
+    pub default: Option<FormRoute>,
+    pub forms: AHashMap<String, FormRoute>,
     pub max_size: usize,
-    pub rate: Option<Rate>,
     pub validate_domain: bool,
     pub from_email: FieldOrDefault,
     pub from_subject: FieldOrDefault,
     pub from_name: FieldOrDefault,
     pub field_honey_pot: Option<String>,
+    pub captcha: Option<Captcha>,
+}
+
+#[derive(Clone)]
+pub struct FormRoute {
+    pub name: String,
+    pub rcpt_to: Vec<String>,
+    pub rate: Option<Rate>,
+    pub subject: Option<Template<String>>,
+    pub body: Option<Template<String>>,
+}
+
+#[derive(Clone)]
+pub struct Captcha {
+    pub verify_url: &'static str,
+    pub response_field: &'static str,
+    pub secret: String,
 }
 
 #[derive(Clone)]
@@ -125,7 +179,7 @@ impl ContactForm {
 
         if !form.enable {
             return None;
-        } else if form.deliver_to.is_empty() {
+        } else if form.deliver_to.is_empty() && form.forms.is_empty() {
             bp.build_error(
                 ObjectType::HttpForm.singleton(),
                 "Contact form is enabled but no recipient addresses are configured",
@@ -133,8 +187,83 @@ impl ContactForm {
             return None;
         }
 
+        let captcha = match form.captcha_provider {
+            CaptchaProvider::Disabled => None,
+            CaptchaProvider::HCaptcha => {
+                Some(("https://api.hcaptcha.com/siteverify", "h-captcha-response"))
+            }
+            CaptchaProvider::Turnstile => Some((
+                "https://challenges.cloudflare.com/turnstile/v0/siteverify",
+                "cf-turnstile-response",
+            )),
+        };
+        let captcha = if let Some((verify_url, response_field)) = captcha {
+            // Disable the form rather than accepting unverified submissions
+            match form.captcha_secret.secret().await {
+                Ok(Some(secret)) => Some(Captcha {
+                    verify_url,
+                    response_field,
+                    secret: secret.into_owned(),
+                }),
+                Ok(None) => {
+                    bp.build_error(
+                        ObjectType::HttpForm.singleton(),
+                        "CAPTCHA verification is enabled but no secret key is configured",
+                    );
+                    return None;
+                }
+                Err(err) => {
+                    bp.invalid_property(
+                        ObjectType::HttpForm.singleton(),
+                        Property::CaptchaSecret,
+                        err,
+                    );
+                    return None;
+                }
+            }
+        } else {
+            None
+        };
+
+        let mut forms = AHashMap::with_capacity(form.forms.len());
+        for (name, route) in form.forms {
+            let mut parse_template =
+                |template: Option<String>| match template.as_deref().map(Template::parse) {
+                    Some(Ok(template)) => Some(template),
+                    Some(Err(err)) => {
+                        bp.invalid_property(
+                            ObjectType::HttpForm.singleton(),
+                            Property::Forms,
+                            format!("Invalid template for form {name:?}: {err}"),
+                        );
+                        None
+                    }
+                    None => None,
+                };
+            let subject = parse_template(route.subject);
+            let body = parse_template(route.body);
+
+            forms.insert(
+                name.clone(),
+                FormRoute {
+                    name,
+                    rcpt_to: route.deliver_to.into_inner(),
+                    rate: route.rate_limit.or_else(|| form.rate_limit.clone()),
+                    subject,
+                    body,
+                },
+            );
+        }
+
         Some(ContactForm {
-            rcpt_to: form.deliver_to.into_inner(),
+            default: (!form.deliver_to.is_empty()).then(|| FormRoute {
+                name: String::new(),
+                rcpt_to: form.deliver_to.into_inner(),
+                rate: form.rate_limit,
+                subject: None,
+                body: None,
+            }),
+            forms,
             max_size: form.max_size as usize,
             validate_domain: form.validate_domain,
             from_email: FieldOrDefault {
@@ -150,9 +279,17 @@ impl ContactForm {
                 default: form.default_name,
             },
             field_honey_pot: form.field_honey_pot,
-            rate: form.rate_limit,
+            captcha,
         })
     }
+
+    pub fn route(&self, name: &str) -> Option<&FormRoute> {
+        if name.is_empty() {
+            self.default.as_ref()
+        } else {
+            self.forms.get(name)
+        }
+    }
 }
 
 impl Network {
@@ -258,7 +395,7 @@ impl Network {
             }
         }
 
-        for (tag, text) in system.provider_info {
+        for (tag, text) in system.provider_info.clone() {
             match tag {
                 ProviderInfo::ProviderName => pacc.info.provider.name = text,
                 ProviderInfo::ProviderShortName => pacc.info.provider.short_name = Some(text),
@@ -309,6 +446,7 @@ impl Network {
                         logo[0].height = text.parse().ok();
                     }
                 }
+                ProviderInfo::BrandColor => {}
             }
         }
 
@@ -328,9 +466,11 @@ impl Network {
             task_manager: bp.setting_infallible::<TaskManager>().await,
             has_acme_tls_challenge,
             has_acme_http_challenge,
+            default_acme_provider_id: system.default_acme_provider_id,
             info: NetworkInfo {
                 mxs: system.mail_exchangers.into_iter().collect(),
                 services: system.services,
+                provider_info: system.provider_info,
                 pacc: Pacc { prefix, suffix },
             },
         };
@@ -390,32 +530,13 @@ impl Http {
             })
             .unwrap_or_default();
 
-        // Add permissive CORS headers
+        // Allow any origin when no CORS origins are configured
         #[cfg(feature = "dev_mode")]
         let use_permissive_cors = true;
 
         #[cfg(not(feature = "dev_mode"))]
         let use_permissive_cors = http.use_permissive_cors;
 
-        if use_permissive_cors {
-            http_headers.push((
-                hyper::header::ACCESS_CONTROL_ALLOW_ORIGIN,
-                hyper::header::HeaderValue::from_static("*"),
-            ));
-            http_headers.push((
-                hyper::header::ACCESS_CONTROL_ALLOW_HEADERS,
-                hyper::header::HeaderValue::from_static(
-                    "Authorization, Content-Type, Accept, X-Requested-With",
-                ),
-            ));
-            http_headers.push((
-                hyper::header::ACCESS_CONTROL_ALLOW_METHODS,
-                hyper::header::HeaderValue::from_static(
-                    "POST, GET, PATCH, PUT, DELETE, HEAD, OPTIONS",
-                ),
-            ));
-        }
-
         // Add HTTP Strict Transport Security
         if http.enable_hsts {
             http_headers.push((
@@ -424,15 +545,70 @@ impl Http {
             ));
         }
 
+        let allowed_endpoint =
+            bp.compile_expr(ObjectType::Http.singleton(), &http.ctx_allowed_endpoints());
+        let cors = Cors {
+            allowed_origins: bp.compile_expr(
+                ObjectType::Http.singleton(),
+                &http.ctx_cors_allowed_origins(),
+            ),
+            allowed_methods: bp.compile_expr(
+                ObjectType::Http.singleton(),
+                &http.ctx_cors_allowed_methods(),
+            ),
+            allowed_headers: bp.compile_expr(
+                ObjectType::Http.singleton(),
+                &http.ctx_cors_allowed_headers(),
+            ),
+            allow_credentials: bp.compile_expr(
+                ObjectType::Http.singleton(),
+                &http.ctx_cors_allow_credentials(),
+            ),
+            allow_any_origin: use_permissive_cors,
+            max_age: http.cors_max_age.into_inner().as_secs(),
+        };
+
+        // Only look up the ASN and country of the client when an expression needs them
+        let asn_geo_lookup = [
+            &allowed_endpoint,
+            &cors.allowed_origins,
+            &cors.allowed_methods,
+            &cors.allowed_headers,
+            &cors.allow_credentials,
+        ]
+        .into_iter()
+        .any(|expr| {
+            expr.has_variable(ExpressionVariable::Asn)
+                || expr.has_variable(ExpressionVariable::Country)
+        });
+
         Http {
             url_https: format!("https://{}", server_name),
             url_http: format!("http://{}", server_name),
-            allowed_endpoint: bp
-                .compile_expr(ObjectType::Http.singleton(), &http.ctx_allowed_endpoints()),
+            allowed_endpoint,
+            asn_geo_lookup,
             rate_authenticated: http.rate_limit_authenticated,
             rate_anonymous: http.rate_limit_anonymous,
             response_headers: http_headers,
             use_forwarded: http.use_x_forwarded,
+            http2: http.enable_http2,
+            h2c: http.enable_h2c,
+            http2_max_concurrent_streams: http.http2_max_concurrent_streams as u32,
+            cors,
+            compression: http.enable_compression.then(|| HttpCompression {
+                min_size: http.compression_min_size as usize,
+                content_types: http
+                    .compression_content_types
+                    .into_inner()
+                    .into_iter()
+                    .map(|content_type| content_type.to_ascii_lowercase())
+                    .collect(),
+            }),
+            health: HealthCheck {
+                store_timeout: http.health_store_timeout.into_inner(),
+                queue_threshold: http.health_queue_threshold,
+                degraded_weight: http.health_degraded_weight,
+            },
         }
     }
 }
@@ -502,6 +678,27 @@ impl ClusterRoles {
             ClusterTaskType::TaskScheduler => self.task_scheduler = enabled,
         }
     }
+
+    pub fn enabled_tasks(&self) -> Vec<ClusterTaskType> {
+        [
+            (ClusterTaskType::StoreMaintenance, self.store_maintenance),
+            (
+                ClusterTaskType::AccountMaintenance,
+                self.account_maintenance,
+            ),
+            (ClusterTaskType::MetricsCalculate, self.metrics_calculate),
+            (ClusterTaskType::MetricsPush, self.metrics_push),
+            (ClusterTaskType::PushNotifications, self.push_notifications),
+            (ClusterTaskType::SearchIndexing, self.search_indexing),
+            (ClusterTaskType::SpamClassifierTraining, self.spam_training),
+            (ClusterTaskType::OutboundMta, self.outbound_mta),
+            (ClusterTaskType::TaskQueueProcessing, self.task_manager),
+            (ClusterTaskType::TaskScheduler, self.task_scheduler),
+        ]
+        .into_iter()
+        .filter_map(|(task_type, enabled)| enabled.then_some(task_type))
+        .collect()
+    }
 }
 
 impl Default for ClusterRoles {
@@ -14,17 +14,19 @@ use crate::{
 };
 use registry::{
     schema::{
-        enums::{NetworkListenerProtocol, TlsCipherSuite, TlsVersion},
+        enums::{NetworkListenerProtocol, TlsCipherSuite, TlsClientAuth, TlsVersion},
         prelude::{ObjectType, SocketAddr},
-        structs::{ClusterListenerGroup, NetworkListener, SystemSettings},
+        structs::{ClusterListenerGroup, Http, NetworkListener, SystemSettings},
     },
     types::{id::ObjectId, map::Map},
 };
 use rustls::{
-    ALL_VERSIONS, ServerConfig, SupportedCipherSuite,
+    ALL_VERSIONS, RootCertStore, ServerConfig, SupportedCipherSuite,
     crypto::aws_lc_rs::{ALL_CIPHER_SUITES, cipher_suite::*, default_provider},
+    server::WebPkiClientVerifier,
 };
-use std::{str::FromStr, sync::Arc};
+use rustls_pemfile::certs;
+use std::{io::Cursor, str::FromStr, sync::Arc};
 use store::registry::{RegistryObject, bootstrap::Bootstrap};
 use tokio::net::TcpSocket;
 use tokio_rustls::TlsAcceptor;
@@ -103,6 +105,7 @@ impl Listeners {
             NetworkListenerProtocol::Imap => ServerProtocol::Imap,
             NetworkListenerProtocol::Pop3 => ServerProtocol::Pop3,
             NetworkListenerProtocol::ManageSieve => ServerProtocol::ManageSieve,
+            NetworkListenerProtocol::AgentCheck => ServerProtocol::AgentCheck,
         };
 
         // Build listeners
@@ -187,6 +190,7 @@ impl Listeners {
 
     pub async fn parse_tcp_acceptors(&mut self, bp: &mut Bootstrap, inner: Arc<Inner>) {
         let resolver = Arc::new(CertificateResolver::new(inner.clone()));
+        let enable_http2 = bp.setting_infallible::<Http>().await.enable_http2;
 
         for listener in std::mem::take(&mut self.parsed_listeners) {
             let id = listener.id;
@@ -253,8 +257,49 @@ impl Listeners {
                         .collect();
                 }
 
+                // Build client certificate verifier
+                let provider = Arc::new(provider);
+                let client_verifier = if listener.tls_client_auth != TlsClientAuth::Disabled {
+                    let mut roots = RootCertStore::empty();
+                    for cert in certs(&mut Cursor::new(
+                        listener
+                            .tls_client_ca_certificates
+                            .as_deref()
+                            .unwrap_or_default(),
+                    )) {
+                        if let Err(err) = cert
+                            .map_err(|err| err.to_string())
+                            .and_then(|cert| roots.add(cert).map_err(|err| err.to_string()))
+                        {
+                            bp.build_error(
+                                id,
+                                format!("Failed to parse client CA certificate: {err}"),
+                            );
+                            return;
+                        }
+                    }
+
+                    let mut builder =
+                        WebPkiClientVerifier::builder_with_provider(roots.into(), provider.clone());
+                    if listener.tls_client_auth == TlsClientAuth::Optional {
+                        builder = builder.allow_unauthenticated();
+                    }
+                    match builder.build() {
+                        Ok(verifier) => Some(verifier),
+                        Err(err) => {
+                            bp.build_error(
+                                id,
+                                format!("Failed to build client certificate verifier: {err}"),
+                            );
+                            return;
+                        }
+                    }
+                } else {
+                    None
+                };
+
                 // Build server config
-                let mut server_config = match ServerConfig::builder_with_provider(provider.into())
+                let mut server_config = match ServerConfig::builder_with_provider(provider)
                     .with_protocol_versions(if tls_v3 == tls_v2 {
                         ALL_VERSIONS
                     } else if tls_v3 {
@@ -262,9 +307,12 @@ impl Listeners {
                     } else {
                         TLS12_VERSION
                     }) {
-                    Ok(server_config) => server_config
-                        .with_no_client_auth()
-                        .with_cert_resolver(resolver.clone()),
+                    Ok(server_config) => if let Some(client_verifier) = client_verifier {
+                        server_config.with_client_cert_verifier(client_verifier)
+                    } else {
+                        server_config.with_no_client_auth()
+                    }
+                    .with_cert_resolver(resolver.clone()),
                     Err(err) => {
                         bp.build_error(id, format!("Failed to build TLS server config: {err}"));
                         return;
@@ -273,12 +321,22 @@ impl Listeners {
 
                 server_config.ignore_client_order = listener.tls_ignore_client_order;
 
+                // Advertise HTTP/2 support using ALPN
+                if enable_http2 && matches!(listener.protocol, NetworkListenerProtocol::Http) {
+                    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+                }
+
                 // Build acceptor
                 let default_config = Arc::new(server_config);
                 TcpAcceptor::Tls {
                     acceptor: TlsAcceptor::from(default_config.clone()),
                     config: default_config,
                     implicit: listener.tls_implicit,
+                    client_cert_principal: if listener.tls_client_auth != TlsClientAuth::Disabled {
+                        listener.tls_client_cert_principal.into_inner()
+                    } else {
+                        vec![]
+                    },
                 }
             } else {
                 TcpAcceptor::Plain
@@ -58,6 +58,7 @@ pub enum ServerProtocol {
     Pop3,
     Http,
     ManageSieve,
+    AgentCheck,
 }
 
 impl ServerProtocol {
@@ -69,6 +70,7 @@ impl ServerProtocol {
             ServerProtocol::Http => "http",
             ServerProtocol::Pop3 => "pop3",
             ServerProtocol::ManageSieve => "managesieve",
+            ServerProtocol::AgentCheck => "agent-check",
         }
     }
 }
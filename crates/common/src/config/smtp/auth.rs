@@ -38,6 +38,7 @@ pub struct MailAuthConfig {
 pub struct DkimAuthConfig {
     pub verify: IfBlock,
     pub sign: IfBlock,
+    pub resign: IfBlock,
     pub strict: bool,
 }
 
@@ -93,6 +94,10 @@ impl MailAuthConfig {
                     ObjectType::SenderAuth.singleton(),
                     &auth.ctx_dkim_sign_domain(),
                 ),
+                resign: bp.compile_expr(
+                    ObjectType::SenderAuth.singleton(),
+                    &auth.ctx_dkim_resign_domain(),
+                ),
                 strict: auth.dkim_strict,
             },
             arc: ArcAuthConfig {
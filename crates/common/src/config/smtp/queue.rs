@@ -17,19 +17,21 @@ use directory::Credentials;
 use mail_auth::IpLookupStrategy;
 use registry::schema::{
     enums::{self, ExpressionConstant, ExpressionVariable, MtaRequiredOrOptional},
-    prelude::ObjectType,
+    prelude::{ObjectType, Property},
     structs::{
         DsnReportSettings, MtaConnectionStrategy, MtaDeliveryExpiration, MtaDeliverySchedule,
         MtaDeliveryScheduleIntervalsOrDefault, MtaInboundThrottle, MtaOutboundStrategy,
-        MtaOutboundThrottle, MtaQueueQuota, MtaRoute, MtaTlsStrategy, MtaVirtualQueue,
+        MtaOutboundThrottle, MtaQueueQuota, MtaRoute, MtaTlsStrategy, MtaVirtualQueue, Rate,
     },
 };
 use std::{
     fmt::Display,
     hash::{Hash, Hasher},
     net::IpAddr,
+    str::FromStr,
     time::Duration,
 };
+use utils::template::Template;
 
 #[derive(
     Debug,
@@ -92,6 +94,22 @@ pub struct Dsn {
     pub name: IfBlock,
     pub address: IfBlock,
     pub sign: IfBlock,
+    pub suppress: IfBlock,
+    pub suppress_list_traffic: bool,
+    pub rate: Option<Rate>,
+    pub delay_subject: IfBlock,
+    pub delay_template: Option<Template<DsnTemplateVariable>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DsnTemplateVariable {
+    Sender,
+    QueueId,
+    Recipients,
+    Address,
+    Host,
+    Reason,
+    Expires,
 }
 
 #[derive(Clone, Debug)]
@@ -211,6 +229,18 @@ impl QueueConfig {
     pub async fn parse(bp: &mut Bootstrap) -> Self {
         let st = bp.setting_infallible::<MtaOutboundStrategy>().await;
         let dsn = bp.setting_infallible::<DsnReportSettings>().await;
+        let delay_template = match dsn.delay_template.as_deref().map(Template::parse) {
+            Some(Ok(template)) => Some(template),
+            Some(Err(err)) => {
+                bp.invalid_property(
+                    ObjectType::DsnReportSettings.singleton(),
+                    Property::DelayTemplate,
+                    format!("Invalid template: {err}"),
+                );
+                None
+            }
+            None => None,
+        };
 
         let mut queue = QueueConfig {
             route: bp.compile_expr(ObjectType::MtaOutboundStrategy.singleton(), &st.ctx_route()),
@@ -236,6 +266,17 @@ impl QueueConfig {
                     ObjectType::DsnReportSettings.singleton(),
                     &dsn.ctx_dkim_sign_domain(),
                 ),
+                suppress: bp.compile_expr(
+                    ObjectType::DsnReportSettings.singleton(),
+                    &dsn.ctx_suppress(),
+                ),
+                suppress_list_traffic: dsn.suppress_list_traffic,
+                rate: dsn.rate_limit,
+                delay_subject: bp.compile_expr(
+                    ObjectType::DsnReportSettings.singleton(),
+                    &dsn.ctx_delay_subject(),
+                ),
+                delay_template,
             },
             inbound_limiters: QueueRateLimiters::parse_inbound(bp).await,
             outbound_limiters: QueueRateLimiters::parse_outbound(bp).await,
@@ -627,6 +668,23 @@ impl QueueQuotas {
     }
 }
 
+impl FromStr for DsnTemplateVariable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sender" => Ok(DsnTemplateVariable::Sender),
+            "queue_id" => Ok(DsnTemplateVariable::QueueId),
+            "recipients" => Ok(DsnTemplateVariable::Recipients),
+            "address" => Ok(DsnTemplateVariable::Address),
+            "host" => Ok(DsnTemplateVariable::Host),
+            "reason" => Ok(DsnTemplateVariable::Reason),
+            "expires" => Ok(DsnTemplateVariable::Expires),
+            _ => Err(format!("Unknown DSN template variable: {}", s)),
+        }
+    }
+}
+
 impl<'x> TryFrom<Variable<'x>> for RequireOptional {
     type Error = ();
 
@@ -12,8 +12,9 @@ use crate::expr::{
 };
 use ahash::AHashSet;
 use hyper::HeaderMap;
+use regex::Regex;
 use registry::schema::{
-    enums::{self, ExpressionConstant, MtaStage},
+    enums::{self, AddressRewriteScope, DuplicateAction, ExpressionConstant, MtaStage},
     prelude::ObjectType,
     structs::{
         MtaExtensions, MtaHook, MtaInboundSession, MtaMilter, MtaStageAuth, MtaStageConnect,
@@ -44,6 +45,7 @@ pub struct SessionConfig {
 
     pub milters: Vec<Milter>,
     pub hooks: Vec<MTAHook>,
+    pub address_rewrites: Vec<AddressRewrite>,
 }
 
 #[derive(Clone)]
@@ -98,6 +100,8 @@ pub struct Rcpt {
     pub errors_max: IfBlock,
     pub errors_wait: IfBlock,
     pub max_recipients: IfBlock,
+    pub max_session_recipients: IfBlock,
+    pub enforce_quota: IfBlock,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -122,6 +126,17 @@ pub struct Data {
     pub add_message_id: IfBlock,
     pub add_date: IfBlock,
     pub add_delivered_to: bool,
+    pub auto_bcc: IfBlock,
+    pub duplicate_action: DuplicateAction,
+    pub duplicate_window: u64,
+}
+
+#[derive(Clone)]
+pub struct AddressRewrite {
+    pub description: String,
+    pub scope: Vec<AddressRewriteScope>,
+    pub pattern: Regex,
+    pub replacement: String,
 }
 
 #[derive(Clone)]
@@ -185,6 +200,29 @@ impl SessionConfig {
         let ext = bp.setting_infallible::<MtaExtensions>().await;
 
         let mut hooks = Vec::new();
+        let mut address_rewrites = Vec::new();
+
+        for rule in session.address_rewrites.values().filter(|rule| rule.enable) {
+            match Regex::new(&rule.pattern) {
+                Ok(pattern) => {
+                    address_rewrites.push(AddressRewrite {
+                        description: rule.description.clone(),
+                        scope: rule.scope.as_slice().to_vec(),
+                        pattern,
+                        replacement: rule.replacement.clone(),
+                    });
+                }
+                Err(err) => {
+                    bp.build_error(
+                        ObjectType::MtaInboundSession.singleton(),
+                        format!(
+                            "Invalid pattern {:?} in address rewrite rule {:?}: {}",
+                            rule.pattern, rule.description, err
+                        ),
+                    );
+                }
+            }
+        }
 
         for hook in bp.list_infallible::<MtaHook>().await {
             let id = hook.id;
@@ -296,6 +334,14 @@ impl SessionConfig {
                     ObjectType::MtaStageRcpt.singleton(),
                     &rcpt.ctx_max_recipients(),
                 ),
+                max_session_recipients: bp.compile_expr(
+                    ObjectType::MtaStageRcpt.singleton(),
+                    &rcpt.ctx_max_session_recipients(),
+                ),
+                enforce_quota: bp.compile_expr(
+                    ObjectType::MtaStageRcpt.singleton(),
+                    &rcpt.ctx_enforce_quota(),
+                ),
             },
             data: Data {
                 script: bp.compile_expr(ObjectType::MtaStageData.singleton(), &data.ctx_script()),
@@ -340,6 +386,10 @@ impl SessionConfig {
                     &data.ctx_add_date_header(),
                 ),
                 add_delivered_to: data.add_delivered_to_header,
+                auto_bcc: bp
+                    .compile_expr(ObjectType::MtaStageData.singleton(), &data.ctx_auto_bcc()),
+                duplicate_action: data.duplicate_action,
+                duplicate_window: data.duplicate_window.as_secs(),
             },
             extensions: Extensions {
                 pipelining: bp
@@ -413,8 +463,30 @@ impl SessionConfig {
                 })
                 .collect(),
             hooks,
+            address_rewrites,
         }
     }
+
+    // Rules are evaluated in order and only the first match is applied, so
+    // a rewritten address is never fed back into the table
+    pub fn rewrite_address(
+        &self,
+        address: &str,
+        scope: AddressRewriteScope,
+    ) -> Option<(&AddressRewrite, String)> {
+        self.address_rewrites
+            .iter()
+            .filter(|rule| rule.scope.contains(&scope))
+            .find(|rule| rule.pattern.is_match(address))
+            .map(|rule| {
+                (
+                    rule,
+                    rule.pattern
+                        .replace(address, rule.replacement.as_str())
+                        .into_owned(),
+                )
+            })
+    }
 }
 
 #[derive(Default)]
@@ -19,6 +19,8 @@ pub struct Storage {
     pub registry: RegistryStore,
     pub data: Store,
     pub blob: BlobStore,
+    pub queue: Option<Store>,
+    pub queue_blob: Option<BlobStore>,
     pub search: SearchStore,
     pub memory: InMemoryStore,
     pub metrics: Store,
@@ -33,6 +35,7 @@ impl Storage {
         let memory = InMemoryStore::build(bp).await.unwrap_or_default();
         let directory = Directories::build(bp).await;
         let search = SearchStore::build(bp).await.unwrap_or_default();
+        let queue = Store::build_queue(bp).await;
 
         if let Err(err) = search.create_indexes().await {
             bp.build_warning(
@@ -45,6 +48,8 @@ impl Storage {
             registry: bp.registry.clone(),
             data: bp.data_store.clone(),
             blob: BlobStore::build(bp).await.unwrap_or_default(),
+            queue_blob: queue.clone().map(BlobStore::Store),
+            queue,
             search,
             coordinator: Coordinator::build(bp, &memory).await.unwrap_or_default(),
             memory,
@@ -13,15 +13,16 @@ use opentelemetry_otlp::{
     LogExporter, MetricExporter, SpanExporter, WithExportConfig, WithHttpConfig,
 };
 use opentelemetry_sdk::{Resource, metrics::Temporality};
-use opentelemetry_semantic_conventions::resource::SERVICE_VERSION;
+use opentelemetry_semantic_conventions::resource::{SERVICE_INSTANCE_ID, SERVICE_VERSION};
 use registry::schema::{
     enums::{EventPolicy, LogRotateFrequency},
     prelude::ObjectType,
-    structs::{self, EventTracingLevel, MetricsPrometheus, Tracer, WebHook},
+    structs::{self, EventTracingLevel, MetricsPrometheus, SystemSettings, Tracer, WebHook},
 };
 use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
 use store::registry::bootstrap::Bootstrap;
 use trc::{EventType, Level, MetricType, TelemetryEvent, ipc::subscriber::Interests};
+use utils::template::Template;
 
 #[derive(Debug)]
 pub struct TelemetrySubscriber {
@@ -36,6 +37,7 @@ pub struct TelemetrySubscriber {
 pub enum TelemetrySubscriberType {
     ConsoleTracer(ConsoleTracer),
     LogTracer(LogTracer),
+    SyslogTracer(SyslogTracer),
     OtelTracer(OtelTracer),
     Webhook(WebhookTracer),
     #[cfg(unix)]
@@ -80,6 +82,20 @@ pub struct LogTracer {
     pub multiline: bool,
 }
 
+#[derive(Debug)]
+pub struct SyslogTracer {
+    pub address: SyslogAddress,
+    pub facility: u8,
+    pub hostname: String,
+}
+
+#[derive(Debug)]
+pub enum SyslogAddress {
+    Unix(String),
+    Udp(String),
+    Tcp(String),
+}
+
 #[derive(Debug)]
 pub struct WebhookTracer {
     pub url: String,
@@ -87,10 +103,29 @@ pub struct WebhookTracer {
     pub timeout: Duration,
     pub throttle: Duration,
     pub discard_after: Duration,
+    pub max_retries: u32,
+    pub retry_backoff: Duration,
+    pub max_batch_size: usize,
+    pub dead_letter_url: Option<String>,
+    pub template: Option<Template<WebhookTemplateVariable>>,
     pub tls_allow_invalid_certs: bool,
     pub headers: HeaderMap,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WebhookTemplateVariable {
+    Events,
+    Total,
+    Event,
+    Id,
+    Type,
+    Level,
+    Description,
+    CreatedAt,
+    Data,
+    Comma,
+}
+
 // SPDX-SnippetBegin
 // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
 // SPDX-License-Identifier: LicenseRef-SEL
@@ -132,6 +167,10 @@ pub struct Metrics {
 #[derive(Debug, Clone, Default)]
 pub struct PrometheusMetrics {
     pub auth: Option<String>,
+    pub domain_breakdown: bool,
+    pub tenant_breakdown: bool,
+    pub max_breakdown_labels: usize,
+    pub otlp_ingest: bool,
 }
 
 impl Telemetry {
@@ -255,6 +294,36 @@ impl Tracers {
                             continue;
                         }
                     }
+                    Tracer::Syslog(tracer) if tracer.enable => {
+                        level = Level::from(tracer.level);
+                        lossy = tracer.lossy;
+                        events = tracer.events;
+                        events_policy = tracer.events_policy;
+                        enable = tracer.enable;
+
+                        let address = if let Some(address) = tracer.address.strip_prefix("udp://") {
+                            SyslogAddress::Udp(address.to_string())
+                        } else if let Some(address) = tracer.address.strip_prefix("tcp://") {
+                            SyslogAddress::Tcp(address.to_string())
+                        } else {
+                            SyslogAddress::Unix(
+                                tracer
+                                    .address
+                                    .strip_prefix("unix://")
+                                    .unwrap_or(&tracer.address)
+                                    .to_string(),
+                            )
+                        };
+
+                        TelemetrySubscriberType::SyslogTracer(SyslogTracer {
+                            address,
+                            facility: tracer.facility as u8,
+                            hostname: bp
+                                .setting_infallible::<SystemSettings>()
+                                .await
+                                .default_hostname,
+                        })
+                    }
                     Tracer::OtelHttp(tracer) if tracer.enable => {
                         level = Level::from(tracer.level);
                         lossy = tracer.lossy;
@@ -387,7 +456,8 @@ impl Tracers {
                 // Parse disabled events
                 let exclude_event = match &tracer.typ {
                     TelemetrySubscriberType::ConsoleTracer(_) => None,
-                    TelemetrySubscriberType::LogTracer(_) => {
+                    TelemetrySubscriberType::LogTracer(_)
+                    | TelemetrySubscriberType::SyslogTracer(_) => {
                         EventType::Telemetry(TelemetryEvent::LogError).into()
                     }
                     TelemetrySubscriberType::OtelTracer(_) => {
@@ -475,6 +545,15 @@ impl Tracers {
                     }
                 };
 
+                let template = match hook.payload_template.as_deref().map(Template::parse) {
+                    Some(Ok(template)) => Some(template),
+                    Some(Err(err)) => {
+                        bp.build_error(id, format!("Invalid payload template: {}", err));
+                        continue;
+                    }
+                    None => None,
+                };
+
                 // Build tracer
                 let mut tracer = TelemetrySubscriber {
                     id: format!("w_{}", id.id()),
@@ -500,6 +579,11 @@ impl Tracers {
                             .into_owned(),
                         throttle: hook.throttle.into_inner(),
                         discard_after: hook.discard_after.into_inner(),
+                        max_retries: hook.max_retries as u32,
+                        retry_backoff: hook.retry_backoff.into_inner(),
+                        max_batch_size: hook.max_batch_size.max(1) as usize,
+                        dead_letter_url: hook.dead_letter_url,
+                        template,
                     }),
                 };
 
@@ -584,6 +668,7 @@ impl Metrics {
         let resource = Resource::builder()
             .with_service_name("stalwart")
             .with_attribute(KeyValue::new(SERVICE_VERSION, env!("CARGO_PKG_VERSION")))
+            .with_attribute(KeyValue::new(SERVICE_INSTANCE_ID, bp.node_id().to_string()))
             .build();
         let instrumentation = InstrumentationScope::builder("stalwart")
             .with_version(env!("CARGO_PKG_VERSION"))
@@ -607,6 +692,10 @@ impl Metrics {
                         auth: prom.auth_username.and_then(|user| {
                             secret.map(|secret| STANDARD.encode(format!("{user}:{secret}")))
                         }),
+                        domain_breakdown: prom.domain_breakdown,
+                        tenant_breakdown: prom.tenant_breakdown,
+                        max_breakdown_labels: prom.max_breakdown_labels as usize,
+                        otlp_ingest: prom.otlp_ingest,
                     })
                 }
                 MetricsPrometheus::Disabled => None,
@@ -755,3 +844,23 @@ impl std::fmt::Debug for OtelMetrics {
             .finish()
     }
 }
+
+impl FromStr for WebhookTemplateVariable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "events" => Ok(WebhookTemplateVariable::Events),
+            "total" => Ok(WebhookTemplateVariable::Total),
+            "event" => Ok(WebhookTemplateVariable::Event),
+            "id" => Ok(WebhookTemplateVariable::Id),
+            "type" => Ok(WebhookTemplateVariable::Type),
+            "level" => Ok(WebhookTemplateVariable::Level),
+            "description" => Ok(WebhookTemplateVariable::Description),
+            "created_at" => Ok(WebhookTemplateVariable::CreatedAt),
+            "data" => Ok(WebhookTemplateVariable::Data),
+            "comma" => Ok(WebhookTemplateVariable::Comma),
+            _ => Err(format!("Unknown webhook template variable: {}", s)),
+        }
+    }
+}
@@ -13,6 +13,7 @@ use crate::expr::{Constant, Expression};
 use compact_str::CompactString;
 use registry::{
     schema::{
+        enums::ExpressionVariable,
         prelude::{ExpressionContext, Property},
         structs,
     },
@@ -71,6 +72,18 @@ impl IfBlock {
     pub fn is_empty(&self) -> bool {
         self.default.is_empty() && self.if_then.is_empty()
     }
+
+    pub fn has_variable(&self, variable: ExpressionVariable) -> bool {
+        self.if_then
+            .iter()
+            .flat_map(|if_then| [&if_then.expr, &if_then.then])
+            .chain([&self.default])
+            .any(|expr| {
+                expr.items
+                    .iter()
+                    .any(|item| matches!(item, ExpressionItem::Variable(v) if *v == variable))
+            })
+    }
 }
 
 impl Expression {
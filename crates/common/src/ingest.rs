@@ -0,0 +1,123 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::Server;
+use mail_parser::Message;
+use std::{future::Future, pin::Pin, sync::Arc};
+use types::{keyword::Keyword, special_use::SpecialUse};
+
+pub type IngestFuture<'x> = Pin<Box<dyn Future<Output = trc::Result<IngestAction>> + Send + 'x>>;
+
+// Extensions receive each parsed message before it is stored and can add
+// headers, keywords or route the message to different mailboxes.
+pub trait IngestExtension: Sync + Send {
+    fn name(&self) -> &str;
+
+    fn on_ingest<'x>(&'x self, server: &'x Server, ctx: IngestContext<'x>) -> IngestFuture<'x>;
+}
+
+pub struct IngestContext<'x> {
+    pub account_id: u32,
+    pub session_id: u64,
+    pub deliver_to: Option<&'x str>,
+    pub is_spam: bool,
+    pub message: &'x Message<'x>,
+    pub mailbox_ids: &'x [u32],
+    pub keywords: &'x [Keyword],
+}
+
+#[derive(Debug, Default)]
+pub struct IngestAction {
+    pub headers: Vec<(String, String)>,
+    pub keywords: Vec<Keyword>,
+    pub mailboxes: Option<Vec<IngestMailbox>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IngestMailbox {
+    Id(u32),
+    Path(String),
+    Role(SpecialUse),
+}
+
+#[derive(Default)]
+pub struct IngestExtensions {
+    extensions: Vec<Arc<dyn IngestExtension>>,
+}
+
+impl IngestExtensions {
+    pub fn is_empty(&self) -> bool {
+        self.extensions.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<dyn IngestExtension>> {
+        self.extensions.iter()
+    }
+}
+
+impl Server {
+    pub fn register_ingest_extension(&self, extension: Arc<dyn IngestExtension>) {
+        self.inner.data.ingest_extensions.rcu(|current| {
+            let mut extensions = current.extensions.clone();
+            extensions.retain(|item| item.name() != extension.name());
+            extensions.push(extension.clone());
+            IngestExtensions { extensions }
+        });
+    }
+
+    pub fn unregister_ingest_extension(&self, name: &str) {
+        self.inner.data.ingest_extensions.rcu(|current| {
+            let mut extensions = current.extensions.clone();
+            extensions.retain(|item| item.name() != name);
+            IngestExtensions { extensions }
+        });
+    }
+}
+
+impl IngestAction {
+    pub fn is_empty(&self) -> bool {
+        self.headers.is_empty() && self.keywords.is_empty() && self.mailboxes.is_none()
+    }
+}
+
+// Header names are printable ASCII without colons (RFC 5322 section 2.2) and
+// values may not contain line breaks that would inject additional headers.
+pub fn is_valid_header(name: &str, value: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|ch| (33..=126).contains(&ch) && ch != b':')
+        && !value.bytes().any(|ch| matches!(ch, b'\r' | b'\n' | b'\0'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_valid_header;
+
+    #[test]
+    fn validate_headers() {
+        for (name, value, expected) in [
+            ("X-Extension", "value", true),
+            ("X-Extension", "", true),
+            ("X-Extension", "value with spaces\tand tabs", true),
+            ("", "value", false),
+            ("X Extension", "value", false),
+            ("X-Extension:", "value", false),
+            ("X-Extensión", "value", false),
+            ("X-Extension\r\nBcc", "value", false),
+            ("X-Extension", "value\r\nBcc: victim@example.org", false),
+            ("X-Extension", "value\nBcc: victim@example.org", false),
+            ("X-Extension", "value\r", false),
+            ("X-Extension", "value\0", false),
+        ] {
+            assert_eq!(
+                is_valid_header(name, value),
+                expected,
+                "{name:?}: {value:?}"
+            );
+        }
+    }
+}
@@ -20,6 +20,7 @@ use std::sync::{
     Arc,
     atomic::{AtomicBool, Ordering},
 };
+use store::dispatch::cache::ReadCacheEviction;
 use tokio::sync::{Semaphore, SemaphorePermit, mpsc};
 use types::type_state::{DataType, StateChange};
 use utils::map::bitmap::Bitmap;
@@ -77,7 +78,41 @@ pub enum BroadcastEvent {
     CacheInvalidate(Vec<CacheInvalidation>),
     CacheInvalidateAll,
     CacheInvalidateNegative,
-    MtaQueueStatus { is_running: bool },
+    StoreCacheInvalidate(ReadCacheEviction),
+    MtaQueueStatus {
+        is_running: bool,
+    },
+    ClusterQuery {
+        request_id: u64,
+        query: ClusterQuery,
+    },
+    ClusterReply {
+        request_id: u64,
+        reply: Vec<u8>,
+    },
+    TerminateSessions {
+        account_id: u32,
+    },
+    MtaQueueRefresh,
+    NodeDrain {
+        node_id: u16,
+        draining: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClusterQuery {
+    Status,
+    Metrics,
+    Sessions,
+    Queue,
+    Drain,
+}
+
+#[derive(Debug)]
+pub struct ClusterReply {
+    pub node_id: u16,
+    pub reply: Vec<u8>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -85,6 +120,15 @@ pub enum RegistryChange {
     Insert(ObjectId),
     Delete(ObjectId),
     Reload(ObjectType),
+    ReloadSubsystem(ReloadSubsystem),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadSubsystem {
+    SpamFilter,
+    Sieve,
+    Certificates,
+    Network,
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
@@ -196,6 +240,79 @@ impl BroadcastEvent {
     }
 }
 
+impl ClusterQuery {
+    pub fn to_id(&self) -> u8 {
+        match self {
+            ClusterQuery::Status => 0,
+            ClusterQuery::Metrics => 1,
+            ClusterQuery::Sessions => 2,
+            ClusterQuery::Queue => 3,
+            ClusterQuery::Drain => 4,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(ClusterQuery::Status),
+            1 => Some(ClusterQuery::Metrics),
+            2 => Some(ClusterQuery::Sessions),
+            3 => Some(ClusterQuery::Queue),
+            4 => Some(ClusterQuery::Drain),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClusterQuery::Status => "status",
+            ClusterQuery::Metrics => "metrics",
+            ClusterQuery::Sessions => "sessions",
+            ClusterQuery::Queue => "queue",
+            ClusterQuery::Drain => "drain",
+        }
+    }
+}
+
+impl ReloadSubsystem {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "spam-filter" => Some(ReloadSubsystem::SpamFilter),
+            "sieve" => Some(ReloadSubsystem::Sieve),
+            "certificates" => Some(ReloadSubsystem::Certificates),
+            "network" => Some(ReloadSubsystem::Network),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReloadSubsystem::SpamFilter => "spam-filter",
+            ReloadSubsystem::Sieve => "sieve",
+            ReloadSubsystem::Certificates => "certificates",
+            ReloadSubsystem::Network => "network",
+        }
+    }
+
+    pub fn to_id(&self) -> u8 {
+        match self {
+            ReloadSubsystem::SpamFilter => 0,
+            ReloadSubsystem::Sieve => 1,
+            ReloadSubsystem::Certificates => 2,
+            ReloadSubsystem::Network => 3,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(ReloadSubsystem::SpamFilter),
+            1 => Some(ReloadSubsystem::Sieve),
+            2 => Some(ReloadSubsystem::Certificates),
+            3 => Some(ReloadSubsystem::Network),
+            _ => None,
+        }
+    }
+}
+
 pub trait ToHash {
     fn to_hash(&self) -> u64;
 }
@@ -8,26 +8,41 @@
 
 use crate::auth::{AccessTokenInner, EmailAddress};
 use crate::manager::application::WebApplications;
+use crate::manager::audit::AUDIT_HASH_LEN;
+use crate::manager::cluster::NodeDelivery;
 use crate::network::asn::AsnGeoLookupData;
+use crate::telemetry::metrics::{
+    bandwidth::BandwidthUsage, breakdown::MetricsBreakdown, cluster::ClusterNodeMetrics,
+    latency::DeliveryLatency,
+};
 use crate::{
     auth::{AccountCache, DomainCache, EmailCache, MailingListCache, RoleCache, TenantCache},
     config::{
         mailstore::{
             email::EmailConfig,
             imap::ImapConfig,
-            scripts::Scripting,
-            spamfilter::{IpResolver, SpamClassifier, SpamFilterConfig},
+            scripts::{Scripting, SieveScripts},
+            spamfilter::{
+                IpResolver, SpamClassifier, SpamFilterConfig, SpamFilterLists, SpamFilterRules,
+            },
         },
         smtp::auth::DkimSigner,
     },
+    ingest::IngestExtensions,
     ipc::TrainTaskController,
-    network::security::BlockedIps,
+    network::{
+        mta_sts::MtaStsFetches,
+        scheduler::RequestScheduler,
+        security::{AllowedIps, BlockedIps},
+        session::ActiveSessions,
+    },
 };
 use ahash::{AHashMap, AHashSet};
 use arc_swap::ArcSwap;
 use auth::oauth::config::OAuthConfig;
-use calcard::common::timezone::Tz;
+use calcard::{common::timezone::Tz, vcard::VCard};
 use config::{
+    audit::AuditLogConfig,
     groupware::GroupwareConfig,
     mailstore::jmap::JmapConfig,
     network::Network,
@@ -38,10 +53,15 @@ use config::{
     storage::Storage,
     telemetry::Metrics,
 };
-use ipc::{BroadcastEvent, PushEvent, QueueEvent, ReportingEvent};
+use ipc::{BroadcastEvent, ClusterReply, PushEvent, QueueEvent, ReportingEvent};
+use jmap_proto::{
+    method::query::{Comparator, Filter},
+    object::email::{EmailComparator, EmailFilter},
+};
 use mail_auth::{MX, Txt};
 use manager::application::Resource;
 use parking_lot::{Mutex, RwLock};
+use registry::types::id::ObjectId;
 use rustls::sign::CertifiedKey;
 use std::sync::atomic::AtomicU64;
 use std::{
@@ -64,6 +84,7 @@ pub mod cache;
 pub mod config;
 pub mod expr;
 pub mod i18n;
+pub mod ingest;
 pub mod ipc;
 pub mod manager;
 pub mod network;
@@ -127,6 +148,13 @@ pub const KV_LOCK_QUEUE_MESSAGE: u8 = 21;
 pub const KV_LOCK_TASK: u8 = 23;
 pub const KV_LOCK_DAV: u8 = 25;
 pub const KV_SIEVE_ID: u8 = 26;
+pub const KV_BAN_HISTORY: u8 = 27;
+pub const KV_BANDWIDTH: u8 = 28;
+pub const KV_RATE_LIMIT_SPAM_TRAIN: u8 = 29;
+pub const KV_RCPT_VERIFY: u8 = 30;
+pub const KV_RATE_LIMIT_DSN: u8 = 31;
+pub const KV_DUPLICATE: u8 = 32;
+pub const KV_SPAM_TRAP: u8 = 33;
 
 #[derive(Clone)]
 pub struct Server {
@@ -144,10 +172,14 @@ pub struct Inner {
 #[allow(clippy::type_complexity)]
 pub struct Data {
     pub spam_classifier: ArcSwap<SpamClassifier>,
+    pub spam_rules: ArcSwap<SpamFilterRules>,
+    pub spam_lists: ArcSwap<SpamFilterLists>,
+    pub sieve_scripts: ArcSwap<SieveScripts>,
 
     pub tls_certificates: ArcSwap<AHashMap<Box<str>, Arc<CertifiedKey>>>,
     pub tls_self_signed_cert: Option<Arc<CertifiedKey>>,
 
+    pub allowed_ips: RwLock<AllowedIps>,
     pub blocked_ips: RwLock<BlockedIps>,
     pub lookup_stores: ArcSwap<AHashMap<Box<str>, InMemoryStore>>,
 
@@ -158,11 +190,25 @@ pub struct Data {
     pub span_id_gen: SnowflakeIdGenerator,
     pub registry_id_gen: SnowflakeIdGenerator,
     pub queue_status: AtomicBool,
+    pub draining: AtomicBool,
+    pub drain_resume_queue: AtomicBool,
 
     pub applications: WebApplications,
     pub logos: Mutex<AHashMap<Box<str>, LogoCache>>,
 
     pub smtp_connectors: TlsConnectors,
+
+    pub registry_revisions: Mutex<AHashMap<ObjectId, u64>>,
+    pub active_sessions: Arc<ActiveSessions>,
+    pub active_deliveries: Mutex<AHashMap<u64, NodeDelivery>>,
+    pub audit_head: tokio::sync::Mutex<Option<[u8; AUDIT_HASH_LEN]>>,
+    pub metrics_breakdown: Mutex<MetricsBreakdown>,
+    pub cluster_metrics: Mutex<AHashMap<String, ClusterNodeMetrics>>,
+    pub delivery_latency: Mutex<DeliveryLatency>,
+    pub mta_sts_fetches: Mutex<MtaStsFetches>,
+    pub ingest_extensions: ArcSwap<IngestExtensions>,
+    pub bandwidth_usage: Mutex<BandwidthUsage>,
+    pub request_scheduler: RequestScheduler,
 }
 
 #[derive(Clone)]
@@ -177,10 +223,12 @@ pub struct Caches {
     pub http_auth: Cache<Box<str>, HttpAuthCache>,
 
     pub messages: Cache<u32, Arc<MessageStoreCache>>,
+    pub email_queries: CacheWithTtl<EmailQueryKey, Arc<EmailQueryCache>>,
     pub files: Cache<u32, Arc<DavResources>>,
     pub contacts: Cache<u32, Arc<DavResources>>,
     pub events: Cache<u32, Arc<DavResources>>,
     pub scheduling: Cache<u32, Arc<DavResources>>,
+    pub directory_cards: CacheWithTtl<DirectoryScope, Arc<DirectoryCards>>,
 
     pub emails: Cache<EmailAddress, EmailCache>,
     pub emails_negative: CacheWithTtl<EmailAddress, ()>,
@@ -204,6 +252,8 @@ pub struct Caches {
     pub dns_mta_sts: CacheWithTtl<Box<str>, Arc<Policy>>,
     pub dns_rbl: CacheWithTtl<Box<str>, Option<Arc<IpResolver>>>,
 
+    pub rate_limited: CacheWithTtl<Box<[u8]>, u64>,
+
     pub negative_cache_ttl: Duration,
 }
 
@@ -216,6 +266,24 @@ pub struct MessageStoreCache {
     pub size: u64,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EmailQueryKey {
+    pub account_id: u32,
+    pub access_id: u32,
+    pub shared_mailboxes: Box<[u32]>,
+    pub filter: Vec<Filter<EmailFilter>>,
+    pub sort: Option<Vec<Comparator<EmailComparator>>>,
+    pub collapse_threads: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct EmailQueryCache {
+    pub emails_change_id: u64,
+    pub mailboxes_change_id: u64,
+    pub total: usize,
+    pub ids: Box<[(u32, u32)]>,
+}
+
 #[derive(Debug, Clone)]
 pub struct MailboxesCache {
     pub change_id: u64,
@@ -273,10 +341,12 @@ pub struct HttpAuthCache {
 pub struct Ipc {
     pub push_tx: mpsc::Sender<PushEvent>,
     pub task_tx: Arc<Notify>,
+    pub firewall_tx: Arc<Notify>,
     pub queue_tx: mpsc::Sender<QueueEvent>,
     pub report_tx: mpsc::Sender<ReportingEvent>,
     pub broadcast_tx: Option<mpsc::Sender<BroadcastEvent>>,
     pub train_task_controller: Arc<TrainTaskController>,
+    pub cluster_replies: Mutex<AHashMap<u64, mpsc::Sender<ClusterReply>>>,
 }
 
 pub struct TlsConnectors {
@@ -298,6 +368,25 @@ pub struct DavResources {
     pub update_lock: Arc<UpdateLock>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DirectoryScope {
+    pub tenant_id: Option<u32>,
+    pub domain_id: Option<u32>,
+}
+
+#[derive(Debug, Default)]
+pub struct DirectoryCards {
+    pub cards: Vec<DirectoryCard>,
+    pub size: u64,
+}
+
+#[derive(Debug)]
+pub struct DirectoryCard {
+    pub document_id: u32,
+    pub hash: u32,
+    pub card: VCard,
+}
+
 #[derive(Debug)]
 pub struct UpdateLock {
     pub semaphore: Semaphore,
@@ -382,6 +471,7 @@ pub struct Core {
     pub smtp: SmtpConfig,
     pub spam: SpamFilterConfig,
     pub groupware: GroupwareConfig,
+    pub audit: AuditLogConfig,
     pub metrics: Metrics,
 
     // SPDX-SnippetBegin
@@ -21,26 +21,39 @@ use store::{
 };
 use trc::{AddContext, Key};
 use types::blob_hash::BlobHash;
+use xxhash_rust::xxh3::xxh3_64;
 
 const APP_BLOB_PREFIX: &str = "STALWART_APP_";
 const MAX_APP_SIZE: usize = 100 * 1024 * 1024;
 
-#[allow(clippy::type_complexity)]
 pub struct WebApplications {
     applications: ArcSwap<Vec<WebApplicationManager>>,
-    routes: ArcSwap<AHashMap<String, Arc<AHashMap<String, Resource<PathBuf>>>>>,
+    routes: ArcSwap<AHashMap<String, Vec<Arc<WebBundle>>>>,
 }
 
 #[derive(Clone)]
 pub struct WebApplicationManager {
     bundle_path: TempDir,
     prefixes: Vec<String>,
+    hostnames: Vec<String>,
+    cache_max_age: Option<u64>,
     description: String,
     url: String,
     expiry: u64,
     blob_key: BlobHash,
 }
 
+struct WebBundle {
+    hostnames: Vec<String>,
+    cache_max_age: Option<u64>,
+    files: AHashMap<String, BundleFile>,
+}
+
+struct BundleFile {
+    resource: Resource<PathBuf>,
+    etag: String,
+}
+
 #[derive(Default, Clone)]
 pub struct Resource<T> {
     pub content_type: Cow<'static, str>,
@@ -58,7 +71,15 @@ impl<T> Resource<T> {
 
 pub struct AppResource {
     pub resource: Resource<Vec<u8>>,
-    pub no_cache: bool,
+    pub etag: String,
+    pub cache: CachePolicy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachePolicy {
+    NoCache,
+    MaxAge(u64),
+    Immutable,
 }
 
 impl WebApplications {
@@ -69,41 +90,28 @@ impl WebApplications {
         }
     }
 
-    pub async fn serve(&self, prefix: &str, path: &str) -> trc::Result<Option<AppResource>> {
-        if let Some(routes) = self.routes.load().get(prefix)
-            && let Some((is_index, resource)) = routes
-                .get(path)
-                .map(|res| (path == "index.html", res))
-                .or_else(|| routes.get("index.html").map(|res| (true, res)))
-        {
-            tokio::fs::read(&resource.contents)
-                .await
-                .map(|mut contents| {
-                    if is_index && let Ok(html) = std::str::from_utf8(&contents) {
-                        contents = html
-                            .replace("<base href=\"/\"", &format!("<base href=\"/{prefix}/\""))
-                            .into_bytes();
-                    }
+    /// Serves a file from the bundle mounted on the first path segment,
+    /// falling back to a bundle mounted at the root of the hostname.
+    pub async fn serve(&self, hostname: &str, path: &str) -> trc::Result<Option<AppResource>> {
+        let routes = self.routes.load();
+        let hostname = hostname.to_lowercase();
+        let path = path.trim_start_matches('/');
 
-                    Some(AppResource {
-                        resource: Resource {
-                            content_type: resource.content_type.clone(),
-                            contents,
-                        },
-                        no_cache: is_index,
-                    })
-                })
-                .map_err(|err| {
-                    trc::ResourceEvent::Error
-                        .reason(err)
-                        .ctx(trc::Key::Path, path.to_string())
-                        .caused_by(trc::location!())
-                })
+        if let Some((prefix, file)) = path.split_once('/')
+            && let Some(bundle) = WebBundle::select(routes.get(prefix), &hostname)
+        {
+            bundle.serve(prefix, file).await
+        } else if let Some(bundle) = WebBundle::select(routes.get(""), &hostname) {
+            bundle.serve("", path).await
         } else {
             Ok(None)
         }
     }
 
+    pub fn is_mounted(&self, hostname: &str, prefix: &str) -> bool {
+        WebBundle::select(self.routes.load().get(prefix), &hostname.to_lowercase()).is_some()
+    }
+
     pub async fn reload(&self, bp: &mut Bootstrap) {
         let mut apps = Vec::new();
         for app in bp.list_infallible::<Application>().await {
@@ -129,11 +137,18 @@ impl WebApplications {
                 );
             }
             match app.unpack(server).await {
-                Ok(app_routes) => {
-                    let app_routes = Arc::new(app_routes);
+                Ok(files) => {
+                    let bundle = Arc::new(WebBundle {
+                        hostnames: app.hostnames.clone(),
+                        cache_max_age: app.cache_max_age,
+                        files,
+                    });
 
                     for prefix in &app.prefixes {
-                        routes.insert(prefix.clone(), app_routes.clone());
+                        routes
+                            .entry(prefix.clone())
+                            .or_insert_with(Vec::new)
+                            .push(bundle.clone());
                     }
                 }
                 Err(err) => {
@@ -168,6 +183,13 @@ impl WebApplicationManager {
             url: app.object.resource_url,
             description: app.object.description,
             expiry: app.object.auto_update_frequency.as_secs(),
+            hostnames: app
+                .object
+                .hostnames
+                .iter()
+                .map(|hostname| hostname.trim_end_matches('.').to_lowercase())
+                .collect(),
+            cache_max_age: app.object.cache_max_age.map(|max_age| max_age.as_secs()),
             prefixes: app
                 .object
                 .url_prefix
@@ -182,7 +204,7 @@ impl WebApplicationManager {
         }
     }
 
-    async fn unpack(&self, server: &Server) -> trc::Result<AHashMap<String, Resource<PathBuf>>> {
+    async fn unpack(&self, server: &Server) -> trc::Result<AHashMap<String, BundleFile>> {
         // Delete any existing bundles
         self.bundle_path.clean().await.map_err(unpack_error)?;
 
@@ -272,6 +294,7 @@ impl WebApplicationManager {
                 let file_name = file.name().to_string();
                 drop(file);
 
+                let etag = format!("\"{:x}\"", xxh3_64(&contents));
                 let path = bundle_path.join(format!("{i:02}"));
                 std::fs::write(&path, contents).map_err(unpack_error)?;
 
@@ -281,21 +304,28 @@ impl WebApplicationManager {
                         .map(|(_, ext)| ext)
                         .unwrap_or_default()
                     {
-                        "html" => "text/html",
+                        "html" | "htm" => "text/html",
                         "css" => "text/css",
                         "wasm" => "application/wasm",
-                        "js" => "application/javascript",
+                        "js" | "mjs" => "application/javascript",
                         "json" => "application/json",
+                        "txt" => "text/plain",
+                        "xml" => "application/xml",
                         "png" => "image/png",
+                        "jpg" | "jpeg" => "image/jpeg",
+                        "gif" => "image/gif",
+                        "webp" => "image/webp",
                         "svg" => "image/svg+xml",
                         "ico" => "image/x-icon",
+                        "woff" => "font/woff",
+                        "woff2" => "font/woff2",
                         _ => "application/octet-stream",
                     }
                     .into(),
                     contents: path,
                 };
 
-                routes.insert(file_name, resource);
+                routes.insert(file_name, BundleFile { resource, etag });
             }
             Ok(routes)
         })
@@ -325,6 +355,70 @@ impl WebApplicationManager {
     }
 }
 
+impl WebBundle {
+    fn select<'x>(bundles: Option<&'x Vec<Arc<WebBundle>>>, hostname: &str) -> Option<&'x Self> {
+        let bundles = bundles?;
+        bundles
+            .iter()
+            .find(|bundle| bundle.hostnames.iter().any(|h| h == hostname))
+            .or_else(|| bundles.iter().find(|bundle| bundle.hostnames.is_empty()))
+            .map(|bundle| bundle.as_ref())
+    }
+
+    async fn serve(&self, prefix: &str, path: &str) -> trc::Result<Option<AppResource>> {
+        // Directories map to their index page, while unknown paths without an
+        // extension are treated as client-side routes of a single page application
+        let file = if path.is_empty() || path.ends_with('/') {
+            self.files.get(&format!("{path}index.html"))
+        } else {
+            self.files.get(path)
+        }
+        .or_else(|| {
+            if !path.rsplit('/').next().unwrap_or_default().contains('.') {
+                self.files.get("index.html")
+            } else {
+                None
+            }
+        });
+        let Some(file) = file else {
+            return Ok(None);
+        };
+
+        let mut contents = tokio::fs::read(&file.resource.contents)
+            .await
+            .map_err(|err| {
+                trc::ResourceEvent::Error
+                    .reason(err)
+                    .ctx(trc::Key::Path, path.to_string())
+                    .caused_by(trc::location!())
+            })?;
+        let is_html = file.resource.content_type == "text/html";
+        if is_html
+            && !prefix.is_empty()
+            && let Ok(html) = std::str::from_utf8(&contents)
+        {
+            contents = html
+                .replace("<base href=\"/\"", &format!("<base href=\"/{prefix}/\""))
+                .into_bytes();
+        }
+
+        Ok(Some(AppResource {
+            resource: Resource {
+                content_type: file.resource.content_type.clone(),
+                contents,
+            },
+            etag: file.etag.clone(),
+            cache: if is_html {
+                CachePolicy::NoCache
+            } else if let Some(max_age) = self.cache_max_age {
+                CachePolicy::MaxAge(max_age)
+            } else {
+                CachePolicy::Immutable
+            },
+        }))
+    }
+}
+
 impl Resource<Vec<u8>> {
     pub fn is_empty(&self) -> bool {
         self.content_type.is_empty() && self.contents.is_empty()
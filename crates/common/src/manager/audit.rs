@@ -0,0 +1,360 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{Server, auth::AccessToken};
+use ahash::AHashMap;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::{net::IpAddr, time::Duration};
+use store::{
+    Deserialize, IterateParams, U32_LEN, ValueKey,
+    write::{
+        AlignedBytes, Archive, Archiver, BatchBuilder, ValueClass, key::DeserializeBigEndian, now,
+    },
+};
+use trc::AddContext;
+use utils::snowflake::SnowflakeIdGenerator;
+
+pub const AUDIT_HASH_LEN: usize = 32;
+
+#[derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub timestamp: u64,
+    pub node_id: u64,
+    pub account_id: u32,
+    pub tenant_id: Option<u32>,
+    pub impersonator_id: Option<u32>,
+    pub remote_ip: IpAddr,
+    pub action: AuditAction,
+    pub object_type: String,
+    pub object_id: Option<u64>,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub prev_hash: [u8; AUDIT_HASH_LEN],
+}
+
+#[derive(rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Create,
+    Update,
+    Destroy,
+}
+
+#[derive(Debug, Default)]
+pub struct AuditFilter {
+    pub account_id: Option<u32>,
+    pub tenant_id: Option<u32>,
+    pub impersonator_id: Option<u32>,
+    pub object_type: Option<String>,
+    pub object_id: Option<u64>,
+    pub from: Option<u64>,
+    pub to: Option<u64>,
+    pub limit: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct AuditVerification {
+    pub entries: u64,
+    pub first_invalid: Option<u64>,
+}
+
+impl AuditEntry {
+    pub fn new(
+        access_token: &AccessToken,
+        remote_ip: IpAddr,
+        action: AuditAction,
+        object_type: impl Into<String>,
+        object_id: Option<u64>,
+    ) -> Self {
+        AuditEntry {
+            timestamp: 0,
+            node_id: 0,
+            account_id: access_token.account_id(),
+            tenant_id: access_token.tenant_id(),
+            impersonator_id: access_token.impersonator_id(),
+            remote_ip,
+            action,
+            object_type: object_type.into(),
+            object_id,
+            before: None,
+            after: None,
+            prev_hash: [0; AUDIT_HASH_LEN],
+        }
+    }
+
+    pub fn with_object_id(mut self, object_id: u64) -> Self {
+        self.object_id = Some(object_id);
+        self
+    }
+
+    pub fn with_before(mut self, value: &impl Serialize) -> Self {
+        self.before = serde_json::to_string(value).ok();
+        self
+    }
+
+    pub fn with_after(mut self, value: &impl Serialize) -> Self {
+        self.after = serde_json::to_string(value).ok();
+        self
+    }
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Update => "update",
+            AuditAction::Destroy => "destroy",
+        }
+    }
+}
+
+impl AuditFilter {
+    fn matches(&self, entry: &AuditEntry) -> bool {
+        self.account_id.is_none_or(|id| entry.account_id == id)
+            && self.tenant_id.is_none_or(|id| entry.tenant_id == Some(id))
+            && self
+                .impersonator_id
+                .is_none_or(|id| entry.impersonator_id == Some(id))
+            && self
+                .object_type
+                .as_ref()
+                .is_none_or(|typ| entry.object_type.eq_ignore_ascii_case(typ))
+            && self.object_id.is_none_or(|id| entry.object_id == Some(id))
+    }
+}
+
+pub struct AuditChain<'x> {
+    head: tokio::sync::MutexGuard<'x, Option<[u8; AUDIT_HASH_LEN]>>,
+    id_gen: &'x SnowflakeIdGenerator,
+    node_id: u64,
+    prev_hash: [u8; AUDIT_HASH_LEN],
+    pending: Option<[u8; AUDIT_HASH_LEN]>,
+}
+
+impl AuditChain<'_> {
+    // Adds the entry to a batch, the chain only moves forward once the batch
+    // has been written and the entry is committed.
+    pub fn append(&mut self, batch: &mut BatchBuilder, mut entry: AuditEntry) -> trc::Result<()> {
+        let entry_id = self.id_gen.generate();
+        entry.timestamp = now();
+        entry.node_id = self.node_id;
+        entry.prev_hash = self.pending.unwrap_or(self.prev_hash);
+        let value = Archiver::new(entry)
+            .serialize()
+            .caused_by(trc::location!())?;
+        self.pending = Some(audit_hash(entry_id, &value));
+        batch.set(ValueClass::AuditLog(entry_id), value);
+        Ok(())
+    }
+
+    pub fn commit(&mut self) {
+        if let Some(hash) = self.pending.take() {
+            self.prev_hash = hash;
+            *self.head = Some(hash);
+        }
+    }
+}
+
+impl Server {
+    // Locks this node's audit chain, each entry stores the hash of the previous one
+    // so that any modification or removal breaks the chain.
+    pub async fn audit_chain(&self) -> trc::Result<AuditChain<'_>> {
+        let node_id = self.core.network.node_id;
+        let head = self.inner.data.audit_head.lock().await;
+        let prev_hash = match *head {
+            Some(hash) => hash,
+            None => self
+                .audit_log_head(node_id)
+                .await
+                .caused_by(trc::location!())?,
+        };
+
+        Ok(AuditChain {
+            head,
+            id_gen: &self.inner.data.registry_id_gen,
+            node_id,
+            prev_hash,
+            pending: None,
+        })
+    }
+
+    pub async fn write_audit_log(&self, entries: Vec<AuditEntry>) -> trc::Result<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let mut chain = self.audit_chain().await?;
+        let mut batch = BatchBuilder::new();
+        for entry in entries {
+            chain.append(&mut batch, entry)?;
+        }
+        self.store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())?;
+        chain.commit();
+
+        Ok(())
+    }
+
+    // Writes performed by impersonated sessions are recorded so they can be
+    // traced back to the impersonator.
+    pub async fn audit_impersonated_write(
+        &self,
+        access_token: &AccessToken,
+        remote_ip: IpAddr,
+        object_type: &str,
+        operation: &str,
+    ) {
+        if access_token.impersonator_id().is_some() {
+            let entry = AuditEntry::new(
+                access_token,
+                remote_ip,
+                AuditAction::Update,
+                object_type,
+                None,
+            )
+            .with_after(&serde_json::json!({ "operation": operation }));
+            if let Err(err) = self.write_audit_log(vec![entry]).await {
+                trc::error!(err.caused_by(trc::location!()));
+            }
+        }
+    }
+
+    // Returns the matching entries, newest first.
+    pub async fn query_audit_log(
+        &self,
+        filter: &AuditFilter,
+    ) -> trc::Result<Vec<(u64, AuditEntry)>> {
+        let from_id = filter
+            .from
+            .map(|from| SnowflakeIdGenerator::from_timestamp(from).unwrap_or(u64::MAX))
+            .unwrap_or_default();
+        let to_id = filter
+            .to
+            .and_then(|to| SnowflakeIdGenerator::from_timestamp(to.saturating_add(1)))
+            .unwrap_or(u64::MAX);
+        let mut results = Vec::new();
+        if from_id > to_id {
+            return Ok(results);
+        }
+
+        self.store()
+            .iterate(
+                IterateParams::new(
+                    ValueKey::from(ValueClass::AuditLog(from_id)),
+                    ValueKey::from(ValueClass::AuditLog(to_id)),
+                )
+                .descending(),
+                |key, value| {
+                    let entry = deserialize_entry(value)?;
+                    if filter.matches(&entry) {
+                        results.push((key.deserialize_be_u64(U32_LEN + 1)?, entry));
+                    }
+
+                    Ok(filter.limit == 0 || results.len() < filter.limit)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(results)
+    }
+
+    // Walks the audit log verifying that every entry links to the previous entry
+    // written by the same node. The oldest entry of each node is trusted as-is,
+    // since earlier entries may have been removed by the retention policy.
+    pub async fn verify_audit_log(&self) -> trc::Result<AuditVerification> {
+        let mut heads: AHashMap<u64, [u8; AUDIT_HASH_LEN]> = AHashMap::new();
+        let mut result = AuditVerification::default();
+
+        self.store()
+            .iterate(
+                IterateParams::new(
+                    ValueKey::from(ValueClass::AuditLog(0)),
+                    ValueKey::from(ValueClass::AuditLog(u64::MAX)),
+                )
+                .ascending(),
+                |key, value| {
+                    let entry_id = key.deserialize_be_u64(U32_LEN + 1)?;
+                    let Ok(entry) = deserialize_entry(value) else {
+                        result.first_invalid = Some(entry_id);
+                        return Ok(false);
+                    };
+                    if heads
+                        .get(&entry.node_id)
+                        .is_some_and(|hash| hash != &entry.prev_hash)
+                    {
+                        result.first_invalid = Some(entry_id);
+                        return Ok(false);
+                    }
+                    heads.insert(entry.node_id, audit_hash(entry_id, value));
+                    result.entries += 1;
+
+                    Ok(true)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(result)
+    }
+
+    pub async fn purge_audit_log(&self, period: Duration) -> trc::Result<()> {
+        let until_entry_id = SnowflakeIdGenerator::from_duration(period).ok_or_else(|| {
+            trc::StoreEvent::UnexpectedError
+                .caused_by(trc::location!())
+                .ctx(
+                    trc::Key::Reason,
+                    "Failed to generate reference audit entry id.",
+                )
+        })?;
+
+        self.store()
+            .delete_range(
+                ValueKey::from(ValueClass::AuditLog(0)),
+                ValueKey::from(ValueClass::AuditLog(until_entry_id)),
+            )
+            .await
+            .caused_by(trc::location!())
+    }
+
+    async fn audit_log_head(&self, node_id: u64) -> trc::Result<[u8; AUDIT_HASH_LEN]> {
+        let mut head = [0; AUDIT_HASH_LEN];
+
+        self.store()
+            .iterate(
+                IterateParams::new(
+                    ValueKey::from(ValueClass::AuditLog(0)),
+                    ValueKey::from(ValueClass::AuditLog(u64::MAX)),
+                )
+                .descending(),
+                |key, value| {
+                    if deserialize_entry(value)?.node_id == node_id {
+                        head = audit_hash(key.deserialize_be_u64(U32_LEN + 1)?, value);
+                        Ok(false)
+                    } else {
+                        Ok(true)
+                    }
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(head)
+    }
+}
+
+fn deserialize_entry(value: &[u8]) -> trc::Result<AuditEntry> {
+    <Archive<AlignedBytes> as Deserialize>::deserialize(value)?.deserialize::<AuditEntry>()
+}
+
+fn audit_hash(entry_id: u64, value: &[u8]) -> [u8; AUDIT_HASH_LEN] {
+    let mut hasher = Sha256::new();
+    hasher.update(entry_id.to_be_bytes());
+    hasher.update(value);
+    hasher.finalize().into()
+}
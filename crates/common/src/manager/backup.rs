@@ -158,7 +158,15 @@ impl Core {
     }
 
     fn backup_subspace(&self, dest: &Path, subspace: u8, schema_version: u32) -> TaskHandle {
-        let store = self.storage.data.clone();
+        let store = if [SUBSPACE_QUEUE_MESSAGE, SUBSPACE_QUEUE_EVENT].contains(&subspace) {
+            self.storage
+                .queue
+                .as_ref()
+                .unwrap_or(&self.storage.data)
+                .clone()
+        } else {
+            self.storage.data.clone()
+        };
         let (handle, writer) = spawn_writer(
             dest.join(format!("subspace_{}", char::from(subspace))),
             subspace,
@@ -28,6 +28,9 @@ pub struct BootManager {
     pub inner: Arc<Inner>,
     pub servers: Listeners,
     pub ipc_rxs: IpcReceivers,
+    pub spam_corpus: Vec<(PathBuf, bool)>,
+    pub migrate: Option<PathBuf>,
+    pub import_mail: Option<(String, PathBuf)>,
 }
 
 pub struct IpcReceivers {
@@ -49,6 +52,11 @@ Options:
   -e, --export <PATH>              Export all store data to a specific path
   -i, --import <PATH>              Import store data from a specific path
   -o, --console                    Open the store console
+      --test-ham <PATH>            Run the spam filter rules against an mbox of ham messages
+      --test-spam <PATH>           Run the spam filter rules against an mbox of spam messages
+      --migrate <PATH>             Queue the account imports described in a JSON file
+      --import-mail <PATH>         Import a Maildir or mbox tree into the account given by --account
+      --account <NAME>             Account to import messages into
   -h, --help                       Print help
   -V, --version                    Print version
 "#
@@ -66,6 +74,10 @@ impl BootManager {
     pub async fn init() -> Self {
         let mut config_path = std::env::var("CONFIG_PATH").ok();
         let mut import_export = StoreOp::None;
+        let mut spam_corpus = Vec::new();
+        let mut migrate = None;
+        let mut import_mail = None;
+        let mut import_account = None;
 
         if config_path.is_none() {
             let mut args = std::env::args().skip(1);
@@ -102,6 +114,21 @@ impl BootManager {
                     ("console" | "o", None) => {
                         import_export = StoreOp::Console;
                     }
+                    ("test-ham", Some(value)) => {
+                        spam_corpus.push((PathBuf::from(value), false));
+                    }
+                    ("test-spam", Some(value)) => {
+                        spam_corpus.push((PathBuf::from(value), true));
+                    }
+                    ("migrate", Some(value)) => {
+                        migrate = Some(PathBuf::from(value));
+                    }
+                    ("import-mail", Some(value)) => {
+                        import_mail = Some(PathBuf::from(value));
+                    }
+                    ("account", Some(value)) => {
+                        import_account = Some(value);
+                    }
                     (_, None) => {
                         failed(&format!("Unrecognized command '{key}', try '--help'."));
                     }
@@ -112,15 +139,26 @@ impl BootManager {
             }
 
             if config_path.is_none() {
-                if import_export == StoreOp::None {
-                    eprintln!("{HELP}");
-                } else {
+                if import_export != StoreOp::None {
                     eprintln!("Missing '--config' argument for import/export.")
+                } else if !spam_corpus.is_empty() {
+                    eprintln!("Missing '--config' argument for spam filter testing.")
+                } else if migrate.is_some() {
+                    eprintln!("Missing '--config' argument for account migration.")
+                } else if import_mail.is_some() {
+                    eprintln!("Missing '--config' argument for mail import.")
+                } else {
+                    eprintln!("{HELP}");
                 }
                 std::process::exit(0);
             }
         }
 
+        if import_mail.is_some() != import_account.is_some() {
+            failed("The '--import-mail' and '--account' arguments must be used together.");
+        }
+        let import_mail = import_account.zip(import_mail);
+
         // Initialize registry
         let registry = RegistryStore::init(PathBuf::from(config_path.unwrap()))
             .await
@@ -145,7 +183,7 @@ impl BootManager {
                 // Parse components
                 let core: Box<Core> =
                     Box::new(Box::pin(Core::parse(&mut bootstrap, storage)).await);
-                let data = Data::parse(&mut bootstrap).await;
+                let data = Data::parse(&mut bootstrap, &core).await;
                 let cache = Caches::parse(&mut bootstrap).await;
 
                 // Enable telemetry
@@ -208,6 +246,32 @@ impl BootManager {
                     cache,
                 });
 
+                // Propagate store read cache evictions to the other nodes
+                if let Some(read_cache) = inner.shared_core.load().storage.data.read_cache()
+                    && let Some(broadcast_tx) = inner.ipc.broadcast_tx.clone()
+                {
+                    read_cache.set_listener(Box::new(move |eviction| {
+                        match broadcast_tx.try_send(BroadcastEvent::StoreCacheInvalidate(eviction))
+                        {
+                            Ok(_) => {}
+                            Err(mpsc::error::TrySendError::Full(event)) => {
+                                // Invalidations must not be dropped under load
+                                let broadcast_tx = broadcast_tx.clone();
+                                tokio::spawn(async move {
+                                    let _ = broadcast_tx.send(event).await;
+                                });
+                            }
+                            Err(mpsc::error::TrySendError::Closed(_)) => {
+                                trc::event!(
+                                    Server(trc::ServerEvent::ThreadError),
+                                    Details = "Error sending store cache invalidation.",
+                                    CausedBy = trc::location!()
+                                );
+                            }
+                        }
+                    }));
+                }
+
                 if !bootstrap.registry.is_recovery_mode() {
                     // Load spam model
                     if let Err(err) = inner.build_server().spam_model_reload().await {
@@ -236,6 +300,9 @@ impl BootManager {
                     bootstrap,
                     servers,
                     ipc_rxs,
+                    spam_corpus,
+                    migrate,
+                    import_mail,
                 }
             }
             StoreOp::Export(path) => {
@@ -288,7 +355,9 @@ pub fn build_ipc(has_pubsub: bool) -> (Ipc, IpcReceivers) {
             report_tx,
             broadcast_tx: has_pubsub.then_some(broadcast_tx),
             task_tx: Arc::new(Notify::new()),
+            firewall_tx: Arc::new(Notify::new()),
             train_task_controller: Arc::new(TrainTaskController::default()),
+            cluster_replies: Default::default(),
         },
         IpcReceivers {
             push_rx: Some(push_rx),
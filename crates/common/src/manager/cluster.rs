@@ -0,0 +1,382 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    IPC_CHANNEL_BUFFER, Server,
+    ipc::{BroadcastEvent, ClusterQuery, ClusterReply, QueueEvent},
+};
+use registry::{
+    schema::enums::{ClusterNodeStatus, ClusterTaskType},
+    types::datetime::UTCDateTime,
+};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{net::IpAddr, sync::atomic::Ordering, time::Duration};
+use tokio::{sync::mpsc, time::Instant};
+use trc::{AddContext, Collector, MetricType};
+use types::id::Id;
+
+const CLUSTER_QUERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct ClusterNodeReply<T> {
+    pub node_id: u16,
+    pub hostname: String,
+    pub status: ClusterNodeStatus,
+    pub last_renewal: Option<UTCDateTime>,
+    pub reply: Option<T>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeStatus {
+    pub version: String,
+    pub queue_running: bool,
+    pub memory: u64,
+    pub recovery_mode: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    #[serde(default)]
+    pub tasks: Vec<ClusterTaskType>,
+    #[serde(default)]
+    pub draining: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeMetric {
+    pub metric: String,
+    #[serde(rename = "@type")]
+    pub metric_type: NodeMetricType,
+    pub count: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sum: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum NodeMetricType {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeSessions {
+    pub smtp: u64,
+    pub imap: u64,
+    pub pop3: u64,
+    pub http: u64,
+    pub sieve: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NodeSessionList {
+    #[serde(flatten)]
+    pub counts: NodeSessions,
+    #[serde(default)]
+    pub sessions: Vec<NodeSession>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeSession {
+    pub session_id: Id,
+    pub protocol: String,
+    pub listener: String,
+    pub remote_ip: IpAddr,
+    pub remote_port: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<Id>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<Id>,
+    pub started: UTCDateTime,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub command: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeQueue {
+    pub queue_running: bool,
+    pub active_deliveries: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deliveries: Vec<NodeDelivery>,
+}
+
+// Delivery attempt in progress on a node, registered by the delivery task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeDelivery {
+    pub queue_id: Id,
+    pub queue_name: String,
+    pub from: String,
+    pub recipients: Vec<String>,
+    pub size: u64,
+    pub started: UTCDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrainStatus {
+    pub node_id: u64,
+    pub draining: bool,
+    pub queue_running: bool,
+    pub active_deliveries: u64,
+    pub sessions: NodeSessions,
+    pub safe_to_stop: bool,
+}
+
+impl NodeSessions {
+    pub fn local() -> Self {
+        NodeSessions {
+            smtp: Collector::read_metric(MetricType::SmtpActiveConnections) as u64,
+            imap: Collector::read_metric(MetricType::ImapActiveConnections) as u64,
+            pop3: Collector::read_metric(MetricType::Pop3ActiveConnections) as u64,
+            http: Collector::read_metric(MetricType::HttpActiveConnections) as u64,
+            sieve: Collector::read_metric(MetricType::SieveActiveConnections) as u64,
+        }
+    }
+}
+
+impl<T> ClusterNodeReply<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> ClusterNodeReply<U> {
+        ClusterNodeReply {
+            node_id: self.node_id,
+            hostname: self.hostname,
+            status: self.status,
+            last_renewal: self.last_renewal,
+            reply: self.reply.map(f),
+        }
+    }
+}
+
+impl Server {
+    // Runs a query on every cluster node, this one included, and collects the replies.
+    // Nodes that do not reply in time are returned without a reply.
+    pub async fn cluster_query<T: DeserializeOwned>(
+        &self,
+        query: ClusterQuery,
+    ) -> trc::Result<Vec<ClusterNodeReply<T>>> {
+        let this_node_id = self.core.network.node_id as u16;
+        let mut nodes = self
+            .registry()
+            .cluster_node_list()
+            .await
+            .caused_by(trc::location!())?
+            .into_iter()
+            .map(|node| ClusterNodeReply {
+                node_id: node.node_id as u16,
+                hostname: node.hostname,
+                status: node.status,
+                last_renewal: Some(node.last_renewal),
+                reply: None,
+            })
+            .collect::<Vec<_>>();
+
+        // Query peers
+        let mut pending = nodes
+            .iter()
+            .filter(|node| node.node_id != this_node_id && node.status == ClusterNodeStatus::Active)
+            .count();
+        if pending > 0 && self.inner.ipc.broadcast_tx.is_some() {
+            let request_id = self.inner.data.queue_id_gen.generate();
+            let (tx, mut rx) = mpsc::channel(IPC_CHANNEL_BUFFER);
+            self.inner.ipc.cluster_replies.lock().insert(request_id, tx);
+            self.cluster_broadcast(BroadcastEvent::ClusterQuery { request_id, query })
+                .await;
+
+            let deadline = Instant::now() + CLUSTER_QUERY_TIMEOUT;
+            while pending > 0 {
+                let Ok(Some(reply)) = tokio::time::timeout_at(deadline, rx.recv()).await else {
+                    break;
+                };
+                if let Some(node) = nodes
+                    .iter_mut()
+                    .find(|node| node.node_id == reply.node_id && node.reply.is_none())
+                {
+                    node.reply = serde_json::from_slice(&reply.reply).ok();
+                    if node.status == ClusterNodeStatus::Active {
+                        pending -= 1;
+                    }
+                }
+            }
+
+            self.inner.ipc.cluster_replies.lock().remove(&request_id);
+        }
+
+        // Add local reply
+        let reply = serde_json::from_slice(&self.cluster_query_local(query).await).ok();
+        if let Some(node) = nodes.iter_mut().find(|node| node.node_id == this_node_id) {
+            node.reply = reply;
+        } else {
+            nodes.push(ClusterNodeReply {
+                node_id: this_node_id,
+                hostname: self.core.network.server_name.clone(),
+                status: ClusterNodeStatus::Active,
+                last_renewal: None,
+                reply,
+            });
+        }
+
+        Ok(nodes)
+    }
+
+    pub async fn reply_cluster_query(&self, request_id: u64, query: ClusterQuery) {
+        let reply = self.cluster_query_local(query).await;
+        self.cluster_broadcast(BroadcastEvent::ClusterReply { request_id, reply })
+            .await;
+    }
+
+    pub fn receive_cluster_reply(&self, request_id: u64, reply: ClusterReply) {
+        if let Some(tx) = self.inner.ipc.cluster_replies.lock().get(&request_id) {
+            let _ = tx.try_send(reply);
+        }
+    }
+
+    // Puts this node in or out of drain mode. New connections are refused and
+    // the queue is paused while draining, the queue is resumed afterwards only
+    // if it was running when the drain started.
+    pub async fn set_draining(&self, draining: bool) {
+        let data = &self.inner.data;
+        if draining {
+            if data.draining.swap(true, Ordering::Relaxed) {
+                return;
+            }
+            data.drain_resume_queue
+                .store(data.queue_status.load(Ordering::Relaxed), Ordering::Relaxed);
+            let _ = self.inner.ipc.queue_tx.send(QueueEvent::Paused(true)).await;
+
+            // Queued messages are shared by all nodes, ask the peers to pick up
+            // the ones that were due for delivery on this node
+            self.cluster_broadcast(BroadcastEvent::MtaQueueRefresh)
+                .await;
+        } else if data.draining.swap(false, Ordering::Relaxed)
+            && data.drain_resume_queue.swap(false, Ordering::Relaxed)
+        {
+            let _ = self
+                .inner
+                .ipc
+                .queue_tx
+                .send(QueueEvent::Paused(false))
+                .await;
+        }
+    }
+
+    pub fn drain_status(&self) -> DrainStatus {
+        let draining = self.inner.data.draining.load(Ordering::Relaxed);
+        let active_deliveries =
+            Collector::read_metric(MetricType::DeliveryActiveConnections) as u64;
+        let sessions = NodeSessions::local();
+
+        // The HTTP connection serving the drain request is not taken into account
+        let safe_to_stop = draining
+            && active_deliveries == 0
+            && sessions.smtp == 0
+            && sessions.imap == 0
+            && sessions.pop3 == 0
+            && sessions.sieve == 0
+            && sessions.http <= 1;
+
+        DrainStatus {
+            node_id: self.core.network.node_id,
+            draining,
+            queue_running: self.inner.data.queue_status.load(Ordering::Relaxed),
+            active_deliveries,
+            sessions,
+            safe_to_stop,
+        }
+    }
+
+    async fn cluster_query_local(&self, query: ClusterQuery) -> Vec<u8> {
+        // SPDX-SnippetBegin
+        // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+        // SPDX-License-Identifier: LicenseRef-SEL
+        #[cfg(feature = "enterprise")]
+        let is_enterprise = self.is_enterprise_edition();
+        // SPDX-SnippetEnd
+
+        #[cfg(not(feature = "enterprise"))]
+        let is_enterprise = false;
+
+        let queue_running = self.inner.data.queue_status.load(Ordering::Relaxed);
+        let result = match query {
+            ClusterQuery::Status => serde_json::to_vec(&NodeStatus {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                queue_running,
+                memory: Collector::read_metric(MetricType::ServerMemory) as u64,
+                recovery_mode: self.registry().is_recovery_mode(),
+                role: self.registry().cluster_role().map(|role| role.to_string()),
+                tasks: self.core.network.roles.enabled_tasks(),
+                draining: self.inner.data.draining.load(Ordering::Relaxed),
+            }),
+            ClusterQuery::Metrics => {
+                let mut metrics = Vec::new();
+                for counter in Collector::collect_counters(is_enterprise) {
+                    metrics.push(NodeMetric {
+                        metric: counter.id().as_str().to_string(),
+                        metric_type: NodeMetricType::Counter,
+                        count: counter.value(),
+                        sum: None,
+                    });
+                }
+                for gauge in Collector::collect_gauges(is_enterprise) {
+                    metrics.push(NodeMetric {
+                        metric: gauge.id().as_str().to_string(),
+                        metric_type: NodeMetricType::Gauge,
+                        count: gauge.get(),
+                        sum: None,
+                    });
+                }
+                for histogram in Collector::collect_histograms(is_enterprise) {
+                    metrics.push(NodeMetric {
+                        metric: histogram.id().as_str().to_string(),
+                        metric_type: NodeMetricType::Histogram,
+                        count: histogram.count(),
+                        sum: Some(histogram.sum()),
+                    });
+                }
+                serde_json::to_vec(&metrics)
+            }
+            ClusterQuery::Sessions => serde_json::to_vec(&NodeSessionList {
+                counts: NodeSessions::local(),
+                sessions: self
+                    .inner
+                    .data
+                    .active_sessions
+                    .list()
+                    .into_iter()
+                    .map(|session| {
+                        let state = session.state();
+                        NodeSession {
+                            session_id: Id::from(session.session_id),
+                            protocol: session.protocol.as_str().to_string(),
+                            listener: session.listener_id.clone(),
+                            remote_ip: session.remote_ip,
+                            remote_port: session.remote_port,
+                            account_id: state.account_id.map(Id::from),
+                            tenant_id: state.tenant_id.map(Id::from),
+                            started: UTCDateTime::from_timestamp(session.started as i64),
+                            command: state.command,
+                        }
+                    })
+                    .collect(),
+            }),
+            ClusterQuery::Queue => serde_json::to_vec(&NodeQueue {
+                queue_running,
+                active_deliveries: Collector::read_metric(MetricType::DeliveryActiveConnections)
+                    as u64,
+                deliveries: self
+                    .inner
+                    .data
+                    .active_deliveries
+                    .lock()
+                    .values()
+                    .cloned()
+                    .collect(),
+            }),
+            ClusterQuery::Drain => serde_json::to_vec(&self.drain_status()),
+        };
+
+        result.unwrap_or_default()
+    }
+}
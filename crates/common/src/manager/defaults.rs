@@ -68,6 +68,8 @@ async fn insert_safe_defaults(bp: &mut Bootstrap) -> trc::Result<()> {
                     resource_url: "file:///Users/me/code/webui/.ignore/webui.zip".into(),
                     unpack_directory: None,
                     url_prefix: Map::new(vec!["/admin".into(), "/account".into()]),
+                    hostnames: Map::default(),
+                    cache_max_age: None,
                 }
                 .into(),
             ))
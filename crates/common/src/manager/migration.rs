@@ -0,0 +1,271 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{Server, auth::EmailCache};
+use registry::{
+    schema::{
+        enums::TaskType,
+        structs::{SecretKey, SecretKeyValue, Task, TaskAccountMigration, TaskStatus},
+    },
+    types::EnumImpl,
+};
+use serde::{Deserialize, Serialize};
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder},
+};
+use trc::AddContext;
+use types::{blob_hash::BlobHash, collection::Collection, field::PrincipalField, id::Id};
+
+/// Progress and delta synchronization state of an account imported from
+/// another server, kept so that later runs only fetch what changed.
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
+)]
+pub struct MigrationState {
+    pub started_at: u64,
+    pub updated_at: u64,
+    pub completed_at: Option<u64>,
+    pub last_error: Option<String>,
+    pub mailboxes: Vec<MailboxSyncState>,
+    pub scripts: Vec<ScriptSyncState>,
+    pub calendars: Vec<DavSyncState>,
+    pub events: Vec<DavSyncState>,
+    pub address_books: Vec<DavSyncState>,
+    pub contacts: Vec<DavSyncState>,
+    pub counters: MigrationCounters,
+}
+
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
+)]
+pub struct MailboxSyncState {
+    pub name: String,
+    pub uid_validity: u32,
+    pub last_uid: u32,
+    pub messages: u64,
+}
+
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
+)]
+pub struct ScriptSyncState {
+    pub name: String,
+    pub hash: BlobHash,
+}
+
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
+)]
+pub struct DavSyncState {
+    pub href: String,
+    pub etag: String,
+    pub document_id: u32,
+}
+
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
+)]
+pub struct MigrationCounters {
+    pub messages: u64,
+    pub scripts: u64,
+    pub events: u64,
+    pub contacts: u64,
+    pub failed: u64,
+}
+
+/// Source server and accounts to import, as accepted by the management API
+/// and the `--migrate` command line option.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationRequest {
+    pub source: MigrationSource,
+    pub accounts: Vec<MigrationAccount>,
+    #[serde(default)]
+    pub create_accounts: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MigrationSource {
+    pub imap_url: Option<String>,
+    pub sieve_url: Option<String>,
+    pub caldav_url: Option<String>,
+    pub carddav_url: Option<String>,
+    pub allow_invalid_certs: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationAccount {
+    pub name: String,
+    #[serde(default)]
+    pub username: Option<String>,
+    pub password: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    pub scheduled: u64,
+    pub failed: u64,
+    pub accounts: Vec<MigrationAccountReport>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationAccountReport {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_id: Option<Id>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl MigrationSource {
+    /// Builds the migration task for an account, expanding `{username}` in
+    /// the DAV URLs with the login used on the source server.
+    pub fn build_task(&self, account_id: u32, account: &MigrationAccount) -> Task {
+        let username = account.source_username();
+        let expand =
+            |url: &Option<String>| url.as_ref().map(|url| url.replace("{username}", username));
+
+        Task::AccountMigration(TaskAccountMigration {
+            account_id: account_id.into(),
+            username: username.to_string(),
+            secret: SecretKey::Value(SecretKeyValue {
+                secret: account.password.clone(),
+            }),
+            imap_url: self.imap_url.clone(),
+            sieve_url: self.sieve_url.clone(),
+            caldav_url: expand(&self.caldav_url),
+            carddav_url: expand(&self.carddav_url),
+            allow_invalid_certs: self.allow_invalid_certs,
+            status: TaskStatus::now(),
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.imap_url.is_none()
+            && self.sieve_url.is_none()
+            && self.caldav_url.is_none()
+            && self.carddav_url.is_none()
+    }
+}
+
+impl MigrationAccount {
+    pub fn source_username(&self) -> &str {
+        self.username.as_deref().unwrap_or(&self.name)
+    }
+}
+
+impl MigrationState {
+    pub fn write(&mut self, account_id: u32, batch: &mut BatchBuilder) -> trc::Result<()> {
+        let archiver = Archiver::new(std::mem::take(self));
+        let value = store::Serialize::serialize(&archiver).caused_by(trc::location!());
+        *self = archiver.into_inner();
+
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Principal)
+            .with_document(0)
+            .set(PrincipalField::MigrationState, value?);
+        Ok(())
+    }
+}
+
+impl Server {
+    pub async fn migration_state(&self, account_id: u32) -> trc::Result<Option<MigrationState>> {
+        self.store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Principal,
+                0,
+                PrincipalField::MigrationState,
+            ))
+            .await
+            .caused_by(trc::location!())?
+            .map(|state| state.deserialize::<MigrationState>())
+            .transpose()
+            .caused_by(trc::location!())
+    }
+
+    /// Resolves the local account an imported account is written to, only
+    /// returning accounts visible to the given tenant.
+    pub async fn migration_account_id(
+        &self,
+        name: &str,
+        tenant_id: Option<u32>,
+    ) -> trc::Result<Option<u32>> {
+        let Some(EmailCache::Account(account_id)) = self
+            .rcpt_id_from_email(&name.to_lowercase())
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(None);
+        };
+
+        if let Some(tenant_id) = tenant_id
+            && self
+                .try_account(account_id)
+                .await
+                .caused_by(trc::location!())?
+                .is_none_or(|account| account.id_tenant != Some(tenant_id))
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(account_id))
+    }
+
+    /// Queues a migration task for every requested account that exists
+    /// locally. Scheduling an account again resumes it as a delta sync.
+    pub async fn schedule_migration(
+        &self,
+        request: &MigrationRequest,
+        tenant_id: Option<u32>,
+    ) -> trc::Result<MigrationReport> {
+        let mut report = MigrationReport::default();
+        let mut batch = BatchBuilder::new();
+
+        for account in &request.accounts {
+            if let Some(account_id) = self.migration_account_id(&account.name, tenant_id).await? {
+                batch.schedule_task(request.source.build_task(account_id, account));
+                report.scheduled += 1;
+                report.accounts.push(MigrationAccountReport {
+                    name: account.name.clone(),
+                    account_id: Some(Id::from(account_id)),
+                    error: None,
+                });
+            } else {
+                report.failed += 1;
+                report.accounts.push(MigrationAccountReport {
+                    name: account.name.clone(),
+                    account_id: None,
+                    error: Some("Account does not exist".to_string()),
+                });
+            }
+        }
+
+        if !batch.is_empty() {
+            trc::event!(
+                TaskManager(trc::TaskManagerEvent::TaskQueued),
+                Type = TaskType::AccountMigration.as_str(),
+                Total = report.scheduled,
+            );
+
+            self.store()
+                .write(batch.build_all())
+                .await
+                .caused_by(trc::location!())?;
+            self.notify_task_queue();
+        }
+
+        Ok(report)
+    }
+}
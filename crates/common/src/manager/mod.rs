@@ -14,10 +14,13 @@ use std::{
 use utils::HttpLimitResponse;
 
 pub mod application;
+pub mod audit;
 pub mod backup;
 pub mod boot;
+pub mod cluster;
 pub mod console;
 pub mod defaults;
+pub mod migration;
 pub mod restore;
 
 pub const SPAM_TRAINER_KEY: &[u8] = "STALWART_SPAM_TRAIN_DATA.lz4".as_bytes();
@@ -13,7 +13,7 @@ use crate::network::acme::{
 };
 use crate::{KV_ACME, Server};
 use chrono::{TimeZone, Utc};
-use dns_update::DnsRecord;
+use dns_update::{DnsRecord, DnsRecordType};
 use futures::future::try_join_all;
 use rcgen::{CertificateParams, DistinguishedName, KeyPair, PKCS_ECDSA_P256_SHA256};
 use std::collections::BTreeSet;
@@ -82,8 +82,9 @@ impl AcmeRequestBuilder {
                 .collect()
         };
 
-        let mut params = CertificateParams::new(domains.clone())
-            .map_err(|err| AcmeError::Crypto(format!("Failed to create certificate params: {}", err)))?;
+        let mut params = CertificateParams::new(domains.clone()).map_err(|err| {
+            AcmeError::Crypto(format!("Failed to create certificate params: {}", err))
+        })?;
         params.distinguished_name = DistinguishedName::new();
         let key_pair = KeyPair::generate_for(&PKCS_ECDSA_P256_SHA256)
             .map_err(|err| AcmeError::Crypto(format!("Failed to generate key pair: {}", err)))?;
@@ -188,7 +189,7 @@ impl AcmeRequestBuilder {
             .auth(url)
             .await?
             .assert_reasonable_retry_after(self.max_retries)?;
-        let mut retry_after = response.retry_after;
+        let retry_after = response.retry_after;
         let auth = response.body;
 
         let (domain, challenge_url) = match auth.status {
@@ -263,7 +264,6 @@ impl AcmeRequestBuilder {
                     ChallengeType::Unknown => unreachable!(),
                 }
 
-                self.challenge(&challenge.url).await?;
                 (domain, challenge.url.clone())
             }
             AuthStatus::Valid => return Ok(()),
@@ -272,6 +272,37 @@ impl AcmeRequestBuilder {
             }
         };
 
+        let result = self
+            .complete_authorization(url, &domain, &challenge_url, retry_after)
+            .await;
+
+        // Remove the challenge record once the authorization is no longer pending
+        if self.challenge == ChallengeType::Dns01
+            && let Some(dns_parameters) = dns_parameters
+        {
+            let domain = domain.strip_prefix("*.").unwrap_or(&domain);
+            let _ = dns_parameters
+                .updater
+                .delete(
+                    dns_parameters.origin.as_deref().unwrap_or(domain),
+                    &format!("_acme-challenge.{}", domain),
+                    DnsRecordType::TXT,
+                )
+                .await;
+        }
+
+        result
+    }
+
+    async fn complete_authorization(
+        &self,
+        url: &str,
+        domain: &str,
+        challenge_url: &str,
+        mut retry_after: Option<Duration>,
+    ) -> AcmeResult<()> {
+        self.challenge(challenge_url).await?;
+
         for i in 0u64..5 {
             tokio::time::sleep(retry_after.unwrap_or_else(|| Duration::from_secs(1u64 << i))).await;
             let response = self
@@ -23,7 +23,14 @@ impl Server {
                     .details("Failed to parse autodiscover request")
                     .ctx(trc::Key::Reason, err)
             })?;
-        let default_host = &self.core.network.server_name;
+        let client_config = self
+            .client_config(
+                emailaddress
+                    .rsplit_once('@')
+                    .map(|(_, domain)| domain)
+                    .unwrap_or_default(),
+            )
+            .await?;
 
         // Build XML response
         let mut config = String::with_capacity(1024);
@@ -54,37 +61,32 @@ impl Server {
         let _ = writeln!(&mut config, "\t\t<Account>");
         let _ = writeln!(&mut config, "\t\t\t<AccountType>email</AccountType>");
         let _ = writeln!(&mut config, "\t\t\t<Action>settings</Action>");
-        for (protocol, service) in &self.core.network.info.services {
-            let (protocol, ports) = match protocol {
-                ServiceProtocol::Imap => ("IMAP", [143, 993]),
-                ServiceProtocol::Pop3 => ("POP3", [110, 995]),
-                ServiceProtocol::Smtp => ("SMTP", [587, 465]),
+        for service in &client_config.services {
+            let protocol = match service.protocol {
+                ServiceProtocol::Imap => "IMAP",
+                ServiceProtocol::Pop3 => "POP3",
+                ServiceProtocol::Smtp => "SMTP",
                 _ => continue,
             };
 
-            for (is_tls, port) in ports.into_iter().enumerate() {
-                if is_tls == 1 || service.cleartext {
-                    let server_name = service.hostname.as_deref().unwrap_or(default_host);
-                    let _ = writeln!(&mut config, "\t\t\t<Protocol>");
-                    let _ = writeln!(&mut config, "\t\t\t\t<Type>{protocol}</Type>",);
-                    let _ = writeln!(&mut config, "\t\t\t\t<Server>{server_name}</Server>");
-                    let _ = writeln!(&mut config, "\t\t\t\t<Port>{port}</Port>");
-                    let _ = writeln!(&mut config, "\t\t\t\t<LoginName>{emailaddress}</LoginName>");
-                    let _ = writeln!(&mut config, "\t\t\t\t<AuthRequired>on</AuthRequired>");
-                    let _ = writeln!(&mut config, "\t\t\t\t<DirectoryPort>0</DirectoryPort>");
-                    let _ = writeln!(&mut config, "\t\t\t\t<ReferralPort>0</ReferralPort>");
-                    let _ = writeln!(
-                        &mut config,
-                        "\t\t\t\t<SSL>{}</SSL>",
-                        if is_tls == 1 { "on" } else { "off" }
-                    );
-                    if is_tls == 1 {
-                        let _ = writeln!(&mut config, "\t\t\t\t<Encryption>TLS</Encryption>");
-                    }
-                    let _ = writeln!(&mut config, "\t\t\t\t<SPA>off</SPA>");
-                    let _ = writeln!(&mut config, "\t\t\t</Protocol>");
-                }
+            let _ = writeln!(&mut config, "\t\t\t<Protocol>");
+            let _ = writeln!(&mut config, "\t\t\t\t<Type>{protocol}</Type>",);
+            let _ = writeln!(&mut config, "\t\t\t\t<Server>{}</Server>", service.hostname);
+            let _ = writeln!(&mut config, "\t\t\t\t<Port>{}</Port>", service.port);
+            let _ = writeln!(&mut config, "\t\t\t\t<LoginName>{emailaddress}</LoginName>");
+            let _ = writeln!(&mut config, "\t\t\t\t<AuthRequired>on</AuthRequired>");
+            let _ = writeln!(&mut config, "\t\t\t\t<DirectoryPort>0</DirectoryPort>");
+            let _ = writeln!(&mut config, "\t\t\t\t<ReferralPort>0</ReferralPort>");
+            let _ = writeln!(
+                &mut config,
+                "\t\t\t\t<SSL>{}</SSL>",
+                if service.is_tls { "on" } else { "off" }
+            );
+            if service.is_tls {
+                let _ = writeln!(&mut config, "\t\t\t\t<Encryption>TLS</Encryption>");
             }
+            let _ = writeln!(&mut config, "\t\t\t\t<SPA>off</SPA>");
+            let _ = writeln!(&mut config, "\t\t\t</Protocol>");
         }
 
         let _ = writeln!(&mut config, "\t\t</Account>");
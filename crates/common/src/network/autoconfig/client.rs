@@ -0,0 +1,162 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::Server;
+use registry::schema::{
+    enums::{ProviderInfo, ServiceProtocol},
+    structs::{Domain, Service, Tenant},
+};
+use trc::AddContext;
+use types::id::Id;
+use utils::map::vec_map::VecMap;
+
+pub struct ClientConfig {
+    pub domain: String,
+    pub provider_name: Option<String>,
+    pub provider_short_name: Option<String>,
+    pub documentation_url: Option<String>,
+    pub services: Vec<ClientService>,
+}
+
+pub struct ClientService {
+    pub protocol: ServiceProtocol,
+    pub hostname: String,
+    pub port: u16,
+    pub is_tls: bool,
+}
+
+impl Server {
+    pub async fn client_config(&self, domain: &str) -> trc::Result<ClientConfig> {
+        let mut services = self.core.network.info.services.clone();
+        let mut provider_info = self.core.network.info.provider_info.clone();
+        if let Some(domain) = self
+            .domain_provider_info(domain, &mut provider_info)
+            .await?
+        {
+            merge_services(&mut services, domain.services);
+        }
+
+        let default_host = &self.core.network.server_name;
+        let mut client_services = Vec::with_capacity(services.len() * 2);
+        for (protocol, service) in &services {
+            let (cleartext_port, tls_port) = match protocol {
+                ServiceProtocol::Imap => (143, 993),
+                ServiceProtocol::Pop3 => (110, 995),
+                ServiceProtocol::Smtp => (587, 465),
+                ServiceProtocol::Jmap
+                | ServiceProtocol::Caldav
+                | ServiceProtocol::Carddav
+                | ServiceProtocol::Webdav => (0, 443),
+                ServiceProtocol::Managesieve => continue,
+            };
+            let hostname = service.hostname.as_deref().unwrap_or(default_host);
+
+            client_services.push(ClientService {
+                protocol: *protocol,
+                hostname: hostname.to_string(),
+                port: service.port.map_or(tls_port, |port| port as u16),
+                is_tls: true,
+            });
+            if service.cleartext && cleartext_port != 0 {
+                client_services.push(ClientService {
+                    protocol: *protocol,
+                    hostname: hostname.to_string(),
+                    port: service
+                        .cleartext_port
+                        .map_or(cleartext_port, |port| port as u16),
+                    is_tls: false,
+                });
+            }
+        }
+
+        Ok(ClientConfig {
+            domain: domain.to_string(),
+            provider_name: provider_info.remove(&ProviderInfo::ProviderName),
+            provider_short_name: provider_info.remove(&ProviderInfo::ProviderShortName),
+            documentation_url: provider_info.remove(&ProviderInfo::UserDocumentation),
+            services: client_services,
+        })
+    }
+}
+
+impl Server {
+    // Applies the tenant and domain provider information, in that order, on top of
+    // the server-wide settings and returns the domain record, if any.
+    pub(crate) async fn domain_provider_info(
+        &self,
+        domain: &str,
+        provider_info: &mut VecMap<ProviderInfo, String>,
+    ) -> trc::Result<Option<Domain>> {
+        let Some(domain_cache) = self.domain(domain).await? else {
+            return Ok(None);
+        };
+
+        if let Some(tenant_id) = domain_cache.id_tenant
+            && let Some(tenant) = self
+                .registry()
+                .object::<Tenant>(Id::from(tenant_id))
+                .await
+                .caused_by(trc::location!())?
+        {
+            for (tag, text) in tenant.provider_info {
+                provider_info.set(tag, text);
+            }
+        }
+
+        let domain = self
+            .registry()
+            .object::<Domain>(Id::from(domain_cache.id))
+            .await
+            .caused_by(trc::location!())?;
+        if let Some(domain) = &domain {
+            for (tag, text) in domain.provider_info.iter() {
+                provider_info.set(*tag, text.clone());
+            }
+        }
+
+        Ok(domain)
+    }
+}
+
+impl ClientConfig {
+    pub fn services(&self, protocol: ServiceProtocol) -> impl Iterator<Item = &ClientService> {
+        self.services
+            .iter()
+            .filter(move |service| service.protocol == protocol)
+    }
+}
+
+impl ClientService {
+    pub fn base_url(&self) -> String {
+        if self.port == 443 {
+            format!("https://{}", self.hostname)
+        } else {
+            format!("https://{}:{}", self.hostname, self.port)
+        }
+    }
+}
+
+pub(crate) fn merge_services(
+    services: &mut VecMap<ServiceProtocol, Service>,
+    overrides: VecMap<ServiceProtocol, Service>,
+) {
+    for (protocol, service) in overrides {
+        if let Some(current) = services.get_mut(&protocol) {
+            if service.hostname.is_some() {
+                current.hostname = service.hostname;
+            }
+            if service.port.is_some() {
+                current.port = service.port;
+            }
+            if service.cleartext_port.is_some() {
+                current.cleartext_port = service.cleartext_port;
+            }
+            current.cleartext = service.cleartext;
+        } else {
+            services.set(protocol, service);
+        }
+    }
+}
@@ -25,7 +25,7 @@ impl Server {
                 .into_err()
                 .details("Missing domain in email address"));
         };
-        let default_host = &self.core.network.server_name;
+        let client_config = self.client_config(domain).await?;
 
         // Build XML response
         let mut config = String::with_capacity(1024);
@@ -33,49 +33,66 @@ impl Server {
         config.push_str("<clientConfig version=\"1.1\">\n");
         let _ = writeln!(&mut config, "\t<emailProvider id=\"{domain}\">");
         let _ = writeln!(&mut config, "\t\t<domain>{domain}</domain>");
-        let _ = writeln!(&mut config, "\t\t<displayName>{emailaddress}</displayName>");
         let _ = writeln!(
             &mut config,
-            "\t\t<displayShortName>{domain}</displayShortName>"
+            "\t\t<displayName>{}</displayName>",
+            client_config
+                .provider_name
+                .as_deref()
+                .unwrap_or(&emailaddress)
         );
-        for (protocol, service) in &self.core.network.info.services {
-            let (protocol, tag, ports) = match protocol {
-                ServiceProtocol::Smtp => ("smtp", "outgoingServer", [587, 465]),
-                ServiceProtocol::Imap => ("imap", "incomingServer", [143, 993]),
-                ServiceProtocol::Pop3 => ("pop3", "incomingServer", [110, 995]),
+        let _ = writeln!(
+            &mut config,
+            "\t\t<displayShortName>{}</displayShortName>",
+            client_config
+                .provider_short_name
+                .as_deref()
+                .unwrap_or(domain)
+        );
+        for service in &client_config.services {
+            let (protocol, tag) = match service.protocol {
+                ServiceProtocol::Smtp => ("smtp", "outgoingServer"),
+                ServiceProtocol::Imap => ("imap", "incomingServer"),
+                ServiceProtocol::Pop3 => ("pop3", "incomingServer"),
                 _ => continue,
             };
-            for (is_tls, port) in ports.into_iter().enumerate() {
-                if is_tls == 1 || service.cleartext {
-                    let server_name = service.hostname.as_deref().unwrap_or(default_host);
-                    let _ = writeln!(&mut config, "\t\t<{tag} type=\"{protocol}\">");
-                    let _ = writeln!(&mut config, "\t\t\t<hostname>{server_name}</hostname>");
-                    let _ = writeln!(&mut config, "\t\t\t<port>{port}</port>");
-                    let _ = writeln!(
-                        &mut config,
-                        "\t\t\t<socketType>{}</socketType>",
-                        if is_tls == 1 { "SSL" } else { "STARTTLS" }
-                    );
-                    let _ = writeln!(&mut config, "\t\t\t<username>{emailaddress}</username>");
-                    let _ = writeln!(
-                        &mut config,
-                        "\t\t\t<authentication>password-cleartext</authentication>"
-                    );
-                    let _ = writeln!(&mut config, "\t\t</{tag}>");
-                }
-            }
+            let _ = writeln!(&mut config, "\t\t<{tag} type=\"{protocol}\">");
+            let _ = writeln!(
+                &mut config,
+                "\t\t\t<hostname>{}</hostname>",
+                service.hostname
+            );
+            let _ = writeln!(&mut config, "\t\t\t<port>{}</port>", service.port);
+            let _ = writeln!(
+                &mut config,
+                "\t\t\t<socketType>{}</socketType>",
+                if service.is_tls { "SSL" } else { "STARTTLS" }
+            );
+            let _ = writeln!(&mut config, "\t\t\t<username>{emailaddress}</username>");
+            let _ = writeln!(
+                &mut config,
+                "\t\t\t<authentication>password-cleartext</authentication>"
+            );
+            let _ = writeln!(&mut config, "\t\t</{tag}>");
+        }
+        if let Some(url) = &client_config.documentation_url {
+            let _ = writeln!(&mut config, "\t\t<documentation url=\"{url}\">");
+            let _ = writeln!(
+                &mut config,
+                "\t\t\t<descr lang=\"en\">Configuration instructions</descr>"
+            );
+            let _ = writeln!(&mut config, "\t\t</documentation>");
         }
 
         config.push_str("\t</emailProvider>\n");
 
-        for (protocol, service) in &self.core.network.info.services {
-            let (tag, protocol, url) = match protocol {
+        for service in &client_config.services {
+            let (tag, protocol, url) = match service.protocol {
                 ServiceProtocol::Carddav => ("addressBook", "carddav", "card"),
                 ServiceProtocol::Caldav => ("calendar", "caldav", "cal"),
                 ServiceProtocol::Webdav => ("fileShare", "webdav", "file"),
                 _ => continue,
             };
-            let server_name = service.hostname.as_deref().unwrap_or(default_host);
 
             let _ = writeln!(&mut config, "\t<{tag} type=\"{protocol}\">");
             let _ = writeln!(&mut config, "\t\t<username>{emailaddress}</username>");
@@ -85,7 +102,8 @@ impl Server {
             );
             let _ = writeln!(
                 &mut config,
-                "\t\t<serverURL>https://{server_name}/dav/{url}</serverURL>"
+                "\t\t<serverURL>{}/dav/{url}</serverURL>",
+                service.base_url()
             );
             let _ = writeln!(&mut config, "\t</{tag}>");
         }
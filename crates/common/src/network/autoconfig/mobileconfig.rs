@@ -0,0 +1,237 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::client::{ClientConfig, ClientService};
+use crate::{Server, manager::application::Resource};
+use quick_xml::escape::escape;
+use registry::schema::enums::ServiceProtocol;
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use utils::url_params::UrlParams;
+
+impl Server {
+    pub async fn handle_mobileconfig_request(
+        &self,
+        uri: Option<&str>,
+    ) -> trc::Result<Resource<Vec<u8>>> {
+        // Obtain parameters
+        let params = UrlParams::new(uri);
+        let emailaddress = params
+            .get("emailaddress")
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+        let Some((_, domain)) = emailaddress
+            .rsplit_once('@')
+            .filter(|(local, domain)| !local.is_empty() && !domain.is_empty())
+        else {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Missing domain in email address"));
+        };
+        let client_config = self.client_config(domain).await?;
+        let emailaddress = escape(emailaddress.as_str());
+        let identifier = domain.rsplit('.').collect::<Vec<_>>().join(".");
+        let display_name = escape(
+            client_config
+                .provider_name
+                .as_deref()
+                .unwrap_or(client_config.domain.as_str()),
+        );
+
+        // Build profile
+        let mut config = String::with_capacity(4096);
+        config.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        config.push_str(
+            "<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \
+             \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n",
+        );
+        config.push_str("<plist version=\"1.0\">\n<dict>\n");
+        config.push_str("\t<key>PayloadContent</key>\n\t<array>\n");
+
+        // Mail account, preferring IMAP over POP3 and implicit TLS over STARTTLS
+        let incoming = preferred_service(&client_config, ServiceProtocol::Imap)
+            .map(|service| (service, "EmailTypeIMAP"))
+            .or_else(|| {
+                preferred_service(&client_config, ServiceProtocol::Pop3)
+                    .map(|service| (service, "EmailTypePOP"))
+            });
+        let outgoing = preferred_service(&client_config, ServiceProtocol::Smtp);
+        if let (Some((incoming, account_type)), Some(outgoing)) = (incoming, outgoing) {
+            let payload_type = "com.apple.mail.managed";
+            config.push_str("\t\t<dict>\n");
+            write_string(&mut config, "EmailAccountDescription", &display_name);
+            write_string(&mut config, "EmailAccountType", account_type);
+            write_string(&mut config, "EmailAddress", &emailaddress);
+            write_string(
+                &mut config,
+                "IncomingMailServerAuthentication",
+                "EmailAuthPassword",
+            );
+            write_string(
+                &mut config,
+                "IncomingMailServerHostName",
+                &incoming.hostname,
+            );
+            write_integer(&mut config, "IncomingMailServerPortNumber", incoming.port);
+            write_bool(&mut config, "IncomingMailServerUseSSL", incoming.is_tls);
+            write_string(&mut config, "IncomingMailServerUsername", &emailaddress);
+            write_string(
+                &mut config,
+                "OutgoingMailServerAuthentication",
+                "EmailAuthPassword",
+            );
+            write_string(
+                &mut config,
+                "OutgoingMailServerHostName",
+                &outgoing.hostname,
+            );
+            write_integer(&mut config, "OutgoingMailServerPortNumber", outgoing.port);
+            write_bool(&mut config, "OutgoingMailServerUseSSL", outgoing.is_tls);
+            write_string(&mut config, "OutgoingMailServerUsername", &emailaddress);
+            write_bool(&mut config, "OutgoingPasswordSameAsIncomingPassword", true);
+            write_payload(
+                &mut config,
+                &emailaddress,
+                &identifier,
+                payload_type,
+                "Email account",
+            );
+            config.push_str("\t\t</dict>\n");
+        }
+
+        // Calendar and contacts accounts
+        for (protocol, prefix, payload_type, description) in [
+            (
+                ServiceProtocol::Caldav,
+                "CalDAV",
+                "com.apple.caldav.account",
+                "Calendar account",
+            ),
+            (
+                ServiceProtocol::Carddav,
+                "CardDAV",
+                "com.apple.carddav.account",
+                "Contacts account",
+            ),
+        ] {
+            let Some(service) = preferred_service(&client_config, protocol) else {
+                continue;
+            };
+            config.push_str("\t\t<dict>\n");
+            write_string(
+                &mut config,
+                &format!("{prefix}AccountDescription"),
+                &display_name,
+            );
+            write_string(&mut config, &format!("{prefix}HostName"), &service.hostname);
+            write_integer(&mut config, &format!("{prefix}Port"), service.port);
+            write_string(&mut config, &format!("{prefix}PrincipalURL"), "/dav/pal/");
+            write_bool(&mut config, &format!("{prefix}UseSSL"), true);
+            write_string(&mut config, &format!("{prefix}Username"), &emailaddress);
+            write_payload(
+                &mut config,
+                &emailaddress,
+                &identifier,
+                payload_type,
+                description,
+            );
+            config.push_str("\t\t</dict>\n");
+        }
+
+        config.push_str("\t</array>\n");
+        write_string(&mut config, "PayloadDisplayName", &display_name);
+        write_string(
+            &mut config,
+            "PayloadIdentifier",
+            &format!("{identifier}.mobileconfig"),
+        );
+        write_string(&mut config, "PayloadOrganization", &display_name);
+        write_bool(&mut config, "PayloadRemovalDisallowed", false);
+        write_string(&mut config, "PayloadType", "Configuration");
+        write_string(
+            &mut config,
+            "PayloadUUID",
+            &payload_uuid(&emailaddress, "Configuration"),
+        );
+        write_integer(&mut config, "PayloadVersion", 1);
+        config.push_str("</dict>\n</plist>\n");
+
+        Ok(Resource::new(
+            "application/x-apple-aspen-config; charset=utf-8",
+            config.into_bytes(),
+        ))
+    }
+}
+
+fn preferred_service(config: &ClientConfig, protocol: ServiceProtocol) -> Option<&ClientService> {
+    config
+        .services(protocol)
+        .find(|service| service.is_tls)
+        .or_else(|| config.services(protocol).next())
+}
+
+fn write_payload(
+    config: &mut String,
+    emailaddress: &str,
+    identifier: &str,
+    payload_type: &str,
+    description: &str,
+) {
+    write_string(config, "PayloadDescription", description);
+    write_string(config, "PayloadDisplayName", description);
+    write_string(
+        config,
+        "PayloadIdentifier",
+        &format!("{identifier}.{payload_type}"),
+    );
+    write_string(config, "PayloadType", payload_type);
+    write_string(
+        config,
+        "PayloadUUID",
+        &payload_uuid(emailaddress, payload_type),
+    );
+    write_integer(config, "PayloadVersion", 1);
+}
+
+// Devices replace an installed profile only when the UUIDs match,
+// so they are derived from the account rather than generated randomly
+fn payload_uuid(emailaddress: &str, payload_type: &str) -> String {
+    let hash = Sha256::new()
+        .chain_update(emailaddress.as_bytes())
+        .chain_update(payload_type.as_bytes())
+        .finalize();
+    let mut uuid = String::with_capacity(36);
+    for (pos, byte) in hash.iter().take(16).enumerate() {
+        if matches!(pos, 4 | 6 | 8 | 10) {
+            uuid.push('-');
+        }
+        let _ = write!(&mut uuid, "{byte:02X}");
+    }
+    uuid
+}
+
+fn write_string(config: &mut String, key: &str, value: &str) {
+    let _ = writeln!(
+        config,
+        "\t\t\t<key>{key}</key>\n\t\t\t<string>{value}</string>"
+    );
+}
+
+fn write_integer(config: &mut String, key: &str, value: u16) {
+    let _ = writeln!(
+        config,
+        "\t\t\t<key>{key}</key>\n\t\t\t<integer>{value}</integer>"
+    );
+}
+
+fn write_bool(config: &mut String, key: &str, value: bool) {
+    let _ = writeln!(
+        config,
+        "\t\t\t<key>{key}</key>\n\t\t\t<{}/>",
+        if value { "true" } else { "false" }
+    );
+}
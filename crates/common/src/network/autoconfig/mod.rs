@@ -6,5 +6,7 @@
 
 pub mod autodiscover;
 pub mod autodiscover_v2;
+pub mod client;
 pub mod legacy_autoconfig;
+pub mod mobileconfig;
 pub mod pacc;
@@ -0,0 +1,260 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::Server;
+use registry::schema::structs::Account;
+use rustls_pki_types::ServerName;
+use std::time::Duration;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpStream,
+    sync::watch,
+};
+use trc::AddContext;
+
+const BACKEND_TIMEOUT: Duration = Duration::from_secs(30);
+const MAX_LINE_LENGTH: usize = 8192;
+
+pub trait BackendStream: AsyncRead + AsyncWrite + Unpin + Send + Sync {}
+
+impl<T: AsyncRead + AsyncWrite + Unpin + Send + Sync> BackendStream for T {}
+
+#[derive(Debug, Default)]
+pub struct AccountRouting {
+    pub proxy_backend: Option<String>,
+    pub login_referral: Option<String>,
+}
+
+pub struct BackendConnection {
+    pub hostname: String,
+    pub port: u16,
+    stream: Box<dyn BackendStream>,
+    buf: Vec<u8>,
+    is_tls: bool,
+}
+
+impl Server {
+    pub async fn account_routing(&self, account_id: u32) -> trc::Result<AccountRouting> {
+        self.registry()
+            .object::<Account>(account_id.into())
+            .await
+            .caused_by(trc::location!())
+            .map(|account| {
+                account
+                    .and_then(|account| account.into_user())
+                    .map(|account| AccountRouting {
+                        proxy_backend: account.proxy_backend,
+                        login_referral: account.login_referral,
+                    })
+                    .unwrap_or_default()
+            })
+    }
+
+    pub async fn connect_backend(
+        &self,
+        hostname: &str,
+        port: u16,
+    ) -> Result<BackendConnection, String> {
+        let config = &self.core.imap;
+        self.connect_remote(
+            hostname,
+            port,
+            config.proxy_implicit_tls,
+            config.proxy_allow_invalid_certs,
+        )
+        .await
+    }
+
+    pub async fn connect_remote(
+        &self,
+        hostname: &str,
+        port: u16,
+        implicit_tls: bool,
+        allow_invalid_certs: bool,
+    ) -> Result<BackendConnection, String> {
+        tokio::time::timeout(BACKEND_TIMEOUT, async {
+            let stream = TcpStream::connect((hostname, port))
+                .await
+                .map_err(|err| format!("Failed to connect to {hostname}:{port}: {err}"))?;
+            let stream: Box<dyn BackendStream> = if implicit_tls {
+                self.tls_connect(hostname, stream, allow_invalid_certs)
+                    .await?
+            } else {
+                Box::new(stream)
+            };
+
+            Ok(BackendConnection {
+                hostname: hostname.to_string(),
+                port,
+                stream,
+                buf: Vec::with_capacity(1024),
+                is_tls: implicit_tls,
+            })
+        })
+        .await
+        .map_err(|_| format!("Connection to {hostname}:{port} timed out"))?
+    }
+
+    /// Upgrades a plain text connection after a successful STARTTLS exchange.
+    pub async fn start_tls(
+        &self,
+        conn: BackendConnection,
+        allow_invalid_certs: bool,
+    ) -> Result<BackendConnection, String> {
+        if !conn.buf.is_empty() {
+            return Err(format!(
+                "{} sent unexpected data before the TLS handshake",
+                conn.hostname
+            ));
+        }
+
+        let stream = tokio::time::timeout(
+            BACKEND_TIMEOUT,
+            self.tls_connect(&conn.hostname, conn.stream, allow_invalid_certs),
+        )
+        .await
+        .map_err(|_| format!("TLS handshake with {} timed out", conn.hostname))??;
+
+        Ok(BackendConnection {
+            stream,
+            is_tls: true,
+            ..conn
+        })
+    }
+
+    async fn tls_connect(
+        &self,
+        hostname: &str,
+        stream: impl BackendStream + 'static,
+        allow_invalid_certs: bool,
+    ) -> Result<Box<dyn BackendStream>, String> {
+        let connector = if allow_invalid_certs {
+            &self.inner.data.smtp_connectors.dummy_verify
+        } else {
+            &self.inner.data.smtp_connectors.pki_verify
+        };
+        let server_name = ServerName::try_from(hostname)
+            .map_err(|_| format!("Invalid TLS server name {hostname}"))?
+            .to_owned();
+
+        Ok(Box::new(
+            connector
+                .connect(server_name, stream)
+                .await
+                .map_err(|err| format!("TLS handshake with {hostname} failed: {err}"))?,
+        ))
+    }
+}
+
+impl BackendConnection {
+    pub async fn read_line(&mut self) -> Result<String, String> {
+        self.read_raw_line()
+            .await
+            .map(|line| String::from_utf8_lossy(&line).trim_end().to_string())
+    }
+
+    /// Reads a line including its trailing CRLF.
+    pub async fn read_raw_line(&mut self) -> Result<Vec<u8>, String> {
+        loop {
+            if let Some(pos) = self.buf.iter().position(|&ch| ch == b'\n') {
+                return Ok(self.buf.drain(..=pos).collect());
+            } else if self.buf.len() > MAX_LINE_LENGTH {
+                return Err(format!("Response line from {} is too long", self.hostname));
+            }
+
+            self.fill_buf().await?;
+        }
+    }
+
+    pub async fn read_bytes(&mut self, len: usize) -> Result<Vec<u8>, String> {
+        while self.buf.len() < len {
+            self.fill_buf().await?;
+        }
+        Ok(self.buf.drain(..len).collect())
+    }
+
+    async fn fill_buf(&mut self) -> Result<(), String> {
+        let mut chunk = [0u8; 8192];
+        let bytes_read = tokio::time::timeout(BACKEND_TIMEOUT, self.stream.read(&mut chunk))
+            .await
+            .map_err(|_| format!("Timeout reading from {}", self.hostname))?
+            .map_err(|err| format!("Failed to read from {}: {err}", self.hostname))?;
+        if bytes_read == 0 {
+            return Err(format!("{} closed the connection", self.hostname));
+        }
+        self.buf.extend_from_slice(&chunk[..bytes_read]);
+        Ok(())
+    }
+
+    pub fn is_tls(&self) -> bool {
+        self.is_tls
+    }
+
+    pub async fn write(&mut self, bytes: &[u8]) -> Result<(), String> {
+        tokio::time::timeout(BACKEND_TIMEOUT, async {
+            self.stream.write_all(bytes).await?;
+            self.stream.flush().await
+        })
+        .await
+        .map_err(|_| format!("Timeout writing to {}", self.hostname))?
+        .map_err(|err| format!("Failed to write to {}: {err}", self.hostname))
+    }
+
+    /// Relays traffic between the client and the backend until either side
+    /// closes the connection, the client goes idle or the server shuts down.
+    pub async fn proxy(
+        self,
+        client_rx: &mut (impl AsyncRead + Unpin),
+        client_tx: &mut (impl AsyncWrite + Unpin),
+        idle_timeout: Duration,
+        shutdown_rx: &mut watch::Receiver<bool>,
+    ) -> Result<(), String> {
+        let hostname = self.hostname;
+        let (mut backend_rx, mut backend_tx) = tokio::io::split(self.stream);
+        if !self.buf.is_empty() {
+            client_tx
+                .write_all(&self.buf)
+                .await
+                .map_err(|err| format!("Failed to write to client: {err}"))?;
+            let _ = client_tx.flush().await;
+        }
+
+        let mut client_buf = vec![0u8; 8192];
+        let mut backend_buf = vec![0u8; 8192];
+        loop {
+            tokio::select! {
+                result = tokio::time::timeout(idle_timeout, client_rx.read(&mut client_buf)) => {
+                    match result {
+                        Ok(Ok(0)) => return Ok(()),
+                        Ok(Ok(bytes_read)) => {
+                            backend_tx
+                                .write_all(&client_buf[..bytes_read])
+                                .await
+                                .map_err(|err| format!("Failed to write to {hostname}: {err}"))?;
+                            let _ = backend_tx.flush().await;
+                        }
+                        Ok(Err(err)) => return Err(format!("Failed to read from client: {err}")),
+                        Err(_) => return Err("Client connection timed out".to_string()),
+                    }
+                },
+                result = backend_rx.read(&mut backend_buf) => {
+                    match result {
+                        Ok(0) => return Ok(()),
+                        Ok(bytes_read) => {
+                            client_tx
+                                .write_all(&backend_buf[..bytes_read])
+                                .await
+                                .map_err(|err| format!("Failed to write to client: {err}"))?;
+                            let _ = client_tx.flush().await;
+                        }
+                        Err(err) => return Err(format!("Failed to read from {hostname}: {err}")),
+                    }
+                },
+                _ = shutdown_rx.changed() => return Ok(()),
+            }
+        }
+    }
+}
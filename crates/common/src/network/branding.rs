@@ -0,0 +1,57 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::Server;
+use registry::schema::enums::ProviderInfo;
+use serde::Serialize;
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Branding {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub short_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logo_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+impl Server {
+    pub async fn branding(&self, hostname: &str) -> trc::Result<Branding> {
+        let hostname = hostname.trim_end_matches('.').to_lowercase();
+        let mut provider_info = self.core.network.info.provider_info.clone();
+
+        // Look up the hostname first, then its registrable domain
+        if self
+            .domain_provider_info(&hostname, &mut provider_info)
+            .await?
+            .is_none()
+            && let Some(domain) = psl::domain_str(&hostname).filter(|domain| *domain != hostname)
+        {
+            self.domain_provider_info(domain, &mut provider_info)
+                .await?;
+        }
+
+        Ok(Branding {
+            name: provider_info.remove(&ProviderInfo::ProviderName),
+            short_name: provider_info.remove(&ProviderInfo::ProviderShortName),
+            logo_url: provider_info
+                .remove(&ProviderInfo::LogoUrl)
+                .filter(|url| url.starts_with("https://") || url.starts_with("http://")),
+            color: provider_info
+                .remove(&ProviderInfo::BrandColor)
+                .filter(|color| is_hex_color(color)),
+        })
+    }
+}
+
+fn is_hex_color(color: &str) -> bool {
+    color.strip_prefix('#').is_some_and(|hex| {
+        matches!(hex.len(), 3 | 6) && hex.chars().all(|ch| ch.is_ascii_hexdigit())
+    })
+}
@@ -0,0 +1,70 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{USER_AGENT, config::network::Captcha};
+use serde::Deserialize;
+use std::{fmt::Write, net::IpAddr, time::Duration};
+use utils::HttpLimitResponse;
+
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_RESPONSE_SIZE: usize = 8192;
+
+#[derive(Debug, Deserialize)]
+struct VerifyResponse {
+    success: bool,
+}
+
+impl Captcha {
+    /// Verifies a CAPTCHA response token with the provider. Errors are only
+    /// returned when the provider could not be reached or sent an invalid reply.
+    pub async fn verify(&self, response: &str, remote_ip: IpAddr) -> Result<bool, String> {
+        let mut body = String::with_capacity(self.secret.len() + response.len() + 64);
+        body.push_str("secret=");
+        url_encode(&mut body, &self.secret);
+        body.push_str("&response=");
+        url_encode(&mut body, response);
+        let _ = write!(&mut body, "&remoteip={remote_ip}");
+
+        let response = reqwest::Client::builder()
+            .timeout(VERIFY_TIMEOUT)
+            .user_agent(USER_AGENT)
+            .build()
+            .map_err(|err| format!("Failed to create HTTP client: {err}"))?
+            .post(self.verify_url)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(body)
+            .send()
+            .await
+            .map_err(|err| format!("Request to {} failed: {err}", self.verify_url))?;
+
+        if !response.status().is_success() {
+            return Err(format!(
+                "Request to {} failed with status {}",
+                self.verify_url,
+                response.status()
+            ));
+        }
+
+        let bytes = response
+            .bytes_with_limit(MAX_RESPONSE_SIZE)
+            .await
+            .map_err(|err| format!("Failed to read response from {}: {err}", self.verify_url))?
+            .ok_or_else(|| format!("Response from {} is too large", self.verify_url))?;
+        serde_json::from_slice::<VerifyResponse>(&bytes)
+            .map(|response| response.success)
+            .map_err(|err| format!("Failed to parse response from {}: {err}", self.verify_url))
+    }
+}
+
+fn url_encode(out: &mut String, value: &str) {
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(byte as char);
+        } else {
+            let _ = write!(out, "%{byte:02X}");
+        }
+    }
+}
@@ -0,0 +1,386 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use ahash::AHashSet;
+use mail_builder::{
+    headers::date::Date,
+    mime::{MimePart, make_boundary},
+};
+use mail_parser::{
+    HeaderName, Message, MessageParser, MessagePart, MessagePartId, PartType,
+    decoders::html::html_to_text,
+};
+use registry::schema::structs::Domain;
+use std::{ops::Range, str::FromStr};
+use utils::template::{Template, TemplateItem, Variables};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DomainDisclaimer {
+    pub text: Template<DisclaimerVariable>,
+    pub html: Template<DisclaimerVariable>,
+    pub inbound: bool,
+    pub wrap_signed: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DisclaimerVariable {
+    Sender,
+    Domain,
+    Date,
+}
+
+impl DomainDisclaimer {
+    pub fn parse(domain: &Domain) -> Result<Option<Self>, String> {
+        let (text, html) = match (
+            domain.disclaimer_text.as_deref(),
+            domain.disclaimer_html.as_deref(),
+        ) {
+            (Some(text), Some(html)) => (text.to_string(), html.to_string()),
+            (Some(text), None) => (text.to_string(), text_to_html(text)),
+            (None, Some(html)) => (html_to_text(html), html.to_string()),
+            (None, None) => return Ok(None),
+        };
+
+        // Plain text footers must not be HTML escaped
+        let mut text = Template::parse(&text)?;
+        for item in &mut text.items {
+            if let TemplateItem::Variable { escape, .. } = item {
+                *escape = false;
+            }
+        }
+
+        Ok(Some(DomainDisclaimer {
+            text,
+            html: Template::parse(&html)?,
+            inbound: domain.disclaimer_inbound,
+            wrap_signed: domain.disclaimer_wrap_signed,
+        }))
+    }
+
+    pub fn apply(&self, raw_message: &[u8], sender: &str, domain: &str) -> Option<Vec<u8>> {
+        let message = MessageParser::new().parse(raw_message)?;
+        let mut variables = Variables::new();
+        let date = Date::now().to_rfc822();
+        variables.insert_single(DisclaimerVariable::Sender, sender);
+        variables.insert_single(DisclaimerVariable::Domain, domain);
+        variables.insert_single(DisclaimerVariable::Date, date.as_str());
+        let text = self.text.eval(&variables);
+        let html = self.html.eval(&variables);
+
+        // Modifying signed or encrypted content would invalidate it
+        let root = message.root_part();
+        if is_protected(root) {
+            return if self.wrap_signed {
+                Some(wrap_message(&message, text))
+            } else {
+                None
+            };
+        }
+
+        // Single part messages are rebuilt with the footer appended to the body
+        if !matches!(root.body, PartType::Multipart(_)) {
+            let part = footer_part(root, &text, &html)?;
+            let mut output = Vec::with_capacity(raw_message.len() + text.len() + html.len());
+            write_headers(&mut output, &message, false);
+            output.extend_from_slice(b"MIME-Version: 1.0\r\n");
+            part.write_part(&mut output).ok()?;
+            return Some(output);
+        }
+
+        // Append the footer to the last text and HTML body parts,
+        // leaving any parts nested inside signed content untouched
+        let mut protected = AHashSet::new();
+        collect_protected(&message, 0, false, &mut protected);
+        let mut edits: Vec<(Range<usize>, Vec<u8>)> = Vec::new();
+        for part_id in [
+            last_body_part(&message, &message.text_body, &protected),
+            last_body_part(&message, &message.html_body, &protected),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            let part = &message.parts[part_id as usize];
+            let range = part.raw_header_offset() as usize..part.raw_end_offset() as usize;
+            if edits.iter().any(|(edit, _)| edit == &range) {
+                continue;
+            }
+            if let Some(footer) = footer_part(part, &text, &html) {
+                let mut bytes = Vec::with_capacity(range.len() + html.len());
+                footer.write_part(&mut bytes).ok()?;
+                edits.push((range, bytes));
+            }
+        }
+        if edits.is_empty() {
+            return None;
+        }
+        edits.sort_unstable_by_key(|(range, _)| range.start);
+
+        let mut output = Vec::with_capacity(raw_message.len() + text.len() + html.len());
+        let mut offset = 0;
+        for (range, bytes) in edits {
+            output.extend_from_slice(raw_message.get(offset..range.start)?);
+            output.extend_from_slice(&bytes);
+            offset = range.end;
+        }
+        output.extend_from_slice(raw_message.get(offset..)?);
+        Some(output)
+    }
+
+    pub fn weight(&self) -> u64 {
+        (std::mem::size_of::<DomainDisclaimer>() + self.text.size + self.html.size) as u64
+    }
+}
+
+fn footer_part<'x>(part: &MessagePart<'_>, text: &str, html: &str) -> Option<MimePart<'x>> {
+    if part
+        .content_disposition()
+        .is_some_and(|cd| cd.ctype().eq_ignore_ascii_case("attachment"))
+    {
+        return None;
+    }
+
+    match &part.body {
+        PartType::Text(body) => {
+            let mut body = body.to_string();
+            if !body.ends_with('\n') {
+                body.push_str("\r\n");
+            }
+            body.push_str("\r\n");
+            body.push_str(text);
+            Some(MimePart::new("text/plain", body))
+        }
+        PartType::Html(body) => {
+            let mut body = body.to_string();
+            let lower = body.to_ascii_lowercase();
+            let pos = lower.rfind("</body>").unwrap_or(body.len());
+            body.insert_str(pos, html);
+            Some(MimePart::new("text/html", body))
+        }
+        _ => None,
+    }
+}
+
+fn last_body_part(
+    message: &Message<'_>,
+    body_parts: &[MessagePartId],
+    protected: &AHashSet<MessagePartId>,
+) -> Option<MessagePartId> {
+    body_parts
+        .iter()
+        .rev()
+        .find(|part_id| {
+            !protected.contains(*part_id)
+                && matches!(
+                    message.parts.get(**part_id as usize).map(|part| &part.body),
+                    Some(PartType::Text(_) | PartType::Html(_))
+                )
+        })
+        .copied()
+}
+
+fn collect_protected(
+    message: &Message<'_>,
+    part_id: MessagePartId,
+    parent_protected: bool,
+    protected: &mut AHashSet<MessagePartId>,
+) {
+    let Some(part) = message.parts.get(part_id as usize) else {
+        return;
+    };
+    let parent_protected = parent_protected || is_protected(part);
+    if parent_protected {
+        protected.insert(part_id);
+    }
+    if let PartType::Multipart(children) = &part.body {
+        for child_id in children {
+            collect_protected(message, *child_id, parent_protected, protected);
+        }
+    }
+}
+
+fn is_protected(part: &MessagePart<'_>) -> bool {
+    part.content_type().is_some_and(|ct| {
+        let sub_type = ct.subtype().unwrap_or_default();
+        (ct.ctype().eq_ignore_ascii_case("multipart")
+            && (sub_type.eq_ignore_ascii_case("signed")
+                || sub_type.eq_ignore_ascii_case("encrypted")))
+            || (ct.ctype().eq_ignore_ascii_case("application")
+                && sub_type.eq_ignore_ascii_case("pkcs7-mime"))
+    })
+}
+
+// Signed content is kept as-is and sent as the first part of a new
+// multipart/mixed message, followed by the footer
+fn wrap_message(message: &Message<'_>, text: String) -> Vec<u8> {
+    let raw_message = message.raw_message();
+    let root = message.root_part();
+    let boundary = make_boundary("_");
+    let mut output = Vec::with_capacity(raw_message.len() + text.len() + 256);
+    write_headers(&mut output, message, false);
+    output.extend_from_slice(b"MIME-Version: 1.0\r\n");
+    output.extend_from_slice(b"Content-Type: multipart/mixed; boundary=\"");
+    output.extend_from_slice(boundary.as_bytes());
+    output.extend_from_slice(b"\"\r\n\r\n--");
+    output.extend_from_slice(boundary.as_bytes());
+    output.extend_from_slice(b"\r\n");
+    write_headers(&mut output, message, true);
+    output.extend_from_slice(b"\r\n");
+    output.extend_from_slice(
+        raw_message
+            .get(root.raw_body_offset() as usize..root.raw_end_offset() as usize)
+            .unwrap_or_default(),
+    );
+    output.extend_from_slice(b"\r\n--");
+    output.extend_from_slice(boundary.as_bytes());
+    output.extend_from_slice(b"\r\n");
+    let _ = MimePart::new("text/plain", text).write_part(&mut output);
+    output.extend_from_slice(b"\r\n--");
+    output.extend_from_slice(boundary.as_bytes());
+    output.extend_from_slice(b"--\r\n");
+    output
+}
+
+fn write_headers(output: &mut Vec<u8>, message: &Message<'_>, content_headers: bool) {
+    let raw_message = message.raw_message();
+    for header in &message.root_part().headers {
+        let is_content_header = match &header.name {
+            HeaderName::MimeVersion => continue,
+            HeaderName::ContentType
+            | HeaderName::ContentTransferEncoding
+            | HeaderName::ContentDisposition
+            | HeaderName::ContentDescription
+            | HeaderName::ContentId
+            | HeaderName::ContentLanguage
+            | HeaderName::ContentLocation => true,
+            _ => false,
+        };
+        if is_content_header == content_headers
+            && let Some(bytes) =
+                raw_message.get(header.offset_field as usize..header.offset_end as usize)
+        {
+            output.extend_from_slice(bytes);
+            if !bytes.ends_with(b"\n") {
+                output.extend_from_slice(b"\r\n");
+            }
+        }
+    }
+}
+
+fn text_to_html(text: &str) -> String {
+    let mut html = String::with_capacity(text.len() + 32);
+    html.push_str("<p>");
+    for (pos, line) in text.lines().enumerate() {
+        if pos > 0 {
+            html.push_str("<br>");
+        }
+        html.push_str(&quick_xml::escape::escape(line));
+    }
+    html.push_str("</p>");
+    html
+}
+
+impl FromStr for DisclaimerVariable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sender" => Ok(DisclaimerVariable::Sender),
+            "domain" => Ok(DisclaimerVariable::Domain),
+            "date" => Ok(DisclaimerVariable::Date),
+            _ => Err(format!("Unknown disclaimer variable: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn disclaimer(wrap_signed: bool) -> DomainDisclaimer {
+        DomainDisclaimer::parse(&Domain {
+            disclaimer_text: Some("Sent by {{sender}} from {{domain}}".to_string()),
+            disclaimer_wrap_signed: wrap_signed,
+            ..Default::default()
+        })
+        .unwrap()
+        .unwrap()
+    }
+
+    fn body_texts(raw_message: &[u8]) -> Vec<String> {
+        let message = MessageParser::new().parse(raw_message).unwrap();
+        message
+            .parts
+            .iter()
+            .filter_map(|part| match &part.body {
+                PartType::Text(text) | PartType::Html(text) => Some(text.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn single_part() {
+        let result = disclaimer(false)
+            .apply(
+                b"From: a@example.org\r\nSubject: test\r\n\r\nHello\r\n",
+                "a@example.org",
+                "example.org",
+            )
+            .unwrap();
+        let result = String::from_utf8(result).unwrap();
+        assert!(result.starts_with("From: a@example.org\r\nSubject: test\r\n"));
+        assert_eq!(
+            body_texts(result.as_bytes()),
+            vec!["Hello\r\n\r\nSent by a@example.org from example.org".to_string()]
+        );
+    }
+
+    #[test]
+    fn multipart_alternative() {
+        let result = disclaimer(false)
+            .apply(
+                concat!(
+                    "From: a@example.org\r\n",
+                    "Content-Type: multipart/alternative; boundary=\"b\"\r\n\r\n",
+                    "--b\r\nContent-Type: text/plain\r\n\r\nHello\r\n",
+                    "--b\r\nContent-Type: text/html\r\n\r\n<html><body>Hello</body></html>\r\n",
+                    "--b--\r\n"
+                )
+                .as_bytes(),
+                "a@example.org",
+                "example.org",
+            )
+            .unwrap();
+        let texts = body_texts(&result);
+        assert_eq!(texts.len(), 2, "{texts:?}");
+        assert!(texts[0].ends_with("Sent by a@example.org from example.org"));
+        assert!(texts[1].contains("<p>Sent by a@example.org from example.org</p></body>"));
+    }
+
+    #[test]
+    fn signed_messages() {
+        let message = concat!(
+            "From: a@example.org\r\n",
+            "Content-Type: multipart/signed; protocol=\"application/pgp-signature\"; ",
+            "boundary=\"s\"\r\n\r\n",
+            "--s\r\nContent-Type: text/plain\r\n\r\nHello\r\n",
+            "--s\r\nContent-Type: application/pgp-signature\r\n\r\nsig\r\n",
+            "--s--\r\n"
+        );
+        assert_eq!(
+            disclaimer(false).apply(message.as_bytes(), "a@example.org", "example.org"),
+            None
+        );
+
+        let result = disclaimer(true)
+            .apply(message.as_bytes(), "a@example.org", "example.org")
+            .unwrap();
+        let result = String::from_utf8(result).unwrap();
+        assert!(result.contains("Content-Type: multipart/mixed"));
+        assert!(result.contains("--s\r\nContent-Type: text/plain\r\n\r\nHello\r\n"));
+        assert!(result.contains("Sent by a@example.org from example.org"));
+    }
+}
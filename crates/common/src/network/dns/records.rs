@@ -4,7 +4,11 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use crate::{Server, config::network::Pacc, network::dkim::generate_dkim_dns_record};
+use crate::{
+    Server,
+    config::network::Pacc,
+    network::{autoconfig::client::merge_services, dkim::generate_dkim_dns_record},
+};
 use ahash::{AHashMap, AHashSet};
 use base64::{Engine, engine::general_purpose};
 use dns_update::{
@@ -160,7 +164,9 @@ impl Server {
                     });
                 }
                 DnsRecordType::Srv => {
-                    for (protocol, service) in &network.info.services {
+                    let mut domain_services = network.info.services.clone();
+                    merge_services(&mut domain_services, domain.services.clone());
+                    for (protocol, service) in &domain_services {
                         let target =
                             format!("{}.", service.hostname.as_deref().unwrap_or(default_host));
                         let services = match protocol {
@@ -180,7 +186,7 @@ impl Server {
                                         target: target.clone(),
                                         priority: 0,
                                         weight: 1,
-                                        port: 443,
+                                        port: service.port.map_or(443, |port| port as u16),
                                     }),
                                 });
                                 continue;
@@ -193,6 +199,12 @@ impl Server {
 
                         for (is_tls, (service_name, port)) in services.into_iter().enumerate() {
                             if is_tls == 1 || service.cleartext {
+                                let port = if is_tls == 1 {
+                                    service.port
+                                } else {
+                                    service.cleartext_port
+                                }
+                                .map_or(port, |port| port as u16);
                                 records.push(NamedDnsRecord {
                                     name: format!("_{service_name}._tcp.{domain_name}."),
                                     record: DnsRecord::SRV(SRVRecord {
@@ -0,0 +1,429 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::SessionStream;
+use crate::Server;
+use sha2::{Digest, Sha256};
+use std::{
+    borrow::Cow,
+    fmt::Write,
+    io,
+    net::IpAddr,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+// Largest ClientHello that is buffered for fingerprinting, clients sending
+// anything bigger are not fingerprinted but the handshake still proceeds.
+const MAX_CLIENT_HELLO_SIZE: usize = 16 * 1024;
+
+const CONTENT_TYPE_HANDSHAKE: u8 = 0x16;
+const HANDSHAKE_CLIENT_HELLO: u8 = 0x01;
+
+const EXT_SERVER_NAME: u16 = 0x0000;
+const EXT_SUPPORTED_GROUPS: u16 = 0x000a;
+const EXT_EC_POINT_FORMATS: u16 = 0x000b;
+const EXT_SIGNATURE_ALGORITHMS: u16 = 0x000d;
+const EXT_ALPN: u16 = 0x0010;
+const EXT_SUPPORTED_VERSIONS: u16 = 0x002b;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsFingerprint {
+    pub ja3: String,
+    pub ja4: String,
+}
+
+// Records the bytes read from the client until the ClientHello is complete,
+// after which reads go straight to the underlying stream.
+pub struct FingerprintStream<T> {
+    inner: T,
+    client_hello: Option<Vec<u8>>,
+    fingerprint: Option<TlsFingerprint>,
+}
+
+enum ClientHelloState {
+    Incomplete,
+    Complete(Vec<u8>),
+    Invalid,
+}
+
+impl<T> FingerprintStream<T> {
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner,
+            client_hello: Some(Vec::with_capacity(2048)),
+            fingerprint: None,
+        }
+    }
+
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for FingerprintStream<T> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        let start = buf.filled().len();
+        let result = Pin::new(&mut this.inner).poll_read(cx, buf);
+
+        if let Poll::Ready(Ok(())) = &result
+            && let Some(client_hello) = &mut this.client_hello
+        {
+            let bytes = &buf.filled()[start..];
+            client_hello.extend_from_slice(bytes);
+
+            match client_hello_message(client_hello) {
+                ClientHelloState::Incomplete
+                    if !bytes.is_empty() && client_hello.len() <= MAX_CLIENT_HELLO_SIZE => {}
+                ClientHelloState::Complete(message) => {
+                    this.fingerprint = TlsFingerprint::parse(&message);
+                    this.client_hello = None;
+                }
+                _ => {
+                    this.client_hello = None;
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for FingerprintStream<T> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+
+    fn poll_write_vectored(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.get_mut().inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+}
+
+impl<T: SessionStream> SessionStream for FingerprintStream<T> {
+    fn is_tls(&self) -> bool {
+        self.inner.is_tls()
+    }
+
+    fn tls_version_and_cipher(&self) -> (Cow<'static, str>, Cow<'static, str>) {
+        self.inner.tls_version_and_cipher()
+    }
+
+    fn peer_certificate(&self) -> Option<&[u8]> {
+        self.inner.peer_certificate()
+    }
+
+    fn tls_fingerprint(&self) -> Option<&TlsFingerprint> {
+        self.fingerprint.as_ref()
+    }
+}
+
+impl Server {
+    pub fn is_tls_fingerprint_blocked(&self, fingerprint: &TlsFingerprint, ip: IpAddr) -> bool {
+        let blocked = &self.core.network.security.blocked_tls_fingerprints;
+        !blocked.is_empty()
+            && (blocked.contains(&fingerprint.ja4) || blocked.contains(&fingerprint.ja3))
+            && !self.is_ip_allowed(ip)
+    }
+}
+
+// Reassembles the ClientHello handshake message, which may be split
+// across several TLS records.
+fn client_hello_message(data: &[u8]) -> ClientHelloState {
+    let mut message = Vec::new();
+    let mut pos = 0;
+
+    while let Some(header) = data.get(pos..pos + 5) {
+        if header[0] != CONTENT_TYPE_HANDSHAKE {
+            return ClientHelloState::Invalid;
+        }
+        let record_len = u16::from_be_bytes([header[3], header[4]]) as usize;
+        let Some(record) = data.get(pos + 5..pos + 5 + record_len) else {
+            break;
+        };
+        message.extend_from_slice(record);
+        pos += 5 + record_len;
+
+        if let Some(header) = message.get(..4) {
+            if header[0] != HANDSHAKE_CLIENT_HELLO {
+                return ClientHelloState::Invalid;
+            }
+            let message_len = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+            if message.len() >= message_len + 4 {
+                message.truncate(message_len + 4);
+                return ClientHelloState::Complete(message);
+            }
+        }
+    }
+
+    if data.first().is_some_and(|ct| *ct != CONTENT_TYPE_HANDSHAKE) {
+        ClientHelloState::Invalid
+    } else {
+        ClientHelloState::Incomplete
+    }
+}
+
+impl TlsFingerprint {
+    // Builds the JA3 and JA4 fingerprints from a ClientHello handshake
+    // message, GREASE values (RFC 8701) are ignored by both methods.
+    pub fn parse(message: &[u8]) -> Option<Self> {
+        let mut reader = Reader {
+            data: message.get(4..)?,
+            pos: 0,
+        };
+        let legacy_version = reader.u16()?;
+        reader.skip(32)?;
+        let session_id_len = reader.u8()? as usize;
+        reader.skip(session_id_len)?;
+        let ciphers = reader
+            .vec_u16()?
+            .into_iter()
+            .filter(|cipher| !is_grease(*cipher))
+            .collect::<Vec<_>>();
+        let compression_len = reader.u8()? as usize;
+        reader.skip(compression_len)?;
+
+        let mut extensions = Vec::new();
+        let mut groups = Vec::new();
+        let mut point_formats = Vec::new();
+        let mut signature_algorithms = Vec::new();
+        let mut alpn = None;
+        let mut max_version = None;
+        let mut has_sni = false;
+
+        if !reader.is_empty() {
+            let mut ext_reader = Reader {
+                data: reader.bytes_u16()?,
+                pos: 0,
+            };
+            while !ext_reader.is_empty() {
+                let ext_type = ext_reader.u16()?;
+                let mut data = Reader {
+                    data: ext_reader.bytes_u16()?,
+                    pos: 0,
+                };
+                if is_grease(ext_type) {
+                    continue;
+                }
+                extensions.push(ext_type);
+
+                match ext_type {
+                    EXT_SERVER_NAME => {
+                        has_sni = true;
+                    }
+                    EXT_SUPPORTED_GROUPS => {
+                        groups = data
+                            .vec_u16()?
+                            .into_iter()
+                            .filter(|group| !is_grease(*group))
+                            .collect();
+                    }
+                    EXT_EC_POINT_FORMATS => {
+                        let len = data.u8()? as usize;
+                        point_formats = data.bytes(len)?.to_vec();
+                    }
+                    EXT_SIGNATURE_ALGORITHMS => {
+                        signature_algorithms = data
+                            .vec_u16()?
+                            .into_iter()
+                            .filter(|alg| !is_grease(*alg))
+                            .collect();
+                    }
+                    EXT_ALPN => {
+                        let mut protocols = Reader {
+                            data: data.bytes_u16()?,
+                            pos: 0,
+                        };
+                        let len = protocols.u8()? as usize;
+                        alpn = Some(protocols.bytes(len)?.to_vec());
+                    }
+                    EXT_SUPPORTED_VERSIONS => {
+                        let len = data.u8()? as usize;
+                        let mut versions = Reader {
+                            data: data.bytes(len)?,
+                            pos: 0,
+                        };
+                        while !versions.is_empty() {
+                            let version = versions.u16()?;
+                            if !is_grease(version) && max_version.is_none_or(|max| version > max) {
+                                max_version = Some(version);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // JA3: version,ciphers,extensions,groups,point formats as decimals
+        let ja3 = format!(
+            "{legacy_version},{},{},{},{}",
+            join_decimal(&ciphers),
+            join_decimal(&extensions),
+            join_decimal(&groups),
+            point_formats
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join("-")
+        );
+        let ja3 = format!("{:x}", md5::compute(ja3.as_bytes()));
+
+        // JA4: readable prefix followed by truncated hashes of the sorted
+        // cipher suites and extensions
+        let version = match max_version.unwrap_or(legacy_version) {
+            0x0304 => "13",
+            0x0303 => "12",
+            0x0302 => "11",
+            0x0301 => "10",
+            0x0300 => "s3",
+            0x0002 => "s2",
+            _ => "00",
+        };
+        let alpn = match alpn.as_deref() {
+            Some([first, .., last])
+                if first.is_ascii_alphanumeric() && last.is_ascii_alphanumeric() =>
+            {
+                format!("{}{}", *first as char, *last as char)
+            }
+            Some([single]) if single.is_ascii_alphanumeric() => {
+                format!("{}{}", *single as char, *single as char)
+            }
+            Some(value @ [_, ..]) => {
+                let hex = format!("{:02x}{:02x}", value[0], value[value.len() - 1]);
+                format!("{}{}", &hex[..1], &hex[3..])
+            }
+            _ => "00".to_string(),
+        };
+        let mut sorted_ciphers = ciphers.clone();
+        sorted_ciphers.sort_unstable();
+        let mut sorted_extensions = extensions
+            .iter()
+            .copied()
+            .filter(|ext| *ext != EXT_SERVER_NAME && *ext != EXT_ALPN)
+            .collect::<Vec<_>>();
+        sorted_extensions.sort_unstable();
+        let mut extensions_str = join_hex(&sorted_extensions);
+        if !signature_algorithms.is_empty() {
+            extensions_str.push('_');
+            extensions_str.push_str(&join_hex(&signature_algorithms));
+        }
+
+        let ja4 = format!(
+            "t{version}{}{:02}{:02}{alpn}_{}_{}",
+            if has_sni { 'd' } else { 'i' },
+            ciphers.len().min(99),
+            extensions.len().min(99),
+            truncated_hash(&join_hex(&sorted_ciphers), sorted_ciphers.is_empty()),
+            truncated_hash(&extensions_str, sorted_extensions.is_empty()),
+        );
+
+        Some(TlsFingerprint { ja3, ja4 })
+    }
+}
+
+struct Reader<'x> {
+    data: &'x [u8],
+    pos: usize,
+}
+
+impl<'x> Reader<'x> {
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn bytes(&mut self, len: usize) -> Option<&'x [u8]> {
+        let bytes = self.data.get(self.pos..self.pos + len)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn skip(&mut self, len: usize) -> Option<()> {
+        self.bytes(len).map(|_| ())
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|b| b[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.bytes(2).map(|b| u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn bytes_u16(&mut self) -> Option<&'x [u8]> {
+        let len = self.u16()? as usize;
+        self.bytes(len)
+    }
+
+    fn vec_u16(&mut self) -> Option<Vec<u16>> {
+        Some(
+            self.bytes_u16()?
+                .chunks_exact(2)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                .collect(),
+        )
+    }
+}
+
+fn is_grease(value: u16) -> bool {
+    value & 0x0f0f == 0x0a0a && value >> 8 == value & 0xff
+}
+
+fn join_decimal(values: &[u16]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn join_hex(values: &[u16]) -> String {
+    let mut result = String::with_capacity(values.len() * 5);
+    for (pos, value) in values.iter().enumerate() {
+        if pos > 0 {
+            result.push(',');
+        }
+        let _ = write!(result, "{value:04x}");
+    }
+    result
+}
+
+fn truncated_hash(value: &str, is_empty: bool) -> String {
+    if !is_empty {
+        let mut hash = String::with_capacity(12);
+        for byte in &Sha256::digest(value.as_bytes())[..6] {
+            let _ = write!(hash, "{byte:02x}");
+        }
+        hash
+    } else {
+        "000000000000".to_string()
+    }
+}
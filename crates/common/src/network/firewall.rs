@@ -0,0 +1,137 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::Server;
+use registry::schema::{prelude::ObjectType, structs};
+use std::time::Duration;
+use store::registry::bootstrap::Bootstrap;
+use utils::Client;
+
+#[derive(Debug, Clone)]
+pub struct FirewallSync {
+    pub backend: FirewallBackend,
+    pub interval: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub enum FirewallBackend {
+    Nftables {
+        family: String,
+        table: String,
+        set_ipv4: String,
+        set_ipv6: String,
+    },
+    Ipset {
+        set_ipv4: String,
+        set_ipv6: String,
+    },
+    Webhook {
+        url: String,
+        client: Client,
+        timeout: Duration,
+    },
+    Crowdsec {
+        url: String,
+        machine_id: String,
+        password: String,
+        client: Client,
+        timeout: Duration,
+    },
+}
+
+impl FirewallSync {
+    pub async fn parse(bp: &mut Bootstrap, security: &structs::Security) -> Option<Self> {
+        let backend = match &security.firewall_sync {
+            structs::FirewallSync::Disabled => return None,
+            structs::FirewallSync::Nftables(nft) => FirewallBackend::Nftables {
+                family: nft.nft_family.clone(),
+                table: nft.nft_table.clone(),
+                set_ipv4: nft.set_ipv4.clone(),
+                set_ipv6: nft.set_ipv6.clone(),
+            },
+            structs::FirewallSync::Ipset(ipset) => FirewallBackend::Ipset {
+                set_ipv4: ipset.set_ipv4.clone(),
+                set_ipv6: ipset.set_ipv6.clone(),
+            },
+            structs::FirewallSync::Webhook(hook) => {
+                match hook
+                    .http_auth
+                    .build_http_client(
+                        hook.http_headers.clone(),
+                        "application/json".into(),
+                        hook.timeout,
+                        hook.allow_invalid_certs,
+                    )
+                    .await
+                {
+                    Ok(client) => FirewallBackend::Webhook {
+                        url: hook.url.clone(),
+                        client,
+                        timeout: hook.timeout.into_inner(),
+                    },
+                    Err(err) => {
+                        bp.build_error(
+                            ObjectType::Security.singleton(),
+                            format!("Unable to build firewall webhook HTTP client: {err}"),
+                        );
+                        return None;
+                    }
+                }
+            }
+            structs::FirewallSync::Crowdsec(crowdsec) => {
+                let client = structs::HttpAuth::Unauthenticated
+                    .build_http_client(
+                        Default::default(),
+                        "application/json".into(),
+                        crowdsec.timeout,
+                        crowdsec.allow_invalid_certs,
+                    )
+                    .await;
+                let password = crowdsec.secret.secret().await;
+
+                match (client, password) {
+                    (Ok(client), Ok(password)) => FirewallBackend::Crowdsec {
+                        url: crowdsec.url.trim_end_matches('/').to_string(),
+                        machine_id: crowdsec.machine_id.clone(),
+                        password: password.into_owned(),
+                        client,
+                        timeout: crowdsec.timeout.into_inner(),
+                    },
+                    (Err(err), _) | (_, Err(err)) => {
+                        bp.build_error(
+                            ObjectType::Security.singleton(),
+                            format!("Unable to configure CrowdSec firewall sync: {err}"),
+                        );
+                        return None;
+                    }
+                }
+            }
+        };
+
+        Some(FirewallSync {
+            backend,
+            interval: security.firewall_sync_interval.into_inner(),
+        })
+    }
+}
+
+impl FirewallBackend {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FirewallBackend::Nftables { .. } => "nftables",
+            FirewallBackend::Ipset { .. } => "ipset",
+            FirewallBackend::Webhook { .. } => "webhook",
+            FirewallBackend::Crowdsec { .. } => "crowdsec",
+        }
+    }
+}
+
+impl Server {
+    #[inline(always)]
+    pub fn notify_firewall_sync(&self) {
+        self.inner.ipc.firewall_tx.notify_one();
+    }
+}
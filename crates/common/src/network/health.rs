@@ -0,0 +1,149 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::Server;
+use serde::Serialize;
+use std::{sync::atomic::Ordering, time::Instant};
+use store::{
+    IterateParams, ValueKey,
+    write::{QueueClass, ValueClass},
+};
+use trc::AddContext;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HealthState {
+    Ready,
+    Degraded,
+    Draining,
+    Down,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoreHealth {
+    Ok,
+    Slow,
+    Unavailable,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthStatus {
+    pub state: HealthState,
+    pub weight: u64,
+    pub draining: bool,
+    pub store: StoreHealth,
+    pub store_latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_backlog: Option<bool>,
+}
+
+impl HealthStatus {
+    pub fn is_ready(&self) -> bool {
+        matches!(self.state, HealthState::Ready | HealthState::Degraded)
+    }
+
+    // Formats the status as a HAProxy agent-check reply
+    pub fn to_agent_reply(&self) -> String {
+        match self.state {
+            HealthState::Ready | HealthState::Degraded => {
+                format!("up ready {}%\n", self.weight)
+            }
+            HealthState::Draining => "drain\n".to_string(),
+            HealthState::Down => "down #store unavailable\n".to_string(),
+        }
+    }
+}
+
+impl Server {
+    pub async fn health_status(&self) -> HealthStatus {
+        let config = &self.core.network.http.health;
+        let draining = self.inner.data.draining.load(Ordering::Relaxed);
+
+        // Read this node's lease to verify that the data store is responsive
+        let time = Instant::now();
+        let store = if !self.core.storage.data.is_none() {
+            match tokio::time::timeout(
+                config.store_timeout,
+                self.store()
+                    .get_value::<String>(ValueKey::from(ValueClass::NodeId(
+                        self.core.network.node_id as u16,
+                    ))),
+            )
+            .await
+            {
+                Ok(Ok(_)) => StoreHealth::Ok,
+                Ok(Err(err)) => {
+                    trc::error!(err.details("Health check failed to read from the data store"));
+                    StoreHealth::Unavailable
+                }
+                Err(_) => StoreHealth::Slow,
+            }
+        } else {
+            StoreHealth::Unavailable
+        };
+        let store_latency_ms = time.elapsed().as_millis() as u64;
+
+        let queue_backlog = if let Some(threshold) = config.queue_threshold
+            && store == StoreHealth::Ok
+        {
+            match self.is_queue_over_threshold(threshold).await {
+                Ok(over_threshold) => Some(over_threshold),
+                Err(err) => {
+                    trc::error!(err.details("Health check failed to read the message queue"));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let state = if store == StoreHealth::Unavailable {
+            HealthState::Down
+        } else if draining {
+            HealthState::Draining
+        } else if store == StoreHealth::Slow || queue_backlog.unwrap_or_default() {
+            HealthState::Degraded
+        } else {
+            HealthState::Ready
+        };
+
+        HealthStatus {
+            state,
+            weight: match state {
+                HealthState::Ready => 100,
+                HealthState::Degraded => config.degraded_weight,
+                HealthState::Draining | HealthState::Down => 0,
+            },
+            draining,
+            store,
+            store_latency_ms,
+            queue_backlog,
+        }
+    }
+
+    // Counts queued messages up to the threshold rather than scanning the whole queue
+    async fn is_queue_over_threshold(&self, threshold: u64) -> trc::Result<bool> {
+        let mut total = 0;
+        self.queue_store()
+            .iterate(
+                IterateParams::new(
+                    ValueKey::from(ValueClass::Queue(QueueClass::Message(0))),
+                    ValueKey::from(ValueClass::Queue(QueueClass::Message(u64::MAX))),
+                )
+                .no_values(),
+                |_, _| {
+                    total += 1;
+
+                    Ok(total <= threshold)
+                },
+            )
+            .await
+            .caused_by(trc::location!())
+            .map(|_| total > threshold)
+    }
+}
@@ -6,22 +6,24 @@
 
 use super::{
     ServerInstance, SessionData, SessionManager, SessionStream, TcpAcceptor,
+    fingerprint::FingerprintStream,
     limiter::{ConcurrencyLimiter, LimiterResult},
 };
 use crate::{
     BuildServer, Inner, Server,
     config::server::{Listener, Listeners, ServerProtocol, TcpListener},
 };
-use proxy_header::io::ProxiedStream;
+use proxy_header::{ParseConfig, io::ProxiedStream};
 use rustls::crypto::aws_lc_rs::cipher_suite::TLS13_AES_128_GCM_SHA256;
 use std::{
     net::{IpAddr, SocketAddr},
-    sync::Arc,
+    sync::{Arc, atomic::Ordering},
+    time::Duration,
 };
 use store::registry::bootstrap::Bootstrap;
 use tokio::{net::TcpStream, sync::watch};
 use tokio_rustls::server::TlsStream;
-use trc::{EventType, HttpEvent, ImapEvent, ManageSieveEvent, Pop3Event, SmtpEvent};
+use trc::{EventType, HttpEvent, ImapEvent, ManageSieveEvent, NetworkEvent, Pop3Event, SmtpEvent};
 use utils::UnwrapFailure;
 
 impl Listener {
@@ -41,9 +43,9 @@ impl Listener {
             acceptor,
             shutdown_rx,
             span_id_gen: self.span_id_gen,
+            sessions: inner.data.active_sessions.clone(),
         });
         let is_tls = matches!(instance.acceptor, TcpAcceptor::Tls { implicit, .. } if implicit);
-        let is_https = is_tls && self.protocol == ServerProtocol::Http;
         let has_proxies = !instance.proxy_networks.is_empty();
 
         // Spawn listeners
@@ -110,6 +112,10 @@ impl Listener {
                         EventType::ManageSieve(ManageSieveEvent::ConnectionStart),
                         EventType::ManageSieve(ManageSieveEvent::ConnectionEnd),
                     ),
+                    ServerProtocol::AgentCheck => (
+                        EventType::Network(NetworkEvent::AgentCheckStart),
+                        EventType::Network(NetworkEvent::AgentCheckEnd),
+                    ),
                 };
 
                 loop {
@@ -118,7 +124,6 @@ impl Listener {
                             match stream {
                                 Ok((stream, remote_addr)) => {
                                     let server = inner.build_server();
-                                    let enable_acme = (is_https && server.has_acme_tls_providers()).then(|| server.clone());
 
                                     if has_proxies && instance.proxy_networks.iter().any(|network| network.matches(&remote_addr.ip())) {
                                         let instance = instance.clone();
@@ -128,15 +133,27 @@ impl Listener {
                                         opts.apply(&stream);
 
                                         tokio::spawn(async move {
-                                            match ProxiedStream::create_from_tokio(stream, Default::default()).await {
+                                            // Trusted peers that do not send a PROXY header are handled as regular connections
+                                            if !has_proxy_header(&stream, instance.protocol).await {
+                                                if let Some(session) = instance.build_session(stream, local_addr, remote_addr, Some(remote_addr.ip()), &server) {
+                                                    // Spawn session
+                                                    manager.spawn(session, is_tls, server, span_start, span_end);
+                                                }
+                                                return;
+                                            }
+
+                                            match ProxiedStream::create_from_tokio(stream, ParseConfig { include_tlvs: true, ..Default::default() }).await {
                                                 Ok(stream) =>{
+                                                    let proxy_ip = remote_addr.ip();
                                                     let remote_addr = stream.proxy_header()
                                                                             .proxied_address()
                                                                             .map(|addr| addr.source)
                                                                             .unwrap_or(remote_addr);
-                                                    if let Some(session) = instance.build_session(stream, local_addr, remote_addr, &server) {
+                                                    // Connections on which the proxy already terminated TLS are not handshaked again
+                                                    let is_tls = is_tls && !stream.is_tls();
+                                                    if let Some(session) = instance.build_session(stream, local_addr, remote_addr, Some(proxy_ip), &server) {
                                                         // Spawn session
-                                                        manager.spawn(session, is_tls, enable_acme, span_start, span_end);
+                                                        manager.spawn(session, is_tls, server, span_start, span_end);
                                                     }
                                                 }
                                                 Err(err) => {
@@ -151,12 +168,12 @@ impl Listener {
                                                 }
                                             }
                                         });
-                                    } else if let Some(session) = instance.build_session(stream, local_addr, remote_addr, &server) {
+                                    } else if let Some(session) = instance.build_session(stream, local_addr, remote_addr, None, &server) {
                                         // Set socket options
                                         opts.apply(&session.stream);
 
                                         // Spawn session
-                                        manager.spawn(session, is_tls, enable_acme, span_start, span_end);
+                                        manager.spawn(session, is_tls, server, span_start, span_end);
                                     }
                                 }
                                 Err(err) => {
@@ -197,6 +214,7 @@ trait BuildSession {
         stream: T,
         local_addr: SocketAddr,
         remote_addr: SocketAddr,
+        proxy_ip: Option<IpAddr>,
         server: &Server,
     ) -> Option<SessionData<T>>;
 }
@@ -207,6 +225,7 @@ impl BuildSession for Arc<ServerInstance> {
         stream: T,
         local_addr: SocketAddr,
         remote_addr: SocketAddr,
+        proxy_ip: Option<IpAddr>,
         server: &Server,
     ) -> Option<SessionData<T>> {
         // Convert mapped IPv6 addresses to IPv4
@@ -229,6 +248,21 @@ impl BuildSession for Arc<ServerInstance> {
                 RemotePort = remote_port,
             );
             None
+        } else if server.inner.data.draining.load(Ordering::Relaxed)
+            && !remote_ip.is_loopback()
+            && self.protocol != ServerProtocol::AgentCheck
+        {
+            // Only local connections are accepted while the node is draining,
+            // load balancers still need to be told that the node is draining
+            trc::event!(
+                Network(trc::NetworkEvent::Closed),
+                ListenerId = self.id.clone(),
+                LocalPort = local_addr.port(),
+                RemoteIp = remote_ip,
+                RemotePort = remote_port,
+                Reason = "Node is draining",
+            );
+            None
         } else if let LimiterResult::Allowed(in_flight) = self.limiter.is_allowed() {
             // Enforce concurrency
             SessionData {
@@ -239,6 +273,7 @@ impl BuildSession for Arc<ServerInstance> {
                 session_id: 0,
                 remote_ip,
                 remote_port,
+                proxy_ip,
                 protocol: self.protocol,
                 instance: self.clone(),
             }
@@ -258,6 +293,35 @@ impl BuildSession for Arc<ServerInstance> {
     }
 }
 
+// PROXY protocol v1 and v2 signatures
+const PROXY_V1_SIGNATURE: &[u8] = b"PROXY ";
+const PROXY_V2_SIGNATURE: &[u8] = b"\r\n\r\n\0\r\nQUIT\n";
+const PROXY_HEADER_TIMEOUT: Duration = Duration::from_secs(5);
+
+async fn has_proxy_header(stream: &TcpStream, protocol: ServerProtocol) -> bool {
+    match protocol {
+        // Server-first protocols: the client waits for a greeting before sending
+        // anything, so connections from trusted peers must start with a PROXY header
+        ServerProtocol::Smtp
+        | ServerProtocol::Lmtp
+        | ServerProtocol::Imap
+        | ServerProtocol::Pop3
+        | ServerProtocol::ManageSieve => true,
+        ServerProtocol::Http | ServerProtocol::AgentCheck => {
+            let mut buf = [0u8; PROXY_V2_SIGNATURE.len()];
+            match tokio::time::timeout(PROXY_HEADER_TIMEOUT, stream.peek(&mut buf)).await {
+                Ok(Ok(len)) if len > 0 => {
+                    let buf = &buf[..len];
+                    buf.starts_with(PROXY_V1_SIGNATURE)
+                        || PROXY_V1_SIGNATURE.starts_with(buf)
+                        || PROXY_V2_SIGNATURE.starts_with(buf)
+                }
+                _ => false,
+            }
+        }
+    }
+}
+
 pub struct SocketOpts {
     pub nodelay: bool,
     pub ttl: Option<u32>,
@@ -347,7 +411,10 @@ impl ServerInstance {
         &self,
         stream: T,
         session_id: u64,
-    ) -> Result<TlsStream<T>, ()> {
+        server: &Server,
+        remote_ip: IpAddr,
+    ) -> Result<TlsStream<FingerprintStream<T>>, ()> {
+        let stream = FingerprintStream::new(stream);
         match &self.acceptor {
             TcpAcceptor::Tls { acceptor, .. } => match acceptor.accept(stream).await {
                 Ok(stream) => {
@@ -372,6 +439,28 @@ impl ServerInstance {
                                 .unwrap_or(TLS13_AES_128_GCM_SHA256)
                         )
                     );
+
+                    if let Some(fingerprint) = stream.tls_fingerprint() {
+                        trc::event!(
+                            Tls(trc::TlsEvent::ClientFingerprint),
+                            SpanId = session_id,
+                            Id = fingerprint.ja4.clone(),
+                            Details = fingerprint.ja3.clone(),
+                        );
+
+                        if server.is_tls_fingerprint_blocked(fingerprint, remote_ip) {
+                            trc::event!(
+                                Security(trc::SecurityEvent::TlsFingerprintBlocked),
+                                ListenerId = self.id.clone(),
+                                SpanId = session_id,
+                                RemoteIp = remote_ip,
+                                Id = fingerprint.ja4.clone(),
+                                Details = fingerprint.ja3.clone(),
+                            );
+                            return Err(());
+                        }
+                    }
+
                     Ok(stream)
                 }
                 Err(err) => {
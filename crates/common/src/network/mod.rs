@@ -4,14 +4,22 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use self::limiter::{ConcurrencyLimiter, InFlight};
+use self::{
+    fingerprint::{FingerprintStream, TlsFingerprint},
+    limiter::{ConcurrencyLimiter, InFlight},
+    session::ActiveSessions,
+};
 use crate::{
     Server,
+    auth::RelayVerify,
     config::server::ServerProtocol,
     expr::{functions::ResolveVariable, *},
 };
 use compact_str::ToCompactString;
-use registry::{schema::enums::ExpressionVariable, types::ipmask::IpAddrOrMask};
+use registry::{
+    schema::enums::{ExpressionVariable, TlsClientCertField},
+    types::ipmask::IpAddrOrMask,
+};
 use rustls::ServerConfig;
 use std::fmt::Debug;
 use std::{borrow::Cow, net::IpAddr, sync::Arc, time::Instant};
@@ -26,20 +34,35 @@ use utils::snowflake::SnowflakeIdGenerator;
 pub mod acme;
 pub mod asn;
 pub mod autoconfig;
+pub mod backend;
+pub mod branding;
+pub mod captcha;
+pub mod disclaimer;
 pub mod dkim;
 pub mod dns;
+pub mod fingerprint;
+pub mod firewall;
+pub mod health;
 pub mod limiter;
 pub mod listen;
 pub mod mta;
+pub mod mta_sts;
+pub mod scheduler;
 pub mod security;
+pub mod session;
+pub mod spam_trap;
 pub mod stream;
+pub mod suppression;
 pub mod tls;
+pub mod unsubscribe;
+pub mod wkd;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub enum RcptResolution {
     Accept,
-    Expand(Arc<[Box<str>]>),
+    Expand(Arc<[Box<str>]>, Option<u32>),
     Rewrite(String),
+    Verify(Arc<RelayVerify>),
     #[default]
     UnknownRecipient,
     UnknownDomain,
@@ -53,6 +76,7 @@ pub struct ServerInstance {
     pub proxy_networks: Vec<IpAddrOrMask>,
     pub shutdown_rx: watch::Receiver<bool>,
     pub span_id_gen: Arc<SnowflakeIdGenerator>,
+    pub sessions: Arc<ActiveSessions>,
 }
 
 #[derive(Default)]
@@ -61,6 +85,7 @@ pub enum TcpAcceptor {
         config: Arc<ServerConfig>,
         acceptor: TlsAcceptor,
         implicit: bool,
+        client_cert_principal: Vec<TlsClientCertField>,
     },
     #[default]
     Plain,
@@ -71,7 +96,7 @@ pub enum TcpAcceptorResult<IO>
 where
     IO: AsyncRead + AsyncWrite + Unpin,
 {
-    Tls(Accept<IO>),
+    Tls(Accept<FingerprintStream<IO>>),
     Plain(IO),
     Close,
 }
@@ -82,6 +107,7 @@ pub struct SessionData<T: SessionStream> {
     pub local_port: u16,
     pub remote_ip: IpAddr,
     pub remote_port: u16,
+    pub proxy_ip: Option<IpAddr>,
     pub protocol: ServerProtocol,
     pub session_id: u64,
     pub in_flight: InFlight,
@@ -91,6 +117,8 @@ pub struct SessionData<T: SessionStream> {
 pub trait SessionStream: AsyncRead + AsyncWrite + Unpin + 'static + Sync + Send {
     fn is_tls(&self) -> bool;
     fn tls_version_and_cipher(&self) -> (Cow<'static, str>, Cow<'static, str>);
+    fn peer_certificate(&self) -> Option<&[u8]>;
+    fn tls_fingerprint(&self) -> Option<&TlsFingerprint>;
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -105,7 +133,7 @@ pub trait SessionManager: Sync + Send + 'static + Clone {
         &self,
         mut session: SessionData<T>,
         is_tls: bool,
-        acme_core: Option<Server>,
+        server: Server,
         span_start: EventType,
         span_end: EventType,
     ) {
@@ -117,6 +145,9 @@ pub trait SessionManager: Sync + Send + 'static + Clone {
             let session_id;
 
             if is_tls {
+                let acme_core = (session.protocol == ServerProtocol::Http
+                    && server.has_acme_tls_providers())
+                .then(|| server.clone());
                 match session
                     .instance
                     .acceptor
@@ -125,6 +156,21 @@ pub trait SessionManager: Sync + Send + 'static + Clone {
                 {
                     TcpAcceptorResult::Tls(accept) => match accept.await {
                         Ok(stream) => {
+                            if let Some(fingerprint) = stream.tls_fingerprint()
+                                && server.is_tls_fingerprint_blocked(fingerprint, session.remote_ip)
+                            {
+                                trc::event!(
+                                    Security(trc::SecurityEvent::TlsFingerprintBlocked),
+                                    ListenerId = session.instance.id.clone(),
+                                    LocalPort = local_port,
+                                    RemoteIp = session.remote_ip,
+                                    RemotePort = session.remote_port,
+                                    Id = fingerprint.ja4.clone(),
+                                    Details = fingerprint.ja3.clone(),
+                                );
+                                return;
+                            }
+
                             // Generate sessionId
                             session.session_id = session.instance.span_id_gen.generate();
                             session_id = session.session_id;
@@ -142,19 +188,31 @@ pub trait SessionManager: Sync + Send + 'static + Clone {
                             )
                             .send_with_metrics();
 
-                            manager
-                                .handle(SessionData {
+                            if let Some(fingerprint) = stream.tls_fingerprint() {
+                                trc::event!(
+                                    Tls(trc::TlsEvent::ClientFingerprint),
+                                    SpanId = session_id,
+                                    Id = fingerprint.ja4.clone(),
+                                    Details = fingerprint.ja3.clone(),
+                                );
+                            }
+
+                            handle_active_session(
+                                manager,
+                                SessionData {
                                     stream,
                                     local_ip: session.local_ip,
                                     local_port: session.local_port,
                                     remote_ip: session.remote_ip,
                                     remote_port: session.remote_port,
+                                    proxy_ip: session.proxy_ip,
                                     protocol: session.protocol,
                                     session_id: session.session_id,
                                     in_flight: session.in_flight,
                                     instance: session.instance,
-                                })
-                                .await;
+                                },
+                            )
+                            .await;
                         }
                         Err(err) => {
                             trc::event!(
@@ -188,7 +246,7 @@ pub trait SessionManager: Sync + Send + 'static + Clone {
                         .send_with_metrics();
 
                         session.stream = stream;
-                        manager.handle(session).await;
+                        handle_active_session(manager, session).await;
                     }
                     TcpAcceptorResult::Close => return,
                 }
@@ -210,7 +268,7 @@ pub trait SessionManager: Sync + Send + 'static + Clone {
                 )
                 .send_with_metrics();
 
-                manager.handle(session).await;
+                handle_active_session(manager, session).await;
             }
 
             // End span
@@ -233,6 +291,29 @@ pub trait SessionManager: Sync + Send + 'static + Clone {
     fn shutdown(&self) -> impl std::future::Future<Output = ()> + Send;
 }
 
+// Keeps track of the session while it is running, dropping it if it gets terminated.
+async fn handle_active_session<M: SessionManager, T: SessionStream>(
+    manager: M,
+    session: SessionData<T>,
+) {
+    let sessions = session.instance.sessions.clone();
+    let session_id = session.session_id;
+    let active_session = sessions.register(
+        session_id,
+        &session.instance.id,
+        session.protocol,
+        session.remote_ip,
+        session.remote_port,
+    );
+
+    tokio::select! {
+        _ = manager.handle(session) => {}
+        _ = active_session.terminated() => {}
+    }
+
+    sessions.unregister(session_id);
+}
+
 impl<T: SessionStream> ResolveVariable for SessionData<T> {
     fn resolve_variable(&self, variable: ExpressionVariable) -> crate::expr::Variable<'_> {
         match variable {
@@ -243,6 +324,18 @@ impl<T: SessionStream> ResolveVariable for SessionData<T> {
             ExpressionVariable::Listener => self.instance.id.as_str().into(),
             ExpressionVariable::Protocol => self.protocol.as_str().into(),
             ExpressionVariable::IsTls => self.stream.is_tls().into(),
+            ExpressionVariable::TlsJa3 => self
+                .stream
+                .tls_fingerprint()
+                .map(|fp| fp.ja3.as_str())
+                .unwrap_or_default()
+                .into(),
+            ExpressionVariable::TlsJa4 => self
+                .stream
+                .tls_fingerprint()
+                .map(|fp| fp.ja4.as_str())
+                .unwrap_or_default()
+                .into(),
             _ => crate::expr::Variable::default(),
         }
     }
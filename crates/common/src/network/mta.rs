@@ -150,7 +150,10 @@ impl Server {
                 }
                 EmailCache::MailingList(id) => {
                     if let Some(list) = self.try_list(id).await? {
-                        return Ok(RcptResolution::Expand(list.recipients.clone()));
+                        return Ok(RcptResolution::Expand(
+                            list.recipients.clone(),
+                            list.allow_unsubscribe.then_some(id),
+                        ));
                     } else {
                         self.inner
                             .cache
@@ -169,7 +172,11 @@ impl Server {
 
         // Verify whether domain relaying is enabled
         if domain.flags & DOMAIN_FLAG_RELAY != 0 {
-            Ok(RcptResolution::Accept)
+            if let Some(relay_verify) = &domain.relay_verify {
+                Ok(RcptResolution::Verify(relay_verify.clone()))
+            } else {
+                Ok(RcptResolution::Accept)
+            }
         } else {
             Ok(RcptResolution::UnknownRecipient)
         }
@@ -193,28 +200,42 @@ impl Server {
         }
     }
 
-    pub fn get_trusted_sieve_script(&self, name: &str, session_id: u64) -> Option<&Arc<Sieve>> {
-        self.core.sieve.trusted_scripts.get(name).or_else(|| {
+    pub fn get_trusted_sieve_script(&self, name: &str, session_id: u64) -> Option<Arc<Sieve>> {
+        let script = self
+            .inner
+            .data
+            .sieve_scripts
+            .load()
+            .trusted
+            .get(name)
+            .cloned();
+        if script.is_none() {
             trc::event!(
                 Sieve(trc::SieveEvent::ScriptNotFound),
                 Id = name.to_string(),
                 SpanId = session_id,
             );
-
-            None
-        })
+        }
+        script
     }
 
-    pub fn get_untrusted_sieve_script(&self, name: &str, session_id: u64) -> Option<&Arc<Sieve>> {
-        self.core.sieve.untrusted_scripts.get(name).or_else(|| {
+    pub fn get_untrusted_sieve_script(&self, name: &str, session_id: u64) -> Option<Arc<Sieve>> {
+        let script = self
+            .inner
+            .data
+            .sieve_scripts
+            .load()
+            .untrusted
+            .get(name)
+            .cloned();
+        if script.is_none() {
             trc::event!(
                 Sieve(trc::SieveEvent::ScriptNotFound),
                 Id = name.to_string(),
                 SpanId = session_id,
             );
-
-            None
-        })
+        }
+        script
     }
 
     pub fn get_route_or_default(&self, name: &str, session_id: u64) -> &RoutingStrategy {
@@ -400,7 +421,7 @@ impl Server {
 
     pub async fn total_queued_messages(&self) -> trc::Result<u64> {
         let mut total = 0;
-        self.store()
+        self.queue_store()
             .iterate(
                 IterateParams::new(
                     ValueKey::from(ValueClass::Queue(QueueClass::Message(0))),
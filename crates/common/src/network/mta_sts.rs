@@ -0,0 +1,78 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::Server;
+use std::{collections::VecDeque, net::IpAddr};
+use store::write::now;
+use trc::AddContext;
+
+const MAX_POLICY_FETCHES: usize = 1024;
+
+#[derive(Default)]
+pub struct MtaStsFetches {
+    fetches: VecDeque<MtaStsFetch>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MtaStsFetch {
+    pub domain: Box<str>,
+    pub domain_id: u32,
+    pub tenant_id: Option<u32>,
+    pub remote_ip: IpAddr,
+    pub policy_id: Box<str>,
+    pub fetched_at: u64,
+}
+
+impl MtaStsFetches {
+    pub fn record(&mut self, fetch: MtaStsFetch) {
+        if self.fetches.len() == MAX_POLICY_FETCHES {
+            self.fetches.pop_front();
+        }
+        self.fetches.push_back(fetch);
+    }
+
+    pub fn recent(&self) -> impl Iterator<Item = &MtaStsFetch> {
+        self.fetches.iter().rev()
+    }
+}
+
+impl Server {
+    // Policies are only served on the "mta-sts" host of a hosted domain,
+    // each fetch is recorded along with the policy id that was served.
+    pub async fn mta_sts_policy(
+        &self,
+        host: &str,
+        remote_ip: IpAddr,
+    ) -> trc::Result<Option<String>> {
+        let Some(policy) = &self.core.smtp.session.mta_sts_policy else {
+            return Ok(None);
+        };
+        let Some(domain_name) = host
+            .strip_prefix("mta-sts.")
+            .map(|domain| domain.to_lowercase())
+        else {
+            return Ok(None);
+        };
+        let Some(domain) = self
+            .domain(&domain_name)
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(None);
+        };
+
+        self.inner.data.mta_sts_fetches.lock().record(MtaStsFetch {
+            domain: domain_name.into_boxed_str(),
+            domain_id: domain.id,
+            tenant_id: domain.id_tenant,
+            remote_ip,
+            policy_id: policy.id.as_str().into(),
+            fetched_at: now(),
+        });
+
+        Ok(Some(policy.to_string()))
+    }
+}
@@ -0,0 +1,287 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use ahash::AHashMap;
+use parking_lot::Mutex;
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::oneshot;
+
+#[derive(Debug, Clone, Default)]
+#[repr(transparent)]
+pub struct RequestScheduler(Arc<Mutex<SchedulerState>>);
+
+#[derive(Debug, Default)]
+struct SchedulerState {
+    in_flight: usize,
+    max_in_flight: usize,
+    accounts: AHashMap<u32, VecDeque<oneshot::Sender<SchedulerPermit>>>,
+    ready: VecDeque<u32>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SchedulerLimits {
+    pub max_in_flight: usize,
+    pub max_queued: usize,
+    pub max_wait: Duration,
+}
+
+// Slots are handed over to waiters as owned permits, so a waiter that is dropped
+// before (or right after) being woken up releases its slot along with the permit
+#[derive(Debug)]
+pub struct SchedulerPermit(Arc<Mutex<SchedulerState>>);
+
+pub enum SchedulerResult {
+    Allowed(SchedulerPermit),
+    Rejected { retry_after: Duration },
+}
+
+impl RequestScheduler {
+    pub async fn acquire(&self, account_id: u32, limits: SchedulerLimits) -> SchedulerResult {
+        // Wake up waiting accounts if the limit was raised
+        let waiters = {
+            let mut guard = self.0.lock();
+            let state = &mut *guard;
+            state.max_in_flight = limits.max_in_flight;
+
+            let mut waiters = Vec::new();
+            while state.in_flight < state.max_in_flight
+                && let Some(tx) = state.next_waiter()
+            {
+                state.in_flight += 1;
+                waiters.push(tx);
+            }
+            waiters
+        };
+        for tx in waiters {
+            let _ = tx.send(SchedulerPermit(self.0.clone()));
+        }
+
+        let mut rx = {
+            let mut guard = self.0.lock();
+            let state = &mut *guard;
+
+            // Run immediately when there is capacity and no other account is waiting
+            if state.in_flight < state.max_in_flight && state.ready.is_empty() {
+                state.in_flight += 1;
+                return SchedulerResult::Allowed(SchedulerPermit(self.0.clone()));
+            }
+
+            let is_waiting = state.accounts.contains_key(&account_id);
+            let queue = state.accounts.entry(account_id).or_default();
+            queue.retain(|tx| !tx.is_closed());
+            if queue.len() >= limits.max_queued {
+                if !is_waiting {
+                    state.accounts.remove(&account_id);
+                }
+                return SchedulerResult::Rejected {
+                    retry_after: limits.max_wait,
+                };
+            }
+            let (tx, rx) = oneshot::channel();
+            queue.push_back(tx);
+            if !is_waiting {
+                state.ready.push_back(account_id);
+            }
+            rx
+        };
+
+        let wait_start = Instant::now();
+        if let Ok(Ok(permit)) = tokio::time::timeout(limits.max_wait, &mut rx).await {
+            return SchedulerResult::Allowed(permit);
+        }
+
+        // A slot may have been handed over right as the wait expired
+        rx.close();
+        if let Ok(permit) = rx.try_recv() {
+            SchedulerResult::Allowed(permit)
+        } else {
+            SchedulerResult::Rejected {
+                retry_after: wait_start.elapsed().max(Duration::from_secs(1)),
+            }
+        }
+    }
+}
+
+impl SchedulerState {
+    // Slots are handed over to waiting accounts in round-robin order,
+    // so an account with a long queue only gets one slot per turn
+    fn next_waiter(&mut self) -> Option<oneshot::Sender<SchedulerPermit>> {
+        while let Some(account_id) = self.ready.pop_front() {
+            let Some(queue) = self.accounts.get_mut(&account_id) else {
+                continue;
+            };
+            let mut waiter = None;
+            while let Some(tx) = queue.pop_front() {
+                if !tx.is_closed() {
+                    waiter = Some(tx);
+                    break;
+                }
+            }
+            if queue.is_empty() {
+                self.accounts.remove(&account_id);
+            } else {
+                self.ready.push_back(account_id);
+            }
+            if waiter.is_some() {
+                return waiter;
+            }
+        }
+
+        None
+    }
+}
+
+impl Drop for SchedulerPermit {
+    fn drop(&mut self) {
+        let waiter = {
+            let mut state = self.0.lock();
+            if state.in_flight <= state.max_in_flight
+                && let Some(tx) = state.next_waiter()
+            {
+                tx
+            } else {
+                state.in_flight = state.in_flight.saturating_sub(1);
+                return;
+            }
+        };
+
+        // The slot is transferred as-is; if the waiter went away in the meantime,
+        // the returned permit is dropped and the slot moves on to the next waiter
+        let _ = waiter.send(SchedulerPermit(self.0.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(max_in_flight: usize, max_queued: usize, max_wait: Duration) -> SchedulerLimits {
+        SchedulerLimits {
+            max_in_flight,
+            max_queued,
+            max_wait,
+        }
+    }
+
+    fn in_flight(scheduler: &RequestScheduler) -> usize {
+        scheduler.0.lock().in_flight
+    }
+
+    async fn acquire(scheduler: &RequestScheduler, account_id: u32, max_wait: Duration) -> bool {
+        matches!(
+            scheduler.acquire(account_id, limits(1, 10, max_wait)).await,
+            SchedulerResult::Allowed(_)
+        )
+    }
+
+    #[tokio::test]
+    async fn dropped_waiter_releases_slot() {
+        let scheduler = RequestScheduler::default();
+        let limits = limits(1, 10, Duration::from_secs(60));
+        let SchedulerResult::Allowed(permit) = scheduler.acquire(1, limits).await else {
+            panic!("expected permit");
+        };
+
+        // Waiter dropped while still queued
+        let mut waiter = Box::pin(scheduler.acquire(2, limits));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), &mut waiter)
+                .await
+                .is_err()
+        );
+        drop(waiter);
+
+        // Waiter dropped after the slot was handed over but before it was polled
+        let mut waiter = Box::pin(scheduler.acquire(3, limits));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), &mut waiter)
+                .await
+                .is_err()
+        );
+        drop(permit);
+        assert_eq!(in_flight(&scheduler), 1);
+        drop(waiter);
+        assert_eq!(in_flight(&scheduler), 0);
+
+        assert!(acquire(&scheduler, 4, Duration::ZERO).await);
+        assert_eq!(in_flight(&scheduler), 0);
+    }
+
+    #[tokio::test]
+    async fn round_robin_between_accounts() {
+        let scheduler = RequestScheduler::default();
+        let limits = limits(1, 10, Duration::from_secs(60));
+        let SchedulerResult::Allowed(permit) = scheduler.acquire(0, limits).await else {
+            panic!("expected permit");
+        };
+
+        // Account 1 queues three requests before account 2 queues one
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        for account_id in [1, 1, 1, 2] {
+            let scheduler = scheduler.clone();
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                if let SchedulerResult::Allowed(_permit) =
+                    scheduler.acquire(account_id, limits).await
+                {
+                    tx.send(account_id).unwrap();
+                }
+            });
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        drop(tx);
+        drop(permit);
+
+        let mut order = Vec::new();
+        while let Some(account_id) = rx.recv().await {
+            order.push(account_id);
+        }
+        assert_eq!(order, [1, 2, 1, 1]);
+        assert_eq!(in_flight(&scheduler), 0);
+    }
+
+    #[tokio::test]
+    async fn rejects_when_queue_is_full() {
+        let scheduler = RequestScheduler::default();
+        let limits = limits(1, 1, Duration::from_secs(60));
+        let SchedulerResult::Allowed(permit) = scheduler.acquire(1, limits).await else {
+            panic!("expected permit");
+        };
+
+        let mut waiter = Box::pin(scheduler.acquire(1, limits));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), &mut waiter)
+                .await
+                .is_err()
+        );
+
+        // The account's queue is full
+        assert!(matches!(
+            scheduler.acquire(1, limits).await,
+            SchedulerResult::Rejected { retry_after } if retry_after == limits.max_wait
+        ));
+
+        // Requests waiting longer than allowed are rejected
+        assert!(!acquire(&scheduler, 2, Duration::from_millis(10)).await);
+
+        // Other accounts can still queue
+        let mut other = Box::pin(scheduler.acquire(2, limits));
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), &mut other)
+                .await
+                .is_err()
+        );
+
+        drop(permit);
+        assert!(matches!(waiter.await, SchedulerResult::Allowed(_)));
+        assert!(matches!(other.await, SchedulerResult::Allowed(_)));
+        assert_eq!(in_flight(&scheduler), 0);
+    }
+}
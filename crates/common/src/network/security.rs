@@ -5,21 +5,23 @@
  */
 
 use crate::{
-    KV_RATE_LIMIT_AUTH, KV_RATE_LIMIT_LOITER, KV_RATE_LIMIT_RCPT, KV_RATE_LIMIT_SCAN, Server,
+    KV_BAN_HISTORY, KV_RATE_LIMIT_AUTH, KV_RATE_LIMIT_LOITER, KV_RATE_LIMIT_RCPT,
+    KV_RATE_LIMIT_SCAN, Server,
     ipc::{BroadcastEvent, RegistryChange},
-    network::ip_to_bytes,
+    network::{firewall::FirewallSync, ip_to_bytes},
 };
 use ahash::AHashSet;
 use registry::{
     schema::{
-        enums::{BlockReason, PasswordHashAlgorithm, PasswordStrength},
+        enums::{BlockReason, LoginAnomalySensitivity, PasswordHashAlgorithm, PasswordStrength},
         prelude::{Object, ObjectType},
         structs::{self, AllowedIp, BlockedIp, Rate, SystemSettings},
     },
     types::{datetime::UTCDateTime, ipmask::IpAddrOrMask},
 };
-use std::{fmt::Debug, hash::Hash, net::IpAddr};
+use std::{fmt::Debug, hash::Hash, net::IpAddr, sync::Arc};
 use store::{
+    dispatch::lookup::KeyValue,
     registry::{
         bootstrap::Bootstrap,
         write::{RegistryWrite, RegistryWriteResult},
@@ -33,14 +35,20 @@ use zxcvbn::Score;
 
 #[derive(Debug, Clone)]
 pub struct Security {
-    pub allowed_ip_addresses: AHashSet<IpWithTtl<IpAddr>>,
-    pub allowed_ip_networks: Vec<IpWithTtl<IpAddrOrMask>>,
-    pub has_allowed_networks: bool,
-    pub blocked_ip_expiration: Option<u64>,
+    pub auth_ban_period: Option<u64>,
+    pub abuse_ban_period: Option<u64>,
+    pub loiter_ban_period: Option<u64>,
+    pub scan_ban_period: Option<u64>,
+    pub repeat_ban_max_period: Option<u64>,
+    pub repeat_ban_decay: u64,
 
     pub http_banned_paths: Vec<MatchType>,
     pub scanner_fail_rate: Option<Rate>,
 
+    pub blocked_tls_fingerprints: AHashSet<String>,
+
+    pub firewall_sync: Option<Arc<FirewallSync>>,
+
     pub auth_fail_rate: Option<Rate>,
     pub rcpt_fail_rate: Option<Rate>,
     pub loiter_fail_rate: Option<Rate>,
@@ -55,6 +63,16 @@ pub struct Security {
     pub password_min_length: u32,
     pub password_min_strength: Score,
     pub password_default_expiration: Option<u64>,
+
+    pub login_anomaly_sensitivity: LoginAnomalySensitivity,
+    pub login_travel_window: u64,
+}
+
+#[derive(Default)]
+pub struct AllowedIps {
+    pub allowed_ip_addresses: AHashSet<IpWithTtl<IpAddr>>,
+    pub allowed_ip_networks: Vec<IpWithTtl<IpAddrOrMask>>,
+    pub has_allowed_networks: bool,
 }
 
 #[derive(Default)]
@@ -72,99 +90,16 @@ pub struct IpWithTtl<T: PartialEq + Eq + Hash> {
 
 impl Security {
     pub async fn parse(bp: &mut Bootstrap) -> Self {
-        let mut allowed_ip_addresses = AHashSet::new();
-        let mut allowed_ip_networks = Vec::new();
-        let mut expired_allows = Vec::new();
-        let now = now();
-
-        for ip in bp.list_infallible::<AllowedIp>().await {
-            let id = ip.id;
-            let revision = ip.revision;
-            let ip = ip.object;
-            let expires_at = ip
-                .expires_at
-                .as_ref()
-                .map(|dt| dt.timestamp() as u64)
-                .unwrap_or(u64::MAX);
-
-            if expires_at > now {
-                if let Some(ip) = ip.address.try_to_ip() {
-                    allowed_ip_addresses.insert(IpWithTtl::new(ip, expires_at));
-                } else {
-                    let ip_with_ttl = IpWithTtl::new(ip.address, expires_at);
-
-                    if !allowed_ip_networks.contains(&ip_with_ttl) {
-                        allowed_ip_networks.push(ip_with_ttl);
-                    }
-                }
-            } else {
-                expired_allows.push((
-                    id,
-                    ip.address.clone(),
-                    Object {
-                        inner: ip.into(),
-                        revision,
-                    },
-                ));
-            }
-        }
-
-        // Add proxy protocol IPs as allowed
-        let system = bp.setting_infallible::<SystemSettings>().await;
-        for ip in system.proxy_trusted_networks {
-            if let Some(ip) = ip.try_to_ip() {
-                allowed_ip_addresses.insert(IpWithTtl::new(ip, u64::MAX));
-            } else {
-                let ip_with_ttl = IpWithTtl::new(ip, u64::MAX);
-                if !allowed_ip_networks.contains(&ip_with_ttl) {
-                    allowed_ip_networks.push(ip_with_ttl);
-                }
-            }
-        }
-
-        if !expired_allows.is_empty() {
-            for (id, _, object) in &expired_allows {
-                if let Err(err) = bp
-                    .registry
-                    .write(RegistryWrite::delete_object(*id, object))
-                    .await
-                {
-                    trc::error!(
-                        err.details("Failed to delete expired allowed IP from registry.")
-                            .caused_by(trc::location!())
-                    );
-                }
-            }
-
-            trc::event!(
-                Security(trc::SecurityEvent::IpAllowExpired),
-                Details = expired_allows
-                    .into_iter()
-                    .map(|(_, ip, _)| trc::Value::from(ip.into_inner().0))
-                    .collect::<Vec<_>>()
-            );
-        }
-
-        #[cfg(not(feature = "test_mode"))]
-        {
-            // Add loopback addresses
-            allowed_ip_addresses.insert(IpWithTtl::new(
-                IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
-                u64::MAX,
-            ));
-            allowed_ip_addresses.insert(IpWithTtl::new(
-                IpAddr::V6(std::net::Ipv6Addr::LOCALHOST),
-                u64::MAX,
-            ));
-        }
-
         let security = bp.setting_infallible::<structs::Security>().await;
         let auth = bp.setting_infallible::<structs::Authentication>().await;
+        let firewall_sync = FirewallSync::parse(bp, &security).await.map(Arc::new);
         Security {
-            has_allowed_networks: !allowed_ip_networks.is_empty(),
-            allowed_ip_addresses,
-            allowed_ip_networks,
-            blocked_ip_expiration: security.auth_ban_period.map(|v| v.as_secs()),
+            auth_ban_period: security.auth_ban_period.map(|v| v.as_secs()),
+            abuse_ban_period: security.abuse_ban_period.map(|v| v.as_secs()),
+            loiter_ban_period: security.loiter_ban_period.map(|v| v.as_secs()),
+            scan_ban_period: security.scan_ban_period.map(|v| v.as_secs()),
+            repeat_ban_max_period: security.repeat_ban_max_period.map(|v| v.as_secs()),
+            repeat_ban_decay: security.repeat_ban_decay.as_secs(),
             auth_fail_rate: security.auth_ban_rate,
             rcpt_fail_rate: security.abuse_ban_rate,
             loiter_fail_rate: security.loiter_ban_rate,
@@ -174,6 +109,8 @@ impl Security {
                 .map(|pattern| MatchType::Matches(GlobPattern::compile(pattern, true)))
                 .collect(),
             scanner_fail_rate: security.scan_ban_rate,
+            blocked_tls_fingerprints: security.blocked_tls_fingerprints.into_iter().collect(),
+            firewall_sync,
             default_role_ids_user: auth.default_user_role_ids.into_inner(),
             default_role_ids_group: auth.default_group_role_ids.into_inner(),
             default_role_ids_tenant: auth.default_tenant_role_ids.into_inner(),
@@ -189,6 +126,8 @@ impl Security {
                 PasswordStrength::Four => Score::Four,
             },
             password_default_expiration: auth.password_default_expiry.map(|v| v.as_secs()),
+            login_anomaly_sensitivity: auth.login_anomaly_sensitivity,
+            login_travel_window: auth.login_travel_window.as_secs(),
         }
     }
 }
@@ -301,11 +240,9 @@ impl Server {
         // Add IP to blocked list
         let now = now();
         let expires_at = self
-            .core
-            .network
-            .security
-            .blocked_ip_expiration
-            .map(|v| now + v);
+            .ban_period(ip, reason)
+            .await?
+            .map(|period| now + period);
         self.inner
             .data
             .blocked_ips
@@ -337,9 +274,48 @@ impl Server {
         )))
         .await;
 
+        // Publish the ban to the external firewall
+        self.notify_firewall_sync();
+
         Ok(())
     }
 
+    // Repeat offenders are banned for twice as long as their previous ban, the
+    // ban history is forgotten once no new bans are issued for the decay period.
+    async fn ban_period(&self, ip: IpAddr, reason: BlockReason) -> trc::Result<Option<u64>> {
+        let security = &self.core.network.security;
+        let period = match reason {
+            BlockReason::AuthFailure => security.auth_ban_period,
+            BlockReason::RcptToFailure => security.abuse_ban_period.or(security.auth_ban_period),
+            BlockReason::Loitering => security.loiter_ban_period.or(security.auth_ban_period),
+            BlockReason::PortScanning => security.scan_ban_period.or(security.auth_ban_period),
+            BlockReason::SpamTrap => security.abuse_ban_period.or(security.auth_ban_period),
+            BlockReason::Manual | BlockReason::Other => security.auth_ban_period,
+        };
+
+        match (period, security.repeat_ban_max_period) {
+            (Some(period), Some(max_period)) if security.repeat_ban_decay > 0 => {
+                let offenses = self
+                    .in_memory_store()
+                    .counter_incr(
+                        KeyValue::with_prefix(KV_BAN_HISTORY, ip_to_bytes(&ip), 1)
+                            .expires(security.repeat_ban_decay),
+                        true,
+                    )
+                    .await
+                    .caused_by(trc::location!())?;
+
+                let multiplier = 1u64 << (offenses.clamp(1, 32) - 1);
+                Ok(Some(
+                    period
+                        .saturating_mul(multiplier)
+                        .min(max_period.max(period)),
+                ))
+            }
+            _ => Ok(period),
+        }
+    }
+
     pub fn has_auth_fail2ban(&self) -> bool {
         self.core.network.security.auth_fail_rate.is_some()
     }
@@ -359,17 +335,13 @@ impl Server {
     }
 
     pub fn is_ip_allowed(&self, ip: IpAddr) -> bool {
-        self.core
-            .network
-            .security
+        let allowed_ips = self.inner.data.allowed_ips.read();
+        allowed_ips
             .allowed_ip_addresses
             .get(&IpWithTtl::new(ip, 0))
             .is_some_and(|v| !v.is_expired())
-            || (self.core.network.security.has_allowed_networks
-                && self
-                    .core
-                    .network
-                    .security
+            || (allowed_ips.has_allowed_networks
+                && allowed_ips
                     .allowed_ip_networks
                     .iter()
                     .any(|network| network.ip.matches(&ip) && !network.is_expired()))
@@ -401,6 +373,102 @@ impl Server {
     }
 }
 
+impl AllowedIps {
+    pub async fn parse(bp: &mut Bootstrap) -> Self {
+        let mut allowed_ip_addresses = AHashSet::new();
+        let mut allowed_ip_networks = Vec::new();
+        let mut expired_allows = Vec::new();
+        let now = now();
+
+        for ip in bp.list_infallible::<AllowedIp>().await {
+            let id = ip.id;
+            let revision = ip.revision;
+            let ip = ip.object;
+            let expires_at = ip
+                .expires_at
+                .as_ref()
+                .map(|dt| dt.timestamp() as u64)
+                .unwrap_or(u64::MAX);
+
+            if expires_at > now {
+                if let Some(ip) = ip.address.try_to_ip() {
+                    allowed_ip_addresses.insert(IpWithTtl::new(ip, expires_at));
+                } else {
+                    let ip_with_ttl = IpWithTtl::new(ip.address, expires_at);
+
+                    if !allowed_ip_networks.contains(&ip_with_ttl) {
+                        allowed_ip_networks.push(ip_with_ttl);
+                    }
+                }
+            } else {
+                expired_allows.push((
+                    id,
+                    ip.address.clone(),
+                    Object {
+                        inner: ip.into(),
+                        revision,
+                    },
+                ));
+            }
+        }
+
+        // Add proxy protocol IPs as allowed
+        let system = bp.setting_infallible::<SystemSettings>().await;
+        for ip in system.proxy_trusted_networks {
+            if let Some(ip) = ip.try_to_ip() {
+                allowed_ip_addresses.insert(IpWithTtl::new(ip, u64::MAX));
+            } else {
+                let ip_with_ttl = IpWithTtl::new(ip, u64::MAX);
+                if !allowed_ip_networks.contains(&ip_with_ttl) {
+                    allowed_ip_networks.push(ip_with_ttl);
+                }
+            }
+        }
+
+        if !expired_allows.is_empty() {
+            for (id, _, object) in &expired_allows {
+                if let Err(err) = bp
+                    .registry
+                    .write(RegistryWrite::delete_object(*id, object))
+                    .await
+                {
+                    trc::error!(
+                        err.details("Failed to delete expired allowed IP from registry.")
+                            .caused_by(trc::location!())
+                    );
+                }
+            }
+
+            trc::event!(
+                Security(trc::SecurityEvent::IpAllowExpired),
+                Details = expired_allows
+                    .into_iter()
+                    .map(|(_, ip, _)| trc::Value::from(ip.into_inner().0))
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[cfg(not(feature = "test_mode"))]
+        {
+            // Add loopback addresses
+            allowed_ip_addresses.insert(IpWithTtl::new(
+                IpAddr::V4(std::net::Ipv4Addr::LOCALHOST),
+                u64::MAX,
+            ));
+            allowed_ip_addresses.insert(IpWithTtl::new(
+                IpAddr::V6(std::net::Ipv6Addr::LOCALHOST),
+                u64::MAX,
+            ));
+        }
+
+        AllowedIps {
+            has_allowed_networks: !allowed_ip_networks.is_empty(),
+            allowed_ip_addresses,
+            allowed_ip_networks,
+        }
+    }
+}
+
 impl BlockedIps {
     pub async fn parse(bp: &mut Bootstrap) -> Self {
         let mut ips = Self::default();
@@ -0,0 +1,109 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{auth::AccessToken, config::server::ServerProtocol};
+use ahash::AHashMap;
+use parking_lot::{Mutex, RwLock};
+use std::{net::IpAddr, sync::Arc};
+use store::write::now;
+use tokio::sync::Notify;
+
+#[derive(Default)]
+pub struct ActiveSessions {
+    sessions: RwLock<AHashMap<u64, Arc<ActiveSession>>>,
+}
+
+pub struct ActiveSession {
+    pub session_id: u64,
+    pub listener_id: String,
+    pub protocol: ServerProtocol,
+    pub remote_ip: IpAddr,
+    pub remote_port: u16,
+    pub started: u64,
+    state: Mutex<ActiveSessionState>,
+    terminate: Notify,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ActiveSessionState {
+    pub account_id: Option<u32>,
+    pub tenant_id: Option<u32>,
+    pub command: String,
+}
+
+impl ActiveSessions {
+    pub fn register(
+        &self,
+        session_id: u64,
+        listener_id: &str,
+        protocol: ServerProtocol,
+        remote_ip: IpAddr,
+        remote_port: u16,
+    ) -> Arc<ActiveSession> {
+        let session = Arc::new(ActiveSession {
+            session_id,
+            listener_id: listener_id.to_string(),
+            protocol,
+            remote_ip,
+            remote_port,
+            started: now(),
+            state: Mutex::new(ActiveSessionState::default()),
+            terminate: Notify::new(),
+        });
+        self.sessions.write().insert(session_id, session.clone());
+        session
+    }
+
+    pub fn unregister(&self, session_id: u64) {
+        self.sessions.write().remove(&session_id);
+    }
+
+    pub fn set_account(&self, session_id: u64, access_token: Option<&AccessToken>) {
+        if let Some(session) = self.sessions.read().get(&session_id) {
+            let mut state = session.state.lock();
+            state.account_id = access_token.map(|token| token.account_id());
+            state.tenant_id = access_token.and_then(|token| token.tenant_id());
+        }
+    }
+
+    pub fn set_command(&self, session_id: u64, command: &str) {
+        if let Some(session) = self.sessions.read().get(&session_id) {
+            let mut state = session.state.lock();
+            state.command.clear();
+            state.command.push_str(command);
+        }
+    }
+
+    pub fn list(&self) -> Vec<Arc<ActiveSession>> {
+        self.sessions.read().values().cloned().collect()
+    }
+
+    // Terminates the sessions matching the filter, returns the number of sessions terminated.
+    pub fn terminate(&self, filter: impl Fn(&ActiveSession) -> bool) -> usize {
+        let mut count = 0;
+        for session in self.sessions.read().values() {
+            if filter(session) {
+                session.terminate.notify_one();
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+impl ActiveSession {
+    pub fn state(&self) -> ActiveSessionState {
+        self.state.lock().clone()
+    }
+
+    pub fn account_id(&self) -> Option<u32> {
+        self.state.lock().account_id
+    }
+
+    pub async fn terminated(&self) {
+        self.terminate.notified().await
+    }
+}
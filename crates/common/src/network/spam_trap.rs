@@ -0,0 +1,127 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{KV_SPAM_TRAP, Server, network::ip_to_bytes};
+use registry::{schema::enums::BlockReason, types::EnumImpl};
+use std::net::IpAddr;
+use store::dispatch::lookup::KeyValue;
+use trc::{SecurityEvent, SpamEvent};
+
+pub struct SpamTrapHit<'x> {
+    pub remote_ip: IpAddr,
+    pub from: &'x str,
+    pub from_domain: &'x str,
+    pub subject: String,
+    pub traps: Vec<String>,
+    pub raw_message: &'x [u8],
+    pub span_id: u64,
+}
+
+impl Server {
+    pub async fn is_spam_trap(&self, address: &str) -> trc::Result<bool> {
+        if let Some(store) = self.get_lookup_store("spam-traps") {
+            store.key_exists(address).await
+        } else {
+            Ok(false)
+        }
+    }
+
+    // Senders are remembered by IP address and envelope domain, so a spammer
+    // rotating either one is still penalized for the other.
+    pub async fn is_spam_trap_sender(&self, ip: IpAddr, domain: &str) -> trc::Result<bool> {
+        if self.core.spam.traps.penalty_for.is_none() {
+            return Ok(false);
+        }
+
+        let store = self.in_memory_store();
+        if store
+            .key_exists(KeyValue::<()>::build_key(KV_SPAM_TRAP, ip_to_bytes(&ip)))
+            .await?
+        {
+            Ok(true)
+        } else if !domain.is_empty() {
+            store
+                .key_exists(KeyValue::<()>::build_key(KV_SPAM_TRAP, domain.as_bytes()))
+                .await
+        } else {
+            Ok(false)
+        }
+    }
+
+    pub async fn spam_trap_hit(&self, hit: SpamTrapHit<'_>) {
+        trc::event!(
+            Spam(SpamEvent::TrapHit),
+            SpanId = hit.span_id,
+            RemoteIp = hit.remote_ip,
+            From = hit.from.to_string(),
+            To = hit
+                .traps
+                .iter()
+                .map(|addr| trc::Value::from(addr.clone()))
+                .collect::<Vec<_>>(),
+        );
+
+        // Trap hits are spam by definition, train regardless of the message score
+        if let Err(err) = self
+            .add_spam_training_sample(
+                hit.raw_message,
+                hit.from.to_string(),
+                hit.subject,
+                true,
+                hit.span_id,
+            )
+            .await
+        {
+            trc::error!(
+                err.span_id(hit.span_id)
+                    .caused_by(trc::location!())
+                    .details("Failed to store spam trap training sample.")
+            );
+        }
+
+        // Penalize the sender
+        if let Some(penalty_for) = self.core.spam.traps.penalty_for {
+            let store = self.in_memory_store();
+            let mut keys = vec![ip_to_bytes(&hit.remote_ip)];
+            if !hit.from_domain.is_empty() {
+                keys.push(hit.from_domain.as_bytes().to_vec());
+            }
+            for key in keys {
+                if let Err(err) = store
+                    .key_set(KeyValue::with_prefix(KV_SPAM_TRAP, key, vec![]).expires(penalty_for))
+                    .await
+                {
+                    trc::error!(
+                        err.span_id(hit.span_id)
+                            .caused_by(trc::location!())
+                            .details("Failed to record spam trap sender.")
+                    );
+                }
+            }
+        }
+
+        // Block the sending IP
+        if self.core.spam.traps.block_ip && !self.is_ip_allowed(hit.remote_ip) {
+            match self.block_ip(hit.remote_ip, BlockReason::SpamTrap).await {
+                Ok(_) => {
+                    trc::event!(
+                        Security(SecurityEvent::AbuseBan),
+                        SpanId = hit.span_id,
+                        RemoteIp = hit.remote_ip,
+                        Reason = BlockReason::SpamTrap.as_str(),
+                    );
+                }
+                Err(err) => {
+                    trc::error!(
+                        err.span_id(hit.span_id)
+                            .caused_by(trc::location!())
+                            .details("Failed to block spam trap sender.")
+                    );
+                }
+            }
+        }
+    }
+}
@@ -13,7 +13,7 @@ use tokio::{
 };
 use tokio_rustls::server::TlsStream;
 
-use super::SessionStream;
+use super::{SessionStream, fingerprint::TlsFingerprint};
 
 impl SessionStream for TcpStream {
     fn is_tls(&self) -> bool {
@@ -23,6 +23,14 @@ impl SessionStream for TcpStream {
     fn tls_version_and_cipher(&self) -> (Cow<'static, str>, Cow<'static, str>) {
         (Cow::Borrowed(""), Cow::Borrowed(""))
     }
+
+    fn peer_certificate(&self) -> Option<&[u8]> {
+        None
+    }
+
+    fn tls_fingerprint(&self) -> Option<&TlsFingerprint> {
+        None
+    }
 }
 
 impl<T: SessionStream> SessionStream for TlsStream<T> {
@@ -62,6 +70,18 @@ impl<T: SessionStream> SessionStream for TlsStream<T> {
             .into(),
         )
     }
+
+    fn peer_certificate(&self) -> Option<&[u8]> {
+        self.get_ref()
+            .1
+            .peer_certificates()
+            .and_then(|certs| certs.first())
+            .map(|cert| cert.as_ref())
+    }
+
+    fn tls_fingerprint(&self) -> Option<&TlsFingerprint> {
+        self.get_ref().0.tls_fingerprint()
+    }
 }
 
 impl SessionStream for ProxiedStream<TcpStream> {
@@ -82,6 +102,14 @@ impl SessionStream for ProxiedStream<TcpStream> {
             })
             .unwrap_or((Cow::Borrowed("unknown"), Cow::Borrowed("unknown")))
     }
+
+    fn peer_certificate(&self) -> Option<&[u8]> {
+        None
+    }
+
+    fn tls_fingerprint(&self) -> Option<&TlsFingerprint> {
+        None
+    }
 }
 
 #[derive(Default)]
@@ -140,4 +168,12 @@ impl SessionStream for NullIo {
             std::borrow::Cow::Borrowed(""),
         )
     }
+
+    fn peer_certificate(&self) -> Option<&[u8]> {
+        None
+    }
+
+    fn tls_fingerprint(&self) -> Option<&TlsFingerprint> {
+        None
+    }
 }
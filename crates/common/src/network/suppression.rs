@@ -0,0 +1,98 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::Server;
+use registry::{
+    schema::{
+        enums::SuppressionReason,
+        prelude::{ObjectType, Property},
+        structs::SuppressedAddress,
+    },
+    types::datetime::UTCDateTime,
+};
+use store::{
+    registry::{
+        RegistryQuery,
+        write::{RegistryWrite, RegistryWriteResult},
+    },
+    write::now,
+};
+use trc::AddContext;
+use types::id::Id;
+
+impl Server {
+    // Entries without a tenant apply to all outbound mail, tenant entries only
+    // to messages sent by members of that tenant.
+    pub async fn suppressed_address(
+        &self,
+        address: &str,
+        tenant_id: Option<u32>,
+    ) -> trc::Result<Option<SuppressedAddress>> {
+        let now = now() as i64;
+        for id in self
+            .registry()
+            .query::<Vec<Id>>(
+                RegistryQuery::new(ObjectType::SuppressedAddress)
+                    .equal(Property::Email, address.to_lowercase()),
+            )
+            .await
+            .caused_by(trc::location!())?
+        {
+            if let Some(entry) = self
+                .registry()
+                .object::<SuppressedAddress>(id)
+                .await
+                .caused_by(trc::location!())?
+                && entry
+                    .member_tenant_id
+                    .is_none_or(|id| Some(id.document_id()) == tenant_id)
+                && entry
+                    .expires_at
+                    .is_none_or(|expires_at| expires_at.timestamp() > now)
+            {
+                return Ok(Some(entry));
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Returns false when the address was already suppressed for the tenant
+    pub async fn suppress_address(
+        &self,
+        address: &str,
+        tenant_id: Option<u32>,
+        reason: SuppressionReason,
+        details: Option<String>,
+    ) -> trc::Result<bool> {
+        let address = address.to_lowercase();
+        if self
+            .suppressed_address(&address, tenant_id)
+            .await?
+            .is_some()
+        {
+            return Ok(false);
+        }
+
+        let result = self
+            .registry()
+            .write(RegistryWrite::insert(
+                &SuppressedAddress {
+                    email: address,
+                    reason,
+                    details,
+                    created_at: UTCDateTime::from_timestamp(now() as i64),
+                    expires_at: None,
+                    member_tenant_id: tenant_id.map(Id::from),
+                }
+                .into(),
+            ))
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(matches!(result, RegistryWriteResult::Success(_)))
+    }
+}
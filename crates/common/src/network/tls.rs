@@ -7,8 +7,10 @@
 use super::{
     ServerInstance, SessionStream, TcpAcceptor, TcpAcceptorResult,
     acme::resolver::{IsTlsAlpnChallenge, build_acme_static_resolver},
+    fingerprint::FingerprintStream,
 };
 use crate::{Inner, Server};
+use registry::schema::enums::TlsClientCertField;
 use rustls::{
     SupportedProtocolVersion,
     server::{ClientHello, ResolvesServerCert},
@@ -117,10 +119,16 @@ impl TcpAcceptor {
                 config,
                 acceptor,
                 implicit,
+                ..
             } if *implicit => match enable_acme {
-                None => TcpAcceptorResult::Tls(acceptor.accept(stream)),
+                None => TcpAcceptorResult::Tls(acceptor.accept(FingerprintStream::new(stream))),
                 Some(core) => {
-                    match LazyConfigAcceptor::new(Default::default(), stream).await {
+                    match LazyConfigAcceptor::new(
+                        Default::default(),
+                        FingerprintStream::new(stream),
+                    )
+                    .await
+                    {
                         Ok(start_handshake) => {
                             if core.has_acme_tls_providers()
                                 && start_handshake.client_hello().is_tls_alpn_challenge()
@@ -193,13 +201,23 @@ impl TcpAcceptor {
     pub fn is_tls(&self) -> bool {
         matches!(self, TcpAcceptor::Tls { .. })
     }
+
+    pub fn client_cert_principal(&self) -> &[TlsClientCertField] {
+        match self {
+            TcpAcceptor::Tls {
+                client_cert_principal,
+                ..
+            } => client_cert_principal,
+            TcpAcceptor::Plain => &[],
+        }
+    }
 }
 
 impl<IO> TcpAcceptorResult<IO>
 where
     IO: AsyncRead + AsyncWrite + Unpin,
 {
-    pub fn unwrap_tls(self) -> Accept<IO> {
+    pub fn unwrap_tls(self) -> Accept<FingerprintStream<IO>> {
         match self {
             TcpAcceptorResult::Tls(accept) => accept,
             _ => panic!("unwrap_tls called on non-TLS acceptor"),
@@ -0,0 +1,117 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    Server, auth::oauth::GrantType, cache::invalidate::CacheInvalidationBuilder,
+    ipc::CacheInvalidation,
+};
+use registry::{
+    schema::{
+        enums::SuppressionReason,
+        prelude::{Object, ObjectType},
+        structs::MailingList,
+    },
+    types::id::ObjectId,
+};
+use store::registry::write::{RegistryWrite, RegistryWriteResult};
+use trc::AddContext;
+use types::id::Id;
+
+// Unsubscribe links remain valid long after the message was delivered, as
+// recipients often act on old list messages.
+pub const LIST_UNSUBSCRIBE_EXPIRY: u64 = 180 * 24 * 3600;
+
+impl Server {
+    // RFC 8058 headers for a single list member, the token identifies both the
+    // list and the member so each recipient has to receive its own copy.
+    pub async fn list_unsubscribe_headers(&self, list_id: u32, rcpt: &str) -> trc::Result<String> {
+        let token = self
+            .encode_access_token(
+                GrantType::ListUnsubscribe,
+                list_id,
+                rcpt,
+                LIST_UNSUBSCRIBE_EXPIRY,
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(format!(
+            concat!(
+                "List-Unsubscribe: <{}/unsubscribe/{}>\r\n",
+                "List-Unsubscribe-Post: List-Unsubscribe=One-Click\r\n"
+            ),
+            self.core.network.http.url_https, token
+        ))
+    }
+
+    pub async fn list_unsubscribe(&self, token: &str) -> trc::Result<bool> {
+        let Ok(token) = self
+            .validate_access_token(GrantType::ListUnsubscribe.into(), token)
+            .await
+        else {
+            return Ok(false);
+        };
+        let list_id = Id::from(token.account_id);
+        let Some(current) = self
+            .registry()
+            .get(ObjectId::new(ObjectType::MailingList, list_id))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(false);
+        };
+        let mut list = MailingList::from(current.clone());
+        let address = token.client_id.to_lowercase();
+
+        // Repeated requests for an address that was already removed still
+        // succeed, mailbox providers may retry the POST
+        let num_recipients = list.recipients.len();
+        list.recipients
+            .inner_mut()
+            .retain(|rcpt| !rcpt.eq_ignore_ascii_case(&address));
+        let tenant_id = list.member_tenant_id.map(|id| id.document_id());
+        let list_name = list.name.clone();
+        if list.recipients.len() != num_recipients {
+            match self
+                .registry()
+                .write(RegistryWrite::update(
+                    list_id,
+                    &Object::from(list),
+                    &current,
+                ))
+                .await
+                .caused_by(trc::location!())?
+            {
+                RegistryWriteResult::Success(_) => {
+                    self.invalidate_caches(
+                        CacheInvalidationBuilder::default()
+                            .with_invalidation(CacheInvalidation::List(token.account_id)),
+                    )
+                    .await
+                    .caused_by(trc::location!())?;
+                }
+                failure => {
+                    return Err(trc::EventType::Registry(trc::RegistryEvent::WriteError)
+                        .into_err()
+                        .caused_by(trc::location!())
+                        .details("Failed to remove list member")
+                        .reason(failure));
+                }
+            }
+        }
+
+        self.suppress_address(
+            &address,
+            tenant_id,
+            SuppressionReason::Unsubscribe,
+            Some(format!("Unsubscribed from list {list_name}")),
+        )
+        .await
+        .caused_by(trc::location!())?;
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,133 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    Server,
+    storage::encryption::{EncryptionMethod, parse_public_key},
+};
+use registry::schema::{prelude::ObjectType, structs::PublicKey};
+use sequoia_openpgp::{Cert, parse::Parse, serialize::SerializeInto};
+use sha1::{Digest, Sha1};
+use store::{registry::RegistryQuery, write::now};
+use trc::AddContext;
+use types::id::Id;
+
+const ZBASE32_ALPHABET: &[u8; 32] = b"ybndrfg8ejkmcpqxot1uwisza345h769";
+
+impl Server {
+    // Web Key Directory lookups (draft-koch-openpgp-webkey-service) are only
+    // answered for hosted domains, using the OpenPGP keys uploaded by the
+    // account that owns the requested address. The hashed local part cannot
+    // be reversed, so requests must include it in the "l" parameter.
+    pub async fn wkd_key(
+        &self,
+        domain: &str,
+        hash: &str,
+        local_part: Option<&str>,
+    ) -> trc::Result<Option<Vec<u8>>> {
+        let Some(local_part) = local_part.filter(|l| !l.is_empty()) else {
+            return Ok(None);
+        };
+        if wkd_hash(local_part) != hash {
+            return Ok(None);
+        }
+        let domain = domain.to_lowercase();
+        if !self.is_wkd_domain(&domain).await? {
+            return Ok(None);
+        }
+        let address = format!("{}@{domain}", local_part.to_lowercase());
+        let Some(account_id) = self
+            .account_id_from_email(&address, false)
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(None);
+        };
+
+        let mut keys = Vec::new();
+        let now = now() as i64;
+        for id in self
+            .registry()
+            .query::<Vec<Id>>(RegistryQuery::new(ObjectType::PublicKey).with_account(account_id))
+            .await
+            .caused_by(trc::location!())?
+        {
+            let Some(key) = self
+                .registry()
+                .object::<PublicKey>(id)
+                .await
+                .caused_by(trc::location!())?
+            else {
+                continue;
+            };
+
+            // Keys restricted to a set of addresses are only published for them
+            if key
+                .expires_at
+                .is_some_and(|expires_at| expires_at.timestamp() <= now)
+                || (!key.email_addresses.is_empty()
+                    && !key
+                        .email_addresses
+                        .iter()
+                        .any(|addr| addr.eq_ignore_ascii_case(&address)))
+            {
+                continue;
+            }
+
+            let Ok(Some(params)) = parse_public_key(&key) else {
+                continue;
+            };
+            if params.method != EncryptionMethod::PGP {
+                continue;
+            }
+            for cert in params.certs.iter() {
+                match Cert::from_bytes(cert.as_ref()).and_then(|cert| cert.export_to_vec()) {
+                    Ok(cert) => keys.extend_from_slice(&cert),
+                    Err(err) => {
+                        trc::event!(
+                            Resource(trc::ResourceEvent::Error),
+                            AccountId = account_id,
+                            Id = id.id(),
+                            Reason = err.to_string(),
+                            Details = "Failed to export OpenPGP key",
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok((!keys.is_empty()).then_some(keys))
+    }
+
+    pub async fn is_wkd_domain(&self, domain: &str) -> trc::Result<bool> {
+        self.domain(&domain.to_lowercase())
+            .await
+            .caused_by(trc::location!())
+            .map(|domain| domain.is_some())
+    }
+}
+
+// Z-Base-32 encoded SHA-1 digest of the lowercased local part
+pub fn wkd_hash(local_part: &str) -> String {
+    let digest = Sha1::digest(local_part.to_ascii_lowercase().as_bytes());
+    let mut result = String::with_capacity(32);
+    let mut buf = 0u32;
+    let mut bits = 0;
+
+    for &byte in digest.iter() {
+        buf = (buf << 8) | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            result.push(ZBASE32_ALPHABET[((buf >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        result.push(ZBASE32_ALPHABET[((buf << (5 - bits)) & 0x1f) as usize] as char);
+    }
+
+    result
+}
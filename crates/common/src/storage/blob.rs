@@ -9,12 +9,19 @@ use mail_parser::{
     Encoding,
     decoders::{base64::base64_decode, quoted_printable::quoted_printable_decode},
 };
+use registry::{
+    schema::{
+        prelude::{ObjectType, Property},
+        structs::SpamTrainingSample,
+    },
+    types::{EnumImpl, ObjectImpl, datetime::UTCDateTime, id::ObjectId},
+};
 use store::{
-    U32_LEN, U64_LEN,
+    SerializeInfallible, U32_LEN, U64_LEN,
     dispatch::lookup::KeyValue,
-    write::{BatchBuilder, BlobLink, BlobOp, now},
+    write::{BatchBuilder, BlobLink, BlobOp, RegistryClass, ValueClass, now},
 };
-use trc::AddContext;
+use trc::{AddContext, SpamEvent};
 use types::{
     blob::{BlobClass, BlobId, BlobSection},
     blob_hash::BlobHash,
@@ -181,6 +188,100 @@ impl Server {
         ))
     }
 
+    // Samples are registry items of the data store and their message is kept
+    // in the data blob store, regardless of where the message came from.
+    pub async fn add_spam_training_sample(
+        &self,
+        raw_message: &[u8],
+        from: String,
+        subject: String,
+        is_spam: bool,
+        span_id: u64,
+    ) -> trc::Result<()> {
+        let Some(config) = &self.core.spam.classifier else {
+            return Ok(());
+        };
+
+        let hash = BlobHash::generate(raw_message);
+        let hold_period = now() + config.hold_samples_for;
+        let sample = SpamTrainingSample {
+            account_id: None,
+            blob_id: BlobId::new(hash.clone(), Default::default()),
+            delete_after_use: false,
+            expires_at: UTCDateTime::from_timestamp(hold_period as i64),
+            from,
+            is_spam,
+            subject,
+        }
+        .to_pickled_vec();
+
+        // Link the blob before uploading it so it is not purged in between
+        let object_id = ObjectType::SpamTrainingSample.to_id();
+        let item_id = self.inner.data.registry_id_gen.generate();
+        let mut batch = BatchBuilder::new();
+        batch
+            .set(
+                BlobOp::Link {
+                    hash: hash.clone(),
+                    to: BlobLink::Temporary { until: hold_period },
+                },
+                ObjectId::new(ObjectType::SpamTrainingSample, item_id.into()).serialize(),
+            )
+            .set(
+                ValueClass::Registry(RegistryClass::Item { object_id, item_id }),
+                sample,
+            )
+            .set(
+                ValueClass::Registry(RegistryClass::Index {
+                    index_id: Property::AccountId.to_id(),
+                    object_id,
+                    item_id,
+                    key: (u32::MAX as u64).serialize(),
+                }),
+                vec![],
+            );
+        self.core
+            .storage
+            .data
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())?;
+
+        if !self
+            .core
+            .storage
+            .data
+            .blob_exists(&hash)
+            .await
+            .caused_by(trc::location!())?
+        {
+            self.core
+                .storage
+                .blob
+                .put_blob(hash.as_ref(), raw_message, self.core.email.compression)
+                .await
+                .caused_by(trc::location!())?;
+
+            let mut batch = BatchBuilder::new();
+            batch.set(BlobOp::Commit { hash }, Vec::new());
+            self.core
+                .storage
+                .data
+                .write(batch.build_all())
+                .await
+                .caused_by(trc::location!())?;
+        }
+
+        trc::event!(
+            Spam(SpamEvent::TrainSampleAdded),
+            Details = if is_spam { "spam" } else { "ham" },
+            Expires = trc::Value::Timestamp(hold_period),
+            SpanId = span_id,
+        );
+
+        Ok(())
+    }
+
     pub async fn get_blob_section(
         &self,
         hash: &BlobHash,
@@ -48,6 +48,24 @@ impl Server {
         &self.core.storage.blob
     }
 
+    #[inline(always)]
+    pub fn queue_store(&self) -> &Store {
+        self.core
+            .storage
+            .queue
+            .as_ref()
+            .unwrap_or(&self.core.storage.data)
+    }
+
+    #[inline(always)]
+    pub fn queue_blob_store(&self) -> &BlobStore {
+        self.core
+            .storage
+            .queue_blob
+            .as_ref()
+            .unwrap_or(&self.core.storage.blob)
+    }
+
     #[inline(always)]
     pub fn search_store(&self) -> &SearchStore {
         &self.core.storage.search
@@ -0,0 +1,162 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{KV_BANDWIDTH, Server};
+use ahash::AHashMap;
+use store::{dispatch::lookup::KeyValue, write::now};
+use trc::AddContext;
+
+const BANDWIDTH_RETENTION_DAYS: u64 = 90;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BandwidthProtocol {
+    Imap,
+    Pop3,
+    Jmap,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BandwidthCounter {
+    pub received: u64,
+    pub sent: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct BandwidthUsage {
+    pub accounts: AHashMap<(u32, BandwidthProtocol), BandwidthCounter>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BandwidthDay {
+    pub day: u64,
+    pub protocols: Vec<(BandwidthProtocol, BandwidthCounter)>,
+}
+
+impl BandwidthProtocol {
+    pub const ALL: [BandwidthProtocol; 3] = [
+        BandwidthProtocol::Imap,
+        BandwidthProtocol::Pop3,
+        BandwidthProtocol::Jmap,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BandwidthProtocol::Imap => "imap",
+            BandwidthProtocol::Pop3 => "pop3",
+            BandwidthProtocol::Jmap => "jmap",
+        }
+    }
+
+    fn id(&self) -> u8 {
+        match self {
+            BandwidthProtocol::Imap => 0,
+            BandwidthProtocol::Pop3 => 1,
+            BandwidthProtocol::Jmap => 2,
+        }
+    }
+}
+
+impl Server {
+    // Usage is accumulated in memory and periodically flushed to the
+    // in-memory store, which avoids a store round trip on every read or write.
+    pub fn record_bandwidth(
+        &self,
+        account_id: u32,
+        protocol: BandwidthProtocol,
+        received: usize,
+        sent: usize,
+    ) {
+        if received == 0 && sent == 0 {
+            return;
+        }
+
+        let mut usage = self.inner.data.bandwidth_usage.lock();
+        let counter = usage.accounts.entry((account_id, protocol)).or_default();
+        counter.received += received as u64;
+        counter.sent += sent as u64;
+    }
+
+    pub async fn flush_bandwidth_usage(&self) -> trc::Result<()> {
+        let accounts = std::mem::take(&mut self.inner.data.bandwidth_usage.lock().accounts);
+        if accounts.is_empty() {
+            return Ok(());
+        }
+
+        let day = now() / 86400;
+        let expires = (BANDWIDTH_RETENTION_DAYS + 1) * 86400;
+        let store = self.in_memory_store();
+
+        for ((account_id, protocol), counter) in accounts {
+            for (is_sent, value) in [(false, counter.received), (true, counter.sent)] {
+                if value > 0 {
+                    store
+                        .counter_incr(
+                            KeyValue::new(
+                                bandwidth_key(account_id, protocol, day, is_sent),
+                                value as i64,
+                            )
+                            .expires(expires),
+                            false,
+                        )
+                        .await
+                        .caused_by(trc::location!())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn bandwidth_usage(
+        &self,
+        account_id: u32,
+        days: u64,
+    ) -> trc::Result<Vec<BandwidthDay>> {
+        let today = now() / 86400;
+        let days = days.clamp(1, BANDWIDTH_RETENTION_DAYS);
+        let store = self.in_memory_store();
+        let mut results = Vec::with_capacity(days as usize);
+
+        for day in (today + 1 - days..=today).rev() {
+            let mut protocols = Vec::with_capacity(BandwidthProtocol::ALL.len());
+            for protocol in BandwidthProtocol::ALL {
+                let received = store
+                    .counter_get(bandwidth_key(account_id, protocol, day, false))
+                    .await
+                    .caused_by(trc::location!())?;
+                let sent = store
+                    .counter_get(bandwidth_key(account_id, protocol, day, true))
+                    .await
+                    .caused_by(trc::location!())?;
+                if received > 0 || sent > 0 {
+                    protocols.push((
+                        protocol,
+                        BandwidthCounter {
+                            received: received as u64,
+                            sent: sent as u64,
+                        },
+                    ));
+                }
+            }
+            results.push(BandwidthDay {
+                day: day * 86400,
+                protocols,
+            });
+        }
+
+        Ok(results)
+    }
+}
+
+fn bandwidth_key(account_id: u32, protocol: BandwidthProtocol, day: u64, is_sent: bool) -> Vec<u8> {
+    let mut key = Vec::with_capacity(11);
+    key.push(KV_BANDWIDTH);
+    key.extend_from_slice(&account_id.to_be_bytes());
+    key.push(protocol.id());
+    key.extend_from_slice(&(day as u32).to_be_bytes());
+    key.push(is_sent as u8);
+    key
+}
@@ -0,0 +1,249 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::Server;
+use ahash::AHashMap;
+use registry::schema::structs::Tenant;
+
+const OTHER_LABEL: &str = "other";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BreakdownMetric {
+    MessageVolume,
+    AuthFailures,
+    QueueSize,
+    QuotaUsed,
+}
+
+#[derive(Debug, Default)]
+pub struct MetricsBreakdown {
+    pub domains: AHashMap<BreakdownMetric, BreakdownSeries>,
+    pub tenants: AHashMap<BreakdownMetric, BreakdownSeries>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BreakdownFamily {
+    pub metric: BreakdownMetric,
+    pub label: &'static str,
+    pub values: Vec<(String, u64)>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct BreakdownSeries {
+    pub values: AHashMap<u32, u64>,
+    pub other: u64,
+}
+
+impl BreakdownMetric {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BreakdownMetric::MessageVolume => "message_volume",
+            BreakdownMetric::AuthFailures => "auth_failures",
+            BreakdownMetric::QueueSize => "queue_size",
+            BreakdownMetric::QuotaUsed => "quota_used",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            BreakdownMetric::MessageVolume => "Messages delivered to local accounts",
+            BreakdownMetric::AuthFailures => "Failed authentication attempts",
+            BreakdownMetric::QueueSize => "Messages waiting in the delivery queue",
+            BreakdownMetric::QuotaUsed => "Disk quota used in bytes",
+        }
+    }
+
+    pub fn is_counter(&self) -> bool {
+        matches!(
+            self,
+            BreakdownMetric::MessageVolume | BreakdownMetric::AuthFailures
+        )
+    }
+}
+
+impl BreakdownSeries {
+    // Once the maximum number of labels is reached, new labels are
+    // aggregated under the "other" label to keep cardinality bounded.
+    pub fn increment(&mut self, id: Option<u32>, value: u64, max_labels: usize) {
+        match id {
+            Some(id) if self.values.contains_key(&id) || self.values.len() < max_labels => {
+                *self.values.entry(id).or_default() += value;
+            }
+            _ => {
+                self.other += value;
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty() && self.other == 0
+    }
+
+    // Keeps the largest values, the rest are aggregated under the "other" label.
+    pub fn from_totals(totals: AHashMap<u32, u64>, other: u64, max_labels: usize) -> Self {
+        let mut totals = totals.into_iter().collect::<Vec<_>>();
+        totals.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+        let other = other + totals.iter().skip(max_labels).map(|(_, v)| *v).sum::<u64>();
+        totals.truncate(max_labels);
+
+        BreakdownSeries {
+            values: totals.into_iter().collect(),
+            other,
+        }
+    }
+}
+
+impl Server {
+    pub async fn record_metrics_breakdown(&self, metric: BreakdownMetric, domain: &str) {
+        let Some(prometheus) = self
+            .core
+            .metrics
+            .prometheus
+            .as_ref()
+            .filter(|p| p.domain_breakdown || p.tenant_breakdown)
+        else {
+            return;
+        };
+
+        // Only domains hosted on this server are used as labels
+        let domain = self.domain(domain).await.ok().flatten();
+        let max_labels = prometheus.max_breakdown_labels;
+        let mut breakdown = self.inner.data.metrics_breakdown.lock();
+        if prometheus.domain_breakdown {
+            breakdown.domains.entry(metric).or_default().increment(
+                domain.as_ref().map(|d| d.id),
+                1,
+                max_labels,
+            );
+        }
+        if prometheus.tenant_breakdown
+            && let Some(tenant_id) = domain.as_ref().and_then(|d| d.id_tenant)
+        {
+            breakdown
+                .tenants
+                .entry(metric)
+                .or_default()
+                .increment(Some(tenant_id), 1, max_labels);
+        }
+    }
+
+    pub fn update_metrics_breakdown(
+        &self,
+        metric: BreakdownMetric,
+        domains: Option<BreakdownSeries>,
+        tenants: Option<BreakdownSeries>,
+    ) {
+        let mut breakdown = self.inner.data.metrics_breakdown.lock();
+        if let Some(domains) = domains {
+            breakdown.domains.insert(metric, domains);
+        }
+        if let Some(tenants) = tenants {
+            breakdown.tenants.insert(metric, tenants);
+        }
+    }
+
+    pub async fn collect_metrics_breakdown(&self) -> Vec<BreakdownFamily> {
+        let Some(prometheus) = &self.core.metrics.prometheus else {
+            return Vec::new();
+        };
+        let (domains, tenants) = {
+            let breakdown = self.inner.data.metrics_breakdown.lock();
+            (
+                if prometheus.domain_breakdown {
+                    breakdown.domains.clone()
+                } else {
+                    AHashMap::new()
+                },
+                if prometheus.tenant_breakdown {
+                    breakdown.tenants.clone()
+                } else {
+                    AHashMap::new()
+                },
+            )
+        };
+        let mut families = Vec::new();
+
+        // Resolve domain names
+        let mut names = AHashMap::new();
+        for id in domains.values().flat_map(|series| series.values.keys()) {
+            if !names.contains_key(id)
+                && let Some(domain) = self.domain_by_id(*id).await.ok().flatten()
+                && let Some(name) = domain.names.first()
+            {
+                names.insert(*id, name.to_string());
+            }
+        }
+        for (metric, series) in domains {
+            if !series.is_empty() {
+                families.push(BreakdownFamily::new(metric, "domain", &series, &names));
+            }
+        }
+
+        // Resolve tenant names
+        let mut names = AHashMap::new();
+        for id in tenants.values().flat_map(|series| series.values.keys()) {
+            if !names.contains_key(id)
+                && let Some(tenant) = self
+                    .registry()
+                    .object::<Tenant>((*id).into())
+                    .await
+                    .ok()
+                    .flatten()
+            {
+                names.insert(*id, tenant.name);
+            }
+        }
+        for (metric, series) in tenants {
+            if !series.is_empty() {
+                families.push(BreakdownFamily::new(metric, "tenant", &series, &names));
+            }
+        }
+
+        families
+    }
+}
+
+impl BreakdownFamily {
+    fn new(
+        metric: BreakdownMetric,
+        label: &'static str,
+        series: &BreakdownSeries,
+        names: &AHashMap<u32, String>,
+    ) -> Self {
+        let mut values = AHashMap::with_capacity(series.values.len() + 1);
+        for (id, value) in &series.values {
+            // Entries that can no longer be resolved are reported as "other"
+            let name = names
+                .get(id)
+                .map(|name| name.as_str())
+                .unwrap_or(OTHER_LABEL);
+            *values.entry(name).or_insert(0) += *value;
+        }
+        if series.other > 0 {
+            *values.entry(OTHER_LABEL).or_insert(0) += series.other;
+        }
+
+        let mut values = values
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect::<Vec<_>>();
+        values.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+        BreakdownFamily {
+            metric,
+            label,
+            values,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        format!("{}_by_{}", self.metric.name(), self.label)
+    }
+
+    pub fn description(&self) -> String {
+        format!("{} by {}", self.metric.description(), self.label)
+    }
+}
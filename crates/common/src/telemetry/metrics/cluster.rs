@@ -0,0 +1,232 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::prometheus::{metric_name, new_label};
+use crate::Server;
+use ahash::AHashMap;
+use opentelemetry_proto::tonic::{
+    collector::metrics::v1::ExportMetricsServiceRequest,
+    common::v1::{KeyValue, any_value},
+    metrics::v1::{NumberDataPoint, metric, number_data_point},
+};
+use opentelemetry_semantic_conventions::resource::SERVICE_INSTANCE_ID;
+use prometheus::proto::{
+    Bucket, Counter, Gauge, Histogram, LabelPair, Metric, MetricFamily, MetricType,
+};
+use prost::Message;
+use std::{
+    net::IpAddr,
+    time::{Duration, Instant},
+};
+
+// Metrics from nodes that stopped pushing are no longer exported
+const NODE_METRICS_TTL: Duration = Duration::from_secs(10 * 60);
+
+#[derive(Debug)]
+pub struct ClusterNodeMetrics {
+    pub received: Instant,
+    pub families: Vec<MetricFamily>,
+}
+
+impl Server {
+    pub fn ingest_otel_metrics(&self, bytes: &[u8], remote_ip: IpAddr) -> trc::Result<()> {
+        let request = ExportMetricsServiceRequest::decode(bytes).map_err(|err| {
+            trc::EventType::Telemetry(trc::TelemetryEvent::OtelMetricsExporterError)
+                .into_err()
+                .reason(err)
+                .details("Failed to decode OTLP metrics request")
+        })?;
+
+        let mut nodes: AHashMap<String, Vec<MetricFamily>> = AHashMap::new();
+        for resource_metrics in request.resource_metrics {
+            let node = resource_metrics
+                .resource
+                .as_ref()
+                .and_then(|resource| {
+                    resource
+                        .attributes
+                        .iter()
+                        .find(|kv| kv.key == SERVICE_INSTANCE_ID)
+                })
+                .and_then(attribute_value)
+                .unwrap_or_else(|| remote_ip.to_string());
+            let families = nodes.entry(node.clone()).or_default();
+
+            for metric in resource_metrics
+                .scope_metrics
+                .into_iter()
+                .flat_map(|scope| scope.metrics)
+            {
+                let mut family = MetricFamily::default();
+                family.set_name(metric_name(&metric.name));
+                family.set_help(metric.description);
+
+                let metrics = match metric.data {
+                    Some(metric::Data::Sum(sum)) => {
+                        family.set_field_type(if sum.is_monotonic {
+                            MetricType::COUNTER
+                        } else {
+                            MetricType::GAUGE
+                        });
+                        sum.data_points
+                            .into_iter()
+                            .map(|dp| {
+                                let mut m = Metric::default();
+                                if sum.is_monotonic {
+                                    let mut counter = Counter::default();
+                                    counter.set_value(number_value(&dp));
+                                    m.set_counter(counter);
+                                } else {
+                                    let mut gauge = Gauge::default();
+                                    gauge.set_value(number_value(&dp));
+                                    m.set_gauge(gauge);
+                                }
+                                m.set_label(labels(&node, &dp.attributes));
+                                m
+                            })
+                            .collect::<Vec<_>>()
+                    }
+                    Some(metric::Data::Gauge(gauge)) => {
+                        family.set_field_type(MetricType::GAUGE);
+                        gauge
+                            .data_points
+                            .into_iter()
+                            .map(|dp| {
+                                let mut m = Metric::default();
+                                let mut gauge = Gauge::default();
+                                gauge.set_value(number_value(&dp));
+                                m.set_gauge(gauge);
+                                m.set_label(labels(&node, &dp.attributes));
+                                m
+                            })
+                            .collect::<Vec<_>>()
+                    }
+                    Some(metric::Data::Histogram(histogram)) => {
+                        family.set_field_type(MetricType::HISTOGRAM);
+                        histogram
+                            .data_points
+                            .into_iter()
+                            .map(|dp| {
+                                let mut m = Metric::default();
+                                let mut h = Histogram::default();
+                                h.set_sample_count(dp.count);
+                                h.set_sample_sum(dp.sum.unwrap_or_default());
+
+                                // OTLP buckets are not cumulative
+                                let mut cumulative_count = 0;
+                                h.set_bucket(
+                                    dp.bucket_counts
+                                        .iter()
+                                        .enumerate()
+                                        .map(|(idx, count)| {
+                                            cumulative_count += *count;
+                                            let mut b = Bucket::default();
+                                            b.set_cumulative_count(cumulative_count);
+                                            b.set_upper_bound(
+                                                dp.explicit_bounds
+                                                    .get(idx)
+                                                    .copied()
+                                                    .unwrap_or(f64::INFINITY),
+                                            );
+                                            b
+                                        })
+                                        .collect(),
+                                );
+                                m.set_histogram(h);
+                                m.set_label(labels(&node, &dp.attributes));
+                                m
+                            })
+                            .collect::<Vec<_>>()
+                    }
+                    _ => continue,
+                };
+
+                if !metrics.is_empty() {
+                    family.set_metric(metrics);
+                    families.push(family);
+                }
+            }
+        }
+
+        let now = Instant::now();
+        let mut cluster_metrics = self.inner.data.cluster_metrics.lock();
+        cluster_metrics
+            .retain(|_, metrics| now.duration_since(metrics.received) < NODE_METRICS_TTL);
+        for (node, families) in nodes {
+            cluster_metrics.insert(
+                node,
+                ClusterNodeMetrics {
+                    received: now,
+                    families,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn merge_cluster_metrics(&self, metrics: &mut Vec<MetricFamily>) {
+        let cluster_metrics = self.inner.data.cluster_metrics.lock();
+        if cluster_metrics.is_empty() {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut positions = metrics
+            .iter()
+            .enumerate()
+            .map(|(idx, family)| (family.name().to_string(), idx))
+            .collect::<AHashMap<_, _>>();
+        for node_metrics in cluster_metrics.values() {
+            if now.duration_since(node_metrics.received) >= NODE_METRICS_TTL {
+                continue;
+            }
+
+            for family in &node_metrics.families {
+                match positions.get(family.name()) {
+                    Some(idx) => {
+                        metrics[*idx].metric.extend(family.metric.iter().cloned());
+                    }
+                    None => {
+                        positions.insert(family.name().to_string(), metrics.len());
+                        metrics.push(family.clone());
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn number_value(dp: &NumberDataPoint) -> f64 {
+    match dp.value {
+        Some(number_data_point::Value::AsDouble(value)) => value,
+        Some(number_data_point::Value::AsInt(value)) => value as f64,
+        None => 0.0,
+    }
+}
+
+fn attribute_value(kv: &KeyValue) -> Option<String> {
+    match kv.value.as_ref()?.value.as_ref()? {
+        any_value::Value::StringValue(value) => Some(value.clone()),
+        any_value::Value::BoolValue(value) => Some(value.to_string()),
+        any_value::Value::IntValue(value) => Some(value.to_string()),
+        any_value::Value::DoubleValue(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+fn labels(node: &str, attributes: &[KeyValue]) -> Vec<LabelPair> {
+    let mut labels = Vec::with_capacity(attributes.len() + 1);
+    labels.push(new_label("node", node));
+    for kv in attributes {
+        if kv.key != "node"
+            && let Some(value) = attribute_value(kv)
+        {
+            labels.push(new_label(metric_name(&kv.key), value));
+        }
+    }
+    labels
+}
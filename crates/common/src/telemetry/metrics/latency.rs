@@ -0,0 +1,120 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::Server;
+use ahash::AHashMap;
+use std::time::Duration;
+use trc::{MetricType, atomics::histogram::AtomicHistogram};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LatencyMetric {
+    ReceivedToFiltered,
+    QueuedToFirstAttempt,
+    QueuedToDelivered,
+}
+
+#[derive(Default)]
+pub struct DeliveryLatency {
+    pub series: AHashMap<LatencyMetric, LatencySeries>,
+}
+
+pub struct LatencySeries {
+    pub total: AtomicHistogram<12>,
+    pub domains: AHashMap<String, AtomicHistogram<12>>,
+    pub other: Option<AtomicHistogram<12>>,
+}
+
+impl LatencyMetric {
+    pub fn name(&self) -> &'static str {
+        match self {
+            LatencyMetric::ReceivedToFiltered => "message_filter_latency",
+            LatencyMetric::QueuedToFirstAttempt => "queue_first_attempt_latency",
+            LatencyMetric::QueuedToDelivered => "queue_delivery_latency",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            LatencyMetric::ReceivedToFiltered => {
+                "Time from message reception until filtering completes"
+            }
+            LatencyMetric::QueuedToFirstAttempt => {
+                "Time from queueing until the first delivery attempt"
+            }
+            LatencyMetric::QueuedToDelivered => "Time from queueing until successful delivery",
+        }
+    }
+
+    fn new_histogram(&self) -> AtomicHistogram<12> {
+        match self {
+            LatencyMetric::ReceivedToFiltered => {
+                AtomicHistogram::<12>::new_short_durations(MetricType::MessageIngestTime)
+            }
+            LatencyMetric::QueuedToFirstAttempt | LatencyMetric::QueuedToDelivered => {
+                AtomicHistogram::<12>::new_long_durations(MetricType::DeliveryTotalTime)
+            }
+        }
+    }
+}
+
+impl LatencySeries {
+    fn new(metric: LatencyMetric) -> Self {
+        LatencySeries {
+            total: metric.new_histogram(),
+            domains: AHashMap::new(),
+            other: None,
+        }
+    }
+
+    // Once the maximum number of labels is reached, new domains are
+    // aggregated under the "other" label to keep cardinality bounded.
+    fn observe(
+        &mut self,
+        metric: LatencyMetric,
+        domain: Option<&str>,
+        value: u64,
+        max_labels: usize,
+    ) {
+        self.total.observe(value);
+
+        if let Some(domain) = domain {
+            if let Some(histogram) = self.domains.get(domain) {
+                histogram.observe(value);
+            } else if self.domains.len() < max_labels {
+                let histogram = metric.new_histogram();
+                histogram.observe(value);
+                self.domains.insert(domain.to_string(), histogram);
+            } else {
+                self.other
+                    .get_or_insert_with(|| metric.new_histogram())
+                    .observe(value);
+            }
+        }
+    }
+}
+
+impl Server {
+    pub fn record_latency(&self, metric: LatencyMetric, domain: &str, elapsed: Duration) {
+        let Some(prometheus) = &self.core.metrics.prometheus else {
+            return;
+        };
+
+        let domain = prometheus.domain_breakdown.then(|| domain.to_lowercase());
+        self.inner
+            .data
+            .delivery_latency
+            .lock()
+            .series
+            .entry(metric)
+            .or_insert_with(|| LatencySeries::new(metric))
+            .observe(
+                metric,
+                domain.as_deref(),
+                elapsed.as_millis() as u64,
+                prometheus.max_breakdown_labels,
+            );
+    }
+}
@@ -4,6 +4,10 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+pub mod bandwidth;
+pub mod breakdown;
+pub mod cluster;
+pub mod latency;
 pub mod otel;
 pub mod prometheus;
 
@@ -4,7 +4,9 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+use super::breakdown::BreakdownFamily;
 use crate::config::telemetry::OtelMetrics;
+use opentelemetry::KeyValue;
 use opentelemetry_sdk::metrics::{
     Temporality,
     data::{
@@ -17,7 +19,12 @@ use std::time::SystemTime;
 use trc::{Collector, TelemetryEvent};
 
 impl OtelMetrics {
-    pub async fn push_metrics(&self, is_enterprise: bool, start_time: SystemTime) {
+    pub async fn push_metrics(
+        &self,
+        is_enterprise: bool,
+        start_time: SystemTime,
+        breakdown: Vec<BreakdownFamily>,
+    ) {
         let mut metrics = Vec::with_capacity(256);
         let time = SystemTime::now();
 
@@ -75,6 +82,44 @@ impl OtelMetrics {
             ));
         }
 
+        // Add per-domain and per-tenant breakdowns
+        for family in breakdown {
+            let name = family.name();
+            let description = family.description();
+            let label = family.label;
+            let is_counter = family.metric.is_counter();
+            let data_points = family
+                .values
+                .into_iter()
+                .map(|(value_name, value)| (vec![KeyValue::new(label, value_name)], value));
+            metrics.push(Metric::new(
+                name,
+                description,
+                "",
+                AggregatedMetrics::U64(if is_counter {
+                    MetricData::Sum(Sum::new(
+                        data_points
+                            .map(|(attributes, value)| SumDataPoint::new(attributes, value, vec![]))
+                            .collect(),
+                        start_time,
+                        time,
+                        Temporality::Cumulative,
+                        true,
+                    ))
+                } else {
+                    MetricData::Gauge(Gauge::new(
+                        data_points
+                            .map(|(attributes, value)| {
+                                GaugeDataPoint::new(attributes, value, vec![])
+                            })
+                            .collect(),
+                        Some(start_time),
+                        time,
+                    ))
+                }),
+            ));
+        }
+
         // Export metrics
         let rm = ResourceMetrics::new(
             self.resource.clone(),
@@ -6,7 +6,7 @@
 
 use prometheus::{
     TextEncoder,
-    proto::{Bucket, Counter, Gauge, Histogram, Metric, MetricFamily, MetricType},
+    proto::{Bucket, Counter, Gauge, Histogram, LabelPair, Metric, MetricFamily, MetricType},
 };
 use trc::{Collector, atomics::histogram::AtomicHistogram};
 
@@ -56,13 +56,86 @@ impl Server {
             metrics.push(metric);
         }
 
+        // Add per-domain and per-tenant breakdowns
+        for family in self.collect_metrics_breakdown().await {
+            let mut metric = MetricFamily::default();
+            metric.set_name(family.name());
+            metric.set_help(family.description());
+            metric.set_field_type(if family.metric.is_counter() {
+                MetricType::COUNTER
+            } else {
+                MetricType::GAUGE
+            });
+            metric.set_metric(
+                family
+                    .values
+                    .into_iter()
+                    .map(|(name, value)| {
+                        let mut m = if family.metric.is_counter() {
+                            new_counter(value)
+                        } else {
+                            new_gauge(value)
+                        };
+                        m.set_label(vec![new_label(family.label, name)]);
+                        m
+                    })
+                    .collect(),
+            );
+            metrics.push(metric);
+        }
+
+        // Add message latency histograms
+        {
+            let latency = self.inner.data.delivery_latency.lock();
+            for (metric, series) in &latency.series {
+                let mut family = MetricFamily::default();
+                family.set_name(metric.name().to_string());
+                family.set_help(metric.description().to_string());
+                family.set_field_type(MetricType::HISTOGRAM);
+                family.set_metric(vec![new_histogram(&series.total)]);
+                metrics.push(family);
+
+                if !series.domains.is_empty() || series.other.is_some() {
+                    let mut family = MetricFamily::default();
+                    family.set_name(format!("{}_by_domain", metric.name()));
+                    family.set_help(format!("{} by domain", metric.description()));
+                    family.set_field_type(MetricType::HISTOGRAM);
+                    family.set_metric(
+                        series
+                            .domains
+                            .iter()
+                            .map(|(domain, histogram)| (domain.as_str(), histogram))
+                            .chain(series.other.as_ref().map(|histogram| ("other", histogram)))
+                            .map(|(domain, histogram)| {
+                                let mut m = new_histogram(histogram);
+                                m.set_label(vec![new_label("domain", domain)]);
+                                m
+                            })
+                            .collect(),
+                    );
+                    metrics.push(family);
+                }
+            }
+        }
+
+        // Add metrics pushed by other cluster nodes
+        if self
+            .core
+            .metrics
+            .prometheus
+            .as_ref()
+            .is_some_and(|p| p.otlp_ingest)
+        {
+            self.merge_cluster_metrics(&mut metrics);
+        }
+
         TextEncoder::new().encode_to_string(&metrics).map_err(|e| {
             trc::EventType::Telemetry(trc::TelemetryEvent::OtelExporterError).reason(e)
         })
     }
 }
 
-fn metric_name(id: impl AsRef<str>) -> String {
+pub(super) fn metric_name(id: impl AsRef<str>) -> String {
     let id = id.as_ref();
     let mut name = String::with_capacity(id.len());
     for c in id.chars() {
@@ -75,6 +148,13 @@ fn metric_name(id: impl AsRef<str>) -> String {
     name
 }
 
+pub(super) fn new_label(name: impl Into<String>, value: impl Into<String>) -> LabelPair {
+    let mut label = LabelPair::default();
+    label.set_name(name.into());
+    label.set_value(value.into());
+    label
+}
+
 fn new_counter(value: u64) -> Metric {
     let mut m = Metric::default();
     let mut counter = Counter::default();
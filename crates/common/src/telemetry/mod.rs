@@ -11,6 +11,7 @@ pub mod webhooks;
 use tracers::log::spawn_log_tracer;
 use tracers::otel::spawn_otel_tracer;
 use tracers::stdout::spawn_console_tracer;
+use tracers::syslog::spawn_syslog_tracer;
 use trc::{Collector, ipc::subscriber::SubscriberBuilder};
 use webhooks::spawn_webhook_tracer;
 
@@ -102,6 +103,9 @@ impl TelemetrySubscriberType {
                 spawn_console_tracer(builder, settings)
             }
             TelemetrySubscriberType::LogTracer(settings) => spawn_log_tracer(builder, settings),
+            TelemetrySubscriberType::SyslogTracer(settings) => {
+                spawn_syslog_tracer(builder, settings)
+            }
             TelemetrySubscriberType::Webhook(settings) => spawn_webhook_tracer(builder, settings),
             TelemetrySubscriberType::OtelTracer(settings) => spawn_otel_tracer(builder, settings),
             #[cfg(unix)]
@@ -40,6 +40,9 @@ impl Subscriber {
         put_field_length_encoded(&mut buf, "MESSAGE", |buf| {
             write!(buf, "{}", event.inner.typ.description()).unwrap()
         });
+        put_field_length_encoded(&mut buf, "EVENT_TYPE", |buf| {
+            write!(buf, "{}", event.inner.typ.as_str()).unwrap()
+        });
 
         let mut seen_keys = AHashSet::new();
         for (key, value) in &event.keys {
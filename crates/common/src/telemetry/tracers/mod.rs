@@ -9,6 +9,7 @@ pub mod journald;
 pub mod log;
 pub mod otel;
 pub mod stdout;
+pub mod syslog;
 
 // SPDX-SnippetBegin
 // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
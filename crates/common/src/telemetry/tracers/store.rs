@@ -159,6 +159,9 @@ impl StoreTracer {
                                     | QueueEvent::RateLimitExceeded
                                     | QueueEvent::ConcurrencyLimitExceeded
                                     | QueueEvent::QuotaExceeded
+                                    | QueueEvent::MessageDelivered
+                                    | QueueEvent::MessageDeferred
+                                    | QueueEvent::MessageBounced
                             )
                             | EventType::Limit(_)
                             | EventType::Tls(_)
@@ -210,12 +213,17 @@ pub fn build_span_document(span_id: u64, trace: Trace) -> IndexDocument {
                     document.index_unsigned(TracingSearchField::QueueId, value);
                 }
                 (
-                    Key::From | Key::To | Key::Domain | Key::Hostname,
+                    Key::From
+                    | Key::To
+                    | Key::Domain
+                    | Key::Hostname
+                    | Key::MessageId
+                    | Key::EnvelopeId,
                     TraceValue::String(TraceValueString { value }),
                 ) => {
                     keywords.insert(value);
                 }
-                (Key::To, TraceValue::List(TraceValueList { value })) => {
+                (Key::To | Key::Tags, TraceValue::List(TraceValueList { value })) => {
                     for value in value {
                         if let TraceValue::String(TraceValueString { value }) = value {
                             keywords.insert(value);
@@ -0,0 +1,167 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::config::telemetry::{SyslogAddress, SyslogTracer};
+use ahash::AHashSet;
+use mail_parser::DateTime;
+use std::io::{self, Write};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpStream, UdpSocket},
+};
+use trc::{Event, EventDetails, Level, TelemetryEvent, ipc::subscriber::SubscriberBuilder};
+
+#[cfg(unix)]
+use tokio::net::UnixDatagram;
+
+const APP_NAME: &str = "stalwart";
+const MAX_MSGID_LEN: usize = 32;
+// Private enterprise number reserved for documentation (RFC 5612)
+const SD_ID: &str = "trc@32473";
+
+pub(crate) fn spawn_syslog_tracer(builder: SubscriberBuilder, settings: SyslogTracer) {
+    let (_, mut rx) = builder.register();
+    tokio::spawn(async move {
+        let mut sink = SyslogSink::default();
+        let mut buf = Vec::with_capacity(512);
+
+        while let Some(events) = rx.recv().await {
+            for event in events {
+                buf.clear();
+                settings.write_message(&mut buf, &event);
+
+                if let Err(err) = sink.send(&settings.address, &buf).await {
+                    trc::event!(
+                        Telemetry(TelemetryEvent::LogError),
+                        Details = "Failed to send event to syslog",
+                        Reason = err.to_string()
+                    );
+                }
+            }
+        }
+    });
+}
+
+impl SyslogTracer {
+    // Formats an event as an RFC 5424 message, event keys are mapped to structured data parameters
+    fn write_message(&self, buf: &mut Vec<u8>, event: &Event<EventDetails>) {
+        let severity = match event.inner.level {
+            Level::Error => 3,
+            Level::Warn => 4,
+            Level::Info => 5,
+            Level::Debug => 6,
+            Level::Trace | Level::Disable => 7,
+        };
+        let hostname = if !self.hostname.is_empty() {
+            self.hostname.as_str()
+        } else {
+            "-"
+        };
+        let msg_id = event.inner.typ.as_str();
+        let _ = write!(
+            buf,
+            "<{}>1 {} {hostname} {APP_NAME} {} {} ",
+            (self.facility as u32) * 8 + severity,
+            DateTime::from_timestamp(event.inner.timestamp as i64).to_rfc3339(),
+            std::process::id(),
+            msg_id.get(..MAX_MSGID_LEN).unwrap_or(msg_id),
+        );
+
+        let mut seen_keys = AHashSet::new();
+        for (key, value) in &event.keys {
+            if seen_keys.insert(*key) {
+                if seen_keys.len() == 1 {
+                    let _ = write!(buf, "[{SD_ID}");
+                }
+                let _ = write!(buf, " {}=\"", key.as_str());
+                let value = value.to_string();
+                for ch in value.chars() {
+                    if matches!(ch, '"' | '\\' | ']') {
+                        buf.push(b'\\');
+                    }
+                    let _ = write!(buf, "{ch}");
+                }
+                buf.push(b'"');
+            }
+        }
+        if !seen_keys.is_empty() {
+            buf.push(b']');
+        } else {
+            buf.push(b'-');
+        }
+
+        let _ = write!(buf, " {}", event.inner.typ.description());
+    }
+}
+
+#[derive(Default)]
+struct SyslogSink {
+    #[cfg(unix)]
+    unix: Option<UnixDatagram>,
+    udp: Option<UdpSocket>,
+    tcp: Option<TcpStream>,
+}
+
+impl SyslogSink {
+    // Sockets are dropped on failure and reopened on the next event
+    async fn send(&mut self, address: &SyslogAddress, message: &[u8]) -> io::Result<()> {
+        match address {
+            #[cfg(unix)]
+            SyslogAddress::Unix(path) => {
+                let socket = match self.unix.take() {
+                    Some(socket) => socket,
+                    None => UnixDatagram::unbound()?,
+                };
+                socket.send_to(message, path).await?;
+                self.unix = Some(socket);
+            }
+            #[cfg(not(unix))]
+            SyslogAddress::Unix(_) => {
+                return Err(io::Error::other(
+                    "Unix sockets are only available on Unix systems",
+                ));
+            }
+            SyslogAddress::Udp(address) => {
+                let socket = match self.udp.take() {
+                    Some(socket) => socket,
+                    None => {
+                        let remote_addr = tokio::net::lookup_host(address.as_str())
+                            .await?
+                            .next()
+                            .ok_or_else(|| {
+                            io::Error::new(io::ErrorKind::NotFound, "Failed to resolve address")
+                        })?;
+                        let socket = UdpSocket::bind(if remote_addr.is_ipv4() {
+                            "0.0.0.0:0"
+                        } else {
+                            "[::]:0"
+                        })
+                        .await?;
+                        socket.connect(remote_addr).await?;
+                        socket
+                    }
+                };
+                socket.send(message).await?;
+                self.udp = Some(socket);
+            }
+            SyslogAddress::Tcp(address) => {
+                let mut stream = match self.tcp.take() {
+                    Some(stream) => stream,
+                    None => TcpStream::connect(address.as_str()).await?,
+                };
+
+                // Octet-counting framing (RFC 6587)
+                let mut frame = Vec::with_capacity(message.len() + 8);
+                let _ = write!(frame, "{} ", message.len());
+                frame.extend_from_slice(message);
+                stream.write_all(&frame).await?;
+                self.tcp = Some(stream);
+            }
+        }
+
+        Ok(())
+    }
+}
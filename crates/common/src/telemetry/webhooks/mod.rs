@@ -4,9 +4,12 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use crate::{LONG_1Y_SLUMBER, config::telemetry::WebhookTracer};
-use base64::{Engine, engine::general_purpose::STANDARD};
+use crate::{
+    LONG_1Y_SLUMBER,
+    config::telemetry::{WebhookTemplateVariable, WebhookTracer},
+};
 use aws_lc_rs::hmac;
+use base64::{Engine, engine::general_purpose::STANDARD};
 use serde::Serialize;
 use std::{
     sync::{
@@ -18,28 +21,32 @@ use std::{
 use store::write::now;
 use tokio::sync::mpsc;
 use trc::{
-    Event, EventDetails, ServerEvent, TelemetryEvent,
+    Event, EventDetails, TelemetryEvent,
     ipc::subscriber::{EventBatch, SubscriberBuilder},
     serializers::json::JsonEventSerializer,
 };
+use utils::template::Variables;
 
 pub(crate) fn spawn_webhook_tracer(builder: SubscriberBuilder, settings: WebhookTracer) {
-    let (tx, mut rx) = builder.register();
+    let (_, mut rx) = builder.register();
     tokio::spawn(async move {
         let settings = Arc::new(settings);
         let mut wakeup_time = LONG_1Y_SLUMBER;
         let discard_after = settings.discard_after.as_secs();
         let mut pending_events = Vec::new();
+        let mut pending_retries: Vec<PendingRetry> = Vec::new();
         let mut next_delivery = Instant::now();
         let in_flight = Arc::new(AtomicBool::new(false));
+        let (retry_tx, mut retry_rx) = mpsc::unbounded_channel();
 
         loop {
-            // Wait for the next event or timeout
-            let event_or_timeout = tokio::time::timeout(wakeup_time, rx.recv()).await;
-            let now = now();
-
-            match event_or_timeout {
-                Ok(Some(events)) => {
+            // Wait for the next event, a failed delivery or timeout
+            tokio::select! {
+                events = rx.recv() => {
+                    let Some(events) = events else {
+                        break;
+                    };
+                    let now = now();
                     let mut discard_count = 0;
                     for event in events {
                         if now.saturating_sub(event.inner.timestamp) < discard_after {
@@ -57,37 +64,61 @@ pub(crate) fn spawn_webhook_tracer(builder: SubscriberBuilder, settings: Webhook
                         );
                     }
                 }
-                Ok(None) => {
-                    break;
+                retry = retry_rx.recv() => {
+                    if let Some(retry) = retry {
+                        pending_retries.push(retry);
+                    }
                 }
-                Err(_) => (),
+                _ = tokio::time::sleep(wakeup_time) => {}
             }
 
-            // Process events
-            let mut next_retry = None;
+            // Process events, retries that are due are delivered first
             let now = Instant::now();
-            if next_delivery <= now {
-                if !pending_events.is_empty() {
+            if next_delivery <= now && !in_flight.load(Ordering::Relaxed) {
+                if let Some(pos) = pending_retries
+                    .iter()
+                    .position(|retry| retry.retry_at <= now)
+                {
                     next_delivery = now + settings.throttle;
-                    if !in_flight.load(Ordering::Relaxed) {
-                        spawn_webhook_handler(
-                            settings.clone(),
-                            in_flight.clone(),
-                            std::mem::take(&mut pending_events),
-                            tx.clone(),
-                        );
-                    }
-                }
-            } else if !pending_events.is_empty() {
-                // Retry later
-                let this_retry = next_delivery - now;
-                match next_retry {
-                    Some(next_retry) if this_retry >= next_retry => {}
-                    _ => {
-                        next_retry = Some(this_retry);
-                    }
+                    spawn_webhook_handler(
+                        settings.clone(),
+                        in_flight.clone(),
+                        retry_tx.clone(),
+                        Delivery::Retry(pending_retries.swap_remove(pos).payload),
+                    );
+                } else if !pending_events.is_empty() {
+                    next_delivery = now + settings.throttle;
+                    let events = if pending_events.len() > settings.max_batch_size {
+                        pending_events.drain(..settings.max_batch_size).collect()
+                    } else {
+                        std::mem::take(&mut pending_events)
+                    };
+                    spawn_webhook_handler(
+                        settings.clone(),
+                        in_flight.clone(),
+                        retry_tx.clone(),
+                        Delivery::Events(events),
+                    );
                 }
             }
+
+            // Wake up when the next delivery can be attempted
+            let mut next_retry = None;
+            if !pending_events.is_empty() {
+                next_retry = Some(if next_delivery > now {
+                    next_delivery - now
+                } else {
+                    settings.throttle
+                });
+            }
+            if let Some(retry_at) = pending_retries.iter().map(|retry| retry.retry_at).min() {
+                let wait = retry_at
+                    .max(next_delivery)
+                    .checked_duration_since(now)
+                    .filter(|wait| !wait.is_zero())
+                    .unwrap_or(settings.throttle);
+                next_retry = Some(next_retry.map_or(wait, |next_retry| next_retry.min(wait)));
+            }
             wakeup_time = next_retry.unwrap_or(LONG_1Y_SLUMBER);
         }
     });
@@ -98,42 +129,164 @@ struct EventWrapper {
     events: JsonEventSerializer<Vec<Arc<Event<EventDetails>>>>,
 }
 
+struct WebhookPayload {
+    body: String,
+    total: usize,
+    attempt: u32,
+}
+
+struct PendingRetry {
+    payload: WebhookPayload,
+    retry_at: Instant,
+}
+
+enum Delivery {
+    Events(EventBatch),
+    Retry(WebhookPayload),
+}
+
 fn spawn_webhook_handler(
     settings: Arc<WebhookTracer>,
     in_flight: Arc<AtomicBool>,
-    events: EventBatch,
-    webhook_tx: mpsc::Sender<EventBatch>,
+    retry_tx: mpsc::UnboundedSender<PendingRetry>,
+    delivery: Delivery,
 ) {
+    in_flight.store(true, Ordering::Relaxed);
     tokio::spawn(async move {
-        in_flight.store(true, Ordering::Relaxed);
-        let wrapper = EventWrapper {
-            events: JsonEventSerializer::new(events).with_id().with_spans(),
+        let payload = match delivery {
+            Delivery::Events(events) => match build_webhook_payload(&settings, events) {
+                Ok((body, total)) => WebhookPayload {
+                    body,
+                    total,
+                    attempt: 0,
+                },
+                Err(err) => {
+                    in_flight.store(false, Ordering::Relaxed);
+                    trc::event!(Telemetry(TelemetryEvent::WebhookError), Details = err);
+                    return;
+                }
+            },
+            Delivery::Retry(payload) => payload,
         };
 
-        if let Err(err) = post_webhook_events(&settings, &wrapper).await {
+        let result = post_webhook_events(&settings, &settings.url, &payload.body).await;
+        in_flight.store(false, Ordering::Relaxed);
+
+        if let Err(err) = result {
             trc::event!(Telemetry(TelemetryEvent::WebhookError), Details = err);
 
-            if webhook_tx.send(wrapper.events.into_inner()).await.is_err() {
+            if payload.attempt < settings.max_retries {
+                // Exponential backoff, never waiting longer than the discard period
+                let backoff = settings
+                    .retry_backoff
+                    .saturating_mul(1 << payload.attempt.min(16))
+                    .min(settings.discard_after);
+                let _ = retry_tx.send(PendingRetry {
+                    payload: WebhookPayload {
+                        attempt: payload.attempt + 1,
+                        ..payload
+                    },
+                    retry_at: Instant::now() + backoff,
+                });
+            } else {
+                dead_letter_webhook_events(&settings, &payload).await;
+            }
+        }
+    });
+}
+
+async fn dead_letter_webhook_events(settings: &WebhookTracer, payload: &WebhookPayload) {
+    // Retries exhausted, hand the batch over to the dead-letter endpoint
+    if let Some(dead_letter_url) = &settings.dead_letter_url {
+        match post_webhook_events(settings, dead_letter_url, &payload.body).await {
+            Ok(_) => {
                 trc::event!(
-                    Server(ServerEvent::ThreadError),
-                    Details = "Failed to send failed webhook events back to main thread",
-                    CausedBy = trc::location!()
+                    Telemetry(TelemetryEvent::WebhookError),
+                    Details = "Sent undeliverable events to dead-letter endpoint",
+                    Url = dead_letter_url.to_string(),
+                    Total = payload.total
+                );
+            }
+            Err(err) => {
+                trc::event!(
+                    Telemetry(TelemetryEvent::WebhookError),
+                    Details = "Discarded undeliverable events",
+                    Reason = err,
+                    Total = payload.total
                 );
             }
         }
+    } else {
+        trc::event!(
+            Telemetry(TelemetryEvent::WebhookError),
+            Details = "Discarded undeliverable events",
+            Total = payload.total
+        );
+    }
+}
 
-        in_flight.store(false, Ordering::Relaxed);
-    });
+fn build_webhook_payload(
+    settings: &WebhookTracer,
+    events: EventBatch,
+) -> Result<(String, usize), String> {
+    let total = events.len();
+
+    let Some(template) = &settings.template else {
+        let wrapper = EventWrapper {
+            events: JsonEventSerializer::new(events).with_id().with_spans(),
+        };
+
+        return serde_json::to_string(&wrapper)
+            .map(|body| (body, total))
+            .map_err(|err| format!("Failed to serialize events: {}", err));
+    };
+
+    // Template variables are expanded to JSON values
+    let mut entries = Vec::with_capacity(total);
+    for (idx, event) in events.iter().enumerate() {
+        let value = serde_json::to_value(
+            JsonEventSerializer::new(event)
+                .with_id()
+                .with_spans()
+                .with_description(),
+        )
+        .map_err(|err| format!("Failed to serialize event: {}", err))?;
+
+        let mut entry = Vec::with_capacity(9);
+        for (variable, field) in [
+            (WebhookTemplateVariable::Id, "id"),
+            (WebhookTemplateVariable::Type, "type"),
+            (WebhookTemplateVariable::Description, "text"),
+            (WebhookTemplateVariable::CreatedAt, "createdAt"),
+            (WebhookTemplateVariable::Data, "data"),
+        ] {
+            if let Some(value) = value.get(field) {
+                entry.push((variable, value.to_string()));
+            }
+        }
+        entry.push((
+            WebhookTemplateVariable::Level,
+            format!("\"{}\"", event.inner.level.as_str().to_ascii_lowercase()),
+        ));
+        entry.push((WebhookTemplateVariable::Event, value.to_string()));
+        if idx + 1 < total {
+            entry.push((WebhookTemplateVariable::Comma, ",".to_string()));
+        }
+        entries.push(entry);
+    }
+
+    let mut variables = Variables::new();
+    variables.insert_single(WebhookTemplateVariable::Total, total.to_string());
+    variables.insert_block(WebhookTemplateVariable::Events, entries);
+
+    Ok((template.eval(&variables), total))
 }
 
 async fn post_webhook_events(
     settings: &WebhookTracer,
-    events: &EventWrapper,
+    url: &str,
+    body: &str,
 ) -> Result<(), String> {
-    // Serialize body
-    let body = serde_json::to_string(events)
-        .map_err(|err| format!("Failed to serialize events: {}", err))?;
-
     // Add HMAC-SHA256 signature
     let mut headers = settings.headers.clone();
     if !settings.key.is_empty() {
@@ -152,19 +305,19 @@ async fn post_webhook_events(
         .danger_accept_invalid_certs(settings.tls_allow_invalid_certs)
         .build()
         .map_err(|err| format!("Failed to create HTTP client: {}", err))?
-        .post(&settings.url)
+        .post(url)
         .headers(headers)
-        .body(body)
+        .body(body.to_string())
         .send()
         .await
-        .map_err(|err| format!("Webhook request to {} failed: {err}", settings.url))?;
+        .map_err(|err| format!("Webhook request to {url} failed: {err}"))?;
 
     if response.status().is_success() {
         Ok(())
     } else {
         Err(format!(
             "Webhook request to {} failed with code {}: {}",
-            settings.url,
+            url,
             response.status().as_u16(),
             response.status().canonical_reason().unwrap_or("Unknown")
         ))
@@ -25,7 +25,7 @@ pub struct KafkaPubSub {
 }
 
 impl KafkaPubSub {
-    pub async fn open(config: KafkaCoordinator) -> Result<Coordinator, String> {
+    pub async fn open(config: KafkaCoordinator, node_id: u16) -> Result<Coordinator, String> {
         if config.brokers.is_empty() {
             return Err("No Kafka brokers specified".to_string());
         }
@@ -33,18 +33,22 @@ impl KafkaPubSub {
         let brokers = config.brokers.into_inner().join(",");
         let mut consumer_builder = ClientConfig::new();
 
+        // Every node needs its own consumer group to receive all events,
+        // offsets are committed only after an event has been processed.
         consumer_builder
-            .set("group.id", config.group_id)
+            .set("group.id", format!("{}-{node_id}", config.group_id))
             .set("bootstrap.servers", &brokers)
             .set("enable.partition.eof", "false")
             .set(
                 "session.timeout.ms",
                 config.timeout_session.as_millis().to_string(),
             )
-            .set("enable.auto.commit", "true");
+            .set("enable.auto.commit", "false");
 
         let producer = ClientConfig::new()
             .set("bootstrap.servers", brokers)
+            .set("acks", "all")
+            .set("enable.idempotence", "true")
             .set(
                 "message.timeout.ms",
                 config.timeout_message.as_millis().to_string(),
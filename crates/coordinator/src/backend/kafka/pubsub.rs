@@ -7,7 +7,7 @@
 use super::{CustomContext, KafkaPubSub, LoggingConsumer};
 use crate::{Msg, PubSubStream};
 use rdkafka::{
-    Message,
+    Message, Offset, TopicPartitionList,
     consumer::{CommitMode, Consumer, StreamConsumer},
     producer::FutureRecord,
 };
@@ -16,6 +16,7 @@ use trc::{ClusterEvent, Error, EventType};
 
 pub struct KafkaPubSubStream {
     subs: LoggingConsumer,
+    pending: Option<TopicPartitionList>,
 }
 
 impl KafkaPubSub {
@@ -43,14 +44,39 @@ impl KafkaPubSub {
             Error::new(EventType::Cluster(ClusterEvent::SubscriberError)).reason(err)
         })?;
 
-        Ok(PubSubStream::Kafka(KafkaPubSubStream { subs }))
+        Ok(PubSubStream::Kafka(KafkaPubSubStream {
+            subs,
+            pending: None,
+        }))
     }
 }
 
 impl KafkaPubSubStream {
+    // The offset of a message is committed once the caller asks for the next one
     pub async fn next(&mut self) -> Option<Msg> {
+        if let Some(offsets) = self.pending.take()
+            && let Err(err) = self.subs.commit(&offsets, CommitMode::Async)
+        {
+            trc::event!(
+                Cluster(ClusterEvent::SubscriberError),
+                Details = "Failed to commit offset",
+                Reason = err.to_string()
+            );
+        }
+
         let msg = self.subs.recv().await.ok()?;
-        let _ = self.subs.commit_message(&msg, CommitMode::Async);
+        let mut offsets = TopicPartitionList::new();
+        if offsets
+            .add_partition_offset(
+                msg.topic(),
+                msg.partition(),
+                Offset::Offset(msg.offset() + 1),
+            )
+            .is_ok()
+        {
+            self.pending = Some(offsets);
+        }
+
         Msg::Kafka(msg.payload().unwrap_or_default().to_vec()).into()
     }
 }
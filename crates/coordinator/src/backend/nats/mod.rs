@@ -4,21 +4,32 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use crate::Coordinator;
-use async_nats::Client;
+use async_nats::{Client, jetstream};
 use registry::schema::structs::NatsCoordinator;
+use tokio::sync::OnceCell;
 
 pub mod pubsub;
 
 #[derive(Debug)]
 pub struct NatsPubSub {
     client: Client,
+    jet_stream: Option<NatsJetStream>,
+}
+
+#[derive(Debug)]
+struct NatsJetStream {
+    context: jetstream::Context,
+    stream: OnceCell<jetstream::stream::Stream>,
+    stream_name: String,
+    consumer_name: String,
+    max_age: Duration,
 }
 
 impl NatsPubSub {
-    pub async fn open(config: NatsCoordinator) -> Result<Coordinator, String> {
+    pub async fn open(config: NatsCoordinator, node_id: u16) -> Result<Coordinator, String> {
         if config.addresses.is_empty() {
             return Err("No Nats addresses specified".to_string());
         }
@@ -47,9 +58,22 @@ impl NatsPubSub {
             opts = opts.token(credentials);
         }
 
-        async_nats::connect_with_options(config.addresses.into_inner(), opts)
+        let client = async_nats::connect_with_options(config.addresses.into_inner(), opts)
             .await
-            .map(|client| Coordinator::Nats(Arc::new(NatsPubSub { client })))
-            .map_err(|err| format!("Failed to connect to Nats: {}", err))
+            .map_err(|err| format!("Failed to connect to Nats: {}", err))?;
+
+        // Each node reads the stream through its own durable consumer
+        let jet_stream = config.use_jet_stream.then(|| NatsJetStream {
+            context: jetstream::new(client.clone()),
+            stream: OnceCell::new(),
+            consumer_name: format!("{}-{node_id}", config.stream_name),
+            stream_name: config.stream_name,
+            max_age: config.stream_max_age.into_inner(),
+        });
+
+        Ok(Coordinator::Nats(Arc::new(NatsPubSub {
+            client,
+            jet_stream,
+        })))
     }
 }
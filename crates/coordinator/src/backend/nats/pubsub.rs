@@ -4,8 +4,13 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::NatsPubSub;
+use super::{NatsJetStream, NatsPubSub};
 use crate::{Msg, PubSubStream};
+use async_nats::jetstream::{
+    self,
+    consumer::{DeliverPolicy, pull},
+    stream::Stream,
+};
 use futures::StreamExt;
 use trc::{ClusterEvent, Error, EventType};
 
@@ -13,22 +18,97 @@ pub struct NatsPubSubStream {
     subs: async_nats::Subscriber,
 }
 
+pub struct NatsJetStreamStream {
+    messages: pull::Stream,
+    pending: Option<jetstream::Message>,
+}
+
 impl NatsPubSub {
     pub async fn publish(&self, topic: &'static str, message: Vec<u8>) -> trc::Result<()> {
-        self.client
-            .publish(topic, message.into())
-            .await
-            .map_err(|err| Error::new(EventType::Cluster(ClusterEvent::PublisherError)).reason(err))
+        if let Some(jet_stream) = &self.jet_stream {
+            jet_stream
+                .stream(topic, ClusterEvent::PublisherError)
+                .await?;
+
+            // Wait for the server to acknowledge that the event was persisted
+            jet_stream
+                .context
+                .publish(topic, message.into())
+                .await
+                .map_err(|err| {
+                    Error::new(EventType::Cluster(ClusterEvent::PublisherError)).reason(err)
+                })?
+                .await
+                .map(|_| ())
+                .map_err(|err| {
+                    Error::new(EventType::Cluster(ClusterEvent::PublisherError)).reason(err)
+                })
+        } else {
+            self.client
+                .publish(topic, message.into())
+                .await
+                .map_err(|err| {
+                    Error::new(EventType::Cluster(ClusterEvent::PublisherError)).reason(err)
+                })
+        }
     }
 
     pub async fn subscribe(&self, topic: &'static str) -> trc::Result<PubSubStream> {
-        self.client
-            .subscribe(topic)
-            .await
-            .map(|subs| PubSubStream::Nats(NatsPubSubStream { subs }))
-            .map_err(|err| {
-                Error::new(EventType::Cluster(ClusterEvent::SubscriberError)).reason(err)
+        if let Some(jet_stream) = &self.jet_stream {
+            jet_stream
+                .stream(topic, ClusterEvent::SubscriberError)
+                .await?
+                .get_or_create_consumer(
+                    &jet_stream.consumer_name,
+                    pull::Config {
+                        durable_name: jet_stream.consumer_name.clone().into(),
+                        deliver_policy: DeliverPolicy::New,
+                        inactive_threshold: jet_stream.max_age,
+                        ..Default::default()
+                    },
+                )
+                .await
+                .map_err(|err| {
+                    Error::new(EventType::Cluster(ClusterEvent::SubscriberError)).reason(err)
+                })?
+                .messages()
+                .await
+                .map(|messages| {
+                    PubSubStream::NatsJetStream(NatsJetStreamStream {
+                        messages,
+                        pending: None,
+                    })
+                })
+                .map_err(|err| {
+                    Error::new(EventType::Cluster(ClusterEvent::SubscriberError)).reason(err)
+                })
+        } else {
+            self.client
+                .subscribe(topic)
+                .await
+                .map(|subs| PubSubStream::Nats(NatsPubSubStream { subs }))
+                .map_err(|err| {
+                    Error::new(EventType::Cluster(ClusterEvent::SubscriberError)).reason(err)
+                })
+        }
+    }
+}
+
+impl NatsJetStream {
+    async fn stream(&self, topic: &'static str, event: ClusterEvent) -> trc::Result<&Stream> {
+        self.stream
+            .get_or_try_init(|| async {
+                self.context
+                    .get_or_create_stream(jetstream::stream::Config {
+                        name: self.stream_name.clone(),
+                        subjects: vec![topic.to_string()],
+                        max_age: self.max_age,
+                        ..Default::default()
+                    })
+                    .await
+                    .map_err(|err| Error::new(EventType::Cluster(event)).reason(err))
             })
+            .await
     }
 }
 
@@ -37,3 +117,36 @@ impl NatsPubSubStream {
         self.subs.next().await.map(Msg::Nats)
     }
 }
+
+impl NatsJetStreamStream {
+    // Messages are acknowledged once the caller asks for the next one,
+    // unacknowledged messages are redelivered after a reconnect.
+    pub async fn next(&mut self) -> Option<Msg> {
+        if let Some(message) = self.pending.take()
+            && let Err(err) = message.ack().await
+        {
+            trc::event!(
+                Cluster(ClusterEvent::SubscriberError),
+                Details = "Failed to acknowledge message",
+                Reason = err.to_string()
+            );
+        }
+
+        loop {
+            match self.messages.next().await? {
+                Ok(message) => {
+                    let msg = Msg::Nats(message.message.clone());
+                    self.pending = Some(message);
+                    return Some(msg);
+                }
+                Err(err) => {
+                    trc::event!(
+                        Cluster(ClusterEvent::SubscriberError),
+                        Details = "Failed to receive message",
+                        Reason = err.to_string()
+                    );
+                }
+            }
+        }
+    }
+}
@@ -26,11 +26,11 @@ impl Coordinator {
             }
             #[cfg(feature = "kafka")]
             structs::Coordinator::Kafka(kafka_coordinator) => {
-                crate::backend::kafka::KafkaPubSub::open(kafka_coordinator).await
+                crate::backend::kafka::KafkaPubSub::open(kafka_coordinator, bp.node_id()).await
             }
             #[cfg(feature = "nats")]
             structs::Coordinator::Nats(nats_coordinator) => {
-                crate::backend::nats::NatsPubSub::open(nats_coordinator).await
+                crate::backend::nats::NatsPubSub::open(nats_coordinator, bp.node_id()).await
             }
             #[cfg(feature = "zenoh")]
             structs::Coordinator::Zenoh(zenoh_coordinator) => {
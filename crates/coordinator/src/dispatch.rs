@@ -54,6 +54,8 @@ impl PubSubStream {
             PubSubStream::RedisCluster(stream) => stream.next().await,
             #[cfg(feature = "nats")]
             PubSubStream::Nats(stream) => stream.next().await,
+            #[cfg(feature = "nats")]
+            PubSubStream::NatsJetStream(stream) => stream.next().await,
             #[cfg(feature = "zenoh")]
             PubSubStream::Zenoh(stream) => stream.next().await,
             #[cfg(feature = "kafka")]
@@ -34,6 +34,8 @@ pub enum PubSubStream {
     RedisCluster(crate::backend::redis::pubsub::RedisClusterPubSubStream),
     #[cfg(feature = "nats")]
     Nats(crate::backend::nats::pubsub::NatsPubSubStream),
+    #[cfg(feature = "nats")]
+    NatsJetStream(crate::backend::nats::pubsub::NatsJetStreamStream),
     #[cfg(feature = "zenoh")]
     Zenoh(crate::backend::zenoh::pubsub::ZenohPubSubStream),
     #[cfg(feature = "kafka")]
@@ -17,7 +17,7 @@ use calcard::common::timezone::Tz;
 use common::{DavName, Server, auth::AccessToken};
 use dav_proto::{Depth, RequestHeaders};
 use groupware::{
-    DestroyArchive,
+    DavCalendarResource, DestroyArchive,
     cache::GroupwareCache,
     calendar::{Calendar, CalendarEvent, CalendarPreferences, Timezone},
 };
@@ -210,6 +210,7 @@ impl CalendarCopyMoveRequestHandler for Server {
                                     Acl::ReadItems
                                 },
                             ))
+                        || to_resources.is_calendar_subscription(to_resource.document_id())
                     {
                         return Err(DavError::Code(StatusCode::FORBIDDEN));
                     }
@@ -252,6 +253,8 @@ impl CalendarCopyMoveRequestHandler for Server {
                                 to_calendar_id,
                                 Acl::RemoveItems,
                             ))
+                        || (is_move && from_resources.is_calendar_subscription(from_calendar_id))
+                        || to_resources.is_calendar_subscription(to_calendar_id)
                     {
                         return Err(DavError::Code(StatusCode::FORBIDDEN));
                     }
@@ -316,6 +319,8 @@ impl CalendarCopyMoveRequestHandler for Server {
                             to_calendar_id,
                             Acl::AddItems,
                         ))
+                    || (is_move && from_resources.is_calendar_subscription(from_calendar_id))
+                    || to_resources.is_calendar_subscription(to_calendar_id)
                 {
                     return Err(DavError::Code(StatusCode::FORBIDDEN));
                 }
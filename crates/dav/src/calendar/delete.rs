@@ -15,7 +15,7 @@ use crate::{
 use common::{Server, auth::AccessToken, sharing::EffectiveAcl};
 use dav_proto::RequestHeaders;
 use groupware::{
-    DestroyArchive,
+    DavCalendarResource, DestroyArchive,
     cache::GroupwareCache,
     calendar::{Calendar, CalendarEvent},
 };
@@ -179,8 +179,9 @@ impl CalendarDeleteRequestHandler for Server {
         } else {
             // Validate ACL
             let calendar_id = delete_resource.parent_id().unwrap();
-            if !access_token.is_member(account_id)
-                && !resources.has_access_to_container(access_token, calendar_id, Acl::RemoveItems)
+            if (!access_token.is_member(account_id)
+                && !resources.has_access_to_container(access_token, calendar_id, Acl::RemoveItems))
+                || resources.is_calendar_subscription(calendar_id)
             {
                 return Err(DavError::Code(StatusCode::FORBIDDEN));
             }
@@ -16,9 +16,13 @@ use calcard::{
         ICalendarProperty, ICalendarTransparency, ICalendarValue,
     },
 };
-use common::{DavResourcePath, DavResources, PROD_ID, Server, auth::AccessToken};
+use chrono::{DateTime, NaiveDateTime};
+use common::{DavResources, PROD_ID, Server, auth::AccessToken};
 use dav_proto::{RequestHeaders, schema::request::FreeBusyQuery};
-use groupware::{cache::GroupwareCache, calendar::CalendarEvent};
+use groupware::{
+    cache::GroupwareCache,
+    calendar::{CALENDAR_AVAILABILITY_NONE, CalendarEvent},
+};
 use http_proto::HttpResponse;
 use hyper::StatusCode;
 use std::str::FromStr;
@@ -27,7 +31,7 @@ use store::{
     write::{AlignedBytes, Archive},
 };
 use store::{
-    ahash::AHashMap,
+    ahash::{AHashMap, AHashSet},
     write::{now, serialize::rkyv_deserialize},
 };
 use trc::AddContext;
@@ -36,6 +40,7 @@ use types::{
     acl::Acl,
     collection::{Collection, SyncCollection},
 };
+use utils::{sanitize_email, url_params::UrlParams};
 
 pub(crate) trait CalendarFreebusyRequestHandler: Sync + Send {
     fn handle_calendar_freebusy_request(
@@ -47,14 +52,23 @@ pub(crate) trait CalendarFreebusyRequestHandler: Sync + Send {
 
     fn build_freebusy_object(
         &self,
-        access_token: &AccessToken,
+        access_token: Option<&AccessToken>,
         request: FreeBusyQuery,
         resources: &DavResources,
         account_id: u32,
-        resource: DavResourcePath<'_>,
+        calendar_ids: &[u32],
     ) -> impl Future<Output = crate::Result<ICalendar>> + Send;
 }
 
+pub trait CalendarFreebusyHttpHandler: Sync + Send {
+    fn handle_http_freebusy_request(
+        &self,
+        access_token: Option<&AccessToken>,
+        account: &str,
+        query: Option<&str>,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
 impl CalendarFreebusyRequestHandler for Server {
     async fn handle_calendar_freebusy_request(
         &self,
@@ -87,68 +101,54 @@ impl CalendarFreebusyRequestHandler for Server {
             return Err(DavError::Code(StatusCode::METHOD_NOT_ALLOWED));
         }
 
-        self.build_freebusy_object(access_token, request, &resources, account_id, resource)
-            .await
-            .map(|ical| {
-                HttpResponse::new(StatusCode::OK)
-                    .with_content_type("text/calendar; charset=utf-8")
-                    .with_text_body(ical.to_string())
-            })
+        self.build_freebusy_object(
+            access_token.into(),
+            request,
+            &resources,
+            account_id,
+            &[resource.document_id()],
+        )
+        .await
+        .map(|ical| {
+            HttpResponse::new(StatusCode::OK)
+                .with_content_type("text/calendar; charset=utf-8")
+                .with_text_body(ical.to_string())
+        })
     }
 
     async fn build_freebusy_object(
         &self,
-        access_token: &AccessToken,
+        access_token: Option<&AccessToken>,
         request: FreeBusyQuery,
         resources: &DavResources,
         account_id: u32,
-        resource: DavResourcePath<'_>,
+        calendar_ids: &[u32],
     ) -> crate::Result<ICalendar> {
-        // Obtain shared ids
-        let shared_ids = if !access_token.is_member(account_id) {
-            resources
-                .shared_items(
+        // Obtain shared ids, anonymous requests have been authorized by the caller
+        let shared_ids = access_token
+            .filter(|access_token| !access_token.is_member(account_id))
+            .map(|access_token| {
+                resources.shared_items(
                     access_token,
                     [Acl::ReadItems, Acl::SchedulingReadFreeBusy],
                     false,
                 )
-                .into()
-        } else {
-            None
-        };
+            });
 
         // Build FreeBusy component
-        let default_tz = resource
-            .resource
-            .calendar_preferences(account_id)
+        let default_tz = calendar_ids
+            .first()
+            .and_then(|calendar_id| resources.container_resource_by_id(*calendar_id))
+            .and_then(|calendar| calendar.calendar_preferences(account_id))
             .map(|p| p.tz)
             .unwrap_or(Tz::UTC);
         let mut entries = Vec::with_capacity(6);
         if let Some(range) = request.range {
-            entries.push(ICalendarEntry {
-                name: ICalendarProperty::Dtstart,
-                params: vec![],
-                values: vec![ICalendarValue::PartialDateTime(Box::new(
-                    PartialDateTime::from_utc_timestamp(range.start),
-                ))],
-            });
-            entries.push(ICalendarEntry {
-                name: ICalendarProperty::Dtend,
-                params: vec![],
-                values: vec![ICalendarValue::PartialDateTime(Box::new(
-                    PartialDateTime::from_utc_timestamp(range.end),
-                ))],
-            });
-            entries.push(ICalendarEntry {
-                name: ICalendarProperty::Dtstamp,
-                params: vec![],
-                values: vec![ICalendarValue::PartialDateTime(Box::new(
-                    PartialDateTime::from_utc_timestamp(now() as i64),
-                ))],
-            });
+            entries.extend(freebusy_range_entries(&range));
 
-            let document_ids = resources
-                .children(resource.document_id())
+            let document_ids = calendar_ids
+                .iter()
+                .flat_map(|calendar_id| resources.children(*calendar_id))
                 .filter(|resource| {
                     shared_ids
                         .as_ref()
@@ -156,7 +156,7 @@ impl CalendarFreebusyRequestHandler for Server {
                         && is_resource_in_time_range(resource.resource, &range)
                 })
                 .map(|resource| resource.document_id())
-                .collect::<Vec<_>>();
+                .collect::<AHashSet<_>>();
 
             let mut fb_entries: AHashMap<ICalendarFreeBusyType, Vec<(i64, i64)>> =
                 AHashMap::with_capacity(document_ids.len());
@@ -281,35 +281,157 @@ impl CalendarFreebusyRequestHandler for Server {
             }
         }
 
-        // Build ICalendar
-        Ok(ICalendar {
-            components: vec![
-                ICalendarComponent {
-                    component_type: ICalendarComponentType::VCalendar,
-                    entries: vec![
-                        ICalendarEntry {
-                            name: ICalendarProperty::Version,
-                            params: vec![],
-                            values: vec![ICalendarValue::Text("2.0".to_string())],
-                        },
-                        ICalendarEntry {
-                            name: ICalendarProperty::Prodid,
-                            params: vec![],
-                            values: vec![ICalendarValue::Text(PROD_ID.to_string())],
-                        },
-                    ],
-                    component_ids: vec![1],
-                },
-                ICalendarComponent {
-                    component_type: ICalendarComponentType::VFreebusy,
-                    entries,
-                    component_ids: vec![],
+        Ok(freebusy_calendar(entries))
+    }
+}
+
+impl CalendarFreebusyHttpHandler for Server {
+    async fn handle_http_freebusy_request(
+        &self,
+        access_token: Option<&AccessToken>,
+        account: &str,
+        query: Option<&str>,
+    ) -> trc::Result<HttpResponse> {
+        let account = percent_encoding::percent_decode_str(account)
+            .decode_utf8()
+            .ok()
+            .and_then(|account| sanitize_email(&account));
+        let Some(account_id) = (match account {
+            Some(account) => self.account_id_from_email(&account, true).await?,
+            None => None,
+        }) else {
+            return Ok(HttpResponse::new(StatusCode::NOT_FOUND));
+        };
+
+        // Parse the requested time range, which is capped to the configured maximum
+        let params = UrlParams::new(query);
+        let max_range = self.core.groupware.freebusy_max_range;
+        let start = match params.get("start") {
+            Some(start) => match parse_utc_timestamp(start) {
+                Some(start) => start,
+                None => return Ok(HttpResponse::new(StatusCode::BAD_REQUEST)),
+            },
+            None => now() as i64,
+        };
+        let end = match params.get("end") {
+            Some(end) => match parse_utc_timestamp(end) {
+                Some(end) if end > start => end.min(start + max_range),
+                _ => return Ok(HttpResponse::new(StatusCode::BAD_REQUEST)),
+            },
+            None => start + max_range,
+        };
+        let range = TimeRange::new(start, end);
+
+        // Aggregate all calendars included in the account's availability
+        let resources = self
+            .fetch_dav_resources(
+                access_token.map_or(account_id, |token| token.account_id()),
+                account_id,
+                SyncCollection::Calendar,
+            )
+            .await
+            .caused_by(trc::location!())?;
+        let calendar_ids = resources
+            .resources
+            .iter()
+            .filter(|resource| {
+                resource
+                    .calendar_preferences(account_id)
+                    .is_some_and(|prefs| prefs.flags & CALENDAR_AVAILABILITY_NONE == 0)
+            })
+            .map(|resource| resource.document_id)
+            .collect::<Vec<_>>();
+
+        match self
+            .build_freebusy_object(
+                access_token,
+                FreeBusyQuery {
+                    range: range.into(),
                 },
-            ],
-        })
+                &resources,
+                account_id,
+                &calendar_ids,
+            )
+            .await
+        {
+            Ok(ical) => Ok(freebusy_response(ical)),
+            Err(DavError::Internal(err)) => Err(err),
+            Err(_) => Ok(HttpResponse::new(StatusCode::BAD_REQUEST)),
+        }
+    }
+}
+
+fn freebusy_range_entries(range: &TimeRange) -> [ICalendarEntry; 3] {
+    [
+        ICalendarEntry {
+            name: ICalendarProperty::Dtstart,
+            params: vec![],
+            values: vec![ICalendarValue::PartialDateTime(Box::new(
+                PartialDateTime::from_utc_timestamp(range.start),
+            ))],
+        },
+        ICalendarEntry {
+            name: ICalendarProperty::Dtend,
+            params: vec![],
+            values: vec![ICalendarValue::PartialDateTime(Box::new(
+                PartialDateTime::from_utc_timestamp(range.end),
+            ))],
+        },
+        ICalendarEntry {
+            name: ICalendarProperty::Dtstamp,
+            params: vec![],
+            values: vec![ICalendarValue::PartialDateTime(Box::new(
+                PartialDateTime::from_utc_timestamp(now() as i64),
+            ))],
+        },
+    ]
+}
+
+fn freebusy_calendar(entries: Vec<ICalendarEntry>) -> ICalendar {
+    ICalendar {
+        components: vec![
+            ICalendarComponent {
+                component_type: ICalendarComponentType::VCalendar,
+                entries: vec![
+                    ICalendarEntry {
+                        name: ICalendarProperty::Version,
+                        params: vec![],
+                        values: vec![ICalendarValue::Text("2.0".to_string())],
+                    },
+                    ICalendarEntry {
+                        name: ICalendarProperty::Prodid,
+                        params: vec![],
+                        values: vec![ICalendarValue::Text(PROD_ID.to_string())],
+                    },
+                ],
+                component_ids: vec![1],
+            },
+            ICalendarComponent {
+                component_type: ICalendarComponentType::VFreebusy,
+                entries,
+                component_ids: vec![],
+            },
+        ],
     }
 }
 
+fn freebusy_response(ical: ICalendar) -> HttpResponse {
+    HttpResponse::new(StatusCode::OK)
+        .with_content_type("text/calendar; charset=utf-8")
+        .with_text_body(ical.to_string())
+        .with_no_store()
+}
+
+fn parse_utc_timestamp(value: &str) -> Option<i64> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.timestamp())
+        .or_else(|_| {
+            NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%SZ")
+                .map(|dt| dt.and_utc().timestamp())
+        })
+        .ok()
+}
+
 fn merge_intervals(mut intervals: Vec<(i64, i64)>) -> Vec<ICalendarValue> {
     if intervals.len() > 1 {
         intervals.sort_unstable_by_key(|a| a.0);
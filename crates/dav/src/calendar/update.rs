@@ -27,8 +27,12 @@ use dav_proto::{
     schema::{property::Rfc1123DateTime, response::CalCondition},
 };
 use groupware::{
+    DavCalendarResource,
     cache::GroupwareCache,
-    calendar::{CalendarEvent, CalendarEventData},
+    calendar::{
+        CalendarEvent, CalendarEventData,
+        booking::{BookingRejection, CalendarBooking},
+    },
     scheduling::{ItipMessages, event_create::itip_create, event_update::itip_update},
 };
 use http_proto::HttpResponse;
@@ -125,8 +129,9 @@ impl CalendarUpdateRequestHandler for Server {
             // Validate ACL
             let parent_id = resource.parent_id().unwrap();
             let document_id = resource.document_id();
-            if !access_token.is_member(account_id)
-                && !resources.has_access_to_container(access_token, parent_id, Acl::ModifyItems)
+            if (!access_token.is_member(account_id)
+                && !resources.has_access_to_container(access_token, parent_id, Acl::ModifyItems))
+                || resources.is_calendar_subscription(parent_id)
             {
                 return Err(DavError::Code(StatusCode::FORBIDDEN));
             }
@@ -219,6 +224,21 @@ impl CalendarUpdateRequestHandler for Server {
                 &mut next_email_alarm,
             );
 
+            // Rescheduled events must still comply with the calendar's booking policy
+            if new_event.data.event_range_start() != event.inner.data.event_range_start()
+                || new_event.data.event_range_end() != event.inner.data.event_range_end()
+            {
+                assert_booking_policy(
+                    self,
+                    access_token,
+                    account_id,
+                    parent_id,
+                    &new_event.data,
+                    Some(document_id),
+                )
+                .await?;
+            }
+
             // Scheduling
             let mut itip_messages = None;
             if self.core.groupware.itip_enabled
@@ -329,12 +349,13 @@ impl CalendarUpdateRequestHandler for Server {
             }
 
             // Validate ACL
-            if !access_token.is_member(account_id)
+            if (!access_token.is_member(account_id)
                 && !resources.has_access_to_container(
                     access_token,
                     parent.document_id(),
                     Acl::AddItems,
-                )
+                ))
+                || resources.is_calendar_subscription(parent.document_id())
             {
                 return Err(DavError::Code(StatusCode::FORBIDDEN));
             }
@@ -382,6 +403,17 @@ impl CalendarUpdateRequestHandler for Server {
                 ..Default::default()
             };
 
+            // Validate booking policy
+            assert_booking_policy(
+                self,
+                access_token,
+                account_id,
+                parent.document_id(),
+                &event.data,
+                None,
+            )
+            .await?;
+
             // Scheduling
             let mut itip_messages = None;
             if self.core.groupware.itip_enabled
@@ -461,6 +493,50 @@ impl CalendarUpdateRequestHandler for Server {
     }
 }
 
+async fn assert_booking_policy(
+    server: &Server,
+    access_token: &AccessToken,
+    account_id: u32,
+    calendar_id: u32,
+    event: &CalendarEventData,
+    document_id: Option<u32>,
+) -> crate::Result<()> {
+    let Some(policy) = server
+        .calendar_booking_policy(account_id, calendar_id)
+        .await
+        .caused_by(trc::location!())?
+    else {
+        return Ok(());
+    };
+
+    // Calendars that require approval only accept bookings from others through scheduling
+    let rejection = if policy.approval_required && !access_token.is_member(account_id) {
+        Some(BookingRejection::ApprovalRequired)
+    } else {
+        server
+            .check_booking_policy(account_id, calendar_id, &policy, event, document_id)
+            .await?
+    };
+
+    if let Some(rejection) = rejection {
+        Err(DavError::Condition(
+            DavErrorCondition::new(
+                StatusCode::PRECONDITION_FAILED,
+                match rejection {
+                    BookingRejection::TooSoon => CalCondition::MinDateTime,
+                    BookingRejection::TooFar => CalCondition::MaxDateTime,
+                    BookingRejection::DoubleBooked
+                    | BookingRejection::ApprovalRequired
+                    | BookingRejection::MaxDuration => CalCondition::ValidCalendarObjectResource,
+                },
+            )
+            .with_details(rejection.description()),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 fn validate_ical(ical: &ICalendar) -> crate::Result<&str> {
     // Validate UIDs
     let mut uids = HashSet::with_capacity(1);
@@ -18,7 +18,10 @@ use dav_proto::{Depth, RequestHeaders};
 use groupware::{
     DestroyArchive,
     cache::GroupwareCache,
-    contact::{AddressBook, AddressBookPreferences, ContactCard},
+    contact::{
+        AddressBook, AddressBookPreferences, ContactCard, DIRECTORY_ADDRESSBOOK_ID,
+        directory::{DirectoryAddressBook, is_directory_card},
+    },
 };
 use http_proto::HttpResponse;
 use hyper::StatusCode;
@@ -194,6 +197,8 @@ impl CardCopyMoveRequestHandler for Server {
                                     Acl::ReadItems
                                 },
                             ))
+                        || (is_move && from_resource.document_id() == DIRECTORY_ADDRESSBOOK_ID)
+                        || to_resource.document_id() == DIRECTORY_ADDRESSBOOK_ID
                     {
                         return Err(DavError::Code(StatusCode::FORBIDDEN));
                     }
@@ -236,6 +241,8 @@ impl CardCopyMoveRequestHandler for Server {
                                 to_addressbook_id,
                                 Acl::RemoveItems,
                             ))
+                        || (is_move && from_addressbook_id == DIRECTORY_ADDRESSBOOK_ID)
+                        || to_addressbook_id == DIRECTORY_ADDRESSBOOK_ID
                     {
                         return Err(DavError::Code(StatusCode::FORBIDDEN));
                     }
@@ -299,6 +306,8 @@ impl CardCopyMoveRequestHandler for Server {
                             to_addressbook_id,
                             Acl::AddItems,
                         ))
+                    || (is_move && from_addressbook_id == DIRECTORY_ADDRESSBOOK_ID)
+                    || to_addressbook_id == DIRECTORY_ADDRESSBOOK_ID
                 {
                     return Err(DavError::Code(StatusCode::FORBIDDEN));
                 }
@@ -358,7 +367,7 @@ impl CardCopyMoveRequestHandler for Server {
                 }
 
                 // Validate ACLs
-                if !access_token.is_member(from_account_id)
+                if (!access_token.is_member(from_account_id)
                     && !from_resources.has_access_to_container(
                         access_token,
                         from_resource.document_id(),
@@ -367,7 +376,8 @@ impl CardCopyMoveRequestHandler for Server {
                         } else {
                             Acl::ReadItems
                         },
-                    )
+                    ))
+                    || (is_move && from_resource.document_id() == DIRECTORY_ADDRESSBOOK_ID)
                 {
                     return Err(DavError::Code(StatusCode::FORBIDDEN));
                 }
@@ -449,12 +459,7 @@ async fn copy_card(
 ) -> crate::Result<HttpResponse> {
     // Fetch card
     let card_ = server
-        .store()
-        .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
-            from_account_id,
-            Collection::ContactCard,
-            from_document_id,
-        ))
+        .contact_archive(from_account_id, Collection::ContactCard, from_document_id)
         .await
         .caused_by(trc::location!())?
         .ok_or(DavError::Code(StatusCode::NOT_FOUND))?;
@@ -481,7 +486,7 @@ async fn copy_card(
     )
     .await?;
 
-    if from_account_id == to_account_id {
+    if from_account_id == to_account_id && !is_directory_card(from_document_id) {
         let mut new_card = card
             .deserialize::<ContactCard>()
             .caused_by(trc::location!())?;
@@ -797,12 +802,7 @@ async fn copy_container(
 ) -> crate::Result<HttpResponse> {
     // Fetch book
     let book_ = server
-        .store()
-        .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
-            from_account_id,
-            Collection::AddressBook,
-            from_document_id,
-        ))
+        .contact_archive(from_account_id, Collection::AddressBook, from_document_id)
         .await
         .caused_by(trc::location!())?
         .ok_or(DavError::Code(StatusCode::NOT_FOUND))?;
@@ -890,12 +890,11 @@ async fn copy_container(
     let mut required_space = 0;
     for from_child_document_id in from_children_ids {
         if let Some(card_) = server
-            .store()
-            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+            .contact_archive(
                 from_account_id,
                 Collection::ContactCard,
                 from_child_document_id,
-            ))
+            )
             .await?
         {
             let card = card_
@@ -925,7 +924,7 @@ async fn copy_container(
                 .deserialize::<ContactCard>()
                 .caused_by(trc::location!())?;
 
-            if from_account_id == to_account_id {
+            if from_account_id == to_account_id && !is_directory_card(from_child_document_id) {
                 if remove_source {
                     new_card
                         .names
@@ -975,7 +974,7 @@ async fn copy_container(
         }
     }
 
-    if from_account_id != to_account_id && required_space > 0 {
+    if required_space > 0 {
         server
             .has_available_quota(
                 server.account(to_account_id).await?.as_ref(),
@@ -17,7 +17,7 @@ use dav_proto::RequestHeaders;
 use groupware::{
     DestroyArchive,
     cache::GroupwareCache,
-    contact::{AddressBook, ContactCard},
+    contact::{AddressBook, ContactCard, DIRECTORY_ADDRESSBOOK_ID},
 };
 use http_proto::HttpResponse;
 use hyper::StatusCode;
@@ -91,12 +91,13 @@ impl CardDeleteRequestHandler for Server {
                 .caused_by(trc::location!())?;
 
             // Validate ACL
-            if !access_token.is_member(account_id)
+            if (!access_token.is_member(account_id)
                 && !book
                     .inner
                     .acls
                     .effective_acl(access_token)
-                    .contains_all([Acl::Delete, Acl::RemoveItems].into_iter())
+                    .contains_all([Acl::Delete, Acl::RemoveItems].into_iter()))
+                || document_id == DIRECTORY_ADDRESSBOOK_ID
             {
                 return Err(DavError::Code(StatusCode::FORBIDDEN));
             }
@@ -157,12 +158,13 @@ impl CardDeleteRequestHandler for Server {
         } else {
             // Validate ACL
             let addressbook_id = delete_resource.parent_id().unwrap();
-            if !access_token.is_member(account_id)
+            if (!access_token.is_member(account_id)
                 && !resources.has_access_to_container(
                     access_token,
                     addressbook_id,
                     Acl::RemoveItems,
-                )
+                ))
+                || addressbook_id == DIRECTORY_ADDRESSBOOK_ID
             {
                 return Err(DavError::Code(StatusCode::FORBIDDEN));
             }
@@ -14,13 +14,12 @@ use crate::{
 };
 use common::{Server, auth::AccessToken};
 use dav_proto::{RequestHeaders, schema::property::Rfc1123DateTime};
-use groupware::{cache::GroupwareCache, contact::ContactCard};
+use groupware::{
+    cache::GroupwareCache,
+    contact::{ContactCard, directory::DirectoryAddressBook},
+};
 use http_proto::HttpResponse;
 use hyper::StatusCode;
-use store::{
-    ValueKey,
-    write::{AlignedBytes, Archive},
-};
 use trc::AddContext;
 use types::{
     acl::Acl,
@@ -81,12 +80,7 @@ impl CardGetRequestHandler for Server {
 
         // Fetch card
         let card_ = self
-            .store()
-            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
-                account_id,
-                Collection::ContactCard,
-                resource.document_id(),
-            ))
+            .contact_archive(account_id, Collection::ContactCard, resource.document_id())
             .await
             .caused_by(trc::location!())?
             .ok_or(DavError::Code(StatusCode::NOT_FOUND))?;
@@ -24,7 +24,7 @@ use dav_proto::{
 };
 use groupware::{
     cache::GroupwareCache,
-    contact::{AddressBook, ContactCard},
+    contact::{AddressBook, ContactCard, DIRECTORY_ADDRESSBOOK_ID},
 };
 use http_proto::HttpResponse;
 use hyper::StatusCode;
@@ -102,6 +102,13 @@ impl CardPropPatchRequestHandler for Server {
             return Ok(HttpResponse::new(StatusCode::NO_CONTENT));
         }
 
+        // The global address list is read-only
+        if document_id == DIRECTORY_ADDRESSBOOK_ID
+            || resource.parent_id() == Some(DIRECTORY_ADDRESSBOOK_ID)
+        {
+            return Err(DavError::Code(StatusCode::FORBIDDEN));
+        }
+
         // Verify ACL
         if !access_token.is_member(account_id) {
             let (acl, document_id) = if resource.is_container() {
@@ -21,7 +21,10 @@ use dav_proto::{
     RequestHeaders, Return,
     schema::{property::Rfc1123DateTime, response::CardCondition},
 };
-use groupware::{cache::GroupwareCache, contact::ContactCard};
+use groupware::{
+    cache::GroupwareCache,
+    contact::{ContactCard, DIRECTORY_ADDRESSBOOK_ID},
+};
 use http_proto::HttpResponse;
 use hyper::StatusCode;
 use store::write::BatchBuilder;
@@ -110,8 +113,9 @@ impl CardUpdateRequestHandler for Server {
             // Validate ACL
             let parent_id = resource.parent_id().unwrap();
             let document_id = resource.document_id();
-            if !access_token.is_member(account_id)
-                && !resources.has_access_to_container(access_token, parent_id, Acl::ModifyItems)
+            if (!access_token.is_member(account_id)
+                && !resources.has_access_to_container(access_token, parent_id, Acl::ModifyItems))
+                || parent_id == DIRECTORY_ADDRESSBOOK_ID
             {
                 return Err(DavError::Code(StatusCode::FORBIDDEN));
             }
@@ -214,12 +218,13 @@ impl CardUpdateRequestHandler for Server {
             }
 
             // Validate ACL
-            if !access_token.is_member(account_id)
+            if (!access_token.is_member(account_id)
                 && !resources.has_access_to_container(
                     access_token,
                     parent.document_id(),
                     Acl::AddItems,
-                )
+                ))
+                || parent.document_id() == DIRECTORY_ADDRESSBOOK_ID
             {
                 return Err(DavError::Code(StatusCode::FORBIDDEN));
             }
@@ -14,11 +14,10 @@ use dav_proto::schema::request::DavPropertyValue;
 use dav_proto::schema::response::{BaseCondition, List, PropResponse};
 use dav_proto::{Condition, Depth, Timeout};
 use dav_proto::{RequestHeaders, schema::request::LockInfo};
-use groupware::cache::GroupwareCache;
+use groupware::{cache::GroupwareCache, contact::directory::DirectoryAddressBook};
 use http_proto::HttpResponse;
 use hyper::StatusCode;
 use std::collections::HashMap;
-use store::ValueKey;
 use store::dispatch::lookup::KeyValue;
 use store::write::serialize::rkyv_deserialize;
 use store::write::{AlignedBytes, Archive, Archiver, now};
@@ -524,12 +523,11 @@ impl LockRequestHandler for Server {
                     if let Some(document_id) =
                         resource_state.document_id.filter(|&id| id != u32::MAX)
                         && let Some(archive) = self
-                            .store()
-                            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                            .contact_archive(
                                 resource_state.account_id,
                                 resource_state.collection,
                                 document_id,
-                            ))
+                            )
                             .await
                             .caused_by(trc::location!())?
                     {
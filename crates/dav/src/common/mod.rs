@@ -61,6 +61,10 @@ pub(crate) enum SyncType {
     #[default]
     None,
     Initial,
+    InitialFrom {
+        id: u64,
+        cursor: u64,
+    },
     From {
         id: u64,
         seq: u32,
@@ -258,13 +262,11 @@ impl<'x> DavQuery<'x> {
         Self {
             resource: DavQueryResource::Uri(resource),
             propfind: changes.properties,
-            sync_type: changes
-                .sync_token
-                .as_deref()
-                .and_then(Urn::parse)
-                .and_then(|urn| urn.try_unwrap_sync())
-                .map(|(id, seq)| SyncType::From { id, seq })
-                .unwrap_or(SyncType::Initial),
+            sync_type: match changes.sync_token.as_deref().and_then(Urn::parse) {
+                Some(Urn::Sync { id, seq }) => SyncType::From { id, seq },
+                Some(Urn::InitialSync { id, cursor }) => SyncType::InitialFrom { id, cursor },
+                _ => SyncType::Initial,
+            },
             depth: match changes.depth {
                 Depth::One => 1,
                 Depth::Infinity => usize::MAX,
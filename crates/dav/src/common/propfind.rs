@@ -53,21 +53,21 @@ use dav_proto::{
 use groupware::calendar::{SCHEDULE_INBOX_ID, SupportedComponent};
 use groupware::{
     DavCalendarResource, DavResourceName, cache::GroupwareCache, calendar::ArchivedTimezone,
+    contact::directory::DirectoryAddressBook,
 };
 use http_proto::HttpResponse;
 use hyper::StatusCode;
 use registry::schema::{enums::Permission, prelude::ObjectType};
 use std::sync::Arc;
-use store::{
-    ValueKey,
-    registry::RegistryQuery,
-    write::{AlignedBytes, Archive},
-};
 use store::{
     ahash::AHashMap,
     query::log::{Change, Query},
     roaring::RoaringBitmap,
 };
+use store::{
+    registry::RegistryQuery,
+    write::{AlignedBytes, Archive},
+};
 use trc::AddContext;
 use types::{
     acl::Acl,
@@ -417,6 +417,7 @@ impl PropFindRequestHandler for Server {
             .account(access_token.account_id())
             .await
             .caused_by(trc::location!())?;
+        let mut num_pending = paths.len();
         'outer: for item in paths {
             let account_id = item.account_id;
             let document_id = item.document_id;
@@ -434,12 +435,7 @@ impl PropFindRequestHandler for Server {
                     item.document_id == SCHEDULE_INBOX_ID,
                 )
             } else if let Some(archive) = self
-                .store()
-                .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
-                    account_id,
-                    collection,
-                    document_id,
-                ))
+                .contact_archive(account_id, collection, document_id)
                 .await
                 .caused_by(trc::location!())?
             {
@@ -1092,13 +1088,14 @@ impl PropFindRequestHandler for Server {
             }
             response.add_response(Response::new_propstat(item.name, prop_stat));
 
+            num_pending -= 1;
             limit -= 1;
             if limit == 0 {
                 break;
             }
         }
 
-        if limit == 0 || is_sync_limited {
+        if (limit == 0 && num_pending > 0) || is_sync_limited {
             response.add_response(
                 Response::new_status([query.uri], StatusCode::INSUFFICIENT_STORAGE)
                     .with_error(BaseCondition::NumberOfMatchesWithinLimit)
@@ -1348,10 +1345,7 @@ async fn get(
 
             true
         }
-        SyncType::Initial => {
-            response.set_sync_token(resources.sync_token());
-            false
-        }
+        SyncType::Initial | SyncType::InitialFrom { .. } => false,
         SyncType::None => false,
     };
 
@@ -1471,6 +1465,50 @@ async fn get(
         }
     }
 
+    // Paginate initial synchronization using the document id of the next item as cursor,
+    // the change id of the first page is kept until all pages have been returned so no
+    // changes are missed
+    if let Some((id, cursor)) = match query.sync_type {
+        SyncType::Initial => Some((resources.highest_change_id, 0)),
+        SyncType::InitialFrom { id, cursor } => Some((id, cursor)),
+        _ => None,
+    } {
+        results.retain(|item| item.sync_cursor() >= cursor);
+        results.sort_unstable_by_key(|item| item.sync_cursor());
+
+        // Items listed under several containers share the same cursor and are never split
+        // across pages
+        let mut page_len = limit.min(results.len());
+        let is_split = |page_len: usize| {
+            page_len > 0
+                && page_len < results.len()
+                && results[page_len].sync_cursor() == results[page_len - 1].sync_cursor()
+        };
+        while is_split(page_len) {
+            page_len -= 1;
+        }
+        if page_len == 0 {
+            page_len = limit.min(results.len());
+            while is_split(page_len) {
+                page_len += 1;
+            }
+        }
+
+        if let Some(next) = results.get(page_len) {
+            *is_sync_limited = true;
+            response.set_sync_token(
+                Urn::InitialSync {
+                    id,
+                    cursor: next.sync_cursor(),
+                }
+                .to_string(),
+            );
+        } else {
+            response.set_sync_token(Urn::Sync { id, seq: 0 }.to_string());
+        }
+        results.truncate(page_len);
+    }
+
     Ok(results)
 }
 
@@ -1574,6 +1612,11 @@ impl PropFindItem {
             is_container: resource.is_container(),
         }
     }
+
+    // Containers are listed before their children
+    fn sync_cursor(&self) -> u64 {
+        ((!self.is_container as u64) << 32) | self.document_id as u64
+    }
 }
 
 impl PropFindData {
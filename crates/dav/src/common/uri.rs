@@ -23,6 +23,7 @@ pub(crate) struct UriResource<A, R> {
 pub(crate) enum Urn {
     Lock(u64),
     Sync { id: u64, seq: u32 },
+    InitialSync { id: u64, cursor: u64 },
 }
 
 pub(crate) type UnresolvedUri<'x> = UriResource<Option<u32>, Option<&'x str>>;
@@ -191,7 +192,14 @@ impl Urn {
         match kind {
             "davlock" => u64::from_str_radix(id, 16).ok().map(Urn::Lock),
             "davsync" => {
-                if let Some((id, seq)) = id.split_once(':') {
+                if let Some((id, seq)) = id
+                    .strip_suffix(":initial")
+                    .and_then(|id| id.split_once(':'))
+                {
+                    let id = u64::from_str_radix(id, 16).ok()?;
+                    let cursor = u64::from_str_radix(seq, 16).ok()?;
+                    Some(Urn::InitialSync { id, cursor })
+                } else if let Some((id, seq)) = id.split_once(':') {
                     let id = u64::from_str_radix(id, 16).ok()?;
                     let seq = u32::from_str_radix(seq, 16).ok()?;
                     Some(Urn::Sync { id, seq })
@@ -211,13 +219,6 @@ impl Urn {
             _ => None,
         }
     }
-
-    pub fn try_unwrap_sync(&self) -> Option<(u64, u32)> {
-        match self {
-            Urn::Sync { id, seq } => Some((*id, *seq)),
-            _ => None,
-        }
-    }
 }
 
 impl Display for Urn {
@@ -231,6 +232,9 @@ impl Display for Urn {
                     write!(f, "urn:stalwart:davsync:{id:x}:{seq:x}")
                 }
             }
+            Urn::InitialSync { id, cursor } => {
+                write!(f, "urn:stalwart:davsync:{id:x}:{cursor:x}:initial")
+            }
         }
     }
 }
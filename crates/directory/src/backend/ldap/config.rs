@@ -89,6 +89,18 @@ impl LdapDirectory {
                 .into_iter()
                 .map(|a| a.to_lowercase())
                 .collect(),
+            attr_proxy_backend: config
+                .attr_proxy_backend
+                .into_inner()
+                .into_iter()
+                .map(|a| a.to_lowercase())
+                .collect(),
+            attr_login_referral: config
+                .attr_login_referral
+                .into_inner()
+                .into_iter()
+                .map(|a| a.to_lowercase())
+                .collect(),
             group_class: config.group_class,
             attrs_principal: vec![],
         };
@@ -101,6 +113,8 @@ impl LdapDirectory {
             &mappings.attr_email_alias,
             &mappings.attr_email,
             &mappings.attr_class,
+            &mappings.attr_proxy_backend,
+            &mappings.attr_login_referral,
         ] {
             mappings
                 .attrs_principal
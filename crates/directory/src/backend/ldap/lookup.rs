@@ -252,6 +252,10 @@ impl LdapMappings {
                 {
                     account.description = Some(desc);
                 }
+            } else if self.attr_proxy_backend.contains(&attr) {
+                account.proxy_backend = value.into_iter().find(|v| !v.is_empty());
+            } else if self.attr_login_referral.contains(&attr) {
+                account.login_referral = value.into_iter().find(|v| !v.is_empty());
             } else if self.attr_groups.contains(&attr) {
                 account.groups.extend(value);
             } else if self.attr_class.contains(&attr) {
@@ -30,6 +30,8 @@ pub struct LdapMappings {
     attr_secret_changed: Vec<String>,
     attr_email: Vec<String>,
     attr_email_alias: Vec<String>,
+    attr_proxy_backend: Vec<String>,
+    attr_login_referral: Vec<String>,
     attrs_principal: Vec<String>,
     group_class: String,
 }
@@ -209,6 +209,8 @@ impl OpenIdDirectory {
                 .and_then(|name_claim| claims.get(name_claim))
                 .and_then(|v| v.as_str())
                 .map(|s| s.to_string()),
+            proxy_backend: None,
+            login_referral: None,
         })
     }
 
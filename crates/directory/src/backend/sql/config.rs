@@ -52,6 +52,8 @@ impl SqlDirectory {
             column_secret: config.column_secret,
             column_type: config.column_class,
             column_description: config.column_description,
+            column_proxy_backend: config.column_proxy_backend,
+            column_login_referral: config.column_login_referral,
         };
 
         Ok(Directory::Sql(SqlDirectory {
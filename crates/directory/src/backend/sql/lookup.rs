@@ -173,6 +173,18 @@ impl SqlMappings {
                     && let Value::Text(text) = value
                 {
                     account.description = Some(text.into_owned());
+                } else if let Some(column_proxy_backend) = &self.column_proxy_backend
+                    && name.eq_ignore_ascii_case(column_proxy_backend)
+                    && let Value::Text(text) = value
+                    && !text.is_empty()
+                {
+                    account.proxy_backend = Some(text.into_owned());
+                } else if let Some(column_login_referral) = &self.column_login_referral
+                    && name.eq_ignore_ascii_case(column_login_referral)
+                    && let Value::Text(text) = value
+                    && !text.is_empty()
+                {
+                    account.login_referral = Some(text.into_owned());
                 }
             }
         }
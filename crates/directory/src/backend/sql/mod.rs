@@ -24,4 +24,6 @@ pub(crate) struct SqlMappings {
     column_secret: String,
     column_type: Option<String>,
     column_description: Option<String>,
+    column_proxy_backend: Option<String>,
+    column_login_referral: Option<String>,
 }
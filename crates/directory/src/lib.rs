@@ -49,6 +49,8 @@ pub struct Account {
     pub secret: Option<String>,
     pub groups: Vec<String>,
     pub description: Option<String>,
+    pub proxy_backend: Option<String>,
+    pub login_referral: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
@@ -0,0 +1,103 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use mail_parser::{Message, MessageParser};
+
+// Classifies messages that must not trigger automatic responses (RFC 3834),
+// such as vacation replies or delivery status notifications, so that bulk
+// senders and mailing lists are not flooded with replies nobody reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BulkTraffic {
+    MailingList,
+    Bulk,
+    AutoSubmitted,
+}
+
+impl BulkTraffic {
+    pub fn detect(message: &Message<'_>) -> Option<Self> {
+        let mut result = None;
+
+        for header in message.root_part().headers() {
+            let name = header.name.as_str();
+            let value = header.value.as_text().map(|value| value.trim());
+
+            if name.eq_ignore_ascii_case("Auto-Submitted") {
+                if value.is_some_and(|value| !value.eq_ignore_ascii_case("no")) {
+                    return Some(BulkTraffic::AutoSubmitted);
+                }
+            } else if name.eq_ignore_ascii_case("X-Auto-Response-Suppress") {
+                if value.is_some_and(|value| {
+                    value.split(',').any(|value| {
+                        let value = value.trim();
+                        value.eq_ignore_ascii_case("All")
+                            || value.eq_ignore_ascii_case("OOF")
+                            || value.eq_ignore_ascii_case("AutoReply")
+                    })
+                }) {
+                    return Some(BulkTraffic::AutoSubmitted);
+                }
+            } else if name.eq_ignore_ascii_case("Precedence") {
+                if value.is_some_and(|value| {
+                    value.eq_ignore_ascii_case("bulk")
+                        || value.eq_ignore_ascii_case("list")
+                        || value.eq_ignore_ascii_case("junk")
+                }) {
+                    result = Some(BulkTraffic::Bulk);
+                }
+            } else if result.is_none()
+                && [
+                    "List-Id",
+                    "List-Unsubscribe",
+                    "List-Post",
+                    "List-Help",
+                    "List-Subscribe",
+                    "List-Owner",
+                    "List-Archive",
+                ]
+                .iter()
+                .any(|list_header| name.eq_ignore_ascii_case(list_header))
+            {
+                result = Some(BulkTraffic::MailingList);
+            }
+        }
+
+        result
+    }
+
+    pub fn detect_raw(raw_message: &[u8]) -> Option<Self> {
+        MessageParser::new()
+            .parse_headers(raw_message)
+            .and_then(|message| Self::detect(&message))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BulkTraffic::MailingList => "mailing-list",
+            BulkTraffic::Bulk => "bulk",
+            BulkTraffic::AutoSubmitted => "auto-submitted",
+        }
+    }
+}
+
+// Vacation responses, whether produced by a user script or by the built-in
+// responder, are new messages marked as "auto-replied". Redirected copies
+// keep the Message-ID of the original even when their headers were edited.
+pub fn is_auto_reply(raw_message: &[u8], original_message_id: Option<&str>) -> bool {
+    MessageParser::new()
+        .parse_headers(raw_message)
+        .is_some_and(|message| {
+            message.message_id() != original_message_id
+                && message.root_part().headers().iter().any(|header| {
+                    header.name.as_str().eq_ignore_ascii_case("Auto-Submitted")
+                        && header.value.as_text().is_some_and(|value| {
+                            value
+                                .trim()
+                                .to_ascii_lowercase()
+                                .starts_with("auto-replied")
+                        })
+                })
+        })
+}
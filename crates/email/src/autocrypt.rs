@@ -0,0 +1,267 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::Server;
+use mail_parser::{Message, MimeHeaders, decoders::base64::base64_decode};
+use sequoia_openpgp::{Cert, parse::Parse};
+use std::future::Future;
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder, now},
+};
+use trc::AddContext;
+use types::{collection::Collection, field::PrincipalField};
+
+const MAX_AUTOCRYPT_PEERS: usize = 256;
+const MAX_AUTOCRYPT_KEY_SIZE: usize = 32 * 1024;
+const LAST_SEEN_UPDATE_INTERVAL: u64 = 24 * 3600;
+
+// Peer keys announced in the Autocrypt headers of incoming messages
+// (Autocrypt Level 1), kept per account so that clients can encrypt replies
+// without a prior key exchange. Once the cache is full the peer that was
+// seen least recently is evicted.
+#[derive(
+    rkyv::Archive,
+    rkyv::Deserialize,
+    rkyv::Serialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AutocryptPeers {
+    pub peers: Vec<AutocryptPeer>,
+}
+
+#[derive(
+    rkyv::Archive,
+    rkyv::Deserialize,
+    rkyv::Serialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AutocryptPeer {
+    pub address: String,
+    pub key_data: String,
+    pub prefer_encrypt: bool,
+    pub last_seen: u64,
+    pub autocrypt_timestamp: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutocryptHeader {
+    pub address: String,
+    pub key_data: String,
+    pub prefer_encrypt: bool,
+}
+
+pub trait AutocryptStore: Sync + Send {
+    fn autocrypt_peers(
+        &self,
+        account_id: u32,
+    ) -> impl Future<Output = trc::Result<AutocryptPeers>> + Send;
+
+    fn set_autocrypt_peers(
+        &self,
+        account_id: u32,
+        peers: &AutocryptPeers,
+    ) -> impl Future<Output = trc::Result<()>> + Send;
+
+    fn autocrypt_ingest(
+        &self,
+        account_id: u32,
+        message: &Message<'_>,
+    ) -> impl Future<Output = trc::Result<()>> + Send;
+}
+
+impl AutocryptStore for Server {
+    async fn autocrypt_peers(&self, account_id: u32) -> trc::Result<AutocryptPeers> {
+        self.store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Principal,
+                0,
+                PrincipalField::AutocryptPeers,
+            ))
+            .await
+            .caused_by(trc::location!())?
+            .map(|peers| {
+                peers
+                    .deserialize::<AutocryptPeers>()
+                    .caused_by(trc::location!())
+            })
+            .transpose()
+            .map(|peers| peers.unwrap_or_default())
+    }
+
+    async fn set_autocrypt_peers(
+        &self,
+        account_id: u32,
+        peers: &AutocryptPeers,
+    ) -> trc::Result<()> {
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Principal)
+            .with_document(0);
+        if !peers.peers.is_empty() {
+            batch.set(
+                PrincipalField::AutocryptPeers,
+                Archiver::new(peers.clone())
+                    .serialize()
+                    .caused_by(trc::location!())?,
+            );
+        } else {
+            batch.clear(PrincipalField::AutocryptPeers);
+        }
+
+        self.store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())
+            .map(|_| ())
+    }
+
+    // Only messages carrying an Autocrypt header are looked at, so peers
+    // that stop sending one keep their last known key until evicted.
+    async fn autocrypt_ingest(&self, account_id: u32, message: &Message<'_>) -> trc::Result<()> {
+        let Some(header) = AutocryptHeader::parse(message) else {
+            return Ok(());
+        };
+        let now = now();
+        let timestamp = message
+            .date()
+            .map(|date| date.to_timestamp().clamp(0, now as i64) as u64)
+            .unwrap_or(now);
+
+        let mut peers = self.autocrypt_peers(account_id).await?;
+        if let Some(peer) = peers
+            .peers
+            .iter_mut()
+            .find(|peer| peer.address == header.address)
+        {
+            // Messages delivered out of order must not replace a newer key
+            if timestamp <= peer.autocrypt_timestamp {
+                return Ok(());
+            }
+            if peer.key_data == header.key_data && peer.prefer_encrypt == header.prefer_encrypt {
+                if timestamp < peer.last_seen + LAST_SEEN_UPDATE_INTERVAL {
+                    return Ok(());
+                }
+            } else {
+                peer.key_data = header.key_data;
+                peer.prefer_encrypt = header.prefer_encrypt;
+            }
+            peer.last_seen = timestamp;
+            peer.autocrypt_timestamp = timestamp;
+        } else {
+            if peers.peers.len() >= MAX_AUTOCRYPT_PEERS
+                && let Some((idx, _)) = peers
+                    .peers
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, peer)| peer.last_seen)
+            {
+                peers.peers.swap_remove(idx);
+            }
+            peers.peers.push(AutocryptPeer {
+                address: header.address,
+                key_data: header.key_data,
+                prefer_encrypt: header.prefer_encrypt,
+                last_seen: timestamp,
+                autocrypt_timestamp: timestamp,
+            });
+        }
+
+        self.set_autocrypt_peers(account_id, &peers).await
+    }
+}
+
+impl AutocryptHeader {
+    // A message is only considered when it has a single sender and exactly
+    // one valid header for that sender, reports are always ignored.
+    pub fn parse(message: &Message<'_>) -> Option<Self> {
+        if message.content_type().is_some_and(|ct| {
+            ct.ctype().eq_ignore_ascii_case("multipart")
+                && ct
+                    .subtype()
+                    .is_some_and(|st| st.eq_ignore_ascii_case("report"))
+        }) {
+            return None;
+        }
+        let from = message.from().filter(|from| from.iter().count() == 1)?;
+        let from = from.first()?.address()?;
+
+        let mut result = None;
+        for header in message.root_part().headers() {
+            if header.name.as_str().eq_ignore_ascii_case("Autocrypt")
+                && let Some(header) = header
+                    .value
+                    .as_text()
+                    .and_then(Self::parse_value)
+                    .filter(|header| header.address.eq_ignore_ascii_case(from))
+            {
+                if result.is_some() {
+                    return None;
+                }
+                result = Some(header);
+            }
+        }
+
+        result
+    }
+
+    pub fn parse_value(value: &str) -> Option<Self> {
+        let mut address = None;
+        let mut key_data = None;
+        let mut prefer_encrypt = false;
+
+        for attribute in value.split(';') {
+            if attribute.trim().is_empty() {
+                continue;
+            }
+            let (name, value) = attribute.split_once('=')?;
+            let name = name.trim();
+            if name.eq_ignore_ascii_case("addr") {
+                address = Some(value.trim().to_lowercase());
+            } else if name.eq_ignore_ascii_case("prefer-encrypt") {
+                prefer_encrypt = value.trim().eq_ignore_ascii_case("mutual");
+            } else if name.eq_ignore_ascii_case("keydata") {
+                key_data = Some(
+                    value
+                        .chars()
+                        .filter(|ch| !ch.is_ascii_whitespace())
+                        .collect::<String>(),
+                );
+            } else if !name.starts_with('_') {
+                // Unknown critical attributes invalidate the header
+                return None;
+            }
+        }
+
+        let key_data = key_data.filter(|key_data| {
+            !key_data.is_empty()
+                && key_data.len() <= MAX_AUTOCRYPT_KEY_SIZE
+                && base64_decode(key_data.as_bytes())
+                    .is_some_and(|bytes| Cert::from_bytes(&bytes).is_ok())
+        })?;
+
+        Some(AutocryptHeader {
+            address: address.filter(|address| address.contains('@'))?,
+            key_data,
+            prefer_encrypt,
+        })
+    }
+}
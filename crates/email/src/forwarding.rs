@@ -0,0 +1,246 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::message::delivery::{AutogeneratedMessage, IngestRecipient};
+use common::{Server, auth::oauth::GrantType};
+use mail_parser::MessageParser;
+use std::future::Future;
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder},
+};
+use trc::AddContext;
+use types::{collection::Collection, field::PrincipalField};
+
+// Confirmation links are valid for three days
+pub const FORWARDING_CONFIRM_EXPIRY: u64 = 3 * 24 * 3600;
+
+// Forwarding is applied when a message is delivered, before any Sieve script
+// runs. Messages are only forwarded once the owner of the destination address
+// has followed the confirmation link sent to it, which prevents accounts from
+// being used to flood third-party mailboxes.
+#[derive(
+    rkyv::Archive,
+    rkyv::Deserialize,
+    rkyv::Serialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountForwarding {
+    pub destination: String,
+    #[serde(default)]
+    pub keep_copy: bool,
+    #[serde(default)]
+    pub verified: bool,
+}
+
+pub trait AccountForwardingStore: Sync + Send {
+    fn account_forwarding(
+        &self,
+        account_id: u32,
+    ) -> impl Future<Output = trc::Result<Option<AccountForwarding>>> + Send;
+
+    fn set_account_forwarding(
+        &self,
+        account_id: u32,
+        forwarding: Option<AccountForwarding>,
+    ) -> impl Future<Output = trc::Result<()>> + Send;
+
+    fn forward_message(
+        &self,
+        account_id: u32,
+        rcpt: &IngestRecipient,
+        raw_message: &[u8],
+        session_id: u64,
+        autogenerated: &mut Vec<AutogeneratedMessage>,
+    ) -> impl Future<Output = bool> + Send;
+
+    fn forwarding_confirm_url(
+        &self,
+        account_id: u32,
+        destination: &str,
+    ) -> impl Future<Output = trc::Result<String>> + Send;
+
+    fn forwarding_confirm(&self, token: &str) -> impl Future<Output = trc::Result<bool>> + Send;
+}
+
+impl AccountForwardingStore for Server {
+    async fn account_forwarding(&self, account_id: u32) -> trc::Result<Option<AccountForwarding>> {
+        self.store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Principal,
+                0,
+                PrincipalField::Forwarding,
+            ))
+            .await
+            .caused_by(trc::location!())?
+            .map(|forwarding| {
+                forwarding
+                    .deserialize::<AccountForwarding>()
+                    .caused_by(trc::location!())
+            })
+            .transpose()
+    }
+
+    async fn set_account_forwarding(
+        &self,
+        account_id: u32,
+        forwarding: Option<AccountForwarding>,
+    ) -> trc::Result<()> {
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Principal)
+            .with_document(0);
+        if let Some(forwarding) = forwarding {
+            batch.set(
+                PrincipalField::Forwarding,
+                Archiver::new(forwarding)
+                    .serialize()
+                    .caused_by(trc::location!())?,
+            );
+        } else {
+            batch.clear(PrincipalField::Forwarding);
+        }
+
+        self.store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())
+            .map(|_| ())
+    }
+
+    // Returns whether a local copy of the message should be kept
+    async fn forward_message(
+        &self,
+        account_id: u32,
+        rcpt: &IngestRecipient,
+        raw_message: &[u8],
+        session_id: u64,
+        autogenerated: &mut Vec<AutogeneratedMessage>,
+    ) -> bool {
+        let forwarding = match self.account_forwarding(account_id).await {
+            Ok(Some(forwarding)) if forwarding.verified => forwarding,
+            Ok(_) => return true,
+            Err(err) => {
+                trc::error!(
+                    err.account_id(account_id)
+                        .span_id(session_id)
+                        .caused_by(trc::location!())
+                );
+                return true;
+            }
+        };
+
+        // Messages that already went through this mailbox are delivered
+        // locally, otherwise two accounts forwarding to each other would loop
+        if MessageParser::new()
+            .parse_headers(raw_message)
+            .is_some_and(|message| {
+                message.root_part().headers().iter().any(|header| {
+                    header.name.as_str().eq_ignore_ascii_case("Delivered-To")
+                        && header
+                            .value
+                            .as_text()
+                            .is_some_and(|value| value.trim().eq_ignore_ascii_case(&rcpt.address))
+                })
+            })
+        {
+            trc::event!(
+                MessageIngest(trc::MessageIngestEvent::Error),
+                AccountId = account_id,
+                To = forwarding.destination,
+                Reason = "Forwarding loop detected.",
+                SpanId = session_id
+            );
+            return true;
+        }
+
+        let delivered_to = format!("Delivered-To: {}\r\n", rcpt.address);
+        if raw_message.len() + delivered_to.len() > self.core.email.mail_max_size {
+            trc::event!(
+                MessageIngest(trc::MessageIngestEvent::Error),
+                AccountId = account_id,
+                To = forwarding.destination,
+                Size = raw_message.len(),
+                Limit = self.core.email.mail_max_size,
+                Reason = "Message too large to forward.",
+                SpanId = session_id
+            );
+            return true;
+        }
+
+        trc::event!(
+            MessageIngest(trc::MessageIngestEvent::Forwarded),
+            AccountId = account_id,
+            From = rcpt.address.clone(),
+            To = forwarding.destination.clone(),
+            Size = raw_message.len(),
+            SpanId = session_id
+        );
+
+        let mut message = Vec::with_capacity(delivered_to.len() + raw_message.len());
+        message.extend_from_slice(delivered_to.as_bytes());
+        message.extend_from_slice(raw_message);
+        autogenerated.push(AutogeneratedMessage {
+            sender_address: rcpt.address.clone(),
+            recipients: vec![forwarding.destination],
+            message,
+        });
+
+        forwarding.keep_copy
+    }
+
+    async fn forwarding_confirm_url(
+        &self,
+        account_id: u32,
+        destination: &str,
+    ) -> trc::Result<String> {
+        let token = self
+            .encode_access_token(
+                GrantType::ForwardConfirm,
+                account_id,
+                destination,
+                FORWARDING_CONFIRM_EXPIRY,
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(format!(
+            "{}/forward/{token}",
+            self.core.network.http.url_https
+        ))
+    }
+
+    async fn forwarding_confirm(&self, token: &str) -> trc::Result<bool> {
+        let Ok(token) = self
+            .validate_access_token(GrantType::ForwardConfirm.into(), token)
+            .await
+        else {
+            return Ok(false);
+        };
+
+        // The link is only valid for the destination it was issued for, a
+        // destination changed in the meantime requires a new confirmation
+        match self.account_forwarding(token.account_id).await? {
+            Some(mut forwarding) if forwarding.destination == token.client_id => {
+                if !forwarding.verified {
+                    forwarding.verified = true;
+                    self.set_account_forwarding(token.account_id, Some(forwarding))
+                        .await?;
+                }
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
@@ -6,7 +6,10 @@
 
 #![deny(clippy::large_futures)]
 
+pub mod auto_response;
+pub mod autocrypt;
 pub mod cache;
+pub mod forwarding;
 pub mod identity;
 pub mod mailbox;
 pub mod message;
@@ -6,8 +6,11 @@
 
 use super::*;
 use crate::{
-    cache::{MessageCacheFetch, email::MessageCacheAccess},
-    message::metadata::MessageData,
+    cache::{MessageCacheFetch, email::MessageCacheAccess, mailbox::MailboxCacheAccess},
+    message::{
+        legal_hold::{LegalHold, LegalHoldManagement},
+        metadata::MessageData,
+    },
 };
 use common::{
     Server, auth::AccessToken, sharing::EffectiveAcl, storage::index::ObjectIndexBuilder,
@@ -45,6 +48,8 @@ pub enum MailboxDestroyError {
     HasEmails,
     NotFound,
     AssertionFailed,
+    LegalHold,
+    Protected,
 }
 
 impl MailboxDestroy for Server {
@@ -61,11 +66,19 @@ impl MailboxDestroy for Server {
             return Ok(Err(MailboxDestroyError::CannotDestroy));
         }
 
-        // Verify that this mailbox does not have sub-mailboxes
+        // Folders protected by policy cannot be deleted
         let cache = self
             .get_cached_messages(account_id)
             .await
             .caused_by(trc::location!())?;
+        if cache
+            .mailbox_by_id(&document_id)
+            .is_some_and(|mailbox| self.core.email.is_protected_folder(mailbox))
+        {
+            return Ok(Err(MailboxDestroyError::Protected));
+        }
+
+        // Verify that this mailbox does not have sub-mailboxes
         if cache
             .mailboxes
             .items
@@ -83,6 +96,22 @@ impl MailboxDestroy for Server {
         let message_ids =
             RoaringBitmap::from_iter(cache.in_mailbox(document_id).map(|m| m.document_id));
 
+        // Held mailboxes cannot be destroyed and, when the whole account is held,
+        // neither can mailboxes that still contain messages
+        match self
+            .legal_hold(account_id)
+            .await
+            .caused_by(trc::location!())?
+        {
+            LegalHold::Mailboxes(mailboxes) if mailboxes.contains_key(&document_id) => {
+                return Ok(Err(MailboxDestroyError::LegalHold));
+            }
+            LegalHold::Account if !message_ids.is_empty() => {
+                return Ok(Err(MailboxDestroyError::LegalHold));
+            }
+            _ => {}
+        }
+
         if !message_ids.is_empty() {
             if remove_emails {
                 // If the message is in multiple mailboxes, untag it from the current mailbox,
@@ -39,6 +39,7 @@ impl MailboxFnc for Server {
 
         // Create mailboxes
         let mut last_document_id = ARCHIVE_ID;
+        let mut paths = Vec::with_capacity(self.core.email.default_folders.len());
         for folder in &self.core.email.default_folders {
             let document_id = match folder.special_use {
                 SpecialUse::Inbox => INBOX_ID,
@@ -66,9 +67,52 @@ impl MailboxFnc for Server {
                 .with_document(document_id)
                 .custom(ObjectIndexBuilder::<(), _>::new().with_changes(object))
                 .caused_by(trc::location!())?;
+            paths.push((folder.name.to_lowercase(), document_id));
         }
+
+        // Create the folders provisioned by policy along with any missing parents
+        for policy in self
+            .core
+            .email
+            .folder_policies
+            .iter()
+            .filter(|policy| policy.create)
+        {
+            let mut parent_id = 0;
+            let mut path = String::with_capacity(policy.path.len());
+            let mut names = policy.path.split('/').peekable();
+            while let Some(name) = names.next() {
+                if !path.is_empty() {
+                    path.push('/');
+                }
+                path.push_str(&name.to_lowercase());
+
+                let document_id =
+                    if let Some((_, document_id)) = paths.iter().find(|(item, _)| item == &path) {
+                        *document_id
+                    } else {
+                        last_document_id += 1;
+                        let mut object = Mailbox::new(name).with_parent_id(parent_id);
+                        if policy.subscribe && names.peek().is_none() {
+                            object.add_subscriber(account_id);
+                        }
+                        batch
+                            .with_document(last_document_id)
+                            .custom(ObjectIndexBuilder::<(), _>::new().with_changes(object))
+                            .caused_by(trc::location!())?;
+                        paths.push((path.clone(), last_document_id));
+                        last_document_id
+                    };
+                parent_id = document_id + 1;
+            }
+        }
+
         self.store()
-            .assign_document_ids(account_id, Collection::Mailbox, (ARCHIVE_ID + 1) as u64)
+            .assign_document_ids(
+                account_id,
+                Collection::Mailbox,
+                (last_document_id + 1) as u64,
+            )
             .await
             .caused_by(trc::location!())?;
 
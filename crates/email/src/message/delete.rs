@@ -4,7 +4,7 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::metadata::MessageData;
+use super::{legal_hold::LegalHoldManagement, metadata::MessageData, retention::EmailRetention};
 use common::{Server, storage::index::ObjectIndexBuilder};
 use groupware::calendar::storage::ItipAutoExpunge;
 use registry::schema::enums::IndexDocumentType;
@@ -21,6 +21,19 @@ use trc::AddContext;
 use types::collection::{Collection, VanishedCollection};
 use types::field::{EmailField, EmailSubmissionField};
 
+// Messages that were requested for deletion but were not deleted
+#[derive(Debug, Default)]
+pub struct NotDeleted {
+    pub not_found: RoaringBitmap,
+    pub legal_hold: RoaringBitmap,
+}
+
+impl NotDeleted {
+    pub fn is_empty(&self) -> bool {
+        self.not_found.is_empty() && self.legal_hold.is_empty()
+    }
+}
+
 pub trait EmailDeletion: Sync + Send {
     fn emails_delete(
         &self,
@@ -28,7 +41,7 @@ pub trait EmailDeletion: Sync + Send {
         tenant_id: Option<u32>,
         batch: &mut BatchBuilder,
         document_ids: RoaringBitmap,
-    ) -> impl Future<Output = trc::Result<RoaringBitmap>> + Send;
+    ) -> impl Future<Output = trc::Result<NotDeleted>> + Send;
 
     fn purge_account(&self, account_id: u32) -> impl Future<Output = trc::Result<()>> + Send;
 
@@ -52,8 +65,13 @@ impl EmailDeletion for Server {
         tenant_id: Option<u32>,
         batch: &mut BatchBuilder,
         document_ids: RoaringBitmap,
-    ) -> trc::Result<RoaringBitmap> {
+    ) -> trc::Result<NotDeleted> {
         let mut deleted_ids = RoaringBitmap::new();
+        let mut held_ids = RoaringBitmap::new();
+        let legal_hold = self
+            .legal_hold(account_id)
+            .await
+            .caused_by(trc::location!())?;
         batch
             .with_account_id(account_id)
             .with_collection(Collection::Email);
@@ -66,6 +84,16 @@ impl EmailDeletion for Server {
                 let metadata = data_
                     .to_unarchived::<MessageData>()
                     .caused_by(trc::location!())?;
+                if legal_hold.is_message_held(
+                    metadata
+                        .inner
+                        .mailboxes
+                        .iter()
+                        .map(|m| m.mailbox_id.to_native()),
+                ) {
+                    held_ids.insert(document_id);
+                    return Ok(true);
+                }
                 for mailbox in metadata.inner.mailboxes.iter() {
                     batch.log_vanished_item(
                         VanishedCollection::Email,
@@ -95,14 +123,15 @@ impl EmailDeletion for Server {
         )
         .await?;
 
-        let not_destroyed = if document_ids.len() == deleted_ids.len() {
-            RoaringBitmap::new()
-        } else {
+        let mut not_deleted = NotDeleted::default();
+        if document_ids.len() != deleted_ids.len() {
+            deleted_ids |= &held_ids;
             deleted_ids ^= document_ids;
-            deleted_ids
-        };
+            not_deleted.not_found = deleted_ids;
+            not_deleted.legal_hold = held_ids;
+        }
 
-        Ok(not_destroyed)
+        Ok(not_deleted)
     }
 
     async fn purge_account(&self, account_id: u32) -> trc::Result<()> {
@@ -113,6 +142,24 @@ impl EmailDeletion for Server {
                 .caused_by(trc::location!())?;
         }
 
+        // Apply the retention policies of the account's tenant
+        if let Some(tenant_id) = self
+            .account(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .id_tenant
+        {
+            let policies = self
+                .tenant(tenant_id)
+                .await
+                .caused_by(trc::location!())?
+                .retention_policies
+                .clone();
+            self.email_retention(account_id, &policies, false)
+                .await
+                .caused_by(trc::location!())?;
+        }
+
         // Auto-expunge iMIP messages
         if let Some(hold_period) = self.core.groupware.itip_inbox_auto_expunge {
             self.itip_auto_expunge(account_id, hold_period)
@@ -4,18 +4,31 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::ingest::{EmailIngest, IngestEmail, IngestSource};
-use crate::{mailbox::INBOX_ID, sieve::ingest::SieveScriptIngest};
+use super::ingest::{EmailIngest, IngestEmail, IngestSource, IngestedEmail};
+use crate::{
+    cache::{MessageCacheFetch, mailbox::MailboxCacheAccess},
+    forwarding::AccountForwardingStore,
+    mailbox::{INBOX_ID, manage::MailboxFnc},
+    push::rules::NotificationRulesStore,
+    sieve::ingest::SieveScriptIngest,
+};
 use common::{
     Server,
-    auth::BuildAccessToken,
+    auth::{
+        AccessToken, BuildAccessToken, DOMAIN_FLAG_SUB_ADDRESSING_CREATE,
+        DOMAIN_FLAG_SUB_ADDRESSING_FOLDERS,
+    },
     ipc::{EmailPush, PushNotification},
 };
 use mail_parser::MessageParser;
 use registry::schema::enums::Permission;
 use std::{borrow::Cow, future::Future};
 use store::ahash::AHashMap;
-use types::blob_hash::BlobHash;
+use trc::AddContext;
+use types::{blob_hash::BlobHash, special_use::SpecialUse};
+use utils::DomainPart;
+
+const DELIVERY_BLOB_HOLD: u64 = 3600;
 
 #[derive(Debug)]
 pub struct IngestMessage {
@@ -30,6 +43,7 @@ pub struct IngestMessage {
 #[derive(Debug)]
 pub struct IngestRecipient {
     pub address: String,
+    pub subaddress: Option<String>,
     pub is_spam: bool,
 }
 
@@ -66,13 +80,7 @@ pub trait MailDelivery: Sync + Send {
 impl MailDelivery for Server {
     async fn deliver_message(&self, message: IngestMessage) -> LocalDeliveryResult {
         // Read message
-        let raw_message = match self
-            .core
-            .storage
-            .blob
-            .get_blob(message.message_blob.as_slice(), 0..usize::MAX)
-            .await
-        {
+        let raw_message = match fetch_message_blob(self, &message.message_blob).await {
             Ok(Some(raw_message)) => raw_message,
             Ok(None) => {
                 trc::event!(
@@ -156,42 +164,70 @@ impl MailDelivery for Server {
                     .assert_has_permission(Permission::EmailReceive)
             }) {
                 Ok(access_token) => {
-                    // Check if there is an active sieve script
-                    match self.sieve_script_get_active(account_id).await {
-                        Ok(None) => {
-                            // Ingest message
-                            self.email_ingest(IngestEmail {
-                                raw_message: &raw_message,
-                                blob_hash: Some(&message.message_blob),
-                                message: MessageParser::new().parse(&raw_message),
-                                access_token: &access_token,
-                                mailbox_ids: vec![INBOX_ID],
-                                keywords: vec![],
-                                received_at: None,
-                                source: IngestSource::Smtp {
-                                    deliver_to: &rcpt.address,
-                                    is_sender_authenticated: message.sender_authenticated,
-                                    is_spam: rcpt.is_spam,
-                                },
-                                session_id: message.session_id,
-                            })
-                            .await
-                        }
-                        Ok(Some(active_script)) => {
-                            self.sieve_script_ingest(
-                                &access_token,
-                                &message.message_blob,
-                                &raw_message,
-                                &message.sender_address,
-                                message.sender_authenticated,
+                    if access_token.has_permission(Permission::EmailForward)
+                        && !self
+                            .forward_message(
+                                account_id,
                                 &rcpt,
+                                &raw_message,
                                 message.session_id,
-                                active_script,
                                 &mut result.autogenerated,
                             )
                             .await
+                    {
+                        // Forwarded without keeping a local copy
+                        Ok(IngestedEmail {
+                            document_id: 0,
+                            thread_id: 0,
+                            change_id: u64::MAX,
+                            blob_id: Default::default(),
+                            size: raw_message.len(),
+                            imap_uids: Vec::new(),
+                        })
+                    } else {
+                        // Check if there is an active sieve script
+                        match self.sieve_script_get_active(account_id).await {
+                            Ok(None) => {
+                                match subaddress_mailbox_id(self, &access_token, &rcpt).await {
+                                    Ok(mailbox_id) => {
+                                        // Ingest message
+                                        self.email_ingest(IngestEmail {
+                                            raw_message: &raw_message,
+                                            blob_hash: Some(&message.message_blob),
+                                            message: MessageParser::new().parse(&raw_message),
+                                            access_token: &access_token,
+                                            mailbox_ids: vec![mailbox_id],
+                                            keywords: vec![],
+                                            received_at: None,
+                                            source: IngestSource::Smtp {
+                                                deliver_to: &rcpt.address,
+                                                is_sender_authenticated: message
+                                                    .sender_authenticated,
+                                                is_spam: rcpt.is_spam,
+                                            },
+                                            session_id: message.session_id,
+                                        })
+                                        .await
+                                    }
+                                    Err(err) => Err(err),
+                                }
+                            }
+                            Ok(Some(active_script)) => {
+                                self.sieve_script_ingest(
+                                    &access_token,
+                                    &message.message_blob,
+                                    &raw_message,
+                                    &message.sender_address,
+                                    message.sender_authenticated,
+                                    &rcpt,
+                                    message.session_id,
+                                    active_script,
+                                    &mut result.autogenerated,
+                                )
+                                .await
+                            }
+                            Err(err) => Err(err),
                         }
-                        Err(err) => Err(err),
                     }
                 }
 
@@ -200,8 +236,10 @@ impl MailDelivery for Server {
 
             let status = match status {
                 Ok(ingested_message) => {
-                    // Notify state change
-                    if ingested_message.change_id != u64::MAX {
+                    // Notify state change, unless the account's notification rules say otherwise
+                    if ingested_message.change_id != u64::MAX
+                        && self.should_notify(account_id, &raw_message).await
+                    {
                         self.broadcast_push_notification(PushNotification::EmailPush(EmailPush {
                             account_id,
                             email_id: ingested_message.document_id,
@@ -269,3 +307,93 @@ impl MailDelivery for Server {
         result
     }
 }
+
+impl IngestRecipient {
+    pub fn original_address(&self) -> Cow<'_, str> {
+        match (&self.subaddress, self.address.rsplit_once('@')) {
+            (Some(subaddress), Some((local_part, domain))) => {
+                Cow::Owned(format!("{local_part}+{subaddress}@{domain}"))
+            }
+            _ => Cow::Borrowed(self.address.as_str()),
+        }
+    }
+}
+
+// Messages addressed to user+folder@domain are filed into the matching
+// mailbox when the domain allows it, otherwise they are delivered to the Inbox
+// Queued messages are kept in the queue blob store, which may be separate from
+// the data blob store. They are copied to the data blob store before delivery,
+// as ingestion links the message under the same hash.
+async fn fetch_message_blob(server: &Server, hash: &BlobHash) -> trc::Result<Option<Vec<u8>>> {
+    let Some(queue_blob) = &server.core.storage.queue_blob else {
+        return server
+            .blob_store()
+            .get_blob(hash.as_slice(), 0..usize::MAX)
+            .await
+            .caused_by(trc::location!());
+    };
+
+    match queue_blob
+        .get_blob(hash.as_slice(), 0..usize::MAX)
+        .await
+        .caused_by(trc::location!())?
+    {
+        Some(raw_message) => {
+            server
+                .put_temporary_blob(u32::MAX, &raw_message, DELIVERY_BLOB_HOLD)
+                .await
+                .caused_by(trc::location!())?;
+            Ok(Some(raw_message))
+        }
+        // Messages that were not queued are staged in the data blob store
+        None => server
+            .blob_store()
+            .get_blob(hash.as_slice(), 0..usize::MAX)
+            .await
+            .caused_by(trc::location!()),
+    }
+}
+
+async fn subaddress_mailbox_id(
+    server: &Server,
+    access_token: &AccessToken,
+    rcpt: &IngestRecipient,
+) -> trc::Result<u32> {
+    let Some(subaddress) = rcpt.subaddress.as_deref().filter(|_| !rcpt.is_spam) else {
+        return Ok(INBOX_ID);
+    };
+    let Some(domain) = server
+        .domain(rcpt.address.domain_part())
+        .await?
+        .filter(|domain| domain.flags & DOMAIN_FLAG_SUB_ADDRESSING_FOLDERS != 0)
+    else {
+        return Ok(INBOX_ID);
+    };
+
+    // Special-use mailboxes are never targeted, so senders cannot file
+    // messages into folders such as Sent or Trash
+    let account_id = access_token.account_id();
+    let cache = server
+        .get_cached_messages(account_id)
+        .await
+        .caused_by(trc::location!())?;
+    if let Some(mailbox) = cache.mailbox_by_path(subaddress) {
+        return Ok(if mailbox.role == SpecialUse::None {
+            mailbox.document_id
+        } else {
+            INBOX_ID
+        });
+    }
+
+    if domain.flags & DOMAIN_FLAG_SUB_ADDRESSING_CREATE != 0
+        && access_token.has_permission(Permission::EmailReceiveCreateMailbox)
+        && let Some(mailbox_id) = server
+            .mailbox_create_path(account_id, subaddress)
+            .await
+            .caused_by(trc::location!())?
+    {
+        return Ok(mailbox_id);
+    }
+
+    Ok(INBOX_ID)
+}
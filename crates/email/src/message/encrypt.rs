@@ -0,0 +1,175 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{
+    crypto::{EncryptMessage, EncryptMessageError},
+    metadata::{MessageData, MessageMetadata, build_metadata_contents},
+};
+use common::{Server, auth::AccountCache, storage::index::ObjectIndexBuilder};
+use mail_parser::MessageParser;
+use registry::schema::{
+    enums::IndexDocumentType,
+    structs::{Task, TaskIndexDocument, TaskStatus},
+};
+use std::future::Future;
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, BatchBuilder, BlobLink, BlobOp},
+};
+use trc::AddContext;
+use types::{blob_hash::BlobHash, collection::Collection, field::EmailField};
+
+pub trait EmailEncryption: Sync + Send {
+    fn email_encrypt(
+        &self,
+        account: &AccountCache,
+        document_id: u32,
+    ) -> impl Future<Output = trc::Result<bool>> + Send;
+}
+
+impl EmailEncryption for Server {
+    // Returns whether the message was stored in plain text and is now encrypted
+    async fn email_encrypt(&self, account: &AccountCache, document_id: u32) -> trc::Result<bool> {
+        let account_id = account.id;
+        let Some(encrypt_keys) = &account.encryption_key else {
+            return Ok(false);
+        };
+        let Some(data_) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::Email,
+                document_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(false);
+        };
+        let Some(metadata_) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Email,
+                document_id,
+                EmailField::Metadata,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(false);
+        };
+        let data = data_
+            .to_unarchived::<MessageData>()
+            .caused_by(trc::location!())?;
+        let metadata = metadata_
+            .to_unarchived::<MessageMetadata>()
+            .caused_by(trc::location!())?;
+        let previous_blob_hash = BlobHash::from(&metadata.inner.blob_hash);
+        let raw_body = self
+            .blob_store()
+            .get_blob(previous_blob_hash.as_slice(), 0..usize::MAX)
+            .await
+            .caused_by(trc::location!())?
+            .ok_or_else(|| {
+                trc::StoreEvent::NotFound
+                    .into_err()
+                    .details("Blob not found.")
+                    .caused_by(trc::location!())
+            })?;
+
+        // Headers added at delivery time are kept in the metadata rather than
+        // in the blob, both are combined so they end up inside the envelope
+        let raw_body = raw_body
+            .get(metadata.inner.blob_body_offset.to_native() as usize..)
+            .unwrap_or_default();
+        let mut raw_message = Vec::with_capacity(metadata.inner.raw_headers.len() + raw_body.len());
+        raw_message.extend_from_slice(metadata.inner.raw_headers.as_ref());
+        raw_message.extend_from_slice(raw_body);
+        let Some(message) = MessageParser::new().parse(&raw_message) else {
+            return Err(trc::StoreEvent::UnexpectedError
+                .into_err()
+                .details("Failed to parse message")
+                .caused_by(trc::location!()));
+        };
+        if message.is_encrypted() {
+            return Ok(false);
+        }
+        let encrypted = match message.encrypt(encrypt_keys, account.flags).await {
+            Ok(encrypted) => encrypted,
+            Err(EncryptMessageError::AlreadyEncrypted) => return Ok(false),
+            Err(EncryptMessageError::Error(err)) => {
+                return Err(trc::StoreEvent::CryptoError
+                    .into_err()
+                    .caused_by(trc::location!())
+                    .reason(err));
+            }
+        };
+        let Some(message) = MessageParser::new().parse(&encrypted) else {
+            return Err(trc::StoreEvent::UnexpectedError
+                .into_err()
+                .details("Failed to parse encrypted message")
+                .caused_by(trc::location!()));
+        };
+        let (blob_hash, blob_hold) = self
+            .put_temporary_blob(account_id, &encrypted, 60)
+            .await
+            .caused_by(trc::location!())?;
+
+        let root_part = message.root_part();
+        let blob_body_offset = root_part.offset_body;
+        let raw_headers = encrypted
+            .get(root_part.offset_header as usize..root_part.offset_body as usize)
+            .unwrap_or_default()
+            .to_vec()
+            .into_boxed_slice();
+        let new_metadata = MessageMetadata {
+            preview: Default::default(),
+            raw_headers,
+            contents: build_metadata_contents(message),
+            blob_hash,
+            blob_body_offset,
+            rcvd_attach: metadata.inner.rcvd_attach.to_native(),
+        };
+        let mut new_data = data.inner.to_builder();
+        new_data.size = encrypted.len() as u32;
+
+        // The search index is rebuilt from the encrypted message so that
+        // terms from the original contents are no longer searchable
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Email)
+            .with_document(document_id)
+            .custom(
+                ObjectIndexBuilder::new()
+                    .with_tenant_id(account.tenant_id())
+                    .with_current(data)
+                    .with_changes(new_data.seal()),
+            )
+            .caused_by(trc::location!())?
+            .clear(BlobOp::Link {
+                hash: previous_blob_hash,
+                to: BlobLink::Document,
+            });
+        new_metadata
+            .index(&mut batch, true)
+            .caused_by(trc::location!())?;
+        batch
+            .clear(blob_hold)
+            .schedule_task(Task::IndexDocument(TaskIndexDocument {
+                account_id: account_id.into(),
+                document_id: document_id.into(),
+                document_type: IndexDocumentType::Email,
+                status: TaskStatus::now(),
+            }));
+
+        self.commit_batch(batch).await.caused_by(trc::location!())?;
+        self.notify_task_queue();
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,162 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::metadata::{ArchivedMessageData, MessageDataBuilder};
+use common::Server;
+use std::future::Future;
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder, now},
+};
+use trc::AddContext;
+use types::{collection::Collection, field::EmailField, keyword::Keyword};
+
+// Keeps track of who changed the keywords or mailboxes of a message, which
+// is mostly useful in shared mailboxes. Only the most recent changes are
+// retained, as configured by the data retention settings.
+#[derive(rkyv::Serialize, rkyv::Deserialize, rkyv::Archive, Debug, Default, Clone)]
+pub struct EmailChangeLog {
+    pub changes: Vec<EmailChange>,
+}
+
+#[derive(rkyv::Serialize, rkyv::Deserialize, rkyv::Archive, Debug, Clone, PartialEq, Eq)]
+pub struct EmailChange {
+    pub changed_at: u64,
+    pub changed_by: u32,
+    pub protocol: EmailChangeProtocol,
+    pub keywords_added: Vec<Keyword>,
+    pub keywords_removed: Vec<Keyword>,
+    pub mailboxes_added: Vec<u32>,
+    pub mailboxes_removed: Vec<u32>,
+}
+
+#[derive(rkyv::Serialize, rkyv::Deserialize, rkyv::Archive, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailChangeProtocol {
+    Jmap,
+    Imap,
+}
+
+pub trait EmailChangeHistory: Sync + Send {
+    fn email_changes(
+        &self,
+        account_id: u32,
+        document_id: u32,
+    ) -> impl Future<Output = trc::Result<Vec<EmailChange>>> + Send;
+
+    fn log_email_change(
+        &self,
+        batch: &mut BatchBuilder,
+        account_id: u32,
+        document_id: u32,
+        change: Option<EmailChange>,
+    ) -> impl Future<Output = trc::Result<()>> + Send;
+}
+
+impl EmailChangeHistory for Server {
+    async fn email_changes(
+        &self,
+        account_id: u32,
+        document_id: u32,
+    ) -> trc::Result<Vec<EmailChange>> {
+        self.store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Email,
+                document_id,
+                EmailField::ChangeLog,
+            ))
+            .await
+            .caused_by(trc::location!())?
+            .map(|log| {
+                log.deserialize::<EmailChangeLog>()
+                    .map(|log| log.changes)
+                    .caused_by(trc::location!())
+            })
+            .unwrap_or_else(|| Ok(Vec::new()))
+    }
+
+    async fn log_email_change(
+        &self,
+        batch: &mut BatchBuilder,
+        account_id: u32,
+        document_id: u32,
+        change: Option<EmailChange>,
+    ) -> trc::Result<()> {
+        let (Some(max_changes), Some(change)) = (self.core.email.email_change_max_history, change)
+        else {
+            return Ok(());
+        };
+
+        let mut changes = self
+            .email_changes(account_id, document_id)
+            .await
+            .caused_by(trc::location!())?;
+        changes.push(change);
+        if changes.len() > max_changes {
+            changes.drain(..changes.len() - max_changes);
+        }
+
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Email)
+            .with_document(document_id)
+            .set(
+                EmailField::ChangeLog,
+                Archiver::new(EmailChangeLog { changes })
+                    .serialize()
+                    .caused_by(trc::location!())?,
+            );
+
+        Ok(())
+    }
+}
+
+impl EmailChange {
+    pub fn new(
+        changed_by: u32,
+        protocol: EmailChangeProtocol,
+        prev_data: &ArchivedMessageData,
+        new_data: &MessageDataBuilder,
+    ) -> Option<Self> {
+        let change = EmailChange {
+            changed_at: now(),
+            changed_by,
+            protocol,
+            keywords_added: new_data.added_keywords(prev_data).cloned().collect(),
+            keywords_removed: new_data
+                .removed_keywords(prev_data)
+                .map(|keyword| keyword.to_native())
+                .collect(),
+            mailboxes_added: new_data
+                .added_mailboxes(prev_data)
+                .map(|mailbox| mailbox.mailbox_id)
+                .collect(),
+            mailboxes_removed: new_data
+                .removed_mailboxes(prev_data)
+                .map(|mailbox| mailbox.mailbox_id.to_native())
+                .collect(),
+        };
+
+        if !change.keywords_added.is_empty()
+            || !change.keywords_removed.is_empty()
+            || !change.mailboxes_added.is_empty()
+            || !change.mailboxes_removed.is_empty()
+        {
+            Some(change)
+        } else {
+            None
+        }
+    }
+}
+
+impl EmailChangeProtocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmailChangeProtocol::Jmap => "jmap",
+            EmailChangeProtocol::Imap => "imap",
+        }
+    }
+}
@@ -0,0 +1,140 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::ImportMessage;
+use std::{
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+use types::keyword::Keyword;
+
+pub struct MaildirReader {
+    files: std::vec::IntoIter<(PathBuf, bool)>,
+    keywords: Vec<Option<Keyword>>,
+}
+
+pub fn is_maildir(path: &Path) -> bool {
+    path.join("cur").is_dir() || path.join("new").is_dir()
+}
+
+impl MaildirReader {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        let mut files = Vec::new();
+        for (dir, is_new) in [("cur", false), ("new", true)] {
+            let dir = path.join(dir);
+            if !dir.is_dir() {
+                continue;
+            }
+            for entry in std::fs::read_dir(dir)? {
+                let entry = entry?;
+                if entry.file_type()?.is_file()
+                    && !entry.file_name().to_string_lossy().starts_with('.')
+                {
+                    files.push((entry.path(), is_new));
+                }
+            }
+        }
+
+        // Unique names start with the delivery timestamp, which keeps the original order
+        files.sort_unstable_by(|a, b| a.0.file_name().cmp(&b.0.file_name()));
+
+        // Dovecot maps lowercase flag letters to the keywords listed in this file
+        let mut keywords = vec![None; 26];
+        if let Ok(contents) = std::fs::read_to_string(path.join("dovecot-keywords")) {
+            for line in contents.lines() {
+                if let Some((idx, name)) = line.trim().split_once(' ')
+                    && let Ok(idx) = idx.parse::<usize>()
+                    && idx < keywords.len()
+                    && !name.trim().is_empty()
+                {
+                    keywords[idx] = Some(Keyword::parse(name.trim()));
+                }
+            }
+        }
+
+        Ok(MaildirReader {
+            files: files.into_iter(),
+            keywords,
+        })
+    }
+
+    fn parse_flags(&self, file_name: &str) -> Vec<Keyword> {
+        let mut keywords = Vec::new();
+        let Some(flags) = file_name
+            .rsplit_once(":2,")
+            .or_else(|| file_name.rsplit_once("!2,"))
+            .map(|(_, flags)| flags)
+        else {
+            return keywords;
+        };
+
+        for ch in flags.chars() {
+            let keyword = match ch {
+                'P' => Keyword::Forwarded,
+                'R' => Keyword::Answered,
+                'S' => Keyword::Seen,
+                'T' => Keyword::Deleted,
+                'D' => Keyword::Draft,
+                'F' => Keyword::Flagged,
+                'a'..='z' => {
+                    match self
+                        .keywords
+                        .get((ch as u8 - b'a') as usize)
+                        .cloned()
+                        .flatten()
+                    {
+                        Some(keyword) => keyword,
+                        None => continue,
+                    }
+                }
+                _ => continue,
+            };
+            if !keywords.contains(&keyword) {
+                keywords.push(keyword);
+            }
+        }
+
+        keywords
+    }
+}
+
+impl Iterator for MaildirReader {
+    type Item = Result<ImportMessage, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, is_new) = self.files.next()?;
+        let contents = match std::fs::read(&path) {
+            Ok(contents) => contents,
+            Err(err) => return Some(Err(format!("Failed to read {}: {err}", path.display()))),
+        };
+        let file_name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+
+        // Dovecot keeps the received date in the file modification time
+        let received_at = std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|modified| modified.as_secs())
+            .or_else(|| {
+                file_name
+                    .split_once('.')
+                    .and_then(|(timestamp, _)| timestamp.parse().ok())
+            });
+
+        Some(Ok(ImportMessage {
+            keywords: if is_new {
+                Vec::new()
+            } else {
+                self.parse_flags(file_name)
+            },
+            contents,
+            received_at,
+        }))
+    }
+}
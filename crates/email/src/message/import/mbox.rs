@@ -0,0 +1,119 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::ImportMessage;
+use mail_parser::mailbox::mbox::MessageIterator;
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+};
+use types::keyword::Keyword;
+
+pub struct MboxReader {
+    messages: MessageIterator<BufReader<File>>,
+}
+
+pub fn is_mbox(path: &Path) -> bool {
+    let mut header = [0u8; 5];
+    File::open(path)
+        .and_then(|mut file| file.read_exact(&mut header))
+        .is_ok_and(|_| &header == b"From ")
+}
+
+impl MboxReader {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        Ok(MboxReader {
+            messages: MessageIterator::new(BufReader::new(File::open(path)?)),
+        })
+    }
+}
+
+impl Iterator for MboxReader {
+    type Item = Result<ImportMessage, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.messages.next()? {
+            Ok(message) => {
+                let received_at = message.internal_date();
+                let contents = message.unwrap_contents();
+                Some(Ok(ImportMessage {
+                    keywords: parse_status_headers(&contents),
+                    contents,
+                    received_at: (received_at != 0).then_some(received_at),
+                }))
+            }
+            Err(_) => Some(Err("Failed to parse mbox message".to_string())),
+        }
+    }
+}
+
+/// Obtains the message flags stored by mbox clients in the Status, X-Status,
+/// X-Mozilla-Status and keyword headers.
+fn parse_status_headers(contents: &[u8]) -> Vec<Keyword> {
+    let mut keywords = Vec::new();
+
+    for line in contents.split(|&ch| ch == b'\n') {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        if line.is_empty() {
+            break;
+        }
+        let Some((name, value)) = std::str::from_utf8(line)
+            .ok()
+            .and_then(|line| line.split_once(':'))
+        else {
+            continue;
+        };
+        let value = value.trim();
+
+        match name.to_ascii_lowercase().as_str() {
+            "status" => {
+                if value.contains('R') {
+                    keywords.push(Keyword::Seen);
+                }
+            }
+            "x-status" => {
+                for ch in value.chars() {
+                    match ch {
+                        'A' => keywords.push(Keyword::Answered),
+                        'F' => keywords.push(Keyword::Flagged),
+                        'T' => keywords.push(Keyword::Draft),
+                        'D' => keywords.push(Keyword::Deleted),
+                        _ => {}
+                    }
+                }
+            }
+            "x-mozilla-status" => {
+                if let Ok(status) = u16::from_str_radix(value, 16) {
+                    for (flag, keyword) in [
+                        (0x0001, Keyword::Seen),
+                        (0x0002, Keyword::Answered),
+                        (0x0004, Keyword::Flagged),
+                        (0x0008, Keyword::Deleted),
+                        (0x1000, Keyword::Forwarded),
+                    ] {
+                        if status & flag != 0 {
+                            keywords.push(keyword);
+                        }
+                    }
+                }
+            }
+            "x-keywords" | "x-mozilla-keys" => {
+                keywords.extend(
+                    value
+                        .split([',', ' '])
+                        .filter(|keyword| !keyword.is_empty())
+                        .map(Keyword::parse),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    keywords.sort_unstable();
+    keywords.dedup();
+    keywords
+}
@@ -0,0 +1,454 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{
+    index::IndexMessage,
+    ingest::{EmailIngest, ThreadInfo, received_date, thread_references},
+    metadata::MessageData,
+};
+use crate::{
+    cache::{MessageCacheFetch, mailbox::MailboxCacheAccess},
+    mailbox::{INBOX_ID, UidMailbox, manage::MailboxFnc},
+};
+use common::Server;
+use mail_parser::MessageParser;
+use registry::{
+    schema::{
+        enums::IndexDocumentType,
+        structs::{Task, TaskIndexDocument, TaskMergeThreads, TaskStatus},
+    },
+    types::map::Map,
+};
+use serde::Serialize;
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use store::write::{AssignedId, BatchBuilder, IndexPropertyClass, ValueClass, now};
+use trc::AddContext;
+use types::{
+    collection::{Collection, SyncCollection},
+    field::{EmailField, MailboxField},
+    keyword::Keyword,
+};
+
+pub mod maildir;
+pub mod mbox;
+
+const IMPORT_BATCH_MESSAGES: usize = 100;
+const IMPORT_BATCH_SIZE: usize = 16 * 1024 * 1024;
+
+pub struct ImportMessage {
+    pub contents: Vec<u8>,
+    pub keywords: Vec<Keyword>,
+    pub received_at: Option<u64>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub folders: u64,
+    pub messages: u64,
+    pub failed: u64,
+    pub size: u64,
+    pub elapsed_ms: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
+
+/// A folder found while scanning a Maildir or mbox tree. Nested folders
+/// use `/` as the hierarchy separator and an empty name stands for the Inbox.
+#[derive(Debug)]
+pub struct ImportFolder {
+    pub name: String,
+    pub source: ImportSource,
+}
+
+#[derive(Debug)]
+pub enum ImportSource {
+    Maildir(PathBuf),
+    Mbox(PathBuf),
+}
+
+pub trait EmailImport: Sync + Send {
+    fn email_import_path(
+        &self,
+        account_id: u32,
+        path: &Path,
+    ) -> impl Future<Output = trc::Result<ImportReport>> + Send;
+
+    fn email_import_batch(
+        &self,
+        account_id: u32,
+        mailbox_id: u32,
+        messages: Vec<ImportMessage>,
+        report: &mut ImportReport,
+    ) -> impl Future<Output = trc::Result<()>> + Send;
+}
+
+impl EmailImport for Server {
+    async fn email_import_path(&self, account_id: u32, path: &Path) -> trc::Result<ImportReport> {
+        let start_time = Instant::now();
+        let mut report = ImportReport::default();
+        let folders = discover_folders(path).map_err(|err| {
+            trc::EventType::Resource(trc::ResourceEvent::Error)
+                .reason(err)
+                .details("Failed to read import path")
+                .ctx(trc::Key::Path, path.display().to_string())
+        })?;
+        let max_size = self.core.email.mail_max_size;
+
+        for folder in folders {
+            let Some(mailbox_id) = import_mailbox_id(self, account_id, &folder.name).await? else {
+                report
+                    .errors
+                    .push(format!("Failed to create mailbox {:?}", folder.name));
+                continue;
+            };
+            report.folders += 1;
+
+            let reader = match &folder.source {
+                ImportSource::Maildir(path) => {
+                    maildir::MaildirReader::open(path).map(FolderReader::Maildir)
+                }
+                ImportSource::Mbox(path) => mbox::MboxReader::open(path).map(FolderReader::Mbox),
+            };
+            let reader = match reader {
+                Ok(reader) => reader,
+                Err(err) => {
+                    report
+                        .errors
+                        .push(format!("Failed to read folder {:?}: {err}", folder.name));
+                    continue;
+                }
+            };
+
+            let mut messages = Vec::new();
+            let mut batch_size = 0;
+            for message in reader {
+                match message {
+                    Ok(message) if message.contents.len() <= max_size => {
+                        batch_size += message.contents.len();
+                        messages.push(message);
+                    }
+                    Ok(_) => {
+                        report.failed += 1;
+                    }
+                    Err(err) => {
+                        report.failed += 1;
+                        report.errors.push(err);
+                    }
+                }
+
+                if messages.len() >= IMPORT_BATCH_MESSAGES || batch_size >= IMPORT_BATCH_SIZE {
+                    self.email_import_batch(
+                        account_id,
+                        mailbox_id,
+                        std::mem::take(&mut messages),
+                        &mut report,
+                    )
+                    .await?;
+                    batch_size = 0;
+                }
+            }
+
+            if !messages.is_empty() {
+                self.email_import_batch(account_id, mailbox_id, messages, &mut report)
+                    .await?;
+            }
+        }
+
+        report.elapsed_ms = start_time.elapsed().as_millis() as u64;
+
+        Ok(report)
+    }
+
+    async fn email_import_batch(
+        &self,
+        account_id: u32,
+        mailbox_id: u32,
+        messages: Vec<ImportMessage>,
+        report: &mut ImportReport,
+    ) -> trc::Result<()> {
+        let account = self.account(account_id).await.caused_by(trc::location!())?;
+        self.has_available_quota(
+            &account,
+            messages
+                .iter()
+                .map(|message| message.contents.len() as u64)
+                .sum(),
+        )
+        .await
+        .caused_by(trc::location!())?;
+
+        let mut parsed_messages = Vec::with_capacity(messages.len());
+        for message in &messages {
+            if let Some(parsed) = MessageParser::new().parse(&message.contents) {
+                parsed_messages.push((message, parsed));
+            } else {
+                report.failed += 1;
+            }
+        }
+        if parsed_messages.is_empty() {
+            return Ok(());
+        }
+
+        // Reserve document ids and IMAP UIDs for the whole batch in a single write
+        let num_messages = parsed_messages.len() as i64;
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Email)
+            .add_and_get(ValueClass::DocumentId, num_messages)
+            .with_collection(Collection::Mailbox)
+            .with_document(mailbox_id)
+            .add_and_get(MailboxField::UidCounter, num_messages);
+        let ids = self
+            .store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())?;
+        let (mut document_id, mut uid) = match ids.ids.as_slice() {
+            [
+                AssignedId::Counter(last_document_id),
+                AssignedId::Counter(last_uid),
+            ] => (
+                (last_document_id - num_messages + 1) as u32,
+                (last_uid - num_messages + 1) as u32,
+            ),
+            _ => {
+                return Err(trc::StoreEvent::UnexpectedError
+                    .caused_by(trc::location!())
+                    .ctx(trc::Key::Reason, "Not all document ids were generated"));
+            }
+        };
+
+        // Messages are not threaded against each other until the merge task runs,
+        // which is the same tradeoff made for IMAP appends
+        let tenant_id = account.id_tenant;
+        let mut batch = BatchBuilder::new();
+        batch.with_account_id(account_id);
+        for (message, parsed) in parsed_messages {
+            let (_, message_ids, subject) = thread_references(&parsed);
+            let thread_result = self
+                .find_thread_id(account_id, subject, &message_ids)
+                .await?;
+            let received_at = message
+                .received_at
+                .or_else(|| received_date(&parsed))
+                .unwrap_or_else(now);
+            let (blob_hash, blob_hold) = self
+                .put_temporary_blob(account_id, &message.contents, 600)
+                .await
+                .caused_by(trc::location!())?;
+
+            let thread_id = if let Some(thread_id) = thread_result.thread_id {
+                thread_id
+            } else {
+                batch
+                    .with_collection(Collection::Thread)
+                    .with_document(document_id)
+                    .log_container_insert(SyncCollection::Thread);
+                document_id
+            };
+
+            let size = parsed.raw_message.len();
+            batch
+                .with_collection(Collection::Email)
+                .with_document(document_id)
+                .index_message(
+                    tenant_id,
+                    parsed,
+                    Vec::new(),
+                    Vec::new(),
+                    blob_hash,
+                    MessageData {
+                        mailboxes: vec![UidMailbox::new(mailbox_id, uid)].into_boxed_slice(),
+                        keywords: message.keywords.clone().into_boxed_slice(),
+                        thread_id,
+                        size: size as u32,
+                    },
+                    received_at,
+                )
+                .caused_by(trc::location!())?
+                .set(
+                    ValueClass::IndexProperty(IndexPropertyClass::Hash {
+                        property: EmailField::Threading.into(),
+                        hash: thread_result.thread_hash,
+                    }),
+                    ThreadInfo::serialize(thread_id, &message_ids),
+                )
+                .schedule_task(Task::IndexDocument(TaskIndexDocument {
+                    account_id: account_id.into(),
+                    document_id: document_id.into(),
+                    document_type: IndexDocumentType::Email,
+                    status: TaskStatus::now(),
+                }))
+                .schedule_task(Task::MergeThreads(TaskMergeThreads {
+                    account_id: account_id.into(),
+                    status: TaskStatus::now(),
+                    thread_name: thread_result.thread_hash.to_string(),
+                    message_ids: Map::new(
+                        message_ids.into_iter().map(|id| id.to_string()).collect(),
+                    ),
+                }))
+                .clear(blob_hold)
+                .commit_point();
+
+            report.messages += 1;
+            report.size += size as u64;
+            document_id += 1;
+            uid += 1;
+        }
+
+        self.commit_batch(batch).await.caused_by(trc::location!())?;
+        self.notify_task_queue();
+
+        trc::event!(
+            MessageIngest(trc::MessageIngestEvent::JmapAppend),
+            AccountId = account_id,
+            MailboxId = mailbox_id,
+            Total = num_messages,
+            Details = "Bulk import",
+        );
+
+        Ok(())
+    }
+}
+
+enum FolderReader {
+    Maildir(maildir::MaildirReader),
+    Mbox(mbox::MboxReader),
+}
+
+impl Iterator for FolderReader {
+    type Item = Result<ImportMessage, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            FolderReader::Maildir(reader) => reader.next(),
+            FolderReader::Mbox(reader) => reader.next(),
+        }
+    }
+}
+
+async fn import_mailbox_id(
+    server: &Server,
+    account_id: u32,
+    name: &str,
+) -> trc::Result<Option<u32>> {
+    if name.is_empty() || name.eq_ignore_ascii_case("inbox") {
+        return Ok(Some(INBOX_ID));
+    }
+
+    // Well-known top-level folders are imported into the matching special-use mailbox
+    if !name.contains('/')
+        && let Some(folder) = server.core.email.default_folders.iter().find(|folder| {
+            folder.name.eq_ignore_ascii_case(name)
+                || folder
+                    .aliases
+                    .iter()
+                    .any(|alias| alias.eq_ignore_ascii_case(name))
+        })
+        && let Some(mailbox) = server
+            .get_cached_messages(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .mailbox_by_role(&folder.special_use)
+    {
+        return Ok(Some(mailbox.document_id));
+    }
+
+    server.mailbox_create_path(account_id, name).await
+}
+
+/// Scans a path for Maildir folders (Maildir++ or nested layout), mbox files
+/// and Thunderbird style `.sbd` mbox hierarchies.
+pub fn discover_folders(path: &Path) -> std::io::Result<Vec<ImportFolder>> {
+    let mut folders = Vec::new();
+
+    if path.is_file() {
+        let name = path
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        folders.push(ImportFolder {
+            name: if name.eq_ignore_ascii_case("mbox") {
+                String::new()
+            } else {
+                name.to_string()
+            },
+            source: ImportSource::Mbox(path.to_path_buf()),
+        });
+    } else {
+        discover_directory(path, "", &mut folders)?;
+    }
+
+    Ok(folders)
+}
+
+fn discover_directory(
+    path: &Path,
+    prefix: &str,
+    folders: &mut Vec<ImportFolder>,
+) -> std::io::Result<()> {
+    let is_maildir = maildir::is_maildir(path);
+    if is_maildir {
+        folders.push(ImportFolder {
+            name: prefix.to_string(),
+            source: ImportSource::Maildir(path.to_path_buf()),
+        });
+    }
+
+    let mut entries = std::fs::read_dir(path)?
+        .filter_map(|entry| entry.ok())
+        .collect::<Vec<_>>();
+    entries.sort_unstable_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let entry_path = entry.path();
+        let Some(file_name) = entry.file_name().to_str().map(|name| name.to_string()) else {
+            continue;
+        };
+
+        if entry_path.is_dir() {
+            if is_maildir && matches!(file_name.as_str(), "cur" | "new" | "tmp") {
+                continue;
+            }
+
+            let name = if let Some(name) = file_name.strip_prefix('.') {
+                // Maildir++ subfolders use dots as the hierarchy separator
+                if !is_maildir || name.is_empty() || !maildir::is_maildir(&entry_path) {
+                    continue;
+                }
+                name.replace('.', "/")
+            } else {
+                file_name
+                    .strip_suffix(".sbd")
+                    .unwrap_or(&file_name)
+                    .to_string()
+            };
+            discover_directory(&entry_path, &join_name(prefix, &name), folders)?;
+        } else if !is_maildir && !file_name.starts_with('.') && mbox::is_mbox(&entry_path) {
+            let name = file_name.strip_suffix(".mbox").unwrap_or(&file_name);
+            folders.push(ImportFolder {
+                name: join_name(prefix, name),
+                source: ImportSource::Mbox(entry_path),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn join_name(prefix: &str, name: &str) -> String {
+    if prefix.is_empty() {
+        name.to_string()
+    } else {
+        format!("{prefix}/{name}")
+    }
+}
@@ -0,0 +1,313 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use flate2::read::ZlibDecoder;
+use quick_xml::{Reader, events::Event};
+use std::io::{Cursor, Read};
+
+const MAX_EXTRACTED_TEXT: usize = 1024 * 1024;
+const MAX_DECOMPRESSED_SIZE: u64 = 16 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentFormat {
+    Pdf,
+    Docx,
+    Xlsx,
+}
+
+impl AttachmentFormat {
+    pub fn detect(subtype: Option<&str>, file_name: Option<&str>) -> Option<Self> {
+        let from_subtype = subtype.and_then(|subtype| {
+            hashify::tiny_map_ignore_case!(subtype.as_bytes(),
+                "pdf" => AttachmentFormat::Pdf,
+                "x-pdf" => AttachmentFormat::Pdf,
+                "vnd.openxmlformats-officedocument.wordprocessingml.document" => AttachmentFormat::Docx,
+                "vnd.openxmlformats-officedocument.spreadsheetml.sheet" => AttachmentFormat::Xlsx,
+            )
+        });
+
+        // Fallback to the file extension for generic content types
+        from_subtype.or_else(|| {
+            file_name
+                .and_then(|name| name.rsplit_once('.'))
+                .and_then(|(_, ext)| {
+                    hashify::tiny_map_ignore_case!(ext.as_bytes(),
+                        "pdf" => AttachmentFormat::Pdf,
+                        "docx" => AttachmentFormat::Docx,
+                        "xlsx" => AttachmentFormat::Xlsx,
+                    )
+                })
+        })
+    }
+
+    pub fn extract_text(&self, contents: &[u8]) -> Option<String> {
+        let text = match self {
+            AttachmentFormat::Pdf => extract_pdf(contents),
+            AttachmentFormat::Docx => extract_ooxml(contents, |name| {
+                name == "word/document.xml"
+                    || (name.starts_with("word/header") || name.starts_with("word/footer"))
+                        && name.ends_with(".xml")
+            }),
+            AttachmentFormat::Xlsx => extract_ooxml(contents, |name| {
+                name == "xl/sharedStrings.xml"
+                    || name.starts_with("xl/worksheets/sheet") && name.ends_with(".xml")
+            }),
+        }?;
+
+        (!text.trim().is_empty()).then_some(text)
+    }
+}
+
+fn extract_ooxml(contents: &[u8], filter: impl Fn(&str) -> bool) -> Option<String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(contents)).ok()?;
+    let mut text = String::new();
+
+    for idx in 0..archive.len() {
+        let Ok(file) = archive.by_index(idx) else {
+            continue;
+        };
+        if !filter(file.name()) || file.size() > MAX_DECOMPRESSED_SIZE {
+            continue;
+        }
+        let mut xml = Vec::with_capacity(file.size() as usize);
+        if file
+            .take(MAX_DECOMPRESSED_SIZE)
+            .read_to_end(&mut xml)
+            .is_ok()
+        {
+            extract_xml_text(&xml, &mut text);
+            if text.len() >= MAX_EXTRACTED_TEXT {
+                break;
+            }
+        }
+    }
+
+    Some(text)
+}
+
+fn extract_xml_text(xml: &[u8], text: &mut String) {
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+    let mut in_text = false;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            // Text runs in both WordprocessingML and SpreadsheetML
+            Ok(Event::Start(tag)) => {
+                in_text = matches!(tag.local_name().as_ref(), b"t");
+            }
+            Ok(Event::End(tag)) => {
+                in_text = false;
+                if matches!(tag.local_name().as_ref(), b"p" | b"si" | b"c" | b"tc") {
+                    push_separator(text);
+                }
+            }
+            Ok(Event::Empty(tag)) => {
+                if matches!(tag.local_name().as_ref(), b"tab" | b"br") {
+                    push_separator(text);
+                }
+            }
+            Ok(Event::Text(value)) if in_text => {
+                if let Ok(value) = value.xml_content() {
+                    text.push_str(&value);
+                }
+            }
+            Ok(Event::GeneralRef(entity)) if in_text => {
+                let entity_ref: &[u8] = entity.as_ref();
+                let value = hashify::tiny_map!(entity_ref,
+                    "lt" => "<",
+                    "gt" => ">",
+                    "amp" => "&",
+                    "apos" => "'",
+                    "quot" => "\"",
+                );
+                if let Some(value) = value {
+                    text.push_str(value);
+                } else if let Ok(Some(ch)) = entity.resolve_char_ref() {
+                    text.push(ch);
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            _ => (),
+        }
+
+        if text.len() >= MAX_EXTRACTED_TEXT {
+            break;
+        }
+        buf.clear();
+    }
+}
+
+fn extract_pdf(contents: &[u8]) -> Option<String> {
+    if !contents.starts_with(b"%PDF-") {
+        return None;
+    }
+
+    let mut text = String::new();
+    let mut pos = 0;
+
+    while let Some(start) = find(&contents[pos..], b"stream").map(|idx| idx + pos) {
+        // Skip the "endstream" keyword
+        if start >= 3 && &contents[start - 3..start] == b"end" {
+            pos = start + 6;
+            continue;
+        }
+
+        let mut data_start = start + 6;
+        if contents.get(data_start) == Some(&b'\r') {
+            data_start += 1;
+        }
+        if contents.get(data_start) == Some(&b'\n') {
+            data_start += 1;
+        }
+        let Some(data_end) =
+            find(&contents[data_start..], b"endstream").map(|idx| idx + data_start)
+        else {
+            break;
+        };
+        pos = data_end + 9;
+
+        // Only plain and deflated content streams are supported
+        let dict_start = start.saturating_sub(1024);
+        let dict = &contents[dict_start..start];
+        let dict = dict
+            .windows(3)
+            .rposition(|w| w == b"obj")
+            .map_or(dict, |idx| &dict[idx..]);
+        if find(dict, b"/Subtype").is_some() || find(dict, b"/Type").is_some() {
+            continue;
+        }
+        let data = &contents[data_start..data_end];
+        let stream = if find(dict, b"/FlateDecode").is_some() {
+            let mut decoded = Vec::new();
+            if ZlibDecoder::new(data)
+                .take(MAX_DECOMPRESSED_SIZE)
+                .read_to_end(&mut decoded)
+                .is_err()
+                && decoded.is_empty()
+            {
+                continue;
+            }
+            decoded
+        } else if find(dict, b"/Filter").is_none() {
+            data.to_vec()
+        } else {
+            continue;
+        };
+
+        extract_pdf_content(&stream, &mut text);
+        if text.len() >= MAX_EXTRACTED_TEXT {
+            break;
+        }
+    }
+
+    Some(text)
+}
+
+fn extract_pdf_content(stream: &[u8], text: &mut String) {
+    let mut in_text = false;
+    let mut pending = String::new();
+    let mut iter = stream.iter().copied().peekable();
+    let mut token = Vec::new();
+
+    while let Some(ch) = iter.next() {
+        match ch {
+            b'(' if in_text => {
+                let mut depth = 1;
+                while let Some(ch) = iter.next() {
+                    match ch {
+                        b'\\' => match iter.next() {
+                            Some(b'n') => pending.push('\n'),
+                            Some(b'r') => pending.push('\r'),
+                            Some(b't') => pending.push('\t'),
+                            Some(ch @ b'0'..=b'7') => {
+                                let mut value = (ch - b'0') as u32;
+                                for _ in 0..2 {
+                                    match iter.peek() {
+                                        Some(ch @ b'0'..=b'7') => {
+                                            value = value * 8 + (ch - b'0') as u32;
+                                            iter.next();
+                                        }
+                                        _ => break,
+                                    }
+                                }
+                                if let Some(ch) = char::from_u32(value & 0xff) {
+                                    pending.push(ch);
+                                }
+                            }
+                            Some(b'\r' | b'\n') | None => (),
+                            Some(ch) => pending.push(ch as char),
+                        },
+                        b'(' => {
+                            depth += 1;
+                            pending.push('(');
+                        }
+                        b')' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                            pending.push(')');
+                        }
+                        ch => pending.push(ch as char),
+                    }
+                }
+            }
+            b'-' | b'0'..=b'9' | b'.' if in_text && token.is_empty() => {
+                // Large negative kerning in TJ arrays usually separates words
+                let mut number = vec![ch];
+                while let Some(ch @ (b'0'..=b'9' | b'.')) = iter.peek().copied() {
+                    number.push(ch);
+                    iter.next();
+                }
+                if ch == b'-'
+                    && std::str::from_utf8(&number)
+                        .ok()
+                        .and_then(|n| n.parse::<f64>().ok())
+                        .is_some_and(|n| n < -200.0)
+                {
+                    pending.push(' ');
+                }
+            }
+            b'A'..=b'Z' | b'a'..=b'z' | b'\'' | b'"' | b'*' => {
+                token.push(ch);
+            }
+            _ => {
+                if !token.is_empty() {
+                    match token.as_slice() {
+                        b"BT" => in_text = true,
+                        b"ET" => {
+                            in_text = false;
+                            push_separator(text);
+                        }
+                        b"Tj" | b"TJ" | b"'" | b"\"" => {
+                            text.push_str(&pending);
+                            if matches!(token.as_slice(), b"'" | b"\"") {
+                                push_separator(text);
+                            }
+                        }
+                        b"Td" | b"TD" | b"T*" | b"Tm" => push_separator(text),
+                        _ => (),
+                    }
+                    pending.clear();
+                    token.clear();
+                }
+            }
+        }
+    }
+}
+
+fn push_separator(text: &mut String) {
+    if !text.is_empty() && !text.ends_with(' ') {
+        text.push(' ');
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
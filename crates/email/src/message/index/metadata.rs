@@ -11,6 +11,7 @@ use crate::message::{
         MESSAGE_HAS_ATTACHMENT, MESSAGE_RECEIVED_MASK, MessageData, MessageMetadata,
         MessageMetadataPart, build_metadata_contents,
     },
+    summary::MessageSummary,
 };
 use common::storage::index::ObjectIndexBuilder;
 use mail_parser::{
@@ -42,6 +43,10 @@ impl MessageMetadata {
                     },
                     Vec::new(),
                 )
+                .set(
+                    EmailField::Summary,
+                    Archiver::new(MessageSummary::from(&self)).serialize()?,
+                )
                 .set(EmailField::Metadata, Archiver::new(self).serialize()?);
         } else {
             batch
@@ -49,7 +54,8 @@ impl MessageMetadata {
                     hash: self.blob_hash.clone(),
                     to: BlobLink::Document,
                 })
-                .clear(EmailField::Metadata);
+                .clear(EmailField::Metadata)
+                .clear(EmailField::Summary);
         }
 
         Ok(())
@@ -82,6 +88,8 @@ impl ArchivedMessageMetadata {
 
         batch
             .clear(EmailField::Metadata)
+            .clear(EmailField::Summary)
+            .clear(EmailField::ChangeLog)
             .clear(ValueClass::IndexProperty(IndexPropertyClass::Hash {
                 property: EmailField::Threading.into(),
                 hash: CheekyHash::new(if !thread_name.is_empty() {
@@ -239,6 +247,12 @@ impl IndexMessage for BatchBuilder {
                 .with_changes(data),
         )
         .caused_by(trc::location!())?
+        .set(
+            EmailField::Summary,
+            Archiver::new(MessageSummary::from(&metadata))
+                .serialize()
+                .caused_by(trc::location!())?,
+        )
         .set(
             EmailField::Metadata,
             Archiver::new(metadata)
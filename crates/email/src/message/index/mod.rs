@@ -12,6 +12,7 @@ use common::storage::index::{IndexItem, IndexValue, IndexableObject};
 use store::write::now;
 use types::{blob_hash::BlobHash, collection::SyncCollection, field::EmailField};
 
+pub mod attachment;
 pub mod extractors;
 pub mod metadata;
 pub mod search;
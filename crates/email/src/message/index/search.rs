@@ -5,7 +5,7 @@
  */
 
 use crate::message::{
-    index::{MAX_MESSAGE_PARTS, extractors::VisitTextArchived},
+    index::{MAX_MESSAGE_PARTS, attachment::AttachmentFormat, extractors::VisitTextArchived},
     metadata::{
         ArchivedMessageMetadata, ArchivedMetadataHeaderName, ArchivedMetadataHeaderValue,
         ArchivedMetadataPartType, DecodedPartContent, MESSAGE_HAS_ATTACHMENT,
@@ -35,6 +35,7 @@ impl ArchivedMessageMetadata {
         document_id: u32,
         raw_message: &[u8],
         index_fields: &AHashSet<SearchField>,
+        index_attachments: bool,
         default_language: Language,
     ) -> IndexDocument {
         let mut detector = LanguageDetector::new();
@@ -326,6 +327,30 @@ impl ArchivedMessageMetadata {
                         }
                     }
                 }
+                ArchivedMetadataPartType::Binary | ArchivedMetadataPartType::InlineBinary
+                    if index_attachments
+                        && (index_fields.is_empty()
+                            || index_fields
+                                .contains(&SearchField::Email(EmailSearchField::Attachment))) =>
+                {
+                    if let Some(format) = AttachmentFormat::detect(
+                        part.content_type().and_then(|ct| ct.subtype()),
+                        part.attachment_name(),
+                    ) && let DecodedPartContent::Binary(contents) =
+                        part.decode_contents(&raw_message)
+                        && let Some(text) = format.extract_text(contents.as_ref())
+                    {
+                        if part_language.is_unknown() {
+                            detector.detect(text.as_ref(), MIN_LANGUAGE_SCORE);
+                        }
+
+                        document.index_text(
+                            SearchField::Email(EmailSearchField::Attachment),
+                            text.as_ref(),
+                            part_language,
+                        );
+                    }
+                }
                 _ => {}
             }
         }
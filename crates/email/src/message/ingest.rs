@@ -6,6 +6,7 @@
 
 use super::crypto::{EncryptMessage, EncryptMessageError};
 use crate::{
+    autocrypt::AutocryptStore,
     cache::{MessageCacheFetch, email::MessageCacheAccess, mailbox::MailboxCacheAccess},
     mailbox::{INBOX_ID, JUNK_ID, SENT_ID, TRASH_ID, UidMailbox},
     message::{
@@ -14,7 +15,12 @@ use crate::{
         metadata::{MessageData, MessageMetadata},
     },
 };
-use common::{Server, auth::AccessToken};
+use common::{
+    KV_RATE_LIMIT_SPAM_TRAIN, Server,
+    auth::AccessToken,
+    ingest::{IngestContext, IngestMailbox, is_valid_header},
+    telemetry::metrics::breakdown::BreakdownMetric,
+};
 use groupware::{
     calendar::itip::{ItipIngest, ItipIngestError},
     scheduling::{ItipError, ItipMessages},
@@ -25,7 +31,7 @@ use mail_parser::{
 };
 use registry::{
     schema::{
-        enums::IndexDocumentType,
+        enums::{EncryptionPolicy, IndexDocumentType},
         prelude::{ObjectType, Permission, Property},
         structs::{SpamTrainingSample, Task, TaskIndexDocument, TaskMergeThreads, TaskStatus},
     },
@@ -161,48 +167,10 @@ impl EmailIngest for Server {
         })?;
 
         // Obtain message references and thread name
-        let mut message_id = None;
-        let mut message_ids = Vec::new();
-        let thread_result = {
-            let mut subject = "";
-            for header in message.root_part().headers().iter().rev() {
-                match &header.name {
-                    HeaderName::MessageId => header.value.visit_text(|id| {
-                        if !id.is_empty() {
-                            if message_id.is_none() {
-                                message_id = id.to_string().into();
-                            }
-                            message_ids.push(CheekyHash::new(id.as_bytes()));
-                        }
-                    }),
-                    HeaderName::InReplyTo
-                    | HeaderName::References
-                    | HeaderName::ResentMessageId => {
-                        header.value.visit_text(|id| {
-                            if !id.is_empty() {
-                                message_ids.push(CheekyHash::new(id.as_bytes()));
-                            }
-                        });
-                    }
-                    HeaderName::Subject if subject.is_empty() => {
-                        subject = thread_name(match &header.value {
-                            HeaderValue::Text(text) => text.as_ref(),
-                            HeaderValue::TextList(list) if !list.is_empty() => {
-                                list.first().unwrap().as_ref()
-                            }
-                            _ => "",
-                        });
-                    }
-                    _ => (),
-                }
-            }
-
-            message_ids.sort_unstable();
-            message_ids.dedup();
-
-            self.find_thread_id(account_id, subject, &message_ids)
-                .await?
-        };
+        let (message_id, message_ids, subject) = thread_references(&message);
+        let thread_result = self
+            .find_thread_id(account_id, subject, &message_ids)
+            .await?;
 
         // Skip duplicate messages for SMTP ingestion
         if !thread_result.duplicate_ids.is_empty() && params.source.is_smtp() {
@@ -360,10 +328,12 @@ impl EmailIngest for Server {
                     }
                 }
 
-                // iMIP processing
-                if self.core.groupware.itip_enabled
+                // iMIP processing, invitations are only imported as tentative events
+                // when they would have been processed
+                let can_process_itip = self.core.groupware.itip_enabled && is_sender_authenticated;
+                let can_import_itip = can_process_itip && account.calendar_auto_import();
+                if can_process_itip
                     && !is_spam
-                    && is_sender_authenticated
                     && params
                         .access_token
                         .has_permission(Permission::CalendarSchedulingReceive)
@@ -383,6 +353,7 @@ impl EmailIngest for Server {
                         }) && let Some(itip_message) = part.text_contents()
                         {
                             if itip_message.len() < self.core.groupware.itip_inbound_max_ical_size {
+                                let mut import_itip = false;
                                 if let Some(sender) = sender.get_or_insert_with(|| {
                                     message
                                         .from()
@@ -414,6 +385,9 @@ impl EmailIngest for Server {
                                             match itip_error {
                                                 ItipError::NothingToSend
                                                 | ItipError::OtherSchedulingAgent => (),
+                                                ItipError::AutoAddDisabled if can_import_itip => {
+                                                    import_itip = true;
+                                                }
                                                 err => {
                                                     trc::event!(
                                                         Calendar(
@@ -432,6 +406,39 @@ impl EmailIngest for Server {
                                         }
                                     }
                                 }
+
+                                // Add new invitations as tentative events
+                                if import_itip {
+                                    match self
+                                        .itip_import_tentative(
+                                            &account_info,
+                                            itip_message,
+                                            message_id.as_deref(),
+                                        )
+                                        .await
+                                    {
+                                        Ok(true) => {
+                                            trc::event!(
+                                                Calendar(trc::CalendarEvent::ItipMessageReceived),
+                                                SpanId = params.session_id,
+                                                AccountId = account_id,
+                                                Details = "Invitation added as tentative event",
+                                            );
+                                        }
+                                        Ok(false) => (),
+                                        Err(ItipIngestError::Message(err)) => {
+                                            trc::event!(
+                                                Calendar(trc::CalendarEvent::ItipMessageError),
+                                                SpanId = params.session_id,
+                                                AccountId = account_id,
+                                                Details = err.to_string(),
+                                            )
+                                        }
+                                        Err(ItipIngestError::Internal(err)) => {
+                                            trc::error!(err.caused_by(trc::location!()));
+                                        }
+                                    }
+                                }
                             } else {
                                 trc::event!(
                                     Calendar(trc::CalendarEvent::ItipMessageError),
@@ -451,6 +458,14 @@ impl EmailIngest for Server {
                     }
                 }
 
+                // Autocrypt peer keys
+                if is_sender_authenticated
+                    && !is_spam
+                    && let Err(err) = self.autocrypt_ingest(account_id, &message).await
+                {
+                    trc::error!(err.span_id(params.session_id).caused_by(trc::location!()));
+                }
+
                 is_spam
             }
             IngestSource::Jmap { train_classifier } | IngestSource::Imap { train_classifier } => {
@@ -471,20 +486,7 @@ impl EmailIngest for Server {
 
                 // Set receivedAt if not present
                 if params.received_at.is_none() {
-                    params.received_at = message
-                        .root_part()
-                        .headers()
-                        .iter()
-                        .filter_map(|header| {
-                            if let (HeaderName::Received, HeaderValue::Received(received)) =
-                                (&header.name, &header.value)
-                            {
-                                received.date.map(|dt| dt.to_timestamp() as u64)
-                            } else {
-                                None
-                            }
-                        })
-                        .max();
+                    params.received_at = received_date(&message);
                 }
 
                 false
@@ -492,6 +494,112 @@ impl EmailIngest for Server {
             _ => false,
         };
 
+        // Run ingest extensions
+        let extensions = self.inner.data.ingest_extensions.load_full();
+        if !extensions.is_empty() && !matches!(params.source, IngestSource::Restore) {
+            let deliver_to = match params.source {
+                IngestSource::Smtp { deliver_to, .. } => Some(deliver_to),
+                _ => None,
+            };
+
+            for extension in extensions.iter() {
+                let action = match extension
+                    .on_ingest(
+                        self,
+                        IngestContext {
+                            account_id,
+                            session_id: params.session_id,
+                            deliver_to,
+                            is_spam,
+                            message: &message,
+                            mailbox_ids: &params.mailbox_ids,
+                            keywords: &params.keywords,
+                        },
+                    )
+                    .await
+                {
+                    Ok(action) if !action.is_empty() => action,
+                    Ok(_) => continue,
+                    Err(err) => {
+                        trc::error!(
+                            err.span_id(params.session_id)
+                                .account_id(account_id)
+                                .details(extension.name().to_string())
+                                .caused_by(trc::location!())
+                        );
+                        continue;
+                    }
+                };
+
+                for (name, value) in action.headers {
+                    if !is_valid_header(&name, &value) {
+                        trc::event!(
+                            MessageIngest(MessageIngestEvent::Error),
+                            SpanId = params.session_id,
+                            AccountId = account_id,
+                            Details = extension.name().to_string(),
+                            Reason = format!("Invalid header {name:?}"),
+                        );
+                        continue;
+                    }
+
+                    let offset_field = extra_headers.len();
+                    let offset_start = offset_field + name.len() + 1;
+                    let _ = write!(&mut extra_headers, "{name}: {value}\r\n");
+                    extra_headers_parsed.push(Header {
+                        name: HeaderName::Other(name.into()),
+                        value: HeaderValue::Text(value.into()),
+                        offset_field: offset_field as u32,
+                        offset_start: offset_start as u32,
+                        offset_end: extra_headers.len() as u32,
+                    });
+                }
+
+                for keyword in action.keywords {
+                    if !params.keywords.contains(&keyword) {
+                        params.keywords.push(keyword);
+                    }
+                }
+
+                if let Some(mailboxes) = action.mailboxes {
+                    let cache = self
+                        .get_cached_messages(account_id)
+                        .await
+                        .caused_by(trc::location!())?;
+                    let mut mailbox_ids = Vec::with_capacity(mailboxes.len());
+                    for mailbox in mailboxes {
+                        let mailbox_id = match &mailbox {
+                            IngestMailbox::Id(id) => cache.has_mailbox_id(id).then_some(*id),
+                            IngestMailbox::Path(path) => {
+                                cache.mailbox_by_path(path).map(|m| m.document_id)
+                            }
+                            IngestMailbox::Role(role) => {
+                                cache.mailbox_by_role(role).map(|m| m.document_id)
+                            }
+                        };
+
+                        if let Some(mailbox_id) = mailbox_id {
+                            if !mailbox_ids.contains(&mailbox_id) {
+                                mailbox_ids.push(mailbox_id);
+                            }
+                        } else {
+                            trc::event!(
+                                MessageIngest(MessageIngestEvent::Error),
+                                SpanId = params.session_id,
+                                AccountId = account_id,
+                                Details = extension.name().to_string(),
+                                Reason = format!("Mailbox {mailbox:?} not found"),
+                            );
+                        }
+                    }
+
+                    if !mailbox_ids.is_empty() {
+                        params.mailbox_ids = mailbox_ids;
+                    }
+                }
+            }
+        }
+
         // Encrypt message
         let do_encrypt = match params.source {
             IngestSource::Jmap { .. } | IngestSource::Imap { .. } => {
@@ -500,8 +608,14 @@ impl EmailIngest for Server {
             IngestSource::Smtp { .. } => self.core.email.encrypt,
             IngestSource::Restore => false,
         };
-        let is_encrypted = if do_encrypt
-            && !message.is_encrypted()
+        let encryption_policy = if do_encrypt && !message.is_encrypted() {
+            self.encryption_policy(&account)
+                .await
+                .caused_by(trc::location!())?
+        } else {
+            EncryptionPolicy::Disabled
+        };
+        let is_encrypted = if encryption_policy != EncryptionPolicy::Disabled
             && let Some(encrypt_keys) = &account.encryption_key
         {
             match message.encrypt(encrypt_keys, account.flags).await {
@@ -542,7 +656,9 @@ impl EmailIngest for Server {
 
                     true
                 }
-                Err(EncryptMessageError::Error(err)) => {
+                Err(EncryptMessageError::Error(err))
+                    if encryption_policy == EncryptionPolicy::Required =>
+                {
                     trc::bail!(
                         trc::StoreEvent::CryptoError
                             .into_err()
@@ -550,9 +666,28 @@ impl EmailIngest for Server {
                             .reason(err)
                     );
                 }
+                Err(EncryptMessageError::Error(err)) => {
+                    // Stored in plain text, the message is picked up by the
+                    // next encryption task scheduled for the account
+                    trc::event!(
+                        MessageIngest(MessageIngestEvent::NotEncrypted),
+                        AccountId = account_id,
+                        Reason = err,
+                        SpanId = params.session_id,
+                    );
+                    false
+                }
                 _ => unreachable!(),
             }
         } else {
+            if encryption_policy == EncryptionPolicy::Required {
+                trc::event!(
+                    MessageIngest(MessageIngestEvent::NotEncrypted),
+                    AccountId = account_id,
+                    Reason = "No encryption key configured for the account.",
+                    SpanId = params.session_id,
+                );
+            }
             false
         };
 
@@ -710,6 +845,13 @@ impl EmailIngest for Server {
             Elapsed = start_time.elapsed(),
         );
 
+        if let IngestSource::Smtp { deliver_to, .. } = params.source
+            && let Some((_, domain)) = deliver_to.rsplit_once('@')
+        {
+            self.record_metrics_breakdown(BreakdownMetric::MessageVolume, domain)
+                .await;
+        }
+
         Ok(IngestedEmail {
             document_id,
             thread_id,
@@ -873,17 +1015,38 @@ impl EmailIngest for Server {
         is_spam: bool,
         span_id: u64,
     ) -> trc::Result<()> {
-        if self.core.spam.classifier.is_some()
-            && let Some(archive) = self
-                .store()
-                .get_value::<Archive<AlignedBytes>>(ValueKey::property(
-                    account_id,
-                    Collection::Email,
-                    document_id,
-                    EmailField::Metadata,
-                ))
+        let Some(config) = &self.core.spam.classifier else {
+            return Ok(());
+        };
+
+        // Junk and not-junk reports are throttled per account
+        if let Some(rate) = &config.train_rate
+            && self
+                .is_rate_allowed(KV_RATE_LIMIT_SPAM_TRAIN, &account_id.to_be_bytes(), rate)
                 .await
                 .caused_by(trc::location!())?
+                .is_some()
+        {
+            trc::event!(
+                Spam(SpamEvent::TrainSampleThrottled),
+                AccountId = account_id,
+                DocumentId = document_id,
+                Details = if is_spam { "spam" } else { "ham" },
+                SpanId = span_id,
+            );
+            return Ok(());
+        }
+
+        if let Some(archive) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Email,
+                document_id,
+                EmailField::Metadata,
+            ))
+            .await
+            .caused_by(trc::location!())?
         {
             let metadata = archive
                 .to_unarchived::<MessageMetadata>()
@@ -969,6 +1132,69 @@ impl EmailIngest for Server {
     }
 }
 
+/// Returns the first Message-ID of a message, the sorted hashes of all the
+/// message ids it references and its thread name.
+pub(crate) fn thread_references<'x>(
+    message: &'x Message<'x>,
+) -> (Option<String>, Vec<CheekyHash>, &'x str) {
+    let mut message_id = None;
+    let mut message_ids = Vec::new();
+    let mut subject = "";
+
+    for header in message.root_part().headers().iter().rev() {
+        match &header.name {
+            HeaderName::MessageId => header.value.visit_text(|id| {
+                if !id.is_empty() {
+                    if message_id.is_none() {
+                        message_id = id.to_string().into();
+                    }
+                    message_ids.push(CheekyHash::new(id.as_bytes()));
+                }
+            }),
+            HeaderName::InReplyTo | HeaderName::References | HeaderName::ResentMessageId => {
+                header.value.visit_text(|id| {
+                    if !id.is_empty() {
+                        message_ids.push(CheekyHash::new(id.as_bytes()));
+                    }
+                });
+            }
+            HeaderName::Subject if subject.is_empty() => {
+                subject = thread_name(match &header.value {
+                    HeaderValue::Text(text) => text.as_ref(),
+                    HeaderValue::TextList(list) if !list.is_empty() => {
+                        list.first().unwrap().as_ref()
+                    }
+                    _ => "",
+                });
+            }
+            _ => (),
+        }
+    }
+
+    message_ids.sort_unstable();
+    message_ids.dedup();
+
+    (message_id, message_ids, subject)
+}
+
+/// Obtains the most recent date found in the Received headers of a message.
+pub(crate) fn received_date(message: &Message<'_>) -> Option<u64> {
+    message
+        .root_part()
+        .headers()
+        .iter()
+        .filter_map(|header| {
+            if let (HeaderName::Received, HeaderValue::Received(received)) =
+                (&header.name, &header.value)
+            {
+                received.date.map(|dt| dt.to_timestamp() as u64)
+            } else {
+                None
+            }
+        })
+        .max()
+}
+
 pub fn has_message_id(a: &[CheekyHash], b: &[u8]) -> bool {
     let mut i = 0;
     let mut j = 0;
@@ -0,0 +1,161 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::cache::{MessageCacheFetch, mailbox::MailboxCacheAccess};
+use common::Server;
+use std::future::Future;
+use store::{
+    IterateParams, U32_LEN, ValueKey,
+    ahash::AHashMap,
+    write::{BatchBuilder, ValueClass, key::DeserializeBigEndian, now},
+};
+use trc::AddContext;
+use types::{collection::Collection, field::MailboxField};
+
+// Messages under legal hold cannot be destroyed or expunged. A hold can be
+// placed on the whole account or on individual mailboxes, in which case it
+// covers every message filed in them.
+#[derive(Debug, Default)]
+pub enum LegalHold {
+    #[default]
+    None,
+    Account,
+    Mailboxes(AHashMap<u32, u64>),
+}
+
+pub trait LegalHoldManagement: Sync + Send {
+    fn legal_hold(&self, account_id: u32) -> impl Future<Output = trc::Result<LegalHold>> + Send;
+
+    fn mailbox_legal_holds(
+        &self,
+        account_id: u32,
+    ) -> impl Future<Output = trc::Result<AHashMap<u32, u64>>> + Send;
+
+    fn set_mailbox_legal_hold(
+        &self,
+        account_id: u32,
+        mailbox_id: u32,
+        hold: bool,
+    ) -> impl Future<Output = trc::Result<bool>> + Send;
+}
+
+impl LegalHold {
+    pub fn is_none(&self) -> bool {
+        matches!(self, LegalHold::None)
+    }
+
+    pub fn is_mailbox_held(&self, mailbox_id: u32) -> bool {
+        match self {
+            LegalHold::None => false,
+            LegalHold::Account => true,
+            LegalHold::Mailboxes(mailboxes) => mailboxes.contains_key(&mailbox_id),
+        }
+    }
+
+    // Whether the mailbox itself is held, messages filed in it cannot be moved
+    // out of it. An account hold only prevents messages from leaving the account.
+    pub fn is_mailbox_hold(&self, mailbox_id: u32) -> bool {
+        matches!(self, LegalHold::Mailboxes(mailboxes) if mailboxes.contains_key(&mailbox_id))
+    }
+
+    pub fn is_message_held(&self, mut mailbox_ids: impl Iterator<Item = u32>) -> bool {
+        match self {
+            LegalHold::None => false,
+            LegalHold::Account => true,
+            LegalHold::Mailboxes(mailboxes) => mailbox_ids.any(|id| mailboxes.contains_key(&id)),
+        }
+    }
+}
+
+impl LegalHoldManagement for Server {
+    async fn legal_hold(&self, account_id: u32) -> trc::Result<LegalHold> {
+        if self
+            .account(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .is_legal_hold()
+        {
+            return Ok(LegalHold::Account);
+        }
+
+        let mailboxes = self
+            .mailbox_legal_holds(account_id)
+            .await
+            .caused_by(trc::location!())?;
+        if !mailboxes.is_empty() {
+            Ok(LegalHold::Mailboxes(mailboxes))
+        } else {
+            Ok(LegalHold::None)
+        }
+    }
+
+    async fn mailbox_legal_holds(&self, account_id: u32) -> trc::Result<AHashMap<u32, u64>> {
+        let mut mailboxes = AHashMap::new();
+        self.store()
+            .iterate(
+                IterateParams::new(
+                    ValueKey {
+                        account_id,
+                        collection: Collection::Mailbox.into(),
+                        document_id: 0,
+                        class: ValueClass::Property(MailboxField::LegalHold.into()),
+                    },
+                    ValueKey {
+                        account_id,
+                        collection: Collection::Mailbox.into(),
+                        document_id: u32::MAX,
+                        class: ValueClass::Property(MailboxField::LegalHold.into()),
+                    },
+                )
+                .ascending(),
+                |key, value| {
+                    mailboxes.insert(
+                        key.deserialize_be_u32(key.len() - U32_LEN)?,
+                        value.deserialize_be_u64(0)?,
+                    );
+
+                    Ok(true)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(mailboxes)
+    }
+
+    async fn set_mailbox_legal_hold(
+        &self,
+        account_id: u32,
+        mailbox_id: u32,
+        hold: bool,
+    ) -> trc::Result<bool> {
+        if !self
+            .get_cached_messages(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .has_mailbox_id(&mailbox_id)
+        {
+            return Ok(false);
+        }
+
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Mailbox)
+            .with_document(mailbox_id);
+        if hold {
+            batch.set(MailboxField::LegalHold, now().to_be_bytes().to_vec());
+        } else {
+            batch.clear(MailboxField::LegalHold);
+        }
+        self.store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(true)
+    }
+}
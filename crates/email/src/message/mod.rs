@@ -8,6 +8,13 @@ pub mod copy;
 pub mod crypto;
 pub mod delete;
 pub mod delivery;
+pub mod encrypt;
+pub mod history;
+pub mod import;
 pub mod index;
 pub mod ingest;
+pub mod legal_hold;
 pub mod metadata;
+pub mod redact;
+pub mod retention;
+pub mod summary;
@@ -0,0 +1,215 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{
+    index::PREVIEW_LENGTH,
+    metadata::{MESSAGE_RECEIVED_MASK, MessageData, MessageMetadata, build_metadata_contents},
+};
+use common::{Server, storage::index::ObjectIndexBuilder};
+use mail_parser::{DateTime, MessageParser, parsers::preview::preview_text};
+use registry::schema::{
+    enums::IndexDocumentType,
+    structs::{Task, TaskIndexDocument, TaskStatus},
+};
+use std::future::Future;
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, BatchBuilder, BlobLink, BlobOp, now},
+};
+use trc::AddContext;
+use types::{blob_hash::BlobHash, collection::Collection, field::EmailField};
+
+const REDACTED_TEXT: &str = "This message has been redacted by an administrator.\r\n";
+
+pub struct RedactedEmail {
+    pub thread_id: u32,
+    pub previous_blob_hash: BlobHash,
+    pub previous_size: u32,
+    pub blob_hash: BlobHash,
+    pub size: u32,
+    pub change_id: u64,
+}
+
+pub trait EmailRedaction: Sync + Send {
+    fn email_redact(
+        &self,
+        account_id: u32,
+        document_id: u32,
+    ) -> impl Future<Output = trc::Result<Option<RedactedEmail>>> + Send;
+}
+
+impl EmailRedaction for Server {
+    async fn email_redact(
+        &self,
+        account_id: u32,
+        document_id: u32,
+    ) -> trc::Result<Option<RedactedEmail>> {
+        let Some(data_) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::Email,
+                document_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(None);
+        };
+        let Some(metadata_) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Email,
+                document_id,
+                EmailField::Metadata,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(None);
+        };
+        let data = data_
+            .to_unarchived::<MessageData>()
+            .caused_by(trc::location!())?;
+        let metadata = metadata_
+            .to_unarchived::<MessageMetadata>()
+            .caused_by(trc::location!())?;
+
+        // The tombstone keeps the original headers so that senders, recipients,
+        // dates and message ids remain available for auditing
+        let tombstone = build_tombstone(&metadata.inner.raw_headers);
+        let Some(message) = MessageParser::new().parse(&tombstone) else {
+            return Err(trc::StoreEvent::UnexpectedError
+                .into_err()
+                .details("Failed to parse redacted message")
+                .caused_by(trc::location!()));
+        };
+        let (blob_hash, blob_hold) = self
+            .put_temporary_blob(account_id, &tombstone, 60)
+            .await
+            .caused_by(trc::location!())?;
+
+        let root_part = message.root_part();
+        let blob_body_offset = root_part.offset_body;
+        let raw_headers = tombstone
+            .get(root_part.offset_header as usize..root_part.offset_body as usize)
+            .unwrap_or_default()
+            .to_vec()
+            .into_boxed_slice();
+        let previous_blob_hash = BlobHash::from(&metadata.inner.blob_hash);
+        let previous_size = data.inner.size.to_native();
+        let thread_id = data.inner.thread_id.to_native();
+        let new_metadata = MessageMetadata {
+            preview: preview_text(REDACTED_TEXT.trim_end().into(), PREVIEW_LENGTH)
+                .into_owned()
+                .into_boxed_str(),
+            raw_headers,
+            contents: build_metadata_contents(message),
+            blob_hash: blob_hash.clone(),
+            blob_body_offset,
+            rcvd_attach: metadata.inner.rcvd_attach.to_native() & MESSAGE_RECEIVED_MASK,
+        };
+        let mut new_data = data.inner.to_builder();
+        new_data.size = tombstone.len() as u32;
+        let tenant_id = self
+            .account(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .tenant_id();
+
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Email)
+            .with_document(document_id)
+            .custom(
+                ObjectIndexBuilder::new()
+                    .with_tenant_id(tenant_id)
+                    .with_current(data)
+                    .with_changes(new_data.seal()),
+            )
+            .caused_by(trc::location!())?
+            .clear(BlobOp::Link {
+                hash: previous_blob_hash.clone(),
+                to: BlobLink::Document,
+            });
+        new_metadata
+            .index(&mut batch, true)
+            .caused_by(trc::location!())?;
+        batch
+            .clear(blob_hold)
+            .schedule_task(Task::IndexDocument(TaskIndexDocument {
+                account_id: account_id.into(),
+                document_id: document_id.into(),
+                document_type: IndexDocumentType::Email,
+                status: TaskStatus::now(),
+            }));
+
+        let change_id = self
+            .commit_batch(batch)
+            .await
+            .and_then(|ids| ids.last_change_id(account_id))
+            .caused_by(trc::location!())?;
+        self.notify_task_queue();
+
+        Ok(Some(RedactedEmail {
+            thread_id,
+            previous_blob_hash,
+            previous_size,
+            blob_hash,
+            size: tombstone.len() as u32,
+            change_id,
+        }))
+    }
+}
+
+fn build_tombstone(raw_headers: &[u8]) -> Vec<u8> {
+    let mut tombstone = Vec::with_capacity(raw_headers.len() + 256);
+    let mut skip_header = false;
+
+    // Content headers are replaced since the original body is discarded
+    for line in raw_headers.split_inclusive(|&ch| ch == b'\n') {
+        match line.first() {
+            Some(b' ' | b'\t') => {}
+            Some(b'\r' | b'\n') | None => break,
+            _ => {
+                let name = line
+                    .iter()
+                    .position(|&ch| ch == b':')
+                    .map_or(line, |pos| &line[..pos]);
+                skip_header = name
+                    .get(..8)
+                    .is_some_and(|prefix| prefix.eq_ignore_ascii_case(b"content-"))
+                    || name.eq_ignore_ascii_case(b"mime-version");
+            }
+        }
+
+        if !skip_header {
+            tombstone.extend_from_slice(line);
+            if !line.ends_with(b"\n") {
+                tombstone.extend_from_slice(b"\r\n");
+            }
+        }
+    }
+
+    tombstone.extend_from_slice(b"X-Redacted-At: ");
+    tombstone.extend_from_slice(
+        DateTime::from_timestamp(now() as i64)
+            .to_rfc822()
+            .as_bytes(),
+    );
+    tombstone.extend_from_slice(
+        concat!(
+            "\r\nMIME-Version: 1.0\r\n",
+            "Content-Type: text/plain; charset=utf-8\r\n",
+            "Content-Transfer-Encoding: 7bit\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    tombstone.extend_from_slice(REDACTED_TEXT.as_bytes());
+    tombstone
+}
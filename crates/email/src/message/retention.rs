@@ -0,0 +1,340 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{
+    delete::EmailDeletion,
+    ingest::EmailIngest,
+    legal_hold::{LegalHold, LegalHoldManagement},
+    metadata::MessageData,
+};
+use crate::{
+    cache::{MessageCacheFetch, email::MessageCacheAccess, mailbox::MailboxCacheAccess},
+    mailbox::{JUNK_ID, TRASH_ID, UidMailbox, manage::MailboxFnc},
+};
+use common::{MessageStoreCache, Server, storage::index::ObjectIndexBuilder};
+use registry::schema::{enums::RetentionAction, structs::RetentionPolicy};
+use std::future::Future;
+use store::{
+    ValueKey,
+    ahash::AHashSet,
+    roaring::RoaringBitmap,
+    search::{EmailSearchField, SearchFilter, SearchQuery},
+    write::{AlignedBytes, Archive, BatchBuilder, SearchIndex, now},
+};
+use trc::AddContext;
+use types::{
+    collection::{Collection, VanishedCollection},
+    keyword::Keyword,
+    special_use::SpecialUse,
+};
+
+#[derive(Debug, Clone)]
+pub struct RetentionResult {
+    pub policy: String,
+    pub action: RetentionAction,
+    pub messages: u64,
+    pub size: u64,
+}
+
+pub trait EmailRetention: Sync + Send {
+    fn email_retention(
+        &self,
+        account_id: u32,
+        policies: &[RetentionPolicy],
+        dry_run: bool,
+    ) -> impl Future<Output = trc::Result<Vec<RetentionResult>>> + Send;
+}
+
+impl EmailRetention for Server {
+    async fn email_retention(
+        &self,
+        account_id: u32,
+        policies: &[RetentionPolicy],
+        dry_run: bool,
+    ) -> trc::Result<Vec<RetentionResult>> {
+        let mut results = Vec::with_capacity(policies.len());
+        if policies.is_empty() {
+            return Ok(results);
+        }
+
+        let cache = self
+            .get_cached_messages(account_id)
+            .await
+            .caused_by(trc::location!())?;
+        let legal_hold = self
+            .legal_hold(account_id)
+            .await
+            .caused_by(trc::location!())?;
+
+        // Policies are evaluated in order and a message is only ever
+        // matched by the first policy that selects it
+        let mut matched_ids = RoaringBitmap::new();
+
+        for policy in policies {
+            let archive_id = match policy.action {
+                RetentionAction::Archive => cache
+                    .mailbox_by_path(&policy.archive_mailbox)
+                    .map(|mailbox| mailbox.document_id),
+                RetentionAction::Delete => None,
+            };
+            let document_ids = retention_candidates(
+                self,
+                account_id,
+                &cache,
+                &legal_hold,
+                policy,
+                archive_id,
+                &matched_ids,
+            )
+            .await
+            .caused_by(trc::location!())?;
+            let size = document_ids
+                .iter()
+                .filter_map(|document_id| cache.email_by_id(&document_id))
+                .map(|message| message.size as u64)
+                .sum();
+            let messages = document_ids.len();
+            matched_ids |= &document_ids;
+
+            if !dry_run && !document_ids.is_empty() {
+                trc::event!(
+                    Store(trc::StoreEvent::AutoExpunge),
+                    Collection = Collection::Email.as_str(),
+                    AccountId = account_id,
+                    Details = policy.name.clone(),
+                    Total = messages,
+                );
+
+                match policy.action {
+                    RetentionAction::Archive => {
+                        let archive_id = if let Some(archive_id) = archive_id {
+                            archive_id
+                        } else if let Some(archive_id) = self
+                            .mailbox_create_path(account_id, &policy.archive_mailbox)
+                            .await
+                            .caused_by(trc::location!())?
+                        {
+                            archive_id
+                        } else {
+                            trc::event!(
+                                Store(trc::StoreEvent::UnexpectedError),
+                                AccountId = account_id,
+                                Details = "Failed to create archive mailbox",
+                                Id = policy.archive_mailbox.clone(),
+                            );
+                            continue;
+                        };
+
+                        let mailbox_ids = policy_mailboxes(&cache, policy);
+                        archive_messages(self, account_id, document_ids, mailbox_ids, archive_id)
+                            .await
+                            .caused_by(trc::location!())?;
+                    }
+                    RetentionAction::Delete => {
+                        let tenant_id = self
+                            .account(account_id)
+                            .await
+                            .caused_by(trc::location!())?
+                            .tenant_id();
+                        let mut batch = BatchBuilder::new();
+                        self.emails_delete(account_id, tenant_id, &mut batch, document_ids)
+                            .await
+                            .caused_by(trc::location!())?;
+                        self.commit_batch(batch).await.caused_by(trc::location!())?;
+                        self.notify_task_queue();
+                    }
+                }
+            }
+
+            results.push(RetentionResult {
+                policy: policy.name.clone(),
+                action: policy.action,
+                messages,
+                size,
+            });
+        }
+
+        Ok(results)
+    }
+}
+
+async fn retention_candidates(
+    server: &Server,
+    account_id: u32,
+    cache: &MessageStoreCache,
+    legal_hold: &LegalHold,
+    policy: &RetentionPolicy,
+    archive_id: Option<u32>,
+    matched_ids: &RoaringBitmap,
+) -> trc::Result<RoaringBitmap> {
+    let mailbox_ids = policy_mailboxes(cache, policy);
+    if mailbox_ids.as_ref().is_some_and(|ids| ids.is_empty()) {
+        return Ok(RoaringBitmap::new());
+    }
+    let keywords = policy
+        .keywords
+        .iter()
+        .map(|keyword| Keyword::parse(keyword))
+        .collect::<Vec<_>>();
+    let exclude_keywords = policy
+        .exclude_keywords
+        .iter()
+        .map(|keyword| Keyword::parse(keyword))
+        .collect::<Vec<_>>();
+    let is_archive = policy.action == RetentionAction::Archive;
+
+    let mut mask = RoaringBitmap::new();
+    for message in cache.emails.items.iter() {
+        if matched_ids.contains(message.document_id)
+            || legal_hold.is_message_held(message.mailboxes.iter().map(|m| m.mailbox_id))
+            || !keywords.iter().all(|k| cache.has_keyword(message, k))
+            || exclude_keywords
+                .iter()
+                .any(|k| cache.has_keyword(message, k))
+        {
+            continue;
+        }
+
+        // Archiving only applies to messages that are filed outside of the archive
+        // mailbox, and with no mailboxes given, deleted and junk mail is left alone
+        let is_match = message.mailboxes.iter().any(|m| {
+            Some(m.mailbox_id) != archive_id
+                && match &mailbox_ids {
+                    Some(ids) => ids.contains(&m.mailbox_id),
+                    None => !is_archive || ![TRASH_ID, JUNK_ID].contains(&m.mailbox_id),
+                }
+        });
+        if is_match {
+            mask.insert(message.document_id);
+        }
+    }
+
+    if mask.is_empty() {
+        return Ok(mask);
+    }
+
+    let cutoff = now().saturating_sub(policy.older_than.as_secs());
+    server
+        .search_store()
+        .query_account(
+            SearchQuery::new(SearchIndex::Email)
+                .with_filter(SearchFilter::le(EmailSearchField::ReceivedAt, cutoff))
+                .with_account_id(account_id)
+                .with_mask(mask),
+        )
+        .await
+        .map(RoaringBitmap::from_iter)
+        .caused_by(trc::location!())
+}
+
+async fn archive_messages(
+    server: &Server,
+    account_id: u32,
+    document_ids: RoaringBitmap,
+    mailbox_ids: Option<AHashSet<u32>>,
+    archive_id: u32,
+) -> trc::Result<()> {
+    let mut batch = BatchBuilder::new();
+
+    for document_id in document_ids {
+        let Some(data_) = server
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::Email,
+                document_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            continue;
+        };
+        let data = data_
+            .to_unarchived::<MessageData>()
+            .caused_by(trc::location!())?;
+
+        // Move the message out of the matched mailboxes into the archive
+        let mut new_data = data.inner.to_builder();
+        let mut removed = Vec::new();
+        for mailbox in data.inner.mailboxes.iter() {
+            let mailbox_id = mailbox.mailbox_id.to_native();
+            if mailbox_id != archive_id
+                && mailbox_ids
+                    .as_ref()
+                    .map_or(![TRASH_ID, JUNK_ID].contains(&mailbox_id), |ids| {
+                        ids.contains(&mailbox_id)
+                    })
+            {
+                new_data.remove_mailbox(mailbox_id);
+                removed.push((mailbox_id, mailbox.uid.to_native()));
+            }
+        }
+        if removed.is_empty() {
+            continue;
+        }
+        if !new_data
+            .mailboxes
+            .iter()
+            .any(|m| m.mailbox_id == archive_id)
+        {
+            let mut uids = server
+                .assign_email_ids(account_id, [archive_id], false)
+                .await
+                .caused_by(trc::location!())?;
+            new_data.add_mailbox(UidMailbox::new(archive_id, uids.next().unwrap_or_default()));
+        }
+
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Email)
+            .with_document(document_id)
+            .custom(
+                ObjectIndexBuilder::new()
+                    .with_current(data)
+                    .with_changes(new_data.seal()),
+            )
+            .caused_by(trc::location!())?;
+        for item in removed {
+            batch.log_vanished_item(VanishedCollection::Email, item);
+        }
+        batch.commit_point();
+
+        if batch.is_large_batch() {
+            server
+                .commit_batch(std::mem::take(&mut batch))
+                .await
+                .caused_by(trc::location!())?;
+        }
+    }
+
+    if !batch.is_empty() {
+        server
+            .commit_batch(batch)
+            .await
+            .caused_by(trc::location!())?;
+    }
+
+    Ok(())
+}
+
+fn policy_mailboxes(cache: &MessageStoreCache, policy: &RetentionPolicy) -> Option<AHashSet<u32>> {
+    if policy.mailboxes.is_empty() {
+        return None;
+    }
+
+    Some(
+        policy
+            .mailboxes
+            .iter()
+            .filter_map(|name| {
+                SpecialUse::parse(name)
+                    .and_then(|role| cache.mailbox_by_role(&role))
+                    .or_else(|| cache.mailbox_by_path(name))
+                    .map(|mailbox| mailbox.document_id)
+            })
+            .collect(),
+    )
+}
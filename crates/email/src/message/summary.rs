@@ -0,0 +1,176 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::metadata::{
+    ArchivedMessageMetadata, ArchivedMetadataHeaderValue, MessageMetadata, MetadataHeader,
+    MetadataHeaderName, MetadataHeaderValue,
+};
+use common::Server;
+use store::{
+    Deserialize, Serialize, ValueKey,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder, rkyv_deserialize},
+};
+use trc::AddContext;
+use types::{blob_hash::BlobHash, collection::Collection, field::EmailField};
+
+// Compact copy of the top-level headers and flags most clients request when
+// listing messages, stored next to the full metadata to keep it small to read
+#[derive(rkyv::Serialize, rkyv::Deserialize, rkyv::Archive, Debug)]
+pub struct MessageSummary {
+    pub headers: Box<[MessageSummaryHeader]>,
+    pub rcvd_attach: u64,
+    pub blob_hash: BlobHash,
+    pub preview: Box<str>,
+}
+
+#[derive(rkyv::Serialize, rkyv::Deserialize, rkyv::Archive, Debug)]
+pub struct MessageSummaryHeader {
+    pub name: MetadataHeaderName,
+    pub value: MetadataHeaderValue,
+}
+
+pub trait MessageSummaryFetch: Sync + Send {
+    fn get_message_summary(
+        &self,
+        account_id: u32,
+        document_id: u32,
+    ) -> impl Future<Output = trc::Result<Option<Archive<AlignedBytes>>>> + Send;
+}
+
+const SUMMARY_HEADERS: [MetadataHeaderName; 11] = [
+    MetadataHeaderName::Subject,
+    MetadataHeaderName::From,
+    MetadataHeaderName::Sender,
+    MetadataHeaderName::To,
+    MetadataHeaderName::Cc,
+    MetadataHeaderName::Bcc,
+    MetadataHeaderName::ReplyTo,
+    MetadataHeaderName::Date,
+    MetadataHeaderName::MessageId,
+    MetadataHeaderName::InReplyTo,
+    MetadataHeaderName::References,
+];
+
+impl From<&MessageMetadata> for MessageSummary {
+    fn from(metadata: &MessageMetadata) -> Self {
+        MessageSummary {
+            headers: metadata
+                .root_part()
+                .headers
+                .iter()
+                .filter(|header| SUMMARY_HEADERS.contains(&header.name))
+                .map(|header| MessageSummaryHeader {
+                    name: header.name.clone(),
+                    value: header.value.clone(),
+                })
+                .collect(),
+            rcvd_attach: metadata.rcvd_attach,
+            blob_hash: metadata.blob_hash.clone(),
+            preview: metadata.preview.clone(),
+        }
+    }
+}
+
+impl ArchivedMessageMetadata {
+    pub fn to_summary(&self) -> trc::Result<MessageSummary> {
+        let mut headers = Vec::new();
+        for header in self.root_part().headers.iter() {
+            if SUMMARY_HEADERS.iter().any(|name| &header.name == name) {
+                let header =
+                    rkyv_deserialize::<_, MetadataHeader>(header).caused_by(trc::location!())?;
+                headers.push(MessageSummaryHeader {
+                    name: header.name,
+                    value: header.value,
+                });
+            }
+        }
+
+        Ok(MessageSummary {
+            headers: headers.into_boxed_slice(),
+            rcvd_attach: self.rcvd_attach.to_native(),
+            blob_hash: BlobHash::from(&self.blob_hash),
+            preview: self.preview.as_ref().into(),
+        })
+    }
+}
+
+impl ArchivedMessageSummary {
+    pub fn header_value(&self, name: &MetadataHeaderName) -> Option<&ArchivedMetadataHeaderValue> {
+        self.headers.iter().rev().find_map(move |header| {
+            if &header.name == name {
+                Some(&header.value)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+impl MessageSummaryFetch for Server {
+    async fn get_message_summary(
+        &self,
+        account_id: u32,
+        document_id: u32,
+    ) -> trc::Result<Option<Archive<AlignedBytes>>> {
+        if let Some(summary) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Email,
+                document_id,
+                EmailField::Summary,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        {
+            return Ok(Some(summary));
+        }
+
+        // Messages stored before summaries were introduced are rebuilt on first access
+        let Some(metadata_) = self
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Email,
+                document_id,
+                EmailField::Metadata,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(None);
+        };
+        let summary = metadata_
+            .unarchive::<MessageMetadata>()
+            .caused_by(trc::location!())?
+            .to_summary()?;
+        let summary = Archiver::new(summary)
+            .serialize()
+            .caused_by(trc::location!())?;
+
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Email)
+            .with_document(document_id)
+            .assert_value(EmailField::Metadata, &metadata_)
+            .set(EmailField::Summary, summary.clone());
+        match self.store().write(batch.build_all()).await {
+            Ok(_) => {}
+            Err(err) if err.is_assertion_failure() => {}
+            Err(err) => {
+                trc::error!(
+                    err.account_id(account_id)
+                        .document_id(document_id)
+                        .caused_by(trc::location!())
+                        .details("Failed to store message summary")
+                );
+            }
+        }
+
+        Archive::<AlignedBytes>::deserialize_owned(summary).map(Some)
+    }
+}
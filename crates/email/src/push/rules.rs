@@ -0,0 +1,245 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::Server;
+use mail_parser::{HeaderName, Message};
+use std::future::Future;
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder},
+};
+use trc::AddContext;
+use types::{collection::Collection, field::PrincipalField};
+
+// Notification rules are evaluated when a message is delivered and decide
+// whether the resulting state change is pushed to the account's subscribers
+// (PushSubscription, WebSocket, EventSource and IMAP IDLE). Rules are tried
+// in order and the first match wins; unmatched messages are always notified.
+#[derive(
+    rkyv::Archive,
+    rkyv::Deserialize,
+    rkyv::Serialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Default,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+)]
+#[serde(transparent)]
+pub struct NotificationRules {
+    pub rules: Vec<NotificationRule>,
+}
+
+#[derive(
+    rkyv::Archive,
+    rkyv::Deserialize,
+    rkyv::Serialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_list: Option<bool>,
+    pub action: NotificationAction,
+}
+
+#[derive(
+    rkyv::Archive,
+    rkyv::Deserialize,
+    rkyv::Serialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum NotificationAction {
+    Immediate,
+    Never,
+}
+
+pub trait NotificationRulesStore: Sync + Send {
+    fn notification_rules(
+        &self,
+        account_id: u32,
+    ) -> impl Future<Output = trc::Result<NotificationRules>> + Send;
+
+    fn set_notification_rules(
+        &self,
+        account_id: u32,
+        rules: NotificationRules,
+    ) -> impl Future<Output = trc::Result<()>> + Send;
+
+    fn should_notify(
+        &self,
+        account_id: u32,
+        raw_message: &[u8],
+    ) -> impl Future<Output = bool> + Send;
+}
+
+impl NotificationRulesStore for Server {
+    async fn notification_rules(&self, account_id: u32) -> trc::Result<NotificationRules> {
+        self.store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Principal,
+                0,
+                PrincipalField::NotificationRules,
+            ))
+            .await
+            .caused_by(trc::location!())?
+            .map(|rules| {
+                rules
+                    .deserialize::<NotificationRules>()
+                    .caused_by(trc::location!())
+            })
+            .unwrap_or_else(|| Ok(NotificationRules::default()))
+    }
+
+    async fn set_notification_rules(
+        &self,
+        account_id: u32,
+        rules: NotificationRules,
+    ) -> trc::Result<()> {
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Principal)
+            .with_document(0);
+        if rules.rules.is_empty() {
+            batch.clear(PrincipalField::NotificationRules);
+        } else {
+            batch.set(
+                PrincipalField::NotificationRules,
+                Archiver::new(rules)
+                    .serialize()
+                    .caused_by(trc::location!())?,
+            );
+        }
+
+        self.store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())
+            .map(|_| ())
+    }
+
+    async fn should_notify(&self, account_id: u32, raw_message: &[u8]) -> bool {
+        match self.notification_rules(account_id).await {
+            Ok(rules) => {
+                rules.rules.is_empty()
+                    || mail_parser::MessageParser::new()
+                        .parse_headers(raw_message)
+                        .is_none_or(|message| {
+                            rules.action(&message) == NotificationAction::Immediate
+                        })
+            }
+            Err(err) => {
+                // Notifying is the safer choice when the rules cannot be read
+                trc::error!(err.account_id(account_id).caused_by(trc::location!()));
+                true
+            }
+        }
+    }
+}
+
+impl NotificationRules {
+    pub fn action(&self, message: &Message<'_>) -> NotificationAction {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(message))
+            .map(|rule| rule.action)
+            .unwrap_or(NotificationAction::Immediate)
+    }
+
+    pub fn validate(&self) -> Result<(), &'static str> {
+        for rule in &self.rules {
+            if rule
+                .from
+                .as_ref()
+                .is_some_and(|from| from.trim().is_empty())
+                || rule
+                    .subject
+                    .as_ref()
+                    .is_some_and(|subject| subject.trim().is_empty())
+            {
+                return Err("Rule conditions cannot be empty");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl NotificationRule {
+    // Conditions are case-insensitive substring matches, so "boss@" and
+    // "@example.org" both work as sender patterns. A rule without any
+    // conditions matches every message.
+    pub fn matches(&self, message: &Message<'_>) -> bool {
+        if let Some(from) = &self.from
+            && !message
+                .from()
+                .and_then(|addr| addr.first())
+                .and_then(|addr| addr.address())
+                .is_some_and(|addr| contains_ignore_case(addr, from))
+        {
+            return false;
+        }
+
+        if let Some(subject) = &self.subject
+            && !message
+                .subject()
+                .is_some_and(|value| contains_ignore_case(value, subject))
+        {
+            return false;
+        }
+
+        if let Some(is_list) = self.is_list
+            && is_list != is_list_message(message)
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+fn is_list_message(message: &Message<'_>) -> bool {
+    message
+        .root_part()
+        .headers()
+        .iter()
+        .any(|header| match &header.name {
+            HeaderName::ListId | HeaderName::ListUnsubscribe => true,
+            HeaderName::Other(name) if name.eq_ignore_ascii_case("Precedence") => {
+                header.value.as_text().is_some_and(|value| {
+                    value.trim().eq_ignore_ascii_case("bulk")
+                        || value.trim().eq_ignore_ascii_case("list")
+                })
+            }
+            _ => false,
+        })
+}
+
+fn contains_ignore_case(value: &str, pattern: &str) -> bool {
+    value
+        .to_lowercase()
+        .contains(&pattern.trim().to_lowercase())
+}
@@ -6,6 +6,7 @@
 
 use super::{ActiveScript, SeenIdHash, SieveScript};
 use crate::{
+    auto_response::{BulkTraffic, is_auto_reply},
     cache::{MessageCacheFetch, mailbox::MailboxCacheAccess},
     mailbox::{INBOX_ID, TRASH_ID, manage::MailboxFnc},
     message::{
@@ -113,6 +114,10 @@ impl SieveScriptIngest for Server {
             .await
             .caused_by(trc::location!())?;
 
+        // Automatic replies are never sent to list and bulk traffic
+        let bulk_traffic = BulkTraffic::detect(&message);
+        let original_message_id = message.message_id().map(str::to_string);
+
         // Create Sieve instance
         let mut instance = self.core.sieve.untrusted_runtime.filter_parsed(message);
 
@@ -126,9 +131,9 @@ impl SieveScriptIngest for Server {
         );
         instance.set_user_address(&mail_from);
 
-        // Set envelope
+        // Set envelope, keeping the subaddress so scripts can test it with ":detail"
         instance.set_envelope(Envelope::From, envelope_from);
-        instance.set_envelope(Envelope::To, envelope_to.address.as_str());
+        instance.set_envelope(Envelope::To, envelope_to.original_address().as_ref());
         instance.set_spam_status(if envelope_to.is_spam {
             SpamStatus::Spam
         } else {
@@ -177,7 +182,7 @@ impl SieveScriptIngest for Server {
                             if let Some(script) =
                                 self.get_untrusted_sieve_script(&name_.to_lowercase(), session_id)
                             {
-                                input = Input::script(name, script.clone());
+                                input = Input::script(name, script);
                             } else {
                                 input = false.into();
                             }
@@ -383,6 +388,22 @@ impl SieveScriptIngest for Server {
                     } => {
                         input = true.into();
                         if let Some(message) = messages.get(message_id) {
+                            if let Some(bulk_traffic) = bulk_traffic
+                                && message_id != 0
+                                && is_auto_reply(
+                                    &message.raw_message,
+                                    original_message_id.as_deref(),
+                                )
+                            {
+                                trc::event!(
+                                    Sieve(SieveEvent::AutoReplySuppressed),
+                                    From = mail_from.clone(),
+                                    Reason = bulk_traffic.as_str(),
+                                    SpanId = session_id
+                                );
+                                continue;
+                            }
+
                             let recipients: Vec<String> = match recipient {
                                 Recipient::Address(rcpt) => vec![rcpt],
                                 Recipient::Group(rcpts) => rcpts,
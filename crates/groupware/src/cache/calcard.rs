@@ -11,7 +11,11 @@ use crate::{
         ArchivedCalendar, ArchivedCalendarEvent, Calendar, CalendarEvent, SCHEDULE_INBOX_ID,
         SCHEDULE_OUTBOX_ID, storage::ItipAutoExpunge,
     },
-    contact::{AddressBook, ArchivedAddressBook, ArchivedContactCard, ContactCard},
+    contact::{
+        AddressBook, ArchivedAddressBook, ArchivedContactCard, ContactCard,
+        DIRECTORY_ADDRESSBOOK_ID,
+        directory::{DirectoryAddressBook, directory_card_name, remove_directory_addressbook},
+    },
 };
 use calcard::common::timezone::Tz;
 use common::{
@@ -19,7 +23,10 @@ use common::{
     TinyCalendarPreferences, UpdateLock,
 };
 use std::sync::Arc;
-use store::ahash::{AHashMap, AHashSet};
+use store::{
+    ahash::{AHashMap, AHashSet},
+    write::BatchBuilder,
+};
 use trc::AddContext;
 use types::{
     acl::AclGrant,
@@ -108,24 +115,84 @@ pub(super) async fn build_calcard_resources(
             .await
             .caused_by(trc::location!())?;
 
-        if cache.paths.is_empty() {
+        // The global address list is served virtually to user accounts, its contents are
+        // announced through the change log by the directory sync task
+        let directory_state = if !is_calendar {
+            server
+                .directory_state(account_id)
+                .await
+                .caused_by(trc::location!())?
+        } else {
+            None
+        };
+        let has_directory = !is_calendar
+            && owner_account_info.is_user_account()
+            && server.core.groupware.directory_addressbook_name.is_some();
+        let needs_directory = has_directory && directory_state.is_none();
+        let drop_directory = !has_directory && directory_state.is_some();
+
+        if cache.paths.is_empty() || needs_directory || drop_directory {
             if is_first_check {
                 if is_calendar {
                     server
                         .create_default_calendar(&access_account_info, &owner_account_info)
                         .await?;
                 } else {
-                    server
-                        .create_default_addressbook(&access_account_info, &owner_account_info)
-                        .await?;
+                    if cache.paths.is_empty() {
+                        server
+                            .create_default_addressbook(&access_account_info, &owner_account_info)
+                            .await?;
+                    }
+                    if needs_directory {
+                        server
+                            .create_directory_addressbook(&owner_account_info)
+                            .await?;
+                    } else if let Some(state) = directory_state.as_ref().filter(|_| drop_directory)
+                    {
+                        let mut batch = BatchBuilder::new();
+                        remove_directory_addressbook(
+                            account_id,
+                            &cache.base_path,
+                            state,
+                            &mut batch,
+                        )?;
+                        server
+                            .commit_batch(batch)
+                            .await
+                            .caused_by(trc::location!())?;
+                    }
                 }
+                cache.paths.clear();
+                cache.resources.clear();
+                cache.size = std::mem::size_of::<DavResources>() as u64;
                 is_first_check = false;
                 continue;
-            } else {
+            } else if cache.paths.is_empty() {
                 return Ok(cache);
             }
         }
 
+        let directory_state = directory_state.filter(|_| has_directory);
+        if let Some(state) = &directory_state {
+            let path = DavPath {
+                path: state.name.clone(),
+                parent_id: None,
+                hierarchy_seq: 1,
+                resource_idx: cache.resources.len(),
+            };
+            cache.size += (std::mem::size_of::<DavPath>()
+                + std::mem::size_of::<DavResource>()
+                + (path.path.len()) * 2) as u64;
+            cache.paths.insert(path);
+            cache.resources.push(DavResource {
+                document_id: DIRECTORY_ADDRESSBOOK_ID,
+                data: DavResourceMetadata::AddressBook {
+                    name: state.name.clone(),
+                    acls: vec![],
+                },
+            });
+        }
+
         let parent_range = cache.resources.len();
         server
             .archives(account_id, item_collection, &(), |document_id, archive| {
@@ -163,6 +230,32 @@ pub(super) async fn build_calcard_resources(
             .await
             .caused_by(trc::location!())?;
 
+        if let Some(state) = directory_state {
+            for entry in state.entries {
+                let name = directory_card_name(entry.document_id);
+                let path = DavPath {
+                    path: format!("{}/{name}", state.name),
+                    parent_id: Some(DIRECTORY_ADDRESSBOOK_ID),
+                    hierarchy_seq: 0,
+                    resource_idx: cache.resources.len(),
+                };
+                cache.size += (std::mem::size_of::<DavPath>()
+                    + std::mem::size_of::<DavResource>()
+                    + name.len()
+                    + path.path.len()) as u64;
+                cache.paths.insert(path);
+                cache.resources.push(DavResource {
+                    document_id: entry.document_id,
+                    data: DavResourceMetadata::ContactCard {
+                        names: vec![DavName {
+                            name,
+                            parent_id: DIRECTORY_ADDRESSBOOK_ID,
+                        }],
+                    },
+                });
+            }
+        }
+
         return Ok(cache);
     }
 }
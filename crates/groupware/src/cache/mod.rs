@@ -7,7 +7,12 @@
 use crate::{
     cache::calcard::{build_scheduling_resources, path_from_scheduling, resource_from_scheduling},
     calendar::{Calendar, CalendarEvent, CalendarPreferences},
-    contact::{AddressBook, AddressBookPreferences, ContactCard},
+    contact::{
+        AddressBook, AddressBookPreferences, ContactCard, DIRECTORY_ADDRESSBOOK_ID,
+        directory::{
+            DirectoryAddressBook, DirectoryState, schedule_directory_sync, set_directory_state,
+        },
+    },
     file::FileNode,
 };
 use ahash::AHashSet;
@@ -24,7 +29,7 @@ use store::{
     SerializeInfallible, ValueKey,
     ahash::AHashMap,
     query::log::{Change, Query},
-    write::{AlignedBytes, Archive, BatchBuilder, ValueClass},
+    write::{AlignedBytes, Archive, BatchBuilder, ValueClass, now},
 };
 use trc::{AddContext, StoreEvent};
 use types::{
@@ -49,6 +54,11 @@ pub trait GroupwareCache: Sync + Send {
         account_info_owner: &AccountCache,
     ) -> impl Future<Output = trc::Result<Option<u32>>> + Send;
 
+    fn create_directory_addressbook(
+        &self,
+        account_info: &AccountCache,
+    ) -> impl Future<Output = trc::Result<()>> + Send;
+
     fn create_default_calendar(
         &self,
         account_info_access: &AccountCache,
@@ -364,6 +374,29 @@ impl GroupwareCache for Server {
         }
     }
 
+    async fn create_directory_addressbook(&self, account_info: &AccountCache) -> trc::Result<()> {
+        if let Some(name) = &self.core.groupware.directory_addressbook_name {
+            let mut batch = BatchBuilder::new();
+            let account_id = account_info.account_id();
+            set_directory_state(
+                account_id,
+                Some(DirectoryState {
+                    name: name.clone(),
+                    entries: vec![],
+                }),
+                &mut batch,
+            )?;
+            batch
+                .with_collection(Collection::AddressBook)
+                .with_document(DIRECTORY_ADDRESSBOOK_ID)
+                .log_container_insert(SyncCollection::AddressBook);
+            schedule_directory_sync(account_id, now() as i64, &mut batch);
+            self.commit_batch(batch).await?;
+        }
+
+        Ok(())
+    }
+
     async fn create_default_calendar(
         &self,
         account_info_access: &AccountCache,
@@ -467,12 +500,7 @@ async fn process_changes(
             Change::InsertItem(id) | Change::UpdateItem(id) => {
                 let document_id = id as u32;
                 if let Some(archive) = server
-                    .store()
-                    .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
-                        account_id,
-                        collection.collection(false),
-                        document_id,
-                    ))
+                    .contact_archive(account_id, collection.collection(false), document_id)
                     .await
                     .caused_by(trc::location!())?
                 {
@@ -495,12 +523,7 @@ async fn process_changes(
             Change::InsertContainer(id) | Change::UpdateContainer(id) => {
                 let document_id = id as u32;
                 if let Some(archive) = server
-                    .store()
-                    .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
-                        account_id,
-                        collection.collection(true),
-                        document_id,
-                    ))
+                    .contact_archive(account_id, collection.collection(true), document_id)
                     .await
                     .caused_by(trc::location!())?
                 {
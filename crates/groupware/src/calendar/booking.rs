@@ -0,0 +1,229 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{CalendarBookingPolicy, CalendarEventData};
+use crate::calendar::itip::ItipIngest;
+use calcard::{
+    common::timezone::Tz,
+    icalendar::{ICalendar, ICalendarComponentType, ICalendarProperty, ICalendarValue},
+};
+use common::{DavResource, Server};
+use store::{
+    ValueKey,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder, now},
+};
+use trc::AddContext;
+use types::{TimeRange, collection::Collection, field::CalendarField};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookingRejection {
+    DoubleBooked,
+    ApprovalRequired,
+    MaxDuration,
+    TooSoon,
+    TooFar,
+}
+
+// Resource accounts are busy whenever any of their calendars is, while
+// calendar policies only consider the events filed in that calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictScope {
+    Account,
+    Calendar { exclude_id: Option<u32> },
+}
+
+pub trait CalendarBooking: Sync + Send {
+    fn calendar_booking_policy(
+        &self,
+        account_id: u32,
+        calendar_id: u32,
+    ) -> impl Future<Output = trc::Result<Option<CalendarBookingPolicy>>> + Send;
+
+    fn check_booking_policy(
+        &self,
+        account_id: u32,
+        calendar_id: u32,
+        policy: &CalendarBookingPolicy,
+        event: &CalendarEventData,
+        document_id: Option<u32>,
+    ) -> impl Future<Output = trc::Result<Option<BookingRejection>>> + Send;
+}
+
+impl CalendarBooking for Server {
+    async fn calendar_booking_policy(
+        &self,
+        account_id: u32,
+        calendar_id: u32,
+    ) -> trc::Result<Option<CalendarBookingPolicy>> {
+        self.store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Calendar,
+                calendar_id,
+                CalendarField::BookingPolicy,
+            ))
+            .await
+            .caused_by(trc::location!())?
+            .map(|policy| policy.deserialize::<CalendarBookingPolicy>())
+            .transpose()
+            .caused_by(trc::location!())
+    }
+
+    async fn check_booking_policy(
+        &self,
+        account_id: u32,
+        calendar_id: u32,
+        policy: &CalendarBookingPolicy,
+        event: &CalendarEventData,
+        document_id: Option<u32>,
+    ) -> trc::Result<Option<BookingRejection>> {
+        if let Some(rejection) = policy.validate(event, now() as i64) {
+            Ok(Some(rejection))
+        } else if policy.no_double_booking
+            && self
+                .has_booking_conflicts(
+                    account_id,
+                    calendar_id,
+                    event,
+                    ConflictScope::Calendar {
+                        exclude_id: document_id,
+                    },
+                )
+                .await?
+        {
+            Ok(Some(BookingRejection::DoubleBooked))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl ConflictScope {
+    pub fn contains(&self, calendar_id: u32, resource: &DavResource) -> bool {
+        match self {
+            ConflictScope::Account => true,
+            ConflictScope::Calendar { exclude_id } => {
+                resource.is_child_of(calendar_id)
+                    && exclude_id.is_none_or(|id| id != resource.document_id)
+            }
+        }
+    }
+}
+
+impl CalendarBookingPolicy {
+    pub fn is_empty(&self) -> bool {
+        !self.no_double_booking
+            && !self.approval_required
+            && self.max_duration.is_none()
+            && self.min_notice.is_none()
+            && self.max_advance.is_none()
+    }
+
+    // Validates the duration and booking window of every instance that
+    // falls within the event's range. Conflicts are checked separately as
+    // they require fetching the calendar's other events.
+    pub fn validate(&self, event: &CalendarEventData, now: i64) -> Option<BookingRejection> {
+        let start = event.event_range_start();
+        let end = event.event_range_end();
+
+        if let Some(min_notice) = self.min_notice
+            && start < now.saturating_add(min_notice as i64)
+        {
+            return Some(BookingRejection::TooSoon);
+        }
+        if let Some(max_advance) = self.max_advance
+            && end > now.saturating_add(max_advance as i64)
+        {
+            return Some(BookingRejection::TooFar);
+        }
+        if let Some(max_duration) = self.max_duration
+            && event
+                .expand(Tz::UTC, TimeRange::new(start, end))
+                .unwrap_or_default()
+                .into_iter()
+                .any(|instance| {
+                    event.event.components[instance.comp_id as usize].component_type
+                        == ICalendarComponentType::VEvent
+                        && instance.end - instance.start > max_duration as i64
+                })
+        {
+            return Some(BookingRejection::MaxDuration);
+        }
+
+        None
+    }
+
+    pub fn write(
+        self,
+        account_id: u32,
+        document_id: u32,
+        batch: &mut BatchBuilder,
+    ) -> trc::Result<&mut BatchBuilder> {
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Calendar)
+            .with_document(document_id);
+        if self.is_empty() {
+            batch.clear(CalendarField::BookingPolicy);
+        } else {
+            batch.set(
+                CalendarField::BookingPolicy,
+                Archiver::new(self)
+                    .serialize()
+                    .caused_by(trc::location!())?,
+            );
+        }
+
+        Ok(batch)
+    }
+}
+
+impl BookingRejection {
+    pub fn description(&self) -> &'static str {
+        match self {
+            BookingRejection::DoubleBooked => "The requested time overlaps an existing booking",
+            BookingRejection::ApprovalRequired => {
+                "Bookings on this calendar require approval and must be sent as scheduling requests"
+            }
+            BookingRejection::MaxDuration => {
+                "The booking exceeds the maximum duration allowed by this calendar"
+            }
+            BookingRejection::TooSoon => {
+                "The booking starts sooner than the minimum notice required by this calendar"
+            }
+            BookingRejection::TooFar => {
+                "The booking ends after the booking window allowed by this calendar"
+            }
+        }
+    }
+
+    // RFC 5546 Section 3.6 request status codes
+    pub fn request_status(&self) -> [&'static str; 2] {
+        match self {
+            BookingRejection::DoubleBooked => ["4.1", "Event conflict.  Date-time is busy."],
+            BookingRejection::ApprovalRequired => ["3.8", "No authority."],
+            BookingRejection::MaxDuration
+            | BookingRejection::TooSoon
+            | BookingRejection::TooFar => ["3.1", "Invalid property value."],
+        }
+    }
+
+    pub fn set_request_status(&self, ical: &mut ICalendar) {
+        let [code, description] = self.request_status();
+        for component in &mut ical.components {
+            if component.component_type.is_scheduling_object() {
+                for entry in &mut component.entries {
+                    if entry.name == ICalendarProperty::RequestStatus {
+                        entry.values = vec![
+                            ICalendarValue::Text(code.to_string()),
+                            ICalendarValue::Text(description.to_string()),
+                        ];
+                    }
+                }
+            }
+        }
+    }
+}
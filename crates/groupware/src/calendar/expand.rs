@@ -4,8 +4,8 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::ArchivedCalendarEventData;
-use crate::calendar::CalendarEventData;
+use super::{ArchivedCalendarEventData, ArchivedComponentTimeRange};
+use crate::calendar::{CalendarEventData, ComponentTimeRange};
 use ahash::AHashSet;
 use calcard::common::timezone::Tz;
 use chrono::{DateTime, TimeZone};
@@ -23,99 +23,35 @@ pub struct CalendarEventExpansion {
 
 impl ArchivedCalendarEventData {
     pub fn expand(&self, default_tz: Tz, limit: TimeRange) -> Option<Vec<CalendarEventExpansion>> {
-        let mut expansion = Vec::with_capacity(self.time_ranges.len());
-        let base_offset = self.base_offset.to_native();
-        let mut base_expansion_id = 0;
-
-        'outer: for range in self.time_ranges.iter() {
-            let instances = range.instances.as_ref();
-            let (offset_or_count, bytes_read) = instances.read_leb128::<u32>()?;
-
-            let comp_id = range.id.to_native() as u32;
-            let duration = range.duration.to_native() as i64;
-            let mut start_tz = Tz::from_id(range.start_tz.to_native())?;
-            let mut end_tz = Tz::from_id(range.end_tz.to_native())?;
-            let is_todo = self.event.components[comp_id as usize]
-                .component_type
-                .is_todo();
-
-            if start_tz.is_floating() && !default_tz.is_floating() {
-                start_tz = default_tz;
-            }
-            if end_tz.is_floating() && !default_tz.is_floating() {
-                end_tz = default_tz;
-            }
-
-            if instances.len() > bytes_read {
-                // Recurring event
-                let unpacker =
-                    BitpackIterator::from_bytes_and_offset(instances, bytes_read, offset_or_count);
-                let mut expansion_id = base_expansion_id;
-                base_expansion_id += offset_or_count;
-                for start_offset in unpacker {
-                    let start_date_naive = start_offset as i64 + base_offset;
-                    let end_date_naive = start_date_naive + duration;
-                    let start = start_tz
-                        .from_local_datetime(
-                            &DateTime::from_timestamp(start_date_naive, 0)?.naive_local(),
-                        )
-                        .single()?
-                        .timestamp();
-                    let end = end_tz
-                        .from_local_datetime(
-                            &DateTime::from_timestamp(end_date_naive, 0)?.naive_local(),
-                        )
-                        .single()?
-                        .timestamp();
-
-                    if limit.is_in_range(is_todo, start, end) {
-                        expansion.push(CalendarEventExpansion {
-                            comp_id,
-                            expansion_id,
-                            start,
-                            end,
-                        });
-                    } else if start > limit.end {
-                        continue 'outer;
-                    }
-
-                    expansion_id += 1;
-                }
-            } else {
-                // Single event
-                let start_date_naive = offset_or_count as i64 + base_offset;
-                let end_date_naive = start_date_naive + duration;
-                let start = start_tz
-                    .from_local_datetime(
-                        &DateTime::from_timestamp(start_date_naive, 0)?.naive_local(),
-                    )
-                    .single()?
-                    .timestamp();
-                let end = end_tz
-                    .from_local_datetime(
-                        &DateTime::from_timestamp(end_date_naive, 0)?.naive_local(),
-                    )
-                    .single()?
-                    .timestamp();
-
-                if limit.is_in_range(is_todo, start, end) {
-                    expansion.push(CalendarEventExpansion {
-                        comp_id,
-                        expansion_id: base_expansion_id,
-                        start,
-                        end,
-                    });
-                }
-
-                base_expansion_id += 1;
-            }
-        }
-
-        Some(expansion)
+        expand_time_ranges(
+            self.time_ranges.iter(),
+            self.base_offset.to_native(),
+            default_tz,
+            limit,
+            |comp_id| {
+                self.event.components[comp_id as usize]
+                    .component_type
+                    .is_todo()
+            },
+        )
     }
 }
 
 impl CalendarEventData {
+    pub fn expand(&self, default_tz: Tz, limit: TimeRange) -> Option<Vec<CalendarEventExpansion>> {
+        expand_time_ranges(
+            self.time_ranges.iter(),
+            self.base_offset,
+            default_tz,
+            limit,
+            |comp_id| {
+                self.event.components[comp_id as usize]
+                    .component_type
+                    .is_todo()
+            },
+        )
+    }
+
     pub fn expand_from_ids(
         &self,
         expansion_ids: &mut AHashSet<u32>,
@@ -281,6 +217,149 @@ impl CalendarEventData {
     }
 }
 
+trait ExpandTimeRange {
+    fn comp_id(&self) -> u32;
+    fn duration(&self) -> i64;
+    fn start_tz(&self) -> u16;
+    fn end_tz(&self) -> u16;
+    fn instances(&self) -> &[u8];
+}
+
+impl ExpandTimeRange for ComponentTimeRange {
+    fn comp_id(&self) -> u32 {
+        self.id as u32
+    }
+
+    fn duration(&self) -> i64 {
+        self.duration as i64
+    }
+
+    fn start_tz(&self) -> u16 {
+        self.start_tz
+    }
+
+    fn end_tz(&self) -> u16 {
+        self.end_tz
+    }
+
+    fn instances(&self) -> &[u8] {
+        self.instances.as_ref()
+    }
+}
+
+impl ExpandTimeRange for ArchivedComponentTimeRange {
+    fn comp_id(&self) -> u32 {
+        self.id.to_native() as u32
+    }
+
+    fn duration(&self) -> i64 {
+        self.duration.to_native() as i64
+    }
+
+    fn start_tz(&self) -> u16 {
+        self.start_tz.to_native()
+    }
+
+    fn end_tz(&self) -> u16 {
+        self.end_tz.to_native()
+    }
+
+    fn instances(&self) -> &[u8] {
+        self.instances.as_ref()
+    }
+}
+
+fn expand_time_ranges<'x, T: ExpandTimeRange + 'x>(
+    time_ranges: impl ExactSizeIterator<Item = &'x T>,
+    base_offset: i64,
+    default_tz: Tz,
+    limit: TimeRange,
+    is_todo: impl Fn(u32) -> bool,
+) -> Option<Vec<CalendarEventExpansion>> {
+    let mut expansion = Vec::with_capacity(time_ranges.len());
+    let mut base_expansion_id = 0;
+
+    'outer: for range in time_ranges {
+        let instances = range.instances();
+        let (offset_or_count, bytes_read) = instances.read_leb128::<u32>()?;
+
+        let comp_id = range.comp_id();
+        let duration = range.duration();
+        let mut start_tz = Tz::from_id(range.start_tz())?;
+        let mut end_tz = Tz::from_id(range.end_tz())?;
+        let is_todo = is_todo(comp_id);
+
+        if start_tz.is_floating() && !default_tz.is_floating() {
+            start_tz = default_tz;
+        }
+        if end_tz.is_floating() && !default_tz.is_floating() {
+            end_tz = default_tz;
+        }
+
+        if instances.len() > bytes_read {
+            // Recurring event
+            let unpacker =
+                BitpackIterator::from_bytes_and_offset(instances, bytes_read, offset_or_count);
+            let mut expansion_id = base_expansion_id;
+            base_expansion_id += offset_or_count;
+            for start_offset in unpacker {
+                let start_date_naive = start_offset as i64 + base_offset;
+                let end_date_naive = start_date_naive + duration;
+                let start = start_tz
+                    .from_local_datetime(
+                        &DateTime::from_timestamp(start_date_naive, 0)?.naive_local(),
+                    )
+                    .single()?
+                    .timestamp();
+                let end = end_tz
+                    .from_local_datetime(
+                        &DateTime::from_timestamp(end_date_naive, 0)?.naive_local(),
+                    )
+                    .single()?
+                    .timestamp();
+
+                if limit.is_in_range(is_todo, start, end) {
+                    expansion.push(CalendarEventExpansion {
+                        comp_id,
+                        expansion_id,
+                        start,
+                        end,
+                    });
+                } else if start > limit.end {
+                    continue 'outer;
+                }
+
+                expansion_id += 1;
+            }
+        } else {
+            // Single event
+            let start_date_naive = offset_or_count as i64 + base_offset;
+            let end_date_naive = start_date_naive + duration;
+            let start = start_tz
+                .from_local_datetime(&DateTime::from_timestamp(start_date_naive, 0)?.naive_local())
+                .single()?
+                .timestamp();
+            let end = end_tz
+                .from_local_datetime(&DateTime::from_timestamp(end_date_naive, 0)?.naive_local())
+                .single()?
+                .timestamp();
+
+            if limit.is_in_range(is_todo, start, end) {
+                expansion.push(CalendarEventExpansion {
+                    comp_id,
+                    expansion_id: base_expansion_id,
+                    start,
+                    end,
+                });
+            }
+
+            base_expansion_id += 1;
+        }
+    }
+
+    Some(expansion)
+}
+
 impl Default for CalendarEventExpansion {
     fn default() -> Self {
         Self {
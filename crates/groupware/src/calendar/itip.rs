@@ -10,9 +10,11 @@ use crate::{
     calendar::{
         CalendarEvent, CalendarEventData, CalendarEventNotification, ChangedBy,
         EVENT_NOTIFICATION_IS_CHANGE,
+        booking::{CalendarBooking, ConflictScope},
     },
     scheduling::{
         ItipError, ItipMessage,
+        event_update::itip_update,
         inbound::{
             MergeResult, itip_import_message, itip_merge_changes, itip_method, itip_process_message,
         },
@@ -22,9 +24,10 @@ use crate::{
 use calcard::{
     common::{IanaString, timezone::Tz},
     icalendar::{
-        ICalendar, ICalendarComponentType, ICalendarEntry, ICalendarMethod, ICalendarParameter,
-        ICalendarParameterName, ICalendarParameterValue, ICalendarParticipationStatus,
-        ICalendarProperty, ICalendarValue,
+        ArchivedICalendarComponentType, ArchivedICalendarStatus, ICalendar, ICalendarComponentType,
+        ICalendarEntry, ICalendarMethod, ICalendarParameter, ICalendarParameterName,
+        ICalendarParameterValue, ICalendarParticipationStatus, ICalendarProperty,
+        ICalendarTransparency, ICalendarValue, Uri,
     },
 };
 use common::{
@@ -33,17 +36,22 @@ use common::{
     config::groupware::CalendarTemplateVariable,
     i18n,
 };
+use registry::schema::enums::SchedulingResourcePolicy;
 use store::{
     ValueKey, rand,
     write::{AlignedBytes, Archive, BatchBuilder, now},
 };
 use trc::AddContext;
 use types::{
-    collection::Collection,
+    TimeRange,
+    collection::{Collection, SyncCollection},
     field::{CalendarEventField, ContactField},
 };
 use utils::{template::Variables, url_params::UrlParams};
 
+// RFC 2392 message-id URLs keep the '@' separator unencoded
+const MID_URL: &percent_encoding::AsciiSet = &RFC_3986.remove(b'@');
+
 pub enum ItipIngestError {
     Message(ItipError),
     Internal(trc::Error),
@@ -61,6 +69,21 @@ pub trait ItipIngest: Sync + Send {
         itip_message: &str,
     ) -> impl Future<Output = Result<Option<ItipMessage<ICalendar>>, ItipIngestError>> + Send;
 
+    fn itip_import_tentative(
+        &self,
+        account_info: &AccountInfo,
+        itip_message: &str,
+        message_id: Option<&str>,
+    ) -> impl Future<Output = Result<bool, ItipIngestError>> + Send;
+
+    fn has_booking_conflicts(
+        &self,
+        account_id: u32,
+        calendar_id: u32,
+        event: &CalendarEventData,
+        scope: ConflictScope,
+    ) -> impl Future<Output = trc::Result<bool>> + Send;
+
     fn http_rsvp_url(
         &self,
         account_id: u32,
@@ -288,8 +311,15 @@ impl ItipIngest for Server {
                 Err(ItipIngestError::Message(ItipError::EventNotFound))
             }
         } else {
+            // Scheduling resources answer requests on their own
+            let resource_policy = account_info
+                .account()
+                .scheduling_resource_policy()
+                .filter(|policy| *policy != SchedulingResourcePolicy::Manual);
+
             // Verify that auto-adding invitations is allowed
-            if !self.core.groupware.itip_auto_add
+            if resource_policy.is_none()
+                && !self.core.groupware.itip_auto_add
                 && !matches!(changed_by, ChangedBy::PrincipalId(_))
                 && !self
                     .document_exists(
@@ -334,7 +364,7 @@ impl ItipIngest for Server {
             // Build event
             let mut next_email_alarm = None;
             let now = now() as i64;
-            let event = CalendarEvent {
+            let mut event = CalendarEvent {
                 names: vec![DavName {
                     name: format!("{}_{}.ics", now, rand::random::<u64>()),
                     parent_id,
@@ -350,6 +380,76 @@ impl ItipIngest for Server {
                 ..Default::default()
             };
 
+            // Invitations that break the calendar's booking policy are declined, while
+            // calendars that require approval leave the decision to the owner
+            let booking_policy = self
+                .calendar_booking_policy(account_id, parent_id)
+                .await
+                .caused_by(trc::location!())?;
+            let rejection = if let Some(policy) = &booking_policy {
+                self.check_booking_policy(account_id, parent_id, policy, &event.data, None)
+                    .await?
+            } else {
+                None
+            };
+            let part_stat = if rejection.is_some() {
+                Some(ICalendarParticipationStatus::Declined)
+            } else if booking_policy
+                .as_ref()
+                .is_some_and(|policy| policy.approval_required)
+            {
+                None
+            } else {
+                // Reply on behalf of the resource
+                match resource_policy {
+                    Some(SchedulingResourcePolicy::AcceptAlways) => {
+                        Some(ICalendarParticipationStatus::Accepted)
+                    }
+                    Some(SchedulingResourcePolicy::DeclineAlways) => {
+                        Some(ICalendarParticipationStatus::Declined)
+                    }
+                    Some(_) => {
+                        if self
+                            .has_booking_conflicts(
+                                account_id,
+                                parent_id,
+                                &event.data,
+                                ConflictScope::Account,
+                            )
+                            .await?
+                        {
+                            Some(ICalendarParticipationStatus::Declined)
+                        } else {
+                            Some(ICalendarParticipationStatus::Accepted)
+                        }
+                    }
+                    None => None,
+                }
+            };
+
+            let mut reply = None;
+            if let Some(part_stat) = part_stat {
+                let old_ical = event.data.event.clone();
+
+                if set_local_part_stat(&mut event.data.event, account_info.addresses(), &part_stat)
+                {
+                    reply =
+                        itip_update(&mut event.data.event, &old_ical, account_info.addresses())?
+                            .into_iter()
+                            .next();
+                    if let (Some(rejection), Some(reply)) = (rejection, &mut reply) {
+                        rejection.set_request_status(&mut reply.message);
+                    }
+
+                    // Declined bookings are not added to the resource calendar
+                    if part_stat == ICalendarParticipationStatus::Declined {
+                        return Ok(reply);
+                    }
+                } else if rejection.is_some() {
+                    return Ok(None);
+                }
+            }
+
             // Obtain document ids
             let document_id = self
                 .store()
@@ -390,10 +490,202 @@ impl ItipIngest for Server {
                 .caused_by(trc::location!())?;
             self.commit_batch(batch).await.caused_by(trc::location!())?;
 
-            Ok(None)
+            Ok(reply)
         }
     }
 
+    async fn itip_import_tentative(
+        &self,
+        account_info: &AccountInfo,
+        itip_message: &str,
+        message_id: Option<&str>,
+    ) -> Result<bool, ItipIngestError> {
+        // Only new invitations are imported
+        let mut ical = ICalendar::parse(itip_message)
+            .map_err(|_| ItipIngestError::Message(ItipError::ICalendarParseError))?;
+        if itip_method(&ical)? != &ICalendarMethod::Request {
+            return Ok(false);
+        }
+        let Some(uid) = ical.uids().next().map(|uid| uid.to_string()) else {
+            return Err(ItipIngestError::Message(ItipError::ICalendarParseError));
+        };
+        let account_id = account_info.account_id();
+        if !self
+            .document_ids_matching(
+                account_id,
+                Collection::CalendarEvent,
+                CalendarEventField::Uid,
+                uid.as_bytes(),
+            )
+            .await
+            .caused_by(trc::location!())?
+            .is_empty()
+        {
+            return Ok(false);
+        }
+        itip_import_message(&mut ical)?;
+
+        // Mark the account's participation as tentative, invitations not addressed
+        // to any of the account's addresses are not imported
+        if !set_local_part_stat(
+            &mut ical,
+            account_info.addresses(),
+            &ICalendarParticipationStatus::Tentative,
+        ) {
+            return Ok(false);
+        }
+
+        // Link the event to the original message
+        if let Some(message_id) = message_id {
+            for component in &mut ical.components {
+                if component.component_type.is_scheduling_object() {
+                    component.entries.push(ICalendarEntry {
+                        name: ICalendarProperty::Attach,
+                        params: vec![],
+                        values: vec![ICalendarValue::Uri(Uri::Location(format!(
+                            "mid:{}",
+                            percent_encoding::utf8_percent_encode(message_id, MID_URL)
+                        )))],
+                    });
+                }
+            }
+        }
+
+        // Validate quota
+        let size = ical.size();
+        if self
+            .has_available_quota(self.account(account_id).await?.as_ref(), size as u64)
+            .await
+            .is_err()
+        {
+            return Err(ItipIngestError::Message(ItipError::QuotaExceeded));
+        }
+
+        // Obtain parent calendar
+        let Some(parent_id) = self
+            .get_or_create_default_calendar(account_id, account_id)
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Err(ItipIngestError::Message(ItipError::NoDefaultCalendar));
+        };
+
+        // Build event
+        let mut next_email_alarm = None;
+        let event = CalendarEvent {
+            names: vec![DavName {
+                name: format!("{}_{}.ics", now(), rand::random::<u64>()),
+                parent_id,
+            }],
+            data: CalendarEventData::new(
+                ical,
+                Tz::Floating,
+                self.core.groupware.max_ical_instances,
+                &mut next_email_alarm,
+            ),
+            size: size as u32,
+            schedule_tag: Some(1),
+            ..Default::default()
+        };
+        let document_id = self
+            .store()
+            .assign_document_ids(account_id, Collection::CalendarEvent, 1)
+            .await
+            .caused_by(trc::location!())?;
+        let mut batch = BatchBuilder::new();
+        event
+            .insert(
+                account_info.account_tenant_ids(),
+                account_id,
+                document_id,
+                next_email_alarm,
+                &mut batch,
+            )
+            .caused_by(trc::location!())?;
+        self.commit_batch(batch).await.caused_by(trc::location!())?;
+
+        Ok(true)
+    }
+
+    async fn has_booking_conflicts(
+        &self,
+        account_id: u32,
+        calendar_id: u32,
+        event: &CalendarEventData,
+        scope: ConflictScope,
+    ) -> trc::Result<bool> {
+        let range = TimeRange::new(event.event_range_start(), event.event_range_end());
+        let resources = self
+            .fetch_dav_resources(account_id, account_id, SyncCollection::Calendar)
+            .await
+            .caused_by(trc::location!())?;
+        let default_tz = resources
+            .container_resource_by_id(calendar_id)
+            .and_then(|calendar| calendar.calendar_preferences(account_id))
+            .map(|preferences| preferences.tz)
+            .unwrap_or(Tz::UTC);
+
+        // Obtain the instances requested
+        let requested = event
+            .expand(default_tz, range)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|instance| {
+                event.event.components[instance.comp_id as usize].component_type
+                    == ICalendarComponentType::VEvent
+            })
+            .collect::<Vec<_>>();
+        if requested.is_empty() {
+            return Ok(false);
+        }
+
+        for resource in resources.resources.iter() {
+            if !resource
+                .event_time_range()
+                .is_some_and(|(start, end)| range.is_in_range(false, start, end))
+                || !scope.contains(calendar_id, resource)
+            {
+                continue;
+            }
+
+            let Some(archive) = self
+                .store()
+                .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                    account_id,
+                    Collection::CalendarEvent,
+                    resource.document_id,
+                ))
+                .await
+                .caused_by(trc::location!())?
+            else {
+                continue;
+            };
+            let booking = archive
+                .unarchive::<CalendarEvent>()
+                .caused_by(trc::location!())?;
+
+            // Only opaque events that have not been cancelled are considered busy time
+            for instance in booking.data.expand(default_tz, range).unwrap_or_default() {
+                let component = &booking.data.event.components[instance.comp_id as usize];
+                if matches!(
+                    component.component_type,
+                    ArchivedICalendarComponentType::VEvent
+                ) && component
+                    .transparency()
+                    .is_none_or(|t| t == &ICalendarTransparency::Opaque)
+                    && !matches!(component.status(), Some(ArchivedICalendarStatus::Cancelled))
+                    && requested
+                        .iter()
+                        .any(|req| req.start < instance.end && req.end > instance.start)
+                {
+                    return Ok(true);
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
     async fn http_rsvp_url(
         &self,
         account_id: u32,
@@ -537,6 +829,41 @@ impl ItipIngest for Server {
     }
 }
 
+fn set_local_part_stat(
+    ical: &mut ICalendar,
+    addresses: &[String],
+    part_stat: &ICalendarParticipationStatus,
+) -> bool {
+    let mut found_participant = false;
+
+    for component in &mut ical.components {
+        if component.component_type.is_scheduling_object() {
+            for entry in &mut component.entries {
+                if entry.name == ICalendarProperty::Attendee
+                    && entry.calendar_address().is_some_and(|v| {
+                        addresses
+                            .iter()
+                            .any(|address| v.eq_ignore_ascii_case(address))
+                    })
+                {
+                    entry.params.retain(|param| {
+                        !matches!(
+                            param.name,
+                            ICalendarParameterName::Partstat | ICalendarParameterName::Rsvp
+                        )
+                    });
+                    entry
+                        .params
+                        .push(ICalendarParameter::partstat(part_stat.clone()));
+                    found_participant = true;
+                }
+            }
+        }
+    }
+
+    found_participant
+}
+
 struct RsvpResponse {
     account_id: u32,
     document_id: u32,
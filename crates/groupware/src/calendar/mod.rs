@@ -5,11 +5,13 @@
  */
 
 pub mod alarm;
+pub mod booking;
 pub mod dates;
 pub mod expand;
 pub mod index;
 pub mod itip;
 pub mod storage;
+pub mod subscription;
 
 use calcard::icalendar::{
     ICalendar, ICalendarComponent, ICalendarComponentType, ICalendarDuration, ICalendarEntry,
@@ -56,6 +58,7 @@ pub const CALENDAR_INVISIBLE: u16 = 1 << 1;
 pub const CALENDAR_AVAILABILITY_NONE: u16 = 1 << 2;
 pub const CALENDAR_AVAILABILITY_ATTENDING: u16 = 1 << 3;
 pub const CALENDAR_AVAILABILITY_ALL: u16 = 1 << 4;
+pub const CALENDAR_SUBSCRIPTION: u16 = 1 << 5;
 
 #[derive(
     rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
@@ -98,6 +101,43 @@ pub struct ParticipantIdentity {
     pub calendar_address: String,
 }
 
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
+)]
+pub struct CalendarSubscription {
+    pub url: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    pub last_sync: i64,
+    pub next_refresh: i64,
+    pub failed_attempts: u32,
+    pub last_error: Option<String>,
+}
+
+#[derive(
+    rkyv::Archive,
+    rkyv::Deserialize,
+    rkyv::Serialize,
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Default,
+    Clone,
+    PartialEq,
+    Eq,
+)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CalendarBookingPolicy {
+    pub no_double_booking: bool,
+    pub approval_required: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_duration: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_notice: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_advance: Option<u64>,
+}
+
 pub const ALERT_WITH_TIME: u16 = 1;
 pub const ALERT_EMAIL: u16 = 1 << 1;
 pub const ALERT_RELATIVE_TO_END: u16 = 1 << 2;
@@ -212,6 +252,12 @@ pub enum Timezone {
 }
 
 impl Calendar {
+    pub fn is_subscription(&self) -> bool {
+        self.preferences
+            .first()
+            .is_some_and(|p| p.flags & CALENDAR_SUBSCRIPTION != 0)
+    }
+
     pub fn preferences(&self, access_token: &AccessToken) -> &CalendarPreferences {
         if self.preferences.len() == 1 {
             &self.preferences[0]
@@ -245,6 +291,12 @@ impl Calendar {
 }
 
 impl ArchivedCalendar {
+    pub fn is_subscription(&self) -> bool {
+        self.preferences
+            .first()
+            .is_some_and(|p| p.flags.to_native() & CALENDAR_SUBSCRIPTION != 0)
+    }
+
     pub fn default_alerts(
         &self,
         access_token: &AccessToken,
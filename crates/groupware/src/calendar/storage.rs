@@ -6,7 +6,7 @@
 
 use super::{
     ArchivedCalendar, ArchivedCalendarEvent, Calendar, CalendarEvent, CalendarPreferences,
-    alarm::CalendarAlarm,
+    CalendarSubscription, alarm::CalendarAlarm,
 };
 use crate::{
     DavResourceName, DestroyArchive, RFC_3986,
@@ -22,21 +22,24 @@ use common::{
     storage::index::ObjectIndexBuilder,
 };
 use registry::{
-    schema::structs::{Task, TaskCalendarAlarmEmail, TaskCalendarAlarmNotification, TaskStatus},
+    schema::structs::{
+        Task, TaskCalendarAlarmEmail, TaskCalendarAlarmNotification, TaskCalendarSubscription,
+        TaskStatus,
+    },
     types::{EnumImpl, ObjectImpl, datetime::UTCDateTime},
 };
 use store::{
     IterateParams, SerializeInfallible, U32_LEN, ValueKey,
     roaring::RoaringBitmap,
     write::{
-        AlignedBytes, Archive, BatchBuilder, IndexPropertyClass, Operation, TaskQueueClass,
-        ValueClass, ValueOp, key::DeserializeBigEndian, now,
+        AlignedBytes, Archive, Archiver, BatchBuilder, IndexPropertyClass, Operation,
+        TaskQueueClass, ValueClass, ValueOp, key::DeserializeBigEndian, now,
     },
 };
 use trc::AddContext;
 use types::{
     collection::{Collection, VanishedCollection},
-    field::CalendarNotificationField,
+    field::{CalendarField, CalendarNotificationField},
     id::Id,
 };
 
@@ -293,6 +296,34 @@ impl Calendar {
     }
 }
 
+impl CalendarSubscription {
+    pub fn write(
+        self,
+        account_id: u32,
+        document_id: u32,
+        batch: &mut BatchBuilder,
+    ) -> trc::Result<&mut BatchBuilder> {
+        let next_refresh = self.next_refresh;
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Calendar)
+            .with_document(document_id)
+            .set(
+                CalendarField::Subscription,
+                Archiver::new(self)
+                    .serialize()
+                    .caused_by(trc::location!())?,
+            )
+            .schedule_task(Task::CalendarSubscriptionSync(TaskCalendarSubscription {
+                account_id: account_id.into(),
+                document_id: document_id.into(),
+                status: TaskStatus::at(next_refresh),
+            }));
+
+        Ok(batch)
+    }
+}
+
 impl CalendarEventNotification {
     pub fn insert(
         self,
@@ -391,7 +422,9 @@ impl DestroyArchive<Archive<&ArchivedCalendar>> {
                     .with_changed_by(changed_by)
                     .with_current(calendar),
             )
-            .caused_by(trc::location!())?;
+            .caused_by(trc::location!())?
+            .clear(CalendarField::Subscription)
+            .clear(CalendarField::BookingPolicy);
         if let Some(delete_path) = delete_path {
             batch.log_vanished_item(VanishedCollection::Calendar, delete_path);
         }
@@ -0,0 +1,119 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{ArchivedCalendarSubscription, CalendarSubscription};
+use ahash::AHashMap;
+use calcard::icalendar::{
+    ICalendar, ICalendarComponent, ICalendarComponentType, ICalendarProperty,
+};
+
+impl CalendarSubscription {
+    pub fn fetch_url(&self) -> String {
+        subscription_fetch_url(&self.url)
+    }
+
+    pub fn next_retry(&self, refresh_interval: u64, max_backoff: u64) -> i64 {
+        let backoff = refresh_interval
+            .saturating_mul(1u64 << self.failed_attempts.min(16))
+            .min(max_backoff.max(refresh_interval));
+        self.last_sync + backoff as i64
+    }
+}
+
+impl ArchivedCalendarSubscription {
+    pub fn fetch_url(&self) -> String {
+        subscription_fetch_url(&self.url)
+    }
+}
+
+fn subscription_fetch_url(url: &str) -> String {
+    if let Some((scheme, rest)) = url.split_once("://") {
+        if scheme.eq_ignore_ascii_case("webcal") || scheme.eq_ignore_ascii_case("webcals") {
+            format!("https://{rest}")
+        } else {
+            url.to_string()
+        }
+    } else {
+        url.to_string()
+    }
+}
+
+// Alarms embedded in subscribed feeds are discarded.
+pub fn split_subscription_feed(feed: &ICalendar) -> AHashMap<String, ICalendar> {
+    split_calendar_objects(feed, false)
+}
+
+// Splits an iCalendar stream into one calendar object resource per UID.
+pub fn split_calendar_objects(feed: &ICalendar, with_alarms: bool) -> AHashMap<String, ICalendar> {
+    let mut objects: AHashMap<String, ICalendar> = AHashMap::new();
+    let root_entries = feed
+        .components
+        .first()
+        .filter(|root| root.component_type == ICalendarComponentType::VCalendar)
+        .map(|root| {
+            root.entries
+                .iter()
+                .filter(|entry| {
+                    matches!(
+                        entry.name,
+                        ICalendarProperty::Version | ICalendarProperty::Prodid
+                    )
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    for component in &feed.components {
+        if !component.component_type.is_scheduling_object() {
+            continue;
+        }
+        let Some(uid) = component.uid() else {
+            continue;
+        };
+
+        let object = objects.entry(uid.to_string()).or_insert_with(|| ICalendar {
+            components: vec![ICalendarComponent {
+                component_type: ICalendarComponentType::VCalendar,
+                entries: root_entries.clone(),
+                component_ids: vec![],
+            }],
+        });
+
+        // Copy component and its sub-components
+        let comp_id = object.components.len() as u32;
+        object.components[0].component_ids.push(comp_id);
+        object.components.push(ICalendarComponent {
+            component_type: component.component_type.clone(),
+            entries: component.entries.clone(),
+            component_ids: vec![],
+        });
+        for sub_component in component
+            .component_ids
+            .iter()
+            .filter_map(|id| feed.component_by_id(*id))
+            .filter(|sub_component| {
+                with_alarms || sub_component.component_type != ICalendarComponentType::VAlarm
+            })
+        {
+            let sub_comp_id = object.components.len() as u32;
+            object.components[comp_id as usize]
+                .component_ids
+                .push(sub_comp_id);
+            object.components.push(ICalendarComponent {
+                component_type: sub_component.component_type.clone(),
+                entries: sub_component.entries.clone(),
+                component_ids: vec![],
+            });
+        }
+    }
+
+    for object in objects.values_mut() {
+        object.copy_timezones(feed);
+    }
+
+    objects
+}
@@ -0,0 +1,389 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{AddressBook, AddressBookPreferences, ContactCard, DIRECTORY_ADDRESSBOOK_ID};
+use calcard::{Entry, Parser};
+use common::{DavName, DirectoryCard, DirectoryCards, DirectoryScope, Server, auth::AccountCache};
+use registry::schema::{
+    enums::{AddressBookDirectoryField, AddressBookDirectoryScope},
+    prelude::ObjectType,
+    structs::{Task, TaskAddressBookDirectory, TaskStatus},
+};
+use std::{sync::Arc, time::Duration};
+use store::{
+    Deserialize, ValueKey,
+    registry::RegistryQuery,
+    roaring::RoaringBitmap,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder},
+    xxhash_rust::xxh3,
+};
+use trc::{AddContext, StoreEvent};
+use types::{
+    collection::{Collection, SyncCollection, VanishedCollection},
+    field::PrincipalField,
+    id::Id,
+};
+
+// Global address list entries are not stored per account, they are served from a
+// shared snapshot under document ids derived from the directory account id.
+pub const DIRECTORY_CARD_ID_BASE: u32 = 1 << 31;
+
+// Cards of the global address list last announced to an account through its change log
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
+)]
+pub struct DirectoryState {
+    pub name: String,
+    pub entries: Vec<DirectoryStateEntry>,
+}
+
+#[derive(
+    rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
+)]
+pub struct DirectoryStateEntry {
+    pub document_id: u32,
+    pub hash: u32,
+}
+
+pub trait DirectoryAddressBook: Sync + Send {
+    fn directory_cards(
+        &self,
+        account: &AccountCache,
+    ) -> impl Future<Output = trc::Result<Arc<DirectoryCards>>> + Send;
+
+    fn directory_state(
+        &self,
+        account_id: u32,
+    ) -> impl Future<Output = trc::Result<Option<DirectoryState>>> + Send;
+
+    fn contact_archive(
+        &self,
+        account_id: u32,
+        collection: Collection,
+        document_id: u32,
+    ) -> impl Future<Output = trc::Result<Option<Archive<AlignedBytes>>>> + Send;
+}
+
+impl DirectoryAddressBook for Server {
+    async fn directory_cards(&self, account: &AccountCache) -> trc::Result<Arc<DirectoryCards>> {
+        let scope = DirectoryScope {
+            tenant_id: account.id_tenant,
+            domain_id: if self.core.groupware.directory_addressbook_scope
+                == AddressBookDirectoryScope::Domain
+            {
+                account.addresses.first().map(|addr| addr.domain_id)
+            } else {
+                None
+            },
+        };
+
+        if let Some(cards) = self.inner.cache.directory_cards.get(&scope) {
+            trc::event!(Store(StoreEvent::CacheHit), Collection = "directoryCards",);
+            return Ok(cards);
+        }
+
+        trc::event!(Store(StoreEvent::CacheMiss), Collection = "directoryCards",);
+
+        let cards = Arc::new(build_directory_cards(self, scope).await?);
+        self.inner.cache.directory_cards.insert(
+            scope,
+            cards.clone(),
+            Duration::from_secs(self.core.groupware.directory_addressbook_refresh),
+        );
+
+        Ok(cards)
+    }
+
+    async fn directory_state(&self, account_id: u32) -> trc::Result<Option<DirectoryState>> {
+        self.store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                account_id,
+                Collection::Principal,
+                0,
+                PrincipalField::DirectoryState,
+            ))
+            .await
+            .caused_by(trc::location!())?
+            .map(|state| {
+                state
+                    .deserialize::<DirectoryState>()
+                    .caused_by(trc::location!())
+            })
+            .transpose()
+    }
+
+    async fn contact_archive(
+        &self,
+        account_id: u32,
+        collection: Collection,
+        document_id: u32,
+    ) -> trc::Result<Option<Archive<AlignedBytes>>> {
+        let is_virtual = match collection {
+            Collection::AddressBook => document_id == DIRECTORY_ADDRESSBOOK_ID,
+            Collection::ContactCard => is_directory_card(document_id),
+            _ => false,
+        };
+        if !is_virtual {
+            return self
+                .store()
+                .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                    account_id,
+                    collection,
+                    document_id,
+                ))
+                .await;
+        }
+
+        let config = &self.core.groupware;
+        let Some(name) = &config.directory_addressbook_name else {
+            return Ok(None);
+        };
+        let Some(account) = self
+            .try_account(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .filter(|account| account.is_user_account())
+        else {
+            return Ok(None);
+        };
+
+        let bytes = if collection == Collection::AddressBook {
+            Archiver::new(AddressBook {
+                name: name.clone(),
+                preferences: vec![AddressBookPreferences {
+                    account_id,
+                    name: config.directory_addressbook_display_name.clone(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            })
+            .serialize()
+        } else {
+            let cards = self.directory_cards(&account).await?;
+            let Ok(idx) = cards
+                .cards
+                .binary_search_by_key(&document_id, |card| card.document_id)
+            else {
+                return Ok(None);
+            };
+            let card = &cards.cards[idx];
+            Archiver::new(ContactCard {
+                names: vec![DavName {
+                    name: directory_card_name(document_id),
+                    parent_id: DIRECTORY_ADDRESSBOOK_ID,
+                }],
+                size: card.card.size() as u32,
+                card: card.card.clone(),
+                ..Default::default()
+            })
+            .serialize()
+        }
+        .caused_by(trc::location!())?;
+
+        Archive::<AlignedBytes>::deserialize_owned(bytes)
+            .caused_by(trc::location!())
+            .map(Some)
+    }
+}
+
+#[inline(always)]
+pub fn is_directory_card(document_id: u32) -> bool {
+    document_id >= DIRECTORY_CARD_ID_BASE && document_id < DIRECTORY_ADDRESSBOOK_ID
+}
+
+pub fn directory_card_name(document_id: u32) -> String {
+    format!(
+        "directory-{}.vcf",
+        Id::from(document_id - DIRECTORY_CARD_ID_BASE)
+    )
+}
+
+pub fn schedule_directory_sync(account_id: u32, due: i64, batch: &mut BatchBuilder) {
+    batch.schedule_task(Task::AddressBookDirectorySync(TaskAddressBookDirectory {
+        account_id: account_id.into(),
+        status: TaskStatus::at(due),
+    }));
+}
+
+pub fn set_directory_state(
+    account_id: u32,
+    state: Option<DirectoryState>,
+    batch: &mut BatchBuilder,
+) -> trc::Result<()> {
+    batch
+        .with_account_id(account_id)
+        .with_collection(Collection::Principal)
+        .with_document(0);
+    if let Some(state) = state {
+        batch.set(
+            PrincipalField::DirectoryState,
+            Archiver::new(state)
+                .serialize()
+                .caused_by(trc::location!())?,
+        );
+    } else {
+        batch.clear(PrincipalField::DirectoryState);
+    }
+    Ok(())
+}
+
+// Removes the global address list from an account's change log after the feature was
+// disabled or the account stopped being eligible for it.
+pub fn remove_directory_addressbook(
+    account_id: u32,
+    base_path: &str,
+    state: &DirectoryState,
+    batch: &mut BatchBuilder,
+) -> trc::Result<()> {
+    batch.with_account_id(account_id);
+    for entry in state.entries.iter() {
+        batch
+            .with_collection(Collection::ContactCard)
+            .with_document(entry.document_id)
+            .log_item_delete(SyncCollection::AddressBook, None)
+            .log_vanished_item(
+                VanishedCollection::AddressBook,
+                format!(
+                    "{base_path}{}/{}",
+                    state.name,
+                    directory_card_name(entry.document_id)
+                ),
+            );
+    }
+    batch
+        .with_collection(Collection::AddressBook)
+        .with_document(DIRECTORY_ADDRESSBOOK_ID)
+        .log_container_delete(SyncCollection::AddressBook);
+    set_directory_state(account_id, None, batch)?;
+    batch.commit_point();
+    Ok(())
+}
+
+// Builds one vCard per account visible in the global address list of the given scope,
+// ordered by document id.
+async fn build_directory_cards(
+    server: &Server,
+    scope: DirectoryScope,
+) -> trc::Result<DirectoryCards> {
+    let config = &server.core.groupware;
+    let account_ids = server
+        .registry()
+        .query::<RoaringBitmap>(
+            RegistryQuery::new(ObjectType::Account).with_tenant(scope.tenant_id),
+        )
+        .await
+        .caused_by(trc::location!())?;
+    let mut cards = DirectoryCards {
+        cards: Vec::with_capacity(account_ids.len() as usize),
+        size: std::mem::size_of::<DirectoryCards>() as u64,
+    };
+
+    for account_id in account_ids {
+        let document_id = DIRECTORY_CARD_ID_BASE.saturating_add(account_id);
+        if !is_directory_card(document_id) {
+            continue;
+        }
+        let Some(entry) = server
+            .try_account(account_id)
+            .await
+            .caused_by(trc::location!())?
+        else {
+            continue;
+        };
+        if entry.id_tenant != scope.tenant_id
+            || scope.domain_id.is_some_and(|domain_id| {
+                entry.addresses.first().map(|addr| addr.domain_id) != Some(domain_id)
+            })
+        {
+            continue;
+        }
+
+        let mut emails = Vec::with_capacity(entry.addresses.len());
+        for addr in entry.addresses.iter() {
+            if let Some(domain) = server
+                .domain_by_id(addr.domain_id)
+                .await
+                .caused_by(trc::location!())?
+                && let Some(name) = domain.names.first()
+            {
+                emails.push(format!("{}@{}", addr.local_part, name));
+            }
+        }
+
+        let mut raw = format!(
+            "BEGIN:VCARD\r\nVERSION:4.0\r\nUID:directory-{}\r\nKIND:{}\r\n",
+            Id::from(account_id),
+            if entry.is_user_account() {
+                "individual"
+            } else {
+                "group"
+            }
+        );
+        let mut has_fn = false;
+        for (field, property) in config.directory_addressbook_fields.iter() {
+            let values = match field {
+                AddressBookDirectoryField::Name => vec![entry.name.to_string()],
+                AddressBookDirectoryField::Description => {
+                    entry.description.iter().map(|d| d.to_string()).collect()
+                }
+                AddressBookDirectoryField::Email => emails.iter().take(1).cloned().collect(),
+                AddressBookDirectoryField::Aliases => emails.iter().skip(1).cloned().collect(),
+                AddressBookDirectoryField::MemberOf => {
+                    let mut groups = Vec::with_capacity(entry.id_member_of.len());
+                    for group_id in entry.id_member_of.iter() {
+                        if let Some(group) = server
+                            .try_account(*group_id)
+                            .await
+                            .caused_by(trc::location!())?
+                        {
+                            groups.push(group.description().unwrap_or(group.name()).to_string());
+                        }
+                    }
+                    groups
+                }
+            };
+
+            for value in values {
+                has_fn |= property == "FN";
+                raw.push_str(property);
+                raw.push(':');
+                escape_text(&value, &mut raw);
+                raw.push_str("\r\n");
+            }
+        }
+        if !has_fn {
+            raw.push_str("FN:");
+            escape_text(entry.description().unwrap_or(entry.name()), &mut raw);
+            raw.push_str("\r\n");
+        }
+        raw.push_str("END:VCARD\r\n");
+
+        if let Entry::VCard(card) = Parser::new(&raw).entry() {
+            cards.size += (std::mem::size_of::<DirectoryCard>() + raw.len()) as u64;
+            cards.cards.push(DirectoryCard {
+                document_id,
+                hash: xxh3::xxh3_64(raw.as_bytes()) as u32,
+                card,
+            });
+        }
+    }
+
+    Ok(cards)
+}
+
+fn escape_text(value: &str, out: &mut String) {
+    for ch in value.chars() {
+        match ch {
+            '\\' | ',' | ';' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            _ => out.push(ch),
+        }
+    }
+}
@@ -4,6 +4,7 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+pub mod directory;
 pub mod index;
 pub mod storage;
 
@@ -11,6 +12,9 @@ use calcard::vcard::VCard;
 use common::{DavName, auth::AccessToken};
 use types::{acl::AclGrant, dead_property::DeadProperty};
 
+// Read-only address book holding the global address list
+pub const DIRECTORY_ADDRESSBOOK_ID: u32 = u32::MAX - 1;
+
 #[derive(
     rkyv::Archive, rkyv::Deserialize, rkyv::Serialize, Debug, Default, Clone, PartialEq, Eq,
 )]
@@ -7,7 +7,8 @@
 #![deny(clippy::large_futures)]
 
 use calcard::common::timezone::Tz;
-use common::DavResources;
+use calendar::CALENDAR_SUBSCRIPTION;
+use common::{DavResourceMetadata, DavResources};
 use percent_encoding::{AsciiSet, CONTROLS};
 use types::collection::{Collection, SyncCollection};
 
@@ -16,6 +17,8 @@ pub mod calendar;
 pub mod contact;
 pub mod file;
 pub mod scheduling;
+pub mod share_link;
+pub mod transfer;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DavResourceName {
@@ -140,6 +143,7 @@ impl From<SyncCollection> for DavResourceName {
 
 pub trait DavCalendarResource {
     fn calendar_default_tz(&self, calendar_id: u32, account_id: u32) -> Option<Tz>;
+    fn is_calendar_subscription(&self, calendar_id: u32) -> bool;
 }
 
 impl DavCalendarResource for DavResources {
@@ -148,4 +152,14 @@ impl DavCalendarResource for DavResources {
             .and_then(|c| c.calendar_preferences(account_id))
             .map(|p| p.tz)
     }
+
+    fn is_calendar_subscription(&self, calendar_id: u32) -> bool {
+        self.container_resource_by_id(calendar_id)
+            .is_some_and(|c| match &c.data {
+                DavResourceMetadata::Calendar { preferences, .. } => preferences
+                    .first()
+                    .is_some_and(|p| p.flags & CALENDAR_SUBSCRIPTION != 0),
+                _ => false,
+            })
+    }
 }
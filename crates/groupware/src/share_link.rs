@@ -0,0 +1,150 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::transfer::{export_address_book, export_calendar};
+use common::{Server, auth::oauth::GrantType};
+use registry::schema::{enums::ShareLinkCollection, structs::ShareLink};
+use std::net::IpAddr;
+use store::write::now;
+use trc::AddContext;
+use types::id::Id;
+
+pub struct ShareLinkExport {
+    pub content_type: &'static str,
+    pub contents: String,
+}
+
+pub trait ShareLinks: Sync + Send {
+    fn share_link_url(
+        &self,
+        account_id: u32,
+        link_id: Id,
+        collection: ShareLinkCollection,
+        expires_in: u64,
+    ) -> impl Future<Output = trc::Result<Option<String>>> + Send;
+
+    fn share_link_export(
+        &self,
+        token: &str,
+        remote_ip: IpAddr,
+    ) -> impl Future<Output = trc::Result<Option<ShareLinkExport>>> + Send;
+}
+
+impl ShareLinks for Server {
+    async fn share_link_url(
+        &self,
+        account_id: u32,
+        link_id: Id,
+        collection: ShareLinkCollection,
+        expires_in: u64,
+    ) -> trc::Result<Option<String>> {
+        if let Some(base_url) = &self.core.groupware.share_link_url {
+            let token = self
+                .encode_access_token(
+                    GrantType::ShareLink,
+                    account_id,
+                    &link_id.id().to_string(),
+                    expires_in,
+                )
+                .await
+                .caused_by(trc::location!())?;
+            let extension = match collection {
+                ShareLinkCollection::Calendar => "ics",
+                ShareLinkCollection::AddressBook => "vcf",
+            };
+
+            Ok(Some(format!("{base_url}/{token}.{extension}")))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn share_link_export(
+        &self,
+        token: &str,
+        remote_ip: IpAddr,
+    ) -> trc::Result<Option<ShareLinkExport>> {
+        // Validate token, the file extension is optional
+        let token = token
+            .rsplit_once('.')
+            .filter(|(_, ext)| matches!(*ext, "ics" | "vcf"))
+            .map_or(token, |(token, _)| token);
+        let Some((account_id, link_id)) = self
+            .validate_access_token(GrantType::ShareLink.into(), token)
+            .await
+            .ok()
+            .and_then(|info| {
+                info.client_id
+                    .parse::<u64>()
+                    .ok()
+                    .map(|link_id| (info.account_id, Id::new(link_id)))
+            })
+        else {
+            trc::event!(
+                WebDav(trc::WebDavEvent::Error),
+                RemoteIp = remote_ip,
+                Reason = "Invalid or expired share link",
+            );
+            return Ok(None);
+        };
+
+        // Make sure the link has not been revoked
+        let Some(link) = self
+            .registry()
+            .object::<ShareLink>(link_id)
+            .await
+            .caused_by(trc::location!())?
+            .filter(|link| {
+                link.account_id.document_id() == account_id
+                    && link
+                        .expires_at
+                        .is_none_or(|at| at.timestamp() > now() as i64)
+            })
+        else {
+            trc::event!(
+                WebDav(trc::WebDavEvent::Error),
+                AccountId = account_id,
+                Id = link_id.id(),
+                RemoteIp = remote_ip,
+                Reason = "Share link has been revoked",
+            );
+            return Ok(None);
+        };
+        let collection_id = link.collection_id.document_id();
+
+        let export = match link.collection {
+            ShareLinkCollection::Calendar => export_calendar(self, account_id, collection_id)
+                .await?
+                .map(|contents| ShareLinkExport {
+                    content_type: "text/calendar; charset=utf-8",
+                    contents,
+                }),
+            ShareLinkCollection::AddressBook => {
+                export_address_book(self, account_id, collection_id)
+                    .await?
+                    .map(|contents| ShareLinkExport {
+                        content_type: "text/vcard; charset=utf-8",
+                        contents,
+                    })
+            }
+        };
+
+        trc::event!(
+            WebDav(if export.is_some() {
+                trc::WebDavEvent::Get
+            } else {
+                trc::WebDavEvent::Error
+            }),
+            AccountId = account_id,
+            DocumentId = collection_id,
+            Id = link_id.id(),
+            RemoteIp = remote_ip,
+            Size = export.as_ref().map_or(0, |export| export.contents.len()),
+        );
+
+        Ok(export)
+    }
+}
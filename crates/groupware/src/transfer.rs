@@ -0,0 +1,558 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    DavCalendarResource, DestroyArchive,
+    cache::GroupwareCache,
+    calendar::{Calendar, CalendarEvent, CalendarEventData, subscription::split_calendar_objects},
+    contact::{ContactCard, DIRECTORY_ADDRESSBOOK_ID, directory::DirectoryAddressBook},
+};
+use calcard::{
+    Entry, Parser,
+    common::timezone::Tz,
+    icalendar::{
+        ICalendar, ICalendarComponent, ICalendarComponentType, ICalendarEntry, ICalendarProperty,
+        ICalendarValue,
+    },
+    vcard::VCard,
+};
+use common::{DavName, PROD_ID, Server};
+use serde::Serialize;
+use store::{
+    ValueKey,
+    ahash::{AHashMap, AHashSet},
+    write::{AlignedBytes, Archive, BatchBuilder, now},
+};
+use trc::AddContext;
+use types::collection::{Collection, SyncCollection};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImportStrategy {
+    // Objects are added or updated by UID, anything else is kept
+    #[default]
+    Merge,
+    // Objects missing from the import are removed
+    Replace,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub failed: usize,
+}
+
+pub trait CollectionImport: Sync + Send {
+    fn import_calendar(
+        &self,
+        account_id: u32,
+        calendar_id: u32,
+        contents: &str,
+        strategy: ImportStrategy,
+    ) -> impl Future<Output = trc::Result<Option<ImportReport>>> + Send;
+
+    fn import_address_book(
+        &self,
+        account_id: u32,
+        addressbook_id: u32,
+        contents: &str,
+        strategy: ImportStrategy,
+    ) -> impl Future<Output = trc::Result<Option<ImportReport>>> + Send;
+}
+
+impl CollectionImport for Server {
+    async fn import_calendar(
+        &self,
+        account_id: u32,
+        calendar_id: u32,
+        contents: &str,
+        strategy: ImportStrategy,
+    ) -> trc::Result<Option<ImportReport>> {
+        let resources = self
+            .fetch_dav_resources(account_id, account_id, SyncCollection::Calendar)
+            .await
+            .caused_by(trc::location!())?;
+        if !resources.has_container_id(&calendar_id)
+            || resources.is_calendar_subscription(calendar_id)
+        {
+            return Ok(None);
+        }
+
+        // Split the stream into one calendar object per UID
+        let mut report = ImportReport::default();
+        let mut objects = AHashMap::new();
+        let mut parser = Parser::new(contents);
+        loop {
+            match parser.entry() {
+                Entry::ICalendar(ical) => objects.extend(split_calendar_objects(&ical, true)),
+                Entry::Eof => break,
+                _ => report.failed += 1,
+            }
+        }
+        let max_size = self.core.groupware.max_ical_size;
+        objects.retain(|_, ical| {
+            let is_valid = ical.size() <= max_size;
+            if !is_valid {
+                report.failed += 1;
+            }
+            is_valid
+        });
+
+        let account_info = self
+            .account_info(account_id)
+            .await
+            .caused_by(trc::location!())?;
+        let max_instances = self.core.groupware.max_ical_instances;
+        let now = now() as i64;
+        let mut batch = BatchBuilder::new();
+
+        // Update or remove existing events
+        for document_id in resources.children_ids(calendar_id) {
+            let Some(event_) = self
+                .store()
+                .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                    account_id,
+                    Collection::CalendarEvent,
+                    document_id,
+                ))
+                .await
+                .caused_by(trc::location!())?
+            else {
+                continue;
+            };
+            let event = event_
+                .to_unarchived::<CalendarEvent>()
+                .caused_by(trc::location!())?;
+
+            match event
+                .inner
+                .data
+                .event
+                .uids()
+                .next()
+                .and_then(|uid| objects.remove(uid))
+            {
+                Some(ical) if ical.to_string() != event.inner.data.event.to_string() => {
+                    let prev_email_alarm = event.inner.data.next_alarm(now, Tz::Floating);
+                    let mut next_email_alarm = None;
+                    let mut new_event = event
+                        .deserialize::<CalendarEvent>()
+                        .caused_by(trc::location!())?;
+                    new_event.size = ical.size() as u32;
+                    new_event.data = CalendarEventData::new(
+                        ical,
+                        Tz::Floating,
+                        max_instances,
+                        &mut next_email_alarm,
+                    );
+                    new_event
+                        .update(
+                            account_info.account_tenant_ids(),
+                            event,
+                            account_id,
+                            document_id,
+                            &mut batch,
+                        )
+                        .caused_by(trc::location!())?;
+                    if prev_email_alarm != next_email_alarm {
+                        if let Some(prev_alarm) = prev_email_alarm {
+                            prev_alarm.delete_task(&mut batch);
+                        }
+                        if let Some(next_alarm) = next_email_alarm {
+                            next_alarm.write_task(&mut batch);
+                        }
+                    }
+                    report.updated += 1;
+                }
+                Some(_) => {}
+                None if strategy == ImportStrategy::Replace => {
+                    DestroyArchive(event)
+                        .delete(
+                            &account_info,
+                            account_id,
+                            document_id,
+                            calendar_id,
+                            None,
+                            false,
+                            &mut batch,
+                        )
+                        .caused_by(trc::location!())?;
+                    report.removed += 1;
+                }
+                None => {}
+            }
+
+            if batch.is_large_batch() {
+                self.commit_batch(std::mem::take(&mut batch))
+                    .await
+                    .caused_by(trc::location!())?;
+            }
+        }
+
+        // Add new events
+        let total_size = objects.values().map(|ical| ical.size() as u64).sum();
+        if !objects.is_empty()
+            && self
+                .has_available_quota(self.account(account_id).await?.as_ref(), total_size)
+                .await
+                .is_err()
+        {
+            report.failed += objects.len();
+        } else if !objects.is_empty() {
+            let mut next_document_id = self
+                .store()
+                .assign_document_ids(account_id, Collection::CalendarEvent, objects.len() as u64)
+                .await
+                .caused_by(trc::location!())?;
+            for ical in objects.into_values() {
+                let document_id = next_document_id;
+                next_document_id -= 1;
+                let mut next_email_alarm = None;
+                CalendarEvent {
+                    names: vec![DavName::new_with_rand_name(calendar_id)],
+                    size: ical.size() as u32,
+                    data: CalendarEventData::new(
+                        ical,
+                        Tz::Floating,
+                        max_instances,
+                        &mut next_email_alarm,
+                    ),
+                    ..Default::default()
+                }
+                .insert(
+                    account_info.account_tenant_ids(),
+                    account_id,
+                    document_id,
+                    next_email_alarm,
+                    &mut batch,
+                )
+                .caused_by(trc::location!())?;
+                report.added += 1;
+
+                if batch.is_large_batch() {
+                    self.commit_batch(std::mem::take(&mut batch))
+                        .await
+                        .caused_by(trc::location!())?;
+                }
+            }
+        }
+
+        if !batch.is_empty() {
+            self.commit_batch(batch).await.caused_by(trc::location!())?;
+        }
+
+        Ok(Some(report))
+    }
+
+    async fn import_address_book(
+        &self,
+        account_id: u32,
+        addressbook_id: u32,
+        contents: &str,
+        strategy: ImportStrategy,
+    ) -> trc::Result<Option<ImportReport>> {
+        let resources = self
+            .fetch_dav_resources(account_id, account_id, SyncCollection::AddressBook)
+            .await
+            .caused_by(trc::location!())?;
+        if !resources.has_container_id(&addressbook_id)
+            || addressbook_id == DIRECTORY_ADDRESSBOOK_ID
+        {
+            return Ok(None);
+        }
+
+        // Cards without a UID are always added
+        let mut report = ImportReport::default();
+        let mut cards: AHashMap<String, VCard> = AHashMap::new();
+        let mut new_cards = Vec::new();
+        let mut parser = Parser::new(contents);
+        let max_size = self.core.groupware.max_vcard_size;
+        loop {
+            match parser.entry() {
+                Entry::VCard(vcard) if vcard.size() <= max_size => {
+                    if let Some(uid) = vcard.uid() {
+                        cards.insert(uid.to_string(), vcard);
+                    } else {
+                        new_cards.push(vcard);
+                    }
+                }
+                Entry::Eof => break,
+                _ => report.failed += 1,
+            }
+        }
+
+        let account_info = self
+            .account_info(account_id)
+            .await
+            .caused_by(trc::location!())?;
+        let mut batch = BatchBuilder::new();
+
+        // Update or remove existing cards
+        for document_id in resources.children_ids(addressbook_id) {
+            let Some(card_) = self
+                .store()
+                .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                    account_id,
+                    Collection::ContactCard,
+                    document_id,
+                ))
+                .await
+                .caused_by(trc::location!())?
+            else {
+                continue;
+            };
+            let card = card_
+                .to_unarchived::<ContactCard>()
+                .caused_by(trc::location!())?;
+
+            match card.inner.card.uid().and_then(|uid| cards.remove(uid)) {
+                Some(vcard) if vcard.to_string() != card.inner.card.to_string() => {
+                    let mut new_card = card
+                        .deserialize::<ContactCard>()
+                        .caused_by(trc::location!())?;
+                    new_card.size = vcard.size() as u32;
+                    new_card.card = vcard;
+                    new_card
+                        .update(
+                            account_info.account_tenant_ids(),
+                            card,
+                            account_id,
+                            document_id,
+                            &mut batch,
+                        )
+                        .caused_by(trc::location!())?;
+                    report.updated += 1;
+                }
+                Some(_) => {}
+                None if strategy == ImportStrategy::Replace => {
+                    DestroyArchive(card)
+                        .delete(
+                            account_info.account_tenant_ids(),
+                            account_id,
+                            document_id,
+                            addressbook_id,
+                            None,
+                            &mut batch,
+                        )
+                        .caused_by(trc::location!())?;
+                    report.removed += 1;
+                }
+                None => {}
+            }
+
+            if batch.is_large_batch() {
+                self.commit_batch(std::mem::take(&mut batch))
+                    .await
+                    .caused_by(trc::location!())?;
+            }
+        }
+
+        // Add new cards
+        new_cards.extend(cards.into_values());
+        let total_size = new_cards.iter().map(|card| card.size() as u64).sum();
+        if !new_cards.is_empty()
+            && self
+                .has_available_quota(self.account(account_id).await?.as_ref(), total_size)
+                .await
+                .is_err()
+        {
+            report.failed += new_cards.len();
+        } else if !new_cards.is_empty() {
+            let mut next_document_id = self
+                .store()
+                .assign_document_ids(account_id, Collection::ContactCard, new_cards.len() as u64)
+                .await
+                .caused_by(trc::location!())?;
+            for card in new_cards {
+                let document_id = next_document_id;
+                next_document_id -= 1;
+                ContactCard {
+                    names: vec![DavName::new_with_rand_name(addressbook_id)],
+                    size: card.size() as u32,
+                    card,
+                    ..Default::default()
+                }
+                .insert(
+                    account_info.account_tenant_ids(),
+                    account_id,
+                    document_id,
+                    &mut batch,
+                )
+                .caused_by(trc::location!())?;
+                report.added += 1;
+
+                if batch.is_large_batch() {
+                    self.commit_batch(std::mem::take(&mut batch))
+                        .await
+                        .caused_by(trc::location!())?;
+                }
+            }
+        }
+
+        if !batch.is_empty() {
+            self.commit_batch(batch).await.caused_by(trc::location!())?;
+        }
+
+        Ok(Some(report))
+    }
+}
+
+pub async fn export_calendar(
+    server: &Server,
+    account_id: u32,
+    calendar_id: u32,
+) -> trc::Result<Option<String>> {
+    let resources = server
+        .fetch_dav_resources(account_id, account_id, SyncCollection::Calendar)
+        .await
+        .caused_by(trc::location!())?;
+    if !resources.has_container_id(&calendar_id) {
+        return Ok(None);
+    }
+    let Some(calendar_) = server
+        .store()
+        .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+            account_id,
+            Collection::Calendar,
+            calendar_id,
+        ))
+        .await
+        .caused_by(trc::location!())?
+    else {
+        return Ok(None);
+    };
+    let calendar = calendar_
+        .unarchive::<Calendar>()
+        .caused_by(trc::location!())?;
+
+    let mut entries = vec![
+        ICalendarEntry {
+            name: ICalendarProperty::Version,
+            params: vec![],
+            values: vec![ICalendarValue::Text("2.0".to_string())],
+        },
+        ICalendarEntry {
+            name: ICalendarProperty::Prodid,
+            params: vec![],
+            values: vec![ICalendarValue::Text(PROD_ID.to_string())],
+        },
+    ];
+    if let Some(preferences) = calendar.preferences.first() {
+        entries.push(ICalendarEntry {
+            name: ICalendarProperty::Other("X-WR-CALNAME".to_string()),
+            params: vec![],
+            values: vec![ICalendarValue::Text(preferences.name.to_string())],
+        });
+    }
+    let mut export = ICalendar {
+        components: vec![ICalendarComponent {
+            component_type: ICalendarComponentType::VCalendar,
+            entries,
+            component_ids: vec![],
+        }],
+    };
+    let mut timezones = AHashSet::new();
+
+    for document_id in resources.children_ids(calendar_id) {
+        let Some(event_) = server
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::CalendarEvent,
+                document_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            continue;
+        };
+        let event = event_
+            .deserialize::<CalendarEvent>()
+            .caused_by(trc::location!())?
+            .data
+            .event;
+
+        for comp_id in event
+            .components
+            .first()
+            .map(|root| root.component_ids.as_slice())
+            .unwrap_or_default()
+        {
+            // Include each timezone definition only once
+            if let Some(component) = event.component_by_id(*comp_id)
+                && component.component_type == ICalendarComponentType::VTimezone
+                && let Some(tz_id) = component
+                    .entries
+                    .iter()
+                    .find(|entry| entry.name == ICalendarProperty::Tzid)
+                    .and_then(|entry| entry.values.first())
+                    .and_then(|value| value.as_text())
+                && !timezones.insert(tz_id.to_string())
+            {
+                continue;
+            }
+
+            if let Some(comp_id) = copy_component(&mut export, &event, *comp_id) {
+                export.components[0].component_ids.push(comp_id);
+            }
+        }
+    }
+
+    Ok(Some(export.to_string()))
+}
+
+pub async fn export_address_book(
+    server: &Server,
+    account_id: u32,
+    addressbook_id: u32,
+) -> trc::Result<Option<String>> {
+    let resources = server
+        .fetch_dav_resources(account_id, account_id, SyncCollection::AddressBook)
+        .await
+        .caused_by(trc::location!())?;
+    if !resources.has_container_id(&addressbook_id) {
+        return Ok(None);
+    }
+
+    let mut export = String::new();
+    for document_id in resources.children_ids(addressbook_id) {
+        let Some(card_) = server
+            .contact_archive(account_id, Collection::ContactCard, document_id)
+            .await
+            .caused_by(trc::location!())?
+        else {
+            continue;
+        };
+        let card = card_
+            .unarchive::<ContactCard>()
+            .caused_by(trc::location!())?;
+        let _ = card
+            .card
+            .write_to(&mut export, card.card.version().unwrap_or_default());
+    }
+
+    Ok(Some(export))
+}
+
+fn copy_component(export: &mut ICalendar, ical: &ICalendar, comp_id: u32) -> Option<u32> {
+    let component = ical.component_by_id(comp_id)?;
+    let new_comp_id = export.components.len() as u32;
+    export.components.push(ICalendarComponent {
+        component_type: component.component_type.clone(),
+        entries: component.entries.clone(),
+        component_ids: vec![],
+    });
+    let component_ids = component
+        .component_ids
+        .iter()
+        .filter_map(|comp_id| copy_component(export, ical, *comp_id))
+        .collect();
+    export.components[new_comp_id as usize].component_ids = component_ids;
+
+    Some(new_comp_id)
+}
@@ -8,14 +8,27 @@ use crate::{HttpContext, HttpRequest, HttpSessionData};
 use common::{
     Server,
     expr::{functions::ResolveVariable, *},
+    network::asn::AsnGeoLookupResult,
 };
 use compact_str::{ToCompactString, format_compact};
-use hyper::StatusCode;
+use hyper::{Method, StatusCode, header};
 use registry::schema::enums::ExpressionVariable;
 
 impl<'x> HttpContext<'x> {
     pub fn new(session: &'x HttpSessionData, req: &'x HttpRequest) -> Self {
-        Self { session, req }
+        Self {
+            session,
+            req,
+            asn_geo_data: AsnGeoLookupResult::default(),
+        }
+    }
+
+    // The lookup is skipped unless an HTTP expression references the ASN or country
+    pub async fn with_asn_geo_data(mut self, server: &Server) -> Self {
+        if server.core.network.http.asn_geo_lookup {
+            self.asn_geo_data = server.lookup_asn_country(self.session.remote_ip).await;
+        }
+        self
     }
 
     #[allow(unused_variables)]
@@ -58,6 +71,77 @@ impl<'x> HttpContext<'x> {
     }
 }
 
+pub struct CorsPolicy {
+    pub allow_origin: String,
+    pub allow_credentials: bool,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+    pub max_age: u64,
+}
+
+impl HttpContext<'_> {
+    // Returns the CORS policy to apply when the request origin is allowed
+    pub async fn cors_policy(&self, server: &Server) -> Option<CorsPolicy> {
+        let origin = self
+            .req
+            .headers()
+            .get(header::ORIGIN)
+            .and_then(|h| h.to_str().ok())?;
+        let cors = &server.core.network.http.cors;
+        let session_id = self.session.session_id;
+        let origins = match server
+            .eval_if::<Vec<String>, _>(&cors.allowed_origins, self, session_id)
+            .await
+        {
+            Some(origins) => origins,
+            None if cors.allow_any_origin => vec!["*".to_string()],
+            None => return None,
+        };
+        let allow_credentials = server
+            .eval_if::<bool, _>(&cors.allow_credentials, self, session_id)
+            .await
+            .unwrap_or(false);
+
+        // Wildcards cannot be used in credentialed requests, the origin is echoed instead
+        let allow_origin = if origins.iter().any(|allowed| allowed == "*") {
+            if allow_credentials {
+                origin.to_string()
+            } else {
+                "*".to_string()
+            }
+        } else if origins
+            .iter()
+            .any(|allowed| allowed.trim_end_matches('/').eq_ignore_ascii_case(origin))
+        {
+            origin.to_string()
+        } else {
+            return None;
+        };
+
+        Some(CorsPolicy {
+            allow_origin,
+            allow_credentials,
+            allowed_methods: server
+                .eval_if(&cors.allowed_methods, self, session_id)
+                .await
+                .unwrap_or_default(),
+            allowed_headers: server
+                .eval_if(&cors.allowed_headers, self, session_id)
+                .await
+                .unwrap_or_default(),
+            max_age: cors.max_age,
+        })
+    }
+
+    pub fn is_cors_preflight(&self) -> bool {
+        self.req.method() == Method::OPTIONS
+            && self
+                .req
+                .headers()
+                .contains_key(header::ACCESS_CONTROL_REQUEST_METHOD)
+    }
+}
+
 impl ResolveVariable for HttpContext<'_> {
     fn resolve_variable(&self, variable: ExpressionVariable) -> Variable<'_> {
         match variable {
@@ -66,6 +150,20 @@ impl ResolveVariable for HttpContext<'_> {
             ExpressionVariable::LocalIp => self.session.local_ip.to_compact_string().into(),
             ExpressionVariable::LocalPort => self.session.local_port.into(),
             ExpressionVariable::IsTls => self.session.is_tls.into(),
+            ExpressionVariable::TlsJa3 => self
+                .session
+                .tls_fingerprint
+                .as_ref()
+                .map(|fp| fp.ja3.as_str())
+                .unwrap_or_default()
+                .into(),
+            ExpressionVariable::TlsJa4 => self
+                .session
+                .tls_fingerprint
+                .as_ref()
+                .map(|fp| fp.ja4.as_str())
+                .unwrap_or_default()
+                .into(),
             ExpressionVariable::Protocol => {
                 if self.session.is_tls { "https" } else { "http" }.into()
             }
@@ -85,6 +183,20 @@ impl ResolveVariable for HttpContext<'_> {
                 })
                 .collect::<Vec<_>>()
                 .into(),
+            ExpressionVariable::Asn => self
+                .asn_geo_data
+                .asn
+                .as_ref()
+                .map(|a| a.id)
+                .unwrap_or_default()
+                .into(),
+            ExpressionVariable::Country => self
+                .asn_geo_data
+                .country
+                .as_ref()
+                .map(|c| c.as_str())
+                .unwrap_or_default()
+                .into(),
             _ => Variable::default(),
         }
     }
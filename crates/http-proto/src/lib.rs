@@ -12,7 +12,7 @@ pub mod response;
 
 pub use form_urlencoded;
 
-use common::network::ServerInstance;
+use common::network::{ServerInstance, asn::AsnGeoLookupResult, fingerprint::TlsFingerprint};
 use hyper::StatusCode;
 use std::{net::IpAddr, sync::Arc};
 
@@ -46,6 +46,7 @@ pub struct HttpResponse {
 pub struct HttpContext<'x> {
     pub session: &'x HttpSessionData,
     pub req: &'x HttpRequest,
+    pub asn_geo_data: AsnGeoLookupResult,
 }
 
 pub struct HttpSessionData {
@@ -56,6 +57,8 @@ pub struct HttpSessionData {
     pub remote_port: u16,
     pub is_tls: bool,
     pub session_id: u64,
+    pub client_certificate: Option<Arc<[u8]>>,
+    pub tls_fingerprint: Option<Arc<TlsFingerprint>>,
 }
 
 pub struct DownloadResponse {
@@ -4,7 +4,8 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use common::manager::application::Resource;
+use common::{config::network::HttpCompression, manager::application::Resource};
+use flate2::{Compression, write::GzEncoder};
 use http_body_util::{BodyExt, Full};
 use hyper::{
     StatusCode,
@@ -12,10 +13,11 @@ use hyper::{
     header::{self, HeaderName, HeaderValue},
 };
 use serde_json::json;
+use std::io::Write;
 
 use crate::{
     DownloadResponse, HtmlResponse, HttpResponse, HttpResponseBody, JsonProblemResponse,
-    JsonResponse, ToHttpResponse,
+    JsonResponse, ToHttpResponse, context::CorsPolicy,
 };
 
 impl HttpResponse {
@@ -179,6 +181,87 @@ impl HttpResponse {
         self
     }
 
+    // Compresses text and binary bodies using the best encoding accepted by the client,
+    // responses below the size threshold or with other content types are left as is.
+    pub async fn with_compression(
+        mut self,
+        accept_encoding: &str,
+        config: &HttpCompression,
+    ) -> Self {
+        let body_len = match &self.body {
+            HttpResponseBody::Text(body) => body.len(),
+            HttpResponseBody::Binary(body) => body.len(),
+            _ => return self,
+        };
+        let Some(headers) = self.builder.headers_mut() else {
+            return self;
+        };
+        if body_len < config.min_size
+            || matches!(
+                self.status,
+                StatusCode::NO_CONTENT | StatusCode::NOT_MODIFIED | StatusCode::PARTIAL_CONTENT
+            )
+            || headers.contains_key(header::CONTENT_ENCODING)
+            || !headers
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|content_type| is_compressible(config, content_type))
+        {
+            return self;
+        }
+
+        headers.append(header::VARY, HeaderValue::from_static("Accept-Encoding"));
+        let Some(encoding) = ContentEncoding::negotiate(accept_encoding) else {
+            return self;
+        };
+
+        // Large bodies are compressed on the blocking pool to avoid stalling the runtime
+        let body = match std::mem::replace(&mut self.body, HttpResponseBody::Empty) {
+            HttpResponseBody::Text(body) => body.into_bytes(),
+            HttpResponseBody::Binary(body) => body,
+            _ => unreachable!(),
+        };
+        let (body, compressed) = if body_len >= BLOCKING_COMPRESSION_SIZE {
+            match tokio::task::spawn_blocking(move || {
+                let compressed = encoding.compress(&body);
+                (body, compressed)
+            })
+            .await
+            {
+                Ok(result) => result,
+                Err(err) => {
+                    trc::event!(
+                        Http(trc::HttpEvent::Error),
+                        Details = "Response compression failed",
+                        Reason = err.to_string()
+                    );
+                    return self;
+                }
+            }
+        } else {
+            let compressed = encoding.compress(&body);
+            (body, compressed)
+        };
+
+        match compressed {
+            Ok(compressed) if compressed.len() < body.len() => {
+                if let Some(headers) = self.builder.headers_mut() {
+                    headers.insert(
+                        header::CONTENT_ENCODING,
+                        HeaderValue::from_static(encoding.as_str()),
+                    );
+                    headers.insert(header::CONTENT_LENGTH, HeaderValue::from(compressed.len()));
+                }
+                self.body = HttpResponseBody::Binary(compressed);
+            }
+            _ => {
+                self.body = HttpResponseBody::Binary(body);
+            }
+        }
+
+        self
+    }
+
     pub fn size(&self) -> usize {
         match &self.body {
             HttpResponseBody::Text(value) => value.len(),
@@ -296,3 +379,192 @@ impl ToHttpResponse for JsonProblemResponse {
             )
     }
 }
+
+// Bodies of at least this size are compressed outside of the async runtime
+const BLOCKING_COMPRESSION_SIZE: usize = 128 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ContentEncoding {
+    Brotli,
+    Zstd,
+    Gzip,
+}
+
+impl ContentEncoding {
+    // Picks the encoding with the highest quality value, ties are resolved
+    // in order of preference (br, zstd, gzip).
+    fn negotiate(accept_encoding: &str) -> Option<Self> {
+        let mut result: Option<(Self, f32)> = None;
+        let mut wildcard = None;
+        let mut rejected = Vec::new();
+
+        for item in accept_encoding.split(',') {
+            let mut parts = item.split(';');
+            let name = parts.next().unwrap_or_default().trim();
+            let quality = parts
+                .find_map(|param| {
+                    param
+                        .trim()
+                        .strip_prefix("q=")
+                        .and_then(|q| q.trim().parse::<f32>().ok())
+                })
+                .unwrap_or(1.0);
+            let encoding = if name.eq_ignore_ascii_case("br") {
+                ContentEncoding::Brotli
+            } else if name.eq_ignore_ascii_case("zstd") {
+                ContentEncoding::Zstd
+            } else if name.eq_ignore_ascii_case("gzip") || name.eq_ignore_ascii_case("x-gzip") {
+                ContentEncoding::Gzip
+            } else if name == "*" {
+                wildcard = Some(quality);
+                continue;
+            } else {
+                continue;
+            };
+
+            if quality <= 0.0 {
+                rejected.push(encoding);
+            } else if result.is_none_or(|(current, current_quality)| {
+                quality > current_quality
+                    || (quality == current_quality && encoding.preference() < current.preference())
+            }) {
+                result = Some((encoding, quality));
+            }
+        }
+
+        result.map(|(encoding, _)| encoding).or_else(|| {
+            wildcard.filter(|quality| *quality > 0.0).and_then(|_| {
+                [
+                    ContentEncoding::Brotli,
+                    ContentEncoding::Zstd,
+                    ContentEncoding::Gzip,
+                ]
+                .into_iter()
+                .find(|encoding| !rejected.contains(encoding))
+            })
+        })
+    }
+
+    fn preference(&self) -> u8 {
+        match self {
+            ContentEncoding::Brotli => 0,
+            ContentEncoding::Zstd => 1,
+            ContentEncoding::Gzip => 2,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ContentEncoding::Brotli => "br",
+            ContentEncoding::Zstd => "zstd",
+            ContentEncoding::Gzip => "gzip",
+        }
+    }
+
+    fn compress(&self, data: &[u8]) -> std::io::Result<Vec<u8>> {
+        match self {
+            ContentEncoding::Brotli => {
+                let mut writer = brotli::CompressorWriter::new(Vec::new(), 4096, 4, 22);
+                writer.write_all(data)?;
+                Ok(writer.into_inner())
+            }
+            ContentEncoding::Zstd => zstd::bulk::compress(data, 3),
+            ContentEncoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Compression::new(5));
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+        }
+    }
+}
+
+fn is_compressible(config: &HttpCompression, content_type: &str) -> bool {
+    let content_type = content_type
+        .split_once(';')
+        .map_or(content_type, |(content_type, _)| content_type)
+        .trim();
+    config.content_types.iter().any(|allowed| {
+        if let Some(prefix) = allowed.strip_suffix('*') {
+            content_type
+                .get(..prefix.len())
+                .is_some_and(|ct| ct.eq_ignore_ascii_case(prefix))
+        } else {
+            content_type.eq_ignore_ascii_case(allowed)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ContentEncoding;
+
+    #[test]
+    fn negotiate_content_encoding() {
+        for (accept_encoding, expected) in [
+            ("", None),
+            ("identity", None),
+            ("deflate", None),
+            ("gzip", Some(ContentEncoding::Gzip)),
+            ("x-gzip", Some(ContentEncoding::Gzip)),
+            ("GZIP", Some(ContentEncoding::Gzip)),
+            ("zstd", Some(ContentEncoding::Zstd)),
+            ("br", Some(ContentEncoding::Brotli)),
+            // Ties are resolved by preference
+            ("gzip, deflate, br, zstd", Some(ContentEncoding::Brotli)),
+            ("gzip, zstd", Some(ContentEncoding::Zstd)),
+            // Quality values
+            ("br;q=0.5, gzip;q=0.8", Some(ContentEncoding::Gzip)),
+            ("br; q=0.9, zstd ;q=1.0", Some(ContentEncoding::Zstd)),
+            ("gzip;q=0, br;q=0", None),
+            ("gzip;q=0.1, br;q=0", Some(ContentEncoding::Gzip)),
+            ("gzip;q=invalid", Some(ContentEncoding::Gzip)),
+            // Wildcards only select encodings that were not rejected
+            ("*", Some(ContentEncoding::Brotli)),
+            ("*;q=0", None),
+            ("br;q=0, *", Some(ContentEncoding::Zstd)),
+            ("br;q=0, zstd;q=0, *;q=0.5", Some(ContentEncoding::Gzip)),
+            ("br;q=0, zstd;q=0, gzip;q=0, *", None),
+            // Explicit encodings take precedence over wildcards
+            ("gzip;q=0.2, *", Some(ContentEncoding::Gzip)),
+        ] {
+            assert_eq!(
+                ContentEncoding::negotiate(accept_encoding),
+                expected,
+                "{accept_encoding:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn compress_round_trip() {
+        use std::io::Read;
+
+        let data = "The quick brown fox jumps over the lazy dog. ".repeat(100);
+        for encoding in [
+            ContentEncoding::Brotli,
+            ContentEncoding::Zstd,
+            ContentEncoding::Gzip,
+        ] {
+            let compressed = encoding.compress(data.as_bytes()).unwrap();
+            assert!(compressed.len() < data.len(), "{encoding:?}");
+
+            let mut decompressed = Vec::new();
+            match encoding {
+                ContentEncoding::Brotli => {
+                    brotli::Decompressor::new(compressed.as_slice(), 4096)
+                        .read_to_end(&mut decompressed)
+                        .unwrap();
+                }
+                ContentEncoding::Zstd => {
+                    decompressed = zstd::bulk::decompress(&compressed, data.len()).unwrap();
+                }
+                ContentEncoding::Gzip => {
+                    flate2::read::GzDecoder::new(compressed.as_slice())
+                        .read_to_end(&mut decompressed)
+                        .unwrap();
+                }
+            }
+            assert_eq!(decompressed, data.as_bytes(), "{encoding:?}");
+        }
+    }
+}
@@ -0,0 +1,57 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{
+    BuildServer, Inner,
+    network::{SessionData, SessionManager, SessionStream},
+};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+
+// Replies to HAProxy agent checks with the health of this node, the
+// connection is closed as soon as the reply is sent.
+#[derive(Clone)]
+pub struct AgentCheckSessionManager {
+    pub inner: Arc<Inner>,
+}
+
+impl AgentCheckSessionManager {
+    pub fn new(inner: Arc<Inner>) -> Self {
+        Self { inner }
+    }
+}
+
+impl SessionManager for AgentCheckSessionManager {
+    #[allow(clippy::manual_async_fn)]
+    fn handle<T: SessionStream>(
+        self,
+        mut session: SessionData<T>,
+    ) -> impl std::future::Future<Output = ()> + Send {
+        async move {
+            let reply = self
+                .inner
+                .build_server()
+                .health_status()
+                .await
+                .to_agent_reply();
+
+            if let Err(err) = session.stream.write_all(reply.as_bytes()).await {
+                trc::event!(
+                    Network(trc::NetworkEvent::WriteError),
+                    SpanId = session.session_id,
+                    Reason = err.to_string(),
+                );
+                return;
+            }
+            let _ = session.stream.shutdown().await;
+        }
+    }
+
+    #[allow(clippy::manual_async_fn)]
+    fn shutdown(&self) -> impl std::future::Future<Output = ()> + Send {
+        async {}
+    }
+}
@@ -0,0 +1,172 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{
+    Server,
+    auth::AccessToken,
+    manager::audit::{AuditEntry, AuditFilter},
+};
+use http_proto::{HttpRequest, HttpResponse, JsonResponse, ToHttpResponse};
+use registry::{schema::prelude::ObjectType, types::datetime::UTCDateTime};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::{future::Future, net::IpAddr, str::FromStr};
+use store::ahash::AHashMap;
+use types::id::Id;
+use utils::url_params::UrlParams;
+
+const DEFAULT_LIMIT: usize = 100;
+
+pub trait AuditLogManagement: Sync + Send {
+    fn handle_audit_log_query(
+        &self,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_audit_log_verify(
+        &self,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuditLogEntry {
+    id: Id,
+    timestamp: UTCDateTime,
+    node_id: u64,
+    account_id: Id,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tenant_id: Option<Id>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    impersonator_id: Option<Id>,
+    remote_ip: IpAddr,
+    action: &'static str,
+    object_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    object_id: Option<Id>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    before: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    after: Option<Value>,
+}
+
+impl AuditLogManagement for Server {
+    async fn handle_audit_log_query(
+        &self,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(ObjectType::Log.query_permission())?;
+        access_token.enforce_permission(ObjectType::Log.get_permission())?;
+
+        let params = UrlParams::new(req.uri().query());
+        let filter = AuditFilter {
+            account_id: parse_id(&params, "accountId")?.map(|id| id.document_id()),
+            tenant_id: access_token.tenant_id(),
+            impersonator_id: parse_id(&params, "impersonatorId")?.map(|id| id.document_id()),
+            object_type: params.get("objectType").map(|typ| typ.to_string()),
+            object_id: parse_id(&params, "objectId")?.map(|id| id.id()),
+            from: parse_date(&params, "from")?,
+            to: parse_date(&params, "to")?,
+            limit: params.parse("limit").unwrap_or(DEFAULT_LIMIT),
+        };
+
+        let mut account_names = AHashMap::new();
+        let mut results = Vec::new();
+        for (id, entry) in self.query_audit_log(&filter).await? {
+            let account_name = match account_names.get(&entry.account_id) {
+                Some(name) => name.clone(),
+                None => {
+                    let name = self
+                        .account(entry.account_id)
+                        .await
+                        .ok()
+                        .map(|account| account.name.to_string());
+                    account_names.insert(entry.account_id, name.clone());
+                    name
+                }
+            };
+
+            results.push(AuditLogEntry::new(id, entry, account_name));
+        }
+
+        Ok(JsonResponse::new(results).no_cache().into_http_response())
+    }
+
+    async fn handle_audit_log_verify(
+        &self,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(ObjectType::Log.query_permission())?;
+        access_token.enforce_permission(ObjectType::Log.get_permission())?;
+
+        let result = self.verify_audit_log().await?;
+
+        Ok(JsonResponse::new(json!({
+            "valid": result.first_invalid.is_none(),
+            "entries": result.entries,
+            "firstInvalid": result.first_invalid.map(Id::from),
+        }))
+        .no_cache()
+        .into_http_response())
+    }
+}
+
+impl AuditLogEntry {
+    fn new(id: u64, entry: AuditEntry, account_name: Option<String>) -> Self {
+        AuditLogEntry {
+            id: Id::from(id),
+            timestamp: UTCDateTime::from_timestamp(entry.timestamp as i64),
+            node_id: entry.node_id,
+            account_id: Id::from(entry.account_id),
+            account_name,
+            tenant_id: entry.tenant_id.map(Id::from),
+            impersonator_id: entry.impersonator_id.map(Id::from),
+            remote_ip: entry.remote_ip,
+            action: entry.action.as_str(),
+            object_type: entry.object_type,
+            object_id: entry.object_id.map(Id::from),
+            before: entry
+                .before
+                .and_then(|value| serde_json::from_str(&value).ok()),
+            after: entry
+                .after
+                .and_then(|value| serde_json::from_str(&value).ok()),
+        }
+    }
+}
+
+fn parse_id(params: &UrlParams<'_>, name: &str) -> trc::Result<Option<Id>> {
+    params
+        .get(name)
+        .map(|id| {
+            Id::from_str(id).map_err(|_| {
+                trc::ResourceEvent::BadParameters
+                    .into_err()
+                    .details(format!("Invalid {name} parameter"))
+            })
+        })
+        .transpose()
+}
+
+fn parse_date(params: &UrlParams<'_>, name: &str) -> trc::Result<Option<u64>> {
+    params
+        .get(name)
+        .map(|date| {
+            UTCDateTime::from_str(date)
+                .map(|date| date.timestamp().max(0) as u64)
+                .map_err(|_| {
+                    trc::ResourceEvent::BadParameters
+                        .into_err()
+                        .details(format!("Invalid {name} parameter"))
+                })
+        })
+        .transpose()
+}
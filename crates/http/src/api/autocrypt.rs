@@ -0,0 +1,104 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::mailbox_stats::authorize_account;
+use common::{
+    Server,
+    auth::AccessToken,
+    manager::audit::{AuditAction, AuditEntry},
+};
+use email::autocrypt::AutocryptStore;
+use http_proto::{HttpResponse, HttpSessionData, JsonResponse, ToHttpResponse};
+use registry::schema::enums::Permission;
+use serde_json::json;
+use std::future::Future;
+use types::id::Id;
+
+pub trait AutocryptManagement: Sync + Send {
+    fn handle_autocrypt_get(
+        &self,
+        account_id: &str,
+        address: Option<&str>,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_autocrypt_destroy(
+        &self,
+        account_id: &str,
+        address: Option<&str>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+impl AutocryptManagement for Server {
+    async fn handle_autocrypt_get(
+        &self,
+        account_id: &str,
+        address: Option<&str>,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::SysPublicKeyGet)?;
+
+        let mut peers = self.autocrypt_peers(account_id).await?;
+        if let Some(address) = address {
+            let address = address.trim().to_lowercase();
+            peers.peers.retain(|peer| peer.address == address);
+            if peers.peers.is_empty() {
+                return Err(trc::ResourceEvent::NotFound.into_err());
+            }
+        }
+
+        Ok(JsonResponse::new(peers).no_cache().into_http_response())
+    }
+
+    async fn handle_autocrypt_destroy(
+        &self,
+        account_id: &str,
+        address: Option<&str>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::SysPublicKeyDestroy)?;
+
+        // Without an address the whole peer cache is removed
+        let previous = self.autocrypt_peers(account_id).await?;
+        let mut peers = previous.clone();
+        if let Some(address) = address {
+            let address = address.trim().to_lowercase();
+            peers.peers.retain(|peer| peer.address != address);
+        } else {
+            peers.peers.clear();
+        }
+
+        if peers != previous {
+            self.set_autocrypt_peers(account_id, &peers).await?;
+
+            let entry = AuditEntry::new(
+                access_token,
+                session.remote_ip,
+                AuditAction::Destroy,
+                "AutocryptPeers",
+                Some(account_id as u64),
+            )
+            .with_before(&json!({
+                "accountId": Id::from(account_id),
+                "addresses": previous.peers.iter().map(|peer| &peer.address).collect::<Vec<_>>()
+            }))
+            .with_after(&json!({
+                "accountId": Id::from(account_id),
+                "addresses": peers.peers.iter().map(|peer| &peer.address).collect::<Vec<_>>()
+            }));
+            if let Err(err) = self.write_audit_log(vec![entry]).await {
+                trc::error!(err.caused_by(trc::location!()));
+            }
+        }
+
+        Ok(JsonResponse::new(peers).no_cache().into_http_response())
+    }
+}
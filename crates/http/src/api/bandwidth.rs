@@ -0,0 +1,119 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::mailbox_stats::authorize_account;
+use common::{Server, auth::AccessToken, telemetry::metrics::bandwidth::BandwidthProtocol};
+use http_proto::{HttpRequest, HttpResponse, JsonResponse, ToHttpResponse};
+use serde::Serialize;
+use std::future::Future;
+use trc::AddContext;
+use types::id::Id;
+use utils::url_params::UrlParams;
+
+pub trait BandwidthManagement: Sync + Send {
+    fn handle_account_bandwidth(
+        &self,
+        account_id: &str,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountBandwidth {
+    account_id: Id,
+    total: ProtocolBandwidth,
+    protocols: Vec<ProtocolBandwidth>,
+    days: Vec<DailyBandwidth>,
+}
+
+#[derive(Debug, Serialize)]
+struct DailyBandwidth {
+    date: u64,
+    total: ProtocolBandwidth,
+    protocols: Vec<ProtocolBandwidth>,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+struct ProtocolBandwidth {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    protocol: Option<&'static str>,
+    received: u64,
+    sent: u64,
+}
+
+impl BandwidthManagement for Server {
+    async fn handle_account_bandwidth(
+        &self,
+        account_id: &str,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        let days = UrlParams::new(req.uri().query())
+            .parse::<u64>("days")
+            .unwrap_or(30);
+
+        let usage = self
+            .bandwidth_usage(account_id, days)
+            .await
+            .caused_by(trc::location!())?;
+
+        let mut total = ProtocolBandwidth::default();
+        let mut protocols = BandwidthProtocol::ALL
+            .iter()
+            .map(|protocol| ProtocolBandwidth {
+                protocol: Some(protocol.as_str()),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let days = usage
+            .into_iter()
+            .map(|day| {
+                let mut day_total = ProtocolBandwidth::default();
+                let day_protocols = day
+                    .protocols
+                    .into_iter()
+                    .map(|(protocol, counter)| {
+                        day_total.received += counter.received;
+                        day_total.sent += counter.sent;
+                        if let Some(totals) = protocols
+                            .iter_mut()
+                            .find(|p| p.protocol == Some(protocol.as_str()))
+                        {
+                            totals.received += counter.received;
+                            totals.sent += counter.sent;
+                        }
+
+                        ProtocolBandwidth {
+                            protocol: Some(protocol.as_str()),
+                            received: counter.received,
+                            sent: counter.sent,
+                        }
+                    })
+                    .collect();
+                total.received += day_total.received;
+                total.sent += day_total.sent;
+
+                DailyBandwidth {
+                    date: day.day,
+                    total: day_total,
+                    protocols: day_protocols,
+                }
+            })
+            .collect();
+
+        Ok(JsonResponse::new(AccountBandwidth {
+            account_id: Id::from(account_id),
+            total,
+            protocols,
+            days,
+        })
+        .no_cache()
+        .into_http_response())
+    }
+}
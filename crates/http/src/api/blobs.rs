@@ -0,0 +1,174 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{Server, auth::AccessToken};
+use http_proto::{HttpRequest, HttpResponse, JsonResponse, ToHttpResponse};
+use registry::{
+    schema::{
+        enums::{Permission, TaskStoreMaintenanceType},
+        prelude::ObjectType,
+        structs::{Task, TaskStatus, TaskStoreMaintenance},
+    },
+    types::EnumImpl,
+};
+use serde::Serialize;
+use std::future::Future;
+use store::write::{BatchBuilder, blob::BlobStats};
+use trc::AddContext;
+use utils::url_params::UrlParams;
+
+pub trait BlobManagement: Sync + Send {
+    fn handle_blob_stats(
+        &self,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_blob_purge(
+        &self,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BlobReport {
+    linked_blobs: u64,
+    links: u64,
+    dedup_ratio: f64,
+    expired_links: u64,
+    orphaned_blobs: u64,
+    reclaimable_bytes: u64,
+    orphaned: Vec<OrphanedBlob>,
+}
+
+#[derive(Debug, Serialize)]
+struct OrphanedBlob {
+    hash: String,
+    size: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PurgeResponse {
+    scheduled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shard_index: Option<u8>,
+}
+
+impl BlobManagement for Server {
+    async fn handle_blob_stats(
+        &self,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(ObjectType::BlobStore.get_permission())?;
+
+        let params = UrlParams::new(req.uri().query());
+        blob_report(
+            self,
+            params.parse("shard"),
+            params.parse("limit").unwrap_or(100),
+        )
+        .await
+        .map(|report| JsonResponse::new(report).no_cache().into_http_response())
+    }
+
+    async fn handle_blob_purge(
+        &self,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(ObjectType::BlobStore.get_permission())?;
+
+        let params = UrlParams::new(req.uri().query());
+        let shard_index = params.parse::<u8>("shard");
+
+        // A dry run lists the blobs that would be deleted
+        if params.parse::<bool>("dryRun").unwrap_or(false) {
+            return blob_report(self, shard_index, params.parse("limit").unwrap_or(100))
+                .await
+                .map(|report| JsonResponse::new(report).no_cache().into_http_response());
+        }
+
+        access_token.enforce_permission(Permission::SysTaskCreate)?;
+
+        trc::event!(
+            TaskManager(trc::TaskManagerEvent::TaskQueued),
+            Type = TaskStoreMaintenanceType::PurgeBlob.as_str()
+        );
+
+        let mut batch = BatchBuilder::new();
+        batch.schedule_task(Task::StoreMaintenance(TaskStoreMaintenance {
+            maintenance_type: TaskStoreMaintenanceType::PurgeBlob,
+            shard_index: shard_index.map(|shard_index| shard_index as u64),
+            status: TaskStatus::now(),
+        }));
+        self.store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())?;
+        self.notify_task_queue();
+
+        Ok(JsonResponse::new(PurgeResponse {
+            scheduled: true,
+            shard_index,
+        })
+        .no_cache()
+        .into_http_response())
+    }
+}
+
+async fn blob_report(
+    server: &Server,
+    shard_index: Option<u8>,
+    limit: usize,
+) -> trc::Result<BlobReport> {
+    let shards = match shard_index {
+        Some(shard_index) => shard_index..=shard_index,
+        None => 0..=u8::MAX,
+    };
+
+    let mut total = BlobStats::default();
+    for shard_index in shards {
+        let stats = server
+            .store()
+            .blob_stats(server.blob_store(), shard_index)
+            .await
+            .caused_by(trc::location!())?;
+        total.linked_blobs += stats.linked_blobs;
+        total.links += stats.links;
+        total.expired_links += stats.expired_links;
+        total.orphaned_blobs.extend(stats.orphaned_blobs);
+    }
+
+    Ok(BlobReport {
+        linked_blobs: total.linked_blobs,
+        links: total.links,
+        dedup_ratio: if total.linked_blobs > 0 {
+            total.links as f64 / total.linked_blobs as f64
+        } else {
+            0.0
+        },
+        expired_links: total.expired_links,
+        orphaned_blobs: total.orphaned_blobs.len() as u64,
+        reclaimable_bytes: total
+            .orphaned_blobs
+            .iter()
+            .map(|(_, size)| *size as u64)
+            .sum(),
+        orphaned: total
+            .orphaned_blobs
+            .into_iter()
+            .take(limit)
+            .map(|(hash, size)| OrphanedBlob {
+                hash: hash.to_hex(),
+                size,
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,203 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::mailbox_stats::authorize_account;
+use common::{
+    Server,
+    auth::AccessToken,
+    manager::audit::{AuditAction, AuditEntry},
+};
+use groupware::{
+    cache::GroupwareCache,
+    calendar::{CalendarBookingPolicy, booking::CalendarBooking},
+};
+use http_proto::{HttpResponse, HttpSessionData, JsonResponse, ToHttpResponse};
+use registry::schema::enums::Permission;
+use serde::Serialize;
+use serde_json::json;
+use std::{future::Future, str::FromStr};
+use store::write::BatchBuilder;
+use trc::AddContext;
+use types::{collection::SyncCollection, id::Id};
+
+pub trait BookingPolicyManagement: Sync + Send {
+    fn handle_booking_policy_list(
+        &self,
+        account_id: &str,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_booking_policy_get(
+        &self,
+        account_id: &str,
+        calendar_id: &str,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_booking_policy_set(
+        &self,
+        account_id: &str,
+        calendar_id: &str,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BookingPolicyResponse {
+    calendar_id: Id,
+    #[serde(flatten)]
+    policy: CalendarBookingPolicy,
+}
+
+impl BookingPolicyManagement for Server {
+    async fn handle_booking_policy_list(
+        &self,
+        account_id: &str,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::JmapCalendarGet)?;
+
+        let resources = self
+            .fetch_dav_resources(account_id, account_id, SyncCollection::Calendar)
+            .await
+            .caused_by(trc::location!())?;
+        let mut policies = Vec::new();
+        for calendar_id in resources.document_ids(true) {
+            if let Some(policy) = self
+                .calendar_booking_policy(account_id, calendar_id)
+                .await?
+            {
+                policies.push(BookingPolicyResponse {
+                    calendar_id: Id::from(calendar_id),
+                    policy,
+                });
+            }
+        }
+
+        Ok(JsonResponse::new(policies).no_cache().into_http_response())
+    }
+
+    async fn handle_booking_policy_get(
+        &self,
+        account_id: &str,
+        calendar_id: &str,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::JmapCalendarGet)?;
+        let calendar_id = calendar_document_id(self, account_id, calendar_id).await?;
+
+        Ok(JsonResponse::new(BookingPolicyResponse {
+            calendar_id: Id::from(calendar_id),
+            policy: self
+                .calendar_booking_policy(account_id, calendar_id)
+                .await?
+                .unwrap_or_default(),
+        })
+        .no_cache()
+        .into_http_response())
+    }
+
+    async fn handle_booking_policy_set(
+        &self,
+        account_id: &str,
+        calendar_id: &str,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::JmapCalendarUpdate)?;
+        let calendar_id = calendar_document_id(self, account_id, calendar_id).await?;
+
+        // An empty body removes the policy
+        let policy = match body.as_deref() {
+            Some(body) if !body.is_empty() => serde_json::from_slice(body).map_err(|err| {
+                trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+            })?,
+            _ => CalendarBookingPolicy::default(),
+        };
+        if policy.max_duration == Some(0)
+            || policy
+                .min_notice
+                .zip(policy.max_advance)
+                .is_some_and(|(min_notice, max_advance)| min_notice >= max_advance)
+        {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Invalid booking window or duration"));
+        }
+
+        let previous = self
+            .calendar_booking_policy(account_id, calendar_id)
+            .await?
+            .unwrap_or_default();
+        let mut batch = BatchBuilder::new();
+        policy
+            .clone()
+            .write(account_id, calendar_id, &mut batch)
+            .caused_by(trc::location!())?;
+        self.store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())?;
+
+        let entry = AuditEntry::new(
+            access_token,
+            session.remote_ip,
+            if policy.is_empty() {
+                AuditAction::Destroy
+            } else {
+                AuditAction::Update
+            },
+            "CalendarBookingPolicy",
+            Some(calendar_id as u64),
+        )
+        .with_before(&json!({ "accountId": Id::from(account_id), "policy": previous }))
+        .with_after(&json!({ "accountId": Id::from(account_id), "policy": policy }));
+        if let Err(err) = self.write_audit_log(vec![entry]).await {
+            trc::error!(err.caused_by(trc::location!()));
+        }
+
+        Ok(JsonResponse::new(BookingPolicyResponse {
+            calendar_id: Id::from(calendar_id),
+            policy,
+        })
+        .no_cache()
+        .into_http_response())
+    }
+}
+
+async fn calendar_document_id(
+    server: &Server,
+    account_id: u32,
+    calendar_id: &str,
+) -> trc::Result<u32> {
+    let calendar_id = Id::from_str(calendar_id)
+        .map_err(|_| {
+            trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Invalid calendar id")
+        })?
+        .document_id();
+
+    if server
+        .fetch_dav_resources(account_id, account_id, SyncCollection::Calendar)
+        .await
+        .caused_by(trc::location!())?
+        .has_container_id(&calendar_id)
+    {
+        Ok(calendar_id)
+    } else {
+        Err(trc::ResourceEvent::NotFound
+            .into_err()
+            .details("Calendar not found"))
+    }
+}
@@ -0,0 +1,232 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{
+    Server,
+    auth::AccessToken,
+    ipc::ClusterQuery,
+    manager::cluster::{
+        ClusterNodeReply, NodeDelivery, NodeMetric, NodeQueue, NodeSession, NodeSessionList,
+        NodeSessions, NodeStatus,
+    },
+};
+use http_proto::{HttpResponse, JsonResponse, ToHttpResponse};
+use registry::{
+    schema::{enums::Permission, prelude::ObjectType},
+    types::{EnumImpl, datetime::UTCDateTime},
+};
+use serde::Serialize;
+use std::future::Future;
+use store::ahash::AHashMap;
+use types::id::Id;
+
+pub trait ClusterManagement: Sync + Send {
+    fn handle_cluster_request(
+        &self,
+        endpoint: &str,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+struct NodeReply<T> {
+    #[serde(rename = "nodeId")]
+    node_id: u16,
+    hostname: String,
+    status: &'static str,
+    #[serde(rename = "lastRenewal")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_renewal: Option<UTCDateTime>,
+    reachable: bool,
+    #[serde(flatten)]
+    reply: Option<T>,
+}
+
+// Entry reported by a node, tagged with the id of the node it belongs to
+#[derive(Debug, Serialize)]
+struct NodeEntry<T> {
+    #[serde(rename = "nodeId")]
+    node_id: u16,
+    #[serde(flatten)]
+    entry: T,
+}
+
+#[derive(Debug, Serialize)]
+struct NodeMetrics {
+    metrics: Vec<NodeMetric>,
+}
+
+#[derive(Debug, Serialize)]
+struct MetricsResponse {
+    nodes: Vec<NodeReply<NodeMetrics>>,
+    total: Vec<NodeMetric>,
+}
+
+#[derive(Debug, Serialize)]
+struct SessionsResponse {
+    nodes: Vec<NodeReply<NodeSessions>>,
+    total: NodeSessions,
+    sessions: Vec<NodeEntry<NodeSession>>,
+}
+
+#[derive(Debug, Serialize)]
+struct QueueResponse {
+    #[serde(rename = "queuedMessages")]
+    queued_messages: u64,
+    #[serde(rename = "activeDeliveries")]
+    active_deliveries: u64,
+    nodes: Vec<NodeReply<NodeQueue>>,
+    deliveries: Vec<NodeEntry<NodeDelivery>>,
+}
+
+impl ClusterManagement for Server {
+    async fn handle_cluster_request(
+        &self,
+        endpoint: &str,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        match endpoint {
+            "nodes" => {
+                access_token.enforce_permission(ObjectType::ClusterNode.query_permission())?;
+                access_token.enforce_permission(ObjectType::ClusterNode.get_permission())?;
+
+                let nodes = self
+                    .cluster_query::<NodeStatus>(ClusterQuery::Status)
+                    .await?
+                    .into_iter()
+                    .map(NodeReply::from)
+                    .collect::<Vec<_>>();
+
+                Ok(JsonResponse::new(nodes).no_cache().into_http_response())
+            }
+            "metrics" => {
+                access_token.enforce_permission(Permission::LiveMetrics)?;
+
+                let nodes = self
+                    .cluster_query::<Vec<NodeMetric>>(ClusterQuery::Metrics)
+                    .await?;
+
+                // Add up the metrics of all nodes
+                let mut total: Vec<NodeMetric> = Vec::new();
+                let mut positions = AHashMap::new();
+                for metric in nodes.iter().flat_map(|node| node.reply.iter().flatten()) {
+                    match positions.get(&(metric.metric.as_str(), metric.metric_type)) {
+                        Some(&pos) => {
+                            let entry = &mut total[pos];
+                            entry.count += metric.count;
+                            if let Some(sum) = metric.sum {
+                                *entry.sum.get_or_insert_default() += sum;
+                            }
+                        }
+                        None => {
+                            positions
+                                .insert((metric.metric.as_str(), metric.metric_type), total.len());
+                            total.push(metric.clone());
+                        }
+                    }
+                }
+
+                Ok(JsonResponse::new(MetricsResponse {
+                    total,
+                    nodes: nodes
+                        .into_iter()
+                        .map(|node| NodeReply::from(node.map(|metrics| NodeMetrics { metrics })))
+                        .collect(),
+                })
+                .no_cache()
+                .into_http_response())
+            }
+            "sessions" => {
+                access_token.enforce_permission(Permission::LiveMetrics)?;
+
+                let nodes = self
+                    .cluster_query::<NodeSessionList>(ClusterQuery::Sessions)
+                    .await?;
+                let tenant_id = access_token.tenant_id().map(Id::from);
+                let mut total = NodeSessions::default();
+                let mut sessions = Vec::new();
+                let nodes = nodes
+                    .into_iter()
+                    .map(|node| {
+                        let node_id = node.node_id;
+                        NodeReply::from(node.map(|list| {
+                            sessions.extend(
+                                list.sessions
+                                    .into_iter()
+                                    .filter(|session| {
+                                        tenant_id.is_none_or(|tenant_id| {
+                                            session.tenant_id == Some(tenant_id)
+                                        })
+                                    })
+                                    .map(|entry| NodeEntry { node_id, entry }),
+                            );
+                            total.smtp += list.counts.smtp;
+                            total.imap += list.counts.imap;
+                            total.pop3 += list.counts.pop3;
+                            total.http += list.counts.http;
+                            total.sieve += list.counts.sieve;
+                            list.counts
+                        }))
+                    })
+                    .collect();
+                sessions.sort_by_key(|session| session.entry.started.timestamp());
+
+                Ok(JsonResponse::new(SessionsResponse {
+                    total,
+                    nodes,
+                    sessions,
+                })
+                .no_cache()
+                .into_http_response())
+            }
+            "queue" => {
+                access_token.enforce_permission(ObjectType::QueuedMessage.query_permission())?;
+
+                // Queued messages are shared by all nodes, deliveries in progress are not
+                let mut nodes = self.cluster_query::<NodeQueue>(ClusterQuery::Queue).await?;
+                let mut deliveries = Vec::new();
+                for node in &mut nodes {
+                    if let Some(queue) = &mut node.reply {
+                        deliveries.extend(std::mem::take(&mut queue.deliveries).into_iter().map(
+                            |entry| NodeEntry {
+                                node_id: node.node_id,
+                                entry,
+                            },
+                        ));
+                    }
+                }
+                deliveries.sort_by_key(|delivery| delivery.entry.started.timestamp());
+
+                Ok(JsonResponse::new(QueueResponse {
+                    queued_messages: self.total_queued_messages().await?,
+                    active_deliveries: nodes
+                        .iter()
+                        .filter_map(|node| node.reply.as_ref())
+                        .map(|queue| queue.active_deliveries)
+                        .sum(),
+                    nodes: nodes.into_iter().map(NodeReply::from).collect(),
+                    deliveries,
+                })
+                .no_cache()
+                .into_http_response())
+            }
+            _ => Err(trc::ResourceEvent::NotFound.into_err()),
+        }
+    }
+}
+
+impl<T> From<ClusterNodeReply<T>> for NodeReply<T> {
+    fn from(node: ClusterNodeReply<T>) -> Self {
+        NodeReply {
+            node_id: node.node_id,
+            hostname: node.hostname,
+            status: node.status.as_str(),
+            last_renewal: node.last_renewal,
+            reachable: node.reply.is_some(),
+            reply: node.reply,
+        }
+    }
+}
@@ -0,0 +1,185 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::mailbox_stats::authorize_account;
+use common::{
+    Server,
+    auth::AccessToken,
+    manager::audit::{AuditAction, AuditEntry},
+};
+use groupware::transfer::{CollectionImport, ImportStrategy, export_address_book, export_calendar};
+use http_proto::{HttpRequest, HttpResponse, HttpSessionData, JsonResponse, ToHttpResponse};
+use hyper::StatusCode;
+use registry::schema::enums::{Permission, ShareLinkCollection};
+use serde_json::json;
+use std::{future::Future, str::FromStr};
+use trc::AddContext;
+use types::id::Id;
+use utils::url_params::UrlParams;
+
+pub trait CollectionTransferManagement: Sync + Send {
+    fn handle_collection_export(
+        &self,
+        account_id: &str,
+        collection: ShareLinkCollection,
+        collection_id: &str,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    #[allow(clippy::too_many_arguments)]
+    fn handle_collection_import(
+        &self,
+        account_id: &str,
+        collection: ShareLinkCollection,
+        collection_id: &str,
+        req: &HttpRequest,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+impl CollectionTransferManagement for Server {
+    async fn handle_collection_export(
+        &self,
+        account_id: &str,
+        collection: ShareLinkCollection,
+        collection_id: &str,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        let collection_id = parse_collection_id(collection_id)?;
+
+        let (contents, content_type, extension) = match collection {
+            ShareLinkCollection::Calendar => {
+                access_token.enforce_permission(Permission::JmapCalendarEventGet)?;
+                (
+                    export_calendar(self, account_id, collection_id).await?,
+                    "text/calendar; charset=utf-8",
+                    "ics",
+                )
+            }
+            ShareLinkCollection::AddressBook => {
+                access_token.enforce_permission(Permission::JmapContactCardGet)?;
+                (
+                    export_address_book(self, account_id, collection_id).await?,
+                    "text/vcard; charset=utf-8",
+                    "vcf",
+                )
+            }
+        };
+        let contents = contents.ok_or_else(|| trc::ResourceEvent::NotFound.into_err())?;
+
+        Ok(HttpResponse::new(StatusCode::OK)
+            .with_content_type(content_type)
+            .with_content_disposition(format!(
+                "attachment; filename=\"{}.{extension}\"",
+                Id::from(collection_id)
+            ))
+            .with_no_store()
+            .with_binary_body(contents))
+    }
+
+    async fn handle_collection_import(
+        &self,
+        account_id: &str,
+        collection: ShareLinkCollection,
+        collection_id: &str,
+        req: &HttpRequest,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        let collection_id = parse_collection_id(collection_id)?;
+        let strategy = match UrlParams::new(req.uri().query()).get("strategy") {
+            None | Some("merge") => ImportStrategy::Merge,
+            Some("replace") => ImportStrategy::Replace,
+            Some(_) => {
+                return Err(trc::ResourceEvent::BadParameters
+                    .into_err()
+                    .details("Invalid import strategy"));
+            }
+        };
+        let body = body.ok_or_else(|| trc::LimitEvent::SizeRequest.into_err())?;
+        let contents = std::str::from_utf8(&body).map_err(|_| {
+            trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Invalid UTF-8 in request body")
+        })?;
+
+        let permissions = match collection {
+            ShareLinkCollection::Calendar => [
+                Permission::JmapCalendarEventCreate,
+                Permission::JmapCalendarEventUpdate,
+                Permission::JmapCalendarEventDestroy,
+            ],
+            ShareLinkCollection::AddressBook => [
+                Permission::JmapContactCardCreate,
+                Permission::JmapContactCardUpdate,
+                Permission::JmapContactCardDestroy,
+            ],
+        };
+        let permissions = if strategy == ImportStrategy::Replace {
+            &permissions[..]
+        } else {
+            &permissions[..2]
+        };
+        for permission in permissions {
+            access_token.enforce_permission(*permission)?;
+        }
+
+        let report = match collection {
+            ShareLinkCollection::Calendar => {
+                self.import_calendar(account_id, collection_id, contents, strategy)
+                    .await?
+            }
+            ShareLinkCollection::AddressBook => {
+                self.import_address_book(account_id, collection_id, contents, strategy)
+                    .await?
+            }
+        }
+        .ok_or_else(|| trc::ResourceEvent::NotFound.into_err())?;
+
+        let entry = AuditEntry::new(
+            access_token,
+            session.remote_ip,
+            AuditAction::Update,
+            match collection {
+                ShareLinkCollection::Calendar => "CalendarImport",
+                ShareLinkCollection::AddressBook => "AddressBookImport",
+            },
+            Some(collection_id as u64),
+        )
+        .with_after(&json!({
+            "accountId": Id::from(account_id),
+            "replace": strategy == ImportStrategy::Replace,
+            "report": &report,
+        }));
+        if let Err(err) = self.write_audit_log(vec![entry]).await {
+            trc::error!(err.caused_by(trc::location!()));
+        }
+
+        Ok(JsonResponse::with_status(
+            if report.failed == 0 {
+                StatusCode::OK
+            } else {
+                StatusCode::MULTI_STATUS
+            },
+            report,
+        )
+        .no_cache()
+        .into_http_response())
+    }
+}
+
+fn parse_collection_id(id: &str) -> trc::Result<u32> {
+    Id::from_str(id).map(|id| id.document_id()).map_err(|_| {
+        trc::ResourceEvent::BadParameters
+            .into_err()
+            .details("Invalid collection id")
+    })
+}
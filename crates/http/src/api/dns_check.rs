@@ -0,0 +1,374 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{Server, auth::AccessToken};
+use dns_update::{DnsRecord, MXRecord, NamedDnsRecord, TLSARecord, bind::BindSerializer};
+use http_proto::{HttpResponse, JsonResponse, ToHttpResponse};
+use mail_auth::{IpLookupStrategy, SpfResult, spf::verify::SpfParameters};
+use registry::{
+    schema::{enums::DnsRecordType, prelude::ObjectType, structs::Domain},
+    types::EnumImpl,
+};
+use serde::Serialize;
+use smtp::outbound::{dane::dnssec::TlsaLookup, lookup::DnsLookup};
+use std::{future::Future, net::IpAddr};
+use trc::AddContext;
+use types::id::Id;
+use utils::HexEncode;
+
+pub trait DnsCheckManagement: Sync + Send {
+    fn handle_dns_check(
+        &self,
+        domain_name: &str,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DnsCheckResponse {
+    domain: String,
+    sending_ips: Vec<IpAddr>,
+    records: Vec<DnsRecordCheck>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DnsRecordCheck {
+    #[serde(rename = "type")]
+    record_type: &'static str,
+    name: String,
+    status: DnsRecordStatus,
+    published: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    suggestion: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum DnsRecordStatus {
+    Ok,
+    Missing,
+    Mismatch,
+    Error,
+}
+
+impl DnsCheckManagement for Server {
+    async fn handle_dns_check(
+        &self,
+        domain_name: &str,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(ObjectType::Domain.get_permission())?;
+
+        let domain_name = domain_name.trim_end_matches('.').to_lowercase();
+        let Some(domain_cache) = self.domain(&domain_name).await? else {
+            return Err(trc::ResourceEvent::NotFound.into_err());
+        };
+        if access_token
+            .tenant_id()
+            .is_some_and(|tenant_id| domain_cache.id_tenant != Some(tenant_id))
+        {
+            return Err(trc::ResourceEvent::NotFound.into_err());
+        }
+        let domain_id = Id::from(domain_cache.id);
+        let Some(domain) = self
+            .registry()
+            .object::<Domain>(domain_id)
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Err(trc::ResourceEvent::NotFound.into_err());
+        };
+
+        // SPF is simulated against the addresses outgoing messages are sent from
+        let sending_ips = self
+            .ip_lookup(
+                &self.core.network.server_name,
+                IpLookupStrategy::Ipv4thenIpv6,
+                10,
+            )
+            .await
+            .unwrap_or_default();
+
+        let mut records = Vec::new();
+        for record_type in [
+            DnsRecordType::Mx,
+            DnsRecordType::Spf,
+            DnsRecordType::Dkim,
+            DnsRecordType::Dmarc,
+            DnsRecordType::MtaSts,
+            DnsRecordType::TlsRpt,
+            DnsRecordType::Tlsa,
+        ] {
+            for record in self
+                .build_dns_records(domain_id, &domain, &[record_type])
+                .await
+                .caused_by(trc::location!())?
+            {
+                let (status, published, details) = match &record.record {
+                    DnsRecord::TXT(_) if record_type == DnsRecordType::Spf => {
+                        check_spf(self, &record.name, &sending_ips).await
+                    }
+                    DnsRecord::TXT(expected) => check_txt(self, &record.name, expected).await,
+                    DnsRecord::MX(expected) => check_mx(self, &record.name, expected).await,
+                    DnsRecord::TLSA(expected) => check_tlsa(self, &record.name, expected).await,
+                    _ => continue,
+                };
+
+                records.push(DnsRecordCheck {
+                    record_type: record_type.as_str(),
+                    name: record.name.clone(),
+                    status,
+                    published,
+                    details,
+                    suggestion: (status != DnsRecordStatus::Ok).then(|| suggestion(&record)),
+                });
+            }
+        }
+
+        Ok(JsonResponse::new(DnsCheckResponse {
+            domain: domain_name,
+            sending_ips,
+            records,
+        })
+        .no_cache()
+        .into_http_response())
+    }
+}
+
+type CheckResult = (DnsRecordStatus, Vec<String>, Option<String>);
+
+async fn check_spf(server: &Server, name: &str, sending_ips: &[IpAddr]) -> CheckResult {
+    let domain = name.trim_end_matches('.');
+    let published = published_txt(server, name, "v=spf1").await;
+    if sending_ips.is_empty() {
+        return (
+            DnsRecordStatus::Error,
+            published,
+            Some("Could not resolve the server's sending addresses".to_string()),
+        );
+    }
+
+    let local_host = &server.core.network.server_name;
+    let sender = format!("postmaster@{domain}");
+    let mut failed = Vec::new();
+    for ip in sending_ips {
+        let output = server
+            .core
+            .smtp
+            .resolvers
+            .dns
+            .check_host(server.inner.cache.build_auth_parameters(SpfParameters::new(
+                *ip, domain, local_host, local_host, &sender,
+            )))
+            .await;
+        match output.result() {
+            SpfResult::Pass => {}
+            SpfResult::None => {
+                return (DnsRecordStatus::Missing, published, None);
+            }
+            SpfResult::TempError => {
+                return (
+                    DnsRecordStatus::Error,
+                    published,
+                    Some("Temporary DNS error while evaluating SPF".to_string()),
+                );
+            }
+            result => {
+                failed.push(format!("{ip}: {}", spf_result_name(result)));
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        (DnsRecordStatus::Ok, published, None)
+    } else {
+        (
+            DnsRecordStatus::Mismatch,
+            published,
+            Some(format!(
+                "SPF does not authorize all sending addresses ({})",
+                failed.join(", ")
+            )),
+        )
+    }
+}
+
+async fn check_txt(server: &Server, name: &str, expected: &str) -> CheckResult {
+    let prefix = expected
+        .split(|c: char| c == ';' || c.is_whitespace())
+        .next()
+        .unwrap_or_default();
+    let published = match server.core.smtp.resolvers.dns.txt_raw_lookup(name).await {
+        Ok(raw) => String::from_utf8_lossy(&raw).into_owned(),
+        Err(mail_auth::Error::DnsRecordNotFound(_)) => String::new(),
+        Err(err) => {
+            return (DnsRecordStatus::Error, Vec::new(), Some(err.to_string()));
+        }
+    };
+
+    // Whitespace and tag separators vary between DNS providers
+    let status = if normalize_txt(&published).contains(&normalize_txt(expected)) {
+        DnsRecordStatus::Ok
+    } else if published.contains(prefix) {
+        DnsRecordStatus::Mismatch
+    } else {
+        DnsRecordStatus::Missing
+    };
+
+    (
+        status,
+        txt_segments(&published, prefix),
+        (status == DnsRecordStatus::Mismatch)
+            .then(|| "Published record differs from the expected value".to_string()),
+    )
+}
+
+async fn check_mx(server: &Server, name: &str, expected: &MXRecord) -> CheckResult {
+    let domain = name.trim_end_matches('.');
+    let mxs = match server
+        .core
+        .smtp
+        .resolvers
+        .dns
+        .mx_lookup(domain, Some(&server.inner.cache.dns_mx))
+        .await
+    {
+        Ok(mxs) => mxs,
+        Err(mail_auth::Error::DnsRecordNotFound(_)) => {
+            return (DnsRecordStatus::Missing, Vec::new(), None);
+        }
+        Err(err) => {
+            return (DnsRecordStatus::Error, Vec::new(), Some(err.to_string()));
+        }
+    };
+
+    let exchange = expected.exchange.trim_end_matches('.');
+    let published = mxs
+        .iter()
+        .flat_map(|mx| {
+            mx.exchanges
+                .iter()
+                .map(move |host| format!("{} {}", mx.preference, host.trim_end_matches('.')))
+        })
+        .collect::<Vec<_>>();
+    let is_published = mxs.iter().any(|mx| {
+        mx.exchanges
+            .iter()
+            .any(|host| host.trim_end_matches('.').eq_ignore_ascii_case(exchange))
+    });
+
+    if is_published {
+        (DnsRecordStatus::Ok, published, None)
+    } else if published.is_empty() {
+        (DnsRecordStatus::Missing, published, None)
+    } else {
+        (
+            DnsRecordStatus::Mismatch,
+            published,
+            Some(format!("{exchange} is not listed as a mail exchanger")),
+        )
+    }
+}
+
+async fn check_tlsa(server: &Server, name: &str, expected: &TLSARecord) -> CheckResult {
+    match server.tlsa_lookup(name).await {
+        Ok(Some(tlsa)) => {
+            let published = tlsa
+                .entries
+                .iter()
+                .map(|entry| {
+                    format!(
+                        "{} {} {} {}",
+                        if entry.is_end_entity { 3 } else { 2 },
+                        if entry.is_spki { 1 } else { 0 },
+                        if entry.is_sha256 { 1 } else { 2 },
+                        entry.data.hex_encode()
+                    )
+                })
+                .collect::<Vec<_>>();
+            if tlsa
+                .entries
+                .iter()
+                .any(|entry| entry.is_spki && entry.is_sha256 && entry.data == expected.cert_data)
+            {
+                (DnsRecordStatus::Ok, published, None)
+            } else {
+                (
+                    DnsRecordStatus::Mismatch,
+                    published,
+                    Some("No published TLSA record matches the current certificate".to_string()),
+                )
+            }
+        }
+        Ok(None) => (
+            DnsRecordStatus::Missing,
+            Vec::new(),
+            Some("No DNSSEC-signed TLSA records found".to_string()),
+        ),
+        Err(mail_auth::Error::DnsRecordNotFound(_)) => (DnsRecordStatus::Missing, Vec::new(), None),
+        Err(err) => (DnsRecordStatus::Error, Vec::new(), Some(err.to_string())),
+    }
+}
+
+async fn published_txt(server: &Server, name: &str, prefix: &str) -> Vec<String> {
+    server
+        .core
+        .smtp
+        .resolvers
+        .dns
+        .txt_raw_lookup(name)
+        .await
+        .map(|raw| txt_segments(&String::from_utf8_lossy(&raw), prefix))
+        .unwrap_or_default()
+}
+
+fn txt_segments(raw: &str, prefix: &str) -> Vec<String> {
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+
+    // TXT strings are returned concatenated, split them at each version tag
+    raw.match_indices(prefix)
+        .map(|(start, _)| {
+            let value = &raw[start..];
+            let end = value[prefix.len()..]
+                .find(prefix)
+                .map_or(value.len(), |end| end + prefix.len());
+            value[..end].trim().to_string()
+        })
+        .collect()
+}
+
+fn normalize_txt(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .trim_end_matches(';')
+        .to_string()
+}
+
+fn suggestion(record: &NamedDnsRecord) -> String {
+    BindSerializer::serialize(std::slice::from_ref(record))
+        .trim()
+        .to_string()
+}
+
+fn spf_result_name(result: SpfResult) -> &'static str {
+    match result {
+        SpfResult::Pass => "pass",
+        SpfResult::Fail => "fail",
+        SpfResult::SoftFail => "softfail",
+        SpfResult::Neutral => "neutral",
+        SpfResult::TempError => "temperror",
+        SpfResult::PermError => "permerror",
+        SpfResult::None => "none",
+    }
+}
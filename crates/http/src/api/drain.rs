@@ -0,0 +1,94 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{
+    Server,
+    auth::AccessToken,
+    ipc::{BroadcastEvent, ClusterQuery},
+    manager::cluster::DrainStatus,
+};
+use http_proto::{HttpResponse, JsonResponse, ToHttpResponse};
+use hyper::Method;
+use registry::schema::enums::Permission;
+use std::future::Future;
+
+pub trait DrainManagement: Sync + Send {
+    fn handle_drain_request(
+        &self,
+        node_id: Option<&str>,
+        method: &Method,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+impl DrainManagement for Server {
+    async fn handle_drain_request(
+        &self,
+        node_id: Option<&str>,
+        method: &Method,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let draining = match *method {
+            Method::GET => {
+                access_token.enforce_permission(Permission::LiveMetrics)?;
+                None
+            }
+            Method::POST => {
+                access_token.enforce_permission(Permission::ActionPauseMtaQueue)?;
+                Some(true)
+            }
+            Method::DELETE => {
+                access_token.enforce_permission(Permission::ActionResumeMtaQueue)?;
+                Some(false)
+            }
+            _ => return Err(trc::ResourceEvent::NotFound.into_err()),
+        };
+
+        // Requests without a node id apply to the node serving them
+        let this_node_id = self.core.network.node_id as u16;
+        let node_id = match node_id {
+            Some(node_id) => node_id.parse::<u16>().map_err(|_| {
+                trc::ResourceEvent::BadParameters
+                    .into_err()
+                    .details("Invalid node id")
+            })?,
+            None => this_node_id,
+        };
+
+        let status = if node_id == this_node_id {
+            if let Some(draining) = draining {
+                self.set_draining(draining).await;
+            }
+
+            self.drain_status()
+        } else {
+            if let Some(draining) = draining {
+                self.cluster_broadcast(BroadcastEvent::NodeDrain { node_id, draining })
+                    .await;
+            }
+
+            // Broadcast events are delivered in order, the node applies the drain
+            // request before replying to the status query
+            self.cluster_query::<DrainStatus>(ClusterQuery::Drain)
+                .await?
+                .into_iter()
+                .find(|node| node.node_id == node_id)
+                .ok_or_else(|| {
+                    trc::ResourceEvent::NotFound
+                        .into_err()
+                        .details("Cluster node not found")
+                })?
+                .reply
+                .ok_or_else(|| {
+                    trc::ResourceEvent::Error
+                        .into_err()
+                        .details("Cluster node did not reply")
+                })?
+        };
+
+        Ok(JsonResponse::new(status).no_cache().into_http_response())
+    }
+}
@@ -0,0 +1,132 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::mailbox_stats::authorize_account;
+use common::{Server, auth::AccessToken};
+use email::{
+    cache::{MessageCacheFetch, email::MessageCacheAccess, mailbox::MailboxCacheAccess},
+    message::history::EmailChangeHistory,
+};
+use http_proto::{HttpResponse, JsonResponse, ToHttpResponse};
+use registry::{schema::enums::Permission, types::datetime::UTCDateTime};
+use serde::Serialize;
+use std::{future::Future, str::FromStr};
+use trc::AddContext;
+use types::{id::Id, keyword::Keyword};
+
+pub trait EmailHistoryManagement: Sync + Send {
+    fn handle_email_history(
+        &self,
+        account_id: &str,
+        email_id: &str,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EmailHistoryResponse {
+    id: Id,
+    changes: Vec<EmailChangeResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EmailChangeResponse {
+    changed_at: UTCDateTime,
+    changed_by: Id,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    changed_by_name: Option<String>,
+    protocol: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    keywords_added: Vec<Keyword>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    keywords_removed: Vec<Keyword>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    mailboxes_added: Vec<MailboxRef>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    mailboxes_removed: Vec<MailboxRef>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MailboxRef {
+    id: Id,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+}
+
+impl EmailHistoryManagement for Server {
+    async fn handle_email_history(
+        &self,
+        account_id: &str,
+        email_id: &str,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::JmapEmailGet)?;
+        let email_id = Id::from_str(email_id).map_err(|_| {
+            trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Invalid email id")
+        })?;
+
+        let cache = self
+            .get_cached_messages(account_id)
+            .await
+            .caused_by(trc::location!())?;
+        if !cache.has_email_id(&email_id.document_id()) {
+            return Err(trc::ResourceEvent::NotFound
+                .into_err()
+                .details("Email not found"));
+        }
+
+        let mut changes = Vec::new();
+        for change in self
+            .email_changes(account_id, email_id.document_id())
+            .await?
+        {
+            // Accounts that have since been deleted are reported by id only
+            let changed_by_name = self
+                .try_account(change.changed_by)
+                .await
+                .caused_by(trc::location!())?
+                .map(|account| account.name().to_string());
+            let mailbox_ref = |mailbox_id: u32| MailboxRef {
+                id: Id::from(mailbox_id),
+                path: cache
+                    .mailbox_by_id(&mailbox_id)
+                    .map(|mailbox| mailbox.path.clone()),
+            };
+
+            changes.push(EmailChangeResponse {
+                changed_at: UTCDateTime::from_timestamp(change.changed_at as i64),
+                changed_by: Id::from(change.changed_by),
+                changed_by_name,
+                protocol: change.protocol.as_str(),
+                keywords_added: change.keywords_added,
+                keywords_removed: change.keywords_removed,
+                mailboxes_added: change
+                    .mailboxes_added
+                    .into_iter()
+                    .map(mailbox_ref)
+                    .collect(),
+                mailboxes_removed: change
+                    .mailboxes_removed
+                    .into_iter()
+                    .map(mailbox_ref)
+                    .collect(),
+            });
+        }
+
+        Ok(JsonResponse::new(EmailHistoryResponse {
+            id: email_id,
+            changes,
+        })
+        .no_cache()
+        .into_http_response())
+    }
+}
@@ -0,0 +1,274 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::mailbox_stats::authorize_account;
+use common::{
+    Server,
+    auth::{AccessToken, BuildAccessToken},
+};
+use email::{
+    cache::{MessageCacheFetch, mailbox::MailboxCacheAccess},
+    mailbox::INBOX_ID,
+    message::ingest::{EmailIngest, IngestEmail, IngestSource},
+};
+use http_proto::{HttpRequest, HttpResponse, HttpSessionData, JsonResponse, ToHttpResponse};
+use hyper::{StatusCode, header::CONTENT_TYPE};
+use mail_parser::MessageParser;
+use registry::schema::enums::Permission;
+use serde::Serialize;
+use std::{
+    future::Future,
+    io::{Cursor, Read},
+    str::FromStr,
+};
+use trc::AddContext;
+use types::id::Id;
+use utils::url_params::UrlParams;
+
+pub trait EmailImportManagement: Sync + Send {
+    fn handle_email_import(
+        &self,
+        account_id: &str,
+        req: &HttpRequest,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EmailImportResponse {
+    mailbox_id: Id,
+    imported: Vec<ImportedEmail>,
+    not_imported: Vec<NotImportedEmail>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ImportedEmail {
+    name: String,
+    id: Id,
+    thread_id: Id,
+    size: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct NotImportedEmail {
+    name: String,
+    reason: String,
+}
+
+struct ImportContext<'x> {
+    access_token: &'x AccessToken,
+    mailbox_id: u32,
+    session_id: u64,
+}
+
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+impl EmailImportManagement for Server {
+    async fn handle_email_import(
+        &self,
+        account_id: &str,
+        req: &HttpRequest,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::JmapEmailImport)?;
+        let body = body.ok_or_else(|| trc::LimitEvent::SizeRequest.into_err())?;
+
+        // Messages are appended to the Inbox unless a mailbox is selected by id or path
+        let params = UrlParams::new(req.uri().query());
+        let cache = self
+            .get_cached_messages(account_id)
+            .await
+            .caused_by(trc::location!())?;
+        let mailbox_id = if let Some(mailbox_id) = params.get("mailboxId") {
+            Id::from_str(mailbox_id)
+                .ok()
+                .map(|id| id.document_id())
+                .filter(|id| cache.has_mailbox_id(id))
+        } else if let Some(path) = params.get("mailbox") {
+            cache
+                .mailbox_by_path(path)
+                .map(|mailbox| mailbox.document_id)
+        } else {
+            Some(INBOX_ID)
+        }
+        .ok_or_else(|| {
+            trc::ResourceEvent::NotFound
+                .into_err()
+                .details("Mailbox not found")
+        })?;
+
+        // Uploads are either a multipart form with one or more files, or a single
+        // message or zip archive sent as the request body
+        let boundary = req
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|h| h.to_str().ok())
+            .and_then(|val| val.parse::<mime::Mime>().ok())
+            .and_then(|content_type| {
+                content_type
+                    .get_param(mime::BOUNDARY)
+                    .map(|boundary| boundary.as_str().to_string())
+            });
+        let files = if let Some(boundary) = boundary {
+            form_data::FormData::new(&body[..], boundary.as_str())
+                .flatten()
+                .filter_map(|mut field| {
+                    let contents = field.bytes().ok()?;
+                    Some((field.filename.unwrap_or(field.name), contents.to_vec()))
+                })
+                .collect::<Vec<_>>()
+        } else {
+            vec![("message.eml".to_string(), body)]
+        };
+
+        let account_token = self
+            .access_token(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .build();
+        let ctx = ImportContext {
+            access_token: &account_token,
+            mailbox_id,
+            session_id: session.session_id,
+        };
+        let mut response = EmailImportResponse {
+            mailbox_id: Id::from(mailbox_id),
+            imported: Vec::new(),
+            not_imported: Vec::new(),
+        };
+
+        'outer: for (name, contents) in files {
+            if !contents.starts_with(ZIP_MAGIC) {
+                if !import_upload(self, &ctx, name, &contents, &mut response).await? {
+                    break;
+                }
+                continue;
+            }
+
+            let mut archive = match zip::ZipArchive::new(Cursor::new(&contents[..])) {
+                Ok(archive) => archive,
+                Err(err) => {
+                    response.not_imported.push(NotImportedEmail {
+                        name,
+                        reason: format!("Invalid zip archive: {err}"),
+                    });
+                    continue;
+                }
+            };
+
+            // Only .eml entries are imported, anything else in the archive is ignored
+            let max_size = self.core.email.mail_max_size as u64;
+            for idx in 0..archive.len() {
+                let (entry_name, message) = {
+                    let Ok(file) = archive.by_index(idx) else {
+                        continue;
+                    };
+                    let entry_name = format!("{name}/{}", file.name());
+                    if file.is_dir() || !file.name().to_ascii_lowercase().ends_with(".eml") {
+                        continue;
+                    } else if file.size() > max_size {
+                        response.not_imported.push(NotImportedEmail {
+                            name: entry_name,
+                            reason: "Message exceeds the maximum allowed size.".to_string(),
+                        });
+                        continue;
+                    }
+
+                    let mut message = Vec::with_capacity(file.size() as usize);
+                    if let Err(err) = file.take(max_size).read_to_end(&mut message) {
+                        response.not_imported.push(NotImportedEmail {
+                            name: entry_name,
+                            reason: format!("Failed to decompress message: {err}"),
+                        });
+                        continue;
+                    }
+                    (entry_name, message)
+                };
+
+                if !import_upload(self, &ctx, entry_name, &message, &mut response).await? {
+                    break 'outer;
+                }
+            }
+        }
+
+        Ok(JsonResponse::with_status(
+            if response.not_imported.is_empty() {
+                StatusCode::OK
+            } else {
+                StatusCode::MULTI_STATUS
+            },
+            response,
+        )
+        .no_cache()
+        .into_http_response())
+    }
+}
+
+/// Ingests a single uploaded message, returning `false` once the account
+/// is over quota and no further messages should be attempted.
+async fn import_upload(
+    server: &Server,
+    ctx: &ImportContext<'_>,
+    name: String,
+    raw_message: &[u8],
+    response: &mut EmailImportResponse,
+) -> trc::Result<bool> {
+    match server
+        .email_ingest(IngestEmail {
+            raw_message,
+            blob_hash: None,
+            message: MessageParser::new().parse(raw_message),
+            access_token: ctx.access_token,
+            mailbox_ids: vec![ctx.mailbox_id],
+            keywords: vec![],
+            received_at: None,
+            source: IngestSource::Jmap {
+                train_classifier: false,
+            },
+            session_id: ctx.session_id,
+        })
+        .await
+    {
+        Ok(email) => {
+            response.imported.push(ImportedEmail {
+                name,
+                id: Id::from_parts(email.thread_id, email.document_id),
+                thread_id: Id::from(email.thread_id),
+                size: email.size,
+            });
+            Ok(true)
+        }
+        Err(mut err) => match err.as_ref() {
+            trc::EventType::Limit(trc::LimitEvent::Quota) => {
+                response.not_imported.push(NotImportedEmail {
+                    name,
+                    reason: "You have exceeded your disk quota.".to_string(),
+                });
+                Ok(false)
+            }
+            trc::EventType::MessageIngest(trc::MessageIngestEvent::Error) => {
+                response.not_imported.push(NotImportedEmail {
+                    name,
+                    reason: err
+                        .take_value(trc::Key::Reason)
+                        .and_then(|v| v.into_string())
+                        .map(|reason| reason.to_string())
+                        .unwrap_or_else(|| "Invalid message.".to_string()),
+                });
+                Ok(true)
+            }
+            _ => Err(err),
+        },
+    }
+}
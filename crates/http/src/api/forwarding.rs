@@ -0,0 +1,205 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::mailbox_stats::authorize_account;
+use common::{
+    Server,
+    auth::AccessToken,
+    manager::audit::{AuditAction, AuditEntry},
+};
+use email::forwarding::{AccountForwarding, AccountForwardingStore};
+use http_proto::{HttpResponse, HttpSessionData, JsonResponse, ToHttpResponse};
+use mail_builder::{MessageBuilder, headers::HeaderType, mime::make_boundary};
+use registry::schema::enums::Permission;
+use serde::Deserialize;
+use serde_json::json;
+use smtp::reporting::send::MtaReportSend;
+use std::future::Future;
+use trc::AddContext;
+use types::id::Id;
+use utils::sanitize_email;
+
+pub trait ForwardingManagement: Sync + Send {
+    fn handle_forwarding_get(
+        &self,
+        account_id: &str,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_forwarding_set(
+        &self,
+        account_id: &str,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_forwarding_destroy(
+        &self,
+        account_id: &str,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ForwardingRequest {
+    destination: String,
+    #[serde(default)]
+    keep_copy: bool,
+}
+
+impl ForwardingManagement for Server {
+    async fn handle_forwarding_get(
+        &self,
+        account_id: &str,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::EmailForward)?;
+
+        let forwarding = self.account_forwarding(account_id).await?;
+
+        Ok(JsonResponse::new(forwarding)
+            .no_cache()
+            .into_http_response())
+    }
+
+    async fn handle_forwarding_set(
+        &self,
+        account_id: &str,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::EmailForward)?;
+        access_token.enforce_permission(Permission::EmailSend)?;
+
+        let request =
+            serde_json::from_slice::<ForwardingRequest>(body.as_deref().unwrap_or_default())
+                .map_err(|err| {
+                    trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+                })?;
+        let destination = sanitize_email(&request.destination).ok_or_else(|| {
+            trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Invalid destination address")
+        })?;
+        if self
+            .account_id_from_email(&destination, false)
+            .await
+            .caused_by(trc::location!())?
+            == Some(account_id)
+        {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Cannot forward messages to the same account"));
+        }
+
+        // Changing the destination requires a new confirmation, while
+        // toggling the keep-copy flag does not
+        let previous = self.account_forwarding(account_id).await?;
+        let verified = previous
+            .as_ref()
+            .is_some_and(|previous| previous.verified && previous.destination == destination);
+        let forwarding = AccountForwarding {
+            destination,
+            keep_copy: request.keep_copy,
+            verified,
+        };
+        self.set_account_forwarding(account_id, Some(forwarding.clone()))
+            .await?;
+
+        if !verified {
+            let account = self.account(account_id).await.caused_by(trc::location!())?;
+            let from = account.name();
+            let url = self
+                .forwarding_confirm_url(account_id, &forwarding.destination)
+                .await?;
+            let message = MessageBuilder::new()
+                .from(from)
+                .to(forwarding.destination.as_str())
+                .header("Auto-Submitted", HeaderType::Text("auto-generated".into()))
+                .message_id(format!(
+                    "<{}@{}>",
+                    make_boundary("."),
+                    self.core.network.server_name
+                ))
+                .subject("Confirm email forwarding")
+                .text_body(format!(
+                    concat!(
+                        "The account {} has requested to forward its incoming ",
+                        "messages to this address.\r\n\r\n",
+                        "To start receiving them, open the following link:\r\n\r\n",
+                        "{}\r\n\r\n",
+                        "If you did not expect this message, you can safely ignore it.\r\n"
+                    ),
+                    from, url
+                ))
+                .write_to_vec()
+                .unwrap_or_default();
+
+            self.send_autogenerated(
+                from,
+                [forwarding.destination.as_str()].into_iter(),
+                message,
+                Some(&self.core.sieve.sign),
+                session.session_id,
+            )
+            .await;
+        }
+
+        let entry = AuditEntry::new(
+            access_token,
+            session.remote_ip,
+            AuditAction::Update,
+            "Forwarding",
+            Some(account_id as u64),
+        )
+        .with_before(&json!({ "accountId": Id::from(account_id), "forwarding": previous }))
+        .with_after(&json!({ "accountId": Id::from(account_id), "forwarding": forwarding }));
+        if let Err(err) = self.write_audit_log(vec![entry]).await {
+            trc::error!(err.caused_by(trc::location!()));
+        }
+
+        Ok(JsonResponse::new(forwarding)
+            .no_cache()
+            .into_http_response())
+    }
+
+    async fn handle_forwarding_destroy(
+        &self,
+        account_id: &str,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::EmailForward)?;
+
+        let previous = self.account_forwarding(account_id).await?;
+        if previous.is_some() {
+            self.set_account_forwarding(account_id, None).await?;
+
+            let entry = AuditEntry::new(
+                access_token,
+                session.remote_ip,
+                AuditAction::Destroy,
+                "Forwarding",
+                Some(account_id as u64),
+            )
+            .with_before(&json!({ "accountId": Id::from(account_id), "forwarding": previous }));
+            if let Err(err) = self.write_audit_log(vec![entry]).await {
+                trc::error!(err.caused_by(trc::location!()));
+            }
+        }
+
+        Ok(JsonResponse::new(None::<AccountForwarding>)
+            .no_cache()
+            .into_http_response())
+    }
+}
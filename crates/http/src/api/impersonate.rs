@@ -0,0 +1,101 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::legal_hold::authorize_admin;
+use common::{
+    Server,
+    auth::{AccessToken, impersonation::IMPERSONATION_MAX_EXPIRY},
+    manager::audit::{AuditAction, AuditEntry},
+};
+use http_proto::{HttpRequest, HttpResponse, HttpSessionData, JsonResponse, ToHttpResponse};
+use registry::schema::enums::Permission;
+use serde::Serialize;
+use serde_json::json;
+use std::future::Future;
+use types::id::Id;
+use utils::url_params::UrlParams;
+
+const DEFAULT_EXPIRY: u64 = 900;
+
+pub trait ImpersonationApi: Sync + Send {
+    fn handle_impersonate(
+        &self,
+        account_id: &str,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ImpersonationToken {
+    account_id: Id,
+    access_token: String,
+    token_type: &'static str,
+    expires_in: u64,
+    read_only: bool,
+}
+
+impl ImpersonationApi for Server {
+    async fn handle_impersonate(
+        &self,
+        account_id: &str,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        let account_id =
+            authorize_admin(self, account_id, access_token, Permission::Impersonate).await?;
+
+        // Impersonated sessions cannot be used to impersonate other accounts
+        if access_token.impersonator_id().is_some() {
+            return Err(trc::SecurityEvent::Unauthorized
+                .into_err()
+                .account_id(access_token.account_id())
+                .details("Impersonation tokens cannot be chained"));
+        }
+
+        let params = UrlParams::new(req.uri().query());
+        let read_only = params.parse::<bool>("readOnly").unwrap_or_default();
+        let expires_in = params
+            .parse::<u64>("expiry")
+            .filter(|expiry| *expiry > 0)
+            .unwrap_or(DEFAULT_EXPIRY)
+            .min(IMPERSONATION_MAX_EXPIRY);
+        let token = self
+            .encode_impersonation_token(access_token, account_id, read_only, expires_in)
+            .await?;
+
+        let entry = AuditEntry::new(
+            access_token,
+            session.remote_ip,
+            AuditAction::Create,
+            "Impersonation",
+            Some(account_id as u64),
+        )
+        .with_after(&json!({
+            "accountId": Id::from(account_id),
+            "readOnly": read_only,
+            "expiresIn": expires_in,
+        }));
+        if let Err(err) = self.write_audit_log(vec![entry]).await {
+            trc::error!(err.caused_by(trc::location!()));
+        }
+
+        Ok(JsonResponse::new(json!({
+            "data": ImpersonationToken {
+                account_id: Id::from(account_id),
+                access_token: token,
+                token_type: "bearer",
+                expires_in,
+                read_only,
+            }
+        }))
+        .no_cache()
+        .into_http_response())
+    }
+}
@@ -0,0 +1,236 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{
+    Server,
+    auth::AccessToken,
+    manager::audit::{AuditAction, AuditEntry},
+};
+use email::message::{legal_hold::LegalHoldManagement, redact::EmailRedaction};
+use http_proto::{HttpResponse, HttpSessionData, JsonResponse, ToHttpResponse};
+use registry::schema::enums::Permission;
+use serde::Serialize;
+use serde_json::json;
+use std::{future::Future, str::FromStr};
+use trc::AddContext;
+use types::{blob_hash::BlobHash, id::Id};
+
+pub trait LegalHoldApi: Sync + Send {
+    fn handle_legal_hold_get(
+        &self,
+        account_id: &str,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_legal_hold_set(
+        &self,
+        account_id: &str,
+        mailbox_id: &str,
+        hold: bool,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_email_redact(
+        &self,
+        account_id: &str,
+        email_id: &str,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LegalHoldStatus {
+    account_id: Id,
+    account_hold: bool,
+    mailboxes: Vec<MailboxHold>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MailboxHold {
+    id: Id,
+    since: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RedactResponse {
+    id: Id,
+    blob_id: String,
+    size: u32,
+}
+
+impl LegalHoldApi for Server {
+    async fn handle_legal_hold_get(
+        &self,
+        account_id: &str,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let account_id =
+            authorize_admin(self, account_id, access_token, Permission::SysAccountGet).await?;
+        let account_hold = self
+            .account(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .is_legal_hold();
+        let mut mailboxes = self
+            .mailbox_legal_holds(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .into_iter()
+            .map(|(mailbox_id, since)| MailboxHold {
+                id: Id::from(mailbox_id),
+                since,
+            })
+            .collect::<Vec<_>>();
+        mailboxes.sort_unstable_by_key(|mailbox| mailbox.since);
+
+        Ok(JsonResponse::new(LegalHoldStatus {
+            account_id: Id::from(account_id),
+            account_hold,
+            mailboxes,
+        })
+        .no_cache()
+        .into_http_response())
+    }
+
+    async fn handle_legal_hold_set(
+        &self,
+        account_id: &str,
+        mailbox_id: &str,
+        hold: bool,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        let account_id =
+            authorize_admin(self, account_id, access_token, Permission::SysAccountUpdate).await?;
+        let mailbox_id = parse_id(mailbox_id)?.document_id();
+
+        if !self
+            .set_mailbox_legal_hold(account_id, mailbox_id, hold)
+            .await
+            .caused_by(trc::location!())?
+        {
+            return Err(trc::ResourceEvent::NotFound.into_err());
+        }
+
+        let entry = AuditEntry::new(
+            access_token,
+            session.remote_ip,
+            AuditAction::Update,
+            "MailboxLegalHold",
+            Some(mailbox_id as u64),
+        )
+        .with_after(&json!({ "accountId": Id::from(account_id), "legalHold": hold }));
+        if let Err(err) = self.write_audit_log(vec![entry]).await {
+            trc::error!(err.caused_by(trc::location!()));
+        }
+
+        Ok(JsonResponse::new(json!({
+            "data": {
+                "id": Id::from(mailbox_id),
+                "legalHold": hold,
+            }
+        }))
+        .no_cache()
+        .into_http_response())
+    }
+
+    async fn handle_email_redact(
+        &self,
+        account_id: &str,
+        email_id: &str,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        let account_id =
+            authorize_admin(self, account_id, access_token, Permission::SysAccountUpdate).await?;
+        let document_id = parse_id(email_id)?.document_id();
+
+        let Some(redacted) = self
+            .email_redact(account_id, document_id)
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Err(trc::ResourceEvent::NotFound.into_err());
+        };
+        let id = Id::from_parts(redacted.thread_id, document_id);
+
+        // Only digests and sizes are recorded, the redacted contents are gone
+        let entry = AuditEntry::new(
+            access_token,
+            session.remote_ip,
+            AuditAction::Update,
+            "EmailRedaction",
+            Some(id.id()),
+        )
+        .with_before(&blob_summary(
+            account_id,
+            &redacted.previous_blob_hash,
+            redacted.previous_size,
+        ))
+        .with_after(&blob_summary(
+            account_id,
+            &redacted.blob_hash,
+            redacted.size,
+        ));
+        if let Err(err) = self.write_audit_log(vec![entry]).await {
+            trc::error!(err.caused_by(trc::location!()));
+        }
+
+        Ok(JsonResponse::new(json!({
+            "data": RedactResponse {
+                id,
+                blob_id: redacted.blob_hash.to_hex(),
+                size: redacted.size,
+            }
+        }))
+        .no_cache()
+        .into_http_response())
+    }
+}
+
+pub(super) async fn authorize_admin(
+    server: &Server,
+    account_id: &str,
+    access_token: &AccessToken,
+    permission: Permission,
+) -> trc::Result<u32> {
+    access_token.enforce_permission(permission)?;
+    let account_id = parse_id(account_id)?.document_id();
+
+    if let Some(tenant_id) = access_token.tenant_id()
+        && server
+            .account(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .id_tenant
+            != Some(tenant_id)
+    {
+        return Err(trc::ResourceEvent::NotFound.into_err());
+    }
+
+    Ok(account_id)
+}
+
+fn parse_id(id: &str) -> trc::Result<Id> {
+    Id::from_str(id).map_err(|_| {
+        trc::ResourceEvent::BadParameters
+            .into_err()
+            .details("Invalid id")
+    })
+}
+
+fn blob_summary(account_id: u32, hash: &BlobHash, size: u32) -> serde_json::Value {
+    json!({
+        "accountId": Id::from(account_id),
+        "blobHash": hash.to_hex(),
+        "size": size,
+    })
+}
@@ -0,0 +1,379 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{Server, auth::AccessToken, telemetry::tracers::TraceEvents};
+use futures_util::{SinkExt, StreamExt};
+use http_proto::*;
+use hyper::StatusCode;
+use hyper_util::rt::TokioIo;
+use registry::schema::{prelude::ObjectType, structs::Trace};
+use std::{
+    future::Future,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use store::ahash::{AHashMap, AHashSet};
+use tokio_tungstenite::WebSocketStream;
+use trc::{
+    Event, EventDetails, EventType, Key, Value,
+    ipc::{bitset::Bitset, subscriber::SubscriberBuilder},
+};
+use tungstenite::{Message, handshake::derive_accept_key, protocol::Role};
+use types::id::Id;
+use utils::url_params::UrlParams;
+
+pub trait LogTailApi: Sync + Send {
+    fn handle_log_tail_request(
+        &self,
+        req: &mut HttpRequest,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Default)]
+pub struct LogTailFilter {
+    accounts: Vec<AccountFilter>,
+    event_types: Vec<EventTypeFilter>,
+    span_ids: AHashSet<u64>,
+    tenant_id: Option<u32>,
+    matched_spans: AHashMap<u64, Instant>,
+}
+
+// Spans whose events are included without checking the account and tenant again,
+// entries are dropped once their span ends or has been idle for too long
+const MAX_MATCHED_SPANS: usize = 10_000;
+const MATCHED_SPAN_TTL: Duration = Duration::from_secs(3600);
+
+#[derive(Debug)]
+enum AccountFilter {
+    Id(u32),
+    Name(String),
+}
+
+#[derive(Debug)]
+enum EventTypeFilter {
+    Exact(EventType),
+    Prefix(String),
+}
+
+impl LogTailApi for Server {
+    async fn handle_log_tail_request(
+        &self,
+        req: &mut HttpRequest,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(ObjectType::Log.query_permission())?;
+        access_token.enforce_permission(ObjectType::Log.get_permission())?;
+
+        let filter = LogTailFilter::parse(
+            UrlParams::new(req.uri().query())
+                .get("filter")
+                .unwrap_or_default(),
+        )?
+        .with_tenant(access_token.tenant_id());
+
+        let headers = req.headers();
+        if headers
+            .get(hyper::header::UPGRADE)
+            .and_then(|h| h.to_str().ok())
+            .is_none_or(|h| !h.eq_ignore_ascii_case("websocket"))
+        {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("WebSocket upgrade failed")
+                .ctx(trc::Key::Reason, "Missing or invalid Upgrade header."));
+        }
+        let derived_key = match (
+            headers
+                .get("Sec-WebSocket-Key")
+                .and_then(|h| h.to_str().ok()),
+            headers
+                .get("Sec-WebSocket-Version")
+                .and_then(|h| h.to_str().ok()),
+        ) {
+            (Some(key), Some("13")) => derive_accept_key(key.as_bytes()),
+            _ => {
+                return Err(trc::ResourceEvent::BadParameters
+                    .into_err()
+                    .details("WebSocket upgrade failed")
+                    .ctx(
+                        trc::Key::Reason,
+                        "Missing or Invalid Sec-WebSocket-Key headers.",
+                    ));
+            }
+        };
+
+        // Only events already enabled by a tracer are received
+        let (_, rx) = SubscriberBuilder::new(format!("log-tail-{}", session.session_id))
+            .with_interests(Box::new(Bitset::all()))
+            .with_lossy(true)
+            .register();
+        let on_upgrade = hyper::upgrade::on(req);
+        let heartbeat = self.core.jmap.web_socket_heartbeat;
+        let session_id = session.session_id;
+        let server = self.clone();
+
+        tokio::spawn(async move {
+            match on_upgrade.await {
+                Ok(upgraded) => {
+                    let mut stream = WebSocketStream::from_raw_socket(
+                        TokioIo::new(upgraded),
+                        Role::Server,
+                        None,
+                    )
+                    .await;
+                    let mut rx = rx;
+                    let mut filter = filter;
+
+                    loop {
+                        tokio::select! {
+                            events = rx.recv() => {
+                                let Some(events) = events else {
+                                    break;
+                                };
+                                let mut matched_events = Vec::with_capacity(events.len());
+                                for event in events {
+                                    if filter.matches(&server, &event).await {
+                                        matched_events.push(event);
+                                    }
+                                }
+                                let events = matched_events;
+                                if events.is_empty() {
+                                    continue;
+                                }
+
+                                let num_events = events.len();
+                                let message = serde_json::to_string(&Trace::build_trace_events(
+                                    events.iter().map(|e| e.as_ref()),
+                                    num_events,
+                                ))
+                                .unwrap_or_default();
+                                if stream.send(Message::Text(message.into())).await.is_err() {
+                                    break;
+                                }
+                            }
+                            message = tokio::time::timeout(heartbeat, stream.next()) => {
+                                match message {
+                                    Ok(Some(Ok(Message::Ping(bytes)))) => {
+                                        if stream.send(Message::Pong(bytes)).await.is_err() {
+                                            break;
+                                        }
+                                    }
+                                    Ok(Some(Ok(Message::Close(frame)))) => {
+                                        let _ = stream.close(frame).await;
+                                        break;
+                                    }
+                                    Ok(Some(Ok(_))) => (),
+                                    Ok(Some(Err(_)) | None) => break,
+                                    Err(_) => {
+                                        if stream.send(Message::Ping(Default::default())).await.is_err() {
+                                            break;
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                Err(err) => {
+                    trc::event!(
+                        Http(trc::HttpEvent::Error),
+                        Details = "Websocket upgrade failed",
+                        SpanId = session_id,
+                        Reason = err.to_string()
+                    );
+                }
+            }
+        });
+
+        Ok(HttpResponse::new(StatusCode::SWITCHING_PROTOCOLS).with_websocket_upgrade(derived_key))
+    }
+}
+
+impl LogTailFilter {
+    // Parses a filter expression such as "account:jane@example.org type:smtp.* span:1234",
+    // terms with different keys must all match while repeated keys match any value.
+    pub fn parse(expr: &str) -> trc::Result<Self> {
+        let mut filter = LogTailFilter::default();
+
+        for term in expr.split_whitespace() {
+            let Some((key, value)) = term.split_once(':').filter(|(_, v)| !v.is_empty()) else {
+                return Err(invalid_filter(term));
+            };
+
+            match key {
+                "account" => {
+                    filter
+                        .accounts
+                        .push(AccountFilter::Name(value.to_lowercase()));
+                }
+                "accountId" => {
+                    filter.accounts.push(AccountFilter::Id(
+                        Id::from_str(value)
+                            .map_err(|_| invalid_filter(term))?
+                            .document_id(),
+                    ));
+                }
+                "type" => {
+                    filter
+                        .event_types
+                        .push(if let Some(prefix) = value.strip_suffix('*') {
+                            EventTypeFilter::Prefix(prefix.to_string())
+                        } else if let Some(event_type) = EventType::parse(value) {
+                            EventTypeFilter::Exact(event_type)
+                        } else {
+                            return Err(invalid_filter(term));
+                        });
+                }
+                "span" => {
+                    filter
+                        .span_ids
+                        .insert(value.parse().map_err(|_| invalid_filter(term))?);
+                }
+                _ => return Err(invalid_filter(term)),
+            }
+        }
+
+        Ok(filter)
+    }
+
+    // Tenant administrators only receive events that belong to accounts of their tenant
+    pub fn with_tenant(mut self, tenant_id: Option<u32>) -> Self {
+        self.tenant_id = tenant_id;
+        self
+    }
+
+    pub async fn matches(&mut self, server: &Server, event: &Arc<Event<EventDetails>>) -> bool {
+        if !self.event_types.is_empty()
+            && !self.event_types.iter().any(|filter| match filter {
+                EventTypeFilter::Exact(event_type) => event.inner.typ == *event_type,
+                EventTypeFilter::Prefix(prefix) => event.inner.typ.as_str().starts_with(prefix),
+            })
+        {
+            return false;
+        }
+
+        let span_id = event.span_id();
+        if !self.span_ids.is_empty()
+            && !span_id.is_some_and(|span_id| self.span_ids.contains(&span_id))
+        {
+            return false;
+        }
+
+        if self.accounts.is_empty() && self.tenant_id.is_none() {
+            return true;
+        }
+
+        // Once an event matches, the remaining events of its span are included
+        let now = Instant::now();
+        let is_span_match = span_id.is_some_and(|span_id| {
+            self.matched_spans
+                .get_mut(&span_id)
+                .filter(|last_match| now.duration_since(**last_match) < MATCHED_SPAN_TTL)
+                .map(|last_match| *last_match = now)
+                .is_some()
+        });
+        if !is_span_match
+            && (!self.matches_account(event) || !self.matches_tenant(server, event).await)
+        {
+            return false;
+        }
+
+        if let Some(span_id) = span_id {
+            if event.inner.typ.is_span_end() {
+                self.matched_spans.remove(&span_id);
+            } else if !is_span_match {
+                if self.matched_spans.len() >= MAX_MATCHED_SPANS {
+                    self.matched_spans
+                        .retain(|_, last_match| now.duration_since(*last_match) < MATCHED_SPAN_TTL);
+                    if self.matched_spans.len() >= MAX_MATCHED_SPANS
+                        && let Some(oldest) = self
+                            .matched_spans
+                            .iter()
+                            .min_by_key(|(_, last_match)| **last_match)
+                            .map(|(span_id, _)| *span_id)
+                    {
+                        self.matched_spans.remove(&oldest);
+                    }
+                }
+                self.matched_spans.insert(span_id, now);
+            }
+        }
+
+        true
+    }
+
+    pub fn num_matched_spans(&self) -> usize {
+        self.matched_spans.len()
+    }
+
+    fn matches_account(&self, event: &Event<EventDetails>) -> bool {
+        self.accounts.is_empty()
+            || event_keys(event).any(|(key, value)| {
+                self.accounts
+                    .iter()
+                    .any(|filter| match (filter, key, value) {
+                        (AccountFilter::Id(id), Key::AccountId, Value::UInt(value)) => {
+                            *value == *id as u64
+                        }
+                        (AccountFilter::Name(name), Key::AccountName, Value::String(value)) => {
+                            value.eq_ignore_ascii_case(name)
+                        }
+                        _ => false,
+                    })
+            })
+    }
+
+    async fn matches_tenant(&self, server: &Server, event: &Event<EventDetails>) -> bool {
+        let Some(tenant_id) = self.tenant_id else {
+            return true;
+        };
+
+        for (key, value) in event_keys(event) {
+            let account_id = match (key, value) {
+                (Key::AccountId, Value::UInt(account_id)) => Some(*account_id as u32),
+                (Key::AccountName, Value::String(name)) => server
+                    .account_id_from_email(name, false)
+                    .await
+                    .ok()
+                    .flatten(),
+                _ => None,
+            };
+
+            if let Some(account_id) = account_id
+                && server
+                    .try_account(account_id)
+                    .await
+                    .ok()
+                    .flatten()
+                    .is_some_and(|account| account.tenant_id() == Some(tenant_id))
+            {
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+fn event_keys(event: &Event<EventDetails>) -> impl Iterator<Item = &(Key, Value)> {
+    event.keys.iter().chain(
+        event
+            .inner
+            .span
+            .as_ref()
+            .map_or([].iter(), |s| s.keys.iter()),
+    )
+}
+
+fn invalid_filter(term: &str) -> trc::Error {
+    trc::ResourceEvent::BadParameters
+        .into_err()
+        .details(format!("Invalid filter term {term:?}"))
+}
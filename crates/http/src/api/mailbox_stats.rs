@@ -0,0 +1,177 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{Server, auth::AccessToken};
+use email::cache::MessageCacheFetch;
+use http_proto::{HttpRequest, HttpResponse, JsonResponse, ToHttpResponse};
+use registry::schema::prelude::ObjectType;
+use serde::Serialize;
+use std::{future::Future, str::FromStr};
+use store::ahash::AHashMap;
+use trc::AddContext;
+use types::id::Id;
+use utils::url_params::UrlParams;
+
+pub trait MailboxStatsManagement: Sync + Send {
+    fn handle_mailbox_stats(
+        &self,
+        account_id: &str,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountMailboxStats {
+    account_id: Id,
+    messages: u64,
+    size: u64,
+    mailboxes: Vec<MailboxStats>,
+    largest_messages: Vec<MessageStats>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MailboxStats {
+    id: Id,
+    name: String,
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<&'static str>,
+    messages: u64,
+    size: u64,
+    unique_size: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MessageStats {
+    id: Id,
+    size: u32,
+    mailbox_ids: Vec<Id>,
+}
+
+impl MailboxStatsManagement for Server {
+    async fn handle_mailbox_stats(
+        &self,
+        account_id: &str,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        let limit = UrlParams::new(req.uri().query())
+            .parse::<usize>("limit")
+            .unwrap_or(10);
+
+        // Statistics are derived from the message cache, which is kept up
+        // to date from the change log rather than by scanning the store
+        let cache = self
+            .get_cached_messages(account_id)
+            .await
+            .caused_by(trc::location!())?;
+        let mut mailboxes = cache
+            .mailboxes
+            .items
+            .iter()
+            .map(|mailbox| {
+                (
+                    mailbox.document_id,
+                    MailboxStats {
+                        id: Id::from(mailbox.document_id),
+                        name: mailbox.name.clone(),
+                        path: mailbox.path.clone(),
+                        role: mailbox.role.as_str(),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect::<AHashMap<_, _>>();
+        let mut size = 0;
+        let mut largest_messages = Vec::with_capacity(limit + 1);
+
+        for message in cache.emails.items.iter() {
+            let message_size = message.size as u64;
+            size += message_size;
+
+            for uid_mailbox in message.mailboxes.iter() {
+                if let Some(stats) = mailboxes.get_mut(&uid_mailbox.mailbox_id) {
+                    stats.messages += 1;
+                    stats.size += message_size;
+                    if message.mailboxes.len() == 1 {
+                        stats.unique_size += message_size;
+                    }
+                }
+            }
+
+            if limit > 0
+                && (largest_messages.len() < limit
+                    || largest_messages
+                        .last()
+                        .is_some_and(|(last_size, _)| *last_size < message.size))
+            {
+                let pos = largest_messages.partition_point(|(size, _)| *size >= message.size);
+                largest_messages.insert(pos, (message.size, message));
+                largest_messages.truncate(limit);
+            }
+        }
+
+        let mut mailboxes = mailboxes.into_values().collect::<Vec<_>>();
+        mailboxes.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+
+        Ok(JsonResponse::new(AccountMailboxStats {
+            account_id: Id::from(account_id),
+            messages: cache.emails.items.len() as u64,
+            size,
+            mailboxes,
+            largest_messages: largest_messages
+                .into_iter()
+                .map(|(size, message)| MessageStats {
+                    id: Id::from_parts(message.thread_id, message.document_id),
+                    size,
+                    mailbox_ids: message
+                        .mailboxes
+                        .iter()
+                        .map(|m| Id::from(m.mailbox_id))
+                        .collect(),
+                })
+                .collect(),
+        })
+        .no_cache()
+        .into_http_response())
+    }
+}
+
+// Users can view their own statistics, administrators need account access
+pub(crate) async fn authorize_account(
+    server: &Server,
+    account_id: &str,
+    access_token: &AccessToken,
+) -> trc::Result<u32> {
+    let account_id = Id::from_str(account_id)
+        .map_err(|_| {
+            trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Invalid account id")
+        })?
+        .document_id();
+
+    if !access_token.is_member(account_id) {
+        access_token.enforce_permission(ObjectType::Account.get_permission())?;
+        if let Some(tenant_id) = access_token.tenant_id()
+            && server
+                .account(account_id)
+                .await
+                .caused_by(trc::location!())?
+                .id_tenant
+                != Some(tenant_id)
+        {
+            return Err(trc::ResourceEvent::NotFound.into_err());
+        }
+    }
+
+    Ok(account_id)
+}
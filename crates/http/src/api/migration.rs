@@ -0,0 +1,346 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::{mailbox_stats::authorize_account, principal::domain_id};
+use common::{
+    Server,
+    auth::AccessToken,
+    manager::migration::{MigrationCounters, MigrationReport, MigrationRequest},
+};
+use http_proto::{HttpResponse, HttpSessionData, JsonResponse, ToHttpResponse};
+use hyper::StatusCode;
+use jmap::registry::set::RegistrySet;
+use jmap_proto::{error::set::SetError, method::set::SetRequest, object::registry::Registry};
+use registry::schema::{
+    enums::Permission,
+    prelude::{ObjectType, Property},
+    structs::{Task, TaskStatus},
+};
+use serde::Serialize;
+use serde_json::{Map, json};
+use std::{collections::HashMap, future::Future};
+use store::{
+    Deserialize, IterateParams, ValueKey,
+    write::{BatchBuilder, TaskQueueClass, ValueClass},
+};
+use trc::AddContext;
+use types::{collection::Collection, field::PrincipalField, id::Id};
+
+pub trait MigrationManagement: Sync + Send {
+    fn handle_migration_start(
+        &self,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_migration_list(
+        &self,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_migration_status(
+        &self,
+        account_id: &str,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_migration_reset(
+        &self,
+        account_id: &str,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MigrationResponse {
+    #[serde(flatten)]
+    report: MigrationReport,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    not_created: HashMap<String, SetError<Property>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MigrationTask {
+    account_id: Id,
+    username: String,
+    status: TaskStatus,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MigrationStatus {
+    account_id: Id,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    started_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    updated_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    completed_at: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    task: Option<TaskStatus>,
+    messages: u64,
+    scripts: u64,
+    events: u64,
+    contacts: u64,
+    failed: u64,
+    mailboxes: Vec<MailboxStatus>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MailboxStatus {
+    name: String,
+    messages: u64,
+    last_uid: u32,
+}
+
+impl MigrationManagement for Server {
+    async fn handle_migration_start(
+        &self,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(Permission::TaskAccountMigration)?;
+
+        let mut request =
+            serde_json::from_slice::<MigrationRequest>(body.as_deref().unwrap_or_default())
+                .map_err(|err| {
+                    trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+                })?;
+        if request.source.is_empty() {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("No source server URLs provided"));
+        }
+        let tenant_id = access_token.tenant_id();
+
+        // Accounts missing locally are created with the password used on the source server
+        let mut not_created = HashMap::new();
+        if request.create_accounts {
+            let mut create = Map::new();
+            for account in &request.accounts {
+                if self
+                    .migration_account_id(&account.name, tenant_id)
+                    .await?
+                    .is_some()
+                {
+                    continue;
+                }
+
+                let Some((name, domain)) = account.name.rsplit_once('@') else {
+                    not_created.insert(
+                        account.name.clone(),
+                        SetError::invalid_properties()
+                            .with_property(Property::Name)
+                            .with_description("Account names must be email addresses."),
+                    );
+                    continue;
+                };
+                match domain_id(self, access_token, domain).await? {
+                    Ok(domain_id) => {
+                        let mut object = json!({
+                            "@type": "User",
+                            "name": name.to_lowercase(),
+                            "domainId": domain_id,
+                            "credentials": {
+                                "0": { "@type": "Password", "secret": account.password }
+                            },
+                        });
+                        if let Some(description) = &account.description {
+                            object["description"] = json!(description);
+                        }
+                        create.insert(account.name.clone(), object);
+                    }
+                    Err(err) => {
+                        not_created.insert(account.name.clone(), err);
+                    }
+                }
+            }
+
+            if !create.is_empty() {
+                let set_request = json!({ "create": create }).to_string();
+                let mut set_request = serde_json::from_str::<SetRequest<'_, Registry>>(
+                    &set_request,
+                )
+                .map_err(|err| {
+                    trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+                })?;
+                set_request.account_id = Id::from(access_token.account_id());
+                let result = self
+                    .registry_set(
+                        ObjectType::Account,
+                        set_request,
+                        access_token,
+                        session,
+                        None,
+                    )
+                    .await?;
+                not_created.extend(result.not_created);
+            }
+
+            request
+                .accounts
+                .retain(|account| !not_created.contains_key(&account.name));
+        }
+
+        let mut report = self.schedule_migration(&request, tenant_id).await?;
+        report.failed += not_created.len() as u64;
+
+        Ok(JsonResponse::with_status(
+            if report.failed == 0 {
+                StatusCode::OK
+            } else {
+                StatusCode::MULTI_STATUS
+            },
+            MigrationResponse {
+                report,
+                not_created,
+            },
+        )
+        .no_cache()
+        .into_http_response())
+    }
+
+    async fn handle_migration_list(&self, access_token: &AccessToken) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(Permission::SysTaskQuery)?;
+
+        let tenant_id = access_token.tenant_id();
+        let mut tasks = Vec::new();
+        self.store()
+            .iterate(
+                IterateParams::new(
+                    ValueKey::from(ValueClass::TaskQueue(TaskQueueClass::Task { id: 0 })),
+                    ValueKey::from(ValueClass::TaskQueue(TaskQueueClass::Task { id: u64::MAX })),
+                ),
+                |_, value| {
+                    if let Ok(Task::AccountMigration(task)) = Task::deserialize(value) {
+                        tasks.push(MigrationTask {
+                            account_id: task.account_id,
+                            username: task.username,
+                            status: task.status,
+                        });
+                    }
+
+                    Ok(true)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        // Tenant administrators only see their own accounts
+        if let Some(tenant_id) = tenant_id {
+            let mut visible = Vec::with_capacity(tasks.len());
+            for task in tasks {
+                if self
+                    .try_account(task.account_id.document_id())
+                    .await
+                    .caused_by(trc::location!())?
+                    .is_some_and(|account| account.id_tenant == Some(tenant_id))
+                {
+                    visible.push(task);
+                }
+            }
+            tasks = visible;
+        }
+
+        Ok(JsonResponse::new(tasks).no_cache().into_http_response())
+    }
+
+    async fn handle_migration_status(
+        &self,
+        account_id: &str,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::SysTaskQuery)?;
+
+        let mut status = MigrationStatus {
+            account_id: Id::from(account_id),
+            ..Default::default()
+        };
+        if let Some(state) = self.migration_state(account_id).await? {
+            let MigrationCounters {
+                messages,
+                scripts,
+                events,
+                contacts,
+                failed,
+            } = state.counters;
+            status.started_at = Some(state.started_at);
+            status.updated_at = Some(state.updated_at);
+            status.completed_at = state.completed_at;
+            status.last_error = state.last_error;
+            status.messages = messages;
+            status.scripts = scripts;
+            status.events = events;
+            status.contacts = contacts;
+            status.failed = failed;
+            status.mailboxes = state
+                .mailboxes
+                .into_iter()
+                .map(|mailbox| MailboxStatus {
+                    name: mailbox.name,
+                    messages: mailbox.messages,
+                    last_uid: mailbox.last_uid,
+                })
+                .collect();
+        }
+
+        // Include the queued task, if any, to report retries and failures
+        self.store()
+            .iterate(
+                IterateParams::new(
+                    ValueKey::from(ValueClass::TaskQueue(TaskQueueClass::Task { id: 0 })),
+                    ValueKey::from(ValueClass::TaskQueue(TaskQueueClass::Task { id: u64::MAX })),
+                ),
+                |_, value| {
+                    if let Ok(Task::AccountMigration(task)) = Task::deserialize(value)
+                        && task.account_id.document_id() == account_id
+                    {
+                        status.task = Some(task.status);
+                        Ok(false)
+                    } else {
+                        Ok(true)
+                    }
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(JsonResponse::new(status).no_cache().into_http_response())
+    }
+
+    async fn handle_migration_reset(
+        &self,
+        account_id: &str,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::TaskAccountMigration)?;
+
+        // Without a synchronization state the next run imports everything again
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Principal)
+            .with_document(0)
+            .clear(PrincipalField::MigrationState);
+        self.store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())?;
+
+        Ok(JsonResponse::new(json!({ "reset": true }))
+            .no_cache()
+            .into_http_response())
+    }
+}
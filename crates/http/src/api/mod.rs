@@ -9,11 +9,73 @@
 // SPDX-License-Identifier: LicenseRef-SEL
 #[cfg(feature = "enterprise")]
 pub mod telemetry;
+#[cfg(feature = "enterprise")]
+pub mod trace;
 // SPDX-SnippetEnd
+pub mod audit;
+pub mod autocrypt;
+pub mod bandwidth;
+pub mod blobs;
+pub mod booking_policy;
+pub mod cluster;
+pub mod collection_transfer;
 pub mod diagnose;
+pub mod dns_check;
+pub mod drain;
+pub mod email_history;
+pub mod email_import;
+pub mod forwarding;
+pub mod impersonate;
+pub mod legal_hold;
+pub mod logs;
+pub mod mailbox_stats;
+pub mod migration;
+pub mod mta_sts;
+pub mod notification_rules;
+pub mod principal;
+pub mod recipient_check;
+pub mod reindex;
+pub mod replay;
+pub mod retention;
+pub mod sessions;
+pub mod settings;
+pub mod settings_export;
+pub mod sieve;
+pub mod spam_corpus;
 
 use crate::{
-    api::diagnose::{DeliveryStage, spawn_delivery_diagnose},
+    api::{
+        audit::AuditLogManagement,
+        autocrypt::AutocryptManagement,
+        bandwidth::BandwidthManagement,
+        blobs::BlobManagement,
+        booking_policy::BookingPolicyManagement,
+        cluster::ClusterManagement,
+        collection_transfer::CollectionTransferManagement,
+        diagnose::{DeliveryStage, spawn_delivery_diagnose},
+        dns_check::DnsCheckManagement,
+        drain::DrainManagement,
+        email_history::EmailHistoryManagement,
+        email_import::EmailImportManagement,
+        forwarding::ForwardingManagement,
+        impersonate::ImpersonationApi,
+        legal_hold::LegalHoldApi,
+        logs::LogTailApi,
+        mailbox_stats::MailboxStatsManagement,
+        migration::MigrationManagement,
+        mta_sts::MtaStsManagement,
+        notification_rules::NotificationRulesManagement,
+        principal::PrincipalManagement,
+        recipient_check::RecipientCheckManagement,
+        reindex::ReindexManagement,
+        replay::ReplayManagement,
+        retention::RetentionManagement,
+        sessions::ActiveSessionManagement,
+        settings::SettingsManagement,
+        settings_export::SettingsExportManagement,
+        sieve::SieveTestManagement,
+        spam_corpus::SpamCorpusManagement,
+    },
     auth::{
         authenticate::Authenticator, oauth::auth::OAuthApiHandler, permissions::AccountApiHandler,
     },
@@ -34,7 +96,7 @@ use hyper::{
 };
 use jmap::api::{ToJmapHttpResponse, ToRequestError};
 use jmap_proto::error::request::RequestError;
-use registry::schema::enums::Permission;
+use registry::schema::enums::{Permission, ShareLinkCollection};
 use std::time::Duration;
 use utils::url_params::UrlParams;
 
@@ -61,7 +123,19 @@ impl ManagementApi for Server {
     ) -> trc::Result<HttpResponse> {
         let is_post = req.method() == Method::POST;
         let body = if is_post {
-            fetch_body(req, 1024 * 1024, session.session_id).await
+            let max_size = if req.uri().path().starts_with("/api/principal/")
+                || req.uri().path().starts_with("/api/spam-filter/corpus")
+                || req.uri().path() == "/api/settings/import"
+                || (req.uri().path().starts_with("/api/account/")
+                    && (req.uri().path().ends_with("/import")
+                        || req.uri().path().contains("/calendar/")
+                        || req.uri().path().contains("/addressbook/")))
+            {
+                self.core.jmap.upload_max_size
+            } else {
+                1024 * 1024
+            };
+            fetch_body(req, max_size, session.session_id).await
         } else {
             None
         };
@@ -90,7 +164,413 @@ impl ManagementApi for Server {
             "account" => {
                 // Authenticate request
                 let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
-                self.handle_account_request(&access_token).await
+
+                match (
+                    path.get(1).copied(),
+                    path.get(2).copied(),
+                    path.get(3).copied(),
+                    req.method(),
+                ) {
+                    (Some(account_id), Some("mailbox-stats"), None, &Method::GET) => {
+                        self.handle_mailbox_stats(account_id, req, &access_token)
+                            .await
+                    }
+                    (Some(account_id), Some("bandwidth"), None, &Method::GET) => {
+                        self.handle_account_bandwidth(account_id, req, &access_token)
+                            .await
+                    }
+                    (Some(account_id), Some("legal-hold"), None, &Method::GET) => {
+                        self.handle_legal_hold_get(account_id, &access_token).await
+                    }
+                    (Some(account_id), Some("legal-hold"), Some(mailbox_id), &Method::PUT) => {
+                        self.handle_legal_hold_set(
+                            account_id,
+                            mailbox_id,
+                            true,
+                            &access_token,
+                            session,
+                        )
+                        .await
+                    }
+                    (Some(account_id), Some("legal-hold"), Some(mailbox_id), &Method::DELETE) => {
+                        self.handle_legal_hold_set(
+                            account_id,
+                            mailbox_id,
+                            false,
+                            &access_token,
+                            session,
+                        )
+                        .await
+                    }
+                    (Some(account_id), Some("impersonate"), None, &Method::POST) => {
+                        self.handle_impersonate(account_id, req, &access_token, session)
+                            .await
+                    }
+                    (Some(account_id), Some("reindex"), None, &Method::POST) => {
+                        self.handle_reindex(Some(account_id), &access_token).await
+                    }
+                    (Some(account_id), Some("reindex"), None, &Method::GET) => {
+                        self.handle_reindex_status(Some(account_id), &access_token)
+                            .await
+                    }
+                    (Some(account_id), Some("migration"), None, &Method::GET) => {
+                        self.handle_migration_status(account_id, &access_token)
+                            .await
+                    }
+                    (Some(account_id), Some("migration"), None, &Method::DELETE) => {
+                        self.handle_migration_reset(account_id, &access_token).await
+                    }
+                    (Some(account_id), Some("email-history"), Some(email_id), &Method::GET) => {
+                        self.handle_email_history(account_id, email_id, &access_token)
+                            .await
+                    }
+                    (Some(account_id), Some("notification-rules"), None, &Method::GET) => {
+                        self.handle_notification_rules_get(account_id, &access_token)
+                            .await
+                    }
+                    (Some(account_id), Some("notification-rules"), None, &Method::POST) => {
+                        self.handle_notification_rules_set(account_id, body, &access_token, session)
+                            .await
+                    }
+                    (Some(account_id), Some("notification-rules"), None, &Method::DELETE) => {
+                        self.handle_notification_rules_set(account_id, None, &access_token, session)
+                            .await
+                    }
+                    (Some(account_id), Some("forwarding"), None, &Method::GET) => {
+                        self.handle_forwarding_get(account_id, &access_token).await
+                    }
+                    (Some(account_id), Some("forwarding"), None, &Method::POST) => {
+                        self.handle_forwarding_set(account_id, body, &access_token, session)
+                            .await
+                    }
+                    (Some(account_id), Some("forwarding"), None, &Method::DELETE) => {
+                        self.handle_forwarding_destroy(account_id, &access_token, session)
+                            .await
+                    }
+                    (Some(account_id), Some("recipient-check"), None, &Method::POST) => {
+                        self.handle_recipient_check(account_id, body, &access_token, session)
+                            .await
+                    }
+                    (Some(account_id), Some("autocrypt"), address, &Method::GET) => {
+                        self.handle_autocrypt_get(
+                            account_id,
+                            address.map(decode_path_element).as_deref(),
+                            &access_token,
+                        )
+                        .await
+                    }
+                    (Some(account_id), Some("autocrypt"), address, &Method::DELETE) => {
+                        self.handle_autocrypt_destroy(
+                            account_id,
+                            address.map(decode_path_element).as_deref(),
+                            &access_token,
+                            session,
+                        )
+                        .await
+                    }
+                    (Some(account_id), Some("import"), None, &Method::POST) => {
+                        self.handle_email_import(account_id, req, body, &access_token, session)
+                            .await
+                    }
+                    (Some(account_id), Some("booking-policy"), None, &Method::GET) => {
+                        self.handle_booking_policy_list(account_id, &access_token)
+                            .await
+                    }
+                    (Some(account_id), Some("booking-policy"), Some(calendar_id), &Method::GET) => {
+                        self.handle_booking_policy_get(account_id, calendar_id, &access_token)
+                            .await
+                    }
+                    (
+                        Some(account_id),
+                        Some("booking-policy"),
+                        Some(calendar_id),
+                        &Method::POST,
+                    ) => {
+                        self.handle_booking_policy_set(
+                            account_id,
+                            calendar_id,
+                            body,
+                            &access_token,
+                            session,
+                        )
+                        .await
+                    }
+                    (
+                        Some(account_id),
+                        Some("booking-policy"),
+                        Some(calendar_id),
+                        &Method::DELETE,
+                    ) => {
+                        self.handle_booking_policy_set(
+                            account_id,
+                            calendar_id,
+                            None,
+                            &access_token,
+                            session,
+                        )
+                        .await
+                    }
+                    (
+                        Some(account_id),
+                        Some(collection @ ("calendar" | "addressbook")),
+                        Some(collection_id),
+                        method @ (&Method::GET | &Method::POST),
+                    ) => {
+                        let collection = if collection == "calendar" {
+                            ShareLinkCollection::Calendar
+                        } else {
+                            ShareLinkCollection::AddressBook
+                        };
+                        if *method == Method::GET {
+                            self.handle_collection_export(
+                                account_id,
+                                collection,
+                                collection_id,
+                                &access_token,
+                            )
+                            .await
+                        } else {
+                            self.handle_collection_import(
+                                account_id,
+                                collection,
+                                collection_id,
+                                req,
+                                body,
+                                &access_token,
+                                session,
+                            )
+                            .await
+                        }
+                    }
+                    (Some(account_id), Some("redact"), Some(email_id), &Method::POST) => {
+                        self.handle_email_redact(account_id, email_id, &access_token, session)
+                            .await
+                    }
+                    _ => self.handle_account_request(&access_token).await,
+                }
+            }
+            "principal" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (path.get(1).copied(), req.method()) {
+                    (Some("import"), &Method::POST) => {
+                        self.handle_principal_import(req, body, &access_token, session)
+                            .await
+                    }
+                    (Some("export"), &Method::GET) => {
+                        self.handle_principal_export(req, &access_token).await
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "tenant" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (
+                    path.get(1).copied(),
+                    path.get(2).copied(),
+                    path.get(3).copied(),
+                    req.method(),
+                ) {
+                    (Some(tenant_id), Some("retention"), Some("preview"), &Method::POST) => {
+                        self.handle_retention_preview(tenant_id, req, body, &access_token)
+                            .await
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "sieve" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (path.get(1).copied(), req.method()) {
+                    (Some("test"), &Method::POST) => {
+                        self.handle_sieve_test(body, &access_token).await
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "spam-filter" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (path.get(1).copied(), req.method()) {
+                    (Some("corpus"), &Method::POST) => {
+                        self.handle_spam_corpus_test(req, body, &access_token).await
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "troubleshoot" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (path.get(1).copied(), path.get(2).copied(), req.method()) {
+                    (Some("replay"), None, &Method::POST) => {
+                        self.handle_message_replay(body, &access_token).await
+                    }
+                    (Some("dns"), Some(domain), &Method::GET) => {
+                        self.handle_dns_check(&decode_path_element(domain), &access_token)
+                            .await
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "settings" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (path.get(1).copied(), req.method()) {
+                    (Some("validate"), &Method::POST) => {
+                        self.handle_settings_validate(body, &access_token, session)
+                            .await
+                    }
+                    (Some("export"), &Method::GET | &Method::POST) => {
+                        self.handle_settings_export(body, &access_token).await
+                    }
+                    (Some("import"), &Method::POST) => {
+                        self.handle_settings_import(req, body, &access_token, session)
+                            .await
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "cluster" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (path.get(1).copied(), req.method()) {
+                    (Some("drain"), method) => {
+                        self.handle_drain_request(path.get(2).copied(), method, &access_token)
+                            .await
+                    }
+                    (Some(endpoint), &Method::GET) => {
+                        self.handle_cluster_request(endpoint, &access_token).await
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "reindex" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (path.get(1).copied(), req.method()) {
+                    (None, &Method::POST) => self.handle_reindex(None, &access_token).await,
+                    (None, &Method::GET) => self.handle_reindex_status(None, &access_token).await,
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "migration" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (path.get(1).copied(), req.method()) {
+                    (None, &Method::POST) => {
+                        self.handle_migration_start(body, &access_token, session)
+                            .await
+                    }
+                    (None, &Method::GET) => self.handle_migration_list(&access_token).await,
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "blobs" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (path.get(1).copied(), req.method()) {
+                    (Some("stats"), &Method::GET) => {
+                        self.handle_blob_stats(req, &access_token).await
+                    }
+                    (Some("purge"), &Method::POST) => {
+                        self.handle_blob_purge(req, &access_token).await
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "reload" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (path.get(1).copied(), req.method()) {
+                    (Some(subsystem), &Method::POST) => {
+                        self.handle_settings_reload(subsystem, &access_token).await
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "audit" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (path.get(1).copied(), req.method()) {
+                    (None, &Method::GET) => self.handle_audit_log_query(req, &access_token).await,
+                    (Some("verify"), &Method::GET) => {
+                        self.handle_audit_log_verify(&access_token).await
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "logs" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (path.get(1).copied(), req.method()) {
+                    (Some("tail"), &Method::GET) => {
+                        self.handle_log_tail_request(req, &access_token, session)
+                            .await
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "mta-sts" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (path.get(1).copied(), req.method()) {
+                    (Some("fetches"), &Method::GET) => {
+                        self.handle_mta_sts_fetches(req, &access_token).await
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "sessions" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+
+                match (path.get(1).copied(), req.method()) {
+                    (None, &Method::GET) => self.handle_list_sessions(req, &access_token).await,
+                    (session_id, &Method::DELETE) => {
+                        self.handle_terminate_sessions(session_id, req, &access_token)
+                            .await
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
+            }
+            "trace" => {
+                // Authenticate request
+                let (_in_flight, access_token) = self.authenticate_headers(req, session).await?;
+                access_token.enforce_permission(Permission::SysTraceQuery)?;
+                access_token.enforce_permission(Permission::SysTraceGet)?;
+
+                match (path.get(1).copied(), req.method()) {
+                    // SPDX-SnippetBegin
+                    // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+                    // SPDX-License-Identifier: LicenseRef-SEL
+                    #[cfg(feature = "enterprise")]
+                    (Some("message"), &Method::GET) if self.core.is_enterprise_edition() => {
+                        use crate::api::trace::MessageTraceApi;
+
+                        self.handle_message_trace_request(req, &access_token).await
+                    }
+                    // SPDX-SnippetEnd
+                    (Some("message"), &Method::GET) => {
+                        Err(trc::ResourceEvent::NotFound
+                            .ctx(trc::Key::Details, "Enterprise feature"))
+                    }
+                    _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                }
             }
             "schema" => {
                 // Authenticate request
@@ -302,6 +782,15 @@ impl ToManageHttpResponse for &trc::Error {
             trc::EventType::Auth(
                 trc::AuthEvent::Failed | trc::AuthEvent::Error | trc::AuthEvent::TokenExpired,
             ) => HttpResponse::unauthorized(true),
+            trc::EventType::Limit(_) => {
+                let response = self.to_request_error().into_http_response();
+                match self.value(trc::Key::Expires).and_then(|v| v.to_uint()) {
+                    Some(retry_after) => {
+                        response.with_header(header::RETRY_AFTER, retry_after.to_string())
+                    }
+                    None => response,
+                }
+            }
             _ => self.to_request_error().into_http_response(),
         }
     }
@@ -0,0 +1,87 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{Server, auth::AccessToken};
+use http_proto::{HttpRequest, HttpResponse, JsonResponse, ToHttpResponse};
+use registry::{schema::enums::Permission, types::datetime::UTCDateTime};
+use serde::Serialize;
+use std::{future::Future, net::IpAddr};
+use utils::url_params::UrlParams;
+
+pub trait MtaStsManagement: Sync + Send {
+    fn handle_mta_sts_fetches(
+        &self,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MtaStsReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    policy_id: Option<String>,
+    fetches: Vec<MtaStsFetchInfo>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MtaStsFetchInfo {
+    domain: String,
+    remote_ip: IpAddr,
+    policy_id: String,
+    fetched_at: UTCDateTime,
+}
+
+impl MtaStsManagement for Server {
+    async fn handle_mta_sts_fetches(
+        &self,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(Permission::SysMtaStsGet)?;
+
+        let params = UrlParams::new(req.uri().query());
+        let domain = params.get("domain").map(|domain| domain.to_lowercase());
+        let limit = params.parse::<usize>("limit").unwrap_or(100);
+        let tenant_id = access_token.tenant_id();
+
+        // Fetches are listed from most to least recent
+        let fetches = self
+            .inner
+            .data
+            .mta_sts_fetches
+            .lock()
+            .recent()
+            .filter(|fetch| {
+                domain
+                    .as_deref()
+                    .is_none_or(|domain| &*fetch.domain == domain)
+                    && tenant_id.is_none_or(|tenant_id| fetch.tenant_id == Some(tenant_id))
+            })
+            .take(limit)
+            .map(|fetch| MtaStsFetchInfo {
+                domain: fetch.domain.to_string(),
+                remote_ip: fetch.remote_ip,
+                policy_id: fetch.policy_id.to_string(),
+                fetched_at: UTCDateTime::from_timestamp(fetch.fetched_at as i64),
+            })
+            .collect();
+
+        Ok(JsonResponse::new(MtaStsReport {
+            policy_id: self
+                .core
+                .smtp
+                .session
+                .mta_sts_policy
+                .as_ref()
+                .map(|policy| policy.id.clone()),
+            fetches,
+        })
+        .no_cache()
+        .into_http_response())
+    }
+}
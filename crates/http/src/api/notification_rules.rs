@@ -0,0 +1,95 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::mailbox_stats::authorize_account;
+use common::{
+    Server,
+    auth::AccessToken,
+    manager::audit::{AuditAction, AuditEntry},
+};
+use email::push::rules::{NotificationRules, NotificationRulesStore};
+use http_proto::{HttpResponse, HttpSessionData, JsonResponse, ToHttpResponse};
+use registry::schema::enums::Permission;
+use serde_json::json;
+use std::future::Future;
+use types::id::Id;
+
+pub trait NotificationRulesManagement: Sync + Send {
+    fn handle_notification_rules_get(
+        &self,
+        account_id: &str,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_notification_rules_set(
+        &self,
+        account_id: &str,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+impl NotificationRulesManagement for Server {
+    async fn handle_notification_rules_get(
+        &self,
+        account_id: &str,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::JmapPushSubscriptionGet)?;
+
+        let rules = self.notification_rules(account_id).await?;
+
+        Ok(JsonResponse::new(rules).no_cache().into_http_response())
+    }
+
+    async fn handle_notification_rules_set(
+        &self,
+        account_id: &str,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::JmapPushSubscriptionUpdate)?;
+
+        // An empty body removes all rules
+        let rules = match body.as_deref() {
+            Some(body) if !body.is_empty() => serde_json::from_slice::<NotificationRules>(body)
+                .map_err(|err| {
+                    trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+                })?,
+            _ => NotificationRules::default(),
+        };
+        rules
+            .validate()
+            .map_err(|reason| trc::ResourceEvent::BadParameters.into_err().details(reason))?;
+
+        let previous = self.notification_rules(account_id).await?;
+        self.set_notification_rules(account_id, rules.clone())
+            .await?;
+
+        let entry = AuditEntry::new(
+            access_token,
+            session.remote_ip,
+            if rules.rules.is_empty() {
+                AuditAction::Destroy
+            } else {
+                AuditAction::Update
+            },
+            "NotificationRules",
+            Some(account_id as u64),
+        )
+        .with_before(&json!({ "accountId": Id::from(account_id), "rules": previous }))
+        .with_after(&json!({ "accountId": Id::from(account_id), "rules": rules }));
+        if let Err(err) = self.write_audit_log(vec![entry]).await {
+            trc::error!(err.caused_by(trc::location!()));
+        }
+
+        Ok(JsonResponse::new(rules).no_cache().into_http_response())
+    }
+}
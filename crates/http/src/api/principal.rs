@@ -0,0 +1,526 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{Server, auth::AccessToken};
+use http_proto::{HttpRequest, HttpResponse, HttpSessionData, JsonResponse, ToHttpResponse};
+use hyper::{StatusCode, header::CONTENT_TYPE};
+use jmap::registry::set::RegistrySet;
+use jmap_proto::{error::set::SetError, method::set::SetRequest, object::registry::Registry};
+use registry::{
+    jmap::{IntoValue, JmapValue, RegistryValue},
+    schema::{
+        enums::StorageQuota,
+        prelude::{ObjectInner, ObjectType, Property},
+        structs::{Account, EmailAlias},
+    },
+    types::{id::ObjectId, list::List},
+};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use store::registry::{RegistryOverlay, RegistryQuery};
+use trc::AddContext;
+use types::id::Id;
+use utils::url_params::UrlParams;
+
+pub trait PrincipalManagement: Sync + Send {
+    fn handle_principal_import(
+        &self,
+        req: &HttpRequest,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_principal_export(
+        &self,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+struct ImportResponse {
+    #[serde(rename = "dryRun")]
+    dry_run: bool,
+    created: usize,
+    failed: usize,
+    rows: Vec<ImportRow>,
+}
+
+#[derive(Debug, Serialize)]
+struct ImportRow {
+    row: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    created: Option<JmapValue<'static>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<SetError<Property>>,
+}
+
+impl PrincipalManagement for Server {
+    async fn handle_principal_import(
+        &self,
+        req: &HttpRequest,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        let [create_permission, _, _] = ObjectType::Account.set_permission();
+        access_token.enforce_permission(create_permission)?;
+
+        let params = UrlParams::new(req.uri().query());
+        let dry_run = params.parse::<bool>("dryRun").unwrap_or(false);
+        let is_csv = params.get("format").map_or_else(
+            || {
+                req.headers()
+                    .get(CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .is_some_and(|value| value.starts_with("text/csv"))
+            },
+            |format| format.eq_ignore_ascii_case("csv"),
+        );
+        let body = body.ok_or_else(|| trc::LimitEvent::SizeRequest.into_err())?;
+
+        // Parse rows
+        let rows = if is_csv {
+            parse_csv(self, access_token, &body).await?
+        } else {
+            serde_json::from_slice::<Vec<Value>>(&body)
+                .map_err(|err| {
+                    trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+                })?
+                .into_iter()
+                .map(|row| {
+                    if row.is_object() {
+                        Ok(row)
+                    } else {
+                        Err(SetError::invalid_properties()
+                            .with_description("Expected an account object."))
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Create accounts in batches, reusing the registry validation
+        let mut response = ImportResponse {
+            dry_run,
+            created: 0,
+            failed: 0,
+            rows: Vec::with_capacity(rows.len()),
+        };
+        let batch_size = self.core.jmap.set_max_objects.max(1);
+        let mut validated = RegistryOverlay::default();
+        let mut rows = rows.into_iter().enumerate().peekable();
+        while rows.peek().is_some() {
+            let mut create = Map::new();
+            let mut batch = Vec::with_capacity(batch_size);
+            for (row_num, row) in rows.by_ref().take(batch_size) {
+                let row_num = row_num + 1;
+                match row {
+                    Ok(row) => {
+                        create.insert(row_num.to_string(), row);
+                        batch.push(row_num);
+                    }
+                    Err(err) => {
+                        response.failed += 1;
+                        response.rows.push(ImportRow {
+                            row: row_num,
+                            created: None,
+                            error: Some(err),
+                        });
+                    }
+                }
+            }
+            if create.is_empty() {
+                continue;
+            }
+
+            let request = json!({ "create": create }).to_string();
+            let mut request =
+                serde_json::from_str::<SetRequest<'_, Registry>>(&request).map_err(|err| {
+                    trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+                })?;
+            request.account_id = Id::from(access_token.account_id());
+            let mut result = self
+                .registry_set(
+                    ObjectType::Account,
+                    request,
+                    access_token,
+                    session,
+                    dry_run.then_some(&mut validated),
+                )
+                .await?;
+
+            for row_num in batch {
+                let client_id = row_num.to_string();
+                if let Some(object) = result.created.remove(&client_id) {
+                    response.created += 1;
+                    response.rows.push(ImportRow {
+                        row: row_num,
+                        created: Some(object),
+                        error: None,
+                    });
+                } else {
+                    response.failed += 1;
+                    response.rows.push(ImportRow {
+                        row: row_num,
+                        created: None,
+                        error: Some(result.not_created.remove(&client_id).unwrap_or_else(|| {
+                            SetError::forbidden().with_description("Account was not created.")
+                        })),
+                    });
+                }
+            }
+        }
+        response.rows.sort_unstable_by_key(|row| row.row);
+
+        Ok(JsonResponse::with_status(
+            if response.failed == 0 {
+                StatusCode::OK
+            } else {
+                StatusCode::MULTI_STATUS
+            },
+            response,
+        )
+        .no_cache()
+        .into_http_response())
+    }
+
+    async fn handle_principal_export(
+        &self,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(ObjectType::Account.query_permission())?;
+        access_token.enforce_permission(ObjectType::Account.get_permission())?;
+
+        let params = UrlParams::new(req.uri().query());
+        let is_csv = params
+            .get("format")
+            .is_some_and(|format| format.eq_ignore_ascii_case("csv"));
+        let tenant_id = access_token.tenant_id();
+        let ids = self
+            .registry()
+            .query::<Vec<Id>>(RegistryQuery::new(ObjectType::Account).with_tenant(tenant_id))
+            .await
+            .caused_by(trc::location!())?;
+
+        let mut objects = Vec::with_capacity(ids.len());
+        let mut csv = csv::Writer::from_writer(Vec::new());
+        if is_csv {
+            csv.write_record([
+                "id",
+                "type",
+                "name",
+                "domain",
+                "description",
+                "aliases",
+                "quota",
+            ])
+            .map_err(csv_error)?;
+        }
+
+        for id in ids {
+            let Some(object) = self
+                .registry()
+                .get(ObjectId::new(ObjectType::Account, id))
+                .await
+                .caused_by(trc::location!())?
+                .filter(|object| {
+                    tenant_id.is_none_or(|tenant_id| {
+                        object.inner.member_tenant_id() == Some(Id::from(tenant_id))
+                    })
+                })
+            else {
+                continue;
+            };
+            let ObjectInner::Account(mut account) = object.inner else {
+                continue;
+            };
+
+            if is_csv {
+                let (account_type, name, domain_id, description, aliases, quotas) = match &account {
+                    Account::User(account) => (
+                        "User",
+                        &account.name,
+                        account.domain_id,
+                        &account.description,
+                        &account.aliases,
+                        &account.quotas,
+                    ),
+                    Account::Group(account) => (
+                        "Group",
+                        &account.name,
+                        account.domain_id,
+                        &account.description,
+                        &account.aliases,
+                        &account.quotas,
+                    ),
+                };
+                let mut alias_list = Vec::with_capacity(aliases.len());
+                for alias in aliases.values() {
+                    alias_list.push(format!(
+                        "{}@{}",
+                        alias.name,
+                        domain_name(self, alias.domain_id).await?
+                    ));
+                }
+
+                csv.write_record([
+                    id.to_string().as_str(),
+                    account_type,
+                    name,
+                    domain_name(self, domain_id).await?.as_str(),
+                    description.as_deref().unwrap_or_default(),
+                    alias_list.join(" ").as_str(),
+                    quotas
+                        .get(&StorageQuota::MaxDiskQuota)
+                        .map(|quota| quota.to_string())
+                        .unwrap_or_default()
+                        .as_str(),
+                ])
+                .map_err(csv_error)?;
+            } else {
+                // Credentials are never exported
+                match &mut account {
+                    Account::User(account) => {
+                        account.credentials = List::default();
+                        if tenant_id.is_some() {
+                            account.member_tenant_id = None;
+                        }
+                    }
+                    Account::Group(account) => {
+                        if tenant_id.is_some() {
+                            account.member_tenant_id = None;
+                        }
+                    }
+                }
+
+                let mut object = account.into_value();
+                if let JmapValue::Object(object) = &mut object {
+                    object.insert_unchecked(Property::Id, RegistryValue::Id(id));
+                }
+                objects.push(object);
+            }
+        }
+
+        if is_csv {
+            Ok(HttpResponse::new(StatusCode::OK)
+                .with_content_type("text/csv; charset=utf-8")
+                .with_no_cache()
+                .with_binary_body(
+                    csv.into_inner()
+                        .map_err(|err| csv_error(err.into_error()))?,
+                ))
+        } else {
+            Ok(JsonResponse::new(objects).no_cache().into_http_response())
+        }
+    }
+}
+
+// Maps a CSV file with a header row to account objects. Supported columns are
+// type, name, domain, domainId, description, password, aliases, quota, locale and timeZone.
+async fn parse_csv(
+    server: &Server,
+    access_token: &AccessToken,
+    body: &[u8],
+) -> trc::Result<Vec<Result<Value, SetError<Property>>>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(body);
+    let headers = reader
+        .headers()
+        .map_err(csv_error)?
+        .iter()
+        .map(|header| header.to_ascii_lowercase())
+        .collect::<Vec<_>>();
+    if let Some(header) = headers.iter().find(|header| {
+        !matches!(
+            header.as_str(),
+            "type"
+                | "name"
+                | "domain"
+                | "domainid"
+                | "description"
+                | "password"
+                | "aliases"
+                | "quota"
+                | "locale"
+                | "timezone"
+        )
+    }) {
+        return Err(trc::ResourceEvent::BadParameters
+            .into_err()
+            .details("Unsupported CSV column")
+            .ctx(trc::Key::Key, header.to_string()));
+    }
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let row = match record {
+            Ok(record) => {
+                parse_csv_row(
+                    server,
+                    access_token,
+                    headers
+                        .iter()
+                        .map(|header| header.as_str())
+                        .zip(record.iter()),
+                )
+                .await?
+            }
+            Err(err) => Err(SetError::invalid_properties().with_description(err.to_string())),
+        };
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+async fn parse_csv_row(
+    server: &Server,
+    access_token: &AccessToken,
+    fields: impl Iterator<Item = (&str, &str)>,
+) -> trc::Result<Result<Value, SetError<Property>>> {
+    let mut account = Map::new();
+    account.insert("@type".to_string(), json!("User"));
+
+    for (header, value) in fields {
+        if value.is_empty() {
+            continue;
+        }
+
+        match header {
+            "type" => {
+                let account_type = if value.eq_ignore_ascii_case("user") {
+                    "User"
+                } else if value.eq_ignore_ascii_case("group") {
+                    "Group"
+                } else {
+                    return Ok(Err(SetError::invalid_properties()
+                        .with_property(Property::Type)
+                        .with_description(format!("Invalid account type {value:?}."))));
+                };
+                account.insert("@type".to_string(), json!(account_type));
+            }
+            "name" => {
+                if let Some((name, domain)) = value.rsplit_once('@') {
+                    match domain_id(server, access_token, domain).await? {
+                        Ok(domain_id) => {
+                            account.insert("name".to_string(), json!(name));
+                            account.insert("domainId".to_string(), json!(domain_id));
+                        }
+                        Err(err) => return Ok(Err(err)),
+                    }
+                } else {
+                    account.insert("name".to_string(), json!(value));
+                }
+            }
+            "domain" => match domain_id(server, access_token, value).await? {
+                Ok(domain_id) => {
+                    account.insert("domainId".to_string(), json!(domain_id));
+                }
+                Err(err) => return Ok(Err(err)),
+            },
+            "domainid" => {
+                account.insert("domainId".to_string(), json!(value));
+            }
+            "description" => {
+                account.insert("description".to_string(), json!(value));
+            }
+            "password" => {
+                account.insert(
+                    "credentials".to_string(),
+                    json!({ "0": { "@type": "Password", "secret": value } }),
+                );
+            }
+            "aliases" => {
+                let mut aliases = Map::new();
+                for (idx, alias) in value
+                    .split([' ', ';'])
+                    .filter(|alias| !alias.is_empty())
+                    .enumerate()
+                {
+                    let Some((name, domain)) = alias.rsplit_once('@') else {
+                        return Ok(Err(SetError::invalid_properties()
+                            .with_property(Property::Aliases)
+                            .with_description(format!("Invalid alias {alias:?}."))));
+                    };
+                    match domain_id(server, access_token, domain).await? {
+                        Ok(domain_id) => {
+                            aliases.insert(
+                                idx.to_string(),
+                                serde_json::to_value(EmailAlias {
+                                    enabled: true,
+                                    name: name.to_string(),
+                                    domain_id,
+                                    description: None,
+                                })
+                                .unwrap_or_default(),
+                            );
+                        }
+                        Err(err) => return Ok(Err(err)),
+                    }
+                }
+                account.insert("aliases".to_string(), Value::Object(aliases));
+            }
+            "quota" => {
+                let Ok(quota) = value.parse::<u64>() else {
+                    return Ok(Err(SetError::invalid_properties()
+                        .with_property(Property::Quotas)
+                        .with_description(format!("Invalid quota {value:?}."))));
+                };
+                account.insert("quotas".to_string(), json!({ "maxDiskQuota": quota }));
+            }
+            "locale" => {
+                account.insert("locale".to_string(), json!(value));
+            }
+            "timezone" => {
+                account.insert("timeZone".to_string(), json!(value));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Ok(Value::Object(account)))
+}
+
+pub(crate) async fn domain_id(
+    server: &Server,
+    access_token: &AccessToken,
+    domain: &str,
+) -> trc::Result<Result<Id, SetError<Property>>> {
+    Ok(server
+        .domain(&domain.to_lowercase())
+        .await
+        .caused_by(trc::location!())?
+        .filter(|domain| {
+            access_token
+                .tenant_id()
+                .is_none_or(|tenant_id| domain.id_tenant == Some(tenant_id))
+        })
+        .map(|domain| Id::from(domain.id))
+        .ok_or_else(|| {
+            SetError::invalid_properties()
+                .with_property(Property::DomainId)
+                .with_description(format!("Domain {domain:?} does not exist."))
+        }))
+}
+
+async fn domain_name(server: &Server, domain_id: Id) -> trc::Result<String> {
+    Ok(server
+        .domain_by_id(domain_id.document_id())
+        .await
+        .caused_by(trc::location!())?
+        .map(|domain| domain.name().to_string())
+        .unwrap_or_default())
+}
+
+fn csv_error(err: csv::Error) -> trc::Error {
+    trc::ResourceEvent::BadParameters
+        .into_err()
+        .reason(err)
+        .details("CSV processing failed")
+}
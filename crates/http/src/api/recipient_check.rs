@@ -0,0 +1,227 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::mailbox_stats::authorize_account;
+use common::{Server, auth::AccessToken, network::RcptResolution};
+use http_proto::{HttpResponse, HttpSessionData, JsonResponse, ToHttpResponse};
+use registry::{schema::enums::Permission, types::EnumImpl};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use store::ahash::{AHashMap, AHashSet};
+use trc::AddContext;
+use utils::sanitize_email;
+
+const MAX_ADDRESSES: usize = 100;
+const MAX_LIST_EXPANSIONS: usize = 1000;
+
+pub trait RecipientCheckManagement: Sync + Send {
+    fn handle_recipient_check(
+        &self,
+        account_id: &str,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecipientCheckRequest {
+    addresses: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RecipientCheck {
+    address: String,
+    status: RecipientStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    members: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    details: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+enum RecipientStatus {
+    Internal,
+    DistributionList,
+    Relay,
+    External,
+    NoMx,
+    Suppressed,
+    UnknownRecipient,
+    Invalid,
+}
+
+impl RecipientCheckManagement for Server {
+    // Recipients are checked without queueing anything, so that compose
+    // clients can warn about problems before the message is submitted.
+    async fn handle_recipient_check(
+        &self,
+        account_id: &str,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        authorize_account(self, account_id, access_token).await?;
+        access_token.enforce_permission(Permission::EmailSend)?;
+
+        let request =
+            serde_json::from_slice::<RecipientCheckRequest>(body.as_deref().unwrap_or_default())
+                .map_err(|err| {
+                    trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+                })?;
+        if request.addresses.len() > MAX_ADDRESSES {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details(format!(
+                    "At most {MAX_ADDRESSES} addresses can be checked at once"
+                )));
+        }
+
+        let mut mx_results: AHashMap<String, (RecipientStatus, Option<String>)> = AHashMap::new();
+        let mut results = Vec::with_capacity(request.addresses.len());
+        for address in request.addresses {
+            let Some(rcpt) = sanitize_email(&address) else {
+                results.push(RecipientCheck {
+                    address,
+                    status: RecipientStatus::Invalid,
+                    members: None,
+                    details: None,
+                });
+                continue;
+            };
+
+            let mut check = RecipientCheck {
+                address,
+                status: RecipientStatus::Internal,
+                members: None,
+                details: None,
+            };
+            match self
+                .rcpt_resolve(&rcpt, session.session_id)
+                .await
+                .caused_by(trc::location!())?
+            {
+                RcptResolution::Accept => {}
+                RcptResolution::Rewrite(address) => {
+                    check.details = Some(format!("Delivered to {address}"));
+                }
+                RcptResolution::Expand(members, _) => {
+                    check.status = RecipientStatus::DistributionList;
+                    check.members =
+                        Some(expand_list_members(self, &rcpt, &members, session.session_id).await?);
+                }
+                RcptResolution::Verify(_) => {
+                    check.status = RecipientStatus::Relay;
+                }
+                RcptResolution::UnknownRecipient => {
+                    check.status = RecipientStatus::UnknownRecipient;
+                }
+                RcptResolution::UnknownDomain => {
+                    if let Some(entry) = self
+                        .suppressed_address(&rcpt, access_token.tenant_id())
+                        .await
+                        .caused_by(trc::location!())?
+                    {
+                        check.status = RecipientStatus::Suppressed;
+                        check.details = Some(format!(
+                            "Address is on the suppression list ({})",
+                            entry.reason.as_str()
+                        ));
+                        results.push(check);
+                        continue;
+                    }
+
+                    let domain = rcpt.rsplit_once('@').unwrap_or_default().1;
+                    let (status, details) = if let Some(result) = mx_results.get(domain) {
+                        result.clone()
+                    } else {
+                        let result = check_mx(self, domain).await;
+                        mx_results.insert(domain.to_string(), result.clone());
+                        result
+                    };
+                    check.status = status;
+                    check.details = details;
+                }
+            }
+            results.push(check);
+        }
+
+        Ok(JsonResponse::new(results).no_cache().into_http_response())
+    }
+}
+
+// Nested lists are expanded so that the count reflects the number of
+// distinct mailboxes the message would reach
+async fn expand_list_members(
+    server: &Server,
+    list: &str,
+    members: &[Box<str>],
+    session_id: u64,
+) -> trc::Result<usize> {
+    let mut seen = AHashSet::from_iter([list.to_string()]);
+    let mut pending = members.iter().map(|m| m.to_string()).collect::<Vec<_>>();
+    let mut count = 0;
+    let mut expansions = 0;
+
+    while let Some(member) = pending.pop() {
+        if !seen.insert(member.clone()) {
+            continue;
+        }
+        match server
+            .rcpt_resolve(&member, session_id)
+            .await
+            .caused_by(trc::location!())?
+        {
+            RcptResolution::Expand(members, _) if expansions < MAX_LIST_EXPANSIONS => {
+                expansions += 1;
+                pending.extend(members.iter().map(|m| m.to_string()));
+            }
+            RcptResolution::UnknownRecipient => {}
+            _ => {
+                count += 1;
+            }
+        }
+    }
+
+    Ok(count)
+}
+
+async fn check_mx(server: &Server, domain: &str) -> (RecipientStatus, Option<String>) {
+    match server
+        .core
+        .smtp
+        .resolvers
+        .dns
+        .mx_lookup(domain, Some(&server.inner.cache.dns_mx))
+        .await
+    {
+        Ok(mxs) => {
+            // A single "." exchange is a null MX (RFC 7505)
+            if mxs.is_empty()
+                || mxs.iter().all(|mx| {
+                    mx.exchanges
+                        .iter()
+                        .all(|host| host.trim_end_matches('.').is_empty())
+                })
+            {
+                (
+                    RecipientStatus::NoMx,
+                    Some(format!("Domain {domain} does not accept email")),
+                )
+            } else {
+                (RecipientStatus::External, None)
+            }
+        }
+        Err(mail_auth::Error::DnsRecordNotFound(_)) => (
+            RecipientStatus::NoMx,
+            Some(format!("No MX records found for domain {domain}")),
+        ),
+        Err(err) => (RecipientStatus::External, Some(err.to_string())),
+    }
+}
@@ -0,0 +1,196 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::mailbox_stats::authorize_account;
+use common::{Server, auth::AccessToken};
+use http_proto::{HttpResponse, JsonResponse, ToHttpResponse};
+use registry::{
+    schema::{
+        enums::{
+            IndexDocumentType, Permission, TaskAccountMaintenanceType, TaskStoreMaintenanceType,
+        },
+        structs::{Task, TaskAccountMaintenance, TaskStatus, TaskStoreMaintenance},
+    },
+    types::EnumImpl,
+};
+use serde::Serialize;
+use std::future::Future;
+use store::{
+    Deserialize, IterateParams, ValueKey,
+    write::{BatchBuilder, TaskQueueClass, ValueClass},
+};
+use trc::AddContext;
+use types::id::Id;
+
+pub trait ReindexManagement: Sync + Send {
+    fn handle_reindex(
+        &self,
+        account_id: Option<&str>,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_reindex_status(
+        &self,
+        account_id: Option<&str>,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReindexResponse {
+    scheduled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_id: Option<Id>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReindexStatus {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_id: Option<Id>,
+    in_progress: bool,
+    pending_accounts: u64,
+    pending: DocumentCounts,
+    failed: DocumentCounts,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DocumentCounts {
+    emails: u64,
+    calendar_events: u64,
+    contacts: u64,
+    files: u64,
+}
+
+impl ReindexManagement for Server {
+    async fn handle_reindex(
+        &self,
+        account_id: Option<&str>,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let (task, task_type, account_id) = if let Some(account_id) = account_id {
+            let account_id = authorize_account(self, account_id, access_token).await?;
+            (
+                Task::AccountMaintenance(TaskAccountMaintenance {
+                    account_id: account_id.into(),
+                    maintenance_type: TaskAccountMaintenanceType::Reindex,
+                    status: TaskStatus::now(),
+                }),
+                TaskAccountMaintenanceType::Reindex.as_str(),
+                Some(Id::from(account_id)),
+            )
+        } else {
+            (
+                Task::StoreMaintenance(TaskStoreMaintenance {
+                    maintenance_type: TaskStoreMaintenanceType::ReindexAccounts,
+                    shard_index: None,
+                    status: TaskStatus::now(),
+                }),
+                TaskStoreMaintenanceType::ReindexAccounts.as_str(),
+                None,
+            )
+        };
+        access_token.enforce_permission(task.permission())?;
+
+        trc::event!(
+            TaskManager(trc::TaskManagerEvent::TaskQueued),
+            Type = task_type
+        );
+
+        let mut batch = BatchBuilder::new();
+        batch.schedule_task(task);
+        self.store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())?;
+        self.notify_task_queue();
+
+        Ok(JsonResponse::new(ReindexResponse {
+            scheduled: true,
+            account_id,
+        })
+        .no_cache()
+        .into_http_response())
+    }
+
+    async fn handle_reindex_status(
+        &self,
+        account_id: Option<&str>,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let account_id = match account_id {
+            Some(account_id) => Some(authorize_account(self, account_id, access_token).await?),
+            None => None,
+        };
+        access_token.enforce_permission(Permission::SysTaskQuery)?;
+
+        // Progress is derived from the indexing tasks still left in the queue
+        let mut status = ReindexStatus {
+            account_id: account_id.map(Id::from),
+            ..Default::default()
+        };
+        self.store()
+            .iterate(
+                IterateParams::new(
+                    ValueKey::from(ValueClass::TaskQueue(TaskQueueClass::Task { id: 0 })),
+                    ValueKey::from(ValueClass::TaskQueue(TaskQueueClass::Task { id: u64::MAX })),
+                ),
+                |_, value| {
+                    let Ok(task) = Task::deserialize(value) else {
+                        return Ok(true);
+                    };
+                    match &task {
+                        Task::IndexDocument(index)
+                            if account_id.is_none_or(|id| index.account_id.document_id() == id) =>
+                        {
+                            let counts = if matches!(index.status, TaskStatus::Failed(_)) {
+                                &mut status.failed
+                            } else {
+                                &mut status.pending
+                            };
+                            match index.document_type {
+                                IndexDocumentType::Email => counts.emails += 1,
+                                IndexDocumentType::Calendar => counts.calendar_events += 1,
+                                IndexDocumentType::Contacts => counts.contacts += 1,
+                                IndexDocumentType::File => counts.files += 1,
+                            }
+                        }
+                        Task::AccountMaintenance(maintenance)
+                            if maintenance.maintenance_type
+                                == TaskAccountMaintenanceType::Reindex
+                                && !matches!(maintenance.status, TaskStatus::Failed(_))
+                                && account_id.is_none_or(|id| {
+                                    maintenance.account_id.document_id() == id
+                                }) =>
+                        {
+                            status.pending_accounts += 1;
+                        }
+                        Task::StoreMaintenance(maintenance)
+                            if maintenance.maintenance_type
+                                == TaskStoreMaintenanceType::ReindexAccounts
+                                && !matches!(maintenance.status, TaskStatus::Failed(_))
+                                && account_id.is_none() =>
+                        {
+                            status.in_progress = true;
+                        }
+                        _ => {}
+                    }
+
+                    Ok(true)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        let pending = &status.pending;
+        status.in_progress |= status.pending_accounts > 0
+            || pending.emails + pending.calendar_events + pending.contacts + pending.files > 0;
+
+        Ok(JsonResponse::new(status).no_cache().into_http_response())
+    }
+}
@@ -0,0 +1,401 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::sieve::{SieveTestEnvelope, SieveTestResponse, sieve_dry_run};
+use common::{
+    Server,
+    auth::{AccessToken, EmailCache},
+    config::mailstore::spamfilter::SpamFilterAction,
+    psl,
+};
+use email::sieve::ingest::SieveScriptIngest;
+use http_proto::{HttpResponse, JsonResponse, ToHttpResponse};
+use mail_auth::{
+    AuthenticatedMessage, DmarcResult, dmarc::verify::DmarcParameters, spf::verify::SpfParameters,
+};
+use mail_parser::MessageParser;
+use registry::schema::{
+    enums::{DmarcDisposition, Permission},
+    structs::DmarcTroubleshootAuthResult,
+};
+use serde::{Deserialize, Serialize};
+use spam_filter::{
+    SpamFilterInput,
+    analysis::{init::SpamFilterInit, score::SpamFilterAnalyzeScore},
+};
+use std::{future::Future, net::IpAddr, time::Instant};
+use trc::AddContext;
+use types::id::Id;
+
+pub trait ReplayManagement: Sync + Send {
+    fn handle_message_replay(
+        &self,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReplayRequest {
+    message: String,
+    remote_ip: IpAddr,
+    ehlo_domain: String,
+    #[serde(default)]
+    mail_from: String,
+    #[serde(default)]
+    rcpt_to: Vec<String>,
+    #[serde(default)]
+    authenticated_as: Option<String>,
+    #[serde(default)]
+    is_tls: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ReplayResponse {
+    stages: Vec<ReplayStage>,
+    elapsed: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "type")]
+enum ReplayStage {
+    SpfEhlo {
+        domain: String,
+        result: DmarcTroubleshootAuthResult,
+    },
+    Iprev {
+        ptr: Vec<String>,
+        result: DmarcTroubleshootAuthResult,
+    },
+    #[serde(rename_all = "camelCase")]
+    SpfMailFrom {
+        domain: String,
+        result: DmarcTroubleshootAuthResult,
+    },
+    Dkim {
+        pass: bool,
+        results: Vec<DmarcTroubleshootAuthResult>,
+    },
+    Arc {
+        result: DmarcTroubleshootAuthResult,
+    },
+    Dmarc {
+        pass: bool,
+        result: DmarcTroubleshootAuthResult,
+        policy: DmarcDisposition,
+    },
+    #[serde(rename_all = "camelCase")]
+    SpamFilter {
+        action: &'static str,
+        score: f32,
+        tags: Vec<SpamFilterTag>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Recipient {
+        address: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        account_id: Option<Id>,
+        status: &'static str,
+    },
+    #[serde(rename_all = "camelCase")]
+    Sieve {
+        address: String,
+        account_id: Id,
+        script: String,
+        #[serde(flatten)]
+        result: SieveTestResponse,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct SpamFilterTag {
+    name: String,
+    score: f32,
+}
+
+impl ReplayManagement for Server {
+    async fn handle_message_replay(
+        &self,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(Permission::ActionTroubleshootDmarc)?;
+        access_token.enforce_permission(Permission::ActionClassifySpam)?;
+
+        let request = serde_json::from_slice::<ReplayRequest>(body.as_deref().unwrap_or_default())
+            .map_err(|err| {
+                trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+            })?;
+        let Some(message) = MessageParser::new()
+            .parse(request.message.as_bytes())
+            .filter(|m| m.root_part().headers().iter().any(|h| !h.name.is_other()))
+        else {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Failed to parse message"));
+        };
+
+        let now = Instant::now();
+        let mut stages = Vec::new();
+        let remote_ip = request.remote_ip;
+        let ehlo_domain = request.ehlo_domain.to_lowercase();
+        let mail_from = request.mail_from.to_lowercase();
+        let mail_from_domain = mail_from.rsplit_once('@').map(|(_, domain)| domain);
+        let local_host = &self.core.network.server_name;
+        let resolver = &self.core.smtp.resolvers.dns;
+
+        // Authentication checks, in the same order as an inbound SMTP session
+        let spf_ehlo_result = resolver
+            .verify_spf(
+                self.inner
+                    .cache
+                    .build_auth_parameters(SpfParameters::verify_ehlo(
+                        remote_ip,
+                        &ehlo_domain,
+                        local_host,
+                    )),
+            )
+            .await;
+        stages.push(ReplayStage::SpfEhlo {
+            domain: spf_ehlo_result.domain().to_string(),
+            result: (&spf_ehlo_result).into(),
+        });
+
+        let iprev_result = resolver
+            .verify_iprev(self.inner.cache.build_auth_parameters(remote_ip))
+            .await;
+        stages.push(ReplayStage::Iprev {
+            ptr: iprev_result
+                .ptr
+                .as_ref()
+                .map(|ptr| ptr.iter().map(|label| label.to_string()).collect())
+                .unwrap_or_default(),
+            result: (&iprev_result).into(),
+        });
+
+        let postmaster = format!("postmaster@{ehlo_domain}");
+        let spf_mail_from_result = resolver
+            .check_host(
+                self.inner
+                    .cache
+                    .build_auth_parameters(match mail_from_domain {
+                        Some(mail_from_domain) => SpfParameters::new(
+                            remote_ip,
+                            mail_from_domain,
+                            &ehlo_domain,
+                            local_host,
+                            &mail_from,
+                        ),
+                        None => SpfParameters::new(
+                            remote_ip,
+                            &ehlo_domain,
+                            &ehlo_domain,
+                            local_host,
+                            &postmaster,
+                        ),
+                    }),
+            )
+            .await;
+        stages.push(ReplayStage::SpfMailFrom {
+            domain: spf_mail_from_result.domain().to_string(),
+            result: (&spf_mail_from_result).into(),
+        });
+
+        let auth_message = AuthenticatedMessage::from_parsed(&message, true);
+        let dkim_output = resolver
+            .verify_dkim(self.inner.cache.build_auth_parameters(&auth_message))
+            .await;
+        stages.push(ReplayStage::Dkim {
+            pass: dkim_output
+                .iter()
+                .any(|d| matches!(d.result(), mail_auth::DkimResult::Pass)),
+            results: dkim_output
+                .iter()
+                .map(|result| result.result().into())
+                .collect(),
+        });
+
+        let arc_output = resolver
+            .verify_arc(self.inner.cache.build_auth_parameters(&auth_message))
+            .await;
+        stages.push(ReplayStage::Arc {
+            result: arc_output.result().into(),
+        });
+
+        let dmarc_output = resolver
+            .verify_dmarc(self.inner.cache.build_auth_parameters(DmarcParameters {
+                message: &auth_message,
+                dkim_output: &dkim_output,
+                rfc5321_mail_from_domain: mail_from_domain.unwrap_or(ehlo_domain.as_str()),
+                spf_output: &spf_mail_from_result,
+                domain_suffix_fn: |domain| psl::domain_str(domain).unwrap_or(domain),
+            }))
+            .await;
+        let dmarc_pass = matches!(dmarc_output.spf_result(), DmarcResult::Pass)
+            || matches!(dmarc_output.dkim_result(), DmarcResult::Pass);
+        let dmarc_result = if dmarc_pass {
+            DmarcResult::Pass
+        } else if dmarc_output.spf_result() != &DmarcResult::None {
+            dmarc_output.spf_result().clone()
+        } else if dmarc_output.dkim_result() != &DmarcResult::None {
+            dmarc_output.dkim_result().clone()
+        } else {
+            DmarcResult::None
+        };
+        let dmarc_policy = dmarc_output.policy();
+        stages.push(ReplayStage::Dmarc {
+            pass: dmarc_pass,
+            result: (&dmarc_result).into(),
+            policy: (&dmarc_policy).into(),
+        });
+
+        // Spam filter, running in test mode so that no training or
+        // reputation updates take place
+        let asn_geo = self.lookup_asn_country(remote_ip).await;
+        let mut ctx = self.spam_filter_init(SpamFilterInput {
+            message: &message,
+            span_id: 0,
+            arc_result: Some(&arc_output),
+            spf_ehlo_result: Some(&spf_ehlo_result),
+            spf_mail_from_result: Some(&spf_mail_from_result),
+            dkim_result: dkim_output.as_slice(),
+            dmarc_result: Some(&dmarc_result),
+            dmarc_policy: Some(&dmarc_policy),
+            iprev_result: Some(&iprev_result),
+            remote_ip,
+            ehlo_domain: Some(ehlo_domain.as_str()),
+            authenticated_as: request.authenticated_as.as_deref(),
+            asn: asn_geo.asn.as_ref().map(|a| a.id),
+            country: asn_geo.country.as_ref().map(|c| c.as_str()),
+            is_tls: request.is_tls,
+            tls_fingerprint: None,
+            env_from: &request.mail_from,
+            env_from_flags: 0,
+            env_rcpt_to: request.rcpt_to.iter().map(String::as_str).collect(),
+            env_rcpt_to_detail: vec![],
+            is_test: true,
+            is_train: false,
+        });
+        let (action, score, is_spam) = match self.spam_filter_classify(&mut ctx).await {
+            SpamFilterAction::Allow(result) if result.is_spam => ("spam", result.score, true),
+            SpamFilterAction::Allow(result) => ("ham", result.score, false),
+            SpamFilterAction::Discard => ("discard", ctx.result.score, false),
+            SpamFilterAction::Reject => ("reject", ctx.result.score, false),
+            SpamFilterAction::Disabled => ("disabled", 0.0, false),
+        };
+        let mut tags = ctx
+            .result
+            .tags
+            .iter()
+            .map(|tag| SpamFilterTag {
+                name: tag.clone(),
+                score: match self.inner.data.spam_lists.load().scores.get(tag) {
+                    Some(SpamFilterAction::Allow(score)) => *score,
+                    _ => 0.0,
+                },
+            })
+            .collect::<Vec<_>>();
+        tags.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        stages.push(ReplayStage::SpamFilter {
+            action,
+            score,
+            tags,
+        });
+
+        // Recipient resolution and personal Sieve scripts, which are
+        // evaluated without filing, sending or discarding anything
+        if !matches!(action, "discard" | "reject") {
+            for rcpt in &request.rcpt_to {
+                let address = rcpt.to_lowercase();
+                let account_id = match self
+                    .rcpt_id_from_email(&address)
+                    .await
+                    .caused_by(trc::location!())?
+                {
+                    Some(EmailCache::Account(account_id)) => account_id,
+                    Some(EmailCache::MailingList(list_id)) => {
+                        stages.push(ReplayStage::Recipient {
+                            address,
+                            account_id: Some(Id::from(list_id)),
+                            status: "mailingList",
+                        });
+                        continue;
+                    }
+                    None => {
+                        stages.push(ReplayStage::Recipient {
+                            address,
+                            account_id: None,
+                            status: "unknown",
+                        });
+                        continue;
+                    }
+                };
+                if let Some(tenant_id) = access_token.tenant_id()
+                    && self
+                        .account(account_id)
+                        .await
+                        .caused_by(trc::location!())?
+                        .id_tenant
+                        != Some(tenant_id)
+                {
+                    stages.push(ReplayStage::Recipient {
+                        address,
+                        account_id: None,
+                        status: "unknown",
+                    });
+                    continue;
+                }
+
+                stages.push(ReplayStage::Recipient {
+                    address: address.clone(),
+                    account_id: Some(Id::from(account_id)),
+                    status: "local",
+                });
+
+                let Some(active_script) = self
+                    .sieve_script_get_active(account_id)
+                    .await
+                    .caused_by(trc::location!())?
+                else {
+                    continue;
+                };
+                let Some(message) = MessageParser::new().parse(request.message.as_bytes()) else {
+                    continue;
+                };
+                let result = sieve_dry_run(
+                    self,
+                    account_id,
+                    &active_script.script_name,
+                    active_script.script.clone(),
+                    message,
+                    &SieveTestEnvelope {
+                        from: request.mail_from.clone(),
+                        to: Some(address.clone()),
+                        is_spam,
+                    },
+                )
+                .await
+                .caused_by(trc::location!())?;
+                stages.push(ReplayStage::Sieve {
+                    address,
+                    account_id: Id::from(account_id),
+                    script: active_script.script_name,
+                    result,
+                });
+            }
+        }
+
+        Ok(JsonResponse::new(ReplayResponse {
+            stages,
+            elapsed: now.elapsed().as_millis() as u64,
+        })
+        .no_cache()
+        .into_http_response())
+    }
+}
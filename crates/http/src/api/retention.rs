@@ -0,0 +1,170 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{Server, auth::AccessToken};
+use email::message::retention::EmailRetention;
+use http_proto::{HttpRequest, HttpResponse, JsonResponse, ToHttpResponse};
+use registry::{
+    schema::{prelude::ObjectType, structs::RetentionPolicy},
+    types::EnumImpl,
+};
+use serde::Serialize;
+use std::{future::Future, str::FromStr, sync::Arc};
+use store::{registry::RegistryQuery, roaring::RoaringBitmap};
+use trc::AddContext;
+use types::id::Id;
+use utils::url_params::UrlParams;
+
+pub trait RetentionManagement: Sync + Send {
+    fn handle_retention_preview(
+        &self,
+        tenant_id: &str,
+        req: &HttpRequest,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RetentionPreview {
+    tenant_id: Id,
+    policies: Vec<PolicyPreview>,
+    accounts: Vec<AccountPreview>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PolicyPreview {
+    name: String,
+    action: &'static str,
+    messages: u64,
+    size: u64,
+    accounts: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AccountPreview {
+    account_id: Id,
+    policies: Vec<PolicyPreview>,
+}
+
+impl RetentionManagement for Server {
+    async fn handle_retention_preview(
+        &self,
+        tenant_id: &str,
+        req: &HttpRequest,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(ObjectType::Tenant.get_permission())?;
+        let tenant_id = parse_id(tenant_id)?.document_id();
+        if access_token
+            .tenant_id()
+            .is_some_and(|access_tenant_id| access_tenant_id != tenant_id)
+        {
+            return Err(trc::ResourceEvent::NotFound.into_err());
+        }
+
+        // Unsaved policies can be previewed by sending them in the request body,
+        // otherwise the policies currently configured for the tenant are used
+        let policies: Arc<[RetentionPolicy]> = match body.filter(|body| !body.is_empty()) {
+            Some(body) => serde_json::from_slice::<Vec<RetentionPolicy>>(&body)
+                .map_err(|err| {
+                    trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+                })?
+                .into(),
+            None => self
+                .tenant(tenant_id)
+                .await
+                .caused_by(trc::location!())?
+                .retention_policies
+                .clone(),
+        };
+
+        let account_ids = match UrlParams::new(req.uri().query()).get("account") {
+            Some(account_id) => {
+                let account_id = parse_id(account_id)?.document_id();
+                if self
+                    .account(account_id)
+                    .await
+                    .caused_by(trc::location!())?
+                    .id_tenant
+                    != Some(tenant_id)
+                {
+                    return Err(trc::ResourceEvent::NotFound.into_err());
+                }
+                RoaringBitmap::from_iter([account_id])
+            }
+            None => self
+                .registry()
+                .query::<RoaringBitmap>(
+                    RegistryQuery::new(ObjectType::Account).with_tenant(tenant_id.into()),
+                )
+                .await
+                .caused_by(trc::location!())?,
+        };
+
+        let mut totals = policies
+            .iter()
+            .map(|policy| PolicyPreview {
+                name: policy.name.clone(),
+                action: policy.action.as_str(),
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        let mut accounts = Vec::new();
+
+        for account_id in account_ids {
+            let results = self
+                .email_retention(account_id, &policies, true)
+                .await
+                .caused_by(trc::location!())?;
+            if results.iter().all(|result| result.messages == 0) {
+                continue;
+            }
+
+            for (total, result) in totals.iter_mut().zip(results.iter()) {
+                if result.messages > 0 {
+                    total.messages += result.messages;
+                    total.size += result.size;
+                    total.accounts += 1;
+                }
+            }
+            accounts.push(AccountPreview {
+                account_id: Id::from(account_id),
+                policies: results
+                    .into_iter()
+                    .filter(|result| result.messages > 0)
+                    .map(|result| PolicyPreview {
+                        name: result.policy,
+                        action: result.action.as_str(),
+                        messages: result.messages,
+                        size: result.size,
+                        accounts: 1,
+                    })
+                    .collect(),
+            });
+        }
+
+        Ok(JsonResponse::new(RetentionPreview {
+            tenant_id: Id::from(tenant_id),
+            policies: totals,
+            accounts,
+        })
+        .no_cache()
+        .into_http_response())
+    }
+}
+
+fn parse_id(id: &str) -> trc::Result<Id> {
+    Id::from_str(id).map_err(|_| {
+        trc::ResourceEvent::BadParameters
+            .into_err()
+            .details("Invalid id")
+    })
+}
@@ -0,0 +1,172 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{Server, auth::AccessToken, ipc::BroadcastEvent, network::session::ActiveSession};
+use http_proto::{HttpRequest, HttpResponse, JsonResponse, ToHttpResponse};
+use registry::{schema::enums::Permission, types::datetime::UTCDateTime};
+use serde::Serialize;
+use serde_json::json;
+use std::{future::Future, net::IpAddr, str::FromStr};
+use store::write::now;
+use trc::AddContext;
+use types::id::Id;
+use utils::url_params::UrlParams;
+
+pub trait ActiveSessionManagement: Sync + Send {
+    fn handle_list_sessions(
+        &self,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_terminate_sessions(
+        &self,
+        session_id: Option<&str>,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionInfo {
+    session_id: Id,
+    protocol: &'static str,
+    listener: String,
+    remote_ip: IpAddr,
+    remote_port: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_id: Option<Id>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_name: Option<String>,
+    started: UTCDateTime,
+    duration: u64,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    command: String,
+}
+
+impl ActiveSessionManagement for Server {
+    async fn handle_list_sessions(
+        &self,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(Permission::LiveMetrics)?;
+
+        let params = UrlParams::new(req.uri().query());
+        let account_id = parse_account_id(&params)?;
+        let protocol = params.get("protocol");
+        let tenant_id = access_token.tenant_id();
+
+        let mut sessions = self.inner.data.active_sessions.list();
+        sessions.sort_unstable_by_key(|session| (session.started, session.session_id));
+
+        let now = now();
+        let mut results = Vec::with_capacity(sessions.len());
+        for session in sessions {
+            let state = session.state();
+            if account_id.is_some_and(|account_id| state.account_id != Some(account_id))
+                || protocol.is_some_and(|protocol| session.protocol.as_str() != protocol)
+                || tenant_id.is_some_and(|tenant_id| state.tenant_id != Some(tenant_id))
+            {
+                continue;
+            }
+
+            let account_name = if let Some(account_id) = state.account_id {
+                self.account(account_id)
+                    .await
+                    .ok()
+                    .map(|account| account.name.to_string())
+            } else {
+                None
+            };
+
+            results.push(SessionInfo {
+                session_id: Id::from(session.session_id),
+                protocol: session.protocol.as_str(),
+                listener: session.listener_id.clone(),
+                remote_ip: session.remote_ip,
+                remote_port: session.remote_port,
+                account_id: state.account_id.map(Id::from),
+                account_name,
+                started: UTCDateTime::from_timestamp(session.started as i64),
+                duration: now.saturating_sub(session.started),
+                command: state.command,
+            });
+        }
+
+        Ok(JsonResponse::new(results).no_cache().into_http_response())
+    }
+
+    async fn handle_terminate_sessions(
+        &self,
+        session_id: Option<&str>,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(Permission::SysAccountUpdate)?;
+
+        let tenant_id = access_token.tenant_id();
+        let is_visible = |session: &ActiveSession| {
+            tenant_id.is_none_or(|tenant_id| session.state().tenant_id == Some(tenant_id))
+        };
+
+        let terminated = if let Some(session_id) = session_id {
+            // Terminate a single session running on this node
+            let session_id = Id::from_str(session_id).map_err(|_| {
+                trc::ResourceEvent::BadParameters
+                    .into_err()
+                    .details("Invalid session id")
+            })?;
+            let terminated =
+                self.inner.data.active_sessions.terminate(|session| {
+                    session.session_id == session_id.id() && is_visible(session)
+                });
+            if terminated == 0 {
+                return Err(trc::ResourceEvent::NotFound.into_err());
+            }
+            terminated
+        } else if let Some(account_id) = parse_account_id(&UrlParams::new(req.uri().query()))? {
+            // Terminate all sessions of an account across the cluster
+            let account = self.account(account_id).await.caused_by(trc::location!())?;
+            if tenant_id.is_some_and(|tenant_id| account.id_tenant != Some(tenant_id)) {
+                return Err(trc::ResourceEvent::NotFound.into_err());
+            }
+
+            self.cluster_broadcast(BroadcastEvent::TerminateSessions { account_id })
+                .await;
+            self.inner
+                .data
+                .active_sessions
+                .terminate(|session| session.account_id() == Some(account_id))
+        } else {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Missing session id or accountId parameter"));
+        };
+
+        Ok(JsonResponse::new(json!({
+            "terminated": terminated,
+        }))
+        .no_cache()
+        .into_http_response())
+    }
+}
+
+fn parse_account_id(params: &UrlParams<'_>) -> trc::Result<Option<u32>> {
+    params
+        .get("accountId")
+        .map(|account_id| {
+            Id::from_str(account_id)
+                .map(|id| id.document_id())
+                .map_err(|_| {
+                    trc::ResourceEvent::BadParameters
+                        .into_err()
+                        .details("Invalid account id")
+                })
+        })
+        .transpose()
+}
@@ -0,0 +1,270 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{
+    Server,
+    auth::AccessToken,
+    cache::reload::{SettingChange, SettingChangeType},
+    ipc::{BroadcastEvent, RegistryChange, ReloadSubsystem},
+};
+use http_proto::{HttpResponse, HttpSessionData, JsonResponse, ToHttpResponse};
+use jmap::registry::set::RegistrySet;
+use jmap_proto::{
+    method::set::{SetRequest, SetResponse},
+    object::registry::Registry,
+};
+use registry::{
+    schema::{enums::Permission, prelude::ObjectType},
+    types::{
+        EnumImpl,
+        error::{Error, ValidationError, Warning},
+        id::ObjectId,
+    },
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use store::registry::RegistryOverlay;
+use types::id::Id;
+
+pub trait SettingsManagement: Sync + Send {
+    fn handle_settings_validate(
+        &self,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_settings_reload(
+        &self,
+        subsystem: &str,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Serialize)]
+struct ValidateResponse {
+    valid: bool,
+    objects: Map<String, Value>,
+    errors: Vec<ValidateIssue>,
+    warnings: Vec<ValidateIssue>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReloadResponse {
+    subsystem: &'static str,
+    applied: bool,
+    changes: Vec<ReloadChange>,
+    errors: Vec<ValidateIssue>,
+    warnings: Vec<ValidateIssue>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReloadChange {
+    #[serde(rename = "objectType")]
+    object_type: &'static str,
+    id: Id,
+    change: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ValidateIssue {
+    #[serde(rename = "objectType")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    object_type: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<Id>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    property: Option<&'static str>,
+    message: String,
+    #[serde(rename = "validationErrors")]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    validation_errors: Vec<ValidationError>,
+}
+
+impl SettingsManagement for Server {
+    async fn handle_settings_validate(
+        &self,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(Permission::ActionReloadSettings)?;
+
+        let changes =
+            serde_json::from_slice::<Map<String, Value>>(body.as_deref().unwrap_or_default())
+                .map_err(|err| {
+                    trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+                })?;
+
+        // Validate each object and collect the accepted changes
+        let mut overlay = RegistryOverlay::default();
+        let mut response = ValidateResponse {
+            valid: true,
+            objects: Map::new(),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        };
+        for (name, arguments) in changes {
+            let object_type = ObjectType::parse(name.strip_prefix("x:").unwrap_or(&name))
+                .ok_or_else(|| {
+                    trc::ResourceEvent::BadParameters
+                        .into_err()
+                        .details(format!("Unknown object type {name:?}"))
+                })?;
+
+            let request = arguments.to_string();
+            let mut request =
+                serde_json::from_str::<SetRequest<'_, Registry>>(&request).map_err(|err| {
+                    trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+                })?;
+            request.account_id = Id::from(access_token.account_id());
+
+            // Enforce the permissions required to apply these changes
+            let [create, update, destroy] = object_type.set_permission();
+            for (permission, has_changes) in [
+                (
+                    create,
+                    request.create.as_ref().is_some_and(|c| !c.is_empty()),
+                ),
+                (
+                    update,
+                    request.update.as_ref().is_some_and(|u| !u.is_empty()),
+                ),
+                (destroy, request.destroy.is_some()),
+            ] {
+                if has_changes {
+                    access_token.enforce_permission(permission)?;
+                }
+            }
+
+            let result = Box::pin(self.registry_set(
+                object_type,
+                request,
+                access_token,
+                session,
+                Some(&mut overlay),
+            ))
+            .await?;
+            response.valid &= !has_failures(&result);
+            response.objects.insert(
+                format!("x:{}", object_type.as_str()),
+                serde_json::to_value(&result).unwrap_or_default(),
+            );
+        }
+
+        // Parse the full configuration with the changes applied
+        let result = self.validate_registry(overlay).await;
+        response.valid &= !result.has_errors();
+        response.errors = result.errors.into_iter().map(ValidateIssue::from).collect();
+        response.warnings = result
+            .warnings
+            .into_iter()
+            .map(ValidateIssue::from)
+            .collect();
+
+        Ok(JsonResponse::new(response).no_cache().into_http_response())
+    }
+
+    async fn handle_settings_reload(
+        &self,
+        subsystem: &str,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(Permission::ActionReloadSettings)?;
+
+        let subsystem = ReloadSubsystem::parse(subsystem).ok_or_else(|| {
+            trc::ResourceEvent::NotFound
+                .into_err()
+                .details(format!("Unknown subsystem {subsystem:?}"))
+        })?;
+        let result = self.reload_subsystem(subsystem).await;
+        let applied = !result.has_errors();
+        if applied {
+            self.cluster_broadcast(BroadcastEvent::RegistryChange(
+                RegistryChange::ReloadSubsystem(subsystem),
+            ))
+            .await;
+        }
+
+        Ok(JsonResponse::new(ReloadResponse {
+            subsystem: subsystem.as_str(),
+            applied,
+            changes: result.changes.into_iter().map(ReloadChange::from).collect(),
+            errors: result.errors.into_iter().map(ValidateIssue::from).collect(),
+            warnings: result
+                .warnings
+                .into_iter()
+                .map(ValidateIssue::from)
+                .collect(),
+        })
+        .no_cache()
+        .into_http_response())
+    }
+}
+
+pub(crate) fn has_failures(response: &SetResponse<Registry>) -> bool {
+    !response.not_created.is_empty()
+        || !response.not_updated.is_empty()
+        || !response.not_destroyed.is_empty()
+}
+
+impl ValidateIssue {
+    pub(crate) fn new(object_id: Option<ObjectId>, message: impl Into<String>) -> Self {
+        ValidateIssue {
+            object_type: object_id.map(|id| id.object().as_str()),
+            id: object_id.map(|id| id.id()),
+            property: None,
+            message: message.into(),
+            validation_errors: Vec::new(),
+        }
+    }
+}
+
+impl From<Error> for ValidateIssue {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::Validation { object_id, errors } => ValidateIssue {
+                message: errors
+                    .iter()
+                    .map(|err| err.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                validation_errors: errors,
+                ..ValidateIssue::new(Some(object_id), "")
+            },
+            Error::Build { object_id, message } => ValidateIssue::new(Some(object_id), message),
+            Error::Internal { object_id, error } => {
+                ValidateIssue::new(object_id, error.to_string())
+            }
+            Error::NotFound { object_id } => {
+                ValidateIssue::new(Some(object_id), "Object not found")
+            }
+        }
+    }
+}
+
+impl From<SettingChange> for ReloadChange {
+    fn from(change: SettingChange) -> Self {
+        ReloadChange {
+            object_type: change.object_id.object().as_str(),
+            id: change.object_id.id(),
+            change: match change.change {
+                SettingChangeType::Added => "added",
+                SettingChangeType::Modified => "modified",
+                SettingChangeType::Removed => "removed",
+            },
+        }
+    }
+}
+
+impl From<Warning> for ValidateIssue {
+    fn from(warning: Warning) -> Self {
+        ValidateIssue {
+            property: warning.property.map(|property| property.as_str()),
+            ..ValidateIssue::new(Some(warning.object_id), warning.message)
+        }
+    }
+}
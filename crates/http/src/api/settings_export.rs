@@ -0,0 +1,858 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::settings::{ValidateIssue, has_failures};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use common::{
+    Server,
+    auth::{AccessToken, oauth::crypto::SymmetricEncrypt},
+    cache::reload::reload_scope,
+    ipc::{BroadcastEvent, RegistryChange},
+};
+use http_proto::{HttpRequest, HttpResponse, HttpSessionData, JsonResponse, ToHttpResponse};
+use jmap::registry::set::RegistrySet;
+use jmap_proto::{method::set::SetRequest, object::registry::Registry};
+use registry::{
+    jmap::IntoValue,
+    schema::{
+        enums::Permission,
+        prelude::{MASKED_PASSWORD, OBJ_SINGLETON, Object, ObjectType},
+    },
+    types::EnumImpl,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use std::{collections::BTreeMap, future::Future, str::FromStr};
+use store::{
+    ahash::AHashMap,
+    rand::{Rng, rng},
+    registry::{RegistryOverlay, RegistryQuery},
+};
+use trc::AddContext;
+use types::id::Id;
+use utils::url_params::UrlParams;
+
+const DOCUMENT_VERSION: u32 = 1;
+const ENCRYPTION_ALGORITHM: &str = "aes-256-gcm-siv";
+const ENCRYPTED_SECRET: &str = "@encrypted";
+const KEY_CONTEXT: &str = "Stalwart settings export key";
+const SECRET_CONTEXT: &str = "Stalwart settings export secret";
+const DATA_KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const KDF_ALGORITHM: &str = "argon2id";
+// Upper bounds for the key derivation parameters of imported documents,
+// which would otherwise let a crafted document exhaust the server's memory.
+const KDF_MAX_MEMORY_COST: u32 = 256 * 1024;
+const KDF_MAX_TIME_COST: u32 = 16;
+const KDF_MAX_PARALLELISM: u32 = 16;
+
+// Object types holding server configuration, principals and user data are
+// managed through their own APIs and are not exported.
+const EXPORTED_OBJECTS: &[ObjectType] = &[
+    ObjectType::AcmeProvider,
+    ObjectType::AddressBook,
+    ObjectType::AiModel,
+    ObjectType::Alert,
+    ObjectType::AllowedIp,
+    ObjectType::Application,
+    ObjectType::Asn,
+    ObjectType::Authentication,
+    ObjectType::BlobStore,
+    ObjectType::Cache,
+    ObjectType::Calendar,
+    ObjectType::CalendarAlarm,
+    ObjectType::CalendarScheduling,
+    ObjectType::Certificate,
+    ObjectType::ClusterRole,
+    ObjectType::Coordinator,
+    ObjectType::DataRetention,
+    ObjectType::DataStore,
+    ObjectType::Directory,
+    ObjectType::DkimReportSettings,
+    ObjectType::DmarcReportSettings,
+    ObjectType::DnsResolver,
+    ObjectType::DnsServer,
+    ObjectType::DsnReportSettings,
+    ObjectType::Email,
+    ObjectType::Enterprise,
+    ObjectType::EventTracingLevel,
+    ObjectType::FileStorage,
+    ObjectType::Http,
+    ObjectType::HttpForm,
+    ObjectType::HttpLookup,
+    ObjectType::Imap,
+    ObjectType::InMemoryStore,
+    ObjectType::Jmap,
+    ObjectType::MemoryLookupKey,
+    ObjectType::MemoryLookupKeyValue,
+    ObjectType::Metrics,
+    ObjectType::MetricsStore,
+    ObjectType::MtaConnectionStrategy,
+    ObjectType::MtaDeliverySchedule,
+    ObjectType::MtaExtensions,
+    ObjectType::MtaHook,
+    ObjectType::MtaInboundSession,
+    ObjectType::MtaInboundThrottle,
+    ObjectType::MtaMilter,
+    ObjectType::MtaOutboundStrategy,
+    ObjectType::MtaOutboundThrottle,
+    ObjectType::MtaQueueQuota,
+    ObjectType::MtaRoute,
+    ObjectType::MtaStageAuth,
+    ObjectType::MtaStageConnect,
+    ObjectType::MtaStageData,
+    ObjectType::MtaStageEhlo,
+    ObjectType::MtaStageMail,
+    ObjectType::MtaStageRcpt,
+    ObjectType::MtaSts,
+    ObjectType::MtaTlsStrategy,
+    ObjectType::MtaVirtualQueue,
+    ObjectType::NetworkListener,
+    ObjectType::OidcProvider,
+    ObjectType::QueueStore,
+    ObjectType::ReportSettings,
+    ObjectType::Search,
+    ObjectType::SearchStore,
+    ObjectType::Security,
+    ObjectType::SenderAuth,
+    ObjectType::Sharing,
+    ObjectType::SieveSystemInterpreter,
+    ObjectType::SieveSystemScript,
+    ObjectType::SieveUserInterpreter,
+    ObjectType::SieveUserScript,
+    ObjectType::SpamClassifier,
+    ObjectType::SpamDnsblServer,
+    ObjectType::SpamDnsblSettings,
+    ObjectType::SpamFileExtension,
+    ObjectType::SpamLlm,
+    ObjectType::SpamPyzor,
+    ObjectType::SpamRspamd,
+    ObjectType::SpamRule,
+    ObjectType::SpamSettings,
+    ObjectType::SpamTag,
+    ObjectType::SpfReportSettings,
+    ObjectType::StoreLookup,
+    ObjectType::SystemSettings,
+    ObjectType::TaskManager,
+    ObjectType::TlsReportSettings,
+    ObjectType::Tracer,
+    ObjectType::TracingStore,
+    ObjectType::WebDav,
+    ObjectType::WebHook,
+];
+
+pub trait SettingsExportManagement: Sync + Send {
+    fn handle_settings_export(
+        &self,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn handle_settings_import(
+        &self,
+        req: &HttpRequest,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ExportRequest {
+    #[serde(rename = "encryptionKey")]
+    encryption_key: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportRequest {
+    settings: SettingsDocument,
+    #[serde(rename = "encryptionKey")]
+    #[serde(default)]
+    encryption_key: Option<String>,
+    #[serde(default)]
+    prune: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SettingsDocument {
+    version: u32,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    encryption: Option<DocumentEncryption>,
+    objects: BTreeMap<String, BTreeMap<String, Value>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DocumentEncryption {
+    algorithm: String,
+    kdf: KeyDerivation,
+    salt: String,
+    key: String,
+}
+
+// Parameters used to derive the key that wraps the data key from the
+// caller's encryption key
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct KeyDerivation {
+    algorithm: String,
+    memory_cost: u32,
+    time_cost: u32,
+    parallelism: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct ImportResponse {
+    valid: bool,
+    applied: bool,
+    changes: Vec<ImportChange>,
+    objects: Map<String, Value>,
+    errors: Vec<ValidateIssue>,
+    warnings: Vec<ValidateIssue>,
+}
+
+#[derive(Debug, Serialize)]
+struct ImportChange {
+    #[serde(rename = "objectType")]
+    object_type: &'static str,
+    id: Id,
+    change: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    properties: Vec<String>,
+}
+
+struct SecretCipher {
+    cipher: SymmetricEncrypt,
+}
+
+impl SettingsExportManagement for Server {
+    async fn handle_settings_export(
+        &self,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(Permission::ActionReloadSettings)?;
+
+        let request = match body.as_deref() {
+            Some(body) if !body.is_empty() => serde_json::from_slice::<ExportRequest>(body)
+                .map_err(|err| {
+                    trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+                })?,
+            _ => ExportRequest::default(),
+        };
+
+        // Secrets are redacted unless an encryption key is provided
+        let (cipher, encryption) = if let Some(encryption_key) = &request.encryption_key {
+            access_token.enforce_permission(Permission::ActionExportSettingsSecrets)?;
+            let (cipher, encryption) = SecretCipher::generate(encryption_key).await?;
+            (Some(cipher), Some(encryption))
+        } else {
+            (None, None)
+        };
+
+        let mut objects = BTreeMap::new();
+        for object_type in EXPORTED_OBJECTS {
+            if !access_token.has_permission(object_type.get_permission()) {
+                continue;
+            }
+
+            let mut exported = BTreeMap::new();
+            for (id, object) in self.settings_objects(*object_type, access_token).await? {
+                let mut value = masked_value(&object);
+                if let Some(cipher) = &cipher {
+                    let unmasked = unmasked_value(&object);
+                    visit_secrets(
+                        &mut value,
+                        Some(&unmasked),
+                        "",
+                        &mut |secret, unmasked, _| {
+                            if let Some(Value::String(unmasked)) = unmasked {
+                                *secret = cipher.seal(unmasked)?;
+                            }
+                            Ok(())
+                        },
+                    )
+                    .map_err(|err| trc::ResourceEvent::Error.into_err().details(err))?;
+                }
+                exported.insert(id.to_string(), value);
+            }
+
+            if !exported.is_empty() {
+                objects.insert(format!("x:{}", object_type.as_str()), exported);
+            }
+        }
+
+        Ok(JsonResponse::new(SettingsDocument {
+            version: DOCUMENT_VERSION,
+            encryption,
+            objects,
+        })
+        .no_cache()
+        .into_http_response())
+    }
+
+    async fn handle_settings_import(
+        &self,
+        req: &HttpRequest,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+        session: &HttpSessionData,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(Permission::ActionReloadSettings)?;
+
+        let dry_run = UrlParams::new(req.uri().query())
+            .parse::<bool>("dryRun")
+            .unwrap_or(false);
+        let request = serde_json::from_slice::<ImportRequest>(body.as_deref().unwrap_or_default())
+            .map_err(|err| {
+                trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+            })?;
+        if request.settings.version != DOCUMENT_VERSION {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details(format!(
+                    "Unsupported settings document version {}",
+                    request.settings.version
+                )));
+        }
+        let cipher = match (&request.settings.encryption, &request.encryption_key) {
+            (Some(encryption), Some(encryption_key)) => {
+                Some(SecretCipher::open(encryption_key, encryption).await?)
+            }
+            (Some(_), None) => {
+                return Err(trc::ResourceEvent::BadParameters
+                    .into_err()
+                    .details("An encryption key is required to import encrypted secrets"));
+            }
+            (None, _) => None,
+        };
+
+        // Compare the document against the stored settings
+        let mut response = ImportResponse {
+            valid: true,
+            applied: false,
+            changes: Vec::new(),
+            objects: Map::new(),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+        };
+        let mut requests = Vec::new();
+        for (name, objects) in request.settings.objects {
+            let object_type = ObjectType::parse(name.strip_prefix("x:").unwrap_or(&name))
+                .filter(|object_type| EXPORTED_OBJECTS.contains(object_type))
+                .ok_or_else(|| {
+                    trc::ResourceEvent::BadParameters
+                        .into_err()
+                        .details(format!("Object type {name:?} cannot be imported"))
+                })?;
+            let is_singleton = (object_type.flags() & OBJ_SINGLETON) != 0;
+            let mut current = self
+                .settings_objects(object_type, access_token)
+                .await?
+                .into_iter()
+                .collect::<AHashMap<_, _>>();
+
+            let mut create = Map::new();
+            let mut update = Map::new();
+            for (id, value) in objects {
+                let id = Id::from_str(&id)
+                    .ok()
+                    .filter(|id| !is_singleton || id.is_singleton())
+                    .ok_or_else(|| {
+                        trc::ResourceEvent::BadParameters
+                            .into_err()
+                            .details(format!("Invalid id {id:?} for object type {name:?}"))
+                    })?;
+                let Value::Object(mut properties) = value else {
+                    return Err(trc::ResourceEvent::BadParameters
+                        .into_err()
+                        .details(format!("Invalid {}", object_type.id(id))));
+                };
+                properties.remove("id");
+
+                // Restore redacted secrets from the stored object and decrypt the rest
+                let object = current.remove(&id);
+                let unmasked = object.as_ref().map(unmasked_value);
+                let mut restore = |secret: &mut Value, unmasked: Option<&Value>, ptr: &str| {
+                    if secret.is_string() {
+                        if let Some(unmasked @ Value::String(_)) = unmasked {
+                            *secret = unmasked.clone();
+                            Ok(())
+                        } else {
+                            Err(format!("Redacted secret at {ptr:?} has no stored value"))
+                        }
+                    } else if let Some(unsealed) =
+                        cipher.as_ref().and_then(|cipher| cipher.unseal(secret))
+                    {
+                        *secret = Value::String(unsealed);
+                        Ok(())
+                    } else {
+                        Err(format!("Failed to decrypt secret at {ptr:?}"))
+                    }
+                };
+                if let Err(err) = properties.iter_mut().try_for_each(|(property, value)| {
+                    visit_secrets(
+                        value,
+                        unmasked
+                            .as_ref()
+                            .and_then(|unmasked| unmasked.get(property.as_str())),
+                        &format!("/{property}"),
+                        &mut restore,
+                    )
+                }) {
+                    response.valid = false;
+                    response
+                        .errors
+                        .push(ValidateIssue::new(Some(object_type.id(id)), err));
+                    continue;
+                }
+
+                if let Some(object) = object {
+                    let mut stored = masked_value(&object);
+                    let _ = visit_secrets(
+                        &mut stored,
+                        unmasked.as_ref(),
+                        "",
+                        &mut |secret, unmasked, _| {
+                            if let Some(unmasked) = unmasked {
+                                *secret = unmasked.clone();
+                            }
+                            Ok(())
+                        },
+                    );
+
+                    let changed = properties
+                        .iter()
+                        .filter(|(property, value)| stored.get(property.as_str()) != Some(*value))
+                        .map(|(property, _)| property.clone())
+                        .collect::<Vec<_>>();
+                    if changed.is_empty() {
+                        continue;
+                    }
+
+                    // Changing the type of an object replaces all of its properties
+                    let patch = if changed.iter().any(|property| property == "@type") {
+                        properties
+                    } else {
+                        properties
+                            .into_iter()
+                            .filter(|(property, _)| changed.contains(property))
+                            .collect()
+                    };
+                    update.insert(id.to_string(), Value::Object(patch));
+                    response
+                        .changes
+                        .push(ImportChange::new(object_type, id, "modified", changed));
+                } else {
+                    create.insert(id.to_string(), Value::Object(properties));
+                    response
+                        .changes
+                        .push(ImportChange::new(object_type, id, "added", Vec::new()));
+                }
+            }
+
+            // Objects missing from the document are only removed when pruning
+            let mut destroy = Vec::new();
+            if request.prune && !is_singleton {
+                let mut removed = current.into_keys().collect::<Vec<_>>();
+                removed.sort_unstable();
+                for id in removed {
+                    destroy.push(id.to_string());
+                    response.changes.push(ImportChange::new(
+                        object_type,
+                        id,
+                        "removed",
+                        Vec::new(),
+                    ));
+                }
+            }
+
+            if create.is_empty() && update.is_empty() && destroy.is_empty() {
+                continue;
+            }
+
+            // Enforce the permissions required to apply these changes
+            let [create_permission, update_permission, destroy_permission] =
+                object_type.set_permission();
+            for (permission, has_changes) in [
+                (create_permission, !create.is_empty()),
+                (update_permission, !update.is_empty()),
+                (destroy_permission, !destroy.is_empty()),
+            ] {
+                if has_changes {
+                    access_token.enforce_permission(permission)?;
+                }
+            }
+
+            requests.push((
+                object_type,
+                json!({
+                    "create": create,
+                    "update": update,
+                    "destroy": destroy,
+                })
+                .to_string(),
+            ));
+        }
+
+        // Validate the changes without saving them
+        let mut overlay = RegistryOverlay::default();
+        for (object_type, request) in &requests {
+            let result = Box::pin(self.registry_set(
+                *object_type,
+                parse_set_request(request, access_token)?,
+                access_token,
+                session,
+                Some(&mut overlay),
+            ))
+            .await?;
+            response.valid &= !has_failures(&result);
+            response.objects.insert(
+                format!("x:{}", object_type.as_str()),
+                serde_json::to_value(&result).unwrap_or_default(),
+            );
+        }
+        let result = self.validate_registry(overlay).await;
+        response.valid &= !result.has_errors();
+        response
+            .errors
+            .extend(result.errors.into_iter().map(ValidateIssue::from));
+        response
+            .warnings
+            .extend(result.warnings.into_iter().map(ValidateIssue::from));
+
+        if dry_run || !response.valid || requests.is_empty() {
+            return Ok(JsonResponse::new(response).no_cache().into_http_response());
+        }
+
+        // Apply the changes and reload the settings
+        for (object_type, request) in &requests {
+            let result = Box::pin(self.registry_set(
+                *object_type,
+                parse_set_request(request, access_token)?,
+                access_token,
+                session,
+                None,
+            ))
+            .await?;
+            response.valid &= !has_failures(&result);
+            response.objects.insert(
+                format!("x:{}", object_type.as_str()),
+                serde_json::to_value(&result).unwrap_or_default(),
+            );
+        }
+        response.applied = true;
+
+        // Reload every subsystem holding imported objects, each one only once
+        let mut reloads = Vec::new();
+        for (object_type, _) in &requests {
+            let object_type = reload_scope(*object_type);
+            if !reloads.contains(&object_type) {
+                reloads.push(object_type);
+            }
+        }
+        for object_type in reloads {
+            let result = Box::pin(self.reload_registry(RegistryChange::Reload(object_type)))
+                .await
+                .caused_by(trc::location!())?;
+            if !result.has_errors() {
+                self.cluster_broadcast(BroadcastEvent::RegistryChange(RegistryChange::Reload(
+                    object_type,
+                )))
+                .await;
+            }
+            response
+                .errors
+                .extend(result.errors.into_iter().map(ValidateIssue::from));
+            response
+                .warnings
+                .extend(result.warnings.into_iter().map(ValidateIssue::from));
+        }
+
+        Ok(JsonResponse::new(response).no_cache().into_http_response())
+    }
+}
+
+trait SettingsObjects: Sync + Send {
+    fn settings_objects(
+        &self,
+        object_type: ObjectType,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<Vec<(Id, Object)>>> + Send;
+}
+
+impl SettingsObjects for Server {
+    // Returns the stored objects of a type, singletons that were never
+    // modified are returned with their default values.
+    async fn settings_objects(
+        &self,
+        object_type: ObjectType,
+        access_token: &AccessToken,
+    ) -> trc::Result<Vec<(Id, Object)>> {
+        if (object_type.flags() & OBJ_SINGLETON) != 0 {
+            let object = self
+                .registry()
+                .get(object_type.singleton())
+                .await
+                .caused_by(trc::location!())?
+                .unwrap_or_else(|| Object::from(object_type));
+            return Ok(vec![(Id::singleton(), object)]);
+        }
+
+        let ids = self
+            .registry()
+            .query::<Vec<Id>>(RegistryQuery::new(object_type).with_tenant(access_token.tenant_id()))
+            .await
+            .caused_by(trc::location!())?;
+        let mut objects = Vec::with_capacity(ids.len());
+        for id in ids {
+            if let Some(object) = self
+                .registry()
+                .get(object_type.id(id))
+                .await
+                .caused_by(trc::location!())?
+            {
+                objects.push((id, object));
+            }
+        }
+
+        Ok(objects)
+    }
+}
+
+impl SecretCipher {
+    // Generates a random data key for the secrets of a document, which is
+    // stored in the document wrapped with the caller's encryption key.
+    async fn generate(encryption_key: &str) -> trc::Result<(Self, DocumentEncryption)> {
+        if encryption_key.is_empty() {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("The encryption key cannot be empty"));
+        }
+
+        let data_key = rng().random::<[u8; DATA_KEY_LEN]>();
+        let salt = rng().random::<[u8; SALT_LEN]>();
+        let kdf = KeyDerivation {
+            algorithm: KDF_ALGORITHM.to_string(),
+            memory_cost: Params::DEFAULT_M_COST,
+            time_cost: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        };
+        let wrapped_key = seal_bytes(&key_cipher(encryption_key, &salt, &kdf).await?, &data_key)?;
+
+        Ok((
+            SecretCipher {
+                cipher: SymmetricEncrypt::new(&data_key, SECRET_CONTEXT),
+            },
+            DocumentEncryption {
+                algorithm: ENCRYPTION_ALGORITHM.to_string(),
+                kdf,
+                salt: STANDARD.encode(salt),
+                key: STANDARD.encode(wrapped_key),
+            },
+        ))
+    }
+
+    async fn open(encryption_key: &str, encryption: &DocumentEncryption) -> trc::Result<Self> {
+        if encryption.algorithm != ENCRYPTION_ALGORITHM {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details(format!(
+                    "Unsupported encryption algorithm {:?}",
+                    encryption.algorithm
+                )));
+        }
+
+        let invalid_key = || {
+            trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Invalid encryption key")
+        };
+        let (salt, wrapped_key) = STANDARD
+            .decode(&encryption.salt)
+            .ok()
+            .zip(STANDARD.decode(&encryption.key).ok())
+            .ok_or_else(invalid_key)?;
+
+        open_bytes(
+            &key_cipher(encryption_key, &salt, &encryption.kdf).await?,
+            &wrapped_key,
+        )
+        .filter(|data_key| data_key.len() == DATA_KEY_LEN)
+        .map(|data_key| SecretCipher {
+            cipher: SymmetricEncrypt::new(&data_key, SECRET_CONTEXT),
+        })
+        .ok_or_else(invalid_key)
+    }
+
+    fn seal(&self, secret: &str) -> Result<Value, String> {
+        seal_bytes(&self.cipher, secret.as_bytes())
+            .map(|sealed| json!({ ENCRYPTED_SECRET: STANDARD.encode(sealed) }))
+            .map_err(|_| "Failed to encrypt secret".to_string())
+    }
+
+    fn unseal(&self, sealed: &Value) -> Option<String> {
+        let sealed = STANDARD
+            .decode(sealed.get(ENCRYPTED_SECRET)?.as_str()?)
+            .ok()?;
+        String::from_utf8(open_bytes(&self.cipher, &sealed)?).ok()
+    }
+}
+
+// Derives the key wrapping the data key with Argon2, which runs on a blocking
+// thread as it is deliberately slow
+async fn key_cipher(
+    encryption_key: &str,
+    salt: &[u8],
+    kdf: &KeyDerivation,
+) -> trc::Result<SymmetricEncrypt> {
+    if kdf.algorithm != KDF_ALGORITHM {
+        return Err(trc::ResourceEvent::BadParameters
+            .into_err()
+            .details(format!(
+                "Unsupported key derivation algorithm {:?}",
+                kdf.algorithm
+            )));
+    } else if kdf.memory_cost > KDF_MAX_MEMORY_COST
+        || kdf.time_cost > KDF_MAX_TIME_COST
+        || kdf.parallelism > KDF_MAX_PARALLELISM
+    {
+        return Err(trc::ResourceEvent::BadParameters
+            .into_err()
+            .details("Key derivation parameters exceed the allowed limits"));
+    }
+
+    let params = Params::new(
+        kdf.memory_cost,
+        kdf.time_cost,
+        kdf.parallelism,
+        Some(DATA_KEY_LEN),
+    )
+    .map_err(|err| {
+        trc::ResourceEvent::BadParameters
+            .into_err()
+            .details("Invalid key derivation parameters")
+            .reason(err)
+    })?;
+    let encryption_key = encryption_key.as_bytes().to_vec();
+    let salt = salt.to_vec();
+
+    tokio::task::spawn_blocking(move || {
+        let mut key = [0u8; DATA_KEY_LEN];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(&encryption_key, &salt, &mut key)
+            .map(|_| SymmetricEncrypt::new(&key, KEY_CONTEXT))
+    })
+    .await
+    .map_err(|err| {
+        trc::EventType::Server(trc::ServerEvent::ThreadError)
+            .caused_by(trc::location!())
+            .reason(err)
+    })?
+    .map_err(|err| {
+        trc::ResourceEvent::BadParameters
+            .into_err()
+            .details("Invalid key derivation parameters")
+            .reason(err)
+    })
+}
+
+// Encrypts the bytes and prepends the random nonce used
+fn seal_bytes(cipher: &SymmetricEncrypt, bytes: &[u8]) -> trc::Result<Vec<u8>> {
+    let nonce = rng().random::<[u8; SymmetricEncrypt::NONCE_LEN]>();
+    let mut sealed = nonce.to_vec();
+    sealed.extend(cipher.encrypt(bytes, &nonce).map_err(|err| {
+        trc::ResourceEvent::Error
+            .into_err()
+            .ctx(trc::Key::Reason, err)
+            .caused_by(trc::location!())
+    })?);
+    Ok(sealed)
+}
+
+fn open_bytes(cipher: &SymmetricEncrypt, sealed: &[u8]) -> Option<Vec<u8>> {
+    if sealed.len() > SymmetricEncrypt::NONCE_LEN {
+        let (nonce, bytes) = sealed.split_at(SymmetricEncrypt::NONCE_LEN);
+        cipher.decrypt(bytes, nonce).ok()
+    } else {
+        None
+    }
+}
+
+// JMAP representation of the object, with its secrets masked
+fn masked_value(object: &Object) -> Value {
+    serde_json::to_value(object.clone().into_value()).unwrap_or_default()
+}
+
+// Serialized object, which includes the value of its secrets
+fn unmasked_value(object: &Object) -> Value {
+    serde_json::to_value(&object.inner).unwrap_or_default()
+}
+
+// Calls the visitor for every masked or encrypted secret of an object,
+// together with the value found at the same location of the unmasked object.
+fn visit_secrets<F>(
+    value: &mut Value,
+    unmasked: Option<&Value>,
+    ptr: &str,
+    visitor: &mut F,
+) -> Result<(), String>
+where
+    F: FnMut(&mut Value, Option<&Value>, &str) -> Result<(), String>,
+{
+    if value.as_str() == Some(MASKED_PASSWORD) || value.get(ENCRYPTED_SECRET).is_some() {
+        return visitor(value, unmasked, ptr);
+    }
+
+    match value {
+        Value::Object(properties) => {
+            for (property, value) in properties {
+                visit_secrets(
+                    value,
+                    unmasked.and_then(|unmasked| unmasked.get(property.as_str())),
+                    &format!("{ptr}/{property}"),
+                    visitor,
+                )?;
+            }
+        }
+        Value::Array(items) => {
+            for (idx, value) in items.iter_mut().enumerate() {
+                visit_secrets(
+                    value,
+                    unmasked.and_then(|unmasked| unmasked.get(idx)),
+                    &format!("{ptr}/{idx}"),
+                    visitor,
+                )?;
+            }
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+fn parse_set_request<'x>(
+    request: &'x str,
+    access_token: &AccessToken,
+) -> trc::Result<SetRequest<'x, Registry>> {
+    let mut request = serde_json::from_str::<SetRequest<'_, Registry>>(request).map_err(|err| {
+        trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+    })?;
+    request.account_id = Id::from(access_token.account_id());
+    Ok(request)
+}
+
+impl ImportChange {
+    fn new(object_type: ObjectType, id: Id, change: &'static str, properties: Vec<String>) -> Self {
+        ImportChange {
+            object_type: object_type.as_str(),
+            id,
+            change,
+            properties,
+        }
+    }
+}
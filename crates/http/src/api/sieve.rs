@@ -0,0 +1,337 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::api::mailbox_stats::authorize_account;
+use common::{Server, auth::AccessToken};
+use email::{
+    cache::{MessageCacheFetch, mailbox::MailboxCacheAccess},
+    sieve::ingest::SieveScriptIngest,
+};
+use http_proto::{HttpResponse, JsonResponse, ToHttpResponse};
+use mail_parser::{Message, MessageParser};
+use registry::schema::enums::Permission;
+use serde::{Deserialize, Serialize};
+use sieve::{Envelope, Event, Input, Mailbox, Recipient, Sieve, SpamStatus};
+use std::{future::Future, str::FromStr, sync::Arc};
+use trc::AddContext;
+use types::{id::Id, special_use::SpecialUse};
+
+pub trait SieveTestManagement: Sync + Send {
+    fn handle_sieve_test(
+        &self,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SieveTestRequest {
+    script: String,
+    message: String,
+    #[serde(default)]
+    envelope: SieveTestEnvelope,
+    #[serde(default)]
+    account_id: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SieveTestEnvelope {
+    #[serde(default)]
+    pub from: String,
+    #[serde(default)]
+    pub to: Option<String>,
+    #[serde(default)]
+    pub is_spam: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SieveTestResponse {
+    actions: Vec<SieveTestAction>,
+    implicit_keep: bool,
+    headers_added: Vec<String>,
+    headers_removed: Vec<String>,
+    errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub(crate) enum SieveTestAction {
+    Keep {
+        flags: Vec<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    FileInto {
+        mailbox: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        mailbox_id: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        special_use: Option<String>,
+        create: bool,
+        flags: Vec<String>,
+    },
+    Discard,
+    Reject {
+        reason: String,
+    },
+    Redirect {
+        recipients: Vec<String>,
+    },
+    Send {
+        recipients: Vec<String>,
+        message: String,
+    },
+    #[serde(rename_all = "camelCase")]
+    SetEnvelope {
+        envelope: String,
+        value: String,
+    },
+    Unsupported {
+        details: String,
+    },
+}
+
+impl SieveTestManagement for Server {
+    async fn handle_sieve_test(
+        &self,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(Permission::SieveCheckScript)?;
+
+        let request =
+            serde_json::from_slice::<SieveTestRequest>(body.as_deref().unwrap_or_default())
+                .map_err(|err| {
+                    trc::EventType::Resource(trc::ResourceEvent::BadParameters).from_json_error(err)
+                })?;
+        let account_id = match &request.account_id {
+            Some(account_id) => authorize_account(self, account_id, access_token).await?,
+            None => access_token.account_id(),
+        };
+
+        let script = self
+            .core
+            .sieve
+            .untrusted_compiler
+            .compile(request.script.as_bytes())
+            .map_err(|err| {
+                trc::ResourceEvent::BadParameters
+                    .into_err()
+                    .details("Failed to compile Sieve script")
+                    .reason(err)
+            })?;
+        let Some(message) = MessageParser::new().parse(request.message.as_bytes()) else {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Failed to parse message"));
+        };
+
+        sieve_dry_run(
+            self,
+            account_id,
+            "test",
+            script.into(),
+            message,
+            &request.envelope,
+        )
+        .await
+        .map(|response| JsonResponse::new(response).no_cache().into_http_response())
+    }
+}
+
+pub(crate) async fn sieve_dry_run(
+    server: &Server,
+    account_id: u32,
+    script_name: &str,
+    script: Arc<Sieve>,
+    message: Message<'_>,
+    envelope: &SieveTestEnvelope,
+) -> trc::Result<SieveTestResponse> {
+    let original_headers = raw_headers(&message);
+
+    let cache = server
+        .get_cached_messages(account_id)
+        .await
+        .caused_by(trc::location!())?;
+    let account = server
+        .account(account_id)
+        .await
+        .caused_by(trc::location!())?;
+    let mut instance = server.core.sieve.untrusted_runtime.filter_parsed(message);
+    instance.set_user_full_name(account.description().unwrap_or_else(|| account.name()));
+    instance.set_user_address(account.name());
+    instance.set_envelope(Envelope::From, envelope.from.as_str());
+    instance.set_envelope(
+        Envelope::To,
+        envelope.to.as_deref().unwrap_or_else(|| account.name()),
+    );
+    instance.set_spam_status(if envelope.is_spam {
+        SpamStatus::Spam
+    } else {
+        SpamStatus::Ham
+    });
+
+    // Events are recorded instead of acted upon, so nothing is delivered,
+    // sent or stored while the script runs
+    let mut input = Input::script(script_name, script);
+    let mut response = SieveTestResponse::default();
+    let mut messages: Vec<Vec<u8>> = Vec::new();
+    let mut has_keep = false;
+    let mut has_discard = false;
+
+    while let Some(event) = instance.run(input) {
+        input = true.into();
+
+        match event {
+            Ok(Event::IncludeScript { name, .. }) => {
+                input = match &name {
+                    sieve::Script::Personal(name_) => {
+                        match server
+                            .sieve_script_get_by_name(account_id, name_)
+                            .await
+                            .caused_by(trc::location!())?
+                        {
+                            Some(script) => Input::script(name, script),
+                            None => false.into(),
+                        }
+                    }
+                    sieve::Script::Global(name_) => {
+                        match server.get_untrusted_sieve_script(&name_.to_lowercase(), 0) {
+                            Some(script) => Input::script(name, script),
+                            None => false.into(),
+                        }
+                    }
+                };
+            }
+            Ok(Event::MailboxExists {
+                mailboxes,
+                special_use,
+            }) => {
+                let roles_exist = special_use.iter().all(|role| {
+                    SpecialUse::parse(role)
+                        .is_some_and(|role| cache.mailbox_by_role(&role).is_some())
+                });
+                let mailboxes_exist = mailboxes.iter().all(|mailbox| match mailbox {
+                    Mailbox::Name(name) => cache.mailbox_by_path(name).is_some(),
+                    Mailbox::Id(id) => {
+                        Id::from_str(id).is_ok_and(|id| cache.has_mailbox_id(&id.document_id()))
+                    }
+                });
+                input = (roles_exist && mailboxes_exist).into();
+            }
+            Ok(Event::DuplicateId { .. }) => {
+                input = false.into();
+            }
+            Ok(Event::Keep { flags, .. }) => {
+                has_keep = true;
+                response.actions.push(SieveTestAction::Keep { flags });
+            }
+            Ok(Event::FileInto {
+                folder,
+                flags,
+                mailbox_id,
+                special_use,
+                create,
+                ..
+            }) => {
+                has_keep = true;
+                response.actions.push(SieveTestAction::FileInto {
+                    mailbox: folder,
+                    mailbox_id,
+                    special_use,
+                    create,
+                    flags,
+                });
+            }
+            Ok(Event::Discard) => {
+                has_discard = true;
+                response.actions.push(SieveTestAction::Discard);
+            }
+            Ok(Event::Reject { reason, .. }) => {
+                has_discard = true;
+                response.actions.push(SieveTestAction::Reject { reason });
+            }
+            Ok(Event::SendMessage {
+                recipient,
+                message_id,
+                ..
+            }) => {
+                let recipients = match recipient {
+                    Recipient::Address(rcpt) => vec![rcpt],
+                    Recipient::Group(rcpts) => rcpts,
+                    Recipient::List(list) => vec![list],
+                };
+                response.actions.push(if message_id == 0 {
+                    SieveTestAction::Redirect { recipients }
+                } else {
+                    SieveTestAction::Send {
+                        recipients,
+                        message: messages
+                            .get(message_id - 1)
+                            .map(|message| String::from_utf8_lossy(message).into_owned())
+                            .unwrap_or_default(),
+                    }
+                });
+            }
+            Ok(Event::CreatedMessage { message, .. }) => {
+                messages.push(message);
+            }
+            Ok(Event::SetEnvelope { envelope, value }) => {
+                response.actions.push(SieveTestAction::SetEnvelope {
+                    envelope: format!("{envelope:?}").to_lowercase(),
+                    value,
+                });
+            }
+            Ok(Event::ListContains { .. }) => {
+                input = false.into();
+            }
+            Ok(event @ (Event::Notify { .. } | Event::Function { .. })) => {
+                response.actions.push(SieveTestAction::Unsupported {
+                    details: format!("{event:?}"),
+                });
+                input = false.into();
+            }
+            Err(err) => {
+                response.errors.push(err.to_string());
+            }
+        }
+    }
+
+    // Same fail-safe as delivery, a script that neither keeps
+    // nor discards results in the message being filed
+    response.implicit_keep = !has_keep && !has_discard;
+
+    if instance.has_message_changed() {
+        let final_headers = raw_headers(instance.message());
+        response.headers_added = final_headers
+            .iter()
+            .filter(|header| !original_headers.contains(header))
+            .cloned()
+            .collect();
+        response.headers_removed = original_headers
+            .iter()
+            .filter(|header| !final_headers.contains(header))
+            .cloned()
+            .collect();
+    }
+
+    Ok(response)
+}
+
+fn raw_headers(message: &Message<'_>) -> Vec<String> {
+    let raw_message = message.raw_message();
+    message
+        .headers()
+        .iter()
+        .filter_map(|header| {
+            raw_message
+                .get(header.offset_field as usize..header.offset_end as usize)
+                .map(|raw| String::from_utf8_lossy(raw).trim_end().to_string())
+        })
+        .collect()
+}
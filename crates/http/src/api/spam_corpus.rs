@@ -0,0 +1,51 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{Server, auth::AccessToken};
+use http_proto::{HttpRequest, HttpResponse, JsonResponse, ToHttpResponse};
+use registry::schema::enums::Permission;
+use spam_filter::analysis::corpus::{SpamCorpusReport, SpamFilterAnalyzeCorpus};
+use std::future::Future;
+use utils::url_params::UrlParams;
+
+pub trait SpamCorpusManagement: Sync + Send {
+    fn handle_spam_corpus_test(
+        &self,
+        req: &HttpRequest,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+}
+
+impl SpamCorpusManagement for Server {
+    async fn handle_spam_corpus_test(
+        &self,
+        req: &HttpRequest,
+        body: Option<Vec<u8>>,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        access_token.enforce_permission(Permission::ActionClassifySpam)?;
+
+        // Messages without their own label header take the label of the upload
+        let label = match UrlParams::new(req.uri().query()).get("label") {
+            Some("spam") => Some(true),
+            Some("ham") => Some(false),
+            None => None,
+            Some(_) => {
+                return Err(trc::ResourceEvent::BadParameters
+                    .into_err()
+                    .details("Invalid corpus label, expected 'ham' or 'spam'"));
+            }
+        };
+        let mbox = body.ok_or_else(|| trc::LimitEvent::SizeRequest.into_err())?;
+
+        let mut report = SpamCorpusReport::default();
+        self.spam_filter_analyze_corpus(&mut report, &mbox, label)
+            .await;
+
+        Ok(JsonResponse::new(report).no_cache().into_http_response())
+    }
+}
@@ -0,0 +1,456 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: LicenseRef-SEL
+ *
+ * This file is subject to the Stalwart Enterprise License Agreement (SEL) and
+ * is NOT open source software.
+ *
+ */
+
+use common::{Server, auth::AccessToken, config::smtp::queue::QueueName};
+use http_proto::{HttpRequest, HttpResponse, JsonResponse, ToHttpResponse};
+use registry::{
+    schema::structs::{Trace, TraceKeyValue, TraceValue, TraceValueUnsignedInt},
+    types::datetime::UTCDateTime,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use smtp::queue::{Recipient, Status, spool::SmtpSpool};
+use std::{collections::BTreeSet, future::Future};
+use store::{
+    ValueKey,
+    search::{SearchFilter, SearchQuery, TracingSearchField},
+    write::{SearchIndex, TelemetryClass, ValueClass},
+};
+use trc::{AddContext, DeliveryEvent, EventType, Key, MessageIngestEvent, QueueEvent, SmtpEvent};
+use types::id::Id;
+use utils::{DomainPart, url_params::UrlParams};
+
+pub trait MessageTraceApi: Sync + Send {
+    fn handle_message_trace_request(
+        &self,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
+
+    fn message_trace(
+        &self,
+        params: &UrlParams<'_>,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<MessageTrace>> + Send;
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageTrace {
+    pub queue_ids: Vec<String>,
+    pub trace_ids: Vec<Id>,
+    pub lifecycle: Vec<LifecycleEntry>,
+    pub queue: Vec<QueueEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LifecycleEntry {
+    pub stage: LifecycleStage,
+    pub timestamp: String,
+    pub trace_id: Id,
+    pub event: String,
+    pub details: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LifecycleStage {
+    Received,
+    Filtered,
+    Queued,
+    Retry,
+    Attempt,
+    Response,
+    Delivered,
+    Failed,
+    Bounced,
+}
+
+// Current queue state of a message that has not been fully delivered yet
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueEntry {
+    pub queue_id: String,
+    pub return_path: String,
+    pub created: UTCDateTime,
+    pub recipients: Vec<QueueRecipient>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueRecipient {
+    pub address: String,
+    pub queue: String,
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_retry: Option<UTCDateTime>,
+}
+
+impl MessageTraceApi for Server {
+    async fn handle_message_trace_request(
+        &self,
+        req: &HttpRequest,
+        access_token: &AccessToken,
+    ) -> trc::Result<HttpResponse> {
+        let params = UrlParams::new(req.uri().query());
+        self.message_trace(&params, access_token)
+            .await
+            .map(|trace| JsonResponse::new(trace).no_cache().into_http_response())
+    }
+
+    async fn message_trace(
+        &self,
+        params: &UrlParams<'_>,
+        access_token: &AccessToken,
+    ) -> trc::Result<MessageTrace> {
+        // Obtain the queue ids of the message
+        let tenant_id = access_token.tenant_id();
+        let mut queue_ids = BTreeSet::new();
+        if let Some(queue_id) = params.get("queueId") {
+            queue_ids.insert(
+                u64::from_str_radix(queue_id.trim_start_matches("0x"), 16).map_err(|_| {
+                    trc::ResourceEvent::BadParameters
+                        .into_err()
+                        .details("Invalid queue id")
+                })?,
+            );
+        } else if let Some(message_id) = params.get("messageId") {
+            let message_id = message_id
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>');
+            for trace_id in self
+                .search_store()
+                .query_global(SearchQuery::new(SearchIndex::Tracing).with_filter(
+                    SearchFilter::has_keyword(TracingSearchField::Keywords, message_id),
+                ))
+                .await
+                .caused_by(trc::location!())?
+            {
+                if let Some(trace) = fetch_trace(self, trace_id).await?
+                    && is_tenant_trace(self, &trace, tenant_id).await?
+                {
+                    for event in trace.events.values() {
+                        for kv in event.key_values.values() {
+                            if let TraceKeyValue {
+                                key: Key::QueueId,
+                                value: TraceValue::UnsignedInt(TraceValueUnsignedInt { value }),
+                            } = kv
+                            {
+                                queue_ids.insert(*value);
+                            }
+                        }
+                    }
+                }
+            }
+        } else {
+            return Err(trc::ResourceEvent::BadParameters
+                .into_err()
+                .details("Missing messageId or queueId parameter"));
+        }
+
+        // Fetch all traces involving these queue ids
+        let mut trace_ids = BTreeSet::new();
+        for queue_id in &queue_ids {
+            trace_ids.extend(
+                self.search_store()
+                    .query_global(
+                        SearchQuery::new(SearchIndex::Tracing)
+                            .with_filter(SearchFilter::eq(TracingSearchField::QueueId, *queue_id)),
+                    )
+                    .await
+                    .caused_by(trc::location!())?,
+            );
+        }
+
+        let mut response = MessageTrace {
+            queue_ids: Vec::with_capacity(queue_ids.len()),
+            trace_ids: Vec::with_capacity(trace_ids.len()),
+            lifecycle: Vec::new(),
+            queue: Vec::new(),
+        };
+        for trace_id in trace_ids.into_iter().take(self.core.jmap.get_max_objects) {
+            let Some(trace) = fetch_trace(self, trace_id).await? else {
+                continue;
+            };
+            if !is_tenant_trace(self, &trace, tenant_id).await? {
+                continue;
+            }
+            let trace_id = Id::from(trace_id);
+            response.trace_ids.push(trace_id);
+
+            for event in trace.events.values() {
+                if let Some(stage) = lifecycle_stage(event.event) {
+                    response.lifecycle.push(LifecycleEntry {
+                        stage,
+                        timestamp: event.timestamp.to_string(),
+                        trace_id,
+                        event: event.event.as_str().to_string(),
+                        details: key_values_to_json(event.key_values.values()),
+                    });
+                }
+            }
+        }
+
+        // Correlate the traces with the queue events still pending for the message
+        for queue_id in queue_ids {
+            let queue_entry = if let Some(message) =
+                self.read_message(queue_id, QueueName::default()).await
+                && (tenant_id.is_none()
+                    || is_tenant_address(self, &message.message.return_path, tenant_id).await?
+                    || is_tenant_recipient(self, &message.message.recipients, tenant_id).await?)
+            {
+                let message = message.message;
+                Some(QueueEntry {
+                    queue_id: format!("{queue_id:x}"),
+                    return_path: message.return_path.to_string(),
+                    created: UTCDateTime::from_timestamp(message.created as i64),
+                    recipients: message
+                        .recipients
+                        .iter()
+                        .map(|rcpt| QueueRecipient {
+                            address: rcpt.address().to_string(),
+                            queue: rcpt.queue.to_string(),
+                            status: rcpt.status.to_string(),
+                            next_retry: matches!(
+                                rcpt.status,
+                                Status::Scheduled | Status::TemporaryFailure(_)
+                            )
+                            .then(|| UTCDateTime::from_timestamp(rcpt.retry.due as i64)),
+                        })
+                        .collect(),
+                })
+            } else {
+                None
+            };
+
+            // Tenants only see the queue ids of their own messages
+            if tenant_id.is_none()
+                || queue_entry.is_some()
+                || response.lifecycle.iter().any(|entry| {
+                    entry.details.get(Key::QueueId.as_str()) == Some(&Value::from(queue_id))
+                })
+            {
+                response.queue_ids.push(format!("{queue_id:x}"));
+            }
+            response.queue.extend(queue_entry);
+        }
+
+        // Traces are ordered by id, keep that order for events logged within the same second
+        response
+            .lifecycle
+            .sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+
+        Ok(response)
+    }
+}
+
+async fn fetch_trace(server: &Server, trace_id: u64) -> trc::Result<Option<Trace>> {
+    server
+        .tracing_store()
+        .get_value::<Trace>(ValueKey::from(ValueClass::Telemetry(TelemetryClass::Span(
+            trace_id,
+        ))))
+        .await
+        .caused_by(trc::location!())
+}
+
+// Traces are visible to a tenant when any of the addresses or domains involved belong to it
+async fn is_tenant_trace(
+    server: &Server,
+    trace: &Trace,
+    tenant_id: Option<u32>,
+) -> trc::Result<bool> {
+    if tenant_id.is_none() {
+        return Ok(true);
+    }
+
+    for event in trace.events.values() {
+        for kv in event.key_values.values() {
+            let is_domain = match kv.key {
+                Key::From | Key::To => false,
+                Key::Domain => true,
+                _ => continue,
+            };
+            let values = match &kv.value {
+                TraceValue::List(list) => list.value.values().collect::<Vec<_>>(),
+                value => vec![value],
+            };
+            for value in values {
+                if let TraceValue::String(value) = value {
+                    let domain = if is_domain {
+                        value.value.as_str()
+                    } else {
+                        value.value.domain_part()
+                    };
+                    if is_tenant_domain(server, domain, tenant_id).await? {
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+async fn is_tenant_recipient(
+    server: &Server,
+    recipients: &[Recipient],
+    tenant_id: Option<u32>,
+) -> trc::Result<bool> {
+    for rcpt in recipients {
+        if is_tenant_address(server, rcpt.address(), tenant_id).await? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+async fn is_tenant_address(
+    server: &Server,
+    address: &str,
+    tenant_id: Option<u32>,
+) -> trc::Result<bool> {
+    is_tenant_domain(server, address.domain_part(), tenant_id).await
+}
+
+async fn is_tenant_domain(
+    server: &Server,
+    domain: &str,
+    tenant_id: Option<u32>,
+) -> trc::Result<bool> {
+    if domain.is_empty() {
+        return Ok(false);
+    }
+
+    Ok(server
+        .domain(&domain.to_lowercase())
+        .await
+        .caused_by(trc::location!())?
+        .is_some_and(|domain| domain.id_tenant == tenant_id))
+}
+
+fn lifecycle_stage(event: EventType) -> Option<LifecycleStage> {
+    match event {
+        EventType::Smtp(
+            SmtpEvent::ConnectionStart
+            | SmtpEvent::Ehlo
+            | SmtpEvent::MailFrom
+            | SmtpEvent::MailFromRewritten
+            | SmtpEvent::RcptTo
+            | SmtpEvent::RcptToRewritten
+            | SmtpEvent::HeaderRewritten,
+        ) => Some(LifecycleStage::Received),
+        EventType::Smtp(
+            SmtpEvent::DkimPass
+            | SmtpEvent::DkimFail
+            | SmtpEvent::ArcPass
+            | SmtpEvent::ArcFail
+            | SmtpEvent::SpfEhloPass
+            | SmtpEvent::SpfEhloFail
+            | SmtpEvent::SpfFromPass
+            | SmtpEvent::SpfFromFail
+            | SmtpEvent::DmarcPass
+            | SmtpEvent::DmarcFail
+            | SmtpEvent::IprevPass
+            | SmtpEvent::IprevFail
+            | SmtpEvent::RcptToGreylisted,
+        )
+        | EventType::Spam(_)
+        | EventType::Milter(_)
+        | EventType::MtaHook(_)
+        | EventType::Sieve(_)
+        | EventType::MessageIngest(MessageIngestEvent::Ham | MessageIngestEvent::Spam) => {
+            Some(LifecycleStage::Filtered)
+        }
+        EventType::Queue(
+            QueueEvent::MessageQueued
+            | QueueEvent::AuthenticatedMessageQueued
+            | QueueEvent::ReportQueued
+            | QueueEvent::AutogeneratedQueued,
+        ) => Some(LifecycleStage::Queued),
+        EventType::Queue(
+            QueueEvent::Rescheduled
+            | QueueEvent::RateLimitExceeded
+            | QueueEvent::ConcurrencyLimitExceeded
+            | QueueEvent::QuotaExceeded
+            | QueueEvent::MessageDeferred,
+        )
+        | EventType::Delivery(
+            DeliveryEvent::RateLimitExceeded
+            | DeliveryEvent::ConcurrencyLimitExceeded
+            | DeliveryEvent::DsnTempFail,
+        ) => Some(LifecycleStage::Retry),
+        EventType::Delivery(DeliveryEvent::AttemptStart | DeliveryEvent::DomainDeliveryStart) => {
+            Some(LifecycleStage::Attempt)
+        }
+        EventType::Delivery(
+            DeliveryEvent::MxLookupFailed
+            | DeliveryEvent::IpLookupFailed
+            | DeliveryEvent::NullMx
+            | DeliveryEvent::ConnectError
+            | DeliveryEvent::GreetingFailed
+            | DeliveryEvent::EhloRejected
+            | DeliveryEvent::AuthFailed
+            | DeliveryEvent::StartTlsError
+            | DeliveryEvent::ImplicitTlsError
+            | DeliveryEvent::MailFromRejected
+            | DeliveryEvent::RcptToRejected
+            | DeliveryEvent::RcptToFailed
+            | DeliveryEvent::MessageRejected,
+        ) => Some(LifecycleStage::Response),
+        EventType::Delivery(DeliveryEvent::Delivered | DeliveryEvent::DsnSuccess)
+        | EventType::Queue(QueueEvent::MessageDelivered) => Some(LifecycleStage::Delivered),
+        EventType::Delivery(DeliveryEvent::Failed) => Some(LifecycleStage::Failed),
+        EventType::Delivery(DeliveryEvent::DsnPermFail | DeliveryEvent::DoubleBounce)
+        | EventType::Queue(QueueEvent::DsnQueued | QueueEvent::MessageBounced) => {
+            Some(LifecycleStage::Bounced)
+        }
+        _ => None,
+    }
+}
+
+fn key_values_to_json<'x>(
+    key_values: impl Iterator<Item = &'x TraceKeyValue>,
+) -> Map<String, Value> {
+    let mut details = Map::new();
+    for kv in key_values {
+        if !matches!(kv.key, Key::SpanId) {
+            details.insert(kv.key.as_str().to_string(), value_to_json(&kv.value));
+        }
+    }
+    details
+}
+
+fn value_to_json(value: &TraceValue) -> Value {
+    match value {
+        TraceValue::String(value) => Value::String(value.value.clone()),
+        TraceValue::UnsignedInt(value) => Value::from(value.value),
+        TraceValue::Integer(value) => Value::from(value.value),
+        TraceValue::Boolean(value) => Value::Bool(value.value),
+        TraceValue::Float(value) => Value::from(value.value.into_inner()),
+        TraceValue::UTCDateTime(value) => Value::String(value.value.to_string()),
+        TraceValue::Duration(value) => Value::from(value.value),
+        TraceValue::IpAddr(value) => Value::String(value.value.to_string()),
+        TraceValue::List(value) => Value::Array(value.value.values().map(value_to_json).collect()),
+        TraceValue::Event(value) => {
+            let mut event = key_values_to_json(value.value.values());
+            event.insert(
+                "event".to_string(),
+                Value::String(value.event.as_str().to_string()),
+            );
+            Value::Object(event)
+        }
+        TraceValue::Null => Value::Null,
+    }
+}
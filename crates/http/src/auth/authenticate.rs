@@ -5,7 +5,10 @@
  */
 
 use common::auth::AccessToken;
-use common::{HttpAuthCache, Server, auth::AuthRequest, network::limiter::InFlight};
+use common::{
+    HttpAuthCache, Server, auth::AuthRequest, config::server::ServerProtocol,
+    network::limiter::InFlight,
+};
 use directory::Credentials;
 use http_proto::{HttpRequest, HttpSessionData};
 use hyper::header;
@@ -39,6 +42,11 @@ impl Authenticator for Server {
                     )?;
 
                     if access_token.revision() == http_cache.revision {
+                        session
+                            .instance
+                            .sessions
+                            .set_account(session.session_id, Some(&access_token));
+
                         // Enforce authenticated rate limit
                         return self
                             .is_http_authenticated_request_allowed(&access_token, session.remote_ip)
@@ -86,20 +94,48 @@ impl Authenticator for Server {
                 credentials,
                 session.session_id,
                 session.remote_ip,
+                ServerProtocol::Http,
             )))
             .await?;
 
-            // Cache credentials
-            self.inner.cache.http_auth.insert(
-                token.into(),
-                HttpAuthCache {
-                    account_id: access_token.account_id(),
-                    revision: access_token.revision(),
-                    credential_id: access_token.credential_id(),
-                    expires: Instant::now()
-                        + Duration::from_secs(self.core.oauth.oauth_expiry_token),
-                },
-            );
+            // Cache credentials, impersonation tokens are always validated again
+            if access_token.impersonator_id().is_none() {
+                self.inner.cache.http_auth.insert(
+                    token.into(),
+                    HttpAuthCache {
+                        account_id: access_token.account_id(),
+                        revision: access_token.revision(),
+                        credential_id: access_token.credential_id(),
+                        expires: Instant::now()
+                            + Duration::from_secs(self.core.oauth.oauth_expiry_token),
+                    },
+                );
+            }
+            session
+                .instance
+                .sessions
+                .set_account(session.session_id, Some(&access_token));
+
+            // Enforce authenticated rate limit
+            self.is_http_authenticated_request_allowed(&access_token, session.remote_ip)
+                .await
+                .map(|in_flight| (in_flight, access_token))
+        } else if let Some(certificate) = &session.client_certificate
+            && !session.instance.acceptor.client_cert_principal().is_empty()
+        {
+            // Authenticate using the client certificate
+            let access_token = self
+                .authenticate_certificate(
+                    certificate,
+                    session.instance.acceptor.client_cert_principal(),
+                    session.session_id,
+                    session.remote_ip,
+                )
+                .await?;
+            session
+                .instance
+                .sessions
+                .set_account(session.session_id, Some(&access_token));
 
             // Enforce authenticated rate limit
             self.is_http_authenticated_request_allowed(&access_token, session.remote_ip)
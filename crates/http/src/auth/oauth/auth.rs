@@ -12,6 +12,7 @@ use common::{
         AuthRequest,
         oauth::{CLIENT_ID_MAX_LEN, DEVICE_CODE_LEN, USER_CODE_ALPHABET, USER_CODE_LEN},
     },
+    config::server::ServerProtocol,
 };
 use directory::Credentials;
 use http_proto::*;
@@ -206,6 +207,7 @@ impl OAuthApiHandler for Server {
                         },
                         session_id: session.session_id,
                         remote_ip: session.remote_ip,
+                        protocol: ServerProtocol::Http,
                     })
                     .await
                 {
@@ -289,6 +291,7 @@ impl OAuthApiHandler for Server {
                                 },
                                 session_id: session.session_id,
                                 remote_ip: session.remote_ip,
+                                protocol: ServerProtocol::Http,
                             })
                             .await
                         {
@@ -8,7 +8,7 @@ use crate::auth::oauth::FormData;
 use chrono::Utc;
 use common::{
     KV_RATE_LIMIT_CONTACT, Server,
-    config::network::{ContactForm, FieldOrDefault},
+    config::network::{ContactForm, FieldOrDefault, FormRoute},
     network::ip_to_bytes,
     psl,
 };
@@ -28,12 +28,14 @@ use serde_json::json;
 use std::{borrow::Cow, fmt::Write, future::Future};
 use store::write::BatchBuilder;
 use trc::AddContext;
+use utils::template::Variables;
 
 pub trait FormHandler: Sync + Send {
     fn handle_contact_form(
         &self,
         session: &HttpSessionData,
         form: &ContactForm,
+        route: &FormRoute,
         form_data: FormData,
     ) -> impl Future<Output = trc::Result<HttpResponse>> + Send;
 }
@@ -43,16 +45,17 @@ impl FormHandler for Server {
         &self,
         session: &HttpSessionData,
         form: &ContactForm,
+        route: &FormRoute,
         form_data: FormData,
     ) -> trc::Result<HttpResponse> {
-        // Validate rate
-        if let Some(rate) = &form.rate
+        // Validate rate, each named form is limited separately
+        if let Some(rate) = &route.rate
             && !session.remote_ip.is_loopback()
             && self
                 .in_memory_store()
                 .is_rate_allowed(
                     KV_RATE_LIMIT_CONTACT,
-                    &ip_to_bytes(&session.remote_ip),
+                    &[route.name.as_bytes(), &ip_to_bytes(&session.remote_ip)].concat(),
                     rate,
                     false,
                 )
@@ -82,10 +85,41 @@ impl FormHandler for Server {
         let from_subject = form_data.get_or_default(&form.from_subject).trim();
         let from_name = form_data.get_or_default(&form.from_name).trim();
 
-        // Validate email
+        // Validate CAPTCHA
         let mut failure = None;
         let mut has_success = false;
-        if form.validate_domain && from_email != form.from_email.default {
+        if let Some(captcha) = &form.captcha {
+            match form_data
+                .get(captcha.response_field)
+                .filter(|response| !response.is_empty())
+            {
+                Some(response) => match captcha.verify(response, session.remote_ip).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        failure = Some(Cow::Borrowed(
+                            "CAPTCHA verification failed. Please try again.",
+                        ));
+                    }
+                    Err(err) => {
+                        trc::event!(
+                            Http(trc::HttpEvent::Error),
+                            SpanId = session.session_id,
+                            Reason = err,
+                            CausedBy = trc::location!()
+                        );
+                        failure = Some(Cow::Borrowed(
+                            "CAPTCHA verification is temporarily unavailable. Please try again later.",
+                        ));
+                    }
+                },
+                None => {
+                    failure = Some(Cow::Borrowed("Please complete the CAPTCHA."));
+                }
+            }
+        }
+
+        // Validate email
+        if failure.is_none() && form.validate_domain && from_email != form.from_email.default {
             if let Some(domain) = from_email.rsplit_once('@').and_then(|(local, domain)| {
                 if !local.is_empty()
                     && domain.contains('.')
@@ -118,26 +152,43 @@ impl FormHandler for Server {
         }
 
         if failure.is_none() {
-            // Build body
-            let mut body = String::with_capacity(1024);
-            for (field, value) in form_data.fields() {
-                if !value.is_empty() {
+            // Build subject and body
+            let captcha_field = form.captcha.as_ref().map(|captcha| captcha.response_field);
+            let fields = form_data
+                .fields()
+                .filter(|(field, value)| !value.is_empty() && captcha_field != Some(field.as_str()))
+                .collect::<Vec<_>>();
+            let date = Utc::now().format("%a, %d %b %Y %T %z").to_string();
+            let ip = format!("{}:{}", session.remote_ip, session.remote_port);
+            let mut variables = Variables::new();
+            if route.subject.is_some() || route.body.is_some() {
+                for (field, value) in &fields {
+                    variables.insert_single(field.to_string(), value.as_str());
+                }
+                variables.insert_single("form".to_string(), route.name.as_str());
+                variables.insert_single("date".to_string(), date.as_str());
+                variables.insert_single("ip".to_string(), ip.as_str());
+            }
+            let subject = route
+                .subject
+                .as_ref()
+                .map(|template| template.eval(&variables))
+                .filter(|subject| !subject.trim().is_empty())
+                .map_or(Cow::Borrowed(from_subject), Cow::Owned);
+            let body = if let Some(template) = &route.body {
+                template.eval(&variables)
+            } else {
+                let mut body = String::with_capacity(1024);
+                for (field, value) in &fields {
                     body.push_str(field);
                     body.push_str(": ");
                     body.push_str(value);
                     body.push_str("\r\n");
                 }
-            }
-            let _ = write!(
-                &mut body,
-                "Date: {}\r\n",
-                Utc::now().format("%a, %d %b %Y %T %z")
-            );
-            let _ = write!(
-                &mut body,
-                "IP: {}:{}\r\n",
-                session.remote_ip, session.remote_port
-            );
+                let _ = write!(&mut body, "Date: {date}\r\n");
+                let _ = write!(&mut body, "IP: {ip}\r\n");
+                body
+            };
 
             // Build message
             let message = MessageBuilder::new()
@@ -145,7 +196,8 @@ impl FormHandler for Server {
                 .header(
                     "To",
                     HeaderType::Address(Address::List(
-                        form.rcpt_to
+                        route
+                            .rcpt_to
                             .iter()
                             .map(|rcpt| {
                                 Address::Address(EmailAddress {
@@ -163,7 +215,7 @@ impl FormHandler for Server {
                     session.remote_ip,
                     session.remote_port
                 ))
-                .subject(from_subject)
+                .subject(subject.as_ref())
                 .text_body(body)
                 .write_to_vec()
                 .unwrap_or_default();
@@ -178,12 +230,13 @@ impl FormHandler for Server {
                 .deliver_message(IngestMessage {
                     sender_address: from_email,
                     sender_authenticated: false,
-                    recipients: form
+                    recipients: route
                         .rcpt_to
                         .iter()
                         .map(|address| IngestRecipient {
                             address: address.clone(),
                             is_spam: false,
+                            subaddress: None,
                         })
                         .collect(),
                     message_blob,
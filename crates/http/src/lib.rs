@@ -6,6 +6,7 @@
 
 #![deny(clippy::large_futures)]
 
+pub mod agent_check;
 pub mod api;
 pub mod auth;
 pub mod form;
@@ -19,22 +19,27 @@ use crate::{
 use common::{
     BuildServer, Inner, KV_ACME, Server,
     ipc::PushEvent,
-    manager::application::Resource,
-    network::{SessionData, SessionManager, SessionStream},
+    manager::application::{CachePolicy, Resource},
+    network::{SessionData, SessionManager, SessionStream, fingerprint::TlsFingerprint},
+    telemetry::metrics::bandwidth::BandwidthProtocol,
 };
-use dav::{DavMethod, request::DavRequestHandler};
-use groupware::{DavResourceName, calendar::itip::ItipIngest};
+use dav::{DavMethod, calendar::freebusy::CalendarFreebusyHttpHandler, request::DavRequestHandler};
+use email::forwarding::AccountForwardingStore;
+use groupware::{DavResourceName, calendar::itip::ItipIngest, share_link::ShareLinks};
 use http_proto::{
     DownloadResponse, HtmlResponse, HttpContext, HttpRequest, HttpResponse, HttpResponseBody,
-    HttpSessionData, JsonProblemResponse, ToHttpResponse, form_urlencoded, request::fetch_body,
+    HttpSessionData, JsonProblemResponse, JsonResponse, ToHttpResponse, form_urlencoded,
+    request::fetch_body,
 };
 use hyper::{
-    Method, StatusCode, body,
-    header::{self, CONTENT_TYPE},
-    server::conn::http1,
+    Method, StatusCode, Version, body,
+    header::{self, CONTENT_TYPE, HeaderValue},
     service::service_fn,
 };
-use hyper_util::rt::TokioIo;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::conn::auto,
+};
 use jmap::{
     api::{
         ToJmapHttpResponse, event_source::EventSourceHandler, request::RequestHandler,
@@ -44,7 +49,7 @@ use jmap::{
     websocket::upgrade::WebSocketUpgrade,
 };
 use jmap_proto::request::{Request, capability::Session};
-use registry::schema::enums::Permission;
+use registry::schema::enums::{FreeBusyAccess, Permission};
 use std::{net::IpAddr, str::FromStr, sync::Arc};
 use store::dispatch::lookup::KeyValue;
 use trc::SecurityEvent;
@@ -68,7 +73,9 @@ impl ParseHttp for Server {
         path.next();
 
         // Validate endpoint access
-        let ctx = HttpContext::new(&session, &req);
+        let ctx = HttpContext::new(&session, &req)
+            .with_asn_geo_data(self)
+            .await;
         match ctx.has_endpoint_access(self).await {
             StatusCode::OK => (),
             status => {
@@ -99,7 +106,7 @@ impl ParseHttp for Server {
                         .await
                         .ok_or_else(|| trc::LimitEvent::SizeRequest.into_err())?;
 
-                        return Ok(self
+                        let response = self
                             .handle_jmap_request(
                                 Request::parse(
                                     &bytes,
@@ -109,8 +116,16 @@ impl ParseHttp for Server {
                                 &access_token,
                                 &session,
                             )
-                            .await
-                            .into_http_response());
+                            .await?
+                            .into_http_response();
+                        self.record_bandwidth(
+                            access_token.account_id(),
+                            BandwidthProtocol::Jmap,
+                            bytes.len(),
+                            response.size(),
+                        );
+
+                        return Ok(response);
                     }
                     ("download", &Method::GET) => {
                         // Authenticate request
@@ -123,20 +138,29 @@ impl ParseHttp for Server {
                             path.next(),
                         ) {
                             return match self.blob_download(&blob_id, &access_token).await? {
-                                Some(blob) => Ok(DownloadResponse {
-                                    filename: name.to_string(),
-                                    content_type: req
-                                        .uri()
-                                        .query()
-                                        .and_then(|q| {
-                                            form_urlencoded::parse(q.as_bytes())
-                                                .find(|(k, _)| k == "accept")
-                                                .map(|(_, v)| v.into_owned())
-                                        })
-                                        .unwrap_or("application/octet-stream".to_string()),
-                                    blob,
+                                Some(blob) => {
+                                    self.record_bandwidth(
+                                        access_token.account_id(),
+                                        BandwidthProtocol::Jmap,
+                                        0,
+                                        blob.len(),
+                                    );
+
+                                    Ok(DownloadResponse {
+                                        filename: name.to_string(),
+                                        content_type: req
+                                            .uri()
+                                            .query()
+                                            .and_then(|q| {
+                                                form_urlencoded::parse(q.as_bytes())
+                                                    .find(|(k, _)| k == "accept")
+                                                    .map(|(_, v)| v.into_owned())
+                                            })
+                                            .unwrap_or("application/octet-stream".to_string()),
+                                        blob,
+                                    }
+                                    .into_http_response())
                                 }
-                                .into_http_response()),
                                 None => Err(trc::ResourceEvent::NotFound.into_err()),
                             };
                         }
@@ -158,18 +182,27 @@ impl ParseHttp for Server {
                             )
                             .await
                             {
-                                Some(bytes) => Ok(self
-                                    .blob_upload(
-                                        account_id,
-                                        req.headers()
-                                            .get(CONTENT_TYPE)
-                                            .and_then(|h| h.to_str().ok())
-                                            .unwrap_or("application/octet-stream"),
-                                        &bytes,
-                                        &access_token,
-                                    )
-                                    .await?
-                                    .into_http_response()),
+                                Some(bytes) => {
+                                    self.record_bandwidth(
+                                        access_token.account_id(),
+                                        BandwidthProtocol::Jmap,
+                                        bytes.len(),
+                                        0,
+                                    );
+
+                                    Ok(self
+                                        .blob_upload(
+                                            account_id,
+                                            req.headers()
+                                                .get(CONTENT_TYPE)
+                                                .and_then(|h| h.to_str().ok())
+                                                .unwrap_or("application/octet-stream"),
+                                            &bytes,
+                                            &access_token,
+                                        )
+                                        .await?
+                                        .into_http_response())
+                                }
                                 None => Err(trc::LimitEvent::SizeUpload.into_err()),
                             };
                         }
@@ -298,13 +331,70 @@ impl ParseHttp for Server {
                     self.is_http_anonymous_request_allowed(session.remote_ip)
                         .await?;
 
-                    return if let Some(policy) = &self.core.smtp.session.mta_sts_policy {
-                        Ok(Resource::new("text/plain", policy.to_string().into_bytes())
-                            .into_http_response())
+                    return if let Some(policy) = self
+                        .mta_sts_policy(
+                            req.headers()
+                                .get(header::HOST)
+                                .and_then(|h| h.to_str().ok())
+                                .map(|h| h.rsplit_once(':').map_or(h, |(h, _)| h))
+                                .unwrap_or_default(),
+                            session.remote_ip,
+                        )
+                        .await?
+                    {
+                        Ok(Resource::new("text/plain", policy.into_bytes()).into_http_response())
                     } else {
                         Err(trc::ResourceEvent::NotFound.into_err())
                     };
                 }
+                ("openpgpkey", &Method::GET) => {
+                    // Limit anonymous requests
+                    self.is_http_anonymous_request_allowed(session.remote_ip)
+                        .await?;
+
+                    // The direct method takes the domain from the host name, while
+                    // the advanced method includes it in the path
+                    let (domain, resource) = match path.next().unwrap_or_default() {
+                        resource @ ("hu" | "policy") => (
+                            req.headers()
+                                .get(header::HOST)
+                                .and_then(|h| h.to_str().ok())
+                                .map(|h| h.rsplit_once(':').map_or(h, |(h, _)| h))
+                                .unwrap_or_default(),
+                            resource,
+                        ),
+                        domain => (domain, path.next().unwrap_or_default()),
+                    };
+
+                    return match resource {
+                        "hu" => {
+                            let local_part = req.uri().query().and_then(|q| {
+                                form_urlencoded::parse(q.as_bytes())
+                                    .find(|(k, _)| k == "l")
+                                    .map(|(_, v)| v.into_owned())
+                            });
+                            match self
+                                .wkd_key(
+                                    domain,
+                                    path.next().unwrap_or_default(),
+                                    local_part.as_deref(),
+                                )
+                                .await?
+                            {
+                                Some(key) => Ok(Resource::new("application/octet-stream", key)
+                                    .into_http_response()
+                                    .with_header("Access-Control-Allow-Origin", "*")),
+                                None => Err(trc::ResourceEvent::NotFound.into_err()),
+                            }
+                        }
+                        "policy" if self.is_wkd_domain(domain).await? => {
+                            Ok(Resource::new("text/plain", vec![])
+                                .into_http_response()
+                                .with_header("Access-Control-Allow-Origin", "*"))
+                        }
+                        _ => Err(trc::ResourceEvent::NotFound.into_err()),
+                    };
+                }
                 ("user-agent-configuration.json", &Method::GET) => {
                     // Limit anonymous requests
                     self.is_http_anonymous_request_allowed(session.remote_ip)
@@ -346,6 +436,16 @@ impl ParseHttp for Server {
                         .await
                         .map(|resource| resource.into_http_response());
                 }
+                ("mobileconfig", &Method::GET) => {
+                    // Limit anonymous requests
+                    self.is_http_anonymous_request_allowed(session.remote_ip)
+                        .await?;
+
+                    return self
+                        .handle_mobileconfig_request(req.uri().query())
+                        .await
+                        .map(|resource| resource.into_http_response());
+                }
                 (_, &Method::OPTIONS) => {
                     return Ok(HttpResponse::new(StatusCode::NO_CONTENT));
                 }
@@ -426,27 +526,112 @@ impl ParseHttp for Server {
                 self.is_http_anonymous_request_allowed(session.remote_ip)
                     .await?;
 
-                if self.core.groupware.itip_http_rsvp_url.is_some()
-                    && req.method() == Method::GET
-                    && path.next().unwrap_or_default() == "rsvp"
-                {
+                match (path.next().unwrap_or_default(), req.method()) {
+                    ("rsvp", &Method::GET) if self.core.groupware.itip_http_rsvp_url.is_some() => {
+                        return self
+                            .http_rsvp_handle(
+                                req.uri().query().unwrap_or_default(),
+                                req.headers()
+                                    .get(header::ACCEPT_LANGUAGE)
+                                    .and_then(|v| v.to_str().ok())
+                                    .map(|lang| {
+                                        let lang = lang.split_once(',').map_or(lang, |(l, _)| l);
+                                        lang.split_once(';').map_or(lang, |(l, _)| l)
+                                    })
+                                    .unwrap_or("en"),
+                            )
+                            .await
+                            .map(|response| {
+                                HtmlResponse::new(response)
+                                    .into_http_response()
+                                    .with_no_store()
+                            });
+                    }
+                    ("freebusy", &Method::GET) => {
+                        if let Some(access) = self.core.groupware.freebusy_http_access {
+                            let access_token = if access == FreeBusyAccess::Authenticated {
+                                Some(self.authenticate_headers(&req, &session).await?.1)
+                            } else {
+                                None
+                            };
+
+                            return self
+                                .handle_http_freebusy_request(
+                                    access_token.as_ref(),
+                                    path.next().unwrap_or_default(),
+                                    req.uri().query(),
+                                )
+                                .await;
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            "share" => {
+                if req.method() == Method::GET && self.core.groupware.share_link_url.is_some() {
+                    // Limit anonymous requests
+                    self.is_http_anonymous_request_allowed(session.remote_ip)
+                        .await?;
+
                     return self
-                        .http_rsvp_handle(
-                            req.uri().query().unwrap_or_default(),
-                            req.headers()
-                                .get(header::ACCEPT_LANGUAGE)
-                                .and_then(|v| v.to_str().ok())
-                                .map(|lang| {
-                                    let lang = lang.split_once(',').map_or(lang, |(l, _)| l);
-                                    lang.split_once(';').map_or(lang, |(l, _)| l)
-                                })
-                                .unwrap_or("en"),
-                        )
+                        .share_link_export(path.next().unwrap_or_default(), session.remote_ip)
                         .await
-                        .map(|response| {
-                            HtmlResponse::new(response)
-                                .into_http_response()
-                                .with_no_store()
+                        .map(|export| match export {
+                            Some(export) => HttpResponse::new(StatusCode::OK)
+                                .with_content_type(export.content_type)
+                                .with_text_body(export.contents)
+                                .with_no_store(),
+                            None => HttpResponse::new(StatusCode::NOT_FOUND),
+                        });
+                }
+            }
+            "forward" => {
+                if req.method() == Method::GET {
+                    // Limit anonymous requests
+                    self.is_http_anonymous_request_allowed(session.remote_ip)
+                        .await?;
+
+                    return self
+                        .forwarding_confirm(path.next().unwrap_or_default())
+                        .await
+                        .map(|confirmed| {
+                            if confirmed {
+                                HttpResponse::new(StatusCode::OK)
+                                    .with_text_body("Email forwarding has been confirmed.")
+                                    .with_content_type("text/plain; charset=utf-8")
+                                    .with_no_store()
+                            } else {
+                                HttpResponse::new(StatusCode::NOT_FOUND)
+                                    .with_text_body("Invalid or expired confirmation link.")
+                                    .with_content_type("text/plain; charset=utf-8")
+                                    .with_no_store()
+                            }
+                        });
+                }
+            }
+            "unsubscribe" => {
+                // One-click unsubscribe (RFC 8058), GET requests are ignored
+                // as they are often issued by link scanners
+                if req.method() == Method::POST {
+                    // Limit anonymous requests
+                    self.is_http_anonymous_request_allowed(session.remote_ip)
+                        .await?;
+
+                    return self
+                        .list_unsubscribe(path.next().unwrap_or_default())
+                        .await
+                        .map(|unsubscribed| {
+                            if unsubscribed {
+                                HttpResponse::new(StatusCode::OK)
+                                    .with_text_body("You have been unsubscribed from the list.")
+                                    .with_content_type("text/plain; charset=utf-8")
+                                    .with_no_store()
+                            } else {
+                                HttpResponse::new(StatusCode::NOT_FOUND)
+                                    .with_text_body("Invalid or expired unsubscribe link.")
+                                    .with_content_type("text/plain; charset=utf-8")
+                                    .with_no_store()
+                            }
                         });
                 }
             }
@@ -508,13 +693,28 @@ impl ParseHttp for Server {
                         return Ok(JsonProblemResponse(StatusCode::OK).into_http_response());
                     }
                     "ready" => {
-                        return Ok(JsonProblemResponse({
-                            if !self.core.storage.data.is_none() {
+                        // Degraded nodes remain in rotation, use the status endpoint
+                        // or an agent check to lower their weight
+                        return Ok(
+                            JsonProblemResponse(if self.health_status().await.is_ready() {
                                 StatusCode::OK
                             } else {
                                 StatusCode::SERVICE_UNAVAILABLE
-                            }
-                        })
+                            })
+                            .into_http_response(),
+                        );
+                    }
+                    "status" => {
+                        let status = self.health_status().await;
+                        return Ok(JsonResponse::with_status(
+                            if status.is_ready() {
+                                StatusCode::OK
+                            } else {
+                                StatusCode::SERVICE_UNAVAILABLE
+                            },
+                            status,
+                        )
+                        .no_cache()
                         .into_http_response());
                     }
                     _ => (),
@@ -542,7 +742,38 @@ impl ParseHttp for Server {
                     }
                 }
                 "otel" => {
-                    // Reserved for future use
+                    if let Some(prometheus) = self
+                        .core
+                        .metrics
+                        .prometheus
+                        .as_ref()
+                        .filter(|p| p.otlp_ingest)
+                        && req.method() == Method::POST
+                    {
+                        if let Some(auth) = &prometheus.auth
+                            && req
+                                .authorization_basic()
+                                .is_none_or(|secret| secret != auth)
+                        {
+                            return Err(trc::AuthEvent::Failed
+                                .into_err()
+                                .details("Invalid or missing credentials.")
+                                .caused_by(trc::location!()));
+                        }
+
+                        let bytes = fetch_body(
+                            &mut req,
+                            self.core.jmap.upload_max_size,
+                            session.session_id,
+                        )
+                        .await
+                        .ok_or_else(|| trc::LimitEvent::SizeRequest.into_err())?;
+                        self.ingest_otel_metrics(&bytes, session.remote_ip)?;
+
+                        // An empty ExportMetricsServiceResponse signals full success
+                        return Ok(Resource::new("application/x-protobuf", Vec::new())
+                            .into_http_response());
+                    }
                 }
                 _ => (),
             },
@@ -573,8 +804,27 @@ impl ParseHttp for Server {
                 }
             }
             // SPDX-SnippetEnd
+            "branding" => {
+                // Limit anonymous requests
+                self.is_http_anonymous_request_allowed(session.remote_ip)
+                    .await?;
+
+                let branding = self
+                    .branding(
+                        req.headers()
+                            .get(header::HOST)
+                            .and_then(|h| h.to_str().ok())
+                            .map(|h| h.rsplit_once(':').map_or(h, |(h, _)| h))
+                            .unwrap_or_default(),
+                    )
+                    .await?;
+
+                return Ok(JsonResponse::new(branding).no_cache().into_http_response());
+            }
             "form" => {
-                if let Some(form) = &self.core.network.contact_form {
+                if let Some(form) = &self.core.network.contact_form
+                    && let Some(route) = form.route(path.next().unwrap_or_default())
+                {
                     match *req.method() {
                         Method::POST => {
                             self.is_http_anonymous_request_allowed(session.remote_ip)
@@ -584,7 +834,9 @@ impl ParseHttp for Server {
                                 FormData::from_request(&mut req, form.max_size, session.session_id)
                                     .await?;
 
-                            return self.handle_contact_form(&session, form, form_data).await;
+                            return self
+                                .handle_contact_form(&session, form, route, form_data)
+                                .await;
                         }
                         Method::OPTIONS => {
                             return Ok(HttpResponse::new(StatusCode::NO_CONTENT));
@@ -602,28 +854,44 @@ impl ParseHttp for Server {
                 return Ok(HtmlResponse::new(page.to_string()).into_http_response());
             }
             external => {
-                if path.next().is_none() {
-                    if !external.is_empty() {
-                        return Ok(HttpResponse::redirect(format!("/{external}/")));
-                    }
-                } else if let Some(resource) = self
-                    .inner
-                    .data
-                    .applications
-                    .serve(
-                        external,
-                        req.uri()
-                            .path()
-                            .get(external.len() + 2..)
-                            .unwrap_or_default(),
-                    )
-                    .await?
+                let hostname = req
+                    .headers()
+                    .get(header::HOST)
+                    .and_then(|h| h.to_str().ok())
+                    .map(|h| h.rsplit_once(':').map_or(h, |(h, _)| h))
+                    .unwrap_or_default();
+                let applications = &self.inner.data.applications;
+
+                if path.next().is_none()
+                    && !external.is_empty()
+                    && applications.is_mounted(hostname, external)
+                {
+                    return Ok(HttpResponse::redirect(format!("/{external}/")));
+                } else if let Some(resource) =
+                    applications.serve(hostname, req.uri().path()).await?
                 {
-                    let response = resource.resource.into_http_response();
-                    return Ok(if !resource.no_cache {
-                        response.with_immutable_cache()
+                    let response = if req
+                        .headers()
+                        .get(header::IF_NONE_MATCH)
+                        .and_then(|h| h.to_str().ok())
+                        .is_some_and(|etags| {
+                            etags.split(',').any(|etag| {
+                                let etag = etag.trim();
+                                etag == "*" || etag.trim_start_matches("W/") == resource.etag
+                            })
+                        }) {
+                        HttpResponse::new(StatusCode::NOT_MODIFIED)
                     } else {
-                        response.with_no_cache()
+                        resource.resource.into_http_response()
+                    }
+                    .with_etag(resource.etag);
+
+                    return Ok(match resource.cache {
+                        CachePolicy::NoCache => response.with_no_cache(),
+                        CachePolicy::MaxAge(max_age) => {
+                            response.with_cache_control(format!("public, max-age={max_age}"))
+                        }
+                        CachePolicy::Immutable => response.with_immutable_cache(),
                     });
                 }
             }
@@ -647,20 +915,57 @@ impl ParseHttp for Server {
 async fn handle_session<T: SessionStream>(inner: Arc<Inner>, session: SessionData<T>) {
     let _in_flight = session.in_flight;
     let is_tls = session.stream.is_tls();
+    let client_certificate: Option<Arc<[u8]>> = session.stream.peer_certificate().map(Arc::from);
+    let tls_fingerprint: Option<Arc<TlsFingerprint>> =
+        session.stream.tls_fingerprint().cloned().map(Arc::new);
 
-    if let Err(http_err) = http1::Builder::new()
-        .keep_alive(true)
-        .serve_connection(
+    // HTTP/2 is negotiated using ALPN on TLS connections, or using prior knowledge on
+    // cleartext connections when h2c is enabled.
+    let (enable_http2, max_concurrent_streams) = {
+        let core = inner.shared_core.load();
+        let http = &core.network.http;
+        (
+            if is_tls { http.http2 } else { http.h2c },
+            http.http2_max_concurrent_streams,
+        )
+    };
+    let mut builder = auto::Builder::new(TokioExecutor::new());
+    builder.http1().keep_alive(true);
+    builder
+        .http2()
+        .max_concurrent_streams(max_concurrent_streams);
+    if !enable_http2 {
+        builder = builder.http1_only();
+    }
+
+    if let Err(http_err) = builder
+        .serve_connection_with_upgrades(
             TokioIo::new(session.stream),
-            service_fn(|req: hyper::Request<body::Incoming>| {
+            service_fn(|mut req: hyper::Request<body::Incoming>| {
                 let instance = session.instance.clone();
                 let inner = inner.clone();
+                let client_certificate = client_certificate.clone();
+                let tls_fingerprint = tls_fingerprint.clone();
 
                 async move {
                     let server = inner.build_server();
 
-                    // Obtain remote IP
-                    let remote_ip = if !server.core.network.http.use_forwarded {
+                    // HTTP/2 requests carry the host in the :authority pseudo-header
+                    if req.version() == Version::HTTP_2
+                        && !req.headers().contains_key(header::HOST)
+                        && let Some(host) = req
+                            .uri()
+                            .authority()
+                            .and_then(|authority| HeaderValue::from_str(authority.as_str()).ok())
+                    {
+                        req.headers_mut().insert(header::HOST, host);
+                    }
+
+                    // Obtain remote IP, forwarded headers are only trusted on connections
+                    // received from one of the listener's trusted proxy networks
+                    let remote_ip = if !server.core.network.http.use_forwarded
+                        || session.proxy_ip.is_none()
+                    {
                         trc::event!(
                             Http(trc::HttpEvent::RequestUrl),
                             SpanId = session.session_id,
@@ -737,21 +1042,43 @@ async fn handle_session<T: SessionStream>(inner: Arc<Inner>, session: SessionDat
                         session.remote_ip
                     };
 
+                    let accept_encoding = req
+                        .headers()
+                        .get(header::ACCEPT_ENCODING)
+                        .and_then(|h| h.to_str().ok())
+                        .map(|h| h.to_string());
+
+                    let session_data = HttpSessionData {
+                        instance,
+                        local_ip: session.local_ip,
+                        local_port: session.local_port,
+                        remote_ip,
+                        remote_port: session.remote_port,
+                        is_tls,
+                        session_id: session.session_id,
+                        client_certificate,
+                        tls_fingerprint,
+                    };
+
+                    // Evaluate CORS policy
+                    let (cors, is_preflight) = {
+                        let ctx = HttpContext::new(&session_data, &req)
+                            .with_asn_geo_data(&server)
+                            .await;
+                        (ctx.cors_policy(&server).await, ctx.is_cors_preflight())
+                    };
+
                     // Parse HTTP request
-                    let response = match Box::pin(server.parse_http_request(
-                        req,
-                        HttpSessionData {
-                            instance,
-                            local_ip: session.local_ip,
-                            local_port: session.local_port,
-                            remote_ip,
-                            remote_port: session.remote_port,
-                            is_tls,
-                            session_id: session.session_id,
-                        },
-                    ))
-                    .await
-                    {
+                    session_data.instance.sessions.set_command(
+                        session.session_id,
+                        &format!("{} {}", req.method(), req.uri().path()),
+                    );
+                    let response = if is_preflight && cors.is_some() {
+                        Ok(HttpResponse::new(StatusCode::NO_CONTENT))
+                    } else {
+                        Box::pin(server.parse_http_request(req, session_data)).await
+                    };
+                    let response = match response {
                         Ok(response) => response,
                         Err(err) => {
                             let response = err.into_http_response();
@@ -775,6 +1102,22 @@ async fn handle_session<T: SessionStream>(inner: Arc<Inner>, session: SessionDat
                         Size = response.size(),
                     );
 
+                    // Add CORS headers
+                    let response = match &cors {
+                        Some(policy) => response.with_cors(policy, is_preflight),
+                        None => response,
+                    };
+
+                    // Compress response
+                    let response = match (&server.core.network.http.compression, accept_encoding) {
+                        (Some(compression), Some(accept_encoding)) => {
+                            response
+                                .with_compression(&accept_encoding, compression)
+                                .await
+                        }
+                        _ => response,
+                    };
+
                     // Build response
                     let mut response = response.build();
 
@@ -791,10 +1134,12 @@ async fn handle_session<T: SessionStream>(inner: Arc<Inner>, session: SessionDat
                 }
             }),
         )
-        .with_upgrades()
         .await
     {
-        if http_err.is_parse() {
+        if http_err
+            .downcast_ref::<hyper::Error>()
+            .is_some_and(|err| err.is_parse())
+        {
             let server = inner.build_server();
             if !server.core.network.http.use_forwarded {
                 match server.is_scanner_fail2banned(session.remote_ip).await {
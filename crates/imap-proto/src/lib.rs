@@ -90,6 +90,25 @@ impl Command {
                 | Command::Thread(true)
         )
     }
+
+    pub fn is_write(&self) -> bool {
+        matches!(
+            self,
+            Command::Create
+                | Command::Delete
+                | Command::Rename
+                | Command::Subscribe
+                | Command::Unsubscribe
+                | Command::Append
+                | Command::Close
+                | Command::Expunge(_)
+                | Command::Store(_)
+                | Command::Copy(_)
+                | Command::Move(_)
+                | Command::SetAcl
+                | Command::DeleteAcl
+        )
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -152,6 +171,11 @@ pub enum ResponseCode {
 
     // USEATTR
     UseAttr,
+
+    // LOGIN-REFERRALS
+    Referral {
+        url: String,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -177,6 +201,25 @@ impl ResponseCode {
             modseq: if modseq > 0 { modseq + 1 } else { 0 },
         }
     }
+
+    pub fn login_referral(username: Option<&str>, hostname: &str) -> Self {
+        let mut url = String::with_capacity(hostname.len() + 32);
+        url.push_str("imap://");
+        if let Some(username) = username {
+            for &ch in username.as_bytes() {
+                if ch.is_ascii_alphanumeric() || b"-._~!$'()*+,&=".contains(&ch) {
+                    url.push(ch as char);
+                } else {
+                    url.push_str(&format!("%{ch:02X}"));
+                }
+            }
+            url.push_str(";AUTH=*@");
+        }
+        url.push_str(hostname);
+        url.push('/');
+
+        ResponseCode::Referral { url }
+    }
 }
 
 impl StatusResponse {
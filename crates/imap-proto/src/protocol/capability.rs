@@ -54,6 +54,7 @@ pub enum Capability {
     QuotaResource(QuotaResourceName),
     QuotaSet,
     JmapAccess,
+    LoginReferrals,
 }
 
 /*
@@ -130,6 +131,7 @@ impl Capability {
             }
             Capability::QuotaSet => b"QUOTA=SET",
             Capability::JmapAccess => b"JMAPACCESS",
+            Capability::LoginReferrals => b"LOGIN-REFERRALS",
         });
     }
 
@@ -182,6 +184,7 @@ impl Capability {
                 Capability::Auth(Mechanism::Plain),
                 Capability::Auth(Mechanism::OAuthBearer),
                 Capability::Auth(Mechanism::XOauth2),
+                Capability::LoginReferrals,
             ]);
         }
         if offer_tls {
@@ -492,6 +492,11 @@ impl ResponseCode {
                 return;
             }
             ResponseCode::UseAttr => b"USEATTR",
+            ResponseCode::Referral { url } => {
+                buf.extend_from_slice(b"REFERRAL ");
+                buf.extend_from_slice(url.as_bytes());
+                return;
+            }
         });
     }
 
@@ -535,6 +540,7 @@ impl ResponseCode {
             ResponseCode::MailboxId { .. } => "MAILBOXID",
             ResponseCode::HighestModseq { .. } => "HIGHESTMODSEQ",
             ResponseCode::UseAttr => "USEATTR",
+            ResponseCode::Referral { .. } => "REFERRAL",
         }
     }
 }
@@ -732,7 +738,7 @@ impl Display for Command {
 
 #[cfg(test)]
 mod tests {
-    use crate::parser::parse_sequence_set;
+    use crate::{ResponseCode, StatusResponse, parser::parse_sequence_set};
 
     #[test]
     fn sequence_set_contains() {
@@ -752,4 +758,35 @@ mod tests {
             );
         }
     }
+    #[test]
+    fn login_referral() {
+        for (username, hostname, expected_response) in [
+            (
+                Some("jane"),
+                "imap.eu.example.org",
+                "A001 NO [REFERRAL imap://jane;AUTH=*@imap.eu.example.org/] Account is hosted on another server.\r\n",
+            ),
+            (
+                Some("jane@example.org"),
+                "imap.eu.example.org",
+                "A001 NO [REFERRAL imap://jane%40example.org;AUTH=*@imap.eu.example.org/] Account is hosted on another server.\r\n",
+            ),
+            (
+                None,
+                "imap.eu.example.org",
+                "A001 NO [REFERRAL imap://imap.eu.example.org/] Account is hosted on another server.\r\n",
+            ),
+        ] {
+            assert_eq!(
+                String::from_utf8(
+                    StatusResponse::no("Account is hosted on another server.")
+                        .with_code(ResponseCode::login_referral(username, hostname))
+                        .with_tag("A001")
+                        .into_bytes()
+                )
+                .unwrap(),
+                expected_response
+            );
+        }
+    }
 }
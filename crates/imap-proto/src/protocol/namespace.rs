@@ -8,18 +8,59 @@ use super::{ImapResponse, quoted_string};
 
 pub struct Response {
     pub shared_prefix: Option<String>,
+    pub public_prefix: Option<String>,
 }
 
 impl ImapResponse for Response {
     fn serialize(self) -> Vec<u8> {
         let mut buf = Vec::with_capacity(64);
-        if let Some(shared_prefix) = &self.shared_prefix {
-            buf.extend_from_slice(b"* NAMESPACE ((\"\" \"/\")) ((");
-            quoted_string(&mut buf, shared_prefix);
-            buf.extend_from_slice(b" \"/\")) NIL\r\n");
-        } else {
-            buf.extend_from_slice(b"* NAMESPACE ((\"\" \"/\")) NIL NIL\r\n");
+        buf.extend_from_slice(b"* NAMESPACE ((\"\" \"/\")) ");
+        for prefix in [&self.shared_prefix, &self.public_prefix] {
+            if let Some(prefix) = prefix {
+                buf.extend_from_slice(b"((");
+                quoted_string(&mut buf, prefix);
+                buf.extend_from_slice(b" \"/\"))");
+            } else {
+                buf.extend_from_slice(b"NIL");
+            }
+            buf.push(b' ');
         }
+        buf.pop();
+        buf.extend_from_slice(b"\r\n");
         buf
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::protocol::ImapResponse;
+
+    #[test]
+    fn serialize_namespace() {
+        for (response, expected) in [
+            (
+                super::Response {
+                    shared_prefix: None,
+                    public_prefix: None,
+                },
+                "* NAMESPACE ((\"\" \"/\")) NIL NIL\r\n",
+            ),
+            (
+                super::Response {
+                    shared_prefix: Some("Shared Folders".into()),
+                    public_prefix: None,
+                },
+                "* NAMESPACE ((\"\" \"/\")) ((\"Shared Folders\" \"/\")) NIL\r\n",
+            ),
+            (
+                super::Response {
+                    shared_prefix: None,
+                    public_prefix: Some("Public Folders".into()),
+                },
+                "* NAMESPACE ((\"\" \"/\")) NIL ((\"Public Folders\" \"/\"))\r\n",
+            ),
+        ] {
+            assert_eq!(String::from_utf8(response.serialize()).unwrap(), expected);
+        }
+    }
+}
@@ -90,6 +90,21 @@ impl<T: SessionStream> Session<T> {
 
         let mut requests = requests.into_iter().peekable();
         while let Some(request) = requests.next() {
+            self.instance
+                .sessions
+                .set_command(self.session_id, &request.command.to_string());
+            if request.command.is_write()
+                && let State::Authenticated { data } | State::Selected { data, .. } = &self.state
+            {
+                self.server
+                    .audit_impersonated_write(
+                        &data.access_token,
+                        self.remote_addr,
+                        "ImapCommand",
+                        &request.command.to_string(),
+                    )
+                    .await;
+            }
             let result = match request.command {
                 Command::List | Command::Lsub => self
                     .handle_list(request)
@@ -248,6 +263,10 @@ impl<T: SessionStream> Session<T> {
             };
 
             match result {
+                Ok(SessionResult::Continue) if self.backend.is_some() => {
+                    // The backend takes over the rest of the session
+                    return SessionResult::Continue;
+                }
                 Ok(SessionResult::Continue) => (),
                 Ok(result) => return result,
                 Err(err) => {
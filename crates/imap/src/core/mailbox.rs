@@ -55,15 +55,12 @@ impl<T: SessionStream> SessionData<T> {
 
         // Fetch shared mailboxes
         for &account_id in session.access_token.shared_accounts(Collection::Mailbox) {
-            let prefix: String = format!(
-                "{}/{}",
-                session.server.core.email.shared_folder,
-                session
+            let prefix = session.server.core.email.shared_account_prefix(
+                &session
                     .server
                     .account(account_id)
                     .await
-                    .caused_by(trc::location!())?
-                    .name()
+                    .caused_by(trc::location!())?,
             );
             mailboxes.push(
                 session
@@ -257,14 +254,12 @@ impl<T: SessionStream> SessionData<T> {
 
             // Fetch mailboxes for each new shared account
             for account_id in added_account_ids {
-                let prefix: String = format!(
-                    "{}/{}",
-                    self.server.core.email.shared_folder,
-                    self.server
+                let prefix = self.server.core.email.shared_account_prefix(
+                    &self
+                        .server
                         .account(account_id)
                         .await
-                        .caused_by(trc::location!())?
-                        .name()
+                        .caused_by(trc::location!())?,
                 );
                 added_accounts.push(
                     self.fetch_account_mailboxes(account_id, prefix.into(), &access_token, None)
@@ -8,7 +8,7 @@ use ahash::AHashMap;
 use common::{
     Inner, Server,
     auth::AccessToken,
-    network::{ServerInstance, SessionStream, limiter::InFlight},
+    network::{ServerInstance, SessionStream, backend::BackendConnection, limiter::InFlight},
 };
 use imap_proto::{
     Command,
@@ -29,6 +29,7 @@ use trc::AddContext;
 pub mod client;
 pub mod mailbox;
 pub mod message;
+pub mod proxy;
 pub mod session;
 
 #[derive(Clone)]
@@ -57,6 +58,8 @@ pub struct Session<T: SessionStream> {
     pub in_flight: InFlight,
     pub remote_addr: IpAddr,
     pub session_id: u64,
+    pub client_certificate: Option<Arc<[u8]>>,
+    pub backend: Option<BackendConnection>,
 }
 
 pub struct SessionData<T: SessionStream> {
@@ -167,6 +170,13 @@ pub enum State<T: SessionStream> {
 }
 
 impl<T: SessionStream> State<T> {
+    pub fn account_id(&self) -> Option<u32> {
+        match self {
+            State::NotAuthenticated { .. } => None,
+            State::Authenticated { data } | State::Selected { data, .. } => Some(data.account_id),
+        }
+    }
+
     pub fn try_replace_stream_tx<U: SessionStream>(
         self,
         new_stream: Arc<tokio::sync::Mutex<WriteHalf<U>>>,
@@ -0,0 +1,147 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::Session;
+use common::{
+    auth::AccessToken,
+    network::{SessionStream, backend::BackendConnection},
+};
+use imap_proto::{ResponseCode, protocol::quoted_string};
+use tokio::sync::watch;
+
+impl<T: SessionStream> Session<T> {
+    pub async fn proxy_login(
+        &mut self,
+        access_token: &AccessToken,
+        hostname: String,
+        username: &str,
+        secret: &str,
+        tag: String,
+    ) -> trc::Result<()> {
+        let port = self.server.core.imap.proxy_imap_port;
+        let mut backend = async {
+            let mut backend = self.server.connect_backend(&hostname, port).await?;
+            let greeting = backend.read_line().await?;
+            if !greeting.starts_with("* OK") {
+                return Err(format!("Unexpected greeting from {hostname}: {greeting}"));
+            }
+
+            // Credentials are never relayed in clear text
+            if !backend.is_tls() {
+                backend
+                    .write(format!("{tag} STARTTLS\r\n").as_bytes())
+                    .await?;
+                let response = read_tagged(&mut backend, &tag).await?;
+                if !is_tagged_ok(&response, &tag) {
+                    return Err(format!("{hostname} does not support STARTTLS: {response}"));
+                }
+                backend = self
+                    .server
+                    .start_tls(backend, self.server.core.imap.proxy_allow_invalid_certs)
+                    .await?;
+            }
+
+            Ok(backend)
+        }
+        .await
+        .map_err(|err| backend_error(err, &tag))?;
+
+        // Log into the backend reusing the client's credentials and tag
+        let response = async {
+            let mut command = Vec::with_capacity(tag.len() + username.len() + secret.len() + 16);
+            command.extend_from_slice(tag.as_bytes());
+            command.extend_from_slice(b" LOGIN ");
+            quoted_string(&mut command, username);
+            command.push(b' ');
+            quoted_string(&mut command, secret);
+            command.extend_from_slice(b"\r\n");
+            backend.write(&command).await?;
+
+            read_tagged(&mut backend, &tag).await
+        }
+        .await
+        .map_err(|err| backend_error(err, &tag))?;
+
+        if !is_tagged_ok(&response, &tag) {
+            return Err(trc::AuthEvent::Failed
+                .into_err()
+                .details("Backend rejected the credentials")
+                .ctx(trc::Key::Hostname, hostname)
+                .reason(response)
+                .id(tag));
+        }
+
+        trc::event!(
+            Imap(trc::ImapEvent::Proxy),
+            SpanId = self.session_id,
+            AccountId = access_token.account_id(),
+            Hostname = hostname,
+            RemotePort = port,
+        );
+
+        self.instance
+            .sessions
+            .set_account(self.session_id, Some(access_token));
+        self.write_bytes(format!("{response}\r\n")).await?;
+        self.backend = Some(backend);
+
+        Ok(())
+    }
+
+    pub async fn proxy(
+        &mut self,
+        backend: BackendConnection,
+        shutdown_rx: &mut watch::Receiver<bool>,
+    ) {
+        let hostname = backend.hostname.clone();
+        let mut stream_tx = self.stream_tx.lock().await;
+
+        if let Err(err) = backend
+            .proxy(
+                &mut self.stream_rx,
+                &mut *stream_tx,
+                self.server.core.imap.timeout_idle,
+                shutdown_rx,
+            )
+            .await
+        {
+            trc::event!(
+                Imap(trc::ImapEvent::Error),
+                SpanId = self.session_id,
+                Hostname = hostname,
+                Reason = err,
+                CausedBy = trc::location!()
+            );
+        }
+    }
+}
+
+async fn read_tagged(backend: &mut BackendConnection, tag: &str) -> Result<String, String> {
+    loop {
+        let line = backend.read_line().await?;
+        if line
+            .strip_prefix(tag)
+            .is_some_and(|line| line.starts_with(' '))
+        {
+            return Ok(line);
+        }
+    }
+}
+
+fn is_tagged_ok(response: &str, tag: &str) -> bool {
+    response
+        .get(tag.len() + 1..tag.len() + 3)
+        .is_some_and(|status| status.eq_ignore_ascii_case("OK"))
+}
+
+fn backend_error(err: String, tag: &str) -> trc::Error {
+    trc::ImapEvent::Error
+        .into_err()
+        .details("Backend server unavailable")
+        .reason(err)
+        .code(ResponseCode::Unavailable)
+        .id(tag.to_string())
+}
@@ -8,7 +8,11 @@ use super::{ImapSessionManager, Session, State};
 use crate::{GREETING_WITH_TLS, GREETING_WITHOUT_TLS};
 use common::{
     BuildServer,
-    network::{SessionData, SessionManager, SessionResult, SessionStream, stream::NullIo},
+    network::{
+        SessionData, SessionManager, SessionResult, SessionStream, fingerprint::FingerprintStream,
+        stream::NullIo,
+    },
+    telemetry::metrics::bandwidth::BandwidthProtocol,
 };
 use imap_proto::{
     protocol::{ProtocolVersion, SerializeResponse},
@@ -58,8 +62,21 @@ impl<T: SessionStream> Session<T> {
                     match result {
                         Ok(Ok(bytes_read)) => {
                             if bytes_read > 0 {
+                                if let Some(account_id) = self.state.account_id() {
+                                    self.server.record_bandwidth(
+                                        account_id,
+                                        BandwidthProtocol::Imap,
+                                        bytes_read,
+                                        0,
+                                    );
+                                }
                                 match self.ingest(&buf[..bytes_read]).await {
-                                    SessionResult::Continue => (),
+                                    SessionResult::Continue => {
+                                        if let Some(backend) = self.backend.take() {
+                                            self.proxy(backend, &mut shutdown_rx).await;
+                                            break;
+                                        }
+                                    }
                                     SessionResult::UpgradeTls => {
                                         return true;
                                     }
@@ -136,6 +153,7 @@ impl<T: SessionStream> Session<T> {
         let _ = session.stream.flush().await;
 
         // Split stream into read and write halves
+        let client_certificate = session.stream.peer_certificate().map(Arc::from);
         let (stream_rx, stream_tx) = tokio::io::split(session.stream);
         let server = manager.inner.build_server();
 
@@ -152,12 +170,14 @@ impl<T: SessionStream> Session<T> {
             session_id: session.session_id,
             in_flight: session.in_flight,
             remote_addr: session.remote_ip,
+            client_certificate,
+            backend: None,
             stream_rx,
             stream_tx: Arc::new(tokio::sync::Mutex::new(stream_tx)),
         })
     }
 
-    pub async fn into_tls(self) -> Result<Session<TlsStream<T>>, ()> {
+    pub async fn into_tls(self) -> Result<Session<TlsStream<FingerprintStream<T>>>, ()> {
         // Drop references to write half from state
         let state = if let Some(state) =
             self.state
@@ -190,8 +210,12 @@ impl<T: SessionStream> Session<T> {
         };
 
         // Upgrade to TLS
-        let (stream_rx, stream_tx) =
-            tokio::io::split(self.instance.tls_accept(stream, self.session_id).await?);
+        let stream = self
+            .instance
+            .tls_accept(stream, self.session_id, &self.server, self.remote_addr)
+            .await?;
+        let client_certificate = stream.peer_certificate().map(Arc::from);
+        let (stream_rx, stream_tx) = tokio::io::split(stream);
         let stream_tx = Arc::new(tokio::sync::Mutex::new(stream_tx));
 
         Ok(Session {
@@ -207,6 +231,8 @@ impl<T: SessionStream> Session<T> {
             session_id: self.session_id,
             in_flight: self.in_flight,
             remote_addr: self.remote_addr,
+            client_certificate,
+            backend: self.backend,
             stream_rx,
             stream_tx,
         })
@@ -224,6 +250,11 @@ impl<T: SessionStream> Session<T> {
             Contents = trc::Value::from_maybe_string(bytes),
         );
 
+        if let Some(account_id) = self.state.account_id() {
+            self.server
+                .record_bandwidth(account_id, BandwidthProtocol::Imap, 0, bytes.len());
+        }
+
         let mut stream = self.stream_tx.lock().await;
         if let Err(err) = stream.write_all(bytes).await {
             Err(trc::NetworkEvent::WriteError
@@ -267,6 +298,9 @@ impl<T: SessionStream> super::SessionData<T> {
             Contents = trc::Value::from_maybe_string(bytes),
         );
 
+        self.server
+            .record_bandwidth(self.account_id, BandwidthProtocol::Imap, 0, bytes.len());
+
         let mut stream = self.stream_tx.lock().await;
         if let Err(err) = stream.write_all(bytes.as_ref()).await {
             Err(trc::NetworkEvent::WriteError
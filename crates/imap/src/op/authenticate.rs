@@ -6,7 +6,8 @@
 
 use crate::core::{Session, SessionData, State};
 use common::{
-    auth::AuthRequest,
+    auth::{AccessToken, AuthRequest},
+    config::server::ServerProtocol,
     network::{SessionStream, limiter::LimiterResult},
 };
 use directory::Credentials;
@@ -58,6 +59,44 @@ impl<T: SessionStream> Session<T> {
                     self.write_bytes(b"+ \r\n".to_vec()).await
                 }
             }
+            Mechanism::External => {
+                // Only the identity derived from the certificate can be used
+                if args
+                    .params
+                    .first()
+                    .is_some_and(|param| !param.is_empty() && param.as_str() != "=")
+                {
+                    return Err(trc::AuthEvent::Error
+                        .into_err()
+                        .details("Authorization identities are not supported.")
+                        .id(args.tag)
+                        .code(ResponseCode::Cannot));
+                }
+
+                let principal = self.instance.acceptor.client_cert_principal();
+                let Some(certificate) = self
+                    .client_certificate
+                    .clone()
+                    .filter(|_| !principal.is_empty())
+                else {
+                    return Err(trc::AuthEvent::Error
+                        .into_err()
+                        .details("No client certificate was provided.")
+                        .id(args.tag)
+                        .code(ResponseCode::Cannot));
+                };
+
+                let result = self
+                    .server
+                    .authenticate_certificate(
+                        &certificate,
+                        principal,
+                        self.session_id,
+                        self.remote_addr,
+                    )
+                    .await;
+                Box::pin(self.complete_authentication(result, args.tag)).await
+            }
             _ => Err(trc::AuthEvent::Error
                 .into_err()
                 .details("Authentication mechanism not supported.")
@@ -68,14 +107,67 @@ impl<T: SessionStream> Session<T> {
 
     pub async fn authenticate(&mut self, credentials: Credentials, tag: String) -> trc::Result<()> {
         // Authenticate
-        let access_token = self
-            .server
-            .authenticate(&AuthRequest::from_credentials(
-                credentials,
-                self.session_id,
-                self.remote_addr,
-            ))
-            .await
+        let request = AuthRequest::from_credentials(
+            credentials,
+            self.session_id,
+            self.remote_addr,
+            ServerProtocol::Imap,
+        );
+        let result = self.server.authenticate(&request).await;
+
+        if let Ok(access_token) = &result
+            && access_token.has_permission(Permission::ImapAuthenticate)
+        {
+            let routing = self
+                .server
+                .account_routing(access_token.account_id())
+                .await
+                .map_err(|err| err.id(tag.clone()))?;
+
+            // Accounts homed on another server are referred to it (RFC 2221)
+            if let Some(hostname) = routing.login_referral {
+                let username = match &request.credentials {
+                    Credentials::Basic { username, .. } => Some(username.as_str()),
+                    Credentials::Bearer { username, .. } => username.as_deref(),
+                };
+
+                trc::event!(
+                    Imap(trc::ImapEvent::LoginReferral),
+                    SpanId = self.session_id,
+                    AccountId = access_token.account_id(),
+                    Hostname = hostname.clone(),
+                );
+
+                return self
+                    .write_bytes(
+                        StatusResponse::no("Account is hosted on another server.")
+                            .with_code(ResponseCode::login_referral(username, &hostname))
+                            .with_tag(tag)
+                            .into_bytes(),
+                    )
+                    .await;
+            }
+
+            // Accounts assigned to a backend are relayed using the same credentials
+            if let Credentials::Basic {
+                username, secret, ..
+            } = &request.credentials
+                && let Some(hostname) = routing.proxy_backend
+            {
+                return Box::pin(self.proxy_login(access_token, hostname, username, secret, tag))
+                    .await;
+            }
+        }
+
+        self.complete_authentication(result, tag).await
+    }
+
+    async fn complete_authentication(
+        &mut self,
+        result: trc::Result<AccessToken>,
+        tag: String,
+    ) -> trc::Result<()> {
+        let access_token = result
             .map_err(|err| {
                 if err.matches(trc::EventType::Auth(trc::AuthEvent::Failed)) {
                     let auth_failures = self.state.auth_failures();
@@ -104,6 +196,9 @@ impl<T: SessionStream> Session<T> {
         };
 
         // Create session
+        self.instance
+            .sessions
+            .set_account(self.session_id, Some(&access_token));
         self.state = State::Authenticated {
             data: Arc::new(
                 SessionData::new(self, access_token, in_flight)
@@ -127,6 +222,7 @@ impl<T: SessionStream> Session<T> {
 
     pub async fn handle_unauthenticate(&mut self, request: Request<Command>) -> trc::Result<()> {
         self.state = State::NotAuthenticated { auth_failures: 0 };
+        self.instance.sessions.set_account(self.session_id, None);
 
         self.write_bytes(
             StatusResponse::completed(Command::Unauthenticate)
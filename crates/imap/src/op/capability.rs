@@ -12,6 +12,7 @@ use imap_proto::{
     Command, StatusResponse,
     protocol::{
         ImapResponse,
+        authenticate::Mechanism,
         capability::{Capability, Response},
     },
     receiver::Request,
@@ -32,18 +33,21 @@ impl<T: SessionStream> Session<T> {
             Elapsed = op_start.elapsed()
         );
 
+        let mut capabilities = Capability::all_capabilities(
+            self.state.is_authenticated(),
+            !self.is_tls && self.instance.acceptor.is_tls(),
+        );
+        if !self.state.is_authenticated()
+            && self.client_certificate.is_some()
+            && !self.instance.acceptor.client_cert_principal().is_empty()
+        {
+            capabilities.push(Capability::Auth(Mechanism::External));
+        }
+
         self.write_bytes(
             StatusResponse::completed(Command::Capability)
                 .with_tag(request.tag)
-                .serialize(
-                    Response {
-                        capabilities: Capability::all_capabilities(
-                            self.state.is_authenticated(),
-                            !self.is_tls && self.instance.acceptor.is_tls(),
-                        ),
-                    }
-                    .serialize(),
-                ),
+                .serialize(Response { capabilities }.serialize()),
         )
         .await
     }
@@ -15,7 +15,9 @@ use email::{
     mailbox::{JUNK_ID, TRASH_ID, UidMailbox},
     message::{
         copy::{CopyMessageError, EmailCopy},
+        history::{EmailChange, EmailChangeHistory, EmailChangeProtocol},
         ingest::EmailIngest,
+        legal_hold::LegalHoldManagement,
         metadata::MessageData,
     },
 };
@@ -171,6 +173,27 @@ impl<T: SessionStream> SessionData<T> {
                 .id(arguments.tag));
         }
 
+        // Messages cannot be moved out of held mailboxes or out of held accounts
+        if is_move {
+            let legal_hold = self
+                .server
+                .legal_hold(src_mailbox.id.account_id)
+                .await
+                .imap_ctx(&arguments.tag, trc::location!())?;
+            let is_held = if src_mailbox.id.account_id == dest_mailbox.account_id {
+                legal_hold.is_mailbox_hold(src_mailbox.id.mailbox_id)
+            } else {
+                legal_hold.is_mailbox_held(src_mailbox.id.mailbox_id)
+            };
+            if is_held {
+                return Err(trc::ImapEvent::Error
+                    .into_err()
+                    .details("Messages in the source mailbox are under legal hold.")
+                    .code(ResponseCode::NoPerm)
+                    .id(arguments.tag));
+            }
+        }
+
         // Verify that the user can append messages to the destination mailbox.
         let dest_mailbox_id = dest_mailbox.mailbox_id;
         if !self
@@ -241,6 +264,12 @@ impl<T: SessionStream> SessionData<T> {
                     if is_move {
                         let mut new_data = data.inner.to_builder();
                         new_data.remove_mailbox(src_mailbox.id.mailbox_id);
+                        let change = EmailChange::new(
+                            self.account_id,
+                            EmailChangeProtocol::Imap,
+                            data.inner,
+                            &new_data,
+                        );
                         batch
                             .with_account_id(account_id)
                             .with_collection(Collection::Email)
@@ -254,8 +283,12 @@ impl<T: SessionStream> SessionData<T> {
                             .log_vanished_item(
                                 VanishedCollection::Email,
                                 (src_mailbox.id.mailbox_id, imap_id.uid),
-                            )
-                            .commit_point();
+                            );
+                        self.server
+                            .log_email_change(&mut batch, account_id, id, change)
+                            .await
+                            .imap_ctx(&arguments.tag, trc::location!())?;
+                        batch.commit_point();
                         did_move = true;
                     }
 
@@ -297,6 +330,12 @@ impl<T: SessionStream> SessionData<T> {
                 }
 
                 // Prepare write batch
+                let change = EmailChange::new(
+                    self.account_id,
+                    EmailChangeProtocol::Imap,
+                    data.inner,
+                    &new_data,
+                );
                 batch
                     .with_account_id(account_id)
                     .with_collection(Collection::Email)
@@ -313,6 +352,10 @@ impl<T: SessionStream> SessionData<T> {
                         (src_mailbox.id.mailbox_id, imap_id.uid),
                     );
                 }
+                self.server
+                    .log_email_change(&mut batch, account_id, id, change)
+                    .await
+                    .imap_ctx(&arguments.tag, trc::location!())?;
 
                 // Add message to training queue
                 if dest_mailbox_id.mailbox_id == JUNK_ID {
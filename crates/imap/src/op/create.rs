@@ -177,52 +177,54 @@ impl<T: SessionStream> SessionData<T> {
         let mut parent_mailbox_name = None;
         let (account_id, path) = {
             let mailboxes = self.mailboxes.lock();
-            let (account, full_path, prefix) =
-                if path.first() == Some(&self.server.core.email.shared_folder.as_str()) {
-                    // Shared Folders/<username>/<folder>
-                    if path.len() < 3 {
-                        return Err(trc::ImapEvent::Error
-                            .into_err()
-                            .details("Mailboxes under root shared folders are not allowed.")
-                            .code(ResponseCode::Cannot));
-                    }
-
-                    // Build path
-                    let root = &mut path[2];
-                    if root.eq_ignore_ascii_case("INBOX") {
-                        *root = "INBOX";
-                    }
-                    let full_path = path.join("/");
-                    let prefix = Some(format!("{}/{}", path[0], path[1]));
+            let (account, full_path, prefix) = if path
+                .first()
+                .is_some_and(|root| self.server.core.email.is_shared_root(root))
+            {
+                // Shared Folders/<username>/<folder> or Public Folders/<group>/<folder>
+                if path.len() < 3 {
+                    return Err(trc::ImapEvent::Error
+                        .into_err()
+                        .details("Mailboxes under root shared folders are not allowed.")
+                        .code(ResponseCode::Cannot));
+                }
 
-                    // Locate account
-                    if let Some(account) = mailboxes
-                        .iter()
-                        .skip(1)
-                        .find(|account| account.prefix == prefix)
-                    {
-                        (account, full_path, prefix)
-                    } else {
-                        #[allow(clippy::unnecessary_literal_unwrap)]
-                        return Err(trc::ImapEvent::Error.into_err().details(format!(
-                            "Shared account '{}' not found.",
-                            prefix.unwrap_or_default()
-                        )));
-                    }
-                } else if let Some(account) = mailboxes.first() {
-                    let root = &mut path[0];
-                    if root.eq_ignore_ascii_case("INBOX") {
-                        *root = "INBOX";
-                    }
+                // Build path
+                let root = &mut path[2];
+                if root.eq_ignore_ascii_case("INBOX") {
+                    *root = "INBOX";
+                }
+                let full_path = path.join("/");
+                let prefix = Some(format!("{}/{}", path[0], path[1]));
 
-                    (account, path.join("/"), None)
+                // Locate account
+                if let Some(account) = mailboxes
+                    .iter()
+                    .skip(1)
+                    .find(|account| account.prefix == prefix)
+                {
+                    (account, full_path, prefix)
                 } else {
-                    return Err(trc::ImapEvent::Error
-                        .into_err()
-                        .details("Internal server error.")
-                        .caused_by(trc::location!())
-                        .code(ResponseCode::ContactAdmin));
-                };
+                    #[allow(clippy::unnecessary_literal_unwrap)]
+                    return Err(trc::ImapEvent::Error.into_err().details(format!(
+                        "Shared account '{}' not found.",
+                        prefix.unwrap_or_default()
+                    )));
+                }
+            } else if let Some(account) = mailboxes.first() {
+                let root = &mut path[0];
+                if root.eq_ignore_ascii_case("INBOX") {
+                    *root = "INBOX";
+                }
+
+                (account, path.join("/"), None)
+            } else {
+                return Err(trc::ImapEvent::Error
+                    .into_err()
+                    .details("Internal server error.")
+                    .caused_by(trc::location!())
+                    .code(ResponseCode::ContactAdmin));
+            };
 
             // Locate parent mailbox
             if account.mailbox_names.contains_key(&full_path) {
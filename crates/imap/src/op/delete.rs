@@ -95,6 +95,12 @@ impl<T: SessionStream> SessionData<T> {
                     ResponseCode::Cannot,
                     "Another process is accessing this mailbox",
                 ),
+                MailboxDestroyError::LegalHold => {
+                    (ResponseCode::NoPerm, "Mailbox is under legal hold")
+                }
+                MailboxDestroyError::Protected => {
+                    (ResponseCode::NoPerm, "Mailbox is protected by policy")
+                }
             };
 
             return Err(trc::ImapEvent::Error
@@ -10,7 +10,7 @@ use ahash::AHashMap;
 use common::{network::SessionStream, storage::index::ObjectIndexBuilder};
 use email::{
     cache::{MessageCacheFetch, email::MessageCacheAccess},
-    message::metadata::MessageData,
+    message::{legal_hold::LegalHoldManagement, metadata::MessageData},
 };
 use imap_proto::{
     Command, ResponseCode, ResponseType, StatusResponse,
@@ -62,6 +62,21 @@ impl<T: SessionStream> Session<T> {
                 .id(request.tag));
         }
 
+        // Messages in mailboxes under legal hold cannot be expunged
+        if data
+            .server
+            .legal_hold(mailbox.id.account_id)
+            .await
+            .imap_ctx(&request.tag, trc::location!())?
+            .is_mailbox_held(mailbox.id.mailbox_id)
+        {
+            return Err(trc::ImapEvent::Error
+                .into_err()
+                .details("Messages in this mailbox are under legal hold.")
+                .code(ResponseCode::NoPerm)
+                .id(request.tag));
+        }
+
         // Parse sequence to operate on
         let sequence = match request.tokens.into_iter().next() {
             Some(Token::Argument(value)) if is_uid => {
@@ -166,6 +181,17 @@ impl<T: SessionStream> SessionData<T> {
         deleted_ids: &RoaringBitmap,
         batch: &mut BatchBuilder,
     ) -> trc::Result<()> {
+        // Messages in mailboxes under legal hold are never removed
+        if self
+            .server
+            .legal_hold(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .is_mailbox_held(mailbox_id)
+        {
+            return Ok(());
+        }
+
         batch
             .with_account_id(account_id)
             .with_collection(Collection::Email);
@@ -13,10 +13,13 @@ use ahash::AHashMap;
 use common::{network::SessionStream, storage::index::ObjectIndexBuilder};
 use email::{
     cache::{MessageCacheFetch, email::MessageCacheAccess},
-    message::metadata::{
-        ArchivedMessageMetadata, ArchivedMessageMetadataContents, ArchivedMetadataHeaderValue,
-        ArchivedMetadataPartType, DecodedParts, MESSAGE_RECEIVED_MASK, MessageData,
-        MessageMetadata, MetadataHeaderName, PART_ENCODING_PROBLEM,
+    message::{
+        history::{EmailChange, EmailChangeHistory, EmailChangeProtocol},
+        metadata::{
+            ArchivedMessageMetadata, ArchivedMessageMetadataContents, ArchivedMetadataHeaderValue,
+            ArchivedMetadataPartType, DecodedParts, MESSAGE_RECEIVED_MASK, MessageData,
+            MessageMetadata, MetadataHeaderName, PART_ENCODING_PROBLEM,
+        },
     },
 };
 use imap_proto::{
@@ -564,6 +567,12 @@ impl<T: SessionStream> SessionData<T> {
                     .imap_ctx(&arguments.tag, trc::location!())?;
                 let mut new_data = data.inner.to_builder();
                 new_data.keywords.push(Keyword::Seen);
+                let change = EmailChange::new(
+                    self.account_id,
+                    EmailChangeProtocol::Imap,
+                    data.inner,
+                    &new_data,
+                );
 
                 batch
                     .with_account_id(account_id)
@@ -574,8 +583,12 @@ impl<T: SessionStream> SessionData<T> {
                             .with_current(data)
                             .with_changes(new_data.seal()),
                     )
-                    .imap_ctx(&arguments.tag, trc::location!())?
-                    .commit_point();
+                    .imap_ctx(&arguments.tag, trc::location!())?;
+                self.server
+                    .log_email_change(&mut batch, account_id, id, change)
+                    .await
+                    .imap_ctx(&arguments.tag, trc::location!())?;
+                batch.commit_point();
             }
         }
 
@@ -177,15 +177,16 @@ impl<T: SessionStream> SessionData<T> {
         let mut list_items = Vec::with_capacity(10);
 
         // Add mailboxes
-        let mut added_shared_folder = false;
+        let mut added_roots = Vec::with_capacity(2);
         for account in self.mailboxes.lock().iter() {
             if let Some(prefix) = &account.prefix {
-                if !added_shared_folder {
-                    if !filter_subscribed
-                        && matches_pattern(&patterns, &self.server.core.email.shared_folder)
-                    {
+                let root = prefix
+                    .split_once('/')
+                    .map_or(prefix.as_str(), |(root, _)| root);
+                if !added_roots.contains(&root) {
+                    if !filter_subscribed && matches_pattern(&patterns, root) {
                         list_items.push(ListItem {
-                            mailbox_name: self.server.core.email.shared_folder.as_str().into(),
+                            mailbox_name: root.into(),
                             attributes: if include_children {
                                 vec![Attribute::HasChildren, Attribute::NoSelect]
                             } else {
@@ -194,7 +195,7 @@ impl<T: SessionStream> SessionData<T> {
                             tags: vec![],
                         });
                     }
-                    added_shared_folder = true;
+                    added_roots.push(root);
                 }
                 if !filter_subscribed && matches_pattern(&patterns, prefix) {
                     list_items.push(ListItem {
@@ -24,19 +24,29 @@ impl<T: SessionStream> Session<T> {
             Elapsed = trc::Value::Duration(0)
         );
 
+        // Group accounts publishing their mailboxes are listed in the shared namespace,
+        // while other accounts are listed in the other users namespace
+        let mut response = Response {
+            shared_prefix: None,
+            public_prefix: None,
+        };
+        let email = &self.server.core.email;
+        for account in self.state.session_data().mailboxes.lock().iter().skip(1) {
+            if account.prefix.as_ref().is_some_and(|prefix| {
+                prefix
+                    .strip_prefix(email.public_folder.as_str())
+                    .is_some_and(|name| name.starts_with('/'))
+            }) {
+                response.public_prefix = Some(email.public_folder.clone());
+            } else {
+                response.shared_prefix = Some(email.shared_folder.clone());
+            }
+        }
+
         self.write_bytes(
             StatusResponse::completed(Command::Namespace)
                 .with_tag(request.tag)
-                .serialize(
-                    Response {
-                        shared_prefix: if self.state.session_data().mailboxes.lock().len() > 1 {
-                            Some(self.server.core.email.shared_folder.as_str().into())
-                        } else {
-                            None
-                        },
-                    }
-                    .serialize(),
-                ),
+                .serialize(response.serialize()),
         )
         .await
     }
@@ -9,6 +9,7 @@ use crate::{
     spawn_op,
 };
 use common::{network::SessionStream, sharing::EffectiveAcl, storage::index::ObjectIndexBuilder};
+use email::cache::{MessageCacheFetch, mailbox::MailboxCacheAccess};
 use imap_proto::{
     Command, ResponseCode, StatusResponse, protocol::rename::Arguments, receiver::Request,
 };
@@ -127,6 +128,22 @@ impl<T: SessionStream> SessionData<T> {
                 .id(arguments.tag));
         }
 
+        // Folders protected by policy cannot be renamed
+        if self
+            .server
+            .get_cached_messages(params.account_id)
+            .await
+            .imap_ctx(&arguments.tag, trc::location!())?
+            .mailbox_by_id(&mailbox_id)
+            .is_some_and(|mailbox| self.server.core.email.is_protected_folder(mailbox))
+        {
+            return Err(trc::ImapEvent::Error
+                .into_err()
+                .details("This mailbox is protected and cannot be renamed.")
+                .code(ResponseCode::NoPerm)
+                .id(arguments.tag));
+        }
+
         // Get new mailbox name from path
         let new_mailbox_name = params.path.pop().unwrap();
 
@@ -89,11 +89,11 @@ impl<T: SessionStream> SessionData<T> {
             mailbox
         } else {
             // Some IMAP clients will try to get the status of a mailbox with the NoSelect flag
-            return if mailbox_name == self.server.core.email.shared_folder
+            return if self.server.core.email.is_shared_root(&mailbox_name)
                 || mailbox_name
                     .split_once('/')
                     .is_some_and(|(base_name, path)| {
-                        base_name == self.server.core.email.shared_folder && !path.contains('/')
+                        self.server.core.email.is_shared_root(base_name) && !path.contains('/')
                     })
             {
                 Ok(StatusItem {
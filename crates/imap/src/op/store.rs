@@ -13,7 +13,11 @@ use ahash::AHashSet;
 use common::{network::SessionStream, storage::index::ObjectIndexBuilder};
 use email::{
     mailbox::TRASH_ID,
-    message::{ingest::EmailIngest, metadata::MessageData},
+    message::{
+        history::{EmailChange, EmailChangeHistory, EmailChangeProtocol},
+        ingest::EmailIngest,
+        metadata::MessageData,
+    },
 };
 use imap_proto::{
     Command, ResponseCode, ResponseType, StatusResponse,
@@ -290,6 +294,12 @@ impl<T: SessionStream> SessionData<T> {
             }
 
             // Write changes
+            let change = EmailChange::new(
+                self.account_id,
+                EmailChangeProtocol::Imap,
+                data.inner,
+                &new_data,
+            );
             batch
                 .with_account_id(account_id)
                 .with_collection(Collection::Email)
@@ -300,6 +310,10 @@ impl<T: SessionStream> SessionData<T> {
                         .with_changes(new_data.seal()),
                 )
                 .imap_ctx(response.tag.as_ref().unwrap(), trc::location!())?;
+            self.server
+                .log_email_change(&mut batch, account_id, *id, change)
+                .await
+                .imap_ctx(response.tag.as_ref().unwrap(), trc::location!())?;
 
             // Add spam train task
             if let Some(learn_spam) = train_spam {
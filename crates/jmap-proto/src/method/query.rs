@@ -58,7 +58,7 @@ pub struct QueryResponse {
     pub limit: Option<usize>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Filter<T>
 where
     T: for<'de> DeserializeArguments<'de> + Default,
@@ -70,7 +70,7 @@ where
     Close,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Comparator<T>
 where
     T: for<'de> DeserializeArguments<'de> + Default,
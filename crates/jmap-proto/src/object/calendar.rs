@@ -39,6 +39,7 @@ pub enum CalendarProperty {
     TimeZone,
     ShareWith,
     MyRights,
+    Source,
 
     // Alert object properties
     When,
@@ -118,6 +119,7 @@ impl Property for CalendarProperty {
             CalendarProperty::TimeZone => "timeZone",
             CalendarProperty::ShareWith => "shareWith",
             CalendarProperty::MyRights => "myRights",
+            CalendarProperty::Source => "source",
             CalendarProperty::When => "when",
             CalendarProperty::Trigger => "trigger",
             CalendarProperty::Offset => "offset",
@@ -229,6 +231,7 @@ impl CalendarProperty {
             b"timeZone" => CalendarProperty::TimeZone,
             b"shareWith" => CalendarProperty::ShareWith,
             b"myRights" => CalendarProperty::MyRights,
+            b"source" => CalendarProperty::Source,
             b"mayReadFreeBusy" => CalendarProperty::Rights(CalendarRight::MayReadFreeBusy),
             b"mayReadItems" => CalendarProperty::Rights(CalendarRight::MayReadItems),
             b"mayWriteAll" => CalendarProperty::Rights(CalendarRight::MayWriteAll),
@@ -74,6 +74,9 @@ pub enum EmailProperty {
     HasAttachment,
     Preview,
 
+    // Extensions
+    ChangeHistory,
+
     // Other
     Keyword(Keyword),
     IdValue(Id),
@@ -167,6 +170,7 @@ impl Property for EmailProperty {
             EmailProperty::Value => "value",
             EmailProperty::IsEncodingProblem => "isEncodingProblem",
             EmailProperty::IsTruncated => "isTruncated",
+            EmailProperty::ChangeHistory => "changeHistory",
             EmailProperty::Header(header) => return header.to_string().into(),
             EmailProperty::Keyword(keyword) => return keyword.to_string().into(),
             EmailProperty::IdValue(id) => return id.to_string().into(),
@@ -260,7 +264,8 @@ impl EmailProperty {
                 "isEncodingProblem" => EmailProperty::IsEncodingProblem,
                 "isTruncated" => EmailProperty::IsTruncated,
                 "hasAttachment" => EmailProperty::HasAttachment,
-                "preview" => EmailProperty::Preview
+                "preview" => EmailProperty::Preview,
+                "changeHistory" => EmailProperty::ChangeHistory
         )
         .or_else(|| {
             if let Some(header) = value.strip_prefix("header:") {
@@ -510,7 +515,7 @@ impl JmapObject for Email {
     const ID_PROPERTY: Self::Property = EmailProperty::Id;
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum EmailFilter {
     InMailbox(Id),
     InMailboxOtherThan(Vec<Id>),
@@ -539,7 +544,7 @@ pub enum EmailFilter {
     _T(String),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum EmailComparator {
     ReceivedAt,
     Size,
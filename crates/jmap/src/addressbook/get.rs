@@ -6,23 +6,23 @@
 
 use crate::{api::acl::JmapRights, changes::state::JmapCacheState};
 use common::{Server, auth::AccessToken, sharing::EffectiveAcl};
-use groupware::{cache::GroupwareCache, contact::AddressBook};
+use groupware::{
+    cache::GroupwareCache,
+    contact::{AddressBook, DIRECTORY_ADDRESSBOOK_ID, directory::DirectoryAddressBook},
+};
 use jmap_proto::{
     method::get::{GetRequest, GetResponse},
     object::addressbook::{self, AddressBookProperty, AddressBookValue},
 };
 use jmap_tools::{Map, Value};
-use store::{
-    ValueKey,
-    roaring::RoaringBitmap,
-    write::{AlignedBytes, Archive, ValueClass},
-};
+use store::{ValueKey, roaring::RoaringBitmap, write::ValueClass};
 use trc::AddContext;
 use types::{
     acl::{Acl, AclGrant},
     collection::{Collection, SyncCollection},
     field::PrincipalField,
 };
+use utils::map::bitmap::Bitmap;
 
 pub trait AddressBookGet: Sync + Send {
     fn address_book_get(
@@ -103,12 +103,7 @@ impl AddressBookGet for Server {
                 continue;
             }
             let _address_book = if let Some(address_book) = self
-                .store()
-                .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
-                    account_id,
-                    Collection::AddressBook,
-                    document_id,
-                ))
+                .contact_archive(account_id, Collection::AddressBook, document_id)
                 .await?
             {
                 address_book
@@ -179,6 +174,26 @@ impl AddressBookGet for Server {
                             ),
                         );
                     }
+                    AddressBookProperty::MyRights if document_id == DIRECTORY_ADDRESSBOOK_ID => {
+                        // The global address list is read-only
+                        let mut acls = if access_token.is_shared(account_id) {
+                            address_book.acls.effective_acl(access_token)
+                        } else {
+                            Bitmap::all()
+                        };
+                        for acl in [
+                            Acl::AddItems,
+                            Acl::ModifyItems,
+                            Acl::RemoveItems,
+                            Acl::Delete,
+                        ] {
+                            acls.remove(acl);
+                        }
+                        result.insert_unchecked(
+                            AddressBookProperty::MyRights,
+                            JmapRights::rights::<addressbook::AddressBook>(acls),
+                        );
+                    }
                     AddressBookProperty::MyRights => {
                         result.insert_unchecked(
                             AddressBookProperty::MyRights,
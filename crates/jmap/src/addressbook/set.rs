@@ -9,7 +9,7 @@ use common::{Server, auth::AccessToken, sharing::EffectiveAcl};
 use groupware::{
     DestroyArchive,
     cache::GroupwareCache,
-    contact::{AddressBook, AddressBookPreferences, ContactCard},
+    contact::{AddressBook, AddressBookPreferences, ContactCard, DIRECTORY_ADDRESSBOOK_ID},
 };
 use http_proto::HttpSessionData;
 use jmap_proto::{
@@ -141,6 +141,13 @@ impl AddressBookSet for Server {
 
             // Obtain address book
             let document_id = id.document_id();
+            if document_id == DIRECTORY_ADDRESSBOOK_ID {
+                response.not_updated.append(
+                    id,
+                    SetError::forbidden().with_description("The global address list is read-only."),
+                );
+                continue 'update;
+            }
             let address_book_ = if let Some(address_book_) = self
                 .store()
                 .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
@@ -239,6 +246,16 @@ impl AddressBookSet for Server {
                     continue;
                 };
 
+                // The global address list is managed by the server
+                if document_id == DIRECTORY_ADDRESSBOOK_ID {
+                    response.not_destroyed.append(
+                        id,
+                        SetError::forbidden()
+                            .with_description("The global address list cannot be deleted."),
+                    );
+                    continue;
+                }
+
                 let Some(address_book_) = self
                     .store()
                     .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
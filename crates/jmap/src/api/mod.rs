@@ -112,9 +112,12 @@ impl ToRequestError for trc::Error {
                 trc::SecurityEvent::Unauthorized | trc::SecurityEvent::IpUnauthorized => {
                     RequestError::forbidden()
                 }
-                trc::SecurityEvent::IpBlockExpired | trc::SecurityEvent::IpAllowExpired => {
-                    RequestError::internal_server_error()
-                }
+                trc::SecurityEvent::IpBlockExpired
+                | trc::SecurityEvent::IpAllowExpired
+                | trc::SecurityEvent::TlsFingerprintBlocked
+                | trc::SecurityEvent::LoginAnomaly
+                | trc::SecurityEvent::FirewallSync
+                | trc::SecurityEvent::FirewallSyncError => RequestError::internal_server_error(),
             },
             trc::EventType::Resource(cause) => match cause {
                 trc::ResourceEvent::NotFound => RequestError::not_found(),
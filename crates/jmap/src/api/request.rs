@@ -50,7 +50,8 @@ use http_proto::HttpSessionData;
 use jmap_proto::{
     request::{
         Call, CopyRequestMethod, GetRequestMethod, ParseRequestMethod, QueryRequestMethod, Request,
-        RequestMethod, SetRequestMethod, method::MethodName,
+        RequestMethod, SetRequestMethod,
+        method::{MethodFunction, MethodName},
     },
     response::{Response, ResponseMethod, SetResponseMethod},
 };
@@ -65,7 +66,7 @@ pub trait RequestHandler: Sync + Send {
         request: Request<'x>,
         access_token: &AccessToken,
         session: &HttpSessionData,
-    ) -> impl Future<Output = Response<'x>> + Send;
+    ) -> impl Future<Output = trc::Result<Response<'x>>> + Send;
 
     fn handle_method_call<'x>(
         &self,
@@ -83,7 +84,7 @@ impl RequestHandler for Server {
         request: Request<'x>,
         access_token: &AccessToken,
         session: &HttpSessionData,
-    ) -> Response<'x> {
+    ) -> trc::Result<Response<'x>> {
         let add_created_ids = request.created_ids.is_some();
         let mut response = Response::new(
             access_token.state(),
@@ -105,6 +106,19 @@ impl RequestHandler for Server {
             loop {
                 let mut next_call = None;
 
+                // Wait for a scheduler slot, shedding the whole request if
+                // nothing has been processed yet
+                let _permit = match self.is_jmap_call_allowed(access_token).await {
+                    Ok(permit) => permit,
+                    Err(error) if response.method_responses.is_empty() => {
+                        return Err(error);
+                    }
+                    Err(error) => {
+                        response.push_error(call.id, error);
+                        break;
+                    }
+                };
+
                 // Add response
                 let method_name = call.name.as_str();
                 match self
@@ -181,6 +195,22 @@ impl RequestHandler for Server {
                             _ => {}
                         }
 
+                        if matches!(
+                            call.name.fnc,
+                            MethodFunction::Set
+                                | MethodFunction::Copy
+                                | MethodFunction::Import
+                                | MethodFunction::Upload
+                        ) {
+                            self.audit_impersonated_write(
+                                access_token,
+                                session.remote_ip,
+                                "JmapMethod",
+                                &method_name,
+                            )
+                            .await;
+                        }
+
                         response.push_response(call.id, call.name, method_response);
                     }
                     Err(error) => {
@@ -212,7 +242,7 @@ impl RequestHandler for Server {
             response.created_ids.clear();
         }
 
-        response
+        Ok(response)
     }
 
     async fn handle_method_call<'x>(
@@ -560,6 +590,7 @@ impl RequestHandler for Server {
                         *req,
                         access_token,
                         session,
+                        None,
                     ))
                     .await?
                     .into()
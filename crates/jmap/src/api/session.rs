@@ -8,7 +8,10 @@ use common::{Server, auth::AccessToken};
 use jmap_proto::request::capability::{
     Account, Capabilities, Capability, EmptyCapabilities, Session,
 };
-use registry::schema::enums::Permission;
+use registry::{
+    schema::enums::{JmapCapability, Permission},
+    types::EnumImpl,
+};
 use std::future::Future;
 use trc::AddContext;
 use types::id::Id;
@@ -32,6 +35,17 @@ impl SessionHandler for Server {
         session.set_state(access_token.state());
         let account_capabilities = &self.core.jmap.capabilities.account;
 
+        // Remove capabilities disabled for the account or its tenant
+        let disabled_capabilities = self
+            .disabled_jmap_capabilities(access_token.account_id())
+            .await
+            .caused_by(trc::location!())?;
+        for capability in Capability::all_capabilities() {
+            if capability.is_disabled(disabled_capabilities) {
+                session.capabilities.remove(capability);
+            }
+        }
+
         // Set primary account
         let account = self
             .account(access_token.account_id())
@@ -45,7 +59,7 @@ impl SessionHandler for Server {
             is_read_only: false,
             account_capabilities: VecMap::with_capacity(account_capabilities.len()),
         };
-        for capability in access_token.account_capabilities() {
+        for capability in access_token.account_capabilities(disabled_capabilities) {
             session.primary_accounts.append(capability, account_id);
             account.account_capabilities.append(
                 capability,
@@ -69,7 +83,7 @@ impl SessionHandler for Server {
                 is_read_only: false,
                 account_capabilities: VecMap::with_capacity(account_capabilities.len()),
             };
-            for capability in access_token.account_capabilities() {
+            for capability in access_token.account_capabilities(disabled_capabilities) {
                 account.account_capabilities.append(
                     capability,
                     account_capabilities
@@ -86,14 +100,21 @@ impl SessionHandler for Server {
 }
 
 trait AccountCapabilities {
-    fn account_capabilities(&self) -> impl Iterator<Item = Capability>;
+    fn account_capabilities(&self, disabled: u32) -> impl Iterator<Item = Capability>;
+}
+
+pub trait CapabilityToggle {
+    fn is_disabled(&self, disabled: u32) -> bool;
 }
 
 impl AccountCapabilities for AccessToken {
-    fn account_capabilities(&self) -> impl Iterator<Item = Capability> {
+    fn account_capabilities(&self, disabled: u32) -> impl Iterator<Item = Capability> {
         Capability::all_capabilities()
             .iter()
             .filter(move |capability| {
+                if capability.is_disabled(disabled) {
+                    return false;
+                }
                 let permission = match capability {
                     Capability::Mail | Capability::MailShare => Permission::JmapEmailGet,
                     Capability::Submission => Permission::JmapEmailSubmissionCreate,
@@ -117,3 +138,25 @@ impl AccountCapabilities for AccessToken {
             .copied()
     }
 }
+
+impl CapabilityToggle for Capability {
+    fn is_disabled(&self, disabled: u32) -> bool {
+        let capability = match self {
+            Capability::Mail | Capability::MailShare => JmapCapability::Mail,
+            Capability::Submission => JmapCapability::Submission,
+            Capability::VacationResponse => JmapCapability::VacationResponse,
+            Capability::Contacts | Capability::ContactsParse => JmapCapability::Contacts,
+            Capability::Calendars | Capability::CalendarsParse => JmapCapability::Calendars,
+            Capability::WebSocket => JmapCapability::WebSocket,
+            Capability::Sieve => JmapCapability::Sieve,
+            Capability::Blob => JmapCapability::Blob,
+            Capability::Quota => JmapCapability::Quota,
+            Capability::Principals
+            | Capability::PrincipalsOwner
+            | Capability::PrincipalsAvailability => JmapCapability::Principals,
+            Capability::FileNode => JmapCapability::FileNode,
+            Capability::Core | Capability::Stalwart => return false,
+        };
+        disabled & (1 << capability.to_id()) != 0
+    }
+}
@@ -47,11 +47,21 @@ impl BlobDownload for Server {
                     .await
                     .caused_by(trc::location!())
             } else {
-                let blob = self
+                let mut blob = self
                     .blob_store()
                     .get_blob(blob_id.hash.as_slice(), 0..usize::MAX)
                     .await
                     .caused_by(trc::location!());
+                if matches!(blob, Ok(None))
+                    && blob_id.class.is_superuser()
+                    && let Some(queue_blob) = &self.core.storage.queue_blob
+                {
+                    // Queued messages may be stored on a separate blob store
+                    blob = queue_blob
+                        .get_blob(blob_id.hash.as_slice(), 0..usize::MAX)
+                        .await
+                        .caused_by(trc::location!());
+                }
                 match (&blob_id.class, blob) {
                     (
                         BlobClass::Linked {
@@ -11,7 +11,7 @@ use groupware::{
     cache::GroupwareCache,
     calendar::{
         ALERT_EMAIL, ALERT_RELATIVE_TO_END, ArchivedDefaultAlert, CALENDAR_INVISIBLE,
-        CALENDAR_SUBSCRIBED, Calendar,
+        CALENDAR_SUBSCRIBED, Calendar, CalendarSubscription,
     },
 };
 use jmap_proto::{
@@ -28,8 +28,9 @@ use trc::AddContext;
 use types::{
     acl::{Acl, AclGrant},
     collection::{Collection, SyncCollection},
-    field::PrincipalField,
+    field::{CalendarField, PrincipalField},
 };
+use utils::map::bitmap::Bitmap;
 
 pub trait CalendarGet: Sync + Send {
     fn calendar_get(
@@ -245,6 +246,27 @@ impl CalendarGet for Server {
                             ),
                         );
                     }
+                    CalendarProperty::MyRights if calendar.is_subscription() => {
+                        // Subscribed calendars are read-only
+                        let mut acls = if access_token.is_shared(account_id) {
+                            calendar.acls.effective_acl(access_token)
+                        } else {
+                            Bitmap::all()
+                        };
+                        for acl in [
+                            Acl::AddItems,
+                            Acl::ModifyItems,
+                            Acl::ModifyItemsOwn,
+                            Acl::ModifyPrivateProperties,
+                            Acl::ModifyRSVP,
+                        ] {
+                            acls.remove(acl);
+                        }
+                        result.insert_unchecked(
+                            CalendarProperty::MyRights,
+                            JmapRights::rights::<calendar::Calendar>(acls),
+                        );
+                    }
                     CalendarProperty::MyRights => {
                         result.insert_unchecked(
                             CalendarProperty::MyRights,
@@ -257,6 +279,29 @@ impl CalendarGet for Server {
                             },
                         );
                     }
+                    CalendarProperty::Source => {
+                        let source = if calendar.is_subscription() {
+                            self.store()
+                                .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+                                    account_id,
+                                    Collection::Calendar,
+                                    document_id,
+                                    CalendarField::Subscription,
+                                ))
+                                .await
+                                .caused_by(trc::location!())?
+                                .map(|subscription| {
+                                    subscription
+                                        .unarchive::<CalendarSubscription>()
+                                        .map(|subscription| subscription.url.to_string())
+                                })
+                                .transpose()
+                                .caused_by(trc::location!())?
+                        } else {
+                            None
+                        };
+                        result.insert_unchecked(CalendarProperty::Source, source);
+                    }
                     property => {
                         result.insert_unchecked(property.clone(), Value::Null);
                     }
@@ -13,7 +13,8 @@ use groupware::{
     calendar::{
         ALERT_EMAIL, ALERT_RELATIVE_TO_END, ALERT_WITH_TIME, CALENDAR_AVAILABILITY_ALL,
         CALENDAR_AVAILABILITY_ATTENDING, CALENDAR_AVAILABILITY_NONE, CALENDAR_INVISIBLE,
-        CALENDAR_SUBSCRIBED, Calendar, CalendarEvent, CalendarPreferences, DefaultAlert, Timezone,
+        CALENDAR_SUBSCRIBED, CALENDAR_SUBSCRIPTION, Calendar, CalendarEvent, CalendarPreferences,
+        CalendarSubscription, DefaultAlert, Timezone,
     },
 };
 use http_proto::HttpSessionData;
@@ -26,10 +27,11 @@ use jmap_proto::{
 };
 use jmap_tools::{JsonPointerItem, Key, Map, Value};
 use rand::{Rng, distr::Alphanumeric};
+use registry::schema::enums::Permission;
 use store::{
     SerializeInfallible, ValueKey,
     ahash::AHashSet,
-    write::{AlignedBytes, Archive, BatchBuilder, ValueClass},
+    write::{AlignedBytes, Archive, BatchBuilder, ValueClass, now},
 };
 use trc::AddContext;
 use types::{
@@ -69,7 +71,7 @@ impl CalendarSet for Server {
 
         // Process creates
         let mut batch = BatchBuilder::new();
-        'create: for (id, object) in request.unwrap_create() {
+        'create: for (id, mut object) in request.unwrap_create() {
             if is_shared {
                 response.not_created.append(
                     id,
@@ -79,6 +81,35 @@ impl CalendarSet for Server {
                 continue 'create;
             }
 
+            // Obtain subscription source
+            let source = match object
+                .as_object_mut()
+                .and_then(|object| object.remove(&Key::Property(CalendarProperty::Source)))
+            {
+                Some(Value::Str(url)) if is_valid_subscription_url(&url) => {
+                    if !access_token.has_permission(Permission::CalendarSubscribe) {
+                        response.not_created.append(
+                            id,
+                            SetError::forbidden().with_description(
+                                "You are not allowed to subscribe to external calendars.",
+                            ),
+                        );
+                        continue 'create;
+                    }
+                    Some(url.into_owned())
+                }
+                Some(Value::Null) | None => None,
+                Some(_) => {
+                    response.not_created.append(
+                        id,
+                        SetError::invalid_properties()
+                            .with_property(CalendarProperty::Source)
+                            .with_description("Invalid subscription URL."),
+                    );
+                    continue 'create;
+                }
+            };
+
             let mut calendar = Calendar {
                 name: rand::rng()
                     .sample_iter(Alphanumeric)
@@ -98,6 +129,9 @@ impl CalendarSet for Server {
                 response.not_created.append(id, err);
                 continue 'create;
             }
+            if source.is_some() {
+                calendar.preferences[0].flags |= CALENDAR_SUBSCRIPTION;
+            }
 
             // Validate ACLs
             if !calendar.acls.is_empty() {
@@ -125,6 +159,15 @@ impl CalendarSet for Server {
                     &mut batch,
                 )
                 .caused_by(trc::location!())?;
+            if let Some(url) = source {
+                CalendarSubscription {
+                    url,
+                    next_refresh: now() as i64,
+                    ..Default::default()
+                }
+                .write(account_id, document_id, &mut batch)
+                .caused_by(trc::location!())?;
+            }
 
             if let Some(MaybeIdReference::Reference(id_ref)) =
                 &request.arguments.on_success_set_is_default
@@ -561,6 +604,16 @@ fn update_calendar(
     Ok(has_acl_changes)
 }
 
+fn is_valid_subscription_url(url: &str) -> bool {
+    url.len() <= 2048
+        && url.split_once("://").is_some_and(|(scheme, rest)| {
+            ["http", "https", "webcal", "webcals"]
+                .iter()
+                .any(|s| scheme.eq_ignore_ascii_case(s))
+                && !rest.is_empty()
+        })
+}
+
 fn value_to_default_alert(
     id: String,
     value: Map<'_, CalendarProperty, CalendarValue>,
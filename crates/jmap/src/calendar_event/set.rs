@@ -20,7 +20,7 @@ use common::{
     auth::{AccessToken, AccountInfo},
 };
 use groupware::{
-    DestroyArchive,
+    DavCalendarResource, DestroyArchive,
     cache::GroupwareCache,
     calendar::{
         ALERT_EMAIL, ALERT_RELATIVE_TO_END, ArchivedDefaultAlert, Calendar, CalendarEvent,
@@ -100,7 +100,7 @@ impl CalendarEventSet for Server {
         let will_destroy = request.unwrap_destroy().into_valid().collect::<Vec<_>>();
 
         // Obtain calendarIds
-        let (can_add_calendars, can_delete_calendars, can_modify_calendars) =
+        let (mut can_add_calendars, mut can_delete_calendars, mut can_modify_calendars) =
             if access_token.is_shared(account_id) {
                 (
                     cache
@@ -117,6 +117,22 @@ impl CalendarEventSet for Server {
                 (None, None, None)
             };
 
+        // Events in subscribed calendars are read-only
+        let subscription_ids = cache
+            .document_ids(true)
+            .filter(|calendar_id| cache.is_calendar_subscription(*calendar_id))
+            .collect::<RoaringBitmap>();
+        if !subscription_ids.is_empty() {
+            for calendar_ids in [
+                &mut can_add_calendars,
+                &mut can_delete_calendars,
+                &mut can_modify_calendars,
+            ] {
+                *calendar_ids.get_or_insert_with(|| cache.document_ids(true).collect()) -=
+                    &subscription_ids;
+            }
+        }
+
         // Process creates
         let mut batch = BatchBuilder::new();
         let send_scheduling_messages = request.arguments.send_scheduling_messages.unwrap_or(false);
@@ -12,16 +12,20 @@ use crate::{
     mailbox::query::MailboxQuery, share_notification::query::ShareNotificationQuery,
     submission::query::EmailSubmissionQuery,
 };
-use common::{Server, auth::AccessToken};
+use common::{EmailQueryCache, Server, auth::AccessToken};
 use jmap_proto::{
     method::{
         changes::{ChangesRequest, ChangesResponse},
+        query::QueryRequest,
         query_changes::{AddedItem, QueryChangesRequest, QueryChangesResponse},
     },
-    object::{JmapObject, NullObject},
+    object::{JmapObject, NullObject, email::Email},
     request::{QueryChangesRequestMethod, method::MethodObject},
+    types::state::State,
 };
 use std::future::Future;
+use store::ahash::AHashSet;
+use types::id::Id;
 
 pub trait QueryChanges: Sync + Send {
     fn query_changes(
@@ -71,7 +75,35 @@ impl QueryChanges for Server {
                         .as_ref()
                         .is_some_and(|sort| sort.iter().any(|s| !s.is_immutable()));
 
-                results = self.email_query((*request).into(), access_token).await?;
+                // When the results at the client's query state are still cached,
+                // the changes are calculated by comparing them with the current results
+                let query: QueryRequest<Email> = (*request).into();
+                let since_change_id = match changes.old_state {
+                    State::Exact(change_id) => Some(change_id),
+                    _ => None,
+                };
+                let previous = self
+                    .email_query_cached(&query, access_token)
+                    .await?
+                    .filter(|previous| Some(previous.emails_change_id) == since_change_id);
+                results = self.email_query(query.clone(), access_token).await?;
+
+                if let Some(previous) = previous
+                    && let Some(current) = self.email_query_cached(&query, access_token).await?
+                {
+                    if has_changes {
+                        diff_query_results(
+                            &mut response,
+                            &previous,
+                            &current,
+                            is_mutable.then_some(&changes.updated),
+                            up_to_id,
+                        );
+                    }
+                    response.total = results.total;
+
+                    return Ok(response);
+                }
             }
             QueryChangesRequestMethod::Mailbox(mut request) => {
                 // Query changes
@@ -271,6 +303,47 @@ impl QueryChanges for Server {
     }
 }
 
+fn diff_query_results(
+    response: &mut QueryChangesResponse,
+    previous: &EmailQueryCache,
+    current: &EmailQueryCache,
+    updated: Option<&Vec<Id>>,
+    up_to_id: Option<Id>,
+) {
+    let previous_ids = previous
+        .ids
+        .iter()
+        .map(|&(thread_id, document_id)| Id::from_parts(thread_id, document_id))
+        .collect::<Vec<_>>();
+    let current_ids = current
+        .ids
+        .iter()
+        .map(|&(thread_id, document_id)| Id::from_parts(thread_id, document_id))
+        .collect::<Vec<_>>();
+    let previous_set = previous_ids.iter().collect::<AHashSet<_>>();
+    let current_set = current_ids.iter().collect::<AHashSet<_>>();
+
+    // Updated items may have changed position in mutable queries, so they are
+    // removed and added back at their current position
+    let updated = updated
+        .map(|updated| updated.iter().collect::<AHashSet<_>>())
+        .unwrap_or_default();
+    let is_updated = |id: &Id| updated.contains(id);
+    for id in &previous_ids {
+        if !current_set.contains(id) || is_updated(id) {
+            response.removed.push(*id);
+        }
+    }
+    for (index, id) in current_ids.iter().enumerate() {
+        if !previous_set.contains(id) || is_updated(id) {
+            response.added.push(AddedItem::new(*id, index));
+        }
+        if up_to_id == Some(*id) {
+            break;
+        }
+    }
+}
+
 fn build_changes_request<T: JmapObject>(req: &QueryChangesRequest<T>) -> ChangesRequest {
     ChangesRequest {
         account_id: req.account_id,
@@ -6,7 +6,10 @@
 
 use crate::{changes::state::JmapCacheState, contact::set::ContactCardSet};
 use common::{Server, auth::AccessToken};
-use groupware::{cache::GroupwareCache, contact::ContactCard};
+use groupware::{
+    cache::GroupwareCache,
+    contact::{ContactCard, directory::DirectoryAddressBook},
+};
 use http_proto::HttpSessionData;
 use jmap_proto::{
     error::set::SetError,
@@ -22,11 +25,7 @@ use jmap_proto::{
     },
     types::state::State,
 };
-use store::{
-    ValueKey,
-    roaring::RoaringBitmap,
-    write::{AlignedBytes, Archive, BatchBuilder},
-};
+use store::{roaring::RoaringBitmap, write::BatchBuilder};
 use trc::AddContext;
 use types::{
     acl::Acl,
@@ -120,12 +119,7 @@ impl JmapContactCardCopy for Server {
             }
 
             let Some(_contact) = self
-                .store()
-                .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
-                    from_account_id,
-                    Collection::ContactCard,
-                    from_contact_id,
-                ))
+                .contact_archive(from_account_id, Collection::ContactCard, from_contact_id)
                 .await?
             else {
                 response.not_created.append(
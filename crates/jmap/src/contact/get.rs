@@ -7,18 +7,17 @@
 use crate::changes::state::JmapCacheState;
 use calcard::jscontact::{JSContactProperty, JSContactValue, import::ConversionOptions};
 use common::{Server, auth::AccessToken};
-use groupware::{cache::GroupwareCache, contact::ContactCard};
+use groupware::{
+    cache::GroupwareCache,
+    contact::{ContactCard, directory::DirectoryAddressBook},
+};
 use jmap_proto::{
     method::get::{GetRequest, GetResponse},
     object::contact,
     request::reference::MaybeResultReference,
 };
 use jmap_tools::{Map, Value};
-use store::{
-    ValueKey,
-    roaring::RoaringBitmap,
-    write::{AlignedBytes, Archive},
-};
+use store::roaring::RoaringBitmap;
 use trc::AddContext;
 use types::{
     acl::Acl,
@@ -106,12 +105,7 @@ impl ContactCardGet for Server {
             }
 
             let _contact = if let Some(contact) = self
-                .store()
-                .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
-                    account_id,
-                    Collection::ContactCard,
-                    document_id,
-                ))
+                .contact_archive(account_id, Collection::ContactCard, document_id)
                 .await?
             {
                 contact
@@ -5,8 +5,12 @@
  */
 
 use crate::{api::query::QueryResponseBuilder, changes::state::JmapCacheState};
+use calcard::vcard::{VCard, VCardProperty};
 use common::{Server, auth::AccessToken};
-use groupware::cache::GroupwareCache;
+use groupware::{
+    cache::GroupwareCache,
+    contact::{DIRECTORY_ADDRESSBOOK_ID, directory::DirectoryAddressBook},
+};
 use jmap_proto::{
     method::query::{Filter, QueryRequest, QueryResponse},
     object::{
@@ -123,6 +127,24 @@ impl ContactCardQuery for Server {
                 .caused_by(trc::location!())?;
         }
 
+        // Cards in the global address list are not indexed, they are matched in memory
+        let mut directory_results = Vec::new();
+        if !access_token.is_shared(account_id) && cache.has_container_id(&DIRECTORY_ADDRESSBOOK_ID)
+        {
+            let cards = self
+                .directory_cards(self.account(account_id).await?.as_ref())
+                .await?;
+            for document_id in cache.children_ids(DIRECTORY_ADDRESSBOOK_ID) {
+                if let Ok(idx) = cards
+                    .cards
+                    .binary_search_by_key(&document_id, |card| card.document_id)
+                    && directory_card_matches(&cards.cards[idx].card, &request.filter)
+                {
+                    directory_results.push(document_id);
+                }
+            }
+        }
+
         for cond in std::mem::take(&mut request.filter) {
             match cond {
                 Filter::Property(cond) => match cond {
@@ -322,13 +344,13 @@ impl ContactCardQuery for Server {
             .await?;
 
         let mut response = QueryResponseBuilder::new(
-            results.len(),
+            results.len() + directory_results.len(),
             self.core.jmap.query_max_results,
             cache.get_state(false),
             &request,
         );
 
-        for document_id in results {
+        for document_id in results.into_iter().chain(directory_results) {
             if !response.add(0, document_id) {
                 break;
             }
@@ -369,3 +391,101 @@ impl ContactCardQuery for Server {
         response.build()
     }
 }
+
+fn directory_card_matches(card: &VCard, filter: &[Filter<ContactCardFilter>]) -> bool {
+    let mut stack: Vec<(Option<&Filter<ContactCardFilter>>, Vec<bool>)> = vec![(None, Vec::new())];
+
+    for cond in filter {
+        match cond {
+            Filter::Property(cond) => {
+                let is_match = match cond {
+                    ContactCardFilter::InAddressBook(MaybeInvalid::Value(id)) => {
+                        id.document_id() == DIRECTORY_ADDRESSBOOK_ID
+                    }
+                    ContactCardFilter::Name(value)
+                    | ContactCardFilter::NameGiven(value)
+                    | ContactCardFilter::NameSurname(value)
+                    | ContactCardFilter::NameSurname2(value) => {
+                        card_contains(card, &[VCardProperty::N, VCardProperty::Fn], value)
+                    }
+                    ContactCardFilter::Nickname(value) => {
+                        card_contains(card, &[VCardProperty::Nickname], value)
+                    }
+                    ContactCardFilter::Organization(value) => {
+                        card_contains(card, &[VCardProperty::Org], value)
+                    }
+                    ContactCardFilter::Phone(value) => {
+                        card_contains(card, &[VCardProperty::Tel], value)
+                    }
+                    ContactCardFilter::OnlineService(value) => card_contains(
+                        card,
+                        &[VCardProperty::Impp, VCardProperty::Socialprofile],
+                        value,
+                    ),
+                    ContactCardFilter::Address(value) => {
+                        card_contains(card, &[VCardProperty::Adr], value)
+                    }
+                    ContactCardFilter::Note(value) => {
+                        card_contains(card, &[VCardProperty::Note], value)
+                    }
+                    ContactCardFilter::HasMember(value) => {
+                        card_contains(card, &[VCardProperty::Member], value)
+                    }
+                    ContactCardFilter::Email(value) => card_contains(
+                        card,
+                        &[VCardProperty::Email],
+                        &sanitize_email(value).unwrap_or_else(|| value.clone()),
+                    ),
+                    ContactCardFilter::Kind(value) => card
+                        .properties(&VCardProperty::Kind)
+                        .flat_map(|e| e.values.iter().filter_map(|v| v.as_text()))
+                        .any(|v| v.eq_ignore_ascii_case(value)),
+                    ContactCardFilter::Uid(value) => card.uid() == Some(value.as_str()),
+                    ContactCardFilter::Text(value) => card_contains(
+                        card,
+                        &[
+                            VCardProperty::N,
+                            VCardProperty::Fn,
+                            VCardProperty::Nickname,
+                            VCardProperty::Org,
+                            VCardProperty::Email,
+                            VCardProperty::Tel,
+                            VCardProperty::Impp,
+                            VCardProperty::Socialprofile,
+                            VCardProperty::Adr,
+                            VCardProperty::Note,
+                        ],
+                        value,
+                    ),
+                    _ => false,
+                };
+                stack.last_mut().unwrap().1.push(is_match);
+            }
+            Filter::And | Filter::Or | Filter::Not => {
+                stack.push((Some(cond), Vec::new()));
+            }
+            Filter::Close => {
+                if stack.len() > 1 {
+                    let (op, results) = stack.pop().unwrap();
+                    let is_match = match op {
+                        Some(Filter::Or) => results.iter().any(|r| *r),
+                        Some(Filter::Not) => !results.iter().any(|r| *r),
+                        _ => results.iter().all(|r| *r),
+                    };
+                    stack.last_mut().unwrap().1.push(is_match);
+                }
+            }
+        }
+    }
+
+    stack.into_iter().next().unwrap().1.into_iter().all(|r| r)
+}
+
+fn card_contains(card: &VCard, properties: &[VCardProperty], value: &str) -> bool {
+    let value = value.to_lowercase();
+    card.entries
+        .iter()
+        .filter(|e| properties.contains(&e.name))
+        .flat_map(|e| e.values.iter().filter_map(|v| v.as_text()))
+        .any(|v| v.to_lowercase().contains(&value))
+}
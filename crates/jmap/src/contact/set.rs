@@ -10,7 +10,11 @@ use common::{
     DavName, DavResources, Server,
     auth::{AccessToken, AccountCache},
 };
-use groupware::{DestroyArchive, cache::GroupwareCache, contact::ContactCard};
+use groupware::{
+    DestroyArchive,
+    cache::GroupwareCache,
+    contact::{ContactCard, DIRECTORY_ADDRESSBOOK_ID, directory::is_directory_card},
+};
 use http_proto::HttpSessionData;
 use jmap_proto::{
     error::set::SetError,
@@ -76,7 +80,7 @@ impl ContactCardSet for Server {
         let will_destroy = request.unwrap_destroy().into_valid().collect::<Vec<_>>();
 
         // Obtain addressBookIds
-        let (can_add_address_books, can_delete_address_books, can_modify_address_books) =
+        let (mut can_add_address_books, mut can_delete_address_books, mut can_modify_address_books) =
             if access_token.is_shared(account_id) {
                 (
                     cache
@@ -93,6 +97,19 @@ impl ContactCardSet for Server {
                 (None, None, None)
             };
 
+        // Cards in the global address list are read-only
+        if cache.has_container_id(&DIRECTORY_ADDRESSBOOK_ID) {
+            for address_book_ids in [
+                &mut can_add_address_books,
+                &mut can_delete_address_books,
+                &mut can_modify_address_books,
+            ] {
+                address_book_ids
+                    .get_or_insert_with(|| cache.document_ids(true).collect())
+                    .remove(DIRECTORY_ADDRESSBOOK_ID);
+            }
+        }
+
         // Process creates
         let mut batch = BatchBuilder::new();
         'create: for (id, object) in request.unwrap_create() {
@@ -129,6 +146,14 @@ impl ContactCardSet for Server {
 
             // Obtain contact card
             let document_id = id.document_id();
+            if is_directory_card(document_id) {
+                response.not_updated.append(
+                    id,
+                    SetError::forbidden()
+                        .with_description("Cards in the global address list are read-only."),
+                );
+                continue 'update;
+            }
             let contact_card_ = if let Some(contact_card_) = self
                 .store()
                 .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
@@ -293,6 +318,14 @@ impl ContactCardSet for Server {
                 response.not_destroyed.append(id, SetError::not_found());
                 continue;
             };
+            if is_directory_card(document_id) {
+                response.not_destroyed.append(
+                    id,
+                    SetError::forbidden()
+                        .with_description("Cards in the global address list are read-only."),
+                );
+                continue 'destroy;
+            }
 
             let Some(contact_card_) = self
                 .store()
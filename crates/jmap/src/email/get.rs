@@ -12,9 +12,13 @@ use crate::{changes::state::JmapCacheState, email::headers::HeaderToValue};
 use common::{Server, auth::AccessToken};
 use email::{
     cache::{MessageCacheFetch, email::MessageCacheAccess},
-    message::metadata::{
-        ArchivedMetadataPartType, MESSAGE_HAS_ATTACHMENT, MESSAGE_RECEIVED_MASK, MessageMetadata,
-        MetadataHeaderName, PART_ENCODING_PROBLEM,
+    message::{
+        history::{EmailChange, EmailChangeHistory},
+        metadata::{
+            ArchivedMetadataPartType, MESSAGE_HAS_ATTACHMENT, MESSAGE_RECEIVED_MASK,
+            MessageMetadata, MetadataHeaderName, PART_ENCODING_PROBLEM,
+        },
+        summary::{MessageSummary, MessageSummaryFetch},
     },
 };
 use jmap_proto::{
@@ -150,12 +154,146 @@ impl EmailGet for Server {
             }
         }
 
+        // Check if the compact summary holds all the requested properties
+        let use_summary = properties.iter().all(|property| {
+            matches!(
+                property,
+                EmailProperty::Id
+                    | EmailProperty::ThreadId
+                    | EmailProperty::BlobId
+                    | EmailProperty::MailboxIds
+                    | EmailProperty::Keywords
+                    | EmailProperty::Size
+                    | EmailProperty::ReceivedAt
+                    | EmailProperty::Preview
+                    | EmailProperty::HasAttachment
+                    | EmailProperty::ChangeHistory
+            ) || summary_header(property).is_some()
+        });
+
         for id in ids {
             // Obtain the email object
             if !message_ids.contains(id.document_id()) {
                 response.not_found.push(id);
                 continue;
             }
+
+            if use_summary {
+                let summary_ = match self
+                    .get_message_summary(account_id, id.document_id())
+                    .await?
+                {
+                    Some(summary) => summary,
+                    None => {
+                        response.not_found.push(id);
+                        continue;
+                    }
+                };
+                let summary = summary_
+                    .unarchive::<MessageSummary>()
+                    .caused_by(trc::location!())?;
+                let data = match cache.email_by_id(&id.document_id()) {
+                    Some(data) => data,
+                    None => {
+                        response.not_found.push(id);
+                        continue;
+                    }
+                };
+                let blob_id = BlobId {
+                    hash: BlobHash::from(&summary.blob_hash),
+                    class: BlobClass::Linked {
+                        account_id,
+                        collection: Collection::Email.into(),
+                        document_id: id.document_id(),
+                    },
+                    section: None,
+                };
+
+                let mut email: Map<'_, EmailProperty, EmailValue> =
+                    Map::with_capacity(properties.len());
+                for property in &properties {
+                    match property {
+                        EmailProperty::Id => {
+                            email.insert_unchecked(EmailProperty::Id, Id::from(*id));
+                        }
+                        EmailProperty::ThreadId => {
+                            email.insert_unchecked(
+                                EmailProperty::ThreadId,
+                                Id::from(id.prefix_id()),
+                            );
+                        }
+                        EmailProperty::BlobId => {
+                            email.insert_unchecked(EmailProperty::BlobId, blob_id.clone());
+                        }
+                        EmailProperty::MailboxIds => {
+                            let mut obj = Map::with_capacity(data.mailboxes.len());
+                            for id in data.mailboxes.iter() {
+                                obj.insert_unchecked(
+                                    EmailProperty::IdValue(Id::from(id.mailbox_id)),
+                                    true,
+                                );
+                            }
+                            email.insert_unchecked(property.clone(), Value::Object(obj));
+                        }
+                        EmailProperty::Keywords => {
+                            let mut obj = Map::with_capacity(2);
+                            for keyword in cache.expand_keywords(data) {
+                                obj.insert_unchecked(EmailProperty::Keyword(keyword), true);
+                            }
+                            email.insert_unchecked(property.clone(), Value::Object(obj));
+                        }
+                        EmailProperty::Size => {
+                            email.insert_unchecked(EmailProperty::Size, data.size);
+                        }
+                        EmailProperty::ReceivedAt => {
+                            email.insert_unchecked(
+                                EmailProperty::ReceivedAt,
+                                EmailValue::Date(UTCDate::from_timestamp(
+                                    (summary.rcvd_attach.to_native() & MESSAGE_RECEIVED_MASK)
+                                        as i64,
+                                )),
+                            );
+                        }
+                        EmailProperty::Preview => {
+                            if !summary.preview.is_empty() {
+                                email.insert_unchecked(
+                                    EmailProperty::Preview,
+                                    summary.preview.to_string(),
+                                );
+                            }
+                        }
+                        EmailProperty::HasAttachment => {
+                            email.insert_unchecked(
+                                EmailProperty::HasAttachment,
+                                (summary.rcvd_attach.to_native() & MESSAGE_HAS_ATTACHMENT) != 0,
+                            );
+                        }
+                        EmailProperty::ChangeHistory => {
+                            email.insert_unchecked(
+                                EmailProperty::ChangeHistory,
+                                change_history(
+                                    self.email_changes(account_id, id.document_id())
+                                        .await
+                                        .caused_by(trc::location!())?,
+                                ),
+                            );
+                        }
+                        _ => {
+                            if let Some((header_name, form)) = summary_header(property) {
+                                email.insert_unchecked(
+                                    property.clone(),
+                                    summary
+                                        .header_value(&header_name)
+                                        .map(|value| HeaderValue::from(value).into_form(&form))
+                                        .unwrap_or_default(),
+                                );
+                            }
+                        }
+                    }
+                }
+                response.list.push(email.into());
+                continue;
+            }
             let metadata_ = match self
                 .store()
                 .get_value::<Archive<AlignedBytes>>(ValueKey::property(
@@ -435,6 +573,16 @@ impl EmailGet for Server {
                         }
                         email.insert_unchecked(EmailProperty::BodyValues, body_values);
                     }
+                    EmailProperty::ChangeHistory => {
+                        email.insert_unchecked(
+                            EmailProperty::ChangeHistory,
+                            change_history(
+                                self.email_changes(account_id, id.document_id())
+                                    .await
+                                    .caused_by(trc::location!())?,
+                            ),
+                        );
+                    }
 
                     _ => {
                         return Err(trc::JmapEvent::InvalidArguments
@@ -449,3 +597,73 @@ impl EmailGet for Server {
         Ok(response)
     }
 }
+
+fn change_history(changes: Vec<EmailChange>) -> Value<'static, EmailProperty, EmailValue> {
+    Value::Array(
+        changes
+            .into_iter()
+            .map(|change| {
+                let mut entry = Map::with_capacity(7)
+                    .with_key_value(
+                        Key::Borrowed("changedAt"),
+                        EmailValue::Date(UTCDate::from_timestamp(change.changed_at as i64)),
+                    )
+                    .with_key_value(Key::Borrowed("changedBy"), Id::from(change.changed_by))
+                    .with_key_value(
+                        Key::Borrowed("protocol"),
+                        Value::Str(change.protocol.as_str().into()),
+                    );
+                for (name, keywords) in [
+                    ("keywordsAdded", change.keywords_added),
+                    ("keywordsRemoved", change.keywords_removed),
+                ] {
+                    if !keywords.is_empty() {
+                        entry.insert_unchecked(
+                            Key::Borrowed(name),
+                            Map::from_iter(keywords.into_iter().map(|keyword| {
+                                (
+                                    Key::Property(EmailProperty::Keyword(keyword)),
+                                    Value::Bool(true),
+                                )
+                            })),
+                        );
+                    }
+                }
+                for (name, mailbox_ids) in [
+                    ("mailboxIdsAdded", change.mailboxes_added),
+                    ("mailboxIdsRemoved", change.mailboxes_removed),
+                ] {
+                    if !mailbox_ids.is_empty() {
+                        entry.insert_unchecked(
+                            Key::Borrowed(name),
+                            Map::from_iter(mailbox_ids.into_iter().map(|mailbox_id| {
+                                (
+                                    Key::Property(EmailProperty::IdValue(Id::from(mailbox_id))),
+                                    Value::Bool(true),
+                                )
+                            })),
+                        );
+                    }
+                }
+                Value::Object(entry)
+            })
+            .collect(),
+    )
+}
+
+fn summary_header(property: &EmailProperty) -> Option<(MetadataHeaderName, HeaderForm)> {
+    match property {
+        EmailProperty::Subject => Some((MetadataHeaderName::Subject, HeaderForm::Text)),
+        EmailProperty::SentAt => Some((MetadataHeaderName::Date, HeaderForm::Date)),
+        EmailProperty::MessageId => Some((MetadataHeaderName::MessageId, HeaderForm::MessageIds)),
+        EmailProperty::InReplyTo => Some((MetadataHeaderName::InReplyTo, HeaderForm::MessageIds)),
+        EmailProperty::References => Some((MetadataHeaderName::References, HeaderForm::MessageIds)),
+        EmailProperty::Sender => Some((MetadataHeaderName::Sender, HeaderForm::Addresses)),
+        EmailProperty::From => Some((MetadataHeaderName::From, HeaderForm::Addresses)),
+        EmailProperty::To => Some((MetadataHeaderName::To, HeaderForm::Addresses)),
+        EmailProperty::Cc => Some((MetadataHeaderName::Cc, HeaderForm::Addresses)),
+        EmailProperty::Bcc => Some((MetadataHeaderName::Bcc, HeaderForm::Addresses)),
+        EmailProperty::ReplyTo => Some((MetadataHeaderName::ReplyTo, HeaderForm::Addresses)),
+        _ => None,
+    }
+}
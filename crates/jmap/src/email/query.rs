@@ -5,15 +5,15 @@
  */
 
 use crate::{api::query::QueryResponseBuilder, changes::state::JmapCacheState};
-use common::{MessageStoreCache, Server, auth::AccessToken};
-use email::cache::{MessageCacheFetch, email::MessageCacheAccess};
+use common::{EmailQueryCache, EmailQueryKey, MessageStoreCache, Server, auth::AccessToken};
+use email::cache::{MessageCacheFetch, email::MessageCacheAccess, mailbox::MailboxCacheAccess};
 use jmap_proto::{
     method::query::{Filter, QueryRequest, QueryResponse},
     object::email::{Email, EmailComparator, EmailFilter},
 };
 use mail_parser::HeaderName;
 use nlp::language::Language;
-use std::future::Future;
+use std::{future::Future, sync::Arc, time::Duration};
 use store::{
     ahash::{AHashMap, AHashSet},
     roaring::RoaringBitmap,
@@ -26,12 +26,22 @@ use trc::AddContext;
 use types::{acl::Acl, keyword::Keyword};
 use utils::map::vec_map::VecMap;
 
+// Full-text indexing runs in the background without bumping the account state,
+// so cached results are only kept for a short time
+const QUERY_CACHE_TTL: Duration = Duration::from_secs(60);
+
 pub trait EmailQuery: Sync + Send {
     fn email_query(
         &self,
         request: QueryRequest<Email>,
         access_token: &AccessToken,
     ) -> impl Future<Output = trc::Result<QueryResponse>> + Send;
+
+    fn email_query_cached(
+        &self,
+        request: &QueryRequest<Email>,
+        access_token: &AccessToken,
+    ) -> impl Future<Output = trc::Result<Option<Arc<EmailQueryCache>>>> + Send;
 }
 
 impl EmailQuery for Server {
@@ -47,6 +57,27 @@ impl EmailQuery for Server {
             .await
             .caused_by(trc::location!())?;
 
+        // Identical queries against an unchanged account are served from the cache
+        let collapse_threads = request.arguments.collapse_threads.unwrap_or(false);
+        let cache_key = query_cache_key(&request, access_token, &cached_messages);
+        if let Some(results) = self
+            .inner
+            .cache
+            .email_queries
+            .get(&cache_key)
+            .filter(|results| {
+                results.emails_change_id == cached_messages.emails.change_id
+                    && results.mailboxes_change_id == cached_messages.mailboxes.change_id
+            })
+        {
+            return build_query_response(
+                &results,
+                self.core.jmap.query_max_results,
+                &cached_messages,
+                &request,
+            );
+        }
+
         for filter in std::mem::take(&mut request.filter) {
             match filter {
                 Filter::Property(cond) => match cond {
@@ -349,38 +380,111 @@ impl EmailQuery for Server {
             )
             .await?;
 
-        let mut response = QueryResponseBuilder::new(
-            results.len(),
+        let total = results.len();
+        let mut ids = Vec::with_capacity(total);
+        let mut seen_thread_ids = AHashSet::new();
+        for document_id in results {
+            let Some(thread_id) = cached_messages
+                .email_by_id(&document_id)
+                .map(|email| email.thread_id)
+            else {
+                continue;
+            };
+            if collapse_threads && !seen_thread_ids.insert(thread_id) {
+                continue;
+            }
+            ids.push((thread_id, document_id));
+        }
+
+        let results = Arc::new(EmailQueryCache {
+            emails_change_id: cached_messages.emails.change_id,
+            mailboxes_change_id: cached_messages.mailboxes.change_id,
+            total,
+            ids: ids.into_boxed_slice(),
+        });
+        self.inner
+            .cache
+            .email_queries
+            .insert(cache_key, results.clone(), QUERY_CACHE_TTL);
+
+        build_query_response(
+            &results,
             self.core.jmap.query_max_results,
-            cached_messages.get_state(false),
+            &cached_messages,
             &request,
-        );
+        )
+    }
 
-        if !results.is_empty() {
-            let collapse_threads = request.arguments.collapse_threads.unwrap_or(false);
-            let mut seen_thread_ids = AHashSet::new();
+    async fn email_query_cached(
+        &self,
+        request: &QueryRequest<Email>,
+        access_token: &AccessToken,
+    ) -> trc::Result<Option<Arc<EmailQueryCache>>> {
+        let cached_messages = self
+            .get_cached_messages(request.account_id.document_id())
+            .await
+            .caused_by(trc::location!())?;
 
-            for document_id in results {
-                let Some(thread_id) = cached_messages
-                    .email_by_id(&document_id)
-                    .map(|email| email.thread_id)
-                else {
-                    continue;
-                };
-                if collapse_threads && !seen_thread_ids.insert(thread_id) {
-                    continue;
-                }
+        Ok(self.inner.cache.email_queries.get(&query_cache_key(
+            request,
+            access_token,
+            &cached_messages,
+        )))
+    }
+}
 
-                if !response.add(thread_id, document_id) {
-                    break;
-                }
-            }
-        }
+// Results are cached per query and per set of mailboxes the user has access to,
+// the cached entry records the state it was calculated at
+fn query_cache_key(
+    request: &QueryRequest<Email>,
+    access_token: &AccessToken,
+    cached_messages: &MessageStoreCache,
+) -> EmailQueryKey {
+    let account_id = request.account_id.document_id();
+    let (access_id, shared_mailboxes) = if access_token.is_shared(account_id) {
+        (
+            access_token.account_id(),
+            cached_messages
+                .shared_mailboxes(access_token, Acl::ReadItems)
+                .into_iter()
+                .collect(),
+        )
+    } else {
+        (account_id, Box::default())
+    };
 
-        response.build()
+    EmailQueryKey {
+        account_id,
+        access_id,
+        shared_mailboxes,
+        filter: request.filter.clone(),
+        sort: request.sort.clone(),
+        collapse_threads: request.arguments.collapse_threads.unwrap_or(false),
     }
 }
 
+fn build_query_response(
+    results: &EmailQueryCache,
+    max_results: usize,
+    cached_messages: &MessageStoreCache,
+    request: &QueryRequest<Email>,
+) -> trc::Result<QueryResponse> {
+    let mut response = QueryResponseBuilder::new(
+        results.total,
+        max_results,
+        cached_messages.get_state(false),
+        request,
+    );
+
+    for &(thread_id, document_id) in results.ids.iter() {
+        if !response.add(thread_id, document_id) {
+            break;
+        }
+    }
+
+    response.build()
+}
+
 fn thread_keywords(cache: &MessageStoreCache, keyword: Keyword, match_all: bool) -> RoaringBitmap {
     let keyword_doc_ids =
         RoaringBitmap::from_iter(cache.with_keyword(&keyword).map(|item| item.document_id));
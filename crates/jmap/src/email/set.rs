@@ -18,7 +18,9 @@ use email::{
     mailbox::{JUNK_ID, TRASH_ID, UidMailbox},
     message::{
         delete::EmailDeletion,
+        history::{EmailChange, EmailChangeHistory, EmailChangeProtocol},
         ingest::{EmailIngest, IngestEmail, IngestSource},
+        legal_hold::LegalHoldManagement,
         metadata::MessageData,
     },
 };
@@ -789,6 +791,10 @@ impl EmailSet for Server {
         }
 
         // Process updates
+        let legal_hold = self
+            .legal_hold(account_id)
+            .await
+            .caused_by(trc::location!())?;
         let mut batch = BatchBuilder::new();
         let mut changed_mailboxes: AHashMap<u32, Vec<u32>> = AHashMap::new();
         let mut will_update = Vec::with_capacity(request.update.as_ref().map_or(0, |u| u.len()));
@@ -990,6 +996,18 @@ impl EmailSet for Server {
 
                 // Add all removed mailboxes to change list
                 for mailbox_id in new_data.removed_mailboxes(data.inner) {
+                    // Messages cannot be removed from mailboxes under legal hold
+                    if legal_hold.is_mailbox_hold(mailbox_id.mailbox_id.to_native()) {
+                        response.not_updated.append(
+                            id,
+                            SetError::forbidden().with_description(format!(
+                                "Mailbox {} is under legal hold.",
+                                Id::from(mailbox_id.mailbox_id.to_native())
+                            )),
+                        );
+                        continue 'update;
+                    }
+
                     // Verify permissions on shared accounts
                     if can_delete_mailbox_ids
                         .as_ref()
@@ -1044,6 +1062,12 @@ impl EmailSet for Server {
             }
 
             // Write changes
+            let change = EmailChange::new(
+                access_token.account_id(),
+                EmailChangeProtocol::Jmap,
+                data.inner,
+                &new_data,
+            );
             batch
                 .with_account_id(account_id)
                 .with_collection(Collection::Email)
@@ -1054,6 +1078,9 @@ impl EmailSet for Server {
                         .with_changes(new_data.seal()),
                 )
                 .caused_by(trc::location!())?;
+            self.log_email_change(&mut batch, account_id, document_id, change)
+                .await
+                .caused_by(trc::location!())?;
 
             if let Some(train_spam) = train_spam {
                 self.add_account_spam_sample(
@@ -1122,7 +1149,18 @@ impl EmailSet for Server {
                 let document_id = destroy_id.document_id();
 
                 if email_ids.contains(document_id) {
-                    if !matches!(&can_destroy_message_ids, Some(ids) if !ids.contains(document_id))
+                    if !legal_hold.is_none()
+                        && cache.email_by_id(&document_id).is_some_and(|message| {
+                            legal_hold
+                                .is_message_held(message.mailboxes.iter().map(|m| m.mailbox_id))
+                        })
+                    {
+                        response.not_destroyed.append(
+                            destroy_id,
+                            SetError::forbidden()
+                                .with_description("This message is under legal hold."),
+                        );
+                    } else if !matches!(&can_destroy_message_ids, Some(ids) if !ids.contains(document_id))
                     {
                         destroy_ids.insert(document_id);
                         response.destroyed.push(destroy_id);
@@ -1161,12 +1199,20 @@ impl EmailSet for Server {
                     self.notify_task_queue();
                 }
 
-                // Mark messages that were not found as not destroyed (this should not occur in practice)
+                // Mark messages that were not found or were placed under legal hold
+                // after the checks above as not destroyed
                 if !not_destroyed.is_empty() {
                     let mut destroyed = Vec::with_capacity(response.destroyed.len());
 
                     for destroy_id in response.destroyed {
-                        if not_destroyed.contains(destroy_id.document_id()) {
+                        let document_id = destroy_id.document_id();
+                        if not_destroyed.legal_hold.contains(document_id) {
+                            response.not_destroyed.append(
+                                destroy_id,
+                                SetError::forbidden()
+                                    .with_description("This message is under legal hold."),
+                            );
+                        } else if not_destroyed.not_found.contains(document_id) {
                             response
                                 .not_destroyed
                                 .append(destroy_id, SetError::not_found());
@@ -312,6 +312,10 @@ impl MailboxSet for Server {
                                     "Another process modified a message in this mailbox ",
                                     "while deleting it, please try again."
                                 )),
+                            MailboxDestroyError::LegalHold => SetError::forbidden()
+                                .with_description("Mailbox is under legal hold."),
+                            MailboxDestroyError::Protected => SetError::forbidden()
+                                .with_description("Mailbox is protected by policy."),
                         },
                     );
                 }
@@ -515,6 +519,18 @@ impl MailboxSet for Server {
 
         let cached_mailboxes = self.get_cached_messages(ctx.account_id).await?;
 
+        // Folders protected by policy cannot be renamed or moved
+        if let Some((document_id, mailbox)) = &update
+            && (mailbox.inner.name != changes.name || mailbox.inner.parent_id != changes.parent_id)
+            && cached_mailboxes
+                .mailbox_by_id(document_id)
+                .is_some_and(|mailbox| self.core.email.is_protected_folder(mailbox))
+        {
+            return Ok(Err(SetError::forbidden().with_description(
+                "You are not allowed to rename or move this mailbox.",
+            )));
+        }
+
         // Verify that the mailbox role is unique.
         if update
             .as_ref()
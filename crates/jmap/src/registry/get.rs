@@ -155,6 +155,7 @@ impl RegistryGet for Server {
             | ObjectType::NetworkListener
             | ObjectType::ClusterRole
             | ObjectType::OidcProvider
+            | ObjectType::QueueStore
             | ObjectType::ReportSettings
             | ObjectType::Search
             | ObjectType::SearchStore
@@ -171,6 +172,7 @@ impl RegistryGet for Server {
             | ObjectType::SpamFileExtension
             | ObjectType::SpamLlm
             | ObjectType::SpamPyzor
+            | ObjectType::SpamRspamd
             | ObjectType::SpamRule
             | ObjectType::SpamSettings
             | ObjectType::SpamTag
@@ -190,6 +192,8 @@ impl RegistryGet for Server {
             | ObjectType::Tenant
             | ObjectType::MaskedEmail
             | ObjectType::PublicKey
+            | ObjectType::ShareLink
+            | ObjectType::SuppressedAddress
             | ObjectType::DkimSignature
             | ObjectType::Domain => {
                 let is_singleton = (get.object_flags & OBJ_SINGLETON) != 0;
@@ -9,7 +9,7 @@ use crate::{
     registry::{
         mapping::{
             RegistryGetResponse, RegistryQueryResponse, RegistrySetResponse,
-            principal::build_set_error,
+            principal::{build_set_error, encrypt_messages_task},
         },
         query::RegistryQueryFilters,
         set::map_write_error,
@@ -45,7 +45,7 @@ use store::{
         RegistryFilterOp,
         write::{RegistryWrite, RegistryWriteResult},
     },
-    write::now,
+    write::{BatchBuilder, now},
 };
 use trc::AddContext;
 use types::id::Id;
@@ -598,6 +598,11 @@ pub(crate) async fn account_set(
         if account.credentials != old_account.credentials {
             cache_invalidator.invalidate(CacheInvalidation::AccessToken(set.account_id));
         }
+        let encrypt_task = encrypt_messages_task(
+            item_id,
+            &account.encryption_at_rest,
+            &old_account.encryption_at_rest,
+        );
 
         let object = Object::new(ObjectInner::Account(Account::User(account)));
         let old_object = Object::with_revision(
@@ -618,6 +623,13 @@ pub(crate) async fn account_set(
             RegistryWriteResult::Success(_) => {
                 // Invalidate caches
                 set.server.invalidate_caches(cache_invalidator).await?;
+
+                if let Some(task) = encrypt_task {
+                    let mut batch = BatchBuilder::new();
+                    batch.schedule_task(task);
+                    set.server.store().write(batch.build_all()).await?;
+                    set.server.notify_task_queue();
+                }
             }
             err => {
                 let err = map_write_error(err);
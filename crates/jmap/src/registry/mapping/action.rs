@@ -364,6 +364,7 @@ async fn classify_spam(server: &Server, mut request: SpamClassify) -> Option<Spa
         asn: asn_geo.asn.as_ref().map(|a| a.id),
         country: asn_geo.country.as_ref().map(|c| c.as_str()),
         is_tls: request.is_tls,
+        tls_fingerprint: None,
         env_from: &request.env_from,
         env_from_flags: match request.env_from_parameters {
             Some(SpamClassifyParameters::Bit7) => MAIL_BODY_7BIT,
@@ -373,6 +374,7 @@ async fn classify_spam(server: &Server, mut request: SpamClassify) -> Option<Spa
             None => 0,
         },
         env_rcpt_to: request.env_rcpt_to.iter().map(String::as_str).collect(),
+        env_rcpt_to_detail: vec![],
         is_test: true,
         is_train: false,
     };
@@ -397,7 +399,7 @@ async fn classify_spam(server: &Server, mut request: SpamClassify) -> Option<Spa
 
     request.tags = VecMap::with_capacity(ctx.result.tags.len());
     for tag in ctx.result.tags {
-        let (score, disposition) = match server.core.spam.lists.scores.get(&tag) {
+        let (score, disposition) = match server.inner.data.spam_lists.load().scores.get(&tag) {
             Some(SpamFilterAction::Allow(score)) => (*score, SpamClassifyTagDisposition::Score),
             Some(SpamFilterAction::Discard) => (0.0, SpamClassifyTagDisposition::Discard),
             _ => (0.0, SpamClassifyTagDisposition::Reject),
@@ -15,6 +15,7 @@ use crate::{
         query::RegistryQueryFilters,
     },
 };
+use email::cache::{MessageCacheFetch, mailbox::MailboxCacheAccess};
 use jmap_proto::{error::set::SetError, types::state::State};
 use jmap_tools::{Key, Value};
 use registry::{
@@ -47,6 +48,7 @@ pub(crate) async fn archived_item_set(
         // Extract new deliverAt value
         let mut status = ArchivedItemStatus::Archived;
         let mut archived_until = None;
+        let mut mailbox_id = None;
         let now = UTCDateTime::now();
         for (key, value) in value.into_expanded_object() {
             match (key, value) {
@@ -76,7 +78,20 @@ pub(crate) async fn archived_item_set(
                         continue 'outer;
                     }
                 }
-                (Key::Property(Property::Id), _) => {}
+                (Key::Property(Property::MailboxId), Value::Str(mailbox_id_)) => {
+                    mailbox_id = Id::from_str(mailbox_id_.as_ref()).ok();
+                    if mailbox_id.is_none() {
+                        set.response.not_updated.append(
+                            id,
+                            SetError::invalid_patch()
+                                .with_property(Property::MailboxId)
+                                .with_description("Invalid value for property"),
+                        );
+                        continue 'outer;
+                    }
+                }
+                (Key::Property(Property::MailboxId | Property::Id), Value::Null)
+                | (Key::Property(Property::Id), _) => {}
                 (key, _) => {
                     set.response.not_updated.append(
                         id,
@@ -140,10 +155,28 @@ pub(crate) async fn archived_item_set(
                         );
                 }
             } else {
-                // Schedule restore task
+                // Make sure the target mailbox exists
                 let item = ArchivedItem::from(item);
                 let account_id = item.account_id();
+                if let Some(mailbox_id) = mailbox_id
+                    && (!matches!(item, ArchivedItem::Email(_))
+                        || !set
+                            .server
+                            .get_cached_messages(account_id.document_id())
+                            .await
+                            .caused_by(trc::location!())?
+                            .has_mailbox_id(&mailbox_id.document_id()))
+                {
+                    set.response.not_updated.append(
+                        id,
+                        SetError::invalid_properties()
+                            .with_property(Property::MailboxId)
+                            .with_description("Mailbox not found"),
+                    );
+                    continue 'outer;
+                }
 
+                // Schedule restore task
                 batch
                     .assert_value(
                         ValueClass::Registry(RegistryClass::Item { object_id, item_id }),
@@ -161,6 +194,7 @@ pub(crate) async fn archived_item_set(
                     }))
                     .schedule_task(Task::RestoreArchivedItem(TaskRestoreArchivedItem {
                         account_id,
+                        mailbox_id,
                         archived_item_type: item.object_type(),
                         archived_until: item.archived_until(),
                         blob_id: item.blob_id().clone(),
@@ -7,16 +7,30 @@
 use crate::registry::mapping::{
     ObjectResponse, RegistrySetResponse, ValidationResult, principal::validate_tenant_quota,
 };
-use common::config::smtp::auth::DkimSigner;
+use common::{
+    config::smtp::auth::DkimSigner,
+    network::dkim::{generate_dkim_private_key, generate_dkim_selector},
+};
 use jmap_proto::error::set::SetError;
-use registry::schema::{enums::TenantStorageQuota, structs::DkimSignature};
+use registry::{
+    jmap::IntoValue,
+    schema::{
+        enums::TenantStorageQuota,
+        prelude::Property,
+        structs::{
+            DkimManagement, DkimManagementProperties, DkimSignature, Domain, SecretText,
+            SecretTextValue,
+        },
+    },
+};
+use trc::AddContext;
 
 pub(crate) async fn validate_dkim_signature(
     set: &RegistrySetResponse<'_>,
     key: &mut DkimSignature,
     old_key: Option<&DkimSignature>,
 ) -> ValidationResult {
-    let response = if old_key.is_none() {
+    let mut response = if old_key.is_none() {
         match validate_tenant_quota(set, TenantStorageQuota::MaxDkimKeys).await? {
             Ok(response) => response,
             Err(err) => {
@@ -27,6 +41,55 @@ pub(crate) async fn validate_dkim_signature(
         ObjectResponse::default()
     };
 
+    // Keys created without a private key or selector are generated here, so
+    // that RSA and Ed25519 keys can be added to a domain for dual signing
+    if old_key.is_none() {
+        let key_type = key.object_type();
+        if matches!(key.private_key(), SecretText::Text(value) if value.secret.is_empty()) {
+            match generate_dkim_private_key(key_type).await? {
+                Ok(secret) => {
+                    *key.private_key_mut() = SecretText::Text(SecretTextValue { secret });
+                }
+                Err(err) => {
+                    return Ok(Err(SetError::invalid_properties()
+                        .with_property(Property::PrivateKey)
+                        .with_description(format!("Failed to generate DKIM key: {err}"))));
+                }
+            }
+        }
+
+        let (DkimSignature::Dkim1Ed25519Sha256(signature)
+        | DkimSignature::Dkim1RsaSha256(signature)) = key;
+        if signature.selector.is_empty() {
+            let template = match set
+                .server
+                .registry()
+                .object::<Domain>(signature.domain_id)
+                .await
+                .caused_by(trc::location!())?
+                .map(|domain| domain.dkim_management)
+            {
+                Some(DkimManagement::Automatic(props)) => props.selector_template,
+                _ => DkimManagementProperties::default().selector_template,
+            };
+            match generate_dkim_selector(&template, key_type) {
+                Ok(selector) => {
+                    response
+                        .object
+                        .insert_unchecked(Property::Selector, selector.clone().into_value());
+                    signature.selector = selector;
+                }
+                Err(err) => {
+                    return Ok(Err(SetError::invalid_properties()
+                        .with_property(Property::Selector)
+                        .with_description(format!(
+                            "Failed to generate DKIM selector: {err}"
+                        ))));
+                }
+            }
+        }
+    }
+
     if old_key.is_none_or(|old_key| old_key.private_key() != key.private_key())
         && let Err(err) = DkimSigner::new("example.com".to_string(), key.clone()).await
     {
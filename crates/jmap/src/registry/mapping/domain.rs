@@ -14,9 +14,9 @@ use registry::{
         enums::{AcmeChallengeType, DkimSignatureType, DnsRecordType, TenantStorageQuota},
         prelude::Property,
         structs::{
-            AcmeProvider, CertificateManagement, DkimManagement, DkimManagementProperties,
-            DnsManagement, DnsServer, Domain, Task, TaskDnsManagement, TaskDomainManagement,
-            TaskStatus,
+            AcmeProvider, CertificateManagement, CertificateManagementProperties, DkimManagement,
+            DkimManagementProperties, DnsManagement, DnsServer, Domain, Task, TaskDnsManagement,
+            TaskDomainManagement, TaskStatus,
         },
     },
     types::map::Map,
@@ -61,6 +61,25 @@ pub(crate) async fn validate_domain(
             .with_description(err)));
     }
 
+    // Issue certificates for new domains using the default ACME provider
+    if old_domain.is_none()
+        && matches!(domain.certificate_management, CertificateManagement::Manual)
+        && let Some(acme_provider_id) = set.server.core.network.default_acme_provider_id
+        && let Some(provider) = set
+            .server
+            .registry()
+            .object::<AcmeProvider>(acme_provider_id)
+            .await?
+        && (provider.challenge_type != AcmeChallengeType::Dns01
+            || matches!(domain.dns_management, DnsManagement::Automatic(_)))
+    {
+        domain.certificate_management =
+            CertificateManagement::Automatic(CertificateManagementProperties {
+                acme_provider_id,
+                subject_alternative_names: Default::default(),
+            });
+    }
+
     // Schedule DNS update task
     let will_trigger_dkim = matches!(domain.dkim_management, DkimManagement::Automatic(_))
         && old_domain
@@ -32,8 +32,10 @@ pub mod principal;
 pub mod public_key;
 pub mod queued_message;
 pub mod report;
+pub mod share_link;
 pub mod sieve;
 pub mod spam_sample;
+pub mod suppression;
 pub mod task;
 pub mod tls;
 
@@ -14,9 +14,12 @@ use jmap_proto::error::set::SetError;
 use registry::{schema::structs::TaskStatus, types::datetime::UTCDateTime};
 use registry::{
     schema::{
-        enums::{AccountType, Permission, TenantStorageQuota},
+        enums::{AccountType, Permission, TaskAccountMaintenanceType, TenantStorageQuota},
         prelude::{MASKED_PASSWORD, ObjectType, Property},
-        structs::{Account, Credential, Role, Task, TaskDestroyAccount},
+        structs::{
+            Account, Credential, EncryptionAtRest, Role, Task, TaskAccountMaintenance,
+            TaskDestroyAccount,
+        },
     },
     types::EnumImpl,
 };
@@ -37,6 +40,7 @@ pub(crate) async fn validate_account(
     set: &RegistrySetResponse<'_>,
     mut account: &mut Account,
     old_account: AccountUpdate<'_>,
+    tasks: &mut Vec<Task>,
 ) -> ValidationResult {
     // SPDX-SnippetBegin
     // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
@@ -200,6 +204,12 @@ pub(crate) async fn validate_account(
                 }
             }
 
+            tasks.extend(encrypt_messages_task(
+                Id::default(),
+                &account.encryption_at_rest,
+                &old_account.encryption_at_rest,
+            ));
+
             account.permissions != old_account.permissions || account.roles != old_account.roles
         }
         (Account::Group(account), AccountUpdate::Update(Account::Group(old_account))) => {
@@ -478,6 +488,26 @@ pub(crate) async fn schedule_account_destruction(
     Ok(())
 }
 
+// Messages stored in plain text, either received before encryption was enabled
+// or left unencrypted after a failure, are encrypted once a new key is set.
+// Messages already encrypted with a previous key are left untouched as the
+// server does not hold the private keys needed to decrypt them.
+pub(crate) fn encrypt_messages_task(
+    account_id: Id,
+    encryption: &EncryptionAtRest,
+    old_encryption: &EncryptionAtRest,
+) -> Option<Task> {
+    if encryption != old_encryption && !matches!(encryption, EncryptionAtRest::Disabled) {
+        Some(Task::AccountMaintenance(TaskAccountMaintenance {
+            account_id,
+            maintenance_type: TaskAccountMaintenanceType::EncryptMessages,
+            status: TaskStatus::now(),
+        }))
+    } else {
+        None
+    }
+}
+
 pub(crate) fn build_set_error(permissions: Vec<Permission>) -> SetError<Property> {
     let mut missing_permissions = String::with_capacity(16);
     let mut total_missing = permissions.len();
@@ -389,9 +389,7 @@ pub(crate) async fn queued_message_query(
 
         let mut results = Vec::with_capacity(8);
         req.server
-            .core
-            .storage
-            .data
+            .queue_store()
             .iterate(
                 IterateParams::new(from_key, to_key).ascending(),
                 |key, value| {
@@ -495,7 +493,7 @@ pub(crate) async fn queued_message_query(
 
         let mut seen_ids = AHashSet::with_capacity(8);
         req.server
-            .store()
+            .queue_store()
             .iterate(
                 IterateParams::new(from_key, to_key)
                     .set_ascending(params.sort_ascending)
@@ -740,7 +738,7 @@ async fn queued_ids(server: &Server, max_results: usize) -> trc::Result<AHashSet
     )));
 
     server
-        .store()
+        .queue_store()
         .iterate(
             IterateParams::new(from_key, to_key).ascending().no_values(),
             |key, _| {
@@ -0,0 +1,136 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::registry::mapping::{ObjectResponse, RegistrySetResponse, ValidationResult};
+use groupware::{cache::GroupwareCache, share_link::ShareLinks};
+use jmap_proto::error::set::SetError;
+use registry::{
+    jmap::{IntoValue, JmapValue},
+    schema::{
+        enums::ShareLinkCollection,
+        prelude::{ObjectType, Property},
+        structs::ShareLink,
+    },
+    types::datetime::UTCDateTime,
+};
+use store::{
+    registry::{RegistryObjectCounter, RegistryQuery},
+    write::now,
+};
+use trc::AddContext;
+use types::{collection::SyncCollection, id::Id};
+use utils::map::vec_map::VecMap;
+
+pub(crate) async fn validate_share_link(
+    set: &RegistrySetResponse<'_>,
+    link: &mut ShareLink,
+    is_create: bool,
+    unpatched_properties: VecMap<Property, JmapValue<'_>>,
+) -> ValidationResult {
+    let mut response = ObjectResponse::default();
+
+    if !is_create {
+        for (key, value) in unpatched_properties {
+            match (key, value) {
+                (Property::Url, JmapValue::Str(url))
+                    if link.url.as_deref() == Some(url.as_ref()) => {}
+                _ => {
+                    return Ok(Err(SetError::invalid_properties()
+                        .with_property(key)
+                        .with_description("Cannot modify read-only property")));
+                }
+            }
+        }
+        return Ok(Ok(response));
+    } else if set.server.core.groupware.share_link_url.is_none() {
+        return Ok(Err(
+            SetError::forbidden().with_description("Share links are disabled on this server.")
+        ));
+    }
+
+    // Validate quotas
+    let num_links = set
+        .server
+        .registry()
+        .query::<RegistryObjectCounter>(
+            RegistryQuery::new(ObjectType::ShareLink).with_account(set.account_id),
+        )
+        .await?
+        .0 as usize;
+    let max_links = set.server.core.groupware.max_share_links;
+    if num_links >= max_links {
+        return Ok(Err(SetError::over_quota().with_description(format!(
+            "You have exceeded your quota of {} share links.",
+            max_links
+        ))));
+    }
+
+    // Make sure the shared collection exists and is owned by the account
+    let resources = set
+        .server
+        .fetch_dav_resources(
+            set.account_id,
+            set.account_id,
+            match link.collection {
+                ShareLinkCollection::Calendar => SyncCollection::Calendar,
+                ShareLinkCollection::AddressBook => SyncCollection::AddressBook,
+            },
+        )
+        .await
+        .caused_by(trc::location!())?;
+    if !resources.has_container_id(&link.collection_id.document_id()) {
+        return Ok(Err(SetError::invalid_properties()
+            .with_property(Property::CollectionId)
+            .with_description("Collection not found.")));
+    }
+
+    // Validate expiration
+    let now = now() as i64;
+    let max_expires_at = now + set.server.core.groupware.share_link_max_expiry as i64;
+    let expires_at = match link.expires_at {
+        Some(expires_at)
+            if expires_at.timestamp() > now && expires_at.timestamp() <= max_expires_at =>
+        {
+            expires_at.timestamp()
+        }
+        Some(_) => {
+            return Ok(Err(SetError::invalid_properties()
+                .with_property(Property::ExpiresAt)
+                .with_description(format!(
+                    "Expiration date must be in the future and within {} days.",
+                    set.server.core.groupware.share_link_max_expiry / 86400
+                ))));
+        }
+        None => max_expires_at,
+    };
+
+    // Issue signed URL
+    let link_id = Id::from(set.server.registry().assign_id());
+    link.created_at = UTCDateTime::from_timestamp(now);
+    link.expires_at = Some(UTCDateTime::from_timestamp(expires_at));
+    link.url = set
+        .server
+        .share_link_url(
+            set.account_id,
+            link_id,
+            link.collection,
+            (expires_at - now) as u64,
+        )
+        .await?;
+
+    response.id = Some(link_id);
+    response
+        .object
+        .insert_unchecked(Property::CreatedAt, link.created_at.into_value());
+    response
+        .object
+        .insert_unchecked(Property::ExpiresAt, link.expires_at.into_value());
+    response
+        .object
+        .insert_unchecked(Property::Url, link.url.clone().into_value());
+
+    Ok(Ok(response))
+}
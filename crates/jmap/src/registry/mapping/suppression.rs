@@ -0,0 +1,62 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::registry::mapping::{ObjectResponse, RegistrySetResponse, ValidationResult};
+use jmap_proto::error::set::SetError;
+use registry::{
+    jmap::IntoValue,
+    schema::{
+        prelude::{ObjectType, Property},
+        structs::SuppressedAddress,
+    },
+    types::datetime::UTCDateTime,
+};
+use store::registry::RegistryQuery;
+use trc::AddContext;
+use types::id::Id;
+
+pub(crate) async fn validate_suppressed_address(
+    set: &RegistrySetResponse<'_>,
+    entry: &mut SuppressedAddress,
+    is_create: bool,
+) -> ValidationResult {
+    let mut response = ObjectResponse::default();
+    if !is_create {
+        return Ok(Ok(response));
+    }
+
+    // Each address can only be suppressed once per tenant
+    for id in set
+        .server
+        .registry()
+        .query::<Vec<Id>>(
+            RegistryQuery::new(ObjectType::SuppressedAddress)
+                .equal(Property::Email, entry.email.as_str()),
+        )
+        .await
+        .caused_by(trc::location!())?
+    {
+        if set
+            .server
+            .registry()
+            .object::<SuppressedAddress>(id)
+            .await
+            .caused_by(trc::location!())?
+            .is_some_and(|existing| existing.member_tenant_id == entry.member_tenant_id)
+        {
+            return Ok(Err(SetError::invalid_properties()
+                .with_property(Property::Email)
+                .with_description("Address is already on the suppression list.")));
+        }
+    }
+
+    entry.created_at = UTCDateTime::now();
+    response
+        .object
+        .insert_unchecked(Property::CreatedAt, entry.created_at.into_value());
+
+    Ok(Ok(response))
+}
@@ -92,7 +92,8 @@ pub(crate) async fn task_set(
             | TaskType::SpamFilterMaintenance
             | TaskType::AcmeRenewal
             | TaskType::DkimManagement
-            | TaskType::DnsManagement => {
+            | TaskType::DnsManagement
+            | TaskType::AccountMigration => {
                 let mut index = IndexBuilder::default();
                 task.index(&mut index);
 
@@ -130,11 +131,14 @@ pub(crate) async fn task_set(
             TaskType::CalendarAlarmEmail
             | TaskType::CalendarAlarmNotification
             | TaskType::CalendarItipMessage
+            | TaskType::CalendarSubscriptionSync
+            | TaskType::AddressBookDirectorySync
             | TaskType::MergeThreads
             | TaskType::DmarcReport
             | TaskType::TlsReport
             | TaskType::DestroyAccount
-            | TaskType::RestoreArchivedItem => {
+            | TaskType::RestoreArchivedItem
+            | TaskType::LoginAnomaly => {
                 set.response.not_created.append(
                     id,
                     SetError::forbidden().with_description(format!(
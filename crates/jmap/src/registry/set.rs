@@ -21,15 +21,20 @@ use crate::registry::{
         public_key::validate_public_key,
         queued_message::queued_message_set,
         report::report_set,
+        share_link::validate_share_link,
         sieve::validate_sieve_script,
         spam_sample::spam_sample_set,
+        suppression::validate_suppressed_address,
         task::task_set,
         tls::{validate_acme_provider, validate_certificate},
     },
 };
 use common::{
-    Server, auth::AccessToken, cache::invalidate::CacheInvalidationBuilder,
+    Server,
+    auth::AccessToken,
+    cache::invalidate::CacheInvalidationBuilder,
     expr::if_block::BootstrapExprExt,
+    manager::audit::{AuditAction, AuditEntry},
 };
 use http_proto::HttpSessionData;
 use jmap_proto::{
@@ -41,7 +46,7 @@ use jmap_proto::{
 };
 use jmap_tools::{JsonPointer, JsonPointerItem, Key};
 use registry::{
-    jmap::{JmapValue, JsonPointerPatch, MaybeUnpatched, RegistryValue},
+    jmap::{IntoValue, JmapValue, JsonPointerPatch, MaybeUnpatched, RegistryValue},
     schema::{
         enums::{Permission, TenantStorageQuota},
         prelude::{
@@ -53,11 +58,12 @@ use registry::{
             SieveUserScript, Task,
         },
     },
-    types::id::ObjectId,
+    types::{EnumImpl, id::ObjectId},
 };
 use std::borrow::Cow;
 use store::{
     registry::{
+        RegistryOverlay,
         bootstrap::Bootstrap,
         write::{RegistryWrite, RegistryWriteResult},
     },
@@ -74,6 +80,7 @@ pub trait RegistrySet: Sync + Send {
         request: SetRequest<'_, Registry>,
         access_token: &AccessToken,
         session: &HttpSessionData,
+        dry_run: Option<&mut RegistryOverlay>,
     ) -> impl Future<Output = trc::Result<SetResponse<Registry>>> + Send;
 }
 
@@ -96,6 +103,7 @@ impl RegistrySet for Server {
         mut request: SetRequest<'_, Registry>,
         access_token: &AccessToken,
         session: &HttpSessionData,
+        mut dry_run: Option<&mut RegistryOverlay>,
     ) -> trc::Result<SetResponse<Registry>> {
         // Initial assertions
         if self.registry().is_bootstrap_mode() && !matches!(object_type, ObjectType::Bootstrap) {
@@ -163,7 +171,7 @@ impl RegistrySet for Server {
             update,
             destroy,
         };
-        match object_type {
+        let response = match object_type {
             ObjectType::AddressBook
             | ObjectType::Asn
             | ObjectType::Authentication
@@ -202,6 +210,7 @@ impl RegistrySet for Server {
             | ObjectType::MtaStageRcpt
             | ObjectType::MtaSts
             | ObjectType::OidcProvider
+            | ObjectType::QueueStore
             | ObjectType::ReportSettings
             | ObjectType::Search
             | ObjectType::SearchStore
@@ -214,6 +223,7 @@ impl RegistrySet for Server {
             | ObjectType::SpamDnsblSettings
             | ObjectType::SpamLlm
             | ObjectType::SpamPyzor
+            | ObjectType::SpamRspamd
             | ObjectType::SpamSettings
             | ObjectType::SpfReportSettings
             | ObjectType::TaskManager
@@ -254,6 +264,8 @@ impl RegistrySet for Server {
             | ObjectType::Tracer
             | ObjectType::WebHook
             | ObjectType::PublicKey
+            | ObjectType::ShareLink
+            | ObjectType::SuppressedAddress
             | ObjectType::DkimSignature
             | ObjectType::MaskedEmail
             | ObjectType::Account
@@ -332,6 +344,11 @@ impl RegistrySet for Server {
 
                 // Process modifications
                 let mut cache_invalidator = CacheInvalidationBuilder::default();
+                let mut audit_chain = if dry_run.is_none() {
+                    Some(self.audit_chain().await?)
+                } else {
+                    None
+                };
                 'outer: for (modification, mut value, mut new_object) in modifications {
                     // Initial validations
                     let is_create = matches!(modification, Modification::Create { .. });
@@ -423,7 +440,8 @@ impl RegistrySet for Server {
                     let mut tasks = Vec::new();
                     let result = match &mut new_object.inner {
                         ObjectInner::Account(account) => {
-                            validate_account(&set, account, modification.as_account()).await?
+                            validate_account(&set, account, modification.as_account(), &mut tasks)
+                                .await?
                         }
                         ObjectInner::Role(role) => {
                             validate_role(&set, role, modification.as_role()).await?
@@ -442,6 +460,12 @@ impl RegistrySet for Server {
                             .await?
                         }
                         // SPDX-SnippetEnd
+                        ObjectInner::ShareLink(link) => {
+                            validate_share_link(&set, link, is_create, unpatched_properties).await?
+                        }
+                        ObjectInner::SuppressedAddress(entry) => {
+                            validate_suppressed_address(&set, entry, is_create).await?
+                        }
                         ObjectInner::PublicKey(key) => {
                             validate_public_key(&set, key, modification.as_public_key()).await?
                         }
@@ -518,17 +542,122 @@ impl RegistrySet for Server {
                         }
                     }
 
-                    // Save object
+                    // Run the write checks and report validated objects without saving them
+                    if let Some(overlay) = dry_run.as_deref_mut() {
+                        let result = match &modification {
+                            Modification::Create {
+                                object: Some(object),
+                                ..
+                            } => {
+                                self.registry()
+                                    .validate(
+                                        RegistryWrite::update(Id::singleton(), &new_object, object),
+                                        overlay,
+                                    )
+                                    .await?
+                            }
+                            Modification::Create { object: None, .. } => {
+                                self.registry()
+                                    .validate(
+                                        RegistryWrite::Insert {
+                                            object: &new_object,
+                                            id: response.id,
+                                        },
+                                        overlay,
+                                    )
+                                    .await?
+                            }
+                            Modification::Update { id, object } => {
+                                if !(is_singleton && object.revision == 0) {
+                                    self.registry()
+                                        .validate(
+                                            RegistryWrite::update(*id, &new_object, object),
+                                            overlay,
+                                        )
+                                        .await?
+                                } else {
+                                    self.registry()
+                                        .validate(RegistryWrite::insert(&new_object), overlay)
+                                        .await?
+                                }
+                            }
+                        };
+
+                        match (modification, result) {
+                            (
+                                Modification::Create { client_id, .. },
+                                RegistryWriteResult::Success(_),
+                            ) => {
+                                let id = if is_singleton {
+                                    Id::singleton()
+                                } else {
+                                    response
+                                        .id
+                                        .unwrap_or_else(|| self.registry().assign_id().into())
+                                };
+                                overlay.set(ObjectId::new(object_type, id), new_object);
+                                set.response
+                                    .created
+                                    .insert(client_id, JmapValue::Object(response.object));
+                            }
+                            (Modification::Update { id, .. }, RegistryWriteResult::Success(_)) => {
+                                overlay.set(ObjectId::new(object_type, id), new_object);
+                                set.response.updated.append(id, None);
+                            }
+                            (Modification::Update { id, .. }, err) => {
+                                set.response.not_updated.append(id, map_write_error(err));
+                            }
+                            (Modification::Create { client_id, .. }, err) => {
+                                set.response
+                                    .not_created
+                                    .append(client_id, map_write_error(err));
+                            }
+                        }
+                        continue;
+                    }
+
+                    // Save object, recording the change in the same batch
+                    let entry = match &modification {
+                        Modification::Create { object, .. } => {
+                            let entry = AuditEntry::new(
+                                access_token,
+                                session.remote_ip,
+                                AuditAction::Create,
+                                object_type.as_str(),
+                                None,
+                            );
+                            match object {
+                                Some(object) => entry.with_before(&object.clone().into_value()),
+                                None => entry,
+                            }
+                        }
+                        Modification::Update { id, object } => AuditEntry::new(
+                            access_token,
+                            session.remote_ip,
+                            AuditAction::Update,
+                            object_type.as_str(),
+                            Some(id.id()),
+                        )
+                        .with_before(&object.clone().into_value()),
+                    }
+                    .with_after(&new_object.clone().into_value());
+                    let audit = |id: Id, batch: &mut BatchBuilder| match audit_chain.as_mut() {
+                        Some(chain) => chain.append(batch, entry.with_object_id(id.id())),
+                        None => Ok(()),
+                    };
                     let result = match &modification {
                         Modification::Create { client_id, object } => {
                             if let Some(object) = object {
                                 if object.inner != new_object.inner {
                                     self.registry()
-                                        .write(RegistryWrite::update(
-                                            Id::singleton(),
-                                            &new_object,
-                                            object,
-                                        ))
+                                        .write_with(
+                                            RegistryWrite::update(
+                                                Id::singleton(),
+                                                &new_object,
+                                                object,
+                                            ),
+                                            audit,
+                                        )
                                         .await?
                                 } else {
                                     set.response.created(client_id.to_string(), Id::singleton());
@@ -536,10 +665,13 @@ impl RegistrySet for Server {
                                 }
                             } else {
                                 self.registry()
-                                    .write(RegistryWrite::Insert {
-                                        object: &new_object,
-                                        id: response.id,
-                                    })
+                                    .write_with(
+                                        RegistryWrite::Insert {
+                                            object: &new_object,
+                                            id: response.id,
+                                        },
+                                        audit,
+                                    )
                                     .await?
                             }
                         }
@@ -547,11 +679,14 @@ impl RegistrySet for Server {
                             if object.inner != new_object.inner {
                                 if !(is_singleton && object.revision == 0) {
                                     self.registry()
-                                        .write(RegistryWrite::update(*id, &new_object, object))
+                                        .write_with(
+                                            RegistryWrite::update(*id, &new_object, object),
+                                            audit,
+                                        )
                                         .await?
                                 } else {
                                     self.registry()
-                                        .write(RegistryWrite::insert(&new_object))
+                                        .write_with(RegistryWrite::insert(&new_object), audit)
                                         .await?
                                 }
                             } else {
@@ -560,6 +695,11 @@ impl RegistrySet for Server {
                             }
                         }
                     };
+                    if let (Some(chain), RegistryWriteResult::Success(_)) =
+                        (audit_chain.as_mut(), &result)
+                    {
+                        chain.commit();
+                    }
 
                     let object_id = match (modification, result) {
                         (Modification::Update { id, object }, RegistryWriteResult::Success(_)) => {
@@ -606,6 +746,7 @@ impl RegistrySet for Server {
                                 Task::AcmeRenewal(task) => task.domain_id = object_id,
                                 Task::DkimManagement(task) => task.domain_id = object_id,
                                 Task::DnsManagement(task) => task.domain_id = object_id,
+                                Task::AccountMaintenance(task) => task.account_id = object_id,
                                 _ => unreachable!(),
                             }
                             batch.schedule_task(task);
@@ -631,20 +772,54 @@ impl RegistrySet for Server {
                                     && object.inner.account_id() != Some(Id::from(set.account_id))))
                         })
                     {
+                        let delete = RegistryWrite::Delete {
+                            object_id,
+                            object: Some(&object),
+                            allowed_orphan_types: if object_type == ObjectType::Account {
+                                &[
+                                    ObjectType::PublicKey,
+                                    ObjectType::MaskedEmail,
+                                    ObjectType::ShareLink,
+                                ]
+                            } else {
+                                &[]
+                            },
+                        };
+
+                        if let Some(overlay) = dry_run.as_deref_mut() {
+                            match self.registry().validate(delete, overlay).await? {
+                                RegistryWriteResult::Success(_) => {
+                                    overlay.remove(object_id);
+                                    set.response.destroyed.push(id);
+                                }
+                                err => {
+                                    set.response.not_destroyed.append(id, map_write_error(err));
+                                }
+                            }
+                            continue;
+                        }
+
+                        let entry = AuditEntry::new(
+                            access_token,
+                            session.remote_ip,
+                            AuditAction::Destroy,
+                            object_type.as_str(),
+                            Some(id.id()),
+                        )
+                        .with_before(&object.clone().into_value());
                         match self
                             .registry()
-                            .write(RegistryWrite::Delete {
-                                object_id,
-                                object: Some(&object),
-                                allowed_orphan_types: if object_type == ObjectType::Account {
-                                    &[ObjectType::PublicKey, ObjectType::MaskedEmail]
-                                } else {
-                                    &[]
-                                },
+                            .write_with(delete, |_, batch| match audit_chain.as_mut() {
+                                Some(chain) => chain.append(batch, entry),
+                                None => Ok(()),
                             })
                             .await?
                         {
                             RegistryWriteResult::Success(_) => {
+                                if let Some(chain) = audit_chain.as_mut() {
+                                    chain.commit();
+                                }
+
                                 // Schedule account deletion
                                 if let ObjectInner::Account(account) = &object.inner {
                                     schedule_account_destruction(set.server, id, account).await?;
@@ -662,10 +837,11 @@ impl RegistrySet for Server {
                     }
                 }
 
-                // Finalize cache invalidation
+                // Release the audit chain and finalize cache invalidation
+                drop(audit_chain);
                 self.invalidate_caches(cache_invalidator).await?;
 
-                Ok(set.into_response())
+                return Ok(set.into_response());
             }
             ObjectType::ArfExternalReport
             | ObjectType::DmarcExternalReport
@@ -708,10 +884,72 @@ impl RegistrySet for Server {
                 set.fail_all_destroy("Telemetry objects cannot be deleted");
                 Ok(set.into_response())
             }
+        }?;
+
+        // Record changes, values are omitted as they may contain credentials
+        if dry_run.is_none()
+            && let Err(err) = self
+                .write_audit_log(audit_set_response(
+                    access_token,
+                    session,
+                    object_type,
+                    &response,
+                ))
+                .await
+        {
+            trc::error!(err.caused_by(trc::location!()));
         }
+
+        Ok(response)
     }
 }
 
+fn audit_set_response(
+    access_token: &AccessToken,
+    session: &HttpSessionData,
+    object_type: ObjectType,
+    response: &SetResponse<Registry>,
+) -> Vec<AuditEntry> {
+    let entry = |action, id: Option<Id>| {
+        AuditEntry::new(
+            access_token,
+            session.remote_ip,
+            action,
+            object_type.as_str(),
+            id.map(|id| id.id()),
+        )
+    };
+
+    response
+        .created
+        .values()
+        .map(|value| {
+            entry(
+                AuditAction::Create,
+                value
+                    .as_object()
+                    .and_then(|obj| obj.get(&Key::Property(Property::Id)))
+                    .and_then(|id| match id {
+                        JmapValue::Element(RegistryValue::Id(id)) => Some(*id),
+                        _ => None,
+                    }),
+            )
+        })
+        .chain(
+            response
+                .updated
+                .keys()
+                .map(|id| entry(AuditAction::Update, Some(*id))),
+        )
+        .chain(
+            response
+                .destroyed
+                .iter()
+                .map(|id| entry(AuditAction::Destroy, Some(*id))),
+        )
+        .collect()
+}
+
 impl RegistrySetResponse<'_> {
     fn failed(&mut self, modification: Modification, error: SetError<Property>) {
         match modification {
@@ -94,15 +94,18 @@ impl WebSocketHandler for Server {
                                         self.core.jmap.request_max_size,
                                     ) {
                                         Ok(WebSocketMessage::Request(request)) => {
-                                            let response = self
+                                            match self
                                                 .handle_jmap_request(
                                                     request.request,
                                                     &access_token,
                                                     &session,
                                                 )
-                                                .await;
-                                            WebSocketResponse::from_response(response, request.id)
-                                            .to_json()
+                                                .await
+                                            {
+                                                Ok(response) => WebSocketResponse::from_response(response, request.id)
+                                                .to_json(),
+                                                Err(err) => WebSocketRequestError::from(err.to_request_error()).to_json(),
+                                            }
                                         }
                                         Ok(WebSocketMessage::PushEnable(push_enable)) => {
                                             change_types = if !push_enable.data_types.is_empty() {
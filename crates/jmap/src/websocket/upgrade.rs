@@ -5,13 +5,15 @@
  */
 
 use super::stream::WebSocketHandler;
+use crate::api::session::CapabilityToggle;
 use common::{Server, auth::AccessToken};
 use http_proto::*;
 use hyper::StatusCode;
 use hyper_util::rt::TokioIo;
+use jmap_proto::request::capability::Capability;
 use std::future::Future;
 use tokio_tungstenite::WebSocketStream;
-use trc::JmapEvent;
+use trc::{AddContext, JmapEvent};
 use tungstenite::{handshake::derive_accept_key, protocol::Role};
 
 pub trait WebSocketUpgrade: Sync + Send {
@@ -68,6 +70,17 @@ impl WebSocketUpgrade for Server {
             }
         };
 
+        if Capability::WebSocket.is_disabled(
+            self.disabled_jmap_capabilities(access_token.account_id())
+                .await
+                .caused_by(trc::location!())?,
+        ) {
+            return Err(trc::SecurityEvent::Unauthorized
+                .into_err()
+                .details("WebSocket capability is disabled")
+                .account_id(access_token.account_id()));
+        }
+
         // Spawn WebSocket connection
         let jmap = self.clone();
         tokio::spawn(async move {
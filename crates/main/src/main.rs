@@ -9,16 +9,22 @@
 #![warn(clippy::cast_possible_wrap)]
 #![warn(clippy::cast_sign_loss)]
 
-use common::{BuildServer, config::server::ServerProtocol, manager::boot::BootManager};
-use http::HttpSessionManager;
+use common::{
+    BuildServer,
+    config::server::ServerProtocol,
+    manager::{boot::BootManager, migration::MigrationRequest},
+};
+use email::message::import::EmailImport;
+use http::{HttpSessionManager, agent_check::AgentCheckSessionManager};
 use imap::core::ImapSessionManager;
 use managesieve::core::ManageSieveSessionManager;
 use pop3::Pop3SessionManager;
 use services::{StartServices, broadcast::subscriber::spawn_broadcast_subscriber};
 use smtp::{StartQueueManager, core::SmtpSessionManager};
+use spam_filter::analysis::corpus::{SpamCorpusReport, SpamFilterAnalyzeCorpus};
 use std::time::Duration;
 use trc::Collector;
-use utils::wait_for_shutdown;
+use utils::{failed, wait_for_shutdown};
 
 #[cfg(feature = "dev_mode")]
 pub mod test_data;
@@ -50,6 +56,62 @@ async fn main() -> std::io::Result<()> {
         return Ok(());
     }
 
+    // Test spam filter rules against a labelled corpus
+    if !init.spam_corpus.is_empty() {
+        let server = init.inner.build_server();
+        let mut report = SpamCorpusReport::default();
+        for (path, is_spam) in &init.spam_corpus {
+            let mbox = std::fs::read(path)
+                .unwrap_or_else(|err| failed(&format!("Failed to read {}: {err}", path.display())));
+            Box::pin(server.spam_filter_analyze_corpus(&mut report, &mbox, Some(*is_spam))).await;
+        }
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    // Queue account imports from another server, the task manager runs them
+    // once the server is started
+    if let Some(path) = &init.migrate {
+        let server = init.inner.build_server();
+        let request = std::fs::read(path)
+            .unwrap_or_else(|err| failed(&format!("Failed to read {}: {err}", path.display())));
+        let request = serde_json::from_slice::<MigrationRequest>(&request)
+            .unwrap_or_else(|err| failed(&format!("Failed to parse {}: {err}", path.display())));
+        if request.source.is_empty() {
+            failed("No source server URLs provided.");
+        }
+        let report = server
+            .schedule_migration(&request, None)
+            .await
+            .unwrap_or_else(|err| failed(&format!("Failed to queue account migration: {err}")));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_default()
+        );
+        return Ok(());
+    }
+
+    // Import a Maildir or mbox tree directly into an account
+    if let Some((account, path)) = &init.import_mail {
+        let server = init.inner.build_server();
+        let account_id = server
+            .migration_account_id(account, None)
+            .await
+            .unwrap_or_else(|err| failed(&format!("Failed to resolve account {account}: {err}")))
+            .unwrap_or_else(|| failed(&format!("Account {account} does not exist.")));
+        let report = Box::pin(server.email_import_path(account_id, path))
+            .await
+            .unwrap_or_else(|err| failed(&format!("Failed to import {}: {err}", path.display())));
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).unwrap_or_default()
+        );
+        return Ok(());
+    }
+
     // Init services
     init.start_services().await;
     init.start_queue_manager();
@@ -106,6 +168,12 @@ async fn main() -> std::io::Result<()> {
                 acceptor,
                 shutdown_rx,
             ),
+            ServerProtocol::AgentCheck => server.spawn(
+                AgentCheckSessionManager::new(init.inner.clone()),
+                init.inner.clone(),
+                acceptor,
+                shutdown_rx,
+            ),
         };
     });
 
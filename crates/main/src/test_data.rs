@@ -171,6 +171,7 @@ fn sample_queued_messages(blob_hashes: Vec<BlobHash>) -> Vec<Message> {
             priority: 0,
             size: raw_messages[0].len() as u64,
             quota_keys: Box::new([]),
+            tags: Box::new([]),
         },
         // Message 2: DSN bounce message with a temporary failure recipient
         Message {
@@ -210,6 +211,7 @@ fn sample_queued_messages(blob_hashes: Vec<BlobHash>) -> Vec<Message> {
             priority: -5,
             size: raw_messages[1].len() as u64,
             quota_keys: Box::new([]),
+            tags: Box::new([]),
         },
         // Message 3: Report message with a temporary failure recipient
         Message {
@@ -242,6 +244,7 @@ fn sample_queued_messages(blob_hashes: Vec<BlobHash>) -> Vec<Message> {
             priority: 10,
             size: raw_messages[2].len() as u64,
             quota_keys: Box::new([]),
+            tags: Box::new([]),
         },
     ]
 }
@@ -87,6 +87,9 @@ impl<T: SessionStream> Session<T> {
 
         for request in requests {
             let command = request.command;
+            self.instance
+                .sessions
+                .set_command(self.session_id, command.as_str());
             match match command {
                 Command::ListScripts => self.handle_listscripts().await,
                 Command::PutScript => self.handle_putscript(request).await,
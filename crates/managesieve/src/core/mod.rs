@@ -105,6 +105,27 @@ impl CommandParser for Command {
     }
 }
 
+impl Command {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Command::Authenticate => "AUTHENTICATE",
+            Command::StartTls => "STARTTLS",
+            Command::Logout => "LOGOUT",
+            Command::Capability => "CAPABILITY",
+            Command::HaveSpace => "HAVESPACE",
+            Command::PutScript => "PUTSCRIPT",
+            Command::ListScripts => "LISTSCRIPTS",
+            Command::SetActive => "SETACTIVE",
+            Command::GetScript => "GETSCRIPT",
+            Command::DeleteScript => "DELETESCRIPT",
+            Command::RenameScript => "RENAMESCRIPT",
+            Command::CheckScript => "CHECKSCRIPT",
+            Command::Noop => "NOOP",
+            Command::Unauthenticate => "UNAUTHENTICATE",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StatusResponse {
     pub code: Option<ResponseCode>,
@@ -8,7 +8,9 @@ use super::{ManageSieveSessionManager, Session, State};
 use crate::SERVER_GREETING;
 use common::{
     BuildServer,
-    network::{SessionData, SessionManager, SessionResult, SessionStream},
+    network::{
+        SessionData, SessionManager, SessionResult, SessionStream, fingerprint::FingerprintStream,
+    },
 };
 use imap_proto::receiver::{self, Receiver};
 use tokio_rustls::server::TlsStream;
@@ -130,11 +132,11 @@ impl<T: SessionStream> Session<T> {
         false
     }
 
-    pub async fn into_tls(self) -> Result<Session<TlsStream<T>>, ()> {
+    pub async fn into_tls(self) -> Result<Session<TlsStream<FingerprintStream<T>>>, ()> {
         Ok(Session {
             stream: self
                 .instance
-                .tls_accept(self.stream, self.session_id)
+                .tls_accept(self.stream, self.session_id, &self.server, self.remote_addr)
                 .await?,
             state: self.state,
             instance: self.instance,
@@ -7,6 +7,7 @@
 use crate::core::{Command, Session, State, StatusResponse};
 use common::{
     auth::AuthRequest,
+    config::server::ServerProtocol,
     network::{SessionStream, limiter::LimiterResult},
 };
 use directory::Credentials;
@@ -72,6 +73,7 @@ impl<T: SessionStream> Session<T> {
                 credentials,
                 self.session_id,
                 self.remote_addr,
+                ServerProtocol::ManageSieve,
             ))
             .await
             .map_err(|err| {
@@ -104,6 +106,9 @@ impl<T: SessionStream> Session<T> {
         };
 
         // Create session
+        self.instance
+            .sessions
+            .set_account(self.session_id, Some(&access_token));
         self.state = State::Authenticated {
             access_token,
             in_flight,
@@ -114,6 +119,7 @@ impl<T: SessionStream> Session<T> {
 
     pub async fn handle_unauthenticate(&mut self) -> trc::Result<Vec<u8>> {
         self.state = State::NotAuthenticated { auth_failures: 0 };
+        self.instance.sessions.set_account(self.session_id, None);
 
         trc::event!(
             ManageSieve(trc::ManageSieveEvent::Unauthenticate),
@@ -162,6 +162,10 @@ impl<T: SessionStream> Session<T> {
             };
 
             match result {
+                Ok(SessionResult::Continue) if self.backend.is_some() => {
+                    // The backend takes over the rest of the session
+                    return SessionResult::Continue;
+                }
                 Ok(SessionResult::Continue) => (),
                 Ok(result) => return result,
                 Err(err) => {
@@ -179,6 +183,10 @@ impl<T: SessionStream> Session<T> {
         &self,
         command: Command<String, Mechanism>,
     ) -> trc::Result<Command<String, Mechanism>> {
+        self.instance
+            .sessions
+            .set_command(self.session_id, command.as_str());
+
         match &command {
             Command::Capa | Command::Quit | Command::Noop => Ok(command),
             Command::Auth {
@@ -11,7 +11,7 @@ use std::{net::IpAddr, sync::Arc};
 use common::{
     Inner, Server,
     auth::AccessToken,
-    network::{ServerInstance, SessionStream, limiter::InFlight},
+    network::{ServerInstance, SessionStream, backend::BackendConnection, limiter::InFlight},
 };
 use mailbox::Mailbox;
 use protocol::request::Parser;
@@ -20,6 +20,7 @@ pub mod client;
 pub mod mailbox;
 pub mod op;
 pub mod protocol;
+pub mod proxy;
 pub mod session;
 
 static SERVER_GREETING: &str = "+OK Stalwart POP3 at your service.\r\n";
@@ -44,6 +45,7 @@ pub struct Session<T: SessionStream> {
     pub in_flight: InFlight,
     pub remote_addr: IpAddr,
     pub session_id: u64,
+    pub backend: Option<BackendConnection>,
 }
 
 pub enum State {
@@ -79,4 +81,11 @@ impl State {
             _ => unreachable!(),
         }
     }
+
+    pub fn account_id(&self) -> Option<u32> {
+        match self {
+            State::Authenticated { access_token, .. } => Some(access_token.account_id()),
+            State::NotAuthenticated { .. } => None,
+        }
+    }
 }
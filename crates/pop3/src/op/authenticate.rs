@@ -10,6 +10,7 @@ use crate::{
 };
 use common::{
     auth::AuthRequest,
+    config::server::ServerProtocol,
     network::{SessionStream, limiter::LimiterResult},
 };
 use directory::Credentials;
@@ -62,13 +63,15 @@ impl<T: SessionStream> Session<T> {
 
     pub async fn handle_auth(&mut self, credentials: Credentials) -> trc::Result<()> {
         // Authenticate
+        let request = AuthRequest::from_credentials(
+            credentials,
+            self.session_id,
+            self.remote_addr,
+            ServerProtocol::Pop3,
+        );
         let access_token = self
             .server
-            .authenticate(&AuthRequest::from_credentials(
-                credentials,
-                self.session_id,
-                self.remote_addr,
-            ))
+            .authenticate(&request)
             .await
             .map_err(|err| {
                 if err.matches(trc::EventType::Auth(trc::AuthEvent::Failed)) {
@@ -92,6 +95,19 @@ impl<T: SessionStream> Session<T> {
             })
             .and_then(|token| token.assert_has_permission(Permission::Pop3Authenticate))?;
 
+        // Accounts assigned to a backend are relayed using the same credentials
+        if let Credentials::Basic {
+            username, secret, ..
+        } = &request.credentials
+            && let Some(hostname) = self
+                .server
+                .account_routing(access_token.account_id())
+                .await?
+                .proxy_backend
+        {
+            return Box::pin(self.proxy_login(&access_token, hostname, username, secret)).await;
+        }
+
         // Enforce concurrency limits
         let in_flight = match access_token.is_imap_request_allowed() {
             LimiterResult::Allowed(in_flight) => Some(in_flight),
@@ -105,6 +121,9 @@ impl<T: SessionStream> Session<T> {
         let mailbox = self.fetch_mailbox(access_token.account_id()).await?;
 
         // Create session
+        self.instance
+            .sessions
+            .set_account(self.session_id, Some(&access_token));
         self.state = State::Authenticated {
             in_flight,
             mailbox,
@@ -110,6 +110,14 @@ impl<T: SessionStream> Session<T> {
                         "Stalwart POP3 bids you farewell ({num_deleted} messages deleted)."
                     ))
                     .await?;
+                } else if !not_deleted.legal_hold.is_empty() {
+                    self.write_bytes(
+                        Response::Err::<u32>(
+                            "Some messages are under legal hold and could not be deleted".into(),
+                        )
+                        .serialize(),
+                    )
+                    .await?;
                 } else {
                     self.write_bytes(
                         Response::Err::<u32>("Some messages could not be deleted".into())
@@ -57,6 +57,29 @@ pub enum Command<T, M> {
     },
 }
 
+impl<T, M> Command<T, M> {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Command::User { .. } => "USER",
+            Command::Pass { .. } => "PASS",
+            Command::Apop { .. } => "APOP",
+            Command::Quit => "QUIT",
+            Command::Stat => "STAT",
+            Command::List { .. } => "LIST",
+            Command::Retr { .. } => "RETR",
+            Command::Dele { .. } | Command::DeleMany { .. } => "DELE",
+            Command::Noop => "NOOP",
+            Command::Rset => "RSET",
+            Command::Top { .. } => "TOP",
+            Command::Uidl { .. } => "UIDL",
+            Command::Capa => "CAPA",
+            Command::Stls => "STLS",
+            Command::Utf8 => "UTF8",
+            Command::Auth { .. } => "AUTH",
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Mechanism {
     Plain,
@@ -0,0 +1,124 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::Session;
+use common::{
+    auth::AccessToken,
+    network::{SessionStream, backend::BackendConnection},
+};
+use tokio::sync::watch;
+
+impl<T: SessionStream> Session<T> {
+    pub async fn proxy_login(
+        &mut self,
+        access_token: &AccessToken,
+        hostname: String,
+        username: &str,
+        secret: &str,
+    ) -> trc::Result<()> {
+        let port = self.server.core.imap.proxy_pop3_port;
+        let mut backend = async {
+            let mut backend = self.server.connect_backend(&hostname, port).await?;
+            let greeting = backend.read_line().await?;
+            if !greeting.starts_with("+OK") {
+                return Err(format!("Unexpected greeting from {hostname}: {greeting}"));
+            }
+
+            // Credentials are never relayed in clear text
+            if !backend.is_tls() {
+                backend.write(b"STLS\r\n").await?;
+                let response = backend.read_line().await?;
+                if !response.starts_with("+OK") {
+                    return Err(format!("{hostname} does not support STLS: {response}"));
+                }
+                backend = self
+                    .server
+                    .start_tls(backend, self.server.core.imap.proxy_allow_invalid_certs)
+                    .await?;
+            }
+
+            Ok(backend)
+        }
+        .await
+        .map_err(backend_error)?;
+
+        // Log into the backend reusing the client's credentials
+        let response = async {
+            backend
+                .write(format!("USER {username}\r\n").as_bytes())
+                .await?;
+            let response = backend.read_line().await?;
+            if !response.starts_with("+OK") {
+                return Ok(response);
+            }
+
+            backend
+                .write(format!("PASS {secret}\r\n").as_bytes())
+                .await?;
+            backend.read_line().await
+        }
+        .await
+        .map_err(backend_error)?;
+
+        if !response.starts_with("+OK") {
+            return Err(trc::AuthEvent::Failed
+                .into_err()
+                .details("Backend rejected the credentials")
+                .ctx(trc::Key::Hostname, hostname)
+                .reason(response));
+        }
+
+        trc::event!(
+            Pop3(trc::Pop3Event::Proxy),
+            SpanId = self.session_id,
+            AccountId = access_token.account_id(),
+            Hostname = hostname,
+            RemotePort = port,
+        );
+
+        self.instance
+            .sessions
+            .set_account(self.session_id, Some(access_token));
+        self.write_bytes(format!("{response}\r\n")).await?;
+        self.backend = Some(backend);
+
+        Ok(())
+    }
+
+    pub async fn proxy(
+        &mut self,
+        backend: BackendConnection,
+        shutdown_rx: &mut watch::Receiver<bool>,
+    ) {
+        let hostname = backend.hostname.clone();
+        let (mut stream_rx, mut stream_tx) = tokio::io::split(&mut self.stream);
+
+        if let Err(err) = backend
+            .proxy(
+                &mut stream_rx,
+                &mut stream_tx,
+                self.server.core.imap.timeout_idle,
+                shutdown_rx,
+            )
+            .await
+        {
+            trc::event!(
+                Pop3(trc::Pop3Event::Error),
+                SpanId = self.session_id,
+                Hostname = hostname,
+                Reason = err,
+                CausedBy = trc::location!()
+            );
+        }
+    }
+}
+
+fn backend_error(err: String) -> trc::Error {
+    trc::Pop3Event::Error
+        .into_err()
+        .details("Backend server unavailable")
+        .reason(err)
+}
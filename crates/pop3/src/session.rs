@@ -13,7 +13,10 @@ use crate::{
 };
 use common::{
     BuildServer,
-    network::{SessionData, SessionManager, SessionResult, SessionStream},
+    network::{
+        SessionData, SessionManager, SessionResult, SessionStream, fingerprint::FingerprintStream,
+    },
+    telemetry::metrics::bandwidth::BandwidthProtocol,
 };
 use std::borrow::Cow;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -38,6 +41,7 @@ impl SessionManager for Pop3SessionManager {
                 in_flight: session.in_flight,
                 remote_addr: session.remote_ip,
                 session_id: session.session_id,
+                backend: None,
             };
 
             if session
@@ -76,8 +80,21 @@ impl<T: SessionStream> Session<T> {
                     match result {
                         Ok(Ok(bytes_read)) => {
                             if bytes_read > 0 {
+                                if let Some(account_id) = self.state.account_id() {
+                                    self.server.record_bandwidth(
+                                        account_id,
+                                        BandwidthProtocol::Pop3,
+                                        bytes_read,
+                                        0,
+                                    );
+                                }
                                 match self.ingest(&buf[..bytes_read]).await {
-                                    SessionResult::Continue => (),
+                                    SessionResult::Continue => {
+                                        if let Some(backend) = self.backend.take() {
+                                            self.proxy(backend, &mut shutdown_rx).await;
+                                            break;
+                                        }
+                                    }
                                     SessionResult::UpgradeTls => {
                                         return true;
                                     }
@@ -132,11 +149,11 @@ impl<T: SessionStream> Session<T> {
         false
     }
 
-    pub async fn into_tls(self) -> Result<Session<TlsStream<T>>, ()> {
+    pub async fn into_tls(self) -> Result<Session<TlsStream<FingerprintStream<T>>>, ()> {
         Ok(Session {
             stream: self
                 .instance
-                .tls_accept(self.stream, self.session_id)
+                .tls_accept(self.stream, self.session_id, &self.server, self.remote_addr)
                 .await?,
             server: self.server,
             instance: self.instance,
@@ -145,6 +162,7 @@ impl<T: SessionStream> Session<T> {
             session_id: self.session_id,
             in_flight: self.in_flight,
             remote_addr: self.remote_addr,
+            backend: self.backend,
         })
     }
 }
@@ -160,6 +178,11 @@ impl<T: SessionStream> Session<T> {
             Contents = trc::Value::from_maybe_string(bytes),
         );
 
+        if let Some(account_id) = self.state.account_id() {
+            self.server
+                .record_bandwidth(account_id, BandwidthProtocol::Pop3, 0, bytes.len());
+        }
+
         self.stream.write_all(bytes.as_ref()).await.map_err(|err| {
             trc::NetworkEvent::WriteError
                 .into_err()
@@ -51,6 +51,34 @@ pub enum ActionType {
     ResumeMtaQueue = 10,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum AddressBookDirectoryField {
+    #[default]
+    Name = 0,
+    Description = 1,
+    Email = 2,
+    Aliases = 3,
+    MemberOf = 4,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum AddressBookDirectoryScope {
+    #[default]
+    Tenant = 0,
+    Domain = 1,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum AddressRewriteScope {
+    #[default]
+    MailFrom = 0,
+    RcptTo = 1,
+    Headers = 2,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum AiModelType {
@@ -187,6 +215,16 @@ pub enum BlockReason {
     PortScanning = 3,
     Manual = 4,
     Other = 5,
+    SpamTrap = 6,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum CaptchaProvider {
+    #[default]
+    Disabled = 0,
+    HCaptcha = 1,
+    Turnstile = 2,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -549,6 +587,15 @@ pub enum DnsServerType {
     GoogleCloudDns = 11,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum DuplicateAction {
+    #[default]
+    Disabled = 0,
+    Discard = 1,
+    Tag = 2,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum EncryptionAtRestType {
@@ -558,6 +605,15 @@ pub enum EncryptionAtRestType {
     Aes256 = 2,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum EncryptionPolicy {
+    #[default]
+    Optional = 0,
+    Required = 1,
+    Disabled = 2,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum EventPolicy {
@@ -620,6 +676,7 @@ pub enum ExpressionVariable {
     EnvFromDomain = 22,
     EnvFromLocal = 23,
     EnvTo = 24,
+    EnvToDetail = 91,
     ExpiresIn = 25,
     From = 26,
     FromDomain = 27,
@@ -679,6 +736,8 @@ pub enum ExpressionVariable {
     Subject = 81,
     SubjectThread = 82,
     SubjectWords = 83,
+    TlsJa3 = 92,
+    TlsJa4 = 93,
     To = 84,
     ToDomain = 85,
     ToLocal = 86,
@@ -698,6 +757,25 @@ pub enum FailureReportingOption {
     SpfFailure = 3,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum FirewallSyncType {
+    #[default]
+    Disabled = 0,
+    Nftables = 1,
+    Ipset = 2,
+    Webhook = 3,
+    Crowdsec = 4,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum FreeBusyAccess {
+    #[default]
+    Authenticated = 0,
+    Anonymous = 1,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum HttpAuthType {
@@ -751,6 +829,23 @@ pub enum IpProtocol {
     Tcp = 1,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum JmapCapability {
+    #[default]
+    Mail = 0,
+    Submission = 1,
+    VacationResponse = 2,
+    Contacts = 3,
+    Calendars = 4,
+    WebSocket = 5,
+    Sieve = 6,
+    Blob = 7,
+    Quota = 8,
+    Principals = 9,
+    FileNode = 10,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum JwtSignatureAlgorithm {
@@ -1120,6 +1215,26 @@ pub enum LogRotateFrequency {
     Never = 3,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum LoginAnomalySensitivity {
+    #[default]
+    Disabled = 0,
+    Low = 1,
+    Medium = 2,
+    High = 3,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum LoginAnomalyType {
+    #[default]
+    NewCountry = 0,
+    ImpossibleTravel = 1,
+    NewNetwork = 2,
+    NewClient = 3,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum LookupStoreType {
@@ -1319,6 +1434,7 @@ pub enum NetworkListenerProtocol {
     Imap = 3,
     Pop3 = 4,
     ManageSieve = 5,
+    AgentCheck = 6,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -1370,6 +1486,7 @@ pub enum Permission {
     CalendarAlarmsSend = 9,
     CalendarSchedulingSend = 10,
     CalendarSchedulingReceive = 11,
+    CalendarSubscribe = 659,
     JmapPushSubscriptionGet = 12,
     JmapPushSubscriptionCreate = 13,
     JmapPushSubscriptionUpdate = 14,
@@ -1602,6 +1719,7 @@ pub enum Permission {
     ActionInvalidateNegativeCaches = 241,
     ActionPauseMtaQueue = 242,
     ActionResumeMtaQueue = 243,
+    ActionExportSettingsSecrets = 680,
     SysActionGet = 244,
     SysActionCreate = 245,
     SysActionUpdate = 246,
@@ -1876,6 +1994,8 @@ pub enum Permission {
     SysPublicKeyUpdate = 515,
     SysPublicKeyDestroy = 516,
     SysPublicKeyQuery = 517,
+    SysQueueStoreGet = 670,
+    SysQueueStoreUpdate = 671,
     SysQueuedMessageGet = 518,
     SysQueuedMessageCreate = 519,
     SysQueuedMessageUpdate = 520,
@@ -1930,6 +2050,8 @@ pub enum Permission {
     SysSpamLlmUpdate = 569,
     SysSpamPyzorGet = 570,
     SysSpamPyzorUpdate = 571,
+    SysSpamRspamdGet = 668,
+    SysSpamRspamdUpdate = 669,
     SysSpamRuleGet = 572,
     SysSpamRuleCreate = 573,
     SysSpamRuleUpdate = 574,
@@ -1954,6 +2076,11 @@ pub enum Permission {
     SysStoreLookupUpdate = 593,
     SysStoreLookupDestroy = 594,
     SysStoreLookupQuery = 595,
+    SysSuppressedAddressGet = 673,
+    SysSuppressedAddressCreate = 674,
+    SysSuppressedAddressUpdate = 675,
+    SysSuppressedAddressDestroy = 676,
+    SysSuppressedAddressQuery = 677,
     SysSystemSettingsGet = 596,
     SysSystemSettingsUpdate = 597,
     TaskIndexDocument = 598,
@@ -1974,6 +2101,12 @@ pub enum Permission {
     TaskAcmeRenewal = 613,
     TaskDkimManagement = 614,
     TaskDnsManagement = 615,
+    TaskCalendarSubscriptionSync = 660,
+    TaskAddressBookDirectorySync = 666,
+    TaskAccountMigration = 672,
+    TaskLoginAnomaly = 678,
+    EmailReceiveCreateMailbox = 667,
+    EmailForward = 679,
     SysTaskGet = 616,
     SysTaskCreate = 617,
     SysTaskUpdate = 618,
@@ -2017,6 +2150,11 @@ pub enum Permission {
     SysWebHookUpdate = 656,
     SysWebHookDestroy = 657,
     SysWebHookQuery = 658,
+    SysShareLinkGet = 661,
+    SysShareLinkCreate = 662,
+    SysShareLinkUpdate = 663,
+    SysShareLinkDestroy = 664,
+    SysShareLinkQuery = 665,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -2058,6 +2196,7 @@ pub enum ProviderInfo {
     LogoUrl = 5,
     LogoWidth = 6,
     LogoHeight = 7,
+    BrandColor = 8,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -2077,6 +2216,18 @@ pub enum QueueExpiryType {
     Attempts = 1,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum QueueStoreType {
+    #[default]
+    Default = 0,
+    RocksDb = 1,
+    Sqlite = 2,
+    FoundationDb = 3,
+    PostgreSql = 4,
+    MySql = 5,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum RecipientFlag {
@@ -2103,6 +2254,23 @@ pub enum RedisProtocol {
     Resp3 = 1,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum RelayVerification {
+    #[default]
+    Disabled = 0,
+    Smtp = 1,
+    Http = 2,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum RetentionAction {
+    #[default]
+    Archive = 0,
+    Delete = 1,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum RolesType {
@@ -2160,6 +2328,25 @@ pub enum S3StoreRegionType {
     Custom = 42,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum SchedulingResourcePolicy {
+    #[default]
+    AcceptIfFree = 0,
+    AcceptAlways = 1,
+    DeclineAlways = 2,
+    Manual = 3,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum SchedulingResourceType {
+    #[default]
+    None = 0,
+    Room = 1,
+    Equipment = 2,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum SearchCalendarField {
@@ -2289,6 +2476,14 @@ pub enum ServiceProtocol {
     Managesieve = 7,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum ShareLinkCollection {
+    #[default]
+    Calendar = 0,
+    AddressBook = 1,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum SieveCapability {
@@ -2517,6 +2712,16 @@ pub enum SubAddressingType {
     Disabled = 2,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum SuppressionReason {
+    #[default]
+    HardBounce = 0,
+    Unsubscribe = 1,
+    Complaint = 2,
+    Manual = 3,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum TaskAccountMaintenanceType {
@@ -2525,6 +2730,8 @@ pub enum TaskAccountMaintenanceType {
     Reindex = 1,
     RecalculateImapUid = 2,
     RecalculateQuota = 3,
+    RepairThreads = 4,
+    EncryptMessages = 5,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -2605,6 +2812,10 @@ pub enum TaskType {
     AcmeRenewal = 15,
     DkimManagement = 16,
     DnsManagement = 17,
+    CalendarSubscriptionSync = 18,
+    AddressBookDirectorySync = 19,
+    AccountMigration = 20,
+    LoginAnomaly = 21,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -3243,6 +3454,24 @@ pub enum TlsCipherSuite {
     TlsEcdheRsaWithChacha20Poly1305Sha256 = 8,
 }
 
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum TlsClientAuth {
+    #[default]
+    Disabled = 0,
+    Optional = 1,
+    Required = 2,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum TlsClientCertField {
+    #[default]
+    SanEmail = 0,
+    SubjectEmail = 1,
+    SubjectCommonName = 2,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum TlsPolicyType {
@@ -3305,6 +3534,7 @@ pub enum TracerType {
     Journal = 2,
     OtelHttp = 3,
     OtelGrpc = 4,
+    Syslog = 5,
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
@@ -3374,10 +3604,14 @@ pub static HTTP_VARIABLE: &[ExpressionVariable] = &[
     ExpressionVariable::LocalPort,
     ExpressionVariable::Protocol,
     ExpressionVariable::IsTls,
+    ExpressionVariable::TlsJa3,
+    ExpressionVariable::TlsJa4,
     ExpressionVariable::Url,
     ExpressionVariable::Path,
     ExpressionVariable::Headers,
     ExpressionVariable::Method,
+    ExpressionVariable::Asn,
+    ExpressionVariable::Country,
 ];
 
 pub static MTA_CONNECTION_VARIABLE: &[ExpressionVariable] = &[
@@ -3388,6 +3622,8 @@ pub static MTA_CONNECTION_VARIABLE: &[ExpressionVariable] = &[
     ExpressionVariable::LocalPort,
     ExpressionVariable::Protocol,
     ExpressionVariable::IsTls,
+    ExpressionVariable::TlsJa3,
+    ExpressionVariable::TlsJa4,
     ExpressionVariable::Asn,
     ExpressionVariable::Country,
 ];
@@ -3400,6 +3636,8 @@ pub static MTA_EHLO_VARIABLE: &[ExpressionVariable] = &[
     ExpressionVariable::LocalPort,
     ExpressionVariable::Protocol,
     ExpressionVariable::IsTls,
+    ExpressionVariable::TlsJa3,
+    ExpressionVariable::TlsJa4,
     ExpressionVariable::HeloDomain,
     ExpressionVariable::Asn,
     ExpressionVariable::Country,
@@ -3413,6 +3651,8 @@ pub static MTA_MAIL_FROM_VARIABLE: &[ExpressionVariable] = &[
     ExpressionVariable::LocalPort,
     ExpressionVariable::Protocol,
     ExpressionVariable::IsTls,
+    ExpressionVariable::TlsJa3,
+    ExpressionVariable::TlsJa4,
     ExpressionVariable::Sender,
     ExpressionVariable::SenderDomain,
     ExpressionVariable::AuthenticatedAs,
@@ -3443,6 +3683,17 @@ pub static MTA_QUEUE_HOST_VARIABLE: &[ExpressionVariable] = &[
     ExpressionVariable::Size,
 ];
 
+pub static MTA_QUEUE_MESSAGE_VARIABLE: &[ExpressionVariable] = &[
+    ExpressionVariable::Sender,
+    ExpressionVariable::SenderDomain,
+    ExpressionVariable::Recipients,
+    ExpressionVariable::Priority,
+    ExpressionVariable::ReceivedFromIp,
+    ExpressionVariable::ReceivedViaPort,
+    ExpressionVariable::Source,
+    ExpressionVariable::Size,
+];
+
 pub static MTA_QUEUE_RCPT_VARIABLE: &[ExpressionVariable] = &[
     ExpressionVariable::Rcpt,
     ExpressionVariable::RcptDomain,
@@ -3490,6 +3741,8 @@ pub static MTA_RCPT_TO_VARIABLE: &[ExpressionVariable] = &[
     ExpressionVariable::LocalPort,
     ExpressionVariable::Protocol,
     ExpressionVariable::IsTls,
+    ExpressionVariable::TlsJa3,
+    ExpressionVariable::TlsJa4,
     ExpressionVariable::Priority,
     ExpressionVariable::HeloDomain,
     ExpressionVariable::Asn,
@@ -3506,10 +3759,13 @@ pub static SPAM_DEFAULT_VARIABLE: &[ExpressionVariable] = &[
     ExpressionVariable::Asn,
     ExpressionVariable::Country,
     ExpressionVariable::IsTls,
+    ExpressionVariable::TlsJa3,
+    ExpressionVariable::TlsJa4,
     ExpressionVariable::EnvFrom,
     ExpressionVariable::EnvFromLocal,
     ExpressionVariable::EnvFromDomain,
     ExpressionVariable::EnvTo,
+    ExpressionVariable::EnvToDetail,
     ExpressionVariable::From,
     ExpressionVariable::FromName,
     ExpressionVariable::FromLocal,
@@ -3555,10 +3811,13 @@ pub static SPAM_EMAIL_VARIABLE: &[ExpressionVariable] = &[
     ExpressionVariable::Asn,
     ExpressionVariable::Country,
     ExpressionVariable::IsTls,
+    ExpressionVariable::TlsJa3,
+    ExpressionVariable::TlsJa4,
     ExpressionVariable::EnvFrom,
     ExpressionVariable::EnvFromLocal,
     ExpressionVariable::EnvFromDomain,
     ExpressionVariable::EnvTo,
+    ExpressionVariable::EnvToDetail,
     ExpressionVariable::From,
     ExpressionVariable::FromName,
     ExpressionVariable::FromLocal,
@@ -3599,10 +3858,13 @@ pub static SPAM_GENERIC_VARIABLE: &[ExpressionVariable] = &[
     ExpressionVariable::Asn,
     ExpressionVariable::Country,
     ExpressionVariable::IsTls,
+    ExpressionVariable::TlsJa3,
+    ExpressionVariable::TlsJa4,
     ExpressionVariable::EnvFrom,
     ExpressionVariable::EnvFromLocal,
     ExpressionVariable::EnvFromDomain,
     ExpressionVariable::EnvTo,
+    ExpressionVariable::EnvToDetail,
     ExpressionVariable::From,
     ExpressionVariable::FromName,
     ExpressionVariable::FromLocal,
@@ -3649,10 +3911,13 @@ pub static SPAM_HEADER_VARIABLE: &[ExpressionVariable] = &[
     ExpressionVariable::Asn,
     ExpressionVariable::Country,
     ExpressionVariable::IsTls,
+    ExpressionVariable::TlsJa3,
+    ExpressionVariable::TlsJa4,
     ExpressionVariable::EnvFrom,
     ExpressionVariable::EnvFromLocal,
     ExpressionVariable::EnvFromDomain,
     ExpressionVariable::EnvTo,
+    ExpressionVariable::EnvToDetail,
     ExpressionVariable::From,
     ExpressionVariable::FromName,
     ExpressionVariable::FromLocal,
@@ -3699,10 +3964,13 @@ pub static SPAM_IP_VARIABLE: &[ExpressionVariable] = &[
     ExpressionVariable::Asn,
     ExpressionVariable::Country,
     ExpressionVariable::IsTls,
+    ExpressionVariable::TlsJa3,
+    ExpressionVariable::TlsJa4,
     ExpressionVariable::EnvFrom,
     ExpressionVariable::EnvFromLocal,
     ExpressionVariable::EnvFromDomain,
     ExpressionVariable::EnvTo,
+    ExpressionVariable::EnvToDetail,
     ExpressionVariable::From,
     ExpressionVariable::FromName,
     ExpressionVariable::FromLocal,
@@ -3752,10 +4020,13 @@ pub static SPAM_URL_VARIABLE: &[ExpressionVariable] = &[
     ExpressionVariable::Asn,
     ExpressionVariable::Country,
     ExpressionVariable::IsTls,
+    ExpressionVariable::TlsJa3,
+    ExpressionVariable::TlsJa4,
     ExpressionVariable::EnvFrom,
     ExpressionVariable::EnvFromLocal,
     ExpressionVariable::EnvFromDomain,
     ExpressionVariable::EnvTo,
+    ExpressionVariable::EnvToDetail,
     ExpressionVariable::From,
     ExpressionVariable::FromName,
     ExpressionVariable::FromLocal,
@@ -247,6 +247,168 @@ impl<'de> serde::Deserialize<'de> for ActionType {
     }
 }
 
+impl EnumImpl for AddressBookDirectoryField {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"name" => AddressBookDirectoryField::Name,
+            b"description" => AddressBookDirectoryField::Description,
+            b"email" => AddressBookDirectoryField::Email,
+            b"aliases" => AddressBookDirectoryField::Aliases,
+            b"memberOf" => AddressBookDirectoryField::MemberOf,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            AddressBookDirectoryField::Name => "name",
+            AddressBookDirectoryField::Description => "description",
+            AddressBookDirectoryField::Email => "email",
+            AddressBookDirectoryField::Aliases => "aliases",
+            AddressBookDirectoryField::MemberOf => "memberOf",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(AddressBookDirectoryField::Name),
+            1 => Some(AddressBookDirectoryField::Description),
+            2 => Some(AddressBookDirectoryField::Email),
+            3 => Some(AddressBookDirectoryField::Aliases),
+            4 => Some(AddressBookDirectoryField::MemberOf),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 5;
+}
+
+impl serde::Serialize for AddressBookDirectoryField {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for AddressBookDirectoryField {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
+impl EnumImpl for AddressBookDirectoryScope {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"tenant" => AddressBookDirectoryScope::Tenant,
+            b"domain" => AddressBookDirectoryScope::Domain,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            AddressBookDirectoryScope::Tenant => "tenant",
+            AddressBookDirectoryScope::Domain => "domain",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(AddressBookDirectoryScope::Tenant),
+            1 => Some(AddressBookDirectoryScope::Domain),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 2;
+}
+
+impl serde::Serialize for AddressBookDirectoryScope {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for AddressBookDirectoryScope {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
+impl EnumImpl for AddressRewriteScope {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"mailFrom" => AddressRewriteScope::MailFrom,
+            b"rcptTo" => AddressRewriteScope::RcptTo,
+            b"headers" => AddressRewriteScope::Headers,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            AddressRewriteScope::MailFrom => "mailFrom",
+            AddressRewriteScope::RcptTo => "rcptTo",
+            AddressRewriteScope::Headers => "headers",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(AddressRewriteScope::MailFrom),
+            1 => Some(AddressRewriteScope::RcptTo),
+            2 => Some(AddressRewriteScope::Headers),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for AddressRewriteScope {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for AddressRewriteScope {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for AiModelType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -947,6 +1109,7 @@ impl EnumImpl for BlockReason {
             b"portScanning" => BlockReason::PortScanning,
             b"manual" => BlockReason::Manual,
             b"other" => BlockReason::Other,
+            b"spamTrap" => BlockReason::SpamTrap,
         }
     }
 
@@ -958,6 +1121,7 @@ impl EnumImpl for BlockReason {
             BlockReason::PortScanning => "portScanning",
             BlockReason::Manual => "manual",
             BlockReason::Other => "other",
+            BlockReason::SpamTrap => "spamTrap",
         }
     }
 
@@ -973,11 +1137,12 @@ impl EnumImpl for BlockReason {
             3 => Some(BlockReason::PortScanning),
             4 => Some(BlockReason::Manual),
             5 => Some(BlockReason::Other),
+            6 => Some(BlockReason::SpamTrap),
             _ => None,
         }
     }
 
-    const COUNT: usize = 6;
+    const COUNT: usize = 7;
 }
 
 impl serde::Serialize for BlockReason {
@@ -999,6 +1164,59 @@ impl<'de> serde::Deserialize<'de> for BlockReason {
     }
 }
 
+impl EnumImpl for CaptchaProvider {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"disabled" => CaptchaProvider::Disabled,
+            b"hCaptcha" => CaptchaProvider::HCaptcha,
+            b"turnstile" => CaptchaProvider::Turnstile,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            CaptchaProvider::Disabled => "disabled",
+            CaptchaProvider::HCaptcha => "hCaptcha",
+            CaptchaProvider::Turnstile => "turnstile",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(CaptchaProvider::Disabled),
+            1 => Some(CaptchaProvider::HCaptcha),
+            2 => Some(CaptchaProvider::Turnstile),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for CaptchaProvider {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for CaptchaProvider {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for CertificateManagementType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -2937,6 +3155,59 @@ impl<'de> serde::Deserialize<'de> for DnsServerType {
     }
 }
 
+impl EnumImpl for DuplicateAction {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"disabled" => DuplicateAction::Disabled,
+            b"discard" => DuplicateAction::Discard,
+            b"tag" => DuplicateAction::Tag,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            DuplicateAction::Disabled => "disabled",
+            DuplicateAction::Discard => "discard",
+            DuplicateAction::Tag => "tag",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(DuplicateAction::Disabled),
+            1 => Some(DuplicateAction::Discard),
+            2 => Some(DuplicateAction::Tag),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for DuplicateAction {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for DuplicateAction {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for EncryptionAtRestType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -2990,6 +3261,59 @@ impl<'de> serde::Deserialize<'de> for EncryptionAtRestType {
     }
 }
 
+impl EnumImpl for EncryptionPolicy {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"optional" => EncryptionPolicy::Optional,
+            b"required" => EncryptionPolicy::Required,
+            b"disabled" => EncryptionPolicy::Disabled,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            EncryptionPolicy::Optional => "optional",
+            EncryptionPolicy::Required => "required",
+            EncryptionPolicy::Disabled => "disabled",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(EncryptionPolicy::Optional),
+            1 => Some(EncryptionPolicy::Required),
+            2 => Some(EncryptionPolicy::Disabled),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for EncryptionPolicy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for EncryptionPolicy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for EventPolicy {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -3171,6 +3495,7 @@ impl EnumImpl for ExpressionVariable {
             b"env_from.domain" => ExpressionVariable::EnvFromDomain,
             b"env_from.local" => ExpressionVariable::EnvFromLocal,
             b"env_to" => ExpressionVariable::EnvTo,
+            b"env_to.detail" => ExpressionVariable::EnvToDetail,
             b"expires_in" => ExpressionVariable::ExpiresIn,
             b"from" => ExpressionVariable::From,
             b"from.domain" => ExpressionVariable::FromDomain,
@@ -3230,6 +3555,8 @@ impl EnumImpl for ExpressionVariable {
             b"subject" => ExpressionVariable::Subject,
             b"subject.thread" => ExpressionVariable::SubjectThread,
             b"subject.words" => ExpressionVariable::SubjectWords,
+            b"tls_ja3" => ExpressionVariable::TlsJa3,
+            b"tls_ja4" => ExpressionVariable::TlsJa4,
             b"to" => ExpressionVariable::To,
             b"to.domain" => ExpressionVariable::ToDomain,
             b"to.local" => ExpressionVariable::ToLocal,
@@ -3268,6 +3595,7 @@ impl EnumImpl for ExpressionVariable {
             ExpressionVariable::EnvFromDomain => "env_from.domain",
             ExpressionVariable::EnvFromLocal => "env_from.local",
             ExpressionVariable::EnvTo => "env_to",
+            ExpressionVariable::EnvToDetail => "env_to.detail",
             ExpressionVariable::ExpiresIn => "expires_in",
             ExpressionVariable::From => "from",
             ExpressionVariable::FromDomain => "from.domain",
@@ -3327,6 +3655,8 @@ impl EnumImpl for ExpressionVariable {
             ExpressionVariable::Subject => "subject",
             ExpressionVariable::SubjectThread => "subject.thread",
             ExpressionVariable::SubjectWords => "subject.words",
+            ExpressionVariable::TlsJa3 => "tls_ja3",
+            ExpressionVariable::TlsJa4 => "tls_ja4",
             ExpressionVariable::To => "to",
             ExpressionVariable::ToDomain => "to.domain",
             ExpressionVariable::ToLocal => "to.local",
@@ -3434,11 +3764,14 @@ impl EnumImpl for ExpressionVariable {
             88 => Some(ExpressionVariable::Url),
             89 => Some(ExpressionVariable::Value),
             90 => Some(ExpressionVariable::ValueLower),
+            91 => Some(ExpressionVariable::EnvToDetail),
+            92 => Some(ExpressionVariable::TlsJa3),
+            93 => Some(ExpressionVariable::TlsJa4),
             _ => None,
         }
     }
 
-    const COUNT: usize = 91;
+    const COUNT: usize = 94;
 }
 
 impl serde::Serialize for ExpressionVariable {
@@ -3516,6 +3849,115 @@ impl<'de> serde::Deserialize<'de> for FailureReportingOption {
     }
 }
 
+impl EnumImpl for FirewallSyncType {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"Disabled" => FirewallSyncType::Disabled,
+            b"Nftables" => FirewallSyncType::Nftables,
+            b"Ipset" => FirewallSyncType::Ipset,
+            b"Webhook" => FirewallSyncType::Webhook,
+            b"Crowdsec" => FirewallSyncType::Crowdsec,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            FirewallSyncType::Disabled => "Disabled",
+            FirewallSyncType::Nftables => "Nftables",
+            FirewallSyncType::Ipset => "Ipset",
+            FirewallSyncType::Webhook => "Webhook",
+            FirewallSyncType::Crowdsec => "Crowdsec",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(FirewallSyncType::Disabled),
+            1 => Some(FirewallSyncType::Nftables),
+            2 => Some(FirewallSyncType::Ipset),
+            3 => Some(FirewallSyncType::Webhook),
+            4 => Some(FirewallSyncType::Crowdsec),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 5;
+}
+
+impl serde::Serialize for FirewallSyncType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for FirewallSyncType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
+impl EnumImpl for FreeBusyAccess {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"authenticated" => FreeBusyAccess::Authenticated,
+            b"anonymous" => FreeBusyAccess::Anonymous,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            FreeBusyAccess::Authenticated => "authenticated",
+            FreeBusyAccess::Anonymous => "anonymous",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(FreeBusyAccess::Authenticated),
+            1 => Some(FreeBusyAccess::Anonymous),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 2;
+}
+
+impl serde::Serialize for FreeBusyAccess {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for FreeBusyAccess {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for HttpAuthType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -3831,6 +4273,83 @@ impl<'de> serde::Deserialize<'de> for IpProtocol {
     }
 }
 
+impl EnumImpl for JmapCapability {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"mail" => JmapCapability::Mail,
+            b"submission" => JmapCapability::Submission,
+            b"vacationResponse" => JmapCapability::VacationResponse,
+            b"contacts" => JmapCapability::Contacts,
+            b"calendars" => JmapCapability::Calendars,
+            b"webSocket" => JmapCapability::WebSocket,
+            b"sieve" => JmapCapability::Sieve,
+            b"blob" => JmapCapability::Blob,
+            b"quota" => JmapCapability::Quota,
+            b"principals" => JmapCapability::Principals,
+            b"fileNode" => JmapCapability::FileNode,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            JmapCapability::Mail => "mail",
+            JmapCapability::Submission => "submission",
+            JmapCapability::VacationResponse => "vacationResponse",
+            JmapCapability::Contacts => "contacts",
+            JmapCapability::Calendars => "calendars",
+            JmapCapability::WebSocket => "webSocket",
+            JmapCapability::Sieve => "sieve",
+            JmapCapability::Blob => "blob",
+            JmapCapability::Quota => "quota",
+            JmapCapability::Principals => "principals",
+            JmapCapability::FileNode => "fileNode",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(JmapCapability::Mail),
+            1 => Some(JmapCapability::Submission),
+            2 => Some(JmapCapability::VacationResponse),
+            3 => Some(JmapCapability::Contacts),
+            4 => Some(JmapCapability::Calendars),
+            5 => Some(JmapCapability::WebSocket),
+            6 => Some(JmapCapability::Sieve),
+            7 => Some(JmapCapability::Blob),
+            8 => Some(JmapCapability::Quota),
+            9 => Some(JmapCapability::Principals),
+            10 => Some(JmapCapability::FileNode),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 11;
+}
+
+impl serde::Serialize for JmapCapability {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for JmapCapability {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for JwtSignatureAlgorithm {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -5018,6 +5537,118 @@ impl<'de> serde::Deserialize<'de> for LogRotateFrequency {
     }
 }
 
+impl EnumImpl for LoginAnomalySensitivity {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"disabled" => LoginAnomalySensitivity::Disabled,
+            b"low" => LoginAnomalySensitivity::Low,
+            b"medium" => LoginAnomalySensitivity::Medium,
+            b"high" => LoginAnomalySensitivity::High,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            LoginAnomalySensitivity::Disabled => "disabled",
+            LoginAnomalySensitivity::Low => "low",
+            LoginAnomalySensitivity::Medium => "medium",
+            LoginAnomalySensitivity::High => "high",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(LoginAnomalySensitivity::Disabled),
+            1 => Some(LoginAnomalySensitivity::Low),
+            2 => Some(LoginAnomalySensitivity::Medium),
+            3 => Some(LoginAnomalySensitivity::High),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 4;
+}
+
+impl serde::Serialize for LoginAnomalySensitivity {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for LoginAnomalySensitivity {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
+impl EnumImpl for LoginAnomalyType {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"newCountry" => LoginAnomalyType::NewCountry,
+            b"impossibleTravel" => LoginAnomalyType::ImpossibleTravel,
+            b"newNetwork" => LoginAnomalyType::NewNetwork,
+            b"newClient" => LoginAnomalyType::NewClient,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            LoginAnomalyType::NewCountry => "newCountry",
+            LoginAnomalyType::ImpossibleTravel => "impossibleTravel",
+            LoginAnomalyType::NewNetwork => "newNetwork",
+            LoginAnomalyType::NewClient => "newClient",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(LoginAnomalyType::NewCountry),
+            1 => Some(LoginAnomalyType::ImpossibleTravel),
+            2 => Some(LoginAnomalyType::NewNetwork),
+            3 => Some(LoginAnomalyType::NewClient),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 4;
+}
+
+impl serde::Serialize for LoginAnomalyType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for LoginAnomalyType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for LookupStoreType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -6063,6 +6694,7 @@ impl EnumImpl for NetworkListenerProtocol {
             b"imap" => NetworkListenerProtocol::Imap,
             b"pop3" => NetworkListenerProtocol::Pop3,
             b"manageSieve" => NetworkListenerProtocol::ManageSieve,
+            b"agentCheck" => NetworkListenerProtocol::AgentCheck,
         }
     }
 
@@ -6074,6 +6706,7 @@ impl EnumImpl for NetworkListenerProtocol {
             NetworkListenerProtocol::Imap => "imap",
             NetworkListenerProtocol::Pop3 => "pop3",
             NetworkListenerProtocol::ManageSieve => "manageSieve",
+            NetworkListenerProtocol::AgentCheck => "agentCheck",
         }
     }
 
@@ -6089,11 +6722,12 @@ impl EnumImpl for NetworkListenerProtocol {
             3 => Some(NetworkListenerProtocol::Imap),
             4 => Some(NetworkListenerProtocol::Pop3),
             5 => Some(NetworkListenerProtocol::ManageSieve),
+            6 => Some(NetworkListenerProtocol::AgentCheck),
             _ => None,
         }
     }
 
-    const COUNT: usize = 6;
+    const COUNT: usize = 7;
 }
 
 impl serde::Serialize for NetworkListenerProtocol {
@@ -6541,6 +7175,7 @@ impl EnumImpl for Permission {
             b"actionInvalidateNegativeCaches" => Permission::ActionInvalidateNegativeCaches,
             b"actionPauseMtaQueue" => Permission::ActionPauseMtaQueue,
             b"actionResumeMtaQueue" => Permission::ActionResumeMtaQueue,
+            b"actionExportSettingsSecrets" => Permission::ActionExportSettingsSecrets,
             b"sysActionGet" => Permission::SysActionGet,
             b"sysActionCreate" => Permission::SysActionCreate,
             b"sysActionUpdate" => Permission::SysActionUpdate,
@@ -6815,6 +7450,8 @@ impl EnumImpl for Permission {
             b"sysPublicKeyUpdate" => Permission::SysPublicKeyUpdate,
             b"sysPublicKeyDestroy" => Permission::SysPublicKeyDestroy,
             b"sysPublicKeyQuery" => Permission::SysPublicKeyQuery,
+            b"sysQueueStoreGet" => Permission::SysQueueStoreGet,
+            b"sysQueueStoreUpdate" => Permission::SysQueueStoreUpdate,
             b"sysQueuedMessageGet" => Permission::SysQueuedMessageGet,
             b"sysQueuedMessageCreate" => Permission::SysQueuedMessageCreate,
             b"sysQueuedMessageUpdate" => Permission::SysQueuedMessageUpdate,
@@ -6869,6 +7506,8 @@ impl EnumImpl for Permission {
             b"sysSpamLlmUpdate" => Permission::SysSpamLlmUpdate,
             b"sysSpamPyzorGet" => Permission::SysSpamPyzorGet,
             b"sysSpamPyzorUpdate" => Permission::SysSpamPyzorUpdate,
+            b"sysSpamRspamdGet" => Permission::SysSpamRspamdGet,
+            b"sysSpamRspamdUpdate" => Permission::SysSpamRspamdUpdate,
             b"sysSpamRuleGet" => Permission::SysSpamRuleGet,
             b"sysSpamRuleCreate" => Permission::SysSpamRuleCreate,
             b"sysSpamRuleUpdate" => Permission::SysSpamRuleUpdate,
@@ -6893,6 +7532,11 @@ impl EnumImpl for Permission {
             b"sysStoreLookupUpdate" => Permission::SysStoreLookupUpdate,
             b"sysStoreLookupDestroy" => Permission::SysStoreLookupDestroy,
             b"sysStoreLookupQuery" => Permission::SysStoreLookupQuery,
+            b"sysSuppressedAddressGet" => Permission::SysSuppressedAddressGet,
+            b"sysSuppressedAddressCreate" => Permission::SysSuppressedAddressCreate,
+            b"sysSuppressedAddressUpdate" => Permission::SysSuppressedAddressUpdate,
+            b"sysSuppressedAddressDestroy" => Permission::SysSuppressedAddressDestroy,
+            b"sysSuppressedAddressQuery" => Permission::SysSuppressedAddressQuery,
             b"sysSystemSettingsGet" => Permission::SysSystemSettingsGet,
             b"sysSystemSettingsUpdate" => Permission::SysSystemSettingsUpdate,
             b"taskIndexDocument" => Permission::TaskIndexDocument,
@@ -6956,6 +7600,18 @@ impl EnumImpl for Permission {
             b"sysWebHookUpdate" => Permission::SysWebHookUpdate,
             b"sysWebHookDestroy" => Permission::SysWebHookDestroy,
             b"sysWebHookQuery" => Permission::SysWebHookQuery,
+            b"calendarSubscribe" => Permission::CalendarSubscribe,
+            b"taskCalendarSubscriptionSync" => Permission::TaskCalendarSubscriptionSync,
+            b"sysShareLinkGet" => Permission::SysShareLinkGet,
+            b"sysShareLinkCreate" => Permission::SysShareLinkCreate,
+            b"sysShareLinkUpdate" => Permission::SysShareLinkUpdate,
+            b"sysShareLinkDestroy" => Permission::SysShareLinkDestroy,
+            b"sysShareLinkQuery" => Permission::SysShareLinkQuery,
+            b"taskAddressBookDirectorySync" => Permission::TaskAddressBookDirectorySync,
+            b"taskAccountMigration" => Permission::TaskAccountMigration,
+            b"taskLoginAnomaly" => Permission::TaskLoginAnomaly,
+            b"emailReceiveCreateMailbox" => Permission::EmailReceiveCreateMailbox,
+            b"emailForward" => Permission::EmailForward,
         }
         .copied()
     }
@@ -7216,6 +7872,7 @@ impl EnumImpl for Permission {
             Permission::ActionInvalidateNegativeCaches => "actionInvalidateNegativeCaches",
             Permission::ActionPauseMtaQueue => "actionPauseMtaQueue",
             Permission::ActionResumeMtaQueue => "actionResumeMtaQueue",
+            Permission::ActionExportSettingsSecrets => "actionExportSettingsSecrets",
             Permission::SysActionGet => "sysActionGet",
             Permission::SysActionCreate => "sysActionCreate",
             Permission::SysActionUpdate => "sysActionUpdate",
@@ -7490,6 +8147,8 @@ impl EnumImpl for Permission {
             Permission::SysPublicKeyUpdate => "sysPublicKeyUpdate",
             Permission::SysPublicKeyDestroy => "sysPublicKeyDestroy",
             Permission::SysPublicKeyQuery => "sysPublicKeyQuery",
+            Permission::SysQueueStoreGet => "sysQueueStoreGet",
+            Permission::SysQueueStoreUpdate => "sysQueueStoreUpdate",
             Permission::SysQueuedMessageGet => "sysQueuedMessageGet",
             Permission::SysQueuedMessageCreate => "sysQueuedMessageCreate",
             Permission::SysQueuedMessageUpdate => "sysQueuedMessageUpdate",
@@ -7544,6 +8203,8 @@ impl EnumImpl for Permission {
             Permission::SysSpamLlmUpdate => "sysSpamLlmUpdate",
             Permission::SysSpamPyzorGet => "sysSpamPyzorGet",
             Permission::SysSpamPyzorUpdate => "sysSpamPyzorUpdate",
+            Permission::SysSpamRspamdGet => "sysSpamRspamdGet",
+            Permission::SysSpamRspamdUpdate => "sysSpamRspamdUpdate",
             Permission::SysSpamRuleGet => "sysSpamRuleGet",
             Permission::SysSpamRuleCreate => "sysSpamRuleCreate",
             Permission::SysSpamRuleUpdate => "sysSpamRuleUpdate",
@@ -7568,6 +8229,11 @@ impl EnumImpl for Permission {
             Permission::SysStoreLookupUpdate => "sysStoreLookupUpdate",
             Permission::SysStoreLookupDestroy => "sysStoreLookupDestroy",
             Permission::SysStoreLookupQuery => "sysStoreLookupQuery",
+            Permission::SysSuppressedAddressGet => "sysSuppressedAddressGet",
+            Permission::SysSuppressedAddressCreate => "sysSuppressedAddressCreate",
+            Permission::SysSuppressedAddressUpdate => "sysSuppressedAddressUpdate",
+            Permission::SysSuppressedAddressDestroy => "sysSuppressedAddressDestroy",
+            Permission::SysSuppressedAddressQuery => "sysSuppressedAddressQuery",
             Permission::SysSystemSettingsGet => "sysSystemSettingsGet",
             Permission::SysSystemSettingsUpdate => "sysSystemSettingsUpdate",
             Permission::TaskIndexDocument => "taskIndexDocument",
@@ -7631,6 +8297,18 @@ impl EnumImpl for Permission {
             Permission::SysWebHookUpdate => "sysWebHookUpdate",
             Permission::SysWebHookDestroy => "sysWebHookDestroy",
             Permission::SysWebHookQuery => "sysWebHookQuery",
+            Permission::CalendarSubscribe => "calendarSubscribe",
+            Permission::TaskCalendarSubscriptionSync => "taskCalendarSubscriptionSync",
+            Permission::SysShareLinkGet => "sysShareLinkGet",
+            Permission::SysShareLinkCreate => "sysShareLinkCreate",
+            Permission::SysShareLinkUpdate => "sysShareLinkUpdate",
+            Permission::SysShareLinkDestroy => "sysShareLinkDestroy",
+            Permission::SysShareLinkQuery => "sysShareLinkQuery",
+            Permission::TaskAddressBookDirectorySync => "taskAddressBookDirectorySync",
+            Permission::TaskAccountMigration => "taskAccountMigration",
+            Permission::TaskLoginAnomaly => "taskLoginAnomaly",
+            Permission::EmailReceiveCreateMailbox => "emailReceiveCreateMailbox",
+            Permission::EmailForward => "emailForward",
         }
     }
 
@@ -7884,6 +8562,7 @@ impl EnumImpl for Permission {
             241 => Some(Permission::ActionInvalidateNegativeCaches),
             242 => Some(Permission::ActionPauseMtaQueue),
             243 => Some(Permission::ActionResumeMtaQueue),
+            680 => Some(Permission::ActionExportSettingsSecrets),
             244 => Some(Permission::SysActionGet),
             245 => Some(Permission::SysActionCreate),
             246 => Some(Permission::SysActionUpdate),
@@ -8158,6 +8837,8 @@ impl EnumImpl for Permission {
             515 => Some(Permission::SysPublicKeyUpdate),
             516 => Some(Permission::SysPublicKeyDestroy),
             517 => Some(Permission::SysPublicKeyQuery),
+            670 => Some(Permission::SysQueueStoreGet),
+            671 => Some(Permission::SysQueueStoreUpdate),
             518 => Some(Permission::SysQueuedMessageGet),
             519 => Some(Permission::SysQueuedMessageCreate),
             520 => Some(Permission::SysQueuedMessageUpdate),
@@ -8212,6 +8893,8 @@ impl EnumImpl for Permission {
             569 => Some(Permission::SysSpamLlmUpdate),
             570 => Some(Permission::SysSpamPyzorGet),
             571 => Some(Permission::SysSpamPyzorUpdate),
+            668 => Some(Permission::SysSpamRspamdGet),
+            669 => Some(Permission::SysSpamRspamdUpdate),
             572 => Some(Permission::SysSpamRuleGet),
             573 => Some(Permission::SysSpamRuleCreate),
             574 => Some(Permission::SysSpamRuleUpdate),
@@ -8299,11 +8982,28 @@ impl EnumImpl for Permission {
             656 => Some(Permission::SysWebHookUpdate),
             657 => Some(Permission::SysWebHookDestroy),
             658 => Some(Permission::SysWebHookQuery),
+            659 => Some(Permission::CalendarSubscribe),
+            660 => Some(Permission::TaskCalendarSubscriptionSync),
+            661 => Some(Permission::SysShareLinkGet),
+            662 => Some(Permission::SysShareLinkCreate),
+            663 => Some(Permission::SysShareLinkUpdate),
+            664 => Some(Permission::SysShareLinkDestroy),
+            665 => Some(Permission::SysShareLinkQuery),
+            666 => Some(Permission::TaskAddressBookDirectorySync),
+            672 => Some(Permission::TaskAccountMigration),
+            678 => Some(Permission::TaskLoginAnomaly),
+            673 => Some(Permission::SysSuppressedAddressGet),
+            674 => Some(Permission::SysSuppressedAddressCreate),
+            675 => Some(Permission::SysSuppressedAddressUpdate),
+            676 => Some(Permission::SysSuppressedAddressDestroy),
+            677 => Some(Permission::SysSuppressedAddressQuery),
+            667 => Some(Permission::EmailReceiveCreateMailbox),
+            679 => Some(Permission::EmailForward),
             _ => None,
         }
     }
 
-    const COUNT: usize = 659;
+    const COUNT: usize = 681;
 }
 
 impl serde::Serialize for Permission {
@@ -8496,6 +9196,7 @@ impl EnumImpl for ProviderInfo {
             b"logoUrl" => ProviderInfo::LogoUrl,
             b"logoWidth" => ProviderInfo::LogoWidth,
             b"logoHeight" => ProviderInfo::LogoHeight,
+            b"brandColor" => ProviderInfo::BrandColor,
         }
     }
 
@@ -8509,6 +9210,7 @@ impl EnumImpl for ProviderInfo {
             ProviderInfo::LogoUrl => "logoUrl",
             ProviderInfo::LogoWidth => "logoWidth",
             ProviderInfo::LogoHeight => "logoHeight",
+            ProviderInfo::BrandColor => "brandColor",
         }
     }
 
@@ -8526,11 +9228,12 @@ impl EnumImpl for ProviderInfo {
             5 => Some(ProviderInfo::LogoUrl),
             6 => Some(ProviderInfo::LogoWidth),
             7 => Some(ProviderInfo::LogoHeight),
+            8 => Some(ProviderInfo::BrandColor),
             _ => None,
         }
     }
 
-    const COUNT: usize = 8;
+    const COUNT: usize = 9;
 }
 
 impl serde::Serialize for ProviderInfo {
@@ -8655,6 +9358,68 @@ impl<'de> serde::Deserialize<'de> for QueueExpiryType {
     }
 }
 
+impl EnumImpl for QueueStoreType {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"Default" => QueueStoreType::Default,
+            b"RocksDb" => QueueStoreType::RocksDb,
+            b"Sqlite" => QueueStoreType::Sqlite,
+            b"FoundationDb" => QueueStoreType::FoundationDb,
+            b"PostgreSql" => QueueStoreType::PostgreSql,
+            b"MySql" => QueueStoreType::MySql,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            QueueStoreType::Default => "Default",
+            QueueStoreType::RocksDb => "RocksDb",
+            QueueStoreType::Sqlite => "Sqlite",
+            QueueStoreType::FoundationDb => "FoundationDb",
+            QueueStoreType::PostgreSql => "PostgreSql",
+            QueueStoreType::MySql => "MySql",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(QueueStoreType::Default),
+            1 => Some(QueueStoreType::RocksDb),
+            2 => Some(QueueStoreType::Sqlite),
+            3 => Some(QueueStoreType::FoundationDb),
+            4 => Some(QueueStoreType::PostgreSql),
+            5 => Some(QueueStoreType::MySql),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 6;
+}
+
+impl serde::Serialize for QueueStoreType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for QueueStoreType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for RecipientFlag {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -8772,8 +9537,111 @@ impl EnumImpl for RedisProtocol {
 
     fn as_str(&self) -> &'static str {
         match self {
-            RedisProtocol::Resp2 => "resp2",
-            RedisProtocol::Resp3 => "resp3",
+            RedisProtocol::Resp2 => "resp2",
+            RedisProtocol::Resp3 => "resp3",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(RedisProtocol::Resp2),
+            1 => Some(RedisProtocol::Resp3),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 2;
+}
+
+impl serde::Serialize for RedisProtocol {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for RedisProtocol {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
+impl EnumImpl for RelayVerification {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"disabled" => RelayVerification::Disabled,
+            b"smtp" => RelayVerification::Smtp,
+            b"http" => RelayVerification::Http,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            RelayVerification::Disabled => "disabled",
+            RelayVerification::Smtp => "smtp",
+            RelayVerification::Http => "http",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(RelayVerification::Disabled),
+            1 => Some(RelayVerification::Smtp),
+            2 => Some(RelayVerification::Http),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for RelayVerification {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for RelayVerification {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
+impl EnumImpl for RetentionAction {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"archive" => RetentionAction::Archive,
+            b"delete" => RetentionAction::Delete,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            RetentionAction::Archive => "archive",
+            RetentionAction::Delete => "delete",
         }
     }
 
@@ -8783,8 +9651,8 @@ impl EnumImpl for RedisProtocol {
 
     fn from_id(id: u16) -> Option<Self> {
         match id {
-            0 => Some(RedisProtocol::Resp2),
-            1 => Some(RedisProtocol::Resp3),
+            0 => Some(RetentionAction::Archive),
+            1 => Some(RetentionAction::Delete),
             _ => None,
         }
     }
@@ -8792,7 +9660,7 @@ impl EnumImpl for RedisProtocol {
     const COUNT: usize = 2;
 }
 
-impl serde::Serialize for RedisProtocol {
+impl serde::Serialize for RetentionAction {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
@@ -8801,7 +9669,7 @@ impl serde::Serialize for RedisProtocol {
     }
 }
 
-impl<'de> serde::Deserialize<'de> for RedisProtocol {
+impl<'de> serde::Deserialize<'de> for RetentionAction {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
@@ -9034,6 +9902,115 @@ impl<'de> serde::Deserialize<'de> for S3StoreRegionType {
     }
 }
 
+impl EnumImpl for SchedulingResourcePolicy {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"acceptIfFree" => SchedulingResourcePolicy::AcceptIfFree,
+            b"acceptAlways" => SchedulingResourcePolicy::AcceptAlways,
+            b"declineAlways" => SchedulingResourcePolicy::DeclineAlways,
+            b"manual" => SchedulingResourcePolicy::Manual,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            SchedulingResourcePolicy::AcceptIfFree => "acceptIfFree",
+            SchedulingResourcePolicy::AcceptAlways => "acceptAlways",
+            SchedulingResourcePolicy::DeclineAlways => "declineAlways",
+            SchedulingResourcePolicy::Manual => "manual",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(SchedulingResourcePolicy::AcceptIfFree),
+            1 => Some(SchedulingResourcePolicy::AcceptAlways),
+            2 => Some(SchedulingResourcePolicy::DeclineAlways),
+            3 => Some(SchedulingResourcePolicy::Manual),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 4;
+}
+
+impl serde::Serialize for SchedulingResourcePolicy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for SchedulingResourcePolicy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
+impl EnumImpl for SchedulingResourceType {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"none" => SchedulingResourceType::None,
+            b"room" => SchedulingResourceType::Room,
+            b"equipment" => SchedulingResourceType::Equipment,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            SchedulingResourceType::None => "none",
+            SchedulingResourceType::Room => "room",
+            SchedulingResourceType::Equipment => "equipment",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(SchedulingResourceType::None),
+            1 => Some(SchedulingResourceType::Room),
+            2 => Some(SchedulingResourceType::Equipment),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for SchedulingResourceType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for SchedulingResourceType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for SearchCalendarField {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -9707,6 +10684,56 @@ impl<'de> serde::Deserialize<'de> for ServiceProtocol {
     }
 }
 
+impl EnumImpl for ShareLinkCollection {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"calendar" => ShareLinkCollection::Calendar,
+            b"addressBook" => ShareLinkCollection::AddressBook,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            ShareLinkCollection::Calendar => "calendar",
+            ShareLinkCollection::AddressBook => "addressBook",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(ShareLinkCollection::Calendar),
+            1 => Some(ShareLinkCollection::AddressBook),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 2;
+}
+
+impl serde::Serialize for ShareLinkCollection {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ShareLinkCollection {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for SieveCapability {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -10809,6 +11836,62 @@ impl<'de> serde::Deserialize<'de> for SubAddressingType {
     }
 }
 
+impl EnumImpl for SuppressionReason {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"hardBounce" => SuppressionReason::HardBounce,
+            b"unsubscribe" => SuppressionReason::Unsubscribe,
+            b"complaint" => SuppressionReason::Complaint,
+            b"manual" => SuppressionReason::Manual,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            SuppressionReason::HardBounce => "hardBounce",
+            SuppressionReason::Unsubscribe => "unsubscribe",
+            SuppressionReason::Complaint => "complaint",
+            SuppressionReason::Manual => "manual",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(SuppressionReason::HardBounce),
+            1 => Some(SuppressionReason::Unsubscribe),
+            2 => Some(SuppressionReason::Complaint),
+            3 => Some(SuppressionReason::Manual),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 4;
+}
+
+impl serde::Serialize for SuppressionReason {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for SuppressionReason {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for TaskAccountMaintenanceType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -10817,6 +11900,8 @@ impl EnumImpl for TaskAccountMaintenanceType {
             b"reindex" => TaskAccountMaintenanceType::Reindex,
             b"recalculateImapUid" => TaskAccountMaintenanceType::RecalculateImapUid,
             b"recalculateQuota" => TaskAccountMaintenanceType::RecalculateQuota,
+            b"repairThreads" => TaskAccountMaintenanceType::RepairThreads,
+            b"encryptMessages" => TaskAccountMaintenanceType::EncryptMessages,
         }
     }
 
@@ -10826,6 +11911,8 @@ impl EnumImpl for TaskAccountMaintenanceType {
             TaskAccountMaintenanceType::Reindex => "reindex",
             TaskAccountMaintenanceType::RecalculateImapUid => "recalculateImapUid",
             TaskAccountMaintenanceType::RecalculateQuota => "recalculateQuota",
+            TaskAccountMaintenanceType::RepairThreads => "repairThreads",
+            TaskAccountMaintenanceType::EncryptMessages => "encryptMessages",
         }
     }
 
@@ -10839,11 +11926,13 @@ impl EnumImpl for TaskAccountMaintenanceType {
             1 => Some(TaskAccountMaintenanceType::Reindex),
             2 => Some(TaskAccountMaintenanceType::RecalculateImapUid),
             3 => Some(TaskAccountMaintenanceType::RecalculateQuota),
+            4 => Some(TaskAccountMaintenanceType::RepairThreads),
+            5 => Some(TaskAccountMaintenanceType::EncryptMessages),
             _ => None,
         }
     }
 
-    const COUNT: usize = 4;
+    const COUNT: usize = 6;
 }
 
 impl serde::Serialize for TaskAccountMaintenanceType {
@@ -11185,6 +12274,10 @@ impl EnumImpl for TaskType {
             b"AcmeRenewal" => TaskType::AcmeRenewal,
             b"DkimManagement" => TaskType::DkimManagement,
             b"DnsManagement" => TaskType::DnsManagement,
+            b"CalendarSubscriptionSync" => TaskType::CalendarSubscriptionSync,
+            b"AddressBookDirectorySync" => TaskType::AddressBookDirectorySync,
+            b"AccountMigration" => TaskType::AccountMigration,
+            b"LoginAnomaly" => TaskType::LoginAnomaly,
         }
     }
 
@@ -11208,6 +12301,10 @@ impl EnumImpl for TaskType {
             TaskType::AcmeRenewal => "AcmeRenewal",
             TaskType::DkimManagement => "DkimManagement",
             TaskType::DnsManagement => "DnsManagement",
+            TaskType::CalendarSubscriptionSync => "CalendarSubscriptionSync",
+            TaskType::AddressBookDirectorySync => "AddressBookDirectorySync",
+            TaskType::AccountMigration => "AccountMigration",
+            TaskType::LoginAnomaly => "LoginAnomaly",
         }
     }
 
@@ -11235,11 +12332,15 @@ impl EnumImpl for TaskType {
             15 => Some(TaskType::AcmeRenewal),
             16 => Some(TaskType::DkimManagement),
             17 => Some(TaskType::DnsManagement),
+            18 => Some(TaskType::CalendarSubscriptionSync),
+            19 => Some(TaskType::AddressBookDirectorySync),
+            20 => Some(TaskType::AccountMigration),
+            21 => Some(TaskType::LoginAnomaly),
             _ => None,
         }
     }
 
-    const COUNT: usize = 18;
+    const COUNT: usize = 22;
 }
 
 impl serde::Serialize for TaskType {
@@ -13261,6 +14362,112 @@ impl<'de> serde::Deserialize<'de> for TlsCipherSuite {
     }
 }
 
+impl EnumImpl for TlsClientAuth {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"disabled" => TlsClientAuth::Disabled,
+            b"optional" => TlsClientAuth::Optional,
+            b"required" => TlsClientAuth::Required,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            TlsClientAuth::Disabled => "disabled",
+            TlsClientAuth::Optional => "optional",
+            TlsClientAuth::Required => "required",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(TlsClientAuth::Disabled),
+            1 => Some(TlsClientAuth::Optional),
+            2 => Some(TlsClientAuth::Required),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for TlsClientAuth {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for TlsClientAuth {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
+impl EnumImpl for TlsClientCertField {
+    fn parse(value: &str) -> Option<Self> {
+        hashify::tiny_map! {
+            value.as_bytes(),
+            b"sanEmail" => TlsClientCertField::SanEmail,
+            b"subjectEmail" => TlsClientCertField::SubjectEmail,
+            b"subjectCommonName" => TlsClientCertField::SubjectCommonName,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            TlsClientCertField::SanEmail => "sanEmail",
+            TlsClientCertField::SubjectEmail => "subjectEmail",
+            TlsClientCertField::SubjectCommonName => "subjectCommonName",
+        }
+    }
+
+    fn to_id(&self) -> u16 {
+        *self as u16
+    }
+
+    fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(TlsClientCertField::SanEmail),
+            1 => Some(TlsClientCertField::SubjectEmail),
+            2 => Some(TlsClientCertField::SubjectCommonName),
+            _ => None,
+        }
+    }
+
+    const COUNT: usize = 3;
+}
+
+impl serde::Serialize for TlsClientCertField {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for TlsClientCertField {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = Cow::<str>::deserialize(deserializer)?;
+        Self::parse(&s).ok_or_else(|| serde::de::Error::unknown_variant(&s, &[]))
+    }
+}
+
 impl EnumImpl for TlsPolicyType {
     fn parse(value: &str) -> Option<Self> {
         hashify::tiny_map! {
@@ -13533,6 +14740,7 @@ impl EnumImpl for TracerType {
             b"Journal" => TracerType::Journal,
             b"OtelHttp" => TracerType::OtelHttp,
             b"OtelGrpc" => TracerType::OtelGrpc,
+            b"Syslog" => TracerType::Syslog,
         }
     }
 
@@ -13543,6 +14751,7 @@ impl EnumImpl for TracerType {
             TracerType::Journal => "Journal",
             TracerType::OtelHttp => "OtelHttp",
             TracerType::OtelGrpc => "OtelGrpc",
+            TracerType::Syslog => "Syslog",
         }
     }
 
@@ -13557,11 +14766,12 @@ impl EnumImpl for TracerType {
             2 => Some(TracerType::Journal),
             3 => Some(TracerType::OtelHttp),
             4 => Some(TracerType::OtelGrpc),
+            5 => Some(TracerType::Syslog),
             _ => None,
         }
     }
 
-    const COUNT: usize = 5;
+    const COUNT: usize = 6;
 }
 
 impl serde::Serialize for TracerType {
@@ -91,6 +91,7 @@ pub enum ObjectInner {
     OAuthClient(OAuthClient),
     OidcProvider(OidcProvider),
     PublicKey(PublicKey),
+    QueueStore(QueueStore),
     QueuedMessage(QueuedMessage),
     ReportSettings(ReportSettings),
     Role(Role),
@@ -98,6 +99,7 @@ pub enum ObjectInner {
     SearchStore(SearchStore),
     Security(Security),
     SenderAuth(SenderAuth),
+    ShareLink(ShareLink),
     Sharing(Sharing),
     SieveSystemInterpreter(SieveSystemInterpreter),
     SieveSystemScript(SieveSystemScript),
@@ -109,12 +111,14 @@ pub enum ObjectInner {
     SpamFileExtension(SpamFileExtension),
     SpamLlm(SpamLlm),
     SpamPyzor(SpamPyzor),
+    SpamRspamd(SpamRspamd),
     SpamRule(SpamRule),
     SpamSettings(SpamSettings),
     SpamTag(SpamTag),
     SpamTrainingSample(SpamTrainingSample),
     SpfReportSettings(SpfReportSettings),
     StoreLookup(StoreLookup),
+    SuppressedAddress(SuppressedAddress),
     SystemSettings(SystemSettings),
     Task(Task),
     TaskManager(TaskManager),
@@ -213,6 +217,7 @@ pub enum ObjectType {
     OAuthClient = 78,
     OidcProvider = 79,
     PublicKey = 80,
+    QueueStore = 119,
     QueuedMessage = 81,
     ReportSettings = 82,
     Role = 83,
@@ -220,6 +225,7 @@ pub enum ObjectType {
     SearchStore = 85,
     Security = 86,
     SenderAuth = 87,
+    ShareLink = 117,
     Sharing = 88,
     SieveSystemInterpreter = 89,
     SieveSystemScript = 90,
@@ -231,12 +237,14 @@ pub enum ObjectType {
     SpamFileExtension = 96,
     SpamLlm = 97,
     SpamPyzor = 98,
+    SpamRspamd = 118,
     SpamRule = 99,
     SpamSettings = 100,
     SpamTag = 101,
     SpamTrainingSample = 102,
     SpfReportSettings = 103,
     StoreLookup = 104,
+    SuppressedAddress = 120,
     SystemSettings = 105,
     Task = 106,
     TaskManager = 107,
@@ -279,6 +287,7 @@ pub enum Property {
     AddReturnPathHeader = 560,
     AdditionalInformation = 838,
     Address = 44,
+    AddressRewrites = 949,
     Addresses = 579,
     AggregateContactInfo = 268,
     AggregateDkimSignDomain = 274,
@@ -298,10 +307,12 @@ pub enum Property {
     AllowPlainTextAuth = 424,
     AllowRelaying = 348,
     AllowSpamTraining = 369,
+    AllowUnsubscribe = 994,
     AllowedEndpoints = 398,
     AllowedIps = 49,
     AllowedNotifyUris = 712,
     Alpha = 388,
+    AnomalyType = 996,
     AnonymousClientRegistration = 614,
     Ansi = 858,
     ApiKey = 325,
@@ -311,6 +322,7 @@ pub enum Property {
     ArcVerify = 690,
     ArchiveDeletedAccountsFor = 203,
     ArchiveDeletedItemsFor = 202,
+    ArchiveMailbox = 933,
     ArchivedAt = 58,
     ArchivedItemType = 820,
     ArchivedUntil = 59,
@@ -322,7 +334,9 @@ pub enum Property {
     AttrDescription = 471,
     AttrEmail = 472,
     AttrEmailAlias = 473,
+    AttrLoginReferral = 974,
     AttrMemberOf = 474,
+    AttrProxyBackend = 967,
     AttrSecret = 475,
     AttrSecretChanged = 476,
     Auid = 215,
@@ -337,6 +351,7 @@ pub enum Property {
     AuthenticatedAs = 740,
     AuthenticationResults = 69,
     AutoAddInvitations = 171,
+    AutoBcc = 945,
     AutoUpdateFrequency = 53,
     BaseDn = 463,
     BearerToken = 403,
@@ -350,15 +365,22 @@ pub enum Property {
     BlobSize = 655,
     BlobStore = 126,
     BlockCount = 766,
+    BlockedTlsFingerprints = 995,
     Body = 38,
     Brokers = 459,
     Bucket = 658,
     BufferSize = 656,
     Buffered = 863,
+    CacheMaxAge = 980,
+    CaldavUrl = 983,
+    CalendarAutoImport = 894,
     Canonicalization = 216,
     CapacityClient = 584,
     CapacityReadBuffer = 585,
     CapacitySubscription = 586,
+    CaptchaProvider = 976,
+    CaptchaSecret = 977,
+    CarddavUrl = 984,
     CatchAllAddress = 346,
     Categories = 759,
     Certificate = 176,
@@ -370,14 +392,21 @@ pub enum Property {
     ClaimName = 611,
     ClaimUsername = 609,
     Cleartext = 693,
+    CleartextPort = 934,
     ClientId = 604,
     ClusterFile = 382,
+    Collection = 883,
+    CollectionId = 884,
     ColumnClass = 781,
     ColumnDescription = 782,
     ColumnEmail = 779,
+    ColumnLoginReferral = 975,
+    ColumnProxyBackend = 968,
     ColumnSecret = 780,
     Comment = 240,
     CompressionAlgorithm = 359,
+    CompressionContentTypes = 910,
+    CompressionMinSize = 909,
     Concurrency = 304,
     Condition = 34,
     Confidence = 760,
@@ -392,7 +421,13 @@ pub enum Property {
     Content = 65,
     ContentTypes = 758,
     Contents = 708,
+    CorsAllowCredentials = 914,
+    CorsAllowedHeaders = 913,
+    CorsAllowedMethods = 912,
+    CorsAllowedOrigins = 911,
+    CorsMaxAge = 915,
     Count = 258,
+    Country = 997,
     Create = 367,
     CreatedAt = 46,
     CreatedBy = 486,
@@ -412,7 +447,9 @@ pub enum Property {
     DateRangeEnd = 246,
     DateRangeStart = 845,
     Day = 192,
+    DeadLetterUrl = 898,
     DeadPropertyMaxSize = 868,
+    DefaultAcmeProviderId = 919,
     DefaultAdminRoleIds = 108,
     DefaultCertificateId = 790,
     DefaultDisplayName = 20,
@@ -435,6 +472,8 @@ pub enum Property {
     DefaultUserRoleIds = 105,
     Definition = 235,
     Delay = 825,
+    DelaySubject = 963,
+    DelayTemplate = 964,
     DeleteAfter = 229,
     DeleteAfterUse = 777,
     DeliverAt = 238,
@@ -445,11 +484,23 @@ pub enum Property {
     Description = 6,
     Details = 297,
     Directory = 12,
+    DirectoryDisplayName = 890,
+    DirectoryEnable = 888,
+    DirectoryFields = 893,
+    DirectoryHrefName = 889,
     DirectoryId = 104,
+    DirectoryRefreshInterval = 892,
+    DirectoryScope = 891,
     DisableCapabilities = 711,
+    DisableFor = 955,
     DisableLanguages = 666,
+    DisabledJmapCapabilities = 935,
     DisabledPermissions = 629,
     DiscardAfter = 872,
+    DisclaimerHtml = 942,
+    DisclaimerInbound = 943,
+    DisclaimerText = 946,
+    DisclaimerWrapSigned = 944,
     Disposition = 747,
     DkimAdspDns = 83,
     DkimCanonicalizedBody = 84,
@@ -458,6 +509,7 @@ pub enum Property {
     DkimIdentity = 87,
     DkimManagement = 343,
     DkimPass = 291,
+    DkimResignDomain = 965,
     DkimResults = 266,
     DkimSelector = 88,
     DkimSelectorDns = 89,
@@ -484,6 +536,7 @@ pub enum Property {
     DocumentId = 804,
     DocumentType = 814,
     Domain = 232,
+    DomainBreakdown = 900,
     DomainId = 221,
     DomainLimit = 750,
     DomainNames = 147,
@@ -491,7 +544,9 @@ pub enum Property {
     Domains = 146,
     Dsn = 519,
     Due = 797,
+    DuplicateAction = 990,
     DuplicateExpiry = 699,
+    DuplicateWindow = 991,
     Duration = 515,
     EabHmacKey = 13,
     EabKeyId = 14,
@@ -508,11 +563,15 @@ pub enum Property {
     EmailDomain = 488,
     EmailLimit = 751,
     EmailPrefix = 487,
+    EmailQueries = 939,
     EmailTemplate = 174,
     Enable = 37,
     EnableAssistedDiscovery = 865,
+    EnableCompression = 908,
     EnableEdns = 305,
+    EnableH2c = 906,
     EnableHsts = 399,
+    EnableHttp2 = 905,
     EnableLogExporter = 860,
     EnableSpamFilter = 562,
     EnableSpanExporter = 861,
@@ -522,7 +581,9 @@ pub enum Property {
     EncryptOnAppend = 357,
     EncryptionAtRest = 9,
     EncryptionKey = 622,
+    EncryptionPolicy = 993,
     Endpoint = 499,
+    EnforceQuota = 957,
     EnvFrom = 742,
     EnvFromParameters = 743,
     EnvId = 639,
@@ -547,6 +608,7 @@ pub enum Property {
     EventStartTz = 802,
     Events = 142,
     EventsPolicy = 855,
+    ExcludeKeywords = 932,
     Expire = 217,
     Expires = 100,
     ExpiresAt = 47,
@@ -561,6 +623,7 @@ pub enum Property {
     Extension = 754,
     Extensions = 257,
     ExtraContactInfo = 243,
+    Facility = 904,
     Factor = 821,
     FailOnTimeout = 490,
     FailedAt = 826,
@@ -586,11 +649,15 @@ pub enum Property {
     FilterLogin = 467,
     FilterMailbox = 468,
     FilterMemberOf = 469,
+    FirewallSync = 1005,
+    FirewallSyncInterval = 1006,
     Flags = 638,
     FlagsAction = 537,
     FlagsProtocol = 538,
+    FolderPolicies = 986,
     ForDomain = 485,
     Format = 415,
+    Forms = 978,
     From = 62,
     FromAddress = 39,
     FromEmail = 165,
@@ -605,6 +672,10 @@ pub enum Property {
     GroupId = 460,
     HeaderFrom = 265,
     Headers = 93,
+    HealthDegradedWeight = 1011,
+    HealthQueueThreshold = 1012,
+    HealthStoreTimeout = 1013,
+    HoldAuditLogFor = 896,
     HoldMetricsFor = 206,
     HoldMtaReportsFor = 204,
     HoldSamplesFor = 730,
@@ -612,8 +683,13 @@ pub enum Property {
     Host = 333,
     HostedZoneId = 331,
     Hostname = 185,
+    Hostnames = 979,
     Hour = 190,
+    Http2MaxConcurrentStreams = 907,
     HttpAuth = 32,
+    HttpFreeBusyAccess = 877,
+    HttpFreeBusyEnable = 876,
+    HttpFreeBusyMaxRange = 878,
     HttpHeaders = 33,
     HttpRsvpEnable = 168,
     HttpRsvpLinkExpiry = 169,
@@ -626,6 +702,7 @@ pub enum Property {
     IdTokenExpiry = 621,
     IdentityAlignment = 91,
     If = 376,
+    ImapUrl = 981,
     ImpersonateServiceAccount = 320,
     ImplicitTls = 546,
     InMemoryStore = 128,
@@ -635,6 +712,7 @@ pub enum Property {
     IncludeSource = 352,
     IndexAsn = 94,
     IndexAsnName = 95,
+    IndexAttachmentContents = 926,
     IndexBatchSize = 664,
     IndexCalendar = 667,
     IndexCalendarFields = 668,
@@ -674,6 +752,7 @@ pub enum Property {
     KeyName = 337,
     KeyPrefix = 120,
     KeyValues = 853,
+    Keywords = 931,
     L1Ratio = 391,
     L2Ratio = 392,
     LastRenewal = 186,
@@ -681,12 +760,17 @@ pub enum Property {
     LearnHamFromReply = 735,
     LearnSpamFromRblHits = 728,
     LearnSpamFromTraps = 729,
+    LegalHold = 927,
     Level = 373,
     LicenseKey = 370,
     ListenerIds = 183,
     Listeners = 188,
     LivePropertyMaxSize = 869,
     Locale = 7,
+    LoginAnomalySensitivity = 998,
+    LoginAt = 999,
+    LoginReferral = 973,
+    LoginTravelWindow = 1000,
     Logo = 341,
     LogoUrl = 371,
     LoiterBanPeriod = 682,
@@ -697,6 +781,8 @@ pub enum Property {
     MailFrom = 284,
     MailFromTimeout = 509,
     MailRua = 841,
+    MailboxId = 895,
+    Mailboxes = 930,
     MailingLists = 154,
     MaintenanceType = 796,
     ManagedZone = 318,
@@ -710,9 +796,12 @@ pub enum Property {
     MaxAttempts = 511,
     MaxAttendees = 157,
     MaxAuthFailures = 425,
+    MaxBatchSize = 897,
+    MaxBreakdownLabels = 902,
     MaxCalendars = 160,
     MaxChangesHistory = 201,
     MaxConcurrent = 426,
+    MaxConcurrentCalls = 936,
     MaxConcurrentRequests = 439,
     MaxConcurrentUploads = 442,
     MaxConnections = 603,
@@ -720,6 +809,7 @@ pub enum Property {
     MaxCpuCycles = 702,
     MaxDelay = 823,
     MaxDuration = 530,
+    MaxEmailChangeHistory = 989,
     MaxEntries = 417,
     MaxEntrySize = 418,
     MaxEventNotifications = 163,
@@ -751,6 +841,8 @@ pub enum Property {
     MaxOutMessages = 704,
     MaxParticipantIdentities = 162,
     MaxPublicKeys = 366,
+    MaxQueueWait = 938,
+    MaxQueuedCalls = 937,
     MaxReceivedHeaders = 561,
     MaxRecipients = 173,
     MaxReconnects = 580,
@@ -766,6 +858,8 @@ pub enum Property {
     MaxScriptNameLength = 719,
     MaxScriptSize = 723,
     MaxScripts = 726,
+    MaxSessionRecipients = 956,
+    MaxShareLinks = 887,
     MaxShares = 696,
     MaxSize = 101,
     MaxStringLength = 724,
@@ -807,6 +901,8 @@ pub enum Property {
     NextNotify = 634,
     NextRetry = 633,
     NextTransitionAt = 223,
+    NftFamily = 1007,
+    NftTable = 1008,
     NoCapabilityCheck = 700,
     NoEcho = 587,
     NoSoliciting = 523,
@@ -819,6 +915,7 @@ pub enum Property {
     NumFeatures = 390,
     NumReplicas = 350,
     NumShards = 351,
+    OlderThan = 929,
     OnSuccessRenewCertificate = 813,
     OpenTelemetry = 495,
     Options = 630,
@@ -829,6 +926,7 @@ pub enum Property {
     OriginalEnvelopeId = 71,
     OriginalMailFrom = 72,
     OriginalRcptTo = 73,
+    OtlpIngest = 903,
     OtpAuth = 5,
     OtpCode = 625,
     OtpUrl = 626,
@@ -847,6 +945,8 @@ pub enum Property {
     PasswordMinLength = 110,
     PasswordMinStrength = 112,
     Path = 380,
+    Pattern = 947,
+    PayloadTemplate = 899,
     Period = 646,
     Permissions = 48,
     PingInterval = 583,
@@ -877,6 +977,7 @@ pub enum Property {
     Port = 299,
     Prefix = 856,
     PreserveIntermediates = 306,
+    PreviousCountry = 1001,
     Priority = 483,
     PrivateKey = 177,
     PrivateZone = 319,
@@ -887,11 +988,19 @@ pub enum Property {
     Prompt = 765,
     PropagationDelay = 313,
     PropagationTimeout = 312,
+    Protect = 985,
     ProtectedHeaders = 713,
     Protocol = 298,
     ProtocolVersion = 533,
     ProviderInfo = 795,
+    ProxyAllowInvalidCerts = 972,
+    ProxyBackend = 966,
+    ProxyImapPort = 969,
+    ProxyImplicitTls = 971,
+    ProxyPop3Port = 970,
     ProxyTrustedNetworks = 792,
+    PublicFolderName = 988,
+    PublicFolders = 987,
     PublicKey = 218,
     PublishRecords = 302,
     PushAttemptWait = 448,
@@ -913,8 +1022,10 @@ pub enum Property {
     RateLimit = 410,
     RateLimitAnonymous = 397,
     RateLimitAuthenticated = 396,
+    RateLimited = 925,
     Ratio = 767,
     RcptToTimeout = 510,
+    ReadCacheSize = 940,
     ReadFromReplicas = 650,
     ReadReplicas = 578,
     Reason = 45,
@@ -932,9 +1043,18 @@ pub enum Property {
     RefreshTokenExpiry = 617,
     RefreshTokenRenewal = 618,
     Region = 330,
+    ReindexRate = 941,
     RejectNonFqdn = 563,
+    RelayVerify = 958,
+    RelayVerifyCacheTtl = 961,
+    RelayVerifyHost = 959,
+    RelayVerifyUrl = 960,
     RemoteIp = 282,
     RenewBefore = 17,
+    RepeatBanDecay = 921,
+    RepeatBanMaxPeriod = 920,
+    ReplaceScore = 952,
+    Replacement = 948,
     Report = 66,
     ReportAddressUri = 349,
     ReportId = 244,
@@ -960,8 +1080,10 @@ pub enum Property {
     ResponsePosExplanation = 763,
     Result = 233,
     ResultType = 832,
+    RetentionPolicies = 928,
     RetireAfter = 228,
     Retry = 420,
+    RetryBackoff = 1014,
     RetryCount = 640,
     RetryDue = 641,
     ReturnPath = 635,
@@ -980,6 +1102,8 @@ pub enum Property {
     ScanBanRate = 684,
     Schedule = 541,
     Scheduling = 143,
+    SchedulingPolicy = 875,
+    SchedulingResource = 874,
     Scope = 281,
     Score = 745,
     ScoreDiscard = 771,
@@ -1002,8 +1126,13 @@ pub enum Property {
     ServiceAccountJson = 316,
     Services = 794,
     SessionToken = 329,
+    SetIpv4 = 1009,
+    SetIpv6 = 1010,
     SetMaxObjects = 440,
     ShardIndex = 830,
+    ShareLinkEnable = 885,
+    ShareLinkMaxExpiry = 886,
+    SieveUrl = 982,
     Sig0Algorithm = 336,
     SignatureAlgorithm = 623,
     SignatureKey = 624,
@@ -1041,12 +1170,22 @@ pub enum Property {
     Store = 778,
     Stores = 694,
     Strategy = 816,
+    StreamMaxAge = 924,
+    StreamName = 923,
     SubAddressing = 347,
+    SubAddressingCreateFolders = 951,
+    SubAddressingFolders = 950,
     Subject = 41,
     SubjectAlternativeNames = 178,
     Subscribe = 368,
+    SubscriptionMaxBackoff = 880,
+    SubscriptionMaxSize = 881,
+    SubscriptionRefreshInterval = 879,
+    SubscriptionTimeout = 882,
     Sum = 494,
     Summary = 808,
+    Suppress = 962,
+    SuppressListTraffic = 992,
     Tag = 748,
     Tags = 746,
     TaskTypes = 189,
@@ -1055,6 +1194,7 @@ pub enum Property {
     TempFailOnError = 528,
     Temperature = 27,
     Template = 167,
+    TenantBreakdown = 901,
     TenantId = 831,
     Tenants = 153,
     Text = 2,
@@ -1080,6 +1220,9 @@ pub enum Property {
     Timestamp = 482,
     Title = 55,
     Tls = 542,
+    TlsClientAuth = 916,
+    TlsClientCaCertificates = 917,
+    TlsClientCertPrincipal = 918,
     TlsDisableCipherSuites = 599,
     TlsDisableProtocols = 600,
     TlsIgnoreClientOrder = 601,
@@ -1092,10 +1235,14 @@ pub enum Property {
     TraceId = 815,
     Tracer = 129,
     TrainFrequency = 734,
+    TrainRateLimit = 953,
     TransactionRetryDelay = 385,
     TransactionRetryLimit = 386,
     TransactionTimeout = 387,
     TransferLimit = 531,
+    TrapBlockIp = 1002,
+    TrapPenaltyFor = 1003,
+    TrapPenaltyScore = 1004,
     TrustContacts = 769,
     TrustReplies = 774,
     TsigAlgorithm = 338,
@@ -1108,6 +1255,7 @@ pub enum Property {
     UrlLimit = 753,
     UrlPrefix = 52,
     Urls = 647,
+    UseJetStream = 922,
     UsePermissiveCors = 400,
     UseTls = 309,
     UseXForwarded = 402,
@@ -1125,6 +1273,7 @@ pub enum Property {
     WebsocketHeartbeat = 455,
     WebsocketThrottle = 456,
     WebsocketTimeout = 457,
+    Weight = 954,
     Zone = 749,
     ZoneIpV4 = 98,
     ZoneIpV6 = 99,
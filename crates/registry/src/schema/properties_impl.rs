@@ -93,6 +93,7 @@ impl EnumImpl for ObjectType {
             b"OAuthClient" => ObjectType::OAuthClient,
             b"OidcProvider" => ObjectType::OidcProvider,
             b"PublicKey" => ObjectType::PublicKey,
+            b"QueueStore" => ObjectType::QueueStore,
             b"QueuedMessage" => ObjectType::QueuedMessage,
             b"ReportSettings" => ObjectType::ReportSettings,
             b"Role" => ObjectType::Role,
@@ -100,6 +101,7 @@ impl EnumImpl for ObjectType {
             b"SearchStore" => ObjectType::SearchStore,
             b"Security" => ObjectType::Security,
             b"SenderAuth" => ObjectType::SenderAuth,
+            b"ShareLink" => ObjectType::ShareLink,
             b"Sharing" => ObjectType::Sharing,
             b"SieveSystemInterpreter" => ObjectType::SieveSystemInterpreter,
             b"SieveSystemScript" => ObjectType::SieveSystemScript,
@@ -111,12 +113,14 @@ impl EnumImpl for ObjectType {
             b"SpamFileExtension" => ObjectType::SpamFileExtension,
             b"SpamLlm" => ObjectType::SpamLlm,
             b"SpamPyzor" => ObjectType::SpamPyzor,
+            b"SpamRspamd" => ObjectType::SpamRspamd,
             b"SpamRule" => ObjectType::SpamRule,
             b"SpamSettings" => ObjectType::SpamSettings,
             b"SpamTag" => ObjectType::SpamTag,
             b"SpamTrainingSample" => ObjectType::SpamTrainingSample,
             b"SpfReportSettings" => ObjectType::SpfReportSettings,
             b"StoreLookup" => ObjectType::StoreLookup,
+            b"SuppressedAddress" => ObjectType::SuppressedAddress,
             b"SystemSettings" => ObjectType::SystemSettings,
             b"Task" => ObjectType::Task,
             b"TaskManager" => ObjectType::TaskManager,
@@ -215,6 +219,7 @@ impl EnumImpl for ObjectType {
             ObjectType::OAuthClient => "OAuthClient",
             ObjectType::OidcProvider => "OidcProvider",
             ObjectType::PublicKey => "PublicKey",
+            ObjectType::QueueStore => "QueueStore",
             ObjectType::QueuedMessage => "QueuedMessage",
             ObjectType::ReportSettings => "ReportSettings",
             ObjectType::Role => "Role",
@@ -222,6 +227,7 @@ impl EnumImpl for ObjectType {
             ObjectType::SearchStore => "SearchStore",
             ObjectType::Security => "Security",
             ObjectType::SenderAuth => "SenderAuth",
+            ObjectType::ShareLink => "ShareLink",
             ObjectType::Sharing => "Sharing",
             ObjectType::SieveSystemInterpreter => "SieveSystemInterpreter",
             ObjectType::SieveSystemScript => "SieveSystemScript",
@@ -233,12 +239,14 @@ impl EnumImpl for ObjectType {
             ObjectType::SpamFileExtension => "SpamFileExtension",
             ObjectType::SpamLlm => "SpamLlm",
             ObjectType::SpamPyzor => "SpamPyzor",
+            ObjectType::SpamRspamd => "SpamRspamd",
             ObjectType::SpamRule => "SpamRule",
             ObjectType::SpamSettings => "SpamSettings",
             ObjectType::SpamTag => "SpamTag",
             ObjectType::SpamTrainingSample => "SpamTrainingSample",
             ObjectType::SpfReportSettings => "SpfReportSettings",
             ObjectType::StoreLookup => "StoreLookup",
+            ObjectType::SuppressedAddress => "SuppressedAddress",
             ObjectType::SystemSettings => "SystemSettings",
             ObjectType::Task => "Task",
             ObjectType::TaskManager => "TaskManager",
@@ -341,6 +349,7 @@ impl EnumImpl for ObjectType {
             78 => Some(ObjectType::OAuthClient),
             79 => Some(ObjectType::OidcProvider),
             80 => Some(ObjectType::PublicKey),
+            119 => Some(ObjectType::QueueStore),
             81 => Some(ObjectType::QueuedMessage),
             82 => Some(ObjectType::ReportSettings),
             83 => Some(ObjectType::Role),
@@ -348,6 +357,7 @@ impl EnumImpl for ObjectType {
             85 => Some(ObjectType::SearchStore),
             86 => Some(ObjectType::Security),
             87 => Some(ObjectType::SenderAuth),
+            117 => Some(ObjectType::ShareLink),
             88 => Some(ObjectType::Sharing),
             89 => Some(ObjectType::SieveSystemInterpreter),
             90 => Some(ObjectType::SieveSystemScript),
@@ -359,12 +369,14 @@ impl EnumImpl for ObjectType {
             96 => Some(ObjectType::SpamFileExtension),
             97 => Some(ObjectType::SpamLlm),
             98 => Some(ObjectType::SpamPyzor),
+            118 => Some(ObjectType::SpamRspamd),
             99 => Some(ObjectType::SpamRule),
             100 => Some(ObjectType::SpamSettings),
             101 => Some(ObjectType::SpamTag),
             102 => Some(ObjectType::SpamTrainingSample),
             103 => Some(ObjectType::SpfReportSettings),
             104 => Some(ObjectType::StoreLookup),
+            120 => Some(ObjectType::SuppressedAddress),
             105 => Some(ObjectType::SystemSettings),
             106 => Some(ObjectType::Task),
             107 => Some(ObjectType::TaskManager),
@@ -381,7 +393,7 @@ impl EnumImpl for ObjectType {
         }
     }
 
-    const COUNT: usize = 117;
+    const COUNT: usize = 121;
 }
 
 impl serde::Serialize for ObjectType {
@@ -432,6 +444,7 @@ impl EnumImpl for Property {
             b"addReturnPathHeader" => Property::AddReturnPathHeader,
             b"additionalInformation" => Property::AdditionalInformation,
             b"address" => Property::Address,
+            b"addressRewrites" => Property::AddressRewrites,
             b"addresses" => Property::Addresses,
             b"aggregateContactInfo" => Property::AggregateContactInfo,
             b"aggregateDkimSignDomain" => Property::AggregateDkimSignDomain,
@@ -451,10 +464,12 @@ impl EnumImpl for Property {
             b"allowPlainTextAuth" => Property::AllowPlainTextAuth,
             b"allowRelaying" => Property::AllowRelaying,
             b"allowSpamTraining" => Property::AllowSpamTraining,
+            b"allowUnsubscribe" => Property::AllowUnsubscribe,
             b"allowedEndpoints" => Property::AllowedEndpoints,
             b"allowedIps" => Property::AllowedIps,
             b"allowedNotifyUris" => Property::AllowedNotifyUris,
             b"alpha" => Property::Alpha,
+            b"anomalyType" => Property::AnomalyType,
             b"anonymousClientRegistration" => Property::AnonymousClientRegistration,
             b"ansi" => Property::Ansi,
             b"apiKey" => Property::ApiKey,
@@ -464,6 +479,7 @@ impl EnumImpl for Property {
             b"arcVerify" => Property::ArcVerify,
             b"archiveDeletedAccountsFor" => Property::ArchiveDeletedAccountsFor,
             b"archiveDeletedItemsFor" => Property::ArchiveDeletedItemsFor,
+            b"archiveMailbox" => Property::ArchiveMailbox,
             b"archivedAt" => Property::ArchivedAt,
             b"archivedItemType" => Property::ArchivedItemType,
             b"archivedUntil" => Property::ArchivedUntil,
@@ -475,7 +491,9 @@ impl EnumImpl for Property {
             b"attrDescription" => Property::AttrDescription,
             b"attrEmail" => Property::AttrEmail,
             b"attrEmailAlias" => Property::AttrEmailAlias,
+            b"attrLoginReferral" => Property::AttrLoginReferral,
             b"attrMemberOf" => Property::AttrMemberOf,
+            b"attrProxyBackend" => Property::AttrProxyBackend,
             b"attrSecret" => Property::AttrSecret,
             b"attrSecretChanged" => Property::AttrSecretChanged,
             b"auid" => Property::Auid,
@@ -490,6 +508,7 @@ impl EnumImpl for Property {
             b"authenticatedAs" => Property::AuthenticatedAs,
             b"authenticationResults" => Property::AuthenticationResults,
             b"autoAddInvitations" => Property::AutoAddInvitations,
+            b"autoBcc" => Property::AutoBcc,
             b"autoUpdateFrequency" => Property::AutoUpdateFrequency,
             b"baseDn" => Property::BaseDn,
             b"bearerToken" => Property::BearerToken,
@@ -503,15 +522,22 @@ impl EnumImpl for Property {
             b"blobSize" => Property::BlobSize,
             b"blobStore" => Property::BlobStore,
             b"blockCount" => Property::BlockCount,
+            b"blockedTlsFingerprints" => Property::BlockedTlsFingerprints,
             b"body" => Property::Body,
             b"brokers" => Property::Brokers,
             b"bucket" => Property::Bucket,
             b"bufferSize" => Property::BufferSize,
             b"buffered" => Property::Buffered,
+            b"cacheMaxAge" => Property::CacheMaxAge,
+            b"caldavUrl" => Property::CaldavUrl,
+            b"calendarAutoImport" => Property::CalendarAutoImport,
             b"canonicalization" => Property::Canonicalization,
             b"capacityClient" => Property::CapacityClient,
             b"capacityReadBuffer" => Property::CapacityReadBuffer,
             b"capacitySubscription" => Property::CapacitySubscription,
+            b"captchaProvider" => Property::CaptchaProvider,
+            b"captchaSecret" => Property::CaptchaSecret,
+            b"carddavUrl" => Property::CarddavUrl,
             b"catchAllAddress" => Property::CatchAllAddress,
             b"categories" => Property::Categories,
             b"certificate" => Property::Certificate,
@@ -523,14 +549,21 @@ impl EnumImpl for Property {
             b"claimName" => Property::ClaimName,
             b"claimUsername" => Property::ClaimUsername,
             b"cleartext" => Property::Cleartext,
+            b"cleartextPort" => Property::CleartextPort,
             b"clientId" => Property::ClientId,
             b"clusterFile" => Property::ClusterFile,
+            b"collection" => Property::Collection,
+            b"collectionId" => Property::CollectionId,
             b"columnClass" => Property::ColumnClass,
             b"columnDescription" => Property::ColumnDescription,
             b"columnEmail" => Property::ColumnEmail,
+            b"columnLoginReferral" => Property::ColumnLoginReferral,
+            b"columnProxyBackend" => Property::ColumnProxyBackend,
             b"columnSecret" => Property::ColumnSecret,
             b"comment" => Property::Comment,
             b"compressionAlgorithm" => Property::CompressionAlgorithm,
+            b"compressionContentTypes" => Property::CompressionContentTypes,
+            b"compressionMinSize" => Property::CompressionMinSize,
             b"concurrency" => Property::Concurrency,
             b"condition" => Property::Condition,
             b"confidence" => Property::Confidence,
@@ -545,7 +578,13 @@ impl EnumImpl for Property {
             b"content" => Property::Content,
             b"contentTypes" => Property::ContentTypes,
             b"contents" => Property::Contents,
+            b"corsAllowCredentials" => Property::CorsAllowCredentials,
+            b"corsAllowedHeaders" => Property::CorsAllowedHeaders,
+            b"corsAllowedMethods" => Property::CorsAllowedMethods,
+            b"corsAllowedOrigins" => Property::CorsAllowedOrigins,
+            b"corsMaxAge" => Property::CorsMaxAge,
             b"count" => Property::Count,
+            b"country" => Property::Country,
             b"create" => Property::Create,
             b"createdAt" => Property::CreatedAt,
             b"createdBy" => Property::CreatedBy,
@@ -565,7 +604,9 @@ impl EnumImpl for Property {
             b"dateRangeEnd" => Property::DateRangeEnd,
             b"dateRangeStart" => Property::DateRangeStart,
             b"day" => Property::Day,
+            b"deadLetterUrl" => Property::DeadLetterUrl,
             b"deadPropertyMaxSize" => Property::DeadPropertyMaxSize,
+            b"defaultAcmeProviderId" => Property::DefaultAcmeProviderId,
             b"defaultAdminRoleIds" => Property::DefaultAdminRoleIds,
             b"defaultCertificateId" => Property::DefaultCertificateId,
             b"defaultDisplayName" => Property::DefaultDisplayName,
@@ -588,6 +629,8 @@ impl EnumImpl for Property {
             b"defaultUserRoleIds" => Property::DefaultUserRoleIds,
             b"definition" => Property::Definition,
             b"delay" => Property::Delay,
+            b"delaySubject" => Property::DelaySubject,
+            b"delayTemplate" => Property::DelayTemplate,
             b"deleteAfter" => Property::DeleteAfter,
             b"deleteAfterUse" => Property::DeleteAfterUse,
             b"deliverAt" => Property::DeliverAt,
@@ -598,11 +641,23 @@ impl EnumImpl for Property {
             b"description" => Property::Description,
             b"details" => Property::Details,
             b"directory" => Property::Directory,
+            b"directoryDisplayName" => Property::DirectoryDisplayName,
+            b"directoryEnable" => Property::DirectoryEnable,
+            b"directoryFields" => Property::DirectoryFields,
+            b"directoryHrefName" => Property::DirectoryHrefName,
             b"directoryId" => Property::DirectoryId,
+            b"directoryRefreshInterval" => Property::DirectoryRefreshInterval,
+            b"directoryScope" => Property::DirectoryScope,
             b"disableCapabilities" => Property::DisableCapabilities,
+            b"disableFor" => Property::DisableFor,
             b"disableLanguages" => Property::DisableLanguages,
+            b"disabledJmapCapabilities" => Property::DisabledJmapCapabilities,
             b"disabledPermissions" => Property::DisabledPermissions,
             b"discardAfter" => Property::DiscardAfter,
+            b"disclaimerHtml" => Property::DisclaimerHtml,
+            b"disclaimerInbound" => Property::DisclaimerInbound,
+            b"disclaimerText" => Property::DisclaimerText,
+            b"disclaimerWrapSigned" => Property::DisclaimerWrapSigned,
             b"disposition" => Property::Disposition,
             b"dkimAdspDns" => Property::DkimAdspDns,
             b"dkimCanonicalizedBody" => Property::DkimCanonicalizedBody,
@@ -611,6 +666,7 @@ impl EnumImpl for Property {
             b"dkimIdentity" => Property::DkimIdentity,
             b"dkimManagement" => Property::DkimManagement,
             b"dkimPass" => Property::DkimPass,
+            b"dkimResignDomain" => Property::DkimResignDomain,
             b"dkimResults" => Property::DkimResults,
             b"dkimSelector" => Property::DkimSelector,
             b"dkimSelectorDns" => Property::DkimSelectorDns,
@@ -637,6 +693,7 @@ impl EnumImpl for Property {
             b"documentId" => Property::DocumentId,
             b"documentType" => Property::DocumentType,
             b"domain" => Property::Domain,
+            b"domainBreakdown" => Property::DomainBreakdown,
             b"domainId" => Property::DomainId,
             b"domainLimit" => Property::DomainLimit,
             b"domainNames" => Property::DomainNames,
@@ -644,7 +701,9 @@ impl EnumImpl for Property {
             b"domains" => Property::Domains,
             b"dsn" => Property::Dsn,
             b"due" => Property::Due,
+            b"duplicateAction" => Property::DuplicateAction,
             b"duplicateExpiry" => Property::DuplicateExpiry,
+            b"duplicateWindow" => Property::DuplicateWindow,
             b"duration" => Property::Duration,
             b"eabHmacKey" => Property::EabHmacKey,
             b"eabKeyId" => Property::EabKeyId,
@@ -661,11 +720,15 @@ impl EnumImpl for Property {
             b"emailDomain" => Property::EmailDomain,
             b"emailLimit" => Property::EmailLimit,
             b"emailPrefix" => Property::EmailPrefix,
+            b"emailQueries" => Property::EmailQueries,
             b"emailTemplate" => Property::EmailTemplate,
             b"enable" => Property::Enable,
             b"enableAssistedDiscovery" => Property::EnableAssistedDiscovery,
+            b"enableCompression" => Property::EnableCompression,
             b"enableEdns" => Property::EnableEdns,
+            b"enableH2c" => Property::EnableH2c,
             b"enableHsts" => Property::EnableHsts,
+            b"enableHttp2" => Property::EnableHttp2,
             b"enableLogExporter" => Property::EnableLogExporter,
             b"enableSpamFilter" => Property::EnableSpamFilter,
             b"enableSpanExporter" => Property::EnableSpanExporter,
@@ -675,7 +738,9 @@ impl EnumImpl for Property {
             b"encryptOnAppend" => Property::EncryptOnAppend,
             b"encryptionAtRest" => Property::EncryptionAtRest,
             b"encryptionKey" => Property::EncryptionKey,
+            b"encryptionPolicy" => Property::EncryptionPolicy,
             b"endpoint" => Property::Endpoint,
+            b"enforceQuota" => Property::EnforceQuota,
             b"envFrom" => Property::EnvFrom,
             b"envFromParameters" => Property::EnvFromParameters,
             b"envId" => Property::EnvId,
@@ -700,6 +765,7 @@ impl EnumImpl for Property {
             b"eventStartTz" => Property::EventStartTz,
             b"events" => Property::Events,
             b"eventsPolicy" => Property::EventsPolicy,
+            b"excludeKeywords" => Property::ExcludeKeywords,
             b"expire" => Property::Expire,
             b"expires" => Property::Expires,
             b"expiresAt" => Property::ExpiresAt,
@@ -714,6 +780,7 @@ impl EnumImpl for Property {
             b"extension" => Property::Extension,
             b"extensions" => Property::Extensions,
             b"extraContactInfo" => Property::ExtraContactInfo,
+            b"facility" => Property::Facility,
             b"factor" => Property::Factor,
             b"failOnTimeout" => Property::FailOnTimeout,
             b"failedAt" => Property::FailedAt,
@@ -739,11 +806,15 @@ impl EnumImpl for Property {
             b"filterLogin" => Property::FilterLogin,
             b"filterMailbox" => Property::FilterMailbox,
             b"filterMemberOf" => Property::FilterMemberOf,
+            b"firewallSync" => Property::FirewallSync,
+            b"firewallSyncInterval" => Property::FirewallSyncInterval,
             b"flags" => Property::Flags,
             b"flagsAction" => Property::FlagsAction,
             b"flagsProtocol" => Property::FlagsProtocol,
+            b"folderPolicies" => Property::FolderPolicies,
             b"forDomain" => Property::ForDomain,
             b"format" => Property::Format,
+            b"forms" => Property::Forms,
             b"from" => Property::From,
             b"fromAddress" => Property::FromAddress,
             b"fromEmail" => Property::FromEmail,
@@ -758,6 +829,10 @@ impl EnumImpl for Property {
             b"groupId" => Property::GroupId,
             b"headerFrom" => Property::HeaderFrom,
             b"headers" => Property::Headers,
+            b"healthDegradedWeight" => Property::HealthDegradedWeight,
+            b"healthQueueThreshold" => Property::HealthQueueThreshold,
+            b"healthStoreTimeout" => Property::HealthStoreTimeout,
+            b"holdAuditLogFor" => Property::HoldAuditLogFor,
             b"holdMetricsFor" => Property::HoldMetricsFor,
             b"holdMtaReportsFor" => Property::HoldMtaReportsFor,
             b"holdSamplesFor" => Property::HoldSamplesFor,
@@ -765,8 +840,13 @@ impl EnumImpl for Property {
             b"host" => Property::Host,
             b"hostedZoneId" => Property::HostedZoneId,
             b"hostname" => Property::Hostname,
+            b"hostnames" => Property::Hostnames,
             b"hour" => Property::Hour,
+            b"http2MaxConcurrentStreams" => Property::Http2MaxConcurrentStreams,
             b"httpAuth" => Property::HttpAuth,
+            b"httpFreeBusyAccess" => Property::HttpFreeBusyAccess,
+            b"httpFreeBusyEnable" => Property::HttpFreeBusyEnable,
+            b"httpFreeBusyMaxRange" => Property::HttpFreeBusyMaxRange,
             b"httpHeaders" => Property::HttpHeaders,
             b"httpRsvpEnable" => Property::HttpRsvpEnable,
             b"httpRsvpLinkExpiry" => Property::HttpRsvpLinkExpiry,
@@ -779,6 +859,7 @@ impl EnumImpl for Property {
             b"idTokenExpiry" => Property::IdTokenExpiry,
             b"identityAlignment" => Property::IdentityAlignment,
             b"if" => Property::If,
+            b"imapUrl" => Property::ImapUrl,
             b"impersonateServiceAccount" => Property::ImpersonateServiceAccount,
             b"implicitTls" => Property::ImplicitTls,
             b"inMemoryStore" => Property::InMemoryStore,
@@ -788,6 +869,7 @@ impl EnumImpl for Property {
             b"includeSource" => Property::IncludeSource,
             b"indexAsn" => Property::IndexAsn,
             b"indexAsnName" => Property::IndexAsnName,
+            b"indexAttachmentContents" => Property::IndexAttachmentContents,
             b"indexBatchSize" => Property::IndexBatchSize,
             b"indexCalendar" => Property::IndexCalendar,
             b"indexCalendarFields" => Property::IndexCalendarFields,
@@ -827,6 +909,7 @@ impl EnumImpl for Property {
             b"keyName" => Property::KeyName,
             b"keyPrefix" => Property::KeyPrefix,
             b"keyValues" => Property::KeyValues,
+            b"keywords" => Property::Keywords,
             b"l1Ratio" => Property::L1Ratio,
             b"l2Ratio" => Property::L2Ratio,
             b"lastRenewal" => Property::LastRenewal,
@@ -834,12 +917,17 @@ impl EnumImpl for Property {
             b"learnHamFromReply" => Property::LearnHamFromReply,
             b"learnSpamFromRblHits" => Property::LearnSpamFromRblHits,
             b"learnSpamFromTraps" => Property::LearnSpamFromTraps,
+            b"legalHold" => Property::LegalHold,
             b"level" => Property::Level,
             b"licenseKey" => Property::LicenseKey,
             b"listenerIds" => Property::ListenerIds,
             b"listeners" => Property::Listeners,
             b"livePropertyMaxSize" => Property::LivePropertyMaxSize,
             b"locale" => Property::Locale,
+            b"loginAnomalySensitivity" => Property::LoginAnomalySensitivity,
+            b"loginAt" => Property::LoginAt,
+            b"loginReferral" => Property::LoginReferral,
+            b"loginTravelWindow" => Property::LoginTravelWindow,
             b"logo" => Property::Logo,
             b"logoUrl" => Property::LogoUrl,
             b"loiterBanPeriod" => Property::LoiterBanPeriod,
@@ -850,6 +938,8 @@ impl EnumImpl for Property {
             b"mailFrom" => Property::MailFrom,
             b"mailFromTimeout" => Property::MailFromTimeout,
             b"mailRua" => Property::MailRua,
+            b"mailboxId" => Property::MailboxId,
+            b"mailboxes" => Property::Mailboxes,
             b"mailingLists" => Property::MailingLists,
             b"maintenanceType" => Property::MaintenanceType,
             b"managedZone" => Property::ManagedZone,
@@ -863,9 +953,12 @@ impl EnumImpl for Property {
             b"maxAttempts" => Property::MaxAttempts,
             b"maxAttendees" => Property::MaxAttendees,
             b"maxAuthFailures" => Property::MaxAuthFailures,
+            b"maxBatchSize" => Property::MaxBatchSize,
+            b"maxBreakdownLabels" => Property::MaxBreakdownLabels,
             b"maxCalendars" => Property::MaxCalendars,
             b"maxChangesHistory" => Property::MaxChangesHistory,
             b"maxConcurrent" => Property::MaxConcurrent,
+            b"maxConcurrentCalls" => Property::MaxConcurrentCalls,
             b"maxConcurrentRequests" => Property::MaxConcurrentRequests,
             b"maxConcurrentUploads" => Property::MaxConcurrentUploads,
             b"maxConnections" => Property::MaxConnections,
@@ -873,6 +966,7 @@ impl EnumImpl for Property {
             b"maxCpuCycles" => Property::MaxCpuCycles,
             b"maxDelay" => Property::MaxDelay,
             b"maxDuration" => Property::MaxDuration,
+            b"maxEmailChangeHistory" => Property::MaxEmailChangeHistory,
             b"maxEntries" => Property::MaxEntries,
             b"maxEntrySize" => Property::MaxEntrySize,
             b"maxEventNotifications" => Property::MaxEventNotifications,
@@ -904,6 +998,8 @@ impl EnumImpl for Property {
             b"maxOutMessages" => Property::MaxOutMessages,
             b"maxParticipantIdentities" => Property::MaxParticipantIdentities,
             b"maxPublicKeys" => Property::MaxPublicKeys,
+            b"maxQueueWait" => Property::MaxQueueWait,
+            b"maxQueuedCalls" => Property::MaxQueuedCalls,
             b"maxReceivedHeaders" => Property::MaxReceivedHeaders,
             b"maxRecipients" => Property::MaxRecipients,
             b"maxReconnects" => Property::MaxReconnects,
@@ -919,6 +1015,8 @@ impl EnumImpl for Property {
             b"maxScriptNameLength" => Property::MaxScriptNameLength,
             b"maxScriptSize" => Property::MaxScriptSize,
             b"maxScripts" => Property::MaxScripts,
+            b"maxSessionRecipients" => Property::MaxSessionRecipients,
+            b"maxShareLinks" => Property::MaxShareLinks,
             b"maxShares" => Property::MaxShares,
             b"maxSize" => Property::MaxSize,
             b"maxStringLength" => Property::MaxStringLength,
@@ -960,6 +1058,8 @@ impl EnumImpl for Property {
             b"nextNotify" => Property::NextNotify,
             b"nextRetry" => Property::NextRetry,
             b"nextTransitionAt" => Property::NextTransitionAt,
+            b"nftFamily" => Property::NftFamily,
+            b"nftTable" => Property::NftTable,
             b"noCapabilityCheck" => Property::NoCapabilityCheck,
             b"noEcho" => Property::NoEcho,
             b"noSoliciting" => Property::NoSoliciting,
@@ -972,6 +1072,7 @@ impl EnumImpl for Property {
             b"numFeatures" => Property::NumFeatures,
             b"numReplicas" => Property::NumReplicas,
             b"numShards" => Property::NumShards,
+            b"olderThan" => Property::OlderThan,
             b"onSuccessRenewCertificate" => Property::OnSuccessRenewCertificate,
             b"openTelemetry" => Property::OpenTelemetry,
             b"options" => Property::Options,
@@ -982,6 +1083,7 @@ impl EnumImpl for Property {
             b"originalEnvelopeId" => Property::OriginalEnvelopeId,
             b"originalMailFrom" => Property::OriginalMailFrom,
             b"originalRcptTo" => Property::OriginalRcptTo,
+            b"otlpIngest" => Property::OtlpIngest,
             b"otpAuth" => Property::OtpAuth,
             b"otpCode" => Property::OtpCode,
             b"otpUrl" => Property::OtpUrl,
@@ -1000,6 +1102,8 @@ impl EnumImpl for Property {
             b"passwordMinLength" => Property::PasswordMinLength,
             b"passwordMinStrength" => Property::PasswordMinStrength,
             b"path" => Property::Path,
+            b"pattern" => Property::Pattern,
+            b"payloadTemplate" => Property::PayloadTemplate,
             b"period" => Property::Period,
             b"permissions" => Property::Permissions,
             b"pingInterval" => Property::PingInterval,
@@ -1030,6 +1134,7 @@ impl EnumImpl for Property {
             b"port" => Property::Port,
             b"prefix" => Property::Prefix,
             b"preserveIntermediates" => Property::PreserveIntermediates,
+            b"previousCountry" => Property::PreviousCountry,
             b"priority" => Property::Priority,
             b"privateKey" => Property::PrivateKey,
             b"privateZone" => Property::PrivateZone,
@@ -1040,11 +1145,19 @@ impl EnumImpl for Property {
             b"prompt" => Property::Prompt,
             b"propagationDelay" => Property::PropagationDelay,
             b"propagationTimeout" => Property::PropagationTimeout,
+            b"protect" => Property::Protect,
             b"protectedHeaders" => Property::ProtectedHeaders,
             b"protocol" => Property::Protocol,
             b"protocolVersion" => Property::ProtocolVersion,
             b"providerInfo" => Property::ProviderInfo,
+            b"proxyAllowInvalidCerts" => Property::ProxyAllowInvalidCerts,
+            b"proxyBackend" => Property::ProxyBackend,
+            b"proxyImapPort" => Property::ProxyImapPort,
+            b"proxyImplicitTls" => Property::ProxyImplicitTls,
+            b"proxyPop3Port" => Property::ProxyPop3Port,
             b"proxyTrustedNetworks" => Property::ProxyTrustedNetworks,
+            b"publicFolderName" => Property::PublicFolderName,
+            b"publicFolders" => Property::PublicFolders,
             b"publicKey" => Property::PublicKey,
             b"publishRecords" => Property::PublishRecords,
             b"pushAttemptWait" => Property::PushAttemptWait,
@@ -1066,8 +1179,10 @@ impl EnumImpl for Property {
             b"rateLimit" => Property::RateLimit,
             b"rateLimitAnonymous" => Property::RateLimitAnonymous,
             b"rateLimitAuthenticated" => Property::RateLimitAuthenticated,
+            b"rateLimited" => Property::RateLimited,
             b"ratio" => Property::Ratio,
             b"rcptToTimeout" => Property::RcptToTimeout,
+            b"readCacheSize" => Property::ReadCacheSize,
             b"readFromReplicas" => Property::ReadFromReplicas,
             b"readReplicas" => Property::ReadReplicas,
             b"reason" => Property::Reason,
@@ -1085,9 +1200,18 @@ impl EnumImpl for Property {
             b"refreshTokenExpiry" => Property::RefreshTokenExpiry,
             b"refreshTokenRenewal" => Property::RefreshTokenRenewal,
             b"region" => Property::Region,
+            b"reindexRate" => Property::ReindexRate,
             b"rejectNonFqdn" => Property::RejectNonFqdn,
+            b"relayVerify" => Property::RelayVerify,
+            b"relayVerifyCacheTtl" => Property::RelayVerifyCacheTtl,
+            b"relayVerifyHost" => Property::RelayVerifyHost,
+            b"relayVerifyUrl" => Property::RelayVerifyUrl,
             b"remoteIp" => Property::RemoteIp,
             b"renewBefore" => Property::RenewBefore,
+            b"repeatBanDecay" => Property::RepeatBanDecay,
+            b"repeatBanMaxPeriod" => Property::RepeatBanMaxPeriod,
+            b"replaceScore" => Property::ReplaceScore,
+            b"replacement" => Property::Replacement,
             b"report" => Property::Report,
             b"reportAddressUri" => Property::ReportAddressUri,
             b"reportId" => Property::ReportId,
@@ -1113,8 +1237,10 @@ impl EnumImpl for Property {
             b"responsePosExplanation" => Property::ResponsePosExplanation,
             b"result" => Property::Result,
             b"resultType" => Property::ResultType,
+            b"retentionPolicies" => Property::RetentionPolicies,
             b"retireAfter" => Property::RetireAfter,
             b"retry" => Property::Retry,
+            b"retryBackoff" => Property::RetryBackoff,
             b"retryCount" => Property::RetryCount,
             b"retryDue" => Property::RetryDue,
             b"returnPath" => Property::ReturnPath,
@@ -1133,6 +1259,8 @@ impl EnumImpl for Property {
             b"scanBanRate" => Property::ScanBanRate,
             b"schedule" => Property::Schedule,
             b"scheduling" => Property::Scheduling,
+            b"schedulingPolicy" => Property::SchedulingPolicy,
+            b"schedulingResource" => Property::SchedulingResource,
             b"scope" => Property::Scope,
             b"score" => Property::Score,
             b"scoreDiscard" => Property::ScoreDiscard,
@@ -1155,8 +1283,13 @@ impl EnumImpl for Property {
             b"serviceAccountJson" => Property::ServiceAccountJson,
             b"services" => Property::Services,
             b"sessionToken" => Property::SessionToken,
+            b"setIpv4" => Property::SetIpv4,
+            b"setIpv6" => Property::SetIpv6,
             b"setMaxObjects" => Property::SetMaxObjects,
             b"shardIndex" => Property::ShardIndex,
+            b"shareLinkEnable" => Property::ShareLinkEnable,
+            b"shareLinkMaxExpiry" => Property::ShareLinkMaxExpiry,
+            b"sieveUrl" => Property::SieveUrl,
             b"sig0Algorithm" => Property::Sig0Algorithm,
             b"signatureAlgorithm" => Property::SignatureAlgorithm,
             b"signatureKey" => Property::SignatureKey,
@@ -1194,12 +1327,22 @@ impl EnumImpl for Property {
             b"store" => Property::Store,
             b"stores" => Property::Stores,
             b"strategy" => Property::Strategy,
+            b"streamMaxAge" => Property::StreamMaxAge,
+            b"streamName" => Property::StreamName,
             b"subAddressing" => Property::SubAddressing,
+            b"subAddressingCreateFolders" => Property::SubAddressingCreateFolders,
+            b"subAddressingFolders" => Property::SubAddressingFolders,
             b"subject" => Property::Subject,
             b"subjectAlternativeNames" => Property::SubjectAlternativeNames,
             b"subscribe" => Property::Subscribe,
+            b"subscriptionMaxBackoff" => Property::SubscriptionMaxBackoff,
+            b"subscriptionMaxSize" => Property::SubscriptionMaxSize,
+            b"subscriptionRefreshInterval" => Property::SubscriptionRefreshInterval,
+            b"subscriptionTimeout" => Property::SubscriptionTimeout,
             b"sum" => Property::Sum,
             b"summary" => Property::Summary,
+            b"suppress" => Property::Suppress,
+            b"suppressListTraffic" => Property::SuppressListTraffic,
             b"tag" => Property::Tag,
             b"tags" => Property::Tags,
             b"taskTypes" => Property::TaskTypes,
@@ -1208,6 +1351,7 @@ impl EnumImpl for Property {
             b"tempFailOnError" => Property::TempFailOnError,
             b"temperature" => Property::Temperature,
             b"template" => Property::Template,
+            b"tenantBreakdown" => Property::TenantBreakdown,
             b"tenantId" => Property::TenantId,
             b"tenants" => Property::Tenants,
             b"text" => Property::Text,
@@ -1233,6 +1377,9 @@ impl EnumImpl for Property {
             b"timestamp" => Property::Timestamp,
             b"title" => Property::Title,
             b"tls" => Property::Tls,
+            b"tlsClientAuth" => Property::TlsClientAuth,
+            b"tlsClientCaCertificates" => Property::TlsClientCaCertificates,
+            b"tlsClientCertPrincipal" => Property::TlsClientCertPrincipal,
             b"tlsDisableCipherSuites" => Property::TlsDisableCipherSuites,
             b"tlsDisableProtocols" => Property::TlsDisableProtocols,
             b"tlsIgnoreClientOrder" => Property::TlsIgnoreClientOrder,
@@ -1245,10 +1392,14 @@ impl EnumImpl for Property {
             b"traceId" => Property::TraceId,
             b"tracer" => Property::Tracer,
             b"trainFrequency" => Property::TrainFrequency,
+            b"trainRateLimit" => Property::TrainRateLimit,
             b"transactionRetryDelay" => Property::TransactionRetryDelay,
             b"transactionRetryLimit" => Property::TransactionRetryLimit,
             b"transactionTimeout" => Property::TransactionTimeout,
             b"transferLimit" => Property::TransferLimit,
+            b"trapBlockIp" => Property::TrapBlockIp,
+            b"trapPenaltyFor" => Property::TrapPenaltyFor,
+            b"trapPenaltyScore" => Property::TrapPenaltyScore,
             b"trustContacts" => Property::TrustContacts,
             b"trustReplies" => Property::TrustReplies,
             b"tsigAlgorithm" => Property::TsigAlgorithm,
@@ -1261,6 +1412,7 @@ impl EnumImpl for Property {
             b"urlLimit" => Property::UrlLimit,
             b"urlPrefix" => Property::UrlPrefix,
             b"urls" => Property::Urls,
+            b"useJetStream" => Property::UseJetStream,
             b"usePermissiveCors" => Property::UsePermissiveCors,
             b"useTls" => Property::UseTls,
             b"useXForwarded" => Property::UseXForwarded,
@@ -1278,6 +1430,7 @@ impl EnumImpl for Property {
             b"websocketHeartbeat" => Property::WebsocketHeartbeat,
             b"websocketThrottle" => Property::WebsocketThrottle,
             b"websocketTimeout" => Property::WebsocketTimeout,
+            b"weight" => Property::Weight,
             b"zone" => Property::Zone,
             b"zoneIpV4" => Property::ZoneIpV4,
             b"zoneIpV6" => Property::ZoneIpV6,
@@ -1311,6 +1464,7 @@ impl EnumImpl for Property {
             Property::AddReturnPathHeader => "addReturnPathHeader",
             Property::AdditionalInformation => "additionalInformation",
             Property::Address => "address",
+            Property::AddressRewrites => "addressRewrites",
             Property::Addresses => "addresses",
             Property::AggregateContactInfo => "aggregateContactInfo",
             Property::AggregateDkimSignDomain => "aggregateDkimSignDomain",
@@ -1330,10 +1484,12 @@ impl EnumImpl for Property {
             Property::AllowPlainTextAuth => "allowPlainTextAuth",
             Property::AllowRelaying => "allowRelaying",
             Property::AllowSpamTraining => "allowSpamTraining",
+            Property::AllowUnsubscribe => "allowUnsubscribe",
             Property::AllowedEndpoints => "allowedEndpoints",
             Property::AllowedIps => "allowedIps",
             Property::AllowedNotifyUris => "allowedNotifyUris",
             Property::Alpha => "alpha",
+            Property::AnomalyType => "anomalyType",
             Property::AnonymousClientRegistration => "anonymousClientRegistration",
             Property::Ansi => "ansi",
             Property::ApiKey => "apiKey",
@@ -1343,6 +1499,7 @@ impl EnumImpl for Property {
             Property::ArcVerify => "arcVerify",
             Property::ArchiveDeletedAccountsFor => "archiveDeletedAccountsFor",
             Property::ArchiveDeletedItemsFor => "archiveDeletedItemsFor",
+            Property::ArchiveMailbox => "archiveMailbox",
             Property::ArchivedAt => "archivedAt",
             Property::ArchivedItemType => "archivedItemType",
             Property::ArchivedUntil => "archivedUntil",
@@ -1354,7 +1511,9 @@ impl EnumImpl for Property {
             Property::AttrDescription => "attrDescription",
             Property::AttrEmail => "attrEmail",
             Property::AttrEmailAlias => "attrEmailAlias",
+            Property::AttrLoginReferral => "attrLoginReferral",
             Property::AttrMemberOf => "attrMemberOf",
+            Property::AttrProxyBackend => "attrProxyBackend",
             Property::AttrSecret => "attrSecret",
             Property::AttrSecretChanged => "attrSecretChanged",
             Property::Auid => "auid",
@@ -1369,6 +1528,7 @@ impl EnumImpl for Property {
             Property::AuthenticatedAs => "authenticatedAs",
             Property::AuthenticationResults => "authenticationResults",
             Property::AutoAddInvitations => "autoAddInvitations",
+            Property::AutoBcc => "autoBcc",
             Property::AutoUpdateFrequency => "autoUpdateFrequency",
             Property::BaseDn => "baseDn",
             Property::BearerToken => "bearerToken",
@@ -1382,15 +1542,22 @@ impl EnumImpl for Property {
             Property::BlobSize => "blobSize",
             Property::BlobStore => "blobStore",
             Property::BlockCount => "blockCount",
+            Property::BlockedTlsFingerprints => "blockedTlsFingerprints",
             Property::Body => "body",
             Property::Brokers => "brokers",
             Property::Bucket => "bucket",
             Property::BufferSize => "bufferSize",
             Property::Buffered => "buffered",
+            Property::CacheMaxAge => "cacheMaxAge",
+            Property::CaldavUrl => "caldavUrl",
+            Property::CalendarAutoImport => "calendarAutoImport",
             Property::Canonicalization => "canonicalization",
             Property::CapacityClient => "capacityClient",
             Property::CapacityReadBuffer => "capacityReadBuffer",
             Property::CapacitySubscription => "capacitySubscription",
+            Property::CaptchaProvider => "captchaProvider",
+            Property::CaptchaSecret => "captchaSecret",
+            Property::CarddavUrl => "carddavUrl",
             Property::CatchAllAddress => "catchAllAddress",
             Property::Categories => "categories",
             Property::Certificate => "certificate",
@@ -1402,14 +1569,21 @@ impl EnumImpl for Property {
             Property::ClaimName => "claimName",
             Property::ClaimUsername => "claimUsername",
             Property::Cleartext => "cleartext",
+            Property::CleartextPort => "cleartextPort",
             Property::ClientId => "clientId",
             Property::ClusterFile => "clusterFile",
+            Property::Collection => "collection",
+            Property::CollectionId => "collectionId",
             Property::ColumnClass => "columnClass",
             Property::ColumnDescription => "columnDescription",
             Property::ColumnEmail => "columnEmail",
+            Property::ColumnLoginReferral => "columnLoginReferral",
+            Property::ColumnProxyBackend => "columnProxyBackend",
             Property::ColumnSecret => "columnSecret",
             Property::Comment => "comment",
             Property::CompressionAlgorithm => "compressionAlgorithm",
+            Property::CompressionContentTypes => "compressionContentTypes",
+            Property::CompressionMinSize => "compressionMinSize",
             Property::Concurrency => "concurrency",
             Property::Condition => "condition",
             Property::Confidence => "confidence",
@@ -1424,7 +1598,13 @@ impl EnumImpl for Property {
             Property::Content => "content",
             Property::ContentTypes => "contentTypes",
             Property::Contents => "contents",
+            Property::CorsAllowCredentials => "corsAllowCredentials",
+            Property::CorsAllowedHeaders => "corsAllowedHeaders",
+            Property::CorsAllowedMethods => "corsAllowedMethods",
+            Property::CorsAllowedOrigins => "corsAllowedOrigins",
+            Property::CorsMaxAge => "corsMaxAge",
             Property::Count => "count",
+            Property::Country => "country",
             Property::Create => "create",
             Property::CreatedAt => "createdAt",
             Property::CreatedBy => "createdBy",
@@ -1444,7 +1624,9 @@ impl EnumImpl for Property {
             Property::DateRangeEnd => "dateRangeEnd",
             Property::DateRangeStart => "dateRangeStart",
             Property::Day => "day",
+            Property::DeadLetterUrl => "deadLetterUrl",
             Property::DeadPropertyMaxSize => "deadPropertyMaxSize",
+            Property::DefaultAcmeProviderId => "defaultAcmeProviderId",
             Property::DefaultAdminRoleIds => "defaultAdminRoleIds",
             Property::DefaultCertificateId => "defaultCertificateId",
             Property::DefaultDisplayName => "defaultDisplayName",
@@ -1467,6 +1649,8 @@ impl EnumImpl for Property {
             Property::DefaultUserRoleIds => "defaultUserRoleIds",
             Property::Definition => "definition",
             Property::Delay => "delay",
+            Property::DelaySubject => "delaySubject",
+            Property::DelayTemplate => "delayTemplate",
             Property::DeleteAfter => "deleteAfter",
             Property::DeleteAfterUse => "deleteAfterUse",
             Property::DeliverAt => "deliverAt",
@@ -1477,11 +1661,23 @@ impl EnumImpl for Property {
             Property::Description => "description",
             Property::Details => "details",
             Property::Directory => "directory",
+            Property::DirectoryDisplayName => "directoryDisplayName",
+            Property::DirectoryEnable => "directoryEnable",
+            Property::DirectoryFields => "directoryFields",
+            Property::DirectoryHrefName => "directoryHrefName",
             Property::DirectoryId => "directoryId",
+            Property::DirectoryRefreshInterval => "directoryRefreshInterval",
+            Property::DirectoryScope => "directoryScope",
             Property::DisableCapabilities => "disableCapabilities",
+            Property::DisableFor => "disableFor",
             Property::DisableLanguages => "disableLanguages",
+            Property::DisabledJmapCapabilities => "disabledJmapCapabilities",
             Property::DisabledPermissions => "disabledPermissions",
             Property::DiscardAfter => "discardAfter",
+            Property::DisclaimerHtml => "disclaimerHtml",
+            Property::DisclaimerInbound => "disclaimerInbound",
+            Property::DisclaimerText => "disclaimerText",
+            Property::DisclaimerWrapSigned => "disclaimerWrapSigned",
             Property::Disposition => "disposition",
             Property::DkimAdspDns => "dkimAdspDns",
             Property::DkimCanonicalizedBody => "dkimCanonicalizedBody",
@@ -1490,6 +1686,7 @@ impl EnumImpl for Property {
             Property::DkimIdentity => "dkimIdentity",
             Property::DkimManagement => "dkimManagement",
             Property::DkimPass => "dkimPass",
+            Property::DkimResignDomain => "dkimResignDomain",
             Property::DkimResults => "dkimResults",
             Property::DkimSelector => "dkimSelector",
             Property::DkimSelectorDns => "dkimSelectorDns",
@@ -1516,6 +1713,7 @@ impl EnumImpl for Property {
             Property::DocumentId => "documentId",
             Property::DocumentType => "documentType",
             Property::Domain => "domain",
+            Property::DomainBreakdown => "domainBreakdown",
             Property::DomainId => "domainId",
             Property::DomainLimit => "domainLimit",
             Property::DomainNames => "domainNames",
@@ -1523,7 +1721,9 @@ impl EnumImpl for Property {
             Property::Domains => "domains",
             Property::Dsn => "dsn",
             Property::Due => "due",
+            Property::DuplicateAction => "duplicateAction",
             Property::DuplicateExpiry => "duplicateExpiry",
+            Property::DuplicateWindow => "duplicateWindow",
             Property::Duration => "duration",
             Property::EabHmacKey => "eabHmacKey",
             Property::EabKeyId => "eabKeyId",
@@ -1540,11 +1740,15 @@ impl EnumImpl for Property {
             Property::EmailDomain => "emailDomain",
             Property::EmailLimit => "emailLimit",
             Property::EmailPrefix => "emailPrefix",
+            Property::EmailQueries => "emailQueries",
             Property::EmailTemplate => "emailTemplate",
             Property::Enable => "enable",
             Property::EnableAssistedDiscovery => "enableAssistedDiscovery",
+            Property::EnableCompression => "enableCompression",
             Property::EnableEdns => "enableEdns",
+            Property::EnableH2c => "enableH2c",
             Property::EnableHsts => "enableHsts",
+            Property::EnableHttp2 => "enableHttp2",
             Property::EnableLogExporter => "enableLogExporter",
             Property::EnableSpamFilter => "enableSpamFilter",
             Property::EnableSpanExporter => "enableSpanExporter",
@@ -1554,7 +1758,9 @@ impl EnumImpl for Property {
             Property::EncryptOnAppend => "encryptOnAppend",
             Property::EncryptionAtRest => "encryptionAtRest",
             Property::EncryptionKey => "encryptionKey",
+            Property::EncryptionPolicy => "encryptionPolicy",
             Property::Endpoint => "endpoint",
+            Property::EnforceQuota => "enforceQuota",
             Property::EnvFrom => "envFrom",
             Property::EnvFromParameters => "envFromParameters",
             Property::EnvId => "envId",
@@ -1579,6 +1785,7 @@ impl EnumImpl for Property {
             Property::EventStartTz => "eventStartTz",
             Property::Events => "events",
             Property::EventsPolicy => "eventsPolicy",
+            Property::ExcludeKeywords => "excludeKeywords",
             Property::Expire => "expire",
             Property::Expires => "expires",
             Property::ExpiresAt => "expiresAt",
@@ -1593,6 +1800,7 @@ impl EnumImpl for Property {
             Property::Extension => "extension",
             Property::Extensions => "extensions",
             Property::ExtraContactInfo => "extraContactInfo",
+            Property::Facility => "facility",
             Property::Factor => "factor",
             Property::FailOnTimeout => "failOnTimeout",
             Property::FailedAt => "failedAt",
@@ -1618,11 +1826,15 @@ impl EnumImpl for Property {
             Property::FilterLogin => "filterLogin",
             Property::FilterMailbox => "filterMailbox",
             Property::FilterMemberOf => "filterMemberOf",
+            Property::FirewallSync => "firewallSync",
+            Property::FirewallSyncInterval => "firewallSyncInterval",
             Property::Flags => "flags",
             Property::FlagsAction => "flagsAction",
             Property::FlagsProtocol => "flagsProtocol",
+            Property::FolderPolicies => "folderPolicies",
             Property::ForDomain => "forDomain",
             Property::Format => "format",
+            Property::Forms => "forms",
             Property::From => "from",
             Property::FromAddress => "fromAddress",
             Property::FromEmail => "fromEmail",
@@ -1637,6 +1849,10 @@ impl EnumImpl for Property {
             Property::GroupId => "groupId",
             Property::HeaderFrom => "headerFrom",
             Property::Headers => "headers",
+            Property::HealthDegradedWeight => "healthDegradedWeight",
+            Property::HealthQueueThreshold => "healthQueueThreshold",
+            Property::HealthStoreTimeout => "healthStoreTimeout",
+            Property::HoldAuditLogFor => "holdAuditLogFor",
             Property::HoldMetricsFor => "holdMetricsFor",
             Property::HoldMtaReportsFor => "holdMtaReportsFor",
             Property::HoldSamplesFor => "holdSamplesFor",
@@ -1644,8 +1860,13 @@ impl EnumImpl for Property {
             Property::Host => "host",
             Property::HostedZoneId => "hostedZoneId",
             Property::Hostname => "hostname",
+            Property::Hostnames => "hostnames",
             Property::Hour => "hour",
+            Property::Http2MaxConcurrentStreams => "http2MaxConcurrentStreams",
             Property::HttpAuth => "httpAuth",
+            Property::HttpFreeBusyAccess => "httpFreeBusyAccess",
+            Property::HttpFreeBusyEnable => "httpFreeBusyEnable",
+            Property::HttpFreeBusyMaxRange => "httpFreeBusyMaxRange",
             Property::HttpHeaders => "httpHeaders",
             Property::HttpRsvpEnable => "httpRsvpEnable",
             Property::HttpRsvpLinkExpiry => "httpRsvpLinkExpiry",
@@ -1658,6 +1879,7 @@ impl EnumImpl for Property {
             Property::IdTokenExpiry => "idTokenExpiry",
             Property::IdentityAlignment => "identityAlignment",
             Property::If => "if",
+            Property::ImapUrl => "imapUrl",
             Property::ImpersonateServiceAccount => "impersonateServiceAccount",
             Property::ImplicitTls => "implicitTls",
             Property::InMemoryStore => "inMemoryStore",
@@ -1667,6 +1889,7 @@ impl EnumImpl for Property {
             Property::IncludeSource => "includeSource",
             Property::IndexAsn => "indexAsn",
             Property::IndexAsnName => "indexAsnName",
+            Property::IndexAttachmentContents => "indexAttachmentContents",
             Property::IndexBatchSize => "indexBatchSize",
             Property::IndexCalendar => "indexCalendar",
             Property::IndexCalendarFields => "indexCalendarFields",
@@ -1706,6 +1929,7 @@ impl EnumImpl for Property {
             Property::KeyName => "keyName",
             Property::KeyPrefix => "keyPrefix",
             Property::KeyValues => "keyValues",
+            Property::Keywords => "keywords",
             Property::L1Ratio => "l1Ratio",
             Property::L2Ratio => "l2Ratio",
             Property::LastRenewal => "lastRenewal",
@@ -1713,12 +1937,17 @@ impl EnumImpl for Property {
             Property::LearnHamFromReply => "learnHamFromReply",
             Property::LearnSpamFromRblHits => "learnSpamFromRblHits",
             Property::LearnSpamFromTraps => "learnSpamFromTraps",
+            Property::LegalHold => "legalHold",
             Property::Level => "level",
             Property::LicenseKey => "licenseKey",
             Property::ListenerIds => "listenerIds",
             Property::Listeners => "listeners",
             Property::LivePropertyMaxSize => "livePropertyMaxSize",
             Property::Locale => "locale",
+            Property::LoginAnomalySensitivity => "loginAnomalySensitivity",
+            Property::LoginAt => "loginAt",
+            Property::LoginReferral => "loginReferral",
+            Property::LoginTravelWindow => "loginTravelWindow",
             Property::Logo => "logo",
             Property::LogoUrl => "logoUrl",
             Property::LoiterBanPeriod => "loiterBanPeriod",
@@ -1729,6 +1958,8 @@ impl EnumImpl for Property {
             Property::MailFrom => "mailFrom",
             Property::MailFromTimeout => "mailFromTimeout",
             Property::MailRua => "mailRua",
+            Property::MailboxId => "mailboxId",
+            Property::Mailboxes => "mailboxes",
             Property::MailingLists => "mailingLists",
             Property::MaintenanceType => "maintenanceType",
             Property::ManagedZone => "managedZone",
@@ -1742,9 +1973,12 @@ impl EnumImpl for Property {
             Property::MaxAttempts => "maxAttempts",
             Property::MaxAttendees => "maxAttendees",
             Property::MaxAuthFailures => "maxAuthFailures",
+            Property::MaxBatchSize => "maxBatchSize",
+            Property::MaxBreakdownLabels => "maxBreakdownLabels",
             Property::MaxCalendars => "maxCalendars",
             Property::MaxChangesHistory => "maxChangesHistory",
             Property::MaxConcurrent => "maxConcurrent",
+            Property::MaxConcurrentCalls => "maxConcurrentCalls",
             Property::MaxConcurrentRequests => "maxConcurrentRequests",
             Property::MaxConcurrentUploads => "maxConcurrentUploads",
             Property::MaxConnections => "maxConnections",
@@ -1752,6 +1986,7 @@ impl EnumImpl for Property {
             Property::MaxCpuCycles => "maxCpuCycles",
             Property::MaxDelay => "maxDelay",
             Property::MaxDuration => "maxDuration",
+            Property::MaxEmailChangeHistory => "maxEmailChangeHistory",
             Property::MaxEntries => "maxEntries",
             Property::MaxEntrySize => "maxEntrySize",
             Property::MaxEventNotifications => "maxEventNotifications",
@@ -1783,6 +2018,8 @@ impl EnumImpl for Property {
             Property::MaxOutMessages => "maxOutMessages",
             Property::MaxParticipantIdentities => "maxParticipantIdentities",
             Property::MaxPublicKeys => "maxPublicKeys",
+            Property::MaxQueueWait => "maxQueueWait",
+            Property::MaxQueuedCalls => "maxQueuedCalls",
             Property::MaxReceivedHeaders => "maxReceivedHeaders",
             Property::MaxRecipients => "maxRecipients",
             Property::MaxReconnects => "maxReconnects",
@@ -1798,6 +2035,8 @@ impl EnumImpl for Property {
             Property::MaxScriptNameLength => "maxScriptNameLength",
             Property::MaxScriptSize => "maxScriptSize",
             Property::MaxScripts => "maxScripts",
+            Property::MaxSessionRecipients => "maxSessionRecipients",
+            Property::MaxShareLinks => "maxShareLinks",
             Property::MaxShares => "maxShares",
             Property::MaxSize => "maxSize",
             Property::MaxStringLength => "maxStringLength",
@@ -1839,6 +2078,8 @@ impl EnumImpl for Property {
             Property::NextNotify => "nextNotify",
             Property::NextRetry => "nextRetry",
             Property::NextTransitionAt => "nextTransitionAt",
+            Property::NftFamily => "nftFamily",
+            Property::NftTable => "nftTable",
             Property::NoCapabilityCheck => "noCapabilityCheck",
             Property::NoEcho => "noEcho",
             Property::NoSoliciting => "noSoliciting",
@@ -1851,6 +2092,7 @@ impl EnumImpl for Property {
             Property::NumFeatures => "numFeatures",
             Property::NumReplicas => "numReplicas",
             Property::NumShards => "numShards",
+            Property::OlderThan => "olderThan",
             Property::OnSuccessRenewCertificate => "onSuccessRenewCertificate",
             Property::OpenTelemetry => "openTelemetry",
             Property::Options => "options",
@@ -1861,6 +2103,7 @@ impl EnumImpl for Property {
             Property::OriginalEnvelopeId => "originalEnvelopeId",
             Property::OriginalMailFrom => "originalMailFrom",
             Property::OriginalRcptTo => "originalRcptTo",
+            Property::OtlpIngest => "otlpIngest",
             Property::OtpAuth => "otpAuth",
             Property::OtpCode => "otpCode",
             Property::OtpUrl => "otpUrl",
@@ -1879,6 +2122,8 @@ impl EnumImpl for Property {
             Property::PasswordMinLength => "passwordMinLength",
             Property::PasswordMinStrength => "passwordMinStrength",
             Property::Path => "path",
+            Property::Pattern => "pattern",
+            Property::PayloadTemplate => "payloadTemplate",
             Property::Period => "period",
             Property::Permissions => "permissions",
             Property::PingInterval => "pingInterval",
@@ -1909,6 +2154,7 @@ impl EnumImpl for Property {
             Property::Port => "port",
             Property::Prefix => "prefix",
             Property::PreserveIntermediates => "preserveIntermediates",
+            Property::PreviousCountry => "previousCountry",
             Property::Priority => "priority",
             Property::PrivateKey => "privateKey",
             Property::PrivateZone => "privateZone",
@@ -1919,11 +2165,19 @@ impl EnumImpl for Property {
             Property::Prompt => "prompt",
             Property::PropagationDelay => "propagationDelay",
             Property::PropagationTimeout => "propagationTimeout",
+            Property::Protect => "protect",
             Property::ProtectedHeaders => "protectedHeaders",
             Property::Protocol => "protocol",
             Property::ProtocolVersion => "protocolVersion",
             Property::ProviderInfo => "providerInfo",
+            Property::ProxyAllowInvalidCerts => "proxyAllowInvalidCerts",
+            Property::ProxyBackend => "proxyBackend",
+            Property::ProxyImapPort => "proxyImapPort",
+            Property::ProxyImplicitTls => "proxyImplicitTls",
+            Property::ProxyPop3Port => "proxyPop3Port",
             Property::ProxyTrustedNetworks => "proxyTrustedNetworks",
+            Property::PublicFolderName => "publicFolderName",
+            Property::PublicFolders => "publicFolders",
             Property::PublicKey => "publicKey",
             Property::PublishRecords => "publishRecords",
             Property::PushAttemptWait => "pushAttemptWait",
@@ -1945,8 +2199,10 @@ impl EnumImpl for Property {
             Property::RateLimit => "rateLimit",
             Property::RateLimitAnonymous => "rateLimitAnonymous",
             Property::RateLimitAuthenticated => "rateLimitAuthenticated",
+            Property::RateLimited => "rateLimited",
             Property::Ratio => "ratio",
             Property::RcptToTimeout => "rcptToTimeout",
+            Property::ReadCacheSize => "readCacheSize",
             Property::ReadFromReplicas => "readFromReplicas",
             Property::ReadReplicas => "readReplicas",
             Property::Reason => "reason",
@@ -1964,9 +2220,18 @@ impl EnumImpl for Property {
             Property::RefreshTokenExpiry => "refreshTokenExpiry",
             Property::RefreshTokenRenewal => "refreshTokenRenewal",
             Property::Region => "region",
+            Property::ReindexRate => "reindexRate",
             Property::RejectNonFqdn => "rejectNonFqdn",
+            Property::RelayVerify => "relayVerify",
+            Property::RelayVerifyCacheTtl => "relayVerifyCacheTtl",
+            Property::RelayVerifyHost => "relayVerifyHost",
+            Property::RelayVerifyUrl => "relayVerifyUrl",
             Property::RemoteIp => "remoteIp",
             Property::RenewBefore => "renewBefore",
+            Property::RepeatBanDecay => "repeatBanDecay",
+            Property::RepeatBanMaxPeriod => "repeatBanMaxPeriod",
+            Property::ReplaceScore => "replaceScore",
+            Property::Replacement => "replacement",
             Property::Report => "report",
             Property::ReportAddressUri => "reportAddressUri",
             Property::ReportId => "reportId",
@@ -1992,8 +2257,10 @@ impl EnumImpl for Property {
             Property::ResponsePosExplanation => "responsePosExplanation",
             Property::Result => "result",
             Property::ResultType => "resultType",
+            Property::RetentionPolicies => "retentionPolicies",
             Property::RetireAfter => "retireAfter",
             Property::Retry => "retry",
+            Property::RetryBackoff => "retryBackoff",
             Property::RetryCount => "retryCount",
             Property::RetryDue => "retryDue",
             Property::ReturnPath => "returnPath",
@@ -2012,6 +2279,8 @@ impl EnumImpl for Property {
             Property::ScanBanRate => "scanBanRate",
             Property::Schedule => "schedule",
             Property::Scheduling => "scheduling",
+            Property::SchedulingPolicy => "schedulingPolicy",
+            Property::SchedulingResource => "schedulingResource",
             Property::Scope => "scope",
             Property::Score => "score",
             Property::ScoreDiscard => "scoreDiscard",
@@ -2034,8 +2303,13 @@ impl EnumImpl for Property {
             Property::ServiceAccountJson => "serviceAccountJson",
             Property::Services => "services",
             Property::SessionToken => "sessionToken",
+            Property::SetIpv4 => "setIpv4",
+            Property::SetIpv6 => "setIpv6",
             Property::SetMaxObjects => "setMaxObjects",
             Property::ShardIndex => "shardIndex",
+            Property::ShareLinkEnable => "shareLinkEnable",
+            Property::ShareLinkMaxExpiry => "shareLinkMaxExpiry",
+            Property::SieveUrl => "sieveUrl",
             Property::Sig0Algorithm => "sig0Algorithm",
             Property::SignatureAlgorithm => "signatureAlgorithm",
             Property::SignatureKey => "signatureKey",
@@ -2073,12 +2347,22 @@ impl EnumImpl for Property {
             Property::Store => "store",
             Property::Stores => "stores",
             Property::Strategy => "strategy",
+            Property::StreamMaxAge => "streamMaxAge",
+            Property::StreamName => "streamName",
             Property::SubAddressing => "subAddressing",
+            Property::SubAddressingCreateFolders => "subAddressingCreateFolders",
+            Property::SubAddressingFolders => "subAddressingFolders",
             Property::Subject => "subject",
             Property::SubjectAlternativeNames => "subjectAlternativeNames",
             Property::Subscribe => "subscribe",
+            Property::SubscriptionMaxBackoff => "subscriptionMaxBackoff",
+            Property::SubscriptionMaxSize => "subscriptionMaxSize",
+            Property::SubscriptionRefreshInterval => "subscriptionRefreshInterval",
+            Property::SubscriptionTimeout => "subscriptionTimeout",
             Property::Sum => "sum",
             Property::Summary => "summary",
+            Property::Suppress => "suppress",
+            Property::SuppressListTraffic => "suppressListTraffic",
             Property::Tag => "tag",
             Property::Tags => "tags",
             Property::TaskTypes => "taskTypes",
@@ -2087,6 +2371,7 @@ impl EnumImpl for Property {
             Property::TempFailOnError => "tempFailOnError",
             Property::Temperature => "temperature",
             Property::Template => "template",
+            Property::TenantBreakdown => "tenantBreakdown",
             Property::TenantId => "tenantId",
             Property::Tenants => "tenants",
             Property::Text => "text",
@@ -2112,6 +2397,9 @@ impl EnumImpl for Property {
             Property::Timestamp => "timestamp",
             Property::Title => "title",
             Property::Tls => "tls",
+            Property::TlsClientAuth => "tlsClientAuth",
+            Property::TlsClientCaCertificates => "tlsClientCaCertificates",
+            Property::TlsClientCertPrincipal => "tlsClientCertPrincipal",
             Property::TlsDisableCipherSuites => "tlsDisableCipherSuites",
             Property::TlsDisableProtocols => "tlsDisableProtocols",
             Property::TlsIgnoreClientOrder => "tlsIgnoreClientOrder",
@@ -2124,10 +2412,14 @@ impl EnumImpl for Property {
             Property::TraceId => "traceId",
             Property::Tracer => "tracer",
             Property::TrainFrequency => "trainFrequency",
+            Property::TrainRateLimit => "trainRateLimit",
             Property::TransactionRetryDelay => "transactionRetryDelay",
             Property::TransactionRetryLimit => "transactionRetryLimit",
             Property::TransactionTimeout => "transactionTimeout",
             Property::TransferLimit => "transferLimit",
+            Property::TrapBlockIp => "trapBlockIp",
+            Property::TrapPenaltyFor => "trapPenaltyFor",
+            Property::TrapPenaltyScore => "trapPenaltyScore",
             Property::TrustContacts => "trustContacts",
             Property::TrustReplies => "trustReplies",
             Property::TsigAlgorithm => "tsigAlgorithm",
@@ -2140,6 +2432,7 @@ impl EnumImpl for Property {
             Property::UrlLimit => "urlLimit",
             Property::UrlPrefix => "urlPrefix",
             Property::Urls => "urls",
+            Property::UseJetStream => "useJetStream",
             Property::UsePermissiveCors => "usePermissiveCors",
             Property::UseTls => "useTls",
             Property::UseXForwarded => "useXForwarded",
@@ -2157,6 +2450,7 @@ impl EnumImpl for Property {
             Property::WebsocketHeartbeat => "websocketHeartbeat",
             Property::WebsocketThrottle => "websocketThrottle",
             Property::WebsocketTimeout => "websocketTimeout",
+            Property::Weight => "weight",
             Property::Zone => "zone",
             Property::ZoneIpV4 => "zoneIpV4",
             Property::ZoneIpV6 => "zoneIpV6",
@@ -2194,6 +2488,7 @@ impl EnumImpl for Property {
             560 => Some(Property::AddReturnPathHeader),
             838 => Some(Property::AdditionalInformation),
             44 => Some(Property::Address),
+            949 => Some(Property::AddressRewrites),
             579 => Some(Property::Addresses),
             268 => Some(Property::AggregateContactInfo),
             274 => Some(Property::AggregateDkimSignDomain),
@@ -2213,10 +2508,12 @@ impl EnumImpl for Property {
             424 => Some(Property::AllowPlainTextAuth),
             348 => Some(Property::AllowRelaying),
             369 => Some(Property::AllowSpamTraining),
+            994 => Some(Property::AllowUnsubscribe),
             398 => Some(Property::AllowedEndpoints),
             49 => Some(Property::AllowedIps),
             712 => Some(Property::AllowedNotifyUris),
             388 => Some(Property::Alpha),
+            996 => Some(Property::AnomalyType),
             614 => Some(Property::AnonymousClientRegistration),
             858 => Some(Property::Ansi),
             325 => Some(Property::ApiKey),
@@ -2226,6 +2523,7 @@ impl EnumImpl for Property {
             690 => Some(Property::ArcVerify),
             203 => Some(Property::ArchiveDeletedAccountsFor),
             202 => Some(Property::ArchiveDeletedItemsFor),
+            933 => Some(Property::ArchiveMailbox),
             58 => Some(Property::ArchivedAt),
             820 => Some(Property::ArchivedItemType),
             59 => Some(Property::ArchivedUntil),
@@ -2237,7 +2535,9 @@ impl EnumImpl for Property {
             471 => Some(Property::AttrDescription),
             472 => Some(Property::AttrEmail),
             473 => Some(Property::AttrEmailAlias),
+            974 => Some(Property::AttrLoginReferral),
             474 => Some(Property::AttrMemberOf),
+            967 => Some(Property::AttrProxyBackend),
             475 => Some(Property::AttrSecret),
             476 => Some(Property::AttrSecretChanged),
             215 => Some(Property::Auid),
@@ -2252,6 +2552,7 @@ impl EnumImpl for Property {
             740 => Some(Property::AuthenticatedAs),
             69 => Some(Property::AuthenticationResults),
             171 => Some(Property::AutoAddInvitations),
+            945 => Some(Property::AutoBcc),
             53 => Some(Property::AutoUpdateFrequency),
             463 => Some(Property::BaseDn),
             403 => Some(Property::BearerToken),
@@ -2265,15 +2566,22 @@ impl EnumImpl for Property {
             655 => Some(Property::BlobSize),
             126 => Some(Property::BlobStore),
             766 => Some(Property::BlockCount),
+            995 => Some(Property::BlockedTlsFingerprints),
             38 => Some(Property::Body),
             459 => Some(Property::Brokers),
             658 => Some(Property::Bucket),
             656 => Some(Property::BufferSize),
             863 => Some(Property::Buffered),
+            980 => Some(Property::CacheMaxAge),
+            983 => Some(Property::CaldavUrl),
+            894 => Some(Property::CalendarAutoImport),
             216 => Some(Property::Canonicalization),
             584 => Some(Property::CapacityClient),
             585 => Some(Property::CapacityReadBuffer),
             586 => Some(Property::CapacitySubscription),
+            976 => Some(Property::CaptchaProvider),
+            977 => Some(Property::CaptchaSecret),
+            984 => Some(Property::CarddavUrl),
             346 => Some(Property::CatchAllAddress),
             759 => Some(Property::Categories),
             176 => Some(Property::Certificate),
@@ -2285,14 +2593,21 @@ impl EnumImpl for Property {
             611 => Some(Property::ClaimName),
             609 => Some(Property::ClaimUsername),
             693 => Some(Property::Cleartext),
+            934 => Some(Property::CleartextPort),
             604 => Some(Property::ClientId),
             382 => Some(Property::ClusterFile),
+            883 => Some(Property::Collection),
+            884 => Some(Property::CollectionId),
             781 => Some(Property::ColumnClass),
             782 => Some(Property::ColumnDescription),
             779 => Some(Property::ColumnEmail),
+            975 => Some(Property::ColumnLoginReferral),
+            968 => Some(Property::ColumnProxyBackend),
             780 => Some(Property::ColumnSecret),
             240 => Some(Property::Comment),
             359 => Some(Property::CompressionAlgorithm),
+            910 => Some(Property::CompressionContentTypes),
+            909 => Some(Property::CompressionMinSize),
             304 => Some(Property::Concurrency),
             34 => Some(Property::Condition),
             760 => Some(Property::Confidence),
@@ -2307,7 +2622,13 @@ impl EnumImpl for Property {
             65 => Some(Property::Content),
             758 => Some(Property::ContentTypes),
             708 => Some(Property::Contents),
+            914 => Some(Property::CorsAllowCredentials),
+            913 => Some(Property::CorsAllowedHeaders),
+            912 => Some(Property::CorsAllowedMethods),
+            911 => Some(Property::CorsAllowedOrigins),
+            915 => Some(Property::CorsMaxAge),
             258 => Some(Property::Count),
+            997 => Some(Property::Country),
             367 => Some(Property::Create),
             46 => Some(Property::CreatedAt),
             486 => Some(Property::CreatedBy),
@@ -2327,7 +2648,9 @@ impl EnumImpl for Property {
             246 => Some(Property::DateRangeEnd),
             845 => Some(Property::DateRangeStart),
             192 => Some(Property::Day),
+            898 => Some(Property::DeadLetterUrl),
             868 => Some(Property::DeadPropertyMaxSize),
+            919 => Some(Property::DefaultAcmeProviderId),
             108 => Some(Property::DefaultAdminRoleIds),
             790 => Some(Property::DefaultCertificateId),
             20 => Some(Property::DefaultDisplayName),
@@ -2350,6 +2673,8 @@ impl EnumImpl for Property {
             105 => Some(Property::DefaultUserRoleIds),
             235 => Some(Property::Definition),
             825 => Some(Property::Delay),
+            963 => Some(Property::DelaySubject),
+            964 => Some(Property::DelayTemplate),
             229 => Some(Property::DeleteAfter),
             777 => Some(Property::DeleteAfterUse),
             238 => Some(Property::DeliverAt),
@@ -2360,11 +2685,23 @@ impl EnumImpl for Property {
             6 => Some(Property::Description),
             297 => Some(Property::Details),
             12 => Some(Property::Directory),
+            890 => Some(Property::DirectoryDisplayName),
+            888 => Some(Property::DirectoryEnable),
+            893 => Some(Property::DirectoryFields),
+            889 => Some(Property::DirectoryHrefName),
             104 => Some(Property::DirectoryId),
+            892 => Some(Property::DirectoryRefreshInterval),
+            891 => Some(Property::DirectoryScope),
             711 => Some(Property::DisableCapabilities),
+            955 => Some(Property::DisableFor),
             666 => Some(Property::DisableLanguages),
+            935 => Some(Property::DisabledJmapCapabilities),
             629 => Some(Property::DisabledPermissions),
             872 => Some(Property::DiscardAfter),
+            942 => Some(Property::DisclaimerHtml),
+            943 => Some(Property::DisclaimerInbound),
+            946 => Some(Property::DisclaimerText),
+            944 => Some(Property::DisclaimerWrapSigned),
             747 => Some(Property::Disposition),
             83 => Some(Property::DkimAdspDns),
             84 => Some(Property::DkimCanonicalizedBody),
@@ -2373,6 +2710,7 @@ impl EnumImpl for Property {
             87 => Some(Property::DkimIdentity),
             343 => Some(Property::DkimManagement),
             291 => Some(Property::DkimPass),
+            965 => Some(Property::DkimResignDomain),
             266 => Some(Property::DkimResults),
             88 => Some(Property::DkimSelector),
             89 => Some(Property::DkimSelectorDns),
@@ -2399,6 +2737,7 @@ impl EnumImpl for Property {
             804 => Some(Property::DocumentId),
             814 => Some(Property::DocumentType),
             232 => Some(Property::Domain),
+            900 => Some(Property::DomainBreakdown),
             221 => Some(Property::DomainId),
             750 => Some(Property::DomainLimit),
             147 => Some(Property::DomainNames),
@@ -2406,7 +2745,9 @@ impl EnumImpl for Property {
             146 => Some(Property::Domains),
             519 => Some(Property::Dsn),
             797 => Some(Property::Due),
+            990 => Some(Property::DuplicateAction),
             699 => Some(Property::DuplicateExpiry),
+            991 => Some(Property::DuplicateWindow),
             515 => Some(Property::Duration),
             13 => Some(Property::EabHmacKey),
             14 => Some(Property::EabKeyId),
@@ -2423,11 +2764,15 @@ impl EnumImpl for Property {
             488 => Some(Property::EmailDomain),
             751 => Some(Property::EmailLimit),
             487 => Some(Property::EmailPrefix),
+            939 => Some(Property::EmailQueries),
             174 => Some(Property::EmailTemplate),
             37 => Some(Property::Enable),
             865 => Some(Property::EnableAssistedDiscovery),
+            908 => Some(Property::EnableCompression),
             305 => Some(Property::EnableEdns),
+            906 => Some(Property::EnableH2c),
             399 => Some(Property::EnableHsts),
+            905 => Some(Property::EnableHttp2),
             860 => Some(Property::EnableLogExporter),
             562 => Some(Property::EnableSpamFilter),
             861 => Some(Property::EnableSpanExporter),
@@ -2437,7 +2782,9 @@ impl EnumImpl for Property {
             357 => Some(Property::EncryptOnAppend),
             9 => Some(Property::EncryptionAtRest),
             622 => Some(Property::EncryptionKey),
+            993 => Some(Property::EncryptionPolicy),
             499 => Some(Property::Endpoint),
+            957 => Some(Property::EnforceQuota),
             742 => Some(Property::EnvFrom),
             743 => Some(Property::EnvFromParameters),
             639 => Some(Property::EnvId),
@@ -2462,6 +2809,7 @@ impl EnumImpl for Property {
             802 => Some(Property::EventStartTz),
             142 => Some(Property::Events),
             855 => Some(Property::EventsPolicy),
+            932 => Some(Property::ExcludeKeywords),
             217 => Some(Property::Expire),
             100 => Some(Property::Expires),
             47 => Some(Property::ExpiresAt),
@@ -2476,6 +2824,7 @@ impl EnumImpl for Property {
             754 => Some(Property::Extension),
             257 => Some(Property::Extensions),
             243 => Some(Property::ExtraContactInfo),
+            904 => Some(Property::Facility),
             821 => Some(Property::Factor),
             490 => Some(Property::FailOnTimeout),
             826 => Some(Property::FailedAt),
@@ -2501,11 +2850,15 @@ impl EnumImpl for Property {
             467 => Some(Property::FilterLogin),
             468 => Some(Property::FilterMailbox),
             469 => Some(Property::FilterMemberOf),
+            1005 => Some(Property::FirewallSync),
+            1006 => Some(Property::FirewallSyncInterval),
             638 => Some(Property::Flags),
             537 => Some(Property::FlagsAction),
             538 => Some(Property::FlagsProtocol),
+            986 => Some(Property::FolderPolicies),
             485 => Some(Property::ForDomain),
             415 => Some(Property::Format),
+            978 => Some(Property::Forms),
             62 => Some(Property::From),
             39 => Some(Property::FromAddress),
             165 => Some(Property::FromEmail),
@@ -2520,6 +2873,10 @@ impl EnumImpl for Property {
             460 => Some(Property::GroupId),
             265 => Some(Property::HeaderFrom),
             93 => Some(Property::Headers),
+            1011 => Some(Property::HealthDegradedWeight),
+            1012 => Some(Property::HealthQueueThreshold),
+            1013 => Some(Property::HealthStoreTimeout),
+            896 => Some(Property::HoldAuditLogFor),
             206 => Some(Property::HoldMetricsFor),
             204 => Some(Property::HoldMtaReportsFor),
             730 => Some(Property::HoldSamplesFor),
@@ -2527,8 +2884,13 @@ impl EnumImpl for Property {
             333 => Some(Property::Host),
             331 => Some(Property::HostedZoneId),
             185 => Some(Property::Hostname),
+            979 => Some(Property::Hostnames),
             190 => Some(Property::Hour),
+            907 => Some(Property::Http2MaxConcurrentStreams),
             32 => Some(Property::HttpAuth),
+            877 => Some(Property::HttpFreeBusyAccess),
+            876 => Some(Property::HttpFreeBusyEnable),
+            878 => Some(Property::HttpFreeBusyMaxRange),
             33 => Some(Property::HttpHeaders),
             168 => Some(Property::HttpRsvpEnable),
             169 => Some(Property::HttpRsvpLinkExpiry),
@@ -2541,6 +2903,7 @@ impl EnumImpl for Property {
             621 => Some(Property::IdTokenExpiry),
             91 => Some(Property::IdentityAlignment),
             376 => Some(Property::If),
+            981 => Some(Property::ImapUrl),
             320 => Some(Property::ImpersonateServiceAccount),
             546 => Some(Property::ImplicitTls),
             128 => Some(Property::InMemoryStore),
@@ -2550,6 +2913,7 @@ impl EnumImpl for Property {
             352 => Some(Property::IncludeSource),
             94 => Some(Property::IndexAsn),
             95 => Some(Property::IndexAsnName),
+            926 => Some(Property::IndexAttachmentContents),
             664 => Some(Property::IndexBatchSize),
             667 => Some(Property::IndexCalendar),
             668 => Some(Property::IndexCalendarFields),
@@ -2589,6 +2953,7 @@ impl EnumImpl for Property {
             337 => Some(Property::KeyName),
             120 => Some(Property::KeyPrefix),
             853 => Some(Property::KeyValues),
+            931 => Some(Property::Keywords),
             391 => Some(Property::L1Ratio),
             392 => Some(Property::L2Ratio),
             186 => Some(Property::LastRenewal),
@@ -2596,12 +2961,17 @@ impl EnumImpl for Property {
             735 => Some(Property::LearnHamFromReply),
             728 => Some(Property::LearnSpamFromRblHits),
             729 => Some(Property::LearnSpamFromTraps),
+            927 => Some(Property::LegalHold),
             373 => Some(Property::Level),
             370 => Some(Property::LicenseKey),
             183 => Some(Property::ListenerIds),
             188 => Some(Property::Listeners),
             869 => Some(Property::LivePropertyMaxSize),
             7 => Some(Property::Locale),
+            998 => Some(Property::LoginAnomalySensitivity),
+            999 => Some(Property::LoginAt),
+            973 => Some(Property::LoginReferral),
+            1000 => Some(Property::LoginTravelWindow),
             341 => Some(Property::Logo),
             371 => Some(Property::LogoUrl),
             682 => Some(Property::LoiterBanPeriod),
@@ -2612,6 +2982,8 @@ impl EnumImpl for Property {
             284 => Some(Property::MailFrom),
             509 => Some(Property::MailFromTimeout),
             841 => Some(Property::MailRua),
+            895 => Some(Property::MailboxId),
+            930 => Some(Property::Mailboxes),
             154 => Some(Property::MailingLists),
             796 => Some(Property::MaintenanceType),
             318 => Some(Property::ManagedZone),
@@ -2625,9 +2997,12 @@ impl EnumImpl for Property {
             511 => Some(Property::MaxAttempts),
             157 => Some(Property::MaxAttendees),
             425 => Some(Property::MaxAuthFailures),
+            897 => Some(Property::MaxBatchSize),
+            902 => Some(Property::MaxBreakdownLabels),
             160 => Some(Property::MaxCalendars),
             201 => Some(Property::MaxChangesHistory),
             426 => Some(Property::MaxConcurrent),
+            936 => Some(Property::MaxConcurrentCalls),
             439 => Some(Property::MaxConcurrentRequests),
             442 => Some(Property::MaxConcurrentUploads),
             603 => Some(Property::MaxConnections),
@@ -2635,6 +3010,7 @@ impl EnumImpl for Property {
             702 => Some(Property::MaxCpuCycles),
             823 => Some(Property::MaxDelay),
             530 => Some(Property::MaxDuration),
+            989 => Some(Property::MaxEmailChangeHistory),
             417 => Some(Property::MaxEntries),
             418 => Some(Property::MaxEntrySize),
             163 => Some(Property::MaxEventNotifications),
@@ -2666,6 +3042,8 @@ impl EnumImpl for Property {
             704 => Some(Property::MaxOutMessages),
             162 => Some(Property::MaxParticipantIdentities),
             366 => Some(Property::MaxPublicKeys),
+            938 => Some(Property::MaxQueueWait),
+            937 => Some(Property::MaxQueuedCalls),
             561 => Some(Property::MaxReceivedHeaders),
             173 => Some(Property::MaxRecipients),
             580 => Some(Property::MaxReconnects),
@@ -2681,6 +3059,8 @@ impl EnumImpl for Property {
             719 => Some(Property::MaxScriptNameLength),
             723 => Some(Property::MaxScriptSize),
             726 => Some(Property::MaxScripts),
+            956 => Some(Property::MaxSessionRecipients),
+            887 => Some(Property::MaxShareLinks),
             696 => Some(Property::MaxShares),
             101 => Some(Property::MaxSize),
             724 => Some(Property::MaxStringLength),
@@ -2722,6 +3102,8 @@ impl EnumImpl for Property {
             634 => Some(Property::NextNotify),
             633 => Some(Property::NextRetry),
             223 => Some(Property::NextTransitionAt),
+            1007 => Some(Property::NftFamily),
+            1008 => Some(Property::NftTable),
             700 => Some(Property::NoCapabilityCheck),
             587 => Some(Property::NoEcho),
             523 => Some(Property::NoSoliciting),
@@ -2734,6 +3116,7 @@ impl EnumImpl for Property {
             390 => Some(Property::NumFeatures),
             350 => Some(Property::NumReplicas),
             351 => Some(Property::NumShards),
+            929 => Some(Property::OlderThan),
             813 => Some(Property::OnSuccessRenewCertificate),
             495 => Some(Property::OpenTelemetry),
             630 => Some(Property::Options),
@@ -2744,6 +3127,7 @@ impl EnumImpl for Property {
             71 => Some(Property::OriginalEnvelopeId),
             72 => Some(Property::OriginalMailFrom),
             73 => Some(Property::OriginalRcptTo),
+            903 => Some(Property::OtlpIngest),
             5 => Some(Property::OtpAuth),
             625 => Some(Property::OtpCode),
             626 => Some(Property::OtpUrl),
@@ -2762,6 +3146,8 @@ impl EnumImpl for Property {
             110 => Some(Property::PasswordMinLength),
             112 => Some(Property::PasswordMinStrength),
             380 => Some(Property::Path),
+            947 => Some(Property::Pattern),
+            899 => Some(Property::PayloadTemplate),
             646 => Some(Property::Period),
             48 => Some(Property::Permissions),
             583 => Some(Property::PingInterval),
@@ -2792,6 +3178,7 @@ impl EnumImpl for Property {
             299 => Some(Property::Port),
             856 => Some(Property::Prefix),
             306 => Some(Property::PreserveIntermediates),
+            1001 => Some(Property::PreviousCountry),
             483 => Some(Property::Priority),
             177 => Some(Property::PrivateKey),
             319 => Some(Property::PrivateZone),
@@ -2802,11 +3189,19 @@ impl EnumImpl for Property {
             765 => Some(Property::Prompt),
             313 => Some(Property::PropagationDelay),
             312 => Some(Property::PropagationTimeout),
+            985 => Some(Property::Protect),
             713 => Some(Property::ProtectedHeaders),
             298 => Some(Property::Protocol),
             533 => Some(Property::ProtocolVersion),
             795 => Some(Property::ProviderInfo),
+            972 => Some(Property::ProxyAllowInvalidCerts),
+            966 => Some(Property::ProxyBackend),
+            969 => Some(Property::ProxyImapPort),
+            971 => Some(Property::ProxyImplicitTls),
+            970 => Some(Property::ProxyPop3Port),
             792 => Some(Property::ProxyTrustedNetworks),
+            988 => Some(Property::PublicFolderName),
+            987 => Some(Property::PublicFolders),
             218 => Some(Property::PublicKey),
             302 => Some(Property::PublishRecords),
             448 => Some(Property::PushAttemptWait),
@@ -2828,8 +3223,10 @@ impl EnumImpl for Property {
             410 => Some(Property::RateLimit),
             397 => Some(Property::RateLimitAnonymous),
             396 => Some(Property::RateLimitAuthenticated),
+            925 => Some(Property::RateLimited),
             767 => Some(Property::Ratio),
             510 => Some(Property::RcptToTimeout),
+            940 => Some(Property::ReadCacheSize),
             650 => Some(Property::ReadFromReplicas),
             578 => Some(Property::ReadReplicas),
             45 => Some(Property::Reason),
@@ -2847,9 +3244,18 @@ impl EnumImpl for Property {
             617 => Some(Property::RefreshTokenExpiry),
             618 => Some(Property::RefreshTokenRenewal),
             330 => Some(Property::Region),
+            941 => Some(Property::ReindexRate),
             563 => Some(Property::RejectNonFqdn),
+            958 => Some(Property::RelayVerify),
+            961 => Some(Property::RelayVerifyCacheTtl),
+            959 => Some(Property::RelayVerifyHost),
+            960 => Some(Property::RelayVerifyUrl),
             282 => Some(Property::RemoteIp),
             17 => Some(Property::RenewBefore),
+            921 => Some(Property::RepeatBanDecay),
+            920 => Some(Property::RepeatBanMaxPeriod),
+            952 => Some(Property::ReplaceScore),
+            948 => Some(Property::Replacement),
             66 => Some(Property::Report),
             349 => Some(Property::ReportAddressUri),
             244 => Some(Property::ReportId),
@@ -2875,8 +3281,10 @@ impl EnumImpl for Property {
             763 => Some(Property::ResponsePosExplanation),
             233 => Some(Property::Result),
             832 => Some(Property::ResultType),
+            928 => Some(Property::RetentionPolicies),
             228 => Some(Property::RetireAfter),
             420 => Some(Property::Retry),
+            1014 => Some(Property::RetryBackoff),
             640 => Some(Property::RetryCount),
             641 => Some(Property::RetryDue),
             635 => Some(Property::ReturnPath),
@@ -2895,6 +3303,8 @@ impl EnumImpl for Property {
             684 => Some(Property::ScanBanRate),
             541 => Some(Property::Schedule),
             143 => Some(Property::Scheduling),
+            875 => Some(Property::SchedulingPolicy),
+            874 => Some(Property::SchedulingResource),
             281 => Some(Property::Scope),
             745 => Some(Property::Score),
             771 => Some(Property::ScoreDiscard),
@@ -2917,8 +3327,13 @@ impl EnumImpl for Property {
             316 => Some(Property::ServiceAccountJson),
             794 => Some(Property::Services),
             329 => Some(Property::SessionToken),
+            1009 => Some(Property::SetIpv4),
+            1010 => Some(Property::SetIpv6),
             440 => Some(Property::SetMaxObjects),
             830 => Some(Property::ShardIndex),
+            885 => Some(Property::ShareLinkEnable),
+            886 => Some(Property::ShareLinkMaxExpiry),
+            982 => Some(Property::SieveUrl),
             336 => Some(Property::Sig0Algorithm),
             623 => Some(Property::SignatureAlgorithm),
             624 => Some(Property::SignatureKey),
@@ -2956,12 +3371,22 @@ impl EnumImpl for Property {
             778 => Some(Property::Store),
             694 => Some(Property::Stores),
             816 => Some(Property::Strategy),
+            924 => Some(Property::StreamMaxAge),
+            923 => Some(Property::StreamName),
             347 => Some(Property::SubAddressing),
+            951 => Some(Property::SubAddressingCreateFolders),
+            950 => Some(Property::SubAddressingFolders),
             41 => Some(Property::Subject),
             178 => Some(Property::SubjectAlternativeNames),
             368 => Some(Property::Subscribe),
+            880 => Some(Property::SubscriptionMaxBackoff),
+            881 => Some(Property::SubscriptionMaxSize),
+            879 => Some(Property::SubscriptionRefreshInterval),
+            882 => Some(Property::SubscriptionTimeout),
             494 => Some(Property::Sum),
             808 => Some(Property::Summary),
+            962 => Some(Property::Suppress),
+            992 => Some(Property::SuppressListTraffic),
             748 => Some(Property::Tag),
             746 => Some(Property::Tags),
             189 => Some(Property::TaskTypes),
@@ -2970,6 +3395,7 @@ impl EnumImpl for Property {
             528 => Some(Property::TempFailOnError),
             27 => Some(Property::Temperature),
             167 => Some(Property::Template),
+            901 => Some(Property::TenantBreakdown),
             831 => Some(Property::TenantId),
             153 => Some(Property::Tenants),
             2 => Some(Property::Text),
@@ -2995,6 +3421,9 @@ impl EnumImpl for Property {
             482 => Some(Property::Timestamp),
             55 => Some(Property::Title),
             542 => Some(Property::Tls),
+            916 => Some(Property::TlsClientAuth),
+            917 => Some(Property::TlsClientCaCertificates),
+            918 => Some(Property::TlsClientCertPrincipal),
             599 => Some(Property::TlsDisableCipherSuites),
             600 => Some(Property::TlsDisableProtocols),
             601 => Some(Property::TlsIgnoreClientOrder),
@@ -3007,10 +3436,14 @@ impl EnumImpl for Property {
             815 => Some(Property::TraceId),
             129 => Some(Property::Tracer),
             734 => Some(Property::TrainFrequency),
+            953 => Some(Property::TrainRateLimit),
             385 => Some(Property::TransactionRetryDelay),
             386 => Some(Property::TransactionRetryLimit),
             387 => Some(Property::TransactionTimeout),
             531 => Some(Property::TransferLimit),
+            1002 => Some(Property::TrapBlockIp),
+            1003 => Some(Property::TrapPenaltyFor),
+            1004 => Some(Property::TrapPenaltyScore),
             769 => Some(Property::TrustContacts),
             774 => Some(Property::TrustReplies),
             338 => Some(Property::TsigAlgorithm),
@@ -3023,6 +3456,7 @@ impl EnumImpl for Property {
             753 => Some(Property::UrlLimit),
             52 => Some(Property::UrlPrefix),
             647 => Some(Property::Urls),
+            922 => Some(Property::UseJetStream),
             400 => Some(Property::UsePermissiveCors),
             309 => Some(Property::UseTls),
             402 => Some(Property::UseXForwarded),
@@ -3040,6 +3474,7 @@ impl EnumImpl for Property {
             455 => Some(Property::WebsocketHeartbeat),
             456 => Some(Property::WebsocketThrottle),
             457 => Some(Property::WebsocketTimeout),
+            954 => Some(Property::Weight),
             749 => Some(Property::Zone),
             98 => Some(Property::ZoneIpV4),
             99 => Some(Property::ZoneIpV6),
@@ -3153,6 +3588,7 @@ impl ObjectType {
             ObjectType::OAuthClient => OAuthClient::FLAGS,
             ObjectType::OidcProvider => OidcProvider::FLAGS,
             ObjectType::PublicKey => PublicKey::FLAGS,
+            ObjectType::QueueStore => QueueStore::FLAGS,
             ObjectType::QueuedMessage => QueuedMessage::FLAGS,
             ObjectType::ReportSettings => ReportSettings::FLAGS,
             ObjectType::Role => Role::FLAGS,
@@ -3160,6 +3596,7 @@ impl ObjectType {
             ObjectType::SearchStore => SearchStore::FLAGS,
             ObjectType::Security => Security::FLAGS,
             ObjectType::SenderAuth => SenderAuth::FLAGS,
+            ObjectType::ShareLink => ShareLink::FLAGS,
             ObjectType::Sharing => Sharing::FLAGS,
             ObjectType::SieveSystemInterpreter => SieveSystemInterpreter::FLAGS,
             ObjectType::SieveSystemScript => SieveSystemScript::FLAGS,
@@ -3171,12 +3608,14 @@ impl ObjectType {
             ObjectType::SpamFileExtension => SpamFileExtension::FLAGS,
             ObjectType::SpamLlm => SpamLlm::FLAGS,
             ObjectType::SpamPyzor => SpamPyzor::FLAGS,
+            ObjectType::SpamRspamd => SpamRspamd::FLAGS,
             ObjectType::SpamRule => SpamRule::FLAGS,
             ObjectType::SpamSettings => SpamSettings::FLAGS,
             ObjectType::SpamTag => SpamTag::FLAGS,
             ObjectType::SpamTrainingSample => SpamTrainingSample::FLAGS,
             ObjectType::SpfReportSettings => SpfReportSettings::FLAGS,
             ObjectType::StoreLookup => StoreLookup::FLAGS,
+            ObjectType::SuppressedAddress => SuppressedAddress::FLAGS,
             ObjectType::SystemSettings => SystemSettings::FLAGS,
             ObjectType::Task => Task::FLAGS,
             ObjectType::TaskManager => TaskManager::FLAGS,
@@ -3393,6 +3832,11 @@ impl ObjectType {
                     IndexSchemaValueType::Id,
                 ),
             ],
+            ObjectType::ShareLink => vec![IndexSchema::new(
+                Property::AccountId,
+                IndexSchemaType::Search,
+                IndexSchemaValueType::Id,
+            )],
             ObjectType::SieveSystemScript => vec![IndexSchema::new(
                 Property::Name,
                 IndexSchemaType::Unique,
@@ -3428,6 +3872,18 @@ impl ObjectType {
                 IndexSchemaType::Search,
                 IndexSchemaValueType::Id,
             )],
+            ObjectType::SuppressedAddress => vec![
+                IndexSchema::new(
+                    Property::Email,
+                    IndexSchemaType::Search,
+                    IndexSchemaValueType::Keyword,
+                ),
+                IndexSchema::new(
+                    Property::MemberTenantId,
+                    IndexSchemaType::Search,
+                    IndexSchemaValueType::Id,
+                ),
+            ],
             ObjectType::Tenant => vec![IndexSchema::new(
                 Property::Text,
                 IndexSchemaType::Search,
@@ -3520,6 +3976,7 @@ impl ObjectType {
             ObjectType::OAuthClient => Permission::SysOAuthClientGet,
             ObjectType::OidcProvider => Permission::SysOidcProviderGet,
             ObjectType::PublicKey => Permission::SysPublicKeyGet,
+            ObjectType::QueueStore => Permission::SysQueueStoreGet,
             ObjectType::QueuedMessage => Permission::SysQueuedMessageGet,
             ObjectType::ReportSettings => Permission::SysReportSettingsGet,
             ObjectType::Role => Permission::SysRoleGet,
@@ -3527,6 +3984,7 @@ impl ObjectType {
             ObjectType::SearchStore => Permission::SysSearchStoreGet,
             ObjectType::Security => Permission::SysSecurityGet,
             ObjectType::SenderAuth => Permission::SysSenderAuthGet,
+            ObjectType::ShareLink => Permission::SysShareLinkGet,
             ObjectType::Sharing => Permission::SysSharingGet,
             ObjectType::SieveSystemInterpreter => Permission::SysSieveSystemInterpreterGet,
             ObjectType::SieveSystemScript => Permission::SysSieveSystemScriptGet,
@@ -3538,12 +3996,14 @@ impl ObjectType {
             ObjectType::SpamFileExtension => Permission::SysSpamFileExtensionGet,
             ObjectType::SpamLlm => Permission::SysSpamLlmGet,
             ObjectType::SpamPyzor => Permission::SysSpamPyzorGet,
+            ObjectType::SpamRspamd => Permission::SysSpamRspamdGet,
             ObjectType::SpamRule => Permission::SysSpamRuleGet,
             ObjectType::SpamSettings => Permission::SysSpamSettingsGet,
             ObjectType::SpamTag => Permission::SysSpamTagGet,
             ObjectType::SpamTrainingSample => Permission::SysSpamTrainingSampleGet,
             ObjectType::SpfReportSettings => Permission::SysSpfReportSettingsGet,
             ObjectType::StoreLookup => Permission::SysStoreLookupGet,
+            ObjectType::SuppressedAddress => Permission::SysSuppressedAddressGet,
             ObjectType::SystemSettings => Permission::SysSystemSettingsGet,
             ObjectType::Task => Permission::SysTaskGet,
             ObjectType::TaskManager => Permission::SysTaskManagerGet,
@@ -3605,6 +4065,7 @@ impl ObjectType {
             ObjectType::PublicKey => Permission::SysPublicKeyQuery,
             ObjectType::QueuedMessage => Permission::SysQueuedMessageQuery,
             ObjectType::Role => Permission::SysRoleQuery,
+            ObjectType::ShareLink => Permission::SysShareLinkQuery,
             ObjectType::SieveSystemScript => Permission::SysSieveSystemScriptQuery,
             ObjectType::SieveUserScript => Permission::SysSieveUserScriptQuery,
             ObjectType::SpamDnsblServer => Permission::SysSpamDnsblServerQuery,
@@ -3613,6 +4074,7 @@ impl ObjectType {
             ObjectType::SpamTag => Permission::SysSpamTagQuery,
             ObjectType::SpamTrainingSample => Permission::SysSpamTrainingSampleQuery,
             ObjectType::StoreLookup => Permission::SysStoreLookupQuery,
+            ObjectType::SuppressedAddress => Permission::SysSuppressedAddressQuery,
             ObjectType::Task => Permission::SysTaskQuery,
             ObjectType::Tenant => Permission::SysTenantQuery,
             ObjectType::TlsExternalReport => Permission::SysTlsExternalReportQuery,
@@ -4031,6 +4493,11 @@ impl ObjectType {
                 Permission::SysPublicKeyUpdate,
                 Permission::SysPublicKeyDestroy,
             ],
+            ObjectType::QueueStore => [
+                Permission::SysQueueStoreUpdate,
+                Permission::SysQueueStoreUpdate,
+                Permission::SysQueueStoreUpdate,
+            ],
             ObjectType::QueuedMessage => [
                 Permission::SysQueuedMessageCreate,
                 Permission::SysQueuedMessageUpdate,
@@ -4066,6 +4533,11 @@ impl ObjectType {
                 Permission::SysSenderAuthUpdate,
                 Permission::SysSenderAuthUpdate,
             ],
+            ObjectType::ShareLink => [
+                Permission::SysShareLinkCreate,
+                Permission::SysShareLinkUpdate,
+                Permission::SysShareLinkDestroy,
+            ],
             ObjectType::Sharing => [
                 Permission::SysSharingUpdate,
                 Permission::SysSharingUpdate,
@@ -4121,6 +4593,11 @@ impl ObjectType {
                 Permission::SysSpamPyzorUpdate,
                 Permission::SysSpamPyzorUpdate,
             ],
+            ObjectType::SpamRspamd => [
+                Permission::SysSpamRspamdUpdate,
+                Permission::SysSpamRspamdUpdate,
+                Permission::SysSpamRspamdUpdate,
+            ],
             ObjectType::SpamRule => [
                 Permission::SysSpamRuleCreate,
                 Permission::SysSpamRuleUpdate,
@@ -4151,6 +4628,11 @@ impl ObjectType {
                 Permission::SysStoreLookupUpdate,
                 Permission::SysStoreLookupDestroy,
             ],
+            ObjectType::SuppressedAddress => [
+                Permission::SysSuppressedAddressCreate,
+                Permission::SysSuppressedAddressUpdate,
+                Permission::SysSuppressedAddressDestroy,
+            ],
             ObjectType::SystemSettings => [
                 Permission::SysSystemSettingsUpdate,
                 Permission::SysSystemSettingsUpdate,
@@ -4299,12 +4781,17 @@ impl ObjectInner {
             ObjectInner::ArchivedItem(ArchivedItem::SieveScript(obj)) => Some(obj.account_id),
             ObjectInner::MaskedEmail(obj) => Some(obj.account_id),
             ObjectInner::PublicKey(obj) => Some(obj.account_id),
+            ObjectInner::ShareLink(obj) => Some(obj.account_id),
             ObjectInner::SpamTrainingSample(obj) => obj.account_id,
             ObjectInner::Task(Task::IndexDocument(obj)) => Some(obj.account_id),
             ObjectInner::Task(Task::UnindexDocument(obj)) => Some(obj.account_id),
             ObjectInner::Task(Task::CalendarAlarmEmail(obj)) => Some(obj.account_id),
             ObjectInner::Task(Task::CalendarAlarmNotification(obj)) => Some(obj.account_id),
             ObjectInner::Task(Task::CalendarItipMessage(obj)) => Some(obj.account_id),
+            ObjectInner::Task(Task::CalendarSubscriptionSync(obj)) => Some(obj.account_id),
+            ObjectInner::Task(Task::AddressBookDirectorySync(obj)) => Some(obj.account_id),
+            ObjectInner::Task(Task::AccountMigration(obj)) => Some(obj.account_id),
+            ObjectInner::Task(Task::LoginAnomaly(obj)) => Some(obj.account_id),
             ObjectInner::Task(Task::MergeThreads(obj)) => Some(obj.account_id),
             ObjectInner::Task(Task::RestoreArchivedItem(obj)) => Some(obj.account_id),
             ObjectInner::Task(Task::DestroyAccount(obj)) => Some(obj.account_id),
@@ -4322,12 +4809,17 @@ impl ObjectInner {
             ObjectInner::ArchivedItem(ArchivedItem::SieveScript(obj)) => obj.account_id = id,
             ObjectInner::MaskedEmail(obj) => obj.account_id = id,
             ObjectInner::PublicKey(obj) => obj.account_id = id,
+            ObjectInner::ShareLink(obj) => obj.account_id = id,
             ObjectInner::SpamTrainingSample(obj) => obj.account_id = Some(id),
             ObjectInner::Task(Task::IndexDocument(obj)) => obj.account_id = id,
             ObjectInner::Task(Task::UnindexDocument(obj)) => obj.account_id = id,
             ObjectInner::Task(Task::CalendarAlarmEmail(obj)) => obj.account_id = id,
             ObjectInner::Task(Task::CalendarAlarmNotification(obj)) => obj.account_id = id,
             ObjectInner::Task(Task::CalendarItipMessage(obj)) => obj.account_id = id,
+            ObjectInner::Task(Task::CalendarSubscriptionSync(obj)) => obj.account_id = id,
+            ObjectInner::Task(Task::AddressBookDirectorySync(obj)) => obj.account_id = id,
+            ObjectInner::Task(Task::AccountMigration(obj)) => obj.account_id = id,
+            ObjectInner::Task(Task::LoginAnomaly(obj)) => obj.account_id = id,
             ObjectInner::Task(Task::MergeThreads(obj)) => obj.account_id = id,
             ObjectInner::Task(Task::RestoreArchivedItem(obj)) => obj.account_id = id,
             ObjectInner::Task(Task::DestroyAccount(obj)) => obj.account_id = id,
@@ -4419,6 +4911,7 @@ impl ObjectInner {
             ObjectInner::OAuthClient(obj) => obj.to_pickled_vec(),
             ObjectInner::OidcProvider(obj) => obj.to_pickled_vec(),
             ObjectInner::PublicKey(obj) => obj.to_pickled_vec(),
+            ObjectInner::QueueStore(obj) => obj.to_pickled_vec(),
             ObjectInner::QueuedMessage(obj) => obj.to_pickled_vec(),
             ObjectInner::ReportSettings(obj) => obj.to_pickled_vec(),
             ObjectInner::Role(obj) => obj.to_pickled_vec(),
@@ -4426,6 +4919,7 @@ impl ObjectInner {
             ObjectInner::SearchStore(obj) => obj.to_pickled_vec(),
             ObjectInner::Security(obj) => obj.to_pickled_vec(),
             ObjectInner::SenderAuth(obj) => obj.to_pickled_vec(),
+            ObjectInner::ShareLink(obj) => obj.to_pickled_vec(),
             ObjectInner::Sharing(obj) => obj.to_pickled_vec(),
             ObjectInner::SieveSystemInterpreter(obj) => obj.to_pickled_vec(),
             ObjectInner::SieveSystemScript(obj) => obj.to_pickled_vec(),
@@ -4437,12 +4931,14 @@ impl ObjectInner {
             ObjectInner::SpamFileExtension(obj) => obj.to_pickled_vec(),
             ObjectInner::SpamLlm(obj) => obj.to_pickled_vec(),
             ObjectInner::SpamPyzor(obj) => obj.to_pickled_vec(),
+            ObjectInner::SpamRspamd(obj) => obj.to_pickled_vec(),
             ObjectInner::SpamRule(obj) => obj.to_pickled_vec(),
             ObjectInner::SpamSettings(obj) => obj.to_pickled_vec(),
             ObjectInner::SpamTag(obj) => obj.to_pickled_vec(),
             ObjectInner::SpamTrainingSample(obj) => obj.to_pickled_vec(),
             ObjectInner::SpfReportSettings(obj) => obj.to_pickled_vec(),
             ObjectInner::StoreLookup(obj) => obj.to_pickled_vec(),
+            ObjectInner::SuppressedAddress(obj) => obj.to_pickled_vec(),
             ObjectInner::SystemSettings(obj) => obj.to_pickled_vec(),
             ObjectInner::Task(obj) => obj.to_pickled_vec(),
             ObjectInner::TaskManager(obj) => obj.to_pickled_vec(),
@@ -4586,6 +5082,7 @@ impl ObjectInner {
             ObjectType::OAuthClient => Pickle::unpickle(stream).map(ObjectInner::OAuthClient),
             ObjectType::OidcProvider => Pickle::unpickle(stream).map(ObjectInner::OidcProvider),
             ObjectType::PublicKey => Pickle::unpickle(stream).map(ObjectInner::PublicKey),
+            ObjectType::QueueStore => Pickle::unpickle(stream).map(ObjectInner::QueueStore),
             ObjectType::QueuedMessage => Pickle::unpickle(stream).map(ObjectInner::QueuedMessage),
             ObjectType::ReportSettings => Pickle::unpickle(stream).map(ObjectInner::ReportSettings),
             ObjectType::Role => Pickle::unpickle(stream).map(ObjectInner::Role),
@@ -4593,6 +5090,7 @@ impl ObjectInner {
             ObjectType::SearchStore => Pickle::unpickle(stream).map(ObjectInner::SearchStore),
             ObjectType::Security => Pickle::unpickle(stream).map(ObjectInner::Security),
             ObjectType::SenderAuth => Pickle::unpickle(stream).map(ObjectInner::SenderAuth),
+            ObjectType::ShareLink => Pickle::unpickle(stream).map(ObjectInner::ShareLink),
             ObjectType::Sharing => Pickle::unpickle(stream).map(ObjectInner::Sharing),
             ObjectType::SieveSystemInterpreter => {
                 Pickle::unpickle(stream).map(ObjectInner::SieveSystemInterpreter)
@@ -4618,6 +5116,7 @@ impl ObjectInner {
             }
             ObjectType::SpamLlm => Pickle::unpickle(stream).map(ObjectInner::SpamLlm),
             ObjectType::SpamPyzor => Pickle::unpickle(stream).map(ObjectInner::SpamPyzor),
+            ObjectType::SpamRspamd => Pickle::unpickle(stream).map(ObjectInner::SpamRspamd),
             ObjectType::SpamRule => Pickle::unpickle(stream).map(ObjectInner::SpamRule),
             ObjectType::SpamSettings => Pickle::unpickle(stream).map(ObjectInner::SpamSettings),
             ObjectType::SpamTag => Pickle::unpickle(stream).map(ObjectInner::SpamTag),
@@ -4628,6 +5127,9 @@ impl ObjectInner {
                 Pickle::unpickle(stream).map(ObjectInner::SpfReportSettings)
             }
             ObjectType::StoreLookup => Pickle::unpickle(stream).map(ObjectInner::StoreLookup),
+            ObjectType::SuppressedAddress => {
+                Pickle::unpickle(stream).map(ObjectInner::SuppressedAddress)
+            }
             ObjectType::SystemSettings => Pickle::unpickle(stream).map(ObjectInner::SystemSettings),
             ObjectType::Task => Pickle::unpickle(stream).map(ObjectInner::Task),
             ObjectType::TaskManager => Pickle::unpickle(stream).map(ObjectInner::TaskManager),
@@ -4855,6 +5357,9 @@ impl ObjectInner {
             ObjectType::PublicKey => {
                 PublicKey::deserialize(deserializer).map(ObjectInner::PublicKey)
             }
+            ObjectType::QueueStore => {
+                QueueStore::deserialize(deserializer).map(ObjectInner::QueueStore)
+            }
             ObjectType::QueuedMessage => {
                 QueuedMessage::deserialize(deserializer).map(ObjectInner::QueuedMessage)
             }
@@ -4870,6 +5375,9 @@ impl ObjectInner {
             ObjectType::SenderAuth => {
                 SenderAuth::deserialize(deserializer).map(ObjectInner::SenderAuth)
             }
+            ObjectType::ShareLink => {
+                ShareLink::deserialize(deserializer).map(ObjectInner::ShareLink)
+            }
             ObjectType::Sharing => Sharing::deserialize(deserializer).map(ObjectInner::Sharing),
             ObjectType::SieveSystemInterpreter => SieveSystemInterpreter::deserialize(deserializer)
                 .map(ObjectInner::SieveSystemInterpreter),
@@ -4897,6 +5405,9 @@ impl ObjectInner {
             ObjectType::SpamPyzor => {
                 SpamPyzor::deserialize(deserializer).map(ObjectInner::SpamPyzor)
             }
+            ObjectType::SpamRspamd => {
+                SpamRspamd::deserialize(deserializer).map(ObjectInner::SpamRspamd)
+            }
             ObjectType::SpamRule => SpamRule::deserialize(deserializer).map(ObjectInner::SpamRule),
             ObjectType::SpamSettings => {
                 SpamSettings::deserialize(deserializer).map(ObjectInner::SpamSettings)
@@ -4911,6 +5422,9 @@ impl ObjectInner {
             ObjectType::StoreLookup => {
                 StoreLookup::deserialize(deserializer).map(ObjectInner::StoreLookup)
             }
+            ObjectType::SuppressedAddress => {
+                SuppressedAddress::deserialize(deserializer).map(ObjectInner::SuppressedAddress)
+            }
             ObjectType::SystemSettings => {
                 SystemSettings::deserialize(deserializer).map(ObjectInner::SystemSettings)
             }
@@ -5055,6 +5569,7 @@ impl Object {
             ObjectInner::OAuthClient(_) => OAuthClient::FLAGS,
             ObjectInner::OidcProvider(_) => OidcProvider::FLAGS,
             ObjectInner::PublicKey(_) => PublicKey::FLAGS,
+            ObjectInner::QueueStore(_) => QueueStore::FLAGS,
             ObjectInner::QueuedMessage(_) => QueuedMessage::FLAGS,
             ObjectInner::ReportSettings(_) => ReportSettings::FLAGS,
             ObjectInner::Role(_) => Role::FLAGS,
@@ -5062,6 +5577,7 @@ impl Object {
             ObjectInner::SearchStore(_) => SearchStore::FLAGS,
             ObjectInner::Security(_) => Security::FLAGS,
             ObjectInner::SenderAuth(_) => SenderAuth::FLAGS,
+            ObjectInner::ShareLink(_) => ShareLink::FLAGS,
             ObjectInner::Sharing(_) => Sharing::FLAGS,
             ObjectInner::SieveSystemInterpreter(_) => SieveSystemInterpreter::FLAGS,
             ObjectInner::SieveSystemScript(_) => SieveSystemScript::FLAGS,
@@ -5073,12 +5589,14 @@ impl Object {
             ObjectInner::SpamFileExtension(_) => SpamFileExtension::FLAGS,
             ObjectInner::SpamLlm(_) => SpamLlm::FLAGS,
             ObjectInner::SpamPyzor(_) => SpamPyzor::FLAGS,
+            ObjectInner::SpamRspamd(_) => SpamRspamd::FLAGS,
             ObjectInner::SpamRule(_) => SpamRule::FLAGS,
             ObjectInner::SpamSettings(_) => SpamSettings::FLAGS,
             ObjectInner::SpamTag(_) => SpamTag::FLAGS,
             ObjectInner::SpamTrainingSample(_) => SpamTrainingSample::FLAGS,
             ObjectInner::SpfReportSettings(_) => SpfReportSettings::FLAGS,
             ObjectInner::StoreLookup(_) => StoreLookup::FLAGS,
+            ObjectInner::SuppressedAddress(_) => SuppressedAddress::FLAGS,
             ObjectInner::SystemSettings(_) => SystemSettings::FLAGS,
             ObjectInner::Task(_) => Task::FLAGS,
             ObjectInner::TaskManager(_) => TaskManager::FLAGS,
@@ -5177,6 +5695,7 @@ impl Object {
             ObjectInner::OAuthClient(_) => ObjectType::OAuthClient,
             ObjectInner::OidcProvider(_) => ObjectType::OidcProvider,
             ObjectInner::PublicKey(_) => ObjectType::PublicKey,
+            ObjectInner::QueueStore(_) => ObjectType::QueueStore,
             ObjectInner::QueuedMessage(_) => ObjectType::QueuedMessage,
             ObjectInner::ReportSettings(_) => ObjectType::ReportSettings,
             ObjectInner::Role(_) => ObjectType::Role,
@@ -5184,6 +5703,7 @@ impl Object {
             ObjectInner::SearchStore(_) => ObjectType::SearchStore,
             ObjectInner::Security(_) => ObjectType::Security,
             ObjectInner::SenderAuth(_) => ObjectType::SenderAuth,
+            ObjectInner::ShareLink(_) => ObjectType::ShareLink,
             ObjectInner::Sharing(_) => ObjectType::Sharing,
             ObjectInner::SieveSystemInterpreter(_) => ObjectType::SieveSystemInterpreter,
             ObjectInner::SieveSystemScript(_) => ObjectType::SieveSystemScript,
@@ -5195,12 +5715,14 @@ impl Object {
             ObjectInner::SpamFileExtension(_) => ObjectType::SpamFileExtension,
             ObjectInner::SpamLlm(_) => ObjectType::SpamLlm,
             ObjectInner::SpamPyzor(_) => ObjectType::SpamPyzor,
+            ObjectInner::SpamRspamd(_) => ObjectType::SpamRspamd,
             ObjectInner::SpamRule(_) => ObjectType::SpamRule,
             ObjectInner::SpamSettings(_) => ObjectType::SpamSettings,
             ObjectInner::SpamTag(_) => ObjectType::SpamTag,
             ObjectInner::SpamTrainingSample(_) => ObjectType::SpamTrainingSample,
             ObjectInner::SpfReportSettings(_) => ObjectType::SpfReportSettings,
             ObjectInner::StoreLookup(_) => ObjectType::StoreLookup,
+            ObjectInner::SuppressedAddress(_) => ObjectType::SuppressedAddress,
             ObjectInner::SystemSettings(_) => ObjectType::SystemSettings,
             ObjectInner::Task(_) => ObjectType::Task,
             ObjectInner::TaskManager(_) => ObjectType::TaskManager,
@@ -5299,6 +5821,7 @@ impl Object {
             ObjectInner::OAuthClient(obj) => obj.validate(errors),
             ObjectInner::OidcProvider(obj) => obj.validate(errors),
             ObjectInner::PublicKey(obj) => obj.validate(errors),
+            ObjectInner::QueueStore(obj) => obj.validate(errors),
             ObjectInner::QueuedMessage(obj) => obj.validate(errors),
             ObjectInner::ReportSettings(obj) => obj.validate(errors),
             ObjectInner::Role(obj) => obj.validate(errors),
@@ -5306,6 +5829,7 @@ impl Object {
             ObjectInner::SearchStore(obj) => obj.validate(errors),
             ObjectInner::Security(obj) => obj.validate(errors),
             ObjectInner::SenderAuth(obj) => obj.validate(errors),
+            ObjectInner::ShareLink(obj) => obj.validate(errors),
             ObjectInner::Sharing(obj) => obj.validate(errors),
             ObjectInner::SieveSystemInterpreter(obj) => obj.validate(errors),
             ObjectInner::SieveSystemScript(obj) => obj.validate(errors),
@@ -5317,12 +5841,14 @@ impl Object {
             ObjectInner::SpamFileExtension(obj) => obj.validate(errors),
             ObjectInner::SpamLlm(obj) => obj.validate(errors),
             ObjectInner::SpamPyzor(obj) => obj.validate(errors),
+            ObjectInner::SpamRspamd(obj) => obj.validate(errors),
             ObjectInner::SpamRule(obj) => obj.validate(errors),
             ObjectInner::SpamSettings(obj) => obj.validate(errors),
             ObjectInner::SpamTag(obj) => obj.validate(errors),
             ObjectInner::SpamTrainingSample(obj) => obj.validate(errors),
             ObjectInner::SpfReportSettings(obj) => obj.validate(errors),
             ObjectInner::StoreLookup(obj) => obj.validate(errors),
+            ObjectInner::SuppressedAddress(obj) => obj.validate(errors),
             ObjectInner::SystemSettings(obj) => obj.validate(errors),
             ObjectInner::Task(obj) => obj.validate(errors),
             ObjectInner::TaskManager(obj) => obj.validate(errors),
@@ -5421,6 +5947,7 @@ impl Object {
             ObjectInner::OAuthClient(obj) => obj.index(i),
             ObjectInner::OidcProvider(obj) => obj.index(i),
             ObjectInner::PublicKey(obj) => obj.index(i),
+            ObjectInner::QueueStore(obj) => obj.index(i),
             ObjectInner::QueuedMessage(obj) => obj.index(i),
             ObjectInner::ReportSettings(obj) => obj.index(i),
             ObjectInner::Role(obj) => obj.index(i),
@@ -5428,6 +5955,7 @@ impl Object {
             ObjectInner::SearchStore(obj) => obj.index(i),
             ObjectInner::Security(obj) => obj.index(i),
             ObjectInner::SenderAuth(obj) => obj.index(i),
+            ObjectInner::ShareLink(obj) => obj.index(i),
             ObjectInner::Sharing(obj) => obj.index(i),
             ObjectInner::SieveSystemInterpreter(obj) => obj.index(i),
             ObjectInner::SieveSystemScript(obj) => obj.index(i),
@@ -5439,12 +5967,14 @@ impl Object {
             ObjectInner::SpamFileExtension(obj) => obj.index(i),
             ObjectInner::SpamLlm(obj) => obj.index(i),
             ObjectInner::SpamPyzor(obj) => obj.index(i),
+            ObjectInner::SpamRspamd(obj) => obj.index(i),
             ObjectInner::SpamRule(obj) => obj.index(i),
             ObjectInner::SpamSettings(obj) => obj.index(i),
             ObjectInner::SpamTag(obj) => obj.index(i),
             ObjectInner::SpamTrainingSample(obj) => obj.index(i),
             ObjectInner::SpfReportSettings(obj) => obj.index(i),
             ObjectInner::StoreLookup(obj) => obj.index(i),
+            ObjectInner::SuppressedAddress(obj) => obj.index(i),
             ObjectInner::SystemSettings(obj) => obj.index(i),
             ObjectInner::Task(obj) => obj.index(i),
             ObjectInner::TaskManager(obj) => obj.index(i),
@@ -5547,6 +6077,7 @@ impl Object {
             ObjectInner::OAuthClient(obj) => obj.patch(pointer, value),
             ObjectInner::OidcProvider(obj) => obj.patch(pointer, value),
             ObjectInner::PublicKey(obj) => obj.patch(pointer, value),
+            ObjectInner::QueueStore(obj) => obj.patch(pointer, value),
             ObjectInner::QueuedMessage(obj) => obj.patch(pointer, value),
             ObjectInner::ReportSettings(obj) => obj.patch(pointer, value),
             ObjectInner::Role(obj) => obj.patch(pointer, value),
@@ -5554,6 +6085,7 @@ impl Object {
             ObjectInner::SearchStore(obj) => obj.patch(pointer, value),
             ObjectInner::Security(obj) => obj.patch(pointer, value),
             ObjectInner::SenderAuth(obj) => obj.patch(pointer, value),
+            ObjectInner::ShareLink(obj) => obj.patch(pointer, value),
             ObjectInner::Sharing(obj) => obj.patch(pointer, value),
             ObjectInner::SieveSystemInterpreter(obj) => obj.patch(pointer, value),
             ObjectInner::SieveSystemScript(obj) => obj.patch(pointer, value),
@@ -5565,12 +6097,14 @@ impl Object {
             ObjectInner::SpamFileExtension(obj) => obj.patch(pointer, value),
             ObjectInner::SpamLlm(obj) => obj.patch(pointer, value),
             ObjectInner::SpamPyzor(obj) => obj.patch(pointer, value),
+            ObjectInner::SpamRspamd(obj) => obj.patch(pointer, value),
             ObjectInner::SpamRule(obj) => obj.patch(pointer, value),
             ObjectInner::SpamSettings(obj) => obj.patch(pointer, value),
             ObjectInner::SpamTag(obj) => obj.patch(pointer, value),
             ObjectInner::SpamTrainingSample(obj) => obj.patch(pointer, value),
             ObjectInner::SpfReportSettings(obj) => obj.patch(pointer, value),
             ObjectInner::StoreLookup(obj) => obj.patch(pointer, value),
+            ObjectInner::SuppressedAddress(obj) => obj.patch(pointer, value),
             ObjectInner::SystemSettings(obj) => obj.patch(pointer, value),
             ObjectInner::Task(obj) => obj.patch(pointer, value),
             ObjectInner::TaskManager(obj) => obj.patch(pointer, value),
@@ -5671,6 +6205,7 @@ impl IntoValue for Object {
             ObjectInner::OAuthClient(obj) => obj.into_value(),
             ObjectInner::OidcProvider(obj) => obj.into_value(),
             ObjectInner::PublicKey(obj) => obj.into_value(),
+            ObjectInner::QueueStore(obj) => obj.into_value(),
             ObjectInner::QueuedMessage(obj) => obj.into_value(),
             ObjectInner::ReportSettings(obj) => obj.into_value(),
             ObjectInner::Role(obj) => obj.into_value(),
@@ -5678,6 +6213,7 @@ impl IntoValue for Object {
             ObjectInner::SearchStore(obj) => obj.into_value(),
             ObjectInner::Security(obj) => obj.into_value(),
             ObjectInner::SenderAuth(obj) => obj.into_value(),
+            ObjectInner::ShareLink(obj) => obj.into_value(),
             ObjectInner::Sharing(obj) => obj.into_value(),
             ObjectInner::SieveSystemInterpreter(obj) => obj.into_value(),
             ObjectInner::SieveSystemScript(obj) => obj.into_value(),
@@ -5689,12 +6225,14 @@ impl IntoValue for Object {
             ObjectInner::SpamFileExtension(obj) => obj.into_value(),
             ObjectInner::SpamLlm(obj) => obj.into_value(),
             ObjectInner::SpamPyzor(obj) => obj.into_value(),
+            ObjectInner::SpamRspamd(obj) => obj.into_value(),
             ObjectInner::SpamRule(obj) => obj.into_value(),
             ObjectInner::SpamSettings(obj) => obj.into_value(),
             ObjectInner::SpamTag(obj) => obj.into_value(),
             ObjectInner::SpamTrainingSample(obj) => obj.into_value(),
             ObjectInner::SpfReportSettings(obj) => obj.into_value(),
             ObjectInner::StoreLookup(obj) => obj.into_value(),
+            ObjectInner::SuppressedAddress(obj) => obj.into_value(),
             ObjectInner::SystemSettings(obj) => obj.into_value(),
             ObjectInner::Task(obj) => obj.into_value(),
             ObjectInner::TaskManager(obj) => obj.into_value(),
@@ -5799,6 +6337,7 @@ impl From<ObjectType> for ObjectInner {
             ObjectType::OAuthClient => ObjectInner::OAuthClient(Default::default()),
             ObjectType::OidcProvider => ObjectInner::OidcProvider(Default::default()),
             ObjectType::PublicKey => ObjectInner::PublicKey(Default::default()),
+            ObjectType::QueueStore => ObjectInner::QueueStore(Default::default()),
             ObjectType::QueuedMessage => ObjectInner::QueuedMessage(Default::default()),
             ObjectType::ReportSettings => ObjectInner::ReportSettings(Default::default()),
             ObjectType::Role => ObjectInner::Role(Default::default()),
@@ -5806,6 +6345,7 @@ impl From<ObjectType> for ObjectInner {
             ObjectType::SearchStore => ObjectInner::SearchStore(Default::default()),
             ObjectType::Security => ObjectInner::Security(Default::default()),
             ObjectType::SenderAuth => ObjectInner::SenderAuth(Default::default()),
+            ObjectType::ShareLink => ObjectInner::ShareLink(Default::default()),
             ObjectType::Sharing => ObjectInner::Sharing(Default::default()),
             ObjectType::SieveSystemInterpreter => {
                 ObjectInner::SieveSystemInterpreter(Default::default())
@@ -5821,12 +6361,14 @@ impl From<ObjectType> for ObjectInner {
             ObjectType::SpamFileExtension => ObjectInner::SpamFileExtension(Default::default()),
             ObjectType::SpamLlm => ObjectInner::SpamLlm(Default::default()),
             ObjectType::SpamPyzor => ObjectInner::SpamPyzor(Default::default()),
+            ObjectType::SpamRspamd => ObjectInner::SpamRspamd(Default::default()),
             ObjectType::SpamRule => ObjectInner::SpamRule(Default::default()),
             ObjectType::SpamSettings => ObjectInner::SpamSettings(Default::default()),
             ObjectType::SpamTag => ObjectInner::SpamTag(Default::default()),
             ObjectType::SpamTrainingSample => ObjectInner::SpamTrainingSample(Default::default()),
             ObjectType::SpfReportSettings => ObjectInner::SpfReportSettings(Default::default()),
             ObjectType::StoreLookup => ObjectInner::StoreLookup(Default::default()),
+            ObjectType::SuppressedAddress => ObjectInner::SuppressedAddress(Default::default()),
             ObjectType::SystemSettings => ObjectInner::SystemSettings(Default::default()),
             ObjectType::Task => ObjectInner::Task(Default::default()),
             ObjectType::TaskManager => ObjectInner::TaskManager(Default::default()),
@@ -7058,6 +7600,21 @@ impl From<Object> for PublicKey {
     }
 }
 
+impl From<QueueStore> for ObjectInner {
+    fn from(value: QueueStore) -> Self {
+        ObjectInner::QueueStore(value)
+    }
+}
+
+impl From<Object> for QueueStore {
+    fn from(obj: Object) -> Self {
+        match obj.inner {
+            ObjectInner::QueueStore(obj) => obj,
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl From<QueuedMessage> for ObjectInner {
     fn from(value: QueuedMessage) -> Self {
         ObjectInner::QueuedMessage(value)
@@ -7163,6 +7720,21 @@ impl From<Object> for SenderAuth {
     }
 }
 
+impl From<ShareLink> for ObjectInner {
+    fn from(value: ShareLink) -> Self {
+        ObjectInner::ShareLink(value)
+    }
+}
+
+impl From<Object> for ShareLink {
+    fn from(obj: Object) -> Self {
+        match obj.inner {
+            ObjectInner::ShareLink(obj) => obj,
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl From<Sharing> for ObjectInner {
     fn from(value: Sharing) -> Self {
         ObjectInner::Sharing(value)
@@ -7328,6 +7900,21 @@ impl From<Object> for SpamPyzor {
     }
 }
 
+impl From<SpamRspamd> for ObjectInner {
+    fn from(value: SpamRspamd) -> Self {
+        ObjectInner::SpamRspamd(value)
+    }
+}
+
+impl From<Object> for SpamRspamd {
+    fn from(obj: Object) -> Self {
+        match obj.inner {
+            ObjectInner::SpamRspamd(obj) => obj,
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl From<SpamRule> for ObjectInner {
     fn from(value: SpamRule) -> Self {
         ObjectInner::SpamRule(value)
@@ -7418,6 +8005,21 @@ impl From<Object> for StoreLookup {
     }
 }
 
+impl From<SuppressedAddress> for ObjectInner {
+    fn from(value: SuppressedAddress) -> Self {
+        ObjectInner::SuppressedAddress(value)
+    }
+}
+
+impl From<Object> for SuppressedAddress {
+    fn from(obj: Object) -> Self {
+        match obj.inner {
+            ObjectInner::SuppressedAddress(obj) => obj,
+            _ => unreachable!(),
+        }
+    }
+}
+
 impl From<SystemSettings> for ObjectInner {
     fn from(value: SystemSettings) -> Self {
         ObjectInner::SystemSettings(value)
@@ -88,6 +88,33 @@ pub struct AddressBook {
     pub max_address_books: Option<u64>,
     #[serde(rename = "maxContacts")]
     pub max_contacts: Option<u64>,
+    #[serde(rename = "directoryEnable")]
+    pub directory_enable: bool,
+    #[serde(rename = "directoryHrefName")]
+    pub directory_href_name: String,
+    #[serde(rename = "directoryDisplayName")]
+    pub directory_display_name: String,
+    #[serde(rename = "directoryScope")]
+    pub directory_scope: AddressBookDirectoryScope,
+    #[serde(rename = "directoryRefreshInterval")]
+    pub directory_refresh_interval: Duration,
+    #[serde(rename = "directoryFields")]
+    pub directory_fields: VecMap<AddressBookDirectoryField, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AddressRewriteRule {
+    #[serde(rename = "enable")]
+    pub enable: bool,
+    #[serde(rename = "description")]
+    pub description: String,
+    #[serde(rename = "scope")]
+    pub scope: Map<AddressRewriteScope>,
+    #[serde(rename = "pattern")]
+    pub pattern: String,
+    #[serde(rename = "replacement")]
+    pub replacement: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -220,6 +247,10 @@ pub struct Application {
     pub resource_url: String,
     #[serde(rename = "urlPrefix")]
     pub url_prefix: Map<String>,
+    #[serde(rename = "hostnames")]
+    pub hostnames: Map<String>,
+    #[serde(rename = "cacheMaxAge")]
+    pub cache_max_age: Option<Duration>,
     #[serde(rename = "autoUpdateFrequency")]
     pub auto_update_frequency: Duration,
     #[serde(rename = "unpackDirectory")]
@@ -478,6 +509,10 @@ pub struct Authentication {
     pub max_app_passwords: Option<u64>,
     #[serde(rename = "maxApiKeys")]
     pub max_api_keys: Option<u64>,
+    #[serde(rename = "loginAnomalySensitivity")]
+    pub login_anomaly_sensitivity: LoginAnomalySensitivity,
+    #[serde(rename = "loginTravelWindow")]
+    pub login_travel_window: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -596,6 +631,8 @@ pub struct Cache {
     pub http_auth: u64,
     #[serde(rename = "messages")]
     pub messages: u64,
+    #[serde(rename = "emailQueries")]
+    pub email_queries: u64,
     #[serde(rename = "domains")]
     pub domains: u64,
     #[serde(rename = "domainNames")]
@@ -618,6 +655,8 @@ pub struct Cache {
     pub dkim_signatures: u64,
     #[serde(rename = "negativeTtl")]
     pub negative_ttl: Duration,
+    #[serde(rename = "rateLimited")]
+    pub rate_limited: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -641,6 +680,14 @@ pub struct Calendar {
     pub max_participant_identities: Option<u64>,
     #[serde(rename = "maxEventNotifications")]
     pub max_event_notifications: Option<u64>,
+    #[serde(rename = "subscriptionRefreshInterval")]
+    pub subscription_refresh_interval: Duration,
+    #[serde(rename = "subscriptionMaxBackoff")]
+    pub subscription_max_backoff: Duration,
+    #[serde(rename = "subscriptionMaxSize")]
+    pub subscription_max_size: u64,
+    #[serde(rename = "subscriptionTimeout")]
+    pub subscription_timeout: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -681,6 +728,12 @@ pub struct CalendarScheduling {
     pub email_template: Option<String>,
     #[serde(rename = "httpRsvpTemplate")]
     pub http_rsvp_template: Option<String>,
+    #[serde(rename = "httpFreeBusyEnable")]
+    pub http_free_busy_enable: bool,
+    #[serde(rename = "httpFreeBusyAccess")]
+    pub http_free_busy_access: FreeBusyAccess,
+    #[serde(rename = "httpFreeBusyMaxRange")]
+    pub http_free_busy_max_range: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -870,12 +923,16 @@ pub struct DataRetention {
     pub blob_cleanup_schedule: Cron,
     #[serde(rename = "maxChangesHistory")]
     pub max_changes_history: Option<u64>,
+    #[serde(rename = "maxEmailChangeHistory")]
+    pub max_email_change_history: Option<u64>,
     #[serde(rename = "archiveDeletedItemsFor")]
     pub archive_deleted_items_for: Option<Duration>,
     #[serde(rename = "archiveDeletedAccountsFor")]
     pub archive_deleted_accounts_for: Option<Duration>,
     #[serde(rename = "holdMtaReportsFor")]
     pub hold_mta_reports_for: Option<Duration>,
+    #[serde(rename = "holdAuditLogFor")]
+    pub hold_audit_log_for: Option<Duration>,
     #[serde(rename = "holdTracesFor")]
     pub hold_traces_for: Option<Duration>,
     #[serde(rename = "holdMetricsFor")]
@@ -1676,6 +1733,32 @@ pub struct Domain {
     pub allow_relaying: bool,
     #[serde(rename = "reportAddressUri")]
     pub report_address_uri: Option<String>,
+    #[serde(rename = "services")]
+    pub services: VecMap<ServiceProtocol, Service>,
+    #[serde(rename = "providerInfo")]
+    pub provider_info: VecMap<ProviderInfo, String>,
+    #[serde(rename = "disclaimerText")]
+    pub disclaimer_text: Option<String>,
+    #[serde(rename = "disclaimerHtml")]
+    pub disclaimer_html: Option<String>,
+    #[serde(rename = "disclaimerInbound")]
+    pub disclaimer_inbound: bool,
+    #[serde(rename = "disclaimerWrapSigned")]
+    pub disclaimer_wrap_signed: bool,
+    #[serde(rename = "subAddressingFolders")]
+    pub sub_addressing_folders: bool,
+    #[serde(rename = "subAddressingCreateFolders")]
+    pub sub_addressing_create_folders: bool,
+    #[serde(rename = "relayVerify")]
+    pub relay_verify: RelayVerification,
+    #[serde(rename = "relayVerifyHost")]
+    pub relay_verify_host: Option<String>,
+    #[serde(rename = "relayVerifyUrl")]
+    pub relay_verify_url: Option<String>,
+    #[serde(rename = "relayVerifyCacheTtl")]
+    pub relay_verify_cache_ttl: Duration,
+    #[serde(rename = "encryptionPolicy")]
+    pub encryption_policy: EncryptionPolicy,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1687,6 +1770,16 @@ pub struct DsnReportSettings {
     pub from_name: Expression,
     #[serde(rename = "dkimSignDomain")]
     pub dkim_sign_domain: Expression,
+    #[serde(rename = "suppress")]
+    pub suppress: Expression,
+    #[serde(rename = "suppressListTraffic")]
+    pub suppress_list_traffic: bool,
+    #[serde(rename = "rateLimit")]
+    pub rate_limit: Option<Rate>,
+    #[serde(rename = "delaySubject")]
+    pub delay_subject: Expression,
+    #[serde(rename = "delayTemplate")]
+    pub delay_template: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1741,6 +1834,10 @@ pub struct Email {
     pub max_masked_addresses: Option<u64>,
     #[serde(rename = "maxPublicKeys")]
     pub max_public_keys: Option<u64>,
+    #[serde(rename = "folderPolicies")]
+    pub folder_policies: VecMap<String, EmailFolderPolicy>,
+    #[serde(rename = "publicFolderName")]
+    pub public_folder_name: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1767,6 +1864,19 @@ pub struct EmailFolder {
     pub subscribe: bool,
     #[serde(rename = "aliases")]
     pub aliases: Map<String>,
+    #[serde(rename = "protect")]
+    pub protect: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmailFolderPolicy {
+    #[serde(rename = "create")]
+    pub create: bool,
+    #[serde(rename = "subscribe")]
+    pub subscribe: bool,
+    #[serde(rename = "protect")]
+    pub protect: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1846,6 +1956,68 @@ pub struct FileSystemStore {
     pub depth: u64,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "@type")]
+pub enum FirewallSync {
+    Disabled,
+    Nftables(FirewallSyncNftables),
+    Ipset(FirewallSyncIpset),
+    Webhook(FirewallSyncWebhook),
+    Crowdsec(FirewallSyncCrowdsec),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FirewallSyncNftables {
+    #[serde(rename = "nftFamily")]
+    pub nft_family: String,
+    #[serde(rename = "nftTable")]
+    pub nft_table: String,
+    #[serde(rename = "setIpv4")]
+    pub set_ipv4: String,
+    #[serde(rename = "setIpv6")]
+    pub set_ipv6: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FirewallSyncIpset {
+    #[serde(rename = "setIpv4")]
+    pub set_ipv4: String,
+    #[serde(rename = "setIpv6")]
+    pub set_ipv6: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FirewallSyncWebhook {
+    #[serde(rename = "url")]
+    pub url: String,
+    #[serde(rename = "httpAuth")]
+    pub http_auth: HttpAuth,
+    #[serde(rename = "httpHeaders")]
+    pub http_headers: VecMap<String, String>,
+    #[serde(rename = "timeout")]
+    pub timeout: Duration,
+    #[serde(rename = "allowInvalidCerts")]
+    pub allow_invalid_certs: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FirewallSyncCrowdsec {
+    #[serde(rename = "url")]
+    pub url: String,
+    #[serde(rename = "machineId")]
+    pub machine_id: String,
+    #[serde(rename = "secret")]
+    pub secret: SecretKey,
+    #[serde(rename = "timeout")]
+    pub timeout: Duration,
+    #[serde(rename = "allowInvalidCerts")]
+    pub allow_invalid_certs: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct FoundationDbStore {
@@ -1861,6 +2033,8 @@ pub struct FoundationDbStore {
     pub transaction_retry_limit: Option<u64>,
     #[serde(rename = "transactionTimeout")]
     pub transaction_timeout: Option<Duration>,
+    #[serde(rename = "readCacheSize")]
+    pub read_cache_size: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1903,6 +2077,10 @@ pub struct GroupAccount {
     pub locale: Locale,
     #[serde(rename = "timeZone")]
     pub time_zone: Option<TimeZone>,
+    #[serde(rename = "legalHold")]
+    pub legal_hold: bool,
+    #[serde(rename = "publicFolders")]
+    pub public_folders: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1922,6 +2100,34 @@ pub struct Http {
     pub response_headers: VecMap<String, String>,
     #[serde(rename = "useXForwarded")]
     pub use_x_forwarded: bool,
+    #[serde(rename = "enableHttp2")]
+    pub enable_http2: bool,
+    #[serde(rename = "enableH2c")]
+    pub enable_h2c: bool,
+    #[serde(rename = "http2MaxConcurrentStreams")]
+    pub http2_max_concurrent_streams: u64,
+    #[serde(rename = "enableCompression")]
+    pub enable_compression: bool,
+    #[serde(rename = "compressionMinSize")]
+    pub compression_min_size: u64,
+    #[serde(rename = "compressionContentTypes")]
+    pub compression_content_types: Map<String>,
+    #[serde(rename = "corsAllowedOrigins")]
+    pub cors_allowed_origins: Expression,
+    #[serde(rename = "corsAllowedMethods")]
+    pub cors_allowed_methods: Expression,
+    #[serde(rename = "corsAllowedHeaders")]
+    pub cors_allowed_headers: Expression,
+    #[serde(rename = "corsAllowCredentials")]
+    pub cors_allow_credentials: Expression,
+    #[serde(rename = "corsMaxAge")]
+    pub cors_max_age: Duration,
+    #[serde(rename = "healthStoreTimeout")]
+    pub health_store_timeout: Duration,
+    #[serde(rename = "healthQueueThreshold")]
+    pub health_queue_threshold: Option<u64>,
+    #[serde(rename = "healthDegradedWeight")]
+    pub health_degraded_weight: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -1975,6 +2181,25 @@ pub struct HttpForm {
     pub field_subject: Option<String>,
     #[serde(rename = "validateDomain")]
     pub validate_domain: bool,
+    #[serde(rename = "captchaProvider")]
+    pub captcha_provider: CaptchaProvider,
+    #[serde(rename = "captchaSecret")]
+    pub captcha_secret: SecretKeyOptional,
+    #[serde(rename = "forms")]
+    pub forms: VecMap<String, HttpFormRoute>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HttpFormRoute {
+    #[serde(rename = "deliverTo")]
+    pub deliver_to: Map<String>,
+    #[serde(rename = "rateLimit")]
+    pub rate_limit: Option<Rate>,
+    #[serde(rename = "subject")]
+    pub subject: Option<String>,
+    #[serde(rename = "body")]
+    pub body: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -2037,6 +2262,14 @@ pub struct Imap {
     pub max_request_rate: Option<Rate>,
     #[serde(rename = "maxRequestSize")]
     pub max_request_size: u64,
+    #[serde(rename = "proxyAllowInvalidCerts")]
+    pub proxy_allow_invalid_certs: bool,
+    #[serde(rename = "proxyImapPort")]
+    pub proxy_imap_port: u64,
+    #[serde(rename = "proxyImplicitTls")]
+    pub proxy_implicit_tls: bool,
+    #[serde(rename = "proxyPop3Port")]
+    pub proxy_pop3_port: u64,
     #[serde(rename = "timeoutAnonymous")]
     pub timeout_anonymous: Duration,
     #[serde(rename = "timeoutAuthenticated")]
@@ -2120,6 +2353,12 @@ pub struct Jmap {
     pub websocket_timeout: Duration,
     #[serde(rename = "maxSubscriptions")]
     pub max_subscriptions: Option<u64>,
+    #[serde(rename = "maxConcurrentCalls")]
+    pub max_concurrent_calls: Option<u64>,
+    #[serde(rename = "maxQueuedCalls")]
+    pub max_queued_calls: u64,
+    #[serde(rename = "maxQueueWait")]
+    pub max_queue_wait: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -2170,8 +2409,12 @@ pub struct LdapDirectory {
     pub attr_email: Map<String>,
     #[serde(rename = "attrEmailAlias")]
     pub attr_email_alias: Map<String>,
+    #[serde(rename = "attrLoginReferral")]
+    pub attr_login_referral: Map<String>,
     #[serde(rename = "attrMemberOf")]
     pub attr_member_of: Map<String>,
+    #[serde(rename = "attrProxyBackend")]
+    pub attr_proxy_backend: Map<String>,
     #[serde(rename = "attrSecret")]
     pub attr_secret: Map<String>,
     #[serde(rename = "attrSecretChanged")]
@@ -2238,6 +2481,8 @@ pub struct MailingList {
     pub member_tenant_id: Option<Id>,
     #[serde(rename = "recipients")]
     pub recipients: Map<String>,
+    #[serde(rename = "allowUnsubscribe")]
+    pub allow_unsubscribe: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -2397,6 +2642,14 @@ pub struct MetricsPrometheusProperties {
     pub auth_secret: SecretKeyOptional,
     #[serde(rename = "authUsername")]
     pub auth_username: Option<String>,
+    #[serde(rename = "domainBreakdown")]
+    pub domain_breakdown: bool,
+    #[serde(rename = "tenantBreakdown")]
+    pub tenant_breakdown: bool,
+    #[serde(rename = "maxBreakdownLabels")]
+    pub max_breakdown_labels: u64,
+    #[serde(rename = "otlpIngest")]
+    pub otlp_ingest: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -2559,6 +2812,8 @@ pub struct MtaInboundSession {
     pub timeout: Expression,
     #[serde(rename = "transferLimit")]
     pub transfer_limit: Expression,
+    #[serde(rename = "addressRewrites")]
+    pub address_rewrites: List<AddressRewriteRule>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -2762,6 +3017,12 @@ pub struct MtaStageData {
     pub script: Expression,
     #[serde(rename = "enableSpamFilter")]
     pub enable_spam_filter: Expression,
+    #[serde(rename = "autoBcc")]
+    pub auto_bcc: Expression,
+    #[serde(rename = "duplicateAction")]
+    pub duplicate_action: DuplicateAction,
+    #[serde(rename = "duplicateWindow")]
+    pub duplicate_window: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -2795,6 +3056,10 @@ pub struct MtaStageRcpt {
     pub wait_on_fail: Expression,
     #[serde(rename = "maxRecipients")]
     pub max_recipients: Expression,
+    #[serde(rename = "maxSessionRecipients")]
+    pub max_session_recipients: Expression,
+    #[serde(rename = "enforceQuota")]
+    pub enforce_quota: Expression,
     #[serde(rename = "allowRelaying")]
     pub allow_relaying: Expression,
     #[serde(rename = "rewrite")]
@@ -2919,6 +3184,12 @@ pub struct NatsCoordinator {
     pub auth_username: Option<String>,
     #[serde(rename = "credentials")]
     pub credentials: SecretTextOptional,
+    #[serde(rename = "useJetStream")]
+    pub use_jet_stream: bool,
+    #[serde(rename = "streamName")]
+    pub stream_name: String,
+    #[serde(rename = "streamMaxAge")]
+    pub stream_max_age: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -2962,6 +3233,12 @@ pub struct NetworkListener {
     pub tls_timeout: Option<Duration>,
     #[serde(rename = "maxConnections")]
     pub max_connections: Option<u64>,
+    #[serde(rename = "tlsClientAuth")]
+    pub tls_client_auth: TlsClientAuth,
+    #[serde(rename = "tlsClientCaCertificates")]
+    pub tls_client_ca_certificates: Option<String>,
+    #[serde(rename = "tlsClientCertPrincipal")]
+    pub tls_client_cert_principal: Map<TlsClientCertField>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3124,6 +3401,8 @@ pub struct PostgreSqlStore {
     pub auth_secret: SecretKeyOptional,
     #[serde(rename = "options")]
     pub options: Option<String>,
+    #[serde(rename = "readCacheSize")]
+    pub read_cache_size: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3179,6 +3458,17 @@ pub struct QueueExpiryTtl {
     pub expires_at: UTCDateTime,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "@type")]
+pub enum QueueStore {
+    Default,
+    RocksDb(RocksDbStore),
+    Sqlite(SqliteStore),
+    FoundationDb(FoundationDbStore),
+    PostgreSql(PostgreSqlStore),
+    MySql(MySqlStore),
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct QueuedMessage {
@@ -3310,6 +3600,27 @@ pub struct ReportSettings {
     pub outbound_report_submitter: Expression,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RetentionPolicy {
+    #[serde(rename = "name")]
+    pub name: String,
+    #[serde(rename = "enabled")]
+    pub enabled: bool,
+    #[serde(rename = "action")]
+    pub action: RetentionAction,
+    #[serde(rename = "olderThan")]
+    pub older_than: Duration,
+    #[serde(rename = "mailboxes")]
+    pub mailboxes: Map<String>,
+    #[serde(rename = "keywords")]
+    pub keywords: Map<String>,
+    #[serde(rename = "excludeKeywords")]
+    pub exclude_keywords: Map<String>,
+    #[serde(rename = "archiveMailbox")]
+    pub archive_mailbox: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct RocksDbStore {
@@ -3454,6 +3765,10 @@ pub struct Search {
     pub index_telemetry: bool,
     #[serde(rename = "indexTracingFields")]
     pub index_tracing_fields: Map<SearchTracingField>,
+    #[serde(rename = "indexAttachmentContents")]
+    pub index_attachment_contents: bool,
+    #[serde(rename = "reindexRate")]
+    pub reindex_rate: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3569,11 +3884,23 @@ pub struct Security {
     pub scan_ban_rate: Option<Rate>,
     #[serde(rename = "scanBanPeriod")]
     pub scan_ban_period: Option<Duration>,
+    #[serde(rename = "repeatBanMaxPeriod")]
+    pub repeat_ban_max_period: Option<Duration>,
+    #[serde(rename = "repeatBanDecay")]
+    pub repeat_ban_decay: Duration,
+    #[serde(rename = "blockedTlsFingerprints")]
+    pub blocked_tls_fingerprints: Map<String>,
+    #[serde(rename = "firewallSync")]
+    pub firewall_sync: FirewallSync,
+    #[serde(rename = "firewallSyncInterval")]
+    pub firewall_sync_interval: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct SenderAuth {
+    #[serde(rename = "dkimResignDomain")]
+    pub dkim_resign_domain: Expression,
     #[serde(rename = "dkimSignDomain")]
     pub dkim_sign_domain: Expression,
     #[serde(rename = "dkimStrict")]
@@ -3612,6 +3939,10 @@ pub struct Service {
     pub hostname: Option<String>,
     #[serde(rename = "cleartext")]
     pub cleartext: bool,
+    #[serde(rename = "port")]
+    pub port: Option<u64>,
+    #[serde(rename = "cleartextPort")]
+    pub cleartext_port: Option<u64>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3628,6 +3959,25 @@ pub struct ShardedInMemoryStore {
     pub stores: List<InMemoryStoreBase>,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShareLink {
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    #[serde(rename = "collection")]
+    pub collection: ShareLinkCollection,
+    #[serde(rename = "collectionId")]
+    pub collection_id: Id,
+    #[serde(rename = "description")]
+    pub description: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: UTCDateTime,
+    #[serde(rename = "expiresAt")]
+    pub expires_at: Option<UTCDateTime>,
+    #[serde(rename = "url")]
+    pub url: Option<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Sharing {
@@ -3635,6 +3985,12 @@ pub struct Sharing {
     pub allow_directory_queries: bool,
     #[serde(rename = "maxShares")]
     pub max_shares: u64,
+    #[serde(rename = "shareLinkEnable")]
+    pub share_link_enable: bool,
+    #[serde(rename = "shareLinkMaxExpiry")]
+    pub share_link_max_expiry: Duration,
+    #[serde(rename = "maxShareLinks")]
+    pub max_share_links: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3772,6 +4128,8 @@ pub struct SpamClassifier {
     pub train_frequency: Option<Duration>,
     #[serde(rename = "learnHamFromReply")]
     pub learn_ham_from_reply: bool,
+    #[serde(rename = "trainRateLimit")]
+    pub train_rate_limit: Option<Rate>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3867,6 +4225,10 @@ pub struct SpamDnsblServerAny {
     pub description: Option<String>,
     #[serde(rename = "enable")]
     pub enable: bool,
+    #[serde(rename = "weight")]
+    pub weight: Float,
+    #[serde(rename = "timeout")]
+    pub timeout: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3882,6 +4244,10 @@ pub struct SpamDnsblServerBody {
     pub description: Option<String>,
     #[serde(rename = "enable")]
     pub enable: bool,
+    #[serde(rename = "weight")]
+    pub weight: Float,
+    #[serde(rename = "timeout")]
+    pub timeout: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3897,6 +4263,10 @@ pub struct SpamDnsblServerDomain {
     pub description: Option<String>,
     #[serde(rename = "enable")]
     pub enable: bool,
+    #[serde(rename = "weight")]
+    pub weight: Float,
+    #[serde(rename = "timeout")]
+    pub timeout: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3912,6 +4282,10 @@ pub struct SpamDnsblServerEmail {
     pub description: Option<String>,
     #[serde(rename = "enable")]
     pub enable: bool,
+    #[serde(rename = "weight")]
+    pub weight: Float,
+    #[serde(rename = "timeout")]
+    pub timeout: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3927,6 +4301,10 @@ pub struct SpamDnsblServerHeader {
     pub description: Option<String>,
     #[serde(rename = "enable")]
     pub enable: bool,
+    #[serde(rename = "weight")]
+    pub weight: Float,
+    #[serde(rename = "timeout")]
+    pub timeout: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3942,6 +4320,10 @@ pub struct SpamDnsblServerIp {
     pub description: Option<String>,
     #[serde(rename = "enable")]
     pub enable: bool,
+    #[serde(rename = "weight")]
+    pub weight: Float,
+    #[serde(rename = "timeout")]
+    pub timeout: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3957,6 +4339,10 @@ pub struct SpamDnsblServerUrl {
     pub description: Option<String>,
     #[serde(rename = "enable")]
     pub enable: bool,
+    #[serde(rename = "weight")]
+    pub weight: Float,
+    #[serde(rename = "timeout")]
+    pub timeout: Duration,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3970,6 +4356,12 @@ pub struct SpamDnsblSettings {
     pub ip_limit: u64,
     #[serde(rename = "urlLimit")]
     pub url_limit: u64,
+    #[serde(rename = "maxFailures")]
+    pub max_failures: u64,
+    #[serde(rename = "disableFor")]
+    pub disable_for: Duration,
+    #[serde(rename = "scoreReject")]
+    pub score_reject: Option<Float>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -4036,6 +4428,25 @@ pub struct SpamPyzor {
     pub allow_count: u64,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpamRspamd {
+    #[serde(rename = "enable")]
+    pub enable: bool,
+    #[serde(rename = "url")]
+    pub url: String,
+    #[serde(rename = "timeout")]
+    pub timeout: Duration,
+    #[serde(rename = "allowInvalidCerts")]
+    pub allow_invalid_certs: bool,
+    #[serde(rename = "replaceScore")]
+    pub replace_score: bool,
+    #[serde(rename = "httpAuth")]
+    pub http_auth: HttpAuth,
+    #[serde(rename = "httpHeaders")]
+    pub http_headers: VecMap<String, String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "@type")]
 pub enum SpamRule {
@@ -4172,6 +4583,12 @@ pub struct SpamSettings {
     pub trust_replies: bool,
     #[serde(rename = "spamFilterRulesUrl")]
     pub spam_filter_rules_url: Option<String>,
+    #[serde(rename = "trapBlockIp")]
+    pub trap_block_ip: bool,
+    #[serde(rename = "trapPenaltyFor")]
+    pub trap_penalty_for: Option<Duration>,
+    #[serde(rename = "trapPenaltyScore")]
+    pub trap_penalty_score: Float,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -4256,6 +4673,10 @@ pub struct SqlDirectory {
     pub column_class: Option<String>,
     #[serde(rename = "columnDescription")]
     pub column_description: Option<String>,
+    #[serde(rename = "columnProxyBackend")]
+    pub column_proxy_backend: Option<String>,
+    #[serde(rename = "columnLoginReferral")]
+    pub column_login_referral: Option<String>,
     #[serde(rename = "queryLogin")]
     pub query_login: String,
     #[serde(rename = "queryRecipient")]
@@ -4303,6 +4724,23 @@ pub struct SubAddressingCustom {
     pub custom_rule: Expression,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SuppressedAddress {
+    #[serde(rename = "email")]
+    pub email: String,
+    #[serde(rename = "reason")]
+    pub reason: SuppressionReason,
+    #[serde(rename = "details")]
+    pub details: Option<String>,
+    #[serde(rename = "createdAt")]
+    pub created_at: UTCDateTime,
+    #[serde(rename = "expiresAt")]
+    pub expires_at: Option<UTCDateTime>,
+    #[serde(rename = "memberTenantId")]
+    pub member_tenant_id: Option<Id>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct SystemSettings {
@@ -4324,6 +4762,8 @@ pub struct SystemSettings {
     pub services: VecMap<ServiceProtocol, Service>,
     #[serde(rename = "providerInfo")]
     pub provider_info: VecMap<ProviderInfo, String>,
+    #[serde(rename = "defaultAcmeProviderId")]
+    pub default_acme_provider_id: Option<Id>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -4347,6 +4787,10 @@ pub enum Task {
     AcmeRenewal(TaskDomainManagement),
     DkimManagement(TaskDomainManagement),
     DnsManagement(TaskDnsManagement),
+    CalendarSubscriptionSync(TaskCalendarSubscription),
+    AddressBookDirectorySync(TaskAddressBookDirectory),
+    AccountMigration(TaskAccountMigration),
+    LoginAnomaly(TaskLoginAnomaly),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -4360,6 +4804,38 @@ pub struct TaskAccountMaintenance {
     pub status: TaskStatus,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskAccountMigration {
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    #[serde(rename = "username")]
+    pub username: String,
+    #[serde(rename = "secret")]
+    pub secret: SecretKey,
+    #[serde(rename = "imapUrl")]
+    pub imap_url: Option<String>,
+    #[serde(rename = "sieveUrl")]
+    pub sieve_url: Option<String>,
+    #[serde(rename = "caldavUrl")]
+    pub caldav_url: Option<String>,
+    #[serde(rename = "carddavUrl")]
+    pub carddav_url: Option<String>,
+    #[serde(rename = "allowInvalidCerts")]
+    pub allow_invalid_certs: bool,
+    #[serde(rename = "status")]
+    pub status: TaskStatus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskAddressBookDirectory {
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    #[serde(rename = "status")]
+    pub status: TaskStatus,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct TaskCalendarAlarmEmail {
@@ -4428,6 +4904,17 @@ pub struct TaskCalendarItipMessage {
     pub status: TaskStatus,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskCalendarSubscription {
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    #[serde(rename = "documentId")]
+    pub document_id: Id,
+    #[serde(rename = "status")]
+    pub status: TaskStatus,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct TaskDestroyAccount {
@@ -4496,6 +4983,27 @@ pub struct TaskIndexTrace {
     pub status: TaskStatus,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskLoginAnomaly {
+    #[serde(rename = "accountId")]
+    pub account_id: Id,
+    #[serde(rename = "anomalyType")]
+    pub anomaly_type: LoginAnomalyType,
+    #[serde(rename = "remoteIp")]
+    pub remote_ip: IpAddr,
+    #[serde(rename = "country")]
+    pub country: Option<String>,
+    #[serde(rename = "previousCountry")]
+    pub previous_country: Option<String>,
+    #[serde(rename = "protocol")]
+    pub protocol: NetworkListenerProtocol,
+    #[serde(rename = "loginAt")]
+    pub login_at: UTCDateTime,
+    #[serde(rename = "status")]
+    pub status: TaskStatus,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct TaskManager {
@@ -4533,6 +5041,8 @@ pub struct TaskRestoreArchivedItem {
     pub archived_until: UTCDateTime,
     #[serde(rename = "accountId")]
     pub account_id: Id,
+    #[serde(rename = "mailboxId")]
+    pub mailbox_id: Option<Id>,
     #[serde(rename = "status")]
     pub status: TaskStatus,
 }
@@ -4656,12 +5166,24 @@ pub struct Tenant {
     pub created_at: UTCDateTime,
     #[serde(rename = "logo")]
     pub logo: Option<String>,
+    #[serde(rename = "providerInfo")]
+    pub provider_info: VecMap<ProviderInfo, String>,
     #[serde(rename = "roles")]
     pub roles: Roles,
     #[serde(rename = "permissions")]
     pub permissions: Permissions,
     #[serde(rename = "quotas")]
     pub quotas: VecMap<TenantStorageQuota, u64>,
+    #[serde(rename = "indexAttachmentContents")]
+    pub index_attachment_contents: bool,
+    #[serde(rename = "retentionPolicies")]
+    pub retention_policies: List<RetentionPolicy>,
+    #[serde(rename = "disabledJmapCapabilities")]
+    pub disabled_jmap_capabilities: Map<JmapCapability>,
+    #[serde(rename = "encryptionPolicy")]
+    pub encryption_policy: EncryptionPolicy,
+    #[serde(rename = "loginAnomalySensitivity")]
+    pub login_anomaly_sensitivity: Option<LoginAnomalySensitivity>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -4903,6 +5425,7 @@ pub enum Tracer {
     Journal(TracerCommon),
     OtelHttp(TracerOtelHttp),
     OtelGrpc(TracerOtelGrpc),
+    Syslog(TracerSyslog),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -5024,6 +5547,25 @@ pub struct TracerStdout {
     pub events_policy: EventPolicy,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TracerSyslog {
+    #[serde(rename = "address")]
+    pub address: String,
+    #[serde(rename = "facility")]
+    pub facility: u64,
+    #[serde(rename = "enable")]
+    pub enable: bool,
+    #[serde(rename = "level")]
+    pub level: TracingLevel,
+    #[serde(rename = "lossy")]
+    pub lossy: bool,
+    #[serde(rename = "events")]
+    pub events: Map<trc::EventType>,
+    #[serde(rename = "eventsPolicy")]
+    pub events_policy: EventPolicy,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "@type")]
 pub enum TracingStore {
@@ -5065,6 +5607,20 @@ pub struct UserAccount {
     pub time_zone: Option<TimeZone>,
     #[serde(rename = "encryptionAtRest")]
     pub encryption_at_rest: EncryptionAtRest,
+    #[serde(rename = "schedulingResource")]
+    pub scheduling_resource: SchedulingResourceType,
+    #[serde(rename = "schedulingPolicy")]
+    pub scheduling_policy: SchedulingResourcePolicy,
+    #[serde(rename = "calendarAutoImport")]
+    pub calendar_auto_import: bool,
+    #[serde(rename = "legalHold")]
+    pub legal_hold: bool,
+    #[serde(rename = "disabledJmapCapabilities")]
+    pub disabled_jmap_capabilities: Map<JmapCapability>,
+    #[serde(rename = "proxyBackend")]
+    pub proxy_backend: Option<String>,
+    #[serde(rename = "loginReferral")]
+    pub login_referral: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -5107,12 +5663,22 @@ pub struct WebHook {
     pub timeout: Duration,
     #[serde(rename = "discardAfter")]
     pub discard_after: Duration,
+    #[serde(rename = "maxRetries")]
+    pub max_retries: u64,
+    #[serde(rename = "retryBackoff")]
+    pub retry_backoff: Duration,
+    #[serde(rename = "maxBatchSize")]
+    pub max_batch_size: u64,
+    #[serde(rename = "deadLetterUrl")]
+    pub dead_letter_url: Option<String>,
     #[serde(rename = "url")]
     pub url: String,
     #[serde(rename = "httpAuth")]
     pub http_auth: HttpAuth,
     #[serde(rename = "httpHeaders")]
     pub http_headers: VecMap<String, String>,
+    #[serde(rename = "payloadTemplate")]
+    pub payload_template: Option<String>,
     #[serde(rename = "enable")]
     pub enable: bool,
     #[serde(rename = "level")]
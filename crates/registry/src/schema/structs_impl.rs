@@ -672,6 +672,14 @@ impl ObjectImpl for AddressBook {
                 errors.push(ValidationError::min_value(Property::MaxContacts, 1));
             }
         }
+        let value = &self.directory_href_name;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::DirectoryHrefName));
+        }
+        let value = &self.directory_display_name;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::DirectoryDisplayName));
+        }
         errors.len() == neb
     }
 
@@ -685,6 +693,12 @@ impl Pickle for AddressBook {
         self.max_v_card_size.pickle(out);
         self.max_address_books.pickle(out);
         self.max_contacts.pickle(out);
+        self.directory_enable.pickle(out);
+        self.directory_href_name.pickle(out);
+        self.directory_display_name.pickle(out);
+        self.directory_scope.pickle(out);
+        self.directory_refresh_interval.pickle(out);
+        self.directory_fields.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -694,6 +708,12 @@ impl Pickle for AddressBook {
         this.max_v_card_size = Pickle::unpickle(stream)?;
         this.max_address_books = Pickle::unpickle(stream)?;
         this.max_contacts = Pickle::unpickle(stream)?;
+        this.directory_enable = Pickle::unpickle(stream)?;
+        this.directory_href_name = Pickle::unpickle(stream)?;
+        this.directory_display_name = Pickle::unpickle(stream)?;
+        this.directory_scope = Pickle::unpickle(stream)?;
+        this.directory_refresh_interval = Pickle::unpickle(stream)?;
+        this.directory_fields = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -706,13 +726,24 @@ impl Default for AddressBook {
             max_v_card_size: 524288u64,
             max_address_books: Some(250u64),
             max_contacts: Default::default(),
+            directory_enable: false,
+            directory_href_name: "directory".to_string(),
+            directory_display_name: "Global Address List".to_string(),
+            directory_scope: AddressBookDirectoryScope::Tenant,
+            directory_refresh_interval: Duration::from_millis(3600000),
+            directory_fields: VecMap::from_iter([
+                (AddressBookDirectoryField::Name, "NICKNAME".to_string()),
+                (AddressBookDirectoryField::Description, "FN".to_string()),
+                (AddressBookDirectoryField::Email, "EMAIL".to_string()),
+                (AddressBookDirectoryField::Aliases, "EMAIL".to_string()),
+            ]),
         }
     }
 }
 
 impl IntoValue for AddressBook {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(7);
+        let mut map = jmap_tools::Map::with_capacity(13);
         map.insert_unchecked(
             Property::DefaultDisplayName,
             self.default_display_name.into_value(),
@@ -727,6 +758,27 @@ impl IntoValue for AddressBook {
             self.max_address_books.into_value(),
         );
         map.insert_unchecked(Property::MaxContacts, self.max_contacts.into_value());
+        map.insert_unchecked(
+            Property::DirectoryEnable,
+            self.directory_enable.into_value(),
+        );
+        map.insert_unchecked(
+            Property::DirectoryHrefName,
+            self.directory_href_name.into_value(),
+        );
+        map.insert_unchecked(
+            Property::DirectoryDisplayName,
+            self.directory_display_name.into_value(),
+        );
+        map.insert_unchecked(Property::DirectoryScope, self.directory_scope.into_value());
+        map.insert_unchecked(
+            Property::DirectoryRefreshInterval,
+            self.directory_refresh_interval.into_value(),
+        );
+        map.insert_unchecked(
+            Property::DirectoryFields,
+            self.directory_fields.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -747,6 +799,110 @@ impl RegistryJsonPropertyPatch for AddressBook {
             Some(Property::MaxVCardSize) => self.max_v_card_size.patch(pointer, value),
             Some(Property::MaxAddressBooks) => self.max_address_books.patch(pointer, value),
             Some(Property::MaxContacts) => self.max_contacts.patch(pointer, value),
+            Some(Property::DirectoryEnable) => self.directory_enable.patch(pointer, value),
+            Some(Property::DirectoryHrefName) => self
+                .directory_href_name
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::DirectoryDisplayName) => self
+                .directory_display_name
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::DirectoryScope) => self.directory_scope.patch(pointer, value),
+            Some(Property::DirectoryRefreshInterval) => {
+                self.directory_refresh_interval.patch(pointer, value)
+            }
+            Some(Property::DirectoryFields) => self.directory_fields.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
+impl AddressRewriteRule {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.description;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Description));
+        }
+        let value = &self.scope;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Scope));
+        }
+        let value = &self.pattern;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Pattern));
+        }
+        let value = &self.replacement;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Replacement));
+        }
+        errors.len() == neb
+    }
+}
+
+impl Pickle for AddressRewriteRule {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.enable.pickle(out);
+        self.description.pickle(out);
+        self.scope.pickle(out);
+        self.pattern.pickle(out);
+        self.replacement.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.enable = Pickle::unpickle(stream)?;
+        this.description = Pickle::unpickle(stream)?;
+        this.scope = Pickle::unpickle(stream)?;
+        this.pattern = Pickle::unpickle(stream)?;
+        this.replacement = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for AddressRewriteRule {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            description: Default::default(),
+            scope: Map::new(vec![
+                AddressRewriteScope::MailFrom,
+                AddressRewriteScope::RcptTo,
+                AddressRewriteScope::Headers,
+            ]),
+            pattern: Default::default(),
+            replacement: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for AddressRewriteRule {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(7);
+        map.insert_unchecked(Property::Enable, self.enable.into_value());
+        map.insert_unchecked(Property::Description, self.description.into_value());
+        map.insert_unchecked(Property::Scope, self.scope.into_value());
+        map.insert_unchecked(Property::Pattern, self.pattern.into_value());
+        map.insert_unchecked(Property::Replacement, self.replacement.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for AddressRewriteRule {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Enable) => self.enable.patch(pointer, value),
+            Some(Property::Description) => self.description.patch(pointer, value),
+            Some(Property::Scope) => self.scope.patch(pointer, value),
+            Some(Property::Pattern) => self.pattern.patch(pointer, value),
+            Some(Property::Replacement) => self.replacement.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -1659,6 +1815,12 @@ impl ObjectImpl for Application {
                 errors.push(ValidationError::required(Property::UnpackDirectory));
             }
         }
+        let value = &self.hostnames;
+        for value in value.iter() {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::Hostnames));
+            }
+        }
         errors.len() == neb
     }
 
@@ -1671,6 +1833,8 @@ impl Pickle for Application {
         self.description.pickle(out);
         self.resource_url.pickle(out);
         self.url_prefix.pickle(out);
+        self.hostnames.pickle(out);
+        self.cache_max_age.pickle(out);
         self.auto_update_frequency.pickle(out);
         self.unpack_directory.pickle(out);
     }
@@ -1681,6 +1845,8 @@ impl Pickle for Application {
         this.description = Pickle::unpickle(stream)?;
         this.resource_url = Pickle::unpickle(stream)?;
         this.url_prefix = Pickle::unpickle(stream)?;
+        this.hostnames = Pickle::unpickle(stream)?;
+        this.cache_max_age = Pickle::unpickle(stream)?;
         this.auto_update_frequency = Pickle::unpickle(stream)?;
         this.unpack_directory = Pickle::unpickle(stream)?;
         Some(this)
@@ -1694,6 +1860,8 @@ impl Default for Application {
             description: Default::default(),
             resource_url: Default::default(),
             url_prefix: Default::default(),
+            hostnames: Default::default(),
+            cache_max_age: Default::default(),
             auto_update_frequency: Duration::from_millis(7776000000),
             unpack_directory: Default::default(),
         }
@@ -1702,11 +1870,13 @@ impl Default for Application {
 
 impl IntoValue for Application {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(8);
+        let mut map = jmap_tools::Map::with_capacity(10);
         map.insert_unchecked(Property::Enabled, self.enabled.into_value());
         map.insert_unchecked(Property::Description, self.description.into_value());
         map.insert_unchecked(Property::ResourceUrl, self.resource_url.into_value());
         map.insert_unchecked(Property::UrlPrefix, self.url_prefix.into_value());
+        map.insert_unchecked(Property::Hostnames, self.hostnames.into_value());
+        map.insert_unchecked(Property::CacheMaxAge, self.cache_max_age.into_value());
         map.insert_unchecked(
             Property::AutoUpdateFrequency,
             self.auto_update_frequency.into_value(),
@@ -1736,6 +1906,10 @@ impl RegistryJsonPropertyPatch for Application {
             Some(Property::UrlPrefix) => self
                 .url_prefix
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::Hostnames) => self
+                .hostnames
+                .patch(pointer.with_validators(&[StringValidator::Hostname]), value),
+            Some(Property::CacheMaxAge) => self.cache_max_age.patch(pointer, value),
             Some(Property::AutoUpdateFrequency) => self.auto_update_frequency.patch(pointer, value),
             Some(Property::UnpackDirectory) => self
                 .unpack_directory
@@ -2091,7 +2265,9 @@ impl RegistryJsonPropertyPatch for ArchivedEmail {
             Some(Property::ArchivedAt) => self.archived_at.patch(pointer, value),
             Some(Property::ArchivedUntil) => self.archived_until.patch(pointer, value),
             Some(Property::BlobId) => self.blob_id.patch(pointer, value),
-            Some(property @ Property::Status) => Ok(MaybeUnpatched::Unpatched { property, value }),
+            Some(property @ (Property::Status | Property::MailboxId)) => {
+                Ok(MaybeUnpatched::Unpatched { property, value })
+            }
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -3353,6 +3529,8 @@ impl Pickle for Authentication {
         self.password_default_expiry.pickle(out);
         self.max_app_passwords.pickle(out);
         self.max_api_keys.pickle(out);
+        self.login_anomaly_sensitivity.pickle(out);
+        self.login_travel_window.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -3369,6 +3547,8 @@ impl Pickle for Authentication {
         this.password_default_expiry = Pickle::unpickle(stream)?;
         this.max_app_passwords = Pickle::unpickle(stream)?;
         this.max_api_keys = Pickle::unpickle(stream)?;
+        this.login_anomaly_sensitivity = Pickle::unpickle(stream)?;
+        this.login_travel_window = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -3388,13 +3568,15 @@ impl Default for Authentication {
             password_default_expiry: Default::default(),
             max_app_passwords: Some(5u64),
             max_api_keys: Some(5u64),
+            login_anomaly_sensitivity: LoginAnomalySensitivity::Disabled,
+            login_travel_window: Duration::from_millis(14400000),
         }
     }
 }
 
 impl IntoValue for Authentication {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(14);
+        let mut map = jmap_tools::Map::with_capacity(16);
         map.insert_unchecked(Property::DirectoryId, self.directory_id.into_value());
         map.insert_unchecked(
             Property::DefaultUserRoleIds,
@@ -3437,6 +3619,14 @@ impl IntoValue for Authentication {
             self.max_app_passwords.into_value(),
         );
         map.insert_unchecked(Property::MaxApiKeys, self.max_api_keys.into_value());
+        map.insert_unchecked(
+            Property::LoginAnomalySensitivity,
+            self.login_anomaly_sensitivity.into_value(),
+        );
+        map.insert_unchecked(
+            Property::LoginTravelWindow,
+            self.login_travel_window.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -3470,6 +3660,10 @@ impl RegistryJsonPropertyPatch for Authentication {
             }
             Some(Property::MaxAppPasswords) => self.max_app_passwords.patch(pointer, value),
             Some(Property::MaxApiKeys) => self.max_api_keys.patch(pointer, value),
+            Some(Property::LoginAnomalySensitivity) => {
+                self.login_anomaly_sensitivity.patch(pointer, value)
+            }
+            Some(Property::LoginTravelWindow) => self.login_travel_window.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -4283,6 +4477,14 @@ impl ObjectImpl for Cache {
         if *value < 2048 {
             errors.push(ValidationError::min_value(Property::DkimSignatures, 2048));
         }
+        let value = &self.rate_limited;
+        if *value < 2048 {
+            errors.push(ValidationError::min_value(Property::RateLimited, 2048));
+        }
+        let value = &self.email_queries;
+        if *value < 2048 {
+            errors.push(ValidationError::min_value(Property::EmailQueries, 2048));
+        }
         errors.len() == neb
     }
 
@@ -4317,6 +4519,8 @@ impl Pickle for Cache {
         self.mailing_lists.pickle(out);
         self.dkim_signatures.pickle(out);
         self.negative_ttl.pickle(out);
+        self.rate_limited.pickle(out);
+        self.email_queries.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -4347,6 +4551,8 @@ impl Pickle for Cache {
         this.mailing_lists = Pickle::unpickle(stream)?;
         this.dkim_signatures = Pickle::unpickle(stream)?;
         this.negative_ttl = Pickle::unpickle(stream)?;
+        this.rate_limited = Pickle::unpickle(stream)?;
+        this.email_queries = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -4369,6 +4575,7 @@ impl Default for Cache {
             files: 10485760,
             http_auth: 1048576,
             messages: 52428800,
+            email_queries: 10485760,
             domains: 5242880,
             domain_names: 10485760,
             domain_names_negative: 1048576,
@@ -4380,13 +4587,14 @@ impl Default for Cache {
             mailing_lists: 2097152,
             dkim_signatures: 10485760,
             negative_ttl: Duration::from_millis(3600000),
+            rate_limited: 1048576,
         }
     }
 }
 
 impl IntoValue for Cache {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(28);
+        let mut map = jmap_tools::Map::with_capacity(30);
         map.insert_unchecked(Property::AccessTokens, self.access_tokens.into_value());
         map.insert_unchecked(Property::Contacts, self.contacts.into_value());
         map.insert_unchecked(Property::DnsIpv4, self.dns_ipv4.into_value());
@@ -4419,6 +4627,8 @@ impl IntoValue for Cache {
         map.insert_unchecked(Property::MailingLists, self.mailing_lists.into_value());
         map.insert_unchecked(Property::DkimSignatures, self.dkim_signatures.into_value());
         map.insert_unchecked(Property::NegativeTtl, self.negative_ttl.into_value());
+        map.insert_unchecked(Property::RateLimited, self.rate_limited.into_value());
+        map.insert_unchecked(Property::EmailQueries, self.email_queries.into_value());
         JmapValue::Object(map)
     }
 }
@@ -4458,6 +4668,8 @@ impl RegistryJsonPropertyPatch for Cache {
             Some(Property::MailingLists) => self.mailing_lists.patch(pointer, value),
             Some(Property::DkimSignatures) => self.dkim_signatures.patch(pointer, value),
             Some(Property::NegativeTtl) => self.negative_ttl.patch(pointer, value),
+            Some(Property::RateLimited) => self.rate_limited.patch(pointer, value),
+            Some(Property::EmailQueries) => self.email_queries.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -4531,6 +4743,10 @@ impl Pickle for Calendar {
         self.max_events.pickle(out);
         self.max_participant_identities.pickle(out);
         self.max_event_notifications.pickle(out);
+        self.subscription_refresh_interval.pickle(out);
+        self.subscription_max_backoff.pickle(out);
+        self.subscription_max_size.pickle(out);
+        self.subscription_timeout.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -4544,6 +4760,10 @@ impl Pickle for Calendar {
         this.max_events = Pickle::unpickle(stream)?;
         this.max_participant_identities = Pickle::unpickle(stream)?;
         this.max_event_notifications = Pickle::unpickle(stream)?;
+        this.subscription_refresh_interval = Pickle::unpickle(stream)?;
+        this.subscription_max_backoff = Pickle::unpickle(stream)?;
+        this.subscription_max_size = Pickle::unpickle(stream)?;
+        this.subscription_timeout = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -4560,13 +4780,17 @@ impl Default for Calendar {
             max_events: Default::default(),
             max_participant_identities: Some(100u64),
             max_event_notifications: Default::default(),
+            subscription_refresh_interval: Duration::from_millis(3600000),
+            subscription_max_backoff: Duration::from_millis(86400000),
+            subscription_max_size: 10485760u64,
+            subscription_timeout: Duration::from_millis(30000),
         }
     }
 }
 
 impl IntoValue for Calendar {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(11);
+        let mut map = jmap_tools::Map::with_capacity(15);
         map.insert_unchecked(
             Property::DefaultDisplayName,
             self.default_display_name.into_value(),
@@ -4594,6 +4818,22 @@ impl IntoValue for Calendar {
             Property::MaxEventNotifications,
             self.max_event_notifications.into_value(),
         );
+        map.insert_unchecked(
+            Property::SubscriptionRefreshInterval,
+            self.subscription_refresh_interval.into_value(),
+        );
+        map.insert_unchecked(
+            Property::SubscriptionMaxBackoff,
+            self.subscription_max_backoff.into_value(),
+        );
+        map.insert_unchecked(
+            Property::SubscriptionMaxSize,
+            self.subscription_max_size.into_value(),
+        );
+        map.insert_unchecked(
+            Property::SubscriptionTimeout,
+            self.subscription_timeout.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -4624,6 +4864,14 @@ impl RegistryJsonPropertyPatch for Calendar {
             Some(Property::MaxEventNotifications) => {
                 self.max_event_notifications.patch(pointer, value)
             }
+            Some(Property::SubscriptionRefreshInterval) => {
+                self.subscription_refresh_interval.patch(pointer, value)
+            }
+            Some(Property::SubscriptionMaxBackoff) => {
+                self.subscription_max_backoff.patch(pointer, value)
+            }
+            Some(Property::SubscriptionMaxSize) => self.subscription_max_size.patch(pointer, value),
+            Some(Property::SubscriptionTimeout) => self.subscription_timeout.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -4787,6 +5035,9 @@ impl Pickle for CalendarScheduling {
         self.max_recipients.pickle(out);
         self.email_template.pickle(out);
         self.http_rsvp_template.pickle(out);
+        self.http_free_busy_enable.pickle(out);
+        self.http_free_busy_access.pickle(out);
+        self.http_free_busy_max_range.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -4800,6 +5051,9 @@ impl Pickle for CalendarScheduling {
         this.max_recipients = Pickle::unpickle(stream)?;
         this.email_template = Pickle::unpickle(stream)?;
         this.http_rsvp_template = Pickle::unpickle(stream)?;
+        this.http_free_busy_enable = Pickle::unpickle(stream)?;
+        this.http_free_busy_access = Pickle::unpickle(stream)?;
+        this.http_free_busy_max_range = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -4816,13 +5070,16 @@ impl Default for CalendarScheduling {
             max_recipients: 100u64,
             email_template: Default::default(),
             http_rsvp_template: Default::default(),
+            http_free_busy_enable: false,
+            http_free_busy_access: Default::default(),
+            http_free_busy_max_range: Duration::from_millis(7776000000),
         }
     }
 }
 
 impl IntoValue for CalendarScheduling {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(11);
+        let mut map = jmap_tools::Map::with_capacity(14);
         map.insert_unchecked(Property::Enable, self.enable.into_value());
         map.insert_unchecked(Property::HttpRsvpEnable, self.http_rsvp_enable.into_value());
         map.insert_unchecked(
@@ -4841,6 +5098,18 @@ impl IntoValue for CalendarScheduling {
             Property::HttpRsvpTemplate,
             self.http_rsvp_template.into_value(),
         );
+        map.insert_unchecked(
+            Property::HttpFreeBusyEnable,
+            self.http_free_busy_enable.into_value(),
+        );
+        map.insert_unchecked(
+            Property::HttpFreeBusyAccess,
+            self.http_free_busy_access.into_value(),
+        );
+        map.insert_unchecked(
+            Property::HttpFreeBusyMaxRange,
+            self.http_free_busy_max_range.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -4863,6 +5132,11 @@ impl RegistryJsonPropertyPatch for CalendarScheduling {
             Some(Property::MaxRecipients) => self.max_recipients.patch(pointer, value),
             Some(Property::EmailTemplate) => self.email_template.patch(pointer, value),
             Some(Property::HttpRsvpTemplate) => self.http_rsvp_template.patch(pointer, value),
+            Some(Property::HttpFreeBusyEnable) => self.http_free_busy_enable.patch(pointer, value),
+            Some(Property::HttpFreeBusyAccess) => self.http_free_busy_access.patch(pointer, value),
+            Some(Property::HttpFreeBusyMaxRange) => {
+                self.http_free_busy_max_range.patch(pointer, value)
+            }
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -6505,9 +6779,11 @@ impl Pickle for DataRetention {
         self.data_cleanup_schedule.pickle(out);
         self.blob_cleanup_schedule.pickle(out);
         self.max_changes_history.pickle(out);
+        self.max_email_change_history.pickle(out);
         self.archive_deleted_items_for.pickle(out);
         self.archive_deleted_accounts_for.pickle(out);
         self.hold_mta_reports_for.pickle(out);
+        self.hold_audit_log_for.pickle(out);
         self.hold_traces_for.pickle(out);
         self.hold_metrics_for.pickle(out);
         self.metrics_collection_interval.pickle(out);
@@ -6523,9 +6799,11 @@ impl Pickle for DataRetention {
         this.data_cleanup_schedule = Pickle::unpickle(stream)?;
         this.blob_cleanup_schedule = Pickle::unpickle(stream)?;
         this.max_changes_history = Pickle::unpickle(stream)?;
+        this.max_email_change_history = Pickle::unpickle(stream)?;
         this.archive_deleted_items_for = Pickle::unpickle(stream)?;
         this.archive_deleted_accounts_for = Pickle::unpickle(stream)?;
         this.hold_mta_reports_for = Pickle::unpickle(stream)?;
+        this.hold_audit_log_for = Pickle::unpickle(stream)?;
         this.hold_traces_for = Pickle::unpickle(stream)?;
         this.hold_metrics_for = Pickle::unpickle(stream)?;
         this.metrics_collection_interval = Pickle::unpickle(stream)?;
@@ -6553,9 +6831,11 @@ impl Default for DataRetention {
                 minute: 0u64,
             }),
             max_changes_history: Some(10000u64),
+            max_email_change_history: Default::default(),
             archive_deleted_items_for: Default::default(),
             archive_deleted_accounts_for: Default::default(),
             hold_mta_reports_for: Some(Duration::from_millis(2592000000)),
+            hold_audit_log_for: Some(Duration::from_millis(31536000000)),
             hold_traces_for: Some(Duration::from_millis(2592000000)),
             hold_metrics_for: Some(Duration::from_millis(7776000000)),
             metrics_collection_interval: Cron::Hourly(CronHourly { minute: 0u64 }),
@@ -6565,7 +6845,7 @@ impl Default for DataRetention {
 
 impl IntoValue for DataRetention {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(16);
+        let mut map = jmap_tools::Map::with_capacity(18);
         map.insert_unchecked(
             Property::ExpungeTrashAfter,
             self.expunge_trash_after.into_value(),
@@ -6598,6 +6878,10 @@ impl IntoValue for DataRetention {
             Property::MaxChangesHistory,
             self.max_changes_history.into_value(),
         );
+        map.insert_unchecked(
+            Property::MaxEmailChangeHistory,
+            self.max_email_change_history.into_value(),
+        );
         map.insert_unchecked(
             Property::ArchiveDeletedItemsFor,
             self.archive_deleted_items_for.into_value(),
@@ -6610,6 +6894,10 @@ impl IntoValue for DataRetention {
             Property::HoldMtaReportsFor,
             self.hold_mta_reports_for.into_value(),
         );
+        map.insert_unchecked(
+            Property::HoldAuditLogFor,
+            self.hold_audit_log_for.into_value(),
+        );
         map.insert_unchecked(Property::HoldTracesFor, self.hold_traces_for.into_value());
         map.insert_unchecked(Property::HoldMetricsFor, self.hold_metrics_for.into_value());
         map.insert_unchecked(
@@ -6641,6 +6929,9 @@ impl RegistryJsonPropertyPatch for DataRetention {
             Some(Property::DataCleanupSchedule) => self.data_cleanup_schedule.patch(pointer, value),
             Some(Property::BlobCleanupSchedule) => self.blob_cleanup_schedule.patch(pointer, value),
             Some(Property::MaxChangesHistory) => self.max_changes_history.patch(pointer, value),
+            Some(Property::MaxEmailChangeHistory) => {
+                self.max_email_change_history.patch(pointer, value)
+            }
             Some(Property::ArchiveDeletedItemsFor) => {
                 self.archive_deleted_items_for.patch(pointer, value)
             }
@@ -6648,6 +6939,7 @@ impl RegistryJsonPropertyPatch for DataRetention {
                 self.archive_deleted_accounts_for.patch(pointer, value)
             }
             Some(Property::HoldMtaReportsFor) => self.hold_mta_reports_for.patch(pointer, value),
+            Some(Property::HoldAuditLogFor) => self.hold_audit_log_for.patch(pointer, value),
             Some(Property::HoldTracesFor) => self.hold_traces_for.patch(pointer, value),
             Some(Property::HoldMetricsFor) => self.hold_metrics_for.patch(pointer, value),
             Some(Property::MetricsCollectionInterval) => {
@@ -12294,6 +12586,36 @@ impl ObjectImpl for Domain {
                 errors.push(ValidationError::required(Property::ReportAddressUri));
             }
         }
+        let value = &self.services;
+        for value in value.values() {
+            value.validate(errors);
+        }
+        let value = &self.provider_info;
+        for value in value.values() {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::ProviderInfo));
+            }
+        }
+        if let Some(value) = &self.disclaimer_text {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::DisclaimerText));
+            }
+        }
+        if let Some(value) = &self.disclaimer_html {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::DisclaimerHtml));
+            }
+        }
+        if let Some(value) = &self.relay_verify_host {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::RelayVerifyHost));
+            }
+        }
+        if let Some(value) = &self.relay_verify_url {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::RelayVerifyUrl));
+            }
+        }
         errors.len() == neb
     }
 
@@ -12333,6 +12655,19 @@ impl Pickle for Domain {
         self.sub_addressing.pickle(out);
         self.allow_relaying.pickle(out);
         self.report_address_uri.pickle(out);
+        self.services.pickle(out);
+        self.provider_info.pickle(out);
+        self.disclaimer_text.pickle(out);
+        self.disclaimer_html.pickle(out);
+        self.disclaimer_inbound.pickle(out);
+        self.disclaimer_wrap_signed.pickle(out);
+        self.sub_addressing_folders.pickle(out);
+        self.sub_addressing_create_folders.pickle(out);
+        self.relay_verify.pickle(out);
+        self.relay_verify_host.pickle(out);
+        self.relay_verify_url.pickle(out);
+        self.relay_verify_cache_ttl.pickle(out);
+        self.encryption_policy.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -12352,6 +12687,19 @@ impl Pickle for Domain {
         this.sub_addressing = Pickle::unpickle(stream)?;
         this.allow_relaying = Pickle::unpickle(stream)?;
         this.report_address_uri = Pickle::unpickle(stream)?;
+        this.services = Pickle::unpickle(stream)?;
+        this.provider_info = Pickle::unpickle(stream)?;
+        this.disclaimer_text = Pickle::unpickle(stream)?;
+        this.disclaimer_html = Pickle::unpickle(stream)?;
+        this.disclaimer_inbound = Pickle::unpickle(stream)?;
+        this.disclaimer_wrap_signed = Pickle::unpickle(stream)?;
+        this.sub_addressing_folders = Pickle::unpickle(stream)?;
+        this.sub_addressing_create_folders = Pickle::unpickle(stream)?;
+        this.relay_verify = Pickle::unpickle(stream)?;
+        this.relay_verify_host = Pickle::unpickle(stream)?;
+        this.relay_verify_url = Pickle::unpickle(stream)?;
+        this.relay_verify_cache_ttl = Pickle::unpickle(stream)?;
+        this.encryption_policy = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -12374,13 +12722,26 @@ impl Default for Domain {
             sub_addressing: Default::default(),
             allow_relaying: false,
             report_address_uri: Some("mailto:postmaster".to_string()),
+            services: Default::default(),
+            provider_info: Default::default(),
+            disclaimer_text: Default::default(),
+            disclaimer_html: Default::default(),
+            disclaimer_inbound: false,
+            disclaimer_wrap_signed: false,
+            sub_addressing_folders: false,
+            sub_addressing_create_folders: false,
+            relay_verify: RelayVerification::Disabled,
+            relay_verify_host: Default::default(),
+            relay_verify_url: Default::default(),
+            relay_verify_cache_ttl: Duration::from_millis(86400000),
+            encryption_policy: EncryptionPolicy::Optional,
         }
     }
 }
 
 impl IntoValue for Domain {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(17);
+        let mut map = jmap_tools::Map::with_capacity(30);
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::Aliases, self.aliases.into_value());
         map.insert_unchecked(Property::IsEnabled, self.is_enabled.into_value());
@@ -12405,6 +12766,40 @@ impl IntoValue for Domain {
             Property::ReportAddressUri,
             self.report_address_uri.into_value(),
         );
+        map.insert_unchecked(Property::Services, self.services.into_value());
+        map.insert_unchecked(Property::ProviderInfo, self.provider_info.into_value());
+        map.insert_unchecked(Property::DisclaimerText, self.disclaimer_text.into_value());
+        map.insert_unchecked(Property::DisclaimerHtml, self.disclaimer_html.into_value());
+        map.insert_unchecked(
+            Property::DisclaimerInbound,
+            self.disclaimer_inbound.into_value(),
+        );
+        map.insert_unchecked(
+            Property::DisclaimerWrapSigned,
+            self.disclaimer_wrap_signed.into_value(),
+        );
+        map.insert_unchecked(
+            Property::SubAddressingFolders,
+            self.sub_addressing_folders.into_value(),
+        );
+        map.insert_unchecked(
+            Property::SubAddressingCreateFolders,
+            self.sub_addressing_create_folders.into_value(),
+        );
+        map.insert_unchecked(Property::RelayVerify, self.relay_verify.into_value());
+        map.insert_unchecked(
+            Property::RelayVerifyHost,
+            self.relay_verify_host.into_value(),
+        );
+        map.insert_unchecked(Property::RelayVerifyUrl, self.relay_verify_url.into_value());
+        map.insert_unchecked(
+            Property::RelayVerifyCacheTtl,
+            self.relay_verify_cache_ttl.into_value(),
+        );
+        map.insert_unchecked(
+            Property::EncryptionPolicy,
+            self.encryption_policy.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -12444,6 +12839,31 @@ impl RegistryJsonPropertyPatch for Domain {
             Some(Property::ReportAddressUri) => self
                 .report_address_uri
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::Services) => self.services.patch(pointer, value),
+            Some(Property::ProviderInfo) => self.provider_info.patch(pointer, value),
+            Some(Property::DisclaimerText) => self.disclaimer_text.patch(pointer, value),
+            Some(Property::DisclaimerHtml) => self.disclaimer_html.patch(pointer, value),
+            Some(Property::DisclaimerInbound) => self.disclaimer_inbound.patch(pointer, value),
+            Some(Property::DisclaimerWrapSigned) => {
+                self.disclaimer_wrap_signed.patch(pointer, value)
+            }
+            Some(Property::SubAddressingFolders) => {
+                self.sub_addressing_folders.patch(pointer, value)
+            }
+            Some(Property::SubAddressingCreateFolders) => {
+                self.sub_addressing_create_folders.patch(pointer, value)
+            }
+            Some(Property::RelayVerify) => self.relay_verify.patch(pointer, value),
+            Some(Property::RelayVerifyHost) => self
+                .relay_verify_host
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::RelayVerifyUrl) => self
+                .relay_verify_url
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::RelayVerifyCacheTtl) => {
+                self.relay_verify_cache_ttl.patch(pointer, value)
+            }
+            Some(Property::EncryptionPolicy) => self.encryption_policy.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -12466,6 +12886,13 @@ impl ObjectImpl for DsnReportSettings {
         value.validate(errors);
         let value = &self.dkim_sign_domain;
         value.validate(errors);
+        let value = &self.suppress;
+        value.validate(errors);
+        if let Some(value) = &self.rate_limit {
+            value.validate(errors);
+        }
+        let value = &self.delay_subject;
+        value.validate(errors);
         errors.len() == neb
     }
 
@@ -12512,11 +12939,39 @@ impl DsnReportSettings {
         }
     }
 
+    pub fn ctx_suppress(&self) -> ExpressionContext<'_> {
+        ExpressionContext {
+            expr: &self.suppress,
+            default: Some(Expression {
+                else_: "false".to_string(),
+                ..Default::default()
+            }),
+            property: Property::Suppress,
+            allowed_variables: MTA_QUEUE_MESSAGE_VARIABLE,
+            allowed_constants: &[],
+        }
+    }
+
+    pub fn ctx_delay_subject(&self) -> ExpressionContext<'_> {
+        ExpressionContext {
+            expr: &self.delay_subject,
+            default: Some(Expression {
+                else_: "'Warning: Delay in message delivery'".to_string(),
+                ..Default::default()
+            }),
+            property: Property::DelaySubject,
+            allowed_variables: MTA_QUEUE_MESSAGE_VARIABLE,
+            allowed_constants: &[],
+        }
+    }
+
     pub fn expression_ctxs(&self) -> Vec<ExpressionContext<'_>> {
         vec![
             self.ctx_from_address(),
             self.ctx_from_name(),
             self.ctx_dkim_sign_domain(),
+            self.ctx_suppress(),
+            self.ctx_delay_subject(),
         ]
     }
 }
@@ -12526,6 +12981,11 @@ impl Pickle for DsnReportSettings {
         self.from_address.pickle(out);
         self.from_name.pickle(out);
         self.dkim_sign_domain.pickle(out);
+        self.suppress.pickle(out);
+        self.suppress_list_traffic.pickle(out);
+        self.rate_limit.pickle(out);
+        self.delay_subject.pickle(out);
+        self.delay_template.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -12533,6 +12993,11 @@ impl Pickle for DsnReportSettings {
         this.from_address = Pickle::unpickle(stream)?;
         this.from_name = Pickle::unpickle(stream)?;
         this.dkim_sign_domain = Pickle::unpickle(stream)?;
+        this.suppress = Pickle::unpickle(stream)?;
+        this.suppress_list_traffic = Pickle::unpickle(stream)?;
+        this.rate_limit = Pickle::unpickle(stream)?;
+        this.delay_subject = Pickle::unpickle(stream)?;
+        this.delay_template = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -12552,16 +13017,35 @@ impl Default for DsnReportSettings {
                 else_: "system('domain')".to_string(),
                 ..Default::default()
             },
+            suppress: Expression {
+                else_: "false".to_string(),
+                ..Default::default()
+            },
+            suppress_list_traffic: true,
+            rate_limit: Default::default(),
+            delay_subject: Expression {
+                else_: "'Warning: Delay in message delivery'".to_string(),
+                ..Default::default()
+            },
+            delay_template: Default::default(),
         }
     }
 }
 
 impl IntoValue for DsnReportSettings {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(5);
+        let mut map = jmap_tools::Map::with_capacity(10);
         map.insert_unchecked(Property::FromAddress, self.from_address.into_value());
         map.insert_unchecked(Property::FromName, self.from_name.into_value());
         map.insert_unchecked(Property::DkimSignDomain, self.dkim_sign_domain.into_value());
+        map.insert_unchecked(Property::Suppress, self.suppress.into_value());
+        map.insert_unchecked(
+            Property::SuppressListTraffic,
+            self.suppress_list_traffic.into_value(),
+        );
+        map.insert_unchecked(Property::RateLimit, self.rate_limit.into_value());
+        map.insert_unchecked(Property::DelaySubject, self.delay_subject.into_value());
+        map.insert_unchecked(Property::DelayTemplate, self.delay_template.into_value());
         JmapValue::Object(map)
     }
 }
@@ -12576,6 +13060,11 @@ impl RegistryJsonPropertyPatch for DsnReportSettings {
             Some(Property::FromAddress) => self.from_address.patch(pointer, value),
             Some(Property::FromName) => self.from_name.patch(pointer, value),
             Some(Property::DkimSignDomain) => self.dkim_sign_domain.patch(pointer, value),
+            Some(Property::Suppress) => self.suppress.patch(pointer, value),
+            Some(Property::SuppressListTraffic) => self.suppress_list_traffic.patch(pointer, value),
+            Some(Property::RateLimit) => self.rate_limit.patch(pointer, value),
+            Some(Property::DelaySubject) => self.delay_subject.patch(pointer, value),
+            Some(Property::DelayTemplate) => self.delay_template.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -12762,6 +13251,10 @@ impl ObjectImpl for Email {
                 errors.push(ValidationError::min_value(Property::MaxPublicKeys, 1));
             }
         }
+        let value = &self.public_folder_name;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::PublicFolderName));
+        }
         errors.len() == neb
     }
 
@@ -12784,6 +13277,8 @@ impl Pickle for Email {
         self.max_mailboxes.pickle(out);
         self.max_masked_addresses.pickle(out);
         self.max_public_keys.pickle(out);
+        self.folder_policies.pickle(out);
+        self.public_folder_name.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -12802,6 +13297,8 @@ impl Pickle for Email {
         this.max_mailboxes = Pickle::unpickle(stream)?;
         this.max_masked_addresses = Pickle::unpickle(stream)?;
         this.max_public_keys = Pickle::unpickle(stream)?;
+        this.folder_policies = Pickle::unpickle(stream)?;
+        this.public_folder_name = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -12823,13 +13320,15 @@ impl Default for Email {
             max_mailboxes: Some(250u64),
             max_masked_addresses: Some(5u64),
             max_public_keys: Some(5u64),
+            folder_policies: Default::default(),
+            public_folder_name: "Public Folders".to_string(),
         }
     }
 }
 
 impl IntoValue for Email {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(16);
+        let mut map = jmap_tools::Map::with_capacity(18);
         map.insert_unchecked(
             Property::MaxAttachmentSize,
             self.max_attachment_size.into_value(),
@@ -12862,6 +13361,11 @@ impl IntoValue for Email {
             self.max_masked_addresses.into_value(),
         );
         map.insert_unchecked(Property::MaxPublicKeys, self.max_public_keys.into_value());
+        map.insert_unchecked(Property::FolderPolicies, self.folder_policies.into_value());
+        map.insert_unchecked(
+            Property::PublicFolderName,
+            self.public_folder_name.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -12891,6 +13395,10 @@ impl RegistryJsonPropertyPatch for Email {
             Some(Property::MaxMailboxes) => self.max_mailboxes.patch(pointer, value),
             Some(Property::MaxMaskedAddresses) => self.max_masked_addresses.patch(pointer, value),
             Some(Property::MaxPublicKeys) => self.max_public_keys.patch(pointer, value),
+            Some(Property::FolderPolicies) => self.folder_policies.patch(pointer, value),
+            Some(Property::PublicFolderName) => self
+                .public_folder_name
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -13012,6 +13520,7 @@ impl Pickle for EmailFolder {
         self.create.pickle(out);
         self.subscribe.pickle(out);
         self.aliases.pickle(out);
+        self.protect.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -13020,6 +13529,7 @@ impl Pickle for EmailFolder {
         this.create = Pickle::unpickle(stream)?;
         this.subscribe = Pickle::unpickle(stream)?;
         this.aliases = Pickle::unpickle(stream)?;
+        this.protect = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -13031,17 +13541,19 @@ impl Default for EmailFolder {
             create: true,
             subscribe: true,
             aliases: Default::default(),
+            protect: false,
         }
     }
 }
 
 impl IntoValue for EmailFolder {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(6);
+        let mut map = jmap_tools::Map::with_capacity(7);
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::Create, self.create.into_value());
         map.insert_unchecked(Property::Subscribe, self.subscribe.into_value());
         map.insert_unchecked(Property::Aliases, self.aliases.into_value());
+        map.insert_unchecked(Property::Protect, self.protect.into_value());
         JmapValue::Object(map)
     }
 }
@@ -13061,6 +13573,62 @@ impl RegistryJsonPropertyPatch for EmailFolder {
             Some(Property::Aliases) => self
                 .aliases
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::Protect) => self.protect.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
+impl Pickle for EmailFolderPolicy {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.create.pickle(out);
+        self.subscribe.pickle(out);
+        self.protect.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.create = Pickle::unpickle(stream)?;
+        this.subscribe = Pickle::unpickle(stream)?;
+        this.protect = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for EmailFolderPolicy {
+    fn default() -> Self {
+        Self {
+            create: true,
+            subscribe: true,
+            protect: false,
+        }
+    }
+}
+
+impl IntoValue for EmailFolderPolicy {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(5);
+        map.insert_unchecked(Property::Create, self.create.into_value());
+        map.insert_unchecked(Property::Subscribe, self.subscribe.into_value());
+        map.insert_unchecked(Property::Protect, self.protect.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for EmailFolderPolicy {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Create) => self.create.patch(pointer, value),
+            Some(Property::Subscribe) => self.subscribe.patch(pointer, value),
+            Some(Property::Protect) => self.protect.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -13680,6 +14248,478 @@ impl RegistryJsonPropertyPatch for FileSystemStore {
     }
 }
 
+impl FirewallSync {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        match self {
+            FirewallSync::Disabled => true,
+            FirewallSync::Nftables(inner) => inner.validate(errors),
+            FirewallSync::Ipset(inner) => inner.validate(errors),
+            FirewallSync::Webhook(inner) => inner.validate(errors),
+            FirewallSync::Crowdsec(inner) => inner.validate(errors),
+        }
+    }
+}
+
+impl Default for FirewallSync {
+    fn default() -> Self {
+        FirewallSync::Disabled
+    }
+}
+
+impl Pickle for FirewallSync {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        match self {
+            FirewallSync::Disabled => {
+                0u16.pickle(out);
+            }
+            FirewallSync::Nftables(inner) => {
+                1u16.pickle(out);
+                inner.pickle(out);
+            }
+            FirewallSync::Ipset(inner) => {
+                2u16.pickle(out);
+                inner.pickle(out);
+            }
+            FirewallSync::Webhook(inner) => {
+                3u16.pickle(out);
+                inner.pickle(out);
+            }
+            FirewallSync::Crowdsec(inner) => {
+                4u16.pickle(out);
+                inner.pickle(out);
+            }
+        }
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        match u16::unpickle(stream)? {
+            0 => Some(FirewallSync::Disabled),
+            1 => Pickle::unpickle(stream).map(FirewallSync::Nftables),
+            2 => Pickle::unpickle(stream).map(FirewallSync::Ipset),
+            3 => Pickle::unpickle(stream).map(FirewallSync::Webhook),
+            4 => Pickle::unpickle(stream).map(FirewallSync::Crowdsec),
+            _ => None,
+        }
+    }
+}
+
+impl IntoValue for FirewallSync {
+    fn into_value(self) -> JmapValue<'static> {
+        match self {
+            FirewallSync::Disabled => {
+                let mut obj = jmap_tools::Map::new();
+                obj.insert_unchecked(Property::Type, JmapValue::Str("Disabled".into()));
+                JmapValue::Object(obj)
+            }
+            FirewallSync::Nftables(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("Nftables".into()));
+                obj
+            }
+            FirewallSync::Ipset(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("Ipset".into()));
+                obj
+            }
+            FirewallSync::Webhook(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("Webhook".into()));
+                obj
+            }
+            FirewallSync::Crowdsec(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("Crowdsec".into()));
+                obj
+            }
+        }
+    }
+}
+
+impl RegistryJsonPatch for FirewallSync {
+    fn patch<'x>(
+        &mut self,
+        pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        if !pointer.has_next() {
+            match object_type(&pointer, &value)? {
+                FirewallSyncType::Disabled => *self = FirewallSync::Disabled,
+                FirewallSyncType::Nftables => *self = FirewallSync::Nftables(Default::default()),
+                FirewallSyncType::Ipset => *self = FirewallSync::Ipset(Default::default()),
+                FirewallSyncType::Webhook => *self = FirewallSync::Webhook(Default::default()),
+                FirewallSyncType::Crowdsec => *self = FirewallSync::Crowdsec(Default::default()),
+            }
+        }
+        match self {
+            FirewallSync::Disabled => pointer.assert_eof(),
+            FirewallSync::Nftables(inner) => inner.patch(pointer, value),
+            FirewallSync::Ipset(inner) => inner.patch(pointer, value),
+            FirewallSync::Webhook(inner) => inner.patch(pointer, value),
+            FirewallSync::Crowdsec(inner) => inner.patch(pointer, value),
+        }
+    }
+}
+
+impl FirewallSync {
+    pub fn object_type(&self) -> FirewallSyncType {
+        match self {
+            FirewallSync::Disabled => FirewallSyncType::Disabled,
+            FirewallSync::Nftables(_) => FirewallSyncType::Nftables,
+            FirewallSync::Ipset(_) => FirewallSyncType::Ipset,
+            FirewallSync::Webhook(_) => FirewallSyncType::Webhook,
+            FirewallSync::Crowdsec(_) => FirewallSyncType::Crowdsec,
+        }
+    }
+}
+
+impl FirewallSyncNftables {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.nft_family;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::NftFamily));
+        }
+        let value = &self.nft_table;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::NftTable));
+        }
+        let value = &self.set_ipv4;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::SetIpv4));
+        }
+        let value = &self.set_ipv6;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::SetIpv6));
+        }
+        errors.len() == neb
+    }
+}
+
+impl Pickle for FirewallSyncNftables {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.nft_family.pickle(out);
+        self.nft_table.pickle(out);
+        self.set_ipv4.pickle(out);
+        self.set_ipv6.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.nft_family = Pickle::unpickle(stream)?;
+        this.nft_table = Pickle::unpickle(stream)?;
+        this.set_ipv4 = Pickle::unpickle(stream)?;
+        this.set_ipv6 = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for FirewallSyncNftables {
+    fn default() -> Self {
+        Self {
+            nft_family: "inet".to_string(),
+            nft_table: "filter".to_string(),
+            set_ipv4: "stalwart_blocked4".to_string(),
+            set_ipv6: "stalwart_blocked6".to_string(),
+        }
+    }
+}
+
+impl IntoValue for FirewallSyncNftables {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(6);
+        map.insert_unchecked(Property::NftFamily, self.nft_family.into_value());
+        map.insert_unchecked(Property::NftTable, self.nft_table.into_value());
+        map.insert_unchecked(Property::SetIpv4, self.set_ipv4.into_value());
+        map.insert_unchecked(Property::SetIpv6, self.set_ipv6.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for FirewallSyncNftables {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::NftFamily) => self
+                .nft_family
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::NftTable) => self
+                .nft_table
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::SetIpv4) => self
+                .set_ipv4
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::SetIpv6) => self
+                .set_ipv6
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
+impl FirewallSyncIpset {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.set_ipv4;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::SetIpv4));
+        }
+        let value = &self.set_ipv6;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::SetIpv6));
+        }
+        errors.len() == neb
+    }
+}
+
+impl Pickle for FirewallSyncIpset {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.set_ipv4.pickle(out);
+        self.set_ipv6.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.set_ipv4 = Pickle::unpickle(stream)?;
+        this.set_ipv6 = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for FirewallSyncIpset {
+    fn default() -> Self {
+        Self {
+            set_ipv4: "stalwart_blocked4".to_string(),
+            set_ipv6: "stalwart_blocked6".to_string(),
+        }
+    }
+}
+
+impl IntoValue for FirewallSyncIpset {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(4);
+        map.insert_unchecked(Property::SetIpv4, self.set_ipv4.into_value());
+        map.insert_unchecked(Property::SetIpv6, self.set_ipv6.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for FirewallSyncIpset {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::SetIpv4) => self
+                .set_ipv4
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::SetIpv6) => self
+                .set_ipv6
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
+impl FirewallSyncWebhook {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.url;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Url));
+        }
+        let value = &self.http_auth;
+        value.validate(errors);
+        let value = &self.http_headers;
+        for value in value.values() {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::HttpHeaders));
+            }
+        }
+        errors.len() == neb
+    }
+}
+
+impl Pickle for FirewallSyncWebhook {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.url.pickle(out);
+        self.http_auth.pickle(out);
+        self.http_headers.pickle(out);
+        self.timeout.pickle(out);
+        self.allow_invalid_certs.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.url = Pickle::unpickle(stream)?;
+        this.http_auth = Pickle::unpickle(stream)?;
+        this.http_headers = Pickle::unpickle(stream)?;
+        this.timeout = Pickle::unpickle(stream)?;
+        this.allow_invalid_certs = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for FirewallSyncWebhook {
+    fn default() -> Self {
+        Self {
+            url: Default::default(),
+            http_auth: Default::default(),
+            http_headers: Default::default(),
+            timeout: Duration::from_millis(30000),
+            allow_invalid_certs: false,
+        }
+    }
+}
+
+impl IntoValue for FirewallSyncWebhook {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(7);
+        map.insert_unchecked(Property::Url, self.url.into_value());
+        map.insert_unchecked(Property::HttpAuth, self.http_auth.into_value());
+        map.insert_unchecked(Property::HttpHeaders, self.http_headers.into_value());
+        map.insert_unchecked(Property::Timeout, self.timeout.into_value());
+        map.insert_unchecked(
+            Property::AllowInvalidCerts,
+            self.allow_invalid_certs.into_value(),
+        );
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for FirewallSyncWebhook {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Url) => self
+                .url
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::HttpAuth) => self.http_auth.patch(pointer, value),
+            Some(Property::HttpHeaders) => self
+                .http_headers
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::Timeout) => self.timeout.patch(pointer, value),
+            Some(Property::AllowInvalidCerts) => self.allow_invalid_certs.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
+impl FirewallSyncCrowdsec {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.url;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Url));
+        }
+        let value = &self.machine_id;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::MachineId));
+        }
+        let value = &self.secret;
+        value.validate(errors);
+        errors.len() == neb
+    }
+}
+
+impl Pickle for FirewallSyncCrowdsec {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.url.pickle(out);
+        self.machine_id.pickle(out);
+        self.secret.pickle(out);
+        self.timeout.pickle(out);
+        self.allow_invalid_certs.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.url = Pickle::unpickle(stream)?;
+        this.machine_id = Pickle::unpickle(stream)?;
+        this.secret = Pickle::unpickle(stream)?;
+        this.timeout = Pickle::unpickle(stream)?;
+        this.allow_invalid_certs = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for FirewallSyncCrowdsec {
+    fn default() -> Self {
+        Self {
+            url: "http://127.0.0.1:8080".to_string(),
+            machine_id: Default::default(),
+            secret: Default::default(),
+            timeout: Duration::from_millis(30000),
+            allow_invalid_certs: false,
+        }
+    }
+}
+
+impl IntoValue for FirewallSyncCrowdsec {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(7);
+        map.insert_unchecked(Property::Url, self.url.into_value());
+        map.insert_unchecked(Property::MachineId, self.machine_id.into_value());
+        map.insert_unchecked(Property::Secret, self.secret.into_value());
+        map.insert_unchecked(Property::Timeout, self.timeout.into_value());
+        map.insert_unchecked(
+            Property::AllowInvalidCerts,
+            self.allow_invalid_certs.into_value(),
+        );
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for FirewallSyncCrowdsec {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Url) => self
+                .url
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::MachineId) => self
+                .machine_id
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::Secret) => self.secret.patch(pointer, value),
+            Some(Property::Timeout) => self.timeout.patch(pointer, value),
+            Some(Property::AllowInvalidCerts) => self.allow_invalid_certs.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl FoundationDbStore {
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
         let neb = errors.len();
@@ -13712,6 +14752,11 @@ impl FoundationDbStore {
                 ));
             }
         }
+        if let Some(value) = &self.read_cache_size {
+            if *value < 1048576 {
+                errors.push(ValidationError::min_value(Property::ReadCacheSize, 1048576));
+            }
+        }
         errors.len() == neb
     }
 }
@@ -13724,6 +14769,7 @@ impl Pickle for FoundationDbStore {
         self.transaction_retry_delay.pickle(out);
         self.transaction_retry_limit.pickle(out);
         self.transaction_timeout.pickle(out);
+        self.read_cache_size.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -13734,6 +14780,7 @@ impl Pickle for FoundationDbStore {
         this.transaction_retry_delay = Pickle::unpickle(stream)?;
         this.transaction_retry_limit = Pickle::unpickle(stream)?;
         this.transaction_timeout = Pickle::unpickle(stream)?;
+        this.read_cache_size = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -13747,13 +14794,14 @@ impl Default for FoundationDbStore {
             transaction_retry_delay: Default::default(),
             transaction_retry_limit: Default::default(),
             transaction_timeout: Default::default(),
+            read_cache_size: Default::default(),
         }
     }
 }
 
 impl IntoValue for FoundationDbStore {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(8);
+        let mut map = jmap_tools::Map::with_capacity(9);
         map.insert_unchecked(Property::ClusterFile, self.cluster_file.into_value());
         map.insert_unchecked(Property::DatacenterId, self.datacenter_id.into_value());
         map.insert_unchecked(Property::MachineId, self.machine_id.into_value());
@@ -13769,6 +14817,7 @@ impl IntoValue for FoundationDbStore {
             Property::TransactionTimeout,
             self.transaction_timeout.into_value(),
         );
+        map.insert_unchecked(Property::ReadCacheSize, self.read_cache_size.into_value());
         JmapValue::Object(map)
     }
 }
@@ -13796,6 +14845,7 @@ impl RegistryJsonPropertyPatch for FoundationDbStore {
                 self.transaction_retry_limit.patch(pointer, value)
             }
             Some(Property::TransactionTimeout) => self.transaction_timeout.patch(pointer, value),
+            Some(Property::ReadCacheSize) => self.read_cache_size.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -13962,6 +15012,8 @@ impl Pickle for GroupAccount {
         self.aliases.pickle(out);
         self.locale.pickle(out);
         self.time_zone.pickle(out);
+        self.legal_hold.pickle(out);
+        self.public_folders.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -13977,6 +15029,8 @@ impl Pickle for GroupAccount {
         this.aliases = Pickle::unpickle(stream)?;
         this.locale = Pickle::unpickle(stream)?;
         this.time_zone = Pickle::unpickle(stream)?;
+        this.legal_hold = Pickle::unpickle(stream)?;
+        this.public_folders = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -13995,13 +15049,15 @@ impl Default for GroupAccount {
             aliases: Default::default(),
             locale: Locale::EnUS,
             time_zone: Default::default(),
+            legal_hold: false,
+            public_folders: false,
         }
     }
 }
 
 impl IntoValue for GroupAccount {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(13);
+        let mut map = jmap_tools::Map::with_capacity(15);
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::DomainId, self.domain_id.into_value());
         map.insert_unchecked(Property::Description, self.description.into_value());
@@ -14013,6 +15069,8 @@ impl IntoValue for GroupAccount {
         map.insert_unchecked(Property::Aliases, self.aliases.into_value());
         map.insert_unchecked(Property::Locale, self.locale.into_value());
         map.insert_unchecked(Property::TimeZone, self.time_zone.into_value());
+        map.insert_unchecked(Property::LegalHold, self.legal_hold.into_value());
+        map.insert_unchecked(Property::PublicFolders, self.public_folders.into_value());
         JmapValue::Object(map)
     }
 }
@@ -14042,6 +15100,8 @@ impl RegistryJsonPropertyPatch for GroupAccount {
             Some(Property::Aliases) => self.aliases.patch(pointer, value),
             Some(Property::Locale) => self.locale.patch(pointer, value),
             Some(Property::TimeZone) => self.time_zone.patch(pointer, value),
+            Some(Property::LegalHold) => self.legal_hold.patch(pointer, value),
+            Some(Property::PublicFolders) => self.public_folders.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -14072,6 +15132,48 @@ impl ObjectImpl for Http {
                 errors.push(ValidationError::required(Property::ResponseHeaders));
             }
         }
+        let value = &self.http2_max_concurrent_streams;
+        if *value < 1 {
+            errors.push(ValidationError::min_value(
+                Property::Http2MaxConcurrentStreams,
+                1,
+            ));
+        }
+        let value = &self.compression_content_types;
+        for value in value.iter() {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::CompressionContentTypes));
+            }
+        }
+        let value = &self.cors_allowed_origins;
+        value.validate(errors);
+        let value = &self.cors_allowed_methods;
+        value.validate(errors);
+        let value = &self.cors_allowed_headers;
+        value.validate(errors);
+        let value = &self.cors_allow_credentials;
+        value.validate(errors);
+        if let Some(value) = &self.health_queue_threshold {
+            if *value < 1 {
+                errors.push(ValidationError::min_value(
+                    Property::HealthQueueThreshold,
+                    1,
+                ));
+            }
+        }
+        let value = &self.health_degraded_weight;
+        if *value < 1 {
+            errors.push(ValidationError::min_value(
+                Property::HealthDegradedWeight,
+                1,
+            ));
+        }
+        if *value > 100 {
+            errors.push(ValidationError::max_value(
+                Property::HealthDegradedWeight,
+                100,
+            ));
+        }
         errors.len() == neb
     }
 
@@ -14092,8 +15194,64 @@ impl Http {
         }
     }
 
+    pub fn ctx_cors_allowed_origins(&self) -> ExpressionContext<'_> {
+        ExpressionContext {
+            expr: &self.cors_allowed_origins,
+            default: None,
+            property: Property::CorsAllowedOrigins,
+            allowed_variables: HTTP_VARIABLE,
+            allowed_constants: &[],
+        }
+    }
+
+    pub fn ctx_cors_allowed_methods(&self) -> ExpressionContext<'_> {
+        ExpressionContext {
+            expr: &self.cors_allowed_methods,
+            default: Some(Expression {
+                else_: "['GET', 'POST', 'PUT', 'PATCH', 'DELETE', 'HEAD', 'OPTIONS']".to_string(),
+                ..Default::default()
+            }),
+            property: Property::CorsAllowedMethods,
+            allowed_variables: HTTP_VARIABLE,
+            allowed_constants: &[],
+        }
+    }
+
+    pub fn ctx_cors_allowed_headers(&self) -> ExpressionContext<'_> {
+        ExpressionContext {
+            expr: &self.cors_allowed_headers,
+            default: Some(Expression {
+                else_: "['Authorization', 'Content-Type', 'Accept', 'X-Requested-With']"
+                    .to_string(),
+                ..Default::default()
+            }),
+            property: Property::CorsAllowedHeaders,
+            allowed_variables: HTTP_VARIABLE,
+            allowed_constants: &[],
+        }
+    }
+
+    pub fn ctx_cors_allow_credentials(&self) -> ExpressionContext<'_> {
+        ExpressionContext {
+            expr: &self.cors_allow_credentials,
+            default: Some(Expression {
+                else_: "false".to_string(),
+                ..Default::default()
+            }),
+            property: Property::CorsAllowCredentials,
+            allowed_variables: HTTP_VARIABLE,
+            allowed_constants: &[],
+        }
+    }
+
     pub fn expression_ctxs(&self) -> Vec<ExpressionContext<'_>> {
-        vec![self.ctx_allowed_endpoints()]
+        vec![
+            self.ctx_allowed_endpoints(),
+            self.ctx_cors_allowed_origins(),
+            self.ctx_cors_allowed_methods(),
+            self.ctx_cors_allowed_headers(),
+            self.ctx_cors_allow_credentials(),
+        ]
     }
 }
 
@@ -14106,6 +15264,20 @@ impl Pickle for Http {
         self.use_permissive_cors.pickle(out);
         self.response_headers.pickle(out);
         self.use_x_forwarded.pickle(out);
+        self.enable_http2.pickle(out);
+        self.enable_h2c.pickle(out);
+        self.http2_max_concurrent_streams.pickle(out);
+        self.enable_compression.pickle(out);
+        self.compression_min_size.pickle(out);
+        self.compression_content_types.pickle(out);
+        self.cors_allowed_origins.pickle(out);
+        self.cors_allowed_methods.pickle(out);
+        self.cors_allowed_headers.pickle(out);
+        self.cors_allow_credentials.pickle(out);
+        self.cors_max_age.pickle(out);
+        self.health_store_timeout.pickle(out);
+        self.health_queue_threshold.pickle(out);
+        self.health_degraded_weight.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -14117,6 +15289,20 @@ impl Pickle for Http {
         this.use_permissive_cors = Pickle::unpickle(stream)?;
         this.response_headers = Pickle::unpickle(stream)?;
         this.use_x_forwarded = Pickle::unpickle(stream)?;
+        this.enable_http2 = Pickle::unpickle(stream)?;
+        this.enable_h2c = Pickle::unpickle(stream)?;
+        this.http2_max_concurrent_streams = Pickle::unpickle(stream)?;
+        this.enable_compression = Pickle::unpickle(stream)?;
+        this.compression_min_size = Pickle::unpickle(stream)?;
+        this.compression_content_types = Pickle::unpickle(stream)?;
+        this.cors_allowed_origins = Pickle::unpickle(stream)?;
+        this.cors_allowed_methods = Pickle::unpickle(stream)?;
+        this.cors_allowed_headers = Pickle::unpickle(stream)?;
+        this.cors_allow_credentials = Pickle::unpickle(stream)?;
+        this.cors_max_age = Pickle::unpickle(stream)?;
+        this.health_store_timeout = Pickle::unpickle(stream)?;
+        this.health_queue_threshold = Pickle::unpickle(stream)?;
+        this.health_degraded_weight = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -14140,13 +15326,43 @@ impl Default for Http {
             use_permissive_cors: false,
             response_headers: Default::default(),
             use_x_forwarded: false,
+            enable_http2: true,
+            enable_h2c: false,
+            http2_max_concurrent_streams: 100u64,
+            enable_compression: true,
+            compression_min_size: 1024u64,
+            compression_content_types: Map::new(vec![
+                "text/*".to_string(),
+                "application/json".to_string(),
+                "application/xml".to_string(),
+                "application/javascript".to_string(),
+                "image/svg+xml".to_string(),
+            ]),
+            cors_allowed_origins: Default::default(),
+            cors_allowed_methods: Expression {
+                else_: "['GET', 'POST', 'PUT', 'PATCH', 'DELETE', 'HEAD', 'OPTIONS']".to_string(),
+                ..Default::default()
+            },
+            cors_allowed_headers: Expression {
+                else_: "['Authorization', 'Content-Type', 'Accept', 'X-Requested-With']"
+                    .to_string(),
+                ..Default::default()
+            },
+            cors_allow_credentials: Expression {
+                else_: "false".to_string(),
+                ..Default::default()
+            },
+            cors_max_age: Duration::from_millis(3600000),
+            health_store_timeout: Duration::from_millis(2000),
+            health_queue_threshold: None,
+            health_degraded_weight: 50u64,
         }
     }
 }
 
 impl IntoValue for Http {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(9);
+        let mut map = jmap_tools::Map::with_capacity(21);
         map.insert_unchecked(
             Property::RateLimitAuthenticated,
             self.rate_limit_authenticated.into_value(),
@@ -14169,6 +15385,53 @@ impl IntoValue for Http {
             self.response_headers.into_value(),
         );
         map.insert_unchecked(Property::UseXForwarded, self.use_x_forwarded.into_value());
+        map.insert_unchecked(Property::EnableHttp2, self.enable_http2.into_value());
+        map.insert_unchecked(Property::EnableH2c, self.enable_h2c.into_value());
+        map.insert_unchecked(
+            Property::Http2MaxConcurrentStreams,
+            self.http2_max_concurrent_streams.into_value(),
+        );
+        map.insert_unchecked(
+            Property::EnableCompression,
+            self.enable_compression.into_value(),
+        );
+        map.insert_unchecked(
+            Property::CompressionMinSize,
+            self.compression_min_size.into_value(),
+        );
+        map.insert_unchecked(
+            Property::CompressionContentTypes,
+            self.compression_content_types.into_value(),
+        );
+        map.insert_unchecked(
+            Property::CorsAllowedOrigins,
+            self.cors_allowed_origins.into_value(),
+        );
+        map.insert_unchecked(
+            Property::CorsAllowedMethods,
+            self.cors_allowed_methods.into_value(),
+        );
+        map.insert_unchecked(
+            Property::CorsAllowedHeaders,
+            self.cors_allowed_headers.into_value(),
+        );
+        map.insert_unchecked(
+            Property::CorsAllowCredentials,
+            self.cors_allow_credentials.into_value(),
+        );
+        map.insert_unchecked(Property::CorsMaxAge, self.cors_max_age.into_value());
+        map.insert_unchecked(
+            Property::HealthStoreTimeout,
+            self.health_store_timeout.into_value(),
+        );
+        map.insert_unchecked(
+            Property::HealthQueueThreshold,
+            self.health_queue_threshold.into_value(),
+        );
+        map.insert_unchecked(
+            Property::HealthDegradedWeight,
+            self.health_degraded_weight.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -14191,6 +15454,30 @@ impl RegistryJsonPropertyPatch for Http {
                 .response_headers
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
             Some(Property::UseXForwarded) => self.use_x_forwarded.patch(pointer, value),
+            Some(Property::EnableHttp2) => self.enable_http2.patch(pointer, value),
+            Some(Property::EnableH2c) => self.enable_h2c.patch(pointer, value),
+            Some(Property::Http2MaxConcurrentStreams) => {
+                self.http2_max_concurrent_streams.patch(pointer, value)
+            }
+            Some(Property::EnableCompression) => self.enable_compression.patch(pointer, value),
+            Some(Property::CompressionMinSize) => self.compression_min_size.patch(pointer, value),
+            Some(Property::CompressionContentTypes) => self
+                .compression_content_types
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::CorsAllowedOrigins) => self.cors_allowed_origins.patch(pointer, value),
+            Some(Property::CorsAllowedMethods) => self.cors_allowed_methods.patch(pointer, value),
+            Some(Property::CorsAllowedHeaders) => self.cors_allowed_headers.patch(pointer, value),
+            Some(Property::CorsAllowCredentials) => {
+                self.cors_allow_credentials.patch(pointer, value)
+            }
+            Some(Property::CorsMaxAge) => self.cors_max_age.patch(pointer, value),
+            Some(Property::HealthStoreTimeout) => self.health_store_timeout.patch(pointer, value),
+            Some(Property::HealthQueueThreshold) => {
+                self.health_queue_threshold.patch(pointer, value)
+            }
+            Some(Property::HealthDegradedWeight) => {
+                self.health_degraded_weight.patch(pointer, value)
+            }
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -14467,6 +15754,12 @@ impl ObjectImpl for HttpForm {
                 errors.push(ValidationError::required(Property::FieldSubject));
             }
         }
+        let value = &self.captcha_secret;
+        value.validate(errors);
+        let value = &self.forms;
+        for value in value.values() {
+            value.validate(errors);
+        }
         errors.len() == neb
     }
 
@@ -14487,6 +15780,9 @@ impl Pickle for HttpForm {
         self.default_subject.pickle(out);
         self.field_subject.pickle(out);
         self.validate_domain.pickle(out);
+        self.captcha_provider.pickle(out);
+        self.captcha_secret.pickle(out);
+        self.forms.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -14503,6 +15799,9 @@ impl Pickle for HttpForm {
         this.default_subject = Pickle::unpickle(stream)?;
         this.field_subject = Pickle::unpickle(stream)?;
         this.validate_domain = Pickle::unpickle(stream)?;
+        this.captcha_provider = Pickle::unpickle(stream)?;
+        this.captcha_secret = Pickle::unpickle(stream)?;
+        this.forms = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -14525,13 +15824,16 @@ impl Default for HttpForm {
             default_subject: "Contact form submission".to_string(),
             field_subject: Default::default(),
             validate_domain: true,
+            captcha_provider: CaptchaProvider::Disabled,
+            captcha_secret: Default::default(),
+            forms: Default::default(),
         }
     }
 }
 
 impl IntoValue for HttpForm {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(14);
+        let mut map = jmap_tools::Map::with_capacity(17);
         map.insert_unchecked(Property::DeliverTo, self.deliver_to.into_value());
         map.insert_unchecked(
             Property::DefaultFromAddress,
@@ -14547,6 +15849,12 @@ impl IntoValue for HttpForm {
         map.insert_unchecked(Property::DefaultSubject, self.default_subject.into_value());
         map.insert_unchecked(Property::FieldSubject, self.field_subject.into_value());
         map.insert_unchecked(Property::ValidateDomain, self.validate_domain.into_value());
+        map.insert_unchecked(
+            Property::CaptchaProvider,
+            self.captcha_provider.into_value(),
+        );
+        map.insert_unchecked(Property::CaptchaSecret, self.captcha_secret.into_value());
+        map.insert_unchecked(Property::Forms, self.forms.into_value());
         JmapValue::Object(map)
     }
 }
@@ -14586,6 +15894,100 @@ impl RegistryJsonPropertyPatch for HttpForm {
                 .field_subject
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
             Some(Property::ValidateDomain) => self.validate_domain.patch(pointer, value),
+            Some(Property::CaptchaProvider) => self.captcha_provider.patch(pointer, value),
+            Some(Property::CaptchaSecret) => self.captcha_secret.patch(pointer, value),
+            Some(Property::Forms) => self.forms.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
+impl HttpFormRoute {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.deliver_to;
+        for value in value.iter() {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::DeliverTo));
+            }
+        }
+        if value.len() < 1 {
+            errors.push(ValidationError::min_items(Property::DeliverTo, 1));
+        }
+        if let Some(value) = &self.rate_limit {
+            value.validate(errors);
+        }
+        if let Some(value) = &self.subject {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::Subject));
+            }
+        }
+        if let Some(value) = &self.body {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::Body));
+            }
+        }
+        errors.len() == neb
+    }
+}
+
+impl Pickle for HttpFormRoute {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.deliver_to.pickle(out);
+        self.rate_limit.pickle(out);
+        self.subject.pickle(out);
+        self.body.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.deliver_to = Pickle::unpickle(stream)?;
+        this.rate_limit = Pickle::unpickle(stream)?;
+        this.subject = Pickle::unpickle(stream)?;
+        this.body = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for HttpFormRoute {
+    fn default() -> Self {
+        Self {
+            deliver_to: Default::default(),
+            rate_limit: Default::default(),
+            subject: Default::default(),
+            body: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for HttpFormRoute {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(6);
+        map.insert_unchecked(Property::DeliverTo, self.deliver_to.into_value());
+        map.insert_unchecked(Property::RateLimit, self.rate_limit.into_value());
+        map.insert_unchecked(Property::Subject, self.subject.into_value());
+        map.insert_unchecked(Property::Body, self.body.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for HttpFormRoute {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::DeliverTo) => self
+                .deliver_to
+                .patch(pointer.with_validators(&[StringValidator::Email]), value),
+            Some(Property::RateLimit) => self.rate_limit.patch(pointer, value),
+            Some(Property::Subject) => self.subject.patch(pointer, value),
+            Some(Property::Body) => self.body.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -14919,6 +16321,20 @@ impl ObjectImpl for Imap {
         if let Some(value) = &self.max_request_rate {
             value.validate(errors);
         }
+        let value = &self.proxy_imap_port;
+        if *value < 1 {
+            errors.push(ValidationError::min_value(Property::ProxyImapPort, 1));
+        }
+        if *value > 65535 {
+            errors.push(ValidationError::max_value(Property::ProxyImapPort, 65535));
+        }
+        let value = &self.proxy_pop3_port;
+        if *value < 1 {
+            errors.push(ValidationError::min_value(Property::ProxyPop3Port, 1));
+        }
+        if *value > 65535 {
+            errors.push(ValidationError::max_value(Property::ProxyPop3Port, 65535));
+        }
         errors.len() == neb
     }
 
@@ -14932,6 +16348,10 @@ impl Pickle for Imap {
         self.max_concurrent.pickle(out);
         self.max_request_rate.pickle(out);
         self.max_request_size.pickle(out);
+        self.proxy_allow_invalid_certs.pickle(out);
+        self.proxy_imap_port.pickle(out);
+        self.proxy_implicit_tls.pickle(out);
+        self.proxy_pop3_port.pickle(out);
         self.timeout_anonymous.pickle(out);
         self.timeout_authenticated.pickle(out);
         self.timeout_idle.pickle(out);
@@ -14944,6 +16364,10 @@ impl Pickle for Imap {
         this.max_concurrent = Pickle::unpickle(stream)?;
         this.max_request_rate = Pickle::unpickle(stream)?;
         this.max_request_size = Pickle::unpickle(stream)?;
+        this.proxy_allow_invalid_certs = Pickle::unpickle(stream)?;
+        this.proxy_imap_port = Pickle::unpickle(stream)?;
+        this.proxy_implicit_tls = Pickle::unpickle(stream)?;
+        this.proxy_pop3_port = Pickle::unpickle(stream)?;
         this.timeout_anonymous = Pickle::unpickle(stream)?;
         this.timeout_authenticated = Pickle::unpickle(stream)?;
         this.timeout_idle = Pickle::unpickle(stream)?;
@@ -14962,6 +16386,10 @@ impl Default for Imap {
                 period: Duration::from_millis(60000),
             }),
             max_request_size: 52428800,
+            proxy_allow_invalid_certs: false,
+            proxy_imap_port: 993u64,
+            proxy_implicit_tls: true,
+            proxy_pop3_port: 995u64,
             timeout_anonymous: Duration::from_millis(60000),
             timeout_authenticated: Duration::from_millis(1800000),
             timeout_idle: Duration::from_millis(1800000),
@@ -14971,7 +16399,7 @@ impl Default for Imap {
 
 impl IntoValue for Imap {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(10);
+        let mut map = jmap_tools::Map::with_capacity(14);
         map.insert_unchecked(
             Property::AllowPlainTextAuth,
             self.allow_plain_text_auth.into_value(),
@@ -14983,6 +16411,16 @@ impl IntoValue for Imap {
         map.insert_unchecked(Property::MaxConcurrent, self.max_concurrent.into_value());
         map.insert_unchecked(Property::MaxRequestRate, self.max_request_rate.into_value());
         map.insert_unchecked(Property::MaxRequestSize, self.max_request_size.into_value());
+        map.insert_unchecked(
+            Property::ProxyAllowInvalidCerts,
+            self.proxy_allow_invalid_certs.into_value(),
+        );
+        map.insert_unchecked(Property::ProxyImapPort, self.proxy_imap_port.into_value());
+        map.insert_unchecked(
+            Property::ProxyImplicitTls,
+            self.proxy_implicit_tls.into_value(),
+        );
+        map.insert_unchecked(Property::ProxyPop3Port, self.proxy_pop3_port.into_value());
         map.insert_unchecked(
             Property::TimeoutAnonymous,
             self.timeout_anonymous.into_value(),
@@ -15008,6 +16446,12 @@ impl RegistryJsonPropertyPatch for Imap {
             Some(Property::MaxConcurrent) => self.max_concurrent.patch(pointer, value),
             Some(Property::MaxRequestRate) => self.max_request_rate.patch(pointer, value),
             Some(Property::MaxRequestSize) => self.max_request_size.patch(pointer, value),
+            Some(Property::ProxyAllowInvalidCerts) => {
+                self.proxy_allow_invalid_certs.patch(pointer, value)
+            }
+            Some(Property::ProxyImapPort) => self.proxy_imap_port.patch(pointer, value),
+            Some(Property::ProxyImplicitTls) => self.proxy_implicit_tls.patch(pointer, value),
+            Some(Property::ProxyPop3Port) => self.proxy_pop3_port.patch(pointer, value),
             Some(Property::TimeoutAnonymous) => self.timeout_anonymous.patch(pointer, value),
             Some(Property::TimeoutAuthenticated) => {
                 self.timeout_authenticated.patch(pointer, value)
@@ -15325,6 +16769,15 @@ impl ObjectImpl for Jmap {
                 errors.push(ValidationError::min_value(Property::MaxSubscriptions, 1));
             }
         }
+        if let Some(value) = &self.max_concurrent_calls {
+            if *value < 1 {
+                errors.push(ValidationError::min_value(Property::MaxConcurrentCalls, 1));
+            }
+        }
+        let value = &self.max_queued_calls;
+        if *value < 1 {
+            errors.push(ValidationError::min_value(Property::MaxQueuedCalls, 1));
+        }
         errors.len() == neb
     }
 
@@ -15361,6 +16814,9 @@ impl Pickle for Jmap {
         self.websocket_throttle.pickle(out);
         self.websocket_timeout.pickle(out);
         self.max_subscriptions.pickle(out);
+        self.max_concurrent_calls.pickle(out);
+        self.max_queued_calls.pickle(out);
+        self.max_queue_wait.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -15393,6 +16849,9 @@ impl Pickle for Jmap {
         this.websocket_throttle = Pickle::unpickle(stream)?;
         this.websocket_timeout = Pickle::unpickle(stream)?;
         this.max_subscriptions = Pickle::unpickle(stream)?;
+        this.max_concurrent_calls = Pickle::unpickle(stream)?;
+        this.max_queued_calls = Pickle::unpickle(stream)?;
+        this.max_queue_wait = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -15428,13 +16887,16 @@ impl Default for Jmap {
             websocket_throttle: Duration::from_millis(1000),
             websocket_timeout: Duration::from_millis(600000),
             max_subscriptions: Some(15u64),
+            max_concurrent_calls: Some(64u64),
+            max_queued_calls: 32u64,
+            max_queue_wait: Duration::from_millis(10000),
         }
     }
 }
 
 impl IntoValue for Jmap {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(30);
+        let mut map = jmap_tools::Map::with_capacity(33);
         map.insert_unchecked(
             Property::ParseLimitEvent,
             self.parse_limit_event.into_value(),
@@ -15517,6 +16979,12 @@ impl IntoValue for Jmap {
             Property::MaxSubscriptions,
             self.max_subscriptions.into_value(),
         );
+        map.insert_unchecked(
+            Property::MaxConcurrentCalls,
+            self.max_concurrent_calls.into_value(),
+        );
+        map.insert_unchecked(Property::MaxQueuedCalls, self.max_queued_calls.into_value());
+        map.insert_unchecked(Property::MaxQueueWait, self.max_queue_wait.into_value());
         JmapValue::Object(map)
     }
 }
@@ -15560,6 +17028,9 @@ impl RegistryJsonPropertyPatch for Jmap {
             Some(Property::WebsocketThrottle) => self.websocket_throttle.patch(pointer, value),
             Some(Property::WebsocketTimeout) => self.websocket_timeout.patch(pointer, value),
             Some(Property::MaxSubscriptions) => self.max_subscriptions.patch(pointer, value),
+            Some(Property::MaxConcurrentCalls) => self.max_concurrent_calls.patch(pointer, value),
+            Some(Property::MaxQueuedCalls) => self.max_queued_calls.patch(pointer, value),
+            Some(Property::MaxQueueWait) => self.max_queue_wait.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -15773,7 +17244,9 @@ impl Pickle for LdapDirectory {
         self.attr_description.pickle(out);
         self.attr_email.pickle(out);
         self.attr_email_alias.pickle(out);
+        self.attr_login_referral.pickle(out);
         self.attr_member_of.pickle(out);
+        self.attr_proxy_backend.pickle(out);
         self.attr_secret.pickle(out);
         self.attr_secret_changed.pickle(out);
         self.group_class.pickle(out);
@@ -15802,7 +17275,9 @@ impl Pickle for LdapDirectory {
         this.attr_description = Pickle::unpickle(stream)?;
         this.attr_email = Pickle::unpickle(stream)?;
         this.attr_email_alias = Pickle::unpickle(stream)?;
+        this.attr_login_referral = Pickle::unpickle(stream)?;
         this.attr_member_of = Pickle::unpickle(stream)?;
+        this.attr_proxy_backend = Pickle::unpickle(stream)?;
         this.attr_secret = Pickle::unpickle(stream)?;
         this.attr_secret_changed = Pickle::unpickle(stream)?;
         this.group_class = Pickle::unpickle(stream)?;
@@ -15834,7 +17309,9 @@ impl Default for LdapDirectory {
             attr_description: Map::new(vec!["description".to_string()]),
             attr_email: Map::new(vec!["mail".to_string()]),
             attr_email_alias: Map::new(vec!["mailAlias".to_string()]),
+            attr_login_referral: Default::default(),
             attr_member_of: Map::new(vec!["memberOf".to_string()]),
+            attr_proxy_backend: Default::default(),
             attr_secret: Map::new(vec!["userPassword".to_string()]),
             attr_secret_changed: Map::new(vec!["pwdChangeTime".to_string()]),
             group_class: "groupOfNames".to_string(),
@@ -15849,7 +17326,7 @@ impl Default for LdapDirectory {
 
 impl IntoValue for LdapDirectory {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(27);
+        let mut map = jmap_tools::Map::with_capacity(29);
         map.insert_unchecked(Property::Description, self.description.into_value());
         map.insert_unchecked(Property::Url, self.url.into_value());
         map.insert_unchecked(Property::Timeout, self.timeout.into_value());
@@ -15875,7 +17352,15 @@ impl IntoValue for LdapDirectory {
         );
         map.insert_unchecked(Property::AttrEmail, self.attr_email.into_value());
         map.insert_unchecked(Property::AttrEmailAlias, self.attr_email_alias.into_value());
+        map.insert_unchecked(
+            Property::AttrLoginReferral,
+            self.attr_login_referral.into_value(),
+        );
         map.insert_unchecked(Property::AttrMemberOf, self.attr_member_of.into_value());
+        map.insert_unchecked(
+            Property::AttrProxyBackend,
+            self.attr_proxy_backend.into_value(),
+        );
         map.insert_unchecked(Property::AttrSecret, self.attr_secret.into_value());
         map.insert_unchecked(
             Property::AttrSecretChanged,
@@ -15948,9 +17433,15 @@ impl RegistryJsonPropertyPatch for LdapDirectory {
             Some(Property::AttrEmailAlias) => self
                 .attr_email_alias
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::AttrLoginReferral) => self
+                .attr_login_referral
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
             Some(Property::AttrMemberOf) => self
                 .attr_member_of
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::AttrProxyBackend) => self
+                .attr_proxy_backend
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
             Some(Property::AttrSecret) => self
                 .attr_secret
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
@@ -16347,6 +17838,7 @@ impl Pickle for MailingList {
         self.aliases.pickle(out);
         self.member_tenant_id.pickle(out);
         self.recipients.pickle(out);
+        self.allow_unsubscribe.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -16357,6 +17849,7 @@ impl Pickle for MailingList {
         this.aliases = Pickle::unpickle(stream)?;
         this.member_tenant_id = Pickle::unpickle(stream)?;
         this.recipients = Pickle::unpickle(stream)?;
+        this.allow_unsubscribe = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -16370,19 +17863,24 @@ impl Default for MailingList {
             aliases: Default::default(),
             member_tenant_id: Default::default(),
             recipients: Default::default(),
+            allow_unsubscribe: false,
         }
     }
 }
 
 impl IntoValue for MailingList {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(8);
+        let mut map = jmap_tools::Map::with_capacity(9);
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::DomainId, self.domain_id.into_value());
         map.insert_unchecked(Property::Description, self.description.into_value());
         map.insert_unchecked(Property::Aliases, self.aliases.into_value());
         map.insert_unchecked(Property::MemberTenantId, self.member_tenant_id.into_value());
         map.insert_unchecked(Property::Recipients, self.recipients.into_value());
+        map.insert_unchecked(
+            Property::AllowUnsubscribe,
+            self.allow_unsubscribe.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -16408,6 +17906,7 @@ impl RegistryJsonPropertyPatch for MailingList {
             Some(Property::Recipients) => self
                 .recipients
                 .patch(pointer.with_validators(&[StringValidator::Email]), value),
+            Some(Property::AllowUnsubscribe) => self.allow_unsubscribe.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -17511,6 +19010,16 @@ impl MetricsPrometheusProperties {
                 errors.push(ValidationError::required(Property::AuthUsername));
             }
         }
+        let value = &self.max_breakdown_labels;
+        if *value > 10000 {
+            errors.push(ValidationError::max_value(
+                Property::MaxBreakdownLabels,
+                10000,
+            ));
+        }
+        if *value < 1 {
+            errors.push(ValidationError::min_value(Property::MaxBreakdownLabels, 1));
+        }
         errors.len() == neb
     }
 }
@@ -17519,12 +19028,20 @@ impl Pickle for MetricsPrometheusProperties {
     fn pickle(&self, out: &mut Vec<u8>) {
         self.auth_secret.pickle(out);
         self.auth_username.pickle(out);
+        self.domain_breakdown.pickle(out);
+        self.tenant_breakdown.pickle(out);
+        self.max_breakdown_labels.pickle(out);
+        self.otlp_ingest.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
         let mut this = Self::default();
         this.auth_secret = Pickle::unpickle(stream)?;
         this.auth_username = Pickle::unpickle(stream)?;
+        this.domain_breakdown = Pickle::unpickle(stream)?;
+        this.tenant_breakdown = Pickle::unpickle(stream)?;
+        this.max_breakdown_labels = Pickle::unpickle(stream)?;
+        this.otlp_ingest = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -17534,15 +19051,32 @@ impl Default for MetricsPrometheusProperties {
         Self {
             auth_secret: Default::default(),
             auth_username: Default::default(),
+            domain_breakdown: false,
+            tenant_breakdown: false,
+            max_breakdown_labels: 100,
+            otlp_ingest: false,
         }
     }
 }
 
 impl IntoValue for MetricsPrometheusProperties {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(4);
+        let mut map = jmap_tools::Map::with_capacity(8);
         map.insert_unchecked(Property::AuthSecret, self.auth_secret.into_value());
         map.insert_unchecked(Property::AuthUsername, self.auth_username.into_value());
+        map.insert_unchecked(
+            Property::DomainBreakdown,
+            self.domain_breakdown.into_value(),
+        );
+        map.insert_unchecked(
+            Property::TenantBreakdown,
+            self.tenant_breakdown.into_value(),
+        );
+        map.insert_unchecked(
+            Property::MaxBreakdownLabels,
+            self.max_breakdown_labels.into_value(),
+        );
+        map.insert_unchecked(Property::OtlpIngest, self.otlp_ingest.into_value());
         JmapValue::Object(map)
     }
 }
@@ -17558,6 +19092,10 @@ impl RegistryJsonPropertyPatch for MetricsPrometheusProperties {
             Some(Property::AuthUsername) => self
                 .auth_username
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::DomainBreakdown) => self.domain_breakdown.patch(pointer, value),
+            Some(Property::TenantBreakdown) => self.tenant_breakdown.patch(pointer, value),
+            Some(Property::MaxBreakdownLabels) => self.max_breakdown_labels.patch(pointer, value),
+            Some(Property::OtlpIngest) => self.otlp_ingest.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -18904,6 +20442,10 @@ impl ObjectImpl for MtaInboundSession {
         value.validate(errors);
         let value = &self.transfer_limit;
         value.validate(errors);
+        let value = &self.address_rewrites;
+        for value in value.values() {
+            value.validate(errors);
+        }
         errors.len() == neb
     }
 
@@ -18964,6 +20506,7 @@ impl Pickle for MtaInboundSession {
         self.max_duration.pickle(out);
         self.timeout.pickle(out);
         self.transfer_limit.pickle(out);
+        self.address_rewrites.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -18971,6 +20514,7 @@ impl Pickle for MtaInboundSession {
         this.max_duration = Pickle::unpickle(stream)?;
         this.timeout = Pickle::unpickle(stream)?;
         this.transfer_limit = Pickle::unpickle(stream)?;
+        this.address_rewrites = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -18990,16 +20534,21 @@ impl Default for MtaInboundSession {
                 else_: "262144000".to_string(),
                 ..Default::default()
             },
+            address_rewrites: Default::default(),
         }
     }
 }
 
 impl IntoValue for MtaInboundSession {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(5);
+        let mut map = jmap_tools::Map::with_capacity(6);
         map.insert_unchecked(Property::MaxDuration, self.max_duration.into_value());
         map.insert_unchecked(Property::Timeout, self.timeout.into_value());
         map.insert_unchecked(Property::TransferLimit, self.transfer_limit.into_value());
+        map.insert_unchecked(
+            Property::AddressRewrites,
+            self.address_rewrites.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -19014,6 +20563,7 @@ impl RegistryJsonPropertyPatch for MtaInboundSession {
             Some(Property::MaxDuration) => self.max_duration.patch(pointer, value),
             Some(Property::Timeout) => self.timeout.patch(pointer, value),
             Some(Property::TransferLimit) => self.transfer_limit.patch(pointer, value),
+            Some(Property::AddressRewrites) => self.address_rewrites.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -20505,6 +22055,8 @@ impl ObjectImpl for MtaStageData {
         value.validate(errors);
         let value = &self.enable_spam_filter;
         value.validate(errors);
+        let value = &self.auto_bcc;
+        value.validate(errors);
         errors.len() == neb
     }
 
@@ -20673,6 +22225,19 @@ impl MtaStageData {
         }
     }
 
+    pub fn ctx_auto_bcc(&self) -> ExpressionContext<'_> {
+        ExpressionContext {
+            expr: &self.auto_bcc,
+            default: Some(Expression {
+                else_: "false".to_string(),
+                ..Default::default()
+            }),
+            property: Property::AutoBcc,
+            allowed_variables: MTA_RCPT_TO_VARIABLE,
+            allowed_constants: &[],
+        }
+    }
+
     pub fn expression_ctxs(&self) -> Vec<ExpressionContext<'_>> {
         vec![
             self.ctx_add_auth_results_header(),
@@ -20686,6 +22251,7 @@ impl MtaStageData {
             self.ctx_max_message_size(),
             self.ctx_script(),
             self.ctx_enable_spam_filter(),
+            self.ctx_auto_bcc(),
         ]
     }
 }
@@ -20704,6 +22270,9 @@ impl Pickle for MtaStageData {
         self.max_message_size.pickle(out);
         self.script.pickle(out);
         self.enable_spam_filter.pickle(out);
+        self.auto_bcc.pickle(out);
+        self.duplicate_action.pickle(out);
+        self.duplicate_window.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -20720,6 +22289,9 @@ impl Pickle for MtaStageData {
         this.max_message_size = Pickle::unpickle(stream)?;
         this.script = Pickle::unpickle(stream)?;
         this.enable_spam_filter = Pickle::unpickle(stream)?;
+        this.auto_bcc = Pickle::unpickle(stream)?;
+        this.duplicate_action = Pickle::unpickle(stream)?;
+        this.duplicate_window = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -20790,13 +22362,19 @@ impl Default for MtaStageData {
                 else_: "is_empty(authenticated_as)".to_string(),
                 ..Default::default()
             },
+            auto_bcc: Expression {
+                else_: "false".to_string(),
+                ..Default::default()
+            },
+            duplicate_action: DuplicateAction::Disabled,
+            duplicate_window: Duration::from_millis(86400000),
         }
     }
 }
 
 impl IntoValue for MtaStageData {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(14);
+        let mut map = jmap_tools::Map::with_capacity(17);
         map.insert_unchecked(
             Property::AddAuthResultsHeader,
             self.add_auth_results_header.into_value(),
@@ -20833,6 +22411,15 @@ impl IntoValue for MtaStageData {
             Property::EnableSpamFilter,
             self.enable_spam_filter.into_value(),
         );
+        map.insert_unchecked(Property::AutoBcc, self.auto_bcc.into_value());
+        map.insert_unchecked(
+            Property::DuplicateAction,
+            self.duplicate_action.into_value(),
+        );
+        map.insert_unchecked(
+            Property::DuplicateWindow,
+            self.duplicate_window.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -20864,6 +22451,9 @@ impl RegistryJsonPropertyPatch for MtaStageData {
             Some(Property::MaxMessageSize) => self.max_message_size.patch(pointer, value),
             Some(Property::Script) => self.script.patch(pointer, value),
             Some(Property::EnableSpamFilter) => self.enable_spam_filter.patch(pointer, value),
+            Some(Property::AutoBcc) => self.auto_bcc.patch(pointer, value),
+            Some(Property::DuplicateAction) => self.duplicate_action.patch(pointer, value),
+            Some(Property::DuplicateWindow) => self.duplicate_window.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -21161,6 +22751,10 @@ impl ObjectImpl for MtaStageRcpt {
         value.validate(errors);
         let value = &self.max_recipients;
         value.validate(errors);
+        let value = &self.max_session_recipients;
+        value.validate(errors);
+        let value = &self.enforce_quota;
+        value.validate(errors);
         let value = &self.allow_relaying;
         value.validate(errors);
         let value = &self.rewrite;
@@ -21213,6 +22807,32 @@ impl MtaStageRcpt {
         }
     }
 
+    pub fn ctx_max_session_recipients(&self) -> ExpressionContext<'_> {
+        ExpressionContext {
+            expr: &self.max_session_recipients,
+            default: Some(Expression {
+                else_: "1000".to_string(),
+                ..Default::default()
+            }),
+            property: Property::MaxSessionRecipients,
+            allowed_variables: MTA_MAIL_FROM_VARIABLE,
+            allowed_constants: &[],
+        }
+    }
+
+    pub fn ctx_enforce_quota(&self) -> ExpressionContext<'_> {
+        ExpressionContext {
+            expr: &self.enforce_quota,
+            default: Some(Expression {
+                else_: "true".to_string(),
+                ..Default::default()
+            }),
+            property: Property::EnforceQuota,
+            allowed_variables: MTA_RCPT_TO_VARIABLE,
+            allowed_constants: &[],
+        }
+    }
+
     pub fn ctx_allow_relaying(&self) -> ExpressionContext<'_> {
         ExpressionContext {
             expr: &self.allow_relaying,
@@ -21257,6 +22877,8 @@ impl MtaStageRcpt {
             self.ctx_max_failures(),
             self.ctx_wait_on_fail(),
             self.ctx_max_recipients(),
+            self.ctx_max_session_recipients(),
+            self.ctx_enforce_quota(),
             self.ctx_allow_relaying(),
             self.ctx_rewrite(),
             self.ctx_script(),
@@ -21272,6 +22894,8 @@ impl Pickle for MtaStageRcpt {
         self.allow_relaying.pickle(out);
         self.rewrite.pickle(out);
         self.script.pickle(out);
+        self.max_session_recipients.pickle(out);
+        self.enforce_quota.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -21282,6 +22906,8 @@ impl Pickle for MtaStageRcpt {
         this.allow_relaying = Pickle::unpickle(stream)?;
         this.rewrite = Pickle::unpickle(stream)?;
         this.script = Pickle::unpickle(stream)?;
+        this.max_session_recipients = Pickle::unpickle(stream)?;
+        this.enforce_quota = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -21301,6 +22927,14 @@ impl Default for MtaStageRcpt {
                 else_: "100".to_string(),
                 ..Default::default()
             },
+            max_session_recipients: Expression {
+                else_: "1000".to_string(),
+                ..Default::default()
+            },
+            enforce_quota: Expression {
+                else_: "true".to_string(),
+                ..Default::default()
+            },
             allow_relaying: Expression {
                 else_: "!is_empty(authenticated_as)".to_string(),
                 ..Default::default()
@@ -21319,10 +22953,15 @@ impl Default for MtaStageRcpt {
 
 impl IntoValue for MtaStageRcpt {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(8);
+        let mut map = jmap_tools::Map::with_capacity(10);
         map.insert_unchecked(Property::MaxFailures, self.max_failures.into_value());
         map.insert_unchecked(Property::WaitOnFail, self.wait_on_fail.into_value());
         map.insert_unchecked(Property::MaxRecipients, self.max_recipients.into_value());
+        map.insert_unchecked(
+            Property::MaxSessionRecipients,
+            self.max_session_recipients.into_value(),
+        );
+        map.insert_unchecked(Property::EnforceQuota, self.enforce_quota.into_value());
         map.insert_unchecked(Property::AllowRelaying, self.allow_relaying.into_value());
         map.insert_unchecked(Property::Rewrite, self.rewrite.into_value());
         map.insert_unchecked(Property::Script, self.script.into_value());
@@ -21340,6 +22979,10 @@ impl RegistryJsonPropertyPatch for MtaStageRcpt {
             Some(Property::MaxFailures) => self.max_failures.patch(pointer, value),
             Some(Property::WaitOnFail) => self.wait_on_fail.patch(pointer, value),
             Some(Property::MaxRecipients) => self.max_recipients.patch(pointer, value),
+            Some(Property::MaxSessionRecipients) => {
+                self.max_session_recipients.patch(pointer, value)
+            }
+            Some(Property::EnforceQuota) => self.enforce_quota.patch(pointer, value),
             Some(Property::AllowRelaying) => self.allow_relaying.patch(pointer, value),
             Some(Property::Rewrite) => self.rewrite.patch(pointer, value),
             Some(Property::Script) => self.script.patch(pointer, value),
@@ -21946,6 +23589,10 @@ impl NatsCoordinator {
         }
         let value = &self.credentials;
         value.validate(errors);
+        let value = &self.stream_name;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::StreamName));
+        }
         errors.len() == neb
     }
 }
@@ -21965,6 +23612,9 @@ impl Pickle for NatsCoordinator {
         self.auth_secret.pickle(out);
         self.auth_username.pickle(out);
         self.credentials.pickle(out);
+        self.use_jet_stream.pickle(out);
+        self.stream_name.pickle(out);
+        self.stream_max_age.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -21982,6 +23632,9 @@ impl Pickle for NatsCoordinator {
         this.auth_secret = Pickle::unpickle(stream)?;
         this.auth_username = Pickle::unpickle(stream)?;
         this.credentials = Pickle::unpickle(stream)?;
+        this.use_jet_stream = Pickle::unpickle(stream)?;
+        this.stream_name = Pickle::unpickle(stream)?;
+        this.stream_max_age = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -22002,13 +23655,16 @@ impl Default for NatsCoordinator {
             auth_secret: Default::default(),
             auth_username: Some("stalwart".to_string()),
             credentials: Default::default(),
+            use_jet_stream: false,
+            stream_name: "stalwart".to_string(),
+            stream_max_age: Duration::from_millis(3600000),
         }
     }
 }
 
 impl IntoValue for NatsCoordinator {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(15);
+        let mut map = jmap_tools::Map::with_capacity(18);
         map.insert_unchecked(Property::Addresses, self.addresses.into_value());
         map.insert_unchecked(Property::MaxReconnects, self.max_reconnects.into_value());
         map.insert_unchecked(
@@ -22031,6 +23687,9 @@ impl IntoValue for NatsCoordinator {
         map.insert_unchecked(Property::AuthSecret, self.auth_secret.into_value());
         map.insert_unchecked(Property::AuthUsername, self.auth_username.into_value());
         map.insert_unchecked(Property::Credentials, self.credentials.into_value());
+        map.insert_unchecked(Property::UseJetStream, self.use_jet_stream.into_value());
+        map.insert_unchecked(Property::StreamName, self.stream_name.into_value());
+        map.insert_unchecked(Property::StreamMaxAge, self.stream_max_age.into_value());
         JmapValue::Object(map)
     }
 }
@@ -22059,6 +23718,11 @@ impl RegistryJsonPropertyPatch for NatsCoordinator {
                 .auth_username
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
             Some(Property::Credentials) => self.credentials.patch(pointer, value),
+            Some(Property::UseJetStream) => self.use_jet_stream.patch(pointer, value),
+            Some(Property::StreamName) => self
+                .stream_name
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::StreamMaxAge) => self.stream_max_age.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -22133,6 +23797,11 @@ impl ObjectImpl for NetworkListener {
                 errors.push(ValidationError::min_value(Property::MaxConnections, 1));
             }
         }
+        if let Some(value) = &self.tls_client_ca_certificates {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::TlsClientCaCertificates));
+            }
+        }
         errors.len() == neb
     }
 
@@ -22162,6 +23831,9 @@ impl Pickle for NetworkListener {
         self.tls_implicit.pickle(out);
         self.tls_timeout.pickle(out);
         self.max_connections.pickle(out);
+        self.tls_client_auth.pickle(out);
+        self.tls_client_ca_certificates.pickle(out);
+        self.tls_client_cert_principal.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -22185,6 +23857,9 @@ impl Pickle for NetworkListener {
         this.tls_implicit = Pickle::unpickle(stream)?;
         this.tls_timeout = Pickle::unpickle(stream)?;
         this.max_connections = Pickle::unpickle(stream)?;
+        this.tls_client_auth = Pickle::unpickle(stream)?;
+        this.tls_client_ca_certificates = Pickle::unpickle(stream)?;
+        this.tls_client_cert_principal = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -22211,13 +23886,19 @@ impl Default for NetworkListener {
             tls_implicit: false,
             tls_timeout: Some(Duration::from_millis(60000)),
             max_connections: Some(8192u64),
+            tls_client_auth: TlsClientAuth::Disabled,
+            tls_client_ca_certificates: Default::default(),
+            tls_client_cert_principal: Map::new(vec![
+                TlsClientCertField::SanEmail,
+                TlsClientCertField::SubjectCommonName,
+            ]),
         }
     }
 }
 
 impl IntoValue for NetworkListener {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(21);
+        let mut map = jmap_tools::Map::with_capacity(24);
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::Bind, self.bind.into_value());
         map.insert_unchecked(Property::Protocol, self.protocol.into_value());
@@ -22261,6 +23942,15 @@ impl IntoValue for NetworkListener {
         map.insert_unchecked(Property::TlsImplicit, self.tls_implicit.into_value());
         map.insert_unchecked(Property::TlsTimeout, self.tls_timeout.into_value());
         map.insert_unchecked(Property::MaxConnections, self.max_connections.into_value());
+        map.insert_unchecked(Property::TlsClientAuth, self.tls_client_auth.into_value());
+        map.insert_unchecked(
+            Property::TlsClientCaCertificates,
+            self.tls_client_ca_certificates.into_value(),
+        );
+        map.insert_unchecked(
+            Property::TlsClientCertPrincipal,
+            self.tls_client_cert_principal.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -22303,6 +23993,13 @@ impl RegistryJsonPropertyPatch for NetworkListener {
             Some(Property::TlsImplicit) => self.tls_implicit.patch(pointer, value),
             Some(Property::TlsTimeout) => self.tls_timeout.patch(pointer, value),
             Some(Property::MaxConnections) => self.max_connections.patch(pointer, value),
+            Some(Property::TlsClientAuth) => self.tls_client_auth.patch(pointer, value),
+            Some(Property::TlsClientCaCertificates) => {
+                self.tls_client_ca_certificates.patch(pointer, value)
+            }
+            Some(Property::TlsClientCertPrincipal) => {
+                self.tls_client_cert_principal.patch(pointer, value)
+            }
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -23276,6 +24973,11 @@ impl PostgreSqlStore {
                 errors.push(ValidationError::required(Property::Options));
             }
         }
+        if let Some(value) = &self.read_cache_size {
+            if *value < 1048576 {
+                errors.push(ValidationError::min_value(Property::ReadCacheSize, 1048576));
+            }
+        }
         errors.len() == neb
     }
 }
@@ -23294,6 +24996,7 @@ impl Pickle for PostgreSqlStore {
         self.auth_username.pickle(out);
         self.auth_secret.pickle(out);
         self.options.pickle(out);
+        self.read_cache_size.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -23310,6 +25013,7 @@ impl Pickle for PostgreSqlStore {
         this.auth_username = Pickle::unpickle(stream)?;
         this.auth_secret = Pickle::unpickle(stream)?;
         this.options = Pickle::unpickle(stream)?;
+        this.read_cache_size = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -23329,13 +25033,14 @@ impl Default for PostgreSqlStore {
             auth_username: Some("stalwart".to_string()),
             auth_secret: Default::default(),
             options: Default::default(),
+            read_cache_size: Default::default(),
         }
     }
 }
 
 impl IntoValue for PostgreSqlStore {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(14);
+        let mut map = jmap_tools::Map::with_capacity(15);
         map.insert_unchecked(Property::Timeout, self.timeout.into_value());
         map.insert_unchecked(Property::UseTls, self.use_tls.into_value());
         map.insert_unchecked(
@@ -23357,6 +25062,7 @@ impl IntoValue for PostgreSqlStore {
         map.insert_unchecked(Property::AuthUsername, self.auth_username.into_value());
         map.insert_unchecked(Property::AuthSecret, self.auth_secret.into_value());
         map.insert_unchecked(Property::Options, self.options.into_value());
+        map.insert_unchecked(Property::ReadCacheSize, self.read_cache_size.into_value());
         JmapValue::Object(map)
     }
 }
@@ -23388,6 +25094,7 @@ impl RegistryJsonPropertyPatch for PostgreSqlStore {
             Some(Property::Options) => self
                 .options
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::ReadCacheSize) => self.read_cache_size.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -23874,6 +25581,162 @@ impl RegistryJsonPropertyPatch for QueueExpiryTtl {
     }
 }
 
+impl ObjectImpl for QueueStore {
+    const FLAGS: u64 = OBJ_SINGLETON;
+    const VERSION: u8 = 0;
+    const OBJECT: ObjectType = ObjectType::QueueStore;
+
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        match self {
+            QueueStore::Default => true,
+            QueueStore::RocksDb(inner) => inner.validate(errors),
+            QueueStore::Sqlite(inner) => inner.validate(errors),
+            QueueStore::FoundationDb(inner) => inner.validate(errors),
+            QueueStore::PostgreSql(inner) => inner.validate(errors),
+            QueueStore::MySql(inner) => inner.validate(errors),
+        }
+    }
+
+    fn index<'x>(&'x self, _: &mut IndexBuilder<'x>) {}
+}
+
+impl Default for QueueStore {
+    fn default() -> Self {
+        QueueStore::Default
+    }
+}
+
+impl Pickle for QueueStore {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        match self {
+            QueueStore::Default => {
+                0u16.pickle(out);
+            }
+            QueueStore::RocksDb(inner) => {
+                1u16.pickle(out);
+                inner.pickle(out);
+            }
+            QueueStore::Sqlite(inner) => {
+                2u16.pickle(out);
+                inner.pickle(out);
+            }
+            QueueStore::FoundationDb(inner) => {
+                3u16.pickle(out);
+                inner.pickle(out);
+            }
+            QueueStore::PostgreSql(inner) => {
+                4u16.pickle(out);
+                inner.pickle(out);
+            }
+            QueueStore::MySql(inner) => {
+                5u16.pickle(out);
+                inner.pickle(out);
+            }
+        }
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        match u16::unpickle(stream)? {
+            0 => Some(QueueStore::Default),
+            1 => Pickle::unpickle(stream).map(QueueStore::RocksDb),
+            2 => Pickle::unpickle(stream).map(QueueStore::Sqlite),
+            3 => Pickle::unpickle(stream).map(QueueStore::FoundationDb),
+            4 => Pickle::unpickle(stream).map(QueueStore::PostgreSql),
+            5 => Pickle::unpickle(stream).map(QueueStore::MySql),
+            _ => None,
+        }
+    }
+}
+
+impl IntoValue for QueueStore {
+    fn into_value(self) -> JmapValue<'static> {
+        match self {
+            QueueStore::Default => {
+                let mut obj = jmap_tools::Map::new();
+                obj.insert_unchecked(Property::Type, JmapValue::Str("Default".into()));
+                JmapValue::Object(obj)
+            }
+            QueueStore::RocksDb(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("RocksDb".into()));
+                obj
+            }
+            QueueStore::Sqlite(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("Sqlite".into()));
+                obj
+            }
+            QueueStore::FoundationDb(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("FoundationDb".into()));
+                obj
+            }
+            QueueStore::PostgreSql(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("PostgreSql".into()));
+                obj
+            }
+            QueueStore::MySql(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("MySql".into()));
+                obj
+            }
+        }
+    }
+}
+
+impl RegistryJsonPatch for QueueStore {
+    fn patch<'x>(
+        &mut self,
+        pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        if !pointer.has_next() {
+            match object_type(&pointer, &value)? {
+                QueueStoreType::Default => *self = QueueStore::Default,
+                QueueStoreType::RocksDb => *self = QueueStore::RocksDb(Default::default()),
+                QueueStoreType::Sqlite => *self = QueueStore::Sqlite(Default::default()),
+                QueueStoreType::FoundationDb => {
+                    *self = QueueStore::FoundationDb(Default::default())
+                }
+                QueueStoreType::PostgreSql => *self = QueueStore::PostgreSql(Default::default()),
+                QueueStoreType::MySql => *self = QueueStore::MySql(Default::default()),
+            }
+        }
+        match self {
+            QueueStore::Default => pointer.assert_eof(),
+            QueueStore::RocksDb(inner) => inner.patch(pointer, value),
+            QueueStore::Sqlite(inner) => inner.patch(pointer, value),
+            QueueStore::FoundationDb(inner) => inner.patch(pointer, value),
+            QueueStore::PostgreSql(inner) => inner.patch(pointer, value),
+            QueueStore::MySql(inner) => inner.patch(pointer, value),
+        }
+    }
+}
+
+impl QueueStore {
+    pub fn object_type(&self) -> QueueStoreType {
+        match self {
+            QueueStore::Default => QueueStoreType::Default,
+            QueueStore::RocksDb(_) => QueueStoreType::RocksDb,
+            QueueStore::Sqlite(_) => QueueStoreType::Sqlite,
+            QueueStore::FoundationDb(_) => QueueStoreType::FoundationDb,
+            QueueStore::PostgreSql(_) => QueueStoreType::PostgreSql,
+            QueueStore::MySql(_) => QueueStoreType::MySql,
+        }
+    }
+}
+
 impl ObjectImpl for QueuedMessage {
     const FLAGS: u64 = 0;
     const VERSION: u8 = 0;
@@ -24768,6 +26631,108 @@ impl RegistryJsonPropertyPatch for ReportSettings {
     }
 }
 
+impl RetentionPolicy {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.name;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Name));
+        }
+        let value = &self.older_than;
+        if value.as_millis() < 86400000 {
+            errors.push(ValidationError::min_value(Property::OlderThan, 86400000));
+        }
+        let value = &self.archive_mailbox;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::ArchiveMailbox));
+        }
+        errors.len() == neb
+    }
+}
+
+impl Pickle for RetentionPolicy {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.name.pickle(out);
+        self.enabled.pickle(out);
+        self.action.pickle(out);
+        self.older_than.pickle(out);
+        self.mailboxes.pickle(out);
+        self.keywords.pickle(out);
+        self.exclude_keywords.pickle(out);
+        self.archive_mailbox.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.name = Pickle::unpickle(stream)?;
+        this.enabled = Pickle::unpickle(stream)?;
+        this.action = Pickle::unpickle(stream)?;
+        this.older_than = Pickle::unpickle(stream)?;
+        this.mailboxes = Pickle::unpickle(stream)?;
+        this.keywords = Pickle::unpickle(stream)?;
+        this.exclude_keywords = Pickle::unpickle(stream)?;
+        this.archive_mailbox = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            enabled: true,
+            action: RetentionAction::Archive,
+            older_than: Duration::from_millis(31536000000),
+            mailboxes: Default::default(),
+            keywords: Default::default(),
+            exclude_keywords: Map::new(vec!["$flagged".to_string()]),
+            archive_mailbox: "Archive".to_string(),
+        }
+    }
+}
+
+impl IntoValue for RetentionPolicy {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(10);
+        map.insert_unchecked(Property::Name, self.name.into_value());
+        map.insert_unchecked(Property::Enabled, self.enabled.into_value());
+        map.insert_unchecked(Property::Action, self.action.into_value());
+        map.insert_unchecked(Property::OlderThan, self.older_than.into_value());
+        map.insert_unchecked(Property::Mailboxes, self.mailboxes.into_value());
+        map.insert_unchecked(Property::Keywords, self.keywords.into_value());
+        map.insert_unchecked(
+            Property::ExcludeKeywords,
+            self.exclude_keywords.into_value(),
+        );
+        map.insert_unchecked(Property::ArchiveMailbox, self.archive_mailbox.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for RetentionPolicy {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Name) => self.name.patch(pointer, value),
+            Some(Property::Enabled) => self.enabled.patch(pointer, value),
+            Some(Property::Action) => self.action.patch(pointer, value),
+            Some(Property::OlderThan) => self.older_than.patch(pointer, value),
+            Some(Property::Mailboxes) => self.mailboxes.patch(pointer, value),
+            Some(Property::Keywords) => self.keywords.patch(pointer, value),
+            Some(Property::ExcludeKeywords) => self.exclude_keywords.patch(pointer, value),
+            Some(Property::ArchiveMailbox) => self.archive_mailbox.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl RocksDbStore {
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
         let neb = errors.len();
@@ -25910,6 +27875,10 @@ impl ObjectImpl for Search {
         if *value < 1 {
             errors.push(ValidationError::min_value(Property::IndexBatchSize, 1));
         }
+        let value = &self.reindex_rate;
+        if *value < 1 {
+            errors.push(ValidationError::min_value(Property::ReindexRate, 1));
+        }
         errors.len() == neb
     }
 
@@ -25929,6 +27898,8 @@ impl Pickle for Search {
         self.index_email_fields.pickle(out);
         self.index_telemetry.pickle(out);
         self.index_tracing_fields.pickle(out);
+        self.index_attachment_contents.pickle(out);
+        self.reindex_rate.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -25944,6 +27915,8 @@ impl Pickle for Search {
         this.index_email_fields = Pickle::unpickle(stream)?;
         this.index_telemetry = Pickle::unpickle(stream)?;
         this.index_tracing_fields = Pickle::unpickle(stream)?;
+        this.index_attachment_contents = Pickle::unpickle(stream)?;
+        this.reindex_rate = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -25998,13 +27971,15 @@ impl Default for Search {
                 SearchTracingField::QueueId,
                 SearchTracingField::Keywords,
             ]),
+            index_attachment_contents: false,
+            reindex_rate: 50u64,
         }
     }
 }
 
 impl IntoValue for Search {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(13);
+        let mut map = jmap_tools::Map::with_capacity(15);
         map.insert_unchecked(Property::IndexBatchSize, self.index_batch_size.into_value());
         map.insert_unchecked(
             Property::DefaultLanguage,
@@ -26034,6 +28009,11 @@ impl IntoValue for Search {
             Property::IndexTracingFields,
             self.index_tracing_fields.into_value(),
         );
+        map.insert_unchecked(
+            Property::IndexAttachmentContents,
+            self.index_attachment_contents.into_value(),
+        );
+        map.insert_unchecked(Property::ReindexRate, self.reindex_rate.into_value());
         JmapValue::Object(map)
     }
 }
@@ -26056,6 +28036,10 @@ impl RegistryJsonPropertyPatch for Search {
             Some(Property::IndexEmailFields) => self.index_email_fields.patch(pointer, value),
             Some(Property::IndexTelemetry) => self.index_telemetry.patch(pointer, value),
             Some(Property::IndexTracingFields) => self.index_tracing_fields.patch(pointer, value),
+            Some(Property::IndexAttachmentContents) => {
+                self.index_attachment_contents.patch(pointer, value)
+            }
+            Some(Property::ReindexRate) => self.reindex_rate.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -27039,6 +29023,14 @@ impl ObjectImpl for Security {
         if let Some(value) = &self.scan_ban_rate {
             value.validate(errors);
         }
+        let value = &self.blocked_tls_fingerprints;
+        for value in value.iter() {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::BlockedTlsFingerprints));
+            }
+        }
+        let value = &self.firewall_sync;
+        value.validate(errors);
         errors.len() == neb
     }
 
@@ -27056,6 +29048,11 @@ impl Pickle for Security {
         self.scan_ban_paths.pickle(out);
         self.scan_ban_rate.pickle(out);
         self.scan_ban_period.pickle(out);
+        self.repeat_ban_max_period.pickle(out);
+        self.repeat_ban_decay.pickle(out);
+        self.blocked_tls_fingerprints.pickle(out);
+        self.firewall_sync.pickle(out);
+        self.firewall_sync_interval.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -27069,6 +29066,11 @@ impl Pickle for Security {
         this.scan_ban_paths = Pickle::unpickle(stream)?;
         this.scan_ban_rate = Pickle::unpickle(stream)?;
         this.scan_ban_period = Pickle::unpickle(stream)?;
+        this.repeat_ban_max_period = Pickle::unpickle(stream)?;
+        this.repeat_ban_decay = Pickle::unpickle(stream)?;
+        this.blocked_tls_fingerprints = Pickle::unpickle(stream)?;
+        this.firewall_sync = Pickle::unpickle(stream)?;
+        this.firewall_sync_interval = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -27110,13 +29112,18 @@ impl Default for Security {
                 period: Duration::from_millis(86400000),
             }),
             scan_ban_period: Default::default(),
+            repeat_ban_max_period: Default::default(),
+            repeat_ban_decay: Duration::from_millis(2592000000),
+            blocked_tls_fingerprints: Map::default(),
+            firewall_sync: Default::default(),
+            firewall_sync_interval: Duration::from_millis(60000),
         }
     }
 }
 
 impl IntoValue for Security {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(11);
+        let mut map = jmap_tools::Map::with_capacity(16);
         map.insert_unchecked(Property::AbuseBanRate, self.abuse_ban_rate.into_value());
         map.insert_unchecked(Property::AbuseBanPeriod, self.abuse_ban_period.into_value());
         map.insert_unchecked(Property::AuthBanRate, self.auth_ban_rate.into_value());
@@ -27129,6 +29136,20 @@ impl IntoValue for Security {
         map.insert_unchecked(Property::ScanBanPaths, self.scan_ban_paths.into_value());
         map.insert_unchecked(Property::ScanBanRate, self.scan_ban_rate.into_value());
         map.insert_unchecked(Property::ScanBanPeriod, self.scan_ban_period.into_value());
+        map.insert_unchecked(
+            Property::RepeatBanMaxPeriod,
+            self.repeat_ban_max_period.into_value(),
+        );
+        map.insert_unchecked(Property::RepeatBanDecay, self.repeat_ban_decay.into_value());
+        map.insert_unchecked(
+            Property::BlockedTlsFingerprints,
+            self.blocked_tls_fingerprints.into_value(),
+        );
+        map.insert_unchecked(Property::FirewallSync, self.firewall_sync.into_value());
+        map.insert_unchecked(
+            Property::FirewallSyncInterval,
+            self.firewall_sync_interval.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -27151,6 +29172,16 @@ impl RegistryJsonPropertyPatch for Security {
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
             Some(Property::ScanBanRate) => self.scan_ban_rate.patch(pointer, value),
             Some(Property::ScanBanPeriod) => self.scan_ban_period.patch(pointer, value),
+            Some(Property::RepeatBanMaxPeriod) => self.repeat_ban_max_period.patch(pointer, value),
+            Some(Property::RepeatBanDecay) => self.repeat_ban_decay.patch(pointer, value),
+            Some(Property::BlockedTlsFingerprints) => self.blocked_tls_fingerprints.patch(
+                pointer.with_validators(&[StringValidator::Trim, StringValidator::Lowercase]),
+                value,
+            ),
+            Some(Property::FirewallSync) => self.firewall_sync.patch(pointer, value),
+            Some(Property::FirewallSyncInterval) => {
+                self.firewall_sync_interval.patch(pointer, value)
+            }
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -27167,6 +29198,8 @@ impl ObjectImpl for SenderAuth {
 
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
         let neb = errors.len();
+        let value = &self.dkim_resign_domain;
+        value.validate(errors);
         let value = &self.dkim_sign_domain;
         value.validate(errors);
         let value = &self.dkim_verify;
@@ -27188,6 +29221,22 @@ impl ObjectImpl for SenderAuth {
 }
 
 impl SenderAuth {
+    pub fn ctx_dkim_resign_domain(&self) -> ExpressionContext<'_> {
+        ExpressionContext {
+            expr: &self.dkim_resign_domain,
+            default: Some(Expression {
+                else_: "false".to_string(),
+                match_: List::from_iter([ExpressionMatch {
+                    if_: "is_local_domain(sender_domain)".to_string(),
+                    then: "sender_domain".to_string(),
+                }]),
+            }),
+            property: Property::DkimResignDomain,
+            allowed_variables: MTA_RCPT_TO_VARIABLE,
+            allowed_constants: &[],
+        }
+    }
+
     pub fn ctx_dkim_sign_domain(&self) -> ExpressionContext<'_> {
         ExpressionContext {
             expr: &self.dkim_sign_domain,
@@ -27297,6 +29346,7 @@ impl SenderAuth {
 
     pub fn expression_ctxs(&self) -> Vec<ExpressionContext<'_>> {
         vec![
+            self.ctx_dkim_resign_domain(),
             self.ctx_dkim_sign_domain(),
             self.ctx_dkim_verify(),
             self.ctx_spf_ehlo_verify(),
@@ -27310,6 +29360,7 @@ impl SenderAuth {
 
 impl Pickle for SenderAuth {
     fn pickle(&self, out: &mut Vec<u8>) {
+        self.dkim_resign_domain.pickle(out);
         self.dkim_sign_domain.pickle(out);
         self.dkim_strict.pickle(out);
         self.dkim_verify.pickle(out);
@@ -27322,6 +29373,7 @@ impl Pickle for SenderAuth {
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
         let mut this = Self::default();
+        this.dkim_resign_domain = Pickle::unpickle(stream)?;
         this.dkim_sign_domain = Pickle::unpickle(stream)?;
         this.dkim_strict = Pickle::unpickle(stream)?;
         this.dkim_verify = Pickle::unpickle(stream)?;
@@ -27337,6 +29389,13 @@ impl Pickle for SenderAuth {
 impl Default for SenderAuth {
     fn default() -> Self {
         Self {
+            dkim_resign_domain: Expression {
+                else_: "false".to_string(),
+                match_: List::from_iter([ExpressionMatch {
+                    if_: "is_local_domain(sender_domain)".to_string(),
+                    then: "sender_domain".to_string(),
+                }]),
+            },
             dkim_sign_domain: Expression {
                 else_: "false".to_string(),
                 match_: List::from_iter([ExpressionMatch {
@@ -27388,7 +29447,11 @@ impl Default for SenderAuth {
 
 impl IntoValue for SenderAuth {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(10);
+        let mut map = jmap_tools::Map::with_capacity(11);
+        map.insert_unchecked(
+            Property::DkimResignDomain,
+            self.dkim_resign_domain.into_value(),
+        );
         map.insert_unchecked(Property::DkimSignDomain, self.dkim_sign_domain.into_value());
         map.insert_unchecked(Property::DkimStrict, self.dkim_strict.into_value());
         map.insert_unchecked(Property::DkimVerify, self.dkim_verify.into_value());
@@ -27411,6 +29474,7 @@ impl RegistryJsonPropertyPatch for SenderAuth {
         value: JmapValue<'x>,
     ) -> PatchResult<'x> {
         match pointer.next_property() {
+            Some(Property::DkimResignDomain) => self.dkim_resign_domain.patch(pointer, value),
             Some(Property::DkimSignDomain) => self.dkim_sign_domain.patch(pointer, value),
             Some(Property::DkimStrict) => self.dkim_strict.patch(pointer, value),
             Some(Property::DkimVerify) => self.dkim_verify.patch(pointer, value),
@@ -27535,6 +29599,22 @@ impl Service {
                 errors.push(ValidationError::required(Property::Hostname));
             }
         }
+        if let Some(value) = &self.port {
+            if *value < 1 {
+                errors.push(ValidationError::min_value(Property::Port, 1));
+            }
+            if *value > 65535 {
+                errors.push(ValidationError::max_value(Property::Port, 65535));
+            }
+        }
+        if let Some(value) = &self.cleartext_port {
+            if *value < 1 {
+                errors.push(ValidationError::min_value(Property::CleartextPort, 1));
+            }
+            if *value > 65535 {
+                errors.push(ValidationError::max_value(Property::CleartextPort, 65535));
+            }
+        }
         errors.len() == neb
     }
 }
@@ -27543,12 +29623,16 @@ impl Pickle for Service {
     fn pickle(&self, out: &mut Vec<u8>) {
         self.hostname.pickle(out);
         self.cleartext.pickle(out);
+        self.port.pickle(out);
+        self.cleartext_port.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
         let mut this = Self::default();
         this.hostname = Pickle::unpickle(stream)?;
         this.cleartext = Pickle::unpickle(stream)?;
+        this.port = Pickle::unpickle(stream)?;
+        this.cleartext_port = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -27558,15 +29642,19 @@ impl Default for Service {
         Self {
             hostname: Default::default(),
             cleartext: false,
+            port: Default::default(),
+            cleartext_port: Default::default(),
         }
     }
 }
 
 impl IntoValue for Service {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(4);
+        let mut map = jmap_tools::Map::with_capacity(6);
         map.insert_unchecked(Property::Hostname, self.hostname.into_value());
         map.insert_unchecked(Property::Cleartext, self.cleartext.into_value());
+        map.insert_unchecked(Property::Port, self.port.into_value());
+        map.insert_unchecked(Property::CleartextPort, self.cleartext_port.into_value());
         JmapValue::Object(map)
     }
 }
@@ -27582,6 +29670,8 @@ impl RegistryJsonPropertyPatch for Service {
                 .hostname
                 .patch(pointer.with_validators(&[StringValidator::Hostname]), value),
             Some(Property::Cleartext) => self.cleartext.patch(pointer, value),
+            Some(Property::Port) => self.port.patch(pointer, value),
+            Some(Property::CleartextPort) => self.cleartext_port.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -27709,6 +29799,130 @@ impl RegistryJsonPropertyPatch for ShardedInMemoryStore {
     }
 }
 
+impl ObjectImpl for ShareLink {
+    const FLAGS: u64 = OBJ_FILTER_ACCOUNT;
+    const VERSION: u8 = 0;
+    const OBJECT: ObjectType = ObjectType::ShareLink;
+
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.account_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::required(Property::AccountId));
+        }
+        let value = &self.collection_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::CollectionId, value));
+        }
+        if let Some(value) = &self.description {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::Description));
+            }
+        }
+        let value = &self.created_at;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::CreatedAt, value));
+        }
+        if let Some(value) = &self.expires_at {
+            if !value.is_valid() {
+                errors.push(ValidationError::invalid(Property::ExpiresAt, value));
+            }
+        }
+        if let Some(value) = &self.url {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::Url));
+            }
+        }
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        i.foreign_key(ObjectType::Account, self.account_id.into(), None);
+        i.search(Property::AccountId, &self.account_id);
+    }
+}
+
+impl Pickle for ShareLink {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.account_id.pickle(out);
+        self.collection.pickle(out);
+        self.collection_id.pickle(out);
+        self.description.pickle(out);
+        self.created_at.pickle(out);
+        self.expires_at.pickle(out);
+        self.url.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.account_id = Pickle::unpickle(stream)?;
+        this.collection = Pickle::unpickle(stream)?;
+        this.collection_id = Pickle::unpickle(stream)?;
+        this.description = Pickle::unpickle(stream)?;
+        this.created_at = Pickle::unpickle(stream)?;
+        this.expires_at = Pickle::unpickle(stream)?;
+        this.url = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for ShareLink {
+    fn default() -> Self {
+        Self {
+            account_id: Default::default(),
+            collection: Default::default(),
+            collection_id: Default::default(),
+            description: Default::default(),
+            created_at: Default::default(),
+            expires_at: Default::default(),
+            url: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for ShareLink {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(9);
+        map.insert_unchecked(Property::AccountId, self.account_id.into_value());
+        map.insert_unchecked(Property::Collection, self.collection.into_value());
+        map.insert_unchecked(Property::CollectionId, self.collection_id.into_value());
+        map.insert_unchecked(Property::Description, self.description.into_value());
+        map.insert_unchecked(Property::CreatedAt, self.created_at.into_value());
+        map.insert_unchecked(Property::ExpiresAt, self.expires_at.into_value());
+        map.insert_unchecked(Property::Url, self.url.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for ShareLink {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::AccountId) => self
+                .account_id
+                .patch(pointer.assert_read_only()?.assert_can_set_account()?, value),
+            Some(Property::Collection) => self.collection.patch(pointer.assert_read_only()?, value),
+            Some(Property::CollectionId) => {
+                self.collection_id.patch(pointer.assert_read_only()?, value)
+            }
+            Some(Property::Description) => self
+                .description
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::CreatedAt) => pointer.assert_server_set(),
+            Some(Property::ExpiresAt) => self.expires_at.patch(pointer.assert_read_only()?, value),
+            Some(Property::Url) => pointer.assert_server_set(),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl ObjectImpl for Sharing {
     const FLAGS: u64 = OBJ_SINGLETON;
     const VERSION: u8 = 0;
@@ -27720,6 +29934,10 @@ impl ObjectImpl for Sharing {
         if *value < 1 {
             errors.push(ValidationError::min_value(Property::MaxShares, 1));
         }
+        let value = &self.max_share_links;
+        if *value < 1 {
+            errors.push(ValidationError::min_value(Property::MaxShareLinks, 1));
+        }
         errors.len() == neb
     }
 
@@ -27730,12 +29948,18 @@ impl Pickle for Sharing {
     fn pickle(&self, out: &mut Vec<u8>) {
         self.allow_directory_queries.pickle(out);
         self.max_shares.pickle(out);
+        self.share_link_enable.pickle(out);
+        self.share_link_max_expiry.pickle(out);
+        self.max_share_links.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
         let mut this = Self::default();
         this.allow_directory_queries = Pickle::unpickle(stream)?;
         this.max_shares = Pickle::unpickle(stream)?;
+        this.share_link_enable = Pickle::unpickle(stream)?;
+        this.share_link_max_expiry = Pickle::unpickle(stream)?;
+        this.max_share_links = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -27745,18 +29969,30 @@ impl Default for Sharing {
         Self {
             allow_directory_queries: false,
             max_shares: 10u64,
+            share_link_enable: true,
+            share_link_max_expiry: Duration::from_millis(7776000000),
+            max_share_links: 20u64,
         }
     }
 }
 
 impl IntoValue for Sharing {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(4);
+        let mut map = jmap_tools::Map::with_capacity(7);
         map.insert_unchecked(
             Property::AllowDirectoryQueries,
             self.allow_directory_queries.into_value(),
         );
         map.insert_unchecked(Property::MaxShares, self.max_shares.into_value());
+        map.insert_unchecked(
+            Property::ShareLinkEnable,
+            self.share_link_enable.into_value(),
+        );
+        map.insert_unchecked(
+            Property::ShareLinkMaxExpiry,
+            self.share_link_max_expiry.into_value(),
+        );
+        map.insert_unchecked(Property::MaxShareLinks, self.max_share_links.into_value());
         JmapValue::Object(map)
     }
 }
@@ -27772,6 +30008,9 @@ impl RegistryJsonPropertyPatch for Sharing {
                 self.allow_directory_queries.patch(pointer, value)
             }
             Some(Property::MaxShares) => self.max_shares.patch(pointer, value),
+            Some(Property::ShareLinkEnable) => self.share_link_enable.patch(pointer, value),
+            Some(Property::ShareLinkMaxExpiry) => self.share_link_max_expiry.patch(pointer, value),
+            Some(Property::MaxShareLinks) => self.max_share_links.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -28572,6 +30811,9 @@ impl ObjectImpl for SpamClassifier {
         if *value < 100 {
             errors.push(ValidationError::min_value(Property::ReservoirCapacity, 100));
         }
+        if let Some(value) = &self.train_rate_limit {
+            value.validate(errors);
+        }
         errors.len() == neb
     }
 
@@ -28590,6 +30832,7 @@ impl Pickle for SpamClassifier {
         self.reservoir_capacity.pickle(out);
         self.train_frequency.pickle(out);
         self.learn_ham_from_reply.pickle(out);
+        self.train_rate_limit.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -28604,6 +30847,7 @@ impl Pickle for SpamClassifier {
         this.reservoir_capacity = Pickle::unpickle(stream)?;
         this.train_frequency = Pickle::unpickle(stream)?;
         this.learn_ham_from_reply = Pickle::unpickle(stream)?;
+        this.train_rate_limit = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -28621,6 +30865,10 @@ impl Default for SpamClassifier {
             reservoir_capacity: 1024u64,
             train_frequency: Some(Duration::from_millis(43200000)),
             learn_ham_from_reply: true,
+            train_rate_limit: Some(Rate {
+                count: 100u64,
+                period: Duration::from_millis(86400000),
+            }),
         }
     }
 }
@@ -28653,6 +30901,7 @@ impl IntoValue for SpamClassifier {
             Property::LearnHamFromReply,
             self.learn_ham_from_reply.into_value(),
         );
+        map.insert_unchecked(Property::TrainRateLimit, self.train_rate_limit.into_value());
         JmapValue::Object(map)
     }
 }
@@ -28676,6 +30925,7 @@ impl RegistryJsonPropertyPatch for SpamClassifier {
             Some(Property::ReservoirCapacity) => self.reservoir_capacity.patch(pointer, value),
             Some(Property::TrainFrequency) => self.train_frequency.patch(pointer, value),
             Some(Property::LearnHamFromReply) => self.learn_ham_from_reply.patch(pointer, value),
+            Some(Property::TrainRateLimit) => self.train_rate_limit.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -29372,6 +31622,13 @@ impl SpamDnsblServerAny {
                 errors.push(ValidationError::required(Property::Description));
             }
         }
+        let value = &self.weight;
+        if *value > Float::new(100.0) {
+            errors.push(ValidationError::max_value(Property::Weight, 100));
+        }
+        if *value < Float::new(0.0) {
+            errors.push(ValidationError::min_value(Property::Weight, 0));
+        }
         errors.len() == neb
     }
 
@@ -29413,6 +31670,8 @@ impl Pickle for SpamDnsblServerAny {
         self.name.pickle(out);
         self.description.pickle(out);
         self.enable.pickle(out);
+        self.weight.pickle(out);
+        self.timeout.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -29422,6 +31681,8 @@ impl Pickle for SpamDnsblServerAny {
         this.name = Pickle::unpickle(stream)?;
         this.description = Pickle::unpickle(stream)?;
         this.enable = Pickle::unpickle(stream)?;
+        this.weight = Pickle::unpickle(stream)?;
+        this.timeout = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -29434,18 +31695,22 @@ impl Default for SpamDnsblServerAny {
             name: Default::default(),
             description: Default::default(),
             enable: true,
+            weight: Float::new(1.0f64),
+            timeout: Duration::from_millis(5000),
         }
     }
 }
 
 impl IntoValue for SpamDnsblServerAny {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(7);
+        let mut map = jmap_tools::Map::with_capacity(9);
         map.insert_unchecked(Property::Tag, self.tag.into_value());
         map.insert_unchecked(Property::Zone, self.zone.into_value());
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::Description, self.description.into_value());
         map.insert_unchecked(Property::Enable, self.enable.into_value());
+        map.insert_unchecked(Property::Weight, self.weight.into_value());
+        map.insert_unchecked(Property::Timeout, self.timeout.into_value());
         JmapValue::Object(map)
     }
 }
@@ -29467,6 +31732,8 @@ impl RegistryJsonPropertyPatch for SpamDnsblServerAny {
             ),
             Some(Property::Description) => self.description.patch(pointer, value),
             Some(Property::Enable) => self.enable.patch(pointer, value),
+            Some(Property::Weight) => self.weight.patch(pointer, value),
+            Some(Property::Timeout) => self.timeout.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -29492,6 +31759,13 @@ impl SpamDnsblServerBody {
                 errors.push(ValidationError::required(Property::Description));
             }
         }
+        let value = &self.weight;
+        if *value > Float::new(100.0) {
+            errors.push(ValidationError::max_value(Property::Weight, 100));
+        }
+        if *value < Float::new(0.0) {
+            errors.push(ValidationError::min_value(Property::Weight, 0));
+        }
         errors.len() == neb
     }
 
@@ -29533,6 +31807,8 @@ impl Pickle for SpamDnsblServerBody {
         self.name.pickle(out);
         self.description.pickle(out);
         self.enable.pickle(out);
+        self.weight.pickle(out);
+        self.timeout.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -29542,6 +31818,8 @@ impl Pickle for SpamDnsblServerBody {
         this.name = Pickle::unpickle(stream)?;
         this.description = Pickle::unpickle(stream)?;
         this.enable = Pickle::unpickle(stream)?;
+        this.weight = Pickle::unpickle(stream)?;
+        this.timeout = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -29554,18 +31832,22 @@ impl Default for SpamDnsblServerBody {
             name: Default::default(),
             description: Default::default(),
             enable: true,
+            weight: Float::new(1.0f64),
+            timeout: Duration::from_millis(5000),
         }
     }
 }
 
 impl IntoValue for SpamDnsblServerBody {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(7);
+        let mut map = jmap_tools::Map::with_capacity(9);
         map.insert_unchecked(Property::Tag, self.tag.into_value());
         map.insert_unchecked(Property::Zone, self.zone.into_value());
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::Description, self.description.into_value());
         map.insert_unchecked(Property::Enable, self.enable.into_value());
+        map.insert_unchecked(Property::Weight, self.weight.into_value());
+        map.insert_unchecked(Property::Timeout, self.timeout.into_value());
         JmapValue::Object(map)
     }
 }
@@ -29587,6 +31869,8 @@ impl RegistryJsonPropertyPatch for SpamDnsblServerBody {
             ),
             Some(Property::Description) => self.description.patch(pointer, value),
             Some(Property::Enable) => self.enable.patch(pointer, value),
+            Some(Property::Weight) => self.weight.patch(pointer, value),
+            Some(Property::Timeout) => self.timeout.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -29612,6 +31896,13 @@ impl SpamDnsblServerDomain {
                 errors.push(ValidationError::required(Property::Description));
             }
         }
+        let value = &self.weight;
+        if *value > Float::new(100.0) {
+            errors.push(ValidationError::max_value(Property::Weight, 100));
+        }
+        if *value < Float::new(0.0) {
+            errors.push(ValidationError::min_value(Property::Weight, 0));
+        }
         errors.len() == neb
     }
 
@@ -29653,6 +31944,8 @@ impl Pickle for SpamDnsblServerDomain {
         self.name.pickle(out);
         self.description.pickle(out);
         self.enable.pickle(out);
+        self.weight.pickle(out);
+        self.timeout.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -29662,6 +31955,8 @@ impl Pickle for SpamDnsblServerDomain {
         this.name = Pickle::unpickle(stream)?;
         this.description = Pickle::unpickle(stream)?;
         this.enable = Pickle::unpickle(stream)?;
+        this.weight = Pickle::unpickle(stream)?;
+        this.timeout = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -29674,18 +31969,22 @@ impl Default for SpamDnsblServerDomain {
             name: Default::default(),
             description: Default::default(),
             enable: true,
+            weight: Float::new(1.0f64),
+            timeout: Duration::from_millis(5000),
         }
     }
 }
 
 impl IntoValue for SpamDnsblServerDomain {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(7);
+        let mut map = jmap_tools::Map::with_capacity(9);
         map.insert_unchecked(Property::Tag, self.tag.into_value());
         map.insert_unchecked(Property::Zone, self.zone.into_value());
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::Description, self.description.into_value());
         map.insert_unchecked(Property::Enable, self.enable.into_value());
+        map.insert_unchecked(Property::Weight, self.weight.into_value());
+        map.insert_unchecked(Property::Timeout, self.timeout.into_value());
         JmapValue::Object(map)
     }
 }
@@ -29707,6 +32006,8 @@ impl RegistryJsonPropertyPatch for SpamDnsblServerDomain {
             ),
             Some(Property::Description) => self.description.patch(pointer, value),
             Some(Property::Enable) => self.enable.patch(pointer, value),
+            Some(Property::Weight) => self.weight.patch(pointer, value),
+            Some(Property::Timeout) => self.timeout.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -29732,6 +32033,13 @@ impl SpamDnsblServerEmail {
                 errors.push(ValidationError::required(Property::Description));
             }
         }
+        let value = &self.weight;
+        if *value > Float::new(100.0) {
+            errors.push(ValidationError::max_value(Property::Weight, 100));
+        }
+        if *value < Float::new(0.0) {
+            errors.push(ValidationError::min_value(Property::Weight, 0));
+        }
         errors.len() == neb
     }
 
@@ -29773,6 +32081,8 @@ impl Pickle for SpamDnsblServerEmail {
         self.name.pickle(out);
         self.description.pickle(out);
         self.enable.pickle(out);
+        self.weight.pickle(out);
+        self.timeout.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -29782,6 +32092,8 @@ impl Pickle for SpamDnsblServerEmail {
         this.name = Pickle::unpickle(stream)?;
         this.description = Pickle::unpickle(stream)?;
         this.enable = Pickle::unpickle(stream)?;
+        this.weight = Pickle::unpickle(stream)?;
+        this.timeout = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -29794,18 +32106,22 @@ impl Default for SpamDnsblServerEmail {
             name: Default::default(),
             description: Default::default(),
             enable: true,
+            weight: Float::new(1.0f64),
+            timeout: Duration::from_millis(5000),
         }
     }
 }
 
 impl IntoValue for SpamDnsblServerEmail {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(7);
+        let mut map = jmap_tools::Map::with_capacity(9);
         map.insert_unchecked(Property::Tag, self.tag.into_value());
         map.insert_unchecked(Property::Zone, self.zone.into_value());
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::Description, self.description.into_value());
         map.insert_unchecked(Property::Enable, self.enable.into_value());
+        map.insert_unchecked(Property::Weight, self.weight.into_value());
+        map.insert_unchecked(Property::Timeout, self.timeout.into_value());
         JmapValue::Object(map)
     }
 }
@@ -29827,6 +32143,8 @@ impl RegistryJsonPropertyPatch for SpamDnsblServerEmail {
             ),
             Some(Property::Description) => self.description.patch(pointer, value),
             Some(Property::Enable) => self.enable.patch(pointer, value),
+            Some(Property::Weight) => self.weight.patch(pointer, value),
+            Some(Property::Timeout) => self.timeout.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -29852,6 +32170,13 @@ impl SpamDnsblServerHeader {
                 errors.push(ValidationError::required(Property::Description));
             }
         }
+        let value = &self.weight;
+        if *value > Float::new(100.0) {
+            errors.push(ValidationError::max_value(Property::Weight, 100));
+        }
+        if *value < Float::new(0.0) {
+            errors.push(ValidationError::min_value(Property::Weight, 0));
+        }
         errors.len() == neb
     }
 
@@ -29893,6 +32218,8 @@ impl Pickle for SpamDnsblServerHeader {
         self.name.pickle(out);
         self.description.pickle(out);
         self.enable.pickle(out);
+        self.weight.pickle(out);
+        self.timeout.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -29902,6 +32229,8 @@ impl Pickle for SpamDnsblServerHeader {
         this.name = Pickle::unpickle(stream)?;
         this.description = Pickle::unpickle(stream)?;
         this.enable = Pickle::unpickle(stream)?;
+        this.weight = Pickle::unpickle(stream)?;
+        this.timeout = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -29914,18 +32243,22 @@ impl Default for SpamDnsblServerHeader {
             name: Default::default(),
             description: Default::default(),
             enable: true,
+            weight: Float::new(1.0f64),
+            timeout: Duration::from_millis(5000),
         }
     }
 }
 
 impl IntoValue for SpamDnsblServerHeader {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(7);
+        let mut map = jmap_tools::Map::with_capacity(9);
         map.insert_unchecked(Property::Tag, self.tag.into_value());
         map.insert_unchecked(Property::Zone, self.zone.into_value());
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::Description, self.description.into_value());
         map.insert_unchecked(Property::Enable, self.enable.into_value());
+        map.insert_unchecked(Property::Weight, self.weight.into_value());
+        map.insert_unchecked(Property::Timeout, self.timeout.into_value());
         JmapValue::Object(map)
     }
 }
@@ -29947,6 +32280,8 @@ impl RegistryJsonPropertyPatch for SpamDnsblServerHeader {
             ),
             Some(Property::Description) => self.description.patch(pointer, value),
             Some(Property::Enable) => self.enable.patch(pointer, value),
+            Some(Property::Weight) => self.weight.patch(pointer, value),
+            Some(Property::Timeout) => self.timeout.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -29972,6 +32307,13 @@ impl SpamDnsblServerIp {
                 errors.push(ValidationError::required(Property::Description));
             }
         }
+        let value = &self.weight;
+        if *value > Float::new(100.0) {
+            errors.push(ValidationError::max_value(Property::Weight, 100));
+        }
+        if *value < Float::new(0.0) {
+            errors.push(ValidationError::min_value(Property::Weight, 0));
+        }
         errors.len() == neb
     }
 
@@ -30013,6 +32355,8 @@ impl Pickle for SpamDnsblServerIp {
         self.name.pickle(out);
         self.description.pickle(out);
         self.enable.pickle(out);
+        self.weight.pickle(out);
+        self.timeout.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -30022,6 +32366,8 @@ impl Pickle for SpamDnsblServerIp {
         this.name = Pickle::unpickle(stream)?;
         this.description = Pickle::unpickle(stream)?;
         this.enable = Pickle::unpickle(stream)?;
+        this.weight = Pickle::unpickle(stream)?;
+        this.timeout = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -30034,18 +32380,22 @@ impl Default for SpamDnsblServerIp {
             name: Default::default(),
             description: Default::default(),
             enable: true,
+            weight: Float::new(1.0f64),
+            timeout: Duration::from_millis(5000),
         }
     }
 }
 
 impl IntoValue for SpamDnsblServerIp {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(7);
+        let mut map = jmap_tools::Map::with_capacity(9);
         map.insert_unchecked(Property::Tag, self.tag.into_value());
         map.insert_unchecked(Property::Zone, self.zone.into_value());
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::Description, self.description.into_value());
         map.insert_unchecked(Property::Enable, self.enable.into_value());
+        map.insert_unchecked(Property::Weight, self.weight.into_value());
+        map.insert_unchecked(Property::Timeout, self.timeout.into_value());
         JmapValue::Object(map)
     }
 }
@@ -30067,6 +32417,8 @@ impl RegistryJsonPropertyPatch for SpamDnsblServerIp {
             ),
             Some(Property::Description) => self.description.patch(pointer, value),
             Some(Property::Enable) => self.enable.patch(pointer, value),
+            Some(Property::Weight) => self.weight.patch(pointer, value),
+            Some(Property::Timeout) => self.timeout.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -30092,6 +32444,13 @@ impl SpamDnsblServerUrl {
                 errors.push(ValidationError::required(Property::Description));
             }
         }
+        let value = &self.weight;
+        if *value > Float::new(100.0) {
+            errors.push(ValidationError::max_value(Property::Weight, 100));
+        }
+        if *value < Float::new(0.0) {
+            errors.push(ValidationError::min_value(Property::Weight, 0));
+        }
         errors.len() == neb
     }
 
@@ -30133,6 +32492,8 @@ impl Pickle for SpamDnsblServerUrl {
         self.name.pickle(out);
         self.description.pickle(out);
         self.enable.pickle(out);
+        self.weight.pickle(out);
+        self.timeout.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -30142,6 +32503,8 @@ impl Pickle for SpamDnsblServerUrl {
         this.name = Pickle::unpickle(stream)?;
         this.description = Pickle::unpickle(stream)?;
         this.enable = Pickle::unpickle(stream)?;
+        this.weight = Pickle::unpickle(stream)?;
+        this.timeout = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -30154,18 +32517,22 @@ impl Default for SpamDnsblServerUrl {
             name: Default::default(),
             description: Default::default(),
             enable: true,
+            weight: Float::new(1.0f64),
+            timeout: Duration::from_millis(5000),
         }
     }
 }
 
 impl IntoValue for SpamDnsblServerUrl {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(7);
+        let mut map = jmap_tools::Map::with_capacity(9);
         map.insert_unchecked(Property::Tag, self.tag.into_value());
         map.insert_unchecked(Property::Zone, self.zone.into_value());
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::Description, self.description.into_value());
         map.insert_unchecked(Property::Enable, self.enable.into_value());
+        map.insert_unchecked(Property::Weight, self.weight.into_value());
+        map.insert_unchecked(Property::Timeout, self.timeout.into_value());
         JmapValue::Object(map)
     }
 }
@@ -30187,6 +32554,8 @@ impl RegistryJsonPropertyPatch for SpamDnsblServerUrl {
             ),
             Some(Property::Description) => self.description.patch(pointer, value),
             Some(Property::Enable) => self.enable.patch(pointer, value),
+            Some(Property::Weight) => self.weight.patch(pointer, value),
+            Some(Property::Timeout) => self.timeout.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -30219,6 +32588,18 @@ impl ObjectImpl for SpamDnsblSettings {
         if *value < 1 {
             errors.push(ValidationError::min_value(Property::UrlLimit, 1));
         }
+        let value = &self.max_failures;
+        if *value < 1 {
+            errors.push(ValidationError::min_value(Property::MaxFailures, 1));
+        }
+        if let Some(value) = &self.score_reject {
+            if *value > Float::new(1000.0) {
+                errors.push(ValidationError::max_value(Property::ScoreReject, 1000));
+            }
+            if *value < Float::new(0.0) {
+                errors.push(ValidationError::min_value(Property::ScoreReject, 0));
+            }
+        }
         errors.len() == neb
     }
 
@@ -30231,6 +32612,9 @@ impl Pickle for SpamDnsblSettings {
         self.email_limit.pickle(out);
         self.ip_limit.pickle(out);
         self.url_limit.pickle(out);
+        self.max_failures.pickle(out);
+        self.disable_for.pickle(out);
+        self.score_reject.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -30239,6 +32623,9 @@ impl Pickle for SpamDnsblSettings {
         this.email_limit = Pickle::unpickle(stream)?;
         this.ip_limit = Pickle::unpickle(stream)?;
         this.url_limit = Pickle::unpickle(stream)?;
+        this.max_failures = Pickle::unpickle(stream)?;
+        this.disable_for = Pickle::unpickle(stream)?;
+        this.score_reject = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -30250,17 +32637,23 @@ impl Default for SpamDnsblSettings {
             email_limit: 50u64,
             ip_limit: 50u64,
             url_limit: 50u64,
+            max_failures: 5u64,
+            disable_for: Duration::from_millis(3600000),
+            score_reject: Default::default(),
         }
     }
 }
 
 impl IntoValue for SpamDnsblSettings {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(6);
+        let mut map = jmap_tools::Map::with_capacity(9);
         map.insert_unchecked(Property::DomainLimit, self.domain_limit.into_value());
         map.insert_unchecked(Property::EmailLimit, self.email_limit.into_value());
         map.insert_unchecked(Property::IpLimit, self.ip_limit.into_value());
         map.insert_unchecked(Property::UrlLimit, self.url_limit.into_value());
+        map.insert_unchecked(Property::MaxFailures, self.max_failures.into_value());
+        map.insert_unchecked(Property::DisableFor, self.disable_for.into_value());
+        map.insert_unchecked(Property::ScoreReject, self.score_reject.into_value());
         JmapValue::Object(map)
     }
 }
@@ -30276,6 +32669,9 @@ impl RegistryJsonPropertyPatch for SpamDnsblSettings {
             Some(Property::EmailLimit) => self.email_limit.patch(pointer, value),
             Some(Property::IpLimit) => self.ip_limit.patch(pointer, value),
             Some(Property::UrlLimit) => self.url_limit.patch(pointer, value),
+            Some(Property::MaxFailures) => self.max_failures.patch(pointer, value),
+            Some(Property::DisableFor) => self.disable_for.patch(pointer, value),
+            Some(Property::ScoreReject) => self.score_reject.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -30755,6 +33151,113 @@ impl RegistryJsonPropertyPatch for SpamPyzor {
     }
 }
 
+impl ObjectImpl for SpamRspamd {
+    const FLAGS: u64 = OBJ_SINGLETON;
+    const VERSION: u8 = 0;
+    const OBJECT: ObjectType = ObjectType::SpamRspamd;
+
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.url;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Url));
+        }
+        let value = &self.http_auth;
+        value.validate(errors);
+        let value = &self.http_headers;
+        for value in value.values() {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::HttpHeaders));
+            }
+        }
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, _: &mut IndexBuilder<'x>) {}
+}
+
+impl Pickle for SpamRspamd {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.enable.pickle(out);
+        self.url.pickle(out);
+        self.timeout.pickle(out);
+        self.allow_invalid_certs.pickle(out);
+        self.replace_score.pickle(out);
+        self.http_auth.pickle(out);
+        self.http_headers.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.enable = Pickle::unpickle(stream)?;
+        this.url = Pickle::unpickle(stream)?;
+        this.timeout = Pickle::unpickle(stream)?;
+        this.allow_invalid_certs = Pickle::unpickle(stream)?;
+        this.replace_score = Pickle::unpickle(stream)?;
+        this.http_auth = Pickle::unpickle(stream)?;
+        this.http_headers = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for SpamRspamd {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            url: "http://127.0.0.1:11333".to_string(),
+            timeout: Duration::from_millis(10000),
+            allow_invalid_certs: false,
+            replace_score: true,
+            http_auth: Default::default(),
+            http_headers: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for SpamRspamd {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(9);
+        map.insert_unchecked(Property::Enable, self.enable.into_value());
+        map.insert_unchecked(Property::Url, self.url.into_value());
+        map.insert_unchecked(Property::Timeout, self.timeout.into_value());
+        map.insert_unchecked(
+            Property::AllowInvalidCerts,
+            self.allow_invalid_certs.into_value(),
+        );
+        map.insert_unchecked(Property::ReplaceScore, self.replace_score.into_value());
+        map.insert_unchecked(Property::HttpAuth, self.http_auth.into_value());
+        map.insert_unchecked(Property::HttpHeaders, self.http_headers.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for SpamRspamd {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Enable) => self.enable.patch(pointer, value),
+            Some(Property::Url) => self
+                .url
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::Timeout) => self.timeout.patch(pointer, value),
+            Some(Property::AllowInvalidCerts) => self.allow_invalid_certs.patch(pointer, value),
+            Some(Property::ReplaceScore) => self.replace_score.patch(pointer, value),
+            Some(Property::HttpAuth) => self.http_auth.patch(pointer, value),
+            Some(Property::HttpHeaders) => self
+                .http_headers
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl ObjectImpl for SpamRule {
     const FLAGS: u64 = 0;
     const VERSION: u8 = 0;
@@ -31802,6 +34305,13 @@ impl ObjectImpl for SpamSettings {
                 errors.push(ValidationError::required(Property::SpamFilterRulesUrl));
             }
         }
+        let value = &self.trap_penalty_score;
+        if *value > Float::new(100.0) {
+            errors.push(ValidationError::max_value(Property::TrapPenaltyScore, 100));
+        }
+        if *value < Float::new(-100.0) {
+            errors.push(ValidationError::min_value(Property::TrapPenaltyScore, -100));
+        }
         errors.len() == neb
     }
 
@@ -31818,6 +34328,9 @@ impl Pickle for SpamSettings {
         self.score_spam.pickle(out);
         self.trust_replies.pickle(out);
         self.spam_filter_rules_url.pickle(out);
+        self.trap_block_ip.pickle(out);
+        self.trap_penalty_for.pickle(out);
+        self.trap_penalty_score.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -31830,6 +34343,9 @@ impl Pickle for SpamSettings {
         this.score_spam = Pickle::unpickle(stream)?;
         this.trust_replies = Pickle::unpickle(stream)?;
         this.spam_filter_rules_url = Pickle::unpickle(stream)?;
+        this.trap_block_ip = Pickle::unpickle(stream)?;
+        this.trap_penalty_for = Pickle::unpickle(stream)?;
+        this.trap_penalty_score = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -31845,13 +34361,16 @@ impl Default for SpamSettings {
             score_spam: Float::new(5.0f64),
             trust_replies: true,
             spam_filter_rules_url: Some("https://github.com/stalwartlabs/spam-filter/releases/latest/download/spam-filter-rules.json.gz".to_string()),
+            trap_block_ip: false,
+            trap_penalty_for: Some(Duration::from_millis(604800000)),
+            trap_penalty_score: Float::new(5.0f64),
         }
     }
 }
 
 impl IntoValue for SpamSettings {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(10);
+        let mut map = jmap_tools::Map::with_capacity(13);
         map.insert_unchecked(Property::TrustContacts, self.trust_contacts.into_value());
         map.insert_unchecked(Property::Enable, self.enable.into_value());
         map.insert_unchecked(Property::GreylistFor, self.greylist_for.into_value());
@@ -31863,6 +34382,12 @@ impl IntoValue for SpamSettings {
             Property::SpamFilterRulesUrl,
             self.spam_filter_rules_url.into_value(),
         );
+        map.insert_unchecked(Property::TrapBlockIp, self.trap_block_ip.into_value());
+        map.insert_unchecked(Property::TrapPenaltyFor, self.trap_penalty_for.into_value());
+        map.insert_unchecked(
+            Property::TrapPenaltyScore,
+            self.trap_penalty_score.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -31884,6 +34409,9 @@ impl RegistryJsonPropertyPatch for SpamSettings {
             Some(Property::SpamFilterRulesUrl) => self
                 .spam_filter_rules_url
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::TrapBlockIp) => self.trap_block_ip.patch(pointer, value),
+            Some(Property::TrapPenaltyFor) => self.trap_penalty_for.patch(pointer, value),
+            Some(Property::TrapPenaltyScore) => self.trap_penalty_score.patch(pointer, value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -32639,6 +35167,8 @@ impl Pickle for SqlDirectory {
         self.column_secret.pickle(out);
         self.column_class.pickle(out);
         self.column_description.pickle(out);
+        self.column_proxy_backend.pickle(out);
+        self.column_login_referral.pickle(out);
         self.query_login.pickle(out);
         self.query_recipient.pickle(out);
         self.query_member_of.pickle(out);
@@ -32654,6 +35184,8 @@ impl Pickle for SqlDirectory {
         this.column_secret = Pickle::unpickle(stream)?;
         this.column_class = Pickle::unpickle(stream)?;
         this.column_description = Pickle::unpickle(stream)?;
+        this.column_proxy_backend = Pickle::unpickle(stream)?;
+        this.column_login_referral = Pickle::unpickle(stream)?;
         this.query_login = Pickle::unpickle(stream)?;
         this.query_recipient = Pickle::unpickle(stream)?;
         this.query_member_of = Pickle::unpickle(stream)?;
@@ -32672,6 +35204,8 @@ impl Default for SqlDirectory {
             column_secret: "secret".to_string(),
             column_class: Some("type".to_string()),
             column_description: Some("description".to_string()),
+            column_proxy_backend: Default::default(),
+            column_login_referral: Default::default(),
             query_login: "SELECT name, secret, description, type FROM accounts WHERE name = $1".to_string(),
             query_recipient: "SELECT name, secret, description, type FROM accounts WHERE name = $1 AND active = true".to_string(),
             query_member_of: Some("SELECT member_of FROM group_members WHERE name = $1".to_string()),
@@ -32683,7 +35217,7 @@ impl Default for SqlDirectory {
 
 impl IntoValue for SqlDirectory {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(13);
+        let mut map = jmap_tools::Map::with_capacity(15);
         map.insert_unchecked(Property::Description, self.description.into_value());
         map.insert_unchecked(Property::Store, self.store.into_value());
         map.insert_unchecked(Property::ColumnEmail, self.column_email.into_value());
@@ -32693,6 +35227,14 @@ impl IntoValue for SqlDirectory {
             Property::ColumnDescription,
             self.column_description.into_value(),
         );
+        map.insert_unchecked(
+            Property::ColumnProxyBackend,
+            self.column_proxy_backend.into_value(),
+        );
+        map.insert_unchecked(
+            Property::ColumnLoginReferral,
+            self.column_login_referral.into_value(),
+        );
         map.insert_unchecked(Property::QueryLogin, self.query_login.into_value());
         map.insert_unchecked(Property::QueryRecipient, self.query_recipient.into_value());
         map.insert_unchecked(Property::QueryMemberOf, self.query_member_of.into_value());
@@ -32728,6 +35270,12 @@ impl RegistryJsonPropertyPatch for SqlDirectory {
             Some(Property::ColumnDescription) => self
                 .column_description
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::ColumnProxyBackend) => self
+                .column_proxy_backend
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::ColumnLoginReferral) => self
+                .column_login_referral
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
             Some(Property::QueryLogin) => self
                 .query_login
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
@@ -33091,6 +35639,127 @@ impl RegistryJsonPropertyPatch for SubAddressingCustom {
     }
 }
 
+impl ObjectImpl for SuppressedAddress {
+    const FLAGS: u64 = OBJ_FILTER_TENANT;
+    const VERSION: u8 = 0;
+    const OBJECT: ObjectType = ObjectType::SuppressedAddress;
+
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.email;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Email));
+        }
+        if let Some(value) = &self.details {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::Details));
+            }
+        }
+        let value = &self.created_at;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::CreatedAt, value));
+        }
+        if let Some(value) = &self.expires_at {
+            if !value.is_valid() {
+                errors.push(ValidationError::invalid(Property::ExpiresAt, value));
+            }
+        }
+        if let Some(value) = &self.member_tenant_id {
+            if !value.is_valid() {
+                errors.push(ValidationError::required(Property::MemberTenantId));
+            }
+        }
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        i.search(Property::Email, &self.email);
+        i.foreign_key(ObjectType::Tenant, self.member_tenant_id, None);
+        if let Some(value) = &self.member_tenant_id {
+            i.search(Property::MemberTenantId, value);
+        }
+    }
+}
+
+impl Pickle for SuppressedAddress {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.email.pickle(out);
+        self.reason.pickle(out);
+        self.details.pickle(out);
+        self.created_at.pickle(out);
+        self.expires_at.pickle(out);
+        self.member_tenant_id.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.email = Pickle::unpickle(stream)?;
+        this.reason = Pickle::unpickle(stream)?;
+        this.details = Pickle::unpickle(stream)?;
+        this.created_at = Pickle::unpickle(stream)?;
+        this.expires_at = Pickle::unpickle(stream)?;
+        this.member_tenant_id = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for SuppressedAddress {
+    fn default() -> Self {
+        Self {
+            email: Default::default(),
+            reason: SuppressionReason::Manual,
+            details: Default::default(),
+            created_at: Default::default(),
+            expires_at: Default::default(),
+            member_tenant_id: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for SuppressedAddress {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(8);
+        map.insert_unchecked(Property::Email, self.email.into_value());
+        map.insert_unchecked(Property::Reason, self.reason.into_value());
+        map.insert_unchecked(Property::Details, self.details.into_value());
+        map.insert_unchecked(Property::CreatedAt, self.created_at.into_value());
+        map.insert_unchecked(Property::ExpiresAt, self.expires_at.into_value());
+        map.insert_unchecked(Property::MemberTenantId, self.member_tenant_id.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for SuppressedAddress {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Email) => self.email.patch(
+                pointer
+                    .assert_read_only()?
+                    .with_validators(&[StringValidator::Email]),
+                value,
+            ),
+            Some(Property::Reason) => self.reason.patch(pointer, value),
+            Some(Property::Details) => self
+                .details
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::CreatedAt) => pointer.assert_server_set(),
+            Some(Property::ExpiresAt) => self.expires_at.patch(pointer, value),
+            Some(Property::MemberTenantId) => self
+                .member_tenant_id
+                .patch(pointer.assert_can_set_tenant()?, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl ObjectImpl for SystemSettings {
     const FLAGS: u64 = OBJ_SINGLETON;
     const VERSION: u8 = 0;
@@ -33143,12 +35812,22 @@ impl ObjectImpl for SystemSettings {
                 errors.push(ValidationError::required(Property::ProviderInfo));
             }
         }
+        if let Some(value) = &self.default_acme_provider_id {
+            if !value.is_valid() {
+                errors.push(ValidationError::required(Property::DefaultAcmeProviderId));
+            }
+        }
         errors.len() == neb
     }
 
     fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
         i.foreign_key(ObjectType::Domain, self.default_domain_id.into(), None);
         i.foreign_key(ObjectType::Certificate, self.default_certificate_id, None);
+        i.foreign_key(
+            ObjectType::AcmeProvider,
+            self.default_acme_provider_id,
+            None,
+        );
     }
 }
 
@@ -33163,6 +35842,7 @@ impl Pickle for SystemSettings {
         self.mail_exchangers.pickle(out);
         self.services.pickle(out);
         self.provider_info.pickle(out);
+        self.default_acme_provider_id.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -33176,6 +35856,7 @@ impl Pickle for SystemSettings {
         this.mail_exchangers = Pickle::unpickle(stream)?;
         this.services = Pickle::unpickle(stream)?;
         this.provider_info = Pickle::unpickle(stream)?;
+        this.default_acme_provider_id = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -33252,13 +35933,14 @@ impl Default for SystemSettings {
                 ),
             ]),
             provider_info: Default::default(),
+            default_acme_provider_id: Default::default(),
         }
     }
 }
 
 impl IntoValue for SystemSettings {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(11);
+        let mut map = jmap_tools::Map::with_capacity(12);
         map.insert_unchecked(
             Property::DefaultHostname,
             self.default_hostname.into_value(),
@@ -33280,6 +35962,10 @@ impl IntoValue for SystemSettings {
         map.insert_unchecked(Property::MailExchangers, self.mail_exchangers.into_value());
         map.insert_unchecked(Property::Services, self.services.into_value());
         map.insert_unchecked(Property::ProviderInfo, self.provider_info.into_value());
+        map.insert_unchecked(
+            Property::DefaultAcmeProviderId,
+            self.default_acme_provider_id.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -33306,6 +35992,9 @@ impl RegistryJsonPropertyPatch for SystemSettings {
             Some(Property::MailExchangers) => self.mail_exchangers.patch(pointer, value),
             Some(Property::Services) => self.services.patch(pointer, value),
             Some(Property::ProviderInfo) => self.provider_info.patch(pointer, value),
+            Some(Property::DefaultAcmeProviderId) => {
+                self.default_acme_provider_id.patch(pointer, value)
+            }
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -33340,6 +36029,10 @@ impl ObjectImpl for Task {
             Task::AcmeRenewal(inner) => inner.validate(errors),
             Task::DkimManagement(inner) => inner.validate(errors),
             Task::DnsManagement(inner) => inner.validate(errors),
+            Task::CalendarSubscriptionSync(inner) => inner.validate(errors),
+            Task::AddressBookDirectorySync(inner) => inner.validate(errors),
+            Task::AccountMigration(inner) => inner.validate(errors),
+            Task::LoginAnomaly(inner) => inner.validate(errors),
         }
     }
 
@@ -33395,6 +36088,18 @@ impl ObjectImpl for Task {
             Task::DnsManagement(object) => {
                 object.index(i);
             }
+            Task::CalendarSubscriptionSync(object) => {
+                object.index(i);
+            }
+            Task::AddressBookDirectorySync(object) => {
+                object.index(i);
+            }
+            Task::AccountMigration(object) => {
+                object.index(i);
+            }
+            Task::LoginAnomaly(object) => {
+                object.index(i);
+            }
         }
     }
 }
@@ -33480,6 +36185,22 @@ impl Pickle for Task {
                 17u16.pickle(out);
                 inner.pickle(out);
             }
+            Task::CalendarSubscriptionSync(inner) => {
+                18u16.pickle(out);
+                inner.pickle(out);
+            }
+            Task::AddressBookDirectorySync(inner) => {
+                19u16.pickle(out);
+                inner.pickle(out);
+            }
+            Task::AccountMigration(inner) => {
+                20u16.pickle(out);
+                inner.pickle(out);
+            }
+            Task::LoginAnomaly(inner) => {
+                21u16.pickle(out);
+                inner.pickle(out);
+            }
         }
     }
 
@@ -33503,6 +36224,10 @@ impl Pickle for Task {
             15 => Pickle::unpickle(stream).map(Task::AcmeRenewal),
             16 => Pickle::unpickle(stream).map(Task::DkimManagement),
             17 => Pickle::unpickle(stream).map(Task::DnsManagement),
+            18 => Pickle::unpickle(stream).map(Task::CalendarSubscriptionSync),
+            19 => Pickle::unpickle(stream).map(Task::AddressBookDirectorySync),
+            20 => Pickle::unpickle(stream).map(Task::AccountMigration),
+            21 => Pickle::unpickle(stream).map(Task::LoginAnomaly),
             _ => None,
         }
     }
@@ -33639,6 +36364,36 @@ impl IntoValue for Task {
                     .insert_unchecked(Property::Type, JmapValue::Str("DnsManagement".into()));
                 obj
             }
+            Task::CalendarSubscriptionSync(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut().unwrap().insert_unchecked(
+                    Property::Type,
+                    JmapValue::Str("CalendarSubscriptionSync".into()),
+                );
+                obj
+            }
+            Task::AddressBookDirectorySync(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut().unwrap().insert_unchecked(
+                    Property::Type,
+                    JmapValue::Str("AddressBookDirectorySync".into()),
+                );
+                obj
+            }
+            Task::AccountMigration(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("AccountMigration".into()));
+                obj
+            }
+            Task::LoginAnomaly(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("LoginAnomaly".into()));
+                obj
+            }
         }
     }
 }
@@ -33681,6 +36436,14 @@ impl RegistryJsonPatch for Task {
                 TaskType::AcmeRenewal => *self = Task::AcmeRenewal(Default::default()),
                 TaskType::DkimManagement => *self = Task::DkimManagement(Default::default()),
                 TaskType::DnsManagement => *self = Task::DnsManagement(Default::default()),
+                TaskType::CalendarSubscriptionSync => {
+                    *self = Task::CalendarSubscriptionSync(Default::default())
+                }
+                TaskType::AddressBookDirectorySync => {
+                    *self = Task::AddressBookDirectorySync(Default::default())
+                }
+                TaskType::AccountMigration => *self = Task::AccountMigration(Default::default()),
+                TaskType::LoginAnomaly => *self = Task::LoginAnomaly(Default::default()),
             }
         }
         match self {
@@ -33702,6 +36465,10 @@ impl RegistryJsonPatch for Task {
             Task::AcmeRenewal(inner) => inner.patch(pointer, value),
             Task::DkimManagement(inner) => inner.patch(pointer, value),
             Task::DnsManagement(inner) => inner.patch(pointer, value),
+            Task::CalendarSubscriptionSync(inner) => inner.patch(pointer, value),
+            Task::AddressBookDirectorySync(inner) => inner.patch(pointer, value),
+            Task::AccountMigration(inner) => inner.patch(pointer, value),
+            Task::LoginAnomaly(inner) => inner.patch(pointer, value),
         }
     }
 }
@@ -33727,6 +36494,10 @@ impl Task {
             Task::AcmeRenewal(_) => TaskType::AcmeRenewal,
             Task::DkimManagement(_) => TaskType::DkimManagement,
             Task::DnsManagement(_) => TaskType::DnsManagement,
+            Task::CalendarSubscriptionSync(_) => TaskType::CalendarSubscriptionSync,
+            Task::AddressBookDirectorySync(_) => TaskType::AddressBookDirectorySync,
+            Task::AccountMigration(_) => TaskType::AccountMigration,
+            Task::LoginAnomaly(_) => TaskType::LoginAnomaly,
         }
     }
 }
@@ -33811,6 +36582,200 @@ impl RegistryJsonPropertyPatch for TaskAccountMaintenance {
     }
 }
 
+impl TaskAccountMigration {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.account_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::required(Property::AccountId));
+        }
+        let value = &self.username;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Username));
+        }
+        let value = &self.secret;
+        value.validate(errors);
+        let value = &self.status;
+        value.validate(errors);
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        i.foreign_key(ObjectType::Account, self.account_id.into(), None);
+    }
+}
+
+impl Pickle for TaskAccountMigration {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.account_id.pickle(out);
+        self.username.pickle(out);
+        self.secret.pickle(out);
+        self.imap_url.pickle(out);
+        self.sieve_url.pickle(out);
+        self.caldav_url.pickle(out);
+        self.carddav_url.pickle(out);
+        self.allow_invalid_certs.pickle(out);
+        self.status.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.account_id = Pickle::unpickle(stream)?;
+        this.username = Pickle::unpickle(stream)?;
+        this.secret = Pickle::unpickle(stream)?;
+        this.imap_url = Pickle::unpickle(stream)?;
+        this.sieve_url = Pickle::unpickle(stream)?;
+        this.caldav_url = Pickle::unpickle(stream)?;
+        this.carddav_url = Pickle::unpickle(stream)?;
+        this.allow_invalid_certs = Pickle::unpickle(stream)?;
+        this.status = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for TaskAccountMigration {
+    fn default() -> Self {
+        Self {
+            account_id: Default::default(),
+            username: Default::default(),
+            secret: Default::default(),
+            imap_url: Default::default(),
+            sieve_url: Default::default(),
+            caldav_url: Default::default(),
+            carddav_url: Default::default(),
+            allow_invalid_certs: false,
+            status: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for TaskAccountMigration {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(11);
+        map.insert_unchecked(Property::AccountId, self.account_id.into_value());
+        map.insert_unchecked(Property::Username, self.username.into_value());
+        map.insert_unchecked(Property::Secret, self.secret.into_value());
+        map.insert_unchecked(Property::ImapUrl, self.imap_url.into_value());
+        map.insert_unchecked(Property::SieveUrl, self.sieve_url.into_value());
+        map.insert_unchecked(Property::CaldavUrl, self.caldav_url.into_value());
+        map.insert_unchecked(Property::CarddavUrl, self.carddav_url.into_value());
+        map.insert_unchecked(
+            Property::AllowInvalidCerts,
+            self.allow_invalid_certs.into_value(),
+        );
+        map.insert_unchecked(Property::Status, self.status.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for TaskAccountMigration {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::AccountId) => self
+                .account_id
+                .patch(pointer.assert_read_only()?.assert_can_set_account()?, value),
+            Some(Property::Username) => self
+                .username
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::Secret) => self.secret.patch(pointer, value),
+            Some(Property::ImapUrl) => self
+                .imap_url
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::SieveUrl) => self
+                .sieve_url
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::CaldavUrl) => self
+                .caldav_url
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::CarddavUrl) => self
+                .carddav_url
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::AllowInvalidCerts) => self.allow_invalid_certs.patch(pointer, value),
+            Some(Property::Status) => self.status.patch(pointer, value),
+            Some(Property::Due) => pointer.assert_server_set(),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
+impl TaskAddressBookDirectory {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.account_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::required(Property::AccountId));
+        }
+        let value = &self.status;
+        value.validate(errors);
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        i.foreign_key(ObjectType::Account, self.account_id.into(), None);
+    }
+}
+
+impl Pickle for TaskAddressBookDirectory {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.account_id.pickle(out);
+        self.status.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.account_id = Pickle::unpickle(stream)?;
+        this.status = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for TaskAddressBookDirectory {
+    fn default() -> Self {
+        Self {
+            account_id: Default::default(),
+            status: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for TaskAddressBookDirectory {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(4);
+        map.insert_unchecked(Property::AccountId, self.account_id.into_value());
+        map.insert_unchecked(Property::Status, self.status.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for TaskAddressBookDirectory {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::AccountId) => self
+                .account_id
+                .patch(pointer.assert_read_only()?.assert_can_set_account()?, value),
+            Some(Property::Status) => self.status.patch(pointer, value),
+            Some(Property::Due) => pointer.assert_server_set(),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl TaskCalendarAlarmEmail {
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
         let neb = errors.len();
@@ -34209,6 +37174,87 @@ impl RegistryJsonPropertyPatch for TaskCalendarItipMessage {
     }
 }
 
+impl TaskCalendarSubscription {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.account_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::required(Property::AccountId));
+        }
+        let value = &self.document_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::DocumentId, value));
+        }
+        let value = &self.status;
+        value.validate(errors);
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        i.foreign_key(ObjectType::Account, self.account_id.into(), None);
+    }
+}
+
+impl Pickle for TaskCalendarSubscription {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.account_id.pickle(out);
+        self.document_id.pickle(out);
+        self.status.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.account_id = Pickle::unpickle(stream)?;
+        this.document_id = Pickle::unpickle(stream)?;
+        this.status = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for TaskCalendarSubscription {
+    fn default() -> Self {
+        Self {
+            account_id: Default::default(),
+            document_id: Default::default(),
+            status: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for TaskCalendarSubscription {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(5);
+        map.insert_unchecked(Property::AccountId, self.account_id.into_value());
+        map.insert_unchecked(Property::DocumentId, self.document_id.into_value());
+        map.insert_unchecked(Property::Status, self.status.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for TaskCalendarSubscription {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::AccountId) => self
+                .account_id
+                .patch(pointer.assert_read_only()?.assert_can_set_account()?, value),
+            Some(Property::DocumentId) => {
+                self.document_id.patch(pointer.assert_read_only()?, value)
+            }
+            Some(Property::Status) => self.status.patch(pointer, value),
+            Some(Property::Due) => pointer.assert_server_set(),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl TaskDestroyAccount {
     fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
         let neb = errors.len();
@@ -34679,6 +37725,121 @@ impl RegistryJsonPropertyPatch for TaskIndexTrace {
     }
 }
 
+impl TaskLoginAnomaly {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.account_id;
+        if !value.is_valid() {
+            errors.push(ValidationError::required(Property::AccountId));
+        }
+        let value = &self.remote_ip;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::RemoteIp, value));
+        }
+        let value = &self.login_at;
+        if !value.is_valid() {
+            errors.push(ValidationError::invalid(Property::LoginAt, value));
+        }
+        let value = &self.status;
+        value.validate(errors);
+        errors.len() == neb
+    }
+
+    fn index<'x>(&'x self, i: &mut IndexBuilder<'x>) {
+        i.foreign_key(ObjectType::Account, self.account_id.into(), None);
+    }
+}
+
+impl Pickle for TaskLoginAnomaly {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.account_id.pickle(out);
+        self.anomaly_type.pickle(out);
+        self.remote_ip.pickle(out);
+        self.country.pickle(out);
+        self.previous_country.pickle(out);
+        self.protocol.pickle(out);
+        self.login_at.pickle(out);
+        self.status.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.account_id = Pickle::unpickle(stream)?;
+        this.anomaly_type = Pickle::unpickle(stream)?;
+        this.remote_ip = Pickle::unpickle(stream)?;
+        this.country = Pickle::unpickle(stream)?;
+        this.previous_country = Pickle::unpickle(stream)?;
+        this.protocol = Pickle::unpickle(stream)?;
+        this.login_at = Pickle::unpickle(stream)?;
+        this.status = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for TaskLoginAnomaly {
+    fn default() -> Self {
+        Self {
+            account_id: Default::default(),
+            anomaly_type: LoginAnomalyType::NewCountry,
+            remote_ip: Default::default(),
+            country: Default::default(),
+            previous_country: Default::default(),
+            protocol: NetworkListenerProtocol::Smtp,
+            login_at: Default::default(),
+            status: Default::default(),
+        }
+    }
+}
+
+impl IntoValue for TaskLoginAnomaly {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(10);
+        map.insert_unchecked(Property::AccountId, self.account_id.into_value());
+        map.insert_unchecked(Property::AnomalyType, self.anomaly_type.into_value());
+        map.insert_unchecked(Property::RemoteIp, self.remote_ip.into_value());
+        map.insert_unchecked(Property::Country, self.country.into_value());
+        map.insert_unchecked(
+            Property::PreviousCountry,
+            self.previous_country.into_value(),
+        );
+        map.insert_unchecked(Property::Protocol, self.protocol.into_value());
+        map.insert_unchecked(Property::LoginAt, self.login_at.into_value());
+        map.insert_unchecked(Property::Status, self.status.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for TaskLoginAnomaly {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::AccountId) => self
+                .account_id
+                .patch(pointer.assert_read_only()?.assert_can_set_account()?, value),
+            Some(Property::AnomalyType) => {
+                self.anomaly_type.patch(pointer.assert_read_only()?, value)
+            }
+            Some(Property::RemoteIp) => self.remote_ip.patch(pointer.assert_read_only()?, value),
+            Some(Property::Country) => self.country.patch(pointer.assert_read_only()?, value),
+            Some(Property::PreviousCountry) => self
+                .previous_country
+                .patch(pointer.assert_read_only()?, value),
+            Some(Property::Protocol) => self.protocol.patch(pointer.assert_read_only()?, value),
+            Some(Property::LoginAt) => self.login_at.patch(pointer.assert_read_only()?, value),
+            Some(Property::Status) => self.status.patch(pointer, value),
+            Some(Property::Due) => pointer.assert_server_set(),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl ObjectImpl for TaskManager {
     const FLAGS: u64 = OBJ_SINGLETON;
     const VERSION: u8 = 0;
@@ -34877,6 +38038,7 @@ impl Pickle for TaskRestoreArchivedItem {
         self.created_at.pickle(out);
         self.archived_until.pickle(out);
         self.account_id.pickle(out);
+        self.mailbox_id.pickle(out);
         self.status.pickle(out);
     }
 
@@ -34887,6 +38049,7 @@ impl Pickle for TaskRestoreArchivedItem {
         this.created_at = Pickle::unpickle(stream)?;
         this.archived_until = Pickle::unpickle(stream)?;
         this.account_id = Pickle::unpickle(stream)?;
+        this.mailbox_id = Pickle::unpickle(stream)?;
         this.status = Pickle::unpickle(stream)?;
         Some(this)
     }
@@ -34900,6 +38063,7 @@ impl Default for TaskRestoreArchivedItem {
             created_at: Default::default(),
             archived_until: Default::default(),
             account_id: Default::default(),
+            mailbox_id: Default::default(),
             status: Default::default(),
         }
     }
@@ -34907,7 +38071,7 @@ impl Default for TaskRestoreArchivedItem {
 
 impl IntoValue for TaskRestoreArchivedItem {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(8);
+        let mut map = jmap_tools::Map::with_capacity(9);
         map.insert_unchecked(Property::BlobId, self.blob_id.into_value());
         map.insert_unchecked(
             Property::ArchivedItemType,
@@ -34916,6 +38080,7 @@ impl IntoValue for TaskRestoreArchivedItem {
         map.insert_unchecked(Property::CreatedAt, self.created_at.into_value());
         map.insert_unchecked(Property::ArchivedUntil, self.archived_until.into_value());
         map.insert_unchecked(Property::AccountId, self.account_id.into_value());
+        map.insert_unchecked(Property::MailboxId, self.mailbox_id.into_value());
         map.insert_unchecked(Property::Status, self.status.into_value());
         JmapValue::Object(map)
     }
@@ -34933,6 +38098,7 @@ impl RegistryJsonPropertyPatch for TaskRestoreArchivedItem {
             Some(Property::CreatedAt) => pointer.assert_server_set(),
             Some(Property::ArchivedUntil) => pointer.assert_server_set(),
             Some(Property::AccountId) => pointer.assert_server_set(),
+            Some(Property::MailboxId) => pointer.assert_server_set(),
             Some(Property::Status) => self.status.patch(pointer, value),
             Some(Property::Due) => pointer.assert_server_set(),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
@@ -35791,6 +38957,16 @@ impl ObjectImpl for Tenant {
         value.validate(errors);
         let value = &self.permissions;
         value.validate(errors);
+        let value = &self.retention_policies;
+        for value in value.values() {
+            value.validate(errors);
+        }
+        let value = &self.provider_info;
+        for value in value.values() {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::ProviderInfo));
+            }
+        }
         errors.len() == neb
     }
 
@@ -35805,9 +38981,15 @@ impl Pickle for Tenant {
         self.name.pickle(out);
         self.created_at.pickle(out);
         self.logo.pickle(out);
+        self.provider_info.pickle(out);
         self.roles.pickle(out);
         self.permissions.pickle(out);
         self.quotas.pickle(out);
+        self.index_attachment_contents.pickle(out);
+        self.retention_policies.pickle(out);
+        self.disabled_jmap_capabilities.pickle(out);
+        self.encryption_policy.pickle(out);
+        self.login_anomaly_sensitivity.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -35815,9 +38997,15 @@ impl Pickle for Tenant {
         this.name = Pickle::unpickle(stream)?;
         this.created_at = Pickle::unpickle(stream)?;
         this.logo = Pickle::unpickle(stream)?;
+        this.provider_info = Pickle::unpickle(stream)?;
         this.roles = Pickle::unpickle(stream)?;
         this.permissions = Pickle::unpickle(stream)?;
         this.quotas = Pickle::unpickle(stream)?;
+        this.index_attachment_contents = Pickle::unpickle(stream)?;
+        this.retention_policies = Pickle::unpickle(stream)?;
+        this.disabled_jmap_capabilities = Pickle::unpickle(stream)?;
+        this.encryption_policy = Pickle::unpickle(stream)?;
+        this.login_anomaly_sensitivity = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -35828,22 +39016,49 @@ impl Default for Tenant {
             name: Default::default(),
             created_at: Default::default(),
             logo: Default::default(),
+            provider_info: Default::default(),
             roles: Default::default(),
             permissions: Default::default(),
             quotas: Default::default(),
+            index_attachment_contents: false,
+            retention_policies: Default::default(),
+            disabled_jmap_capabilities: Default::default(),
+            encryption_policy: EncryptionPolicy::Optional,
+            login_anomaly_sensitivity: Default::default(),
         }
     }
 }
 
 impl IntoValue for Tenant {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(8);
+        let mut map = jmap_tools::Map::with_capacity(14);
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::CreatedAt, self.created_at.into_value());
         map.insert_unchecked(Property::Logo, self.logo.into_value());
+        map.insert_unchecked(Property::ProviderInfo, self.provider_info.into_value());
         map.insert_unchecked(Property::Roles, self.roles.into_value());
         map.insert_unchecked(Property::Permissions, self.permissions.into_value());
         map.insert_unchecked(Property::Quotas, self.quotas.into_value());
+        map.insert_unchecked(
+            Property::IndexAttachmentContents,
+            self.index_attachment_contents.into_value(),
+        );
+        map.insert_unchecked(
+            Property::RetentionPolicies,
+            self.retention_policies.into_value(),
+        );
+        map.insert_unchecked(
+            Property::DisabledJmapCapabilities,
+            self.disabled_jmap_capabilities.into_value(),
+        );
+        map.insert_unchecked(
+            Property::EncryptionPolicy,
+            self.encryption_policy.into_value(),
+        );
+        map.insert_unchecked(
+            Property::LoginAnomalySensitivity,
+            self.login_anomaly_sensitivity.into_value(),
+        );
         JmapValue::Object(map)
     }
 }
@@ -35858,10 +39073,22 @@ impl RegistryJsonPropertyPatch for Tenant {
             Some(Property::Name) => self.name.patch(pointer, value),
             Some(Property::CreatedAt) => pointer.assert_server_set(),
             Some(Property::Logo) => self.logo.patch(pointer, value),
+            Some(Property::ProviderInfo) => self.provider_info.patch(pointer, value),
             Some(Property::Roles) => self.roles.patch(pointer, value),
             Some(Property::Permissions) => self.permissions.patch(pointer, value),
             Some(Property::Quotas) => self.quotas.patch(pointer, value),
+            Some(Property::IndexAttachmentContents) => {
+                self.index_attachment_contents.patch(pointer, value)
+            }
+            Some(Property::RetentionPolicies) => self.retention_policies.patch(pointer, value),
+            Some(Property::DisabledJmapCapabilities) => {
+                self.disabled_jmap_capabilities.patch(pointer, value)
+            }
+            Some(Property::EncryptionPolicy) => self.encryption_policy.patch(pointer, value),
             Some(Property::UsedDiskQuota) => pointer.assert_server_set(),
+            Some(Property::LoginAnomalySensitivity) => {
+                self.login_anomaly_sensitivity.patch(pointer, value)
+            }
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -37688,6 +40915,7 @@ impl ObjectImpl for Tracer {
             Tracer::Journal(inner) => inner.validate(errors),
             Tracer::OtelHttp(inner) => inner.validate(errors),
             Tracer::OtelGrpc(inner) => inner.validate(errors),
+            Tracer::Syslog(inner) => inner.validate(errors),
         }
     }
 
@@ -37723,6 +40951,10 @@ impl Pickle for Tracer {
                 4u16.pickle(out);
                 inner.pickle(out);
             }
+            Tracer::Syslog(inner) => {
+                5u16.pickle(out);
+                inner.pickle(out);
+            }
         }
     }
 
@@ -37733,6 +40965,7 @@ impl Pickle for Tracer {
             2 => Pickle::unpickle(stream).map(Tracer::Journal),
             3 => Pickle::unpickle(stream).map(Tracer::OtelHttp),
             4 => Pickle::unpickle(stream).map(Tracer::OtelGrpc),
+            5 => Pickle::unpickle(stream).map(Tracer::Syslog),
             _ => None,
         }
     }
@@ -37776,6 +41009,13 @@ impl IntoValue for Tracer {
                     .insert_unchecked(Property::Type, JmapValue::Str("OtelGrpc".into()));
                 obj
             }
+            Tracer::Syslog(obj) => {
+                let mut obj = obj.into_value();
+                obj.as_object_mut()
+                    .unwrap()
+                    .insert_unchecked(Property::Type, JmapValue::Str("Syslog".into()));
+                obj
+            }
         }
     }
 }
@@ -37793,6 +41033,7 @@ impl RegistryJsonPatch for Tracer {
                 TracerType::Journal => *self = Tracer::Journal(Default::default()),
                 TracerType::OtelHttp => *self = Tracer::OtelHttp(Default::default()),
                 TracerType::OtelGrpc => *self = Tracer::OtelGrpc(Default::default()),
+                TracerType::Syslog => *self = Tracer::Syslog(Default::default()),
             }
         }
         match self {
@@ -37801,6 +41042,7 @@ impl RegistryJsonPatch for Tracer {
             Tracer::Journal(inner) => inner.patch(pointer, value),
             Tracer::OtelHttp(inner) => inner.patch(pointer, value),
             Tracer::OtelGrpc(inner) => inner.patch(pointer, value),
+            Tracer::Syslog(inner) => inner.patch(pointer, value),
         }
     }
 }
@@ -37813,6 +41055,7 @@ impl Tracer {
             Tracer::Journal(_) => TracerType::Journal,
             Tracer::OtelHttp(_) => TracerType::OtelHttp,
             Tracer::OtelGrpc(_) => TracerType::OtelGrpc,
+            Tracer::Syslog(_) => TracerType::Syslog,
         }
     }
 }
@@ -38342,6 +41585,98 @@ impl RegistryJsonPropertyPatch for TracerStdout {
     }
 }
 
+impl TracerSyslog {
+    fn validate(&self, errors: &mut Vec<ValidationError>) -> bool {
+        let neb = errors.len();
+        let value = &self.address;
+        if value.is_empty() {
+            errors.push(ValidationError::required(Property::Address));
+        }
+        let value = &self.facility;
+        if *value > 23 {
+            errors.push(ValidationError::max_value(Property::Facility, 23));
+        }
+        errors.len() == neb
+    }
+}
+
+impl Pickle for TracerSyslog {
+    fn pickle(&self, out: &mut Vec<u8>) {
+        self.address.pickle(out);
+        self.facility.pickle(out);
+        self.enable.pickle(out);
+        self.level.pickle(out);
+        self.lossy.pickle(out);
+        self.events.pickle(out);
+        self.events_policy.pickle(out);
+    }
+
+    fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
+        let mut this = Self::default();
+        this.address = Pickle::unpickle(stream)?;
+        this.facility = Pickle::unpickle(stream)?;
+        this.enable = Pickle::unpickle(stream)?;
+        this.level = Pickle::unpickle(stream)?;
+        this.lossy = Pickle::unpickle(stream)?;
+        this.events = Pickle::unpickle(stream)?;
+        this.events_policy = Pickle::unpickle(stream)?;
+        Some(this)
+    }
+}
+
+impl Default for TracerSyslog {
+    fn default() -> Self {
+        Self {
+            address: "/dev/log".to_string(),
+            facility: 2,
+            enable: true,
+            level: TracingLevel::Info,
+            lossy: false,
+            events: Default::default(),
+            events_policy: EventPolicy::Exclude,
+        }
+    }
+}
+
+impl IntoValue for TracerSyslog {
+    fn into_value(self) -> JmapValue<'static> {
+        let mut map = jmap_tools::Map::with_capacity(9);
+        map.insert_unchecked(Property::Address, self.address.into_value());
+        map.insert_unchecked(Property::Facility, self.facility.into_value());
+        map.insert_unchecked(Property::Enable, self.enable.into_value());
+        map.insert_unchecked(Property::Level, self.level.into_value());
+        map.insert_unchecked(Property::Lossy, self.lossy.into_value());
+        map.insert_unchecked(Property::Events, self.events.into_value());
+        map.insert_unchecked(Property::EventsPolicy, self.events_policy.into_value());
+        JmapValue::Object(map)
+    }
+}
+
+impl RegistryJsonPropertyPatch for TracerSyslog {
+    fn patch_property<'x>(
+        &mut self,
+        mut pointer: JsonPointerPatch<'_>,
+        value: JmapValue<'x>,
+    ) -> PatchResult<'x> {
+        match pointer.next_property() {
+            Some(Property::Address) => self
+                .address
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::Facility) => self.facility.patch(pointer, value),
+            Some(Property::Enable) => self.enable.patch(pointer, value),
+            Some(Property::Level) => self.level.patch(pointer, value),
+            Some(Property::Lossy) => self.lossy.patch(pointer, value),
+            Some(Property::Events) => self.events.patch(pointer, value),
+            Some(Property::EventsPolicy) => self.events_policy.patch(pointer, value),
+            Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
+                property: Property::Type,
+                value,
+            }),
+            _ => Err(PatchError::new(pointer, "Invalid property")),
+        }
+    }
+}
+
 impl ObjectImpl for TracingStore {
     const FLAGS: u64 = OBJ_SINGLETON;
     const VERSION: u8 = 0;
@@ -38526,6 +41861,16 @@ impl UserAccount {
         }
         let value = &self.encryption_at_rest;
         value.validate(errors);
+        if let Some(value) = &self.proxy_backend {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::ProxyBackend));
+            }
+        }
+        if let Some(value) = &self.login_referral {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::LoginReferral));
+            }
+        }
         errors.len() == neb
     }
 
@@ -38576,6 +41921,13 @@ impl Pickle for UserAccount {
         self.locale.pickle(out);
         self.time_zone.pickle(out);
         self.encryption_at_rest.pickle(out);
+        self.scheduling_resource.pickle(out);
+        self.scheduling_policy.pickle(out);
+        self.calendar_auto_import.pickle(out);
+        self.legal_hold.pickle(out);
+        self.disabled_jmap_capabilities.pickle(out);
+        self.proxy_backend.pickle(out);
+        self.login_referral.pickle(out);
     }
 
     fn unpickle(stream: &mut crate::pickle::PickledStream<'_>) -> Option<Self> {
@@ -38594,6 +41946,13 @@ impl Pickle for UserAccount {
         this.locale = Pickle::unpickle(stream)?;
         this.time_zone = Pickle::unpickle(stream)?;
         this.encryption_at_rest = Pickle::unpickle(stream)?;
+        this.scheduling_resource = Pickle::unpickle(stream)?;
+        this.scheduling_policy = Pickle::unpickle(stream)?;
+        this.calendar_auto_import = Pickle::unpickle(stream)?;
+        this.legal_hold = Pickle::unpickle(stream)?;
+        this.disabled_jmap_capabilities = Pickle::unpickle(stream)?;
+        this.proxy_backend = Pickle::unpickle(stream)?;
+        this.login_referral = Pickle::unpickle(stream)?;
         Some(this)
     }
 }
@@ -38615,13 +41974,20 @@ impl Default for UserAccount {
             locale: Locale::EnUS,
             time_zone: Default::default(),
             encryption_at_rest: Default::default(),
+            scheduling_resource: Default::default(),
+            scheduling_policy: Default::default(),
+            calendar_auto_import: false,
+            legal_hold: false,
+            disabled_jmap_capabilities: Default::default(),
+            proxy_backend: Default::default(),
+            login_referral: Default::default(),
         }
     }
 }
 
 impl IntoValue for UserAccount {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(16);
+        let mut map = jmap_tools::Map::with_capacity(23);
         map.insert_unchecked(Property::Name, self.name.into_value());
         map.insert_unchecked(Property::DomainId, self.domain_id.into_value());
         map.insert_unchecked(Property::Credentials, self.credentials.into_value());
@@ -38639,6 +42005,25 @@ impl IntoValue for UserAccount {
             Property::EncryptionAtRest,
             self.encryption_at_rest.into_value(),
         );
+        map.insert_unchecked(
+            Property::SchedulingResource,
+            self.scheduling_resource.into_value(),
+        );
+        map.insert_unchecked(
+            Property::SchedulingPolicy,
+            self.scheduling_policy.into_value(),
+        );
+        map.insert_unchecked(
+            Property::CalendarAutoImport,
+            self.calendar_auto_import.into_value(),
+        );
+        map.insert_unchecked(Property::LegalHold, self.legal_hold.into_value());
+        map.insert_unchecked(
+            Property::DisabledJmapCapabilities,
+            self.disabled_jmap_capabilities.into_value(),
+        );
+        map.insert_unchecked(Property::ProxyBackend, self.proxy_backend.into_value());
+        map.insert_unchecked(Property::LoginReferral, self.login_referral.into_value());
         JmapValue::Object(map)
     }
 }
@@ -38671,6 +42056,19 @@ impl RegistryJsonPropertyPatch for UserAccount {
             Some(Property::Locale) => self.locale.patch(pointer, value),
             Some(Property::TimeZone) => self.time_zone.patch(pointer, value),
             Some(Property::EncryptionAtRest) => self.encryption_at_rest.patch(pointer, value),
+            Some(Property::SchedulingResource) => self.scheduling_resource.patch(pointer, value),
+            Some(Property::SchedulingPolicy) => self.scheduling_policy.patch(pointer, value),
+            Some(Property::CalendarAutoImport) => self.calendar_auto_import.patch(pointer, value),
+            Some(Property::LegalHold) => self.legal_hold.patch(pointer, value),
+            Some(Property::DisabledJmapCapabilities) => {
+                self.disabled_jmap_capabilities.patch(pointer, value)
+            }
+            Some(Property::ProxyBackend) => self
+                .proxy_backend
+                .patch(pointer.with_validators(&[StringValidator::Hostname]), value),
+            Some(Property::LoginReferral) => self
+                .login_referral
+                .patch(pointer.with_validators(&[StringValidator::Hostname]), value),
             Some(Property::Type) => Ok(MaybeUnpatched::Unpatched {
                 property: Property::Type,
                 value,
@@ -38898,6 +42296,22 @@ impl ObjectImpl for WebHook {
         let neb = errors.len();
         let value = &self.signature_key;
         value.validate(errors);
+        let value = &self.max_retries;
+        if *value > 100 {
+            errors.push(ValidationError::max_value(Property::MaxRetries, 100));
+        }
+        let value = &self.max_batch_size;
+        if *value > 100000 {
+            errors.push(ValidationError::max_value(Property::MaxBatchSize, 100000));
+        }
+        if *value < 1 {
+            errors.push(ValidationError::min_value(Property::MaxBatchSize, 1));
+        }
+        if let Some(value) = &self.dead_letter_url {
+            if value.is_empty() {
+                errors.push(ValidationError::required(Property::DeadLetterUrl));
+            }
+        }
         let value = &self.url;
         if value.is_empty() {
             errors.push(ValidationError::required(Property::Url));
@@ -38923,9 +42337,14 @@ impl Pickle for WebHook {
         self.throttle.pickle(out);
         self.timeout.pickle(out);
         self.discard_after.pickle(out);
+        self.max_retries.pickle(out);
+        self.retry_backoff.pickle(out);
+        self.max_batch_size.pickle(out);
+        self.dead_letter_url.pickle(out);
         self.url.pickle(out);
         self.http_auth.pickle(out);
         self.http_headers.pickle(out);
+        self.payload_template.pickle(out);
         self.enable.pickle(out);
         self.level.pickle(out);
         self.lossy.pickle(out);
@@ -38940,9 +42359,14 @@ impl Pickle for WebHook {
         this.throttle = Pickle::unpickle(stream)?;
         this.timeout = Pickle::unpickle(stream)?;
         this.discard_after = Pickle::unpickle(stream)?;
+        this.max_retries = Pickle::unpickle(stream)?;
+        this.retry_backoff = Pickle::unpickle(stream)?;
+        this.max_batch_size = Pickle::unpickle(stream)?;
+        this.dead_letter_url = Pickle::unpickle(stream)?;
         this.url = Pickle::unpickle(stream)?;
         this.http_auth = Pickle::unpickle(stream)?;
         this.http_headers = Pickle::unpickle(stream)?;
+        this.payload_template = Pickle::unpickle(stream)?;
         this.enable = Pickle::unpickle(stream)?;
         this.level = Pickle::unpickle(stream)?;
         this.lossy = Pickle::unpickle(stream)?;
@@ -38960,9 +42384,14 @@ impl Default for WebHook {
             throttle: Duration::from_millis(1000),
             timeout: Duration::from_millis(30000),
             discard_after: Duration::from_millis(300000),
+            max_retries: 5,
+            retry_backoff: Duration::from_millis(1000),
+            max_batch_size: 1000,
+            dead_letter_url: Default::default(),
             url: Default::default(),
             http_auth: Default::default(),
             http_headers: Default::default(),
+            payload_template: Default::default(),
             enable: true,
             level: TracingLevel::Info,
             lossy: false,
@@ -38974,7 +42403,7 @@ impl Default for WebHook {
 
 impl IntoValue for WebHook {
     fn into_value(self) -> JmapValue<'static> {
-        let mut map = jmap_tools::Map::with_capacity(15);
+        let mut map = jmap_tools::Map::with_capacity(20);
         map.insert_unchecked(
             Property::AllowInvalidCerts,
             self.allow_invalid_certs.into_value(),
@@ -38983,9 +42412,17 @@ impl IntoValue for WebHook {
         map.insert_unchecked(Property::Throttle, self.throttle.into_value());
         map.insert_unchecked(Property::Timeout, self.timeout.into_value());
         map.insert_unchecked(Property::DiscardAfter, self.discard_after.into_value());
+        map.insert_unchecked(Property::MaxRetries, self.max_retries.into_value());
+        map.insert_unchecked(Property::RetryBackoff, self.retry_backoff.into_value());
+        map.insert_unchecked(Property::MaxBatchSize, self.max_batch_size.into_value());
+        map.insert_unchecked(Property::DeadLetterUrl, self.dead_letter_url.into_value());
         map.insert_unchecked(Property::Url, self.url.into_value());
         map.insert_unchecked(Property::HttpAuth, self.http_auth.into_value());
         map.insert_unchecked(Property::HttpHeaders, self.http_headers.into_value());
+        map.insert_unchecked(
+            Property::PayloadTemplate,
+            self.payload_template.into_value(),
+        );
         map.insert_unchecked(Property::Enable, self.enable.into_value());
         map.insert_unchecked(Property::Level, self.level.into_value());
         map.insert_unchecked(Property::Lossy, self.lossy.into_value());
@@ -39007,6 +42444,12 @@ impl RegistryJsonPropertyPatch for WebHook {
             Some(Property::Throttle) => self.throttle.patch(pointer, value),
             Some(Property::Timeout) => self.timeout.patch(pointer, value),
             Some(Property::DiscardAfter) => self.discard_after.patch(pointer, value),
+            Some(Property::MaxRetries) => self.max_retries.patch(pointer, value),
+            Some(Property::RetryBackoff) => self.retry_backoff.patch(pointer, value),
+            Some(Property::MaxBatchSize) => self.max_batch_size.patch(pointer, value),
+            Some(Property::DeadLetterUrl) => self
+                .dead_letter_url
+                .patch(pointer.with_validators(&[StringValidator::Trim]), value),
             Some(Property::Url) => self
                 .url
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
@@ -39014,6 +42457,7 @@ impl RegistryJsonPropertyPatch for WebHook {
             Some(Property::HttpHeaders) => self
                 .http_headers
                 .patch(pointer.with_validators(&[StringValidator::Trim]), value),
+            Some(Property::PayloadTemplate) => self.payload_template.patch(pointer, value),
             Some(Property::Enable) => self.enable.patch(pointer, value),
             Some(Property::Level) => self.level.patch(pointer, value),
             Some(Property::Lossy) => self.lossy.patch(pointer, value),
@@ -30,6 +30,10 @@ impl Task {
             Task::DkimManagement(task) => task.status = status,
             Task::DnsManagement(task) => task.status = status,
             Task::TenantMaintenance(task) => task.status = status,
+            Task::CalendarSubscriptionSync(task) => task.status = status,
+            Task::AddressBookDirectorySync(task) => task.status = status,
+            Task::AccountMigration(task) => task.status = status,
+            Task::LoginAnomaly(task) => task.status = status,
         }
     }
 
@@ -53,6 +57,10 @@ impl Task {
             Task::DkimManagement(task) => &task.status,
             Task::DnsManagement(task) => &task.status,
             Task::TenantMaintenance(task) => &task.status,
+            Task::CalendarSubscriptionSync(task) => &task.status,
+            Task::AddressBookDirectorySync(task) => &task.status,
+            Task::AccountMigration(task) => &task.status,
+            Task::LoginAnomaly(task) => &task.status,
         }
     }
 
@@ -92,6 +100,10 @@ impl Task {
             Task::DkimManagement(_) => Permission::TaskDkimManagement,
             Task::DnsManagement(_) => Permission::TaskDnsManagement,
             Task::TenantMaintenance(_) => Permission::TaskTenantMaintenance,
+            Task::CalendarSubscriptionSync(_) => Permission::TaskCalendarSubscriptionSync,
+            Task::AddressBookDirectorySync(_) => Permission::TaskAddressBookDirectorySync,
+            Task::AccountMigration(_) => Permission::TaskAccountMigration,
+            Task::LoginAnomaly(_) => Permission::TaskLoginAnomaly,
         }
     }
 }
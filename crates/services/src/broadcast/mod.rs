@@ -5,13 +5,15 @@
  */
 
 use common::ipc::{
-    BroadcastEvent, CacheInvalidation, CalendarAlert, EmailPush, PushNotification, RegistryChange,
+    BroadcastEvent, CacheInvalidation, CalendarAlert, ClusterQuery, EmailPush, PushNotification,
+    RegistryChange, ReloadSubsystem,
 };
 use registry::{
     schema::prelude::ObjectType,
     types::{EnumImpl, id::ObjectId},
 };
 use std::{borrow::Borrow, io::Write};
+use store::dispatch::cache::ReadCacheEviction;
 use types::{id::Id, type_state::StateChange};
 use utils::{
     codec::leb128::{Leb128Iterator, Leb128Writer},
@@ -41,6 +43,12 @@ impl BroadcastBatch<Vec<BroadcastEvent>> {
         self.messages.len() < MAX_BATCH_SIZE
     }
 
+    pub fn has_store_evictions(&self) -> bool {
+        self.messages
+            .iter()
+            .any(|message| matches!(message, BroadcastEvent::StoreCacheInvalidate(_)))
+    }
+
     pub fn serialize(&self, node_id: u16) -> Vec<u8> {
         let mut serialized =
             Vec::with_capacity((self.messages.len() * 10) + std::mem::size_of::<u16>());
@@ -91,6 +99,10 @@ impl BroadcastBatch<Vec<BroadcastEvent>> {
                         serialized.push(6u8);
                         let _ = serialized.write_leb128(object.to_id());
                     }
+                    RegistryChange::ReloadSubsystem(subsystem) => {
+                        serialized.push(12u8);
+                        serialized.push(subsystem.to_id());
+                    }
                 },
                 BroadcastEvent::CacheInvalidate(items) => {
                     serialized.push(7u8);
@@ -119,6 +131,19 @@ impl BroadcastBatch<Vec<BroadcastEvent>> {
                 BroadcastEvent::CacheInvalidateNegative => {
                     serialized.push(9u8);
                 }
+                BroadcastEvent::StoreCacheInvalidate(eviction) => match eviction {
+                    ReadCacheEviction::Keys(keys) => {
+                        serialized.push(16u8);
+                        let _ = serialized.write_leb128(keys.len());
+                        for key in keys {
+                            let _ = serialized.write_leb128(key.len());
+                            let _ = serialized.write(key);
+                        }
+                    }
+                    ReadCacheEviction::All => {
+                        serialized.push(17u8);
+                    }
+                },
                 BroadcastEvent::MtaQueueStatus { is_running } => {
                     if *is_running {
                         serialized.push(10u8);
@@ -126,6 +151,28 @@ impl BroadcastBatch<Vec<BroadcastEvent>> {
                         serialized.push(11u8);
                     }
                 }
+                BroadcastEvent::ClusterQuery { request_id, query } => {
+                    serialized.push(13u8);
+                    let _ = serialized.write_leb128(*request_id);
+                    serialized.push(query.to_id());
+                }
+                BroadcastEvent::ClusterReply { request_id, reply } => {
+                    serialized.push(14u8);
+                    let _ = serialized.write_leb128(*request_id);
+                    let _ = serialized.write_leb128(reply.len());
+                    let _ = serialized.write(reply);
+                }
+                BroadcastEvent::TerminateSessions { account_id } => {
+                    serialized.push(15u8);
+                    let _ = serialized.write_leb128(*account_id);
+                }
+                BroadcastEvent::MtaQueueRefresh => {
+                    serialized.push(18u8);
+                }
+                BroadcastEvent::NodeDrain { node_id, draining } => {
+                    serialized.push(if *draining { 19u8 } else { 20u8 });
+                    let _ = serialized.write_leb128(*node_id);
+                }
             }
         }
         serialized
@@ -249,6 +296,61 @@ where
                 9 => Ok(Some(BroadcastEvent::CacheInvalidateNegative)),
                 10 => Ok(Some(BroadcastEvent::MtaQueueStatus { is_running: true })),
                 11 => Ok(Some(BroadcastEvent::MtaQueueStatus { is_running: false })),
+                12 => {
+                    let subsystem = self.messages.next().ok_or(())?.borrow().to_owned();
+                    Ok(Some(BroadcastEvent::RegistryChange(
+                        RegistryChange::ReloadSubsystem(
+                            ReloadSubsystem::from_id(subsystem).ok_or(())?,
+                        ),
+                    )))
+                }
+                13 => {
+                    let request_id = self.messages.next_leb128::<u64>().ok_or(())?;
+                    let query = self.messages.next().ok_or(())?.borrow().to_owned();
+                    Ok(Some(BroadcastEvent::ClusterQuery {
+                        request_id,
+                        query: ClusterQuery::from_id(query).ok_or(())?,
+                    }))
+                }
+                14 => {
+                    let request_id = self.messages.next_leb128::<u64>().ok_or(())?;
+                    let len = self.messages.next_leb128::<usize>().ok_or(())?;
+                    let mut reply = vec![0u8; len];
+                    for byte in reply.iter_mut() {
+                        *byte = self.messages.next().ok_or(())?.borrow().to_owned();
+                    }
+                    Ok(Some(BroadcastEvent::ClusterReply { request_id, reply }))
+                }
+                15 => Ok(Some(BroadcastEvent::TerminateSessions {
+                    account_id: self.messages.next_leb128().ok_or(())?,
+                })),
+                16 => {
+                    let count = self.messages.next_leb128::<usize>().ok_or(())?;
+                    let mut keys = Vec::with_capacity(count);
+                    for _ in 0..count {
+                        let len = self.messages.next_leb128::<usize>().ok_or(())?;
+                        let mut key = vec![0u8; len];
+                        for byte in key.iter_mut() {
+                            *byte = self.messages.next().ok_or(())?.borrow().to_owned();
+                        }
+                        keys.push(key.into_boxed_slice());
+                    }
+                    Ok(Some(BroadcastEvent::StoreCacheInvalidate(
+                        ReadCacheEviction::Keys(keys),
+                    )))
+                }
+                17 => Ok(Some(BroadcastEvent::StoreCacheInvalidate(
+                    ReadCacheEviction::All,
+                ))),
+                18 => Ok(Some(BroadcastEvent::MtaQueueRefresh)),
+                19 => Ok(Some(BroadcastEvent::NodeDrain {
+                    node_id: self.messages.next_leb128().ok_or(())?,
+                    draining: true,
+                })),
+                20 => Ok(Some(BroadcastEvent::NodeDrain {
+                    node_id: self.messages.next_leb128().ok_or(())?,
+                    draining: false,
+                })),
                 _ => Err(()),
             }
         } else {
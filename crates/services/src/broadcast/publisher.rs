@@ -7,6 +7,7 @@
 use std::sync::Arc;
 
 use common::{Inner, ipc::BroadcastEvent};
+use store::dispatch::cache::ReadCacheEviction;
 use tokio::sync::mpsc;
 use trc::ClusterEvent;
 
@@ -44,7 +45,13 @@ pub fn spawn_broadcast_publisher(inner: Arc<Inner>, mut event_rx: mpsc::Receiver
                     batch.clear();
                 }
                 Err(err) => {
+                    // Store cache invalidations cannot be lost, other nodes are asked
+                    // to flush their caches on the next publish
+                    let has_store_evictions = batch.has_store_evictions();
                     batch.clear();
+                    if has_store_evictions {
+                        batch.insert(BroadcastEvent::StoreCacheInvalidate(ReadCacheEviction::All));
+                    }
                     trc::event!(Cluster(ClusterEvent::PublisherError), CausedBy = err);
                 }
             }
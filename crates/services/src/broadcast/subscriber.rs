@@ -7,10 +7,11 @@
 use crate::broadcast::{BROADCAST_TOPIC, BroadcastBatch};
 use common::{
     BuildServer, Inner,
-    ipc::{BroadcastEvent, PushEvent, PushNotification, QueueEvent, RegistryChange},
+    ipc::{BroadcastEvent, ClusterReply, PushEvent, PushNotification, QueueEvent, RegistryChange},
 };
 use registry::types::EnumImpl;
 use std::{sync::Arc, time::Duration};
+use store::dispatch::cache::ReadCacheEviction;
 use tokio::sync::watch;
 use trc::{ClusterEvent, ServerEvent};
 
@@ -146,6 +147,11 @@ pub fn spawn_broadcast_subscriber(inner: Arc<Inner>, mut shutdown_rx: watch::Rec
                                             BroadcastEvent::CacheInvalidateNegative => {
                                                 inner.build_server().invalidate_all_local_negative_caches();
                                             }
+                                            BroadcastEvent::StoreCacheInvalidate(eviction) => {
+                                                if let Some(read_cache) = inner.build_server().store().read_cache() {
+                                                    read_cache.evict_local(&eviction);
+                                                }
+                                            }
                                             BroadcastEvent::MtaQueueStatus { is_running } => {
                                                 let _ = inner
                                                         .ipc
@@ -153,6 +159,23 @@ pub fn spawn_broadcast_subscriber(inner: Arc<Inner>, mut shutdown_rx: watch::Rec
                                                         .send(QueueEvent::Paused(!is_running))
                                                         .await;
                                             }
+                                            BroadcastEvent::ClusterQuery { request_id, query } => {
+                                                inner.build_server().reply_cluster_query(request_id, query).await;
+                                            }
+                                            BroadcastEvent::ClusterReply { request_id, reply } => {
+                                                inner.build_server().receive_cluster_reply(request_id, ClusterReply { node_id, reply });
+                                            }
+                                            BroadcastEvent::TerminateSessions { account_id } => {
+                                                inner.data.active_sessions.terminate(|session| session.account_id() == Some(account_id));
+                                            }
+                                            BroadcastEvent::MtaQueueRefresh => {
+                                                let _ = inner.ipc.queue_tx.send(QueueEvent::Refresh).await;
+                                            }
+                                            BroadcastEvent::NodeDrain { node_id: target_id, draining } => {
+                                                if target_id == this_node_id {
+                                                    inner.build_server().set_draining(draining).await;
+                                                }
+                                            }
                                             BroadcastEvent::RegistryChange(change) => {
                                                 match Box::pin(inner.build_server().reload_registry(change)).await {
                                                     Ok(result) => {
@@ -183,6 +206,11 @@ pub fn spawn_broadcast_subscriber(inner: Arc<Inner>, mut shutdown_rx: watch::Rec
                             trc::event!(
                                 Cluster(ClusterEvent::SubscriberDisconnected),
                             );
+
+                            // Invalidations sent while disconnected are lost
+                            if let Some(read_cache) = inner.build_server().store().read_cache() {
+                                read_cache.evict_local(&ReadCacheEviction::All);
+                            }
                         }
                     }
                 },
@@ -237,6 +265,9 @@ fn log_event(event: &BroadcastEvent) -> trc::Value {
             RegistryChange::Reload(object) => {
                 trc::Value::Array(vec!["RegistryReload".into(), object.as_str().into()])
             }
+            RegistryChange::ReloadSubsystem(subsystem) => {
+                trc::Value::Array(vec!["SubsystemReload".into(), subsystem.as_str().into()])
+            }
         },
         BroadcastEvent::CacheInvalidate(items) => {
             let mut array = Vec::with_capacity(items.len() + 1);
@@ -248,6 +279,12 @@ fn log_event(event: &BroadcastEvent) -> trc::Value {
         }
         BroadcastEvent::CacheInvalidateAll => "CacheInvalidateAll".into(),
         BroadcastEvent::CacheInvalidateNegative => "CacheInvalidateNegative".into(),
+        BroadcastEvent::StoreCacheInvalidate(eviction) => match eviction {
+            ReadCacheEviction::Keys(keys) => {
+                trc::Value::Array(vec!["StoreCacheInvalidate".into(), keys.len().into()])
+            }
+            ReadCacheEviction::All => "StoreCacheInvalidateAll".into(),
+        },
         BroadcastEvent::MtaQueueStatus { is_running } => {
             if *is_running {
                 "MtaQueueRunning".into()
@@ -255,5 +292,28 @@ fn log_event(event: &BroadcastEvent) -> trc::Value {
                 "MtaQueuePaused".into()
             }
         }
+        BroadcastEvent::ClusterQuery { request_id, query } => trc::Value::Array(vec![
+            "ClusterQuery".into(),
+            (*request_id).into(),
+            query.as_str().into(),
+        ]),
+        BroadcastEvent::ClusterReply { request_id, reply } => trc::Value::Array(vec![
+            "ClusterReply".into(),
+            (*request_id).into(),
+            reply.len().into(),
+        ]),
+        BroadcastEvent::TerminateSessions { account_id } => {
+            trc::Value::Array(vec!["TerminateSessions".into(), (*account_id).into()])
+        }
+        BroadcastEvent::MtaQueueRefresh => "MtaQueueRefresh".into(),
+        BroadcastEvent::NodeDrain { node_id, draining } => trc::Value::Array(vec![
+            if *draining {
+                "NodeDrain"
+            } else {
+                "NodeUndrain"
+            }
+            .into(),
+            (*node_id).into(),
+        ]),
     }
 }
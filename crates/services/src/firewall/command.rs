@@ -0,0 +1,197 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::FirewallUpdate;
+use registry::types::ipmask::IpAddrOrMask;
+use std::{
+    fmt::Write as _,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+pub(super) async fn publish_nftables(
+    update: &FirewallUpdate,
+    family: &str,
+    table: &str,
+    set_ipv4: &str,
+    set_ipv6: &str,
+) -> Result<(), String> {
+    let script = nft_script(update, &format!("{family} {table}"), set_ipv4, set_ipv6);
+    run("nft", &["-f", "-"], script).await
+}
+
+pub(super) async fn publish_ipset(
+    update: &FirewallUpdate,
+    set_ipv4: &str,
+    set_ipv6: &str,
+) -> Result<(), String> {
+    let script = ipset_script(update, set_ipv4, set_ipv6);
+    run("ipset", &["restore", "-exist"], script).await
+}
+
+async fn run(
+    program: &'static str,
+    args: &'static [&'static str],
+    script: String,
+) -> Result<(), String> {
+    if script.is_empty() {
+        return Ok(());
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|err| format!("Failed to execute {program}: {err}"))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(script.as_bytes())
+                .map_err(|err| format!("Failed to write to {program}: {err}"))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|err| format!("Failed to execute {program}: {err}"))?;
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(format!(
+                "{program} exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ))
+        }
+    })
+    .await
+    .map_err(|err| format!("Join error: {err}"))?
+}
+
+// All changes are applied in a single nft transaction, either every element
+// is updated or none is.
+fn nft_script(update: &FirewallUpdate, table: &str, set_ipv4: &str, set_ipv6: &str) -> String {
+    let mut script = String::new();
+
+    for (set, is_ipv4) in [(set_ipv4, true), (set_ipv6, false)] {
+        let mut removals = Vec::new();
+        let mut additions = Vec::new();
+
+        for address in &update.unban {
+            if is_ipv4_address(address) == is_ipv4 {
+                removals.push(address.to_string());
+            }
+        }
+        for ban in &update.ban {
+            if is_ipv4_address(&ban.address) == is_ipv4 {
+                if ban.replaces {
+                    removals.push(ban.address.to_string());
+                }
+                additions.push(match ban.timeout() {
+                    Some(timeout) => format!("{} timeout {timeout}s", ban.address),
+                    None => ban.address.to_string(),
+                });
+            }
+        }
+
+        if update.full {
+            let _ = writeln!(script, "flush set {table} {set}");
+        }
+        if !removals.is_empty() {
+            // Deleting an element that already timed out fails and aborts the
+            // whole transaction, adding it first makes the removal succeed
+            // whether or not it is still present.
+            let removals = removals.join(", ");
+            let _ = writeln!(script, "add element {table} {set} {{ {removals} }}");
+            let _ = writeln!(script, "delete element {table} {set} {{ {removals} }}");
+        }
+        if !additions.is_empty() {
+            let _ = writeln!(
+                script,
+                "add element {table} {set} {{ {} }}",
+                additions.join(", ")
+            );
+        }
+    }
+
+    script
+}
+
+// ipset restore with -exist ignores missing entries on removal and
+// refreshes the timeout of existing entries on addition.
+fn ipset_script(update: &FirewallUpdate, set_ipv4: &str, set_ipv6: &str) -> String {
+    let mut script = String::new();
+
+    if update.full {
+        let _ = writeln!(script, "flush {set_ipv4}");
+        let _ = writeln!(script, "flush {set_ipv6}");
+    }
+    for address in &update.unban {
+        let set = if is_ipv4_address(address) {
+            set_ipv4
+        } else {
+            set_ipv6
+        };
+        let _ = writeln!(script, "del {set} {address}");
+    }
+    for ban in &update.ban {
+        let set = if is_ipv4_address(&ban.address) {
+            set_ipv4
+        } else {
+            set_ipv6
+        };
+        // A zero timeout makes the entry permanent
+        let _ = writeln!(
+            script,
+            "add {set} {} timeout {}",
+            ban.address,
+            ban.timeout().unwrap_or_default()
+        );
+    }
+
+    script
+}
+
+fn is_ipv4_address(address: &IpAddrOrMask) -> bool {
+    matches!(address, IpAddrOrMask::V4 { .. })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FirewallUpdate, nft_script};
+    use crate::firewall::FirewallBan;
+
+    #[test]
+    fn nft_removals_tolerate_missing_elements() {
+        let update = FirewallUpdate {
+            full: false,
+            ban: vec![
+                FirewallBan {
+                    address: "192.0.2.1".parse().unwrap(),
+                    expires_at: None,
+                    replaces: true,
+                },
+                FirewallBan {
+                    address: "2001:db8::1".parse().unwrap(),
+                    expires_at: None,
+                    replaces: false,
+                },
+            ],
+            unban: vec!["192.0.2.2".parse().unwrap()],
+        };
+
+        assert_eq!(
+            nft_script(&update, "inet filter", "ban4", "ban6"),
+            concat!(
+                "add element inet filter ban4 { 192.0.2.2, 192.0.2.1 }\n",
+                "delete element inet filter ban4 { 192.0.2.2, 192.0.2.1 }\n",
+                "add element inet filter ban4 { 192.0.2.1 }\n",
+                "add element inet filter ban6 { 2001:db8::1 }\n",
+            )
+        );
+    }
+}
@@ -0,0 +1,243 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::FirewallUpdate;
+use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+use registry::types::{datetime::UTCDateTime, ipmask::IpAddrOrMask};
+use reqwest::{Method, Response};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use store::write::now;
+use utils::Client;
+
+const CROWDSEC_SCENARIO: &str = "stalwart/ban";
+const CROWDSEC_PERMANENT: &str = "87600h";
+
+#[derive(Serialize)]
+struct WebhookPayload {
+    sync: bool,
+    ban: Vec<WebhookBan>,
+    unban: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookBan {
+    address: String,
+    expires_at: Option<String>,
+}
+
+#[derive(Serialize)]
+struct CrowdsecLogin<'x> {
+    machine_id: &'x str,
+    password: &'x str,
+}
+
+#[derive(Deserialize)]
+struct CrowdsecToken {
+    token: String,
+}
+
+#[derive(Serialize)]
+struct CrowdsecAlert {
+    scenario: &'static str,
+    scenario_hash: &'static str,
+    scenario_version: &'static str,
+    message: String,
+    events_count: u32,
+    start_at: String,
+    stop_at: String,
+    capacity: u32,
+    leakspeed: &'static str,
+    simulated: bool,
+    events: Vec<()>,
+    source: CrowdsecSource,
+    decisions: Vec<CrowdsecDecision>,
+}
+
+#[derive(Serialize)]
+struct CrowdsecSource {
+    scope: &'static str,
+    value: String,
+}
+
+#[derive(Serialize)]
+struct CrowdsecDecision {
+    duration: String,
+    origin: &'static str,
+    scenario: &'static str,
+    scope: &'static str,
+    #[serde(rename = "type")]
+    typ: &'static str,
+    value: String,
+}
+
+pub(super) async fn publish_webhook(
+    update: &FirewallUpdate,
+    url: &str,
+    client: &Client,
+    timeout: Duration,
+) -> Result<(), String> {
+    let payload = WebhookPayload {
+        sync: update.full,
+        ban: update
+            .ban
+            .iter()
+            .map(|ban| WebhookBan {
+                address: ban.address.to_string(),
+                expires_at: ban
+                    .expires_at
+                    .map(|ts| UTCDateTime::from_timestamp(ts as i64).to_string()),
+            })
+            .collect(),
+        unban: update
+            .unban
+            .iter()
+            .map(|address| address.to_string())
+            .collect(),
+    };
+    let body = serde_json::to_string(&payload)
+        .map_err(|err| format!("Failed to serialize webhook payload: {err}"))?;
+
+    send(client.post(url).timeout(timeout).body(body), url).await?;
+
+    Ok(())
+}
+
+pub(super) async fn publish_crowdsec(
+    update: &FirewallUpdate,
+    url: &str,
+    machine_id: &str,
+    password: &str,
+    client: &Client,
+    timeout: Duration,
+) -> Result<(), String> {
+    // Obtain a watcher token
+    let login_url = format!("{url}/v1/watchers/login");
+    let body = serde_json::to_string(&CrowdsecLogin {
+        machine_id,
+        password,
+    })
+    .map_err(|err| format!("Failed to serialize CrowdSec login: {err}"))?;
+    let response = send(
+        client.post(&login_url).timeout(timeout).body(body),
+        &login_url,
+    )
+    .await?
+    .bytes()
+    .await
+    .map_err(|err| format!("Failed to read CrowdSec login response: {err}"))?;
+    let token = serde_json::from_slice::<CrowdsecToken>(&response)
+        .map_err(|err| format!("Invalid CrowdSec login response: {err}"))?
+        .token;
+
+    // Remove previous decisions
+    let mut deletions = Vec::with_capacity(update.unban.len() + 1);
+    if update.full {
+        deletions.push(format!(
+            "{url}/v1/decisions?scenario={}",
+            utf8_percent_encode(CROWDSEC_SCENARIO, NON_ALPHANUMERIC)
+        ));
+    }
+    for address in &update.unban {
+        deletions.push(format!(
+            "{url}/v1/decisions?scope={}&value={}",
+            crowdsec_scope(address),
+            utf8_percent_encode(&address.to_string(), NON_ALPHANUMERIC)
+        ));
+    }
+    for decisions_url in deletions {
+        send(
+            client
+                .request(Method::DELETE, &decisions_url)
+                .timeout(timeout)
+                .bearer_auth(&token),
+            &decisions_url,
+        )
+        .await?;
+    }
+
+    // Add new decisions
+    if !update.ban.is_empty() {
+        let timestamp = UTCDateTime::from_timestamp(now() as i64).to_string();
+        let alerts = update
+            .ban
+            .iter()
+            .map(|ban| {
+                let scope = crowdsec_scope(&ban.address);
+                let value = ban.address.to_string();
+                CrowdsecAlert {
+                    scenario: CROWDSEC_SCENARIO,
+                    scenario_hash: "",
+                    scenario_version: "",
+                    message: format!("Stalwart banned {value}"),
+                    events_count: 1,
+                    start_at: timestamp.clone(),
+                    stop_at: timestamp.clone(),
+                    capacity: 0,
+                    leakspeed: "0",
+                    simulated: false,
+                    events: vec![],
+                    source: CrowdsecSource {
+                        scope,
+                        value: value.clone(),
+                    },
+                    decisions: vec![CrowdsecDecision {
+                        duration: ban
+                            .timeout()
+                            .map(|timeout| format!("{timeout}s"))
+                            .unwrap_or_else(|| CROWDSEC_PERMANENT.to_string()),
+                        origin: "stalwart",
+                        scenario: CROWDSEC_SCENARIO,
+                        scope,
+                        typ: "ban",
+                        value,
+                    }],
+                }
+            })
+            .collect::<Vec<_>>();
+        let body = serde_json::to_string(&alerts)
+            .map_err(|err| format!("Failed to serialize CrowdSec alerts: {err}"))?;
+        let alerts_url = format!("{url}/v1/alerts");
+        send(
+            client
+                .post(&alerts_url)
+                .timeout(timeout)
+                .bearer_auth(&token)
+                .body(body),
+            &alerts_url,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+async fn send(request: reqwest::RequestBuilder, url: &str) -> Result<Response, String> {
+    let response = request
+        .send()
+        .await
+        .map_err(|err| format!("Request to {url} failed: {err}"))?;
+
+    if response.status().is_success() {
+        Ok(response)
+    } else {
+        Err(format!(
+            "Request to {} failed with code {}: {}",
+            url,
+            response.status().as_u16(),
+            response.status().canonical_reason().unwrap_or("Unknown")
+        ))
+    }
+}
+
+fn crowdsec_scope(address: &IpAddrOrMask) -> &'static str {
+    if address.try_to_ip().is_some() {
+        "Ip"
+    } else {
+        "Range"
+    }
+}
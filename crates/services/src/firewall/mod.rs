@@ -0,0 +1,207 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{
+    BuildServer, Inner, Server,
+    network::firewall::{FirewallBackend, FirewallSync},
+};
+use registry::types::ipmask::IpAddrOrMask;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use store::{ahash::AHashMap, write::now};
+use trc::SecurityEvent;
+
+mod command;
+mod http;
+
+const DISABLED_INTERVAL: Duration = Duration::from_secs(60);
+
+pub(crate) struct FirewallState {
+    config: Arc<FirewallSync>,
+    // Entries pushed to the firewall and their expiration, None when the
+    // firewall contents are unknown and a full reconciliation is required.
+    published: Option<AHashMap<IpAddrOrMask, u64>>,
+}
+
+pub(crate) struct FirewallUpdate {
+    pub full: bool,
+    pub ban: Vec<FirewallBan>,
+    pub unban: Vec<IpAddrOrMask>,
+}
+
+pub(crate) struct FirewallBan {
+    pub address: IpAddrOrMask,
+    pub expires_at: Option<u64>,
+    pub replaces: bool,
+}
+
+pub fn spawn_firewall_sync(inner: Arc<Inner>) {
+    tokio::spawn(async move {
+        let rx = inner.ipc.firewall_tx.clone();
+        let mut state: Option<FirewallState> = None;
+
+        loop {
+            let server = inner.build_server();
+            let sleep_for = if let Some(config) = &server.core.network.security.firewall_sync {
+                // Start over with a full reconciliation after a restart or a
+                // configuration change.
+                if state
+                    .as_ref()
+                    .is_none_or(|state| !Arc::ptr_eq(&state.config, config))
+                {
+                    state = Some(FirewallState {
+                        config: config.clone(),
+                        published: None,
+                    });
+                }
+                if let Some(state) = &mut state {
+                    server.sync_firewall(state).await;
+                }
+                config.interval
+            } else {
+                state = None;
+                DISABLED_INTERVAL
+            };
+
+            // Wait for a ban or unban, or until the next reconciliation is due
+            let _ = tokio::time::timeout(sleep_for, rx.notified()).await;
+        }
+    });
+}
+
+pub(crate) trait FirewallSyncManager: Sync + Send {
+    fn sync_firewall(&self, state: &mut FirewallState) -> impl Future<Output = ()> + Send;
+    fn blocked_entries(&self) -> AHashMap<IpAddrOrMask, u64>;
+}
+
+impl FirewallSyncManager for Server {
+    async fn sync_firewall(&self, state: &mut FirewallState) {
+        let desired = self.blocked_entries();
+        let now = now();
+        let update = match &state.published {
+            Some(published) => FirewallUpdate {
+                full: false,
+                ban: desired
+                    .iter()
+                    .filter(|(address, expires_at)| published.get(*address) != Some(*expires_at))
+                    .map(|(address, expires_at)| {
+                        FirewallBan::new(
+                            address.clone(),
+                            *expires_at,
+                            published.contains_key(address),
+                        )
+                    })
+                    .collect(),
+                // Entries that expired are removed by the firewall itself
+                unban: published
+                    .iter()
+                    .filter(|(address, expires_at)| {
+                        **expires_at > now && !desired.contains_key(*address)
+                    })
+                    .map(|(address, _)| address.clone())
+                    .collect(),
+            },
+            None => FirewallUpdate {
+                full: true,
+                ban: desired
+                    .iter()
+                    .map(|(address, expires_at)| {
+                        FirewallBan::new(address.clone(), *expires_at, false)
+                    })
+                    .collect(),
+                unban: vec![],
+            },
+        };
+
+        if !update.full && update.ban.is_empty() && update.unban.is_empty() {
+            return;
+        }
+
+        let time = Instant::now();
+        let backend = &state.config.backend;
+        let result = match backend {
+            FirewallBackend::Nftables {
+                family,
+                table,
+                set_ipv4,
+                set_ipv6,
+            } => command::publish_nftables(&update, family, table, set_ipv4, set_ipv6).await,
+            FirewallBackend::Ipset { set_ipv4, set_ipv6 } => {
+                command::publish_ipset(&update, set_ipv4, set_ipv6).await
+            }
+            FirewallBackend::Webhook {
+                url,
+                client,
+                timeout,
+            } => http::publish_webhook(&update, url, client, *timeout).await,
+            FirewallBackend::Crowdsec {
+                url,
+                machine_id,
+                password,
+                client,
+                timeout,
+            } => http::publish_crowdsec(&update, url, machine_id, password, client, *timeout).await,
+        };
+
+        match result {
+            Ok(_) => {
+                trc::event!(
+                    Security(SecurityEvent::FirewallSync),
+                    Type = backend.as_str(),
+                    Details = if update.full { "full" } else { "incremental" },
+                    Total = update.ban.len() + update.unban.len(),
+                    Elapsed = time.elapsed(),
+                );
+                state.published = Some(desired);
+            }
+            Err(err) => {
+                trc::event!(
+                    Security(SecurityEvent::FirewallSyncError),
+                    Type = backend.as_str(),
+                    Reason = err,
+                );
+                state.published = None;
+            }
+        }
+    }
+
+    fn blocked_entries(&self) -> AHashMap<IpAddrOrMask, u64> {
+        let blocked_ips = self.inner.data.blocked_ips.read();
+        let mut entries = AHashMap::with_capacity(
+            blocked_ips.blocked_ip_addresses.len() + blocked_ips.blocked_ip_networks.len(),
+        );
+
+        for entry in &blocked_ips.blocked_ip_addresses {
+            if !entry.is_expired() && !self.is_ip_allowed(entry.ip) {
+                entries.insert(IpAddrOrMask::from_ip(entry.ip), entry.expires_at);
+            }
+        }
+        for entry in &blocked_ips.blocked_ip_networks {
+            if !entry.is_expired() {
+                entries.insert(entry.ip.clone(), entry.expires_at);
+            }
+        }
+
+        entries
+    }
+}
+
+impl FirewallBan {
+    fn new(address: IpAddrOrMask, expires_at: u64, replaces: bool) -> Self {
+        FirewallBan {
+            address,
+            expires_at: (expires_at != u64::MAX).then_some(expires_at),
+            replaces,
+        }
+    }
+
+    pub fn timeout(&self) -> Option<u64> {
+        self.expires_at
+            .map(|expires_at| expires_at.saturating_sub(now()).max(1))
+    }
+}
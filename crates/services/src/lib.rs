@@ -14,9 +14,13 @@ use common::{
 use state_manager::manager::spawn_push_router;
 use std::sync::Arc;
 
-use crate::task_manager::{manager::spawn_task_manager, scheduler::spawn_task_scheduler};
+use crate::{
+    firewall::spawn_firewall_sync,
+    task_manager::{manager::spawn_task_manager, scheduler::spawn_task_scheduler},
+};
 
 pub mod broadcast;
+pub mod firewall;
 pub mod state_manager;
 pub mod task_manager;
 
@@ -59,6 +63,9 @@ impl SpawnServices for IpcReceivers {
             // Spawn task manager
             spawn_task_manager(inner.clone());
 
+            // Spawn firewall sync
+            spawn_firewall_sync(inner.clone());
+
             // Spawn task scheduler
             spawn_task_scheduler(inner);
         }
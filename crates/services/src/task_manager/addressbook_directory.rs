@@ -0,0 +1,197 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::task_manager::TaskResult;
+use common::Server;
+use groupware::{
+    cache::GroupwareCache,
+    contact::{
+        DIRECTORY_ADDRESSBOOK_ID,
+        directory::{
+            DirectoryAddressBook, DirectoryState, DirectoryStateEntry, directory_card_name,
+            remove_directory_addressbook, schedule_directory_sync, set_directory_state,
+        },
+    },
+};
+use registry::schema::structs::TaskAddressBookDirectory;
+use store::write::{BatchBuilder, now};
+use trc::{AddContext, TaskManagerEvent};
+use types::collection::{Collection, SyncCollection, VanishedCollection};
+
+pub(crate) trait AddressBookDirectoryTask: Sync + Send {
+    fn refresh_addressbook_directory(
+        &self,
+        task: &TaskAddressBookDirectory,
+    ) -> impl Future<Output = TaskResult> + Send;
+}
+
+impl AddressBookDirectoryTask for Server {
+    async fn refresh_addressbook_directory(&self, task: &TaskAddressBookDirectory) -> TaskResult {
+        match refresh_addressbook_directory(self, task).await {
+            Ok(result) => result,
+            Err(err) => {
+                let result = TaskResult::temporary(err.to_string());
+                trc::error!(
+                    err.account_id(task.account_id.document_id())
+                        .caused_by(trc::location!())
+                        .details("Failed to refresh global address list")
+                );
+                result
+            }
+        }
+    }
+}
+
+async fn refresh_addressbook_directory(
+    server: &Server,
+    task: &TaskAddressBookDirectory,
+) -> trc::Result<TaskResult> {
+    let account_id = task.account_id.document_id();
+    let Some(account_info) = server
+        .try_account(account_id)
+        .await
+        .caused_by(trc::location!())?
+    else {
+        trc::event!(
+            TaskManager(TaskManagerEvent::MetadataNotFound),
+            Details = "Account not found",
+            AccountId = account_id,
+        );
+
+        return Ok(TaskResult::Success(vec![]));
+    };
+    let Some(state) = server
+        .directory_state(account_id)
+        .await
+        .caused_by(trc::location!())?
+    else {
+        trc::event!(
+            TaskManager(TaskManagerEvent::MetadataNotFound),
+            Details = "Global address list not found",
+            AccountId = account_id,
+        );
+
+        return Ok(TaskResult::Success(vec![]));
+    };
+    let resources = server
+        .fetch_dav_resources(account_id, account_id, SyncCollection::AddressBook)
+        .await
+        .caused_by(trc::location!())?;
+
+    let config = &server.core.groupware;
+    let mut batch = BatchBuilder::new();
+
+    if let Some(name) = config
+        .directory_addressbook_name
+        .as_ref()
+        .filter(|_| account_info.is_user_account())
+    {
+        // Announce the differences between the shared snapshot and the cards
+        // this account was last told about
+        let cards = server.directory_cards(&account_info).await?;
+        let is_renamed = &state.name != name;
+        let mut entries = Vec::with_capacity(cards.cards.len());
+        let mut old_entries = state.entries.iter().peekable();
+
+        batch.with_account_id(account_id);
+        if is_renamed {
+            batch
+                .with_collection(Collection::AddressBook)
+                .with_document(DIRECTORY_ADDRESSBOOK_ID)
+                .log_container_update(SyncCollection::AddressBook);
+        }
+
+        for card in cards.cards.iter() {
+            while let Some(old_entry) = old_entries.next_if(|e| e.document_id < card.document_id) {
+                log_removed_card(&mut batch, &resources.base_path, &state.name, old_entry);
+            }
+
+            batch
+                .with_collection(Collection::ContactCard)
+                .with_document(card.document_id);
+            match old_entries.next_if(|e| e.document_id == card.document_id) {
+                Some(old_entry) if is_renamed => {
+                    batch
+                        .log_item_update(SyncCollection::AddressBook, None)
+                        .log_vanished_item(
+                            VanishedCollection::AddressBook,
+                            resources.format_item(&format!(
+                                "{}/{}",
+                                state.name,
+                                directory_card_name(old_entry.document_id)
+                            )),
+                        );
+                }
+                Some(old_entry) if old_entry.hash != card.hash => {
+                    batch.log_item_update(SyncCollection::AddressBook, None);
+                }
+                Some(_) => {}
+                None => {
+                    batch.log_item_insert(SyncCollection::AddressBook, None);
+                }
+            }
+
+            entries.push(DirectoryStateEntry {
+                document_id: card.document_id,
+                hash: card.hash,
+            });
+        }
+        for old_entry in old_entries {
+            log_removed_card(&mut batch, &resources.base_path, &state.name, old_entry);
+        }
+
+        if is_renamed || entries != state.entries {
+            set_directory_state(
+                account_id,
+                Some(DirectoryState {
+                    name: name.clone(),
+                    entries,
+                }),
+                &mut batch,
+            )
+            .caused_by(trc::location!())?;
+        }
+
+        // Schedule next refresh
+        schedule_directory_sync(
+            account_id,
+            now() as i64 + config.directory_addressbook_refresh as i64,
+            &mut batch,
+        );
+    } else {
+        // The global address list was disabled, remove it
+        remove_directory_addressbook(account_id, &resources.base_path, &state, &mut batch)
+            .caused_by(trc::location!())?;
+    }
+
+    if !batch.is_empty() {
+        server
+            .commit_batch(batch)
+            .await
+            .caused_by(trc::location!())?;
+    }
+
+    Ok(TaskResult::Success(vec![]))
+}
+
+fn log_removed_card(
+    batch: &mut BatchBuilder,
+    base_path: &str,
+    addressbook_name: &str,
+    entry: &DirectoryStateEntry,
+) {
+    batch
+        .with_collection(Collection::ContactCard)
+        .with_document(entry.document_id)
+        .log_item_delete(SyncCollection::AddressBook, None)
+        .log_vanished_item(
+            VanishedCollection::AddressBook,
+            format!(
+                "{base_path}{addressbook_name}/{}",
+                directory_card_name(entry.document_id)
+            ),
+        );
+}
@@ -53,8 +53,12 @@ impl DestroyAccountTask for Server {
 async fn destroy_account(server: &Server, task: &TaskDestroyAccount) -> trc::Result<TaskResult> {
     let account_id = task.account_id.document_id();
 
-    // Destroy public keys and masked emails
-    for object in [ObjectType::PublicKey, ObjectType::MaskedEmail] {
+    // Destroy public keys, masked emails and share links
+    for object in [
+        ObjectType::PublicKey,
+        ObjectType::MaskedEmail,
+        ObjectType::ShareLink,
+    ] {
         let mut batch = BatchBuilder::new();
         let ids = server
             .registry()
@@ -328,7 +328,11 @@ pub(crate) async fn reindex_telemetry(server: &Server) -> trc::Result<()> {
 }
 
 pub(crate) async fn reindex_account(server: &Server, account_id: u32) -> trc::Result<()> {
+    // Documents are spread over time so that reindexing a large account
+    // does not starve the task queue
     let now = now() as i64;
+    let rate = server.core.email.reindex_rate.max(1) as i64;
+    let mut position = 0i64;
 
     let mut batch = BatchBuilder::new();
 
@@ -345,8 +349,9 @@ pub(crate) async fn reindex_account(server: &Server, account_id: u32) -> trc::Re
             account_id: account_id.into(),
             document_id: document_id.into(),
             document_type: IndexDocumentType::Email,
-            status: TaskStatus::at(now + rand::rng().random_range(0..=300)),
+            status: TaskStatus::at(now + position / rate),
         }));
+        position += 1;
 
         if batch.is_large_batch() {
             server.core.storage.data.write(batch.build_all()).await?;
@@ -367,17 +372,17 @@ pub(crate) async fn reindex_account(server: &Server, account_id: u32) -> trc::Re
             )
             .await
             .caused_by(trc::location!())?;
-        let mut batch = BatchBuilder::new();
 
         for document_id in cache.document_ids(false) {
             batch.schedule_task(Task::IndexDocument(TaskIndexDocument {
                 account_id: account_id.into(),
                 document_id: document_id.into(),
                 document_type,
-                status: TaskStatus::at(now + rand::rng().random_range(0..=300)),
+                status: TaskStatus::at(now + position / rate),
             }));
+            position += 1;
 
-            if batch.len() >= 2000 {
+            if batch.is_large_batch() {
                 server.core.storage.data.write(batch.build_all()).await?;
                 batch = BatchBuilder::new();
             }
@@ -429,11 +434,29 @@ async fn build_email_document(
                         .details("Blob not found")
                 })?;
 
+            // Attachment contents are indexed if enabled for the account's tenant
+            let index_attachments = match server
+                .try_account(account_id)
+                .await
+                .caused_by(trc::location!())?
+                .and_then(|account| account.id_tenant)
+            {
+                Some(tenant_id) => {
+                    server
+                        .tenant(tenant_id)
+                        .await
+                        .caused_by(trc::location!())?
+                        .index_attachment_contents
+                }
+                None => server.core.email.index_attachment_contents,
+            };
+
             Ok(Some(metadata.index_document(
                 account_id,
                 document_id,
                 &raw_message,
                 index_fields,
+                index_attachments,
                 server.core.email.default_language,
             )))
         }
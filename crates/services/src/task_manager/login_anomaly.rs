@@ -0,0 +1,111 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::Server;
+use mail_builder::{MessageBuilder, headers::HeaderType, mime::make_boundary};
+use registry::{
+    schema::{enums::LoginAnomalyType, structs::TaskLoginAnomaly},
+    types::EnumImpl,
+};
+use smtp::reporting::send::MtaReportSend;
+use trc::AddContext;
+
+use crate::task_manager::TaskResult;
+
+pub(crate) trait LoginAnomalyTask: Sync + Send {
+    fn notify_login_anomaly(
+        &self,
+        task: &TaskLoginAnomaly,
+    ) -> impl Future<Output = TaskResult> + Send;
+}
+
+impl LoginAnomalyTask for Server {
+    async fn notify_login_anomaly(&self, task: &TaskLoginAnomaly) -> TaskResult {
+        match notify_login_anomaly(self, task).await {
+            Ok(result) => result,
+            Err(err) => {
+                let result = TaskResult::temporary(err.to_string());
+                trc::error!(
+                    err.account_id(task.account_id.document_id())
+                        .details("Failed to send login anomaly notification")
+                );
+                result
+            }
+        }
+    }
+}
+
+async fn notify_login_anomaly(
+    server: &Server,
+    task: &TaskLoginAnomaly,
+) -> trc::Result<TaskResult> {
+    let account_id = task.account_id.document_id();
+    let account = server
+        .account(account_id)
+        .await
+        .caused_by(trc::location!())?;
+    let rcpt_to = account.name();
+    let Some((_, domain)) = rcpt_to.rsplit_once('@') else {
+        return Ok(TaskResult::permanent("Account does not have an email address"));
+    };
+
+    let reason = match task.anomaly_type {
+        LoginAnomalyType::NewCountry => "from a country not seen before on this account",
+        LoginAnomalyType::ImpossibleTravel => {
+            "from a different country shortly after a previous login"
+        }
+        LoginAnomalyType::NewNetwork => "from a network not seen before on this account",
+        LoginAnomalyType::NewClient => "using a protocol not seen before on this account",
+    };
+    let mut details = format!(
+        "Time: {}\r\nIP address: {}\r\nProtocol: {}\r\n",
+        task.login_at,
+        task.remote_ip,
+        task.protocol.as_str(),
+    );
+    if let Some(country) = &task.country {
+        details.push_str(&format!("Country: {country}\r\n"));
+    }
+    if let Some(previous_country) = &task.previous_country {
+        details.push_str(&format!("Previous country: {previous_country}\r\n"));
+    }
+
+    let from = format!("security-notification@{domain}");
+    let message = MessageBuilder::new()
+        .from(from.as_str())
+        .to(rcpt_to)
+        .header("Auto-Submitted", HeaderType::Text("auto-generated".into()))
+        .message_id(format!(
+            "<{}@{}>",
+            make_boundary("."),
+            server.core.network.server_name
+        ))
+        .subject("Unusual sign-in to your account")
+        .text_body(format!(
+            concat!(
+                "A sign-in to the account {} was detected {}.\r\n\r\n",
+                "{}\r\n",
+                "If this was you, no action is needed. Otherwise, change your ",
+                "password and review the app passwords and API keys of your ",
+                "account.\r\n"
+            ),
+            rcpt_to, reason, details
+        ))
+        .write_to_vec()
+        .unwrap_or_default();
+
+    server
+        .send_autogenerated(
+            from.as_str(),
+            [rcpt_to].into_iter(),
+            message,
+            Some(&server.core.sieve.sign),
+            0,
+        )
+        .await;
+
+    Ok(TaskResult::Success(vec![]))
+}
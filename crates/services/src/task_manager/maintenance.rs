@@ -9,6 +9,7 @@ use std::time::Instant;
 use crate::task_manager::{
     TaskResult,
     index::{reindex_account, reindex_telemetry},
+    merge_threads::repair_threads,
 };
 use common::{
     KV_ACME, KV_GREYLIST, KV_LOCK_DAV, KV_LOCK_QUEUE_MESSAGE, KV_LOCK_TASK, KV_OAUTH,
@@ -19,7 +20,9 @@ use common::{
 };
 use email::{
     cache::MessageCacheFetch,
-    message::{delete::EmailDeletion, ingest::EmailIngest, metadata::MessageData},
+    message::{
+        delete::EmailDeletion, encrypt::EmailEncryption, ingest::EmailIngest, metadata::MessageData,
+    },
 };
 use groupware::{
     calendar::{Calendar, CalendarEvent, CalendarEventNotification},
@@ -28,7 +31,10 @@ use groupware::{
 };
 use registry::{
     schema::{
-        enums::{TaskAccountMaintenanceType, TaskStoreMaintenanceType, TaskTenantMaintenanceType},
+        enums::{
+            EncryptionPolicy, TaskAccountMaintenanceType, TaskStoreMaintenanceType,
+            TaskTenantMaintenanceType,
+        },
         prelude::{Object, ObjectInner, ObjectType, Property},
         structs::{
             Task, TaskAccountMaintenance, TaskStatus, TaskStoreMaintenance, TaskTenantMaintenance,
@@ -214,12 +220,26 @@ async fn store_maintenance(
                 .await
                 .caused_by(trc::location!())?;
 
+            if let Some(queue_store) = &server.core.storage.queue {
+                queue_store
+                    .purge_store()
+                    .await
+                    .caused_by(trc::location!())?;
+            }
+
             server
                 .in_memory_store()
                 .purge_in_memory_store()
                 .await
                 .caused_by(trc::location!())?;
 
+            if let Some(audit_retention) = server.core.audit.retention {
+                server
+                    .purge_audit_log(audit_retention)
+                    .await
+                    .caused_by(trc::location!())?;
+            }
+
             // SPDX-SnippetBegin
             // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
             // SPDX-License-Identifier: LicenseRef-SEL
@@ -270,6 +290,15 @@ async fn store_maintenance(
                     .purge_blobs(server.blob_store().clone(), shard_index as u8)
                     .await
                     .caused_by(trc::location!())?;
+
+                if let (Some(queue_store), Some(queue_blob)) =
+                    (&server.core.storage.queue, &server.core.storage.queue_blob)
+                {
+                    queue_store
+                        .purge_blobs(queue_blob.clone(), shard_index as u8)
+                        .await
+                        .caused_by(trc::location!())?;
+                }
             } else {
                 let mut batch = BatchBuilder::new();
                 let now = now() as i64;
@@ -421,6 +450,12 @@ async fn account_maintenance(
         TaskAccountMaintenanceType::RecalculateQuota => {
             recalculate_quota(server, task.account_id.document_id()).await?;
         }
+        TaskAccountMaintenanceType::RepairThreads => {
+            repair_threads(server, task.account_id.document_id()).await?;
+        }
+        TaskAccountMaintenanceType::EncryptMessages => {
+            encrypt_messages(server, task.account_id.document_id()).await?;
+        }
     }
 
     Ok(TaskResult::Success(vec![]))
@@ -636,3 +671,40 @@ async fn reset_imap_uids(server: &Server, account_id: u32) -> trc::Result<(u32,
 
     Ok((mailbox_count, email_count))
 }
+
+async fn encrypt_messages(server: &Server, account_id: u32) -> trc::Result<()> {
+    let account = server
+        .account(account_id)
+        .await
+        .caused_by(trc::location!())?;
+    if !server.core.email.encrypt
+        || account.encryption_key.is_none()
+        || server
+            .encryption_policy(&account)
+            .await
+            .caused_by(trc::location!())?
+            == EncryptionPolicy::Disabled
+    {
+        return Ok(());
+    }
+
+    let cache = server
+        .get_cached_messages(account_id)
+        .await
+        .caused_by(trc::location!())?;
+
+    // Failures are reported per message and do not stop the task, the
+    // message remains in plain text until the next encryption run
+    for document_id in cache.emails.items.iter().map(|i| i.document_id) {
+        if let Err(err) = server.email_encrypt(&account, document_id).await {
+            trc::event!(
+                MessageIngest(trc::MessageIngestEvent::NotEncrypted),
+                AccountId = account_id,
+                DocumentId = document_id,
+                CausedBy = err,
+            );
+        }
+    }
+
+    Ok(())
+}
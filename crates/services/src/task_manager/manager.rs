@@ -5,6 +5,7 @@
  */
 
 use crate::task_manager::acme::AcmeTask;
+use crate::task_manager::addressbook_directory::AddressBookDirectoryTask;
 use crate::task_manager::alarm::SendAlarmTask;
 use crate::task_manager::destroy_account::DestroyAccountTask;
 use crate::task_manager::dkim::DkimManagementTask;
@@ -12,11 +13,14 @@ use crate::task_manager::dns::DnsManagementTask;
 use crate::task_manager::imip::SendImipTask;
 use crate::task_manager::index::SearchIndexTask;
 use crate::task_manager::lock::TaskLockManager;
+use crate::task_manager::login_anomaly::LoginAnomalyTask;
 use crate::task_manager::maintenance::MaintenanceTask;
 use crate::task_manager::merge_threads::MergeThreadsTask;
+use crate::task_manager::migration::AccountMigrationTask;
 use crate::task_manager::report::{self, SubmitReportTask};
 use crate::task_manager::restore_item::RestoreItemTask;
 use crate::task_manager::spam_classifier::SpamFilterMaintenanceTask;
+use crate::task_manager::subscription::CalendarSubscriptionTask;
 use crate::task_manager::{
     DEFAULT_LOCK_EXPIRY, Locked, QUEUE_REFRESH_INTERVAL, TaskDetails, TaskFailureType, TaskInfo,
     TaskJob, TaskManagerIpc, TaskResult,
@@ -77,6 +81,7 @@ pub fn spawn_task_manager(inner: Arc<Inner>) {
         shutdown_rx: watch::channel(false).1,
         proxy_networks: vec![],
         span_id_gen: Arc::new(SnowflakeIdGenerator::new()),
+        sessions: Default::default(),
     });
 
     // Spawn workers for each task type
@@ -93,18 +98,22 @@ pub fn spawn_task_manager(inner: Arc<Inner>) {
             TaskType::DestroyAccount
             | TaskType::AccountMaintenance
             | TaskType::TenantMaintenance
-            | TaskType::StoreMaintenance => 1,
+            | TaskType::StoreMaintenance
+            | TaskType::AccountMigration => 1,
             TaskType::SpamFilterMaintenance => 2,
             TaskType::CalendarAlarmEmail
             | TaskType::CalendarAlarmNotification
             | TaskType::CalendarItipMessage
+            | TaskType::CalendarSubscriptionSync
+            | TaskType::AddressBookDirectorySync
             | TaskType::MergeThreads
             | TaskType::DmarcReport
             | TaskType::TlsReport
             | TaskType::RestoreArchivedItem
             | TaskType::AcmeRenewal
             | TaskType::DkimManagement
-            | TaskType::DnsManagement => TASK_QUEUE_BUFFER,
+            | TaskType::DnsManagement
+            | TaskType::LoginAnomaly => TASK_QUEUE_BUFFER,
         };
 
         let (tx, mut rx) = mpsc::channel::<TaskJob>(channel_capacity);
@@ -218,6 +227,14 @@ pub fn spawn_task_manager(inner: Arc<Inner>) {
                                 Task::CalendarItipMessage(task) => {
                                     server.send_imip(task, server_instance.clone()).await
                                 }
+                                Task::CalendarSubscriptionSync(task) => {
+                                    server.refresh_calendar_subscription(task).await
+                                }
+                                Task::AddressBookDirectorySync(task) => {
+                                    server.refresh_addressbook_directory(task).await
+                                }
+                                Task::AccountMigration(task) => server.migrate_account(task).await,
+                                Task::LoginAnomaly(task) => server.notify_login_anomaly(task).await,
                                 Task::MergeThreads(task) => server.merge_threads(task).await,
                                 Task::DmarcReport(task) => {
                                     server
@@ -366,13 +383,17 @@ impl TaskQueueManager for Server {
                                 TaskType::CalendarAlarmEmail
                                 | TaskType::CalendarAlarmNotification
                                 | TaskType::CalendarItipMessage
+                                | TaskType::CalendarSubscriptionSync
+                                | TaskType::AddressBookDirectorySync
                                 | TaskType::MergeThreads
                                 | TaskType::DmarcReport
                                 | TaskType::TlsReport
                                 | TaskType::RestoreArchivedItem
                                 | TaskType::AcmeRenewal
                                 | TaskType::DkimManagement
-                                | TaskType::DnsManagement => true,
+                                | TaskType::DnsManagement
+                                | TaskType::AccountMigration
+                                | TaskType::LoginAnomaly => roles.task_manager,
                             };
 
                             if !enabled {
@@ -14,7 +14,7 @@ use registry::schema::structs::TaskMergeThreads;
 use std::{str::FromStr, time::Duration};
 use store::{
     IterateParams, Key, U32_LEN, ValueKey,
-    ahash::AHashMap,
+    ahash::{AHashMap, AHashSet},
     rand::Rng,
     write::{
         AlignedBytes, Archive, BatchBuilder, IndexPropertyClass, MergeResult, Params, ValueClass,
@@ -197,42 +197,7 @@ async fn merge_threads(
                             .caused_by(trc::location!())?;
 
                         // Update thread index property
-                        batch.merge_fnc(
-                            ValueClass::IndexProperty(IndexPropertyClass::Hash {
-                                property: EmailField::Threading.into(),
-                                hash: thread_hash,
-                            }),
-                            Params::with_capacity(3)
-                                .with_u64(thread_id as u64)
-                                .with_u64(group_thread_id as u64),
-                            |params, _, bytes| {
-                                let new_thread_id = params.u64(0) as u32;
-                                let old_thread_id = params.u64(1) as u32;
-
-                                let mut thread_index = bytes
-                                    .filter(|v| v.len() > U32_LEN)
-                                    .ok_or_else(|| {
-                                        trc::StoreEvent::AssertValueFailed
-                                            .into_err()
-                                            .details("Message no longer exists.")
-                                            .caused_by(trc::location!())
-                                    })?
-                                    .to_vec();
-
-                                if thread_index.as_slice().deserialize_be_u32(0)? != old_thread_id {
-                                    return Err(
-                                        trc::StoreEvent::AssertValueFailed
-                                            .into_err()
-                                            .details("Thread id mismatch, likely due to concurrent modification.")
-                                            .caused_by(trc::location!())
-                                    );
-                                }
-
-                                thread_index[0..U32_LEN].copy_from_slice(&new_thread_id.to_be_bytes());
-
-                                Ok(MergeResult::Update(thread_index))
-                            },
-                        );
+                        move_thread_index(&mut batch, thread_hash, thread_id, group_thread_id);
                     }
                 }
             }
@@ -251,3 +216,248 @@ async fn merge_threads(
         }
     }
 }
+
+fn move_thread_index(
+    batch: &mut BatchBuilder,
+    thread_hash: CheekyHash,
+    thread_id: u32,
+    old_thread_id: u32,
+) {
+    batch.merge_fnc(
+        ValueClass::IndexProperty(IndexPropertyClass::Hash {
+            property: EmailField::Threading.into(),
+            hash: thread_hash,
+        }),
+        Params::with_capacity(3)
+            .with_u64(thread_id as u64)
+            .with_u64(old_thread_id as u64),
+        |params, _, bytes| {
+            let new_thread_id = params.u64(0) as u32;
+            let old_thread_id = params.u64(1) as u32;
+
+            let mut thread_index = bytes
+                .filter(|v| v.len() > U32_LEN)
+                .ok_or_else(|| {
+                    trc::StoreEvent::AssertValueFailed
+                        .into_err()
+                        .details("Message no longer exists.")
+                        .caused_by(trc::location!())
+                })?
+                .to_vec();
+
+            if thread_index.as_slice().deserialize_be_u32(0)? != old_thread_id {
+                return Err(trc::StoreEvent::AssertValueFailed
+                    .into_err()
+                    .details("Thread id mismatch, likely due to concurrent modification.")
+                    .caused_by(trc::location!()));
+            }
+
+            thread_index[0..U32_LEN].copy_from_slice(&new_thread_id.to_be_bytes());
+
+            Ok(MergeResult::Update(thread_index))
+        },
+    );
+}
+
+struct ThreadEntry {
+    document_id: u32,
+    thread_id: u32,
+    references: Vec<u8>,
+}
+
+pub(crate) async fn repair_threads(server: &Server, account_id: u32) -> trc::Result<()> {
+    let from_key = ValueKey {
+        account_id,
+        collection: Collection::Email.into(),
+        document_id: 0,
+        class: ValueClass::IndexProperty(IndexPropertyClass::Hash {
+            property: EmailField::Threading.into(),
+            hash: CheekyHash::NULL,
+        }),
+    };
+    let to_key = ValueKey {
+        account_id,
+        collection: Collection::Email.into(),
+        document_id: u32::MAX,
+        class: ValueClass::IndexProperty(IndexPropertyClass::Hash {
+            property: EmailField::Threading.into(),
+            hash: CheekyHash::FULL,
+        }),
+    };
+    let mut prefix = from_key.serialize(0);
+    prefix.truncate(prefix.len() - U32_LEN - CheekyHash::NULL.len());
+    let hash_pos = prefix.len();
+
+    // Group messages by thread name
+    let mut groups: AHashMap<CheekyHash, Vec<ThreadEntry>> = AHashMap::new();
+    server
+        .store()
+        .iterate(
+            IterateParams::new(from_key, to_key).ascending(),
+            |key, value| {
+                if key.starts_with(&prefix)
+                    && let Some(hash) = key.get(hash_pos..).and_then(CheekyHash::deserialize)
+                    && key.len() == hash_pos + hash.len() + U32_LEN
+                {
+                    groups.entry(hash).or_default().push(ThreadEntry {
+                        document_id: key.deserialize_be_u32(key.len() - U32_LEN)?,
+                        thread_id: value.deserialize_be_u32(0)?,
+                        references: value.get(U32_LEN..).unwrap_or_default().to_vec(),
+                    });
+                }
+
+                Ok(true)
+            },
+        )
+        .await
+        .caused_by(trc::location!())?;
+
+    let current_ids = groups
+        .values()
+        .flatten()
+        .map(|entry| entry.thread_id)
+        .collect::<AHashSet<_>>();
+    let mut assigned_ids = AHashSet::with_capacity(current_ids.len());
+    let mut changes = Vec::new();
+
+    for (thread_hash, entries) in &groups {
+        // Messages sharing a message id belong to the same thread
+        let mut parents = (0..entries.len()).collect::<Vec<_>>();
+        let mut owners: AHashMap<&[u8], usize> = AHashMap::new();
+        for (idx, entry) in entries.iter().enumerate() {
+            for reference in entry.references.chunks_exact(CheekyHash::HASH_SIZE) {
+                if let Some(&owner) = owners.get(reference) {
+                    let (a, b) = (find_root(&mut parents, owner), find_root(&mut parents, idx));
+                    if a != b {
+                        parents[b] = a;
+                    }
+                } else {
+                    owners.insert(reference, idx);
+                }
+            }
+        }
+
+        let mut components: AHashMap<usize, Vec<&ThreadEntry>> = AHashMap::new();
+        for (idx, entry) in entries.iter().enumerate() {
+            components
+                .entry(find_root(&mut parents, idx))
+                .or_default()
+                .push(entry);
+        }
+        let mut components = components.into_values().collect::<Vec<_>>();
+        components.sort_unstable_by(|a, b| {
+            b.len()
+                .cmp(&a.len())
+                .then_with(|| a[0].document_id.cmp(&b[0].document_id))
+        });
+
+        for component in components {
+            // Keep the most common thread id that was not assigned already,
+            // otherwise a new thread is created
+            let mut thread_merge = ThreadMerge::new();
+            for entry in &component {
+                if !assigned_ids.contains(&entry.thread_id) {
+                    thread_merge.add(entry.thread_id, entry.document_id);
+                }
+            }
+            let thread_id = if thread_merge.num_thread_ids() > 0 {
+                thread_merge.merge_thread_id()
+            } else if let Some(thread_id) = component
+                .iter()
+                .map(|entry| entry.document_id)
+                .filter(|id| !current_ids.contains(id) && !assigned_ids.contains(id))
+                .min()
+            {
+                thread_id
+            } else {
+                assigned_ids.extend(component.iter().map(|entry| entry.thread_id));
+                continue;
+            };
+            assigned_ids.insert(thread_id);
+
+            for entry in component {
+                if entry.thread_id != thread_id {
+                    changes.push((*thread_hash, entry.document_id, entry.thread_id, thread_id));
+                }
+            }
+        }
+    }
+
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    // Log new threads first and removed threads last
+    let mut batch = BatchBuilder::new();
+    batch
+        .with_account_id(account_id)
+        .with_collection(Collection::Thread);
+    for &thread_id in assigned_ids.difference(&current_ids) {
+        batch
+            .with_document(thread_id)
+            .log_container_insert(SyncCollection::Thread);
+    }
+    batch.with_collection(Collection::Email);
+
+    for (thread_hash, document_id, old_thread_id, thread_id) in changes {
+        let Some(data_) = server
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::Email,
+                document_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            continue;
+        };
+        let data = data_
+            .to_unarchived::<MessageData>()
+            .caused_by(trc::location!())?;
+        let mut new_data = data
+            .deserialize::<MessageData>()
+            .caused_by(trc::location!())?;
+        new_data.thread_id = thread_id;
+        batch
+            .with_document(document_id)
+            .custom(
+                ObjectIndexBuilder::new()
+                    .with_current(data)
+                    .with_changes(new_data),
+            )
+            .caused_by(trc::location!())?;
+        move_thread_index(&mut batch, thread_hash, thread_id, old_thread_id);
+
+        if batch.is_large_batch() {
+            server
+                .commit_batch(batch)
+                .await
+                .caused_by(trc::location!())?;
+            batch = BatchBuilder::new();
+            batch
+                .with_account_id(account_id)
+                .with_collection(Collection::Email);
+        }
+    }
+
+    batch.with_collection(Collection::Thread);
+    for &thread_id in current_ids.difference(&assigned_ids) {
+        batch
+            .with_document(thread_id)
+            .log_container_delete(SyncCollection::Thread);
+    }
+    server
+        .commit_batch(batch)
+        .await
+        .caused_by(trc::location!())
+        .map(|_| ())
+}
+
+fn find_root(parents: &mut [usize], mut idx: usize) -> usize {
+    while parents[idx] != idx {
+        parents[idx] = parents[parents[idx]];
+        idx = parents[idx];
+    }
+    idx
+}
@@ -0,0 +1,138 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use common::{
+    Server,
+    telemetry::metrics::breakdown::{BreakdownMetric, BreakdownSeries},
+};
+use registry::schema::prelude::ObjectType;
+use smtp::queue::Message;
+use store::{
+    Deserialize, IterateParams, ValueKey,
+    ahash::AHashMap,
+    registry::RegistryQuery,
+    roaring::RoaringBitmap,
+    write::{AlignedBytes, Archive, QueueClass, ValueClass},
+};
+use trc::AddContext;
+
+pub(crate) trait MetricsBreakdownTask: Sync + Send {
+    fn calculate_metrics_breakdown(&self) -> impl Future<Output = trc::Result<()>> + Send;
+}
+
+#[derive(Default)]
+struct Totals {
+    domains: AHashMap<u32, u64>,
+    tenants: AHashMap<u32, u64>,
+    other: u64,
+}
+
+impl MetricsBreakdownTask for Server {
+    async fn calculate_metrics_breakdown(&self) -> trc::Result<()> {
+        let Some(prometheus) = self
+            .core
+            .metrics
+            .prometheus
+            .as_ref()
+            .filter(|p| p.domain_breakdown || p.tenant_breakdown)
+        else {
+            return Ok(());
+        };
+
+        // Obtain queued messages by sender domain
+        let mut sender_domains: AHashMap<String, u64> = AHashMap::new();
+        self.queue_store()
+            .iterate(
+                IterateParams::new(
+                    ValueKey::from(ValueClass::Queue(QueueClass::Message(0))),
+                    ValueKey::from(ValueClass::Queue(QueueClass::Message(u64::MAX))),
+                )
+                .ascending(),
+                |key, value| {
+                    let message = <Archive<AlignedBytes> as Deserialize>::deserialize(value)
+                        .add_context(|ctx| ctx.ctx(trc::Key::Key, key))?;
+                    let message = message
+                        .unarchive::<Message>()
+                        .add_context(|ctx| ctx.ctx(trc::Key::Key, key))?;
+                    if let Some((_, domain)) = message.return_path.rsplit_once('@') {
+                        *sender_domains.entry(domain.to_lowercase()).or_default() += 1;
+                    }
+
+                    Ok(true)
+                },
+            )
+            .await
+            .caused_by(trc::location!())?;
+
+        let mut queue = Totals::default();
+        for (domain, total) in sender_domains {
+            match self.domain(&domain).await.caused_by(trc::location!())? {
+                Some(domain) => queue.add(domain.id, domain.id_tenant, total),
+                None => queue.other += total,
+            }
+        }
+
+        // Obtain used quota by account domain
+        let mut quota = Totals::default();
+        for account_id in self
+            .registry()
+            .query::<RoaringBitmap>(RegistryQuery::new(ObjectType::Account))
+            .await
+            .caused_by(trc::location!())?
+        {
+            let Some(account) = self
+                .try_account(account_id)
+                .await
+                .caused_by(trc::location!())?
+            else {
+                continue;
+            };
+            let used = self
+                .get_used_quota_account(account_id)
+                .await
+                .caused_by(trc::location!())?
+                .max(0) as u64;
+            if used == 0 {
+                continue;
+            }
+
+            match account.addresses.first() {
+                Some(address) => quota.add(address.domain_id, account.id_tenant, used),
+                None => quota.other += used,
+            }
+        }
+
+        for (metric, totals) in [
+            (BreakdownMetric::QueueSize, queue),
+            (BreakdownMetric::QuotaUsed, quota),
+        ] {
+            self.update_metrics_breakdown(
+                metric,
+                prometheus.domain_breakdown.then(|| {
+                    BreakdownSeries::from_totals(
+                        totals.domains,
+                        totals.other,
+                        prometheus.max_breakdown_labels,
+                    )
+                }),
+                prometheus.tenant_breakdown.then(|| {
+                    BreakdownSeries::from_totals(totals.tenants, 0, prometheus.max_breakdown_labels)
+                }),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Totals {
+    fn add(&mut self, domain_id: u32, tenant_id: Option<u32>, value: u64) {
+        *self.domains.entry(domain_id).or_default() += value;
+        if let Some(tenant_id) = tenant_id {
+            *self.tenants.entry(tenant_id).or_default() += value;
+        }
+    }
+}
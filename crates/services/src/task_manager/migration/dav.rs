@@ -0,0 +1,730 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{Migration, MigrationError, MigrationResult};
+use calcard::{Entry, Parser, common::timezone::Tz};
+use common::{DavName, DavResources, Server, manager::migration::DavSyncState};
+use groupware::{
+    cache::GroupwareCache,
+    calendar::{Calendar, CalendarEvent, CalendarEventData, CalendarPreferences},
+    contact::{AddressBook, AddressBookPreferences, ContactCard},
+};
+use quick_xml::{Reader, events::Event};
+use reqwest::{Client, Method, StatusCode, Url};
+use std::time::Duration;
+use store::{
+    ValueKey,
+    ahash::AHashMap,
+    write::{AlignedBytes, Archive, BatchBuilder},
+};
+use trc::AddContext;
+use types::collection::{Collection, SyncCollection};
+use utils::{HttpLimitResponse, http::build_http_client};
+
+const DAV_TIMEOUT: Duration = Duration::from_secs(60);
+const MAX_PROPFIND_SIZE: usize = 20 * 1024 * 1024;
+const COMMIT_BATCH_SIZE: usize = 50;
+
+const PROPFIND_COLLECTIONS: &str = concat!(
+    "<?xml version=\"1.0\" encoding=\"utf-8\"?>",
+    "<D:propfind xmlns:D=\"DAV:\"><D:prop>",
+    "<D:resourcetype/><D:displayname/>",
+    "</D:prop></D:propfind>"
+);
+const PROPFIND_ITEMS: &str = concat!(
+    "<?xml version=\"1.0\" encoding=\"utf-8\"?>",
+    "<D:propfind xmlns:D=\"DAV:\"><D:prop>",
+    "<D:resourcetype/><D:getetag/>",
+    "</D:prop></D:propfind>"
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DavKind {
+    Calendar,
+    AddressBook,
+}
+
+struct DavClient {
+    client: Client,
+}
+
+#[derive(Debug, Default)]
+struct DavEntry {
+    href: String,
+    etag: Option<String>,
+    display_name: Option<String>,
+    is_collection: bool,
+    is_calendar: bool,
+    is_address_book: bool,
+}
+
+#[derive(Clone, Copy)]
+enum DavField {
+    Href,
+    Etag,
+    DisplayName,
+}
+
+pub(crate) async fn migrate_calendars(
+    migration: &mut Migration<'_>,
+    url: &str,
+) -> MigrationResult<bool> {
+    let result = migrate_collections(migration, url, DavKind::Calendar).await;
+
+    // Pick up alarms of the imported events
+    migration.server.notify_task_queue();
+
+    result
+}
+
+pub(crate) async fn migrate_contacts(
+    migration: &mut Migration<'_>,
+    url: &str,
+) -> MigrationResult<bool> {
+    migrate_collections(migration, url, DavKind::AddressBook).await
+}
+
+async fn migrate_collections(
+    migration: &mut Migration<'_>,
+    url: &str,
+    kind: DavKind,
+) -> MigrationResult<bool> {
+    let server = migration.server;
+    let account_id = migration.account_id;
+    let home = Url::parse(url)
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https"))
+        .ok_or_else(|| MigrationError::Permanent(format!("Invalid server URL {url:?}")))?;
+    let client = DavClient {
+        client: build_http_client(
+            [],
+            Some(&migration.task.username),
+            Some(&migration.secret),
+            None,
+            None,
+            DAV_TIMEOUT,
+            migration.task.allow_invalid_certs,
+        )
+        .map_err(MigrationError::Permanent)?,
+    };
+    let (collection, sync_collection, max_size) = match kind {
+        DavKind::Calendar => (
+            Collection::CalendarEvent,
+            SyncCollection::Calendar,
+            server.core.groupware.max_ical_size,
+        ),
+        DavKind::AddressBook => (
+            Collection::ContactCard,
+            SyncCollection::AddressBook,
+            server.core.groupware.max_vcard_size,
+        ),
+    };
+    let account = server
+        .account(account_id)
+        .await
+        .caused_by(trc::location!())?;
+
+    for remote in client
+        .propfind(&home, PROPFIND_COLLECTIONS)
+        .await?
+        .into_iter()
+        .filter(|entry| match kind {
+            DavKind::Calendar => entry.is_calendar,
+            DavKind::AddressBook => entry.is_address_book,
+        })
+    {
+        let resources = server
+            .fetch_dav_resources(account_id, account_id, sync_collection)
+            .await
+            .caused_by(trc::location!())?;
+        let (container_id, container_name) =
+            container_id(migration, kind, &resources, &remote).await?;
+
+        // Index the items imported by previous runs
+        let items = match kind {
+            DavKind::Calendar => &migration.state.events,
+            DavKind::AddressBook => &migration.state.contacts,
+        };
+        let mut imported = items
+            .iter()
+            .enumerate()
+            .map(|(idx, item)| (item.href.clone(), idx))
+            .collect::<AHashMap<_, _>>();
+
+        let mut batch = BatchBuilder::new();
+        let mut pending = 0;
+        let remote_url = Url::parse(&remote.href).map_err(|_| {
+            MigrationError::Temporary(format!("Invalid collection URL {:?}", remote.href))
+        })?;
+        for item in client.propfind(&remote_url, PROPFIND_ITEMS).await? {
+            let Some(etag) = item.etag.filter(|_| !item.is_collection) else {
+                continue;
+            };
+            let previous = imported.get(&item.href).map(|idx| {
+                let state = match kind {
+                    DavKind::Calendar => &migration.state.events[*idx],
+                    DavKind::AddressBook => &migration.state.contacts[*idx],
+                };
+                (state.etag == etag, state.document_id)
+            });
+            if previous.is_some_and(|(is_unchanged, _)| is_unchanged) {
+                continue;
+            }
+
+            // Fetch and parse the item
+            let Some(contents) = client.get(&item.href, max_size).await? else {
+                migration.state.counters.failed += 1;
+                continue;
+            };
+            let entry = std::str::from_utf8(&contents)
+                .ok()
+                .map(|contents| Parser::new(contents).entry());
+            server
+                .has_available_quota(&account, contents.len() as u64)
+                .await?;
+
+            let current = if let Some((_, document_id)) = previous {
+                server
+                    .store()
+                    .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                        account_id,
+                        collection,
+                        document_id,
+                    ))
+                    .await
+                    .caused_by(trc::location!())?
+                    .map(|archive| (document_id, archive))
+            } else {
+                None
+            };
+            let name = item_name(&item.href)
+                .filter(|name| {
+                    resources
+                        .by_path(&format!("{container_name}/{name}"))
+                        .is_none()
+                })
+                .map(|name| DavName::new(name, container_id))
+                .unwrap_or_else(|| DavName::new_with_rand_name(container_id));
+
+            let document_id = match (entry, current) {
+                (Some(Entry::ICalendar(ical)), current) if kind == DavKind::Calendar => {
+                    let max_instances = server.core.groupware.max_ical_instances;
+                    if let Some((document_id, archive)) = current {
+                        let event = archive
+                            .to_unarchived::<CalendarEvent>()
+                            .caused_by(trc::location!())?;
+                        let mut new_event = event
+                            .deserialize::<CalendarEvent>()
+                            .caused_by(trc::location!())?;
+                        new_event.size = contents.len() as u32;
+                        new_event.data =
+                            CalendarEventData::new(ical, Tz::Floating, max_instances, &mut None);
+                        new_event
+                            .update(
+                                account.account_tenant_ids(),
+                                event,
+                                account_id,
+                                document_id,
+                                &mut batch,
+                            )
+                            .caused_by(trc::location!())?;
+                        document_id
+                    } else {
+                        let document_id = server
+                            .store()
+                            .assign_document_ids(account_id, collection, 1)
+                            .await
+                            .caused_by(trc::location!())?;
+                        let mut next_alarm = None;
+                        CalendarEvent {
+                            names: vec![name],
+                            size: contents.len() as u32,
+                            data: CalendarEventData::new(
+                                ical,
+                                Tz::Floating,
+                                max_instances,
+                                &mut next_alarm,
+                            ),
+                            ..Default::default()
+                        }
+                        .insert(
+                            account.account_tenant_ids(),
+                            account_id,
+                            document_id,
+                            next_alarm,
+                            &mut batch,
+                        )
+                        .caused_by(trc::location!())?;
+                        document_id
+                    }
+                }
+                (Some(Entry::VCard(vcard)), current) if kind == DavKind::AddressBook => {
+                    if let Some((document_id, archive)) = current {
+                        let card = archive
+                            .to_unarchived::<ContactCard>()
+                            .caused_by(trc::location!())?;
+                        let mut new_card = card
+                            .deserialize::<ContactCard>()
+                            .caused_by(trc::location!())?;
+                        new_card.size = contents.len() as u32;
+                        new_card.card = vcard;
+                        new_card
+                            .update(
+                                account.account_tenant_ids(),
+                                card,
+                                account_id,
+                                document_id,
+                                &mut batch,
+                            )
+                            .caused_by(trc::location!())?;
+                        document_id
+                    } else {
+                        let document_id = server
+                            .store()
+                            .assign_document_ids(account_id, collection, 1)
+                            .await
+                            .caused_by(trc::location!())?;
+                        ContactCard {
+                            names: vec![name],
+                            size: contents.len() as u32,
+                            card: vcard,
+                            ..Default::default()
+                        }
+                        .insert(
+                            account.account_tenant_ids(),
+                            account_id,
+                            document_id,
+                            &mut batch,
+                        )
+                        .caused_by(trc::location!())?;
+                        document_id
+                    }
+                }
+                _ => {
+                    migration.state.counters.failed += 1;
+                    continue;
+                }
+            };
+
+            // Track the imported item
+            let (items, counter) = match kind {
+                DavKind::Calendar => (
+                    &mut migration.state.events,
+                    &mut migration.state.counters.events,
+                ),
+                DavKind::AddressBook => (
+                    &mut migration.state.contacts,
+                    &mut migration.state.counters.contacts,
+                ),
+            };
+            *counter += 1;
+            if let Some(idx) = imported.get(&item.href) {
+                items[*idx].etag = etag;
+                items[*idx].document_id = document_id;
+            } else {
+                imported.insert(item.href.clone(), items.len());
+                items.push(DavSyncState {
+                    href: item.href,
+                    etag,
+                    document_id,
+                });
+            }
+
+            pending += 1;
+            if pending >= COMMIT_BATCH_SIZE {
+                commit(server, std::mem::take(&mut batch)).await?;
+                migration.checkpoint().await?;
+                pending = 0;
+                if migration.is_expired() {
+                    return Ok(false);
+                }
+            }
+        }
+
+        if !batch.is_empty() {
+            commit(server, batch).await?;
+        }
+        migration.checkpoint().await?;
+        if migration.is_expired() {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// Returns the local collection that receives the items of a remote one,
+/// reusing a collection with the same name before creating a new one.
+async fn container_id(
+    migration: &mut Migration<'_>,
+    kind: DavKind,
+    resources: &DavResources,
+    remote: &DavEntry,
+) -> MigrationResult<(u32, String)> {
+    let server = migration.server;
+    let account_id = migration.account_id;
+    let containers = match kind {
+        DavKind::Calendar => &mut migration.state.calendars,
+        DavKind::AddressBook => &mut migration.state.address_books,
+    };
+
+    if let Some(state) = containers
+        .iter()
+        .find(|state| state.href == remote.href)
+        .filter(|state| resources.has_container_id(&state.document_id))
+        && let Some(path) = resources.container_resource_path_by_id(state.document_id)
+    {
+        return Ok((state.document_id, path.path().to_string()));
+    }
+
+    let name = item_name(&remote.href).unwrap_or_else(|| "imported".to_string());
+    let display_name = remote.display_name.clone().unwrap_or_else(|| name.clone());
+    let document_id =
+        if let Some(path) = resources.by_path(&name).filter(|path| path.is_container()) {
+            path.document_id()
+        } else {
+            let account = server
+                .account(account_id)
+                .await
+                .caused_by(trc::location!())?;
+            let mut batch = BatchBuilder::new();
+            match kind {
+                DavKind::Calendar => {
+                    let document_id = server
+                        .store()
+                        .assign_document_ids(account_id, Collection::Calendar, 1)
+                        .await
+                        .caused_by(trc::location!())?;
+                    Calendar {
+                        name: name.clone(),
+                        preferences: vec![CalendarPreferences {
+                            account_id,
+                            name: display_name,
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }
+                    .insert(
+                        account.account_tenant_ids(),
+                        account_id,
+                        document_id,
+                        &mut batch,
+                    )
+                    .caused_by(trc::location!())?;
+                    document_id
+                }
+                DavKind::AddressBook => {
+                    let document_id = server
+                        .store()
+                        .assign_document_ids(account_id, Collection::AddressBook, 1)
+                        .await
+                        .caused_by(trc::location!())?;
+                    AddressBook {
+                        name: name.clone(),
+                        preferences: vec![AddressBookPreferences {
+                            account_id,
+                            name: display_name,
+                            ..Default::default()
+                        }],
+                        ..Default::default()
+                    }
+                    .insert(
+                        account.account_tenant_ids(),
+                        account_id,
+                        document_id,
+                        &mut batch,
+                    )
+                    .caused_by(trc::location!())?;
+                    document_id
+                }
+            };
+            commit(server, batch).await?;
+            document_id
+        };
+
+    if let Some(state) = containers
+        .iter_mut()
+        .find(|state| state.href == remote.href)
+    {
+        state.document_id = document_id;
+    } else {
+        containers.push(DavSyncState {
+            href: remote.href.clone(),
+            etag: String::new(),
+            document_id,
+        });
+    }
+
+    Ok((document_id, name))
+}
+
+async fn commit(server: &Server, batch: BatchBuilder) -> MigrationResult<()> {
+    server
+        .commit_batch(batch)
+        .await
+        .caused_by(trc::location!())
+        .map(|_| ())
+        .map_err(Into::into)
+}
+
+fn item_name(href: &str) -> Option<String> {
+    let url = Url::parse(href).ok()?;
+    let name = url.path_segments()?.rev().find(|name| !name.is_empty())?;
+    percent_encoding::percent_decode_str(name)
+        .decode_utf8()
+        .ok()
+        .map(|name| name.into_owned())
+        .filter(|name| !name.contains('/'))
+}
+
+impl DavClient {
+    async fn propfind(&self, url: &Url, body: &'static str) -> MigrationResult<Vec<DavEntry>> {
+        let response = self
+            .client
+            .request(Method::from_bytes(b"PROPFIND").unwrap(), url.clone())
+            .header("Depth", "1")
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(body)
+            .send()
+            .await
+            .map_err(|err| MigrationError::Temporary(format!("Request to {url} failed: {err}")))?;
+
+        match response.status() {
+            StatusCode::MULTI_STATUS | StatusCode::OK => {}
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+                return Err(MigrationError::Permanent(format!(
+                    "Authentication failed on {url}"
+                )));
+            }
+            status => {
+                return Err(MigrationError::Temporary(format!(
+                    "Request to {url} failed with status {status}"
+                )));
+            }
+        }
+
+        let xml = response
+            .bytes_with_limit(MAX_PROPFIND_SIZE)
+            .await
+            .map_err(|err| {
+                MigrationError::Temporary(format!("Failed to read response from {url}: {err}"))
+            })?
+            .ok_or_else(|| {
+                MigrationError::Temporary(format!("Response from {url} is too large"))
+            })?;
+        let base = url.path().trim_end_matches('/');
+
+        Ok(parse_multistatus(url, &xml)
+            .into_iter()
+            .filter(|entry| {
+                Url::parse(&entry.href).is_ok_and(|href| href.path().trim_end_matches('/') != base)
+            })
+            .collect())
+    }
+
+    /// Downloads an item, returning `None` when it no longer exists or
+    /// exceeds the maximum size.
+    async fn get(&self, url: &str, max_size: usize) -> MigrationResult<Option<Vec<u8>>> {
+        let response =
+            self.client.get(url).send().await.map_err(|err| {
+                MigrationError::Temporary(format!("Request to {url} failed: {err}"))
+            })?;
+
+        match response.status() {
+            status if status.is_success() => {
+                response.bytes_with_limit(max_size).await.map_err(|err| {
+                    MigrationError::Temporary(format!("Failed to read response from {url}: {err}"))
+                })
+            }
+            StatusCode::NOT_FOUND | StatusCode::GONE => Ok(None),
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Err(MigrationError::Permanent(
+                format!("Authentication failed on {url}"),
+            )),
+            status => Err(MigrationError::Temporary(format!(
+                "Request to {url} failed with status {status}"
+            ))),
+        }
+    }
+}
+
+fn parse_multistatus(base: &Url, xml: &[u8]) -> Vec<DavEntry> {
+    let mut reader = Reader::from_reader(xml);
+    let mut buf = Vec::new();
+    let mut entries = Vec::new();
+    let mut entry: Option<DavEntry> = None;
+    let mut field = None;
+    let mut text = String::new();
+    let mut in_resource_type = false;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(tag)) => match tag.local_name().as_ref() {
+                b"response" => {
+                    entry = Some(DavEntry::default());
+                }
+                b"resourcetype" => {
+                    in_resource_type = true;
+                }
+                name if in_resource_type => {
+                    if let Some(entry) = &mut entry {
+                        entry.set_resource_type(name);
+                    }
+                }
+                name if entry.is_some() => {
+                    field = match name {
+                        b"href" => Some(DavField::Href),
+                        b"getetag" => Some(DavField::Etag),
+                        b"displayname" => Some(DavField::DisplayName),
+                        _ => None,
+                    };
+                    text.clear();
+                }
+                _ => {}
+            },
+            Ok(Event::Empty(tag)) if in_resource_type => {
+                if let Some(entry) = &mut entry {
+                    entry.set_resource_type(tag.local_name().as_ref());
+                }
+            }
+            Ok(Event::End(tag)) => match tag.local_name().as_ref() {
+                b"response" => {
+                    if let Some(entry) = entry.take().filter(|entry| !entry.href.is_empty()) {
+                        entries.push(entry);
+                    }
+                }
+                b"resourcetype" => {
+                    in_resource_type = false;
+                }
+                b"href" | b"getetag" | b"displayname" => {
+                    if let (Some(field), Some(entry)) = (field.take(), &mut entry) {
+                        let value = text.trim();
+                        match field {
+                            DavField::Href => {
+                                if let Ok(href) = base.join(value) {
+                                    entry.href = href.to_string();
+                                }
+                            }
+                            DavField::Etag if !value.is_empty() => {
+                                entry.etag = Some(value.to_string());
+                            }
+                            DavField::DisplayName if !value.is_empty() => {
+                                entry.display_name = Some(value.to_string());
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Text(value)) if field.is_some() => {
+                if let Ok(value) = value.xml_content() {
+                    text.push_str(&value);
+                }
+            }
+            Ok(Event::GeneralRef(entity)) if field.is_some() => match entity.as_ref() {
+                b"lt" => text.push('<'),
+                b"gt" => text.push('>'),
+                b"amp" => text.push('&'),
+                b"apos" => text.push('\''),
+                b"quot" => text.push('"'),
+                _ => {
+                    if let Ok(Some(ch)) = entity.resolve_char_ref() {
+                        text.push(ch);
+                    }
+                }
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => (),
+        }
+        buf.clear();
+    }
+
+    entries
+}
+
+impl DavEntry {
+    fn set_resource_type(&mut self, name: &[u8]) {
+        match name {
+            b"collection" => self.is_collection = true,
+            b"calendar" => self.is_calendar = true,
+            b"addressbook" => self.is_address_book = true,
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{item_name, parse_multistatus};
+    use reqwest::Url;
+
+    #[test]
+    fn parse_propfind_response() {
+        let base = Url::parse("https://dav.example.org/cal/jdoe/").unwrap();
+        let entries = parse_multistatus(
+            &base,
+            concat!(
+                "<?xml version=\"1.0\" encoding=\"utf-8\"?>",
+                "<d:multistatus xmlns:d=\"DAV:\" xmlns:c=\"urn:ietf:params:xml:ns:caldav\">",
+                "<d:response><d:href>/cal/jdoe/</d:href><d:propstat><d:prop>",
+                "<d:resourcetype><d:collection/></d:resourcetype>",
+                "</d:prop></d:propstat></d:response>",
+                "<d:response><d:href>/cal/jdoe/work%20events/</d:href><d:propstat><d:prop>",
+                "<d:displayname>Work &amp; Meetings</d:displayname>",
+                "<d:resourcetype><d:collection/><c:calendar/></d:resourcetype>",
+                "</d:prop></d:propstat></d:response>",
+                "<d:response><d:href>event.ics</d:href><d:propstat><d:prop>",
+                "<d:getetag>\"1234\"</d:getetag><d:resourcetype/>",
+                "</d:prop></d:propstat></d:response>",
+                "<d:response><d:propstat><d:prop>",
+                "<d:getetag>\"missing-href\"</d:getetag>",
+                "</d:prop></d:propstat></d:response>",
+                "</d:multistatus>"
+            )
+            .as_bytes(),
+        );
+        assert_eq!(entries.len(), 3);
+
+        assert_eq!(entries[0].href, "https://dav.example.org/cal/jdoe/");
+        assert!(entries[0].is_collection);
+        assert!(!entries[0].is_calendar);
+
+        assert_eq!(
+            entries[1].href,
+            "https://dav.example.org/cal/jdoe/work%20events/"
+        );
+        assert_eq!(entries[1].display_name.as_deref(), Some("Work & Meetings"));
+        assert!(entries[1].is_collection);
+        assert!(entries[1].is_calendar);
+        assert!(!entries[1].is_address_book);
+
+        assert_eq!(
+            entries[2].href,
+            "https://dav.example.org/cal/jdoe/event.ics"
+        );
+        assert_eq!(entries[2].etag.as_deref(), Some("\"1234\""));
+        assert!(!entries[2].is_collection);
+    }
+
+    #[test]
+    fn parse_item_names() {
+        for (href, expected) in [
+            ("https://dav.example.org/cal/jdoe/work/", Some("work")),
+            (
+                "https://dav.example.org/cal/jdoe/event.ics",
+                Some("event.ics"),
+            ),
+            (
+                "https://dav.example.org/cal/jdoe/my%20event.ics",
+                Some("my event.ics"),
+            ),
+            ("https://dav.example.org/cal/jdoe/a%2Fb.ics", None),
+            ("https://dav.example.org/", None),
+        ] {
+            assert_eq!(item_name(href).as_deref(), expected, "{href}");
+        }
+    }
+}
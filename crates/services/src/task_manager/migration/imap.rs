@@ -0,0 +1,609 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{
+    Migration, MigrationError, MigrationResult, Token, parse_server_url, quote, read_response,
+    tokenize,
+};
+use base64::{Engine, engine::general_purpose};
+use common::{
+    Server, auth::BuildAccessToken, manager::migration::MailboxSyncState,
+    network::backend::BackendConnection,
+};
+use email::{
+    cache::{MessageCacheFetch, mailbox::MailboxCacheAccess},
+    mailbox::{INBOX_ID, manage::MailboxFnc},
+    message::ingest::{EmailIngest, IngestEmail, IngestSource},
+};
+use imap_proto::{parser::parse_datetime, protocol::Flag, utf7::utf7_decode};
+use mail_parser::MessageParser;
+use trc::AddContext;
+use types::{keyword::Keyword, special_use::SpecialUse};
+
+const FETCH_BATCH_SIZE: usize = 25;
+
+struct ImapClient {
+    conn: BackendConnection,
+    next_tag: u32,
+    max_literal: usize,
+}
+
+enum ImapResponse {
+    Untagged(Vec<Token>),
+    Continuation,
+    Tagged { is_ok: bool, text: String },
+}
+
+struct RemoteMailbox {
+    name: String,
+    local_path: String,
+    special_use: Option<SpecialUse>,
+}
+
+pub(crate) async fn migrate_mail(
+    migration: &mut Migration<'_>,
+    url: &str,
+) -> MigrationResult<bool> {
+    let server = migration.server;
+    let account_id = migration.account_id;
+    let max_size = server.core.email.mail_max_size;
+    let mut client = ImapClient::connect(
+        server,
+        url,
+        migration.task.allow_invalid_certs,
+        max_size + 1024,
+    )
+    .await?;
+    client
+        .login(&migration.task.username, &migration.secret)
+        .await?;
+
+    let access_token = server
+        .access_token(account_id)
+        .await
+        .caused_by(trc::location!())?
+        .build();
+
+    for mailbox in client.list().await? {
+        // Resolve the local mailbox
+        let cache = server
+            .get_cached_messages(account_id)
+            .await
+            .caused_by(trc::location!())?;
+        let mailbox_id = if mailbox.name.eq_ignore_ascii_case("INBOX") {
+            INBOX_ID
+        } else if let Some(local) = mailbox
+            .special_use
+            .as_ref()
+            .and_then(|role| cache.mailbox_by_role(role))
+        {
+            local.document_id
+        } else if let Some(mailbox_id) = server
+            .mailbox_create_path(account_id, &mailbox.local_path)
+            .await
+            .caused_by(trc::location!())?
+        {
+            mailbox_id
+        } else {
+            migration.state.counters.failed += 1;
+            continue;
+        };
+
+        // Obtain the synchronization state
+        let (uid_validity, exists) = client.examine(&mailbox.name).await?;
+        if exists == 0 {
+            continue;
+        }
+        let state_idx = if let Some(idx) = migration
+            .state
+            .mailboxes
+            .iter()
+            .position(|state| state.name == mailbox.name)
+        {
+            idx
+        } else {
+            migration.state.mailboxes.push(MailboxSyncState {
+                name: mailbox.name.clone(),
+                uid_validity,
+                ..Default::default()
+            });
+            migration.state.mailboxes.len() - 1
+        };
+        if migration.state.mailboxes[state_idx].uid_validity != uid_validity {
+            let state = &mut migration.state.mailboxes[state_idx];
+            state.uid_validity = uid_validity;
+            state.last_uid = 0;
+        }
+        let last_uid = migration.state.mailboxes[state_idx].last_uid;
+
+        // Skip messages that cannot be stored locally
+        let mut uids = Vec::new();
+        for (uid, size) in client.uid_sizes(last_uid).await? {
+            if size <= max_size {
+                uids.push(uid);
+            } else {
+                migration.state.counters.failed += 1;
+            }
+        }
+
+        for uids in uids.chunks(FETCH_BATCH_SIZE) {
+            let command = format!(
+                "UID FETCH {} (UID FLAGS INTERNALDATE BODY.PEEK[])",
+                uids.iter()
+                    .map(|uid| uid.to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            );
+            client.send(&command).await?;
+
+            loop {
+                match client.read().await? {
+                    ImapResponse::Untagged(tokens) => {
+                        let Some(message) = FetchedMessage::parse(tokens) else {
+                            continue;
+                        };
+                        let is_imported = match MessageParser::new().parse(&message.contents) {
+                            Some(parsed) => match server
+                                .email_ingest(IngestEmail {
+                                    raw_message: &message.contents,
+                                    blob_hash: None,
+                                    message: parsed.into(),
+                                    access_token: &access_token,
+                                    mailbox_ids: vec![mailbox_id],
+                                    keywords: message.keywords,
+                                    received_at: message.received_at,
+                                    source: IngestSource::Imap {
+                                        train_classifier: false,
+                                    },
+                                    session_id: 0,
+                                })
+                                .await
+                            {
+                                Ok(_) => true,
+                                Err(err)
+                                    if err.matches(trc::EventType::MessageIngest(
+                                        trc::MessageIngestEvent::Error,
+                                    )) =>
+                                {
+                                    false
+                                }
+                                Err(err) => return Err(err.into()),
+                            },
+                            None => false,
+                        };
+
+                        // Progress is tracked per message so that retries do not
+                        // import the same message twice
+                        let state = &mut migration.state.mailboxes[state_idx];
+                        state.last_uid = state.last_uid.max(message.uid);
+                        if is_imported {
+                            state.messages += 1;
+                            migration.state.counters.messages += 1;
+                        } else {
+                            migration.state.counters.failed += 1;
+                        }
+                    }
+                    ImapResponse::Tagged { is_ok: true, .. } => break,
+                    ImapResponse::Tagged { text, .. } => {
+                        return Err(MigrationError::Temporary(format!(
+                            "Failed to fetch messages from {:?}: {text}",
+                            mailbox.name
+                        )));
+                    }
+                    ImapResponse::Continuation => {}
+                }
+            }
+
+            migration.checkpoint().await?;
+            if migration.is_expired() {
+                return Ok(false);
+            }
+        }
+    }
+
+    client.logout().await;
+
+    Ok(true)
+}
+
+impl ImapClient {
+    async fn connect(
+        server: &Server,
+        url: &str,
+        allow_invalid_certs: bool,
+        max_literal: usize,
+    ) -> MigrationResult<Self> {
+        let (hostname, port, implicit_tls) =
+            parse_server_url(url, &[("imap", 143, false), ("imaps", 993, true)])?;
+        let conn = server
+            .connect_remote(&hostname, port, implicit_tls, allow_invalid_certs)
+            .await
+            .map_err(MigrationError::Temporary)?;
+        let mut client = ImapClient {
+            conn,
+            next_tag: 0,
+            max_literal,
+        };
+
+        match client.read().await? {
+            ImapResponse::Untagged(tokens)
+                if tokens.first().is_some_and(|token| token.is_atom("OK")) => {}
+            _ => {
+                return Err(MigrationError::Temporary(format!(
+                    "{hostname} did not send a valid IMAP greeting"
+                )));
+            }
+        }
+
+        // Credentials are never sent in clear text
+        if !client.conn.is_tls() {
+            let capabilities = client.command("CAPABILITY").await?;
+            if !capabilities
+                .iter()
+                .flatten()
+                .any(|token| token.is_atom("STARTTLS"))
+            {
+                return Err(MigrationError::Permanent(format!(
+                    "{hostname} does not support STARTTLS, use an imaps:// URL instead"
+                )));
+            }
+            client.command("STARTTLS").await?;
+            client.conn = server
+                .start_tls(client.conn, allow_invalid_certs)
+                .await
+                .map_err(MigrationError::Temporary)?;
+        }
+
+        Ok(client)
+    }
+
+    async fn login(&mut self, username: &str, secret: &str) -> MigrationResult<()> {
+        let result = if let (Some(username), Some(secret)) = (quote(username), quote(secret)) {
+            self.send(&format!("LOGIN {username} {secret}")).await?;
+            self.read_tagged().await?
+        } else {
+            self.send("AUTHENTICATE PLAIN").await?;
+            match self.read().await? {
+                ImapResponse::Continuation => {
+                    let credentials =
+                        general_purpose::STANDARD.encode(format!("\0{username}\0{secret}"));
+                    self.conn
+                        .write(format!("{credentials}\r\n").as_bytes())
+                        .await
+                        .map_err(MigrationError::Temporary)?;
+                    self.read_tagged().await?
+                }
+                ImapResponse::Tagged { is_ok, text } => (Vec::new(), is_ok, text),
+                ImapResponse::Untagged(_) => self.read_tagged().await?,
+            }
+        };
+
+        match result {
+            (_, true, _) => Ok(()),
+            (_, false, text) => Err(MigrationError::Permanent(format!(
+                "Authentication failed for {username} on {}: {text}",
+                self.conn.hostname
+            ))),
+        }
+    }
+
+    async fn list(&mut self) -> MigrationResult<Vec<RemoteMailbox>> {
+        let mut mailboxes = Vec::new();
+
+        for tokens in self.command("LIST \"\" \"*\"").await? {
+            let mut tokens = tokens.into_iter();
+            if !tokens.next().is_some_and(|token| token.is_atom("LIST")) {
+                continue;
+            }
+            let (Some(Token::List(attributes)), Some(delimiter), Some(name)) =
+                (tokens.next(), tokens.next(), tokens.next())
+            else {
+                continue;
+            };
+            let Some(name) = name
+                .into_bytes()
+                .and_then(|name| String::from_utf8(name).ok())
+            else {
+                continue;
+            };
+
+            // Virtual mailboxes would import the same messages twice
+            let mut special_use = None;
+            let mut is_selectable = true;
+            for attribute in &attributes {
+                let Some(attribute) = attribute.as_str() else {
+                    continue;
+                };
+                if ["\\Noselect", "\\NonExistent", "\\All", "\\Flagged"]
+                    .iter()
+                    .any(|skip| attribute.eq_ignore_ascii_case(skip))
+                {
+                    is_selectable = false;
+                } else if let Some(role) = attribute
+                    .strip_prefix('\\')
+                    .and_then(SpecialUse::parse)
+                    .filter(|role| {
+                        matches!(
+                            role,
+                            SpecialUse::Trash
+                                | SpecialUse::Junk
+                                | SpecialUse::Drafts
+                                | SpecialUse::Sent
+                                | SpecialUse::Archive
+                        )
+                    })
+                {
+                    special_use = Some(role);
+                }
+            }
+            if !is_selectable {
+                continue;
+            }
+
+            let decoded = utf7_decode(&name).unwrap_or_else(|| name.clone());
+            let local_path = match delimiter.as_str().and_then(|d| d.chars().next()) {
+                Some(delimiter) if delimiter != '/' => decoded
+                    .split(delimiter)
+                    .map(|part| part.replace('/', "_"))
+                    .collect::<Vec<_>>()
+                    .join("/"),
+                _ => decoded,
+            };
+
+            mailboxes.push(RemoteMailbox {
+                name,
+                local_path,
+                special_use,
+            });
+        }
+
+        Ok(mailboxes)
+    }
+
+    async fn examine(&mut self, mailbox: &str) -> MigrationResult<(u32, u32)> {
+        let mailbox = quote(mailbox).ok_or_else(|| {
+            MigrationError::Permanent(format!("Unsupported mailbox name {mailbox:?}"))
+        })?;
+        let mut uid_validity = 0;
+        let mut exists = 0;
+
+        for tokens in self.command(&format!("EXAMINE {mailbox}")).await? {
+            match tokens.as_slice() {
+                [Token::Atom(count), Token::Atom(keyword), ..]
+                    if keyword.eq_ignore_ascii_case(b"EXISTS") =>
+                {
+                    exists = parse_number(count).unwrap_or_default();
+                }
+                [Token::Atom(status), Token::Atom(code), ..]
+                    if status.eq_ignore_ascii_case(b"OK") && code.starts_with(b"[") =>
+                {
+                    if let Some(value) = code
+                        .strip_prefix(b"[")
+                        .and_then(|code| code.strip_suffix(b"]"))
+                        .and_then(|code| std::str::from_utf8(code).ok())
+                        .and_then(|code| {
+                            let (name, value) = code.split_once(' ')?;
+                            name.eq_ignore_ascii_case("UIDVALIDITY").then_some(value)
+                        })
+                    {
+                        uid_validity = value.trim().parse().unwrap_or_default();
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok((uid_validity, exists))
+    }
+
+    /// Returns the UIDs and sizes of the messages above the last imported UID.
+    async fn uid_sizes(&mut self, last_uid: u32) -> MigrationResult<Vec<(u32, usize)>> {
+        let mut uids = Vec::new();
+
+        for tokens in self
+            .command(&format!("UID FETCH {}:* (UID RFC822.SIZE)", last_uid + 1))
+            .await?
+        {
+            let mut tokens = tokens.into_iter();
+            let (Some(_), Some(command), Some(Token::List(items))) =
+                (tokens.next(), tokens.next(), tokens.next())
+            else {
+                continue;
+            };
+            if !command.is_atom("FETCH") {
+                continue;
+            }
+
+            let mut uid = 0;
+            let mut size = 0;
+            let mut items = items.into_iter();
+            while let (Some(name), Some(value)) = (items.next(), items.next()) {
+                if name.is_atom("UID") {
+                    uid = value.as_bytes().and_then(parse_number).unwrap_or_default();
+                } else if name.is_atom("RFC822.SIZE") {
+                    size = value.as_bytes().and_then(parse_number).unwrap_or_default();
+                }
+            }
+
+            // "n:*" always matches the last message, even when its UID is lower
+            if uid > last_uid {
+                uids.push((uid, size));
+            }
+        }
+        uids.sort_unstable();
+
+        Ok(uids)
+    }
+
+    async fn logout(&mut self) {
+        let _ = self.command("LOGOUT").await;
+    }
+
+    async fn command(&mut self, command: &str) -> MigrationResult<Vec<Vec<Token>>> {
+        self.send(command).await?;
+        match self.read_tagged().await? {
+            (untagged, true, _) => Ok(untagged),
+            (_, false, text) => Err(MigrationError::Temporary(format!(
+                "{} rejected command {}: {text}",
+                self.conn.hostname,
+                command.split_once(' ').map_or(command, |(name, _)| name)
+            ))),
+        }
+    }
+
+    async fn send(&mut self, command: &str) -> MigrationResult<()> {
+        self.next_tag += 1;
+        self.conn
+            .write(format!("M{} {command}\r\n", self.next_tag).as_bytes())
+            .await
+            .map_err(MigrationError::Temporary)
+    }
+
+    async fn read_tagged(&mut self) -> MigrationResult<(Vec<Vec<Token>>, bool, String)> {
+        let mut untagged = Vec::new();
+
+        loop {
+            match self.read().await? {
+                ImapResponse::Untagged(tokens) => untagged.push(tokens),
+                ImapResponse::Tagged { is_ok, text } => return Ok((untagged, is_ok, text)),
+                ImapResponse::Continuation => {
+                    return Err(MigrationError::Temporary(format!(
+                        "{} sent an unexpected continuation request",
+                        self.conn.hostname
+                    )));
+                }
+            }
+        }
+    }
+
+    async fn read(&mut self) -> MigrationResult<ImapResponse> {
+        let response = read_response(&mut self.conn, self.max_literal).await?;
+        let tag = format!("M{} ", self.next_tag);
+
+        if let Some(untagged) = response.strip_prefix(b"* ") {
+            let tokens = tokenize(untagged);
+            if tokens.first().is_some_and(|token| token.is_atom("BYE")) {
+                Err(MigrationError::Temporary(format!(
+                    "{} closed the connection: {}",
+                    self.conn.hostname,
+                    String::from_utf8_lossy(untagged).trim()
+                )))
+            } else {
+                Ok(ImapResponse::Untagged(tokens))
+            }
+        } else if response.starts_with(b"+") {
+            Ok(ImapResponse::Continuation)
+        } else if let Some(status) = response.strip_prefix(tag.as_bytes()) {
+            let status = String::from_utf8_lossy(status);
+            let (status, text) = status.trim().split_once(' ').unwrap_or((&status, ""));
+            Ok(ImapResponse::Tagged {
+                is_ok: status.eq_ignore_ascii_case("OK"),
+                text: text.to_string(),
+            })
+        } else {
+            Err(MigrationError::Temporary(format!(
+                "{} sent an invalid response: {}",
+                self.conn.hostname,
+                String::from_utf8_lossy(&response).trim()
+            )))
+        }
+    }
+}
+
+struct FetchedMessage {
+    uid: u32,
+    contents: Vec<u8>,
+    keywords: Vec<Keyword>,
+    received_at: Option<u64>,
+}
+
+impl FetchedMessage {
+    fn parse(tokens: Vec<Token>) -> Option<Self> {
+        let mut tokens = tokens.into_iter();
+        let (Some(_), Some(command), Some(Token::List(items))) =
+            (tokens.next(), tokens.next(), tokens.next())
+        else {
+            return None;
+        };
+        if !command.is_atom("FETCH") {
+            return None;
+        }
+
+        let mut uid = 0;
+        let mut contents = None;
+        let mut keywords = Vec::new();
+        let mut received_at = None;
+        let mut items = items.into_iter();
+        while let (Some(name), Some(value)) = (items.next(), items.next()) {
+            if name.is_atom("UID") {
+                uid = value.as_bytes().and_then(parse_number).unwrap_or_default();
+            } else if name.is_atom("FLAGS")
+                && let Token::List(flags) = value
+            {
+                keywords = flags
+                    .into_iter()
+                    .filter_map(|flag| Flag::parse_imap(flag.into_bytes()?).ok())
+                    .filter(|flag| !matches!(flag, Flag::Recent))
+                    .map(Keyword::from)
+                    .collect();
+            } else if name.is_atom("INTERNALDATE") {
+                received_at = value
+                    .as_bytes()
+                    .and_then(|date| parse_datetime(date).ok())
+                    .filter(|date| *date > 0)
+                    .map(|date| date as u64);
+            } else if name.is_atom("BODY[]") {
+                contents = value.into_bytes();
+            }
+        }
+
+        Some(FetchedMessage {
+            uid,
+            contents: contents?,
+            keywords,
+            received_at,
+        })
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &[u8]) -> Option<T> {
+    std::str::from_utf8(value).ok()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FetchedMessage, tokenize};
+    use types::keyword::Keyword;
+
+    #[test]
+    fn parse_fetch_response() {
+        let message = FetchedMessage::parse(tokenize(
+            concat!(
+                "3 FETCH (UID 42 FLAGS (\\Seen \\Recent \\Flagged) ",
+                "INTERNALDATE \"01-Jan-2024 10:00:00 +0000\" ",
+                "BODY[] {17}\r\nSubject: Test\r\n\r\n)\r\n"
+            )
+            .as_bytes(),
+        ))
+        .unwrap();
+        assert_eq!(message.uid, 42);
+        assert_eq!(message.contents, b"Subject: Test\r\n\r\n");
+        assert_eq!(message.keywords, vec![Keyword::Seen, Keyword::Flagged]);
+        assert_eq!(message.received_at, Some(1704103200));
+
+        // Untagged responses without a body or that are not FETCH responses are ignored
+        for response in [
+            "3 FETCH (UID 42 FLAGS (\\Seen))\r\n",
+            "3 EXPUNGE\r\n",
+            "OK [UIDNEXT 43]\r\n",
+        ] {
+            assert!(
+                FetchedMessage::parse(tokenize(response.as_bytes())).is_none(),
+                "{response:?}"
+            );
+        }
+    }
+}
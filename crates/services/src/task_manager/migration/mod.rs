@@ -0,0 +1,497 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+pub mod dav;
+pub mod imap;
+pub mod sieve;
+
+use crate::task_manager::TaskResult;
+use common::{Server, manager::migration::MigrationState, network::backend::BackendConnection};
+use registry::schema::structs::{Task, TaskAccountMigration, TaskStatus};
+use reqwest::Url;
+use std::time::{Duration, Instant};
+use store::write::{BatchBuilder, now};
+use trc::{AddContext, TaskManagerEvent};
+
+/// Imports run in slices so the task lock never expires while the
+/// source server is still being read.
+const MIGRATION_TIME_BUDGET: Duration = Duration::from_secs(30 * 60);
+
+pub(crate) trait AccountMigrationTask: Sync + Send {
+    fn migrate_account(
+        &self,
+        task: &TaskAccountMigration,
+    ) -> impl Future<Output = TaskResult> + Send;
+}
+
+#[derive(Debug)]
+pub(crate) enum MigrationError {
+    Permanent(String),
+    Temporary(String),
+    Internal(trc::Error),
+}
+
+pub(crate) type MigrationResult<T> = Result<T, MigrationError>;
+
+pub(crate) struct Migration<'x> {
+    pub server: &'x Server,
+    pub task: &'x TaskAccountMigration,
+    pub account_id: u32,
+    pub secret: String,
+    pub state: MigrationState,
+    deadline: Instant,
+}
+
+impl AccountMigrationTask for Server {
+    async fn migrate_account(&self, task: &TaskAccountMigration) -> TaskResult {
+        let account_id = task.account_id.document_id();
+        let secret = match task.secret.secret().await {
+            Ok(secret) => secret.into_owned(),
+            Err(err) => return TaskResult::permanent(err),
+        };
+        let mut state = match self.migration_state(account_id).await {
+            Ok(state) => state.unwrap_or_default(),
+            Err(err) => {
+                let result = TaskResult::temporary(err.to_string());
+                trc::error!(
+                    err.account_id(account_id)
+                        .caused_by(trc::location!())
+                        .details("Failed to obtain account migration state")
+                );
+                return result;
+            }
+        };
+
+        // Previously completed imports are resumed as a delta synchronization
+        if state.started_at == 0 || state.completed_at.is_some() {
+            state.started_at = now();
+            state.completed_at = None;
+        }
+
+        let mut migration = Migration {
+            server: self,
+            task,
+            account_id,
+            secret,
+            state,
+            deadline: Instant::now() + MIGRATION_TIME_BUDGET,
+        };
+        let result = match migration.run().await {
+            Ok(true) => {
+                migration.state.completed_at = now().into();
+                migration.state.last_error = None;
+                TaskResult::Success(vec![])
+            }
+            Ok(false) => {
+                let mut task = task.clone();
+                task.status = TaskStatus::now();
+                migration.state.last_error = None;
+                TaskResult::Success(vec![Task::AccountMigration(task)])
+            }
+            Err(MigrationError::Permanent(reason)) => {
+                migration.state.last_error = reason.clone().into();
+                TaskResult::permanent(reason)
+            }
+            Err(MigrationError::Temporary(reason)) => {
+                migration.state.last_error = reason.clone().into();
+                TaskResult::temporary(reason)
+            }
+            Err(MigrationError::Internal(err)) => {
+                let result = TaskResult::temporary(err.to_string());
+                migration.state.last_error = err.to_string().into();
+                trc::error!(
+                    err.account_id(account_id)
+                        .caused_by(trc::location!())
+                        .details("Failed to import account")
+                );
+                result
+            }
+        };
+
+        if let Err(err) = migration.checkpoint().await {
+            trc::error!(
+                err.account_id(account_id)
+                    .caused_by(trc::location!())
+                    .details("Failed to write account migration state")
+            );
+        }
+
+        result
+    }
+}
+
+impl Migration<'_> {
+    async fn run(&mut self) -> MigrationResult<bool> {
+        if self
+            .server
+            .try_account(self.account_id)
+            .await
+            .caused_by(trc::location!())?
+            .is_none()
+        {
+            trc::event!(
+                TaskManager(TaskManagerEvent::MetadataNotFound),
+                Details = "Account not found",
+                AccountId = self.account_id,
+            );
+            return Err(MigrationError::Permanent("Account not found".to_string()));
+        }
+
+        // Each phase reports whether it finished before the time budget ran out
+        if let Some(url) = self.task.imap_url.clone()
+            && !imap::migrate_mail(self, &url).await?
+        {
+            return Ok(false);
+        }
+        if let Some(url) = self.task.sieve_url.clone()
+            && !sieve::migrate_scripts(self, &url).await?
+        {
+            return Ok(false);
+        }
+        if let Some(url) = self.task.caldav_url.clone()
+            && !dav::migrate_calendars(self, &url).await?
+        {
+            return Ok(false);
+        }
+        if let Some(url) = self.task.carddav_url.clone()
+            && !dav::migrate_contacts(self, &url).await?
+        {
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Instant::now() >= self.deadline
+    }
+
+    pub async fn checkpoint(&mut self) -> trc::Result<()> {
+        let mut batch = BatchBuilder::new();
+        self.state.updated_at = now();
+        self.state.write(self.account_id, &mut batch)?;
+        self.server
+            .store()
+            .write(batch.build_all())
+            .await
+            .caused_by(trc::location!())
+            .map(|_| ())
+    }
+}
+
+impl From<trc::Error> for MigrationError {
+    fn from(err: trc::Error) -> Self {
+        if err.matches(trc::EventType::Limit(trc::LimitEvent::Quota)) {
+            MigrationError::Permanent("Account quota exceeded".to_string())
+        } else {
+            MigrationError::Internal(err)
+        }
+    }
+}
+
+/// Parses a server URL into its hostname, port and whether the connection
+/// starts with TLS.
+pub(crate) fn parse_server_url(
+    url: &str,
+    schemes: &[(&str, u16, bool)],
+) -> MigrationResult<(String, u16, bool)> {
+    let invalid = || MigrationError::Permanent(format!("Invalid server URL {url:?}"));
+    let url = Url::parse(url).map_err(|_| invalid())?;
+    let (_, default_port, implicit_tls) = schemes
+        .iter()
+        .find(|(scheme, _, _)| url.scheme().eq_ignore_ascii_case(scheme))
+        .ok_or_else(invalid)?;
+    let hostname = url
+        .host_str()
+        .filter(|host| !host.is_empty())
+        .ok_or_else(invalid)?;
+
+    Ok((
+        hostname.to_string(),
+        url.port().unwrap_or(*default_port),
+        *implicit_tls,
+    ))
+}
+
+#[derive(Debug)]
+pub(crate) enum Token {
+    Atom(Vec<u8>),
+    String(Vec<u8>),
+    Nil,
+    List(Vec<Token>),
+}
+
+impl Token {
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Token::Atom(value) | Token::String(value) => Some(value),
+            Token::Nil | Token::List(_) => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        self.as_bytes()
+            .and_then(|value| std::str::from_utf8(value).ok())
+    }
+
+    pub fn into_bytes(self) -> Option<Vec<u8>> {
+        match self {
+            Token::Atom(value) | Token::String(value) => Some(value),
+            Token::Nil | Token::List(_) => None,
+        }
+    }
+
+    pub fn is_atom(&self, atom: &str) -> bool {
+        matches!(self, Token::Atom(value) if value.eq_ignore_ascii_case(atom.as_bytes()))
+    }
+}
+
+/// Reads a response line from an IMAP or ManageSieve server, including the
+/// contents of any literals it announces.
+pub(crate) async fn read_response(
+    conn: &mut BackendConnection,
+    max_literal: usize,
+) -> MigrationResult<Vec<u8>> {
+    let mut response = Vec::new();
+
+    loop {
+        let line = conn
+            .read_raw_line()
+            .await
+            .map_err(MigrationError::Temporary)?;
+        let literal_size = literal_size(&line);
+        response.extend_from_slice(&line);
+
+        match literal_size {
+            Some(size) if size > max_literal => {
+                return Err(MigrationError::Temporary(format!(
+                    "{} sent a literal of {size} bytes, exceeding the maximum of {max_literal} bytes",
+                    conn.hostname
+                )));
+            }
+            Some(size) => {
+                response.extend(
+                    conn.read_bytes(size)
+                        .await
+                        .map_err(MigrationError::Temporary)?,
+                );
+            }
+            None => return Ok(response),
+        }
+    }
+}
+
+fn literal_size(line: &[u8]) -> Option<usize> {
+    let line = line.strip_suffix(b"\n")?;
+    let line = line
+        .strip_suffix(b"\r")
+        .unwrap_or(line)
+        .strip_suffix(b"}")?;
+    let start = line.iter().rposition(|&ch| ch == b'{')?;
+    let size = &line[start + 1..];
+    let size = size.strip_suffix(b"+").unwrap_or(size);
+
+    std::str::from_utf8(size).ok()?.parse().ok()
+}
+
+pub(crate) fn tokenize(response: &[u8]) -> Vec<Token> {
+    let mut pos = 0;
+    tokenize_list(response, &mut pos, false)
+}
+
+fn tokenize_list(bytes: &[u8], pos: &mut usize, is_nested: bool) -> Vec<Token> {
+    let mut tokens = Vec::new();
+
+    while let Some(&ch) = bytes.get(*pos) {
+        match ch {
+            b' ' | b'\r' | b'\n' | b'\t' => {
+                *pos += 1;
+            }
+            b'(' => {
+                *pos += 1;
+                tokens.push(Token::List(tokenize_list(bytes, pos, true)));
+            }
+            b')' => {
+                *pos += 1;
+                if is_nested {
+                    break;
+                }
+            }
+            b'"' => {
+                *pos += 1;
+                let mut value = Vec::new();
+                while let Some(&ch) = bytes.get(*pos) {
+                    *pos += 1;
+                    match ch {
+                        b'\\' => {
+                            if let Some(&ch) = bytes.get(*pos) {
+                                value.push(ch);
+                                *pos += 1;
+                            }
+                        }
+                        b'"' => break,
+                        _ => value.push(ch),
+                    }
+                }
+                tokens.push(Token::String(value));
+            }
+            b'{' => {
+                let Some(end) = bytes[*pos..].iter().position(|&ch| ch == b'}') else {
+                    *pos = bytes.len();
+                    break;
+                };
+                let size = std::str::from_utf8(&bytes[*pos + 1..*pos + end])
+                    .ok()
+                    .and_then(|size| size.trim_end_matches('+').parse::<usize>().ok())
+                    .unwrap_or_default();
+                *pos += end + 1;
+                if bytes.get(*pos) == Some(&b'\r') {
+                    *pos += 1;
+                }
+                if bytes.get(*pos) == Some(&b'\n') {
+                    *pos += 1;
+                }
+                let end = (*pos + size).min(bytes.len());
+                tokens.push(Token::String(bytes[*pos..end].to_vec()));
+                *pos = end;
+            }
+            _ => {
+                let start = *pos;
+                let mut depth = 0;
+                while let Some(&ch) = bytes.get(*pos) {
+                    match ch {
+                        b'[' => depth += 1,
+                        b']' => depth -= 1,
+                        b' ' | b'(' | b')' if depth > 0 => {}
+                        b' ' | b'(' | b')' | b'\r' | b'\n' => break,
+                        _ => {}
+                    }
+                    *pos += 1;
+                }
+                let value = bytes[start..*pos].to_vec();
+                tokens.push(if value.eq_ignore_ascii_case(b"NIL") {
+                    Token::Nil
+                } else {
+                    Token::Atom(value)
+                });
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Quotes a string for IMAP and ManageSieve commands, returns `None` when
+/// the value can only be sent as a literal.
+pub(crate) fn quote(value: &str) -> Option<String> {
+    if value
+        .bytes()
+        .all(|ch| ch.is_ascii() && !matches!(ch, b'\0' | b'\r' | b'\n'))
+    {
+        let mut quoted = String::with_capacity(value.len() + 2);
+        quoted.push('"');
+        for ch in value.chars() {
+            if matches!(ch, '"' | '\\') {
+                quoted.push('\\');
+            }
+            quoted.push(ch);
+        }
+        quoted.push('"');
+        Some(quoted)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Token, literal_size, parse_server_url, quote, tokenize};
+
+    #[test]
+    fn tokenize_responses() {
+        let tokens = tokenize(b"* LIST (\\HasNoChildren \\Sent) \"/\" \"Sent \\\"Items\\\"\"\r\n");
+        assert!(tokens[0].is_atom("*"));
+        assert!(tokens[1].is_atom("LIST"));
+        let Token::List(flags) = &tokens[2] else {
+            panic!("expected a list, found {:?}", tokens[2]);
+        };
+        assert!(flags[0].is_atom("\\HasNoChildren"));
+        assert!(flags[1].is_atom("\\Sent"));
+        assert_eq!(tokens[3].as_str(), Some("/"));
+        assert_eq!(tokens[4].as_str(), Some("Sent \"Items\""));
+
+        // Literals, NIL and bracketed atoms
+        let tokens =
+            tokenize(b"* 1 FETCH (UID 7 BODY[HEADER.FIELDS (FROM)] {5}\r\nHello X NIL)\r\n");
+        let Token::List(items) = &tokens[3] else {
+            panic!("expected a list, found {:?}", tokens[3]);
+        };
+        assert_eq!(items[1].as_str(), Some("7"));
+        assert!(items[2].is_atom("BODY[HEADER.FIELDS (FROM)]"));
+        assert_eq!(items[3].as_str(), Some("Hello"));
+        assert!(items[4].is_atom("X"));
+        assert!(matches!(items[5], Token::Nil));
+
+        // Truncated literals do not read past the end of the response
+        let tokens = tokenize(b"{10}\r\nabc");
+        assert_eq!(tokens[0].as_str(), Some("abc"));
+    }
+
+    #[test]
+    fn parse_literal_size() {
+        for (line, expected) in [
+            (&b"* 1 FETCH (BODY[] {1024}\r\n"[..], Some(1024)),
+            (b"\"script\" {12+}\r\n", Some(12)),
+            (b"{0}\n", Some(0)),
+            (b"* OK done\r\n", None),
+            (b"{abc}\r\n", None),
+            (b"{10}", None),
+        ] {
+            assert_eq!(
+                literal_size(line),
+                expected,
+                "{:?}",
+                String::from_utf8_lossy(line)
+            );
+        }
+    }
+
+    #[test]
+    fn quote_strings() {
+        assert_eq!(quote("INBOX").as_deref(), Some("\"INBOX\""));
+        assert_eq!(
+            quote("a \"quoted\" \\ name").as_deref(),
+            Some("\"a \\\"quoted\\\" \\\\ name\"")
+        );
+        assert_eq!(quote("Entwürfe"), None);
+        assert_eq!(quote("line\r\nbreak"), None);
+    }
+
+    #[test]
+    fn parse_urls() {
+        let schemes = [("imap", 143, false), ("imaps", 993, true)];
+        for (url, expected) in [
+            (
+                "imap://mail.example.org",
+                Some(("mail.example.org", 143, false)),
+            ),
+            (
+                "IMAPS://mail.example.org",
+                Some(("mail.example.org", 993, true)),
+            ),
+            ("imaps://10.0.0.1:1993", Some(("10.0.0.1", 1993, true))),
+            ("https://mail.example.org", None),
+            ("imap://", None),
+            ("mail.example.org", None),
+        ] {
+            assert_eq!(
+                parse_server_url(url, &schemes).ok(),
+                expected.map(|(host, port, tls)| (host.to_string(), port, tls)),
+                "{url}"
+            );
+        }
+    }
+}
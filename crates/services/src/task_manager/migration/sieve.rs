@@ -0,0 +1,381 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::{
+    Migration, MigrationError, MigrationResult, Token, parse_server_url, read_response, tokenize,
+};
+use base64::{Engine, engine::general_purpose};
+use common::{
+    Server, manager::migration::ScriptSyncState, network::backend::BackendConnection,
+    storage::index::ObjectIndexBuilder,
+};
+use email::sieve::{SieveScript, ingest::SieveScriptIngest};
+use registry::schema::enums::StorageQuota;
+use store::{
+    Serialize, ValueKey,
+    write::{AlignedBytes, Archive, Archiver, BatchBuilder},
+};
+use trc::AddContext;
+use types::{
+    blob_hash::BlobHash,
+    collection::Collection,
+    field::{PrincipalField, SieveField},
+};
+
+const MAX_SCRIPT_SIZE: usize = 1024 * 1024;
+
+struct SieveClient {
+    conn: BackendConnection,
+    max_literal: usize,
+}
+
+struct SieveResponse {
+    lines: Vec<Vec<Token>>,
+    is_ok: bool,
+    text: String,
+}
+
+pub(crate) async fn migrate_scripts(
+    migration: &mut Migration<'_>,
+    url: &str,
+) -> MigrationResult<bool> {
+    let server = migration.server;
+    let account_id = migration.account_id;
+    let mut client = SieveClient::connect(
+        server,
+        url,
+        migration.task.allow_invalid_certs,
+        MAX_SCRIPT_SIZE,
+    )
+    .await?;
+    client
+        .authenticate(&migration.task.username, &migration.secret)
+        .await?;
+
+    let mut active_script = None;
+    for (name, is_active) in client.list_scripts().await? {
+        let script = client.get_script(&name).await?;
+        let local_name = if name.eq_ignore_ascii_case("vacation") {
+            "vacation-imported".to_string()
+        } else {
+            name.trim().to_string()
+        };
+        if local_name.is_empty() || local_name.len() > server.core.email.sieve_max_script_name {
+            migration.state.counters.failed += 1;
+            continue;
+        }
+        if is_active {
+            active_script = Some(local_name.clone());
+        }
+
+        // Skip scripts that did not change since the last run
+        let hash = BlobHash::generate(&script);
+        if migration
+            .state
+            .scripts
+            .iter()
+            .any(|state| state.name == local_name && state.hash == hash)
+        {
+            continue;
+        }
+
+        if import_script(server, account_id, &local_name, script).await? {
+            migration.state.counters.scripts += 1;
+            if let Some(state) = migration
+                .state
+                .scripts
+                .iter_mut()
+                .find(|state| state.name == local_name)
+            {
+                state.hash = hash;
+            } else {
+                migration.state.scripts.push(ScriptSyncState {
+                    name: local_name,
+                    hash,
+                });
+            }
+        } else {
+            migration.state.counters.failed += 1;
+        }
+    }
+    client.logout().await;
+
+    // Activate the imported script unless the account already has one
+    if let Some(name) = active_script
+        && server
+            .sieve_script_get_active_id(account_id)
+            .await
+            .caused_by(trc::location!())?
+            .is_none()
+        && let Some(document_id) = script_id(server, account_id, &name).await?
+    {
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::Principal)
+            .with_document(0)
+            .set(PrincipalField::ActiveScriptId, document_id.serialize());
+        server
+            .commit_batch(batch)
+            .await
+            .caused_by(trc::location!())?;
+    }
+
+    migration.checkpoint().await?;
+
+    Ok(!migration.is_expired())
+}
+
+async fn import_script(
+    server: &Server,
+    account_id: u32,
+    name: &str,
+    mut script: Vec<u8>,
+) -> MigrationResult<bool> {
+    let account = server
+        .account(account_id)
+        .await
+        .caused_by(trc::location!())?;
+    let script_size = script.len();
+    server
+        .has_available_quota(&account, script_size as u64)
+        .await?;
+
+    match server.core.sieve.untrusted_compiler.compile(&script) {
+        Ok(compiled_script) => {
+            script.extend(
+                Archiver::new(compiled_script)
+                    .untrusted()
+                    .serialize()
+                    .caused_by(trc::location!())?,
+            );
+        }
+        Err(_) => return Ok(false),
+    }
+
+    let mut batch = BatchBuilder::new();
+    if let Some(document_id) = script_id(server, account_id, name).await? {
+        let Some(script_) = server
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::SieveScript,
+                document_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            return Ok(false);
+        };
+        let current = script_
+            .to_unarchived::<SieveScript>()
+            .caused_by(trc::location!())?;
+        let (blob_hash, blob_hold) = server.put_temporary_blob(account_id, &script, 60).await?;
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::SieveScript)
+            .with_document(document_id)
+            .custom(
+                ObjectIndexBuilder::new()
+                    .with_changes(
+                        current
+                            .deserialize()
+                            .caused_by(trc::location!())?
+                            .with_size(script_size as u32)
+                            .with_blob_hash(blob_hash),
+                    )
+                    .with_current(current)
+                    .with_changed_by(account.account_tenant_ids()),
+            )
+            .caused_by(trc::location!())?
+            .clear(blob_hold);
+    } else {
+        if server
+            .document_ids(account_id, Collection::SieveScript, SieveField::Name)
+            .await
+            .caused_by(trc::location!())?
+            .len()
+            >= server.object_quota(account.object_quotas(), StorageQuota::MaxSieveScripts) as u64
+        {
+            return Ok(false);
+        }
+
+        let (blob_hash, blob_hold) = server.put_temporary_blob(account_id, &script, 60).await?;
+        let document_id = server
+            .store()
+            .assign_document_ids(account_id, Collection::SieveScript, 1)
+            .await
+            .caused_by(trc::location!())?;
+        batch
+            .with_account_id(account_id)
+            .with_collection(Collection::SieveScript)
+            .with_document(document_id)
+            .custom(
+                ObjectIndexBuilder::<(), _>::new()
+                    .with_changes(
+                        SieveScript::new(name.to_string(), blob_hash).with_size(script_size as u32),
+                    )
+                    .with_changed_by(account.account_tenant_ids()),
+            )
+            .caused_by(trc::location!())?
+            .clear(blob_hold);
+    }
+
+    server
+        .commit_batch(batch)
+        .await
+        .caused_by(trc::location!())?;
+
+    Ok(true)
+}
+
+async fn script_id(server: &Server, account_id: u32, name: &str) -> trc::Result<Option<u32>> {
+    server
+        .document_ids_matching(
+            account_id,
+            Collection::SieveScript,
+            SieveField::Name,
+            name.to_lowercase().as_bytes(),
+        )
+        .await
+        .caused_by(trc::location!())
+        .map(|ids| ids.min())
+}
+
+impl SieveClient {
+    async fn connect(
+        server: &Server,
+        url: &str,
+        allow_invalid_certs: bool,
+        max_literal: usize,
+    ) -> MigrationResult<Self> {
+        let (hostname, port, _) = parse_server_url(url, &[("sieve", 4190, false)])?;
+        let conn = server
+            .connect_remote(&hostname, port, false, allow_invalid_certs)
+            .await
+            .map_err(MigrationError::Temporary)?;
+        let mut client = SieveClient { conn, max_literal };
+
+        // Credentials are never sent in clear text
+        let capabilities = client.read_status().await?;
+        if !capabilities
+            .lines
+            .iter()
+            .any(|line| line.first().and_then(Token::as_str) == Some("STARTTLS"))
+        {
+            return Err(MigrationError::Permanent(format!(
+                "{hostname} does not support STARTTLS"
+            )));
+        }
+        client.command("STARTTLS").await?;
+        client.conn = server
+            .start_tls(client.conn, allow_invalid_certs)
+            .await
+            .map_err(MigrationError::Temporary)?;
+        client.read_status().await?;
+
+        Ok(client)
+    }
+
+    async fn authenticate(&mut self, username: &str, secret: &str) -> MigrationResult<()> {
+        let credentials = general_purpose::STANDARD.encode(format!("\0{username}\0{secret}"));
+        self.conn
+            .write(format!("AUTHENTICATE \"PLAIN\" \"{credentials}\"\r\n").as_bytes())
+            .await
+            .map_err(MigrationError::Temporary)?;
+        let response = self.read_status().await?;
+        if response.is_ok {
+            Ok(())
+        } else {
+            Err(MigrationError::Permanent(format!(
+                "Authentication failed for {username} on {}: {}",
+                self.conn.hostname, response.text
+            )))
+        }
+    }
+
+    async fn list_scripts(&mut self) -> MigrationResult<Vec<(String, bool)>> {
+        Ok(self
+            .command("LISTSCRIPTS")
+            .await?
+            .into_iter()
+            .filter_map(|line| {
+                let mut tokens = line.into_iter();
+                let name = tokens.next()?.into_bytes()?;
+                let is_active = tokens.next().is_some_and(|token| token.is_atom("ACTIVE"));
+                String::from_utf8(name).ok().map(|name| (name, is_active))
+            })
+            .collect())
+    }
+
+    async fn get_script(&mut self, name: &str) -> MigrationResult<Vec<u8>> {
+        self.conn
+            .write(format!("GETSCRIPT {{{}+}}\r\n{name}\r\n", name.len()).as_bytes())
+            .await
+            .map_err(MigrationError::Temporary)?;
+        let response = self.read_status().await?;
+        if response.is_ok {
+            Ok(response
+                .lines
+                .into_iter()
+                .next()
+                .and_then(|line| line.into_iter().next())
+                .and_then(Token::into_bytes)
+                .unwrap_or_default())
+        } else {
+            Err(MigrationError::Temporary(format!(
+                "Failed to fetch script {name:?} from {}: {}",
+                self.conn.hostname, response.text
+            )))
+        }
+    }
+
+    async fn logout(&mut self) {
+        let _ = self.command("LOGOUT").await;
+    }
+
+    async fn command(&mut self, command: &str) -> MigrationResult<Vec<Vec<Token>>> {
+        self.conn
+            .write(format!("{command}\r\n").as_bytes())
+            .await
+            .map_err(MigrationError::Temporary)?;
+        let response = self.read_status().await?;
+        if response.is_ok {
+            Ok(response.lines)
+        } else {
+            Err(MigrationError::Temporary(format!(
+                "{} rejected command {command}: {}",
+                self.conn.hostname, response.text
+            )))
+        }
+    }
+
+    async fn read_status(&mut self) -> MigrationResult<SieveResponse> {
+        let mut lines = Vec::new();
+
+        loop {
+            let mut tokens = tokenize(&read_response(&mut self.conn, self.max_literal).await?);
+            match tokens.first() {
+                Some(status) if status.is_atom("OK") || status.is_atom("NO") => {
+                    let is_ok = status.is_atom("OK");
+                    let text = tokens
+                        .drain(1..)
+                        .filter_map(|token| token.as_str().map(|text| text.to_string()))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    return Ok(SieveResponse { lines, is_ok, text });
+                }
+                Some(status) if status.is_atom("BYE") => {
+                    return Err(MigrationError::Temporary(format!(
+                        "{} closed the connection",
+                        self.conn.hostname
+                    )));
+                }
+                _ => lines.push(tokens),
+            }
+        }
+    }
+}
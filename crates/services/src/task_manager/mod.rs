@@ -16,6 +16,7 @@ use tokio::sync::mpsc;
 use trc::TaskManagerEvent;
 
 pub mod acme;
+pub mod addressbook_directory;
 pub mod alarm;
 pub mod destroy_account;
 pub mod dkim;
@@ -23,13 +24,17 @@ pub mod dns;
 pub mod imip;
 pub mod index;
 pub mod lock;
+pub mod login_anomaly;
 pub mod maintenance;
 pub mod manager;
 pub mod merge_threads;
+pub mod migration;
+pub mod metrics;
 pub mod report;
 pub mod restore_item;
 pub mod scheduler;
 pub mod spam_classifier;
+pub mod subscription;
 
 const QUEUE_REFRESH_INTERVAL: u64 = 60 * 5; // 5 minutes
 const DEFAULT_LOCK_EXPIRY: u64 = 60 * 60; // 1 hour
@@ -105,6 +110,10 @@ impl TaskInfo for Task {
             Task::DkimManagement(_) => "DkimManagement",
             Task::DnsManagement(_) => "DnsManagement",
             Task::TenantMaintenance(_) => "TenantMaintenance",
+            Task::CalendarSubscriptionSync(_) => "CalendarSubscriptionSync",
+            Task::AddressBookDirectorySync(_) => "AddressBookDirectorySync",
+            Task::AccountMigration(_) => "AccountMigration",
+            Task::LoginAnomaly(_) => "LoginAnomaly",
         }
     }
 }
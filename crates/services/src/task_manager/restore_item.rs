@@ -6,16 +6,19 @@
 
 use common::{Server, auth::BuildAccessToken};
 use email::{
+    cache::{MessageCacheFetch, mailbox::MailboxCacheAccess},
     mailbox::INBOX_ID,
     message::ingest::{EmailIngest, IngestEmail, IngestSource},
 };
 use mail_parser::MessageParser;
 use registry::schema::{enums::ArchivedItemType, structs::TaskRestoreArchivedItem};
-use store::write::{BatchBuilder, BlobLink, BlobOp};
+use store::write::{BatchBuilder, BlobLink, BlobOp, now};
 use trc::AddContext;
 
 use crate::task_manager::TaskResult;
 
+const RESTORE_HOLD_PERIOD: u64 = 3600;
+
 pub(crate) trait RestoreItemTask: Sync + Send {
     fn restore_item(
         &self,
@@ -48,6 +51,47 @@ async fn restore_item(server: &Server, task: &TaskRestoreArchivedItem) -> trc::R
                 .await
                 .caused_by(trc::location!())?;
 
+            // Restore into the requested mailbox if it still exists
+            let mailbox_id = match task.mailbox_id {
+                Some(mailbox_id)
+                    if server
+                        .get_cached_messages(account_id)
+                        .await
+                        .caused_by(trc::location!())?
+                        .has_mailbox_id(&mailbox_id.document_id()) =>
+                {
+                    mailbox_id.document_id()
+                }
+                _ => INBOX_ID,
+            };
+
+            // Resurrect the blob while it has not been purged yet, holding it until
+            // the restored message links to it
+            if !server
+                .store()
+                .blob_exists(&task.blob_id.hash)
+                .await
+                .caused_by(trc::location!())?
+            {
+                return Ok(TaskResult::permanent(
+                    "Archived message is no longer available",
+                ));
+            }
+            let hold_until = now() + RESTORE_HOLD_PERIOD;
+            let mut batch = BatchBuilder::new();
+            batch.with_account_id(account_id).set(
+                BlobOp::Link {
+                    hash: task.blob_id.hash.clone(),
+                    to: BlobLink::Temporary { until: hold_until },
+                },
+                vec![],
+            );
+            server
+                .store()
+                .write(batch.build_all())
+                .await
+                .caused_by(trc::location!())?;
+
             let Some(bytes) = server
                 .blob_store()
                 .get_blob(task.blob_id.hash.as_slice(), 0..usize::MAX)
@@ -62,7 +106,7 @@ async fn restore_item(server: &Server, task: &TaskRestoreArchivedItem) -> trc::R
                     message: MessageParser::new().parse(&bytes),
                     blob_hash: Some(&task.blob_id.hash),
                     access_token: &access_token.build(),
-                    mailbox_ids: vec![INBOX_ID],
+                    mailbox_ids: vec![mailbox_id],
                     keywords: vec![],
                     received_at: (task.created_at.timestamp() as u64).into(),
                     source: IngestSource::Restore,
@@ -72,12 +116,18 @@ async fn restore_item(server: &Server, task: &TaskRestoreArchivedItem) -> trc::R
             {
                 Ok(_) => {
                     let mut batch = BatchBuilder::new();
-                    batch.with_account_id(account_id).clear(BlobOp::Link {
-                        hash: task.blob_id.hash.clone(),
-                        to: BlobLink::Temporary {
-                            until: task.archived_until.timestamp() as u64,
-                        },
-                    });
+                    batch
+                        .with_account_id(account_id)
+                        .clear(BlobOp::Link {
+                            hash: task.blob_id.hash.clone(),
+                            to: BlobLink::Temporary {
+                                until: task.archived_until.timestamp() as u64,
+                            },
+                        })
+                        .clear(BlobOp::Link {
+                            hash: task.blob_id.hash.clone(),
+                            to: BlobLink::Temporary { until: hold_until },
+                        });
                     server.store().write(batch.build_all()).await?;
 
                     Ok(TaskResult::Success(vec![]))
@@ -12,6 +12,7 @@ use std::{
     time::{Instant, SystemTime},
 };
 
+use crate::task_manager::metrics::MetricsBreakdownTask;
 use common::{
     BuildServer, Inner, LONG_1D_SLUMBER,
     config::{mailstore::spamfilter, telemetry::OtelMetrics},
@@ -254,6 +255,7 @@ pub fn spawn_task_scheduler(inner: Arc<Inner>) {
 
                             if roles.metrics_push {
                                 let otel = otel.clone();
+                                let server = server.clone();
 
                                 // SPDX-SnippetBegin
                                 // SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
@@ -267,7 +269,12 @@ pub fn spawn_task_scheduler(inner: Arc<Inner>) {
 
                                 tokio::spawn(async move {
                                     let elapsed = Instant::now();
-                                    otel.push_metrics(is_enterprise, start_time).await;
+                                    otel.push_metrics(
+                                        is_enterprise,
+                                        start_time,
+                                        server.collect_metrics_breakdown().await,
+                                    )
+                                    .await;
 
                                     trc::event!(
                                         Telemetry(TelemetryEvent::MetricsPushed),
@@ -312,6 +319,12 @@ pub fn spawn_task_scheduler(inner: Arc<Inner>) {
                                 }
                                 // SPDX-SnippetEnd
 
+                                if let Err(err) = server.calculate_metrics_breakdown().await {
+                                    trc::error!(
+                                        err.details("Failed to calculate metrics breakdown")
+                                    );
+                                }
+
                                 if update_other_metrics {
                                     match server.total_accounts().await {
                                         Ok(total) => {
@@ -343,6 +356,11 @@ pub fn spawn_task_scheduler(inner: Arc<Inner>) {
                                 }
                             }
 
+                            // Each node flushes the bandwidth it accounted locally
+                            if let Err(err) = server.flush_bandwidth_usage().await {
+                                trc::error!(err.details("Failed to flush bandwidth usage"));
+                            }
+
                             match tokio::task::spawn_blocking(memory_stats::memory_stats).await {
                                 Ok(Some(stats)) => {
                                     Collector::update_gauge(
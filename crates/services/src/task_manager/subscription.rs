@@ -0,0 +1,455 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::task_manager::TaskResult;
+use calcard::{Entry, Parser, common::timezone::Tz, icalendar::ICalendar};
+use common::{DavName, DavResources, Server, USER_AGENT, auth::BuildAccessToken};
+use groupware::{
+    DestroyArchive,
+    cache::GroupwareCache,
+    calendar::{
+        Calendar, CalendarEvent, CalendarEventData, CalendarSubscription,
+        subscription::split_subscription_feed,
+    },
+};
+use registry::schema::{enums::Permission, structs::TaskCalendarSubscription};
+use reqwest::{
+    StatusCode, Url,
+    header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, LOCATION},
+    redirect::Policy,
+};
+use store::{
+    ValueKey,
+    ahash::AHashMap,
+    write::{AlignedBytes, Archive, BatchBuilder, now},
+};
+use trc::{AddContext, TaskManagerEvent};
+use types::{
+    collection::{Collection, SyncCollection},
+    field::CalendarField,
+};
+use utils::{HttpLimitResponse, http::resolve_public_host};
+
+const MAX_REDIRECTS: usize = 5;
+
+pub(crate) trait CalendarSubscriptionTask: Sync + Send {
+    fn refresh_calendar_subscription(
+        &self,
+        task: &TaskCalendarSubscription,
+    ) -> impl Future<Output = TaskResult> + Send;
+}
+
+impl CalendarSubscriptionTask for Server {
+    async fn refresh_calendar_subscription(&self, task: &TaskCalendarSubscription) -> TaskResult {
+        match refresh_calendar_subscription(self, task).await {
+            Ok(result) => result,
+            Err(err) => {
+                let result = TaskResult::temporary(err.to_string());
+                trc::error!(
+                    err.account_id(task.account_id.document_id())
+                        .document_id(task.document_id.document_id())
+                        .caused_by(trc::location!())
+                        .details("Failed to refresh calendar subscription")
+                );
+                result
+            }
+        }
+    }
+}
+
+enum FetchResult {
+    NotModified,
+    Modified {
+        contents: Vec<u8>,
+        etag: Option<String>,
+        last_modified: Option<String>,
+    },
+}
+
+async fn refresh_calendar_subscription(
+    server: &Server,
+    task: &TaskCalendarSubscription,
+) -> trc::Result<TaskResult> {
+    let account_id = task.account_id.document_id();
+    let document_id = task.document_id.document_id();
+
+    // Fetch subscription
+    let Some(subscription_) = server
+        .store()
+        .get_value::<Archive<AlignedBytes>>(ValueKey::property(
+            account_id,
+            Collection::Calendar,
+            document_id,
+            CalendarField::Subscription,
+        ))
+        .await
+        .caused_by(trc::location!())?
+    else {
+        trc::event!(
+            TaskManager(TaskManagerEvent::MetadataNotFound),
+            Details = "Calendar subscription not found",
+            AccountId = account_id,
+            DocumentId = document_id,
+        );
+
+        return Ok(TaskResult::Success(vec![]));
+    };
+    let mut subscription = subscription_
+        .deserialize::<CalendarSubscription>()
+        .caused_by(trc::location!())?;
+
+    // Make sure the calendar still exists
+    let resources = server
+        .fetch_dav_resources(account_id, account_id, SyncCollection::Calendar)
+        .await
+        .caused_by(trc::location!())?;
+    if !resources.has_container_id(&document_id)
+        || server
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::Calendar,
+                document_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+            .map(|calendar_| {
+                calendar_
+                    .unarchive::<Calendar>()
+                    .map(|calendar| !calendar.is_subscription())
+            })
+            .transpose()
+            .caused_by(trc::location!())?
+            .unwrap_or(true)
+    {
+        trc::event!(
+            TaskManager(TaskManagerEvent::MetadataNotFound),
+            Details = "Subscribed calendar not found",
+            AccountId = account_id,
+            DocumentId = document_id,
+        );
+
+        return Ok(TaskResult::Success(vec![]));
+    }
+
+    // Skip stale tasks, another refresh is already scheduled
+    let now = now() as i64;
+    if subscription.next_refresh > now + 60 {
+        return Ok(TaskResult::Success(vec![]));
+    }
+
+    // Make sure the account is still allowed to subscribe
+    let access_token = server
+        .access_token(account_id)
+        .await
+        .caused_by(trc::location!())?
+        .build();
+    if !access_token.has_permission(Permission::CalendarSubscribe) {
+        trc::event!(
+            TaskManager(TaskManagerEvent::TaskIgnored),
+            Reason = "Account does not have permission to subscribe to calendars",
+            AccountId = account_id,
+            DocumentId = document_id,
+        );
+        return Ok(TaskResult::Success(vec![]));
+    }
+
+    let config = &server.core.groupware;
+    let url = subscription.fetch_url();
+    let mut batch = BatchBuilder::new();
+    subscription.last_sync = now;
+
+    match fetch_subscription(server, &subscription, &url).await {
+        Ok(FetchResult::NotModified) => {
+            subscription.failed_attempts = 0;
+            subscription.last_error = None;
+        }
+        Ok(FetchResult::Modified {
+            contents,
+            etag,
+            last_modified,
+        }) => match std::str::from_utf8(&contents)
+            .ok()
+            .map(|contents| Parser::new(contents).entry())
+        {
+            Some(Entry::ICalendar(feed)) => match sync_subscription_events(
+                server,
+                &resources,
+                account_id,
+                document_id,
+                split_subscription_feed(&feed),
+                &mut batch,
+            )
+            .await
+            {
+                Ok((added, updated, removed)) => {
+                    trc::event!(
+                        Resource(trc::ResourceEvent::DownloadExternal),
+                        Url = url.clone(),
+                        AccountId = account_id,
+                        DocumentId = document_id,
+                        Size = contents.len(),
+                        Details = vec![
+                            trc::Value::from(added),
+                            trc::Value::from(updated),
+                            trc::Value::from(removed),
+                        ],
+                    );
+
+                    subscription.etag = etag;
+                    subscription.last_modified = last_modified;
+                    subscription.failed_attempts = 0;
+                    subscription.last_error = None;
+                }
+                Err(err)
+                    if err.matches(trc::EventType::Limit(trc::LimitEvent::Quota))
+                        || err.matches(trc::EventType::Limit(trc::LimitEvent::TenantQuota)) =>
+                {
+                    // Discard the partial sync, the feed is retried on the next refresh
+                    batch = BatchBuilder::new();
+                    subscription.failed_attempts += 1;
+                    subscription.last_error = "Quota exceeded".to_string().into();
+                }
+                Err(err) => return Err(err),
+            },
+            _ => {
+                subscription.failed_attempts += 1;
+                subscription.last_error = "Failed to parse iCalendar feed".to_string().into();
+            }
+        },
+        Err(err) => {
+            subscription.failed_attempts += 1;
+            subscription.last_error = err.into();
+        }
+    }
+
+    if let Some(err) = &subscription.last_error {
+        trc::event!(
+            Resource(trc::ResourceEvent::Error),
+            Url = url,
+            AccountId = account_id,
+            DocumentId = document_id,
+            Reason = err.clone(),
+            Total = subscription.failed_attempts,
+        );
+    }
+
+    // Schedule next refresh
+    subscription.next_refresh = subscription.next_retry(
+        config.subscription_refresh_interval,
+        config.subscription_max_backoff,
+    );
+    subscription
+        .write(account_id, document_id, &mut batch)
+        .caused_by(trc::location!())?;
+    server
+        .commit_batch(batch)
+        .await
+        .caused_by(trc::location!())?;
+
+    Ok(TaskResult::Success(vec![]))
+}
+
+async fn fetch_subscription(
+    server: &Server,
+    subscription: &CalendarSubscription,
+    url: &str,
+) -> Result<FetchResult, String> {
+    let config = &server.core.groupware;
+    let mut url = Url::parse(url).map_err(|err| format!("Invalid URL {url}: {err}"))?;
+
+    // Redirects are followed manually so every hop goes through the address checks
+    for _ in 0..=MAX_REDIRECTS {
+        let (host, addrs) = resolve_public_host(&url).await?;
+        let client = reqwest::Client::builder()
+            .timeout(config.subscription_timeout)
+            .user_agent(USER_AGENT)
+            .redirect(Policy::none())
+            .resolve_to_addrs(&host, &addrs)
+            .build()
+            .map_err(|err| format!("Failed to build HTTP client: {err}"))?;
+        let mut request = client.get(url.clone());
+        if let Some(etag) = &subscription.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &subscription.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|err| format!("Failed to fetch {url}: {err}"))?;
+
+        match response.status() {
+            StatusCode::NOT_MODIFIED => return Ok(FetchResult::NotModified),
+            status if status.is_redirection() => {
+                url = response
+                    .headers()
+                    .get(LOCATION)
+                    .and_then(|location| location.to_str().ok())
+                    .and_then(|location| url.join(location).ok())
+                    .filter(|url| matches!(url.scheme(), "http" | "https"))
+                    .ok_or_else(|| format!("Invalid redirect from {url}: Code: {status}"))?;
+            }
+            status if status.is_success() => {
+                let header = |name| {
+                    response
+                        .headers()
+                        .get(name)
+                        .and_then(|value| value.to_str().ok())
+                        .map(|value| value.to_string())
+                };
+                let etag = header(ETAG);
+                let last_modified = header(LAST_MODIFIED);
+
+                return response
+                    .bytes_with_limit(config.subscription_max_size)
+                    .await
+                    .map_err(|err| format!("Failed to fetch {url}: {err}"))?
+                    .map(|contents| FetchResult::Modified {
+                        contents,
+                        etag,
+                        last_modified,
+                    })
+                    .ok_or_else(|| format!("Feed exceeds maximum size: {url}"));
+            }
+            status => return Err(format!("Failed to fetch {url}: Code: {status}")),
+        }
+    }
+
+    Err(format!("Too many redirects fetching {url}"))
+}
+
+async fn sync_subscription_events(
+    server: &Server,
+    resources: &DavResources,
+    account_id: u32,
+    calendar_id: u32,
+    mut objects: AHashMap<String, ICalendar>,
+    batch: &mut BatchBuilder,
+) -> trc::Result<(usize, usize, usize)> {
+    let account_info = server
+        .account_info(account_id)
+        .await
+        .caused_by(trc::location!())?;
+    let config = &server.core.groupware;
+    let mut updated = 0;
+    let mut removed = 0;
+    let mut size_delta: i64 = 0;
+
+    // Update or remove existing events
+    for document_id in resources.children_ids(calendar_id) {
+        let Some(event_) = server
+            .store()
+            .get_value::<Archive<AlignedBytes>>(ValueKey::archive(
+                account_id,
+                Collection::CalendarEvent,
+                document_id,
+            ))
+            .await
+            .caused_by(trc::location!())?
+        else {
+            continue;
+        };
+        let event = event_
+            .to_unarchived::<CalendarEvent>()
+            .caused_by(trc::location!())?;
+
+        match event
+            .inner
+            .data
+            .event
+            .uids()
+            .next()
+            .and_then(|uid| objects.remove(uid))
+        {
+            Some(ical) if ical.to_string() != event.inner.data.event.to_string() => {
+                let mut new_event = event
+                    .deserialize::<CalendarEvent>()
+                    .caused_by(trc::location!())?;
+                new_event.size = ical.size() as u32;
+                size_delta += new_event.size as i64 - u32::from(event.inner.size) as i64;
+                new_event.data = CalendarEventData::new(
+                    ical,
+                    Tz::Floating,
+                    config.max_ical_instances,
+                    &mut None,
+                );
+                new_event
+                    .update(
+                        account_info.account_tenant_ids(),
+                        event,
+                        account_id,
+                        document_id,
+                        batch,
+                    )
+                    .caused_by(trc::location!())?;
+                updated += 1;
+            }
+            Some(_) => {}
+            None => {
+                size_delta -= u32::from(event.inner.size) as i64;
+                DestroyArchive(event)
+                    .delete(
+                        &account_info,
+                        account_id,
+                        document_id,
+                        calendar_id,
+                        None,
+                        false,
+                        batch,
+                    )
+                    .caused_by(trc::location!())?;
+                removed += 1;
+            }
+        }
+    }
+
+    // Add new events
+    let added = objects.len();
+    if added > 0 {
+        let mut next_document_id = server
+            .store()
+            .assign_document_ids(account_id, Collection::CalendarEvent, added as u64)
+            .await
+            .caused_by(trc::location!())?;
+        for ical in objects.into_values() {
+            let document_id = next_document_id;
+            let size = ical.size() as u32;
+            next_document_id -= 1;
+            size_delta += size as i64;
+            CalendarEvent {
+                names: vec![DavName::new_with_rand_name(calendar_id)],
+                size,
+                data: CalendarEventData::new(
+                    ical,
+                    Tz::Floating,
+                    config.max_ical_instances,
+                    &mut None,
+                ),
+                ..Default::default()
+            }
+            .insert(
+                account_info.account_tenant_ids(),
+                account_id,
+                document_id,
+                None,
+                batch,
+            )
+            .caused_by(trc::location!())?;
+        }
+    }
+
+    // Validate quota
+    if size_delta > 0 {
+        server
+            .has_available_quota(account_info.account(), size_delta as u64)
+            .await?;
+    }
+
+    Ok((added, updated, removed))
+}
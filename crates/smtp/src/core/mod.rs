@@ -4,10 +4,14 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use crate::{inbound::auth::SaslToken, queue::QueueId};
+use crate::{
+    inbound::auth::SaslToken,
+    queue::{QueueId, subaddress_detail},
+};
+use ahash::AHashMap;
 use common::{
     Inner, Server,
-    auth::AccountInfo,
+    auth::{AccessToken, AccountInfo},
     config::smtp::auth::VerifyStrategy,
     network::{ServerInstance, asn::AsnGeoLookupResult},
 };
@@ -72,11 +76,15 @@ pub struct SessionData {
 
     pub mail_from: Option<SessionAddress>,
     pub rcpt_to: Vec<SessionAddress>,
+    pub list_rcpts: AHashMap<String, u32>,
+    pub spam_traps: Vec<String>,
     pub rcpt_errors: usize,
     pub rcpt_oks: usize,
+    pub rcpt_total: usize,
     pub message: Vec<u8>,
 
     pub authenticated_as: Option<AccountInfo>,
+    pub impersonated_by: Option<AccessToken>,
     pub auth_errors: usize,
 
     pub priority: i16,
@@ -86,6 +94,7 @@ pub struct SessionData {
     pub valid_until: Instant,
     pub bytes_left: usize,
     pub messages_sent: usize,
+    pub declared_size: usize,
 
     pub iprev: Option<IprevOutput>,
     pub spf_ehlo: Option<SpfOutput>,
@@ -120,6 +129,7 @@ pub struct SessionParameters {
     pub rcpt_errors_max: usize,
     pub rcpt_errors_wait: Duration,
     pub rcpt_max: usize,
+    pub rcpt_session_max: usize,
     pub rcpt_dsn: bool,
     pub can_expn: bool,
     pub can_vrfy: bool,
@@ -152,14 +162,19 @@ impl SessionData {
             helo_domain: String::new(),
             mail_from: None,
             rcpt_to: Vec::new(),
+            list_rcpts: AHashMap::new(),
+            spam_traps: Vec::new(),
             authenticated_as: None,
+            impersonated_by: None,
             priority: 0,
             valid_until: Instant::now(),
             rcpt_errors: 0,
             rcpt_oks: 0,
+            rcpt_total: 0,
             message: Vec::with_capacity(0),
             auth_errors: 0,
             messages_sent: 0,
+            declared_size: 0,
             bytes_left: 0,
             delivery_by: 0,
             future_release: 0,
@@ -229,6 +244,7 @@ impl Session<common::network::stream::NullIo> {
                 rcpt_errors_max: Default::default(),
                 rcpt_errors_wait: Default::default(),
                 rcpt_max: Default::default(),
+                rcpt_session_max: Default::default(),
                 rcpt_dsn: Default::default(),
                 max_message_size: Default::default(),
                 iprev: VerifyStrategy::Disable,
@@ -275,10 +291,14 @@ impl SessionData {
             helo_domain: "localhost".into(),
             mail_from,
             rcpt_to,
+            list_rcpts: AHashMap::new(),
+            spam_traps: Vec::new(),
             rcpt_errors: 0,
             rcpt_oks: 0,
+            rcpt_total: 0,
             message,
             authenticated_as: Some(authenticated_as),
+            impersonated_by: None,
             auth_errors: 0,
             priority: 0,
             delivery_by: 0,
@@ -286,6 +306,7 @@ impl SessionData {
             valid_until: Instant::now(),
             bytes_left: 0,
             messages_sent: 0,
+            declared_size: 0,
             iprev: None,
             spf_ehlo: None,
             spf_mail_from: None,
@@ -311,4 +332,8 @@ impl SessionAddress {
             dsn_info: None,
         }
     }
+
+    pub fn subaddress(&self) -> Option<&str> {
+        subaddress_detail(self.dsn_info.as_deref()?, &self.address_lcase)
+    }
 }
@@ -132,6 +132,11 @@ impl<T: SessionStream> Session<T> {
             .eval_if(&rc.max_recipients, self, self.data.session_id)
             .await
             .unwrap_or(100);
+        self.params.rcpt_session_max = self
+            .server
+            .eval_if(&rc.max_session_recipients, self, self.data.session_id)
+            .await
+            .unwrap_or(1000);
         self.params.rcpt_dsn = self
             .server
             .eval_if(
@@ -5,11 +5,17 @@
  */
 
 use crate::core::Session;
-use common::{auth::AuthRequest, network::SessionStream};
+use common::{
+    auth::{AccessToken, AuthRequest},
+    config::server::ServerProtocol,
+    network::SessionStream,
+};
 use directory::Credentials;
 use mail_parser::decoders::base64::base64_decode;
 use registry::schema::enums::Permission;
-use smtp_proto::{AUTH_LOGIN, AUTH_OAUTHBEARER, AUTH_PLAIN, AUTH_XOAUTH2, IntoString};
+use smtp_proto::{
+    AUTH_EXTERNAL, AUTH_LOGIN, AUTH_OAUTHBEARER, AUTH_PLAIN, AUTH_XOAUTH2, IntoString,
+};
 use trc::AuthEvent;
 
 pub struct SaslToken {
@@ -20,7 +26,7 @@ pub struct SaslToken {
 impl SaslToken {
     pub fn from_mechanism(mechanism: u64) -> Option<SaslToken> {
         match mechanism {
-            AUTH_PLAIN | AUTH_LOGIN => SaslToken {
+            AUTH_PLAIN | AUTH_LOGIN | AUTH_EXTERNAL => SaslToken {
                 mechanism,
                 credentials: Credentials::Basic {
                     username: String::new(),
@@ -50,7 +56,7 @@ impl<T: SessionStream> Session<T> {
     ) -> Result<bool, ()> {
         if response.is_empty() {
             match (token.mechanism, &token.credentials) {
-                (AUTH_PLAIN | AUTH_XOAUTH2 | AUTH_OAUTHBEARER, _) => {
+                (AUTH_PLAIN | AUTH_XOAUTH2 | AUTH_OAUTHBEARER | AUTH_EXTERNAL, _) => {
                     self.write(b"334 \r\n").await?;
                     return Ok(true);
                 }
@@ -65,6 +71,11 @@ impl<T: SessionStream> Session<T> {
                 }
                 _ => (),
             }
+        } else if token.mechanism == AUTH_EXTERNAL {
+            // Authorization identities other than the certificate owner are not supported
+            if response == b"=" || base64_decode(response).is_some_and(|authz| authz.is_empty()) {
+                return Box::pin(self.authenticate_external()).await;
+            }
         } else if let Some(response) = base64_decode(response) {
             match (token.mechanism, &mut token.credentials) {
                 (AUTH_PLAIN, _) => {
@@ -115,12 +126,47 @@ impl<T: SessionStream> Session<T> {
                 credentials,
                 self.data.session_id,
                 self.data.remote_ip,
+                ServerProtocol::Smtp,
             ))
-            .await
-            .and_then(|access_token| access_token.assert_has_permission(Permission::EmailSend));
+            .await;
+        self.complete_authentication(result).await
+    }
 
-        let result = match result {
-            Ok(access_token) => self.server.account_info(access_token.account_id()).await,
+    pub async fn authenticate_external(&mut self) -> Result<bool, ()> {
+        let result = if let Some(certificate) = self.stream.peer_certificate() {
+            self.server
+                .authenticate_certificate(
+                    certificate,
+                    self.instance.acceptor.client_cert_principal(),
+                    self.data.session_id,
+                    self.data.remote_ip,
+                )
+                .await
+        } else {
+            Err(trc::AuthEvent::Failed
+                .into_err()
+                .details("No client certificate was provided."))
+        };
+        self.complete_authentication(result).await
+    }
+
+    async fn complete_authentication(
+        &mut self,
+        result: trc::Result<AccessToken>,
+    ) -> Result<bool, ()> {
+        let result = match result
+            .and_then(|access_token| access_token.assert_has_permission(Permission::EmailSend))
+        {
+            Ok(access_token) => {
+                self.instance
+                    .sessions
+                    .set_account(self.data.session_id, Some(&access_token));
+                self.data.impersonated_by = access_token
+                    .impersonator_id()
+                    .is_some()
+                    .then(|| access_token.clone());
+                self.server.account_info(access_token.account_id()).await
+            }
             Err(err) => Err(err),
         };
 
@@ -211,6 +257,17 @@ impl<T: SessionStream> Session<T> {
         self.data.authenticated_as.is_some()
     }
 
+    // EXTERNAL is offered when the client presented a certificate that can be mapped to an account
+    pub fn external_auth_mechanism(&self) -> u64 {
+        if self.stream.peer_certificate().is_some()
+            && !self.instance.acceptor.client_cert_principal().is_empty()
+        {
+            AUTH_EXTERNAL
+        } else {
+            0
+        }
+    }
+
     pub fn authenticated_emails(&self) -> &[String] {
         self.data.authenticated_as.as_ref().unwrap().addresses()
     }
@@ -0,0 +1,175 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    core::Session,
+    outbound::{
+        client::SmtpClient,
+        error::{AssertReply, ClientError},
+        lookup::DnsLookup,
+    },
+};
+use common::{KV_RCPT_VERIFY, auth::RelayVerify, network::SessionStream};
+use mail_auth::IpLookupStrategy;
+use smtp_proto::Severity;
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
+use store::dispatch::lookup::KeyValue;
+use trc::{AddContext, SmtpEvent};
+
+const VERIFY_TIMEOUT: Duration = Duration::from_secs(30);
+
+impl<T: SessionStream> Session<T> {
+    pub async fn verify_relay_rcpt(&self, rcpt: &str, verify: &RelayVerify) -> trc::Result<bool> {
+        // Check whether the recipient was verified recently
+        let key = KeyValue::<()>::build_key(KV_RCPT_VERIFY, rcpt);
+        if let Some(result) = self
+            .server
+            .in_memory_store()
+            .key_get::<String>(key.clone())
+            .await
+            .caused_by(trc::location!())?
+        {
+            return Ok(result == "1");
+        }
+
+        let exists = match verify {
+            RelayVerify::Smtp { host, .. } => self.smtp_callout(rcpt, host.as_deref()).await,
+            RelayVerify::Http { url, .. } => http_lookup(url, rcpt).await,
+        }
+        .map_err(|err| {
+            SmtpEvent::RcptVerifyFailed
+                .into_err()
+                .ctx(trc::Key::To, rcpt.to_string())
+                .details(err)
+        })?;
+
+        if let Err(err) = self
+            .server
+            .in_memory_store()
+            .key_set(
+                KeyValue::new(key, if exists { b"1" } else { b"0" }.to_vec())
+                    .expires(verify.cache_ttl().as_secs()),
+            )
+            .await
+        {
+            trc::error!(
+                err.span_id(self.data.session_id)
+                    .caused_by(trc::location!())
+                    .details("Failed to cache recipient verification result.")
+            );
+        }
+
+        Ok(exists)
+    }
+
+    async fn smtp_callout(&self, rcpt: &str, host: Option<&str>) -> Result<bool, String> {
+        // Use the configured host or the primary MX of the recipient domain
+        let (hostname, port) = match host {
+            Some(host) => host
+                .rsplit_once(':')
+                .and_then(|(host, port)| port.parse::<u16>().ok().map(|port| (host, port)))
+                .map(|(host, port)| (host.to_string(), port))
+                .unwrap_or_else(|| (host.to_string(), 25)),
+            None => {
+                let domain = rcpt
+                    .rsplit_once('@')
+                    .map(|(_, domain)| domain)
+                    .unwrap_or(rcpt);
+                let mx = self
+                    .server
+                    .core
+                    .smtp
+                    .resolvers
+                    .dns
+                    .mx_lookup(domain, Some(&self.server.inner.cache.dns_mx))
+                    .await
+                    .map_err(|err| format!("MX lookup for {domain} failed: {err}"))?;
+                let host = mx
+                    .iter()
+                    .flat_map(|mx| mx.exchanges.iter())
+                    .next()
+                    .ok_or_else(|| format!("No MX records found for {domain}"))?;
+                (host.trim_end_matches('.').to_string(), 25)
+            }
+        };
+        let ip = match hostname.parse::<IpAddr>() {
+            Ok(ip) => ip,
+            Err(_) => self
+                .server
+                .ip_lookup(&hostname, IpLookupStrategy::Ipv4thenIpv6, 1)
+                .await
+                .map_err(|err| format!("Failed to resolve {hostname}: {err}"))?
+                .into_iter()
+                .next()
+                .ok_or_else(|| format!("No IP addresses found for {hostname}"))?,
+        };
+
+        let mut client = SmtpClient::connect(
+            SocketAddr::new(ip, port),
+            VERIFY_TIMEOUT,
+            self.data.session_id,
+        )
+        .await
+        .map_err(|err| format!("Failed to connect to {hostname}: {err}"))?;
+        let result = async {
+            tokio::time::timeout(VERIFY_TIMEOUT, client.read())
+                .await
+                .map_err(|_| ClientError::Timeout)??
+                .assert_code(220)?;
+            client
+                .cmd(format!("HELO {}\r\n", self.hostname))
+                .await?
+                .assert_positive_completion()?;
+            client
+                .cmd(b"MAIL FROM:<>\r\n")
+                .await?
+                .assert_positive_completion()?;
+            client.cmd(format!("RCPT TO:<{rcpt}>\r\n")).await
+        }
+        .await;
+        client.quit().await;
+
+        let response = result.map_err(|err| format!("Callout to {hostname} failed: {err}"))?;
+        match response.severity() {
+            Severity::PositiveCompletion => Ok(true),
+            Severity::PermanentNegativeCompletion if matches!(response.code(), 550 | 551 | 553) => {
+                Ok(false)
+            }
+            _ => Err(format!(
+                "Callout to {hostname} returned an unexpected response: {} {}",
+                response.code(),
+                response.message()
+            )),
+        }
+    }
+}
+
+async fn http_lookup(url: &str, rcpt: &str) -> Result<bool, String> {
+    let response = reqwest::Client::builder()
+        .user_agent(common::USER_AGENT)
+        .timeout(VERIFY_TIMEOUT)
+        .build()
+        .map_err(|err| format!("Failed to create HTTP client: {err}"))?
+        .get(url.replace(
+            "{address}",
+            &form_urlencoded::byte_serialize(rcpt.as_bytes()).collect::<String>(),
+        ))
+        .send()
+        .await
+        .map_err(|err| format!("Lookup request failed: {err}"))?;
+
+    match response.status().as_u16() {
+        200..=299 => Ok(true),
+        404 => Ok(false),
+        code => Err(format!(
+            "Lookup request failed with code {code}: {}",
+            response.status().canonical_reason().unwrap_or("Unknown")
+        )),
+    }
+}
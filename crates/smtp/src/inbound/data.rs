@@ -9,8 +9,8 @@ use crate::{
     core::{Session, SessionAddress, State},
     inbound::milter::Modification,
     queue::{
-        self, Message, MessageSource, MessageWrapper, QueueEnvelope, RCPT_SPAM_PAYLOAD,
-        quota::HasQueueQuota,
+        self, FROM_BULK_TRAFFIC, Message, MessageSource, MessageWrapper, QueueEnvelope,
+        RCPT_SPAM_PAYLOAD, quota::HasQueueQuota,
     },
     reporting::analysis::AnalyzeReport,
     scripts::ScriptResult,
@@ -24,18 +24,23 @@ use common::{
             session::Stage,
         },
     },
-    network::SessionStream,
+    network::{SessionStream, spam_trap::SpamTrapHit},
     psl,
     scripts::ScriptModification,
+    telemetry::metrics::latency::LatencyMetric,
 };
+use email::auto_response::BulkTraffic;
 use mail_auth::{
     AuthenticatedMessage, AuthenticationResults, DkimResult, DmarcResult, ReceivedSpf,
     common::{crypto::Algorithm, headers::HeaderWriter, verify::VerifySignature},
     dmarc::{self, verify::DmarcParameters},
 };
 use mail_builder::headers::{date::Date, message_id::generate_message_id_header};
-use mail_parser::{MessageParser, parsers::fields::thread::thread_name};
-use registry::schema::structs::Rate;
+use mail_parser::{HeaderName, HeaderValue, MessageParser, parsers::fields::thread::thread_name};
+use registry::schema::{
+    enums::{AddressRewriteScope, DuplicateAction},
+    structs::Rate,
+};
 use sieve::runtime::Variable;
 use smtp_proto::{
     MAIL_BY_RETURN, RCPT_NOTIFY_DELAY, RCPT_NOTIFY_FAILURE, RCPT_NOTIFY_NEVER, RCPT_NOTIFY_SUCCESS,
@@ -44,11 +49,18 @@ use std::{
     borrow::Cow,
     time::{Instant, SystemTime},
 };
-use trc::SmtpEvent;
+use trc::{DkimEvent, SmtpEvent};
 use utils::DomainPart;
 
+const MESSAGE_TAGS_HEADER: &str = "X-Message-Tags";
+const AUTO_BCC_HEADER: &str = "X-Auto-Bcc";
+const MAX_MESSAGE_TAGS: usize = 16;
+const MAX_MESSAGE_TAG_LEN: usize = 128;
+
 impl<T: SessionStream> Session<T> {
     pub async fn queue_message(&mut self) -> Cow<'static, [u8]> {
+        let received = Instant::now();
+
         // Parse message
         let raw_message = std::mem::take(&mut self.data.message);
         let parsed_message = match MessageParser::new()
@@ -73,6 +85,10 @@ impl<T: SessionStream> Session<T> {
         );
         let has_date_header = auth_message.has_date_header();
         let has_message_id_header = auth_message.has_message_id_header();
+        let has_list_unsubscribe = parsed_message
+            .headers()
+            .iter()
+            .any(|header| header.name == HeaderName::ListUnsubscribe);
 
         // Loop detection
         let dc = &self.server.core.smtp.session.data;
@@ -95,6 +111,70 @@ impl<T: SessionStream> Session<T> {
                 .into();
         }
 
+        // Messages addressed to spam traps are never delivered to them
+        if !self.data.spam_traps.is_empty() {
+            let traps = std::mem::take(&mut self.data.spam_traps);
+            self.data
+                .rcpt_to
+                .retain(|rcpt| !traps.contains(&rcpt.address_lcase));
+            let mail_from = self.data.mail_from.as_ref().unwrap();
+            self.server
+                .spam_trap_hit(SpamTrapHit {
+                    remote_ip: self.data.remote_ip,
+                    from: &mail_from.address_lcase,
+                    from_domain: &mail_from.domain,
+                    subject: thread_name(parsed_message.subject().unwrap_or_default()).to_string(),
+                    traps,
+                    raw_message: &raw_message,
+                    span_id: self.data.session_id,
+                })
+                .await;
+
+            if self.data.rcpt_to.is_empty() {
+                self.data.messages_sent += 1;
+                return (b"250 2.0.0 Message queued for delivery.\r\n"[..]).into();
+            }
+        }
+
+        // Detect messages already delivered to the same recipients
+        let mut duplicates = self.check_duplicates(&parsed_message, &raw_message).await;
+        let is_bulk_traffic = BulkTraffic::detect(&parsed_message).is_some();
+        let mut tag_duplicate = false;
+        if let Some(check) = &mut duplicates
+            && !check.duplicates.is_empty()
+        {
+            let addresses = check
+                .duplicates
+                .iter()
+                .map(|idx| trc::Value::from(self.data.rcpt_to[*idx].address_lcase.clone()))
+                .collect::<Vec<_>>();
+
+            if dc.duplicate_action == DuplicateAction::Discard {
+                trc::event!(
+                    Smtp(SmtpEvent::DuplicateDiscarded),
+                    SpanId = self.data.session_id,
+                    To = addresses,
+                );
+
+                for idx in check.duplicates.drain(..).rev() {
+                    self.data.rcpt_to.remove(idx);
+                    check.keys.remove(idx);
+                }
+                if self.data.rcpt_to.is_empty() {
+                    self.data.messages_sent += 1;
+                    return (b"250 2.0.0 Message queued for delivery.\r\n"[..]).into();
+                }
+            } else {
+                trc::event!(
+                    Smtp(SmtpEvent::DuplicateTagged),
+                    SpanId = self.data.session_id,
+                    To = addresses,
+                );
+
+                tag_duplicate = true;
+            }
+        }
+
         // Verify DKIM
         let dkim = self
             .server
@@ -414,8 +494,14 @@ impl<T: SessionStream> Session<T> {
             .write_header(&mut headers);
         }
 
+        // Tag duplicate deliveries
+        if tag_duplicate {
+            headers.extend_from_slice(b"X-Duplicate: Yes\r\n");
+        }
+
         // Run SPAM filter
         let mut train_spam = None;
+        let mut rspamd = None;
         if self.server.core.spam.enabled
             && self
                 .server
@@ -436,6 +522,7 @@ impl<T: SessionStream> Session<T> {
                 SpamFilterAction::Allow(score) => {
                     // Add headers
                     headers.extend_from_slice(score.headers.as_bytes());
+                    rspamd = score.rspamd;
                     train_spam = score.train_spam.map(|is_spam| {
                         (
                             is_spam,
@@ -494,6 +581,20 @@ impl<T: SessionStream> Session<T> {
             }
         };
 
+        // Keep passing DKIM signatures, modifications past this point may invalidate them
+        let dkim_pass = dkim_output
+            .iter()
+            .filter(|o| matches!(o.result(), DkimResult::Pass))
+            .filter_map(|o| o.signature())
+            .map(|s| {
+                (
+                    s.domain().to_lowercase(),
+                    s.selector().to_string(),
+                    s.b.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+
         // Apply modifications
         let mut edited_message = if !modifications.is_empty() {
             self.data
@@ -513,7 +614,7 @@ impl<T: SessionStream> Session<T> {
                     .map(|s| (s, name))
             })
         {
-            let params = self
+            let mut params = self
                 .build_script_parameters("data")
                 .with_auth_headers(&headers)
                 .set_variable(
@@ -561,6 +662,19 @@ impl<T: SessionStream> Session<T> {
                         .unwrap_or_default(),
                 )
                 .with_message(parsed_message);
+            if let Some(rspamd) = &rspamd {
+                params = params
+                    .set_variable("rspamd.score", Variable::Float(rspamd.score as f64))
+                    .set_variable("rspamd.action", rspamd.action.clone())
+                    .set_variable(
+                        "rspamd.symbols",
+                        rspamd
+                            .symbols
+                            .iter()
+                            .map(|(name, _)| Variable::from(name.clone()))
+                            .collect::<Vec<_>>(),
+                    );
+            }
 
             let modifications = match self.run_script(script_id, script.clone(), params).await {
                 ScriptResult::Accept { modifications } => modifications,
@@ -597,12 +711,135 @@ impl<T: SessionStream> Session<T> {
             }
         }
 
-        // Build message
+        // Rewrite header addresses
+        if let Some(message) =
+            self.rewrite_headers(edited_message.as_deref().unwrap_or(raw_message.as_slice()))
+        {
+            edited_message = Some(message);
+        }
+
+        // Extract and strip message tags from authenticated submissions
+        let mut tags = Vec::new();
+        if self.is_authenticated()
+            && let Some(message) = self.extract_tags(
+                edited_message.as_deref().unwrap_or(raw_message.as_slice()),
+                &mut tags,
+            )
+        {
+            edited_message = Some(message);
+        }
+
+        // Append domain disclaimer
+        if let Some(message) = self
+            .apply_disclaimer(edited_message.as_deref().unwrap_or(raw_message.as_slice()))
+            .await
+        {
+            edited_message = Some(message);
+        }
+
+        // Record filtering latency once per recipient domain
+        let elapsed = received.elapsed();
+        for (idx, rcpt) in self.data.rcpt_to.iter().enumerate() {
+            if !self.data.rcpt_to[..idx]
+                .iter()
+                .any(|prev| prev.domain == rcpt.domain)
+            {
+                self.server.record_latency(
+                    LatencyMetric::ReceivedToFiltered,
+                    &rcpt.domain,
+                    elapsed,
+                );
+            }
+        }
+
+        // Add automatic BCC recipients to authenticated submissions, copies
+        // are tagged so they never trigger further copies
+        let bcc_rcpts = if self.is_authenticated()
+            && !parsed_message
+                .headers()
+                .iter()
+                .any(|header| header.name.as_str().eq_ignore_ascii_case(AUTO_BCC_HEADER))
+        {
+            self.auto_bcc_recipients().await
+        } else {
+            Vec::new()
+        };
+
+        // Build message, list members that can unsubscribe receive their own copy
         let mail_from = self.data.mail_from.clone().unwrap();
-        let rcpt_to = std::mem::take(&mut self.data.rcpt_to);
-        let mut message = self
-            .build_message(mail_from, rcpt_to, message_id, self.data.session_id)
-            .await;
+        let mut rcpt_to = std::mem::take(&mut self.data.rcpt_to);
+        let list_rcpts = if !has_list_unsubscribe && !self.data.list_rcpts.is_empty() {
+            let list_rcpts = std::mem::take(&mut self.data.list_rcpts);
+            let mut members = Vec::new();
+            rcpt_to.retain(|rcpt| {
+                if let Some(list_id) = list_rcpts.get(&rcpt.address_lcase) {
+                    members.push((rcpt.clone(), *list_id));
+                    false
+                } else {
+                    true
+                }
+            });
+            members
+        } else {
+            Vec::new()
+        };
+        let mut messages = Vec::with_capacity(list_rcpts.len() + 2);
+        if !rcpt_to.is_empty() || (list_rcpts.is_empty() && bcc_rcpts.is_empty()) {
+            messages.push((
+                self.build_message(mail_from.clone(), rcpt_to, message_id, self.data.session_id)
+                    .await,
+                None,
+            ));
+        }
+        if !bcc_rcpts.is_empty() {
+            let queue_id = if messages.is_empty() {
+                message_id
+            } else {
+                self.server.inner.data.queue_id_gen.generate()
+            };
+            messages.push((
+                self.build_message(mail_from.clone(), bcc_rcpts, queue_id, self.data.session_id)
+                    .await,
+                Some(format!("{AUTO_BCC_HEADER}: Yes\r\n")),
+            ));
+        }
+        for (rcpt, list_id) in list_rcpts {
+            let list_headers = match self
+                .server
+                .list_unsubscribe_headers(list_id, &rcpt.address_lcase)
+                .await
+            {
+                Ok(list_headers) => Some(list_headers),
+                Err(err) => {
+                    trc::error!(
+                        err.span_id(self.data.session_id)
+                            .details("Failed to build List-Unsubscribe headers")
+                    );
+                    None
+                }
+            };
+            let queue_id = if messages.is_empty() {
+                message_id
+            } else {
+                self.server.inner.data.queue_id_gen.generate()
+            };
+            messages.push((
+                self.build_message(
+                    mail_from.clone(),
+                    vec![rcpt],
+                    queue_id,
+                    self.data.session_id,
+                )
+                .await,
+                list_headers,
+            ));
+        }
+        for (message, _) in &mut messages {
+            message.message.tags = tags.clone().into_boxed_slice();
+            if is_bulk_traffic {
+                message.message.flags |= FROM_BULK_TRAFFIC;
+            }
+        }
 
         // Add Return-Path
         if self
@@ -612,7 +849,7 @@ impl<T: SessionStream> Session<T> {
             .unwrap_or(true)
         {
             headers.extend_from_slice(b"Return-Path: <");
-            headers.extend_from_slice(message.message.return_path.as_bytes());
+            headers.extend_from_slice(messages[0].0.message.return_path.as_bytes());
             headers.extend_from_slice(b">\r\n");
         }
 
@@ -642,91 +879,193 @@ impl<T: SessionStream> Session<T> {
 
         // DKIM sign
         let raw_message = edited_message.as_deref().unwrap_or(raw_message.as_slice());
-        if let Some(sign_with_domain) = self
+        let mut sign_with_domain = self
             .server
             .eval_if::<String, _>(&ac.dkim.sign, self, self.data.session_id)
-            .await
-        {
-            match self.server.dkim_signers(&sign_with_domain).await {
-                Ok(Some(signers)) => {
-                    for signer in signers.as_ref() {
-                        match signer.sign_chained(&[headers.as_ref(), raw_message]) {
-                            Ok(signature) => {
-                                signature.write_header(&mut headers);
-                            }
-                            Err(err) => {
-                                trc::error!(
-                                    trc::Error::from(err)
-                                        .span_id(self.data.session_id)
-                                        .details("Failed to DKIM sign message")
-                                );
-                            }
-                        }
-                    }
-                }
-                Ok(None) => {}
-                Err(err) => {
-                    trc::error!(
-                        err.span_id(self.data.session_id)
-                            .details("Failed to retrieve DKIM signers")
-                    );
+            .await;
+
+        // Re-sign messages whose DKIM signatures no longer verify
+        if edited_message.is_some() && !dkim_pass.is_empty() {
+            let invalidated = self
+                .invalidated_dkim_signatures(dkim_pass, raw_message)
+                .await;
+            if !invalidated.is_empty() {
+                if sign_with_domain.is_none() {
+                    sign_with_domain = self
+                        .server
+                        .eval_if::<String, _>(&ac.dkim.resign, self, self.data.session_id)
+                        .await;
                 }
+
+                trc::event!(
+                    Dkim(DkimEvent::SignatureInvalidated),
+                    SpanId = self.data.session_id,
+                    Details = invalidated
+                        .into_iter()
+                        .map(|(domain, selector)| {
+                            trc::Value::from(format!("{selector}._domainkey.{domain}"))
+                        })
+                        .collect::<Vec<_>>(),
+                    Domain = sign_with_domain.clone(),
+                );
             }
         }
 
-        // Update size
-        message.message.size = (raw_message.len() + headers.len()) as u64;
+        // List-Unsubscribe and automatic BCC headers have to be covered by the
+        // DKIM signature (RFC 8058), so each copy is signed separately
+        let mut signed_messages = Vec::with_capacity(messages.len());
+        for (mut message, list_headers) in messages {
+            let mut message_headers = headers.clone();
+            if let Some(list_headers) = list_headers {
+                message_headers.extend_from_slice(list_headers.as_bytes());
+            }
+            if let Some(sign_with_domain) = &sign_with_domain {
+                self.dkim_sign(sign_with_domain, &mut message_headers, raw_message)
+                    .await;
+            }
+
+            // Update size
+            message.message.size = (raw_message.len() + message_headers.len()) as u64;
 
-        // Verify queue quota
-        if self.server.has_quota(&mut message).await {
-            // Prepare webhook event
-            let queue_id = message.queue_id;
+            // Verify queue quota
+            if !self.server.has_quota(&mut message).await {
+                return (b"452 4.3.1 Mail system full, try again later.\r\n"[..]).into();
+            }
+            signed_messages.push((message, message_headers));
+        }
 
-            // Queue message
-            let source = if !self.is_authenticated() {
-                let dmarc_pass = dmarc_result.is_some_and(|result| result == DmarcResult::Pass);
+        // Queue message
+        let queue_id = signed_messages[0].0.queue_id;
+        let mut source = if !self.is_authenticated() {
+            let dmarc_pass = dmarc_result.is_some_and(|result| result == DmarcResult::Pass);
 
-                #[cfg(feature = "test_mode")]
-                {
-                    MessageSource::Unauthenticated {
-                        dmarc_pass: dmarc_pass || message.message.return_path.starts_with("dmarc-"),
-                        train_spam,
-                    }
+            #[cfg(feature = "test_mode")]
+            {
+                MessageSource::Unauthenticated {
+                    dmarc_pass: dmarc_pass
+                        || signed_messages[0]
+                            .0
+                            .message
+                            .return_path
+                            .starts_with("dmarc-"),
+                    train_spam,
                 }
+            }
 
-                #[cfg(not(feature = "test_mode"))]
-                {
-                    MessageSource::Unauthenticated {
-                        dmarc_pass,
-                        train_spam,
-                    }
+            #[cfg(not(feature = "test_mode"))]
+            {
+                MessageSource::Unauthenticated {
+                    dmarc_pass,
+                    train_spam,
                 }
-            } else {
-                MessageSource::Authenticated
-            };
-            if message
+            }
+        } else {
+            MessageSource::Authenticated
+        };
+        for (message, message_headers) in signed_messages {
+            if !message
                 .queue(
-                    Some(&headers),
+                    Some(&message_headers),
                     raw_message,
                     self.data.session_id,
                     &self.server,
-                    source,
+                    source.clone(),
                 )
                 .await
             {
-                self.state = State::Accepted(queue_id);
-                self.data.messages_sent += 1;
-                format!("250 2.0.0 Message queued with id {queue_id:x}.\r\n")
-                    .into_bytes()
-                    .into()
-            } else {
-                (b"451 4.3.5 Unable to accept message at this time.\r\n"[..]).into()
+                return (b"451 4.3.5 Unable to accept message at this time.\r\n"[..]).into();
+            }
+
+            // Spam training only applies once per message
+            if let MessageSource::Unauthenticated { train_spam, .. } = &mut source {
+                *train_spam = None;
+            }
+        }
+
+        self.state = State::Accepted(queue_id);
+        self.data.messages_sent += 1;
+        if let Some(access_token) = &self.data.impersonated_by {
+            self.server
+                .audit_impersonated_write(
+                    access_token,
+                    self.data.remote_ip,
+                    "SmtpMessage",
+                    &format!("{queue_id:x}"),
+                )
+                .await;
+        }
+        if let Some(check) = duplicates {
+            self.record_duplicates(check.keys).await;
+        }
+        format!("250 2.0.0 Message queued with id {queue_id:x}.\r\n")
+            .into_bytes()
+            .into()
+    }
+
+    async fn dkim_sign(&self, domain: &str, headers: &mut Vec<u8>, raw_message: &[u8]) {
+        match self.server.dkim_signers(domain).await {
+            Ok(Some(signers)) => {
+                // All signatures cover the same header block, otherwise the
+                // ones added later would depend on the earlier DKIM-Signature
+                let mut signatures = Vec::with_capacity(signers.len() * 256);
+                for signer in signers.as_ref() {
+                    match signer.sign_chained(&[headers.as_ref(), raw_message]) {
+                        Ok(signature) => {
+                            signature.write_header(&mut signatures);
+                        }
+                        Err(err) => {
+                            trc::error!(
+                                trc::Error::from(err)
+                                    .span_id(self.data.session_id)
+                                    .details("Failed to DKIM sign message")
+                            );
+                        }
+                    }
+                }
+                headers.extend_from_slice(&signatures);
+            }
+            Ok(None) => {}
+            Err(err) => {
+                trc::error!(
+                    err.span_id(self.data.session_id)
+                        .details("Failed to retrieve DKIM signers")
+                );
             }
-        } else {
-            (b"452 4.3.1 Mail system full, try again later.\r\n"[..]).into()
         }
     }
 
+    async fn invalidated_dkim_signatures(
+        &self,
+        signatures: Vec<(String, String, Vec<u8>)>,
+        message: &[u8],
+    ) -> Vec<(String, String)> {
+        let output = if let Some(parsed_message) = MessageParser::new().parse(message) {
+            let auth_message = AuthenticatedMessage::from_parsed(
+                &parsed_message,
+                self.server.core.smtp.mail_auth.dkim.strict,
+            );
+            self.server
+                .core
+                .smtp
+                .resolvers
+                .dns
+                .verify_dkim(self.server.inner.cache.build_auth_parameters(&auth_message))
+                .await
+                .into_iter()
+                .filter(|o| matches!(o.result(), DkimResult::Pass))
+                .filter_map(|o| o.signature().map(|s| s.b.clone()))
+                .collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+
+        signatures
+            .into_iter()
+            .filter(|(_, _, b)| !output.contains(b))
+            .map(|(domain, selector, _)| (domain, selector))
+            .collect()
+    }
+
     pub async fn build_message(
         &self,
         mail_from: SessionAddress,
@@ -748,6 +1087,7 @@ impl<T: SessionStream> Session<T> {
             env_id: mail_from.dsn_info.map(|i| i.into_boxed_str()),
             blob_hash: Default::default(),
             quota_keys: Default::default(),
+            tags: Default::default(),
             received_from_ip: self.data.remote_ip,
             received_via_port: self.data.local_port,
         };
@@ -898,6 +1238,221 @@ impl<T: SessionStream> Session<T> {
         }
     }
 
+    // The expression is evaluated once per message and only against the
+    // original recipients, so copies never trigger further copies
+    async fn auto_bcc_recipients(&self) -> Vec<SessionAddress> {
+        let mut recipients: Vec<SessionAddress> = Vec::new();
+        let Some(addresses) = self
+            .server
+            .eval_if::<Vec<String>, _>(
+                &self.server.core.smtp.session.data.auto_bcc,
+                self,
+                self.data.session_id,
+            )
+            .await
+        else {
+            return recipients;
+        };
+        let Some(mail_from) = self.data.mail_from.as_ref() else {
+            return recipients;
+        };
+
+        for address in addresses {
+            let address_lcase = address.trim().to_lowercase();
+            let domain = address_lcase.domain_part();
+            if domain.is_empty()
+                || address_lcase == mail_from.address_lcase
+                || self
+                    .data
+                    .rcpt_to
+                    .iter()
+                    .chain(recipients.iter())
+                    .any(|rcpt| rcpt.address_lcase == address_lcase)
+            {
+                continue;
+            }
+
+            trc::event!(
+                Smtp(SmtpEvent::RcptTo),
+                SpanId = self.data.session_id,
+                To = address_lcase.clone(),
+                Details = "Automatic BCC",
+            );
+
+            // Senders are not notified about deliveries to hidden recipients
+            recipients.push(SessionAddress {
+                domain: domain.into(),
+                address: address_lcase.clone(),
+                address_lcase,
+                flags: RCPT_NOTIFY_NEVER,
+                dsn_info: None,
+            });
+        }
+
+        recipients
+    }
+
+    async fn apply_disclaimer(&self, raw_message: &[u8]) -> Option<Vec<u8>> {
+        let mail_from = self.data.mail_from.as_ref()?;
+
+        // A single copy is queued for all recipients, so inbound footers
+        // can only be added when every recipient belongs to the same domain
+        let (domain, is_inbound) = if self.is_authenticated() {
+            (mail_from.domain.as_str(), false)
+        } else {
+            let domain = &self.data.rcpt_to.first()?.domain;
+            if self.data.rcpt_to.iter().any(|rcpt| &rcpt.domain != domain) {
+                return None;
+            }
+            (domain.as_str(), true)
+        };
+
+        let domain = match self.server.domain(domain).await {
+            Ok(Some(domain)) => domain,
+            Ok(None) => return None,
+            Err(err) => {
+                trc::error!(
+                    err.caused_by(trc::location!())
+                        .span_id(self.data.session_id)
+                        .details("Failed to lookup local domain")
+                );
+                return None;
+            }
+        };
+
+        domain
+            .disclaimer
+            .as_ref()
+            .filter(|disclaimer| !is_inbound || disclaimer.inbound)?
+            .apply(raw_message, &mail_from.address, domain.name())
+    }
+
+    // Addresses are replaced in place, which keeps display names, comments
+    // and folding of the original header values intact
+    fn rewrite_headers(&self, raw_message: &[u8]) -> Option<Vec<u8>> {
+        let config = &self.server.core.smtp.session;
+        if !config
+            .address_rewrites
+            .iter()
+            .any(|rule| rule.scope.contains(&AddressRewriteScope::Headers))
+        {
+            return None;
+        }
+
+        let message = MessageParser::new().parse_headers(raw_message)?;
+        let mut output = Vec::with_capacity(raw_message.len());
+        let mut offset = 0;
+
+        for header in message.headers() {
+            let (
+                HeaderName::From
+                | HeaderName::Sender
+                | HeaderName::ReplyTo
+                | HeaderName::To
+                | HeaderName::Cc,
+                HeaderValue::Address(address),
+            ) = (&header.name, &header.value)
+            else {
+                continue;
+            };
+
+            let range = header.offset_start as usize..header.offset_end as usize;
+            let mut value = raw_message.get(range.clone())?.to_vec();
+            let mut search_from = 0;
+            let mut has_changes = false;
+
+            for addr in address.iter() {
+                let Some(addr) = addr.address.as_deref().filter(|addr| !addr.is_empty()) else {
+                    continue;
+                };
+                let Some((rule, new_address)) = config
+                    .rewrite_address(&addr.to_lowercase(), AddressRewriteScope::Headers)
+                    .filter(|(_, new_address)| new_address.contains('@'))
+                else {
+                    continue;
+                };
+                let Some(pos) = value
+                    .get(search_from..)
+                    .and_then(|value| {
+                        value
+                            .windows(addr.len())
+                            .position(|window| window == addr.as_bytes())
+                    })
+                    .map(|pos| pos + search_from)
+                else {
+                    continue;
+                };
+
+                trc::event!(
+                    Smtp(SmtpEvent::HeaderRewritten),
+                    SpanId = self.data.session_id,
+                    Details = header.name.as_str().to_string(),
+                    From = addr.to_string(),
+                    To = new_address.clone(),
+                    Reason = rule.description.clone(),
+                );
+
+                search_from = pos + new_address.len();
+                value.splice(pos..pos + addr.len(), new_address.into_bytes());
+                has_changes = true;
+            }
+
+            if has_changes {
+                output.extend_from_slice(raw_message.get(offset..range.start)?);
+                output.extend_from_slice(&value);
+                offset = range.end;
+            }
+        }
+
+        if offset > 0 {
+            output.extend_from_slice(raw_message.get(offset..)?);
+            Some(output)
+        } else {
+            None
+        }
+    }
+
+    fn extract_tags(&self, raw_message: &[u8], tags: &mut Vec<Box<str>>) -> Option<Vec<u8>> {
+        let message = MessageParser::new().parse_headers(raw_message)?;
+        let mut output = Vec::with_capacity(raw_message.len());
+        let mut offset = 0;
+
+        for header in message.headers() {
+            let HeaderName::Other(name) = &header.name else {
+                continue;
+            };
+            if !name.eq_ignore_ascii_case(MESSAGE_TAGS_HEADER) {
+                continue;
+            }
+
+            for tag in header
+                .value
+                .as_text()
+                .unwrap_or_default()
+                .split(',')
+                .map(|tag| tag.trim())
+            {
+                if !tag.is_empty()
+                    && tag.len() <= MAX_MESSAGE_TAG_LEN
+                    && tags.len() < MAX_MESSAGE_TAGS
+                    && !tags.iter().any(|t| t.as_ref() == tag)
+                {
+                    tags.push(tag.into());
+                }
+            }
+
+            output.extend_from_slice(raw_message.get(offset..header.offset_field as usize)?);
+            offset = header.offset_end as usize;
+        }
+
+        if offset > 0 {
+            output.extend_from_slice(raw_message.get(offset..)?);
+            Some(output)
+        } else {
+            None
+        }
+    }
+
     fn write_received(&self, headers: &mut Vec<u8>, id: u64) {
         headers.extend_from_slice(b"Received: from ");
         headers.extend_from_slice(self.data.helo_domain.as_bytes());
@@ -0,0 +1,91 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::core::Session;
+use common::{KV_DUPLICATE, network::SessionStream};
+use mail_parser::Message;
+use registry::schema::enums::DuplicateAction;
+use store::dispatch::lookup::KeyValue;
+
+pub struct DuplicateCheck {
+    pub keys: Vec<Vec<u8>>,
+    pub duplicates: Vec<usize>,
+}
+
+impl<T: SessionStream> Session<T> {
+    // A message is identified by its Message-ID and a hash of its body, so
+    // resent messages that reuse the Message-ID with new content are not
+    // mistaken for duplicates. Headers are left out as forwarders add their own.
+    pub async fn check_duplicates(
+        &self,
+        message: &Message<'_>,
+        raw_message: &[u8],
+    ) -> Option<DuplicateCheck> {
+        if self.server.core.smtp.session.data.duplicate_action == DuplicateAction::Disabled
+            || self.data.authenticated_as.is_some()
+        {
+            return None;
+        }
+
+        let message_id = message.message_id()?;
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(message_id.as_bytes());
+        hasher.update(&[0]);
+        hasher.update(
+            raw_message
+                .get(message.root_part().raw_body_offset() as usize..)
+                .unwrap_or_default(),
+        );
+        let digest = hasher.finalize();
+
+        let mut keys = Vec::with_capacity(self.data.rcpt_to.len());
+        let mut duplicates = Vec::new();
+        for (idx, rcpt) in self.data.rcpt_to.iter().enumerate() {
+            let mut key = Vec::with_capacity(digest.as_bytes().len() + rcpt.address_lcase.len());
+            key.extend_from_slice(digest.as_bytes());
+            key.extend_from_slice(rcpt.address_lcase.as_bytes());
+            let key = KeyValue::<()>::build_key(KV_DUPLICATE, key);
+
+            match self.server.in_memory_store().key_exists(key.clone()).await {
+                Ok(true) => {
+                    duplicates.push(idx);
+                }
+                Ok(false) => {}
+                Err(err) => {
+                    trc::error!(
+                        err.span_id(self.data.session_id)
+                            .caused_by(trc::location!())
+                            .details("Failed to check for duplicate message.")
+                    );
+                }
+            }
+            keys.push(key);
+        }
+
+        Some(DuplicateCheck { keys, duplicates })
+    }
+
+    // Recorded only once the message is queued, otherwise a sender retrying
+    // after a temporary failure would have its message dropped.
+    pub async fn record_duplicates(&self, keys: Vec<Vec<u8>>) {
+        let expires = self.server.core.smtp.session.data.duplicate_window;
+
+        for key in keys {
+            if let Err(err) = self
+                .server
+                .in_memory_store()
+                .key_set(KeyValue::new(key, vec![]).expires(expires))
+                .await
+            {
+                trc::error!(
+                    err.span_id(self.data.session_id)
+                        .caused_by(trc::location!())
+                        .details("Failed to record delivered message.")
+                );
+            }
+        }
+    }
+}
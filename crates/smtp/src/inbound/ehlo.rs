@@ -218,8 +218,8 @@ impl<T: SessionStream> Session<T> {
                 .server
                 .eval_if::<Mechanism, _>(&ac.mechanisms, self, self.data.session_id)
                 .await
-                .unwrap_or_default()
-                .into();
+                .map_or(0, u64::from)
+                | self.external_auth_mechanism();
             if response.auth_mechanisms != 0 {
                 response.capabilities |= EXT_AUTH;
             }
@@ -10,8 +10,9 @@ use crate::{
 };
 use common::{config::smtp::session::Stage, network::SessionStream, scripts::ScriptModification};
 use mail_auth::{IprevOutput, IprevResult, SpfOutput, SpfResult, spf::verify::SpfParameters};
-use registry::schema::structs::Rate;
+use registry::schema::{enums::AddressRewriteScope, structs::Rate};
 use smtp_proto::{MAIL_BY_NOTIFY, MAIL_BY_RETURN, MAIL_REQUIRETLS, MailFrom, MtPriority};
+use spam_filter::analysis::dnsbl::SpamFilterAnalyzeDnsbl;
 use std::{
     borrow::Cow,
     time::{Duration, Instant, SystemTime},
@@ -148,6 +149,37 @@ impl<T: SessionStream> Session<T> {
                 .await;
         }
 
+        // DNSBL checks on the remote IP and the sender
+        if let Some(threshold) = self.server.core.spam.dnsbl.reject_threshold
+            && self.server.core.spam.enabled
+            && !self.is_authenticated()
+        {
+            let mut message = self.data.dnsbl_error.clone();
+            let mail_from = self.data.mail_from.as_ref().unwrap();
+            if message.is_none()
+                && !mail_from.address_lcase.is_empty()
+                && self
+                    .server
+                    .spam_filter_dnsbl_mail_from(&mail_from.address_lcase, self.data.session_id)
+                    .await
+                    >= threshold
+            {
+                message = b"554 5.7.1 Sender address blocked using DNSBL.\r\n"
+                    .to_vec()
+                    .into();
+            }
+
+            if let Some(message) = message {
+                let mail_from = self.data.mail_from.take().unwrap();
+                trc::event!(
+                    Smtp(SmtpEvent::DnsblRejected),
+                    From = mail_from.address_lcase,
+                    SpanId = self.data.session_id,
+                );
+                return self.write(&message).await;
+            }
+        }
+
         // Sieve filtering
         if let Some((script, script_id)) = self
             .server
@@ -228,6 +260,30 @@ impl<T: SessionStream> Session<T> {
             }
         }
 
+        // Rewrite table
+        let mail_from = self.data.mail_from.as_mut().unwrap();
+        if !mail_from.address_lcase.is_empty()
+            && let Some((rule, new_address)) = self
+                .server
+                .core
+                .smtp
+                .session
+                .rewrite_address(&mail_from.address_lcase, AddressRewriteScope::MailFrom)
+            && new_address.contains('@')
+        {
+            trc::event!(
+                Smtp(SmtpEvent::MailFromRewritten),
+                SpanId = self.data.session_id,
+                Details = mail_from.address_lcase.clone(),
+                From = new_address.clone(),
+                Reason = rule.description.clone(),
+            );
+
+            mail_from.address_lcase = new_address.to_lowercase();
+            mail_from.domain = mail_from.address_lcase.domain_part().into();
+            mail_from.address = new_address;
+        }
+
         // Make sure that the authenticated user is allowed to send from this address
         match self.authenticated_as() {
             Some(authenticated_as)
@@ -377,6 +433,7 @@ impl<T: SessionStream> Session<T> {
                 .write(b"552 5.3.4 Message too big for system.\r\n")
                 .await;
         }
+        self.data.declared_size = from.size;
         if from.hold_for != 0 || from.hold_until != 0 {
             if let Some(max_hold) = self
                 .server
@@ -13,7 +13,9 @@ use mail_auth::{
 };
 
 pub mod auth;
+pub mod callout;
 pub mod data;
+pub mod duplicate;
 pub mod ehlo;
 pub mod hooks;
 pub mod mail;
@@ -14,6 +14,10 @@ use common::{
     network::{RcptResolution, SessionStream},
     scripts::ScriptModification,
 };
+use registry::{
+    schema::enums::{AddressRewriteScope, SuppressionReason},
+    types::EnumImpl,
+};
 use smtp_proto::{
     RCPT_NOTIFY_DELAY, RCPT_NOTIFY_FAILURE, RCPT_NOTIFY_NEVER, RCPT_NOTIFY_SUCCESS, RcptTo,
 };
@@ -53,7 +57,16 @@ impl<T: SessionStream> Session<T> {
                 SpanId = self.data.session_id,
                 Limit = self.params.rcpt_max,
             );
-            return self.write(b"455 4.5.3 Too many recipients.\r\n").await;
+            return self.write(b"452 4.5.3 Too many recipients.\r\n").await;
+        } else if self.data.rcpt_total >= self.params.rcpt_session_max {
+            trc::event!(
+                Smtp(SmtpEvent::TooManyRecipients),
+                SpanId = self.data.session_id,
+                Limit = self.params.rcpt_session_max,
+            );
+            return self
+                .write(b"452 4.5.3 Too many recipients for this session.\r\n")
+                .await;
         }
 
         // Verify parameters
@@ -99,7 +112,7 @@ impl<T: SessionStream> Session<T> {
             .and_then(|name| {
                 self.server
                     .get_trusted_sieve_script(&name, self.data.session_id)
-                    .map(|s| (s.clone(), name))
+                    .map(|s| (s, name))
             });
 
         let session_config = &self.server.core.smtp.session;
@@ -173,6 +186,29 @@ impl<T: SessionStream> Session<T> {
                 }
             }
 
+            // Rewrite table
+            let rcpt = self.data.rcpt_to.last_mut().unwrap();
+            if let Some((rule, new_address)) = self
+                .server
+                .core
+                .smtp
+                .session
+                .rewrite_address(&rcpt.address_lcase, AddressRewriteScope::RcptTo)
+                && new_address.contains('@')
+            {
+                trc::event!(
+                    Smtp(SmtpEvent::RcptToRewritten),
+                    SpanId = self.data.session_id,
+                    Details = rcpt.address_lcase.clone(),
+                    To = new_address.clone(),
+                    Reason = rule.description.clone(),
+                );
+
+                rcpt.address_lcase = new_address.to_lowercase();
+                rcpt.domain = rcpt.address_lcase.domain_part().into();
+                rcpt.address = new_address;
+            }
+
             // Check for duplicates
             let rcpt = self.data.rcpt_to.last().unwrap();
             if self.data.rcpt_to.iter().filter(|r| r == &rcpt).count() > 1 {
@@ -187,8 +223,34 @@ impl<T: SessionStream> Session<T> {
             }
         }
 
-        // Verify address
+        // Spam traps are accepted but never delivered
         let rcpt = self.data.rcpt_to.last().unwrap();
+        if self.data.authenticated_as.is_none() {
+            match self.server.is_spam_trap(&rcpt.address_lcase).await {
+                Ok(true) => {
+                    trc::event!(
+                        Smtp(SmtpEvent::RcptTo),
+                        SpanId = self.data.session_id,
+                        To = rcpt.address_lcase.clone(),
+                    );
+
+                    self.data.spam_traps.push(rcpt.address_lcase.clone());
+                    self.data.rcpt_oks += 1;
+                    self.data.rcpt_total += 1;
+                    return self.write(b"250 2.1.5 OK\r\n").await;
+                }
+                Ok(false) => {}
+                Err(err) => {
+                    trc::error!(
+                        err.span_id(self.data.session_id)
+                            .caused_by(trc::location!())
+                            .details("Failed to check spam trap.")
+                    );
+                }
+            }
+        }
+
+        // Verify address
         let mut rcpt_members = None;
 
         match self
@@ -196,7 +258,11 @@ impl<T: SessionStream> Session<T> {
             .rcpt_resolve(&rcpt.address_lcase, self.data.session_id)
             .await
         {
-            Ok(RcptResolution::Accept) => {}
+            Ok(RcptResolution::Accept) => {
+                if self.data.declared_size > 0 && !self.verify_rcpt_quota().await {
+                    return self.write(b"552 5.2.2 Mailbox full.\r\n").await;
+                }
+            }
             Ok(RcptResolution::Rewrite(address)) => {
                 let orig_addr = self.data.rcpt_to.pop().unwrap();
                 let mut new_addr = SessionAddress::new(address);
@@ -205,6 +271,10 @@ impl<T: SessionStream> Session<T> {
                     new_addr.dsn_info = format!("rfc822;{}", orig_addr.address_lcase).into();
                     new_addr.flags = orig_addr.flags;
                     self.data.rcpt_to.push(new_addr);
+
+                    if self.data.declared_size > 0 && !self.verify_rcpt_quota().await {
+                        return self.write(b"552 5.2.2 Mailbox full.\r\n").await;
+                    }
                 } else {
                     trc::event!(
                         Smtp(SmtpEvent::RcptToDuplicate),
@@ -215,8 +285,36 @@ impl<T: SessionStream> Session<T> {
                     return self.write(b"250 2.1.5 OK\r\n").await;
                 }
             }
-            Ok(RcptResolution::Expand(members)) => {
-                rcpt_members = Some(members);
+            Ok(RcptResolution::Expand(members, unsubscribe_list)) => {
+                rcpt_members = Some((members, unsubscribe_list));
+            }
+            Ok(RcptResolution::Verify(relay_verify)) => {
+                match self
+                    .verify_relay_rcpt(&rcpt.address_lcase, &relay_verify)
+                    .await
+                {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        trc::event!(
+                            Smtp(SmtpEvent::MailboxDoesNotExist),
+                            SpanId = self.data.session_id,
+                            To = rcpt.address_lcase.clone(),
+                        );
+
+                        let rcpt_to = self.data.rcpt_to.pop().unwrap().address_lcase;
+                        return self
+                            .rcpt_error(b"550 5.1.1 Mailbox does not exist.\r\n", rcpt_to)
+                            .await;
+                    }
+                    Err(err) => {
+                        trc::error!(err.span_id(self.data.session_id));
+
+                        self.data.rcpt_to.pop();
+                        return self
+                            .write(b"451 4.4.3 Unable to verify address at this time.\r\n")
+                            .await;
+                    }
+                }
             }
             Ok(RcptResolution::UnknownRecipient) => {
                 trc::event!(
@@ -248,6 +346,45 @@ impl<T: SessionStream> Session<T> {
                         .rcpt_error(b"550 5.1.2 Relay not allowed.\r\n", rcpt_to)
                         .await;
                 }
+
+                // Suppression lists only apply to authenticated submissions
+                if let Some(account) = &self.data.authenticated_as {
+                    match self
+                        .server
+                        .suppressed_address(&rcpt.address_lcase, account.account.tenant_id())
+                        .await
+                    {
+                        Ok(Some(entry)) => {
+                            let reason = match entry.reason {
+                                SuppressionReason::HardBounce => "a previous permanent failure",
+                                SuppressionReason::Unsubscribe => "an unsubscribe request",
+                                SuppressionReason::Complaint => "a complaint",
+                                SuppressionReason::Manual => "a manual entry",
+                            };
+
+                            trc::event!(
+                                Smtp(SmtpEvent::RcptToSuppressed),
+                                SpanId = self.data.session_id,
+                                To = rcpt.address_lcase.clone(),
+                                Reason = entry.reason.as_str(),
+                            );
+
+                            self.data.rcpt_to.pop();
+                            let response = format!(
+                                "550 5.7.1 Recipient is on the suppression list due to {reason}.\r\n"
+                            );
+                            return self.write(response.as_bytes()).await;
+                        }
+                        Ok(None) => {}
+                        Err(err) => {
+                            trc::error!(
+                                err.span_id(self.data.session_id)
+                                    .caused_by(trc::location!())
+                                    .details("Failed to check suppression list.")
+                            );
+                        }
+                    }
+                }
             }
             Err(err) => {
                 trc::error!(
@@ -351,7 +488,7 @@ impl<T: SessionStream> Session<T> {
         }
 
         // Expand list
-        if let Some(members) = rcpt_members {
+        if let Some((members, unsubscribe_list)) = rcpt_members {
             let list_addr = self.data.rcpt_to.pop().unwrap();
             let orcpt = format!("rfc822;{}", list_addr.address_lcase);
             for member in members.as_ref() {
@@ -359,6 +496,11 @@ impl<T: SessionStream> Session<T> {
                 if !self.data.rcpt_to.contains(&member_addr)
                     && member_addr.address_lcase != list_addr.address_lcase
                 {
+                    if let Some(list_id) = unsubscribe_list {
+                        self.data
+                            .list_rcpts
+                            .insert(member_addr.address_lcase.clone(), list_id);
+                    }
                     member_addr.dsn_info = orcpt.clone().into();
                     member_addr.flags = list_addr.flags;
                     self.data.rcpt_to.push(member_addr);
@@ -367,9 +509,69 @@ impl<T: SessionStream> Session<T> {
         }
 
         self.data.rcpt_oks += 1;
+        self.data.rcpt_total += 1;
         self.write(b"250 2.1.5 OK\r\n").await
     }
 
+    async fn verify_rcpt_quota(&mut self) -> bool {
+        if !self
+            .server
+            .eval_if(
+                &self.server.core.smtp.session.rcpt.enforce_quota,
+                self,
+                self.data.session_id,
+            )
+            .await
+            .unwrap_or(true)
+        {
+            return true;
+        }
+
+        let rcpt = self.data.rcpt_to.last().unwrap();
+        let result = match self
+            .server
+            .account_id_from_email(&rcpt.address_lcase, true)
+            .await
+        {
+            Ok(Some(account_id)) => match self.server.try_account(account_id).await {
+                Ok(Some(account)) => {
+                    self.server
+                        .has_available_quota(&account, self.data.declared_size as u64)
+                        .await
+                }
+                Ok(None) => Ok(()),
+                Err(err) => Err(err),
+            },
+            Ok(None) => Ok(()),
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Ok(()) => true,
+            Err(err)
+                if err.matches(trc::EventType::Limit(trc::LimitEvent::Quota))
+                    || err.matches(trc::EventType::Limit(trc::LimitEvent::TenantQuota)) =>
+            {
+                let rcpt = self.data.rcpt_to.pop().unwrap();
+                trc::event!(
+                    Smtp(SmtpEvent::MailboxFull),
+                    SpanId = self.data.session_id,
+                    To = rcpt.address_lcase,
+                    Size = self.data.declared_size,
+                );
+                false
+            }
+            Err(err) => {
+                trc::error!(
+                    err.span_id(self.data.session_id)
+                        .caused_by(trc::location!())
+                        .details("Failed to verify quota.")
+                );
+                true
+            }
+        }
+    }
+
     async fn rcpt_error(&mut self, response: &[u8], rcpt: String) -> Result<(), ()> {
         tokio::time::sleep(self.params.rcpt_errors_wait).await;
         self.data.rcpt_errors += 1;
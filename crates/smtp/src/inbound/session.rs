@@ -35,7 +35,7 @@ impl<T: SessionStream> Session<T> {
             match &mut state {
                 State::Request(receiver) => loop {
                     match receiver.ingest(&mut iter) {
-                        Ok(request) => match request {
+                        Ok(request) => match self.track_request(request) {
                             Request::Rcpt { to } => {
                                 self.handle_rcpt_to(to).await?;
                             }
@@ -94,8 +94,8 @@ impl<T: SessionStream> Session<T> {
                                         self.data.session_id,
                                     )
                                     .await
-                                    .unwrap_or_default()
-                                    .into();
+                                    .map_or(0, u64::from)
+                                    | self.external_auth_mechanism();
                                 if auth == 0 {
                                     trc::event!(
                                         Smtp(SmtpEvent::AuthNotAllowed),
@@ -463,6 +463,33 @@ impl<T: SessionStream> Session<T> {
 
         Ok(true)
     }
+
+    fn track_request(&self, request: Request<String>) -> Request<String> {
+        self.instance.sessions.set_command(
+            self.data.session_id,
+            match &request {
+                Request::Ehlo { .. } => "EHLO",
+                Request::Lhlo { .. } => "LHLO",
+                Request::Helo { .. } => "HELO",
+                Request::Mail { .. } => "MAIL",
+                Request::Rcpt { .. } => "RCPT",
+                Request::Bdat { .. } => "BDAT",
+                Request::Auth { .. } => "AUTH",
+                Request::Noop { .. } => "NOOP",
+                Request::Vrfy { .. } => "VRFY",
+                Request::Expn { .. } => "EXPN",
+                Request::Help { .. } => "HELP",
+                Request::Etrn { .. } => "ETRN",
+                Request::Atrn { .. } => "ATRN",
+                Request::Burl { .. } => "BURL",
+                Request::StartTls => "STARTTLS",
+                Request::Data => "DATA",
+                Request::Rset => "RSET",
+                Request::Quit => "QUIT",
+            },
+        );
+        request
+    }
 }
 
 impl<T: AsyncWrite + AsyncRead + Unpin> Session<T> {
@@ -470,11 +497,14 @@ impl<T: AsyncWrite + AsyncRead + Unpin> Session<T> {
         self.data.mail_from = None;
         self.data.spf_mail_from = None;
         self.data.rcpt_to.clear();
+        self.data.list_rcpts.clear();
+        self.data.spam_traps.clear();
         self.data.message = Vec::with_capacity(0);
         self.data.priority = 0;
         self.data.delivery_by = 0;
         self.data.future_release = 0;
         self.data.rcpt_oks = 0;
+        self.data.declared_size = 0;
     }
 
     #[inline(always)]
@@ -587,6 +617,18 @@ impl<T: SessionStream> ResolveVariable for Session<T> {
             ExpressionVariable::LocalIp => self.data.local_ip_str.as_str().into(),
             ExpressionVariable::LocalPort => self.data.local_port.into(),
             ExpressionVariable::IsTls => self.stream.is_tls().into(),
+            ExpressionVariable::TlsJa3 => self
+                .stream
+                .tls_fingerprint()
+                .map(|fp| fp.ja3.as_str())
+                .unwrap_or_default()
+                .into(),
+            ExpressionVariable::TlsJa4 => self
+                .stream
+                .tls_fingerprint()
+                .map(|fp| fp.ja4.as_str())
+                .unwrap_or_default()
+                .into(),
             ExpressionVariable::Priority => self.data.priority.to_compact_string().into(),
             ExpressionVariable::Protocol => self.instance.protocol.as_str().into(),
             ExpressionVariable::Asn => self
@@ -67,6 +67,7 @@ impl<T: SessionStream> Session<T> {
             asn: self.data.asn_geo_data.asn.as_ref().map(|a| a.id),
             country: self.data.asn_geo_data.country.as_ref().map(|c| c.as_str()),
             is_tls: self.stream.is_tls(),
+            tls_fingerprint: self.stream.tls_fingerprint(),
             env_from: self
                 .data
                 .mail_from
@@ -85,6 +86,12 @@ impl<T: SessionStream> Session<T> {
                 .iter()
                 .map(|r| r.address_lcase.as_str())
                 .collect(),
+            env_rcpt_to_detail: self
+                .data
+                .rcpt_to
+                .iter()
+                .filter_map(|r| r.subaddress())
+                .collect(),
             is_test: false,
             is_train: false,
         }
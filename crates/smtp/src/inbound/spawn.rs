@@ -11,8 +11,9 @@ use crate::{
 use common::{
     BuildServer,
     config::smtp::session::Stage,
-    network::{self, SessionManager, SessionStream},
+    network::{self, SessionManager, SessionStream, fingerprint::FingerprintStream},
 };
+use spam_filter::analysis::dnsbl::SpamFilterAnalyzeDnsbl;
 use std::time::Instant;
 use tokio_rustls::server::TlsStream;
 use trc::{SecurityEvent, SmtpEvent};
@@ -109,6 +110,24 @@ impl<T: SessionStream> Session<T> {
             return false;
         }
 
+        // DNSBL checks on the remote IP, the rejection is deferred until MAIL FROM
+        // so that authenticated clients can still submit messages
+        if let Some(threshold) = self.server.core.spam.dnsbl.reject_threshold
+            && self.server.core.spam.enabled
+            && self
+                .server
+                .spam_filter_dnsbl_connect(self.data.remote_ip, self.data.session_id)
+                .await
+                >= threshold
+        {
+            self.data.dnsbl_error = format!(
+                "554 5.7.1 Service unavailable; client host [{}] blocked using DNSBL.\r\n",
+                self.data.remote_ip
+            )
+            .into_bytes()
+            .into();
+        }
+
         // Obtain hostname
         self.hostname = self
             .server
@@ -250,12 +269,17 @@ impl<T: SessionStream> Session<T> {
         false
     }
 
-    pub async fn into_tls(self) -> Result<Session<TlsStream<T>>, ()> {
+    pub async fn into_tls(self) -> Result<Session<TlsStream<FingerprintStream<T>>>, ()> {
         Ok(Session {
             hostname: self.hostname,
             stream: self
                 .instance
-                .tls_accept(self.stream, self.data.session_id)
+                .tls_accept(
+                    self.stream,
+                    self.data.session_id,
+                    &self.server,
+                    self.data.remote_ip,
+                )
                 .await?,
             state: self.state,
             data: self.data,
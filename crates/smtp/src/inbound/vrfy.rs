@@ -17,7 +17,9 @@ impl<T: SessionStream> Session<T> {
                 .rcpt_resolve(&address.to_lowercase(), self.data.session_id)
                 .await
             {
-                Ok(RcptResolution::Accept | RcptResolution::Rewrite(_)) => {
+                Ok(
+                    RcptResolution::Accept | RcptResolution::Rewrite(_) | RcptResolution::Verify(_),
+                ) => {
                     trc::event!(
                         Smtp(SmtpEvent::Vrfy),
                         SpanId = self.data.session_id,
@@ -30,7 +32,7 @@ impl<T: SessionStream> Session<T> {
                 Ok(
                     RcptResolution::UnknownRecipient
                     | RcptResolution::UnknownDomain
-                    | RcptResolution::Expand(_),
+                    | RcptResolution::Expand(..),
                 ) => {
                     trc::event!(
                         Smtp(SmtpEvent::VrfyNotFound),
@@ -69,7 +71,7 @@ impl<T: SessionStream> Session<T> {
                 .rcpt_resolve(&address.to_lowercase(), self.data.session_id)
                 .await
             {
-                Ok(RcptResolution::Expand(addresses)) => {
+                Ok(RcptResolution::Expand(addresses, _)) => {
                     let mut result = String::with_capacity(32);
                     for (pos, value) in addresses.iter().enumerate() {
                         let _ = write!(
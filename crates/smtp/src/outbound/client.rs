@@ -176,7 +176,7 @@ impl<T: AsyncRead + AsyncWrite + Unpin> SmtpClient<T> {
     ) -> Result<(), Status<HostResponse<Box<str>>, ErrorDetails>> {
         match params
             .server
-            .blob_store()
+            .queue_blob_store()
             .get_blob(message.message.blob_hash.as_slice(), 0..usize::MAX)
             .await
         {
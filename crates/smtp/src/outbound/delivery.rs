@@ -28,19 +28,23 @@ use common::Server;
 use common::config::smtp::queue::RoutingStrategy;
 use common::config::{server::ServerProtocol, smtp::report::AggregateFrequency};
 use common::ipc::{PolicyType, QueueEvent, QueueEventStatus, TlsEvent};
+use common::manager::cluster::NodeDelivery;
+use common::telemetry::metrics::latency::LatencyMetric;
 use compact_str::ToCompactString;
 use mail_auth::{
     mta_sts::TlsRpt,
     report::tlsrpt::{FailureDetails, ResultType},
 };
+use registry::types::datetime::UTCDateTime;
 use smtp_proto::MAIL_REQUIRETLS;
 use std::sync::Arc;
 use std::{
     net::{IpAddr, Ipv4Addr, SocketAddr},
-    time::Instant,
+    time::{Duration, Instant},
 };
 use store::write::{BatchBuilder, QueueClass, ValueClass, now};
 use trc::{DaneEvent, DeliveryEvent, MtaStsEvent, ServerEvent, TlsRptEvent};
+use types::id::Id;
 
 impl QueuedMessage {
     pub fn try_deliver(self, server: Server) {
@@ -53,6 +57,17 @@ impl QueuedMessage {
                     message.span_id = server.inner.data.span_id_gen.generate();
                     let span_id = message.span_id;
 
+                    let recipients = message
+                        .message
+                        .recipients
+                        .iter()
+                        .filter(|r| {
+                            matches!(r.status, Status::Scheduled | Status::TemporaryFailure(_))
+                                && r.queue == message.queue_name
+                        })
+                        .map(|r| r.address().to_string())
+                        .collect::<Vec<_>>();
+
                     trc::event!(
                         Delivery(DeliveryEvent::AttemptStart),
                         SpanId = message.span_id,
@@ -63,29 +78,31 @@ impl QueuedMessage {
                         } else {
                             trc::Value::String("<>".into())
                         },
-                        To = message
-                            .message
-                            .recipients
+                        To = recipients
                             .iter()
-                            .filter_map(|r| {
-                                if matches!(
-                                    r.status,
-                                    Status::Scheduled | Status::TemporaryFailure(_)
-                                ) && r.queue == message.queue_name
-                                {
-                                    Some(trc::Value::String(r.address().into()))
-                                } else {
-                                    None
-                                }
-                            })
+                            .map(|r| trc::Value::String(r.as_str().into()))
                             .collect::<Vec<_>>(),
                         Size = message.message.size,
                         Total = message.message.recipients.len(),
                     );
 
+                    // Register the attempt so it can be listed by the cluster queue API
+                    server.inner.data.active_deliveries.lock().insert(
+                        span_id,
+                        NodeDelivery {
+                            queue_id: Id::from(message.queue_id),
+                            queue_name: message.queue_name.to_string(),
+                            from: message.message.return_path.to_string(),
+                            recipients,
+                            size: message.message.size,
+                            started: UTCDateTime::now(),
+                        },
+                    );
+
                     // Attempt delivery
                     let start_time = Instant::now();
                     let queue_event = self.deliver_task(server.clone(), message).await;
+                    server.inner.data.active_deliveries.lock().remove(&span_id);
 
                     trc::event!(
                         Delivery(DeliveryEvent::AttemptEnd),
@@ -108,7 +125,7 @@ impl QueuedMessage {
                         },
                     )));
 
-                    if let Err(err) = server.store().write(batch.build_all()).await {
+                    if let Err(err) = server.queue_store().write(batch.build_all()).await {
                         trc::error!(
                             err.details("Failed to delete queue event.")
                                 .caused_by(trc::location!())
@@ -272,6 +289,18 @@ impl QueuedMessage {
                 }
             }
 
+            // Record queueing latency for recipients never attempted before
+            if rcpt_idxs
+                .iter()
+                .any(|idx| message.message.recipients[*idx].retry.inner == 0)
+            {
+                server.record_latency(
+                    LatencyMetric::QueuedToFirstAttempt,
+                    domain,
+                    Duration::from_secs(now_.saturating_sub(message.message.created)),
+                );
+            }
+
             // Obtain next hop
             let (mut remote_hosts, mx_config, is_smtp) = match route {
                 RoutingStrategy::Local => {
@@ -1327,8 +1356,9 @@ impl MessageWrapper {
         let now = now();
         let mut has_pending_delivery = false;
         let mut matches_queue = false;
+        let mut expired = Vec::new();
 
-        for rcpt in self.message.recipients.iter_mut() {
+        for (rcpt_idx, rcpt) in self.message.recipients.iter_mut().enumerate() {
             match &rcpt.status {
                 Status::TemporaryFailure(err) if rcpt.is_expired(self.message.created, now) => {
                     trc::event!(
@@ -1348,6 +1378,7 @@ impl MessageWrapper {
 
                     rcpt.status =
                         std::mem::replace(&mut rcpt.status, Status::Scheduled).into_permanent();
+                    expired.push(rcpt_idx);
                 }
                 Status::Scheduled if rcpt.is_expired(self.message.created, now) => {
                     trc::event!(
@@ -1371,6 +1402,7 @@ impl MessageWrapper {
                             "Message expired without any delivery attempts made.".into(),
                         ),
                     });
+                    expired.push(rcpt_idx);
                 }
                 Status::Completed(_) | Status::PermanentFailure(_) => (),
                 _ => {
@@ -1380,6 +1412,10 @@ impl MessageWrapper {
             }
         }
 
+        for rcpt_idx in expired {
+            self.log_rcpt_status(rcpt_idx);
+        }
+
         if has_pending_delivery {
             PendingDelivery::Yes(matches_queue)
         } else {
@@ -1394,6 +1430,13 @@ impl MessageWrapper {
         server: &Server,
     ) {
         let needs_retry = matches!(&status, Status::TemporaryFailure(_) | Status::Scheduled);
+        if matches!(&status, Status::Completed(_)) {
+            server.record_latency(
+                LatencyMetric::QueuedToDelivered,
+                self.message.recipients[rcpt_idx].domain_part(),
+                Duration::from_secs(now().saturating_sub(self.message.created)),
+            );
+        }
         self.message.recipients[rcpt_idx].status = status;
 
         if needs_retry {
@@ -1412,6 +1455,8 @@ impl MessageWrapper {
             rcpt.expires = queue.expiry;
             rcpt.queue = queue.virtual_queue;
         }
+
+        self.log_rcpt_status(rcpt_idx);
     }
 
     pub fn set_rcpt_rate_limit(&mut self, rcpt_idx: usize, retry_at: u64) {
@@ -1421,5 +1466,64 @@ impl MessageWrapper {
             entity: "localhost".into(),
             details: Error::RateLimited,
         });
+
+        self.log_rcpt_status(rcpt_idx);
+    }
+
+    fn log_rcpt_status(&self, rcpt_idx: usize) {
+        let rcpt = &self.message.recipients[rcpt_idx];
+        let env_id = self
+            .message
+            .env_id
+            .as_ref()
+            .map(|id| trc::Value::String(id.as_ref().into()));
+
+        match &rcpt.status {
+            Status::Completed(response) => {
+                trc::event!(
+                    Queue(trc::QueueEvent::MessageDelivered),
+                    SpanId = self.span_id,
+                    QueueId = self.queue_id,
+                    EnvelopeId = env_id,
+                    Tags = self.message.trace_tags(),
+                    From = self.message.return_path.to_string(),
+                    To = rcpt.address().to_string(),
+                    Hostname = response.hostname.to_string(),
+                    Code = response.response.code,
+                    Details = response.response.message.to_string(),
+                );
+            }
+            Status::TemporaryFailure(err) => {
+                trc::event!(
+                    Queue(trc::QueueEvent::MessageDeferred),
+                    SpanId = self.span_id,
+                    QueueId = self.queue_id,
+                    EnvelopeId = env_id,
+                    Tags = self.message.trace_tags(),
+                    From = self.message.return_path.to_string(),
+                    To = rcpt.address().to_string(),
+                    Hostname = err.entity.to_string(),
+                    Reason = from_error_details(&err.details),
+                    NextRetry = trc::Value::Timestamp(rcpt.retry.due),
+                    Expires = rcpt
+                        .expiration_time(self.message.created)
+                        .map(trc::Value::Timestamp),
+                );
+            }
+            Status::PermanentFailure(err) => {
+                trc::event!(
+                    Queue(trc::QueueEvent::MessageBounced),
+                    SpanId = self.span_id,
+                    QueueId = self.queue_id,
+                    EnvelopeId = env_id,
+                    Tags = self.message.trace_tags(),
+                    From = self.message.return_path.to_string(),
+                    To = rcpt.address().to_string(),
+                    Hostname = err.entity.to_string(),
+                    Reason = from_error_details(&err.details),
+                );
+            }
+            Status::Scheduled => {}
+        }
     }
 }
@@ -34,6 +34,7 @@ impl MessageWrapper {
             recipients.push(IngestRecipient {
                 address: rcpt_addr.to_lowercase(),
                 is_spam: rcpt.flags & RCPT_SPAM_PAYLOAD != 0,
+                subaddress: rcpt.subaddress().map(str::to_string),
             });
             pending_recipients.push((rcpt_idx, rcpt_addr));
         }
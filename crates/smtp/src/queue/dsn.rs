@@ -6,37 +6,59 @@
 
 use super::spool::SmtpSpool;
 use super::{
-    Error, ErrorDetails, HostResponse, Message, MessageSource, QueueEnvelope, RCPT_DSN_SENT,
-    Recipient, Status,
+    Error, ErrorDetails, FROM_AUTHENTICATED, FROM_BULK_TRAFFIC, HostResponse, Message,
+    MessageSource, QueueEnvelope, RCPT_DSN_SENT, Recipient, Status,
 };
 use crate::queue::{MessageWrapper, UnexpectedResponse};
 use crate::reporting::send::MtaReportSend;
-use common::Server;
+use common::{KV_RATE_LIMIT_DSN, Server, config::smtp::queue::DsnTemplateVariable};
 use mail_builder::MessageBuilder;
 use mail_builder::headers::HeaderType;
 use mail_builder::headers::content_type::ContentType;
 use mail_builder::mime::{BodyPart, MimePart, make_boundary};
 use mail_parser::DateTime;
+use registry::schema::enums::SuppressionReason;
 use smtp_proto::{
     RCPT_NOTIFY_DELAY, RCPT_NOTIFY_FAILURE, RCPT_NOTIFY_NEVER, RCPT_NOTIFY_SUCCESS, Response,
 };
 use std::fmt::Write;
 use std::future::Future;
 use store::write::now;
+use utils::{DomainPart, template::Variables};
 
 pub trait SendDsn: Sync + Send {
     fn send_dsn(&self, message: &mut MessageWrapper) -> impl Future<Output = ()> + Send;
     fn log_dsn(&self, message: &MessageWrapper) -> impl Future<Output = ()> + Send;
+    fn suppress_bounced_recipients(
+        &self,
+        message: &MessageWrapper,
+    ) -> impl Future<Output = ()> + Send;
+    fn is_dsn_suppressed(
+        &self,
+        message: &MessageWrapper,
+    ) -> impl Future<Output = Option<&'static str>> + Send;
 }
 
 impl SendDsn for Server {
     async fn send_dsn(&self, message: &mut MessageWrapper) {
         // Send DSN events
         self.log_dsn(message).await;
+        self.suppress_bounced_recipients(message).await;
 
         if !message.message.return_path.is_empty() {
             // Build DSN
             if let Some(dsn) = message.build_dsn(self).await {
+                // Avoid backscatter to forged or flooded return paths
+                if let Some(reason) = self.is_dsn_suppressed(message).await {
+                    trc::event!(
+                        Delivery(trc::DeliveryEvent::DsnSuppressed),
+                        SpanId = message.span_id,
+                        From = message.message.return_path.to_string(),
+                        Reason = reason,
+                    );
+                    return;
+                }
+
                 let mut dsn_message = self.new_message("", message.span_id);
                 dsn_message
                     .add_recipient(message.message.return_path.as_ref(), self)
@@ -64,6 +86,112 @@ impl SendDsn for Server {
         }
     }
 
+    async fn is_dsn_suppressed(&self, message: &MessageWrapper) -> Option<&'static str> {
+        let config = &self.core.smtp.queue.dsn;
+
+        if self
+            .eval_if(&config.suppress, &message.message, message.span_id)
+            .await
+            .unwrap_or(false)
+        {
+            return Some("policy");
+        }
+
+        // Avoid flooding mailing lists and bulk senders with bounces, which
+        // would otherwise receive one for every failed recipient
+        if config.suppress_list_traffic && message.has_flag(FROM_BULK_TRAFFIC) {
+            return Some("list-traffic");
+        }
+
+        if let Some(rate) = &config.rate {
+            match self
+                .is_rate_allowed(
+                    KV_RATE_LIMIT_DSN,
+                    message.message.return_path.domain_part().as_bytes(),
+                    rate,
+                )
+                .await
+            {
+                Ok(Some(_)) => return Some("rate-limit"),
+                Ok(None) => {}
+                Err(err) => {
+                    trc::error!(err.span_id(message.span_id).caused_by(trc::location!()));
+                }
+            }
+        }
+
+        None
+    }
+
+    // Recipients that a remote server reported as non-existent are added to
+    // the sender's suppression list, so that later submissions fail early
+    // instead of bouncing again.
+    async fn suppress_bounced_recipients(&self, message: &MessageWrapper) {
+        if !message.has_flag(FROM_AUTHENTICATED) {
+            return;
+        }
+
+        let mut tenant_id = None;
+        let mut tenant_resolved = false;
+        for rcpt in &message.message.recipients {
+            if rcpt.has_flag(RCPT_DSN_SENT) {
+                continue;
+            }
+            let Status::PermanentFailure(ErrorDetails {
+                details: Error::UnexpectedResponse(UnexpectedResponse { command, response }),
+                ..
+            }) = &rcpt.status
+            else {
+                continue;
+            };
+            if !command
+                .get(..4)
+                .is_some_and(|cmd| cmd.eq_ignore_ascii_case("RCPT"))
+                || !matches!(response.esc, [5, 1, 1 | 6 | 10])
+            {
+                continue;
+            }
+
+            if !tenant_resolved {
+                tenant_resolved = true;
+                tenant_id = match self.domain(message.message.return_path.domain_part()).await {
+                    Ok(domain) => domain.and_then(|domain| domain.id_tenant),
+                    Err(err) => {
+                        trc::error!(
+                            err.span_id(message.span_id)
+                                .caused_by(trc::location!())
+                                .details("Failed to lookup domain")
+                        );
+                        return;
+                    }
+                };
+            }
+
+            if let Err(err) = self
+                .suppress_address(
+                    &rcpt.address,
+                    tenant_id,
+                    SuppressionReason::HardBounce,
+                    Some(format!(
+                        "{} {}.{}.{} {}",
+                        response.code,
+                        response.esc[0],
+                        response.esc[1],
+                        response.esc[2],
+                        response.message
+                    )),
+                )
+                .await
+            {
+                trc::error!(
+                    err.span_id(message.span_id)
+                        .caused_by(trc::location!())
+                        .details("Failed to update suppression list")
+                );
+            }
+        }
+    }
+
     async fn log_dsn(&self, message: &MessageWrapper) {
         let now = now();
 
@@ -137,6 +265,7 @@ impl MessageWrapper {
         let mut txt_delay = String::new();
         let mut txt_failed = String::new();
         let mut dsn = String::new();
+        let mut delayed = Vec::new();
 
         for rcpt in &mut self.message.recipients {
             if rcpt.has_flag(RCPT_DSN_SENT | RCPT_NOTIFY_NEVER) {
@@ -159,6 +288,13 @@ impl MessageWrapper {
                     rcpt.status.write_dsn(&mut dsn);
                     rcpt.write_dsn_will_retry_until(self.message.created, &mut dsn);
                     response.write_dsn_text(&rcpt.address, &mut txt_delay);
+                    if config.dsn.delay_template.is_some() {
+                        delayed.push(rcpt.dsn_template_variables(
+                            self.message.created,
+                            response.entity.as_ref(),
+                            &response.details,
+                        ));
+                    }
                 }
                 Status::PermanentFailure(response) => {
                     rcpt.flags |= RCPT_DSN_SENT;
@@ -179,6 +315,13 @@ impl MessageWrapper {
                         details: Error::ConcurrencyLimited,
                     }
                     .write_dsn_text(&rcpt.address, &mut txt_delay);
+                    if config.dsn.delay_template.is_some() {
+                        delayed.push(rcpt.dsn_template_variables(
+                            self.message.created,
+                            "localhost",
+                            &Error::ConcurrencyLimited,
+                        ));
+                    }
                 }
                 _ => continue,
             }
@@ -250,6 +393,27 @@ impl MessageWrapper {
             txt.push_str("\r\n");
         }
 
+        // Delay warnings may use their own subject and template
+        let subject = if has_delay && !has_success && !has_failure {
+            if let Some(template) = &config.dsn.delay_template {
+                let mut variables = Variables::new();
+                variables.insert_single(
+                    DsnTemplateVariable::Sender,
+                    self.message.return_path.to_string(),
+                );
+                variables.insert_single(DsnTemplateVariable::QueueId, self.queue_id.to_string());
+                variables.insert_block(DsnTemplateVariable::Recipients, delayed);
+                txt = template.eval(&variables);
+            }
+
+            server
+                .eval_if(&config.dsn.delay_subject, &self.message, self.span_id)
+                .await
+                .unwrap_or_else(|| subject.to_string())
+        } else {
+            subject.to_string()
+        };
+
         // Update next delay notification time
         if has_delay {
             let mut changes = Vec::new();
@@ -314,7 +478,7 @@ impl MessageWrapper {
 
         // Fetch up to MAX_HEADER_SIZE bytes of message headers
         let headers = match server
-            .blob_store()
+            .queue_blob_store()
             .get_blob(self.message.blob_hash.as_slice(), 0..MAX_HEADER_SIZE)
             .await
         {
@@ -530,6 +694,25 @@ impl Recipient {
         let _ = write!(dsn, "Final-Recipient: rfc822;{}\r\n", self.address);
     }
 
+    fn dsn_template_variables(
+        &self,
+        created: u64,
+        host: &str,
+        reason: &Error,
+    ) -> Vec<(DsnTemplateVariable, String)> {
+        vec![
+            (DsnTemplateVariable::Address, self.address.to_string()),
+            (DsnTemplateVariable::Host, host.to_string()),
+            (DsnTemplateVariable::Reason, reason.to_string()),
+            (
+                DsnTemplateVariable::Expires,
+                self.expiration_time(created)
+                    .map(|expires| DateTime::from_timestamp(expires as i64).to_rfc822())
+                    .unwrap_or_default(),
+            ),
+        ]
+    }
+
     fn write_dsn_will_retry_until(&self, created: u64, dsn: &mut String) {
         if let Some(expires) = self.expiration_time(created)
             && expires > now()
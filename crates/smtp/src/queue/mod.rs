@@ -70,6 +70,7 @@ pub struct Message {
 
     pub size: u64,
     pub quota_keys: Box<[QuotaKey]>,
+    pub tags: Box<[Box<str>]>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -125,6 +126,7 @@ pub const FROM_UNAUTHENTICATED_DMARC: u64 = 1 << 34;
 pub const FROM_DSN: u64 = 1 << 35;
 pub const FROM_REPORT: u64 = 1 << 36;
 pub const FROM_AUTOGENERATED: u64 = 1 << 37;
+pub const FROM_BULK_TRAFFIC: u64 = 1 << 38;
 
 pub const RCPT_DSN_SENT: u64 = 1 << 32;
 //pub const RCPT_STATUS_CHANGED: u64 = 1 << 33;
@@ -323,22 +325,7 @@ impl<'x> ResolveVariable for QueueEnvelope<'x> {
             .into(),
             ExpressionVariable::QueueName => self.rcpt.queue.as_str().into(),
             ExpressionVariable::QueueAge => now().saturating_sub(self.message.created).into(),
-            ExpressionVariable::Source => if (self.message.flags & FROM_AUTHENTICATED) != 0 {
-                "authenticated"
-            } else if (self.message.flags & FROM_UNAUTHENTICATED_DMARC) != 0 {
-                "dmarc_pass"
-            } else if (self.message.flags & FROM_UNAUTHENTICATED) != 0 {
-                "unauthenticated"
-            } else if (self.message.flags & FROM_DSN) != 0 {
-                "dsn"
-            } else if (self.message.flags & FROM_REPORT) != 0 {
-                "report"
-            } else if (self.message.flags & FROM_AUTOGENERATED) != 0 {
-                "autogenerated"
-            } else {
-                "unknown"
-            }
-            .into(),
+            ExpressionVariable::Source => self.message.source().into(),
             ExpressionVariable::Mx => self.mx.into(),
             ExpressionVariable::Priority => self.message.priority.into(),
             ExpressionVariable::RemoteIp => self.remote_ip.to_compact_string().into(),
@@ -369,6 +356,10 @@ impl ResolveVariable for Message {
                 .collect::<Vec<_>>()
                 .into(),
             ExpressionVariable::Priority => self.priority.into(),
+            ExpressionVariable::ReceivedFromIp => self.received_from_ip.to_compact_string().into(),
+            ExpressionVariable::ReceivedViaPort => self.received_via_port.into(),
+            ExpressionVariable::Source => self.source().into(),
+            ExpressionVariable::Size => self.size.into(),
             _ => "".into(),
         }
     }
@@ -378,6 +369,37 @@ impl ResolveVariable for Message {
     }
 }
 
+impl Message {
+    pub fn source(&self) -> &'static str {
+        if (self.flags & FROM_AUTHENTICATED) != 0 {
+            "authenticated"
+        } else if (self.flags & FROM_UNAUTHENTICATED_DMARC) != 0 {
+            "dmarc_pass"
+        } else if (self.flags & FROM_UNAUTHENTICATED) != 0 {
+            "unauthenticated"
+        } else if (self.flags & FROM_DSN) != 0 {
+            "dsn"
+        } else if (self.flags & FROM_REPORT) != 0 {
+            "report"
+        } else if (self.flags & FROM_AUTOGENERATED) != 0 {
+            "autogenerated"
+        } else {
+            "unknown"
+        }
+    }
+
+    pub fn trace_tags(&self) -> Option<trc::Value> {
+        (!self.tags.is_empty()).then(|| {
+            trc::Value::Array(
+                self.tags
+                    .iter()
+                    .map(|tag| trc::Value::String(tag.as_ref().into()))
+                    .collect(),
+            )
+        })
+    }
+}
+
 pub struct RecipientDomain<'x>(&'x str);
 
 impl<'x> RecipientDomain<'x> {
@@ -438,6 +460,25 @@ impl Recipient {
     pub fn domain_part(&self) -> &str {
         self.address.domain_part()
     }
+
+    pub fn subaddress(&self) -> Option<&str> {
+        subaddress_detail(self.orcpt.as_deref()?, &self.address)
+    }
+}
+
+// Sub-addresses are removed from the recipient when it is resolved, with the
+// original address kept as the ORCPT, so the detail is recovered from there
+pub fn subaddress_detail<'x>(orcpt: &'x str, address: &str) -> Option<&'x str> {
+    let (addr_type, orcpt) = orcpt.split_once(';')?;
+    let (local_part, domain) = orcpt.rsplit_once('@')?;
+    let (base, detail) = local_part.split_once('+')?;
+    let (address_local, address_domain) = address.rsplit_once('@')?;
+
+    (addr_type.eq_ignore_ascii_case("rfc822")
+        && !detail.is_empty()
+        && base.eq_ignore_ascii_case(address_local)
+        && domain.eq_ignore_ascii_case(address_domain))
+    .then_some(detail)
 }
 
 impl ArchivedRecipient {
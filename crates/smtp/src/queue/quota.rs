@@ -136,9 +136,7 @@ impl HasQueueQuota for Server {
             let key = quota.new_key(envelope, "");
             if let Some(max_size) = quota.size {
                 let used_size = self
-                    .core
-                    .storage
-                    .data
+                    .queue_store()
                     .get_counter(ValueKey::from(ValueClass::Queue(QueueClass::QuotaSize(
                         key.as_ref().to_vec(),
                     ))))
@@ -156,9 +154,7 @@ impl HasQueueQuota for Server {
 
             if let Some(max_messages) = quota.messages {
                 let total_messages = self
-                    .core
-                    .storage
-                    .data
+                    .queue_store()
                     .get_counter(ValueKey::from(ValueClass::Queue(QueueClass::QuotaCount(
                         key.as_ref().to_vec(),
                     ))))
@@ -17,11 +17,7 @@ use ahash::AHashSet;
 use common::config::smtp::queue::QueueName;
 use common::ipc::QueueEvent;
 use common::{KV_LOCK_QUEUE_MESSAGE, Server};
-use registry::schema::prelude::{ObjectType, Property};
-use registry::schema::structs::SpamTrainingSample;
-use registry::types::datetime::UTCDateTime;
-use registry::types::id::ObjectId;
-use registry::types::{EnumImpl, ObjectImpl};
+use mail_parser::MessageParser;
 use std::borrow::Cow;
 use std::collections::hash_map::Entry;
 use std::future::Future;
@@ -31,11 +27,10 @@ use store::write::key::DeserializeBigEndian;
 use store::write::serialize::rkyv_deserialize;
 use store::write::{
     AlignedBytes, Archive, Archiver, BatchBuilder, BlobLink, BlobOp, MergeResult, Params,
-    QueueClass, RegistryClass, ValueClass, now,
+    QueueClass, ValueClass, now,
 };
-use store::{Deserialize, IterateParams, Serialize, SerializeInfallible, U64_LEN, ValueKey};
-use trc::{AddContext, ServerEvent, SpamEvent};
-use types::blob::BlobId;
+use store::{Deserialize, IterateParams, Serialize, U64_LEN, ValueKey};
+use trc::{AddContext, ServerEvent};
 use types::blob_hash::BlobHash;
 use utils::DomainPart;
 
@@ -98,6 +93,7 @@ impl SmtpSpool for Server {
                 size: 0,
                 blob_hash: Default::default(),
                 quota_keys: Default::default(),
+                tags: Default::default(),
                 received_from_ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
                 received_via_port: 0,
             },
@@ -128,7 +124,7 @@ impl SmtpSpool for Server {
 
         queue.locked_revision += 1;
         let result = self
-            .store()
+            .queue_store()
             .iterate(
                 IterateParams::new(from_key, to_key).ascending().no_values(),
                 |key, _| {
@@ -275,7 +271,7 @@ impl SmtpSpool for Server {
         &self,
         id: QueueId,
     ) -> trc::Result<Option<Archive<AlignedBytes>>> {
-        self.store()
+        self.queue_store()
             .get_value::<Archive<AlignedBytes>>(ValueKey::from(ValueClass::Queue(
                 QueueClass::Message(id),
             )))
@@ -376,7 +372,7 @@ impl MessageWrapper {
             },
             vec![],
         );
-        if let Err(err) = server.store().write(batch.build_all()).await {
+        if let Err(err) = server.queue_store().write(batch.build_all()).await {
             trc::error!(
                 err.details("Failed to write to store.")
                     .span_id(session_id)
@@ -386,7 +382,7 @@ impl MessageWrapper {
             return false;
         }
         if let Err(err) = server
-            .blob_store()
+            .queue_blob_store()
             .put_blob(
                 self.message.blob_hash.as_slice(),
                 message.as_ref(),
@@ -407,6 +403,12 @@ impl MessageWrapper {
             Queue(event),
             SpanId = session_id,
             QueueId = self.queue_id,
+            EnvelopeId = self
+                .message
+                .env_id
+                .as_ref()
+                .map(|id| trc::Value::String(id.as_ref().into())),
+            Tags = self.message.trace_tags(),
             From = if !self.message.return_path.is_empty() {
                 trc::Value::String(self.message.return_path.as_ref().into())
             } else {
@@ -418,6 +420,9 @@ impl MessageWrapper {
                 .iter()
                 .map(|r| trc::Value::String(r.address.as_ref().into()))
                 .collect::<Vec<_>>(),
+            MessageId = MessageParser::new()
+                .parse_headers(message.as_ref())
+                .and_then(|m| m.message_id().map(|id| trc::Value::String(id.into()))),
             Size = self.message.size,
             NextRetry = self
                 .message
@@ -456,53 +461,12 @@ impl MessageWrapper {
             );
         }
 
-        if let Some((is_spam, subject)) = train_spam
-            && let Some(config) = &server.core.spam.classifier
-        {
-            let hold_period = now + config.hold_samples_for;
-            let sample = SpamTrainingSample {
-                account_id: None,
-                blob_id: BlobId::new(self.message.blob_hash.clone(), Default::default()),
-                delete_after_use: false,
-                expires_at: UTCDateTime::from_timestamp(hold_period as i64),
-                from: self.message.return_path.to_string(),
-                is_spam,
-                subject,
-            }
-            .to_pickled_vec();
-
-            let object_id = ObjectType::SpamTrainingSample.to_id();
-            let item_id = server.inner.data.registry_id_gen.generate();
-            batch
-                .set(
-                    BlobOp::Link {
-                        hash: self.message.blob_hash.clone(),
-                        to: BlobLink::Temporary { until: hold_period },
-                    },
-                    ObjectId::new(ObjectType::SpamTrainingSample, item_id.into()).serialize(),
-                )
-                .set(
-                    ValueClass::Registry(RegistryClass::Item { object_id, item_id }),
-                    sample,
-                )
-                .set(
-                    ValueClass::Registry(RegistryClass::Index {
-                        index_id: Property::AccountId.to_id(),
-                        object_id,
-                        item_id,
-                        key: (u32::MAX as u64).serialize(),
-                    }),
-                    vec![],
-                );
-
-            trc::event!(
-                Spam(SpamEvent::TrainSampleAdded),
-                Details = if is_spam { "spam" } else { "ham" },
-                Expires = trc::Value::Timestamp(hold_period),
-                SpanId = self.span_id,
-            );
-        }
+        let train_spam = train_spam
+            .map(|(is_spam, subject)| (is_spam, subject, self.message.return_path.to_string()));
 
+        // Queue entries and the links keeping the queued blob alive are
+        // written to the queue store, which is purged against the queue
+        // blob store
         batch
             .clear(BlobOp::Link {
                 hash: self.message.blob_hash.clone(),
@@ -538,7 +502,7 @@ impl MessageWrapper {
                 },
             );
 
-        if let Err(err) = server.store().write(batch.build_all()).await {
+        if let Err(err) = server.queue_store().write(batch.build_all()).await {
             trc::error!(
                 err.details("Failed to write to store.")
                     .span_id(session_id)
@@ -548,6 +512,19 @@ impl MessageWrapper {
             return false;
         }
 
+        // Training samples belong to the data store
+        if let Some((is_spam, subject, from)) = train_spam
+            && let Err(err) = server
+                .add_spam_training_sample(message.as_ref(), from, subject, is_spam, session_id)
+                .await
+        {
+            trc::error!(
+                err.span_id(session_id)
+                    .caused_by(trc::location!())
+                    .details("Failed to store spam training sample.")
+            );
+        }
+
         // Queue the message
         if server
             .inner
@@ -689,7 +666,7 @@ impl MessageWrapper {
             );
         }
 
-        if let Err(err) = server.store().write(batch.build_all()).await {
+        if let Err(err) = server.queue_store().write(batch.build_all()).await {
             trc::error!(
                 err.details("Failed to save changes.")
                     .span_id(self.span_id)
@@ -746,7 +723,7 @@ impl MessageWrapper {
             })
             .clear(ValueClass::Queue(QueueClass::Message(self.queue_id)));
 
-        if let Err(err) = server.store().write(batch.build_all()).await {
+        if let Err(err) = server.queue_store().write(batch.build_all()).await {
             trc::error!(
                 err.details("Failed to write to update queue.")
                     .span_id(self.span_id)
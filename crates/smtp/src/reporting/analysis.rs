@@ -8,12 +8,15 @@ use ahash::AHashSet;
 use common::{Server, psl};
 use mail_auth::{
     flate2::read::GzDecoder,
-    report::{Feedback, Report, tlsrpt::TlsReport},
+    report::{Feedback, FeedbackType, Report, tlsrpt::TlsReport},
     zip,
 };
 use mail_parser::{Message, MimeHeaders, PartType};
 use registry::{
-    schema::structs::{ArfExternalReport, DmarcExternalReport, TlsExternalReport},
+    schema::{
+        enums::SuppressionReason,
+        structs::{ArfExternalReport, DmarcExternalReport, TlsExternalReport},
+    },
     types::datetime::UTCDateTime,
 };
 use std::{
@@ -23,6 +26,7 @@ use std::{
 use store::write::{BatchBuilder, now};
 use trc::IncomingReportEvent;
 use types::id::Id;
+use utils::{DomainPart, sanitize_email};
 
 use crate::reporting::{inbound::LogReport, index::ExternalReportIndex};
 
@@ -246,6 +250,9 @@ impl AnalyzeReport for Server {
                         Some(report) => {
                             // Log
                             report.log();
+
+                            // Suppress recipients that complained
+                            suppress_complaint(&core, &report, session_id).await;
                             Format::Arf(report.into_owned())
                         }
                         None => {
@@ -344,6 +351,54 @@ impl AnalyzeReport for Server {
     }
 }
 
+// Only complaints about messages sent from a hosted domain are considered,
+// the recipient is suppressed for the tenant that owns the sender domain.
+async fn suppress_complaint(server: &Server, report: &Feedback<'_>, session_id: u64) {
+    if !matches!(
+        report.feedback_type,
+        FeedbackType::Abuse | FeedbackType::Fraud
+    ) {
+        return;
+    }
+    let [Some(rcpt_to), Some(mail_from)] = [&report.original_rcpt_to, &report.original_mail_from]
+        .map(|addr| {
+            addr.as_deref()
+                .and_then(|addr| sanitize_email(addr.trim_matches(['<', '>'])))
+        })
+    else {
+        return;
+    };
+
+    let result = match server.domain(mail_from.domain_part()).await {
+        Ok(Some(domain)) => {
+            server
+                .suppress_address(
+                    &rcpt_to,
+                    domain.id_tenant,
+                    SuppressionReason::Complaint,
+                    Some(format!(
+                        "Feedback report from {}",
+                        report
+                            .reporting_mta
+                            .as_deref()
+                            .unwrap_or("unknown reporter")
+                    )),
+                )
+                .await
+        }
+        Ok(None) => return,
+        Err(err) => Err(err),
+    };
+
+    if let Err(err) = result {
+        trc::error!(
+            err.span_id(session_id)
+                .caused_by(trc::location!())
+                .details("Failed to update suppression list")
+        );
+    }
+}
+
 async fn tenant_ids(server: &Server, domains: AHashSet<&str>) -> Option<Id> {
     let mut tenant_ids = Vec::with_capacity(domains.len());
     for domain in domains {
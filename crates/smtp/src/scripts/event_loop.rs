@@ -77,7 +77,9 @@ impl RunScript for Server {
                 Ok(event) => match event {
                     Event::IncludeScript { name, optional } => {
                         let name_ = name.as_str().to_lowercase();
-                        if let Some(script) = self.core.sieve.trusted_scripts.get(&name_) {
+                        if let Some(script) =
+                            self.inner.data.sieve_scripts.load().trusted.get(&name_)
+                        {
                             input = Input::script(name, script.clone());
                         } else if optional {
                             input = false.into();
@@ -31,17 +31,32 @@ impl SpamFilterAnalyzeClassify for Server {
     }
 
     async fn spam_filter_analyze_spam_trap(&self, ctx: &mut SpamFilterContext<'_>) -> bool {
-        if let Some(store) = self.get_lookup_store("spam-traps") {
-            for addr in &ctx.output.env_to_addr {
-                match store.key_exists(addr.address.as_str()).await {
-                    Ok(true) => {
-                        ctx.result.add_tag("SPAM_TRAP");
-                        return true;
-                    }
-                    Ok(false) => (),
-                    Err(err) => {
-                        trc::error!(err.span_id(ctx.input.span_id).caused_by(trc::location!()));
-                    }
+        // Senders that recently hit a spam trap
+        match self
+            .is_spam_trap_sender(
+                ctx.input.remote_ip,
+                ctx.output.env_from_addr.domain_part.fqdn.as_str(),
+            )
+            .await
+        {
+            Ok(true) => {
+                ctx.result.add_tag("TRAP_SENDER");
+            }
+            Ok(false) => (),
+            Err(err) => {
+                trc::error!(err.span_id(ctx.input.span_id).caused_by(trc::location!()));
+            }
+        }
+
+        for addr in &ctx.output.env_to_addr {
+            match self.is_spam_trap(addr.address.as_str()).await {
+                Ok(true) => {
+                    ctx.result.add_tag("SPAM_TRAP");
+                    return true;
+                }
+                Ok(false) => (),
+                Err(err) => {
+                    trc::error!(err.span_id(ctx.input.span_id).caused_by(trc::location!()));
                 }
             }
         }
@@ -0,0 +1,158 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    SpamFilterInput,
+    analysis::{init::SpamFilterInit, score::SpamFilterAnalyzeScore},
+};
+use common::{Server, config::mailstore::spamfilter::SpamFilterAction};
+use mail_parser::{MessageParser, mailbox::mbox::MessageIterator};
+use serde::Serialize;
+use std::{collections::BTreeMap, future::Future};
+
+pub const CORPUS_LABEL_HEADER: &str = "X-Corpus-Label";
+
+pub trait SpamFilterAnalyzeCorpus: Sync + Send {
+    fn spam_filter_analyze_corpus(
+        &self,
+        report: &mut SpamCorpusReport,
+        mbox: &[u8],
+        label: Option<bool>,
+    ) -> impl Future<Output = ()> + Send;
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpamCorpusReport {
+    pub ham: u64,
+    pub spam: u64,
+    pub skipped: u64,
+    pub false_positives: u64,
+    pub false_negatives: u64,
+    pub rules: BTreeMap<String, SpamCorpusRule>,
+    pub score_distribution: BTreeMap<i32, SpamCorpusBucket>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpamCorpusRule {
+    pub score: f32,
+    pub ham_hits: u64,
+    pub spam_hits: u64,
+    pub ham_rate: f32,
+    pub spam_rate: f32,
+    pub false_positives: u64,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct SpamCorpusBucket {
+    pub ham: u64,
+    pub spam: u64,
+}
+
+impl SpamFilterAnalyzeCorpus for Server {
+    async fn spam_filter_analyze_corpus(
+        &self,
+        report: &mut SpamCorpusReport,
+        mbox: &[u8],
+        label: Option<bool>,
+    ) {
+        for message in MessageIterator::new(mbox) {
+            let Ok(contents) = message.map(|message| message.unwrap_contents()) else {
+                report.skipped += 1;
+                continue;
+            };
+            let Some(message) = MessageParser::new().parse(&contents) else {
+                report.skipped += 1;
+                continue;
+            };
+
+            // Messages may carry their own label, otherwise the corpus label applies
+            let is_spam = match message
+                .header_raw(CORPUS_LABEL_HEADER)
+                .map(|label| label.trim().to_ascii_lowercase())
+                .as_deref()
+            {
+                Some("spam") => true,
+                Some("ham") => false,
+                _ => match label {
+                    Some(is_spam) => is_spam,
+                    None => {
+                        report.skipped += 1;
+                        continue;
+                    }
+                },
+            };
+
+            let mut ctx =
+                self.spam_filter_init(SpamFilterInput::from_message(&message, 0).test_mode());
+            let (score, classified_spam) = match self.spam_filter_classify(&mut ctx).await {
+                SpamFilterAction::Allow(result) => (result.score, result.is_spam),
+                SpamFilterAction::Discard | SpamFilterAction::Reject => (ctx.result.score, true),
+                SpamFilterAction::Disabled => (0.0, false),
+            };
+
+            if is_spam {
+                report.spam += 1;
+                if !classified_spam {
+                    report.false_negatives += 1;
+                }
+            } else {
+                report.ham += 1;
+                if classified_spam {
+                    report.false_positives += 1;
+                }
+            }
+
+            let bucket = report
+                .score_distribution
+                .entry(score.floor() as i32)
+                .or_default();
+            if is_spam {
+                bucket.spam += 1;
+            } else {
+                bucket.ham += 1;
+            }
+
+            for tag in &ctx.result.tags {
+                let rule = report
+                    .rules
+                    .entry(tag.clone())
+                    .or_insert_with(|| SpamCorpusRule {
+                        score: match self.inner.data.spam_lists.load().scores.get(tag) {
+                            Some(SpamFilterAction::Allow(score)) => *score,
+                            _ => 0.0,
+                        },
+                        ..Default::default()
+                    });
+                if is_spam {
+                    rule.spam_hits += 1;
+                    if rule.score < 0.0 {
+                        rule.false_positives += 1;
+                    }
+                } else {
+                    rule.ham_hits += 1;
+                    if rule.score > 0.0 {
+                        rule.false_positives += 1;
+                    }
+                }
+            }
+        }
+
+        for rule in report.rules.values_mut() {
+            rule.ham_rate = rate(rule.ham_hits, report.ham);
+            rule.spam_rate = rate(rule.spam_hits, report.spam);
+        }
+    }
+}
+
+fn rate(hits: u64, total: u64) -> f32 {
+    if total > 0 {
+        hits as f32 / total as f32
+    } else {
+        0.0
+    }
+}
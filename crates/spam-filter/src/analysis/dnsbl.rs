@@ -0,0 +1,54 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use super::is_trusted_domain;
+use crate::{
+    Email, Recipient,
+    modules::{dnsbl::dnsbl_score, expression::StringResolver},
+};
+use common::{
+    Server,
+    config::mailstore::spamfilter::{Element, IpResolver},
+};
+use std::{future::Future, net::IpAddr};
+
+pub trait SpamFilterAnalyzeDnsbl: Sync + Send {
+    fn spam_filter_dnsbl_connect(
+        &self,
+        remote_ip: IpAddr,
+        span_id: u64,
+    ) -> impl Future<Output = f32> + Send;
+
+    fn spam_filter_dnsbl_mail_from(
+        &self,
+        sender: &str,
+        span_id: u64,
+    ) -> impl Future<Output = f32> + Send;
+}
+
+impl SpamFilterAnalyzeDnsbl for Server {
+    async fn spam_filter_dnsbl_connect(&self, remote_ip: IpAddr, span_id: u64) -> f32 {
+        if remote_ip.is_loopback() || self.is_ip_allowed(remote_ip) {
+            return 0.0;
+        }
+
+        dnsbl_score(self, &IpResolver::new(remote_ip), Element::Ip, span_id).await
+    }
+
+    async fn spam_filter_dnsbl_mail_from(&self, sender: &str, span_id: u64) -> f32 {
+        let sender = Recipient {
+            email: Email::new(sender),
+            name: None,
+        };
+        let domain = sender.email.domain_part.fqdn.as_str();
+        if !sender.email.is_valid() || is_trusted_domain(self, domain, span_id).await {
+            return 0.0;
+        }
+
+        dnsbl_score(self, &sender, Element::Email, span_id).await
+            + dnsbl_score(self, &StringResolver(domain), Element::Domain, span_id).await
+    }
+}
@@ -385,12 +385,14 @@ impl SpamFilterAnalyzeMime for Server {
                     ctx.result.add_tag("MIME_BAD_UNICODE");
                 }
                 let attach_name = attach_name.trim().to_lowercase();
-                if let Some((name, ext)) = attach_name.rsplit_once('.').and_then(|(name, ext)| {
-                    Some((name, self.core.spam.lists.file_extensions.get(ext)?))
-                }) {
+                let lists = self.inner.data.spam_lists.load_full();
+                if let Some((name, ext)) = attach_name
+                    .rsplit_once('.')
+                    .and_then(|(name, ext)| Some((name, lists.file_extensions.get(ext)?)))
+                {
                     let sub_ext = name
                         .rsplit_once('.')
-                        .and_then(|(_, ext)| self.core.spam.lists.file_extensions.get(ext));
+                        .and_then(|(_, ext)| lists.file_extensions.get(ext));
 
                     if ext.is_bad {
                         // Attachment has a bad extension
@@ -15,8 +15,10 @@ use std::{
 };
 
 pub mod classifier;
+pub mod corpus;
 pub mod date;
 pub mod dmarc;
+pub mod dnsbl;
 pub mod domain;
 pub mod ehlo;
 pub mod from;
@@ -26,10 +28,12 @@ pub mod init;
 pub mod ip;
 pub mod messageid;
 pub mod mime;
+pub mod phishing;
 pub mod pyzor;
 pub mod received;
 pub mod recipient;
 pub mod replyto;
+pub mod rspamd;
 pub mod rules;
 pub mod score;
 pub mod subject;
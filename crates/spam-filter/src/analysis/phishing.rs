@@ -0,0 +1,149 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::SpamFilterContext;
+use common::Server;
+use registry::schema::prelude::{ObjectType, Property};
+use std::future::Future;
+use store::{registry::RegistryQuery, roaring::RoaringBitmap};
+use trc::AddContext;
+
+pub trait SpamFilterAnalyzePhishing: Sync + Send {
+    fn spam_filter_analyze_phishing(
+        &self,
+        ctx: &mut SpamFilterContext<'_>,
+    ) -> impl Future<Output = ()> + Send;
+}
+
+// ASCII sequences commonly swapped to imitate a domain name
+const ASCII_CONFUSABLES: &[(&str, &str)] = &[
+    ("rn", "m"),
+    ("vv", "w"),
+    ("cl", "d"),
+    ("0", "o"),
+    ("1", "l"),
+    ("i", "l"),
+    ("l", "i"),
+];
+const MAX_CANDIDATES: usize = 16;
+const MAX_NAME_MATCHES: usize = 5;
+
+impl SpamFilterAnalyzePhishing for Server {
+    async fn spam_filter_analyze_phishing(&self, ctx: &mut SpamFilterContext<'_>) {
+        let from_addr = &ctx.output.from.email;
+        if ctx.input.authenticated_as.is_some() || !from_addr.is_valid() {
+            return;
+        }
+
+        // Mail from hosted domains is covered by sender authentication
+        let from_domain = from_addr.domain_part.fqdn.as_str();
+        match self.domain(from_domain).await {
+            Ok(None) => {}
+            Ok(Some(_)) => return,
+            Err(err) => {
+                trc::error!(err.span_id(ctx.input.span_id).caused_by(trc::location!()));
+                return;
+            }
+        }
+
+        // Punycode, homoglyph and ASCII lookalikes of hosted domains
+        for candidate in lookalike_candidates(from_domain) {
+            match self.domain(&candidate).await {
+                Ok(Some(_)) => {
+                    ctx.result.add_tag(if from_domain.is_ascii() {
+                        "FROM_LOOKALIKE_DOMAIN"
+                    } else {
+                        "FROM_HOMOGRAPH_DOMAIN"
+                    });
+                    ctx.result
+                        .phishing
+                        .push(format!("lookalike-domain={candidate}"));
+                    break;
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    trc::error!(err.span_id(ctx.input.span_id).caused_by(trc::location!()));
+                    break;
+                }
+            }
+        }
+
+        // Display names of local principals used by external senders
+        let from_name = normalize_name(ctx.output.from.name.as_deref().unwrap_or_default());
+        if from_name.is_empty() || !from_name.contains(' ') {
+            return;
+        }
+        let ids = match self
+            .registry()
+            .query::<RoaringBitmap>(
+                RegistryQuery::new(ObjectType::Account).text(Property::Text, from_name.clone()),
+            )
+            .await
+            .caused_by(trc::location!())
+        {
+            Ok(ids) => ids,
+            Err(err) => {
+                trc::error!(err.span_id(ctx.input.span_id));
+                return;
+            }
+        };
+        for account_id in ids.into_iter().take(MAX_NAME_MATCHES) {
+            match self.try_account(account_id).await {
+                Ok(Some(account)) => {
+                    if account
+                        .description
+                        .as_deref()
+                        .is_some_and(|name| normalize_name(name) == from_name)
+                    {
+                        ctx.result.add_tag("FROM_DN_IMPERSONATION");
+                        ctx.result
+                            .phishing
+                            .push("display-name-impersonation".to_string());
+                        break;
+                    }
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    trc::error!(err.span_id(ctx.input.span_id).caused_by(trc::location!()));
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn lookalike_candidates(fqdn: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+
+    if fqdn.is_ascii() {
+        for (from, to) in ASCII_CONFUSABLES {
+            for (pos, _) in fqdn.match_indices(from) {
+                let candidate = format!("{}{to}{}", &fqdn[..pos], &fqdn[pos + from.len()..]);
+                if !candidates.contains(&candidate) {
+                    candidates.push(candidate);
+                }
+                if candidates.len() >= MAX_CANDIDATES {
+                    return candidates;
+                }
+            }
+        }
+    } else if let Ok(cured) = decancer::cure(fqdn, decancer::Options::default()) {
+        let cured = cured.to_string();
+        if cured != fqdn && cured.is_ascii() {
+            candidates.push(cured);
+        }
+    }
+
+    candidates
+}
+
+fn normalize_name(name: &str) -> String {
+    let name = name.trim().trim_matches(|c| c == '"' || c == '\'');
+    let name = decancer::cure(name, decancer::Options::default())
+        .map(|cured| cured.to_string())
+        .unwrap_or_else(|_| name.to_lowercase());
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
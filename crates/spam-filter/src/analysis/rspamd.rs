@@ -0,0 +1,46 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use std::{future::Future, time::Instant};
+
+use common::Server;
+
+use crate::{SpamFilterContext, modules::rspamd::rspamd_check};
+
+pub trait SpamFilterAnalyzeRspamd: Sync + Send {
+    fn spam_filter_analyze_rspamd(
+        &self,
+        ctx: &mut SpamFilterContext<'_>,
+    ) -> impl Future<Output = ()> + Send;
+}
+
+impl SpamFilterAnalyzeRspamd for Server {
+    async fn spam_filter_analyze_rspamd(&self, ctx: &mut SpamFilterContext<'_>) {
+        if let Some(config) = &self.core.spam.rspamd {
+            let time = Instant::now();
+            match rspamd_check(ctx, config).await {
+                Ok(Some(result)) => {
+                    trc::event!(
+                        Spam(trc::SpamEvent::Rspamd),
+                        Result = result.action.clone(),
+                        Value = result.score,
+                        Total = result.symbols.len(),
+                        SpanId = ctx.input.span_id,
+                        Elapsed = time.elapsed()
+                    );
+                    ctx.result.rspamd = Some(result);
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    trc::error!(
+                        err.span_id(ctx.input.span_id)
+                            .ctx(trc::Key::Elapsed, time.elapsed())
+                    );
+                }
+            }
+        }
+    }
+}
@@ -25,9 +25,11 @@ pub trait SpamFilterAnalyzeRules: Sync + Send {
 
 impl SpamFilterAnalyzeRules for Server {
     async fn spam_filter_analyze_rules(&self, ctx: &mut SpamFilterContext<'_>) {
-        if !self.core.spam.rules.url.is_empty() {
+        let rules = self.inner.data.spam_rules.load_full();
+
+        if !rules.url.is_empty() {
             for url in &ctx.output.urls {
-                for rule in &self.core.spam.rules.url {
+                for rule in &rules.url {
                     if let Some(tag) = self
                         .eval_if::<String, _>(
                             rule,
@@ -42,11 +44,11 @@ impl SpamFilterAnalyzeRules for Server {
             }
         }
 
-        if !self.core.spam.rules.domain.is_empty() {
+        if !rules.domain.is_empty() {
             for domain in &ctx.output.domains {
                 let resolver = StringResolver(domain.element.as_str());
 
-                for rule in &self.core.spam.rules.domain {
+                for rule in &rules.domain {
                     if let Some(tag) = self
                         .eval_if::<String, _>(
                             rule,
@@ -61,9 +63,9 @@ impl SpamFilterAnalyzeRules for Server {
             }
         }
 
-        if !self.core.spam.rules.email.is_empty() {
+        if !rules.email.is_empty() {
             for email in &ctx.output.emails {
-                for rule in &self.core.spam.rules.email {
+                for rule in &rules.email {
                     if let Some(tag) = self
                         .eval_if::<String, _>(
                             rule,
@@ -83,7 +85,7 @@ impl SpamFilterAnalyzeRules for Server {
                 (&ctx.output.recipients_bcc, Location::HeaderBcc),
             ] {
                 for email in rcpt {
-                    for rule in &self.core.spam.rules.email {
+                    for rule in &rules.email {
                         if let Some(tag) = self
                             .eval_if::<String, _>(
                                 rule,
@@ -99,11 +101,11 @@ impl SpamFilterAnalyzeRules for Server {
             }
         }
 
-        if !self.core.spam.rules.ip.is_empty() {
+        if !rules.ip.is_empty() {
             for ip in &ctx.output.ips {
                 let ip_resolver = IpResolver::new(ip.element);
 
-                for rule in &self.core.spam.rules.ip {
+                for rule in &rules.ip {
                     if let Some(tag) = self
                         .eval_if::<String, _>(
                             rule,
@@ -118,7 +120,7 @@ impl SpamFilterAnalyzeRules for Server {
             }
         }
 
-        if !self.core.spam.rules.header.is_empty() {
+        if !rules.header.is_empty() {
             for header in ctx.input.message.headers() {
                 let raw = String::from_utf8_lossy(
                     ctx.input
@@ -132,7 +134,7 @@ impl SpamFilterAnalyzeRules for Server {
                     raw: raw.as_ref(),
                 };
 
-                for rule in &self.core.spam.rules.header {
+                for rule in &rules.header {
                     if let Some(tag) = self
                         .eval_if::<String, _>(
                             rule,
@@ -147,7 +149,7 @@ impl SpamFilterAnalyzeRules for Server {
             }
         }
 
-        if !self.core.spam.rules.body.is_empty() {
+        if !rules.body.is_empty() {
             for (idx, part) in ctx.output.text_parts.iter().enumerate() {
                 let text = match part {
                     TextPart::Plain { text_body, .. } => *text_body,
@@ -164,7 +166,7 @@ impl SpamFilterAnalyzeRules for Server {
                 };
                 let string_resolver = StringResolver(text);
 
-                for rule in &self.core.spam.rules.body {
+                for rule in &rules.body {
                     if let Some(tag) = self
                         .eval_if::<String, _>(
                             rule,
@@ -179,9 +181,9 @@ impl SpamFilterAnalyzeRules for Server {
             }
         }
 
-        if !self.core.spam.rules.any.is_empty() {
+        if !rules.any.is_empty() {
             let dummy_resolver = StringResolver("");
-            for rule in &self.core.spam.rules.any {
+            for rule in &rules.any {
                 if let Some(tag) = self
                     .eval_if::<String, _>(
                         rule,
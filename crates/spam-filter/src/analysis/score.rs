@@ -12,11 +12,13 @@ use crate::{
         ehlo::SpamFilterAnalyzeEhlo, from::SpamFilterAnalyzeFrom,
         headers::SpamFilterAnalyzeHeaders, html::SpamFilterAnalyzeHtml, ip::SpamFilterAnalyzeIp,
         messageid::SpamFilterAnalyzeMid, mime::SpamFilterAnalyzeMime,
-        pyzor::SpamFilterAnalyzePyzor, received::SpamFilterAnalyzeReceived,
-        recipient::SpamFilterAnalyzeRecipient, replyto::SpamFilterAnalyzeReplyTo,
+        phishing::SpamFilterAnalyzePhishing, pyzor::SpamFilterAnalyzePyzor,
+        received::SpamFilterAnalyzeReceived, recipient::SpamFilterAnalyzeRecipient,
+        replyto::SpamFilterAnalyzeReplyTo, rspamd::SpamFilterAnalyzeRspamd,
         rules::SpamFilterAnalyzeRules, subject::SpamFilterAnalyzeSubject,
         url::SpamFilterAnalyzeUrl,
     },
+    modules::rspamd::RspamdResult,
 };
 use common::{Server, config::mailstore::spamfilter::SpamFilterAction};
 use std::{fmt::Write, future::Future, vec};
@@ -47,6 +49,7 @@ pub struct SpamFilterScore {
     pub train_spam: Option<bool>,
     pub score: f32,
     pub is_spam: bool,
+    pub rspamd: Option<RspamdResult>,
 }
 
 impl SpamFilterAnalyzeScore for Server {
@@ -59,10 +62,13 @@ impl SpamFilterAnalyzeScore for Server {
         let mut header_len = 60;
         let mut is_spam_trap = false;
         let mut rbl_count = 0;
+        let lists = self.inner.data.spam_lists.load_full();
 
         for tag in &ctx.result.tags {
-            let score = match self.core.spam.lists.scores.get(tag) {
-                Some(SpamFilterAction::Allow(score)) => *score,
+            let score = match lists.scores.get(tag) {
+                Some(SpamFilterAction::Allow(score)) => {
+                    *score * ctx.result.rbl_weights.get(tag).copied().unwrap_or(1.0)
+                }
                 Some(SpamFilterAction::Discard) => {
                     return SpamFilterAction::Discard;
                 }
@@ -83,6 +89,37 @@ impl SpamFilterAnalyzeScore for Server {
             }
         }
 
+        // An external rspamd verdict either replaces or adds to the rule score
+        if let Some(rspamd) = &ctx.result.rspamd {
+            if self
+                .core
+                .spam
+                .rspamd
+                .as_ref()
+                .is_some_and(|config| config.replace_score)
+            {
+                ctx.result.score = rspamd.score;
+            } else {
+                ctx.result.score += rspamd.score;
+            }
+            header_len += rspamd
+                .symbols
+                .iter()
+                .map(|(name, _)| name.len() + 10)
+                .sum::<usize>()
+                + 60;
+        }
+
+        if !ctx.result.phishing.is_empty() {
+            header_len += ctx
+                .result
+                .phishing
+                .iter()
+                .map(|entry| entry.len() + 2)
+                .sum::<usize>()
+                + 20;
+        }
+
         let mut final_score = ctx.result.score;
         let mut avg_confidence: f32 = 0.0;
         let mut total_results = 0;
@@ -149,6 +186,27 @@ impl SpamFilterAnalyzeScore for Server {
             }
             headers.push_str("\r\n");
 
+            if let Some(rspamd) = &ctx.result.rspamd {
+                let _ = write!(
+                    &mut headers,
+                    "X-Spam-Rspamd: action={}, score={:.2}",
+                    rspamd.action, rspamd.score
+                );
+                for (idx, (name, score)) in rspamd.symbols.iter().enumerate() {
+                    headers.push_str(if idx > 0 { ",\r\n\t" } else { ";\r\n\t" });
+                    let _ = write!(&mut headers, "{} ({:.2})", name, score);
+                }
+                headers.push_str("\r\n");
+            }
+
+            if !ctx.result.phishing.is_empty() {
+                let _ = write!(
+                    &mut headers,
+                    "X-Spam-Phishing: {}\r\n",
+                    ctx.result.phishing.join("; ")
+                );
+            }
+
             if let Some((category, explanation)) = &ctx.result.llm_result {
                 let _ = write!(&mut headers, "X-Spam-LLM: {category} ({explanation})\r\n",);
             }
@@ -186,6 +244,7 @@ impl SpamFilterAnalyzeScore for Server {
                 train_spam,
                 score: final_score,
                 is_spam,
+                rspamd: ctx.result.rspamd.take(),
             })
         }
     }
@@ -221,6 +280,9 @@ impl SpamFilterAnalyzeScore for Server {
         // From and Envelope From analysis
         self.spam_filter_analyze_from(ctx).await;
 
+        // Lookalike domain and display name impersonation analysis
+        self.spam_filter_analyze_phishing(ctx).await;
+
         // Reply-To analysis
         self.spam_filter_analyze_reply_to(ctx).await;
 
@@ -255,6 +317,9 @@ impl SpamFilterAnalyzeScore for Server {
         // Pyzor checks
         self.spam_filter_analyze_pyzor(ctx).await;
 
+        // Rspamd checks
+        self.spam_filter_analyze_rspamd(ctx).await;
+
         // Model classification
         self.spam_filter_analyze_classify(ctx).await;
 
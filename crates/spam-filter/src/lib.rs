@@ -11,15 +11,17 @@ pub mod modules;
 
 use analysis::ElementLocation;
 use analysis::url::UrlParts;
+use common::network::fingerprint::TlsFingerprint;
 use mail_auth::{ArcOutput, DkimOutput, DmarcResult, IprevOutput, SpfOutput, dmarc::Policy};
 use mail_parser::Message;
 use modules::html::HtmlToken;
+use modules::rspamd::RspamdResult;
 use nlp::tokenizers::types::TokenType;
 use std::borrow::Cow;
 use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 use std::net::{IpAddr, Ipv4Addr};
-use store::ahash::AHashSet;
+use store::ahash::{AHashMap, AHashSet};
 
 pub struct SpamFilterInput<'x> {
     pub message: &'x Message<'x>,
@@ -43,11 +45,13 @@ pub struct SpamFilterInput<'x> {
 
     // TLS
     pub is_tls: bool,
+    pub tls_fingerprint: Option<&'x TlsFingerprint>,
 
     // Envelope
     pub env_from: &'x str,
     pub env_from_flags: u64,
     pub env_rcpt_to: Vec<&'x str>,
+    pub env_rcpt_to_detail: Vec<&'x str>,
 
     pub is_train: bool,
     pub is_test: bool,
@@ -107,7 +111,10 @@ pub struct SpamFilterResult {
     pub rbl_domain_checks: usize,
     pub rbl_url_checks: usize,
     pub rbl_email_checks: usize,
+    pub rbl_weights: AHashMap<String, f32>,
     pub llm_result: Option<(String, String)>,
+    pub rspamd: Option<RspamdResult>,
+    pub phishing: Vec<String>,
 }
 
 pub struct SpamFilterContext<'x> {
@@ -154,9 +161,11 @@ impl<'x> SpamFilterInput<'x> {
             asn: None,
             country: None,
             is_tls: true,
+            tls_fingerprint: None,
             env_from: "",
             env_from_flags: 0,
             env_rcpt_to: vec![],
+            env_rcpt_to_detail: vec![],
             is_test: false,
             is_train: false,
         }
@@ -166,6 +175,11 @@ impl<'x> SpamFilterInput<'x> {
         self.is_train = true;
         self
     }
+
+    pub fn test_mode(mut self) -> Self {
+        self.is_test = true;
+        self
+    }
 }
 
 impl PartialEq for Hostname {
@@ -8,15 +8,16 @@ use super::expression::SpamFilterResolver;
 use crate::SpamFilterContext;
 use common::{
     Server,
-    config::mailstore::spamfilter::{DnsBlServer, Element, IpResolver, Location},
+    config::mailstore::spamfilter::{DnsBlServer, Element, IpResolver, Location, SpamFilterAction},
     expr::functions::ResolveVariable,
 };
 use mail_auth::{Error, common::resolver::ToFqdn};
 use std::{
     net::Ipv4Addr,
-    sync::Arc,
+    sync::{Arc, atomic::Ordering},
     time::{Duration, Instant},
 };
+use store::write::now;
 use trc::SpamEvent;
 
 pub(crate) async fn check_dnsbl(
@@ -58,6 +59,9 @@ pub(crate) async fn check_dnsbl(
             )
             .await
         {
+            if dnsbl.weight != 1.0 {
+                ctx.result.rbl_weights.insert(tag.clone(), dnsbl.weight);
+            }
             ctx.result.add_tag(tag);
         }
     }
@@ -78,7 +82,6 @@ async fn is_dnsbl(
     element: Element,
     checks: &mut usize,
 ) -> Option<String> {
-    let time = Instant::now();
     let zone = server
         .eval_if::<String, _>(&config.zone, &resolver, resolver.ctx.input.span_id)
         .await?;
@@ -109,82 +112,7 @@ async fn is_dnsbl(
         }
     }
 
-    let result = match server.inner.cache.dns_rbl.get(zone.as_str()) {
-        Some(Some(result)) => result,
-        Some(None) => return None,
-        None => {
-            *checks += 1;
-
-            match server
-                .core
-                .smtp
-                .resolvers
-                .dns
-                .ipv4_lookup_raw(zone.to_fqdn().as_ref())
-                .await
-            {
-                Ok(result) => {
-                    trc::event!(
-                        Spam(SpamEvent::Dnsbl),
-                        Hostname = zone.clone(),
-                        Result = result
-                            .entry
-                            .iter()
-                            .map(|ip| trc::Value::from(ip.to_string()))
-                            .collect::<Vec<_>>(),
-                        Details = element.as_str(),
-                        Elapsed = time.elapsed()
-                    );
-
-                    let entry = Arc::new(IpResolver::new(
-                        result
-                            .entry
-                            .iter()
-                            .copied()
-                            .next()
-                            .unwrap_or(Ipv4Addr::BROADCAST)
-                            .into(),
-                    ));
-
-                    server.inner.cache.dns_rbl.insert_with_expiry(
-                        zone.into(),
-                        Some(entry.clone()),
-                        result.expires,
-                    );
-
-                    entry
-                }
-                Err(Error::DnsRecordNotFound(_)) => {
-                    trc::event!(
-                        Spam(SpamEvent::Dnsbl),
-                        Hostname = zone.clone(),
-                        Result = trc::Value::None,
-                        Details = element.as_str(),
-                        Elapsed = time.elapsed()
-                    );
-
-                    server.inner.cache.dns_rbl.insert(
-                        zone.into(),
-                        None,
-                        Duration::from_secs(86400),
-                    );
-
-                    return None;
-                }
-                Err(err) => {
-                    trc::event!(
-                        Spam(SpamEvent::DnsblError),
-                        Hostname = zone,
-                        Elapsed = time.elapsed(),
-                        Details = element.as_str(),
-                        CausedBy = err.to_string()
-                    );
-
-                    return None;
-                }
-            }
-        }
-    };
+    let result = dnsbl_lookup(server, config, zone, element, checks).await?;
 
     server
         .eval_if(
@@ -194,3 +122,190 @@ async fn is_dnsbl(
         )
         .await
 }
+
+pub(crate) async fn dnsbl_score(
+    server: &Server,
+    resolver: &impl ResolveVariable,
+    scope: Element,
+    span_id: u64,
+) -> f32 {
+    let mut score = 0.0;
+    let mut checks = 0;
+
+    for dnsbl in &server.core.spam.dnsbl.servers {
+        if dnsbl.scope == scope
+            && let Some(zone) = server
+                .eval_if::<String, _>(&dnsbl.zone, resolver, span_id)
+                .await
+            && let Some(result) = dnsbl_lookup(server, dnsbl, zone, scope, &mut checks).await
+            && let Some(tag) = server
+                .eval_if::<String, _>(&dnsbl.tags, result.as_ref(), span_id)
+                .await
+        {
+            score += match server.inner.data.spam_lists.load().scores.get(&tag) {
+                Some(SpamFilterAction::Allow(tag_score)) => *tag_score * dnsbl.weight,
+                Some(SpamFilterAction::Reject) => f32::INFINITY,
+                Some(SpamFilterAction::Discard | SpamFilterAction::Disabled) | None => 0.0,
+            };
+        }
+    }
+
+    score
+}
+
+async fn dnsbl_lookup(
+    server: &Server,
+    config: &DnsBlServer,
+    zone: String,
+    element: Element,
+    checks: &mut usize,
+) -> Option<Arc<IpResolver>> {
+    match server.inner.cache.dns_rbl.get(zone.as_str()) {
+        Some(Some(result)) => return Some(result),
+        Some(None) => return None,
+        None => {}
+    }
+
+    // Lists that keep failing are skipped until the disable period expires
+    let health = &config.health;
+    if health.disabled_until.load(Ordering::Relaxed) > now() {
+        return None;
+    }
+
+    *checks += 1;
+    health.queries.fetch_add(1, Ordering::Relaxed);
+    let time = Instant::now();
+    let result = tokio::time::timeout(
+        config.timeout,
+        server
+            .core
+            .smtp
+            .resolvers
+            .dns
+            .ipv4_lookup_raw(zone.to_fqdn().as_ref()),
+    )
+    .await;
+
+    match result {
+        Ok(Ok(result)) if result.entry.iter().any(is_refused) => {
+            // Public resolvers and excessive query volumes get 127.255.255.x answers
+            trc::event!(
+                Spam(SpamEvent::DnsblError),
+                Id = config.id.clone(),
+                Hostname = zone,
+                Elapsed = time.elapsed(),
+                Details = element.as_str(),
+                CausedBy = "Query refused"
+            );
+            record_failure(server, config);
+
+            None
+        }
+        Ok(Ok(result)) => {
+            health.failures.store(0, Ordering::Relaxed);
+            health.hits.fetch_add(1, Ordering::Relaxed);
+
+            trc::event!(
+                Spam(SpamEvent::Dnsbl),
+                Id = config.id.clone(),
+                Hostname = zone.clone(),
+                Result = result
+                    .entry
+                    .iter()
+                    .map(|ip| trc::Value::from(ip.to_string()))
+                    .collect::<Vec<_>>(),
+                Details = element.as_str(),
+                Elapsed = time.elapsed()
+            );
+
+            let entry = Arc::new(IpResolver::new(
+                result
+                    .entry
+                    .iter()
+                    .copied()
+                    .next()
+                    .unwrap_or(Ipv4Addr::BROADCAST)
+                    .into(),
+            ));
+
+            server.inner.cache.dns_rbl.insert_with_expiry(
+                zone.into(),
+                Some(entry.clone()),
+                result.expires,
+            );
+
+            Some(entry)
+        }
+        Ok(Err(Error::DnsRecordNotFound(_))) => {
+            health.failures.store(0, Ordering::Relaxed);
+
+            trc::event!(
+                Spam(SpamEvent::Dnsbl),
+                Id = config.id.clone(),
+                Hostname = zone.clone(),
+                Result = trc::Value::None,
+                Details = element.as_str(),
+                Elapsed = time.elapsed()
+            );
+
+            server
+                .inner
+                .cache
+                .dns_rbl
+                .insert(zone.into(), None, Duration::from_secs(86400));
+
+            None
+        }
+        Ok(Err(err)) => {
+            trc::event!(
+                Spam(SpamEvent::DnsblError),
+                Id = config.id.clone(),
+                Hostname = zone,
+                Elapsed = time.elapsed(),
+                Details = element.as_str(),
+                CausedBy = err.to_string()
+            );
+            record_failure(server, config);
+
+            None
+        }
+        Err(_) => {
+            trc::event!(
+                Spam(SpamEvent::DnsblError),
+                Id = config.id.clone(),
+                Hostname = zone,
+                Elapsed = time.elapsed(),
+                Details = element.as_str(),
+                CausedBy = "Query timed out"
+            );
+            record_failure(server, config);
+
+            None
+        }
+    }
+}
+
+fn record_failure(server: &Server, config: &DnsBlServer) {
+    let health = &config.health;
+    let settings = &server.core.spam.dnsbl;
+    if health.failures.fetch_add(1, Ordering::Relaxed) + 1 >= settings.max_failures {
+        let disabled_until = now() + settings.disable_for.as_secs();
+        health.failures.store(0, Ordering::Relaxed);
+        health
+            .disabled_until
+            .store(disabled_until, Ordering::Relaxed);
+
+        trc::event!(
+            Spam(SpamEvent::DnsblDisabled),
+            Id = config.id.clone(),
+            Total = health.queries.load(Ordering::Relaxed),
+            TotalSuccesses = health.hits.load(Ordering::Relaxed),
+            Expires = trc::Value::Timestamp(disabled_until),
+        );
+    }
+}
+
+fn is_refused(ip: &Ipv4Addr) -> bool {
+    let octets = ip.octets();
+    octets[0] == 127 && octets[1] == 255 && octets[2] == 255
+}
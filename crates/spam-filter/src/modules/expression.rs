@@ -49,6 +49,20 @@ impl<T: ResolveVariable> ResolveVariable for SpamFilterResolver<'_, T> {
             ExpressionVariable::Asn => self.ctx.input.asn.unwrap_or_default().into(),
             ExpressionVariable::Country => self.ctx.input.country.unwrap_or_default().into(),
             ExpressionVariable::IsTls => self.ctx.input.is_tls.into(),
+            ExpressionVariable::TlsJa3 => self
+                .ctx
+                .input
+                .tls_fingerprint
+                .map(|fp| fp.ja3.as_str())
+                .unwrap_or_default()
+                .into(),
+            ExpressionVariable::TlsJa4 => self
+                .ctx
+                .input
+                .tls_fingerprint
+                .map(|fp| fp.ja4.as_str())
+                .unwrap_or_default()
+                .into(),
             ExpressionVariable::EnvFrom => self.ctx.output.env_from_addr.address.as_str().into(),
             ExpressionVariable::EnvFromLocal => {
                 self.ctx.output.env_from_addr.local_part.as_str().into()
@@ -69,6 +83,14 @@ impl<T: ResolveVariable> ResolveVariable for SpamFilterResolver<'_, T> {
                 .map(|e| Variable::from(e.address.as_str()))
                 .collect::<Vec<_>>()
                 .into(),
+            ExpressionVariable::EnvToDetail => self
+                .ctx
+                .input
+                .env_rcpt_to_detail
+                .iter()
+                .map(|detail| Variable::from(*detail))
+                .collect::<Vec<_>>()
+                .into(),
             ExpressionVariable::From => self.ctx.output.from.email.address.as_str().into(),
             ExpressionVariable::FromName => self
                 .ctx
@@ -9,4 +9,5 @@ pub mod dnsbl;
 pub mod expression;
 pub mod html;
 pub mod pyzor;
+pub mod rspamd;
 pub mod sanitize;
@@ -0,0 +1,124 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::SpamFilterContext;
+use common::config::mailstore::spamfilter::RspamdConfig;
+use serde::Deserialize;
+use std::collections::HashMap;
+use utils::HttpLimitResponse;
+
+const MAX_RESPONSE_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RspamdResult {
+    pub score: f32,
+    pub action: String,
+    pub symbols: Vec<(String, f32)>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RspamdResponse {
+    #[serde(default)]
+    is_skipped: bool,
+    #[serde(default)]
+    score: f32,
+    #[serde(default)]
+    action: String,
+    #[serde(default)]
+    symbols: HashMap<String, RspamdSymbol>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RspamdSymbol {
+    #[serde(default)]
+    score: f32,
+}
+
+pub(crate) async fn rspamd_check(
+    ctx: &SpamFilterContext<'_>,
+    config: &RspamdConfig,
+) -> trc::Result<Option<RspamdResult>> {
+    // Pass the envelope and session details using the rspamd protocol headers
+    let input = &ctx.input;
+    let mut request = config
+        .client
+        .post(&config.url)
+        .header("IP", input.remote_ip.to_string())
+        .header("From", input.env_from);
+    if let Some(helo) = input.ehlo_domain.filter(|helo| !helo.is_empty()) {
+        request = request.header("Helo", helo);
+    }
+    if let Some(ptr) = &ctx.output.iprev_ptr {
+        request = request.header("Hostname", ptr.as_str());
+    }
+    if let Some(user) = input.authenticated_as {
+        request = request.header("User", user);
+    }
+    for rcpt in &input.env_rcpt_to {
+        request = request.header("Rcpt", *rcpt);
+    }
+
+    let response = request
+        .body(input.message.raw_message().to_vec())
+        .send()
+        .await
+        .map_err(|err| {
+            trc::SpamEvent::RspamdError
+                .into_err()
+                .ctx(trc::Key::Url, config.url.clone())
+                .reason(err)
+                .details("Request failed")
+        })?;
+
+    if !response.status().is_success() {
+        return Err(trc::SpamEvent::RspamdError
+            .into_err()
+            .ctx(trc::Key::Url, config.url.clone())
+            .ctx(trc::Key::Code, response.status().as_u16())
+            .details("Unexpected response status"));
+    }
+
+    let bytes = response
+        .bytes_with_limit(MAX_RESPONSE_SIZE)
+        .await
+        .map_err(|err| {
+            trc::SpamEvent::RspamdError
+                .into_err()
+                .ctx(trc::Key::Url, config.url.clone())
+                .reason(err)
+                .details("Failed to read response")
+        })?
+        .ok_or_else(|| {
+            trc::SpamEvent::RspamdError
+                .into_err()
+                .ctx(trc::Key::Url, config.url.clone())
+                .details("Response too large")
+        })?;
+    let response = serde_json::from_slice::<RspamdResponse>(&bytes).map_err(|err| {
+        trc::SpamEvent::RspamdError
+            .into_err()
+            .ctx(trc::Key::Url, config.url.clone())
+            .reason(err)
+            .details("Failed to parse response")
+    })?;
+
+    if !response.is_skipped {
+        let mut symbols = response
+            .symbols
+            .into_iter()
+            .map(|(name, symbol)| (name, symbol.score))
+            .collect::<Vec<_>>();
+        symbols.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+        Ok(Some(RspamdResult {
+            score: response.score,
+            action: response.action,
+            symbols,
+        }))
+    } else {
+        Ok(None)
+    }
+}
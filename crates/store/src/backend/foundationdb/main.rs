@@ -5,7 +5,7 @@
  */
 
 use super::FdbStore;
-use crate::Store;
+use crate::{Store, dispatch::cache::ReadCache};
 use foundationdb::{Database, api, options::DatabaseOption};
 use registry::schema::structs;
 use std::sync::Arc;
@@ -52,6 +52,7 @@ impl FdbStore {
             guard,
             db,
             version: Default::default(),
+            read_cache: config.read_cache_size.map(ReadCache::new),
         })))
     }
 }
@@ -4,6 +4,7 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+use crate::dispatch::cache::ReadCache;
 use foundationdb::{Database, FdbError, api::NetworkAutoStop};
 use std::time::{Duration, Instant};
 
@@ -20,6 +21,7 @@ pub struct FdbStore {
     db: Database,
     guard: NetworkAutoStop,
     version: parking_lot::Mutex<ReadVersion>,
+    pub(crate) read_cache: Option<ReadCache>,
 }
 
 pub(crate) struct ReadVersion {
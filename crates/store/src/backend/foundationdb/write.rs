@@ -182,6 +182,7 @@ impl FdbStore {
                                         | ValueClass::Queue(_)
                                         | ValueClass::Registry(RegistryClass::Item { .. })
                                         | ValueClass::ShareNotification { .. }
+                                        | ValueClass::AuditLog(_)
                                         | ValueClass::Telemetry(TelemetryClass::Metric { .. })
                                         | ValueClass::TaskQueue(TaskQueueClass::Task { .. })
                                         | ValueClass::InMemory(_)
@@ -7,6 +7,7 @@
 use super::{PostgresStore, into_error};
 use crate::{
     backend::postgres::{PsqlSearchField, into_pool_error, tls::MakeRustlsConnect},
+    dispatch::cache::ReadCache,
     search::{
         CalendarSearchField, ContactSearchField, EmailSearchField, SearchableField,
         TracingSearchField,
@@ -60,6 +61,7 @@ impl PostgresStore {
                     cfg.create_pool(Some(Runtime::Tokio1), NoTls)
                 }
                 .map_err(|e| format!("Failed to create connection pool: {e}"))?,
+                read_cache: None,
             })));
         }
 
@@ -73,6 +75,7 @@ impl PostgresStore {
                 cfg.create_pool(Some(Runtime::Tokio1), NoTls)
             }
             .map_err(|e| format!("Failed to create connection pool: {e}"))?,
+            read_cache: config.read_cache_size.map(ReadCache::new),
         }));
 
         // SPDX-SnippetBegin
@@ -5,6 +5,7 @@
  */
 
 use crate::{
+    dispatch::cache::ReadCache,
     search::{
         CalendarSearchField, ContactSearchField, EmailSearchField, FileSearchField, SearchField,
         TracingSearchField,
@@ -23,6 +24,7 @@ pub mod write;
 
 pub struct PostgresStore {
     pub(crate) conn_pool: Pool,
+    pub(crate) read_cache: Option<ReadCache>,
 }
 
 #[inline(always)]
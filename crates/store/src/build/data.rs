@@ -7,7 +7,7 @@
 use crate::{Store, registry::bootstrap::Bootstrap};
 use registry::schema::{
     prelude::ObjectType,
-    structs::{DataStore, MetricsStore, TracingStore},
+    structs::{DataStore, MetricsStore, QueueStore, TracingStore},
 };
 
 #[allow(unreachable_patterns)]
@@ -63,6 +63,25 @@ impl Store {
         }
     }
 
+    pub async fn build_queue(bp: &mut Bootstrap) -> Option<Self> {
+        let config = match bp.setting_infallible::<QueueStore>().await {
+            QueueStore::Default => return None,
+            QueueStore::RocksDb(store) => DataStore::RocksDb(store),
+            QueueStore::Sqlite(store) => DataStore::Sqlite(store),
+            QueueStore::FoundationDb(store) => DataStore::FoundationDb(store),
+            QueueStore::PostgreSql(store) => DataStore::PostgreSql(store),
+            QueueStore::MySql(store) => DataStore::MySql(store),
+        };
+
+        match Self::build(config).await {
+            Ok(store) => Some(store),
+            Err(err) => {
+                bp.build_error(ObjectType::QueueStore.singleton(), err);
+                None
+            }
+        }
+    }
+
     pub async fn build_metrics(bp: &mut Bootstrap) -> Option<Self> {
         let result = match bp.setting_infallible::<MetricsStore>().await {
             MetricsStore::Disabled => Ok(None),
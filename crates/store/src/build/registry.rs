@@ -319,6 +319,7 @@ impl RegistryStore {
             env_push_shard_id: push_shard_id,
             env_hostname: hostname,
             id_generator: utils::snowflake::SnowflakeIdGenerator::new(),
+            overlay: None,
         })
         .await
         .unwrap()
@@ -0,0 +1,265 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    Deserialize, Key, SUBSPACE_PROPERTY, Store, WITH_SUBSPACE,
+    write::{Batch, Operation},
+};
+use std::{
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+use trc::StoreEvent;
+use utils::cache::CacheWithTtl;
+
+// Bounds how long an entry can survive a cluster invalidation that was lost
+const READ_CACHE_TTL: Duration = Duration::from_secs(300);
+
+pub type ReadCacheListener = Box<dyn Fn(ReadCacheEviction) + Sync + Send>;
+
+pub struct ReadCache {
+    entries: CacheWithTtl<Box<[u8]>, Arc<[u8]>>,
+    generation: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    listener: OnceLock<ReadCacheListener>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReadCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadCacheEviction {
+    Keys(Vec<Box<[u8]>>),
+    All,
+}
+
+pub(crate) struct RawValue(pub Vec<u8>);
+
+impl ReadCache {
+    pub fn new(size: u64) -> Self {
+        ReadCache {
+            entries: CacheWithTtl::new(size, 1024),
+            generation: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            listener: OnceLock::new(),
+        }
+    }
+
+    // Registers the callback that propagates local evictions to other nodes
+    pub fn set_listener(&self, listener: ReadCacheListener) {
+        let _ = self.listener.set(listener);
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_cacheable(key: &impl Key) -> bool {
+        key.subspace() == SUBSPACE_PROPERTY
+    }
+
+    pub(crate) fn key(key: &impl Key) -> Box<[u8]> {
+        key.serialize(WITH_SUBSPACE).into_boxed_slice()
+    }
+
+    pub(crate) fn get(&self, key: &[u8]) -> Option<Arc<[u8]>> {
+        let result = self.entries.get(key);
+        if result.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            trc::event!(Store(StoreEvent::ReadCacheHit));
+        } else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            trc::event!(Store(StoreEvent::ReadCacheMiss));
+        }
+        result
+    }
+
+    pub fn stats(&self) -> ReadCacheStats {
+        ReadCacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    // Values read before a concurrent eviction are discarded, otherwise
+    // they could overwrite the eviction with a stale copy
+    pub(crate) fn insert(&self, key: Box<[u8]>, value: Arc<[u8]>, generation: u64) {
+        if self.generation() == generation {
+            self.entries.insert(key, value, READ_CACHE_TTL);
+        }
+    }
+
+    // Returns the cached keys written by the batch and the ones it asserts
+    pub(crate) fn batch_keys(batch: &Batch<'_>) -> (Vec<Box<[u8]>>, Vec<Box<[u8]>>) {
+        let mut written = Vec::new();
+        let mut asserted = Vec::new();
+        let mut account_id = u32::MAX;
+        let mut collection = u8::MAX;
+        let mut document_id = u32::MAX;
+
+        for op in batch.ops.iter() {
+            let (class, keys) = match op {
+                Operation::AccountId {
+                    account_id: account_id_,
+                } => {
+                    account_id = *account_id_;
+                    continue;
+                }
+                Operation::Collection {
+                    collection: collection_,
+                } => {
+                    collection = u8::from(*collection_);
+                    continue;
+                }
+                Operation::DocumentId {
+                    document_id: document_id_,
+                } => {
+                    document_id = *document_id_;
+                    continue;
+                }
+                Operation::Value { class, .. } => (class, &mut written),
+                Operation::AssertValue { class, .. } => (class, &mut asserted),
+                Operation::Index { .. } | Operation::Log { .. } => continue,
+            };
+
+            if class.subspace(collection) == SUBSPACE_PROPERTY {
+                keys.push(
+                    class
+                        .serialize(account_id, collection, document_id, WITH_SUBSPACE)
+                        .into_boxed_slice(),
+                );
+            }
+        }
+
+        (written, asserted)
+    }
+
+    pub fn evict(&self, eviction: ReadCacheEviction) {
+        self.evict_local(&eviction);
+        if let Some(listener) = self.listener.get() {
+            listener(eviction);
+        }
+    }
+
+    pub fn evict_local(&self, eviction: &ReadCacheEviction) {
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        match eviction {
+            ReadCacheEviction::Keys(keys) => {
+                for key in keys {
+                    self.entries.remove(key.as_ref());
+                }
+            }
+            ReadCacheEviction::All => {
+                self.entries.clear();
+            }
+        }
+    }
+}
+
+impl Store {
+    pub fn read_cache(&self) -> Option<&ReadCache> {
+        match self {
+            #[cfg(feature = "foundation")]
+            Self::FoundationDb(store) => store.read_cache.as_ref(),
+            #[cfg(feature = "postgres")]
+            Self::PostgreSQL(store) => store.read_cache.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl Deserialize for RawValue {
+    fn deserialize(bytes: &[u8]) -> trc::Result<Self> {
+        Ok(RawValue(bytes.to_vec()))
+    }
+
+    fn deserialize_owned(bytes: Vec<u8>) -> trc::Result<Self> {
+        Ok(RawValue(bytes))
+    }
+
+    fn deserialize_owned_with_key(_: &[u8], bytes: Vec<u8>) -> trc::Result<Self> {
+        Ok(RawValue(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ValueKey,
+        write::{BatchBuilder, ValueClass},
+    };
+    use std::sync::Mutex;
+    use types::collection::Collection;
+
+    #[test]
+    fn read_cache() {
+        let cache = ReadCache::new(1024 * 1024);
+        let key = ReadCache::key(&ValueKey::property(1, Collection::Email, 2, 0u8));
+        let other_key = ReadCache::key(&ValueKey::property(1, Collection::Email, 2, 1u8));
+
+        // Hits and misses are counted
+        assert!(cache.get(&key).is_none());
+        cache.insert(
+            key.clone(),
+            Arc::from(b"value".as_slice()),
+            cache.generation(),
+        );
+        assert_eq!(cache.get(&key).as_deref(), Some(b"value".as_slice()));
+        assert_eq!(cache.stats(), ReadCacheStats { hits: 1, misses: 1 });
+
+        // Values read before an eviction are not cached
+        let generation = cache.generation();
+        cache.evict_local(&ReadCacheEviction::Keys(vec![key.clone()]));
+        cache.insert(key.clone(), Arc::from(b"stale".as_slice()), generation);
+        assert!(cache.get(&key).is_none());
+
+        // Written and asserted keys match the ones used for reads
+        let mut batch = BatchBuilder::new();
+        batch
+            .with_account_id(1)
+            .with_collection(Collection::Email)
+            .with_document(2)
+            .assert_value(ValueClass::Property(1), 0u64)
+            .set(ValueClass::Property(0), b"value".to_vec());
+        let (written, asserted) = ReadCache::batch_keys(&batch.build_all());
+        assert_eq!(written, vec![key.clone()]);
+        assert_eq!(asserted, vec![other_key.clone()]);
+
+        // Evictions are sent to the other nodes, local ones are not
+        let evictions = Arc::new(Mutex::new(Vec::new()));
+        let evictions_ = evictions.clone();
+        cache.set_listener(Box::new(move |eviction| {
+            evictions_.lock().unwrap().push(eviction);
+        }));
+        for key in [&key, &other_key] {
+            cache.insert(
+                key.clone(),
+                Arc::from(b"value".as_slice()),
+                cache.generation(),
+            );
+        }
+        cache.evict(ReadCacheEviction::Keys(vec![key.clone()]));
+        assert!(cache.get(&key).is_none());
+        assert!(cache.get(&other_key).is_some());
+        cache.evict_local(&ReadCacheEviction::All);
+        assert!(cache.get(&other_key).is_none());
+        assert_eq!(
+            *evictions.lock().unwrap(),
+            vec![ReadCacheEviction::Keys(vec![key])]
+        );
+    }
+}
@@ -4,7 +4,10 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
-use super::DocumentSet;
+use super::{
+    DocumentSet,
+    cache::{RawValue, ReadCache, ReadCacheEviction},
+};
 use crate::{
     Deserialize, IterateParams, Key, QueryResult, SUBSPACE_COUNTER, SUBSPACE_INDEXES,
     SUBSPACE_LOGS, Store, U32_LEN, Value, ValueKey,
@@ -14,12 +17,42 @@ use crate::{
     },
 };
 use compact_str::ToCompactString;
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 use trc::{AddContext, StoreEvent};
 use types::collection::Collection;
 
 impl Store {
     pub async fn get_value<U>(&self, key: impl Key) -> trc::Result<Option<U>>
+    where
+        U: Deserialize + 'static,
+    {
+        if let Some(cache) = self.read_cache()
+            && ReadCache::is_cacheable(&key)
+        {
+            let cache_key = ReadCache::key(&key);
+            if let Some(bytes) = cache.get(&cache_key) {
+                return U::deserialize_with_key(&cache_key[1..], &bytes)
+                    .map(Some)
+                    .caused_by(trc::location!());
+            }
+
+            let generation = cache.generation();
+            return match self.get_value_uncached::<RawValue>(key).await? {
+                Some(RawValue(bytes)) => {
+                    let bytes = Arc::<[u8]>::from(bytes);
+                    let value = U::deserialize_with_key(&cache_key[1..], &bytes)
+                        .caused_by(trc::location!())?;
+                    cache.insert(cache_key, bytes, generation);
+                    Ok(Some(value))
+                }
+                None => Ok(None),
+            };
+        }
+
+        self.get_value_uncached(key).await
+    }
+
+    async fn get_value_uncached<U>(&self, key: impl Key) -> trc::Result<Option<U>>
     where
         U: Deserialize + 'static,
     {
@@ -163,6 +196,9 @@ impl Store {
     pub async fn write(&self, batch: Batch<'_>) -> trc::Result<AssignedIds> {
         let start_time = Instant::now();
         let ops = batch.ops.len();
+        let eviction = self
+            .read_cache()
+            .map(|cache| (cache, ReadCache::batch_keys(&batch)));
 
         let result = match self {
             #[cfg(feature = "sqlite")]
@@ -185,6 +221,20 @@ impl Store {
             Self::None => Err(trc::StoreEvent::NotConfigured.into()),
         };
 
+        if let Some((cache, (written, asserted))) = eviction {
+            // Evict regardless of the outcome, a failed commit may still have been applied
+            if !written.is_empty() {
+                cache.evict(ReadCacheEviction::Keys(written));
+            }
+
+            // A failed assertion may have been caused by a stale cached value whose
+            // invalidation never reached this node, the retry has to read from the store
+            if !asserted.is_empty() && result.as_ref().is_err_and(|err| err.is_assertion_failure())
+            {
+                cache.evict_local(&ReadCacheEviction::Keys(asserted));
+            }
+        }
+
         trc::event!(
             Store(StoreEvent::DataWrite),
             Elapsed = start_time.elapsed(),
@@ -239,7 +289,9 @@ impl Store {
     }
 
     pub async fn delete_range(&self, from: impl Key, to: impl Key) -> trc::Result<()> {
-        match self {
+        let eviction = self.read_cache().filter(|_| ReadCache::is_cacheable(&from));
+
+        let result = match self {
             #[cfg(feature = "sqlite")]
             Self::SQLite(store) => store.delete_range(from, to).await,
             #[cfg(feature = "foundation")]
@@ -259,7 +311,13 @@ impl Store {
             // SPDX-SnippetEnd
             Self::None => Err(trc::StoreEvent::NotConfigured.into()),
         }
-        .caused_by(trc::location!())
+        .caused_by(trc::location!());
+
+        if let Some(cache) = eviction {
+            cache.evict(ReadCacheEviction::All);
+        }
+
+        result
     }
 
     pub async fn delete_documents(
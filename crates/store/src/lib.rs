@@ -218,6 +218,7 @@ pub struct RegistryStoreInner {
     pub(crate) env_push_shard_id: u32,
     pub(crate) env_hostname: String,
     pub(crate) id_generator: SnowflakeIdGenerator,
+    pub(crate) overlay: Option<Arc<crate::registry::RegistryOverlay>>,
 }
 
 #[cfg(feature = "sqlite")]
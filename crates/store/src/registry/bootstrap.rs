@@ -5,6 +5,7 @@
  */
 
 use crate::{RegistryStore, Store, registry::RegistryObject};
+use ahash::{AHashMap, AHashSet};
 use registry::{
     schema::{
         prelude::{Object, ObjectType, Property},
@@ -25,6 +26,8 @@ pub struct Bootstrap {
     pub warnings: Vec<Warning>,
     pub has_fatal_errors: bool,
     pub role: Option<ClusterRole>,
+    pub revisions: AHashMap<ObjectId, u64>,
+    pub listed: AHashSet<ObjectType>,
 }
 
 impl Bootstrap {
@@ -58,13 +61,20 @@ impl Bootstrap {
             warnings: Vec::new(),
             has_fatal_errors: false,
             role: None,
+            revisions: AHashMap::new(),
+            listed: AHashSet::new(),
         }
     }
 
     pub async fn setting<T: ObjectImpl + From<Object>>(&mut self) -> trc::Result<T> {
         let object_id = T::OBJECT.singleton();
+        let object = self.registry.get(object_id).await?;
+        self.listed.insert(T::OBJECT);
+        if let Some(object) = &object {
+            self.revisions.insert(object_id, object.revision);
+        }
 
-        if let Some(setting) = self.registry.object::<T>(object_id.id()).await? {
+        if let Some(setting) = object.map(T::from) {
             let mut errors = Vec::new();
             if setting.validate(&mut errors) {
                 return Ok(setting);
@@ -92,8 +102,11 @@ impl Bootstrap {
     }
 
     pub async fn get_infallible<T: ObjectImpl + From<Object>>(&mut self, id: Id) -> Option<T> {
-        match self.registry.object::<T>(id).await {
-            Ok(Some(setting)) => {
+        let object_id = ObjectId::new(T::OBJECT, id);
+        match self.registry.get(object_id).await {
+            Ok(Some(object)) => {
+                self.revisions.insert(object_id, object.revision);
+                let setting = T::from(object);
                 let mut errors = Vec::new();
                 if setting.validate(&mut errors) {
                     Some(setting)
@@ -128,10 +141,17 @@ impl Bootstrap {
         &mut self,
     ) -> Vec<RegistryObject<T>> {
         match self.registry.list::<T>().await {
-            Ok(objects) => objects
-                .into_iter()
-                .filter(|object| self.validate(object.id, &object.object))
-                .collect(),
+            Ok(objects) => {
+                self.listed.insert(T::OBJECT);
+                for object in &objects {
+                    self.revisions.insert(object.id, object.revision);
+                }
+
+                objects
+                    .into_iter()
+                    .filter(|object| self.validate(object.id, &object.object))
+                    .collect()
+            }
             Err(err) => {
                 if !self.has_fatal_errors {
                     self.errors.push(Error::Internal {
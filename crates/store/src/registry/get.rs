@@ -6,7 +6,7 @@
 
 use crate::{
     IterateParams, RegistryStore, SUBSPACE_REGISTRY, U16_LEN, U64_LEN, ValueKey,
-    registry::{RegistryObject, local::RegistryInit},
+    registry::{RegistryObject, RegistryOverlay, local::RegistryInit},
     write::{
         AnyClass, RegistryClass, ValueClass,
         key::{DeserializeBigEndian, KeySerializer},
@@ -17,12 +17,26 @@ use registry::{
     schema::prelude::{Object, ObjectType},
     types::{EnumImpl, ObjectImpl, id::ObjectId},
 };
+use std::sync::Arc;
 use trc::AddContext;
 use types::id::Id;
 
 impl RegistryStore {
+    pub fn with_overlay(&self, overlay: RegistryOverlay) -> Self {
+        let mut inner = self.0.as_ref().clone();
+        inner.overlay = Some(Arc::new(overlay));
+        RegistryStore(Arc::new(inner))
+    }
+
     pub async fn get(&self, object_id: ObjectId) -> trc::Result<Option<Object>> {
-        if object_id.object() != ObjectType::DataStore {
+        if let Some(object) = self
+            .0
+            .overlay
+            .as_ref()
+            .and_then(|overlay| overlay.get(&object_id))
+        {
+            Ok(object.clone())
+        } else if object_id.object() != ObjectType::DataStore {
             self.0
                 .store
                 .get_value::<Object>(ValueKey::from(ValueClass::Registry(RegistryClass::Item {
@@ -100,6 +114,20 @@ impl RegistryStore {
             .await
             .caused_by(trc::location!())?;
 
+        // Apply uncommitted changes
+        if let Some(overlay) = &self.0.overlay {
+            results.retain(|result| overlay.get(&result.id).is_none());
+            for (object_id, object) in overlay.objects(object_type) {
+                if let Some(object) = object {
+                    results.push(RegistryObject {
+                        id: *object_id,
+                        object: T::from(object.clone()),
+                        revision: 0,
+                    });
+                }
+            }
+        }
+
         Ok(results)
     }
 }
@@ -21,6 +21,7 @@ impl RegistryStoreInner {
             local_path,
             store: Store::None,
             id_generator: SnowflakeIdGenerator::new(),
+            overlay: None,
             node_id: 0,
             env_recovery_mode: std::env::var("STALWART_RECOVERY_MODE")
                 .ok()
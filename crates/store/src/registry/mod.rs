@@ -12,8 +12,10 @@ pub mod write;
 
 use crate::{
     Deserialize, SerializeInfallible, U16_LEN, U32_LEN, U64_LEN,
+    registry::write::serialize_composite_key,
     write::key::{DeserializeBigEndian, KeySerializer},
 };
+use ahash::AHashMap;
 use registry::{
     pickle::{Pickle, PickledStream},
     schema::{
@@ -23,7 +25,11 @@ use registry::{
             Trace,
         },
     },
-    types::{EnumImpl, ObjectImpl, id::ObjectId},
+    types::{
+        EnumImpl, ObjectImpl,
+        id::ObjectId,
+        index::{IndexBuilder, IndexKey},
+    },
 };
 use types::id::Id;
 
@@ -51,7 +57,7 @@ pub(crate) enum RegistryQueryStart {
     None,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct RegistryFilter {
     pub property: Property,
     pub op: RegistryFilterOp,
@@ -75,7 +81,7 @@ pub enum RegistryFilterOp {
     TextMatch,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum RegistryFilterValue {
     String(String),
     Bytes(Vec<u8>),
@@ -84,6 +90,87 @@ pub enum RegistryFilterValue {
     Boolean(bool),
 }
 
+// Uncommitted changes layered on top of the stored registry objects
+#[derive(Debug, Default)]
+pub struct RegistryOverlay(AHashMap<ObjectId, Option<Object>>);
+
+impl RegistryOverlay {
+    pub fn set(&mut self, object_id: ObjectId, object: Object) {
+        self.0.insert(object_id, Some(object));
+    }
+
+    pub fn remove(&mut self, object_id: ObjectId) {
+        self.0.insert(object_id, None);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn get(&self, object_id: &ObjectId) -> Option<&Option<Object>> {
+        self.0.get(object_id)
+    }
+
+    pub(crate) fn objects(
+        &self,
+        object_type: ObjectType,
+    ) -> impl Iterator<Item = (&ObjectId, &Option<Object>)> {
+        self.0
+            .iter()
+            .filter(move |(object_id, _)| object_id.object() == object_type)
+    }
+
+    // Returns the uncommitted object holding a primary key, other than the excluded one
+    pub(crate) fn primary_key(
+        &self,
+        object_type: Option<ObjectType>,
+        property: Property,
+        key: &[u8],
+        exclude: Option<ObjectId>,
+    ) -> Option<ObjectId> {
+        self.0.iter().find_map(|(object_id, object)| {
+            let object = object.as_ref()?;
+            if Some(*object_id) == exclude
+                || object_type.is_some_and(|object_type| object_type != object_id.object())
+            {
+                return None;
+            }
+
+            let mut index = IndexBuilder::default();
+            object.index(&mut index);
+            index
+                .keys
+                .iter()
+                .any(|index_key| {
+                    matches!(index_key, IndexKey::Unique {
+                        property: index_property,
+                        value_1,
+                        value_2,
+                        global,
+                    } if *index_property == property
+                        && *global == object_type.is_none()
+                        && serialize_composite_key(value_1, value_2) == key)
+                })
+                .then_some(*object_id)
+        })
+    }
+
+    // Returns the uncommitted objects holding a foreign key to the given object
+    pub(crate) fn references(&self, to_object_id: ObjectId) -> impl Iterator<Item = ObjectId> {
+        self.0.iter().filter_map(move |(object_id, object)| {
+            let mut index = IndexBuilder::default();
+            object.as_ref()?.index(&mut index);
+            index
+                .keys
+                .iter()
+                .any(|index_key| {
+                    matches!(index_key, IndexKey::ForeignKey { object_id, .. } if *object_id == to_object_id)
+                })
+                .then_some(*object_id)
+        })
+    }
+}
+
 impl Deserialize for Object {
     fn deserialize_with_key(key: &[u8], bytes: &[u8]) -> trc::Result<Self> {
         let revision = xxhash_rust::xxh3::xxh3_64(bytes);
@@ -5,11 +5,11 @@
  */
 
 use crate::{
-    IterateParams, RegistryStore, SUBSPACE_REGISTRY_IDX, SUBSPACE_REGISTRY_PK, Store, U16_LEN,
-    U64_LEN, ValueKey,
+    IterateParams, RegistryStore, SUBSPACE_REGISTRY_IDX, SUBSPACE_REGISTRY_PK, SerializeInfallible,
+    Store, U16_LEN, U64_LEN, ValueKey,
     registry::{
         RegistryFilter, RegistryFilterOp, RegistryFilterValue, RegistryObjectCounter,
-        RegistryQuery, RegistryQueryStart,
+        RegistryOverlay, RegistryQuery, RegistryQueryStart, write::serialize_composite_key,
     },
     write::{
         AnyClass, RegistryClass, ValueClass,
@@ -18,16 +18,70 @@ use crate::{
 };
 use ahash::AHashSet;
 use registry::{
-    schema::prelude::{OBJ_FILTER_ACCOUNT, OBJ_FILTER_TENANT, OBJ_SINGLETON, ObjectType, Property},
-    types::{EnumImpl, id::ObjectId},
+    schema::prelude::{
+        OBJ_FILTER_ACCOUNT, OBJ_FILTER_TENANT, OBJ_SINGLETON, Object, ObjectType, Property,
+    },
+    types::{
+        EnumImpl,
+        id::ObjectId,
+        index::{IndexBuilder, IndexKey},
+    },
 };
 use roaring::RoaringBitmap;
-use std::{borrow::Cow, ops::BitAndAssign};
+use std::{borrow::Cow, collections::BTreeSet, ops::BitAndAssign};
 use trc::AddContext;
 use types::id::Id;
 
 impl RegistryStore {
     pub async fn query<T: RegistryQueryResults>(&self, query: RegistryQuery) -> trc::Result<T> {
+        if let Some(overlay) = &self.0.overlay
+            && overlay.objects(query.object_type).next().is_some()
+        {
+            self.query_overlay(overlay, query).await
+        } else {
+            self.query_store(query).await
+        }
+    }
+
+    // Queries the stored objects without pagination, replaces the ones changed by the
+    // overlay with their uncommitted versions and then paginates the results
+    async fn query_overlay<T: RegistryQueryResults>(
+        &self,
+        overlay: &RegistryOverlay,
+        query: RegistryQuery,
+    ) -> trc::Result<T> {
+        let object_type = query.object_type;
+        let mut ids = self
+            .query_store::<Vec<Id>>(RegistryQuery {
+                object_type,
+                filters: query.filters.clone(),
+                start: RegistryQueryStart::None,
+                limit: None,
+            })
+            .await?
+            .into_iter()
+            .filter(|id| overlay.get(&ObjectId::new(object_type, *id)).is_none())
+            .map(|id| id.id())
+            .collect::<BTreeSet<_>>();
+        for (object_id, object) in overlay.objects(object_type) {
+            if let Some(object) = object
+                && object_matches(object, &query.filters)
+            {
+                ids.insert(object_id.id().id());
+            }
+        }
+
+        let mut results = ResultsPagination::<T>::new(&query);
+        results.deferred_pagination = false;
+        for id in ids {
+            if !results.push(id) {
+                break;
+            }
+        }
+        Ok(results.list)
+    }
+
+    async fn query_store<T: RegistryQueryResults>(&self, query: RegistryQuery) -> trc::Result<T> {
         if query.filters.is_empty() {
             return all_ids::<T>(&self.0.store, query).await;
         }
@@ -148,6 +202,13 @@ impl RegistryStore {
             self.query::<RegistryObjectCounter>(RegistryQuery::new(object_type))
                 .await
                 .map(|r| r.0)
+        } else if let Some(object) = self
+            .0
+            .overlay
+            .as_ref()
+            .and_then(|overlay| overlay.get(&ObjectId::new(object_type, Id::singleton())))
+        {
+            Ok(object.is_some() as usize)
         } else {
             self.store()
                 .key_exists(ValueKey::from(RegistryClass::Item {
@@ -165,6 +226,25 @@ impl RegistryStore {
         object_type: Option<ObjectType>,
         property: Property,
         key: Vec<u8>,
+    ) -> trc::Result<Option<ObjectId>> {
+        let Some(overlay) = &self.0.overlay else {
+            return self.primary_key_store(object_type, property, key).await;
+        };
+
+        if let Some(object_id) = overlay.primary_key(object_type, property, &key, None) {
+            Ok(Some(object_id))
+        } else {
+            self.primary_key_store(object_type, property, key)
+                .await
+                .map(|object_id| object_id.filter(|object_id| overlay.get(object_id).is_none()))
+        }
+    }
+
+    async fn primary_key_store(
+        &self,
+        object_type: Option<ObjectType>,
+        property: Property,
+        key: Vec<u8>,
     ) -> trc::Result<Option<ObjectId>> {
         self.store()
             .get_value::<ObjectId>(ValueKey::from(ValueClass::Registry(
@@ -492,6 +572,67 @@ async fn pk_range<T: RegistryQueryResults>(
         .inspect(|_| results.list.sort())
 }
 
+// Evaluates the query filters against the index keys of an uncommitted object
+fn object_matches(object: &Object, filters: &[RegistryFilter]) -> bool {
+    let mut index = IndexBuilder::default();
+    object.index(&mut index);
+
+    filters.iter().all(|filter| {
+        let values = index
+            .keys
+            .iter()
+            .filter_map(|key| match key {
+                IndexKey::Search { property, value }
+                    if !filter.is_pk && *property == filter.property =>
+                {
+                    Some(value.serialize())
+                }
+                IndexKey::Unique {
+                    property,
+                    value_1,
+                    value_2,
+                    ..
+                } if filter.is_pk && *property == filter.property => {
+                    Some(serialize_composite_key(value_1, value_2))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        match (filter.op, &filter.value) {
+            (RegistryFilterOp::TextMatch, RegistryFilterValue::String(text)) => text
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|s| s.len() > 1)
+                .all(|word| {
+                    let word = word.to_lowercase();
+                    values.iter().any(|value| value == word.as_bytes())
+                }),
+            (op, match_value) => {
+                let match_value = match_value.serialize();
+                values.iter().any(|value| match op {
+                    RegistryFilterOp::LowerThan => *value < match_value,
+                    RegistryFilterOp::LowerEqualThan => *value <= match_value,
+                    RegistryFilterOp::GreaterThan => *value > match_value,
+                    RegistryFilterOp::GreaterEqualThan => *value >= match_value,
+                    RegistryFilterOp::Equal | RegistryFilterOp::TextMatch => *value == match_value,
+                })
+            }
+        }
+    })
+}
+
+impl SerializeInfallible for RegistryFilterValue {
+    fn serialize(&self) -> Vec<u8> {
+        match self {
+            RegistryFilterValue::String(v) => v.as_bytes().to_vec(),
+            RegistryFilterValue::Bytes(v) => v.clone(),
+            RegistryFilterValue::U64(v) => v.to_be_bytes().to_vec(),
+            RegistryFilterValue::U16(v) => v.to_be_bytes().to_vec(),
+            RegistryFilterValue::Boolean(v) => vec![*v as u8],
+        }
+    }
+}
+
 pub trait RegistryQueryResults: Default + Sized + Sync + Send {
     fn push(&mut self, id: u64);
     fn has_items(&self) -> bool;
@@ -6,6 +6,7 @@
 
 use crate::{
     IterateParams, RegistryStore, SerializeInfallible, U16_LEN, U64_LEN, ValueKey,
+    registry::RegistryOverlay,
     write::{
         BatchBuilder, RegistryClass, ValueClass,
         assert::AssertValue,
@@ -74,6 +75,39 @@ pub enum RegistryWrite<'x> {
 
 impl RegistryStore {
     pub async fn write(&self, write: RegistryWrite<'_>) -> trc::Result<RegistryWriteResult> {
+        self.write_or_validate(write, None, |_, _| Ok(())).await
+    }
+
+    // Writes the object together with the values added by `extra`, which receives
+    // the id of the written object. It is only called once all checks passed.
+    pub async fn write_with(
+        &self,
+        write: RegistryWrite<'_>,
+        extra: impl FnOnce(Id, &mut BatchBuilder) -> trc::Result<()> + Send,
+    ) -> trc::Result<RegistryWriteResult> {
+        self.write_or_validate(write, None, extra).await
+    }
+
+    // Runs the same checks as a write without modifying the store, no ids are
+    // allocated and inserted objects are reported under a provisional id.
+    // Keys are checked against the stored objects as modified by the overlay.
+    pub async fn validate(
+        &self,
+        write: RegistryWrite<'_>,
+        overlay: &RegistryOverlay,
+    ) -> trc::Result<RegistryWriteResult> {
+        self.write_or_validate(write, Some(overlay), |_, _| Ok(()))
+            .await
+    }
+
+    async fn write_or_validate(
+        &self,
+        write: RegistryWrite<'_>,
+        dry_run: Option<&RegistryOverlay>,
+        extra: impl FnOnce(Id, &mut BatchBuilder) -> trc::Result<()> + Send,
+    ) -> trc::Result<RegistryWriteResult> {
+        let commit = dry_run.is_none();
+        let overlay = dry_run.or(self.0.overlay.as_deref());
         let mut set_index = IndexBuilder::default();
         let mut clear_index = IndexBuilder::default();
 
@@ -149,7 +183,8 @@ impl RegistryStore {
                 allowed_orphan_types,
             } => {
                 return if object_id.object().flags() & OBJ_SINGLETON == 0 {
-                    self.delete(object_id, object, allowed_orphan_types).await
+                    self.delete(object_id, object, allowed_orphan_types, dry_run, extra)
+                        .await
                 } else {
                     Ok(RegistryWriteResult::CannotDeleteSingleton)
                 };
@@ -167,6 +202,8 @@ impl RegistryStore {
         if let ObjectInner::DataStore(data_store) = &object.inner {
             if generate_id {
                 return Ok(RegistryWriteResult::NotSupported);
+            } else if !commit {
+                return Ok(RegistryWriteResult::Success(Id::singleton()));
             }
 
             return self
@@ -199,65 +236,18 @@ impl RegistryStore {
                     type_filter,
                 } => {
                     // Verify that the referenced object exists
-                    let item_id = foreign_id.id().id();
-                    let object_id = foreign_id.object().to_id();
-                    let object_flags = foreign_id.object().flags();
-                    let key = if type_filter != &IndexValue::None {
-                        RegistryClass::Index {
-                            index_id: Property::Type.to_id(),
-                            object_id,
-                            item_id,
-                            key: type_filter.serialize(),
-                        }
+                    let exists = if let Some(foreign) =
+                        overlay.and_then(|overlay| overlay.get(foreign_id))
+                    {
+                        foreign.as_ref().is_some_and(|foreign| {
+                            foreign_key_matches(foreign, type_filter, tenant_id, account_id)
+                        })
                     } else {
-                        RegistryClass::IndexId { object_id, item_id }
+                        self.foreign_key_exists(*foreign_id, type_filter, tenant_id, account_id)
+                            .await?
                     };
 
-                    if !self
-                        .0
-                        .store
-                        .key_exists(ValueKey::from(ValueClass::Registry(key)))
-                        .await
-                        .caused_by(trc::location!())?
-                    {
-                        return Ok(RegistryWriteResult::InvalidForeignKey {
-                            object_id: *foreign_id,
-                        });
-                    } else if let Some(tenant_id) = tenant_id
-                        && (object_flags & OBJ_FILTER_TENANT) != 0
-                        && !self
-                            .0
-                            .store
-                            .key_exists(ValueKey::from(ValueClass::Registry(
-                                RegistryClass::Index {
-                                    index_id: Property::MemberTenantId.to_id(),
-                                    object_id,
-                                    item_id,
-                                    key: IndexValue::U64(tenant_id).serialize(),
-                                },
-                            )))
-                            .await
-                            .caused_by(trc::location!())?
-                    {
-                        return Ok(RegistryWriteResult::InvalidForeignKey {
-                            object_id: *foreign_id,
-                        });
-                    } else if (object_flags & OBJ_FILTER_ACCOUNT) != 0
-                        && let Some(account_id) = account_id
-                        && !self
-                            .0
-                            .store
-                            .key_exists(ValueKey::from(ValueClass::Registry(
-                                RegistryClass::Index {
-                                    index_id: Property::AccountId.to_id(),
-                                    object_id,
-                                    item_id,
-                                    key: IndexValue::U64(account_id).serialize(),
-                                },
-                            )))
-                            .await
-                            .caused_by(trc::location!())?
-                    {
+                    if !exists {
                         return Ok(RegistryWriteResult::InvalidForeignKey {
                             object_id: *foreign_id,
                         });
@@ -269,18 +259,36 @@ impl RegistryStore {
                     value_2,
                     global,
                 } => {
-                    let key = ValueKey::from(ValueClass::Registry(RegistryClass::PrimaryKey {
-                        object_id: (!*global).then_some(object_id),
-                        index_id: property.to_id(),
-                        key: serialize_composite_key(value_1, value_2),
-                    }));
-                    if let Some(existing_id) = self
-                        .0
-                        .store
-                        .get_value::<ObjectId>(key)
-                        .await
-                        .caused_by(trc::location!())?
-                        && existing_id != ObjectId::new(object_type, Id::new(item_id))
+                    let this_id = ObjectId::new(object_type, Id::new(item_id));
+                    let key = serialize_composite_key(value_1, value_2);
+                    let existing_id = match overlay.and_then(|overlay| {
+                        overlay.primary_key(
+                            (!*global).then_some(object_type),
+                            *property,
+                            &key,
+                            Some(this_id),
+                        )
+                    }) {
+                        Some(existing_id) => Some(existing_id),
+                        None => self
+                            .0
+                            .store
+                            .get_value::<ObjectId>(ValueKey::from(ValueClass::Registry(
+                                RegistryClass::PrimaryKey {
+                                    object_id: (!*global).then_some(object_id),
+                                    index_id: property.to_id(),
+                                    key,
+                                },
+                            )))
+                            .await
+                            .caused_by(trc::location!())?
+                            .filter(|existing_id| {
+                                overlay.is_none_or(|overlay| overlay.get(existing_id).is_none())
+                            }),
+                    };
+
+                    if let Some(existing_id) = existing_id
+                        && existing_id != this_id
                     {
                         return Ok(RegistryWriteResult::PrimaryKeyConflict {
                             property: *property,
@@ -293,7 +301,7 @@ impl RegistryStore {
         }
 
         // Assign id
-        if generate_id {
+        if generate_id && commit {
             let mut id_batch = BatchBuilder::new();
             id_batch.add_and_get(
                 ValueClass::Registry(RegistryClass::IdCounter { object_id }),
@@ -320,6 +328,8 @@ impl RegistryStore {
                     ),
                 }],
             });
+        } else if !commit {
+            return Ok(RegistryWriteResult::Success(Id::new(item_id)));
         }
 
         // Build batch
@@ -337,6 +347,7 @@ impl RegistryStore {
                 ValueClass::Registry(RegistryClass::Item { object_id, item_id }),
                 out,
             );
+        extra(Id::new(item_id), &mut batch)?;
 
         self.store()
             .write(batch.build_all())
@@ -349,6 +360,8 @@ impl RegistryStore {
         object_id: ObjectId,
         object: Option<&Object>,
         allowed_orphan_types: &[ObjectType],
+        dry_run: Option<&RegistryOverlay>,
+        extra: impl FnOnce(Id, &mut BatchBuilder) -> trc::Result<()>,
     ) -> trc::Result<RegistryWriteResult> {
         let object_type = object_id.object();
         let object_type_id = object_type.to_id();
@@ -371,7 +384,9 @@ impl RegistryStore {
         object.index(&mut clear_index);
 
         // Validate relationships
-        let mut linked = self.linked_objects(object_id).await?;
+        let mut linked = self
+            .linked_objects_with(object_id, dry_run.or(self.0.overlay.as_deref()))
+            .await?;
         if !linked.is_empty() {
             if !allowed_orphan_types.is_empty() {
                 linked.retain(|object_id| !allowed_orphan_types.contains(&object_id.object()));
@@ -385,6 +400,10 @@ impl RegistryStore {
             }
         }
 
+        if dry_run.is_some() {
+            return Ok(RegistryWriteResult::Success(id));
+        }
+
         // Build deletion batch
         let mut batch = BatchBuilder::new();
         batch
@@ -404,6 +423,7 @@ impl RegistryStore {
                 item_id,
             }))
             .registry_index(object_type_id, item_id, clear_index.keys.iter(), false);
+        extra(id, &mut batch)?;
 
         self.0
             .store
@@ -414,6 +434,15 @@ impl RegistryStore {
     }
 
     pub async fn linked_objects(&self, object_id: ObjectId) -> trc::Result<Vec<ObjectId>> {
+        self.linked_objects_with(object_id, self.0.overlay.as_deref())
+            .await
+    }
+
+    async fn linked_objects_with(
+        &self,
+        object_id: ObjectId,
+        overlay: Option<&RegistryOverlay>,
+    ) -> trc::Result<Vec<ObjectId>> {
         let object_type_id = object_id.object().to_id();
         let item_id = object_id.id().id();
         let mut linked = Vec::new();
@@ -454,8 +483,83 @@ impl RegistryStore {
                 },
             )
             .await
-            .caused_by(trc::location!())
-            .map(|_| linked)
+            .caused_by(trc::location!())?;
+
+        // Apply uncommitted changes
+        if let Some(overlay) = overlay {
+            linked.retain(|linked_id| overlay.get(linked_id).is_none());
+            linked.extend(overlay.references(object_id));
+        }
+
+        Ok(linked)
+    }
+
+    async fn foreign_key_exists(
+        &self,
+        foreign_id: ObjectId,
+        type_filter: &IndexValue<'_>,
+        tenant_id: Option<u64>,
+        account_id: Option<u64>,
+    ) -> trc::Result<bool> {
+        let item_id = foreign_id.id().id();
+        let object_id = foreign_id.object().to_id();
+        let object_flags = foreign_id.object().flags();
+        let key = if type_filter != &IndexValue::None {
+            RegistryClass::Index {
+                index_id: Property::Type.to_id(),
+                object_id,
+                item_id,
+                key: type_filter.serialize(),
+            }
+        } else {
+            RegistryClass::IndexId { object_id, item_id }
+        };
+
+        if !self
+            .0
+            .store
+            .key_exists(ValueKey::from(ValueClass::Registry(key)))
+            .await
+            .caused_by(trc::location!())?
+        {
+            return Ok(false);
+        }
+
+        if let Some(tenant_id) = tenant_id
+            && (object_flags & OBJ_FILTER_TENANT) != 0
+            && !self
+                .0
+                .store
+                .key_exists(ValueKey::from(ValueClass::Registry(RegistryClass::Index {
+                    index_id: Property::MemberTenantId.to_id(),
+                    object_id,
+                    item_id,
+                    key: IndexValue::U64(tenant_id).serialize(),
+                })))
+                .await
+                .caused_by(trc::location!())?
+        {
+            return Ok(false);
+        }
+
+        if (object_flags & OBJ_FILTER_ACCOUNT) != 0
+            && let Some(account_id) = account_id
+            && !self
+                .0
+                .store
+                .key_exists(ValueKey::from(ValueClass::Registry(RegistryClass::Index {
+                    index_id: Property::AccountId.to_id(),
+                    object_id,
+                    item_id,
+                    key: IndexValue::U64(account_id).serialize(),
+                })))
+                .await
+                .caused_by(trc::location!())?
+        {
+            return Ok(false);
+        }
+
+        Ok(true)
     }
 
     #[inline(always)]
@@ -525,7 +629,38 @@ impl BatchBuilder {
     }
 }
 
-fn serialize_composite_key(value_1: &IndexValue<'_>, value_2: &IndexValue<'_>) -> Vec<u8> {
+// Same checks as `foreign_key_exists` against an object that was not written yet
+fn foreign_key_matches(
+    foreign: &Object,
+    type_filter: &IndexValue<'_>,
+    tenant_id: Option<u64>,
+    account_id: Option<u64>,
+) -> bool {
+    let object_flags = foreign.object_type().flags();
+    let mut index = IndexBuilder::default();
+    foreign.index(&mut index);
+    let has_key = |property: Property, value: &IndexValue<'_>| {
+        index.keys.iter().any(|key| {
+            matches!(key, IndexKey::Search { property: key_property, value: key_value }
+                if *key_property == property && key_value.serialize() == value.serialize())
+        })
+    };
+
+    (type_filter == &IndexValue::None || has_key(Property::Type, type_filter))
+        && tenant_id.is_none_or(|tenant_id| {
+            (object_flags & OBJ_FILTER_TENANT) == 0
+                || has_key(Property::MemberTenantId, &IndexValue::U64(tenant_id))
+        })
+        && ((object_flags & OBJ_FILTER_ACCOUNT) == 0
+            || account_id.is_none_or(|account_id| {
+                has_key(Property::AccountId, &IndexValue::U64(account_id))
+            }))
+}
+
+pub(crate) fn serialize_composite_key(
+    value_1: &IndexValue<'_>,
+    value_2: &IndexValue<'_>,
+) -> Vec<u8> {
     let mut key = value_1.serialize();
     match value_2 {
         IndexValue::Text(text) => key.extend_from_slice(text.as_bytes()),
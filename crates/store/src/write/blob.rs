@@ -27,6 +27,14 @@ pub struct BlobQuota {
     pub count: usize,
 }
 
+#[derive(Debug, Default)]
+pub struct BlobStats {
+    pub linked_blobs: u64,
+    pub links: u64,
+    pub expired_links: u64,
+    pub orphaned_blobs: Vec<(BlobHash, usize)>,
+}
+
 impl Store {
     pub async fn blob_exists(&self, hash: impl AsRef<BlobHash> + Sync + Send) -> trc::Result<bool> {
         self.key_exists(ValueKey {
@@ -89,48 +97,7 @@ impl Store {
         let mut total_active = 0;
         let mut total_deleted = 0;
         let started = Instant::now();
-
-        // Validate linked blobs
-        let mut from_hash = BlobHash::default();
-        let mut to_hash = BlobHash::new_max();
-        from_hash.0[0] = shard_index;
-        to_hash.0[0] = shard_index;
-        let from_key = ValueKey {
-            account_id: 0,
-            collection: 0,
-            document_id: 0,
-            class: ValueClass::Blob(BlobOp::Commit { hash: from_hash }),
-        };
-        let to_key = ValueKey {
-            account_id: u32::MAX,
-            collection: u8::MAX,
-            document_id: u32::MAX,
-            class: ValueClass::Blob(BlobOp::Link {
-                hash: to_hash,
-                to: BlobLink::Document,
-            }),
-        };
-
-        let mut state = BlobPurgeState::new();
-        self.iterate(
-            IterateParams::new(from_key, to_key).ascending(),
-            |key, value| {
-                let hash =
-                    BlobHash::try_from_hash_slice(key.get(0..BLOB_HASH_LEN).ok_or_else(|| {
-                        trc::Error::corrupted_key(key, value.into(), trc::location!())
-                    })?)
-                    .unwrap();
-
-                state.update_hash(hash);
-                state.process_key(key, value)?;
-
-                Ok(true)
-            },
-        )
-        .await
-        .caused_by(trc::location!())?;
-
-        state.finalize(BlobHash::default());
+        let state = self.scan_blobs(shard_index).await?;
 
         // Delete expired or unlinked blobs
         for (_, op) in &state.delete_keys {
@@ -203,6 +170,81 @@ impl Store {
 
         Ok(())
     }
+
+    // Reports what a purge of the shard would do without deleting anything,
+    // the size of each orphaned blob is read from the blob store.
+    pub async fn blob_stats(
+        &self,
+        blob_store: &BlobStore,
+        shard_index: u8,
+    ) -> trc::Result<BlobStats> {
+        let state = self.scan_blobs(shard_index).await?;
+        let mut stats = BlobStats {
+            linked_blobs: state.total_active - 1, // Exclude default hash
+            links: state.total_links,
+            expired_links: state.total_expired_links,
+            orphaned_blobs: Vec::new(),
+        };
+
+        for (_, op) in state.delete_keys {
+            if let BlobOp::Commit { hash } = op {
+                let size = blob_store
+                    .get_blob(hash.as_ref(), 0..usize::MAX)
+                    .await
+                    .caused_by(trc::location!())?
+                    .map_or(0, |blob| blob.len());
+                stats.orphaned_blobs.push((hash, size));
+            }
+        }
+
+        Ok(stats)
+    }
+
+    async fn scan_blobs(&self, shard_index: u8) -> trc::Result<BlobPurgeState> {
+        // Validate linked blobs
+        let mut from_hash = BlobHash::default();
+        let mut to_hash = BlobHash::new_max();
+        from_hash.0[0] = shard_index;
+        to_hash.0[0] = shard_index;
+        let from_key = ValueKey {
+            account_id: 0,
+            collection: 0,
+            document_id: 0,
+            class: ValueClass::Blob(BlobOp::Commit { hash: from_hash }),
+        };
+        let to_key = ValueKey {
+            account_id: u32::MAX,
+            collection: u8::MAX,
+            document_id: u32::MAX,
+            class: ValueClass::Blob(BlobOp::Link {
+                hash: to_hash,
+                to: BlobLink::Document,
+            }),
+        };
+
+        let mut state = BlobPurgeState::new();
+        self.iterate(
+            IterateParams::new(from_key, to_key).ascending(),
+            |key, value| {
+                let hash =
+                    BlobHash::try_from_hash_slice(key.get(0..BLOB_HASH_LEN).ok_or_else(|| {
+                        trc::Error::corrupted_key(key, value.into(), trc::location!())
+                    })?)
+                    .unwrap();
+
+                state.update_hash(hash);
+                state.process_key(key, value)?;
+
+                Ok(true)
+            },
+        )
+        .await
+        .caused_by(trc::location!())?;
+
+        state.finalize(BlobHash::default());
+
+        Ok(state)
+    }
 }
 
 struct BlobPurgeState {
@@ -213,6 +255,8 @@ struct BlobPurgeState {
     now: u64,
     total_deleted: u64,
     total_active: u64,
+    total_links: u64,
+    total_expired_links: u64,
 }
 
 impl BlobPurgeState {
@@ -225,6 +269,8 @@ impl BlobPurgeState {
             now: now(),
             total_deleted: 0,
             total_active: 0,
+            total_links: 0,
+            total_expired_links: 0,
         }
     }
 
@@ -264,6 +310,7 @@ impl BlobPurgeState {
                 // Temporary link
                 let until = key.deserialize_be_u64(BLOB_HASH_LEN + U32_LEN)?;
                 if until <= self.now {
+                    self.total_expired_links += 1;
                     let account_id = key.deserialize_be_u32(BLOB_HASH_LEN)?;
                     self.delete_keys.push((
                         Some(account_id),
@@ -277,12 +324,14 @@ impl BlobPurgeState {
                             .push((account_id, ObjectId::deserialize(value)?));
                     }
                 } else {
+                    self.total_links += 1;
                     self.last_hash_is_linked = true;
                 }
                 Ok(())
             }
             DOC_LINK | ID_LINK => {
                 // Document/Id link
+                self.total_links += 1;
                 self.last_hash_is_linked = true;
                 Ok(())
             }
@@ -362,6 +362,9 @@ impl ValueClass {
                 .write(*notify_account_id)
                 .write(u8::from(SyncCollection::ShareNotification))
                 .write(*notification_id),
+            ValueClass::AuditLog(entry_id) => {
+                serializer.write(u32::MAX).write(u8::MAX).write(*entry_id)
+            }
             ValueClass::SearchIndex(index) => match &index.typ {
                 SearchIndexType::Term { field, hash } => {
                     let class = index.index.as_u8();
@@ -524,7 +527,7 @@ impl ValueClass {
             },
             ValueClass::DocumentId | ValueClass::Quota | ValueClass::TenantQuota(_) => U32_LEN + 1,
             ValueClass::ChangeId => U32_LEN,
-            ValueClass::ShareNotification { .. } => U32_LEN + U64_LEN + 1,
+            ValueClass::ShareNotification { .. } | ValueClass::AuditLog(_) => U32_LEN + U64_LEN + 1,
             ValueClass::NodeId(_) => (U16_LEN * 3) + 1,
             ValueClass::SearchIndex(v) => match &v.typ {
                 SearchIndexType::Term { hash, .. } => U64_LEN + hash.len() + 2,
@@ -593,7 +596,7 @@ impl ValueClass {
             | ValueClass::ChangeId
             | ValueClass::Quota
             | ValueClass::TenantQuota(_) => SUBSPACE_COUNTER,
-            ValueClass::ShareNotification { .. } => SUBSPACE_LOGS,
+            ValueClass::ShareNotification { .. } | ValueClass::AuditLog(_) => SUBSPACE_LOGS,
             ValueClass::SearchIndex(_) => SUBSPACE_SEARCH_INDEX,
             ValueClass::Any(any) => any.subspace,
         }
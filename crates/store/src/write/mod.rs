@@ -18,7 +18,7 @@ use types::{
     blob_hash::BlobHash,
     collection::{Collection, SyncCollection, VanishedCollection},
     field::{
-        CalendarEventField, CalendarNotificationField, ContactField, EmailField,
+        CalendarEventField, CalendarField, CalendarNotificationField, ContactField, EmailField,
         EmailSubmissionField, Field, MailboxField, PrincipalField, SieveField,
     },
 };
@@ -179,6 +179,7 @@ pub enum ValueClass {
         notification_id: u64,
         notify_account_id: u32,
     },
+    AuditLog(u64),
     DocumentId,
     ChangeId,
     Quota,
@@ -498,6 +499,12 @@ impl From<ContactField> for ValueClass {
     }
 }
 
+impl From<CalendarField> for ValueClass {
+    fn from(value: CalendarField) -> Self {
+        ValueClass::Property(value.into())
+    }
+}
+
 impl From<CalendarEventField> for ValueClass {
     fn from(value: CalendarEventField) -> Self {
         ValueClass::Property(value.into())
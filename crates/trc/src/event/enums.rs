@@ -6,8 +6,8 @@
 
 // This file is auto-generated. Do not edit directly.
 
-pub const TOTAL_EVENT_COUNT: usize = 605;
-pub const TOTAL_METRIC_COUNT: usize = 339;
+pub const TOTAL_EVENT_COUNT: usize = 637;
+pub const TOTAL_METRIC_COUNT: usize = 343;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EventType {
@@ -197,6 +197,7 @@ pub enum DeliveryEvent {
     DsnSuccess = 88,
     DsnTempFail = 89,
     DsnPermFail = 87,
+    DsnSuppressed = 613,
     RawInput = 105,
     RawOutput = 106,
 }
@@ -226,6 +227,7 @@ pub enum DkimEvent {
     SignatureCreated = 596,
     SignaturePublished = 597,
     SignatureRetiring = 598,
+    SignatureInvalidated = 617,
     SignatureRetired = 599,
     SignatureDeleted = 600,
 }
@@ -314,6 +316,8 @@ pub enum ImapEvent {
     Error = 168,
     RawInput = 183,
     RawOutput = 184,
+    Proxy = 618,
+    LoginReferral = 620,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -435,6 +439,8 @@ pub enum MessageIngestEvent {
     ImapAppend = 284,
     JmapAppend = 285,
     Duplicate = 281,
+    Forwarded = 623,
+    NotEncrypted = 625,
     Error = 282,
     SearchIndex = 142,
 }
@@ -498,6 +504,8 @@ pub enum NetworkEvent {
     Closed = 317,
     ProxyError = 323,
     SetOptError = 325,
+    AgentCheckStart = 633,
+    AgentCheckEnd = 634,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -542,6 +550,7 @@ pub enum Pop3Event {
     Error = 350,
     RawInput = 356,
     RawOutput = 357,
+    Proxy = 619,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -568,6 +577,9 @@ pub enum QueueEvent {
     ConcurrencyLimitExceeded = 375,
     QuotaExceeded = 383,
     BackPressure = 48,
+    MessageDelivered = 614,
+    MessageDeferred = 615,
+    MessageBounced = 616,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -604,8 +616,12 @@ pub enum SecurityEvent {
     ScanBan = 558,
     LoiterBan = 550,
     IpBlocked = 318,
+    TlsFingerprintBlocked = 628,
+    LoginAnomaly = 629,
     IpBlockExpired = 593,
     IpAllowExpired = 594,
+    FirewallSync = 631,
+    FirewallSyncError = 632,
     IpUnauthorized = 279,
     Unauthorized = 552,
 }
@@ -631,6 +647,7 @@ pub enum SieveEvent {
     ActionReject = 399,
     SendMessage = 406,
     MessageTooLarge = 401,
+    AutoReplySuppressed = 624,
     ScriptNotFound = 405,
     ListNotFound = 400,
     RuntimeError = 404,
@@ -675,17 +692,24 @@ pub enum SmtpEvent {
     MailFromUnauthenticated = 447,
     MailFromUnauthorized = 448,
     MailFromNotAllowed = 551,
+    DnsblRejected = 610,
     MailFromRewritten = 446,
     MailFromMissing = 445,
     MailFrom = 444,
     MultipleMailFrom = 456,
     MailboxDoesNotExist = 449,
+    MailboxFull = 611,
+    DuplicateDiscarded = 621,
+    DuplicateTagged = 622,
+    RcptVerifyFailed = 612,
     RelayNotAllowed = 468,
     RcptTo = 464,
     RcptToDuplicate = 465,
     RcptToRewritten = 467,
+    HeaderRewritten = 605,
     RcptToMissing = 466,
     RcptToGreylisted = 561,
+    RcptToSuppressed = 626,
     TooManyRecipients = 484,
     TooManyInvalidRcpt = 482,
     RawInput = 462,
@@ -731,12 +755,17 @@ pub enum SmtpEvent {
 pub enum SpamEvent {
     Pyzor = 564,
     PyzorError = 494,
+    Rspamd = 606,
+    RspamdError = 607,
     Dnsbl = 562,
     DnsblError = 563,
+    DnsblDisabled = 609,
     TrainStarted = 588,
     TrainCompleted = 495,
     TrainSampleAdded = 143,
     TrainSampleNotFound = 491,
+    TrainSampleThrottled = 608,
+    TrapHit = 630,
     Classify = 490,
     ModelLoaded = 589,
     ModelNotReady = 496,
@@ -786,6 +815,8 @@ pub enum StoreEvent {
     CacheHit = 51,
     CacheStale = 52,
     CacheUpdate = 577,
+    ReadCacheHit = 635,
+    ReadCacheMiss = 636,
     BlobMissingMarker = 507,
     DataWrite = 513,
     DataIterate = 512,
@@ -837,6 +868,7 @@ pub enum TelemetryEvent {
 #[repr(u16)]
 pub enum TlsEvent {
     Handshake = 543,
+    ClientFingerprint = 627,
     HandshakeError = 544,
     NotConfigured = 547,
     CertificateNotFound = 542,
@@ -939,6 +971,7 @@ pub enum MetricType {
     DeliveryDsnSuccess = 85,
     DeliveryDsnTempFail = 86,
     DeliveryDsnPermFail = 87,
+    DeliveryDsnSuppressed = 339,
     DkimPass = 88,
     DkimNeutral = 89,
     DkimFail = 90,
@@ -1165,6 +1198,7 @@ pub enum MetricType {
     SpamDnsblError = 288,
     SpamTrainCompleted = 289,
     SpamTrainSampleAdded = 290,
+    SpamTrapHit = 340,
     SpamClassify = 291,
     SpamModelNotReady = 292,
     SpfPass = 293,
@@ -1202,6 +1236,8 @@ pub enum MetricType {
     StoreHttpStoreError = 321,
     StoreBlobMissingMarker = 322,
     StoreDataWrite = 323,
+    StoreReadCacheHit = 341,
+    StoreReadCacheMiss = 342,
     StoreDataIterate = 324,
     StoreBlobRead = 325,
     StoreBlobWrite = 326,
@@ -1290,4 +1326,6 @@ pub enum Key {
     Value = 63,
     Version = 64,
     QueueName = 65,
+    EnvelopeId = 66,
+    Tags = 67,
 }
@@ -117,6 +117,7 @@ impl EventType {
             b"delivery.dsn-success" => EventType::Delivery(DeliveryEvent::DsnSuccess),
             b"delivery.dsn-temp-fail" => EventType::Delivery(DeliveryEvent::DsnTempFail),
             b"delivery.dsn-perm-fail" => EventType::Delivery(DeliveryEvent::DsnPermFail),
+            b"delivery.dsn-suppressed" => EventType::Delivery(DeliveryEvent::DsnSuppressed),
             b"delivery.raw-input" => EventType::Delivery(DeliveryEvent::RawInput),
             b"delivery.raw-output" => EventType::Delivery(DeliveryEvent::RawOutput),
             b"dkim.pass" => EventType::Dkim(DkimEvent::Pass),
@@ -141,6 +142,7 @@ impl EventType {
             b"dkim.signature-created" => EventType::Dkim(DkimEvent::SignatureCreated),
             b"dkim.signature-published" => EventType::Dkim(DkimEvent::SignaturePublished),
             b"dkim.signature-retiring" => EventType::Dkim(DkimEvent::SignatureRetiring),
+            b"dkim.signature-invalidated" => EventType::Dkim(DkimEvent::SignatureInvalidated),
             b"dkim.signature-retired" => EventType::Dkim(DkimEvent::SignatureRetired),
             b"dkim.signature-deleted" => EventType::Dkim(DkimEvent::SignatureDeleted),
             b"dmarc.pass" => EventType::Dmarc(DmarcEvent::Pass),
@@ -204,6 +206,8 @@ impl EventType {
             b"imap.error" => EventType::Imap(ImapEvent::Error),
             b"imap.raw-input" => EventType::Imap(ImapEvent::RawInput),
             b"imap.raw-output" => EventType::Imap(ImapEvent::RawOutput),
+            b"imap.proxy" => EventType::Imap(ImapEvent::Proxy),
+            b"imap.login-referral" => EventType::Imap(ImapEvent::LoginReferral),
             b"incoming-report.dmarc-report" => EventType::IncomingReport(IncomingReportEvent::DmarcReport),
             b"incoming-report.dmarc-report-with-warnings" => EventType::IncomingReport(IncomingReportEvent::DmarcReportWithWarnings),
             b"incoming-report.tls-report" => EventType::IncomingReport(IncomingReportEvent::TlsReport),
@@ -290,6 +294,8 @@ impl EventType {
             b"message-ingest.imap-append" => EventType::MessageIngest(MessageIngestEvent::ImapAppend),
             b"message-ingest.jmap-append" => EventType::MessageIngest(MessageIngestEvent::JmapAppend),
             b"message-ingest.duplicate" => EventType::MessageIngest(MessageIngestEvent::Duplicate),
+            b"message-ingest.forwarded" => EventType::MessageIngest(MessageIngestEvent::Forwarded),
+            b"message-ingest.not-encrypted" => EventType::MessageIngest(MessageIngestEvent::NotEncrypted),
             b"message-ingest.error" => EventType::MessageIngest(MessageIngestEvent::Error),
             b"message-ingest.search-index" => EventType::MessageIngest(MessageIngestEvent::SearchIndex),
             b"milter.read" => EventType::Milter(MilterEvent::Read),
@@ -333,6 +339,8 @@ impl EventType {
             b"network.closed" => EventType::Network(NetworkEvent::Closed),
             b"network.proxy-error" => EventType::Network(NetworkEvent::ProxyError),
             b"network.set-opt-error" => EventType::Network(NetworkEvent::SetOptError),
+            b"network.agent-check-start" => EventType::Network(NetworkEvent::AgentCheckStart),
+            b"network.agent-check-end" => EventType::Network(NetworkEvent::AgentCheckEnd),
             b"outgoing-report.spf-report" => EventType::OutgoingReport(OutgoingReportEvent::SpfReport),
             b"outgoing-report.spf-rate-limited" => EventType::OutgoingReport(OutgoingReportEvent::SpfRateLimited),
             b"outgoing-report.dkim-report" => EventType::OutgoingReport(OutgoingReportEvent::DkimReport),
@@ -367,6 +375,7 @@ impl EventType {
             b"pop3.error" => EventType::Pop3(Pop3Event::Error),
             b"pop3.raw-input" => EventType::Pop3(Pop3Event::RawInput),
             b"pop3.raw-output" => EventType::Pop3(Pop3Event::RawOutput),
+            b"pop3.proxy" => EventType::Pop3(Pop3Event::Proxy),
             b"push-subscription.success" => EventType::PushSubscription(PushSubscriptionEvent::Success),
             b"push-subscription.error" => EventType::PushSubscription(PushSubscriptionEvent::Error),
             b"push-subscription.not-found" => EventType::PushSubscription(PushSubscriptionEvent::NotFound),
@@ -383,6 +392,9 @@ impl EventType {
             b"queue.concurrency-limit-exceeded" => EventType::Queue(QueueEvent::ConcurrencyLimitExceeded),
             b"queue.quota-exceeded" => EventType::Queue(QueueEvent::QuotaExceeded),
             b"queue.back-pressure" => EventType::Queue(QueueEvent::BackPressure),
+            b"queue.message-delivered" => EventType::Queue(QueueEvent::MessageDelivered),
+            b"queue.message-deferred" => EventType::Queue(QueueEvent::MessageDeferred),
+            b"queue.message-bounced" => EventType::Queue(QueueEvent::MessageBounced),
             b"registry.local-read-error" => EventType::Registry(RegistryEvent::LocalReadError),
             b"registry.local-write-error" => EventType::Registry(RegistryEvent::LocalWriteError),
             b"registry.local-parse-error" => EventType::Registry(RegistryEvent::LocalParseError),
@@ -404,8 +416,12 @@ impl EventType {
             b"security.scan-ban" => EventType::Security(SecurityEvent::ScanBan),
             b"security.loiter-ban" => EventType::Security(SecurityEvent::LoiterBan),
             b"security.ip-blocked" => EventType::Security(SecurityEvent::IpBlocked),
+            b"security.tls-fingerprint-blocked" => EventType::Security(SecurityEvent::TlsFingerprintBlocked),
+            b"security.login-anomaly" => EventType::Security(SecurityEvent::LoginAnomaly),
             b"security.ip-block-expired" => EventType::Security(SecurityEvent::IpBlockExpired),
             b"security.ip-allow-expired" => EventType::Security(SecurityEvent::IpAllowExpired),
+            b"security.firewall-sync" => EventType::Security(SecurityEvent::FirewallSync),
+            b"security.firewall-sync-error" => EventType::Security(SecurityEvent::FirewallSyncError),
             b"security.ip-unauthorized" => EventType::Security(SecurityEvent::IpUnauthorized),
             b"security.unauthorized" => EventType::Security(SecurityEvent::Unauthorized),
             b"server.startup" => EventType::Server(ServerEvent::Startup),
@@ -421,6 +437,7 @@ impl EventType {
             b"sieve.action-reject" => EventType::Sieve(SieveEvent::ActionReject),
             b"sieve.send-message" => EventType::Sieve(SieveEvent::SendMessage),
             b"sieve.message-too-large" => EventType::Sieve(SieveEvent::MessageTooLarge),
+            b"sieve.auto-reply-suppressed" => EventType::Sieve(SieveEvent::AutoReplySuppressed),
             b"sieve.script-not-found" => EventType::Sieve(SieveEvent::ScriptNotFound),
             b"sieve.list-not-found" => EventType::Sieve(SieveEvent::ListNotFound),
             b"sieve.runtime-error" => EventType::Sieve(SieveEvent::RuntimeError),
@@ -460,17 +477,24 @@ impl EventType {
             b"smtp.mail-from-unauthenticated" => EventType::Smtp(SmtpEvent::MailFromUnauthenticated),
             b"smtp.mail-from-unauthorized" => EventType::Smtp(SmtpEvent::MailFromUnauthorized),
             b"smtp.mail-from-not-allowed" => EventType::Smtp(SmtpEvent::MailFromNotAllowed),
+            b"smtp.dnsbl-rejected" => EventType::Smtp(SmtpEvent::DnsblRejected),
             b"smtp.mail-from-rewritten" => EventType::Smtp(SmtpEvent::MailFromRewritten),
             b"smtp.mail-from-missing" => EventType::Smtp(SmtpEvent::MailFromMissing),
             b"smtp.mail-from" => EventType::Smtp(SmtpEvent::MailFrom),
             b"smtp.multiple-mail-from" => EventType::Smtp(SmtpEvent::MultipleMailFrom),
             b"smtp.mailbox-does-not-exist" => EventType::Smtp(SmtpEvent::MailboxDoesNotExist),
+            b"smtp.mailbox-full" => EventType::Smtp(SmtpEvent::MailboxFull),
+            b"smtp.duplicate-discarded" => EventType::Smtp(SmtpEvent::DuplicateDiscarded),
+            b"smtp.duplicate-tagged" => EventType::Smtp(SmtpEvent::DuplicateTagged),
+            b"smtp.rcpt-verify-failed" => EventType::Smtp(SmtpEvent::RcptVerifyFailed),
             b"smtp.relay-not-allowed" => EventType::Smtp(SmtpEvent::RelayNotAllowed),
             b"smtp.rcpt-to" => EventType::Smtp(SmtpEvent::RcptTo),
             b"smtp.rcpt-to-duplicate" => EventType::Smtp(SmtpEvent::RcptToDuplicate),
             b"smtp.rcpt-to-rewritten" => EventType::Smtp(SmtpEvent::RcptToRewritten),
+            b"smtp.header-rewritten" => EventType::Smtp(SmtpEvent::HeaderRewritten),
             b"smtp.rcpt-to-missing" => EventType::Smtp(SmtpEvent::RcptToMissing),
             b"smtp.rcpt-to-greylisted" => EventType::Smtp(SmtpEvent::RcptToGreylisted),
+            b"smtp.rcpt-to-suppressed" => EventType::Smtp(SmtpEvent::RcptToSuppressed),
             b"smtp.too-many-recipients" => EventType::Smtp(SmtpEvent::TooManyRecipients),
             b"smtp.too-many-invalid-rcpt" => EventType::Smtp(SmtpEvent::TooManyInvalidRcpt),
             b"smtp.raw-input" => EventType::Smtp(SmtpEvent::RawInput),
@@ -511,12 +535,17 @@ impl EventType {
             b"smtp.request-too-large" => EventType::Smtp(SmtpEvent::RequestTooLarge),
             b"spam.pyzor" => EventType::Spam(SpamEvent::Pyzor),
             b"spam.pyzor-error" => EventType::Spam(SpamEvent::PyzorError),
+            b"spam.rspamd" => EventType::Spam(SpamEvent::Rspamd),
+            b"spam.rspamd-error" => EventType::Spam(SpamEvent::RspamdError),
             b"spam.dnsbl" => EventType::Spam(SpamEvent::Dnsbl),
             b"spam.dnsbl-error" => EventType::Spam(SpamEvent::DnsblError),
+            b"spam.dnsbl-disabled" => EventType::Spam(SpamEvent::DnsblDisabled),
             b"spam.train-started" => EventType::Spam(SpamEvent::TrainStarted),
             b"spam.train-completed" => EventType::Spam(SpamEvent::TrainCompleted),
             b"spam.train-sample-added" => EventType::Spam(SpamEvent::TrainSampleAdded),
             b"spam.train-sample-not-found" => EventType::Spam(SpamEvent::TrainSampleNotFound),
+            b"spam.train-sample-throttled" => EventType::Spam(SpamEvent::TrainSampleThrottled),
+            b"spam.trap-hit" => EventType::Spam(SpamEvent::TrapHit),
             b"spam.classify" => EventType::Spam(SpamEvent::Classify),
             b"spam.model-loaded" => EventType::Spam(SpamEvent::ModelLoaded),
             b"spam.model-not-ready" => EventType::Spam(SpamEvent::ModelNotReady),
@@ -556,6 +585,8 @@ impl EventType {
             b"store.cache-hit" => EventType::Store(StoreEvent::CacheHit),
             b"store.cache-stale" => EventType::Store(StoreEvent::CacheStale),
             b"store.cache-update" => EventType::Store(StoreEvent::CacheUpdate),
+            b"store.read-cache-hit" => EventType::Store(StoreEvent::ReadCacheHit),
+            b"store.read-cache-miss" => EventType::Store(StoreEvent::ReadCacheMiss),
             b"store.blob-missing-marker" => EventType::Store(StoreEvent::BlobMissingMarker),
             b"store.data-write" => EventType::Store(StoreEvent::DataWrite),
             b"store.data-iterate" => EventType::Store(StoreEvent::DataIterate),
@@ -592,6 +623,7 @@ impl EventType {
             b"telemetry.metrics-stored" => EventType::Telemetry(TelemetryEvent::MetricsStored),
             b"telemetry.metrics-pushed" => EventType::Telemetry(TelemetryEvent::MetricsPushed),
             b"tls.handshake" => EventType::Tls(TlsEvent::Handshake),
+            b"tls.client-fingerprint" => EventType::Tls(TlsEvent::ClientFingerprint),
             b"tls.handshake-error" => EventType::Tls(TlsEvent::HandshakeError),
             b"tls.not-configured" => EventType::Tls(TlsEvent::NotConfigured),
             b"tls.certificate-not-found" => EventType::Tls(TlsEvent::CertificateNotFound),
@@ -746,6 +778,7 @@ impl EventType {
             EventType::Delivery(DeliveryEvent::DsnSuccess) => "delivery.dsn-success",
             EventType::Delivery(DeliveryEvent::DsnTempFail) => "delivery.dsn-temp-fail",
             EventType::Delivery(DeliveryEvent::DsnPermFail) => "delivery.dsn-perm-fail",
+            EventType::Delivery(DeliveryEvent::DsnSuppressed) => "delivery.dsn-suppressed",
             EventType::Delivery(DeliveryEvent::RawInput) => "delivery.raw-input",
             EventType::Delivery(DeliveryEvent::RawOutput) => "delivery.raw-output",
             EventType::Dkim(DkimEvent::Pass) => "dkim.pass",
@@ -772,6 +805,7 @@ impl EventType {
             EventType::Dkim(DkimEvent::SignatureCreated) => "dkim.signature-created",
             EventType::Dkim(DkimEvent::SignaturePublished) => "dkim.signature-published",
             EventType::Dkim(DkimEvent::SignatureRetiring) => "dkim.signature-retiring",
+            EventType::Dkim(DkimEvent::SignatureInvalidated) => "dkim.signature-invalidated",
             EventType::Dkim(DkimEvent::SignatureRetired) => "dkim.signature-retired",
             EventType::Dkim(DkimEvent::SignatureDeleted) => "dkim.signature-deleted",
             EventType::Dmarc(DmarcEvent::Pass) => "dmarc.pass",
@@ -835,6 +869,8 @@ impl EventType {
             EventType::Imap(ImapEvent::Error) => "imap.error",
             EventType::Imap(ImapEvent::RawInput) => "imap.raw-input",
             EventType::Imap(ImapEvent::RawOutput) => "imap.raw-output",
+            EventType::Imap(ImapEvent::Proxy) => "imap.proxy",
+            EventType::Imap(ImapEvent::LoginReferral) => "imap.login-referral",
             EventType::IncomingReport(IncomingReportEvent::DmarcReport) => {
                 "incoming-report.dmarc-report"
             }
@@ -967,6 +1003,8 @@ impl EventType {
                 "message-ingest.jmap-append"
             }
             EventType::MessageIngest(MessageIngestEvent::Duplicate) => "message-ingest.duplicate",
+            EventType::MessageIngest(MessageIngestEvent::Forwarded) => "message-ingest.forwarded",
+            EventType::MessageIngest(MessageIngestEvent::NotEncrypted) => "message-ingest.not-encrypted",
             EventType::MessageIngest(MessageIngestEvent::Error) => "message-ingest.error",
             EventType::MessageIngest(MessageIngestEvent::SearchIndex) => {
                 "message-ingest.search-index"
@@ -1014,6 +1052,8 @@ impl EventType {
             EventType::Network(NetworkEvent::Closed) => "network.closed",
             EventType::Network(NetworkEvent::ProxyError) => "network.proxy-error",
             EventType::Network(NetworkEvent::SetOptError) => "network.set-opt-error",
+            EventType::Network(NetworkEvent::AgentCheckStart) => "network.agent-check-start",
+            EventType::Network(NetworkEvent::AgentCheckEnd) => "network.agent-check-end",
             EventType::OutgoingReport(OutgoingReportEvent::SpfReport) => {
                 "outgoing-report.spf-report"
             }
@@ -1076,6 +1116,7 @@ impl EventType {
             EventType::Pop3(Pop3Event::Error) => "pop3.error",
             EventType::Pop3(Pop3Event::RawInput) => "pop3.raw-input",
             EventType::Pop3(Pop3Event::RawOutput) => "pop3.raw-output",
+            EventType::Pop3(Pop3Event::Proxy) => "pop3.proxy",
             EventType::PushSubscription(PushSubscriptionEvent::Success) => {
                 "push-subscription.success"
             }
@@ -1100,6 +1141,9 @@ impl EventType {
             }
             EventType::Queue(QueueEvent::QuotaExceeded) => "queue.quota-exceeded",
             EventType::Queue(QueueEvent::BackPressure) => "queue.back-pressure",
+            EventType::Queue(QueueEvent::MessageDelivered) => "queue.message-delivered",
+            EventType::Queue(QueueEvent::MessageDeferred) => "queue.message-deferred",
+            EventType::Queue(QueueEvent::MessageBounced) => "queue.message-bounced",
             EventType::Registry(RegistryEvent::LocalReadError) => "registry.local-read-error",
             EventType::Registry(RegistryEvent::LocalWriteError) => "registry.local-write-error",
             EventType::Registry(RegistryEvent::LocalParseError) => "registry.local-parse-error",
@@ -1127,8 +1171,12 @@ impl EventType {
             EventType::Security(SecurityEvent::ScanBan) => "security.scan-ban",
             EventType::Security(SecurityEvent::LoiterBan) => "security.loiter-ban",
             EventType::Security(SecurityEvent::IpBlocked) => "security.ip-blocked",
+            EventType::Security(SecurityEvent::TlsFingerprintBlocked) => "security.tls-fingerprint-blocked",
+            EventType::Security(SecurityEvent::LoginAnomaly) => "security.login-anomaly",
             EventType::Security(SecurityEvent::IpBlockExpired) => "security.ip-block-expired",
             EventType::Security(SecurityEvent::IpAllowExpired) => "security.ip-allow-expired",
+            EventType::Security(SecurityEvent::FirewallSync) => "security.firewall-sync",
+            EventType::Security(SecurityEvent::FirewallSyncError) => "security.firewall-sync-error",
             EventType::Security(SecurityEvent::IpUnauthorized) => "security.ip-unauthorized",
             EventType::Security(SecurityEvent::Unauthorized) => "security.unauthorized",
             EventType::Server(ServerEvent::Startup) => "server.startup",
@@ -1144,6 +1192,7 @@ impl EventType {
             EventType::Sieve(SieveEvent::ActionReject) => "sieve.action-reject",
             EventType::Sieve(SieveEvent::SendMessage) => "sieve.send-message",
             EventType::Sieve(SieveEvent::MessageTooLarge) => "sieve.message-too-large",
+            EventType::Sieve(SieveEvent::AutoReplySuppressed) => "sieve.auto-reply-suppressed",
             EventType::Sieve(SieveEvent::ScriptNotFound) => "sieve.script-not-found",
             EventType::Sieve(SieveEvent::ListNotFound) => "sieve.list-not-found",
             EventType::Sieve(SieveEvent::RuntimeError) => "sieve.runtime-error",
@@ -1185,17 +1234,24 @@ impl EventType {
             EventType::Smtp(SmtpEvent::MailFromUnauthenticated) => "smtp.mail-from-unauthenticated",
             EventType::Smtp(SmtpEvent::MailFromUnauthorized) => "smtp.mail-from-unauthorized",
             EventType::Smtp(SmtpEvent::MailFromNotAllowed) => "smtp.mail-from-not-allowed",
+            EventType::Smtp(SmtpEvent::DnsblRejected) => "smtp.dnsbl-rejected",
             EventType::Smtp(SmtpEvent::MailFromRewritten) => "smtp.mail-from-rewritten",
             EventType::Smtp(SmtpEvent::MailFromMissing) => "smtp.mail-from-missing",
             EventType::Smtp(SmtpEvent::MailFrom) => "smtp.mail-from",
             EventType::Smtp(SmtpEvent::MultipleMailFrom) => "smtp.multiple-mail-from",
             EventType::Smtp(SmtpEvent::MailboxDoesNotExist) => "smtp.mailbox-does-not-exist",
+            EventType::Smtp(SmtpEvent::MailboxFull) => "smtp.mailbox-full",
+            EventType::Smtp(SmtpEvent::DuplicateDiscarded) => "smtp.duplicate-discarded",
+            EventType::Smtp(SmtpEvent::DuplicateTagged) => "smtp.duplicate-tagged",
+            EventType::Smtp(SmtpEvent::RcptVerifyFailed) => "smtp.rcpt-verify-failed",
             EventType::Smtp(SmtpEvent::RelayNotAllowed) => "smtp.relay-not-allowed",
             EventType::Smtp(SmtpEvent::RcptTo) => "smtp.rcpt-to",
             EventType::Smtp(SmtpEvent::RcptToDuplicate) => "smtp.rcpt-to-duplicate",
             EventType::Smtp(SmtpEvent::RcptToRewritten) => "smtp.rcpt-to-rewritten",
+            EventType::Smtp(SmtpEvent::HeaderRewritten) => "smtp.header-rewritten",
             EventType::Smtp(SmtpEvent::RcptToMissing) => "smtp.rcpt-to-missing",
             EventType::Smtp(SmtpEvent::RcptToGreylisted) => "smtp.rcpt-to-greylisted",
+            EventType::Smtp(SmtpEvent::RcptToSuppressed) => "smtp.rcpt-to-suppressed",
             EventType::Smtp(SmtpEvent::TooManyRecipients) => "smtp.too-many-recipients",
             EventType::Smtp(SmtpEvent::TooManyInvalidRcpt) => "smtp.too-many-invalid-rcpt",
             EventType::Smtp(SmtpEvent::RawInput) => "smtp.raw-input",
@@ -1238,12 +1294,17 @@ impl EventType {
             EventType::Smtp(SmtpEvent::RequestTooLarge) => "smtp.request-too-large",
             EventType::Spam(SpamEvent::Pyzor) => "spam.pyzor",
             EventType::Spam(SpamEvent::PyzorError) => "spam.pyzor-error",
+            EventType::Spam(SpamEvent::Rspamd) => "spam.rspamd",
+            EventType::Spam(SpamEvent::RspamdError) => "spam.rspamd-error",
             EventType::Spam(SpamEvent::Dnsbl) => "spam.dnsbl",
             EventType::Spam(SpamEvent::DnsblError) => "spam.dnsbl-error",
+            EventType::Spam(SpamEvent::DnsblDisabled) => "spam.dnsbl-disabled",
             EventType::Spam(SpamEvent::TrainStarted) => "spam.train-started",
             EventType::Spam(SpamEvent::TrainCompleted) => "spam.train-completed",
             EventType::Spam(SpamEvent::TrainSampleAdded) => "spam.train-sample-added",
             EventType::Spam(SpamEvent::TrainSampleNotFound) => "spam.train-sample-not-found",
+            EventType::Spam(SpamEvent::TrainSampleThrottled) => "spam.train-sample-throttled",
+            EventType::Spam(SpamEvent::TrapHit) => "spam.trap-hit",
             EventType::Spam(SpamEvent::Classify) => "spam.classify",
             EventType::Spam(SpamEvent::ModelLoaded) => "spam.model-loaded",
             EventType::Spam(SpamEvent::ModelNotReady) => "spam.model-not-ready",
@@ -1283,6 +1344,8 @@ impl EventType {
             EventType::Store(StoreEvent::CacheHit) => "store.cache-hit",
             EventType::Store(StoreEvent::CacheStale) => "store.cache-stale",
             EventType::Store(StoreEvent::CacheUpdate) => "store.cache-update",
+            EventType::Store(StoreEvent::ReadCacheHit) => "store.read-cache-hit",
+            EventType::Store(StoreEvent::ReadCacheMiss) => "store.read-cache-miss",
             EventType::Store(StoreEvent::BlobMissingMarker) => "store.blob-missing-marker",
             EventType::Store(StoreEvent::DataWrite) => "store.data-write",
             EventType::Store(StoreEvent::DataIterate) => "store.data-iterate",
@@ -1333,6 +1396,7 @@ impl EventType {
             EventType::Telemetry(TelemetryEvent::MetricsStored) => "telemetry.metrics-stored",
             EventType::Telemetry(TelemetryEvent::MetricsPushed) => "telemetry.metrics-pushed",
             EventType::Tls(TlsEvent::Handshake) => "tls.handshake",
+            EventType::Tls(TlsEvent::ClientFingerprint) => "tls.client-fingerprint",
             EventType::Tls(TlsEvent::HandshakeError) => "tls.handshake-error",
             EventType::Tls(TlsEvent::NotConfigured) => "tls.not-configured",
             EventType::Tls(TlsEvent::CertificateNotFound) => "tls.certificate-not-found",
@@ -1472,6 +1536,7 @@ impl EventType {
             EventType::Delivery(DeliveryEvent::DsnSuccess) => 88,
             EventType::Delivery(DeliveryEvent::DsnTempFail) => 89,
             EventType::Delivery(DeliveryEvent::DsnPermFail) => 87,
+            EventType::Delivery(DeliveryEvent::DsnSuppressed) => 613,
             EventType::Delivery(DeliveryEvent::RawInput) => 105,
             EventType::Delivery(DeliveryEvent::RawOutput) => 106,
             EventType::Dkim(DkimEvent::Pass) => 121,
@@ -1496,6 +1561,7 @@ impl EventType {
             EventType::Dkim(DkimEvent::SignatureCreated) => 596,
             EventType::Dkim(DkimEvent::SignaturePublished) => 597,
             EventType::Dkim(DkimEvent::SignatureRetiring) => 598,
+            EventType::Dkim(DkimEvent::SignatureInvalidated) => 617,
             EventType::Dkim(DkimEvent::SignatureRetired) => 599,
             EventType::Dkim(DkimEvent::SignatureDeleted) => 600,
             EventType::Dmarc(DmarcEvent::Pass) => 134,
@@ -1559,6 +1625,8 @@ impl EventType {
             EventType::Imap(ImapEvent::Error) => 168,
             EventType::Imap(ImapEvent::RawInput) => 183,
             EventType::Imap(ImapEvent::RawOutput) => 184,
+            EventType::Imap(ImapEvent::Proxy) => 618,
+            EventType::Imap(ImapEvent::LoginReferral) => 620,
             EventType::IncomingReport(IncomingReportEvent::DmarcReport) => 200,
             EventType::IncomingReport(IncomingReportEvent::DmarcReportWithWarnings) => 201,
             EventType::IncomingReport(IncomingReportEvent::TlsReport) => 206,
@@ -1645,6 +1713,8 @@ impl EventType {
             EventType::MessageIngest(MessageIngestEvent::ImapAppend) => 284,
             EventType::MessageIngest(MessageIngestEvent::JmapAppend) => 285,
             EventType::MessageIngest(MessageIngestEvent::Duplicate) => 281,
+            EventType::MessageIngest(MessageIngestEvent::Forwarded) => 623,
+            EventType::MessageIngest(MessageIngestEvent::NotEncrypted) => 625,
             EventType::MessageIngest(MessageIngestEvent::Error) => 282,
             EventType::MessageIngest(MessageIngestEvent::SearchIndex) => 142,
             EventType::Milter(MilterEvent::Read) => 299,
@@ -1688,6 +1758,8 @@ impl EventType {
             EventType::Network(NetworkEvent::Closed) => 317,
             EventType::Network(NetworkEvent::ProxyError) => 323,
             EventType::Network(NetworkEvent::SetOptError) => 325,
+            EventType::Network(NetworkEvent::AgentCheckStart) => 633,
+            EventType::Network(NetworkEvent::AgentCheckEnd) => 634,
             EventType::OutgoingReport(OutgoingReportEvent::SpfReport) => 342,
             EventType::OutgoingReport(OutgoingReportEvent::SpfRateLimited) => 341,
             EventType::OutgoingReport(OutgoingReportEvent::DkimReport) => 330,
@@ -1722,6 +1794,7 @@ impl EventType {
             EventType::Pop3(Pop3Event::Error) => 350,
             EventType::Pop3(Pop3Event::RawInput) => 356,
             EventType::Pop3(Pop3Event::RawOutput) => 357,
+            EventType::Pop3(Pop3Event::Proxy) => 619,
             EventType::PushSubscription(PushSubscriptionEvent::Success) => 373,
             EventType::PushSubscription(PushSubscriptionEvent::Error) => 371,
             EventType::PushSubscription(PushSubscriptionEvent::NotFound) => 372,
@@ -1738,6 +1811,9 @@ impl EventType {
             EventType::Queue(QueueEvent::ConcurrencyLimitExceeded) => 375,
             EventType::Queue(QueueEvent::QuotaExceeded) => 383,
             EventType::Queue(QueueEvent::BackPressure) => 48,
+            EventType::Queue(QueueEvent::MessageDelivered) => 614,
+            EventType::Queue(QueueEvent::MessageDeferred) => 615,
+            EventType::Queue(QueueEvent::MessageBounced) => 616,
             EventType::Registry(RegistryEvent::LocalReadError) => 62,
             EventType::Registry(RegistryEvent::LocalWriteError) => 54,
             EventType::Registry(RegistryEvent::LocalParseError) => 60,
@@ -1759,8 +1835,12 @@ impl EventType {
             EventType::Security(SecurityEvent::ScanBan) => 558,
             EventType::Security(SecurityEvent::LoiterBan) => 550,
             EventType::Security(SecurityEvent::IpBlocked) => 318,
+            EventType::Security(SecurityEvent::TlsFingerprintBlocked) => 628,
+            EventType::Security(SecurityEvent::LoginAnomaly) => 629,
             EventType::Security(SecurityEvent::IpBlockExpired) => 593,
             EventType::Security(SecurityEvent::IpAllowExpired) => 594,
+            EventType::Security(SecurityEvent::FirewallSync) => 631,
+            EventType::Security(SecurityEvent::FirewallSyncError) => 632,
             EventType::Security(SecurityEvent::IpUnauthorized) => 279,
             EventType::Security(SecurityEvent::Unauthorized) => 552,
             EventType::Server(ServerEvent::Startup) => 393,
@@ -1776,6 +1856,7 @@ impl EventType {
             EventType::Sieve(SieveEvent::ActionReject) => 399,
             EventType::Sieve(SieveEvent::SendMessage) => 406,
             EventType::Sieve(SieveEvent::MessageTooLarge) => 401,
+            EventType::Sieve(SieveEvent::AutoReplySuppressed) => 624,
             EventType::Sieve(SieveEvent::ScriptNotFound) => 405,
             EventType::Sieve(SieveEvent::ListNotFound) => 400,
             EventType::Sieve(SieveEvent::RuntimeError) => 404,
@@ -1815,17 +1896,24 @@ impl EventType {
             EventType::Smtp(SmtpEvent::MailFromUnauthenticated) => 447,
             EventType::Smtp(SmtpEvent::MailFromUnauthorized) => 448,
             EventType::Smtp(SmtpEvent::MailFromNotAllowed) => 551,
+            EventType::Smtp(SmtpEvent::DnsblRejected) => 610,
             EventType::Smtp(SmtpEvent::MailFromRewritten) => 446,
             EventType::Smtp(SmtpEvent::MailFromMissing) => 445,
             EventType::Smtp(SmtpEvent::MailFrom) => 444,
             EventType::Smtp(SmtpEvent::MultipleMailFrom) => 456,
             EventType::Smtp(SmtpEvent::MailboxDoesNotExist) => 449,
+            EventType::Smtp(SmtpEvent::MailboxFull) => 611,
+            EventType::Smtp(SmtpEvent::DuplicateDiscarded) => 621,
+            EventType::Smtp(SmtpEvent::DuplicateTagged) => 622,
+            EventType::Smtp(SmtpEvent::RcptVerifyFailed) => 612,
             EventType::Smtp(SmtpEvent::RelayNotAllowed) => 468,
             EventType::Smtp(SmtpEvent::RcptTo) => 464,
             EventType::Smtp(SmtpEvent::RcptToDuplicate) => 465,
             EventType::Smtp(SmtpEvent::RcptToRewritten) => 467,
+            EventType::Smtp(SmtpEvent::HeaderRewritten) => 605,
             EventType::Smtp(SmtpEvent::RcptToMissing) => 466,
             EventType::Smtp(SmtpEvent::RcptToGreylisted) => 561,
+            EventType::Smtp(SmtpEvent::RcptToSuppressed) => 626,
             EventType::Smtp(SmtpEvent::TooManyRecipients) => 484,
             EventType::Smtp(SmtpEvent::TooManyInvalidRcpt) => 482,
             EventType::Smtp(SmtpEvent::RawInput) => 462,
@@ -1866,12 +1954,17 @@ impl EventType {
             EventType::Smtp(SmtpEvent::RequestTooLarge) => 470,
             EventType::Spam(SpamEvent::Pyzor) => 564,
             EventType::Spam(SpamEvent::PyzorError) => 494,
+            EventType::Spam(SpamEvent::Rspamd) => 606,
+            EventType::Spam(SpamEvent::RspamdError) => 607,
             EventType::Spam(SpamEvent::Dnsbl) => 562,
             EventType::Spam(SpamEvent::DnsblError) => 563,
+            EventType::Spam(SpamEvent::DnsblDisabled) => 609,
             EventType::Spam(SpamEvent::TrainStarted) => 588,
             EventType::Spam(SpamEvent::TrainCompleted) => 495,
             EventType::Spam(SpamEvent::TrainSampleAdded) => 143,
             EventType::Spam(SpamEvent::TrainSampleNotFound) => 491,
+            EventType::Spam(SpamEvent::TrainSampleThrottled) => 608,
+            EventType::Spam(SpamEvent::TrapHit) => 630,
             EventType::Spam(SpamEvent::Classify) => 490,
             EventType::Spam(SpamEvent::ModelLoaded) => 589,
             EventType::Spam(SpamEvent::ModelNotReady) => 496,
@@ -1911,6 +2004,8 @@ impl EventType {
             EventType::Store(StoreEvent::CacheHit) => 51,
             EventType::Store(StoreEvent::CacheStale) => 52,
             EventType::Store(StoreEvent::CacheUpdate) => 577,
+            EventType::Store(StoreEvent::ReadCacheHit) => 635,
+            EventType::Store(StoreEvent::ReadCacheMiss) => 636,
             EventType::Store(StoreEvent::BlobMissingMarker) => 507,
             EventType::Store(StoreEvent::DataWrite) => 513,
             EventType::Store(StoreEvent::DataIterate) => 512,
@@ -1947,6 +2042,7 @@ impl EventType {
             EventType::Telemetry(TelemetryEvent::MetricsStored) => 366,
             EventType::Telemetry(TelemetryEvent::MetricsPushed) => 146,
             EventType::Tls(TlsEvent::Handshake) => 543,
+            EventType::Tls(TlsEvent::ClientFingerprint) => 627,
             EventType::Tls(TlsEvent::HandshakeError) => 544,
             EventType::Tls(TlsEvent::NotConfigured) => 547,
             EventType::Tls(TlsEvent::CertificateNotFound) => 542,
@@ -2082,6 +2178,7 @@ impl EventType {
             88 => Some(EventType::Delivery(DeliveryEvent::DsnSuccess)),
             89 => Some(EventType::Delivery(DeliveryEvent::DsnTempFail)),
             87 => Some(EventType::Delivery(DeliveryEvent::DsnPermFail)),
+            613 => Some(EventType::Delivery(DeliveryEvent::DsnSuppressed)),
             105 => Some(EventType::Delivery(DeliveryEvent::RawInput)),
             106 => Some(EventType::Delivery(DeliveryEvent::RawOutput)),
             121 => Some(EventType::Dkim(DkimEvent::Pass)),
@@ -2106,6 +2203,7 @@ impl EventType {
             596 => Some(EventType::Dkim(DkimEvent::SignatureCreated)),
             597 => Some(EventType::Dkim(DkimEvent::SignaturePublished)),
             598 => Some(EventType::Dkim(DkimEvent::SignatureRetiring)),
+            617 => Some(EventType::Dkim(DkimEvent::SignatureInvalidated)),
             599 => Some(EventType::Dkim(DkimEvent::SignatureRetired)),
             600 => Some(EventType::Dkim(DkimEvent::SignatureDeleted)),
             134 => Some(EventType::Dmarc(DmarcEvent::Pass)),
@@ -2169,6 +2267,8 @@ impl EventType {
             168 => Some(EventType::Imap(ImapEvent::Error)),
             183 => Some(EventType::Imap(ImapEvent::RawInput)),
             184 => Some(EventType::Imap(ImapEvent::RawOutput)),
+            618 => Some(EventType::Imap(ImapEvent::Proxy)),
+            620 => Some(EventType::Imap(ImapEvent::LoginReferral)),
             200 => Some(EventType::IncomingReport(IncomingReportEvent::DmarcReport)),
             201 => Some(EventType::IncomingReport(
                 IncomingReportEvent::DmarcReportWithWarnings,
@@ -2273,6 +2373,8 @@ impl EventType {
             284 => Some(EventType::MessageIngest(MessageIngestEvent::ImapAppend)),
             285 => Some(EventType::MessageIngest(MessageIngestEvent::JmapAppend)),
             281 => Some(EventType::MessageIngest(MessageIngestEvent::Duplicate)),
+            623 => Some(EventType::MessageIngest(MessageIngestEvent::Forwarded)),
+            625 => Some(EventType::MessageIngest(MessageIngestEvent::NotEncrypted)),
             282 => Some(EventType::MessageIngest(MessageIngestEvent::Error)),
             142 => Some(EventType::MessageIngest(MessageIngestEvent::SearchIndex)),
             299 => Some(EventType::Milter(MilterEvent::Read)),
@@ -2316,6 +2418,8 @@ impl EventType {
             317 => Some(EventType::Network(NetworkEvent::Closed)),
             323 => Some(EventType::Network(NetworkEvent::ProxyError)),
             325 => Some(EventType::Network(NetworkEvent::SetOptError)),
+            633 => Some(EventType::Network(NetworkEvent::AgentCheckStart)),
+            634 => Some(EventType::Network(NetworkEvent::AgentCheckEnd)),
             342 => Some(EventType::OutgoingReport(OutgoingReportEvent::SpfReport)),
             341 => Some(EventType::OutgoingReport(
                 OutgoingReportEvent::SpfRateLimited,
@@ -2370,6 +2474,7 @@ impl EventType {
             350 => Some(EventType::Pop3(Pop3Event::Error)),
             356 => Some(EventType::Pop3(Pop3Event::RawInput)),
             357 => Some(EventType::Pop3(Pop3Event::RawOutput)),
+            619 => Some(EventType::Pop3(Pop3Event::Proxy)),
             373 => Some(EventType::PushSubscription(PushSubscriptionEvent::Success)),
             371 => Some(EventType::PushSubscription(PushSubscriptionEvent::Error)),
             372 => Some(EventType::PushSubscription(PushSubscriptionEvent::NotFound)),
@@ -2386,6 +2491,9 @@ impl EventType {
             375 => Some(EventType::Queue(QueueEvent::ConcurrencyLimitExceeded)),
             383 => Some(EventType::Queue(QueueEvent::QuotaExceeded)),
             48 => Some(EventType::Queue(QueueEvent::BackPressure)),
+            614 => Some(EventType::Queue(QueueEvent::MessageDelivered)),
+            615 => Some(EventType::Queue(QueueEvent::MessageDeferred)),
+            616 => Some(EventType::Queue(QueueEvent::MessageBounced)),
             62 => Some(EventType::Registry(RegistryEvent::LocalReadError)),
             54 => Some(EventType::Registry(RegistryEvent::LocalWriteError)),
             60 => Some(EventType::Registry(RegistryEvent::LocalParseError)),
@@ -2407,8 +2515,12 @@ impl EventType {
             558 => Some(EventType::Security(SecurityEvent::ScanBan)),
             550 => Some(EventType::Security(SecurityEvent::LoiterBan)),
             318 => Some(EventType::Security(SecurityEvent::IpBlocked)),
+            628 => Some(EventType::Security(SecurityEvent::TlsFingerprintBlocked)),
+            629 => Some(EventType::Security(SecurityEvent::LoginAnomaly)),
             593 => Some(EventType::Security(SecurityEvent::IpBlockExpired)),
             594 => Some(EventType::Security(SecurityEvent::IpAllowExpired)),
+            631 => Some(EventType::Security(SecurityEvent::FirewallSync)),
+            632 => Some(EventType::Security(SecurityEvent::FirewallSyncError)),
             279 => Some(EventType::Security(SecurityEvent::IpUnauthorized)),
             552 => Some(EventType::Security(SecurityEvent::Unauthorized)),
             393 => Some(EventType::Server(ServerEvent::Startup)),
@@ -2424,6 +2536,7 @@ impl EventType {
             399 => Some(EventType::Sieve(SieveEvent::ActionReject)),
             406 => Some(EventType::Sieve(SieveEvent::SendMessage)),
             401 => Some(EventType::Sieve(SieveEvent::MessageTooLarge)),
+            624 => Some(EventType::Sieve(SieveEvent::AutoReplySuppressed)),
             405 => Some(EventType::Sieve(SieveEvent::ScriptNotFound)),
             400 => Some(EventType::Sieve(SieveEvent::ListNotFound)),
             404 => Some(EventType::Sieve(SieveEvent::RuntimeError)),
@@ -2463,17 +2576,24 @@ impl EventType {
             447 => Some(EventType::Smtp(SmtpEvent::MailFromUnauthenticated)),
             448 => Some(EventType::Smtp(SmtpEvent::MailFromUnauthorized)),
             551 => Some(EventType::Smtp(SmtpEvent::MailFromNotAllowed)),
+            610 => Some(EventType::Smtp(SmtpEvent::DnsblRejected)),
             446 => Some(EventType::Smtp(SmtpEvent::MailFromRewritten)),
             445 => Some(EventType::Smtp(SmtpEvent::MailFromMissing)),
             444 => Some(EventType::Smtp(SmtpEvent::MailFrom)),
             456 => Some(EventType::Smtp(SmtpEvent::MultipleMailFrom)),
             449 => Some(EventType::Smtp(SmtpEvent::MailboxDoesNotExist)),
+            611 => Some(EventType::Smtp(SmtpEvent::MailboxFull)),
+            621 => Some(EventType::Smtp(SmtpEvent::DuplicateDiscarded)),
+            622 => Some(EventType::Smtp(SmtpEvent::DuplicateTagged)),
+            612 => Some(EventType::Smtp(SmtpEvent::RcptVerifyFailed)),
             468 => Some(EventType::Smtp(SmtpEvent::RelayNotAllowed)),
             464 => Some(EventType::Smtp(SmtpEvent::RcptTo)),
             465 => Some(EventType::Smtp(SmtpEvent::RcptToDuplicate)),
             467 => Some(EventType::Smtp(SmtpEvent::RcptToRewritten)),
+            605 => Some(EventType::Smtp(SmtpEvent::HeaderRewritten)),
             466 => Some(EventType::Smtp(SmtpEvent::RcptToMissing)),
             561 => Some(EventType::Smtp(SmtpEvent::RcptToGreylisted)),
+            626 => Some(EventType::Smtp(SmtpEvent::RcptToSuppressed)),
             484 => Some(EventType::Smtp(SmtpEvent::TooManyRecipients)),
             482 => Some(EventType::Smtp(SmtpEvent::TooManyInvalidRcpt)),
             462 => Some(EventType::Smtp(SmtpEvent::RawInput)),
@@ -2514,12 +2634,17 @@ impl EventType {
             470 => Some(EventType::Smtp(SmtpEvent::RequestTooLarge)),
             564 => Some(EventType::Spam(SpamEvent::Pyzor)),
             494 => Some(EventType::Spam(SpamEvent::PyzorError)),
+            606 => Some(EventType::Spam(SpamEvent::Rspamd)),
+            607 => Some(EventType::Spam(SpamEvent::RspamdError)),
             562 => Some(EventType::Spam(SpamEvent::Dnsbl)),
             563 => Some(EventType::Spam(SpamEvent::DnsblError)),
+            609 => Some(EventType::Spam(SpamEvent::DnsblDisabled)),
             588 => Some(EventType::Spam(SpamEvent::TrainStarted)),
             495 => Some(EventType::Spam(SpamEvent::TrainCompleted)),
             143 => Some(EventType::Spam(SpamEvent::TrainSampleAdded)),
             491 => Some(EventType::Spam(SpamEvent::TrainSampleNotFound)),
+            608 => Some(EventType::Spam(SpamEvent::TrainSampleThrottled)),
+            630 => Some(EventType::Spam(SpamEvent::TrapHit)),
             490 => Some(EventType::Spam(SpamEvent::Classify)),
             589 => Some(EventType::Spam(SpamEvent::ModelLoaded)),
             496 => Some(EventType::Spam(SpamEvent::ModelNotReady)),
@@ -2559,6 +2684,8 @@ impl EventType {
             51 => Some(EventType::Store(StoreEvent::CacheHit)),
             52 => Some(EventType::Store(StoreEvent::CacheStale)),
             577 => Some(EventType::Store(StoreEvent::CacheUpdate)),
+            635 => Some(EventType::Store(StoreEvent::ReadCacheHit)),
+            636 => Some(EventType::Store(StoreEvent::ReadCacheMiss)),
             507 => Some(EventType::Store(StoreEvent::BlobMissingMarker)),
             513 => Some(EventType::Store(StoreEvent::DataWrite)),
             512 => Some(EventType::Store(StoreEvent::DataIterate)),
@@ -2599,6 +2726,7 @@ impl EventType {
             366 => Some(EventType::Telemetry(TelemetryEvent::MetricsStored)),
             146 => Some(EventType::Telemetry(TelemetryEvent::MetricsPushed)),
             543 => Some(EventType::Tls(TlsEvent::Handshake)),
+            627 => Some(EventType::Tls(TlsEvent::ClientFingerprint)),
             544 => Some(EventType::Tls(TlsEvent::HandshakeError)),
             547 => Some(EventType::Tls(TlsEvent::NotConfigured)),
             542 => Some(EventType::Tls(TlsEvent::CertificateNotFound)),
@@ -2644,6 +2772,8 @@ impl EventType {
             EventType::Network(NetworkEvent::BindError) => Level::Error,
             EventType::Network(NetworkEvent::SplitError) => Level::Error,
             EventType::Network(NetworkEvent::SetOptError) => Level::Error,
+            EventType::Network(NetworkEvent::AgentCheckStart) => Level::Debug,
+            EventType::Network(NetworkEvent::AgentCheckEnd) => Level::Debug,
             EventType::Registry(RegistryEvent::LocalReadError) => Level::Error,
             EventType::Registry(RegistryEvent::LocalWriteError) => Level::Error,
             EventType::Registry(RegistryEvent::LocalParseError) => Level::Error,
@@ -2737,9 +2867,11 @@ impl EventType {
             EventType::Delivery(DeliveryEvent::DsnSuccess) => Level::Info,
             EventType::Delivery(DeliveryEvent::DsnTempFail) => Level::Info,
             EventType::Delivery(DeliveryEvent::DsnPermFail) => Level::Info,
+            EventType::Delivery(DeliveryEvent::DsnSuppressed) => Level::Info,
             EventType::Dkim(DkimEvent::SignatureCreated) => Level::Info,
             EventType::Dkim(DkimEvent::SignaturePublished) => Level::Info,
             EventType::Dkim(DkimEvent::SignatureRetiring) => Level::Info,
+            EventType::Dkim(DkimEvent::SignatureInvalidated) => Level::Info,
             EventType::Dkim(DkimEvent::SignatureRetired) => Level::Info,
             EventType::Dkim(DkimEvent::SignatureDeleted) => Level::Info,
             EventType::Dns(DnsEvent::RecordCreated) => Level::Info,
@@ -2763,6 +2895,8 @@ impl EventType {
             EventType::MessageIngest(MessageIngestEvent::ImapAppend) => Level::Info,
             EventType::MessageIngest(MessageIngestEvent::JmapAppend) => Level::Info,
             EventType::MessageIngest(MessageIngestEvent::Duplicate) => Level::Info,
+            EventType::MessageIngest(MessageIngestEvent::Forwarded) => Level::Info,
+            EventType::MessageIngest(MessageIngestEvent::NotEncrypted) => Level::Warn,
             EventType::MessageIngest(MessageIngestEvent::SearchIndex) => Level::Info,
             EventType::Milter(MilterEvent::ActionAccept) => Level::Info,
             EventType::Milter(MilterEvent::ActionDiscard) => Level::Info,
@@ -2819,8 +2953,12 @@ impl EventType {
             EventType::Security(SecurityEvent::ScanBan) => Level::Info,
             EventType::Security(SecurityEvent::LoiterBan) => Level::Info,
             EventType::Security(SecurityEvent::IpBlocked) => Level::Info,
+            EventType::Security(SecurityEvent::TlsFingerprintBlocked) => Level::Info,
+            EventType::Security(SecurityEvent::LoginAnomaly) => Level::Warn,
             EventType::Security(SecurityEvent::IpBlockExpired) => Level::Info,
             EventType::Security(SecurityEvent::IpAllowExpired) => Level::Info,
+            EventType::Security(SecurityEvent::FirewallSync) => Level::Info,
+            EventType::Security(SecurityEvent::FirewallSyncError) => Level::Warn,
             EventType::Security(SecurityEvent::IpUnauthorized) => Level::Info,
             EventType::Security(SecurityEvent::Unauthorized) => Level::Info,
             EventType::Server(ServerEvent::Startup) => Level::Info,
@@ -2852,9 +2990,14 @@ impl EventType {
             EventType::Smtp(SmtpEvent::InvalidEhlo) => Level::Info,
             EventType::Smtp(SmtpEvent::MailFrom) => Level::Info,
             EventType::Smtp(SmtpEvent::MailboxDoesNotExist) => Level::Info,
+            EventType::Smtp(SmtpEvent::MailboxFull) => Level::Info,
+            EventType::Smtp(SmtpEvent::DuplicateDiscarded) => Level::Info,
+            EventType::Smtp(SmtpEvent::DuplicateTagged) => Level::Info,
+            EventType::Smtp(SmtpEvent::RcptVerifyFailed) => Level::Info,
             EventType::Smtp(SmtpEvent::RelayNotAllowed) => Level::Info,
             EventType::Smtp(SmtpEvent::RcptTo) => Level::Info,
             EventType::Smtp(SmtpEvent::RcptToGreylisted) => Level::Info,
+            EventType::Smtp(SmtpEvent::RcptToSuppressed) => Level::Info,
             EventType::Smtp(SmtpEvent::TooManyRecipients) => Level::Info,
             EventType::Smtp(SmtpEvent::TooManyInvalidRcpt) => Level::Info,
             EventType::Smtp(SmtpEvent::Vrfy) => Level::Info,
@@ -2872,6 +3015,7 @@ impl EventType {
             EventType::Spam(SpamEvent::ModelNotReady) => Level::Info,
             EventType::Spam(SpamEvent::ModelNotFound) => Level::Info,
             EventType::Spam(SpamEvent::RulesUpdated) => Level::Info,
+            EventType::Spam(SpamEvent::TrapHit) => Level::Info,
             EventType::Store(StoreEvent::BlobStorePurged) => Level::Info,
             EventType::Store(StoreEvent::DataStorePurged) => Level::Info,
             EventType::TaskManager(TaskManagerEvent::TaskQueued) => Level::Info,
@@ -2881,6 +3025,7 @@ impl EventType {
             EventType::Telemetry(TelemetryEvent::AlertMessage) => Level::Info,
             EventType::Telemetry(TelemetryEvent::MetricsCollected) => Level::Info,
             EventType::Tls(TlsEvent::Handshake) => Level::Info,
+            EventType::Tls(TlsEvent::ClientFingerprint) => Level::Debug,
             EventType::Tls(TlsEvent::ExpiredCertificateRemoved) => Level::Info,
             EventType::TlsRpt(TlsRptEvent::RecordFetch) => Level::Info,
             EventType::TlsRpt(TlsRptEvent::RecordFetchError) => Level::Info,
@@ -2949,10 +3094,17 @@ impl EventType {
             EventType::MtaHook(MtaHookEvent::Error) => Level::Warn,
             EventType::Network(NetworkEvent::ProxyError) => Level::Warn,
             EventType::Queue(QueueEvent::BackPressure) => Level::Warn,
+            EventType::Queue(QueueEvent::MessageDelivered) => Level::Info,
+            EventType::Queue(QueueEvent::MessageDeferred) => Level::Info,
+            EventType::Imap(ImapEvent::Proxy) => Level::Info,
+            EventType::Imap(ImapEvent::LoginReferral) => Level::Info,
+            EventType::Pop3(Pop3Event::Proxy) => Level::Info,
+            EventType::Queue(QueueEvent::MessageBounced) => Level::Info,
             EventType::Registry(RegistryEvent::BuildWarning) => Level::Warn,
             EventType::Server(ServerEvent::RecoveryMode) => Level::Warn,
             EventType::Server(ServerEvent::BootstrapMode) => Level::Warn,
             EventType::Sieve(SieveEvent::MessageTooLarge) => Level::Warn,
+            EventType::Sieve(SieveEvent::AutoReplySuppressed) => Level::Info,
             EventType::Sieve(SieveEvent::ScriptNotFound) => Level::Warn,
             EventType::Sieve(SieveEvent::ListNotFound) => Level::Warn,
             EventType::Sieve(SieveEvent::NotSupported) => Level::Warn,
@@ -2960,6 +3112,7 @@ impl EventType {
             EventType::Smtp(SmtpEvent::IdNotFound) => Level::Warn,
             EventType::Smtp(SmtpEvent::MissingLocalHostname) => Level::Warn,
             EventType::Spam(SpamEvent::TrainSampleNotFound) => Level::Warn,
+            EventType::Spam(SpamEvent::DnsblDisabled) => Level::Warn,
             EventType::Store(StoreEvent::HttpStoreError) => Level::Warn,
             EventType::Store(StoreEvent::BlobMissingMarker) => Level::Warn,
             EventType::TaskManager(TaskManagerEvent::TaskFailed) => Level::Warn,
@@ -3105,6 +3258,7 @@ impl EventType {
             EventType::Delivery(DeliveryEvent::DsnSuccess) => "DSN success notification",
             EventType::Delivery(DeliveryEvent::DsnTempFail) => "DSN temporary failure notification",
             EventType::Delivery(DeliveryEvent::DsnPermFail) => "DSN permanent failure notification",
+            EventType::Delivery(DeliveryEvent::DsnSuppressed) => "DSN suppressed",
             EventType::Delivery(DeliveryEvent::RawInput) => "Raw SMTP input received",
             EventType::Delivery(DeliveryEvent::RawOutput) => "Raw SMTP output sent",
             EventType::Dkim(DkimEvent::Pass) => "DKIM verification passed",
@@ -3131,6 +3285,7 @@ impl EventType {
             EventType::Dkim(DkimEvent::SignatureCreated) => "DKIM signature created",
             EventType::Dkim(DkimEvent::SignaturePublished) => "DKIM signature published",
             EventType::Dkim(DkimEvent::SignatureRetiring) => "DKIM signature retiring",
+            EventType::Dkim(DkimEvent::SignatureInvalidated) => "DKIM signature invalidated",
             EventType::Dkim(DkimEvent::SignatureRetired) => "DKIM signature retired",
             EventType::Dkim(DkimEvent::SignatureDeleted) => "DKIM signature deleted",
             EventType::Dmarc(DmarcEvent::Pass) => "DMARC check passed",
@@ -3198,6 +3353,8 @@ impl EventType {
             EventType::Imap(ImapEvent::Error) => "IMAP error occurred",
             EventType::Imap(ImapEvent::RawInput) => "Raw IMAP input received",
             EventType::Imap(ImapEvent::RawOutput) => "Raw IMAP output sent",
+            EventType::Imap(ImapEvent::Proxy) => "IMAP session proxied to backend",
+            EventType::Imap(ImapEvent::LoginReferral) => "IMAP login referred to another server",
             EventType::IncomingReport(IncomingReportEvent::DmarcReport) => "DMARC report received",
             EventType::IncomingReport(IncomingReportEvent::DmarcReportWithWarnings) => {
                 "DMARC report received with warnings"
@@ -3330,6 +3487,8 @@ impl EventType {
             EventType::MessageIngest(MessageIngestEvent::ImapAppend) => "Message appended via IMAP",
             EventType::MessageIngest(MessageIngestEvent::JmapAppend) => "Message appended via JMAP",
             EventType::MessageIngest(MessageIngestEvent::Duplicate) => "Skipping duplicate message",
+            EventType::MessageIngest(MessageIngestEvent::Forwarded) => "Message forwarded",
+            EventType::MessageIngest(MessageIngestEvent::NotEncrypted) => "Message stored without encryption",
             EventType::MessageIngest(MessageIngestEvent::Error) => "Message ingestion error",
             EventType::MessageIngest(MessageIngestEvent::SearchIndex) => "Search index updated",
             EventType::Milter(MilterEvent::Read) => "Reading from Milter",
@@ -3377,6 +3536,8 @@ impl EventType {
             EventType::Network(NetworkEvent::Closed) => "Network connection closed",
             EventType::Network(NetworkEvent::ProxyError) => "Proxy protocol error",
             EventType::Network(NetworkEvent::SetOptError) => "Network set option error",
+            EventType::Network(NetworkEvent::AgentCheckStart) => "Agent check connection started",
+            EventType::Network(NetworkEvent::AgentCheckEnd) => "Agent check connection ended",
             EventType::OutgoingReport(OutgoingReportEvent::SpfReport) => "SPF report sent",
             EventType::OutgoingReport(OutgoingReportEvent::SpfRateLimited) => {
                 "SPF report rate limited"
@@ -3435,6 +3596,7 @@ impl EventType {
             EventType::Pop3(Pop3Event::Error) => "POP3 error occurred",
             EventType::Pop3(Pop3Event::RawInput) => "Raw POP3 input received",
             EventType::Pop3(Pop3Event::RawOutput) => "Raw POP3 output sent",
+            EventType::Pop3(Pop3Event::Proxy) => "POP3 session proxied to backend",
             EventType::PushSubscription(PushSubscriptionEvent::Success) => {
                 "Push subscription successful"
             }
@@ -3459,6 +3621,9 @@ impl EventType {
             EventType::Queue(QueueEvent::ConcurrencyLimitExceeded) => "Concurrency limit exceeded",
             EventType::Queue(QueueEvent::QuotaExceeded) => "Quota exceeded",
             EventType::Queue(QueueEvent::BackPressure) => "Queue backpressure detected",
+            EventType::Queue(QueueEvent::MessageDelivered) => "Queued message delivered",
+            EventType::Queue(QueueEvent::MessageDeferred) => "Queued message delivery deferred",
+            EventType::Queue(QueueEvent::MessageBounced) => "Queued message bounced",
             EventType::Registry(RegistryEvent::LocalReadError) => "Local registry read error",
             EventType::Registry(RegistryEvent::LocalWriteError) => "Local registry write error",
             EventType::Registry(RegistryEvent::LocalParseError) => "Local registry parse error",
@@ -3490,8 +3655,12 @@ impl EventType {
             EventType::Security(SecurityEvent::ScanBan) => "Banned due to scan",
             EventType::Security(SecurityEvent::LoiterBan) => "Banned due to loitering",
             EventType::Security(SecurityEvent::IpBlocked) => "Blocked IP address",
+            EventType::Security(SecurityEvent::TlsFingerprintBlocked) => "Blocked TLS fingerprint",
+            EventType::Security(SecurityEvent::LoginAnomaly) => "Unusual login detected",
             EventType::Security(SecurityEvent::IpBlockExpired) => "IP block expired",
             EventType::Security(SecurityEvent::IpAllowExpired) => "IP allow expired",
+            EventType::Security(SecurityEvent::FirewallSync) => "Firewall synchronized",
+            EventType::Security(SecurityEvent::FirewallSyncError) => "Firewall synchronization failed",
             EventType::Security(SecurityEvent::IpUnauthorized) => "Unauthorized IP address",
             EventType::Security(SecurityEvent::Unauthorized) => "Unauthorized access",
             EventType::Server(ServerEvent::Startup) => "Starting Stalwart Server",
@@ -3507,6 +3676,7 @@ impl EventType {
             EventType::Sieve(SieveEvent::ActionReject) => "Sieve action: Reject",
             EventType::Sieve(SieveEvent::SendMessage) => "Sieve sending message",
             EventType::Sieve(SieveEvent::MessageTooLarge) => "Sieve message too large",
+            EventType::Sieve(SieveEvent::AutoReplySuppressed) => "Sieve auto-reply suppressed",
             EventType::Sieve(SieveEvent::ScriptNotFound) => "Sieve script not found",
             EventType::Sieve(SieveEvent::ListNotFound) => "Sieve list not found",
             EventType::Sieve(SieveEvent::RuntimeError) => "Sieve runtime error",
@@ -3548,17 +3718,24 @@ impl EventType {
             }
             EventType::Smtp(SmtpEvent::MailFromUnauthorized) => "MAIL FROM unauthorized",
             EventType::Smtp(SmtpEvent::MailFromNotAllowed) => "MAIL FROM not allowed",
+            EventType::Smtp(SmtpEvent::DnsblRejected) => "Rejected by DNSBL",
             EventType::Smtp(SmtpEvent::MailFromRewritten) => "MAIL FROM address rewritten",
             EventType::Smtp(SmtpEvent::MailFromMissing) => "MAIL FROM address missing",
             EventType::Smtp(SmtpEvent::MailFrom) => "SMTP MAIL FROM command",
             EventType::Smtp(SmtpEvent::MultipleMailFrom) => "Multiple MAIL FROM commands",
             EventType::Smtp(SmtpEvent::MailboxDoesNotExist) => "Mailbox does not exist",
+            EventType::Smtp(SmtpEvent::MailboxFull) => "Mailbox quota exceeded",
+            EventType::Smtp(SmtpEvent::DuplicateDiscarded) => "Duplicate message discarded",
+            EventType::Smtp(SmtpEvent::DuplicateTagged) => "Duplicate message tagged",
+            EventType::Smtp(SmtpEvent::RcptVerifyFailed) => "Recipient verification failed",
             EventType::Smtp(SmtpEvent::RelayNotAllowed) => "Relay not allowed",
             EventType::Smtp(SmtpEvent::RcptTo) => "SMTP RCPT TO command",
             EventType::Smtp(SmtpEvent::RcptToDuplicate) => "Duplicate RCPT TO",
             EventType::Smtp(SmtpEvent::RcptToRewritten) => "RCPT TO address rewritten",
+            EventType::Smtp(SmtpEvent::HeaderRewritten) => "Message header addresses rewritten",
             EventType::Smtp(SmtpEvent::RcptToMissing) => "RCPT TO address missing",
             EventType::Smtp(SmtpEvent::RcptToGreylisted) => "RCPT TO greylisted",
+            EventType::Smtp(SmtpEvent::RcptToSuppressed) => "RCPT TO suppressed",
             EventType::Smtp(SmtpEvent::TooManyRecipients) => "Too many recipients",
             EventType::Smtp(SmtpEvent::TooManyInvalidRcpt) => "Too many invalid recipients",
             EventType::Smtp(SmtpEvent::RawInput) => "Raw SMTP input received",
@@ -3601,12 +3778,17 @@ impl EventType {
             EventType::Smtp(SmtpEvent::RequestTooLarge) => "Request too large",
             EventType::Spam(SpamEvent::Pyzor) => "Pyzor success",
             EventType::Spam(SpamEvent::PyzorError) => "Pyzor error",
+            EventType::Spam(SpamEvent::Rspamd) => "Rspamd check completed",
+            EventType::Spam(SpamEvent::RspamdError) => "Rspamd error",
             EventType::Spam(SpamEvent::Dnsbl) => "DNSBL query",
             EventType::Spam(SpamEvent::DnsblError) => "Error querying DNSBL",
+            EventType::Spam(SpamEvent::DnsblDisabled) => "DNSBL temporarily disabled",
             EventType::Spam(SpamEvent::TrainStarted) => "Spam classifier training started",
             EventType::Spam(SpamEvent::TrainCompleted) => "Spam classifier training completed",
             EventType::Spam(SpamEvent::TrainSampleAdded) => "New training sample added",
             EventType::Spam(SpamEvent::TrainSampleNotFound) => "Training sample not found",
+            EventType::Spam(SpamEvent::TrainSampleThrottled) => "Training sample rate limited",
+            EventType::Spam(SpamEvent::TrapHit) => "Spam trap hit",
             EventType::Spam(SpamEvent::Classify) => "Classifying message for spam",
             EventType::Spam(SpamEvent::ModelLoaded) => "Spam classifier model loaded",
             EventType::Spam(SpamEvent::ModelNotReady) => "Spam classifier model not ready",
@@ -3648,6 +3830,8 @@ impl EventType {
             EventType::Store(StoreEvent::CacheHit) => "Cache hit",
             EventType::Store(StoreEvent::CacheStale) => "Cache is stale",
             EventType::Store(StoreEvent::CacheUpdate) => "Cache update",
+            EventType::Store(StoreEvent::ReadCacheHit) => "Read cache hit",
+            EventType::Store(StoreEvent::ReadCacheMiss) => "Read cache miss",
             EventType::Store(StoreEvent::BlobMissingMarker) => "Blob missing marker",
             EventType::Store(StoreEvent::DataWrite) => "Write batch operation",
             EventType::Store(StoreEvent::DataIterate) => "Data store iteration operation",
@@ -3698,6 +3882,7 @@ impl EventType {
             EventType::Telemetry(TelemetryEvent::MetricsStored) => "Metric store",
             EventType::Telemetry(TelemetryEvent::MetricsPushed) => "Metrics pushed",
             EventType::Tls(TlsEvent::Handshake) => "TLS handshake",
+            EventType::Tls(TlsEvent::ClientFingerprint) => "TLS client fingerprint",
             EventType::Tls(TlsEvent::HandshakeError) => "TLS handshake error",
             EventType::Tls(TlsEvent::NotConfigured) => "TLS not configured",
             EventType::Tls(TlsEvent::CertificateNotFound) => "TLS certificate not found",
@@ -3779,6 +3964,8 @@ impl EventType {
             EventType::Imap(ImapEvent::Error) => "IMAP error",
             EventType::Imap(ImapEvent::RawInput) => "IMAP error",
             EventType::Imap(ImapEvent::RawOutput) => "IMAP error",
+            EventType::Imap(ImapEvent::Proxy) => "IMAP error",
+            EventType::Imap(ImapEvent::LoginReferral) => "IMAP error",
             EventType::Jmap(JmapEvent::MethodCall) => "Other message",
             EventType::Jmap(JmapEvent::InvalidArguments) => "Invalid arguments",
             EventType::Jmap(JmapEvent::RequestTooLarge) => "Request too large",
@@ -3843,6 +4030,8 @@ impl EventType {
             EventType::Network(NetworkEvent::Closed) => "Network error",
             EventType::Network(NetworkEvent::ProxyError) => "Network error",
             EventType::Network(NetworkEvent::SetOptError) => "Network error",
+            EventType::Network(NetworkEvent::AgentCheckStart) => "Network error",
+            EventType::Network(NetworkEvent::AgentCheckEnd) => "Network error",
             EventType::Pop3(Pop3Event::ConnectionStart) => "POP3 error",
             EventType::Pop3(Pop3Event::ConnectionEnd) => "POP3 error",
             EventType::Pop3(Pop3Event::Delete) => "POP3 error",
@@ -3861,6 +4050,7 @@ impl EventType {
             EventType::Pop3(Pop3Event::Error) => "POP3 error",
             EventType::Pop3(Pop3Event::RawInput) => "POP3 error",
             EventType::Pop3(Pop3Event::RawOutput) => "POP3 error",
+            EventType::Pop3(Pop3Event::Proxy) => "POP3 error",
             EventType::Resource(ResourceEvent::NotFound) => "Not found",
             EventType::Resource(ResourceEvent::BadParameters) => "Bad parameters",
             EventType::Resource(ResourceEvent::Error) => "Resource error",
@@ -3870,8 +4060,12 @@ impl EventType {
             EventType::Security(SecurityEvent::ScanBan) => "Insufficient permissions",
             EventType::Security(SecurityEvent::LoiterBan) => "Insufficient permissions",
             EventType::Security(SecurityEvent::IpBlocked) => "Insufficient permissions",
+            EventType::Security(SecurityEvent::TlsFingerprintBlocked) => "Insufficient permissions",
+            EventType::Security(SecurityEvent::LoginAnomaly) => "Login deviates from account history",
             EventType::Security(SecurityEvent::IpBlockExpired) => "Insufficient permissions",
             EventType::Security(SecurityEvent::IpAllowExpired) => "Insufficient permissions",
+            EventType::Security(SecurityEvent::FirewallSync) => "Insufficient permissions",
+            EventType::Security(SecurityEvent::FirewallSyncError) => "Insufficient permissions",
             EventType::Security(SecurityEvent::IpUnauthorized) => "Unauthorized IP address",
             EventType::Security(SecurityEvent::Unauthorized) => "Insufficient permissions",
             EventType::Smtp(SmtpEvent::ConnectionStart) => "SMTP error",
@@ -3907,17 +4101,24 @@ impl EventType {
             EventType::Smtp(SmtpEvent::MailFromUnauthenticated) => "SMTP error",
             EventType::Smtp(SmtpEvent::MailFromUnauthorized) => "SMTP error",
             EventType::Smtp(SmtpEvent::MailFromNotAllowed) => "SMTP error",
+            EventType::Smtp(SmtpEvent::DnsblRejected) => "SMTP error",
             EventType::Smtp(SmtpEvent::MailFromRewritten) => "SMTP error",
             EventType::Smtp(SmtpEvent::MailFromMissing) => "SMTP error",
             EventType::Smtp(SmtpEvent::MailFrom) => "SMTP error",
             EventType::Smtp(SmtpEvent::MultipleMailFrom) => "SMTP error",
             EventType::Smtp(SmtpEvent::MailboxDoesNotExist) => "SMTP error",
+            EventType::Smtp(SmtpEvent::MailboxFull) => "SMTP error",
+            EventType::Smtp(SmtpEvent::DuplicateDiscarded) => "SMTP error",
+            EventType::Smtp(SmtpEvent::DuplicateTagged) => "SMTP error",
+            EventType::Smtp(SmtpEvent::RcptVerifyFailed) => "SMTP error",
             EventType::Smtp(SmtpEvent::RelayNotAllowed) => "SMTP error",
             EventType::Smtp(SmtpEvent::RcptTo) => "SMTP error",
             EventType::Smtp(SmtpEvent::RcptToDuplicate) => "SMTP error",
             EventType::Smtp(SmtpEvent::RcptToRewritten) => "SMTP error",
+            EventType::Smtp(SmtpEvent::HeaderRewritten) => "SMTP error",
             EventType::Smtp(SmtpEvent::RcptToMissing) => "SMTP error",
             EventType::Smtp(SmtpEvent::RcptToGreylisted) => "SMTP error",
+            EventType::Smtp(SmtpEvent::RcptToSuppressed) => "SMTP error",
             EventType::Smtp(SmtpEvent::TooManyRecipients) => "SMTP error",
             EventType::Smtp(SmtpEvent::TooManyInvalidRcpt) => "SMTP error",
             EventType::Smtp(SmtpEvent::RawInput) => "SMTP error",
@@ -3983,6 +4184,8 @@ impl EventType {
             EventType::Store(StoreEvent::CacheHit) => "Store error",
             EventType::Store(StoreEvent::CacheStale) => "Store error",
             EventType::Store(StoreEvent::CacheUpdate) => "Store error",
+            EventType::Store(StoreEvent::ReadCacheHit) => "Store error",
+            EventType::Store(StoreEvent::ReadCacheMiss) => "Store error",
             EventType::Store(StoreEvent::BlobMissingMarker) => "Blob is missing marker",
             EventType::Store(StoreEvent::DataWrite) => "Store error",
             EventType::Store(StoreEvent::DataIterate) => "Store error",
@@ -4102,6 +4305,7 @@ impl EventType {
             EventType::Delivery(DeliveryEvent::DsnSuccess),
             EventType::Delivery(DeliveryEvent::DsnTempFail),
             EventType::Delivery(DeliveryEvent::DsnPermFail),
+            EventType::Delivery(DeliveryEvent::DsnSuppressed),
             EventType::Delivery(DeliveryEvent::RawInput),
             EventType::Delivery(DeliveryEvent::RawOutput),
             EventType::Dkim(DkimEvent::Pass),
@@ -4126,6 +4330,7 @@ impl EventType {
             EventType::Dkim(DkimEvent::SignatureCreated),
             EventType::Dkim(DkimEvent::SignaturePublished),
             EventType::Dkim(DkimEvent::SignatureRetiring),
+            EventType::Dkim(DkimEvent::SignatureInvalidated),
             EventType::Dkim(DkimEvent::SignatureRetired),
             EventType::Dkim(DkimEvent::SignatureDeleted),
             EventType::Dmarc(DmarcEvent::Pass),
@@ -4189,6 +4394,8 @@ impl EventType {
             EventType::Imap(ImapEvent::Error),
             EventType::Imap(ImapEvent::RawInput),
             EventType::Imap(ImapEvent::RawOutput),
+            EventType::Imap(ImapEvent::Proxy),
+            EventType::Imap(ImapEvent::LoginReferral),
             EventType::IncomingReport(IncomingReportEvent::DmarcReport),
             EventType::IncomingReport(IncomingReportEvent::DmarcReportWithWarnings),
             EventType::IncomingReport(IncomingReportEvent::TlsReport),
@@ -4275,6 +4482,8 @@ impl EventType {
             EventType::MessageIngest(MessageIngestEvent::ImapAppend),
             EventType::MessageIngest(MessageIngestEvent::JmapAppend),
             EventType::MessageIngest(MessageIngestEvent::Duplicate),
+            EventType::MessageIngest(MessageIngestEvent::Forwarded),
+            EventType::MessageIngest(MessageIngestEvent::NotEncrypted),
             EventType::MessageIngest(MessageIngestEvent::Error),
             EventType::MessageIngest(MessageIngestEvent::SearchIndex),
             EventType::Milter(MilterEvent::Read),
@@ -4318,6 +4527,8 @@ impl EventType {
             EventType::Network(NetworkEvent::Closed),
             EventType::Network(NetworkEvent::ProxyError),
             EventType::Network(NetworkEvent::SetOptError),
+            EventType::Network(NetworkEvent::AgentCheckStart),
+            EventType::Network(NetworkEvent::AgentCheckEnd),
             EventType::OutgoingReport(OutgoingReportEvent::SpfReport),
             EventType::OutgoingReport(OutgoingReportEvent::SpfRateLimited),
             EventType::OutgoingReport(OutgoingReportEvent::DkimReport),
@@ -4352,6 +4563,7 @@ impl EventType {
             EventType::Pop3(Pop3Event::Error),
             EventType::Pop3(Pop3Event::RawInput),
             EventType::Pop3(Pop3Event::RawOutput),
+            EventType::Pop3(Pop3Event::Proxy),
             EventType::PushSubscription(PushSubscriptionEvent::Success),
             EventType::PushSubscription(PushSubscriptionEvent::Error),
             EventType::PushSubscription(PushSubscriptionEvent::NotFound),
@@ -4368,6 +4580,9 @@ impl EventType {
             EventType::Queue(QueueEvent::ConcurrencyLimitExceeded),
             EventType::Queue(QueueEvent::QuotaExceeded),
             EventType::Queue(QueueEvent::BackPressure),
+            EventType::Queue(QueueEvent::MessageDelivered),
+            EventType::Queue(QueueEvent::MessageDeferred),
+            EventType::Queue(QueueEvent::MessageBounced),
             EventType::Registry(RegistryEvent::LocalReadError),
             EventType::Registry(RegistryEvent::LocalWriteError),
             EventType::Registry(RegistryEvent::LocalParseError),
@@ -4389,8 +4604,12 @@ impl EventType {
             EventType::Security(SecurityEvent::ScanBan),
             EventType::Security(SecurityEvent::LoiterBan),
             EventType::Security(SecurityEvent::IpBlocked),
+            EventType::Security(SecurityEvent::TlsFingerprintBlocked),
+            EventType::Security(SecurityEvent::LoginAnomaly),
             EventType::Security(SecurityEvent::IpBlockExpired),
             EventType::Security(SecurityEvent::IpAllowExpired),
+            EventType::Security(SecurityEvent::FirewallSync),
+            EventType::Security(SecurityEvent::FirewallSyncError),
             EventType::Security(SecurityEvent::IpUnauthorized),
             EventType::Security(SecurityEvent::Unauthorized),
             EventType::Server(ServerEvent::Startup),
@@ -4406,6 +4625,7 @@ impl EventType {
             EventType::Sieve(SieveEvent::ActionReject),
             EventType::Sieve(SieveEvent::SendMessage),
             EventType::Sieve(SieveEvent::MessageTooLarge),
+            EventType::Sieve(SieveEvent::AutoReplySuppressed),
             EventType::Sieve(SieveEvent::ScriptNotFound),
             EventType::Sieve(SieveEvent::ListNotFound),
             EventType::Sieve(SieveEvent::RuntimeError),
@@ -4445,17 +4665,24 @@ impl EventType {
             EventType::Smtp(SmtpEvent::MailFromUnauthenticated),
             EventType::Smtp(SmtpEvent::MailFromUnauthorized),
             EventType::Smtp(SmtpEvent::MailFromNotAllowed),
+            EventType::Smtp(SmtpEvent::DnsblRejected),
             EventType::Smtp(SmtpEvent::MailFromRewritten),
             EventType::Smtp(SmtpEvent::MailFromMissing),
             EventType::Smtp(SmtpEvent::MailFrom),
             EventType::Smtp(SmtpEvent::MultipleMailFrom),
             EventType::Smtp(SmtpEvent::MailboxDoesNotExist),
+            EventType::Smtp(SmtpEvent::MailboxFull),
+            EventType::Smtp(SmtpEvent::DuplicateDiscarded),
+            EventType::Smtp(SmtpEvent::DuplicateTagged),
+            EventType::Smtp(SmtpEvent::RcptVerifyFailed),
             EventType::Smtp(SmtpEvent::RelayNotAllowed),
             EventType::Smtp(SmtpEvent::RcptTo),
             EventType::Smtp(SmtpEvent::RcptToDuplicate),
             EventType::Smtp(SmtpEvent::RcptToRewritten),
+            EventType::Smtp(SmtpEvent::HeaderRewritten),
             EventType::Smtp(SmtpEvent::RcptToMissing),
             EventType::Smtp(SmtpEvent::RcptToGreylisted),
+            EventType::Smtp(SmtpEvent::RcptToSuppressed),
             EventType::Smtp(SmtpEvent::TooManyRecipients),
             EventType::Smtp(SmtpEvent::TooManyInvalidRcpt),
             EventType::Smtp(SmtpEvent::RawInput),
@@ -4496,12 +4723,17 @@ impl EventType {
             EventType::Smtp(SmtpEvent::RequestTooLarge),
             EventType::Spam(SpamEvent::Pyzor),
             EventType::Spam(SpamEvent::PyzorError),
+            EventType::Spam(SpamEvent::Rspamd),
+            EventType::Spam(SpamEvent::RspamdError),
             EventType::Spam(SpamEvent::Dnsbl),
             EventType::Spam(SpamEvent::DnsblError),
+            EventType::Spam(SpamEvent::DnsblDisabled),
             EventType::Spam(SpamEvent::TrainStarted),
             EventType::Spam(SpamEvent::TrainCompleted),
             EventType::Spam(SpamEvent::TrainSampleAdded),
             EventType::Spam(SpamEvent::TrainSampleNotFound),
+            EventType::Spam(SpamEvent::TrainSampleThrottled),
+            EventType::Spam(SpamEvent::TrapHit),
             EventType::Spam(SpamEvent::Classify),
             EventType::Spam(SpamEvent::ModelLoaded),
             EventType::Spam(SpamEvent::ModelNotReady),
@@ -4541,6 +4773,8 @@ impl EventType {
             EventType::Store(StoreEvent::CacheHit),
             EventType::Store(StoreEvent::CacheStale),
             EventType::Store(StoreEvent::CacheUpdate),
+            EventType::Store(StoreEvent::ReadCacheHit),
+            EventType::Store(StoreEvent::ReadCacheMiss),
             EventType::Store(StoreEvent::BlobMissingMarker),
             EventType::Store(StoreEvent::DataWrite),
             EventType::Store(StoreEvent::DataIterate),
@@ -4577,6 +4811,7 @@ impl EventType {
             EventType::Telemetry(TelemetryEvent::MetricsStored),
             EventType::Telemetry(TelemetryEvent::MetricsPushed),
             EventType::Tls(TlsEvent::Handshake),
+            EventType::Tls(TlsEvent::ClientFingerprint),
             EventType::Tls(TlsEvent::HandshakeError),
             EventType::Tls(TlsEvent::NotConfigured),
             EventType::Tls(TlsEvent::CertificateNotFound),
@@ -4673,6 +4908,7 @@ impl MetricType {
             b"delivery.dsn-success" => MetricType::DeliveryDsnSuccess,
             b"delivery.dsn-temp-fail" => MetricType::DeliveryDsnTempFail,
             b"delivery.dsn-perm-fail" => MetricType::DeliveryDsnPermFail,
+            b"delivery.dsn-suppressed" => MetricType::DeliveryDsnSuppressed,
             b"dkim.pass" => MetricType::DkimPass,
             b"dkim.neutral" => MetricType::DkimNeutral,
             b"dkim.fail" => MetricType::DkimFail,
@@ -4899,6 +5135,7 @@ impl MetricType {
             b"spam.dnsbl-error" => MetricType::SpamDnsblError,
             b"spam.train-completed" => MetricType::SpamTrainCompleted,
             b"spam.train-sample-added" => MetricType::SpamTrainSampleAdded,
+            b"spam.trap-hit" => MetricType::SpamTrapHit,
             b"spam.classify" => MetricType::SpamClassify,
             b"spam.model-not-ready" => MetricType::SpamModelNotReady,
             b"spf.pass" => MetricType::SpfPass,
@@ -4936,6 +5173,8 @@ impl MetricType {
             b"store.http-store-error" => MetricType::StoreHttpStoreError,
             b"store.blob-missing-marker" => MetricType::StoreBlobMissingMarker,
             b"store.data-write" => MetricType::StoreDataWrite,
+            b"store.read-cache-hit" => MetricType::StoreReadCacheHit,
+            b"store.read-cache-miss" => MetricType::StoreReadCacheMiss,
             b"store.data-iterate" => MetricType::StoreDataIterate,
             b"store.blob-read" => MetricType::StoreBlobRead,
             b"store.blob-write" => MetricType::StoreBlobWrite,
@@ -5018,6 +5257,7 @@ impl MetricType {
             MetricType::DeliveryDsnSuccess => "delivery.dsn-success",
             MetricType::DeliveryDsnTempFail => "delivery.dsn-temp-fail",
             MetricType::DeliveryDsnPermFail => "delivery.dsn-perm-fail",
+            MetricType::DeliveryDsnSuppressed => "delivery.dsn-suppressed",
             MetricType::DkimPass => "dkim.pass",
             MetricType::DkimNeutral => "dkim.neutral",
             MetricType::DkimFail => "dkim.fail",
@@ -5254,6 +5494,7 @@ impl MetricType {
             MetricType::SpamDnsblError => "spam.dnsbl-error",
             MetricType::SpamTrainCompleted => "spam.train-completed",
             MetricType::SpamTrainSampleAdded => "spam.train-sample-added",
+            MetricType::SpamTrapHit => "spam.trap-hit",
             MetricType::SpamClassify => "spam.classify",
             MetricType::SpamModelNotReady => "spam.model-not-ready",
             MetricType::SpfPass => "spf.pass",
@@ -5291,6 +5532,8 @@ impl MetricType {
             MetricType::StoreHttpStoreError => "store.http-store-error",
             MetricType::StoreBlobMissingMarker => "store.blob-missing-marker",
             MetricType::StoreDataWrite => "store.data-write",
+            MetricType::StoreReadCacheHit => "store.read-cache-hit",
+            MetricType::StoreReadCacheMiss => "store.read-cache-miss",
             MetricType::StoreDataIterate => "store.data-iterate",
             MetricType::StoreBlobRead => "store.blob-read",
             MetricType::StoreBlobWrite => "store.blob-write",
@@ -5374,6 +5617,7 @@ impl MetricType {
             MetricType::DeliveryDsnSuccess => 85,
             MetricType::DeliveryDsnTempFail => 86,
             MetricType::DeliveryDsnPermFail => 87,
+            MetricType::DeliveryDsnSuppressed => 339,
             MetricType::DkimPass => 88,
             MetricType::DkimNeutral => 89,
             MetricType::DkimFail => 90,
@@ -5600,6 +5844,7 @@ impl MetricType {
             MetricType::SpamDnsblError => 288,
             MetricType::SpamTrainCompleted => 289,
             MetricType::SpamTrainSampleAdded => 290,
+            MetricType::SpamTrapHit => 340,
             MetricType::SpamClassify => 291,
             MetricType::SpamModelNotReady => 292,
             MetricType::SpfPass => 293,
@@ -5637,6 +5882,8 @@ impl MetricType {
             MetricType::StoreHttpStoreError => 321,
             MetricType::StoreBlobMissingMarker => 322,
             MetricType::StoreDataWrite => 323,
+            MetricType::StoreReadCacheHit => 341,
+            MetricType::StoreReadCacheMiss => 342,
             MetricType::StoreDataIterate => 324,
             MetricType::StoreBlobRead => 325,
             MetricType::StoreBlobWrite => 326,
@@ -5718,6 +5965,7 @@ impl MetricType {
             85 => Some(MetricType::DeliveryDsnSuccess),
             86 => Some(MetricType::DeliveryDsnTempFail),
             87 => Some(MetricType::DeliveryDsnPermFail),
+            339 => Some(MetricType::DeliveryDsnSuppressed),
             88 => Some(MetricType::DkimPass),
             89 => Some(MetricType::DkimNeutral),
             90 => Some(MetricType::DkimFail),
@@ -5944,6 +6192,7 @@ impl MetricType {
             288 => Some(MetricType::SpamDnsblError),
             289 => Some(MetricType::SpamTrainCompleted),
             290 => Some(MetricType::SpamTrainSampleAdded),
+            340 => Some(MetricType::SpamTrapHit),
             291 => Some(MetricType::SpamClassify),
             292 => Some(MetricType::SpamModelNotReady),
             293 => Some(MetricType::SpfPass),
@@ -5981,6 +6230,8 @@ impl MetricType {
             321 => Some(MetricType::StoreHttpStoreError),
             322 => Some(MetricType::StoreBlobMissingMarker),
             323 => Some(MetricType::StoreDataWrite),
+            341 => Some(MetricType::StoreReadCacheHit),
+            342 => Some(MetricType::StoreReadCacheMiss),
             324 => Some(MetricType::StoreDataIterate),
             325 => Some(MetricType::StoreBlobRead),
             326 => Some(MetricType::StoreBlobWrite),
@@ -6060,6 +6311,7 @@ impl MetricType {
             MetricType::DeliveryDsnSuccess => 88,
             MetricType::DeliveryDsnTempFail => 89,
             MetricType::DeliveryDsnPermFail => 87,
+            MetricType::DeliveryDsnSuppressed => 613,
             MetricType::DkimPass => 121,
             MetricType::DkimNeutral => 119,
             MetricType::DkimFail => 114,
@@ -6267,6 +6519,7 @@ impl MetricType {
             MetricType::SpamDnsblError => 563,
             MetricType::SpamTrainCompleted => 495,
             MetricType::SpamTrainSampleAdded => 143,
+            MetricType::SpamTrapHit => 630,
             MetricType::SpamClassify => 490,
             MetricType::SpamModelNotReady => 496,
             MetricType::SpfPass => 501,
@@ -6300,6 +6553,8 @@ impl MetricType {
             MetricType::StoreHttpStoreError => 493,
             MetricType::StoreBlobMissingMarker => 507,
             MetricType::StoreDataWrite => 513,
+            MetricType::StoreReadCacheHit => 635,
+            MetricType::StoreReadCacheMiss => 636,
             MetricType::StoreDataIterate => 512,
             MetricType::StoreBlobRead => 508,
             MetricType::StoreBlobWrite => 509,
@@ -6383,6 +6638,7 @@ impl MetricType {
             MetricType::DeliveryDsnSuccess => "DSN success notification",
             MetricType::DeliveryDsnTempFail => "DSN temporary failure notification",
             MetricType::DeliveryDsnPermFail => "DSN permanent failure notification",
+            MetricType::DeliveryDsnSuppressed => "DSN suppressed",
             MetricType::DkimPass => "DKIM verification passed",
             MetricType::DkimNeutral => "DKIM verification neutral",
             MetricType::DkimFail => "DKIM verification failed",
@@ -6619,6 +6875,7 @@ impl MetricType {
             MetricType::SpamDnsblError => "Error querying DNSBL",
             MetricType::SpamTrainCompleted => "Spam classifier training completed",
             MetricType::SpamTrainSampleAdded => "New training sample added",
+            MetricType::SpamTrapHit => "Spam trap hit",
             MetricType::SpamClassify => "Classifying message for spam",
             MetricType::SpamModelNotReady => "Spam classifier model not ready",
             MetricType::SpfPass => "SPF check passed",
@@ -6656,6 +6913,8 @@ impl MetricType {
             MetricType::StoreHttpStoreError => "Error updating HTTP store",
             MetricType::StoreBlobMissingMarker => "Blob missing marker",
             MetricType::StoreDataWrite => "Write batch operation",
+            MetricType::StoreReadCacheHit => "Read cache hit",
+            MetricType::StoreReadCacheMiss => "Read cache miss",
             MetricType::StoreDataIterate => "Data store iteration operation",
             MetricType::StoreBlobRead => "Blob read operation",
             MetricType::StoreBlobWrite => "Blob write operation",
@@ -6744,6 +7003,7 @@ impl MetricType {
             | MetricType::DeliveryDsnSuccess
             | MetricType::DeliveryDsnTempFail
             | MetricType::DeliveryDsnPermFail
+            | MetricType::DeliveryDsnSuppressed
             | MetricType::DkimPass
             | MetricType::DkimNeutral
             | MetricType::DkimFail
@@ -6951,6 +7211,7 @@ impl MetricType {
             | MetricType::SpamDnsblError
             | MetricType::SpamTrainCompleted
             | MetricType::SpamTrainSampleAdded
+            | MetricType::SpamTrapHit
             | MetricType::SpamClassify
             | MetricType::SpamModelNotReady
             | MetricType::SpfPass
@@ -6984,6 +7245,8 @@ impl MetricType {
             | MetricType::StoreHttpStoreError
             | MetricType::StoreBlobMissingMarker
             | MetricType::StoreDataWrite
+            | MetricType::StoreReadCacheHit
+            | MetricType::StoreReadCacheMiss
             | MetricType::StoreDataIterate
             | MetricType::StoreBlobRead
             | MetricType::StoreBlobWrite
@@ -7081,6 +7344,7 @@ impl MetricType {
             MetricType::DeliveryDsnSuccess,
             MetricType::DeliveryDsnTempFail,
             MetricType::DeliveryDsnPermFail,
+            MetricType::DeliveryDsnSuppressed,
             MetricType::DkimPass,
             MetricType::DkimNeutral,
             MetricType::DkimFail,
@@ -7307,6 +7571,7 @@ impl MetricType {
             MetricType::SpamDnsblError,
             MetricType::SpamTrainCompleted,
             MetricType::SpamTrainSampleAdded,
+            MetricType::SpamTrapHit,
             MetricType::SpamClassify,
             MetricType::SpamModelNotReady,
             MetricType::SpfPass,
@@ -7344,6 +7609,8 @@ impl MetricType {
             MetricType::StoreHttpStoreError,
             MetricType::StoreBlobMissingMarker,
             MetricType::StoreDataWrite,
+            MetricType::StoreReadCacheHit,
+            MetricType::StoreReadCacheMiss,
             MetricType::StoreDataIterate,
             MetricType::StoreBlobRead,
             MetricType::StoreBlobWrite,
@@ -7435,6 +7702,8 @@ impl Key {
             b"value" => Key::Value,
             b"version" => Key::Version,
             b"queueName" => Key::QueueName,
+            b"envelopeId" => Key::EnvelopeId,
+            b"tags" => Key::Tags,
         }
         .copied()
     }
@@ -7507,6 +7776,8 @@ impl Key {
             Key::Value => "value",
             Key::Version => "version",
             Key::QueueName => "queueName",
+            Key::EnvelopeId => "envelopeId",
+            Key::Tags => "tags",
         }
     }
 
@@ -7582,11 +7853,13 @@ impl Key {
             63 => Some(Key::Value),
             64 => Some(Key::Version),
             65 => Some(Key::QueueName),
+            66 => Some(Key::EnvelopeId),
+            67 => Some(Key::Tags),
             _ => None,
         }
     }
 
-    pub const COUNT: usize = 66;
+    pub const COUNT: usize = 68;
 }
 
 impl serde::Serialize for Key {
@@ -21,6 +21,14 @@ pub enum ContactField {
     CreatedToUpdated,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum CalendarField {
+    Subscription,
+    BookingPolicy,
+    Archive,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
 pub enum CalendarEventField {
@@ -40,14 +48,17 @@ pub enum CalendarNotificationField {
 pub enum EmailField {
     Archive,
     Metadata,
+    Summary,
     Threading,
     DeletedAt,
+    ChangeLog,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
 pub enum MailboxField {
     UidCounter = 84,
+    LegalHold = 85,
     Archive = ARCHIVE_FIELD,
 }
 
@@ -78,10 +89,16 @@ pub enum IdentityField {
 pub enum PrincipalField {
     Archive = ARCHIVE_FIELD,
     ParticipantIdentities = 45,
+    MigrationState = 46,
     DefaultCalendarId = 47,
     DefaultAddressBookId = 48,
     ActiveScriptId = 49,
     PushSubscriptions = 44,
+    NotificationRules = 43,
+    Forwarding = 42,
+    AutocryptPeers = 41,
+    LoginHistory = 40,
+    DirectoryState = 39,
 }
 
 impl From<ContactField> for u8 {
@@ -95,6 +112,16 @@ impl From<ContactField> for u8 {
     }
 }
 
+impl From<CalendarField> for u8 {
+    fn from(value: CalendarField) -> Self {
+        match value {
+            CalendarField::Subscription => 0,
+            CalendarField::BookingPolicy => 1,
+            CalendarField::Archive => ARCHIVE_FIELD,
+        }
+    }
+}
+
 impl From<CalendarEventField> for u8 {
     fn from(value: CalendarEventField) -> Self {
         match value {
@@ -117,8 +144,10 @@ impl From<EmailField> for u8 {
     fn from(value: EmailField) -> Self {
         match value {
             EmailField::Metadata => 71,
+            EmailField::Summary => 72,
             EmailField::Threading => 90,
             EmailField::DeletedAt => 91,
+            EmailField::ChangeLog => 92,
             EmailField::Archive => ARCHIVE_FIELD,
         }
     }
@@ -128,6 +157,7 @@ impl From<MailboxField> for u8 {
     fn from(value: MailboxField) -> Self {
         match value {
             MailboxField::UidCounter => 84,
+            MailboxField::LegalHold => 85,
             MailboxField::Archive => ARCHIVE_FIELD,
         }
     }
@@ -156,10 +186,16 @@ impl From<PrincipalField> for u8 {
     fn from(value: PrincipalField) -> Self {
         match value {
             PrincipalField::ParticipantIdentities => 45,
+            PrincipalField::MigrationState => 46,
             PrincipalField::DefaultCalendarId => 47,
             PrincipalField::DefaultAddressBookId => 48,
             PrincipalField::ActiveScriptId => 49,
             PrincipalField::PushSubscriptions => 44,
+            PrincipalField::NotificationRules => 43,
+            PrincipalField::Forwarding => 42,
+            PrincipalField::AutocryptPeers => 41,
+            PrincipalField::LoginHistory => 40,
+            PrincipalField::DirectoryState => 39,
             PrincipalField::Archive => ARCHIVE_FIELD,
         }
     }
@@ -186,6 +222,12 @@ impl From<ContactField> for Field {
     }
 }
 
+impl From<CalendarField> for Field {
+    fn from(value: CalendarField) -> Self {
+        Field(u8::from(value))
+    }
+}
+
 impl From<CalendarEventField> for Field {
     fn from(value: CalendarEventField) -> Self {
         Field(u8::from(value))
@@ -248,6 +290,7 @@ impl Field {
 
 impl FieldType for Field {}
 impl FieldType for ContactField {}
+impl FieldType for CalendarField {}
 impl FieldType for CalendarEventField {}
 impl FieldType for CalendarNotificationField {}
 impl FieldType for EmailField {}
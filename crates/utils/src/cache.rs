@@ -253,6 +253,12 @@ impl<T: CacheItemWeight> CacheItemWeight for Arc<[T]> {
     }
 }
 
+impl CacheItemWeight for u8 {
+    fn weight(&self) -> u64 {
+        std::mem::size_of::<u8>() as u64
+    }
+}
+
 impl CacheItemWeight for u32 {
     fn weight(&self) -> u64 {
         std::mem::size_of::<u32>() as u64
@@ -6,10 +6,14 @@
 
 use base64::{Engine, engine::general_purpose};
 use reqwest::{
-    Client,
+    Client, Url,
     header::{AUTHORIZATION, CONTENT_TYPE, HeaderMap, HeaderName, HeaderValue, USER_AGENT},
 };
-use std::{str::FromStr, time::Duration};
+use std::{
+    net::{IpAddr, SocketAddr},
+    str::FromStr,
+    time::Duration,
+};
 
 pub fn build_http_client(
     raw_headers: impl IntoIterator<Item = (String, String)>,
@@ -78,3 +82,100 @@ pub fn build_http_headers(
 
     Ok(headers)
 }
+
+/// Resolves the host of a user supplied URL, failing if any of its addresses
+/// is not publicly routable. The returned addresses should be pinned in the
+/// HTTP client to prevent the name from being re-resolved to a different host.
+pub async fn resolve_public_host(url: &Url) -> Result<(String, Vec<SocketAddr>), String> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| format!("Invalid URL {url}: missing host"))?;
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| format!("Invalid URL {url}: unsupported scheme"))?;
+    let addrs = match host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+    {
+        Ok(ip) => vec![SocketAddr::new(ip, port)],
+        Err(_) => tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|err| format!("Failed to resolve {host}: {err}"))?
+            .collect(),
+    };
+
+    if addrs.is_empty() {
+        Err(format!("Failed to resolve {host}: no addresses found"))
+    } else if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+        Err(format!(
+            "Refusing to connect to {host}: {} is not a public address",
+            addr.ip()
+        ))
+    } else {
+        Ok((host.to_string(), addrs))
+    }
+}
+
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                || a == 0
+                || a >= 240
+                || (a == 100 && (64..128).contains(&b))
+                || (a == 198 && (18..20).contains(&b)))
+        }
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(ip));
+            }
+            let first = ip.segments()[0];
+            !(ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80
+                || (first == 0x2001 && ip.segments()[1] == 0x0db8))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_public_ip;
+    use std::net::IpAddr;
+
+    #[test]
+    fn public_ip_classification() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:10.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse::<IpAddr>().unwrap()), "{ip}");
+        }
+
+        for ip in ["1.1.1.1", "93.184.216.34", "2606:4700:4700::1111"] {
+            assert!(is_public_ip(ip.parse::<IpAddr>().unwrap()), "{ip}");
+        }
+    }
+}
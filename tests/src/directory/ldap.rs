@@ -29,6 +29,8 @@ pub async fn test() {
             secret: Some("$app$8958830913002348890$".into()),
             groups: vec!["sales@example.org".into()],
             description: Some("John Doe".into()),
+            proxy_backend: None,
+            login_referral: None,
         }
     );
     assert_eq!(
@@ -45,6 +47,8 @@ pub async fn test() {
             secret: Some("$app$4096614298472586996$".into()),
             groups: vec!["sales@example.org".into(), "corporate@example.org".into()],
             description: Some("Jane Smith".into()),
+            proxy_backend: None,
+            login_referral: None,
         }
     );
     assert!(
@@ -76,6 +80,8 @@ pub async fn test() {
             secret: Some("this is John's LDAP password".into()),
             groups: vec!["sales@example.org".into()],
             description: Some("John Doe".into()),
+            proxy_backend: None,
+            login_referral: None,
         }
     );
     assert!(
@@ -96,7 +102,9 @@ pub async fn test() {
             email_aliases: vec!["john@example.org".into()],
             secret: Some("this is John's LDAP password".into()),
             groups: vec!["sales@example.org".into()],
-            description: Some("John Doe".into())
+            description: Some("John Doe".into()),
+            proxy_backend: None,
+            login_referral: None,
         })
     );
     assert_eq!(
@@ -106,7 +114,9 @@ pub async fn test() {
             email_aliases: vec![],
             secret: Some("this is Jane's LDAP password".into()),
             groups: vec!["sales@example.org".into(), "corporate@example.org".into()],
-            description: Some("Jane Smith".into())
+            description: Some("Jane Smith".into()),
+            proxy_backend: None,
+            login_referral: None,
         })
     );
     assert_eq!(
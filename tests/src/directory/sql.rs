@@ -98,6 +98,8 @@ pub async fn test() {
             secret: Some("john secret".to_string()),
             groups: vec!["sales@example.org".to_string()],
             description: Some("John Doe".to_string()),
+            proxy_backend: None,
+            login_referral: None,
         }
     );
     assert!(
@@ -119,6 +121,8 @@ pub async fn test() {
             secret: Some("john secret".to_string()),
             groups: vec!["sales@example.org".to_string()],
             description: Some("John Doe".to_string()),
+            proxy_backend: None,
+            login_referral: None,
         })
     );
     assert_eq!(
@@ -129,6 +133,8 @@ pub async fn test() {
             secret: Some("jane secret".to_string()),
             groups: vec!["sales@example.org".to_string()],
             description: Some("Jane Doe".to_string()),
+            proxy_backend: None,
+            login_referral: None,
         })
     );
     assert_eq!(
@@ -30,6 +30,8 @@ pub async fn test() {
                 secret: "supersecret".to_string().into(),
                 groups: vec![],
                 description: "John Doe".to_string().into(),
+                proxy_backend: None,
+                login_referral: None,
             })
             .await
             .is_err()
@@ -48,6 +50,8 @@ pub async fn test() {
             "sales@example.org".to_string(),
         ],
         description: "John Doe".to_string().into(),
+        proxy_backend: "imap.legacy.example.org".to_string().into(),
+        login_referral: "imap.eu.example.org".to_string().into(),
     };
     let result = test
         .server
@@ -71,6 +75,14 @@ pub async fn test() {
     );
     assert_eq!(account_out.name, "john");
     assert_eq!(account_out.description.as_deref(), Some("John Doe"));
+    assert_eq!(
+        account_out.proxy_backend.as_deref(),
+        Some("imap.legacy.example.org")
+    );
+    assert_eq!(
+        account_out.login_referral.as_deref(),
+        Some("imap.eu.example.org")
+    );
     assert_eq!(
         account_out
             .credentials
@@ -164,6 +176,8 @@ pub async fn test() {
     account_in.groups.pop();
     account_in.groups.push("support@example.org".to_string());
     account_in.secret = "evenmoresecret".to_string().into();
+    account_in.proxy_backend = None;
+    account_in.login_referral = "imap.us.example.org".to_string().into();
     assert_eq!(
         test.server
             .synchronize_account(account_in.clone())
@@ -191,6 +205,11 @@ pub async fn test() {
         Some("evenmoresecret")
     );
     assert_eq!(account_out.description.as_deref(), Some("Johnathan Doe"));
+    assert_eq!(account_out.proxy_backend, None);
+    assert_eq!(
+        account_out.login_referral.as_deref(),
+        Some("imap.us.example.org")
+    );
     assert_eq!(account_out.aliases.len(), 3);
     let aliases = account_out.aliases.iter().collect::<Vec<_>>();
     assert_eq!(
@@ -0,0 +1,198 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    jmap::mail::submission::{
+        MockMessage, assert_message_delivery, expect_nothing, spawn_mock_smtp_server,
+    },
+    utils::{dns::DnsCache, http::HttpRequest, server::TestServer, smtp::SmtpConnection},
+};
+use common::auth::AccessToken;
+use email::forwarding::{AccountForwarding, AccountForwardingStore};
+use http::api::forwarding::ForwardingManagement;
+use jmap_client::email::query::Filter;
+use registry::schema::enums::Permission;
+use serde_json::json;
+use std::time::Instant;
+
+pub async fn test(test: &TestServer) {
+    println!("Running Forwarding tests...");
+
+    // Create test account
+    let server = test.server.clone();
+    let account = test.account("jdoe@example.com");
+    let account_id = account.id().document_id();
+    let client = account.jmap_client().await;
+    let api = HttpRequest::with_credentials(8899, account.name(), account.secret());
+    let api_path = format!("/api/account/{}/forwarding", account.id_string());
+
+    // Start mock SMTP server
+    let (mut smtp_rx, smtp_settings) = spawn_mock_smtp_server();
+    server.ipv4_add(
+        "localhost",
+        vec!["127.0.0.1".parse().unwrap()],
+        Instant::now() + std::time::Duration::from_secs(10),
+    );
+
+    // Managing forwarding requires the dedicated permission
+    for (permission, is_allowed) in [
+        (Permission::JmapVacationResponseGet, false),
+        (Permission::EmailForward, true),
+    ] {
+        assert_eq!(
+            server
+                .handle_forwarding_get(
+                    account.id_string(),
+                    &AccessToken::from_permissions(account_id, [permission]),
+                )
+                .await
+                .is_ok(),
+            is_allowed
+        );
+    }
+
+    // Setting a destination sends a confirmation link to it
+    let forwarding = api
+        .post::<AccountForwarding>(
+            &api_path,
+            &json!({ "destination": "forward@remote.org", "keepCopy": true }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        forwarding,
+        AccountForwarding {
+            destination: "forward@remote.org".into(),
+            keep_copy: true,
+            verified: false,
+        }
+    );
+    assert_message_delivery(
+        &mut smtp_rx,
+        MockMessage::new(
+            "<jdoe@example.com>",
+            ["<forward@remote.org>"],
+            "@Confirm email forwarding",
+        ),
+    )
+    .await;
+
+    // Messages are not forwarded until the destination is confirmed
+    let mut lmtp = SmtpConnection::connect().await;
+    lmtp.ingest(
+        "bill@remote.org",
+        &["jdoe@example.com"],
+        forwarded_message("TPS Report").as_str(),
+    )
+    .await;
+    expect_nothing(&mut smtp_rx).await;
+
+    let url = server
+        .forwarding_confirm_url(account_id, "forward@remote.org")
+        .await
+        .unwrap();
+    let token = url.rsplit('/').next().unwrap();
+    assert!(server.forwarding_confirm(token).await.unwrap());
+    assert!(
+        api.get::<AccountForwarding>(&api_path)
+            .await
+            .unwrap()
+            .verified
+    );
+
+    // Confirmed destinations receive a copy
+    lmtp.ingest(
+        "bill@remote.org",
+        &["jdoe@example.com"],
+        forwarded_message("TPS Report -- friendly reminder").as_str(),
+    )
+    .await;
+    assert_message_delivery(
+        &mut smtp_rx,
+        MockMessage::new(
+            "<jdoe@example.com>",
+            ["<forward@remote.org>"],
+            "@Delivered-To: jdoe@example.com",
+        ),
+    )
+    .await;
+    assert_eq!(local_messages(&client).await, 2);
+
+    // Messages that already went through this mailbox are not forwarded again
+    lmtp.ingest(
+        "forward@remote.org",
+        &["jdoe@example.com"],
+        format!(
+            "Delivered-To: jdoe@example.com\r\n{}",
+            forwarded_message("TPS Report -- looped")
+        )
+        .as_str(),
+    )
+    .await;
+    expect_nothing(&mut smtp_rx).await;
+    assert_eq!(local_messages(&client).await, 3);
+
+    // Toggling the local copy keeps the destination verified
+    let forwarding = api
+        .post::<AccountForwarding>(
+            &api_path,
+            &json!({ "destination": "forward@remote.org", "keepCopy": false }),
+        )
+        .await
+        .unwrap();
+    assert!(forwarding.verified && !forwarding.keep_copy);
+    smtp_settings.lock().do_stop = true;
+    lmtp.ingest(
+        "bill@remote.org",
+        &["jdoe@example.com"],
+        forwarded_message("TPS Report -- final notice").as_str(),
+    )
+    .await;
+    lmtp.quit().await;
+    assert_message_delivery(
+        &mut smtp_rx,
+        MockMessage::new(
+            "<jdoe@example.com>",
+            ["<forward@remote.org>"],
+            "@final notice",
+        ),
+    )
+    .await;
+    assert_eq!(local_messages(&client).await, 3);
+
+    // Remove test data
+    assert_eq!(
+        api.delete::<Option<AccountForwarding>>(&api_path)
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(server.account_forwarding(account_id).await.unwrap(), None);
+    test.destroy_all_mailboxes(account).await;
+    test.assert_is_empty().await;
+}
+
+async fn local_messages(client: &jmap_client::client::Client) -> usize {
+    client
+        .email_query(None::<Filter>, None::<Vec<_>>)
+        .await
+        .unwrap()
+        .ids()
+        .len()
+}
+
+fn forwarded_message(subject: &str) -> String {
+    format!(
+        concat!(
+            "From: bill@remote.org\r\n",
+            "To: jdoe@example.com\r\n",
+            "Subject: {}\r\n",
+            "\r\n",
+            "I'm going to need those TPS reports ASAP."
+        ),
+        subject
+    )
+}
@@ -0,0 +1,136 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    jmap::mail::acl::assert_forbidden,
+    utils::{
+        imap::{AssertResult, Type},
+        server::TestServer,
+    },
+};
+use email::message::{delete::EmailDeletion, legal_hold::LegalHoldManagement};
+use imap_proto::ResponseType;
+use jmap_client::mailbox::Role;
+use std::str::FromStr;
+use store::{roaring::RoaringBitmap, write::BatchBuilder};
+use types::id::Id;
+
+pub async fn test(test: &TestServer) {
+    println!("Running Legal Hold tests...");
+    let server = &test.server;
+    let account = test.account("jdoe@example.com");
+    let account_id = account.id().document_id();
+    let client = account.jmap_client().await;
+
+    // Create a held mailbox and a regular one
+    let held_id = client
+        .mailbox_create("Held", None::<String>, Role::None)
+        .await
+        .unwrap()
+        .take_id();
+    let other_id = client
+        .mailbox_create("Not Held", None::<String>, Role::None)
+        .await
+        .unwrap()
+        .take_id();
+    let email_id = client
+        .email_import(
+            concat!(
+                "From: bill@example.com\r\n",
+                "To: jdoe@example.com\r\n",
+                "Subject: Evidence\r\n",
+                "\r\n",
+                "Please keep this message."
+            )
+            .as_bytes()
+            .to_vec(),
+            [&held_id],
+            None::<Vec<&str>>,
+            None,
+        )
+        .await
+        .unwrap()
+        .take_id();
+    let held_document_id = Id::from_str(&held_id).unwrap().document_id();
+    let email_document_id = Id::from_str(&email_id).unwrap().document_id();
+    assert!(
+        server
+            .set_mailbox_legal_hold(account_id, held_document_id, true)
+            .await
+            .unwrap()
+    );
+
+    // Messages cannot be removed from a held mailbox over JMAP
+    assert_forbidden(client.email_set_mailboxes(&email_id, [&other_id]).await);
+    assert_forbidden(client.email_set_mailbox(&email_id, &held_id, false).await);
+    assert_forbidden(client.email_destroy(&email_id).await);
+
+    // Filing them in other mailboxes is allowed
+    client
+        .email_set_mailbox(&email_id, &other_id, true)
+        .await
+        .unwrap();
+    client
+        .email_set_mailbox(&email_id, &other_id, false)
+        .await
+        .unwrap();
+
+    // Nor can they be moved out of it or expunged over IMAP
+    let mut imap = account.imap_client().await;
+    imap.send("SELECT Held").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+    imap.send("MOVE 1 \"Not Held\"").await;
+    imap.assert_read(Type::Tagged, ResponseType::No)
+        .await
+        .assert_response_code("NOPERM");
+    imap.send("STORE 1 +FLAGS (\\Deleted)").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+    imap.send("EXPUNGE").await;
+    imap.assert_read(Type::Tagged, ResponseType::No)
+        .await
+        .assert_response_code("NOPERM");
+
+    // Deletions report the held messages
+    let not_deleted = server
+        .emails_delete(
+            account_id,
+            None,
+            &mut BatchBuilder::new(),
+            RoaringBitmap::from_iter([email_document_id, u32::MAX - 1]),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        not_deleted.legal_hold,
+        RoaringBitmap::from_iter([email_document_id])
+    );
+    assert_eq!(
+        not_deleted.not_found,
+        RoaringBitmap::from_iter([u32::MAX - 1])
+    );
+    assert!(
+        client
+            .email_get(&email_id, None::<Vec<_>>)
+            .await
+            .unwrap()
+            .is_some()
+    );
+
+    // Releasing the hold allows the message to be moved and destroyed
+    assert!(
+        server
+            .set_mailbox_legal_hold(account_id, held_document_id, false)
+            .await
+            .unwrap()
+    );
+    imap.send("MOVE 1 \"Not Held\"").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+    client.email_destroy(&email_id).await.unwrap();
+
+    // Empty store
+    account.destroy_all_mailboxes_for_account(account_id).await;
+    test.assert_is_empty().await;
+}
@@ -7,10 +7,13 @@
 pub mod acl;
 pub mod changes;
 pub mod copy;
+pub mod forwarding;
 pub mod get;
+pub mod legal_hold;
 pub mod mailbox;
 pub mod parse;
 pub mod query;
+pub mod query_cache;
 pub mod query_changes;
 pub mod search_snippet;
 pub mod set;
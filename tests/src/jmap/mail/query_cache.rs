@@ -0,0 +1,162 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::server::TestServer;
+use common::{EmailQueryCache, EmailQueryKey};
+use jmap_client::{
+    client::Client, core::query::QueryResponse, email::query::Filter, mailbox::Role,
+};
+use jmap_proto::{method::query, object::email::EmailFilter};
+use std::{str::FromStr, sync::Arc};
+use store::ahash::AHashSet;
+use types::id::Id;
+
+pub async fn test(test: &TestServer) {
+    println!("Running Email query cache tests...");
+    let account = test.account("jdoe@example.com");
+    let account_id = account.id().document_id();
+    let client = account.jmap_client().await;
+
+    let mailbox_id = client
+        .mailbox_create("Query Cache", None::<String>, Role::None)
+        .await
+        .unwrap()
+        .take_id();
+    let other_mailbox_id = client
+        .mailbox_create("Query Cache Other", None::<String>, Role::None)
+        .await
+        .unwrap()
+        .take_id();
+    let mut message_ids = Vec::new();
+    for (subject, mailbox_id) in [
+        ("cached 1", &mailbox_id),
+        ("cached 2", &mailbox_id),
+        ("cached 3", &mailbox_id),
+        ("not cached", &other_mailbox_id),
+    ] {
+        message_ids.push(import_message(&client, subject, mailbox_id).await);
+    }
+    let cache_key = EmailQueryKey {
+        account_id,
+        access_id: account_id,
+        shared_mailboxes: Box::default(),
+        filter: vec![query::Filter::Property(EmailFilter::InMailbox(
+            Id::from_str(&mailbox_id).unwrap(),
+        ))],
+        sort: None,
+        collapse_threads: false,
+    };
+
+    // The first query populates the cache
+    let response = query_mailbox(&client, &mailbox_id).await;
+    assert_eq!(id_set(response.ids()), id_set(&message_ids[..3]));
+    let cached = cached_results(test, &cache_key);
+    assert_eq!(cached.total, 3);
+
+    // Repeating the query against an unchanged account is served from the cache
+    let repeated = query_mailbox(&client, &mailbox_id).await;
+    assert_eq!(repeated.ids(), response.ids());
+    assert_eq!(repeated.query_state(), response.query_state());
+    assert!(Arc::ptr_eq(&cached, &cached_results(test, &cache_key)));
+
+    // Email changes invalidate the cached results
+    client
+        .email_set_keyword(&message_ids[0], "$seen", true)
+        .await
+        .unwrap();
+    let response = query_mailbox(&client, &mailbox_id).await;
+    assert_ne!(response.query_state(), repeated.query_state());
+    let current = cached_results(test, &cache_key);
+    assert!(!Arc::ptr_eq(&cached, &current));
+    assert!(current.emails_change_id > cached.emails_change_id);
+    assert_eq!(current.mailboxes_change_id, cached.mailboxes_change_id);
+    let cached = current;
+
+    // Mailbox changes invalidate the cached results as well
+    client
+        .mailbox_rename(&other_mailbox_id, "Query Cache Renamed")
+        .await
+        .unwrap();
+    let response = query_mailbox(&client, &mailbox_id).await;
+    assert_eq!(id_set(response.ids()), id_set(&message_ids[..3]));
+    let current = cached_results(test, &cache_key);
+    assert!(!Arc::ptr_eq(&cached, &current));
+    assert_eq!(current.emails_change_id, cached.emails_change_id);
+    assert!(current.mailboxes_change_id > cached.mailboxes_change_id);
+
+    // Query changes are calculated from the results cached at the client's state,
+    // so updates to messages that never matched the query are not reported
+    let query_state = response.query_state().to_string();
+    client
+        .email_set_mailboxes(&message_ids[1], [&other_mailbox_id])
+        .await
+        .unwrap();
+    client
+        .email_set_keyword(&message_ids[3], "$seen", true)
+        .await
+        .unwrap();
+    let added_id = import_message(&client, "cached 4", &mailbox_id).await;
+    let mut request = client.build();
+    request
+        .query_email_changes(query_state)
+        .filter(Filter::in_mailbox(&mailbox_id));
+    let changes = request.send_query_email_changes().await.unwrap();
+    assert_eq!(changes.removed(), [message_ids[1].as_str()]);
+    assert_eq!(changes.added().len(), 1);
+    assert_eq!(changes.added()[0].id(), added_id);
+    assert_eq!(changes.total(), Some(3));
+
+    // The current results were cached while calculating the changes
+    let current = cached_results(test, &cache_key);
+    assert_eq!(current.total, 3);
+    let response = query_mailbox(&client, &mailbox_id).await;
+    assert_eq!(
+        id_set(response.ids()),
+        id_set(&[
+            message_ids[0].clone(),
+            message_ids[2].clone(),
+            added_id.clone()
+        ])
+    );
+    assert_eq!(response.ids()[changes.added()[0].index()], added_id);
+    assert!(Arc::ptr_eq(&current, &cached_results(test, &cache_key)));
+
+    test.destroy_all_mailboxes(account).await;
+    test.assert_is_empty().await;
+}
+
+async fn import_message(client: &Client, subject: &str, mailbox_id: &str) -> String {
+    client
+        .email_import(
+            format!("From: bill@example.com\r\nSubject: {subject}\r\n\r\ntest").into_bytes(),
+            [mailbox_id],
+            None::<Vec<&str>>,
+            None,
+        )
+        .await
+        .unwrap()
+        .take_id()
+}
+
+async fn query_mailbox(client: &Client, mailbox_id: &str) -> QueryResponse {
+    client
+        .email_query(Some(Filter::in_mailbox(mailbox_id)), None::<Vec<_>>)
+        .await
+        .unwrap()
+}
+
+fn id_set(ids: &[String]) -> AHashSet<&str> {
+    ids.iter().map(String::as_str).collect()
+}
+
+fn cached_results(test: &TestServer, key: &EmailQueryKey) -> Arc<EmailQueryCache> {
+    test.server
+        .inner
+        .cache
+        .email_queries
+        .get(key)
+        .expect("query results were not cached")
+}
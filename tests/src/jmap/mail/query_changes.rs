@@ -322,6 +322,48 @@ pub async fn test(test: &TestServer) {
         states.push(new_state);
     }
 
+    // Changes are calculated from the cached results at the client's query state,
+    // so messages that never matched the query are not reported
+    let moved_id = client
+        .email_import(
+            b"From: test_3\nSubject: cached_1\n\ntest".to_vec(),
+            [&mailbox1_id],
+            None::<Vec<&str>>,
+            None,
+        )
+        .await
+        .unwrap()
+        .take_id();
+    let query = client
+        .email_query(
+            Some(email::query::Filter::in_mailbox(&mailbox1_id)),
+            Some(vec![email::query::Comparator::received_at()]),
+        )
+        .await
+        .unwrap();
+    assert!(query.ids().contains(&moved_id));
+    client
+        .email_import(
+            b"From: test_3\nSubject: cached_2\n\ntest".to_vec(),
+            [&mailbox2_id],
+            None::<Vec<&str>>,
+            None,
+        )
+        .await
+        .unwrap();
+    client
+        .email_set_mailboxes(&moved_id, [&mailbox2_id])
+        .await
+        .unwrap();
+    let mut request = client.build();
+    request
+        .query_email_changes(query.query_state())
+        .filter(email::query::Filter::in_mailbox(&mailbox1_id))
+        .sort([email::query::Comparator::received_at()]);
+    let changes = request.send_query_email_changes().await.unwrap();
+    assert_eq!(changes.removed(), [moved_id]);
+    assert!(changes.added().is_empty());
+
     test.destroy_all_mailboxes(account).await;
     test.assert_is_empty().await;
 }
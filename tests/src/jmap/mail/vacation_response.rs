@@ -98,6 +98,24 @@ pub async fn test(test: &TestServer) {
 
     expect_nothing(&mut smtp_rx).await;
 
+    // Mailing list and bulk traffic should not
+    // trigger a vacation response
+    lmtp.ingest(
+        "announce@lists.remote.org",
+        &["jdoe@example.com"],
+        concat!(
+            "From: announce@lists.remote.org\r\n",
+            "To: jdoe@example.com\r\n",
+            "List-Id: Initech Announcements <announce.lists.remote.org>\r\n",
+            "Subject: New cover sheets for TPS reports\r\n",
+            "\r\n",
+            "Please make sure to use the new cover sheets from now on.",
+        ),
+    )
+    .await;
+
+    expect_nothing(&mut smtp_rx).await;
+
     // Vacation responses should honor the configured date ranges
     client
         .vacation_response_set_dates(
@@ -176,13 +176,16 @@ pub async fn jmap_tests() {
     mail::search_snippet::test(&test).await;
     mail::changes::test(&test).await;
     mail::query_changes::test(&test).await;
+    mail::query_cache::test(&test).await;
     mail::copy::test(&test).await;
+    mail::legal_hold::test(&test).await;
     mail::thread_get::test(&test).await;
     mail::thread_merge::test(&test).await;
     mail::mailbox::test(&test).await;
     mail::acl::test(&test).await;
     mail::sieve_script::test(&test).await;
     mail::vacation_response::test(&test).await;
+    mail::forwarding::test(&test).await;
     mail::submission::test(&test).await;
 
     core::event_source::test(&test).await;
@@ -47,11 +47,11 @@ use spam_filter::{
         ehlo::SpamFilterAnalyzeEhlo, from::SpamFilterAnalyzeFrom,
         headers::SpamFilterAnalyzeHeaders, html::SpamFilterAnalyzeHtml, init::SpamFilterInit,
         ip::SpamFilterAnalyzeIp, llm::SpamFilterAnalyzeLlm, messageid::SpamFilterAnalyzeMid,
-        mime::SpamFilterAnalyzeMime, pyzor::SpamFilterAnalyzePyzor,
-        received::SpamFilterAnalyzeReceived, recipient::SpamFilterAnalyzeRecipient,
-        replyto::SpamFilterAnalyzeReplyTo, rules::SpamFilterAnalyzeRules,
-        score::SpamFilterAnalyzeScore, subject::SpamFilterAnalyzeSubject,
-        url::SpamFilterAnalyzeUrl,
+        mime::SpamFilterAnalyzeMime, phishing::SpamFilterAnalyzePhishing,
+        pyzor::SpamFilterAnalyzePyzor, received::SpamFilterAnalyzeReceived,
+        recipient::SpamFilterAnalyzeRecipient, replyto::SpamFilterAnalyzeReplyTo,
+        rules::SpamFilterAnalyzeRules, score::SpamFilterAnalyzeScore,
+        subject::SpamFilterAnalyzeSubject, url::SpamFilterAnalyzeUrl,
     },
     modules::{
         classifier::{SpamClassifier, Token},
@@ -140,6 +140,15 @@ async fn antispam() {
             namespace: "url-redirectors".into(),
         })
         .await;
+    admin
+        .create_user_account(
+            "jane.smith@phishing-target.org",
+            "secret",
+            "Jane Smith",
+            &[],
+            vec![],
+        )
+        .await;
     admin.mta_allow_relaying().await;
     admin.mta_no_auth().await;
     admin.mta_allow_non_fqdn().await;
@@ -281,6 +290,7 @@ async fn antispam() {
         "classifier",
         "pyzor",
         "llm",
+        "phishing",
     ] {
         if filter_test
             .as_ref()
@@ -657,6 +667,9 @@ async fn antispam() {
                 "llm" => {
                     server.spam_filter_analyze_llm(&mut spam_ctx).await;
                 }
+                "phishing" => {
+                    server.spam_filter_analyze_phishing(&mut spam_ctx).await;
+                }
                 _ => panic!("Invalid test {test_name:?}"),
             }
 
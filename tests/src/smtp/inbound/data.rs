@@ -11,16 +11,22 @@ use crate::{
     },
     utils::server::TestServerBuilder,
 };
+use common::{
+    KV_DUPLICATE,
+    auth::{AccountCache, AccountInfo},
+};
 use registry::{
     schema::{
-        enums::MtaQueueQuotaKey,
-        prelude::ObjectType,
+        enums::{DuplicateAction, MtaQueueQuotaKey, SuppressionReason},
+        prelude::{ObjectType, Property},
         structs::{
-            Expression, ExpressionMatch, MtaQueueQuota, MtaStageData, SenderAuth, SpamSettings,
+            Expression, ExpressionMatch, MailingList, MemoryLookupKey, MtaQueueQuota, MtaStageData,
+            SenderAuth, SpamSettings,
         },
     },
     types::{list::List, map::Map},
 };
+use std::{net::IpAddr, sync::Arc};
 
 #[tokio::test]
 async fn data() {
@@ -248,6 +254,47 @@ async fn data() {
         .assert_contains("Authentication-Results: ")
         .assert_contains("Received-SPF: ");
 
+    // Message tags are extracted from authenticated submissions and stripped
+    session.data.authenticated_as = Some(AccountInfo {
+        account_id: u32::MAX,
+        addresses: vec!["bill@foobar.org".to_string()],
+        account: Arc::new(AccountCache {
+            name: "bill@foobar.org".into(),
+            ..Default::default()
+        }),
+    });
+    session
+        .send_message(
+            "bill@foobar.org",
+            &["mike@test.com"],
+            concat!(
+                "From: bill@foobar.org\r\n",
+                "To: mike@test.com\r\n",
+                "X-Message-Tags: campaign-42, tenant-a, campaign-42\r\n",
+                "Subject: Tagged message\r\n",
+                "\r\n",
+                "Hello world!\r\n"
+            ),
+            "250",
+        )
+        .await;
+    let message = test.expect_message().await;
+    assert_eq!(
+        message
+            .message
+            .tags
+            .iter()
+            .map(|tag| tag.as_ref())
+            .collect::<Vec<&str>>(),
+        vec!["campaign-42", "tenant-a"]
+    );
+    message
+        .read_lines(&test)
+        .await
+        .assert_contains("Subject: Tagged message")
+        .assert_not_contains("X-Message-Tags");
+    session.data.authenticated_as = None;
+
     // Only one message is allowed in the queue from john@doe.org
     session.data.remote_ip_str = "10.0.0.2".into();
     session.eval_session_params().await;
@@ -302,8 +349,52 @@ async fn data() {
         )
         .await;
 
+    // Duplicate deliveries to the same recipient are discarded
+    test.clear_queue().await;
+    let admin = test.account("admin");
+    admin
+        .registry_update_setting(
+            MtaStageData {
+                duplicate_action: DuplicateAction::Discard,
+                ..Default::default()
+            },
+            &[Property::DuplicateAction],
+        )
+        .await;
+    admin.reload_settings().await;
+    test.reload_core();
+    test.expect_reload_settings().await;
+    let mut session = test.new_mta_session();
+    session.data.remote_ip_str = "10.0.0.2".into();
+    session.eval_session_params().await;
+    session.ehlo("mx.doe.org").await;
+    session
+        .send_message("jane@foobar.org", &["mike@test.com"], "test:no_dkim", "250")
+        .await;
+    test.expect_message().await;
+    session
+        .send_message("jane@foobar.org", &["mike@test.com"], "test:no_dkim", "250")
+        .await;
+    test.assert_no_events();
+    session
+        .send_message(
+            "jane@foobar.org",
+            &["mike@test.com", "bill@foobar.org"],
+            "test:no_dkim",
+            "250",
+        )
+        .await;
+    let message = test.expect_message().await;
+    assert_eq!(message.message.recipients.len(), 1);
+    assert_eq!(message.message.recipients[0].address(), "bill@foobar.org");
+
     // Make sure store is empty
     test.clear_queue().await;
+    test.server
+        .in_memory_store()
+        .key_delete_prefix(&[KV_DUPLICATE])
+        .await
+        .unwrap();
     let admin = test.account("admin");
     admin.registry_destroy_all(ObjectType::MtaQueueQuota).await;
     admin
@@ -311,3 +402,336 @@ async fn data() {
         .await;
     test.assert_is_empty().await;
 }
+
+#[tokio::test]
+async fn data_list_unsubscribe() {
+    let mut test = TestServerBuilder::new("smtp_list_unsubscribe_test")
+        .await
+        .with_http_listener(19053)
+        .await
+        .disable_services()
+        .capture_queue()
+        .build()
+        .await;
+
+    let admin = test.account("admin");
+    admin
+        .create_user_account(
+            "bill@foobar.org",
+            "p4ssw0rd + extra safety",
+            "Bill Foobar",
+            &[],
+            vec![],
+        )
+        .await;
+    let domain_id = admin.find_or_create_domain("foobar.org").await;
+    let list_id = admin
+        .registry_create_object(MailingList {
+            domain_id,
+            name: "news".into(),
+            allow_unsubscribe: true,
+            recipients: Map::new(vec!["alice@remote.org".into(), "bob@remote.org".into()]),
+            ..Default::default()
+        })
+        .await;
+    admin.mta_no_auth().await;
+    admin
+        .registry_create_object(SpamSettings {
+            enable: false,
+            ..Default::default()
+        })
+        .await;
+    admin.reload_settings().await;
+    test.reload_core();
+    test.expect_reload_settings().await;
+
+    let mut session = test.new_mta_session();
+    session.data.remote_ip_str = "10.0.0.1".into();
+    session.eval_session_params().await;
+    session.ehlo("mx.doe.org").await;
+    session
+        .send_message(
+            "john@doe.org",
+            &["news@foobar.org", "bill@foobar.org"],
+            "test:no_dkim",
+            "250",
+        )
+        .await;
+
+    // Each list member receives its own copy with a personal unsubscribe link
+    let mut tokens = Vec::new();
+    let messages = test.read_queued_messages().await;
+    assert_eq!(messages.len(), 3);
+    for message in messages {
+        assert_eq!(message.message.recipients.len(), 1);
+        let rcpt = message.message.recipients[0].address().to_string();
+        let contents = message.read_message(&test).await;
+        if rcpt == "bill@foobar.org" {
+            assert!(!contents.contains("List-Unsubscribe"), "{contents}");
+        } else {
+            assert!(
+                contents.contains("List-Unsubscribe-Post: List-Unsubscribe=One-Click\r\n"),
+                "{contents}"
+            );
+            let token = contents
+                .split_once("/unsubscribe/")
+                .and_then(|(_, token)| token.split_once('>'))
+                .unwrap()
+                .0
+                .to_string();
+            tokens.push((rcpt, token));
+        }
+    }
+    assert_eq!(tokens.len(), 2);
+
+    // Unsubscribing removes the member and suppresses the address
+    let (address, token) = tokens
+        .iter()
+        .find(|(address, _)| address == "alice@remote.org")
+        .unwrap();
+    assert!(test.server.list_unsubscribe(token).await.unwrap());
+    assert!(test.server.list_unsubscribe(token).await.unwrap());
+    assert!(!test.server.list_unsubscribe("invalid").await.unwrap());
+    let list = test
+        .account("admin")
+        .registry_get::<MailingList>(list_id)
+        .await;
+    assert_eq!(
+        list.recipients.iter().collect::<Vec<_>>(),
+        vec!["bob@remote.org"]
+    );
+    assert_eq!(
+        test.server
+            .suppressed_address(address, None)
+            .await
+            .unwrap()
+            .unwrap()
+            .reason,
+        SuppressionReason::Unsubscribe
+    );
+
+    // Messages that already carry List-Unsubscribe headers are not split
+    test.clear_queue().await;
+    session
+        .send_message(
+            "john@doe.org",
+            &["news@foobar.org"],
+            concat!(
+                "From: john@doe.org\r\n",
+                "To: news@foobar.org\r\n",
+                "List-Unsubscribe: <mailto:leave@doe.org>\r\n",
+                "Subject: Newsletter\r\n",
+                "\r\n",
+                "Hello world!\r\n"
+            ),
+            "250",
+        )
+        .await;
+    let messages = test.read_queued_messages().await;
+    assert_eq!(messages.len(), 1);
+    assert!(
+        !messages[0]
+            .read_message(&test)
+            .await
+            .contains("List-Unsubscribe-Post")
+    );
+}
+
+#[tokio::test]
+async fn data_spam_trap() {
+    let mut test = TestServerBuilder::new("smtp_spam_trap_test")
+        .await
+        .with_http_listener(19054)
+        .await
+        .disable_services()
+        .capture_queue()
+        .build()
+        .await;
+
+    let admin = test.account("admin");
+    admin
+        .create_user_account(
+            "bill@foobar.org",
+            "p4ssw0rd + extra safety",
+            "Bill Foobar",
+            &[],
+            vec![],
+        )
+        .await;
+    admin
+        .registry_create_object(MemoryLookupKey {
+            is_glob_pattern: false,
+            key: "trap@foobar.org".into(),
+            namespace: "spam-traps".into(),
+        })
+        .await;
+    admin.mta_no_auth().await;
+    admin
+        .registry_create_object(SpamSettings {
+            enable: false,
+            trap_block_ip: true,
+            ..Default::default()
+        })
+        .await;
+    admin.reload_settings().await;
+    admin.reload_lookup_stores().await;
+    test.reload_core();
+    test.expect_reload_settings().await;
+
+    // Trap recipients are accepted but removed from the message
+    let remote_ip: IpAddr = "10.0.0.3".parse().unwrap();
+    let mut session = test.new_mta_session();
+    session.data.remote_ip = remote_ip;
+    session.data.remote_ip_str = remote_ip.to_string();
+    session.eval_session_params().await;
+    session.ehlo("mx.doe.org").await;
+    session
+        .send_message(
+            "john@doe.org",
+            &["trap@foobar.org", "bill@foobar.org"],
+            "test:no_dkim",
+            "250",
+        )
+        .await;
+    let message = test.expect_message().await;
+    assert_eq!(message.message.recipients.len(), 1);
+    assert_eq!(message.message.recipients[0].address(), "bill@foobar.org");
+
+    // Messages addressed only to traps are never queued
+    session
+        .send_message("john@doe.org", &["trap@foobar.org"], "test:no_dkim", "250")
+        .await;
+    test.assert_no_events();
+
+    // The sender is penalized by IP address and envelope domain, and blocked
+    let other_ip: IpAddr = "10.0.0.4".parse().unwrap();
+    assert!(
+        test.server
+            .is_spam_trap_sender(remote_ip, "other.org")
+            .await
+            .unwrap()
+    );
+    assert!(
+        test.server
+            .is_spam_trap_sender(other_ip, "doe.org")
+            .await
+            .unwrap()
+    );
+    assert!(
+        !test
+            .server
+            .is_spam_trap_sender(other_ip, "other.org")
+            .await
+            .unwrap()
+    );
+    assert!(test.server.is_ip_blocked(remote_ip));
+    assert!(!test.server.is_ip_blocked(other_ip));
+}
+
+#[tokio::test]
+async fn data_auto_bcc() {
+    let mut test = TestServerBuilder::new("smtp_auto_bcc_test")
+        .await
+        .with_http_listener(19056)
+        .await
+        .disable_services()
+        .capture_queue()
+        .build()
+        .await;
+
+    let admin = test.account("admin");
+    admin.mta_no_auth().await;
+    admin
+        .registry_create_object(SpamSettings {
+            enable: false,
+            ..Default::default()
+        })
+        .await;
+    admin
+        .registry_create_object(MtaStageData {
+            auto_bcc: Expression {
+                else_: "'audit@foobar.org'".into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await;
+    admin.reload_settings().await;
+    test.reload_core();
+    test.expect_reload_settings().await;
+
+    let mut session = test.new_mta_session();
+    session.data.remote_ip_str = "10.0.0.1".into();
+    session.eval_session_params().await;
+    session.ehlo("mx.doe.org").await;
+
+    // Unauthenticated messages are not copied
+    session
+        .send_message("john@doe.org", &["bill@foobar.org"], "test:no_dkim", "250")
+        .await;
+    let messages = test.read_queued_messages().await;
+    assert_eq!(messages.len(), 1);
+    assert_eq!(
+        messages[0].message.recipients[0].address(),
+        "bill@foobar.org"
+    );
+    test.clear_queue().await;
+
+    // Authenticated submissions are copied to the BCC recipients, only the copy is tagged
+    session.data.authenticated_as = Some(AccountInfo {
+        account_id: u32::MAX,
+        addresses: vec!["bill@foobar.org".to_string()],
+        account: Arc::new(AccountCache {
+            name: "bill@foobar.org".into(),
+            ..Default::default()
+        }),
+    });
+    session
+        .send_message(
+            "bill@foobar.org",
+            &["mike@test.com"],
+            concat!(
+                "From: bill@foobar.org\r\n",
+                "To: mike@test.com\r\n",
+                "Subject: Audited message\r\n",
+                "\r\n",
+                "Hello world!\r\n"
+            ),
+            "250",
+        )
+        .await;
+    let messages = test.read_queued_messages().await;
+    assert_eq!(messages.len(), 2);
+    for message in messages {
+        assert_eq!(message.message.recipients.len(), 1);
+        let rcpt = message.message.recipients[0].address().to_string();
+        let contents = message.read_message(&test).await;
+        assert!(contents.contains("Subject: Audited message"), "{contents}");
+        match rcpt.as_str() {
+            "mike@test.com" => assert!(!contents.contains("X-Auto-Bcc"), "{contents}"),
+            "audit@foobar.org" => assert!(contents.contains("X-Auto-Bcc: Yes\r\n"), "{contents}"),
+            _ => panic!("unexpected recipient {rcpt}"),
+        }
+    }
+    test.clear_queue().await;
+
+    // Copies are never copied again
+    session
+        .send_message(
+            "bill@foobar.org",
+            &["mike@test.com"],
+            concat!(
+                "From: bill@foobar.org\r\n",
+                "To: mike@test.com\r\n",
+                "X-Auto-Bcc: Yes\r\n",
+                "Subject: Forwarded copy\r\n",
+                "\r\n",
+                "Hello world!\r\n"
+            ),
+            "250",
+        )
+        .await;
+    let messages = test.read_queued_messages().await;
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].message.recipients[0].address(), "mike@test.com");
+}
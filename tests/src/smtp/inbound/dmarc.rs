@@ -87,6 +87,10 @@ async fn dmarc() {
                 else_: "'localdomain.org'".into(),
                 ..Default::default()
             },
+            dkim_resign_domain: Expression {
+                else_: "false".into(),
+                ..Default::default()
+            },
             dkim_verify: Expression {
                 match_: List::from_iter([ExpressionMatch {
                     if_: "sender_domain = 'test.net'".into(),
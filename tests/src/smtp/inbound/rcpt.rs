@@ -10,16 +10,28 @@ use crate::{
 };
 use registry::{
     schema::{
-        enums::MtaInboundThrottleKey,
+        enums::{MtaInboundThrottleKey, RelayVerification, SuppressionReason},
         structs::{
-            Expression, ExpressionMatch, MtaExtensions, MtaInboundThrottle, MtaStageRcpt, Rate,
+            CertificateManagement, DkimManagement, DnsManagement, Domain, Expression,
+            ExpressionMatch, MtaExtensions, MtaInboundThrottle, MtaStageAuth, MtaStageRcpt, Rate,
+            SuppressedAddress,
         },
     },
     types::{list::List, map::Map},
 };
 use smtp::core::State;
 use smtp_proto::{RCPT_NOTIFY_DELAY, RCPT_NOTIFY_FAILURE, RCPT_NOTIFY_SUCCESS};
-use std::time::Duration;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpListener,
+};
 
 #[tokio::test]
 async fn rcpt() {
@@ -79,6 +91,10 @@ async fn rcpt() {
                 }]),
                 else_: "5".into(),
             },
+            max_session_recipients: Expression {
+                else_: "6".into(),
+                ..Default::default()
+            },
             wait_on_fail: Expression {
                 match_: List::from_iter([ExpressionMatch {
                     if_: "remote_ip = '10.0.0.1'".into(),
@@ -157,7 +173,7 @@ async fn rcpt() {
     // Restore rate limit
     tokio::time::sleep(Duration::from_millis(1100)).await;
     session.rcpt_to("Mike@FooBar.org", "250").await;
-    session.rcpt_to("john@foobar.org", "455 4.5.3").await;
+    session.rcpt_to("john@foobar.org", "452 4.5.3").await;
 
     // Check recipients
     assert_eq!(session.data.rcpt_to.len(), 3);
@@ -189,4 +205,160 @@ async fn rcpt() {
     let rcpt = session.data.rcpt_to.last().unwrap();
     assert!((rcpt.flags & (RCPT_NOTIFY_DELAY | RCPT_NOTIFY_SUCCESS | RCPT_NOTIFY_FAILURE)) != 0);
     assert_eq!(rcpt.dsn_info.as_ref().unwrap(), "Jane.Doe@Foobar.org");
+
+    // Recipients are counted across transactions
+    session.rset().await;
+    session.mail_from("john@example.net", "250").await;
+    session.rcpt_to("bill@foobar.org", "250").await;
+    session.rcpt_to("mike@foobar.org", "452 4.5.3").await;
+    assert_eq!(session.data.rcpt_total, 6);
+}
+
+#[tokio::test]
+async fn rcpt_relay_verify() {
+    let mut test = TestServerBuilder::new("smtp_rcpt_verify_test")
+        .await
+        .with_http_listener(19051)
+        .await
+        .disable_services()
+        .build()
+        .await;
+
+    // Relay domain verified against a downstream server
+    let callouts = spawn_mock_callout_server().await;
+    let admin = test.account("admin");
+    admin
+        .registry_create_object(Domain {
+            name: "relay.org".into(),
+            certificate_management: CertificateManagement::Manual,
+            dns_management: DnsManagement::Manual,
+            dkim_management: DkimManagement::Manual,
+            allow_relaying: true,
+            relay_verify: RelayVerification::Smtp,
+            relay_verify_host: Some("127.0.0.1:9926".into()),
+            ..Default::default()
+        })
+        .await;
+    admin.mta_no_auth().await;
+    admin
+        .registry_create_object(MtaStageRcpt {
+            wait_on_fail: Expression {
+                else_: "5ms".into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await;
+    admin.reload_settings().await;
+    test.reload_core();
+
+    let mut session = test.new_mta_session();
+    session.data.remote_ip_str = "10.0.0.1".into();
+    session.eval_session_params().await;
+    session.ehlo("mx1.foobar.org").await;
+    session.mail_from("john@example.net", "250").await;
+    session.rcpt_to("jane@relay.org", "250").await;
+    session.rcpt_to("unknown@relay.org", "550 5.1.1").await;
+    assert_eq!(callouts.load(Ordering::Relaxed), 2);
+
+    // Verification results are cached
+    session.rset().await;
+    session.mail_from("john@example.net", "250").await;
+    session.rcpt_to("jane@relay.org", "250").await;
+    session.rcpt_to("unknown@relay.org", "550 5.1.1").await;
+    assert_eq!(callouts.load(Ordering::Relaxed), 2);
+}
+
+#[tokio::test]
+async fn rcpt_suppression() {
+    let mut test = TestServerBuilder::new("smtp_rcpt_suppression_test")
+        .await
+        .with_http_listener(19052)
+        .await
+        .disable_services()
+        .build()
+        .await;
+
+    let admin = test.account("admin");
+    admin
+        .create_user_account(
+            "john@foobar.org",
+            "12345 + extra safety",
+            "John Doe",
+            &[],
+            vec![],
+        )
+        .await;
+    admin
+        .registry_create_object(SuppressedAddress {
+            email: "bounced@remote.org".into(),
+            reason: SuppressionReason::HardBounce,
+            ..Default::default()
+        })
+        .await;
+    admin
+        .registry_create_object(MtaStageAuth {
+            sasl_mechanisms: Expression {
+                else_: "[plain, login]".into(),
+                ..Default::default()
+            },
+            require: Expression {
+                else_: "true".into(),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await;
+    admin.reload_settings().await;
+    test.reload_core();
+
+    let mut session = test.new_mta_session();
+    session.data.remote_ip_str = "10.0.0.1".into();
+    session.eval_session_params().await;
+    session.stream.tls = true;
+    session.ehlo("mx.foobar.org").await;
+    session
+        .auth_plain("john@foobar.org", "12345 + extra safety", "235 2.7.0")
+        .await;
+    session.mail_from("john@foobar.org", "250").await;
+
+    // Suppressed addresses are rejected regardless of case
+    session.rcpt_to("Bounced@Remote.org", "550 5.7.1").await;
+    session.rcpt_to("other@remote.org", "250").await;
+    assert_eq!(session.data.rcpt_to.len(), 1);
+}
+
+async fn spawn_mock_callout_server() -> Arc<AtomicUsize> {
+    let listener = TcpListener::bind("127.0.0.1:9926")
+        .await
+        .unwrap_or_else(|e| panic!("Failed to bind mock SMTP server to 127.0.0.1:9926: {e}"));
+    let callouts = Arc::new(AtomicUsize::new(0));
+    let callouts_ = callouts.clone();
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            callouts_.fetch_add(1, Ordering::Relaxed);
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            writer.write_all(b"220 mock ESMTP\r\n").await.unwrap();
+            while let Ok(Some(line)) = lines.next_line().await {
+                let line = line.to_lowercase();
+                let response: &[u8] = if line.starts_with("rcpt to:<jane@") {
+                    b"250 2.1.5 OK\r\n"
+                } else if line.starts_with("rcpt to:") {
+                    b"550 5.1.1 No such user\r\n"
+                } else if line.starts_with("quit") {
+                    let _ = writer.write_all(b"221 Bye\r\n").await;
+                    break;
+                } else {
+                    b"250 OK\r\n"
+                };
+                if writer.write_all(response).await.is_err() {
+                    break;
+                }
+            }
+        }
+    });
+
+    callouts
 }
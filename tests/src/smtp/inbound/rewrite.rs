@@ -6,11 +6,14 @@
 
 use crate::{smtp::session::TestSession, utils::server::TestServerBuilder};
 use registry::{
-    schema::structs::{
-        Expression, ExpressionMatch, MtaStageMail, MtaStageRcpt, SieveSystemInterpreter,
-        SieveSystemScript,
+    schema::{
+        enums::AddressRewriteScope,
+        structs::{
+            AddressRewriteRule, Expression, ExpressionMatch, MtaInboundSession, MtaStageMail,
+            MtaStageRcpt, SieveSystemInterpreter, SieveSystemScript,
+        },
     },
-    types::list::List,
+    types::{list::List, map::Map},
 };
 
 const MAIL_SCRIPT: &str = r#"require ["variables", "envelope"];
@@ -112,6 +115,37 @@ pub async fn address_rewrite() {
             ..Default::default()
         })
         .await;
+    admin
+        .registry_create_object(MtaInboundSession {
+            address_rewrites: List::from_iter([
+                AddressRewriteRule {
+                    description: "Disabled rule".into(),
+                    enable: false,
+                    pattern: "^(.+)@legacy\\.example$".into(),
+                    replacement: "disabled@example.org".into(),
+                    ..Default::default()
+                },
+                AddressRewriteRule {
+                    description: "Legacy domain migration".into(),
+                    scope: Map::new(vec![
+                        AddressRewriteScope::MailFrom,
+                        AddressRewriteScope::RcptTo,
+                    ]),
+                    pattern: "^(.+)@legacy\\.example$".into(),
+                    replacement: "$1@example.org".into(),
+                    ..Default::default()
+                },
+                AddressRewriteRule {
+                    description: "Headers only".into(),
+                    scope: Map::new(vec![AddressRewriteScope::Headers]),
+                    pattern: "^(.+)@old\\.example$".into(),
+                    replacement: "$1@example.org".into(),
+                    ..Default::default()
+                },
+            ]),
+            ..Default::default()
+        })
+        .await;
     for (name, contents) in [("mail", MAIL_SCRIPT), ("rcpt", MAIL_RCPT)] {
         admin
             .registry_create_object(SieveSystemScript {
@@ -163,4 +197,22 @@ pub async fn address_rewrite() {
         session.data.rcpt_to.last().unwrap().address,
         "marysmith@foobar.org"
     );
+
+    // Rewrite table
+    session.reset();
+    session.mail_from("John@Legacy.example", "250").await;
+    assert_eq!(
+        session.data.mail_from.as_ref().unwrap().address_lcase,
+        "john@example.org"
+    );
+    session.rcpt_to("jane@legacy.example", "250").await;
+    assert_eq!(
+        session.data.rcpt_to.last().unwrap().address_lcase,
+        "jane@example.org"
+    );
+    session.rcpt_to("jane@old.example", "250").await;
+    assert_eq!(
+        session.data.rcpt_to.last().unwrap().address_lcase,
+        "jane@old.example"
+    );
 }
@@ -204,7 +204,8 @@ async fn sieve_scripts() {
     assert!(!session.init_conn().await);
 
     // Run tests
-    for (name, script) in &test.server.core.sieve.trusted_scripts {
+    let scripts = test.server.inner.data.sieve_scripts.load_full();
+    for (name, script) in &scripts.trusted {
         if name.starts_with("stage_") || name.ends_with("_include") {
             continue;
         }
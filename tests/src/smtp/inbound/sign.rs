@@ -77,6 +77,10 @@ async fn sign_and_seal() {
                 else_: "'example.com'".into(),
                 ..Default::default()
             },
+            dkim_resign_domain: Expression {
+                else_: "false".into(),
+                ..Default::default()
+            },
             dkim_verify: Expression {
                 else_: "relaxed".into(),
                 ..Default::default()
@@ -208,6 +208,7 @@ async fn strategies() {
         priority: 0,
         size: 978,
         quota_keys: Default::default(),
+        tags: Default::default(),
     };
 
     assert_eq!(
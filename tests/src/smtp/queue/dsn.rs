@@ -8,11 +8,11 @@ use crate::utils::server::{TestServer, TestServerBuilder};
 use common::config::smtp::queue::{QueueExpiry, QueueName};
 use registry::schema::{
     enums::CompressionAlgo,
-    structs::{DsnReportSettings, Expression, ReportSettings},
+    structs::{DsnReportSettings, Expression, Rate, ReportSettings},
 };
 use smtp::queue::{
-    Error, ErrorDetails, HostResponse, Message, MessageWrapper, Recipient, Schedule, Status,
-    UnexpectedResponse, dsn::SendDsn,
+    Error, ErrorDetails, FROM_BULK_TRAFFIC, FROM_UNAUTHENTICATED, HostResponse, Message,
+    MessageWrapper, Recipient, Schedule, Status, UnexpectedResponse, dsn::SendDsn,
 };
 use smtp_proto::{RCPT_NOTIFY_DELAY, RCPT_NOTIFY_FAILURE, RCPT_NOTIFY_SUCCESS, Response};
 use std::{
@@ -71,6 +71,7 @@ async fn generate_dsn() {
             priority: 0,
             blob_hash: BlobHash::generate(dsn_original.as_bytes()),
             quota_keys: Default::default(),
+            tags: Default::default(),
             received_from_ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
             received_via_port: 0,
         },
@@ -108,6 +109,15 @@ async fn generate_dsn() {
                 else_: "'Mail Delivery Subsystem'".into(),
                 ..Default::default()
             },
+            suppress: Expression {
+                else_: "source == 'unauthenticated'".into(),
+                ..Default::default()
+            },
+            rate_limit: Some(Rate {
+                count: 5,
+                period: 3600000u64.into(),
+            }),
+            ..Default::default()
         })
         .await;
     let domain_id = local_admin.find_or_create_domain("example.org").await;
@@ -194,6 +204,30 @@ async fn generate_dsn() {
     // Load queue
     let queue = local.read_queued_messages().await;
     assert_eq!(queue.len(), 4);
+
+    // Bounces to unauthenticated senders are suppressed
+    message.message.flags = FROM_UNAUTHENTICATED;
+    message.message.recipients.last_mut().unwrap().notify.due = now();
+    local.server.send_dsn(&mut message).await;
+    local.assert_no_events();
+    assert_eq!(local.read_queued_messages().await.len(), 4);
+
+    // Bounces to mailing lists and bulk senders are suppressed
+    message.message.flags = FROM_BULK_TRAFFIC;
+    message.message.recipients.last_mut().unwrap().notify.due = now();
+    local.server.send_dsn(&mut message).await;
+    local.assert_no_events();
+    assert_eq!(local.read_queued_messages().await.len(), 4);
+
+    // DSNs to the same destination are rate limited
+    message.message.flags = 0;
+    message.message.recipients.last_mut().unwrap().notify.due = now();
+    local.server.send_dsn(&mut message).await;
+    local.expect_message().await;
+    message.message.recipients.last_mut().unwrap().notify.due = now();
+    local.server.send_dsn(&mut message).await;
+    local.assert_no_events();
+    assert_eq!(local.read_queued_messages().await.len(), 5);
 }
 
 impl TestServer {
@@ -13,6 +13,7 @@ pub mod concurrent;
 pub mod dsn;
 pub mod manager;
 pub mod retry;
+pub mod store;
 pub mod virtualq;
 
 pub fn build_rcpt(address: &str, retry: u64, notify: u64, expires: u64) -> Recipient {
@@ -43,6 +44,7 @@ pub fn new_message(queue_id: u64) -> MessageWrapper {
             env_id: None,
             priority: 0,
             quota_keys: Default::default(),
+            tags: Default::default(),
             blob_hash: Default::default(),
             received_from_ip: IpAddr::V4(Ipv4Addr::LOCALHOST),
             received_via_port: 0,
@@ -0,0 +1,125 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{smtp::session::TestSession, utils::server::TestServerBuilder};
+use email::cache::MessageCacheFetch;
+use registry::schema::structs::{BlobStore, FileSystemStore, QueueStore, SqliteStore};
+use std::time::Duration;
+use store::{
+    IterateParams, Store, ValueKey,
+    write::{QueueClass, ValueClass},
+};
+
+#[tokio::test]
+#[serial_test::serial]
+async fn dedicated_queue_store() {
+    let builder = TestServerBuilder::new("smtp_dedicated_queue_store")
+        .await
+        .with_http_listener(19055)
+        .await;
+    let path = builder.tmp_dir().to_string();
+    let mut test = builder
+        .with_object(QueueStore::Sqlite(SqliteStore {
+            path: format!("{path}/queue.db"),
+            ..Default::default()
+        }))
+        .await
+        .with_object(BlobStore::FileSystem(FileSystemStore {
+            path: format!("{path}/blobs"),
+            ..Default::default()
+        }))
+        .await
+        .disable_services()
+        .build()
+        .await;
+    assert!(test.server.core.storage.queue.is_some());
+
+    // Create test account
+    let admin = test.account("admin");
+    let account = admin
+        .create_user_account(
+            "jane@example.org",
+            "abcde + extra safety",
+            "Jane Smith",
+            &[],
+            vec![],
+        )
+        .await;
+    let account_id = account.id().document_id();
+    admin.mta_disable_spam_filter().await;
+    admin.mta_no_auth().await;
+    admin.reload_settings().await;
+    test.reload_core();
+
+    // Queue a message for a local recipient
+    let mut session = test.new_mta_session();
+    session.data.remote_ip_str = "10.0.0.1".into();
+    session.eval_session_params().await;
+    session.ehlo("mx.test.org").await;
+    session
+        .send_message(
+            "john@test.org",
+            &["jane@example.org"],
+            "test:no_dkim",
+            "250",
+        )
+        .await;
+
+    // The message is read from the queue blob store and ingested into the data blob store
+    let mut items = Vec::new();
+    for _ in 0..50 {
+        items = test
+            .server
+            .get_cached_messages(account_id)
+            .await
+            .unwrap()
+            .emails
+            .items
+            .iter()
+            .map(|item| item.document_id)
+            .collect();
+        if !items.is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(items.len(), 1);
+    let contents = test.fetch_email(account_id, items[0]).await;
+    assert!(
+        String::from_utf8_lossy(&contents).contains("Subject: Is dinner ready?"),
+        "{}",
+        String::from_utf8_lossy(&contents)
+    );
+
+    // Queue entries are only written to the queue store
+    assert_eq!(queued_messages(test.server.store()).await, 0);
+    for _ in 0..50 {
+        if queued_messages(test.server.queue_store()).await == 0 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(queued_messages(test.server.queue_store()).await, 0);
+}
+
+async fn queued_messages(store: &Store) -> usize {
+    let mut count = 0;
+    store
+        .iterate(
+            IterateParams::new(
+                ValueKey::from(ValueClass::Queue(QueueClass::Message(0))),
+                ValueKey::from(ValueClass::Queue(QueueClass::Message(u64::MAX))),
+            )
+            .no_values(),
+            |_, _| {
+                count += 1;
+                Ok(true)
+            },
+        )
+        .await
+        .unwrap();
+    count
+}
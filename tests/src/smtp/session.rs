@@ -4,21 +4,41 @@
  * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
  */
 
+use crate::utils::server::TestServerBuilder;
 use base64::{Engine, engine::general_purpose};
 use common::{
     Server,
     config::server::ServerProtocol,
-    network::{ServerInstance, SessionStream, TcpAcceptor, limiter::ConcurrencyLimiter},
+    network::{
+        ServerInstance, SessionStream, TcpAcceptor, fingerprint::TlsFingerprint,
+        limiter::ConcurrencyLimiter,
+    },
 };
-use rustls::{ServerConfig, server::ResolvesServerCert};
+use registry::{
+    schema::{
+        enums::{NetworkListenerProtocol, TlsClientAuth, TlsClientCertField},
+        prelude::SocketAddr,
+        structs::NetworkListener,
+    },
+    types::map::Map,
+};
+use rustls::{
+    ServerConfig, SignatureScheme, client::ResolvesClientCert,
+    crypto::aws_lc_rs::sign::any_supported_type, server::ResolvesServerCert, sign::CertifiedKey,
+};
+use rustls_pki_types::ServerName;
 use smtp::core::{Session, SessionAddress, SessionData, SessionParameters, State};
-use std::{borrow::Cow, path::PathBuf, sync::Arc};
+use std::{borrow::Cow, io::Cursor, path::PathBuf, str::FromStr, sync::Arc, time::Duration};
 use tokio::{
-    io::{AsyncRead, AsyncWrite},
+    io::{
+        AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, Lines, ReadHalf,
+        WriteHalf,
+    },
+    net::TcpStream,
     sync::watch,
 };
-use tokio_rustls::TlsAcceptor;
-use utils::snowflake::SnowflakeIdGenerator;
+use tokio_rustls::{TlsAcceptor, TlsConnector, client::TlsStream};
+use utils::{snowflake::SnowflakeIdGenerator, tls::rustls_client_config};
 
 pub struct DummyIo {
     pub tx_buf: Vec<u8>,
@@ -75,6 +95,14 @@ impl SessionStream for DummyIo {
     fn tls_version_and_cipher(&self) -> (Cow<'static, str>, Cow<'static, str>) {
         ("".into(), "".into())
     }
+
+    fn peer_certificate(&self) -> Option<&[u8]> {
+        None
+    }
+
+    fn tls_fingerprint(&self) -> Option<&TlsFingerprint> {
+        None
+    }
 }
 
 impl Unpin for DummyIo {}
@@ -366,11 +394,13 @@ impl TestServerInstance for ServerInstance {
                 config: tls_config.clone(),
                 acceptor: TlsAcceptor::from(tls_config),
                 implicit: false,
+                client_cert_principal: vec![],
             },
             limiter: ConcurrencyLimiter::new(100),
             shutdown_rx,
             proxy_networks: vec![],
             span_id_gen: Arc::new(SnowflakeIdGenerator::new()),
+            sessions: Default::default(),
         }
     }
 }
@@ -387,3 +417,204 @@ impl ResolvesServerCert for DummyCertResolver {
 pub fn test_server_instance() -> ServerInstance {
     ServerInstance::test_with_shutdown(watch::channel(false).1)
 }
+
+#[tokio::test]
+async fn auth_external() {
+    let test = TestServerBuilder::new("smtp_auth_external_test")
+        .await
+        .with_http_listener(19002)
+        .await
+        .with_dummy_tls_cert(["*.example.org"])
+        .await
+        .with_object(mtls_listener("smtp-mtls", 9465, TlsClientAuth::Required))
+        .await
+        .with_object(mtls_listener(
+            "smtp-mtls-optional",
+            9466,
+            TlsClientAuth::Optional,
+        ))
+        .await
+        .disable_services()
+        .build()
+        .await;
+    test.account("admin")
+        .create_user_account(
+            "jane@example.org",
+            "abcde + extra safety",
+            "Jane Smith",
+            &[],
+            vec![],
+        )
+        .await;
+
+    // Connections without a client certificate or with one issued by an
+    // untrusted CA are rejected when client authentication is required
+    for certificate in [None, Some("client_rogue")] {
+        if let Ok(mut conn) = MtlsConnection::connect(9465, certificate).await {
+            assert!(
+                conn.read().await.is_none(),
+                "Expected {certificate:?} to be rejected"
+            );
+        }
+    }
+
+    // EXTERNAL is not offered without a client certificate
+    let mut conn = MtlsConnection::connect(9466, None).await.unwrap();
+    conn.read().await.unwrap().assert_code("220");
+    conn.cmd("EHLO mx.example.org", "250")
+        .await
+        .assert_not_contains("EXTERNAL");
+    conn.cmd("AUTH EXTERNAL =", "5").await;
+
+    // Certificates that do not map to an account are rejected
+    let mut conn = MtlsConnection::connect(9465, Some("client_unknown"))
+        .await
+        .unwrap();
+    conn.read().await.unwrap().assert_code("220");
+    conn.cmd("EHLO mx.example.org", "250")
+        .await
+        .assert_contains("EXTERNAL");
+    conn.cmd("AUTH EXTERNAL =", "535 5.7.8").await;
+
+    // Authorization identities are not supported
+    let mut conn = MtlsConnection::connect(9465, Some("client_jane"))
+        .await
+        .unwrap();
+    conn.read().await.unwrap().assert_code("220");
+    conn.cmd("EHLO mx.example.org", "250")
+        .await
+        .assert_contains("EXTERNAL");
+    conn.cmd(
+        &format!(
+            "AUTH EXTERNAL {}",
+            general_purpose::STANDARD.encode("john@example.org")
+        ),
+        "500 5.5.6",
+    )
+    .await;
+
+    // Successful authentication with an initial response
+    conn.cmd("AUTH EXTERNAL =", "235 2.7.0").await;
+    conn.cmd("AUTH EXTERNAL =", "503 5.5.1").await;
+    conn.cmd("MAIL FROM:<jane@example.org>", "250").await;
+
+    // Successful authentication after a challenge
+    let mut conn = MtlsConnection::connect(9466, Some("client_jane"))
+        .await
+        .unwrap();
+    conn.read().await.unwrap().assert_code("220");
+    conn.cmd("EHLO mx.example.org", "250")
+        .await
+        .assert_contains("EXTERNAL");
+    conn.cmd("AUTH EXTERNAL", "334").await;
+    conn.cmd("=", "235 2.7.0").await;
+}
+
+fn mtls_listener(name: &str, port: u16, client_auth: TlsClientAuth) -> NetworkListener {
+    NetworkListener {
+        bind: Map::new(vec![
+            SocketAddr::from_str(&format!("0.0.0.0:{port}")).unwrap(),
+        ]),
+        name: name.to_string(),
+        protocol: NetworkListenerProtocol::Smtp,
+        use_tls: true,
+        tls_implicit: true,
+        tls_client_auth: client_auth,
+        tls_client_ca_certificates: Some(load_test_cert("client_ca.pem")),
+        tls_client_cert_principal: Map::new(vec![TlsClientCertField::SanEmail]),
+        ..Default::default()
+    }
+}
+
+fn load_test_cert(file: &str) -> String {
+    let mut test_file = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    test_file.push("resources");
+    test_file.push("smtp");
+    test_file.push("certs");
+    test_file.push(file);
+    std::fs::read_to_string(test_file).unwrap()
+}
+
+struct MtlsConnection {
+    reader: Lines<BufReader<ReadHalf<TlsStream<TcpStream>>>>,
+    writer: WriteHalf<TlsStream<TcpStream>>,
+}
+
+impl MtlsConnection {
+    async fn connect(port: u16, certificate: Option<&str>) -> std::io::Result<Self> {
+        let mut config = rustls_client_config(true).unwrap();
+        if let Some(certificate) = certificate {
+            let certs = rustls_pemfile::certs(&mut Cursor::new(load_test_cert(&format!(
+                "{certificate}.pem"
+            ))))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+            let key = rustls_pemfile::private_key(&mut Cursor::new(load_test_cert(&format!(
+                "{certificate}_key.pem"
+            ))))
+            .unwrap()
+            .unwrap();
+            config.client_auth_cert_resolver = Arc::new(ClientCertResolver(Arc::new(
+                CertifiedKey::new(certs, any_supported_type(&key).unwrap()),
+            )));
+        }
+
+        let (reader, writer) = tokio::io::split(
+            TlsConnector::from(Arc::new(config))
+                .connect(
+                    ServerName::try_from("mx.example.org").unwrap().to_owned(),
+                    TcpStream::connect(format!("127.0.0.1:{port}"))
+                        .await
+                        .unwrap(),
+                )
+                .await?,
+        );
+
+        Ok(MtlsConnection {
+            reader: BufReader::new(reader).lines(),
+            writer,
+        })
+    }
+
+    // Returns None when the server closed the connection or the handshake failed
+    async fn read(&mut self) -> Option<Vec<String>> {
+        let mut lines = Vec::new();
+        loop {
+            match tokio::time::timeout(Duration::from_millis(1500), self.reader.next_line()).await {
+                Ok(Ok(Some(line))) => {
+                    let is_done = line.as_bytes().get(3) != Some(&b'-');
+                    lines.push(line);
+                    if is_done {
+                        return Some(lines);
+                    }
+                }
+                Ok(Ok(None) | Err(_)) => return None,
+                Err(_) => panic!("Timeout while waiting for server response: {lines:?}"),
+            }
+        }
+    }
+
+    async fn cmd(&mut self, cmd: &str, expected_code: &str) -> Vec<String> {
+        self.writer
+            .write_all(format!("{cmd}\r\n").as_bytes())
+            .await
+            .unwrap();
+        self.read()
+            .await
+            .expect("Connection closed")
+            .assert_code(expected_code)
+    }
+}
+
+#[derive(Debug)]
+struct ClientCertResolver(Arc<CertifiedKey>);
+
+impl ResolvesClientCert for ClientCertResolver {
+    fn resolve(&self, _: &[&[u8]], _: &[SignatureScheme]) -> Option<Arc<CertifiedKey>> {
+        Some(self.0.clone())
+    }
+
+    fn has_certs(&self) -> bool {
+        true
+    }
+}
@@ -28,7 +28,7 @@ use registry::{
 use std::str::FromStr;
 use store::{
     registry::{
-        RegistryQuery,
+        RegistryOverlay, RegistryQuery,
         write::{RegistryWrite, RegistryWriteResult},
     },
     write::now,
@@ -442,6 +442,140 @@ pub async fn test(test: &TestServer) {
         vec![domain_id_2, domain_id]
     );
 
+    // Dry-run validation should take uncommitted changes into account
+    let mut overlay = RegistryOverlay::default();
+    let overlay_domain_id = Id::new(r.assign_id());
+    let overlay_group_id = Id::new(r.assign_id());
+    let new_domain = |name: &str| -> Object {
+        Domain {
+            name: name.into(),
+            certificate_management: CertificateManagement::Manual,
+            dns_management: DnsManagement::Manual,
+            dkim_management: DkimManagement::Manual,
+            is_enabled: true,
+            ..Default::default()
+        }
+        .into()
+    };
+    overlay.remove(ObjectId::new(ObjectType::Domain, domain_id_2));
+    overlay.set(
+        ObjectId::new(ObjectType::Domain, overlay_domain_id),
+        new_domain("test.com"),
+    );
+    assert_eq!(
+        r.validate(RegistryWrite::insert(&new_domain("test.com")), &overlay)
+            .await
+            .unwrap(),
+        RegistryWriteResult::PrimaryKeyConflict {
+            property: Property::Name,
+            existing_id: ObjectId::new(ObjectType::Domain, overlay_domain_id),
+        }
+    );
+    assert!(matches!(
+        r.validate(RegistryWrite::insert(&new_domain("test.net")), &overlay)
+            .await
+            .unwrap(),
+        RegistryWriteResult::Success(_)
+    ));
+    let overlay_group: Object = Account::Group(GroupAccount {
+        name: "overlay-group".into(),
+        domain_id: overlay_domain_id,
+        ..Default::default()
+    })
+    .into();
+    assert!(matches!(
+        r.validate(RegistryWrite::insert(&overlay_group), &overlay)
+            .await
+            .unwrap(),
+        RegistryWriteResult::Success(_)
+    ));
+    assert_eq!(
+        r.validate(
+            RegistryWrite::insert(
+                &Account::Group(GroupAccount {
+                    name: "overlay-group".into(),
+                    domain_id: domain_id_2,
+                    ..Default::default()
+                })
+                .into()
+            ),
+            &overlay
+        )
+        .await
+        .unwrap(),
+        RegistryWriteResult::InvalidForeignKey {
+            object_id: ObjectId::new(ObjectType::Domain, domain_id_2),
+        }
+    );
+    overlay.set(
+        ObjectId::new(ObjectType::Account, overlay_group_id),
+        overlay_group,
+    );
+    assert_eq!(
+        r.validate(
+            RegistryWrite::delete(ObjectId::new(ObjectType::Domain, overlay_domain_id)),
+            &overlay
+        )
+        .await
+        .unwrap(),
+        RegistryWriteResult::CannotDeleteLinked {
+            object_id: ObjectId::new(ObjectType::Domain, overlay_domain_id),
+            linked_objects: vec![ObjectId::new(ObjectType::Account, overlay_group_id)],
+        }
+    );
+
+    // Reads through the overlay should return the uncommitted changes
+    let ro = r.with_overlay(overlay);
+    let mut expected = vec![domain_id, overlay_domain_id];
+    expected.sort();
+    assert_eq!(
+        ro.query::<Vec<Id>>(RegistryQuery::new(ObjectType::Domain))
+            .await
+            .unwrap(),
+        expected
+    );
+    assert_eq!(ro.count_object(ObjectType::Domain).await.unwrap(), 2);
+    assert_eq!(
+        ro.query::<Vec<Id>>(RegistryQuery::new(ObjectType::Domain).equal_pk(
+            Property::Name,
+            "test.com".to_string(),
+            true,
+        ))
+        .await
+        .unwrap(),
+        vec![overlay_domain_id]
+    );
+    assert_eq!(
+        ro.query::<Vec<Id>>(RegistryQuery::new(ObjectType::Domain).equal_pk(
+            Property::Name,
+            "test.net".to_string(),
+            true,
+        ))
+        .await
+        .unwrap(),
+        Vec::<Id>::new()
+    );
+    assert_eq!(
+        ro.linked_objects(ObjectId::new(ObjectType::Domain, overlay_domain_id))
+            .await
+            .unwrap(),
+        vec![ObjectId::new(ObjectType::Account, overlay_group_id)]
+    );
+
+    // Nothing should have been written
+    assert_eq!(
+        r.query::<Vec<Id>>(RegistryQuery::new(ObjectType::Domain))
+            .await
+            .unwrap(),
+        vec![domain_id, domain_id_2]
+    );
+    assert!(
+        r.get(ObjectId::new(ObjectType::Domain, overlay_domain_id))
+            .await
+            .unwrap()
+            .is_none()
+    );
+
     // Delete everything
     let old_account = r
         .get(ObjectId::new(ObjectType::Account, account_id))
@@ -239,6 +239,78 @@ pub async fn test(test: &mut TestServer) {
         .await
         .assert_contains(&format!("Subject: undelete test for {}", john.name()));
 
+    // Delete the restored message again
+    john_imap.send("STORE 1 +FLAGS (\\Deleted)").await;
+    john_imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+    john_imap.send("EXPUNGE").await;
+    john_imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+    test.wait_for_tasks().await;
+    let ids = john
+        .registry_query(
+            ObjectType::ArchivedItem,
+            Vec::<(&str, &str)>::new(),
+            Vec::<&str>::new(),
+        )
+        .await
+        .object_ids()
+        .collect::<Vec<_>>();
+    assert_eq!(ids.len(), 1);
+    assert_ne!(ids[0], john_archive_id);
+    let john_archive_id = ids[0];
+
+    // Restoring into a non-existent mailbox should fail
+    john.registry_update_object_expect_err(
+        ObjectType::ArchivedItem,
+        john_archive_id,
+        json!({
+            Property::Status: ArchivedItemStatus::RequestRestore,
+            Property::MailboxId: Id::from(u32::MAX).to_string(),
+        }),
+    )
+    .await
+    .assert_type(SetErrorType::InvalidProperties);
+
+    // Request restore for John's archived item into a new mailbox
+    john_imap.send("CREATE Restored").await;
+    let mailbox_id = john_imap
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .into_response_code()
+        .strip_prefix("MAILBOXID (")
+        .and_then(|id| id.strip_suffix(')'))
+        .unwrap()
+        .to_string();
+    john.registry_update_object(
+        ObjectType::ArchivedItem,
+        john_archive_id,
+        json!({
+            Property::Status: ArchivedItemStatus::RequestRestore,
+            Property::MailboxId: mailbox_id,
+        }),
+    )
+    .await;
+    test.wait_for_tasks().await;
+
+    // Make sure the message is back in the requested mailbox
+    john_imap.send("STATUS INBOX (MESSAGES)").await;
+    john_imap
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("MESSAGES 0");
+    john_imap.send("STATUS Restored (MESSAGES)").await;
+    john_imap
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains("MESSAGES 1");
+
+    john_imap.send("SELECT Restored").await;
+    john_imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+    john_imap.send("FETCH 1 BODY[]").await;
+    john_imap
+        .assert_read(Type::Tagged, ResponseType::Ok)
+        .await
+        .assert_contains(&format!("Subject: undelete test for {}", john.name()));
+
     // Jane's archived item should be deleted on the next purge
     admin
         .registry_create_object(Task::StoreMaintenance(TaskStoreMaintenance {
@@ -335,7 +407,7 @@ pub async fn test(test: &mut TestServer) {
     john_imap
         .authenticate("jdoe@example.org", "brand new secret")
         .await;
-    john_imap.send("SELECT INBOX").await;
+    john_imap.send("SELECT Restored").await;
     john_imap.assert_read(Type::Tagged, ResponseType::Ok).await;
     john_imap.send("FETCH 1 BODY[]").await;
     john_imap
@@ -0,0 +1,161 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::server::TestServer;
+use common::{
+    Server,
+    manager::audit::{AuditAction, AuditEntry, AuditFilter},
+};
+use registry::{
+    schema::{prelude::ObjectType, structs::SieveSystemScript},
+    types::EnumImpl,
+};
+use serde_json::json;
+use std::time::Duration;
+use store::write::{Archiver, BatchBuilder, ValueClass};
+
+pub async fn test(test: &TestServer) {
+    println!("Running audit log tests...");
+
+    let server = &test.server;
+    let admin = test.account("admin@example.org");
+    let script = SieveSystemScript {
+        name: "audit_test".into(),
+        is_active: false,
+        contents: "keep;".into(),
+        ..Default::default()
+    };
+
+    // Registry changes are recorded with their previous and new values
+    let script_id = admin.registry_create_object(script.clone()).await;
+    admin
+        .registry_update_object(
+            ObjectType::SieveSystemScript,
+            script_id,
+            json!({"description": "Audited script"}),
+        )
+        .await;
+    let num_entries = script_entries(server, None).await.len();
+
+    // Rejected changes are not recorded
+    admin.registry_create_object_expect_err(script).await;
+    assert_eq!(script_entries(server, None).await.len(), num_entries);
+
+    // Destroy the script
+    assert_eq!(
+        admin
+            .registry_destroy(ObjectType::SieveSystemScript, [script_id])
+            .await
+            .destroyed_ids()
+            .collect::<Vec<_>>(),
+        vec![script_id]
+    );
+    let entries = script_entries(server, Some(script_id.id())).await;
+    assert_eq!(
+        entries
+            .iter()
+            .map(|(_, entry)| entry.action)
+            .collect::<Vec<_>>(),
+        vec![
+            AuditAction::Destroy,
+            AuditAction::Update,
+            AuditAction::Create
+        ]
+    );
+    let (_, destroy) = &entries[0];
+    let (_, update) = &entries[1];
+    let (create_id, create) = &entries[2];
+    assert!(create.before.is_none());
+    assert!(create.after.as_ref().unwrap().contains("audit_test"));
+    assert!(!update.before.as_ref().unwrap().contains("Audited script"));
+    assert!(update.after.as_ref().unwrap().contains("Audited script"));
+    assert!(destroy.before.as_ref().unwrap().contains("Audited script"));
+    assert!(destroy.after.is_none());
+    for (_, entry) in &entries {
+        assert_eq!(entry.account_id, admin.id().document_id());
+    }
+
+    // The chain is intact
+    let verification = server.verify_audit_log().await.unwrap();
+    assert_eq!(verification.first_invalid, None);
+    assert!(verification.entries >= entries.len() as u64);
+
+    // Modifying an entry breaks the link from the entry that follows it
+    let next_id = server
+        .query_audit_log(&AuditFilter::default())
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(id, _)| id)
+        .filter(|id| id > create_id)
+        .min()
+        .unwrap();
+    let mut tampered = create.clone();
+    tampered.after = Some("{}".into());
+    write_entry(server, *create_id, Some(tampered)).await;
+    assert_eq!(
+        server.verify_audit_log().await.unwrap().first_invalid,
+        Some(next_id)
+    );
+
+    // Restoring the original entry repairs the chain
+    write_entry(server, *create_id, Some(create.clone())).await;
+    assert_eq!(server.verify_audit_log().await.unwrap().first_invalid, None);
+
+    // Removing an entry breaks the chain as well
+    write_entry(server, *create_id, None).await;
+    assert_eq!(
+        server.verify_audit_log().await.unwrap().first_invalid,
+        Some(next_id)
+    );
+    write_entry(server, *create_id, Some(create.clone())).await;
+    assert_eq!(server.verify_audit_log().await.unwrap().first_invalid, None);
+
+    // Entries past the retention period are purged, new entries are linked to the
+    // last purged one and the oldest remaining entry is trusted
+    server.purge_audit_log(Duration::ZERO).await.unwrap();
+    let verification = server.verify_audit_log().await.unwrap();
+    assert_eq!(verification.entries, 0);
+    assert_eq!(verification.first_invalid, None);
+    let script_id = admin
+        .registry_create_object(SieveSystemScript {
+            name: "audit_test".into(),
+            is_active: false,
+            contents: "keep;".into(),
+            ..Default::default()
+        })
+        .await;
+    admin
+        .registry_destroy(ObjectType::SieveSystemScript, [script_id])
+        .await;
+    let verification = server.verify_audit_log().await.unwrap();
+    assert_eq!(verification.entries, 2);
+    assert_eq!(verification.first_invalid, None);
+}
+
+async fn script_entries(server: &Server, object_id: Option<u64>) -> Vec<(u64, AuditEntry)> {
+    server
+        .query_audit_log(&AuditFilter {
+            object_type: Some(ObjectType::SieveSystemScript.as_str().to_string()),
+            object_id,
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+}
+
+async fn write_entry(server: &Server, entry_id: u64, entry: Option<AuditEntry>) {
+    let mut batch = BatchBuilder::new();
+    if let Some(entry) = entry {
+        batch.set(
+            ValueClass::AuditLog(entry_id),
+            Archiver::new(entry).serialize().unwrap(),
+        );
+    } else {
+        batch.clear(ValueClass::AuditLog(entry_id));
+    }
+    server.store().write(batch.build_all()).await.unwrap();
+}
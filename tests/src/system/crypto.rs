@@ -10,9 +10,13 @@ use common::{
         ACCOUNT_FLAG_ENCRYPT_ALGO_AES128, ACCOUNT_FLAG_ENCRYPT_ALGO_AES256,
         ACCOUNT_FLAG_ENCRYPT_METHOD_PGP, ACCOUNT_FLAG_ENCRYPT_METHOD_SMIME,
     },
+    network::wkd::wkd_hash,
     storage::encryption::{EncryptionMethod, parse_public_key},
 };
-use email::message::crypto::EncryptMessage;
+use email::{
+    autocrypt::{AutocryptHeader, AutocryptStore},
+    message::crypto::EncryptMessage,
+};
 use mail_parser::{MessageParser, MimeHeaders};
 use registry::schema::{
     prelude::{ObjectType, Property},
@@ -198,6 +202,130 @@ pub async fn test(test: &mut TestServer) {
         }
     }
 
+    // Enable encryption with a different key, messages stored in plain text
+    // should be encrypted while those already encrypted are left intact
+    account
+        .registry_update_object(
+            ObjectType::AccountSettings,
+            Id::singleton(),
+            json!({
+                Property::EncryptionAtRest: EncryptionAtRest::Aes256(EncryptionSettings {
+                    allow_spam_training: true,
+                    encrypt_on_append: true,
+                    public_key: cert_ids[0],
+                })
+            }),
+        )
+        .await;
+    test.wait_for_tasks().await;
+
+    let mut request = client.build();
+    request.get_email();
+    let emails = request.send_get_email().await.unwrap().take_list();
+    assert_eq!(emails.len(), 3, "3 messages were expected: {:#?}.", emails);
+
+    for email in emails {
+        let message =
+            String::from_utf8(client.download(email.blob_id().unwrap()).await.unwrap()).unwrap();
+        if message.contains("should be encrypted") {
+            assert!(
+                message.contains("Content-Type: multipart/encrypted"),
+                "got message {message}, expected message encrypted with the previous key"
+            );
+        } else if message.contains("already encrypted") {
+            assert!(
+                message.contains("xjMEZMYfNhYJKwYBBAHaRw8BAQdAYy"),
+                "got message {message}, expected message to be left intact"
+            );
+        } else if message.contains("plain text") {
+            assert!(
+                message.contains("Content-Type: application/pkcs7-mime")
+                    && !message.contains("I'm going to need those TPS reports ASAP."),
+                "got message {message}, expected message encrypted with the new key"
+            );
+        } else {
+            panic!("Unexpected message: {:#?}", message)
+        }
+    }
+
+    // The uploaded OpenPGP key is published in the Web Key Directory,
+    // while the S/MIME certificate is not
+    assert_eq!(wkd_hash("Joe.Doe"), "iy9q119eutrkn8s1mk4r39qejnbu3n5q");
+    let wkd_key = test
+        .server
+        .wkd_key("example.org", &wkd_hash("jdoe"), Some("jdoe"))
+        .await
+        .unwrap()
+        .expect("expected OpenPGP key in WKD");
+    assert!(wkd_key.first().is_some_and(|tag| tag & 0x80 != 0));
+    for (hash, local_part) in [("jdoe", "bill"), ("bill", "bill"), ("jdoe", "")] {
+        assert_eq!(
+            test.server
+                .wkd_key("example.org", &wkd_hash(hash), Some(local_part))
+                .await
+                .unwrap(),
+            None
+        );
+    }
+    assert!(test.server.is_wkd_domain("example.org").await.unwrap());
+    assert!(!test.server.is_wkd_domain("example.invalid").await.unwrap());
+
+    // Keys announced in Autocrypt headers are added to the peer cache
+    let account_id = account.id().document_id();
+    let key_data = concat!(
+        "xjMEZMYfNhYJKwYBBAHaRw8BAQdAYyTN1HzqapLw8xwkCGwa0OjsgT/JqhcB/+Dy",
+        "Ga1fsBrNG0pvaG4gRG9lIDxqb2huQGV4YW1wbGUub3JnPsKJBBMWCAAxFiEEg836",
+        "pwbXpuQ/THMtpJwd4oBfIrUFAmTGHzYCGwMECwkIBwUVCAkKCwUWAgMBAAAKCRCk",
+        "nB3igF8itYhyAQD2jEdeYa3gyQ47X9YWZTK1wEJkN8W9//V1fYl2XQwqlQEA0qBv",
+        "Ai6nUh99oDw+/zQ8DFIKdeb5Ti4tu/X58PdpiQ7OOARkxh82EgorBgEEAZdVAQUB",
+        "AQdAvXz2FbFN0DovQF/ACnZyczTsSIQp0mvmF1PE+aijbC8DAQgHwngEGBYIACAW",
+        "IQSDzfqnBtem5D9Mcy2knB3igF8itQUCZMYfNgIbDAAKCRCknB3igF8itRnoAQC3",
+        "GzPmgx7TnB+SexPuJV/DoKSMJ0/X+hbEFcZkulxaDQEAh+xiJCvf+ZNAKw6kFhsL",
+        "UuZhEDktxnY6Ehz3aB7FawA=",
+    );
+    for (from, autocrypt) in [
+        (
+            "bill@example.org",
+            format!("addr=bill@example.org; unknown=1; keydata={key_data}"),
+        ),
+        (
+            "john@example.org",
+            format!("addr=bill@example.org; keydata={key_data}"),
+        ),
+        (
+            "bill@example.org",
+            format!("addr=bill@example.org; prefer-encrypt=mutual; _note=1; keydata={key_data}"),
+        ),
+    ] {
+        let message = format!(
+            concat!(
+                "From: {}\r\n",
+                "To: jdoe@example.org\r\n",
+                "Autocrypt: {}\r\n",
+                "Subject: Autocrypt\r\n",
+                "\r\n",
+                "Hello\r\n"
+            ),
+            from, autocrypt
+        );
+        test.server
+            .autocrypt_ingest(
+                account_id,
+                &MessageParser::new().parse(message.as_bytes()).unwrap(),
+            )
+            .await
+            .unwrap();
+    }
+    let peers = test.server.autocrypt_peers(account_id).await.unwrap();
+    assert_eq!(peers.peers.len(), 1, "{peers:?}");
+    assert_eq!(peers.peers[0].address, "bill@example.org");
+    assert_eq!(peers.peers[0].key_data, key_data);
+    assert!(peers.peers[0].prefer_encrypt);
+    assert!(
+        AutocryptHeader::parse_value(&format!("addr=bill@example.org; keydata=AAAA{key_data}"))
+            .is_none()
+    );
+
     test.account("admin@example.org")
         .destroy_account(account)
         .await;
@@ -444,10 +444,13 @@ pub async fn test(test: &TestServer) {
             .rcpt_resolve("newsletter@example.com", 0)
             .await
             .unwrap(),
-        RcptResolution::Expand(Arc::from(Box::from_iter([
-            "jdoe@example.com".into(),
-            "sales@example.com".into()
-        ])))
+        RcptResolution::Expand(
+            Arc::from(Box::from_iter([
+                "jdoe@example.com".into(),
+                "sales@example.com".into()
+            ])),
+            None
+        )
     );
     assert_eq!(
         test.server
@@ -0,0 +1,128 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::server::TestServer;
+use common::ipc::QueueEvent;
+use serde_json::Value;
+use std::{sync::atomic::Ordering, time::Duration};
+use tokio::{io::AsyncReadExt, net::TcpStream};
+
+pub const AGENT_CHECK_PORT: u16 = 11300;
+
+pub async fn test(test: &mut TestServer) {
+    println!("Running health check tests...");
+
+    // Healthy node
+    assert_eq!(agent_check().await, "up ready 100%\n");
+    let (status, health) = health_status().await;
+    assert_eq!(status, 200);
+    assert_eq!(health["state"], "ready");
+    assert_eq!(health["store"], "ok");
+    assert_eq!(health["weight"], 100);
+    assert_eq!(healthz("ready").await, 200);
+
+    // Draining node, loopback connections are still accepted
+    test.server
+        .inner
+        .data
+        .draining
+        .store(true, Ordering::Relaxed);
+    assert_eq!(agent_check().await, "drain\n");
+    let (status, health) = health_status().await;
+    assert_eq!(status, 503);
+    assert_eq!(health["state"], "draining");
+    assert_eq!(health["draining"], true);
+    assert_eq!(health["weight"], 0);
+    assert_eq!(healthz("ready").await, 503);
+    assert_eq!(healthz("live").await, 200);
+
+    test.server
+        .inner
+        .data
+        .draining
+        .store(false, Ordering::Relaxed);
+    assert_eq!(agent_check().await, "up ready 100%\n");
+
+    // Draining pauses the queue and resumes it afterwards
+    let server = &test.server;
+    server.set_draining(true).await;
+    assert!(server.drain_status().draining);
+    assert_eq!(agent_check().await, "drain\n");
+    wait_for_queue(test, false).await;
+    server.set_draining(false).await;
+    wait_for_queue(test, true).await;
+    assert!(!server.drain_status().draining);
+    assert_eq!(agent_check().await, "up ready 100%\n");
+
+    // A queue paused before draining is left paused
+    let _ = server
+        .inner
+        .ipc
+        .queue_tx
+        .send(QueueEvent::Paused(true))
+        .await;
+    wait_for_queue(test, false).await;
+    server.set_draining(true).await;
+    server.set_draining(false).await;
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(!server.inner.data.queue_status.load(Ordering::Relaxed));
+    let _ = server
+        .inner
+        .ipc
+        .queue_tx
+        .send(QueueEvent::Paused(false))
+        .await;
+    wait_for_queue(test, true).await;
+}
+
+async fn wait_for_queue(test: &TestServer, running: bool) {
+    for _ in 0..50 {
+        if test.server.inner.data.queue_status.load(Ordering::Relaxed) == running {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("Queue running status did not change to {running}");
+}
+
+async fn agent_check() -> String {
+    let mut stream = TcpStream::connect(("127.0.0.1", AGENT_CHECK_PORT))
+        .await
+        .unwrap();
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply).await.unwrap();
+    reply
+}
+
+async fn health_status() -> (u16, Value) {
+    let response = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap()
+        .get("https://127.0.0.1:8899/healthz/status")
+        .send()
+        .await
+        .unwrap();
+    let status = response.status().as_u16();
+
+    (
+        status,
+        serde_json::from_slice(&response.bytes().await.unwrap()).unwrap(),
+    )
+}
+
+async fn healthz(endpoint: &str) -> u16 {
+    let response = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap()
+        .get(format!("https://127.0.0.1:8899/healthz/{endpoint}"))
+        .send()
+        .await
+        .unwrap();
+
+    response.status().as_u16()
+}
@@ -0,0 +1,164 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{
+    imap::{ImapConnection, Type},
+    server::TestServer,
+};
+use base64::{Engine, engine::general_purpose};
+use common::{
+    Server,
+    auth::{
+        AccessToken, Permissions,
+        permissions::{BuildPermissions, ReadOnlyPermissions},
+    },
+    manager::audit::{AuditEntry, AuditFilter},
+};
+use imap_proto::ResponseType;
+use jmap_client::{
+    client::{Client, Credentials},
+    mailbox::Role,
+};
+use registry::schema::enums::Permission;
+use std::net::{IpAddr, Ipv4Addr};
+
+pub async fn test(test: &TestServer) {
+    println!("Running impersonation tests...");
+
+    let server = &test.server;
+    let remote_ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+    let admin_id = test.account("admin@example.org").id().document_id();
+    let user = test
+        .create_user_account(
+            "admin@example.org",
+            "impersonated@example.org",
+            "this is the impersonated secret",
+            &[],
+            "Impersonated",
+        )
+        .await;
+    let user_id = user.id().document_id();
+
+    // Read-only sessions cannot fetch messages, as that sets the \Seen flag
+    let permissions = Permissions::from_permission(&[
+        Permission::ImapFetch,
+        Permission::ImapSearch,
+        Permission::ImapStore,
+    ])
+    .read_only();
+    assert!(permissions.get(Permission::ImapSearch as usize));
+    assert!(!permissions.get(Permission::ImapFetch as usize));
+    assert!(!permissions.get(Permission::ImapStore as usize));
+
+    // Impersonation cannot grant permissions the impersonator lacks
+    let limited = AccessToken::from_permissions(admin_id, [Permission::Impersonate]);
+    for read_only in [false, true] {
+        assert!(
+            server
+                .encode_impersonation_token(&limited, user_id, read_only, 60)
+                .await
+                .is_err()
+        );
+    }
+
+    // Read-only sessions cannot make changes
+    let admin = server
+        .access_token(admin_id)
+        .await
+        .and_then(|token| AccessToken::new(token, remote_ip))
+        .unwrap();
+    let token = server
+        .encode_impersonation_token(&admin, user_id, true, 60)
+        .await
+        .unwrap();
+    let client = impersonation_client(&token).await;
+    assert_eq!(client.default_account_id(), user.id_string());
+    assert!(
+        client
+            .mailbox_create("Read-only", None::<String>, Role::None)
+            .await
+            .is_err()
+    );
+    assert!(
+        impersonated_entries(server, admin_id, user_id)
+            .await
+            .is_empty()
+    );
+
+    // Changes made over JMAP are tagged with the impersonator
+    let token = server
+        .encode_impersonation_token(&admin, user_id, false, 60)
+        .await
+        .unwrap();
+    let client = impersonation_client(&token).await;
+    client
+        .mailbox_create("Impersonated", None::<String>, Role::None)
+        .await
+        .unwrap();
+    let entries = impersonated_entries(server, admin_id, user_id).await;
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].object_type, "JmapMethod");
+    assert!(entries[0].after.as_ref().unwrap().contains("Mailbox/set"));
+
+    // And so are changes made over IMAP
+    let mut imap = ImapConnection::connect(b"_x ").await;
+    imap.assert_read(Type::Untagged, ResponseType::Ok).await;
+    imap.send(&format!(
+        "AUTHENTICATE OAUTHBEARER {}",
+        general_purpose::STANDARD.encode(format!(
+            "n,a={},\u{1}auth=Bearer {}\u{1}\u{1}",
+            "impersonated@example.org", token
+        ))
+    ))
+    .await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+    imap.send("CREATE \"Impersonated IMAP\"").await;
+    imap.assert_read(Type::Tagged, ResponseType::Ok).await;
+    let entries = impersonated_entries(server, admin_id, user_id).await;
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].object_type, "ImapCommand");
+
+    // Changes made by the account owner are not tagged
+    user.jmap_client()
+        .await
+        .mailbox_create("Not Impersonated", None::<String>, Role::None)
+        .await
+        .unwrap();
+    assert_eq!(
+        impersonated_entries(server, admin_id, user_id).await.len(),
+        2
+    );
+
+    test.destroy_all_mailboxes(&user).await;
+}
+
+async fn impersonation_client(token: &str) -> Client {
+    Client::new()
+        .credentials(Credentials::bearer(token))
+        .accept_invalid_certs(true)
+        .follow_redirects(["127.0.0.1"])
+        .connect("https://127.0.0.1:8899")
+        .await
+        .unwrap()
+}
+
+async fn impersonated_entries(
+    server: &Server,
+    impersonator_id: u32,
+    account_id: u32,
+) -> Vec<AuditEntry> {
+    server
+        .query_audit_log(&AuditFilter {
+            account_id: Some(account_id),
+            impersonator_id: Some(impersonator_id),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(_, entry)| entry)
+        .collect()
+}
@@ -0,0 +1,95 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::server::TestServer;
+use common::config::server::ServerProtocol;
+use registry::schema::{
+    enums::LoginAnomalySensitivity, prelude::Property, structs::Authentication,
+};
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    time::Duration,
+};
+
+pub async fn test(test: &TestServer) {
+    println!("Running login anomaly tests...");
+
+    let server = &test.server;
+    set_sensitivity(test, LoginAnomalySensitivity::Low).await;
+
+    let user = test
+        .create_user_account(
+            "admin@example.org",
+            "anomaly@example.org",
+            "this is the anomaly secret",
+            &[],
+            "Anomaly",
+        )
+        .await;
+    let account_id = user.id().document_id();
+    assert_eq!(server.login_history(account_id).await.unwrap(), None);
+
+    // The history is recorded in the background after a successful login
+    user.jmap_client().await;
+    let mut history = None;
+    for _ in 0..50 {
+        history = server.login_history(account_id).await.unwrap();
+        if history.is_some() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(history.unwrap().clients, ["http"]);
+
+    // Concurrent logins do not overwrite each other's changes
+    let protocols = [
+        ServerProtocol::Imap,
+        ServerProtocol::Pop3,
+        ServerProtocol::Smtp,
+        ServerProtocol::ManageSieve,
+    ];
+    for result in
+        futures::future::join_all(protocols.iter().enumerate().map(|(session_id, protocol)| {
+            server.update_login_history(
+                account_id,
+                None,
+                IpAddr::V4(Ipv4Addr::LOCALHOST),
+                session_id as u64,
+                *protocol,
+            )
+        }))
+        .await
+    {
+        result.unwrap();
+    }
+    let history = server.login_history(account_id).await.unwrap().unwrap();
+    assert_eq!(history.clients.len(), protocols.len() + 1);
+    for protocol in protocols {
+        assert!(
+            history.clients.iter().any(|c| c == protocol.as_str()),
+            "{:?} missing from {:?}",
+            protocol,
+            history.clients
+        );
+    }
+
+    set_sensitivity(test, LoginAnomalySensitivity::Disabled).await;
+    test.destroy_all_mailboxes(&user).await;
+}
+
+async fn set_sensitivity(test: &TestServer, sensitivity: LoginAnomalySensitivity) {
+    let admin = test.account("admin@example.org");
+    admin
+        .registry_update_setting(
+            Authentication {
+                login_anomaly_sensitivity: sensitivity,
+                ..Default::default()
+            },
+            &[Property::LoginAnomalySensitivity],
+        )
+        .await;
+    admin.reload_settings().await;
+}
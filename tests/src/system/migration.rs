@@ -0,0 +1,201 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::{
+    utils::server::TestServer,
+    webdav::{TEST_ICAL_1, TEST_VCARD_1},
+};
+use common::manager::migration::{
+    MigrationAccount, MigrationCounters, MigrationRequest, MigrationSource,
+};
+use hyper::StatusCode;
+use jmap_client::{email, mailbox::Role, sieve};
+
+pub const SIEVE_STARTTLS_PORT: u16 = 4191;
+
+pub async fn test(test: &TestServer) {
+    println!("Running account migration tests...");
+
+    let server = &test.server;
+    let source = test
+        .create_user_account(
+            "admin@example.org",
+            "migration-source@example.org",
+            "this is the source secret",
+            &[],
+            "Migration Source",
+        )
+        .await;
+    let destination = test
+        .create_user_account(
+            "admin@example.org",
+            "migration-destination@example.org",
+            "this is the destination secret",
+            &[],
+            "Migration Destination",
+        )
+        .await;
+    let destination_id = destination.id().document_id();
+
+    // Populate the source account
+    let source_client = source.jmap_client().await;
+    let mailbox_id = source_client
+        .mailbox_create("Migrated", None::<String>, Role::None)
+        .await
+        .unwrap()
+        .take_id();
+    source_client
+        .email_import(
+            migration_message("First message").into_bytes(),
+            [&mailbox_id],
+            ["$seen"].into(),
+            None,
+        )
+        .await
+        .unwrap();
+    source_client
+        .sieve_script_create("migrated", b"keep;".to_vec(), true)
+        .await
+        .unwrap();
+    let dav_client = source.webdav_client();
+    for (path, item, contents) in [
+        (
+            "/dav/card/migration-source%40example.org/migrated-cards",
+            "card.vcf",
+            TEST_VCARD_1,
+        ),
+        (
+            "/dav/cal/migration-source%40example.org/migrated-events",
+            "event.ics",
+            TEST_ICAL_1,
+        ),
+    ] {
+        dav_client
+            .request("MKCOL", path, "")
+            .await
+            .with_status(StatusCode::CREATED);
+        dav_client
+            .request("PUT", &format!("{path}/{item}"), contents)
+            .await
+            .with_status(StatusCode::CREATED);
+    }
+
+    // Import everything into the destination account
+    let request = MigrationRequest {
+        source: MigrationSource {
+            imap_url: Some("imap://127.0.0.1:9991".into()),
+            sieve_url: Some(format!("sieve://127.0.0.1:{SIEVE_STARTTLS_PORT}")),
+            caldav_url: Some(
+                "https://127.0.0.1:8899/dav/cal/migration-source%40example.org/".into(),
+            ),
+            carddav_url: Some(
+                "https://127.0.0.1:8899/dav/card/migration-source%40example.org/".into(),
+            ),
+            allow_invalid_certs: true,
+        },
+        accounts: vec![MigrationAccount {
+            name: "migration-destination@example.org".into(),
+            username: Some("migration-source@example.org".into()),
+            password: "this is the source secret".into(),
+            description: None,
+        }],
+        create_accounts: false,
+    };
+    run_migration(test, &request).await;
+    let state = server
+        .migration_state(destination_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(state.completed_at.is_some(), "{:?}", state.last_error);
+    assert_eq!(
+        state.counters,
+        MigrationCounters {
+            messages: 1,
+            scripts: 1,
+            events: 1,
+            contacts: 1,
+            failed: 0,
+        }
+    );
+
+    // Messages keep their mailbox and flags
+    let destination_client = destination.jmap_client().await;
+    let email_ids = destination_client
+        .email_query(None::<email::query::Filter>, None::<Vec<_>>)
+        .await
+        .unwrap()
+        .take_ids();
+    assert_eq!(email_ids.len(), 1);
+    let email = destination_client
+        .email_get(
+            &email_ids[0],
+            [email::Property::Subject, email::Property::Keywords].into(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(email.subject(), Some("First message"));
+    assert_eq!(email.keywords(), ["$seen"]);
+    let scripts = destination_client
+        .sieve_script_query(None::<sieve::query::Filter>, None::<Vec<_>>)
+        .await
+        .unwrap()
+        .take_ids();
+    assert_eq!(scripts.len(), 1);
+
+    // Running the import again only fetches what changed
+    source_client
+        .email_import(
+            migration_message("Second message").into_bytes(),
+            [&mailbox_id],
+            None::<Vec<&str>>,
+            None,
+        )
+        .await
+        .unwrap();
+    run_migration(test, &request).await;
+    let state = server
+        .migration_state(destination_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(state.counters.messages, 2);
+    assert_eq!(state.counters.scripts, 1);
+    assert_eq!(state.counters.events, 1);
+    assert_eq!(state.counters.contacts, 1);
+    assert_eq!(
+        destination_client
+            .email_query(None::<email::query::Filter>, None::<Vec<_>>)
+            .await
+            .unwrap()
+            .ids()
+            .len(),
+        2
+    );
+
+    test.destroy_all_mailboxes(&source).await;
+    test.destroy_all_mailboxes(&destination).await;
+}
+
+async fn run_migration(test: &TestServer, request: &MigrationRequest) {
+    let report = test.server.schedule_migration(request, None).await.unwrap();
+    assert_eq!(report.scheduled, 1, "{:?}", report.accounts);
+    test.wait_for_tasks().await;
+}
+
+fn migration_message(subject: &str) -> String {
+    format!(
+        concat!(
+            "From: bill@example.com\r\n",
+            "To: migration-source@example.org\r\n",
+            "Subject: {}\r\n",
+            "\r\n",
+            "This message was imported from another server."
+        ),
+        subject
+    )
+}
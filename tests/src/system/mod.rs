@@ -6,20 +6,30 @@
 
 pub mod antispam;
 pub mod archiving;
+pub mod audit;
 pub mod authentication;
 pub mod authorization;
 pub mod crypto;
 pub mod delivery;
 pub mod directory;
+pub mod health;
+pub mod impersonation;
+pub mod login_anomaly;
+pub mod migration;
 pub mod oidc;
 pub mod purge;
 pub mod quota;
+pub mod reload;
 pub mod security;
+pub mod settings_export;
 pub mod task;
 pub mod tenant;
 
 use crate::utils::server::TestServerBuilder;
-use registry::schema::structs::{Expression, Imap, MtaStageAuth};
+use registry::schema::{
+    enums::NetworkListenerProtocol,
+    structs::{Expression, Imap, MtaStageAuth},
+};
 
 #[tokio::test(flavor = "multi_thread")]
 pub async fn system_tests() {
@@ -27,6 +37,20 @@ pub async fn system_tests() {
         .await
         .with_default_listeners()
         .await
+        .with_listener(
+            NetworkListenerProtocol::AgentCheck,
+            "agent-check",
+            health::AGENT_CHECK_PORT,
+            false,
+        )
+        .await
+        .with_listener(
+            NetworkListenerProtocol::ManageSieve,
+            "sieve-starttls",
+            migration::SIEVE_STARTTLS_PORT,
+            false,
+        )
+        .await
         .with_object(Imap {
             allow_plain_text_auth: true,
             ..Default::default()
@@ -64,6 +88,12 @@ pub async fn system_tests() {
     authorization::test(&mut test).await;
     tenant::test(&mut test).await;
     security::test(&mut test).await;
+    reload::test(&mut test).await;
+    settings_export::test(&test).await;
+    audit::test(&test).await;
+    impersonation::test(&test).await;
+    login_anomaly::test(&test).await;
+    migration::test(&test).await;
     quota::test(&mut test).await;
     purge::test(&mut test).await;
     delivery::test(&mut test).await;
@@ -71,6 +101,7 @@ pub async fn system_tests() {
     antispam::test(&mut test).await;
     archiving::test(&mut test).await;
     task::test(&mut test).await;
+    health::test(&mut test).await;
 
     if test.is_reset() {
         test.temp_dir.delete();
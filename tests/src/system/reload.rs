@@ -0,0 +1,225 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{registry::UnwrapRegistryId, server::TestServer};
+use common::{
+    cache::reload::{ReloadResult, SettingChangeType},
+    ipc::ReloadSubsystem,
+};
+use registry::{
+    schema::{
+        prelude::{Object, ObjectType},
+        structs::{AllowedIp, SieveSystemScript, SpamTag, SpamTagScore},
+    },
+    types::{float::Float, id::ObjectId, ipmask::IpAddrOrMask},
+};
+use std::{net::IpAddr, str::FromStr, sync::Arc};
+use store::registry::write::RegistryWrite;
+
+pub async fn test(test: &mut TestServer) {
+    println!("Running subsystem reload tests...");
+
+    let server = &test.server;
+    let core = server.inner.shared_core.load_full();
+
+    // Objects written straight to the registry are only applied after a reload
+    let ip = IpAddr::from_str("10.9.8.7").unwrap();
+    let allowed_ip: Object = AllowedIp {
+        address: IpAddrOrMask::from_str("10.9.8.7").unwrap(),
+        ..Default::default()
+    }
+    .into();
+    let allowed_ip_id = server
+        .registry()
+        .write(RegistryWrite::insert(&allowed_ip))
+        .await
+        .unwrap()
+        .unwrap_id(trc::location!());
+    let spam_tag: Object = SpamTag::Score(SpamTagScore {
+        tag: "RELOAD_TEST_TAG".into(),
+        score: Float::new(7.5),
+    })
+    .into();
+    let spam_tag_id = server
+        .registry()
+        .write(RegistryWrite::insert(&spam_tag))
+        .await
+        .unwrap()
+        .unwrap_id(trc::location!());
+    let script = SieveSystemScript {
+        name: "reload_test".into(),
+        is_active: true,
+        contents: "keep;".into(),
+        ..Default::default()
+    };
+    let script_object: Object = script.clone().into();
+    let script_id = server
+        .registry()
+        .write(RegistryWrite::insert(&script_object))
+        .await
+        .unwrap()
+        .unwrap_id(trc::location!());
+    assert!(!server.is_ip_allowed(ip));
+    assert!(
+        server
+            .inner
+            .data
+            .spam_lists
+            .load()
+            .scores
+            .get("RELOAD_TEST_TAG")
+            .is_none()
+    );
+    assert!(server.get_trusted_sieve_script("reload_test", 0).is_none());
+
+    // Reload the network subsystem
+    let result = server.reload_subsystem(ReloadSubsystem::Network).await;
+    assert_reloaded(
+        &result,
+        &[(
+            ObjectId::new(ObjectType::AllowedIp, allowed_ip_id),
+            SettingChangeType::Added,
+        )],
+    );
+    assert!(server.is_ip_allowed(ip));
+    assert!(
+        server
+            .inner
+            .data
+            .spam_lists
+            .load()
+            .scores
+            .get("RELOAD_TEST_TAG")
+            .is_none()
+    );
+    assert!(server.get_trusted_sieve_script("reload_test", 0).is_none());
+
+    // Reload the spam filter subsystem
+    let result = server.reload_subsystem(ReloadSubsystem::SpamFilter).await;
+    assert_reloaded(
+        &result,
+        &[(
+            ObjectId::new(ObjectType::SpamTag, spam_tag_id),
+            SettingChangeType::Added,
+        )],
+    );
+    assert!(
+        server
+            .inner
+            .data
+            .spam_lists
+            .load()
+            .scores
+            .get("RELOAD_TEST_TAG")
+            .is_some()
+    );
+    assert!(server.get_trusted_sieve_script("reload_test", 0).is_none());
+
+    // Reload the Sieve subsystem
+    let result = server.reload_subsystem(ReloadSubsystem::Sieve).await;
+    assert_reloaded(
+        &result,
+        &[(
+            ObjectId::new(ObjectType::SieveSystemScript, script_id),
+            SettingChangeType::Added,
+        )],
+    );
+    let compiled_script = server.get_trusted_sieve_script("reload_test", 0).unwrap();
+
+    // Reloading again reports no changes
+    let result = server.reload_subsystem(ReloadSubsystem::Sieve).await;
+    assert_reloaded(&result, &[]);
+    assert!(result.changes.is_empty());
+
+    // Scripts that fail to compile leave the running ones in place
+    let invalid_script: Object = SieveSystemScript {
+        contents: "if {".into(),
+        ..script
+    }
+    .into();
+    server
+        .registry()
+        .write(RegistryWrite::update(
+            script_id,
+            &invalid_script,
+            &script_object,
+        ))
+        .await
+        .unwrap()
+        .unwrap_id(trc::location!());
+    let result = server.reload_subsystem(ReloadSubsystem::Sieve).await;
+    assert!(result.has_errors());
+    assert!(result.changes.is_empty());
+    assert!(Arc::ptr_eq(
+        &compiled_script,
+        &server.get_trusted_sieve_script("reload_test", 0).unwrap()
+    ));
+
+    // Removed objects are reported and unloaded
+    for (object_type, id) in [
+        (ObjectType::AllowedIp, allowed_ip_id),
+        (ObjectType::SpamTag, spam_tag_id),
+        (ObjectType::SieveSystemScript, script_id),
+    ] {
+        server
+            .registry()
+            .write(RegistryWrite::delete(ObjectId::new(object_type, id)))
+            .await
+            .unwrap()
+            .unwrap_id(trc::location!());
+    }
+    for (subsystem, object_id) in [
+        (
+            ReloadSubsystem::Network,
+            ObjectId::new(ObjectType::AllowedIp, allowed_ip_id),
+        ),
+        (
+            ReloadSubsystem::SpamFilter,
+            ObjectId::new(ObjectType::SpamTag, spam_tag_id),
+        ),
+        (
+            ReloadSubsystem::Sieve,
+            ObjectId::new(ObjectType::SieveSystemScript, script_id),
+        ),
+    ] {
+        let result = server.reload_subsystem(subsystem).await;
+        assert_reloaded(&result, &[(object_id, SettingChangeType::Removed)]);
+    }
+    assert!(!server.is_ip_allowed(ip));
+    assert!(
+        server
+            .inner
+            .data
+            .spam_lists
+            .load()
+            .scores
+            .get("RELOAD_TEST_TAG")
+            .is_none()
+    );
+    assert!(server.get_trusted_sieve_script("reload_test", 0).is_none());
+
+    // None of the reloads replaced the running core
+    assert!(Arc::ptr_eq(&core, &server.inner.shared_core.load_full()));
+}
+
+fn assert_reloaded(result: &ReloadResult, expected: &[(ObjectId, SettingChangeType)]) {
+    assert!(
+        !result.has_errors(),
+        "unexpected reload errors: {:?}",
+        result.errors
+    );
+    assert!(!result.replaced_core);
+    for (object_id, change) in expected {
+        assert!(
+            result
+                .changes
+                .iter()
+                .any(|c| c.object_id == *object_id && c.change == *change),
+            "missing {change:?} for {object_id:?} in {:?}",
+            result.changes
+        );
+    }
+}
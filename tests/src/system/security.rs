@@ -7,12 +7,15 @@
 use crate::{
     system::authentication::validate_password_with_ip,
     utils::{
+        http_server::{HttpMessage, spawn_mock_http_server},
         imap::{ImapConnection, Type},
         registry::UnwrapRegistryId,
         server::TestServer,
     },
 };
 use common::ipc::RegistryChange;
+use http_proto::HttpResponse;
+use hyper::{Method, StatusCode};
 use imap_proto::ResponseType;
 use jmap_client::{
     client::{Client, Credentials},
@@ -28,7 +31,7 @@ use registry::{
 };
 use serde_json::json;
 use std::{net::Ipv4Addr, sync::Arc, time::Duration};
-use store::{registry::write::RegistryWrite, write::now};
+use store::{parking_lot::Mutex, registry::write::RegistryWrite, write::now};
 use types::id::Id;
 
 pub async fn test(test: &mut TestServer) {
@@ -168,13 +171,35 @@ pub async fn test(test: &mut TestServer) {
         .await;
     }
 
-    // Set fail2ban expiration
+    // Spawn mock firewall webhook
+    let firewall_events = Arc::new(Mutex::new(Vec::<serde_json::Value>::new()));
+    let firewall_events_ = firewall_events.clone();
+    let _tx = spawn_mock_http_server(
+        test,
+        Arc::new(move |req: HttpMessage| {
+            assert_eq!(req.uri.path(), "/firewall");
+            assert_eq!(req.method, Method::POST);
+            firewall_events_
+                .lock()
+                .push(serde_json::from_slice(req.body.as_ref().unwrap()).unwrap());
+            HttpResponse::new(StatusCode::OK)
+        }),
+        19055,
+    )
+    .await;
+
+    // Set fail2ban expiration and publish bans to the firewall webhook
     admin
         .registry_update_object(
             ObjectType::Security,
             Id::singleton(),
             json!({
-                Property::AuthBanPeriod: registry::types::duration::Duration::from_millis(1000)
+                Property::AuthBanPeriod: registry::types::duration::Duration::from_millis(1000),
+                Property::FirewallSync: {
+                    "@type": "Webhook",
+                    "url": "https://127.0.0.1:19055/firewall",
+                    "allowInvalidCerts": true
+                }
             }),
         )
         .await;
@@ -207,6 +232,20 @@ pub async fn test(test: &mut TestServer) {
 
     // After 1 second the ban should be lifted
     tokio::time::sleep(Duration::from_secs(2)).await;
+
+    // The ban should have been published to the firewall, starting with a full sync
+    {
+        let events = firewall_events.lock();
+        assert_eq!(events.first().unwrap()["sync"], true, "{events:?}");
+        assert!(
+            events.iter().any(|event| event["ban"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|ban| ban["address"] == "10.0.0.2" && ban["expiresAt"].is_string())),
+            "{events:?}"
+        );
+    }
     validate_password_with_ip(
         "user@example.org",
         "this is a very strong password",
@@ -307,7 +346,7 @@ pub async fn test(test: &mut TestServer) {
         client.upload(None, b"sleep".to_vec(), None).await,
         Err(jmap_client::Error::Problem(err)) if err.status() == Some(400)));
 
-    // Disable X-Forwarded-For processing
+    // Disable X-Forwarded-For processing and firewall sync
     admin
         .registry_update_setting(
             Http {
@@ -317,6 +356,17 @@ pub async fn test(test: &mut TestServer) {
             &[Property::UseXForwarded],
         )
         .await;
+    admin
+        .registry_update_object(
+            ObjectType::Security,
+            Id::singleton(),
+            json!({
+                Property::FirewallSync: {
+                    "@type": "Disabled"
+                }
+            }),
+        )
+        .await;
     admin.reload_settings().await;
 
     // Destroy account
@@ -0,0 +1,92 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::{http::HttpRequest, server::TestServer};
+use common::auth::AccessToken;
+use http::api::settings_export::SettingsExportManagement;
+use registry::schema::enums::Permission;
+use serde_json::{Value, json};
+
+pub async fn test(test: &TestServer) {
+    println!("Running settings export tests...");
+
+    let server = &test.server;
+    let admin = test.account("admin@example.org");
+    let api = HttpRequest::with_credentials(8899, admin.name(), admin.secret());
+
+    // Exporting secrets requires a dedicated permission
+    let limited =
+        AccessToken::from_permissions(admin.id().document_id(), [Permission::ActionReloadSettings]);
+    assert!(server.handle_settings_export(None, &limited).await.is_ok());
+    assert!(
+        server
+            .handle_settings_export(
+                Some(br#"{"encryptionKey": "export secret"}"#.to_vec()),
+                &limited
+            )
+            .await
+            .is_err()
+    );
+
+    // Documents without an encryption key have their secrets redacted
+    let document = api
+        .post::<Value>("/api/settings/export", &json!({}))
+        .await
+        .unwrap();
+    assert!(document.get("encryption").is_none(), "{document}");
+    assert!(document["objects"].is_object(), "{document}");
+
+    // The key wrapping the secrets is derived with Argon2
+    let document = api
+        .post::<Value>(
+            "/api/settings/export",
+            &json!({ "encryptionKey": "export secret" }),
+        )
+        .await
+        .unwrap();
+    let kdf = &document["encryption"]["kdf"];
+    assert_eq!(kdf["algorithm"], "argon2id", "{document}");
+    for param in ["memoryCost", "timeCost", "parallelism"] {
+        assert!(kdf[param].as_u64().is_some_and(|v| v > 0), "{document}");
+    }
+
+    // Encrypted documents can only be imported with the same key
+    let response = import(&api, &document, "export secret").await;
+    assert!(response.get("changes").is_some(), "{response}");
+    let response = import(&api, &document, "wrong secret").await;
+    assert!(
+        response.to_string().contains("Invalid encryption key"),
+        "{response}"
+    );
+
+    // Crafted documents cannot request an expensive key derivation
+    let memory_cost = kdf["memoryCost"].clone();
+    let mut document = document;
+    document["encryption"]["kdf"]["memoryCost"] = json!(u32::MAX);
+    let response = import(&api, &document, "export secret").await;
+    assert!(
+        response.to_string().contains("exceed the allowed limits"),
+        "{response}"
+    );
+    document["encryption"]["kdf"]["memoryCost"] = memory_cost;
+    document["encryption"]["kdf"]["algorithm"] = json!("blake3");
+    let response = import(&api, &document, "export secret").await;
+    assert!(
+        response
+            .to_string()
+            .contains("Unsupported key derivation algorithm"),
+        "{response}"
+    );
+}
+
+async fn import(api: &HttpRequest, document: &Value, encryption_key: &str) -> Value {
+    api.post::<Value>(
+        "/api/settings/import?dryRun=true",
+        &json!({ "settings": document, "encryptionKey": encryption_key }),
+    )
+    .await
+    .unwrap()
+}
@@ -529,7 +529,8 @@ pub async fn test(test: &mut TestServer) {
                 sender_authenticated: true,
                 recipients: vec![IngestRecipient {
                     address: "user@tenantx.org".to_string(),
-                    is_spam: false
+                    is_spam: false,
+                    subaddress: None,
                 }],
                 message_blob: message_blob.clone(),
                 message_size: TEST_MESSAGE.len() as u64,
@@ -565,7 +566,8 @@ pub async fn test(test: &mut TestServer) {
                 sender_authenticated: true,
                 recipients: vec![IngestRecipient {
                     address: "user@tenantx.org".to_string(),
-                    is_spam: false
+                    is_spam: false,
+                    subaddress: None,
                 }],
                 message_blob,
                 message_size: TEST_MESSAGE.len() as u64,
@@ -0,0 +1,241 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::server::TestServer;
+use http::api::logs::LogTailFilter;
+use registry::schema::{
+    prelude::ObjectType,
+    structs::{
+        Account, CertificateManagement, DkimManagement, DnsManagement, Domain, Tenant, UserAccount,
+    },
+};
+use std::sync::Arc;
+use store::write::now;
+use trc::{AuthEvent, Event, EventDetails, EventType, Key, Level, SmtpEvent, Value};
+
+pub async fn test(test: &TestServer) {
+    println!("Running log tail tests...");
+
+    let server = &test.server;
+    let admin = test.account("admin@example.org");
+    let admin_id = admin.id().document_id();
+
+    // Create an account that belongs to a tenant
+    let tenant_id = admin
+        .registry_create_object(Tenant {
+            name: "Log Tenant".into(),
+            ..Default::default()
+        })
+        .await;
+    let domain_id = admin
+        .registry_create_object(Domain {
+            name: "logtenant.org".into(),
+            member_tenant_id: tenant_id.into(),
+            certificate_management: CertificateManagement::Manual,
+            dns_management: DnsManagement::Manual,
+            dkim_management: DkimManagement::Manual,
+            ..Default::default()
+        })
+        .await;
+    let tenant_account_id = admin
+        .registry_create_object(Account::User(UserAccount {
+            name: "jane".into(),
+            domain_id,
+            member_tenant_id: tenant_id.into(),
+            credentials: List::from_iter([Credential::Password(PasswordCredential {
+                secret: "log tenant secret".into(),
+                ..Default::default()
+            })]),
+            ..Default::default()
+        }))
+        .await;
+    let tenant_account = tenant_account_id.document_id();
+
+    // Without a tenant or account filter all events are included
+    let mut filter = LogTailFilter::parse("").unwrap();
+    assert!(
+        filter
+            .matches(server, &event(EventType::Smtp(SmtpEvent::Ehlo), 1, None))
+            .await
+    );
+    assert_eq!(filter.num_matched_spans(), 0);
+
+    // Event type and span filters
+    let mut filter = LogTailFilter::parse("type:smtp.* span:1").unwrap();
+    assert!(
+        filter
+            .matches(server, &event(EventType::Smtp(SmtpEvent::Ehlo), 1, None))
+            .await
+    );
+    assert!(
+        !filter
+            .matches(server, &event(EventType::Smtp(SmtpEvent::Ehlo), 2, None))
+            .await
+    );
+    assert!(
+        !filter
+            .matches(server, &event(EventType::Auth(AuthEvent::Success), 1, None))
+            .await
+    );
+    for expr in ["type:", "span:abc", "type:not-an-event", "unknown:value"] {
+        assert!(LogTailFilter::parse(expr).is_err(), "{expr}");
+    }
+
+    // Tenant administrators only receive events of their tenant's accounts
+    let mut filter = LogTailFilter::parse("")
+        .unwrap()
+        .with_tenant(tenant_id.document_id().into());
+    assert!(
+        !filter
+            .matches(server, &event(EventType::Smtp(SmtpEvent::Ehlo), 10, None))
+            .await
+    );
+    assert!(
+        !filter
+            .matches(
+                server,
+                &event(EventType::Auth(AuthEvent::Success), 10, Some(admin_id))
+            )
+            .await
+    );
+    assert!(
+        filter
+            .matches(
+                server,
+                &event(
+                    EventType::Auth(AuthEvent::Success),
+                    11,
+                    Some(tenant_account)
+                )
+            )
+            .await
+    );
+    assert!(
+        filter
+            .matches(
+                server,
+                &Arc::new(Event::with_keys(
+                    details(EventType::Auth(AuthEvent::Success)),
+                    vec![
+                        (Key::SpanId, Value::UInt(12)),
+                        (Key::AccountName, Value::String("jane@logtenant.org".into())),
+                    ],
+                ))
+            )
+            .await
+    );
+
+    // Subsequent events of a matched span are included until the span ends
+    assert!(
+        filter
+            .matches(server, &event(EventType::Smtp(SmtpEvent::Ehlo), 11, None))
+            .await
+    );
+    assert!(
+        !filter
+            .matches(server, &event(EventType::Smtp(SmtpEvent::Ehlo), 10, None))
+            .await
+    );
+    assert_eq!(filter.num_matched_spans(), 2);
+    assert!(
+        filter
+            .matches(
+                server,
+                &event(EventType::Smtp(SmtpEvent::ConnectionEnd), 11, None)
+            )
+            .await
+    );
+    assert_eq!(filter.num_matched_spans(), 1);
+    assert!(
+        !filter
+            .matches(server, &event(EventType::Smtp(SmtpEvent::Ehlo), 11, None))
+            .await
+    );
+
+    // Account filters are combined with the tenant scope
+    let mut filter = LogTailFilter::parse(&format!("accountId:{}", admin.id()))
+        .unwrap()
+        .with_tenant(tenant_id.document_id().into());
+    assert!(
+        !filter
+            .matches(
+                server,
+                &event(EventType::Auth(AuthEvent::Success), 20, Some(admin_id))
+            )
+            .await
+    );
+    let mut filter = LogTailFilter::parse(&format!("accountId:{}", admin.id())).unwrap();
+    assert!(
+        filter
+            .matches(
+                server,
+                &event(EventType::Auth(AuthEvent::Success), 20, Some(admin_id))
+            )
+            .await
+    );
+    assert!(
+        !filter
+            .matches(
+                server,
+                &event(
+                    EventType::Auth(AuthEvent::Success),
+                    21,
+                    Some(tenant_account)
+                )
+            )
+            .await
+    );
+
+    // The number of tracked spans is bounded
+    let mut filter = LogTailFilter::parse(&format!("accountId:{}", admin.id())).unwrap();
+    for span_id in 0..10_100 {
+        assert!(
+            filter
+                .matches(
+                    server,
+                    &event(EventType::Auth(AuthEvent::Success), span_id, Some(admin_id))
+                )
+                .await
+        );
+    }
+    assert_eq!(filter.num_matched_spans(), 10_000);
+    assert!(
+        filter
+            .matches(
+                server,
+                &event(EventType::Smtp(SmtpEvent::Ehlo), 10_099, None)
+            )
+            .await
+    );
+
+    // Cleanup
+    admin
+        .registry_destroy(ObjectType::Account, [tenant_account_id])
+        .await;
+    admin
+        .registry_destroy(ObjectType::Domain, [domain_id])
+        .await;
+    admin
+        .registry_destroy(ObjectType::Tenant, [tenant_id])
+        .await;
+}
+
+fn details(typ: EventType) -> EventDetails {
+    EventDetails {
+        typ,
+        timestamp: now(),
+        level: Level::Info,
+        span: None,
+    }
+}
+
+fn event(typ: EventType, span_id: u64, account_id: Option<u32>) -> Arc<Event<EventDetails>> {
+    let mut keys = vec![(Key::SpanId, Value::UInt(span_id))];
+    if let Some(account_id) = account_id {
+        keys.push((Key::AccountId, Value::UInt(account_id as u64)));
+    }
+    Arc::new(Event::with_keys(details(typ), keys))
+}
@@ -0,0 +1,152 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: LicenseRef-SEL
+ *
+ * This file is subject to the Stalwart Enterprise License Agreement (SEL) and
+ * is NOT open source software.
+ *
+ */
+
+use crate::utils::{http::HttpRequest, server::TestServer, smtp::SmtpConnection};
+use common::{
+    auth::{AccessToken, AccessTokenInner},
+    ipc::QueueEvent,
+    telemetry::tracers::store::TracingStore,
+};
+use http::api::trace::{LifecycleStage, MessageTrace, MessageTraceApi};
+use std::{sync::Arc, time::Duration};
+use utils::url_params::UrlParams;
+
+pub async fn test(test: &TestServer) {
+    println!("Running message trace tests...");
+
+    let server = &test.server;
+    let admin = test.account("admin@example.org");
+    let api = HttpRequest::with_credentials(8899, admin.name(), admin.secret());
+    server
+        .tracing_store()
+        .purge_spans(Duration::from_secs(0), server.search_store().into())
+        .await
+        .unwrap();
+
+    // Keep the message in the queue until its trace has been inspected
+    server
+        .inner
+        .ipc
+        .queue_tx
+        .send(QueueEvent::Paused(true))
+        .await
+        .unwrap();
+    let mut lmtp = SmtpConnection::connect().await;
+    lmtp.ingest(
+        "bill@example.org",
+        &["admin@example.org"],
+        concat!(
+            "From: bill@example.org\r\n",
+            "To: admin@example.org\r\n",
+            "Message-ID: <tps-report-trace@example.org>\r\n",
+            "Subject: TPS Report\r\n",
+            "\r\n",
+            "I'm going to need those TPS reports ASAP."
+        ),
+    )
+    .await;
+    lmtp.quit().await;
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    server.notify_task_queue();
+    test.wait_for_tasks().await;
+
+    // The message is located by its Message-ID and correlated with its queue entry
+    let trace = api
+        .get::<MessageTrace>("/api/trace/message?messageId=%3Ctps-report-trace@example.org%3E")
+        .await
+        .unwrap();
+    assert_eq!(trace.queue_ids.len(), 1, "{trace:?}");
+    assert!(!trace.trace_ids.is_empty(), "{trace:?}");
+    for stage in [LifecycleStage::Received, LifecycleStage::Queued] {
+        assert!(
+            trace.lifecycle.iter().any(|entry| entry.stage == stage),
+            "{stage:?} missing from {trace:?}"
+        );
+    }
+    assert_eq!(trace.queue.len(), 1, "{trace:?}");
+    let queued = &trace.queue[0];
+    assert_eq!(queued.queue_id, trace.queue_ids[0]);
+    assert_eq!(queued.return_path, "bill@example.org");
+    assert_eq!(queued.recipients.len(), 1);
+    assert_eq!(queued.recipients[0].address, "admin@example.org");
+    assert_eq!(queued.recipients[0].status, "Scheduled");
+    assert!(queued.recipients[0].next_retry.is_some());
+
+    // Looking up the queue id returns the same message
+    let by_queue_id = api
+        .get::<MessageTrace>(&format!(
+            "/api/trace/message?queueId={}",
+            trace.queue_ids[0]
+        ))
+        .await
+        .unwrap();
+    assert_eq!(by_queue_id.queue_ids, trace.queue_ids);
+    assert_eq!(by_queue_id.trace_ids, trace.trace_ids);
+    assert_eq!(by_queue_id.queue.len(), 1);
+
+    // Tenants do not see messages that do not involve any of their domains
+    let tenant_token = AccessToken::new_maybe_invalid(Arc::new(
+        AccessTokenInner::from_id(admin.id().document_id()).with_tenant_id(Some(u32::MAX - 1)),
+    ));
+    for query in [
+        "messageId=tps-report-trace@example.org".to_string(),
+        format!("queueId={}", trace.queue_ids[0]),
+    ] {
+        let trace = server
+            .message_trace(&UrlParams::new(Some(&query)), &tenant_token)
+            .await
+            .unwrap();
+        assert!(
+            trace.queue_ids.is_empty()
+                && trace.trace_ids.is_empty()
+                && trace.lifecycle.is_empty()
+                && trace.queue.is_empty(),
+            "{query}: {trace:?}"
+        );
+    }
+
+    // Once delivered the message leaves the queue and the attempt is traced
+    server
+        .inner
+        .ipc
+        .queue_tx
+        .send(QueueEvent::Paused(false))
+        .await
+        .unwrap();
+    let mut trace = None;
+    for _ in 0..50 {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        server.notify_task_queue();
+        test.wait_for_tasks().await;
+        let result = api
+            .get::<MessageTrace>("/api/trace/message?messageId=tps-report-trace@example.org")
+            .await
+            .unwrap();
+        if result.queue.is_empty()
+            && result
+                .lifecycle
+                .iter()
+                .any(|entry| entry.stage == LifecycleStage::Attempt)
+        {
+            trace = Some(result);
+            break;
+        }
+    }
+    let trace = trace.expect("message was not delivered");
+    assert_eq!(trace.queue_ids, by_queue_id.queue_ids);
+    assert!(trace.trace_ids.len() > by_queue_id.trace_ids.len());
+
+    test.destroy_all_mailboxes(admin).await;
+    server
+        .tracing_store()
+        .purge_spans(Duration::from_secs(0), server.search_store().into())
+        .await
+        .unwrap();
+}
@@ -5,6 +5,8 @@
  */
 
 pub mod alerts;
+pub mod logs;
+pub mod message_trace;
 pub mod metrics;
 pub mod tracing;
 pub mod webhooks;
@@ -57,8 +59,10 @@ pub async fn telemetry_tests() {
 
     alerts::test(&test).await;
     metrics::test(&test).await;
+    message_trace::test(&test).await;
     tracing::test(&test).await;
     webhooks::test(&test).await;
+    logs::test(&test).await;
 
     if test.is_reset() {
         test.temp_dir.delete();
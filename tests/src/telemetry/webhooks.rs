@@ -6,6 +6,7 @@
 
 use crate::utils::server::TestServer;
 use crate::utils::smtp::SmtpConnection;
+use aws_lc_rs::hmac;
 use base64::{Engine, engine::general_purpose::STANDARD};
 use common::{manager::application::Resource, telemetry::tracers::store::TracingStore};
 use http_proto::{ToHttpResponse, request::fetch_body};
@@ -21,7 +22,6 @@ use registry::{
     },
     types::map::Map,
 };
-use aws_lc_rs::hmac;
 use std::{
     sync::{
         Arc,
@@ -36,6 +36,7 @@ use trc::EventType;
 struct MockWebhookEndpoint {
     pub _tx: watch::Sender<bool>,
     pub events: Mutex<Vec<serde_json::Value>>,
+    pub dead_letters: Mutex<Vec<serde_json::Value>>,
     pub reject: AtomicBool,
 }
 
@@ -114,6 +115,100 @@ pub async fn test(test: &TestServer) {
         "\"jdoe@example.org\"",
     ]);
 
+    // Replace the webhook with one that uses a payload template
+    admin.registry_destroy_all(ObjectType::WebHook).await;
+    admin
+        .registry_create_object(WebHook {
+            enable: true,
+            url: "http://127.0.0.1:8821/hook".into(),
+            signature_key: SecretKeyOptional::Value(SecretKeyValue {
+                secret: "ovos-moles".into(),
+            }),
+            throttle: 100u64.into(),
+            max_retries: 3,
+            payload_template: Some(
+                concat!(
+                    "{\"events\":[{{#each events}}",
+                    "{\"kind\":{{!type}},\"severity\":{{!level}},\"data\":{{!data}}}",
+                    "{{!comma}}{{/each events}}]}"
+                )
+                .into(),
+            ),
+            events: Map::new(
+                EventType::variants()
+                    .iter()
+                    .filter(|ev| ev.as_str().starts_with("smtp.connection-"))
+                    .copied()
+                    .collect(),
+            ),
+            events_policy: EventPolicy::Include,
+            ..Default::default()
+        })
+        .await;
+    admin.reload_settings().await;
+    let mut lmtp = SmtpConnection::connect().await;
+    lmtp.ingest(
+        "bill@example.org",
+        &["jdoe@example.org"],
+        concat!(
+            "From: bill@example.org\r\n",
+            "To: jdoe@example.org\r\n",
+            "Subject: TPS Report (again)\r\n",
+            "\r\n",
+            "Did you get the memo?"
+        ),
+    )
+    .await;
+    test.wait_for_tasks().await;
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    webhook.assert_contains(&["\"kind\": \"smtp.connection-start\"", "\"severity\": \""]);
+
+    // Failed deliveries are retried in the background and dead-lettered once
+    // the retries are exhausted
+    admin.registry_destroy_all(ObjectType::WebHook).await;
+    admin
+        .registry_create_object(WebHook {
+            enable: true,
+            url: "http://127.0.0.1:8821/hook".into(),
+            signature_key: SecretKeyOptional::Value(SecretKeyValue {
+                secret: "ovos-moles".into(),
+            }),
+            throttle: 50u64.into(),
+            retry_backoff: 50u64.into(),
+            max_retries: 2,
+            dead_letter_url: Some("http://127.0.0.1:8821/dead-letter".into()),
+            events: Map::new(
+                EventType::variants()
+                    .iter()
+                    .filter(|ev| ev.as_str().starts_with("smtp.connection-"))
+                    .copied()
+                    .collect(),
+            ),
+            events_policy: EventPolicy::Include,
+            ..Default::default()
+        })
+        .await;
+    admin.reload_settings().await;
+    webhook.reject();
+    let mut lmtp = SmtpConnection::connect().await;
+    lmtp.ingest(
+        "bill@example.org",
+        &["jdoe@example.org"],
+        concat!(
+            "From: bill@example.org\r\n",
+            "To: jdoe@example.org\r\n",
+            "Subject: TPS Report (dead letter)\r\n",
+            "\r\n",
+            "Yeah, I'm going to need you to go ahead and come in tomorrow."
+        ),
+    )
+    .await;
+    test.wait_for_tasks().await;
+    tokio::time::sleep(Duration::from_millis(1000)).await;
+    webhook.assert_is_empty();
+    webhook.assert_dead_letters_contain(&["smtp.connection-start"]);
+    webhook.accept();
+
     // Cleanup
     admin.registry_destroy_all(ObjectType::WebHook).await;
     admin.reload_settings().await;
@@ -146,11 +241,26 @@ impl MockWebhookEndpoint {
         self.reject.store(false, Ordering::Relaxed);
     }
 
-    /*pub fn reject(&self) {
+    pub fn reject(&self) {
         self.reject.store(true, Ordering::Relaxed);
     }
 
-    pub fn clear(&self) {
+    pub fn assert_dead_letters_contain(&self, expected: &[&str]) {
+        let events =
+            serde_json::to_string_pretty(&self.dead_letters.lock().drain(..).collect::<Vec<_>>())
+                .unwrap();
+
+        for string in expected {
+            if !events.contains(string) {
+                panic!(
+                    "Expected dead letters to contain '{}', but they did not. Events: {}",
+                    string, events
+                );
+            }
+        }
+    }
+
+    /*pub fn clear(&self) {
         self.events.lock().clear();
     }*/
 
@@ -164,6 +274,7 @@ fn spawn_mock_webhook_endpoint() -> Arc<MockWebhookEndpoint> {
     let endpoint_ = Arc::new(MockWebhookEndpoint {
         _tx,
         events: Mutex::new(vec![]),
+        dead_letters: Mutex::new(vec![]),
         reject: true.into(),
     });
 
@@ -205,7 +316,14 @@ fn spawn_mock_webhook_endpoint() -> Arc<MockWebhookEndpoint> {
                                         let request = serde_json::from_slice::<WebhookRequest>(&body)
                                         .expect("Failed to parse JSON");
 
-                                        if !endpoint.reject.load(Ordering::Relaxed) {
+                                        if req.uri().path() == "/dead-letter" {
+                                            endpoint.dead_letters.lock().extend(request.events);
+
+                                            Ok::<_, hyper::Error>(
+                                                Resource::new("application/json", "[]".to_string().into_bytes())
+                                                .into_http_response().build(),
+                                            )
+                                        } else if !endpoint.reject.load(Ordering::Relaxed) {
                                             //let c = print!("received webhook: {}", serde_json::to_string_pretty(&request).unwrap());
 
                                             // Add events
@@ -33,7 +33,7 @@ use common::{
 };
 use email::message::metadata::MessageMetadata;
 use groupware::cache::GroupwareCache;
-use http::HttpSessionManager;
+use http::{HttpSessionManager, agent_check::AgentCheckSessionManager};
 use imap::core::ImapSessionManager;
 use jmap_client::client::Client;
 use managesieve::core::ManageSieveSessionManager;
@@ -242,6 +242,10 @@ impl TestServerBuilder {
         self
     }
 
+    pub fn tmp_dir(&self) -> &str {
+        self.temp_dir.path.as_os_str().to_str().unwrap()
+    }
+
     pub async fn insert_object(&self, object: impl Into<Object>) -> Id {
         self.bootstrap
             .registry
@@ -353,7 +357,7 @@ impl TestServerBuilder {
 
         // Parse components
         let core = Box::pin(Core::parse(&mut self.bootstrap, storage)).await;
-        let data = Data::parse(&mut self.bootstrap).await;
+        let data = Data::parse(&mut self.bootstrap, &core).await;
         let cache = Caches::parse(&mut self.bootstrap).await;
 
         // Enable telemetry
@@ -432,6 +436,12 @@ impl TestServerBuilder {
                     acceptor,
                     shutdown_rx,
                 ),
+                ServerProtocol::AgentCheck => server.spawn(
+                    AgentCheckSessionManager::new(inner.clone()),
+                    inner.clone(),
+                    acceptor,
+                    shutdown_rx,
+                ),
             };
         });
 
@@ -577,11 +587,16 @@ impl TestServer {
         let (ipc, ipc_rxs) = build_ipc(false);
 
         let mut bp = Bootstrap::new_uninitialized(self.server.registry().clone());
+        let data = Data::default();
+        let current = &self.server.inner.data;
+        data.spam_rules.store(current.spam_rules.load_full());
+        data.spam_lists.store(current.spam_lists.load_full());
+        data.sieve_scripts.store(current.sieve_scripts.load_full());
 
         (
             Inner {
                 shared_core: self.server.core.as_ref().clone().into_shared(),
-                data: Default::default(),
+                data,
                 ipc,
                 cache: Caches::parse(&mut bp).await,
             }
@@ -0,0 +1,132 @@
+/*
+ * SPDX-FileCopyrightText: 2020 Stalwart Labs LLC <hello@stalw.art>
+ *
+ * SPDX-License-Identifier: AGPL-3.0-only OR LicenseRef-SEL
+ */
+
+use crate::utils::server::TestServer;
+use common::auth::AccessToken;
+use dav::calendar::freebusy::CalendarFreebusyHttpHandler;
+use groupware::DavResourceName;
+use http_proto::{HttpResponse, HttpResponseBody};
+use hyper::StatusCode;
+
+pub async fn test(test: &TestServer) {
+    println!("Running HTTP free/busy tests...");
+    let server = &test.server;
+    let client = test.account("jane@example.com").webdav_client();
+    let event_path = format!(
+        "{}/jane%40example.com/default/freebusy.ics",
+        DavResourceName::Cal.base_path()
+    );
+    client
+        .request("PUT", &event_path, FREEBUSY_EVENT)
+        .await
+        .with_status(StatusCode::CREATED);
+    let range = Some("start=20300101T000000Z&end=20300102T000000Z");
+
+    // Unknown or invalid accounts are not found
+    for account in [
+        "unknown@example.com",
+        "not-an-address",
+        "jane%40unknown.org",
+    ] {
+        let response = server
+            .handle_http_freebusy_request(None, account, range)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{account}");
+    }
+
+    // Invalid time ranges are rejected
+    for query in [
+        "start=yesterday",
+        "start=20300102T000000Z&end=20300101T000000Z",
+    ] {
+        let response = server
+            .handle_http_freebusy_request(None, "jane@example.com", Some(query))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{query}");
+    }
+
+    // Busy periods are aggregated from the account's calendars, also when
+    // the account is looked up by an alias
+    for account in ["jane@example.com", "jane.smith%40example.com"] {
+        let lines = freebusy_lines(
+            server
+                .handle_http_freebusy_request(None, account, range)
+                .await
+                .unwrap(),
+        );
+        for expected in [
+            "BEGIN:VFREEBUSY",
+            "DTSTART:20300101T000000Z",
+            "DTEND:20300102T000000Z",
+            "FREEBUSY;FBTYPE=BUSY:20300101T100000Z/20300101T110000Z",
+        ] {
+            assert!(lines.iter().any(|line| line == expected), "{lines:?}");
+        }
+    }
+
+    // Periods outside the requested range are not included
+    let lines = freebusy_lines(
+        server
+            .handle_http_freebusy_request(
+                None,
+                "jane@example.com",
+                Some("start=20300102T000000Z&end=20300103T000000Z"),
+            )
+            .await
+            .unwrap(),
+    );
+    assert!(lines.iter().any(|line| line == "BEGIN:VFREEBUSY"));
+    assert!(
+        !lines.iter().any(|line| line.starts_with("FREEBUSY")),
+        "{lines:?}"
+    );
+
+    // Authenticated requests only include the events shared with the caller
+    let bill_token: AccessToken = server
+        .access_token(test.account("bill@example.com").id().document_id())
+        .await
+        .unwrap()
+        .build();
+    let lines = freebusy_lines(
+        server
+            .handle_http_freebusy_request(Some(&bill_token), "jane@example.com", range)
+            .await
+            .unwrap(),
+    );
+    assert!(lines.iter().any(|line| line == "BEGIN:VFREEBUSY"));
+    assert!(
+        !lines.iter().any(|line| line.starts_with("FREEBUSY")),
+        "{lines:?}"
+    );
+
+    client
+        .request("DELETE", &event_path, "")
+        .await
+        .with_status(StatusCode::NO_CONTENT);
+}
+
+fn freebusy_lines(response: HttpResponse) -> Vec<String> {
+    assert_eq!(response.status(), StatusCode::OK);
+    match response.body() {
+        HttpResponseBody::Text(text) => text.lines().map(|line| line.to_string()).collect(),
+        _ => panic!("unexpected free/busy response body"),
+    }
+}
+
+const FREEBUSY_EVENT: &str = r#"BEGIN:VCALENDAR
+VERSION:2.0
+PRODID:-//Example Corp.//CalDAV Client//EN
+BEGIN:VEVENT
+UID:freebusy-http-1@example.com
+DTSTAMP:20291201T000000Z
+DTSTART:20300101T100000Z
+DTEND:20300101T110000Z
+SUMMARY:Busy
+END:VEVENT
+END:VCALENDAR
+"#;
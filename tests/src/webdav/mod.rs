@@ -32,6 +32,7 @@ pub mod cal_query;
 pub mod cal_scheduling;
 pub mod card_query;
 pub mod copy_move;
+pub mod freebusy;
 pub mod lock;
 pub mod mkcol;
 pub mod multiget;
@@ -191,6 +192,7 @@ pub async fn webdav_tests() {
     acl::test(&test).await;
     card_query::test(&test).await;
     cal_query::test(&test).await;
+    freebusy::test(&test).await;
     cal_alarm::test(&test).await;
     cal_itip::test();
     cal_scheduling::test(&test).await;
@@ -223,7 +223,78 @@ pub async fn test(test: &TestServer) {
         }
         assert!(expected_changes.is_empty(), "{:?}", expected_changes);
 
-        // Test 10: Expect changes after deletion
+        // Test 10: Truncated initial sync, removing an already listed collection
+        // between pages must not cause later items to be skipped
+        let paging_collection = format!("{}paging-collection/", user_base_path);
+        client
+            .mkcol("MKCOL", &paging_collection, [], [])
+            .await
+            .with_status(StatusCode::CREATED);
+        let expected_hrefs = client
+            .sync_collection(&user_base_path, "", Depth::Infinity, None, ["D:getetag"])
+            .await
+            .hrefs()
+            .into_iter()
+            .map(|href| href.to_string())
+            .collect::<AHashSet<_>>();
+        assert!(expected_hrefs.contains(&paging_collection));
+        let mut initial_hrefs = AHashSet::new();
+        let mut initial_sync_token = String::new();
+        let mut is_paging_collection_deleted = false;
+        for _ in 0..10 {
+            let response = client
+                .sync_collection(
+                    &user_base_path,
+                    &initial_sync_token,
+                    Depth::Infinity,
+                    2.into(),
+                    ["D:getetag"],
+                )
+                .await;
+            initial_sync_token = response.sync_token().to_string();
+            let mut is_truncated = false;
+            for href in response.hrefs() {
+                if href == user_base_path {
+                    is_truncated = true;
+                } else {
+                    assert!(
+                        initial_hrefs.insert(href.to_string()),
+                        "Duplicate href: {href}"
+                    );
+                }
+            }
+            if !is_truncated {
+                break;
+            }
+            if !is_paging_collection_deleted && initial_hrefs.contains(&paging_collection) {
+                client
+                    .request("DELETE", &paging_collection, "")
+                    .await
+                    .with_status(StatusCode::NO_CONTENT);
+                is_paging_collection_deleted = true;
+            }
+        }
+        assert!(is_paging_collection_deleted);
+        assert_eq!(initial_hrefs, expected_hrefs);
+        let response = client
+            .sync_collection(
+                &user_base_path,
+                &initial_sync_token,
+                Depth::Infinity,
+                None,
+                ["D:getetag"],
+            )
+            .await;
+        sync_token = response.sync_token().to_string();
+        response
+            .with_href_count(1)
+            .with_value("D:multistatus.D:response.D:href", &paging_collection)
+            .with_value(
+                "D:multistatus.D:response.D:status",
+                "HTTP/1.1 404 Not Found",
+            );
+
+        // Test 11: Expect changes after deletion
         client
             .request("DELETE", &new_file, "")
             .await